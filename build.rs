@@ -104,12 +104,17 @@ fn main() {
         println!("cargo:rerun-if-changed=proto/ddsketch_full.proto");
         println!("cargo:rerun-if-changed=proto/dd_metric.proto");
         println!("cargo:rerun-if-changed=proto/google/pubsub/v1/pubsub.proto");
+        println!("cargo:rerun-if-changed=proto/spiffe/workload.proto");
         println!("cargo:rerun-if-changed=proto/vector.proto");
         println!(
             "cargo:rerun-if-changed=proto/opentelemetry/proto/collector/logs/v1/logs_service.proto"
         );
+        println!(
+            "cargo:rerun-if-changed=proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto"
+        );
         println!("cargo:rerun-if-changed=proto/opentelemetry/proto/common/v1/common.proto");
         println!("cargo:rerun-if-changed=proto/opentelemetry/proto/logs/v1/logs.proto");
+        println!("cargo:rerun-if-changed=proto/opentelemetry/proto/metrics/v1/metrics.proto");
         println!("cargo:rerun-if-changed=proto/opentelemetry/proto/resource/v1/resource.proto");
 
         let mut prost_build = prost_build::Config::new();
@@ -120,19 +125,28 @@ fn main() {
                 prost_build,
                 &[
                     "lib/vector-core/proto/event.proto",
+                    "lib/vector-core/proto/event_v2.proto",
                     "proto/dnstap.proto",
                     "proto/ddsketch_full.proto",
                     "proto/dd_metric.proto",
                     "proto/dd_trace.proto",
                     "proto/google/pubsub/v1/pubsub.proto",
+                    "proto/spiffe/workload.proto",
                     "proto/vector.proto",
                     "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
                 ],
                 &["proto/", "lib/vector-core/proto/"],
             )
             .unwrap();
     }
 
+    #[cfg(feature = "sources-network_flows")]
+    compile_bpf_program(
+        "src/sources/network_flows/bpf/flows.bpf.c",
+        "network_flows.bpf.o",
+    );
+
     // We keep track of which environment variables we slurp in, and then emit stanzas at the end to
     // inform Cargo when it needs to rerun this build script.  This allows us to avoid rerunning it
     // every single time unless something _actually_ changes.
@@ -209,3 +223,45 @@ fn main() {
     // Emit the aforementioned stanzas.
     tracker.emit_rerun_stanzas();
 }
+
+/// Compiles an eBPF program with `clang` into `OUT_DIR`, from where it's embedded into the binary.
+///
+/// The program includes the kernel UAPI and libbpf headers, which must be installed on the build
+/// host. The `CLANG` environment variable can be set to use a specific `clang` binary.
+#[cfg(feature = "sources-network_flows")]
+fn compile_bpf_program(source: &str, output: &str) {
+    println!("cargo:rerun-if-changed={}", source);
+    println!("cargo:rerun-if-env-changed=CLANG");
+
+    // The source using the program is only compiled on the targets it supports, in
+    // `src/sources/mod.rs`, so the program isn't built for the others either.
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("linux") {
+        return;
+    }
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").expect("TARGET_ARCH is always set");
+    let bpf_arch = match target_arch.as_str() {
+        "x86_64" => "x86",
+        "aarch64" => "arm64",
+        _ => return,
+    };
+
+    let base_dir = env::var("OUT_DIR").expect("OUT_DIR not present in build script!");
+    let clang = env::var("CLANG").unwrap_or_else(|_| "clang".into());
+    let status = std::process::Command::new(&clang)
+        .args(["-O2", "-g", "-target", "bpf", "-Wall"])
+        .arg(format!("-D__TARGET_ARCH_{}", bpf_arch))
+        // Architecture-specific UAPI headers, such as `asm/types.h`, live in a multiarch
+        // directory on Debian-based distributions.
+        .arg(format!("-I/usr/include/{}-linux-gnu", target_arch))
+        .arg("-c")
+        .arg(source)
+        .arg("-o")
+        .arg(Path::new(&base_dir).join(output))
+        .status()
+        .unwrap_or_else(|error| panic!("Failed to run {}: {}", clang, error));
+    assert!(
+        status.success(),
+        "Failed to compile eBPF program {}",
+        source
+    );
+}
@@ -0,0 +1,242 @@
+//! Contains common definitions for CEF codec support
+
+/// CEF header fields, as represented in events. Definitions from the ArcSight "Implementing
+/// ArcSight Common Event Format (CEF)" guide.
+pub mod cef_fields {
+    /// The version of the CEF format.
+    pub const VERSION: &str = "cef_version";
+
+    /// The vendor of the device that sent the event.
+    pub const DEVICE_VENDOR: &str = "device_vendor";
+
+    /// The product name of the device that sent the event.
+    pub const DEVICE_PRODUCT: &str = "device_product";
+
+    /// The version of the device that sent the event.
+    pub const DEVICE_VERSION: &str = "device_version";
+
+    /// A unique identifier per event type, also known as the signature ID.
+    pub const DEVICE_EVENT_CLASS_ID: &str = "device_event_class_id";
+
+    /// A human-readable description of the event.
+    pub const NAME: &str = "name";
+
+    /// The importance of the event, either an integer from 0 to 10 or one of `Unknown`, `Low`,
+    /// `Medium`, `High` and `Very-High`.
+    pub const SEVERITY: &str = "severity";
+}
+
+/// Appends a header field, escaping pipes and backslashes.
+pub(crate) fn escape_header(value: &str, output: &mut String) {
+    for c in value.chars() {
+        match c {
+            '\\' | '|' => {
+                output.push('\\');
+                output.push(c);
+            }
+            '\r' | '\n' => output.push(' '),
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Appends an extension value, escaping backslashes, equal signs, line breaks and the given
+/// delimiter, if any.
+pub(crate) fn escape_value(value: &str, delimiter: Option<char>, output: &mut String) {
+    for c in value.chars() {
+        match c {
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\\' | '=' => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ if Some(c) == delimiter => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Reverses the escaping applied by `escape_header` and `escape_value`. Backslashes that don't
+/// start a known escape sequence are kept as-is.
+pub(crate) fn unescape(value: &str, delimiter: Option<char>) -> String {
+    let mut output = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => output.push('\n'),
+            Some('r') => output.push('\r'),
+            Some(c @ ('\\' | '|' | '=')) => output.push(c),
+            Some(c) if Some(c) == delimiter => output.push(c),
+            Some(c) => {
+                output.push('\\');
+                output.push(c);
+            }
+            None => output.push('\\'),
+        }
+    }
+
+    output
+}
+
+/// Splits off the given number of pipe-terminated header fields, returning the unescaped fields
+/// and the remainder of the input, or `None` if the input has fewer fields.
+pub(crate) fn split_header(input: &str, count: usize) -> Option<(Vec<String>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, c) in input.char_indices() {
+        if fields.len() == count {
+            break;
+        }
+
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '|' => {
+                fields.push(unescape(&input[start..index], None));
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if fields.len() == count {
+        Some((fields, &input[start..]))
+    } else {
+        None
+    }
+}
+
+/// Splits CEF extensions into key/value pairs.
+///
+/// Extension values may contain unescaped spaces, so a key starts after the last space preceding
+/// an unescaped equal sign. An unescaped equal sign that isn't preceded by a space since the last
+/// key is considered to be part of the value, which is what most consumers do with messages from
+/// producers that don't escape them.
+pub(crate) fn split_extensions(input: &str) -> Result<Vec<(String, String)>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // The byte offsets of the start of each key and of its equal sign.
+    let mut keys: Vec<(usize, usize)> = Vec::new();
+    let mut escaped = false;
+
+    for (index, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' => {
+                let key_start = input[..index].rfind(' ').map_or(0, |space| space + 1);
+                let previous_separator = keys.last().map(|(_, separator)| *separator);
+                let is_value = key_start == index
+                    || previous_separator.map_or(false, |separator| key_start <= separator);
+                if !is_value {
+                    keys.push((key_start, index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match keys.first() {
+        Some((0, _)) => {}
+        _ => return Err(format!("Invalid CEF extension: {:?}", input)),
+    }
+
+    Ok(keys
+        .iter()
+        .enumerate()
+        .map(|(position, (key_start, separator))| {
+            let value_end = keys
+                .get(position + 1)
+                .map_or(input.len(), |(next_key_start, _)| *next_key_start);
+            let value = input[separator + 1..value_end].trim_end();
+            (
+                input[*key_start..*separator].to_owned(),
+                unescape(value, None),
+            )
+        })
+        .collect())
+}
+
+/// Checks whether the given CEF extension key is valid. The specification only allows
+/// alphanumeric characters.
+pub(crate) fn is_valid_extension_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_and_unescapes() {
+        let mut header = String::new();
+        escape_header(r"a|b\c=d", &mut header);
+        assert_eq!(header, r"a\|b\\c=d");
+        assert_eq!(unescape(&header, None), r"a|b\c=d");
+
+        let mut value = String::new();
+        escape_value("a=b\\c\nd|e", None, &mut value);
+        assert_eq!(value, r"a\=b\\c\nd|e");
+        assert_eq!(unescape(&value, None), "a=b\\c\nd|e");
+    }
+
+    #[test]
+    fn splits_header() {
+        let (fields, rest) =
+            split_header(r"0|Acme\|Corp|App|1.0|100|Login|5|src=1.2.3.4", 7).unwrap();
+        assert_eq!(
+            fields,
+            vec!["0", "Acme|Corp", "App", "1.0", "100", "Login", "5"]
+        );
+        assert_eq!(rest, "src=1.2.3.4");
+
+        assert!(split_header("0|Acme|App", 7).is_none());
+    }
+
+    #[test]
+    fn splits_extensions() {
+        let pairs =
+            split_extensions(r"src=10.0.0.1 msg=Failed login for user=admin act=block cs1=a\=b")
+                .unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("src".to_owned(), "10.0.0.1".to_owned()),
+                ("msg".to_owned(), "Failed login for".to_owned()),
+                ("user".to_owned(), "admin".to_owned()),
+                ("act".to_owned(), "block".to_owned()),
+                ("cs1".to_owned(), "a=b".to_owned()),
+            ]
+        );
+
+        assert!(split_extensions("  ").unwrap().is_empty());
+        assert!(split_extensions("garbage src=1").is_err());
+    }
+
+    #[test]
+    fn keeps_unescaped_equal_signs_in_values() {
+        let pairs = split_extensions("request=/?a=b&c=d act=allow").unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("request".to_owned(), "/?a=b&c=d".to_owned()),
+                ("act".to_owned(), "allow".to_owned()),
+            ]
+        );
+    }
+}
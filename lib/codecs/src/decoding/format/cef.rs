@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::path;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+
+use super::Deserializer;
+use crate::{
+    cef::{split_extensions, split_header},
+    cef_fields::*,
+};
+
+/// Config used to build a `CefDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CefDeserializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the CEF deserializer.
+    pub cef: CefDeserializerOptions,
+}
+
+impl CefDeserializerConfig {
+    /// Creates a new `CefDeserializerConfig`.
+    pub const fn new(cef: CefDeserializerOptions) -> Self {
+        Self { cef }
+    }
+
+    /// Build the `CefDeserializer` from this configuration.
+    pub fn build(&self) -> CefDeserializer {
+        CefDeserializer {
+            extensions: self.cef.extensions.clone(),
+        }
+    }
+
+    /// Return the type of event built by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let definition =
+            schema::Definition::new(Kind::object(Collection::empty()), [log_namespace])
+                .with_field(VERSION, Kind::integer(), None)
+                .with_field(DEVICE_VENDOR, Kind::bytes(), None)
+                .with_field(DEVICE_PRODUCT, Kind::bytes(), None)
+                .with_field(DEVICE_VERSION, Kind::bytes(), None)
+                .with_field(DEVICE_EVENT_CLASS_ID, Kind::bytes(), None)
+                .with_field(NAME, Kind::bytes(), None)
+                .with_field(SEVERITY, Kind::bytes(), Some("severity"))
+                // Extensions are decoded as strings, but may be mapped to nested fields.
+                .unknown_fields(Kind::bytes().or_object(Collection::any()));
+
+        match log_namespace {
+            LogNamespace::Legacy => definition.with_field(
+                log_schema().timestamp_key(),
+                Kind::timestamp(),
+                Some("timestamp"),
+            ),
+            LogNamespace::Vector => definition,
+        }
+    }
+}
+
+/// Options for building a `CefDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CefDeserializerOptions {
+    /// Mapping of CEF extension keys to the event fields they are decoded into.
+    ///
+    /// Extensions that aren't mapped are decoded into top-level fields named after their key.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub extensions: HashMap<String, String>,
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a message in the ArcSight
+/// Common Event Format.
+///
+/// Anything preceding the `CEF:` prefix, such as a syslog header, is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct CefDeserializer {
+    extensions: HashMap<String, String>,
+}
+
+impl CefDeserializer {
+    /// Creates a new `CefDeserializer`.
+    pub fn new(options: CefDeserializerOptions) -> Self {
+        CefDeserializerConfig::new(options).build()
+    }
+
+    fn parse_message(&self, message: &str) -> vector_core::Result<LogEvent> {
+        let message = message
+            .find("CEF:")
+            .map(|start| &message[start + 4..])
+            .ok_or("Missing CEF prefix")?;
+        let (header, extensions) =
+            split_header(message, 7).ok_or("CEF header must contain seven fields")?;
+
+        let version = header[0]
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| format!("Invalid CEF version: {:?}", header[0]))?;
+
+        let mut log = LogEvent::default();
+        log.insert(path!(VERSION), version);
+        for (field, value) in [
+            DEVICE_VENDOR,
+            DEVICE_PRODUCT,
+            DEVICE_VERSION,
+            DEVICE_EVENT_CLASS_ID,
+            NAME,
+            SEVERITY,
+        ]
+        .into_iter()
+        .zip(header.into_iter().skip(1))
+        {
+            log.insert(path!(field), value);
+        }
+
+        for (key, value) in split_extensions(extensions)? {
+            match self.extensions.get(&key) {
+                Some(field) => log.insert(field.as_str(), value),
+                None => log.insert(path!(key.as_str()), value),
+            };
+        }
+
+        Ok(log)
+    }
+}
+
+impl Deserializer for CefDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let message = std::str::from_utf8(&bytes)
+            .map_err(|error| format!("Error parsing CEF message: {}", error))?;
+        let mut log = self.parse_message(message.trim_end())?;
+
+        if let LogNamespace::Legacy = log_namespace {
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vector_core::event::Value;
+
+    #[test]
+    fn deserialize_cef() {
+        let input = Bytes::from(
+            r"<134>Feb 14 19:04:54 host CEF:0|Acme\|Corp|Gateway|1.0|100|Login failed|5|src=10.0.0.1 msg=Bad password for admin cs1=a\=b",
+        );
+        let deserializer = CefDeserializer::default();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log[VERSION], Value::from(0));
+        assert_eq!(log[DEVICE_VENDOR], "Acme|Corp".into());
+        assert_eq!(log[DEVICE_PRODUCT], "Gateway".into());
+        assert_eq!(log[DEVICE_VERSION], "1.0".into());
+        assert_eq!(log[DEVICE_EVENT_CLASS_ID], "100".into());
+        assert_eq!(log[NAME], "Login failed".into());
+        assert_eq!(log[SEVERITY], "5".into());
+        assert_eq!(log["src"], "10.0.0.1".into());
+        assert_eq!(log["msg"], "Bad password for admin".into());
+        assert_eq!(log["cs1"], "a=b".into());
+        assert!(!log.contains(log_schema().timestamp_key()));
+    }
+
+    #[test]
+    fn deserialize_cef_mapped_extensions() {
+        let input = Bytes::from("CEF:0|Acme|Gateway|1.0|100|Login failed|5|src=10.0.0.1 msg=Hi");
+        let deserializer = CefDeserializer::new(CefDeserializerOptions {
+            extensions: HashMap::from([
+                ("src".to_owned(), "source.ip".to_owned()),
+                ("msg".to_owned(), "message".to_owned()),
+            ]),
+        });
+
+        let events = deserializer.parse(input, LogNamespace::Legacy).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["source.ip"], "10.0.0.1".into());
+        assert_eq!(log["message"], "Hi".into());
+        assert!(!log.contains("src"));
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_cef_invalid() {
+        let deserializer = CefDeserializer::default();
+
+        for input in [
+            "not a CEF message",
+            "CEF:0|Acme|Gateway|1.0",
+            "CEF:x|Acme|Gateway|1.0|100|Login failed|5|",
+        ] {
+            assert!(deserializer
+                .parse(Bytes::from(input), LogNamespace::Vector)
+                .is_err());
+        }
+    }
+}
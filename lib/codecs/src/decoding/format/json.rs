@@ -4,7 +4,9 @@ use bytes::Bytes;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
+use tracing::warn;
 use value::Kind;
+use vector_config::configurable_component;
 use vector_core::{
     config::{log_schema, DataType, LogNamespace},
     event::Event,
@@ -15,7 +17,14 @@ use super::Deserializer;
 
 /// Config used to build a `JsonDeserializer`.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
-pub struct JsonDeserializerConfig;
+pub struct JsonDeserializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the JSON deserializer.
+    pub json: JsonDeserializerOptions,
+}
 
 impl JsonDeserializerConfig {
     /// Build the `JsonDeserializer` from this configuration.
@@ -50,19 +59,74 @@ impl JsonDeserializerConfig {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Creates a new `JsonDeserializerConfig` with the given options.
+    pub const fn new_with_options(json: JsonDeserializerOptions) -> Self {
+        Self { json }
+    }
+}
+
+/// Options for building a `JsonDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JsonDeserializerOptions {
+    /// Parse JSON with SIMD instructions, which is significantly faster for large messages.
+    ///
+    /// This requires Vector to be built with the `codecs-simd-json` feature, for CPUs with AVX2 or
+    /// SSE4.2 on x86 and x86_64 (such as with `-C target-cpu=native`), as the instructions are
+    /// selected at compile time. Other builds fall back to the standard parser.
+    #[serde(default)]
+    pub simd: bool,
 }
 
 /// Deserializer that builds `Event`s from a byte frame containing JSON.
 #[derive(Debug, Clone, Default)]
-pub struct JsonDeserializer;
+pub struct JsonDeserializer {
+    simd: bool,
+}
 
 impl JsonDeserializer {
     /// Creates a new `JsonDeserializer`.
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Creates a new `JsonDeserializer` parsing JSON with SIMD instructions when this build has
+    /// them.
+    pub fn new_simd() -> Self {
+        Self {
+            simd: SIMD_COMPILED_IN,
+        }
+    }
+
+    fn parse_json(&self, bytes: &[u8]) -> vector_core::Result<serde_json::Value> {
+        // Only set when the SIMD parser is compiled in.
+        if self.simd {
+            #[cfg(feature = "simd-json")]
+            {
+                // The SIMD parser works in place, so it needs its own copy of the input.
+                let mut bytes = bytes.to_vec();
+                return simd_json::serde::from_slice(&mut bytes)
+                    .map_err(|error| format!("Error parsing JSON: {:?}", error).into());
+            }
+        }
+
+        serde_json::from_slice(bytes)
+            .map_err(|error| format!("Error parsing JSON: {:?}", error).into())
+    }
 }
 
+/// Whether this build parses JSON with SIMD instructions.
+///
+/// The SIMD parser selects its instructions when it's compiled rather than detecting them at
+/// runtime, so this depends on the target features of the build, not on the CPU Vector runs on.
+/// Without them it uses a scalar fallback, which is no faster than the standard parser.
+const SIMD_COMPILED_IN: bool = cfg!(all(
+    feature = "simd-json",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(target_feature = "avx2", target_feature = "sse4.2")
+));
+
 impl Deserializer for JsonDeserializer {
     fn parse(
         &self,
@@ -75,8 +139,7 @@ impl Deserializer for JsonDeserializer {
             return Ok(smallvec![]);
         }
 
-        let json: serde_json::Value = serde_json::from_slice(&bytes)
-            .map_err(|error| format!("Error parsing JSON: {:?}", error))?;
+        let json = self.parse_json(&bytes)?;
 
         // If the root is an Array, split it into multiple events
         let mut events = match json {
@@ -109,8 +172,13 @@ impl Deserializer for JsonDeserializer {
 }
 
 impl From<&JsonDeserializerConfig> for JsonDeserializer {
-    fn from(_: &JsonDeserializerConfig) -> Self {
-        Self
+    fn from(config: &JsonDeserializerConfig) -> Self {
+        if config.json.simd && !SIMD_COMPILED_IN {
+            warn!(message = "Parsing JSON with SIMD instructions isn't available in this build, using the standard parser.");
+        }
+        Self {
+            simd: config.json.simd && SIMD_COMPILED_IN,
+        }
     }
 }
 
@@ -186,6 +254,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn deserialize_json_simd() {
+        let deserializer = JsonDeserializer::new();
+        let simd_deserializer = JsonDeserializer::new_simd();
+
+        for input in [
+            r#"{ "foo": 123, "bar": [true, null, 1.5, "baz"] }"#,
+            r#"[{ "foo": 123 }, { "bar": { "baz": "qux" } }]"#,
+        ] {
+            let input = Bytes::from(input);
+            assert_eq!(
+                simd_deserializer
+                    .parse(input.clone(), LogNamespace::Vector)
+                    .unwrap(),
+                deserializer.parse(input, LogNamespace::Vector).unwrap()
+            );
+        }
+
+        assert!(simd_deserializer
+            .parse(Bytes::from("{ foo"), LogNamespace::Vector)
+            .is_err());
+    }
+
     #[test]
     fn deserialize_error_invalid_json() {
         let input = Bytes::from("{ foo");
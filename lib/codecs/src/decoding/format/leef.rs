@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use chrono::Utc;
+use lookup::path;
+use serde::{Deserialize, Serialize};
+use smallvec::{smallvec, SmallVec};
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+
+use super::Deserializer;
+use crate::{
+    cef::{split_header, unescape},
+    leef::{parse_delimiter, DEFAULT_DELIMITER},
+    leef_fields::*,
+};
+
+/// Config used to build a `LeefDeserializer`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct LeefDeserializerConfig {
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    /// Options for the LEEF deserializer.
+    pub leef: LeefDeserializerOptions,
+}
+
+impl LeefDeserializerConfig {
+    /// Creates a new `LeefDeserializerConfig`.
+    pub const fn new(leef: LeefDeserializerOptions) -> Self {
+        Self { leef }
+    }
+
+    /// Build the `LeefDeserializer` from this configuration.
+    pub fn build(&self) -> LeefDeserializer {
+        LeefDeserializer {
+            attributes: self.leef.attributes.clone(),
+        }
+    }
+
+    /// Return the type of event built by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let definition =
+            schema::Definition::new(Kind::object(Collection::empty()), [log_namespace])
+                .with_field(VERSION, Kind::bytes(), None)
+                .with_field(DEVICE_VENDOR, Kind::bytes(), None)
+                .with_field(DEVICE_PRODUCT, Kind::bytes(), None)
+                .with_field(DEVICE_VERSION, Kind::bytes(), None)
+                .with_field(EVENT_ID, Kind::bytes(), None)
+                // Attributes are decoded as strings, but may be mapped to nested fields.
+                .unknown_fields(Kind::bytes().or_object(Collection::any()));
+
+        match log_namespace {
+            LogNamespace::Legacy => definition.with_field(
+                log_schema().timestamp_key(),
+                Kind::timestamp(),
+                Some("timestamp"),
+            ),
+            LogNamespace::Vector => definition,
+        }
+    }
+}
+
+/// Options for building a `LeefDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LeefDeserializerOptions {
+    /// Mapping of LEEF attribute keys to the event fields they are decoded into.
+    ///
+    /// Attributes that aren't mapped are decoded into top-level fields named after their key.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub attributes: HashMap<String, String>,
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a message in the IBM QRadar
+/// Log Event Extended Format, version 1.0 or 2.0.
+///
+/// Anything preceding the `LEEF:` prefix, such as a syslog header, is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct LeefDeserializer {
+    attributes: HashMap<String, String>,
+}
+
+impl LeefDeserializer {
+    /// Creates a new `LeefDeserializer`.
+    pub fn new(options: LeefDeserializerOptions) -> Self {
+        LeefDeserializerConfig::new(options).build()
+    }
+
+    fn parse_message(&self, message: &str) -> vector_core::Result<LogEvent> {
+        let message = message
+            .find("LEEF:")
+            .map(|start| &message[start + 5..])
+            .ok_or("Missing LEEF prefix")?;
+
+        let (header, attributes, delimiter) = if message.starts_with("1.0|") {
+            let (header, attributes) =
+                split_header(message, 5).ok_or("LEEF 1.0 header must contain five fields")?;
+            (header, attributes, DEFAULT_DELIMITER)
+        } else if message.starts_with("2.0|") {
+            let (mut header, attributes) =
+                split_header(message, 6).ok_or("LEEF 2.0 header must contain six fields")?;
+            let delimiter = header.pop().unwrap_or_default();
+            let delimiter = parse_delimiter(&delimiter)
+                .ok_or_else(|| format!("Invalid LEEF delimiter: {:?}", delimiter))?;
+            (header, attributes, delimiter)
+        } else {
+            return Err("Unsupported LEEF version".into());
+        };
+
+        let mut log = LogEvent::default();
+        for (field, value) in [
+            VERSION,
+            DEVICE_VENDOR,
+            DEVICE_PRODUCT,
+            DEVICE_VERSION,
+            EVENT_ID,
+        ]
+        .into_iter()
+        .zip(header)
+        {
+            log.insert(path!(field), value);
+        }
+
+        for attribute in split_attributes(attributes, delimiter) {
+            if attribute.trim().is_empty() {
+                continue;
+            }
+            let (key, value) = attribute
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid LEEF attribute: {:?}", attribute))?;
+            let value = unescape(value, Some(delimiter));
+
+            match self.attributes.get(key) {
+                Some(field) => log.insert(field.as_str(), value),
+                None => log.insert(path!(key), value),
+            };
+        }
+
+        Ok(log)
+    }
+}
+
+/// Splits attributes on the delimiter, skipping escaped delimiters.
+fn split_attributes(input: &str, delimiter: char) -> Vec<&str> {
+    let mut attributes = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+
+    for (index, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == delimiter {
+            attributes.push(&input[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    attributes.push(&input[start..]);
+
+    attributes
+}
+
+impl Deserializer for LeefDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_core::Result<SmallVec<[Event; 1]>> {
+        if bytes.is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let message = std::str::from_utf8(&bytes)
+            .map_err(|error| format!("Error parsing LEEF message: {}", error))?;
+        let mut log = self.parse_message(message.trim_end_matches(|c| c == '\r' || c == '\n'))?;
+
+        if let LogNamespace::Legacy = log_namespace {
+            let timestamp_key = log_schema().timestamp_key();
+            if !log.contains(timestamp_key) {
+                log.insert(timestamp_key, Utc::now());
+            }
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_leef_1() {
+        let input = Bytes::from(
+            "<13>Jan 18 11:07:53 host LEEF:1.0|Acme|Gateway|1.0|login|src=10.0.0.1\tusrName=admin\tmsg=a b",
+        );
+        let deserializer = LeefDeserializer::default();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log[VERSION], "1.0".into());
+        assert_eq!(log[DEVICE_VENDOR], "Acme".into());
+        assert_eq!(log[DEVICE_PRODUCT], "Gateway".into());
+        assert_eq!(log[DEVICE_VERSION], "1.0".into());
+        assert_eq!(log[EVENT_ID], "login".into());
+        assert_eq!(log["src"], "10.0.0.1".into());
+        assert_eq!(log["usrName"], "admin".into());
+        assert_eq!(log["msg"], "a b".into());
+    }
+
+    #[test]
+    fn deserialize_leef_2() {
+        let input = Bytes::from(r"LEEF:2.0|Acme|Gateway|1.0|login|^|src=10.0.0.1^usrName=a\^b^");
+        let deserializer = LeefDeserializer::new(LeefDeserializerOptions {
+            attributes: HashMap::from([("src".to_owned(), "source.ip".to_owned())]),
+        });
+
+        let events = deserializer.parse(input, LogNamespace::Legacy).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log[VERSION], "2.0".into());
+        assert_eq!(log["source.ip"], "10.0.0.1".into());
+        assert_eq!(log["usrName"], "a^b".into());
+        assert!(log[log_schema().timestamp_key()].is_timestamp());
+    }
+
+    #[test]
+    fn deserialize_leef_hex_delimiter() {
+        let input = Bytes::from("LEEF:2.0|Acme|Gateway|1.0|login|x09|src=10.0.0.1\tdst=10.0.0.2");
+        let deserializer = LeefDeserializer::default();
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log["src"], "10.0.0.1".into());
+        assert_eq!(log["dst"], "10.0.0.2".into());
+    }
+
+    #[test]
+    fn deserialize_leef_invalid() {
+        let deserializer = LeefDeserializer::default();
+
+        for input in [
+            "not a LEEF message",
+            "LEEF:3.0|Acme|Gateway|1.0|login|",
+            "LEEF:1.0|Acme|Gateway",
+            "LEEF:1.0|Acme|Gateway|1.0|login|garbage",
+        ] {
+            assert!(deserializer
+                .parse(Bytes::from(input), LogNamespace::Vector)
+                .is_err());
+        }
+    }
+}
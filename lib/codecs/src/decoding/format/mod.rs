@@ -4,17 +4,21 @@
 #![deny(missing_docs)]
 
 mod bytes;
+mod cef;
 mod gelf;
 mod json;
+mod leef;
 mod native;
 mod native_json;
 #[cfg(feature = "syslog")]
 mod syslog;
 
 use ::bytes::Bytes;
+pub use cef::{CefDeserializer, CefDeserializerConfig, CefDeserializerOptions};
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig};
-pub use json::{JsonDeserializer, JsonDeserializerConfig};
+pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
+pub use leef::{LeefDeserializer, LeefDeserializerConfig, LeefDeserializerOptions};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{NativeJsonDeserializer, NativeJsonDeserializerConfig};
 use smallvec::SmallVec;
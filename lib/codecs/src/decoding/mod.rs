@@ -8,9 +8,11 @@ pub mod framing;
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
-    GelfDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, NativeDeserializer,
-    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, CefDeserializer,
+    CefDeserializerConfig, CefDeserializerOptions, GelfDeserializer, GelfDeserializerConfig,
+    JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions, LeefDeserializer,
+    LeefDeserializerConfig, LeefDeserializerOptions, NativeDeserializer, NativeDeserializerConfig,
+    NativeJsonDeserializer, NativeJsonDeserializerConfig,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig};
@@ -227,7 +229,14 @@ pub enum DeserializerConfig {
     /// Configures the `BytesDeserializer`.
     Bytes,
     /// Configures the `JsonDeserializer`.
-    Json,
+    Json {
+        /// Options for the JSON deserializer.
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        json: JsonDeserializerOptions,
+    },
     #[cfg(feature = "syslog")]
     /// Configures the `SyslogDeserializer`.
     Syslog,
@@ -237,6 +246,24 @@ pub enum DeserializerConfig {
     NativeJson,
     /// Configures the `GelfDeserializer`.
     Gelf,
+    /// Configures the `CefDeserializer`.
+    Cef {
+        /// Options for the CEF deserializer.
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        cef: CefDeserializerOptions,
+    },
+    /// Configures the `LeefDeserializer`.
+    Leef {
+        /// Options for the LEEF deserializer.
+        #[serde(
+            default,
+            skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+        )]
+        leef: LeefDeserializerOptions,
+    },
 }
 
 impl From<BytesDeserializerConfig> for DeserializerConfig {
@@ -246,8 +273,8 @@ impl From<BytesDeserializerConfig> for DeserializerConfig {
 }
 
 impl From<JsonDeserializerConfig> for DeserializerConfig {
-    fn from(_: JsonDeserializerConfig) -> Self {
-        Self::Json
+    fn from(config: JsonDeserializerConfig) -> Self {
+        Self::Json { json: config.json }
     }
 }
 
@@ -264,12 +291,26 @@ impl From<GelfDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<CefDeserializerConfig> for DeserializerConfig {
+    fn from(config: CefDeserializerConfig) -> Self {
+        Self::Cef { cef: config.cef }
+    }
+}
+
+impl From<LeefDeserializerConfig> for DeserializerConfig {
+    fn from(config: LeefDeserializerConfig) -> Self {
+        Self::Leef { leef: config.leef }
+    }
+}
+
 impl DeserializerConfig {
     /// Build the `Deserializer` from this configuration.
     pub fn build(&self) -> Deserializer {
         match self {
             DeserializerConfig::Bytes => Deserializer::Bytes(BytesDeserializerConfig.build()),
-            DeserializerConfig::Json => Deserializer::Json(JsonDeserializerConfig.build()),
+            DeserializerConfig::Json { json } => {
+                Deserializer::Json(JsonDeserializerConfig::new_with_options(json.clone()).build())
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => Deserializer::Syslog(SyslogDeserializerConfig.build()),
             DeserializerConfig::Native => Deserializer::Native(NativeDeserializerConfig.build()),
@@ -277,6 +318,12 @@ impl DeserializerConfig {
                 Deserializer::NativeJson(NativeJsonDeserializerConfig.build())
             }
             DeserializerConfig::Gelf => Deserializer::Gelf(GelfDeserializerConfig.build()),
+            DeserializerConfig::Cef { cef } => {
+                Deserializer::Cef(CefDeserializerConfig::new(cef.clone()).build())
+            }
+            DeserializerConfig::Leef { leef } => {
+                Deserializer::Leef(LeefDeserializerConfig::new(leef.clone()).build())
+            }
         }
     }
 
@@ -285,8 +332,10 @@ impl DeserializerConfig {
         match self {
             DeserializerConfig::Native => FramingConfig::LengthDelimited,
             DeserializerConfig::Bytes
-            | DeserializerConfig::Json
+            | DeserializerConfig::Json { .. }
             | DeserializerConfig::Gelf
+            | DeserializerConfig::Cef { .. }
+            | DeserializerConfig::Leef { .. }
             | DeserializerConfig::NativeJson => FramingConfig::NewlineDelimited {
                 newline_delimited: Default::default(),
             },
@@ -301,12 +350,20 @@ impl DeserializerConfig {
     pub fn output_type(&self) -> DataType {
         match self {
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
-            DeserializerConfig::Json => JsonDeserializerConfig.output_type(),
+            DeserializerConfig::Json { json } => {
+                JsonDeserializerConfig::new_with_options(json.clone()).output_type()
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig.output_type(),
             DeserializerConfig::Native => NativeDeserializerConfig.output_type(),
             DeserializerConfig::NativeJson => NativeJsonDeserializerConfig.output_type(),
             DeserializerConfig::Gelf => GelfDeserializerConfig.output_type(),
+            DeserializerConfig::Cef { cef } => {
+                CefDeserializerConfig::new(cef.clone()).output_type()
+            }
+            DeserializerConfig::Leef { leef } => {
+                LeefDeserializerConfig::new(leef.clone()).output_type()
+            }
         }
     }
 
@@ -314,7 +371,10 @@ impl DeserializerConfig {
     pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
         match self {
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
-            DeserializerConfig::Json => JsonDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Json { json } => {
+                JsonDeserializerConfig::new_with_options(json.clone())
+                    .schema_definition(log_namespace)
+            }
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog => SyslogDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::Native => NativeDeserializerConfig.schema_definition(log_namespace),
@@ -322,6 +382,12 @@ impl DeserializerConfig {
                 NativeJsonDeserializerConfig.schema_definition(log_namespace)
             }
             DeserializerConfig::Gelf => GelfDeserializerConfig.schema_definition(log_namespace),
+            DeserializerConfig::Cef { cef } => {
+                CefDeserializerConfig::new(cef.clone()).schema_definition(log_namespace)
+            }
+            DeserializerConfig::Leef { leef } => {
+                LeefDeserializerConfig::new(leef.clone()).schema_definition(log_namespace)
+            }
         }
     }
 }
@@ -344,6 +410,10 @@ pub enum Deserializer {
     Boxed(BoxedDeserializer),
     /// Uses a `GelfDeserializer` for deserialization.
     Gelf(GelfDeserializer),
+    /// Uses a `CefDeserializer` for deserialization.
+    Cef(CefDeserializer),
+    /// Uses a `LeefDeserializer` for deserialization.
+    Leef(LeefDeserializer),
 }
 
 impl format::Deserializer for Deserializer {
@@ -361,6 +431,8 @@ impl format::Deserializer for Deserializer {
             Deserializer::NativeJson(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Boxed(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Gelf(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Cef(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Leef(deserializer) => deserializer.parse(bytes, log_namespace),
         }
     }
 }
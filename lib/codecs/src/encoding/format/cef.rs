@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    cef::{escape_header, escape_value, is_valid_extension_key},
+    cef_fields::*,
+    encoding::BuildError,
+};
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The version of the CEF format written by the serializer.
+const CEF_VERSION: &str = "0";
+
+/// Errors that can occur during CEF serialization.
+#[derive(Debug, Snafu)]
+pub enum CefSerializerError {
+    #[snafu(display(r#"LogEvent does not contain required field: "{}""#, field))]
+    MissingField { field: String },
+}
+
+/// Config used to build a `CefSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CefSerializerConfig {
+    /// Options for the CEF serializer.
+    pub cef: CefSerializerOptions,
+}
+
+impl CefSerializerConfig {
+    /// Creates a new `CefSerializerConfig`.
+    pub const fn new(cef: CefSerializerOptions) -> Self {
+        Self { cef }
+    }
+
+    /// Build the `CefSerializer` from this configuration.
+    pub fn build(&self) -> Result<CefSerializer, BuildError> {
+        if let Some(key) = self
+            .cef
+            .extensions
+            .keys()
+            .find(|key| !is_valid_extension_key(key))
+        {
+            return Err(format!(
+                "Invalid CEF extension key {:?}, keys may only contain alphanumeric characters",
+                key
+            )
+            .into());
+        }
+
+        let mut header = format!("CEF:{}|", CEF_VERSION);
+        for field in [
+            &self.cef.device_vendor,
+            &self.cef.device_product,
+            &self.cef.device_version,
+        ] {
+            escape_header(field, &mut header);
+            header.push('|');
+        }
+
+        Ok(CefSerializer {
+            header,
+            options: self.cef.clone(),
+        })
+    }
+
+    /// The data type of events that are accepted by `CefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Options for building a `CefSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CefSerializerOptions {
+    /// The vendor written to the `Device Vendor` header.
+    pub device_vendor: String,
+
+    /// The product written to the `Device Product` header.
+    pub device_product: String,
+
+    /// The version written to the `Device Version` header.
+    pub device_version: String,
+
+    /// The field written to the `Device Event Class ID` header.
+    #[serde(default = "default_device_event_class_id_field")]
+    pub device_event_class_id_field: String,
+
+    /// The field written to the `Name` header.
+    #[serde(default = "default_name_field")]
+    pub name_field: String,
+
+    /// The field written to the `Severity` header.
+    #[serde(default = "default_severity_field")]
+    pub severity_field: String,
+
+    /// Mapping of CEF extension keys to the event fields they are written from.
+    ///
+    /// Fields that don't exist in the event are omitted.
+    #[serde(default)]
+    pub extensions: BTreeMap<String, String>,
+}
+
+fn default_device_event_class_id_field() -> String {
+    DEVICE_EVENT_CLASS_ID.to_owned()
+}
+
+fn default_name_field() -> String {
+    NAME.to_owned()
+}
+
+fn default_severity_field() -> String {
+    SEVERITY.to_owned()
+}
+
+/// Serializer that converts an `Event` to bytes using the ArcSight Common Event Format.
+#[derive(Debug, Clone)]
+pub struct CefSerializer {
+    /// The escaped, constant part of the header.
+    header: String,
+    options: CefSerializerOptions,
+}
+
+impl CefSerializer {
+    /// Creates a new `CefSerializer`.
+    pub fn new(options: CefSerializerOptions) -> Result<Self, BuildError> {
+        CefSerializerConfig::new(options).build()
+    }
+}
+
+impl Encoder<Event> for CefSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let mut output = self.header.clone();
+
+        for field in [
+            &self.options.device_event_class_id_field,
+            &self.options.name_field,
+            &self.options.severity_field,
+        ] {
+            let value = get_string(&log, field)
+                .ok_or_else(|| MissingFieldSnafu { field }.build().to_string())?;
+            escape_header(&value, &mut output);
+            output.push('|');
+        }
+
+        let mut first = true;
+        for (key, field) in &self.options.extensions {
+            if let Some(value) = get_string(&log, field) {
+                if !first {
+                    output.push(' ');
+                }
+                first = false;
+
+                output.push_str(key);
+                output.push('=');
+                escape_value(&value, None, &mut output);
+            }
+        }
+
+        buffer.extend_from_slice(output.as_bytes());
+
+        Ok(())
+    }
+}
+
+/// Gets the value of the field as a string. Timestamps are written as milliseconds since the Unix
+/// epoch, which both CEF and LEEF accept for their date attributes.
+pub(super) fn get_string(log: &LogEvent, field: &str) -> Option<String> {
+    match log.get(field)? {
+        Value::Null => None,
+        Value::Timestamp(timestamp) => Some(timestamp.timestamp_millis().to_string()),
+        value => Some(value.to_string_lossy()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    fn options() -> CefSerializerOptions {
+        CefSerializerOptions {
+            device_vendor: "Acme|Corp".to_owned(),
+            device_product: "Gateway".to_owned(),
+            device_version: "1.0".to_owned(),
+            device_event_class_id_field: default_device_event_class_id_field(),
+            name_field: default_name_field(),
+            severity_field: default_severity_field(),
+            extensions: btreemap! {
+                "src" => "source.ip",
+                "rt" => "timestamp",
+                "msg" => "message",
+                "suser" => "user",
+            },
+        }
+    }
+
+    #[test]
+    fn serialize_cef() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "device_event_class_id" => Value::from("100"),
+            "name" => Value::from("Login failed"),
+            "severity" => Value::from(5),
+            "source" => Value::from(btreemap! { "ip" => Value::from("10.0.0.1") }),
+            "timestamp" => Value::from(Utc.timestamp(1_500_000_000, 0)),
+            "message" => Value::from("a=b\nc"),
+        }));
+        let mut serializer = CefSerializer::new(options()).unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event, &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            r"CEF:0|Acme\|Corp|Gateway|1.0|100|Login failed|5|msg=a\=b\nc rt=1500000000000 src=10.0.0.1"
+        );
+    }
+
+    #[test]
+    fn serialize_cef_missing_header_field() {
+        let event = Event::Log(LogEvent::from(btreemap! {
+            "device_event_class_id" => Value::from("100"),
+            "severity" => Value::from(5),
+        }));
+        let mut serializer = CefSerializer::new(options()).unwrap();
+        let mut bytes = BytesMut::new();
+
+        let error = serializer.encode(event, &mut bytes).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            r#"LogEvent does not contain required field: "name""#
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_extension_keys() {
+        let mut options = options();
+        options
+            .extensions
+            .insert("bad key".to_owned(), "message".to_owned());
+
+        assert!(CefSerializer::new(options).is_err());
+    }
+}
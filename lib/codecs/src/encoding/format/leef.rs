@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+
+use super::cef::get_string;
+use crate::{
+    cef::{escape_header, escape_value},
+    encoding::BuildError,
+    leef::{format_delimiter, is_valid_attribute_key, DEFAULT_DELIMITER},
+    leef_fields::*,
+};
+use bytes::BytesMut;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio_util::codec::Encoder;
+use vector_core::{config::DataType, event::Event, schema};
+
+/// Errors that can occur during LEEF serialization.
+#[derive(Debug, Snafu)]
+pub enum LeefSerializerError {
+    #[snafu(display(r#"LogEvent does not contain required field: "{}""#, field))]
+    MissingField { field: String },
+}
+
+/// Config used to build a `LeefSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LeefSerializerConfig {
+    /// Options for the LEEF serializer.
+    pub leef: LeefSerializerOptions,
+}
+
+impl LeefSerializerConfig {
+    /// Creates a new `LeefSerializerConfig`.
+    pub const fn new(leef: LeefSerializerOptions) -> Self {
+        Self { leef }
+    }
+
+    /// Build the `LeefSerializer` from this configuration.
+    pub fn build(&self) -> Result<LeefSerializer, BuildError> {
+        let delimiter = self.leef.delimiter;
+        if matches!(self.leef.version, LeefVersion::V1) && delimiter != DEFAULT_DELIMITER {
+            return Err("LEEF 1.0 only supports tab delimited attributes".into());
+        }
+        if !delimiter.is_ascii() || matches!(delimiter, '=' | '|' | '\\' | '\n' | '\r') {
+            return Err(format!("Invalid LEEF attribute delimiter {:?}", delimiter).into());
+        }
+        if let Some(key) = self
+            .leef
+            .attributes
+            .keys()
+            .find(|key| !is_valid_attribute_key(key, delimiter))
+        {
+            return Err(format!("Invalid LEEF attribute key {:?}", key).into());
+        }
+
+        let mut header = format!("LEEF:{}|", self.leef.version.as_str());
+        for field in [
+            &self.leef.device_vendor,
+            &self.leef.device_product,
+            &self.leef.device_version,
+        ] {
+            escape_header(field, &mut header);
+            header.push('|');
+        }
+
+        Ok(LeefSerializer {
+            header,
+            options: self.leef.clone(),
+        })
+    }
+
+    /// The data type of events that are accepted by `LeefSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// The version of the LEEF format.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub enum LeefVersion {
+    /// LEEF 1.0, which always delimits attributes with tabs.
+    #[serde(rename = "1.0")]
+    V1,
+    /// LEEF 2.0, which allows choosing the attribute delimiter.
+    #[serde(rename = "2.0")]
+    V2,
+}
+
+impl LeefVersion {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "1.0",
+            Self::V2 => "2.0",
+        }
+    }
+}
+
+impl Default for LeefVersion {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+/// Options for building a `LeefSerializer`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LeefSerializerOptions {
+    /// The version of the LEEF format.
+    #[serde(default)]
+    pub version: LeefVersion,
+
+    /// The character delimiting attributes. Only LEEF 2.0 supports delimiters other than a tab.
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+
+    /// The vendor written to the `Vendor` header.
+    pub device_vendor: String,
+
+    /// The product written to the `Product` header.
+    pub device_product: String,
+
+    /// The version written to the `Version` header.
+    pub device_version: String,
+
+    /// The field written to the `EventID` header.
+    #[serde(default = "default_event_id_field")]
+    pub event_id_field: String,
+
+    /// Mapping of LEEF attribute keys to the event fields they are written from.
+    ///
+    /// Fields that don't exist in the event are omitted.
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+}
+
+const fn default_delimiter() -> char {
+    DEFAULT_DELIMITER
+}
+
+fn default_event_id_field() -> String {
+    EVENT_ID.to_owned()
+}
+
+/// Serializer that converts an `Event` to bytes using the IBM QRadar Log Event Extended Format.
+#[derive(Debug, Clone)]
+pub struct LeefSerializer {
+    /// The escaped, constant part of the header.
+    header: String,
+    options: LeefSerializerOptions,
+}
+
+impl LeefSerializer {
+    /// Creates a new `LeefSerializer`.
+    pub fn new(options: LeefSerializerOptions) -> Result<Self, BuildError> {
+        LeefSerializerConfig::new(options).build()
+    }
+}
+
+impl Encoder<Event> for LeefSerializer {
+    type Error = vector_core::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+        let delimiter = self.options.delimiter;
+        let mut output = self.header.clone();
+
+        let field = &self.options.event_id_field;
+        let event_id = get_string(&log, field)
+            .ok_or_else(|| MissingFieldSnafu { field }.build().to_string())?;
+        escape_header(&event_id, &mut output);
+        output.push('|');
+
+        if matches!(self.options.version, LeefVersion::V2) {
+            output.push_str(&format_delimiter(delimiter));
+            output.push('|');
+        }
+
+        let mut first = true;
+        for (key, field) in &self.options.attributes {
+            if let Some(value) = get_string(&log, field) {
+                if !first {
+                    output.push(delimiter);
+                }
+                first = false;
+
+                output.push_str(key);
+                output.push('=');
+                escape_value(&value, Some(delimiter), &mut output);
+            }
+        }
+
+        buffer.extend_from_slice(output.as_bytes());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use vector_common::btreemap;
+    use vector_core::event::{LogEvent, Value};
+
+    fn options(version: LeefVersion, delimiter: char) -> LeefSerializerOptions {
+        LeefSerializerOptions {
+            version,
+            delimiter,
+            device_vendor: "Acme".to_owned(),
+            device_product: "Gateway".to_owned(),
+            device_version: "1.0".to_owned(),
+            event_id_field: default_event_id_field(),
+            attributes: btreemap! {
+                "src" => "source_ip",
+                "usrName" => "user",
+            },
+        }
+    }
+
+    fn event() -> Event {
+        Event::Log(LogEvent::from(btreemap! {
+            "event_id" => Value::from("login"),
+            "source_ip" => Value::from("10.0.0.1"),
+            "user" => Value::from("a^b"),
+        }))
+    }
+
+    #[test]
+    fn serialize_leef_1() {
+        let mut serializer = LeefSerializer::new(options(LeefVersion::V1, '\t')).unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event(), &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            "LEEF:1.0|Acme|Gateway|1.0|login|src=10.0.0.1\tusrName=a^b"
+        );
+    }
+
+    #[test]
+    fn serialize_leef_2() {
+        let mut serializer = LeefSerializer::new(options(LeefVersion::V2, '^')).unwrap();
+        let mut bytes = BytesMut::new();
+
+        serializer.encode(event(), &mut bytes).unwrap();
+
+        assert_eq!(
+            bytes.freeze(),
+            r"LEEF:2.0|Acme|Gateway|1.0|login|^|src=10.0.0.1^usrName=a\^b"
+        );
+    }
+
+    #[test]
+    fn rejects_custom_delimiter_for_leef_1() {
+        assert!(LeefSerializer::new(options(LeefVersion::V1, '^')).is_err());
+    }
+}
@@ -4,8 +4,10 @@
 #![deny(missing_docs)]
 
 mod avro;
+mod cef;
 mod gelf;
 mod json;
+mod leef;
 mod logfmt;
 mod native;
 mod native_json;
@@ -15,9 +17,11 @@ mod text;
 use std::fmt::Debug;
 
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
+pub use cef::{CefSerializer, CefSerializerConfig, CefSerializerOptions};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
 pub use json::{JsonSerializer, JsonSerializerConfig};
+pub use leef::{LeefSerializer, LeefSerializerConfig, LeefSerializerOptions, LeefVersion};
 pub use logfmt::{LogfmtSerializer, LogfmtSerializerConfig};
 pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
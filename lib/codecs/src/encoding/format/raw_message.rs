@@ -1,10 +1,10 @@
-use bytes::{BufMut, BytesMut};
+use bytes::{BufMut, Bytes, BytesMut};
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::Encoder;
 use value::Kind;
 use vector_core::{
     config::{log_schema, DataType},
-    event::Event,
+    event::{Event, Value},
     schema,
 };
 
@@ -43,6 +43,24 @@ impl RawMessageSerializer {
     pub const fn new() -> Self {
         Self
     }
+
+    /// Returns the message of the event without copying it, if it is stored as bytes.
+    ///
+    /// This gives the same output as `encode`, so that sinks without framing can pass the
+    /// received bytes through.
+    pub fn passthrough(&self, event: &Event) -> Option<Bytes> {
+        let message_key = log_schema().message_key();
+
+        let log = event.maybe_as_log()?;
+
+        match log
+            .get_by_meaning(message_key)
+            .or_else(|| log.get(message_key))
+        {
+            Some(Value::Bytes(bytes)) => Some(bytes.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl Encoder<Event> for RawMessageSerializer {
@@ -82,4 +100,25 @@ mod tests {
 
         assert_eq!(buffer.freeze(), Bytes::from("foo"));
     }
+
+    #[test]
+    fn passthrough_bytes() {
+        let message = Bytes::from("foo");
+        let input = Event::from(LogEvent::from(message.clone()));
+        let serializer = RawMessageSerializer;
+
+        let output = serializer.passthrough(&input).unwrap();
+
+        assert_eq!(output, message);
+        assert_eq!(output.as_ptr(), message.as_ptr());
+    }
+
+    #[test]
+    fn passthrough_requires_bytes() {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key(), Value::Integer(42));
+        let serializer = RawMessageSerializer;
+
+        assert_eq!(serializer.passthrough(&Event::from(log)), None);
+    }
 }
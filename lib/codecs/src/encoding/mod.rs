@@ -8,11 +8,12 @@ use std::fmt::Debug;
 
 use bytes::BytesMut;
 pub use format::{
-    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
-    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
-    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
-    TextSerializerConfig,
+    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, CefSerializer,
+    CefSerializerConfig, CefSerializerOptions, GelfSerializer, GelfSerializerConfig,
+    JsonSerializer, JsonSerializerConfig, LeefSerializer, LeefSerializerConfig,
+    LeefSerializerOptions, LeefVersion, LogfmtSerializer, LogfmtSerializerConfig,
+    NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig,
+    RawMessageSerializer, RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
@@ -191,10 +192,20 @@ pub enum SerializerConfig {
         /// Options for the avro serializer.
         avro: AvroSerializerOptions,
     },
+    /// Configures the `CefSerializer`.
+    Cef {
+        /// Options for the CEF serializer.
+        cef: CefSerializerOptions,
+    },
     /// Configures the `GelfSerializer`.
     Gelf,
     /// Configures the `JsonSerializer`.
     Json,
+    /// Configures the `LeefSerializer`.
+    Leef {
+        /// Options for the LEEF serializer.
+        leef: LeefSerializerOptions,
+    },
     /// Configures the `LogfmtSerializer`.
     Logfmt,
     /// Configures the `NativeSerializer`.
@@ -213,6 +224,12 @@ impl From<AvroSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<CefSerializerConfig> for SerializerConfig {
+    fn from(config: CefSerializerConfig) -> Self {
+        Self::Cef { cef: config.cef }
+    }
+}
+
 impl From<GelfSerializerConfig> for SerializerConfig {
     fn from(_: GelfSerializerConfig) -> Self {
         Self::Gelf
@@ -225,6 +242,12 @@ impl From<JsonSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<LeefSerializerConfig> for SerializerConfig {
+    fn from(config: LeefSerializerConfig) -> Self {
+        Self::Leef { leef: config.leef }
+    }
+}
+
 impl From<LogfmtSerializerConfig> for SerializerConfig {
     fn from(_: LogfmtSerializerConfig) -> Self {
         Self::Logfmt
@@ -262,8 +285,14 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => Ok(Serializer::Avro(
                 AvroSerializerConfig::new(avro.schema.clone()).build()?,
             )),
+            SerializerConfig::Cef { cef } => Ok(Serializer::Cef(
+                CefSerializerConfig::new(cef.clone()).build()?,
+            )),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
             SerializerConfig::Json => Ok(Serializer::Json(JsonSerializerConfig.build())),
+            SerializerConfig::Leef { leef } => Ok(Serializer::Leef(
+                LeefSerializerConfig::new(leef.clone()).build()?,
+            )),
             SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
             SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
             SerializerConfig::NativeJson => {
@@ -282,8 +311,10 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => {
                 AvroSerializerConfig::new(avro.schema.clone()).input_type()
             }
+            SerializerConfig::Cef { cef } => CefSerializerConfig::new(cef.clone()).input_type(),
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
             SerializerConfig::Json => JsonSerializerConfig.input_type(),
+            SerializerConfig::Leef { leef } => LeefSerializerConfig::new(leef.clone()).input_type(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
@@ -298,8 +329,14 @@ impl SerializerConfig {
             SerializerConfig::Avro { avro } => {
                 AvroSerializerConfig::new(avro.schema.clone()).schema_requirement()
             }
+            SerializerConfig::Cef { cef } => {
+                CefSerializerConfig::new(cef.clone()).schema_requirement()
+            }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
             SerializerConfig::Json => JsonSerializerConfig.schema_requirement(),
+            SerializerConfig::Leef { leef } => {
+                LeefSerializerConfig::new(leef.clone()).schema_requirement()
+            }
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
@@ -314,10 +351,14 @@ impl SerializerConfig {
 pub enum Serializer {
     /// Uses an `AvroSerializer` for serialization.
     Avro(AvroSerializer),
+    /// Uses a `CefSerializer` for serialization.
+    Cef(CefSerializer),
     /// Uses a `GelfSerializer` for serialization.
     Gelf(GelfSerializer),
     /// Uses a `JsonSerializer` for serialization.
     Json(JsonSerializer),
+    /// Uses a `LeefSerializer` for serialization.
+    Leef(LeefSerializer),
     /// Uses a `LogfmtSerializer` for serialization.
     Logfmt(LogfmtSerializer),
     /// Uses a `NativeSerializer` for serialization.
@@ -336,6 +377,8 @@ impl Serializer {
         match self {
             Serializer::Json(_) | Serializer::NativeJson(_) | Serializer::Gelf(_) => true,
             Serializer::Avro(_)
+            | Serializer::Cef(_)
+            | Serializer::Leef(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
@@ -355,6 +398,8 @@ impl Serializer {
             Serializer::Json(serializer) => serializer.to_json_value(event),
             Serializer::NativeJson(serializer) => serializer.to_json_value(event),
             Serializer::Avro(_)
+            | Serializer::Cef(_)
+            | Serializer::Leef(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
@@ -371,6 +416,12 @@ impl From<AvroSerializer> for Serializer {
     }
 }
 
+impl From<CefSerializer> for Serializer {
+    fn from(serializer: CefSerializer) -> Self {
+        Self::Cef(serializer)
+    }
+}
+
 impl From<GelfSerializer> for Serializer {
     fn from(serializer: GelfSerializer) -> Self {
         Self::Gelf(serializer)
@@ -383,6 +434,12 @@ impl From<JsonSerializer> for Serializer {
     }
 }
 
+impl From<LeefSerializer> for Serializer {
+    fn from(serializer: LeefSerializer) -> Self {
+        Self::Leef(serializer)
+    }
+}
+
 impl From<LogfmtSerializer> for Serializer {
     fn from(serializer: LogfmtSerializer) -> Self {
         Self::Logfmt(serializer)
@@ -419,8 +476,10 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
     fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
         match self {
             Serializer::Avro(serializer) => serializer.encode(event, buffer),
+            Serializer::Cef(serializer) => serializer.encode(event, buffer),
             Serializer::Gelf(serializer) => serializer.encode(event, buffer),
             Serializer::Json(serializer) => serializer.encode(event, buffer),
+            Serializer::Leef(serializer) => serializer.encode(event, buffer),
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
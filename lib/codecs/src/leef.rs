@@ -0,0 +1,74 @@
+//! Contains common definitions for LEEF codec support
+
+/// LEEF header fields, as represented in events. Definitions from the IBM QRadar "Log Event
+/// Extended Format (LEEF)" guide.
+pub mod leef_fields {
+    /// The version of the LEEF format, either `1.0` or `2.0`.
+    pub const VERSION: &str = "leef_version";
+
+    /// The vendor of the device that sent the event.
+    pub const DEVICE_VENDOR: &str = "device_vendor";
+
+    /// The product name of the device that sent the event.
+    pub const DEVICE_PRODUCT: &str = "device_product";
+
+    /// The version of the device that sent the event.
+    pub const DEVICE_VERSION: &str = "device_version";
+
+    /// A unique identifier for the event type.
+    pub const EVENT_ID: &str = "event_id";
+}
+
+/// The attribute delimiter used by LEEF 1.0, and by LEEF 2.0 unless specified otherwise.
+pub(crate) const DEFAULT_DELIMITER: char = '\t';
+
+/// Formats the delimiter for the LEEF 2.0 header. Whitespace and control characters are written
+/// in their hexadecimal form to survive transports that mangle them.
+pub(crate) fn format_delimiter(delimiter: char) -> String {
+    if delimiter.is_ascii_whitespace() || delimiter.is_ascii_control() {
+        format!("x{:02X}", delimiter as u32)
+    } else {
+        delimiter.to_string()
+    }
+}
+
+/// Parses the delimiter from the LEEF 2.0 header, which is either a single character or its
+/// hexadecimal code prefixed with `x` or `0x`. An empty delimiter defaults to a tab.
+pub(crate) fn parse_delimiter(delimiter: &str) -> Option<char> {
+    let mut chars = delimiter.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => Some(DEFAULT_DELIMITER),
+        (Some(c), None) => Some(c),
+        _ => {
+            let hex = delimiter
+                .strip_prefix("0x")
+                .or_else(|| delimiter.strip_prefix('x'))?;
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        }
+    }
+}
+
+/// Checks whether the given LEEF attribute key is valid for the given delimiter.
+pub(crate) fn is_valid_attribute_key(key: &str, delimiter: char) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c != delimiter && c != '=' && c != '\\' && !c.is_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_and_parses_delimiters() {
+        assert_eq!(format_delimiter('\t'), "x09");
+        assert_eq!(format_delimiter('^'), "^");
+
+        assert_eq!(parse_delimiter(""), Some('\t'));
+        assert_eq!(parse_delimiter("^"), Some('^'));
+        assert_eq!(parse_delimiter("x09"), Some('\t'));
+        assert_eq!(parse_delimiter("0x7C"), Some('|'));
+        assert_eq!(parse_delimiter("xyz"), None);
+    }
+}
@@ -18,10 +18,18 @@ pub enum Error {
 
 /// Parses a given source field value by applying the list of grok rules until the first match found.
 pub fn parse_grok(source_field: &str, grok_rules: &[GrokRule]) -> Result<Value, Error> {
-    for rule in grok_rules {
+    parse_grok_with_rule(source_field, grok_rules).map(|(_, value)| value)
+}
+
+/// Parses a given source field value like `parse_grok`, also returning the index of the matching rule.
+pub fn parse_grok_with_rule(
+    source_field: &str,
+    grok_rules: &[GrokRule],
+) -> Result<(usize, Value), Error> {
+    for (index, rule) in grok_rules.iter().enumerate() {
         match apply_grok_rule(source_field, rule) {
             Err(Error::NoMatch) => continue,
-            other => return other,
+            other => return other.map(|value| (index, value)),
         }
     }
     Err(Error::NoMatch)
@@ -82,6 +82,25 @@ pub struct OptPseudoSection {
     pub dnssec_ok: bool,
     pub udp_max_payload_size: u16,
     pub options: Vec<EdnsOptionEntry>,
+    pub client_subnet: Option<ClientSubnet>,
+    pub extended_errors: Vec<ExtendedDnsError>,
+}
+
+/// The EDNS Client Subnet option, see RFC 7871.
+#[derive(Clone, Debug, Default)]
+pub struct ClientSubnet {
+    pub family: u16,
+    pub source_prefix_length: u8,
+    pub scope_prefix_length: u8,
+    pub address: Option<String>,
+}
+
+/// The Extended DNS Error option, see RFC 8914.
+#[derive(Clone, Debug, Default)]
+pub struct ExtendedDnsError {
+    pub info_code: u16,
+    pub purpose: Option<&'static str>,
+    pub extra_text: Option<String>,
 }
 
 #[derive(Clone, Debug, Default)]
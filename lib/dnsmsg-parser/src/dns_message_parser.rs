@@ -1,4 +1,5 @@
 use std::fmt::Write as _;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::Utf8Error;
 
 use data_encoding::{BASE32HEX_NOPAD, BASE64, HEXUPPER};
@@ -22,10 +23,14 @@ use trust_dns_proto::{
 };
 
 use super::dns_message::{
-    self, DnsQueryMessage, DnsRecord, DnsUpdateMessage, EdnsOptionEntry, OptPseudoSection,
-    QueryHeader, QueryQuestion, UpdateHeader, ZoneInfo,
+    self, ClientSubnet, DnsQueryMessage, DnsRecord, DnsUpdateMessage, EdnsOptionEntry,
+    ExtendedDnsError, OptPseudoSection, QueryHeader, QueryQuestion, UpdateHeader, ZoneInfo,
 };
 
+/// The EDNS option codes of the options parsed into structured fields.
+const EDNS_OPTION_CODE_CLIENT_SUBNET: u16 = 8;
+const EDNS_OPTION_CODE_EXTENDED_DNS_ERROR: u16 = 15;
+
 /// Error type for DNS message parsing
 #[derive(Error, Debug)]
 pub enum DnsMessageParserError {
@@ -73,9 +78,15 @@ impl DnsMessageParser {
         let rcode_high = edns_section.as_ref().map_or(0, |edns| edns.extended_rcode);
         let response_code = (u16::from(rcode_high) << 4) | ((u16::from(header.rcode)) & 0x000F);
 
+        // With EDNS, 16 is the extended BADVERS code rather than the TSIG BADSIG one.
+        let response = match (&edns_section, response_code) {
+            (Some(_), 16) => Some("BADVERS"),
+            _ => parse_response_code(response_code),
+        };
+
         Ok(DnsQueryMessage {
             response_code,
-            response: parse_response_code(response_code),
+            response,
             header,
             question_section: self.parse_dns_query_message_question_section(&msg),
             answer_section: self.parse_dns_message_section(msg.answers())?,
@@ -823,15 +834,124 @@ fn parse_dns_update_message_header(dns_message: &TrustDnsMessage) -> UpdateHeade
 }
 
 fn parse_edns(dns_message: &TrustDnsMessage) -> Option<OptPseudoSection> {
-    dns_message.edns().map(|edns| OptPseudoSection {
-        extended_rcode: edns.rcode_high(),
-        version: edns.version(),
-        dnssec_ok: edns.dnssec_ok(),
-        udp_max_payload_size: edns.max_payload(),
-        options: parse_edns_options(edns),
+    dns_message.edns().map(|edns| {
+        let mut client_subnet = None;
+        let mut extended_errors = Vec::new();
+        for (code, option) in edns.options().as_ref() {
+            let data = match option {
+                EdnsOption::Unknown(_, data) => data.clone(),
+                option => Vec::<u8>::from(option),
+            };
+            match u16::from(*code) {
+                EDNS_OPTION_CODE_CLIENT_SUBNET => client_subnet = parse_client_subnet(&data),
+                EDNS_OPTION_CODE_EXTENDED_DNS_ERROR => {
+                    extended_errors.extend(parse_extended_dns_error(&data))
+                }
+                _ => {}
+            }
+        }
+
+        OptPseudoSection {
+            extended_rcode: edns.rcode_high(),
+            version: edns.version(),
+            dnssec_ok: edns.dnssec_ok(),
+            udp_max_payload_size: edns.max_payload(),
+            options: parse_edns_options(edns),
+            client_subnet,
+            extended_errors,
+        }
     })
 }
 
+fn parse_client_subnet(data: &[u8]) -> Option<ClientSubnet> {
+    if data.len() < 4 {
+        return None;
+    }
+    let family = u16::from_be_bytes([data[0], data[1]]);
+    let source_prefix_length = data[2];
+    let scope_prefix_length = data[3];
+    // The address is truncated to the bytes covered by the source prefix length.
+    let address = &data[4..];
+    let address = match family {
+        1 if address.len() <= 4 => {
+            let mut octets = [0u8; 4];
+            octets[..address.len()].copy_from_slice(address);
+            Some(IpAddr::from(Ipv4Addr::from(octets)).to_string())
+        }
+        2 if address.len() <= 16 => {
+            let mut octets = [0u8; 16];
+            octets[..address.len()].copy_from_slice(address);
+            Some(IpAddr::from(Ipv6Addr::from(octets)).to_string())
+        }
+        _ => None,
+    };
+
+    Some(ClientSubnet {
+        family,
+        source_prefix_length,
+        scope_prefix_length,
+        address,
+    })
+}
+
+fn parse_extended_dns_error(data: &[u8]) -> Option<ExtendedDnsError> {
+    if data.len() < 2 {
+        return None;
+    }
+    let info_code = u16::from_be_bytes([data[0], data[1]]);
+    let extra_text = String::from_utf8_lossy(&data[2..])
+        .trim_end_matches('\0')
+        .to_string();
+
+    Some(ExtendedDnsError {
+        info_code,
+        purpose: parse_extended_dns_error_purpose(info_code),
+        extra_text: if extra_text.is_empty() {
+            None
+        } else {
+            Some(extra_text)
+        },
+    })
+}
+
+fn parse_extended_dns_error_purpose(info_code: u16) -> Option<&'static str> {
+    match info_code {
+        0 => Some("Other"),
+        1 => Some("Unsupported DNSKEY Algorithm"),
+        2 => Some("Unsupported DS Digest Type"),
+        3 => Some("Stale Answer"),
+        4 => Some("Forged Answer"),
+        5 => Some("DNSSEC Indeterminate"),
+        6 => Some("DNSSEC Bogus"),
+        7 => Some("Signature Expired"),
+        8 => Some("Signature Not Yet Valid"),
+        9 => Some("DNSKEY Missing"),
+        10 => Some("RRSIGs Missing"),
+        11 => Some("No Zone Key Bit Set"),
+        12 => Some("NSEC Missing"),
+        13 => Some("Cached Error"),
+        14 => Some("Not Ready"),
+        15 => Some("Blocked"),
+        16 => Some("Censored"),
+        17 => Some("Filtered"),
+        18 => Some("Prohibited"),
+        19 => Some("Stale NXDOMAIN Answer"),
+        20 => Some("Not Authoritative"),
+        21 => Some("Not Supported"),
+        22 => Some("No Reachable Authority"),
+        23 => Some("Network Error"),
+        24 => Some("Invalid Data"),
+        _ => None,
+    }
+}
+
+fn parse_edns_opt_name(opt_code: EdnsCode) -> String {
+    match u16::from(opt_code) {
+        EDNS_OPTION_CODE_EXTENDED_DNS_ERROR => "EDE".to_string(),
+        _ => format!("{:?}", opt_code),
+    }
+}
+
 fn parse_edns_options(edns: &Edns) -> Vec<EdnsOptionEntry> {
     edns.options()
         .as_ref()
@@ -853,7 +973,7 @@ fn parse_edns_opt_dnssec_algorithms(
     let algorithm_names: Vec<String> = algorithms.iter().map(|alg| alg.to_string()).collect();
     EdnsOptionEntry {
         opt_code: Into::<u16>::into(opt_code),
-        opt_name: format!("{:?}", opt_code),
+        opt_name: parse_edns_opt_name(opt_code),
         opt_data: algorithm_names.join(" "),
     }
 }
@@ -861,7 +981,7 @@ fn parse_edns_opt_dnssec_algorithms(
 fn parse_edns_opt(opt_code: EdnsCode, opt_data: &[u8]) -> EdnsOptionEntry {
     EdnsOptionEntry {
         opt_code: Into::<u16>::into(opt_code),
-        opt_name: format!("{:?}", opt_code),
+        opt_name: parse_edns_opt_name(opt_code),
         opt_data: BASE64.encode(opt_data),
     }
 }
@@ -1193,6 +1313,33 @@ mod tests {
         assert_ne!(dns_query_message.answer_section[0].rdata_bytes, None);
     }
 
+    #[test]
+    fn test_parse_client_subnet() {
+        let subnet = parse_client_subnet(&[0, 1, 24, 0, 192, 0, 2]).unwrap();
+        assert_eq!(subnet.family, 1);
+        assert_eq!(subnet.source_prefix_length, 24);
+        assert_eq!(subnet.scope_prefix_length, 0);
+        assert_eq!(subnet.address.as_deref(), Some("192.0.2.0"));
+
+        let subnet = parse_client_subnet(&[0, 2, 48, 56, 0x20, 0x01, 0x0d, 0xb8, 0, 1]).unwrap();
+        assert_eq!(subnet.scope_prefix_length, 56);
+        assert_eq!(subnet.address.as_deref(), Some("2001:db8:1::"));
+
+        assert!(parse_client_subnet(&[0, 1]).is_none());
+    }
+
+    #[test]
+    fn test_parse_extended_dns_error() {
+        let error = parse_extended_dns_error(b"\x00\x06bad signature").unwrap();
+        assert_eq!(error.info_code, 6);
+        assert_eq!(error.purpose, Some("DNSSEC Bogus"));
+        assert_eq!(error.extra_text.as_deref(), Some("bad signature"));
+
+        let error = parse_extended_dns_error(&[0, 15]).unwrap();
+        assert_eq!(error.purpose, Some("Blocked"));
+        assert_eq!(error.extra_text, None);
+    }
+
     #[test]
     fn test_format_bytes_as_hex_string() {
         assert_eq!(
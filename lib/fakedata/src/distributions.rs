@@ -0,0 +1,41 @@
+//! Random values following the distributions seen in real traffic, rather than uniform ones.
+
+use std::f64::consts::TAU;
+
+use rand::{seq::SliceRandom, thread_rng, Rng};
+
+/// A value from a normal distribution, using the Box-Muller transform.
+pub(crate) fn normal(mean: f64, std_dev: f64) -> f64 {
+    let mut rng = thread_rng();
+    // `1 - u` is in (0, 1], so that its log is finite.
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen();
+    mean + std_dev * (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+/// A value from a log-normal distribution, such as response sizes and latencies, which are
+/// mostly close to `median` with a long tail of large values.
+pub(crate) fn log_normal(median: f64, sigma: f64) -> f64 {
+    normal(median.ln(), sigma).exp()
+}
+
+/// One of the choices, picked with a probability proportional to its weight.
+pub(crate) fn weighted<T: Copy>(choices: &[(T, u32)]) -> T {
+    choices
+        .choose_weighted(&mut thread_rng(), |(_, weight)| *weight)
+        .expect("choices must not be empty")
+        .0
+}
+
+/// Whether an event with the given probability happens.
+pub(crate) fn chance(probability: f64) -> bool {
+    thread_rng().gen_bool(probability)
+}
+
+/// A random lowercase hexadecimal identifier of the given number of bytes, such as a trace ID.
+pub(crate) fn hex_id(bytes: usize) -> String {
+    let mut rng = thread_rng();
+    (0..bytes)
+        .map(|_| format!("{:02x}", rng.gen::<u8>()))
+        .collect()
+}
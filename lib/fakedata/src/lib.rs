@@ -1,3 +1,6 @@
 #![deny(warnings)]
 
+mod distributions;
 pub mod logs;
+pub mod metrics;
+pub mod traces;
@@ -5,6 +5,9 @@ use chrono::{
 };
 use fakedata_generator::{gen_domain, gen_ipv4, gen_username};
 use rand::{thread_rng, Rng};
+use serde_json::{json, Map, Value};
+
+use crate::distributions::{chance, hex_id, log_normal, weighted};
 
 static APPLICATION_NAMES: [&str; 10] = [
     "auth", "data", "deploy", "etl", "scraper", "cron", "ingress", "egress", "alerter", "fwd",
@@ -45,6 +48,106 @@ static ERROR_MESSAGES: [&str; 9] = [
     "Pretty pretty pretty good",
 ];
 
+// Weighted by how often they show up in the access logs of a typical website.
+static WEIGHTED_HTTP_CODES: [(usize, u32); 15] = [
+    (200, 820),
+    (201, 15),
+    (204, 15),
+    (301, 10),
+    (302, 20),
+    (304, 45),
+    (400, 12),
+    (401, 8),
+    (403, 6),
+    (404, 25),
+    (429, 4),
+    (500, 8),
+    (502, 4),
+    (503, 4),
+    (504, 4),
+];
+
+static WEIGHTED_HTTP_METHODS: [(&str, u32); 6] = [
+    ("GET", 800),
+    ("POST", 140),
+    ("PUT", 25),
+    ("DELETE", 15),
+    ("HEAD", 15),
+    ("PATCH", 5),
+];
+
+// Roughly following Zipf's law: a few pages get most of the traffic.
+static WEIGHTED_WEB_PATHS: [(&str, u32); 12] = [
+    ("/", 300),
+    ("/static/js/app.js", 150),
+    ("/static/css/main.css", 100),
+    ("/api/v1/products", 75),
+    ("/favicon.ico", 60),
+    ("/api/v1/cart", 50),
+    ("/images/logo.png", 43),
+    ("/login", 37),
+    ("/search?q=vector", 33),
+    ("/api/v1/orders", 30),
+    ("/account/settings", 27),
+    ("/health", 25),
+];
+
+static WEIGHTED_USER_AGENTS: [(&str, u32); 7] = [
+    (
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/104.0.0.0 Safari/537.36",
+        400,
+    ),
+    (
+        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/15.6 Safari/605.1.15",
+        180,
+    ),
+    (
+        "Mozilla/5.0 (iPhone; CPU iPhone OS 15_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/15.6 Mobile/15E148 Safari/604.1",
+        170,
+    ),
+    ("Mozilla/5.0 (X11; Linux x86_64; rv:103.0) Gecko/20100101 Firefox/103.0", 90),
+    ("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)", 80),
+    ("curl/7.84.0", 50),
+    ("kube-probe/1.24", 30),
+];
+
+static WEIGHTED_APP_LEVELS: [(&str, u32); 4] =
+    [("debug", 150), ("info", 700), ("warn", 100), ("error", 50)];
+
+static APP_SERVICES: [&str; 6] = [
+    "checkout", "payments", "inventory", "search", "accounts", "shipping",
+];
+
+static APP_MESSAGES: [&str; 8] = [
+    "Request completed",
+    "Cache miss, fetching from database",
+    "Retrying request to upstream service",
+    "User session refreshed",
+    "Order placed",
+    "Payment authorization declined",
+    "Connection pool exhausted, waiting for a connection",
+    "Background job finished",
+];
+
+/// The fields of JSON application logs that have realistic values. Other fields get random words.
+pub const APP_FIELDS: [&str; 11] = [
+    "timestamp",
+    "level",
+    "service",
+    "message",
+    "host",
+    "request_id",
+    "trace_id",
+    "user_id",
+    "duration_ms",
+    "status",
+    "version",
+];
+
+static RANDOM_WORDS: [&str; 8] = [
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel",
+];
+
 const APACHE_COMMON_TIME_FORMAT: &str = "%d/%b/%Y:%T %z";
 const APACHE_ERROR_TIME_FORMAT: &str = "%a %b %d %T %Y";
 const SYSLOG_3164_FORMAT: &str = "%b %d %T";
@@ -129,6 +232,122 @@ pub fn json_log_line() -> String {
     )
 }
 
+pub fn apache_combined_log_line() -> String {
+    // Example log line:
+    // 203.0.113.7 - - [31/Oct/2020:19:06:10 -0700] "GET / HTTP/1.1" 200 5321 "-" "curl/7.84.0"
+    AccessRequest::random().combined()
+}
+
+pub fn nginx_log_line() -> String {
+    // The `combined` format, followed by the request time in seconds as commonly added to it.
+    // Example log line:
+    // 203.0.113.7 - - [31/Oct/2020:19:06:10 -0700] "GET / HTTP/1.1" 200 532 "-" "curl/7.84.0" 0.042
+    let request = AccessRequest::random();
+    format!("{} {:.3}", request.combined(), request.request_time)
+}
+
+pub fn app_json_log_line(fields: &[String]) -> String {
+    // Example log line, with the default fields:
+    // {"timestamp":"2020-11-05T18:11:43.975Z","level":"info","service":"checkout", \
+    //   "message":"Request completed","request_id":"5f1c0e9a2b7d4e6f","duration_ms":42}
+    let level = weighted(&WEIGHTED_APP_LEVELS);
+    let object = fields
+        .iter()
+        .map(|field| (field.clone(), app_field_value(field, level)))
+        .collect::<Map<_, _>>();
+    Value::Object(object).to_string()
+}
+
+/// A request to a web server, with fields following the distributions of real traffic.
+struct AccessRequest {
+    address: String,
+    user: String,
+    method: &'static str,
+    path: &'static str,
+    version: &'static str,
+    status: usize,
+    bytes: usize,
+    referer: String,
+    user_agent: &'static str,
+    request_time: f64,
+}
+
+impl AccessRequest {
+    fn random() -> Self {
+        let status = weighted(&WEIGHTED_HTTP_CODES);
+        // Responses without a body, and errors, are small, while successful ones have a long tail.
+        let bytes = match status {
+            204 | 304 => 0,
+            200..=299 => log_normal(5000.0, 1.2) as usize,
+            _ => log_normal(300.0, 0.5) as usize,
+        };
+        // Server errors are often timeouts, and are slower than successful requests.
+        let request_time = if status >= 500 {
+            log_normal(1.5, 1.0)
+        } else {
+            log_normal(0.05, 0.8)
+        };
+        Self {
+            address: ipv4_address(),
+            user: if chance(0.1) {
+                username()
+            } else {
+                "-".to_owned()
+            },
+            method: weighted(&WEIGHTED_HTTP_METHODS),
+            path: weighted(&WEIGHTED_WEB_PATHS),
+            version: weighted(&[("HTTP/1.1", 60), ("HTTP/2.0", 38), ("HTTP/1.0", 2)]),
+            status,
+            bytes,
+            referer: if chance(0.6) {
+                format!("https://{}{}", domain(), weighted(&WEIGHTED_WEB_PATHS))
+            } else {
+                "-".to_owned()
+            },
+            user_agent: weighted(&WEIGHTED_USER_AGENTS),
+            request_time,
+        }
+    }
+
+    /// The request in the `combined` format shared by Apache and nginx.
+    fn combined(&self) -> String {
+        format!(
+            "{} - {} [{}] \"{} {} {}\" {} {} \"{}\" \"{}\"",
+            self.address,
+            self.user,
+            timestamp_apache_common(),
+            self.method,
+            self.path,
+            self.version,
+            self.status,
+            self.bytes,
+            self.referer,
+            self.user_agent,
+        )
+    }
+}
+
+fn app_field_value(field: &str, level: &str) -> Value {
+    match field {
+        "timestamp" => json!(timestamp_syslog_5424()),
+        "level" => json!(level),
+        "service" => json!(random_from_array(&APP_SERVICES)),
+        "message" => json!(random_from_array(&APP_MESSAGES)),
+        "host" => json!(format!(
+            "ip-10-0-{}-{}",
+            random_in_range(0, 16),
+            random_in_range(1, 255)
+        )),
+        "request_id" => json!(hex_id(8)),
+        "trace_id" => json!(hex_id(16)),
+        "user_id" => json!(random_in_range(1, 100_000)),
+        "duration_ms" => json!(log_normal(40.0, 0.9).round() as u64),
+        "status" => json!(weighted(&WEIGHTED_HTTP_CODES)),
+        "version" => json!(format!("1.{}.{}", random_in_range(0, 20), random_in_range(0, 10))),
+        _ => json!(random_from_array(&RANDOM_WORDS)),
+    }
+}
+
 // Formatted timestamps
 fn timestamp_apache_common() -> DelayedFormat<StrftimeItems<'static>> {
     Local::now().format(APACHE_COMMON_TIME_FORMAT)
@@ -0,0 +1,42 @@
+use std::f64::consts::TAU;
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::json;
+
+use crate::distributions::normal;
+
+// The kinds of series generated, with their typical value and the relative amplitude of their
+// seasonal variation.
+static SERIES_KINDS: [(&str, f64, f64); 4] = [
+    ("http_requests_per_second", 200.0, 0.6),
+    ("cpu_utilization_percent", 45.0, 0.4),
+    ("queue_depth", 30.0, 0.8),
+    ("memory_used_bytes", 2.0e9, 0.1),
+];
+
+pub fn metric_json_line(n: usize, series: usize, period_secs: f64) -> String {
+    // Example point, of the series `n % series`:
+    // {"name":"cpu_utilization_percent","kind":"gauge","tags":{"host":"host-1"}, \
+    //   "timestamp":"2020-11-05T18:11:43.975Z","value":52.3}
+    let index = n % series;
+    let (name, base, amplitude) = SERIES_KINDS[index % SERIES_KINDS.len()];
+    let now = Utc::now();
+
+    // Each host peaks at a slightly different time, like servers in different regions would.
+    let phase = TAU * (now.timestamp_millis() as f64 / 1000.0 / period_secs)
+        + index as f64 / series as f64;
+    let seasonal = 1.0 + amplitude * phase.sin();
+    let mut value = (base * seasonal * normal(1.0, 0.05)).max(0.0);
+    if name == "cpu_utilization_percent" {
+        value = value.min(100.0);
+    }
+
+    json!({
+        "name": name,
+        "kind": "gauge",
+        "tags": { "host": format!("host-{}", index / SERIES_KINDS.len()) },
+        "timestamp": now.to_rfc3339_opts(SecondsFormat::Millis, true),
+        "value": value,
+    })
+    .to_string()
+}
@@ -0,0 +1,83 @@
+use chrono::Utc;
+use rand::{thread_rng, Rng};
+use serde_json::{json, Value};
+
+use crate::distributions::{chance, hex_id, log_normal, weighted};
+
+static ROOT_OPERATIONS: [(&str, u32); 5] = [
+    ("GET /api/v1/products", 500),
+    ("GET /api/v1/cart", 200),
+    ("POST /api/v1/cart", 150),
+    ("POST /api/v1/orders", 100),
+    ("GET /api/v1/orders", 50),
+];
+
+// The services called while handling a request, with the operation they perform.
+static DOWNSTREAM_OPERATIONS: [(&str, &str); 8] = [
+    ("accounts", "accounts.authenticate"),
+    ("inventory", "inventory.check_stock"),
+    ("pricing", "pricing.quote"),
+    ("payments", "payments.authorize"),
+    ("search", "search.query"),
+    ("postgres", "SELECT products"),
+    ("redis", "GET session"),
+    ("kafka", "produce order_events"),
+];
+
+pub fn trace_json_line() -> String {
+    // Example trace, with one span per call, nested in the span of the root request:
+    // {"trace_id":"4bf92f3577b34da6a3ce929d0e0e4736","spans":[{"span_id":"00f067aa0ba902b7", \
+    //   "parent_span_id":null,"service":"frontend","name":"GET /api/v1/cart", \
+    //   "start_time_unix_nano":1604599903975000000,"duration_nano":48000000,"status":"ok"},...]}
+    let trace_id = hex_id(16);
+    let root_id = hex_id(8);
+    let start = Utc::now().timestamp_nanos();
+    let duration = (log_normal(0.08, 0.7) * 1e9) as i64;
+    // Most of the request is spent in the downstream calls, which are made one after the other.
+    let calls = thread_rng().gen_range(1..=5);
+    let mut spans = vec![span(
+        &root_id,
+        None,
+        "frontend",
+        weighted(&ROOT_OPERATIONS),
+        start,
+        duration,
+    )];
+    let slot = duration / (calls + 1);
+    for call in 0..calls {
+        let (service, name) =
+            DOWNSTREAM_OPERATIONS[thread_rng().gen_range(0..DOWNSTREAM_OPERATIONS.len())];
+        let offset = slot / 2 + call * slot;
+        let call_duration = thread_rng().gen_range(slot / 4..=slot);
+        spans.push(span(
+            &hex_id(8),
+            Some(&root_id),
+            service,
+            name,
+            start + offset,
+            call_duration,
+        ));
+    }
+
+    json!({ "trace_id": trace_id, "spans": spans }).to_string()
+}
+
+fn span(
+    span_id: &str,
+    parent_span_id: Option<&str>,
+    service: &str,
+    name: &str,
+    start_time_unix_nano: i64,
+    duration_nano: i64,
+) -> Value {
+    let status = if chance(0.02) { "error" } else { "ok" };
+    json!({
+        "span_id": span_id,
+        "parent_span_id": parent_span_id,
+        "service": service,
+        "name": name,
+        "start_time_unix_nano": start_time_unix_nano,
+        "duration_nano": duration_nano,
+        "status": status,
+    })
+}
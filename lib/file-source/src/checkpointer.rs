@@ -40,6 +40,18 @@ struct Checkpoint {
     modified: DateTime<Utc>,
 }
 
+/// Persists the checkpoints somewhere other than the data directory, such as a store shared by
+/// several instances.
+///
+/// Both methods are only called from blocking threads.
+pub trait CheckpointBackend: Send + Sync {
+    /// Reads the checkpoints last saved, returning `None` if they were never saved.
+    fn load(&self) -> Result<Option<Vec<u8>>, io::Error>;
+
+    /// Atomically replaces the saved checkpoints.
+    fn save(&self, checkpoints: &[u8]) -> Result<(), io::Error>;
+}
+
 pub struct Checkpointer {
     directory: PathBuf,
     tmp_file_path: PathBuf,
@@ -47,6 +59,7 @@ pub struct Checkpointer {
     glob_string: String,
     checkpoints: Arc<CheckpointsView>,
     last: Mutex<Option<State>>,
+    backend: Option<Box<dyn CheckpointBackend>>,
 }
 
 /// A thread-safe handle for reading and writing checkpoints in-memory across
@@ -205,6 +218,18 @@ impl Checkpointer {
             stable_file_path,
             checkpoints: Arc::new(CheckpointsView::default()),
             last: Mutex::new(None),
+            backend: None,
+        }
+    }
+
+    /// Creates a checkpointer persisting the checkpoints to `backend`.
+    ///
+    /// Checkpoints previously written to the data directory are still read when the backend has
+    /// none yet, so that switching to a backend doesn't lose the position in each file.
+    pub fn with_backend(data_dir: &Path, backend: Box<dyn CheckpointBackend>) -> Checkpointer {
+        Checkpointer {
+            backend: Some(backend),
+            ..Checkpointer::new(data_dir)
         }
     }
 
@@ -301,6 +326,12 @@ impl Checkpointer {
         // Fetch last written state.
         let mut last = self.last.lock().expect("Data poisoned.");
         if last.as_ref() != Some(&current) {
+            if let Some(backend) = &self.backend {
+                backend.save(&serde_json::to_vec(&current)?)?;
+                *last = Some(current);
+                return Ok(self.checkpoints.checkpoints.len());
+            }
+
             // Write the new checkpoints to a tmp file and flush it fully to
             // disk. If vector dies anywhere during this section, the existing
             // stable file will still be in its current valid state and we'll be
@@ -338,6 +369,29 @@ impl Checkpointer {
     /// format but falling back to the legacy system when those files are found
     /// instead.
     pub fn read_checkpoints(&mut self, ignore_before: Option<DateTime<Utc>>) {
+        if let Some(backend) = &self.backend {
+            match backend.load().and_then(|checkpoints| {
+                checkpoints
+                    .map(|checkpoints| {
+                        serde_json::from_slice(&checkpoints)
+                            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+                    })
+                    .transpose()
+            }) {
+                Ok(Some(state)) => {
+                    info!(message = "Loaded checkpoint data.");
+                    self.checkpoints.set_state(state, ignore_before);
+                    return;
+                }
+                // Fall back to the checkpoints written to the data directory, if any.
+                Ok(None) => {}
+                Err(error) => {
+                    warn!(message = "Unable to load checkpoint data.", %error);
+                    return;
+                }
+            }
+        }
+
         // First try reading from the tmp file location. If this works, it means
         // that the previous process was interrupted in the process of
         // checkpointing and the tmp file should contain more recent data that
@@ -423,12 +477,31 @@ mod test {
     use pretty_assertions::assert_eq;
     use tempfile::tempdir;
 
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
     use super::{
         super::{FingerprintStrategy, Fingerprinter},
-        Checkpoint, Checkpointer, FileFingerprint, FilePosition, CHECKPOINT_FILE_NAME,
-        TMP_FILE_NAME,
+        Checkpoint, CheckpointBackend, Checkpointer, FileFingerprint, FilePosition,
+        CHECKPOINT_FILE_NAME, TMP_FILE_NAME,
     };
 
+    #[derive(Default)]
+    struct MemoryBackend(Mutex<Option<Vec<u8>>>);
+
+    impl CheckpointBackend for Arc<MemoryBackend> {
+        fn load(&self) -> Result<Option<Vec<u8>>, io::Error> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn save(&self, checkpoints: &[u8]) -> Result<(), io::Error> {
+            *self.0.lock().unwrap() = Some(checkpoints.to_vec());
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_checkpointer_basics() {
         let fingerprints = vec![
@@ -526,6 +599,36 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_checkpointer_backend() {
+        let fingerprint = FileFingerprint::DevInode(1, 2);
+        let position: FilePosition = 1234;
+        let data_dir = tempdir().unwrap();
+        let backend = Arc::new(MemoryBackend::default());
+
+        // Checkpoints already in the data directory are read until the backend has some.
+        {
+            let mut chkptr = Checkpointer::new(data_dir.path());
+            chkptr.update_checkpoint(fingerprint, position);
+            chkptr.write_checkpoints().unwrap();
+        }
+        {
+            let mut chkptr = Checkpointer::with_backend(data_dir.path(), Box::new(backend.clone()));
+            chkptr.read_checkpoints(None);
+            assert_eq!(chkptr.get_checkpoint(fingerprint), Some(position));
+            chkptr.update_checkpoint(fingerprint, position + 1);
+            chkptr.write_checkpoints().unwrap();
+        }
+        assert!(backend.0.lock().unwrap().is_some());
+
+        // From then on, the backend is preferred over the data directory.
+        {
+            let mut chkptr = Checkpointer::with_backend(data_dir.path(), Box::new(backend));
+            chkptr.read_checkpoints(None);
+            assert_eq!(chkptr.get_checkpoint(fingerprint), Some(position + 1));
+        }
+    }
+
     #[test]
     fn test_checkpointer_fingerprint_upgrades_unknown() {
         let log_dir = tempdir().unwrap();
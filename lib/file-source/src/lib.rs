@@ -14,7 +14,7 @@ mod metadata_ext;
 pub mod paths_provider;
 
 pub use self::{
-    checkpointer::{Checkpointer, CheckpointsView, CHECKPOINT_FILE_NAME},
+    checkpointer::{CheckpointBackend, Checkpointer, CheckpointsView, CHECKPOINT_FILE_NAME},
     file_server::{FileServer, Line, Shutdown as FileServerShutdown},
     fingerprinter::{FileFingerprint, FingerprintStrategy, Fingerprinter},
     internal_events::FileSourceInternalEvents,
@@ -157,6 +157,7 @@ pub fn init_instrumentation() {
 // reads it from the buffer.
 //
 
+#[allow(dead_code)]
 pub async fn wtr_measurement<const N: usize>(
     mut sender: BufferSender<Message<N>>,
     mut receiver: BufferReceiver<Message<N>>,
@@ -170,6 +171,7 @@ pub async fn wtr_measurement<const N: usize>(
     while receiver.next().await.is_some() {}
 }
 
+#[allow(dead_code)]
 pub async fn war_measurement<const N: usize>(
     mut sender: BufferSender<Message<N>>,
     mut receiver: BufferReceiver<Message<N>>,
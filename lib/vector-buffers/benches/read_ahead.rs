@@ -0,0 +1,91 @@
+use std::{mem, num::NonZeroU64, time::Duration};
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BatchSize, BenchmarkGroup, BenchmarkId,
+    Criterion, SamplingMode, Throughput,
+};
+use temp_dir::TempDir;
+use tokio::runtime::{Handle, Runtime};
+use vector_buffers::{BufferType, WhenFull};
+
+use crate::common::{init_instrumentation, Message};
+
+mod common;
+
+/// Amount of records written before reading them back, in bytes.
+///
+/// This spans more than one data file, as the disk v2 reader only decodes records ahead of the
+/// caller in the data files the writer is done with.
+const TOTAL_BYTES: usize = 192 * 1024 * 1024;
+
+macro_rules! experiment {
+    ($criterion:expr, [$( $width:expr ),*]) => {{
+        let mut group: BenchmarkGroup<WallTime> = $criterion.benchmark_group("buffer-disk-v2");
+        group.sampling_mode(SamplingMode::Flat);
+        init_instrumentation();
+
+        let rt = Runtime::new().unwrap();
+
+        $(
+            let max_events = TOTAL_BYTES / mem::size_of::<Message<$width>>();
+            let bytes = mem::size_of::<Message<$width>>();
+            group.throughput(Throughput::Elements(max_events as u64));
+            group.bench_with_input(
+                BenchmarkId::new("read-finished-data-files", bytes),
+                &max_events,
+                |b, max_events| {
+                    b.to_async(&rt)
+                        .iter_batched(
+                            || {
+                                let data_dir = TempDir::new().expect("could not make data dir");
+                                let path = data_dir.path().to_path_buf();
+                                let variant = BufferType::DiskV2 {
+                                    max_size: NonZeroU64::new(4 * TOTAL_BYTES as u64).unwrap(),
+                                    when_full: WhenFull::Block,
+                                };
+
+                                // Only the reads are measured, so all the records are written up front.
+                                let receiver = tokio::task::block_in_place(move || {
+                                    Handle::current().block_on(async move {
+                                        let (mut sender, receiver, messages) = crate::common::setup::<$width>(
+                                            variant,
+                                            *max_events,
+                                            Some(path),
+                                            String::from("read-ahead"),
+                                        )
+                                        .await;
+                                        for msg in messages.into_iter() {
+                                            sender.send(msg).await.unwrap();
+                                        }
+                                        drop(sender);
+                                        receiver
+                                    })
+                                });
+                                (receiver, data_dir)
+                            },
+                            |(mut receiver, data_dir)| async move {
+                                while receiver.next().await.is_some() {}
+                                drop(data_dir)
+                            },
+                            BatchSize::PerIteration,
+                        )
+                },
+            );
+        )*
+    }};
+}
+
+/// Reads back all the records of data files the writer has moved on from, as after a sink outage.
+fn read_finished_data_files(c: &mut Criterion) {
+    experiment!(c, [4, 16, 64, 256]);
+}
+
+criterion_group!(
+    name = read_ahead;
+    config = Criterion::default()
+        .measurement_time(Duration::from_secs(60))
+        .confidence_level(0.99)
+        .sample_size(10);
+    targets = read_finished_data_files
+);
+criterion_main!(read_ahead);
@@ -28,6 +28,7 @@ pub mod test;
 pub mod topology;
 
 pub(crate) mod variants;
+pub use variants::disk_v2::inspect;
 
 use std::fmt::Debug;
 
@@ -36,6 +36,12 @@ pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(500);
 // buffer throughput and estimate how many IOPS will be consumed, etc.
 pub const DEFAULT_WRITE_BUFFER_SIZE: usize = 256 * 1024;
 
+// Decoding is the most expensive part of reading a record, so when the reader is catching up on
+// data files the writer is done with, it decodes a few batches of records ahead in parallel.  Four
+// decoders is enough to keep a sink fed without tying up too many of the blocking threads of the
+// runtime.
+pub const DEFAULT_READ_AHEAD_DECODERS: usize = 4;
+
 // We specifically limit ourselves to 0-31 for file IDs in test, because it lets us more quickly
 // create/consume the file IDs so we can test edge cases like file ID rollover and "writer is
 // waiting to open file that reader is still on".
@@ -153,6 +159,13 @@ pub struct DiskBufferConfig<FS> {
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
 
+    /// Maximum number of batches of records the reader decodes in parallel, ahead of the record
+    /// being read.
+    ///
+    /// Records are only read ahead in data files the writer has finished with, and are always
+    /// handed to the caller in the order they were written.  A value of one disables read-ahead.
+    pub(crate) read_ahead_decoders: usize,
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -173,6 +186,7 @@ where
     pub(crate) max_record_size: Option<usize>,
     pub(crate) write_buffer_size: Option<usize>,
     pub(crate) flush_interval: Option<Duration>,
+    pub(crate) read_ahead_decoders: Option<usize>,
     pub(crate) filesystem: FS,
 }
 
@@ -188,6 +202,7 @@ impl DiskBufferConfigBuilder {
             max_record_size: None,
             write_buffer_size: None,
             flush_interval: None,
+            read_ahead_decoders: None,
             filesystem: ProductionFilesystem,
         }
     }
@@ -268,6 +283,19 @@ where
         self
     }
 
+    /// Sets the maximum number of batches of records the reader decodes in parallel, ahead of the
+    /// record being read.
+    ///
+    /// Records are only read ahead in data files the writer has finished with, and are always
+    /// handed to the caller in the order they were written.  A value of one disables read-ahead.
+    ///
+    /// Defaults to 4.
+    #[allow(dead_code)]
+    pub fn read_ahead_decoders(mut self, amount: usize) -> Self {
+        self.read_ahead_decoders = Some(amount);
+        self
+    }
+
     /// Filesystem implementation for opening data files.
     ///
     /// We allow parameterizing the filesystem implementation for ease of testing.  The "filesystem"
@@ -287,6 +315,7 @@ where
             max_record_size: self.max_record_size,
             write_buffer_size: self.write_buffer_size,
             flush_interval: self.flush_interval,
+            read_ahead_decoders: self.read_ahead_decoders,
             filesystem,
         }
     }
@@ -301,6 +330,9 @@ where
         let max_record_size = self.max_record_size.unwrap_or(DEFAULT_MAX_RECORD_SIZE);
         let write_buffer_size = self.write_buffer_size.unwrap_or(DEFAULT_WRITE_BUFFER_SIZE);
         let flush_interval = self.flush_interval.unwrap_or(DEFAULT_FLUSH_INTERVAL);
+        let read_ahead_decoders = self
+            .read_ahead_decoders
+            .unwrap_or(DEFAULT_READ_AHEAD_DECODERS);
         let filesystem = self.filesystem;
 
         // Validate the input parameters.
@@ -380,6 +412,13 @@ where
             });
         }
 
+        if read_ahead_decoders == 0 {
+            return Err(BuildError::InvalidParameter {
+                param_name: "read_ahead_decoders",
+                reason: "cannot be zero".to_string(),
+            });
+        }
+
         // Users configure the `max_size` of their disk buffers, which translates to the `max_buffer_size` field here,
         // and represents the maximum desired size of a disk buffer in terms of on-disk usage. In order to meet this
         // request, we do a few things internally and also enforce a lower bound on `max_buffer_size` to ensure we can
@@ -427,6 +466,7 @@ where
             max_record_size,
             write_buffer_size,
             flush_interval,
+            read_ahead_decoders,
             filesystem,
         })
     }
@@ -0,0 +1,469 @@
+//! Offline inspection and repair of disk buffers.
+//!
+//! These functions operate directly on the files of a disk buffer, without loading it, so that
+//! operators can check a buffer for damage and recover from partial writes while Vector is
+//! stopped. Repairing a buffer truncates each damaged data file to its last valid record: the
+//! records in the remainder of the file are lost, but the rest of the buffer stays readable.
+
+use std::{
+    fs::{self, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+};
+
+use fslock::LockFile;
+use rkyv::AlignedVec;
+use snafu::{ResultExt, Snafu};
+
+use super::{
+    common::create_crc32c_hasher,
+    get_disk_v2_data_dir_path,
+    ledger::LedgerState,
+    record::{validate_record_archive, RecordStatus},
+    ser::try_as_archive,
+};
+
+const DATA_FILE_PREFIX: &str = "buffer-data-";
+const DATA_FILE_SUFFIX: &str = ".dat";
+
+/// Error that occurred while inspecting or repairing a disk buffer.
+#[derive(Debug, Snafu)]
+pub enum InspectError {
+    /// There is no disk buffer with the given ID.
+    #[snafu(display("no disk buffer found at {}", path.display()))]
+    NotFound { path: PathBuf },
+
+    /// The buffer is in use by a running Vector process.
+    #[snafu(display(
+        "failed to lock buffer.lock; is another Vector process running and using this buffer?"
+    ))]
+    Locked,
+
+    /// A general I/O error occurred.
+    #[snafu(display("I/O error on {}: {}", path.display(), source))]
+    Io { path: PathBuf, source: io::Error },
+}
+
+/// State of the buffer as recorded in its ledger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LedgerInspection {
+    /// Next record ID to use when writing a record.
+    pub writer_next_record_id: u64,
+    /// The current data file ID being written to.
+    pub writer_current_data_file_id: u16,
+    /// The current data file ID being read from.
+    pub reader_current_data_file_id: u16,
+    /// The last record ID read by the reader.
+    pub reader_last_record_id: u64,
+}
+
+impl LedgerInspection {
+    /// The number of unacknowledged events in the buffer.
+    pub fn unread_events(&self) -> u64 {
+        self.writer_next_record_id
+            .wrapping_sub(self.reader_last_record_id)
+            .saturating_sub(1)
+    }
+}
+
+/// Damage found in a data file, ending its readable part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DataFileDamage {
+    /// The file ends in the middle of a record.
+    PartialWrite { offset: u64 },
+
+    /// A record was read in full, but its checksum doesn't match its contents.
+    Corrupted {
+        offset: u64,
+        calculated: u32,
+        actual: u32,
+    },
+
+    /// A record could not be deserialized.
+    Malformed { offset: u64, reason: String },
+}
+
+impl DataFileDamage {
+    /// The offset of the first byte of the damaged record.
+    pub fn offset(&self) -> u64 {
+        match self {
+            Self::PartialWrite { offset }
+            | Self::Corrupted { offset, .. }
+            | Self::Malformed { offset, .. } => *offset,
+        }
+    }
+}
+
+/// Contents of a single data file of a disk buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataFileInspection {
+    pub path: PathBuf,
+    pub file_id: u16,
+    /// The size of the file, in bytes.
+    pub size: u64,
+    /// The number of valid records in the file, up to the first damaged one.
+    pub records: u64,
+    /// The ID of the first valid record in the file.
+    pub first_record_id: Option<u64>,
+    /// The ID of the last valid record in the file.
+    pub last_record_id: Option<u64>,
+    /// The damage that ends the readable part of the file, if any.
+    pub damage: Option<DataFileDamage>,
+}
+
+impl DataFileInspection {
+    /// The length of the readable part of the file, which a repair truncates the file to.
+    pub fn valid_len(&self) -> u64 {
+        self.damage
+            .as_ref()
+            .map_or(self.size, DataFileDamage::offset)
+    }
+}
+
+/// Contents of a disk buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BufferInspection {
+    pub path: PathBuf,
+    /// The ledger state, if the ledger exists and could be read.
+    pub ledger: Option<LedgerInspection>,
+    /// The data files, ordered by file ID.
+    pub data_files: Vec<DataFileInspection>,
+}
+
+impl BufferInspection {
+    /// Whether any of the data files is damaged.
+    pub fn is_damaged(&self) -> bool {
+        self.data_files.iter().any(|file| file.damage.is_some())
+    }
+}
+
+/// A data file truncated by [`repair_buffer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepairedDataFile {
+    pub path: PathBuf,
+    /// The number of bytes removed from the end of the file.
+    pub truncated_bytes: u64,
+}
+
+/// Lists the IDs of the disk buffers in the given data directory, which are the IDs of the sinks
+/// they belong to.
+///
+/// # Errors
+///
+/// If the buffer directory exists but can't be read, an error variant will be returned.
+pub fn list_buffers(data_dir: &Path) -> Result<Vec<String>, InspectError> {
+    let path = get_disk_v2_data_dir_path(data_dir, "");
+    let entries = match fs::read_dir(&path) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(InspectError::Io { path, source }),
+    };
+
+    let mut ids = Vec::new();
+    for entry in entries {
+        let entry = entry.context(IoSnafu { path: &path })?;
+        if entry.path().is_dir() {
+            ids.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
+
+/// Reads the ledger and all data files of the disk buffer with the given ID.
+///
+/// # Errors
+///
+/// If the buffer doesn't exist, or any of its files can't be read, an error variant will be
+/// returned.
+pub fn inspect_buffer(data_dir: &Path, id: &str) -> Result<BufferInspection, InspectError> {
+    let path = get_disk_v2_data_dir_path(data_dir, id);
+    if !path.is_dir() {
+        return Err(InspectError::NotFound { path });
+    }
+
+    let ledger = read_ledger(&path.join("buffer.db"))?;
+
+    let mut data_files = Vec::new();
+    for entry in fs::read_dir(&path).context(IoSnafu { path: &path })? {
+        let entry = entry.context(IoSnafu { path: &path })?;
+        if let Some(file_id) = parse_data_file_id(&entry.file_name().to_string_lossy()) {
+            data_files.push(inspect_data_file(entry.path(), file_id)?);
+        }
+    }
+    data_files.sort_by_key(|file| file.file_id);
+
+    Ok(BufferInspection {
+        path,
+        ledger,
+        data_files,
+    })
+}
+
+/// Truncates every damaged data file of the disk buffer with the given ID to its last valid
+/// record.
+///
+/// The buffer lock is held during the repair, so that a buffer in use by Vector is never modified.
+///
+/// # Errors
+///
+/// If the buffer doesn't exist, is locked, or any of its files can't be read or truncated, an
+/// error variant will be returned.
+pub fn repair_buffer(data_dir: &Path, id: &str) -> Result<Vec<RepairedDataFile>, InspectError> {
+    let path = get_disk_v2_data_dir_path(data_dir, id);
+    if !path.is_dir() {
+        return Err(InspectError::NotFound { path });
+    }
+
+    let lock_path = path.join("buffer.lock");
+    let mut lock = LockFile::open(&lock_path).context(IoSnafu { path: &lock_path })?;
+    if !lock.try_lock().context(IoSnafu { path: &lock_path })? {
+        return Err(InspectError::Locked);
+    }
+
+    let mut repaired = Vec::new();
+    for file in inspect_buffer(data_dir, id)?.data_files {
+        if file.damage.is_none() {
+            continue;
+        }
+
+        let valid_len = file.valid_len();
+        let handle = OpenOptions::new()
+            .write(true)
+            .open(&file.path)
+            .context(IoSnafu { path: &file.path })?;
+        handle
+            .set_len(valid_len)
+            .and_then(|_| handle.sync_all())
+            .context(IoSnafu { path: &file.path })?;
+
+        repaired.push(RepairedDataFile {
+            truncated_bytes: file.size - valid_len,
+            path: file.path,
+        });
+    }
+    Ok(repaired)
+}
+
+fn parse_data_file_id(name: &str) -> Option<u16> {
+    name.strip_prefix(DATA_FILE_PREFIX)?
+        .strip_suffix(DATA_FILE_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+fn read_ledger(path: &Path) -> Result<Option<LedgerInspection>, InspectError> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) if !bytes.is_empty() => bytes,
+        Ok(_) => return Ok(None),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(source) => {
+            return Err(InspectError::Io {
+                path: path.to_owned(),
+                source,
+            })
+        }
+    };
+
+    let mut buf = AlignedVec::new();
+    buf.extend_from_slice(&bytes);
+    Ok(try_as_archive::<LedgerState>(&buf)
+        .ok()
+        .map(|state| LedgerInspection {
+            writer_next_record_id: state.get_next_writer_record_id(),
+            writer_current_data_file_id: state.get_current_writer_file_id(),
+            reader_current_data_file_id: state.get_current_reader_file_id(),
+            reader_last_record_id: state.get_last_reader_record_id(),
+        }))
+}
+
+fn inspect_data_file(path: PathBuf, file_id: u16) -> Result<DataFileInspection, InspectError> {
+    let data = fs::read(&path).context(IoSnafu { path: &path })?;
+    let (records, first_record_id, last_record_id, damage) = scan_records(&data);
+
+    Ok(DataFileInspection {
+        path,
+        file_id,
+        size: data.len() as u64,
+        records,
+        first_record_id,
+        last_record_id,
+        damage,
+    })
+}
+
+/// Reads the length-delimited records of a data file until its end or the first damaged record.
+fn scan_records(data: &[u8]) -> (u64, Option<u64>, Option<u64>, Option<DataFileDamage>) {
+    let checksummer = create_crc32c_hasher();
+    let mut buf = AlignedVec::new();
+    let (mut records, mut first_record_id, mut last_record_id) = (0, None, None);
+
+    let mut offset = 0;
+    while offset < data.len() {
+        let record_offset = offset as u64;
+        let remaining = &data[offset..];
+        if remaining.len() < 8 {
+            let damage = DataFileDamage::PartialWrite {
+                offset: record_offset,
+            };
+            return (records, first_record_id, last_record_id, Some(damage));
+        }
+
+        let length = u64::from_be_bytes(remaining[..8].try_into().expect("slice is 8 bytes"));
+        let record_len = usize::try_from(length).unwrap_or(usize::MAX);
+        if record_len == 0 {
+            let damage = DataFileDamage::Malformed {
+                offset: record_offset,
+                reason: "record length was zero".to_string(),
+            };
+            return (records, first_record_id, last_record_id, Some(damage));
+        }
+        if remaining.len() - 8 < record_len {
+            let damage = DataFileDamage::PartialWrite {
+                offset: record_offset,
+            };
+            return (records, first_record_id, last_record_id, Some(damage));
+        }
+
+        buf.clear();
+        buf.extend_from_slice(&remaining[8..8 + record_len]);
+        match validate_record_archive(buf.as_slice(), &checksummer) {
+            RecordStatus::Valid { id, .. } => {
+                records += 1;
+                first_record_id.get_or_insert(id);
+                last_record_id = Some(id);
+                offset += 8 + record_len;
+            }
+            RecordStatus::Corrupted { calculated, actual } => {
+                let damage = DataFileDamage::Corrupted {
+                    offset: record_offset,
+                    calculated,
+                    actual,
+                };
+                return (records, first_record_id, last_record_id, Some(damage));
+            }
+            RecordStatus::FailedDeserialization(error) => {
+                let damage = DataFileDamage::Malformed {
+                    offset: record_offset,
+                    reason: error.into_inner(),
+                };
+                return (records, first_record_id, last_record_id, Some(damage));
+            }
+        }
+    }
+
+    (records, first_record_id, last_record_id, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{
+            backed_archive::BackedArchive,
+            record::Record,
+            tests::{create_default_buffer_v2, read_next_some},
+        },
+        *,
+    };
+    use crate::test::{with_temp_dir, SizedRecord};
+
+    #[test]
+    fn parses_data_file_ids() {
+        assert_eq!(parse_data_file_id("buffer-data-0.dat"), Some(0));
+        assert_eq!(parse_data_file_id("buffer-data-65535.dat"), Some(65535));
+        assert_eq!(parse_data_file_id("buffer.db"), None);
+        assert_eq!(parse_data_file_id("buffer-data-x.dat"), None);
+    }
+
+    #[test]
+    fn detects_partial_length_delimiter() {
+        let (records, _, _, damage) = scan_records(&[0, 0, 0]);
+        assert_eq!(records, 0);
+        assert_eq!(damage, Some(DataFileDamage::PartialWrite { offset: 0 }));
+    }
+
+    #[test]
+    fn detects_partial_record() {
+        let mut data = 64u64.to_be_bytes().to_vec();
+        data.extend_from_slice(&[0; 16]);
+        let (_, _, _, damage) = scan_records(&data);
+        assert_eq!(damage, Some(DataFileDamage::PartialWrite { offset: 0 }));
+    }
+
+    #[test]
+    fn empty_file_is_intact() {
+        assert_eq!(scan_records(&[]), (0, None, None, None));
+    }
+
+    #[tokio::test]
+    async fn repaired_buffer_is_readable_past_corrupted_record() {
+        with_temp_dir(|dir| {
+            let data_dir = dir.to_path_buf();
+            let buffer_dir = get_disk_v2_data_dir_path(&data_dir, "sink");
+
+            async move {
+                let (mut writer, _, ledger) = create_default_buffer_v2(buffer_dir.clone()).await;
+                let first_bytes_written = writer
+                    .write_record(SizedRecord::new(64))
+                    .await
+                    .expect("write should not fail");
+                let second_bytes_written = writer
+                    .write_record(SizedRecord::new(65))
+                    .await
+                    .expect("write should not fail");
+                writer.flush().await.expect("flush should not fail");
+
+                let data_file_path = ledger.get_current_writer_data_file_path();
+                drop(writer);
+                drop(ledger);
+
+                // Flip a bit in the checksum of the second record, leaving the record itself
+                // deserializable, as if the bit had flipped on disk.
+                let mut data = fs::read(&data_file_path).expect("read should not fail");
+                let record_start = first_bytes_written + 8;
+                let mut record = AlignedVec::new();
+                record.extend_from_slice(&data[record_start..]);
+                let mut backed_record = BackedArchive::<_, Record>::from_backing(record)
+                    .expect("archive should not fail");
+                {
+                    let archived = backed_record.get_archive_mut();
+                    let checksum =
+                        unsafe { archived.map_unchecked_mut(|record| &mut record.checksum) };
+                    *checksum.get_mut() ^= 1 << 15;
+                }
+                data[record_start..].copy_from_slice(backed_record.get_backing_ref());
+                fs::write(&data_file_path, &data).expect("write should not fail");
+
+                let inspection =
+                    inspect_buffer(&data_dir, "sink").expect("inspect should not fail");
+                assert!(matches!(
+                    inspection.data_files[0].damage,
+                    Some(DataFileDamage::Corrupted { offset, .. })
+                        if offset == first_bytes_written as u64
+                ));
+
+                let repaired = repair_buffer(&data_dir, "sink").expect("repair should not fail");
+                assert_eq!(repaired.len(), 1);
+                assert_eq!(repaired[0].truncated_bytes, second_bytes_written as u64);
+                let inspection =
+                    inspect_buffer(&data_dir, "sink").expect("inspect should not fail");
+                assert!(!inspection.is_damaged());
+                assert_eq!(inspection.data_files[0].records, 1);
+
+                // The writer rolls over to a new data file, as the ledger is ahead of the last
+                // record left in the old one, and the reader moves on to it without an error.
+                let (mut writer, mut reader, _) =
+                    create_default_buffer_v2::<_, SizedRecord>(buffer_dir).await;
+                writer
+                    .write_record(SizedRecord::new(66))
+                    .await
+                    .expect("write should not fail");
+                writer.flush().await.expect("flush should not fail");
+
+                assert_eq!(read_next_some(&mut reader).await, SizedRecord::new(64));
+                assert_eq!(read_next_some(&mut reader).await, SizedRecord::new(66));
+            }
+        })
+        .await;
+    }
+}
@@ -1,6 +1,7 @@
-use std::{io, path::Path};
+use std::{io, panic, path::Path};
 
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use tokio::io::{AsyncRead, AsyncWrite};
 
 /// File metadata.
@@ -15,7 +16,8 @@ impl Metadata {
     }
 }
 
-/// Generalized interface for opening and deleting files from a filesystem.
+/// Generalized interface for opening and deleting files from a filesystem, and for running the
+/// blocking work that goes with reading them.
 #[async_trait]
 pub trait Filesystem: Send + Sync {
     type File: AsyncFile;
@@ -81,6 +83,26 @@ pub trait Filesystem: Send + Sync {
     /// If an I/O error occurred when attempting to delete the file, an error variant will be
     /// returned describing the underlying error.
     async fn delete_file(&self, path: &Path) -> io::Result<()>;
+
+    /// Runs CPU-bound work, such as decoding records, without blocking the asynchronous runtime.
+    ///
+    /// By default, the work runs on a blocking thread, and any panic is resumed when the returned
+    /// future is polled.
+    fn spawn_blocking<F, R>(&self, work: F) -> BoxFuture<'static, R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        Box::pin(async move {
+            match tokio::task::spawn_blocking(work).await {
+                Ok(result) => result,
+                Err(e) => match e.try_into_panic() {
+                    Ok(payload) => panic::resume_unwind(payload),
+                    Err(e) => panic!("blocking task failed: {}", e),
+                },
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -150,6 +150,7 @@ use vector_common::finalization::Finalizable;
 
 mod backed_archive;
 mod common;
+pub mod inspect;
 mod io;
 mod ledger;
 mod reader;
@@ -1,13 +1,17 @@
 use std::{
-    cmp, fmt,
+    cmp,
+    collections::VecDeque,
+    fmt,
     io::{self, ErrorKind},
     marker::PhantomData,
+    mem,
     num::NonZeroU64,
     path::PathBuf,
     sync::Arc,
 };
 
 use crc32fast::Hasher;
+use futures::future::BoxFuture;
 use rkyv::{archived_root, AlignedVec};
 use snafu::{ResultExt, Snafu};
 use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
@@ -194,6 +198,52 @@ impl<T: Bufferable> PartialEq for ReaderError<T> {
     }
 }
 
+/// A record taken out of a [`RecordReader`] after being validated, so that it can be decoded
+/// elsewhere.
+pub(super) struct ValidatedRecord {
+    buf: AlignedVec,
+}
+
+impl ValidatedRecord {
+    /// Decodes the record.
+    ///
+    /// # Errors
+    ///
+    /// If an error occurs during decoding, an error variant will be returned describing the error.
+    pub fn decode<T: Bufferable>(&self) -> Result<T, ReaderError<T>> {
+        // SAFETY:
+        // - `RecordReader::take_record` is the only method that can create a `ValidatedRecord`
+        // - it requires a `ReadToken`, which we only get if there's a valid record in its buffer
+        // - `try_next_record` does all the archive checks, checksum validation, etc
+        let record = unsafe { archived_root::<Record<'_>>(&self.buf) };
+
+        decode_record_payload(record)
+    }
+}
+
+/// Maximum number of records decoded together by a single read-ahead decoder.
+///
+/// Decoding a record is usually far cheaper than handing it off to a blocking thread, so records are
+/// decoded in batches to amortize the cost of the handoff.
+const READ_AHEAD_BATCH_RECORDS: usize = 32;
+
+/// The ID and size of a record read ahead of the caller.
+type RecordInfo = (u64, u64);
+
+/// Records read ahead of the caller, which are being decoded together on a blocking thread.
+struct PendingBatch<T: Bufferable> {
+    records: Vec<RecordInfo>,
+    decoded: BoxFuture<'static, Vec<Result<T, ReaderError<T>>>>,
+}
+
+impl<T: Bufferable> fmt::Debug for PendingBatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingBatch")
+            .field("records", &self.records)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Buffered reader that handles deserialization, checksumming, and decoding of records.
 pub(super) struct RecordReader<R, T> {
     reader: BufReader<R>,
@@ -378,6 +428,24 @@ where
 
         decode_record_payload(record)
     }
+
+    /// Takes the record associated with the given [`ReadToken`] out of the reader, without
+    /// decoding it.
+    ///
+    /// # Panics
+    ///
+    /// Like [`read_record`], this method will panic if the `ReadToken` has expired.
+    pub fn take_record(&mut self, token: ReadToken) -> ValidatedRecord {
+        let record_id = token.into_record_id();
+        assert_eq!(
+            self.current_record_id, record_id,
+            "using expired read token; this is a serious bug"
+        );
+
+        ValidatedRecord {
+            buf: mem::replace(&mut self.aligned_buf, AlignedVec::new()),
+        }
+    }
 }
 
 impl<R, T> fmt::Debug for RecordReader<R, T>
@@ -411,6 +479,10 @@ where
     record_acks: OrderedAcknowledgements<u64, u64>,
     data_file_acks: OrderedAcknowledgements<u64, (PathBuf, u64)>,
     finalizer: OrderedFinalizer<u64>,
+    read_ahead_decoders: usize,
+    read_ahead: VecDeque<PendingBatch<T>>,
+    read_ahead_decoded: VecDeque<(RecordInfo, Result<T, ReaderError<T>>)>,
+    read_ahead_error: Option<ReaderError<T>>,
     _t: PhantomData<T>,
 }
 
@@ -424,6 +496,7 @@ where
     pub(crate) fn new(ledger: Arc<Ledger<FS>>, finalizer: OrderedFinalizer<u64>) -> Self {
         let ledger_last_reader_record_id = ledger.state().get_last_reader_record_id();
         let next_expected_record_id = ledger_last_reader_record_id.wrapping_add(1);
+        let read_ahead_decoders = ledger.config().read_ahead_decoders;
 
         Self {
             ledger,
@@ -437,6 +510,10 @@ where
             record_acks: OrderedAcknowledgements::from_acked(next_expected_record_id),
             data_file_acks: OrderedAcknowledgements::from_acked(0),
             finalizer,
+            read_ahead_decoders,
+            read_ahead: VecDeque::new(),
+            read_ahead_decoded: VecDeque::new(),
+            read_ahead_error: None,
             _t: PhantomData,
         }
     }
@@ -932,9 +1009,56 @@ where
     /// the error.
     #[cfg_attr(test, instrument(skip(self), level = "trace"))]
     pub async fn next(&mut self) -> Result<Option<T>, ReaderError<T>> {
+        // Hand off any records we've already read ahead, in order, before reading anything else.
+        // We only stop reading ahead when we hit the end of the data file or an error, so once the
+        // read-ahead records are drained, we pick up where we left off.
+        if let Some(result) = self.next_read_ahead().await {
+            return result.map(Some);
+        }
+
+        if let Some(e) = self.read_ahead_error.take() {
+            if e.is_bad_read() {
+                self.roll_to_next_data_file();
+            }
+
+            return Err(e);
+        }
+
+        let token = match self.next_token().await? {
+            Some(token) => token,
+            None => return Ok(None),
+        };
+
+        if self.should_read_ahead() {
+            self.fill_read_ahead(token).await;
+            return self
+                .next_read_ahead()
+                .await
+                .expect("read-ahead should contain at least the record just read")
+                .map(Some);
+        }
+
+        // We got a read token, so our record is present in the reader, and now we can actually read
+        // it out and return it.
+        let record_id = token.record_id();
+        let record_bytes = token.record_bytes() as u64;
+
+        let reader = self
+            .reader
+            .as_mut()
+            .expect("reader should exist after `ensure_ready_for_read`");
+        let record = reader.read_record(token)?;
+
+        self.finish_read(record_id, record_bytes, record).map(Some)
+    }
+
+    /// Waits until a record can be read, and returns the token to read it.
+    ///
+    /// If the writer is closed and there is no more data in the buffer, `None` is returned.
+    async fn next_token(&mut self) -> Result<Option<ReadToken>, ReaderError<T>> {
         let mut force_check_pending_data_files = false;
 
-        let token = loop {
+        loop {
             // Handle any pending acknowledgements first.
             self.handle_pending_acknowledgements(force_check_pending_data_files)
                 .await
@@ -981,7 +1105,7 @@ where
                 // writer to signal us that there's some actual data to read.
                 Ok(None) => {}
                 // We got a valid record, so keep the token.
-                Ok(Some(token)) => break token,
+                Ok(Some(token)) => return Ok(Some(token)),
                 // A length-delimited payload was read, but we failed to deserialize it as a valid
                 // record, or we deseralized it and the checksum was invalid.  Either way, we're not
                 // sure the rest of the data file is even valid, so roll to the next file.
@@ -1062,19 +1186,95 @@ where
                     return Ok(None);
                 }
             }
-        };
+        }
+    }
 
-        // We got a read token, so our record is present in the reader, and now we can actually read
-        // it out and return it.
-        let record_id = token.record_id();
-        let record_bytes = token.record_bytes() as u64;
+    /// Whether or not records should be decoded ahead of the caller.
+    ///
+    /// We only read ahead once we're synchronized with the ledger, and only in data files that the
+    /// writer is done with: these are exactly the files that build up during a long outage of the
+    /// sink, and reading ahead in them can never run into a record that hasn't been fully written.
+    fn should_read_ahead(&self) -> bool {
+        let (reader_file_id, writer_file_id) = self.ledger.get_current_reader_writer_file_id();
+        self.read_ahead_decoders > 1 && self.ready_to_read && reader_file_id != writer_file_id
+    }
 
+    /// Reads records ahead of the caller, starting with the record for the given token, and decodes
+    /// them in parallel on blocking threads.
+    ///
+    /// Records are split into batches of up to `READ_AHEAD_BATCH_RECORDS`, each decoded by a single
+    /// blocking task, with up to `read_ahead_decoders` batches in flight.
+    ///
+    /// Reading ahead stops at the end of the current data file, as rolling to the next data file
+    /// must only happen once all of its records have been handed off.  If an error is encountered,
+    /// it is held until the records read before it have been handed off.
+    async fn fill_read_ahead(&mut self, token: ReadToken) {
+        let filesystem = self.ledger.filesystem();
         let reader = self
             .reader
             .as_mut()
             .expect("reader should exist after `ensure_ready_for_read`");
-        let mut record = reader.read_record(token)?;
 
+        let mut next_token = Some(token);
+        let mut exhausted = false;
+        while !exhausted && self.read_ahead.len() < self.read_ahead_decoders {
+            let mut records = Vec::with_capacity(READ_AHEAD_BATCH_RECORDS);
+            let mut batch = Vec::with_capacity(READ_AHEAD_BATCH_RECORDS);
+            while batch.len() < READ_AHEAD_BATCH_RECORDS {
+                let token = match next_token.take() {
+                    Some(token) => token,
+                    None => match reader.try_next_record(true).await {
+                        Ok(Some(token)) => token,
+                        Ok(None) => {
+                            exhausted = true;
+                            break;
+                        }
+                        Err(e) => {
+                            self.read_ahead_error = Some(e);
+                            exhausted = true;
+                            break;
+                        }
+                    },
+                };
+
+                records.push((token.record_id(), token.record_bytes() as u64));
+                batch.push(reader.take_record(token));
+            }
+
+            if batch.is_empty() {
+                break;
+            }
+
+            self.read_ahead.push_back(PendingBatch {
+                records,
+                decoded: filesystem.spawn_blocking(move || {
+                    batch.iter().map(ValidatedRecord::decode::<T>).collect()
+                }),
+            });
+        }
+    }
+
+    /// Hands off the next record read ahead, waiting for its batch to be decoded if need be.
+    ///
+    /// Returns `None` if no records were read ahead.
+    async fn next_read_ahead(&mut self) -> Option<Result<T, ReaderError<T>>> {
+        if self.read_ahead_decoded.is_empty() {
+            let pending = self.read_ahead.pop_front()?;
+            let decoded = pending.decoded.await;
+            self.read_ahead_decoded.extend(pending.records.into_iter().zip(decoded));
+        }
+
+        let ((record_id, record_bytes), decoded) = self.read_ahead_decoded.pop_front()?;
+        Some(decoded.and_then(|record| self.finish_read(record_id, record_bytes, record)))
+    }
+
+    /// Tracks a decoded record as read, and attaches the finalizer used to acknowledge it.
+    fn finish_read(
+        &mut self,
+        record_id: u64,
+        record_bytes: u64,
+        mut record: T,
+    ) -> Result<T, ReaderError<T>> {
         let record_events: u64 = record
             .event_count()
             .try_into()
@@ -1098,7 +1298,7 @@ where
             );
         }
 
-        Ok(Some(record))
+        Ok(record)
     }
 }
 
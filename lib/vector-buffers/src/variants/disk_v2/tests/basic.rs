@@ -5,7 +5,10 @@ use tokio_test::{assert_pending, assert_ready, task::spawn};
 use tracing::Instrument;
 use vector_common::finalization::Finalizable;
 
-use super::{create_default_buffer_v2, read_next, read_next_some};
+use super::{
+    create_buffer_v2_with_max_data_file_size, create_default_buffer_v2,
+    get_minimum_data_file_size_for_record_payload, read_next, read_next_some,
+};
 use crate::{
     assert_buffer_is_empty, assert_buffer_records,
     test::{acknowledge, install_tracing_helpers, with_temp_dir, MultiEventRecord, SizedRecord},
@@ -232,3 +235,45 @@ async fn initial_size_correct_with_multievents() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn read_ahead_hands_off_records_in_order() {
+    let _a = install_tracing_helpers();
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            // Size our data files so that each one holds a handful of records, which gives the
+            // reader several data files that the writer is done with, and that it can read ahead in.
+            let largest_record = SizedRecord::new(139);
+            let max_data_file_size =
+                get_minimum_data_file_size_for_record_payload(&largest_record) * 4;
+            let (mut writer, mut reader, ledger) =
+                create_buffer_v2_with_max_data_file_size(data_dir, max_data_file_size).await;
+
+            // Give every record a different size so we can tell them apart when reading them back.
+            let input_sizes = (120..140).collect::<Vec<_>>();
+            for size in input_sizes.iter().copied() {
+                writer
+                    .write_record(SizedRecord::new(size))
+                    .await
+                    .expect("write should not fail");
+            }
+            writer.flush().await.expect("flush should not fail");
+            writer.close();
+
+            let (reader_file_id, writer_file_id) = ledger.get_current_reader_writer_file_id();
+            assert!(writer_file_id > reader_file_id + 1);
+
+            let mut sizes_read = Vec::new();
+            while let Some(record) = read_next(&mut reader).await {
+                sizes_read.push(record.0);
+                acknowledge(record).await;
+            }
+
+            assert_eq!(input_sizes, sizes_read);
+            assert_buffer_is_empty!(ledger);
+        }
+    })
+    .await;
+}
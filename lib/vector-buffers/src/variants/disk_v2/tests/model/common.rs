@@ -33,8 +33,8 @@ pub enum Progress {
 }
 
 pub fn arb_buffer_config() -> impl Strategy<Value = DiskBufferConfig<TestFilesystem>> {
-    any::<(u16, u16, u16)>()
-        .prop_map(|(n1, n2, n3)| {
+    any::<(u16, u16, u16, u8)>()
+        .prop_map(|(n1, n2, n3, n4)| {
             let max_buffer_size = u64::from(n1) * 64;
             let max_data_file_size = u64::from(n2) * 2;
             let max_record_size = n3
@@ -54,6 +54,9 @@ pub fn arb_buffer_config() -> impl Strategy<Value = DiskBufferConfig<TestFilesys
                 // readers to make progress, and we're not testing anything about whether or not the
                 // ledger makes it to disk durably.
                 .flush_interval(Duration::from_secs(10))
+                // Covers reading without read-ahead, as well as with a few decoders.  The test
+                // filesystem decodes read-ahead records inline, so reads stay deterministic.
+                .read_ahead_decoders(usize::from(n4 % 4) + 1)
                 .filesystem(TestFilesystem::default())
         })
         .prop_filter_map(
@@ -9,6 +9,7 @@ use std::{
 };
 
 use async_trait::async_trait;
+use futures::future::{self, BoxFuture};
 use parking_lot::Mutex;
 use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
@@ -356,4 +357,14 @@ impl Filesystem for TestFilesystem {
             Err(io_err_not_found())
         }
     }
+
+    /// Runs the work inline, so that whether or not a read completes on a given poll depends only
+    /// on the model, and not on the scheduling of blocking threads.
+    fn spawn_blocking<F, R>(&self, work: F) -> BoxFuture<'static, R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        Box::pin(future::ready(work()))
+    }
 }
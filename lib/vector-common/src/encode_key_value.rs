@@ -27,6 +27,17 @@ impl Error for EncodingError {
     }
 }
 
+/// How keys and values are quoted and escaped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Escaping {
+    /// Values containing whitespace or quotes are quoted.
+    Default,
+    /// Follows the logfmt format as parsed by `go-logfmt`, and so by Grafana Loki: values
+    /// containing spaces, `=`, quotes or control characters, as well as empty values, are quoted
+    /// with Go escape sequences, and such characters are replaced by `_` in keys.
+    Strict,
+}
+
 /// Encodes input to key value format with specified
 /// delimiters in field order where unspecified fields
 /// will follow after them. `Flattens_boolean` values
@@ -41,37 +52,72 @@ pub fn to_string<V: Serialize>(
     key_value_delimiter: &str,
     field_delimiter: &str,
     flatten_boolean: bool,
+) -> Result<String, EncodingError> {
+    to_string_with_options(
+        input,
+        fields_order,
+        key_value_delimiter,
+        field_delimiter,
+        flatten_boolean,
+        ".",
+        Escaping::Default,
+    )
+}
+
+/// Encodes input like `to_string`, joining the keys of nested fields with `flatten_separator`
+/// and escaping keys and values according to `escaping`.
+///
+/// A field of `fields_order` naming an object places all of its nested fields, sorted by key.
+///
+/// # Errors
+///
+/// Returns an `EncodingError` if the input contains non-`String` map keys.
+pub fn to_string_with_options<V: Serialize>(
+    input: &BTreeMap<String, V>,
+    fields_order: &[String],
+    key_value_delimiter: &str,
+    field_delimiter: &str,
+    flatten_boolean: bool,
+    flatten_separator: &str,
+    escaping: Escaping,
 ) -> Result<String, EncodingError> {
     let mut output = String::new();
 
-    let mut input = flatten(input, '.')?;
+    let mut input = flatten(input, flatten_separator)?;
 
-    for field in fields_order.iter() {
-        match (input.remove(field), flatten_boolean) {
-            (Some(Data::Boolean(false)), true) | (None, _) => (),
-            (Some(Data::Boolean(true)), true) => {
-                encode_string(&mut output, field);
-                output.push_str(field_delimiter);
-            }
-            (Some(value), _) => {
-                encode_field(&mut output, field, &value.to_string(), key_value_delimiter);
-                output.push_str(field_delimiter);
+    let encode = |output: &mut String, key: &str, value: &Data| {
+        match (value, flatten_boolean) {
+            (Data::Boolean(false), true) => return,
+            (Data::Boolean(true), true) => encode_key(output, key, escaping),
+            (_, _) => {
+                encode_key(output, key, escaping);
+                output.push_str(key_value_delimiter);
+                encode_value(output, &value.to_string(), escaping);
             }
         };
+        output.push_str(field_delimiter);
+    };
+
+    for field in fields_order.iter() {
+        if let Some(value) = input.remove(field) {
+            encode(&mut output, field, &value);
+        }
+
+        let prefix = format!("{}{}", field, flatten_separator);
+        let nested = input
+            .range(prefix.clone()..)
+            .map(|(key, _)| key)
+            .take_while(|key| key.starts_with(&prefix))
+            .cloned()
+            .collect::<Vec<_>>();
+        for key in nested {
+            let value = input.remove(&key).expect("key exists");
+            encode(&mut output, &key, &value);
+        }
     }
 
     for (key, value) in &input {
-        match (value, flatten_boolean) {
-            (Data::Boolean(false), true) => (),
-            (Data::Boolean(true), true) => {
-                encode_string(&mut output, key);
-                output.push_str(field_delimiter);
-            }
-            (_, _) => {
-                encode_field(&mut output, key, &value.to_string(), key_value_delimiter);
-                output.push_str(field_delimiter);
-            }
-        };
+        encode(&mut output, key, value);
     }
 
     if output.ends_with(field_delimiter) {
@@ -83,7 +129,7 @@ pub fn to_string<V: Serialize>(
 
 fn flatten<'a>(
     input: impl IntoIterator<Item = (&'a String, impl Serialize)> + 'a,
-    separator: char,
+    separator: &str,
 ) -> Result<BTreeMap<String, Data>, EncodingError> {
     let mut map = BTreeMap::new();
     for (key, value) in input {
@@ -92,10 +138,24 @@ fn flatten<'a>(
     Ok(map)
 }
 
-fn encode_field<'a>(output: &mut String, key: &str, value: &str, key_value_delimiter: &'a str) {
-    encode_string(output, key);
-    output.push_str(key_value_delimiter);
-    encode_string(output, value);
+fn encode_key(output: &mut String, key: &str, escaping: Escaping) {
+    match escaping {
+        Escaping::Default => encode_string(output, key),
+        Escaping::Strict if key.is_empty() => output.push('_'),
+        Escaping::Strict => {
+            output.extend(
+                key.chars()
+                    .map(|c| if needs_strict_quoting(c) { '_' } else { c }),
+            )
+        }
+    }
+}
+
+fn encode_value(output: &mut String, value: &str, escaping: Escaping) {
+    match escaping {
+        Escaping::Default => encode_string(output, value),
+        Escaping::Strict => encode_string_strict(output, value),
+    }
 }
 
 fn encode_string(output: &mut String, str: &str) {
@@ -119,6 +179,33 @@ fn encode_string(output: &mut String, str: &str) {
     }
 }
 
+fn needs_strict_quoting(c: char) -> bool {
+    c <= ' ' || c == '=' || c == '"' || c.is_control()
+}
+
+fn encode_string_strict(output: &mut String, str: &str) {
+    if !str.is_empty() && !str.chars().any(needs_strict_quoting) {
+        output.push_str(str);
+        return;
+    }
+
+    output.push('"');
+    for c in str.chars() {
+        match c {
+            '\\' => output.push_str(r#"\\"#),
+            '"' => output.push_str(r#"\""#),
+            '\n' => output.push_str(r#"\n"#),
+            '\r' => output.push_str(r#"\r"#),
+            '\t' => output.push_str(r#"\t"#),
+            c if c.is_control() => {
+                write!(output, "\\u{:04x}", u32::from(c)).expect("writing to a string");
+            }
+            _ => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
 enum Data {
     None,
     Boolean(bool),
@@ -151,12 +238,12 @@ impl fmt::Display for Data {
 
 struct KeyValueSerializer<'a> {
     key: String,
-    separator: char,
+    separator: &'a str,
     output: &'a mut BTreeMap<String, Data>,
 }
 
 impl<'a> KeyValueSerializer<'a> {
-    fn new(key: String, separator: char, output: &'a mut BTreeMap<String, Data>) -> Self {
+    fn new(key: String, separator: &'a str, output: &'a mut BTreeMap<String, Data>) -> Self {
         Self {
             key,
             separator,
@@ -179,7 +266,7 @@ impl<'a> KeyValueSerializer<'a> {
     }
 
     fn descend(mut self, child: impl fmt::Display) -> Self {
-        self.key.push(self.separator);
+        self.key.push_str(self.separator);
         write!(&mut self.key, "{}", child).expect("Shouldn't be reachable.");
         self
     }
@@ -728,6 +815,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nested_fields_ordering_by_parent() {
+        assert_eq!(
+            &to_string_with_options::<Value>(
+                &btreemap! {
+                    "log" => json!({
+                        "file": {
+                            "path": "encode_key_value.rs",
+                            "line": 42
+                        },
+                    }),
+                    "agent" => json!({
+                        "name": "vector",
+                    }),
+                    "event" => "log"
+                },
+                &["log".to_owned(), "event".to_owned()],
+                "=",
+                " ",
+                true,
+                "_",
+                Escaping::Default,
+            )
+            .unwrap(),
+            "log_file_line=42 log_file_path=encode_key_value.rs event=log agent_name=vector"
+        );
+    }
+
+    #[test]
+    fn strict_escaping() {
+        assert_eq!(
+            &to_string_with_options::<Value>(
+                &btreemap! {
+                    "lvl" => "info",
+                    "empty" => "",
+                    "query" => "a=b",
+                    "msg" => "some\nfield\\and \"things\"\u{1}",
+                    "path" => r#"C:\logs"#,
+                    "space key" => "foo"
+                },
+                &[],
+                "=",
+                " ",
+                true,
+                ".",
+                Escaping::Strict,
+            )
+            .unwrap(),
+            r#"empty="" lvl=info msg="some\nfield\\and \"things\"\u0001" path=C:\logs query="a=b" space_key=foo"#
+        );
+    }
+
     #[test]
     fn non_string_keys() {
         #[derive(Serialize)]
@@ -1,8 +1,9 @@
 fn main() {
     println!("cargo:rerun-if-changed=proto/event.proto");
+    println!("cargo:rerun-if-changed=proto/event_v2.proto");
     prost_build::Config::new()
         .btree_map(&["."])
         .bytes(&["raw_bytes"])
-        .compile_protos(&["proto/event.proto"], &["proto/"])
+        .compile_protos(&["proto/event.proto", "proto/event_v2.proto"], &["proto/"])
         .unwrap();
 }
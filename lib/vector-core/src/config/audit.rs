@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Where the audit records of configuration changes are written, besides the internal logs and
+/// metrics.
+///
+/// Every load of the configuration, at startup or on reload, is recorded along with what triggered
+/// it, the components it added, changed or removed, and whether it was applied or rolled back.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AuditConfig {
+    /// The file the records are appended to, one JSON object per line.
+    pub path: PathBuf,
+}
@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Where sources persist their checkpoints, the position they reached in their input, so that
+/// they resume from it after a restart.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckpointStoreConfig {
+    /// One file per checkpoint, in the data directory of each source.
+    File,
+
+    /// A SQLite database shared by all sources.
+    Sqlite {
+        /// The path of the database, created if it doesn't exist.
+        path: PathBuf,
+    },
+
+    /// A Kubernetes ConfigMap shared by all sources, so that checkpoints outlive the pod Vector
+    /// runs in.
+    KubernetesConfigMap {
+        /// The name of the ConfigMap, created if it doesn't exist.
+        name: String,
+
+        /// The namespace of the ConfigMap. Defaults to the namespace Vector runs in.
+        #[serde(default)]
+        namespace: Option<String>,
+
+        /// Identifies the instance of Vector the checkpoints belong to, so that instances sharing
+        /// the ConfigMap don't overwrite each other's checkpoints. Defaults to the node name in
+        /// `VECTOR_SELF_NODE_NAME`, then the pod name in `VECTOR_SELF_POD_NAME`, then the
+        /// hostname.
+        #[serde(default)]
+        identity: Option<String>,
+    },
+}
+
+impl Default for CheckpointStoreConfig {
+    fn default() -> Self {
+        Self::File
+    }
+}
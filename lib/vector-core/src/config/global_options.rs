@@ -3,7 +3,10 @@ use snafu::{ResultExt, Snafu};
 use std::{fs::DirBuilder, path::PathBuf};
 use vector_common::TimeZone;
 
-use super::{proxy::ProxyConfig, AcknowledgementsConfig, LogSchema};
+use super::{
+    proxy::ProxyConfig, AcknowledgementsConfig, AuditConfig, CheckpointStoreConfig, LogSchema,
+    MemoryBudgetConfig, RunAsConfig, TelemetryConfig,
+};
 use crate::serde::bool_or_struct;
 
 #[derive(Debug, Snafu)]
@@ -45,6 +48,16 @@ pub struct GlobalOptions {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub acknowledgements: AcknowledgementsConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub checkpoint_store: CheckpointStoreConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub run_as: Option<RunAsConfig>,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub memory_budget: Option<MemoryBudgetConfig>,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub telemetry: TelemetryConfig,
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub audit: Option<AuditConfig>,
 }
 
 impl GlobalOptions {
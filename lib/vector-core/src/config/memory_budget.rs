@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// A limit on the estimated memory used by the state of the components.
+///
+/// Components keeping state that grows with their input, such as the `reduce`, `dedupe` and
+/// `aggregate` transforms, trim it when the total nears the limit, and sources wait before sending
+/// more events while it is exceeded.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryBudgetConfig {
+    /// The maximum estimated size, in bytes, of the state of all components together.
+    pub max_bytes: usize,
+
+    /// The percentage of `max_bytes` above which components trim their state.
+    #[serde(default = "default_trim_threshold_percent")]
+    pub trim_threshold_percent: u8,
+}
+
+const fn default_trim_threshold_percent() -> u8 {
+    80
+}
+
+impl MemoryBudgetConfig {
+    /// The estimated size, in bytes, above which components trim their state.
+    pub fn trim_threshold_bytes(&self) -> usize {
+        let percent = usize::from(self.trim_threshold_percent.min(100));
+        self.max_bytes / 100 * percent + self.max_bytes % 100 * percent / 100
+    }
+}
@@ -2,15 +2,25 @@ use std::{fmt, num::NonZeroUsize};
 
 use bitmask_enum::bitmask;
 
+mod audit;
+mod checkpoint_store;
 mod global_options;
 mod log_schema;
+mod memory_budget;
 pub mod proxy;
+mod run_as;
+mod telemetry;
 
 use crate::event::LogEvent;
+pub use audit::AuditConfig;
+pub use checkpoint_store::CheckpointStoreConfig;
 pub use global_options::GlobalOptions;
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 use lookup::lookup_v2::Path;
 use lookup::path;
+pub use memory_budget::MemoryBudgetConfig;
+pub use run_as::RunAsConfig;
+pub use telemetry::{TagPolicy, TelemetryConfig, TelemetryTagsConfig};
 use serde::{Deserialize, Serialize};
 use value::Value;
 pub use vector_common::config::ComponentKey;
@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// The identity Vector drops to once its sources have bound their sockets and opened their files.
+///
+/// This lets Vector start as root to listen on privileged ports or read protected files without
+/// processing events as root.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RunAsConfig {
+    /// The user, by name or numeric ID, to switch to.
+    #[serde(default)]
+    pub user: Option<String>,
+
+    /// The group, by name or numeric ID, to switch to. Defaults to the primary group of `user`.
+    #[serde(default)]
+    pub group: Option<String>,
+
+    /// A seccomp profile to apply to the whole process after switching user, in the JSON format
+    /// of `seccompiler`. The filter named `vector` in the profile is used. Linux only.
+    #[serde(default)]
+    pub seccomp_profile: Option<PathBuf>,
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Options for the internal metrics Vector reports about itself.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryConfig {
+    /// How the tags of internal metrics are emitted.
+    #[serde(default)]
+    pub tags: TelemetryTagsConfig,
+}
+
+/// How high-cardinality tags, such as the file a source reads from or the address of a peer, are
+/// emitted on internal metrics.
+///
+/// Those tags create one series per file, peer or partition, which can make internal metrics
+/// dominate the cost of storing metrics downstream.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TelemetryTagsConfig {
+    /// The tags considered high-cardinality.
+    #[serde(default = "default_high_cardinality_tags")]
+    pub high_cardinality: Vec<String>,
+
+    /// The policy applied to high-cardinality tags, for components without one of their own.
+    #[serde(default)]
+    pub policy: TagPolicy,
+
+    /// The policy applied to high-cardinality tags, by component type.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub components: BTreeMap<String, TagPolicy>,
+}
+
+fn default_high_cardinality_tags() -> Vec<String> {
+    [
+        "container_id",
+        "container_name",
+        "file",
+        "partition",
+        "peer_addr",
+        "peer_path",
+        "pod_name",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+impl Default for TelemetryTagsConfig {
+    fn default() -> Self {
+        Self {
+            high_cardinality: default_high_cardinality_tags(),
+            policy: TagPolicy::default(),
+            components: BTreeMap::new(),
+        }
+    }
+}
+
+impl TelemetryTagsConfig {
+    /// The policy applied to the high-cardinality tags of components of the given type.
+    pub fn policy_for(&self, component_type: Option<&str>) -> TagPolicy {
+        component_type
+            .and_then(|component_type| self.components.get(component_type))
+            .copied()
+            .unwrap_or(self.policy)
+    }
+
+    /// Whether every component emits its high-cardinality tags as is.
+    pub fn emits_all(&self) -> bool {
+        self.policy == TagPolicy::Emit
+            && self
+                .components
+                .values()
+                .all(|policy| *policy == TagPolicy::Emit)
+    }
+}
+
+/// What to do with the high-cardinality tags of an internal metric.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagPolicy {
+    /// Emit the metric with the tags.
+    Emit,
+
+    /// Emit the metric without the tags, aggregating the series that only differed by them.
+    ///
+    /// Gauges with any of the tags are dropped, as the values of their series can't be combined.
+    Aggregate,
+
+    /// Don't emit metrics that have any of the tags.
+    Drop,
+}
+
+impl Default for TagPolicy {
+    fn default() -> Self {
+        Self::Emit
+    }
+}
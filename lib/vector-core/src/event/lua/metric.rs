@@ -8,7 +8,7 @@ use crate::{
         metric::{self, MetricSketch},
         Metric, MetricKind, MetricValue, StatisticKind,
     },
-    metrics::AgentDDSketch,
+    metrics::{AgentDDSketch, HyperLogLog},
 };
 
 impl<'a> ToLua<'a> for MetricKind {
@@ -150,6 +150,14 @@ impl<'a> ToLua<'a> for Metric {
                         sketch_tbl.raw_set("n", bin_map.counts)?;
                         sketch_tbl
                     }
+                    MetricSketch::HyperLogLog(hll) => {
+                        let sketch_tbl = lua.create_table()?;
+                        sketch_tbl.raw_set("type", "hyperloglog")?;
+                        sketch_tbl.raw_set("precision", hll.precision())?;
+                        sketch_tbl.raw_set("estimate", hll.estimate())?;
+                        sketch_tbl.raw_set("registers", hll.registers().to_vec())?;
+                        sketch_tbl
+                    }
                 };
 
                 tbl.raw_set("sketch", sketch_tbl)?;
@@ -249,6 +257,20 @@ impl<'a> FromLua<'a> for Metric {
                             ),
                         })?
                 }
+                "hyperloglog" => {
+                    let precision: u8 = sketch.raw_get("precision")?;
+                    let registers: Vec<u8> = sketch.raw_get("registers")?;
+
+                    HyperLogLog::from_raw(precision, registers)
+                        .map(MetricValue::from)
+                        .ok_or(LuaError::FromLuaConversionError {
+                            from: value.type_name(),
+                            to: "Metric",
+                            message: Some(
+                                "Invalid structure for converting to HyperLogLog".to_string(),
+                            ),
+                        })?
+                }
                 x => {
                     return Err(LuaError::FromLuaConversionError {
                         from: value.type_name(),
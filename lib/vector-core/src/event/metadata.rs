@@ -3,6 +3,7 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use value::{Kind, Secrets, Value};
 use vector_common::EventDataEq;
@@ -13,6 +14,7 @@ use crate::{schema, ByteSizeOf};
 
 const DATADOG_API_KEY: &str = "datadog_api_key";
 const SPLUNK_HEC_TOKEN: &str = "splunk_hec_token";
+const EXPIRES_AT: &str = "expires_at";
 
 /// The top-level metadata structure contained by both `struct Metric`
 /// and `struct LogEvent` types.
@@ -84,6 +86,38 @@ impl EventMetadata {
     }
 }
 
+/// Expiration access functions
+impl EventMetadata {
+    /// Returns when the event expires, if it has a TTL.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.value
+            .get(EXPIRES_AT)
+            .and_then(Value::as_timestamp)
+            .copied()
+    }
+
+    /// Sets when the event expires.
+    pub fn set_expires_at(&mut self, expires_at: DateTime<Utc>) {
+        self.value.insert(EXPIRES_AT, expires_at);
+    }
+
+    /// Sets the event to expire after the given TTL, unless it already has an expiration. The event
+    /// never expires if the TTL goes past the latest representable time.
+    pub fn set_default_ttl(&mut self, ttl: Duration) {
+        if self.expires_at().is_none() {
+            if let Some(expires_at) = Utc::now().checked_add_signed(ttl) {
+                self.set_expires_at(expires_at);
+            }
+        }
+    }
+
+    /// Returns whether the event expired by the given time.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at()
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
 impl Default for EventMetadata {
     fn default() -> Self {
         Self {
@@ -242,4 +276,24 @@ mod test {
         assert_eq!(metadata.datadog_api_key().unwrap().as_ref(), SECRET);
         assert_eq!(metadata.splunk_hec_token().unwrap().as_ref(), SECRET2);
     }
+
+    #[test]
+    fn expiration() {
+        let now = Utc::now();
+        let mut metadata = EventMetadata::default();
+        assert!(!metadata.is_expired(now));
+
+        metadata.set_default_ttl(Duration::seconds(60));
+        let expires_at = metadata.expires_at().unwrap();
+        assert!(!metadata.is_expired(now));
+        assert!(metadata.is_expired(now + Duration::seconds(61)));
+
+        // An expiration set by a source or by `remap` is kept.
+        metadata.set_default_ttl(Duration::seconds(3600));
+        assert_eq!(metadata.expires_at(), Some(expires_at));
+
+        let mut metadata = EventMetadata::default();
+        metadata.set_default_ttl(Duration::max_value());
+        assert_eq!(metadata.expires_at(), None);
+    }
 }
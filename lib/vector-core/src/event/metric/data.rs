@@ -1,3 +1,5 @@
+use std::num::NonZeroU32;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use vector_common::byte_size_of::ByteSizeOf;
@@ -9,6 +11,10 @@ pub struct MetricData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<DateTime<Utc>>,
 
+    /// The time span, in milliseconds, the value of the metric was collected over, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_ms: Option<NonZeroU32>,
+
     pub kind: MetricKind,
 
     #[serde(flatten)]
@@ -21,6 +27,11 @@ impl MetricData {
         self.timestamp.as_ref()
     }
 
+    /// Gets the interval, in milliseconds, this data was collected over, if available.
+    pub fn interval_ms(&self) -> Option<NonZeroU32> {
+        self.interval_ms
+    }
+
     /// Gets a reference to the value of this data.
     pub fn value(&self) -> &MetricValue {
         &self.value
@@ -38,6 +49,7 @@ impl MetricData {
     pub fn into_absolute(self) -> Self {
         Self {
             timestamp: self.timestamp,
+            interval_ms: self.interval_ms,
             kind: MetricKind::Absolute,
             value: self.value,
         }
@@ -50,12 +62,15 @@ impl MetricData {
     pub fn into_incremental(self) -> Self {
         Self {
             timestamp: self.timestamp,
+            interval_ms: self.interval_ms,
             kind: MetricKind::Incremental,
             value: self.value,
         }
     }
 
     /// Creates a `MetricData` directly from the raw components of another `MetricData`.
+    ///
+    /// The interval is not one of the parts, and is left unset.
     pub fn from_parts(
         timestamp: Option<DateTime<Utc>>,
         kind: MetricKind,
@@ -63,6 +78,7 @@ impl MetricData {
     ) -> Self {
         Self {
             timestamp,
+            interval_ms: None,
             kind,
             value,
         }
@@ -107,6 +123,27 @@ impl MetricData {
     pub fn zero(&mut self) {
         self.value.zero();
     }
+
+    /// Converts this metric to the per-second rate of its counter over its interval, as an
+    /// absolute gauge.
+    ///
+    /// Only incremental counters with a known interval can be converted, otherwise `None` is
+    /// returned.
+    pub fn to_rate(&self) -> Option<Self> {
+        match (self.kind, &self.value, self.interval_ms) {
+            (MetricKind::Incremental, MetricValue::Counter { value }, Some(interval_ms)) => {
+                Some(Self {
+                    timestamp: self.timestamp,
+                    interval_ms: self.interval_ms,
+                    kind: MetricKind::Absolute,
+                    value: MetricValue::Gauge {
+                        value: value * 1000.0 / f64::from(interval_ms.get()),
+                    },
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl AsRef<MetricData> for MetricData {
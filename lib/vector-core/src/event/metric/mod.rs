@@ -4,6 +4,7 @@ use std::{
     collections::{btree_map, BTreeMap},
     convert::AsRef,
     fmt::{self, Display, Formatter},
+    num::NonZeroU32,
 };
 
 use chrono::{DateTime, Utc};
@@ -65,6 +66,7 @@ impl Metric {
             },
             data: MetricData {
                 timestamp: None,
+                interval_ms: None,
                 kind,
                 value,
             },
@@ -96,6 +98,14 @@ impl Metric {
         self
     }
 
+    /// Consumes this metric, returning it with an updated interval.
+    #[inline]
+    #[must_use]
+    pub fn with_interval_ms(mut self, interval_ms: Option<NonZeroU32>) -> Self {
+        self.data.interval_ms = interval_ms;
+        self
+    }
+
     pub fn add_finalizer(&mut self, finalizer: EventFinalizer) {
         self.metadata.add_finalizer(finalizer);
     }
@@ -139,6 +149,11 @@ impl Metric {
         &self.series
     }
 
+    /// Gets a mutable reference to the series of this metric.
+    pub fn series_mut(&mut self) -> &mut MetricSeries {
+        &mut self.series
+    }
+
     /// Gets a reference to the data of this metric.
     pub fn data(&self) -> &MetricData {
         &self.data
@@ -191,6 +206,12 @@ impl Metric {
         self.data.timestamp
     }
 
+    /// Gets the interval, in milliseconds, the value of this metric was collected over, if available.
+    #[inline]
+    pub fn interval_ms(&self) -> Option<NonZeroU32> {
+        self.data.interval_ms
+    }
+
     /// Gets a reference to the value of this metric.
     #[inline]
     pub fn value(&self) -> &MetricValue {
@@ -757,6 +778,33 @@ mod test {
         assert!(!new_reset_counter.subtract(&old_counter));
     }
 
+    #[test]
+    fn counter_to_rate() {
+        let counter = Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 30.0 },
+        )
+        .with_timestamp(Some(ts()));
+
+        // The rate is over the interval, which must be known.
+        assert_eq!(counter.data().to_rate(), None);
+
+        let counter = counter.with_interval_ms(NonZeroU32::new(10_000));
+        let rate = counter.data().to_rate().unwrap();
+        assert_eq!(rate.kind, MetricKind::Absolute);
+        assert_eq!(rate.value, MetricValue::Gauge { value: 3.0 });
+        assert_eq!(rate.timestamp, Some(ts()));
+
+        // Only incremental counters have a rate.
+        let gauge = counter
+            .clone()
+            .with_value(MetricValue::Gauge { value: 30.0 });
+        assert_eq!(gauge.data().to_rate(), None);
+        let absolute = counter.into_absolute();
+        assert_eq!(absolute.data().to_rate(), None);
+    }
+
     #[test]
     fn subtract_aggregated_histograms() {
         // Make sure a newer/higher count aggregated histogram can subtract an older/lower count
@@ -1023,4 +1071,29 @@ mod test {
         let converted = distrib_value.distribution_to_sketch();
         assert!(matches!(converted, Some(MetricValue::Sketch { .. })));
     }
+
+    #[test]
+    fn approximate_set() {
+        let set = MetricValue::Set {
+            values: ["a", "b"].into_iter().map(Into::into).collect(),
+        };
+        assert!(set.set_to_sketch(2).is_none());
+
+        let mut approximate = set.set_to_sketch(12).unwrap();
+        let other = MetricValue::Set {
+            values: ["b", "c"].into_iter().map(Into::into).collect(),
+        };
+        assert!(approximate.add(&other));
+        assert!(approximate.add(&other.set_to_sketch(12).unwrap()));
+        assert!(!approximate.add(&other.set_to_sketch(13).unwrap()));
+        // Approximate sets can't be subtracted.
+        assert!(!approximate.subtract(&other.set_to_sketch(12).unwrap()));
+
+        match approximate {
+            MetricValue::Sketch {
+                sketch: MetricSketch::HyperLogLog(hll),
+            } => assert_eq!(hll.estimate().round(), 3.0),
+            value => panic!("Expected an approximate set, got {:?}.", value),
+        }
+    }
 }
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use vector_common::byte_size_of::ByteSizeOf;
 
 use super::{samples_to_buckets, write_list, write_word};
-use crate::metrics::AgentDDSketch;
+use crate::metrics::{AgentDDSketch, HyperLogLog};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -40,8 +40,8 @@ pub enum MetricValue {
         count: u64,
         sum: f64,
     },
-    /// A data structure that can answer questions about the cumulative distribution of the contained samples in
-    /// space-efficient way.
+    /// A data structure that can answer questions about the cumulative distribution of the contained samples, or the
+    /// number of unique values of a set, in a space-efficient way.
     ///
     /// Sketches represent the data in a way that queries over it have bounded error guarantees without needing to hold
     /// every single sample in memory. They are also, typically, able to be merged with other sketches of the same type
@@ -126,6 +126,26 @@ impl MetricValue {
         }
     }
 
+    /// Converts a set to an approximate set, backed by a HyperLogLog with the given precision.
+    ///
+    /// If this value is not a set, or the precision isn't supported, then `None` is returned.  Otherwise,
+    /// `Some(MetricValue::Sketch)` is returned.
+    pub fn set_to_sketch(&self, precision: u8) -> Option<MetricValue> {
+        match self {
+            MetricValue::Set { values } => {
+                let mut hll = HyperLogLog::new(precision)?;
+                for value in values {
+                    hll.insert(value);
+                }
+
+                Some(MetricValue::Sketch {
+                    sketch: MetricSketch::HyperLogLog(hll),
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Zeroes out all the values contained in this value.
     ///
     /// This keeps all the bucket/value vectors for the histogram and summary metric types intact while zeroing the
@@ -161,6 +181,7 @@ impl MetricValue {
                 MetricSketch::AgentDDSketch(ddsketch) => {
                     ddsketch.clear();
                 }
+                MetricSketch::HyperLogLog(hll) => hll.clear(),
             },
         }
     }
@@ -225,7 +246,23 @@ impl MetricValue {
                         MetricSketch::AgentDDSketch(ddsketch),
                         MetricSketch::AgentDDSketch(ddsketch2),
                     ) => ddsketch.merge(ddsketch2).is_ok(),
+                    (MetricSketch::HyperLogLog(hll), MetricSketch::HyperLogLog(hll2)) => {
+                        hll.merge(hll2).is_ok()
+                    }
+                    _ => false,
+                }
+            }
+            // Exact sets are folded into approximate ones, so that sets can be aggregated approximately.
+            (
+                Self::Sketch {
+                    sketch: MetricSketch::HyperLogLog(hll),
+                },
+                Self::Set { values },
+            ) => {
+                for value in values {
+                    hll.insert(value);
                 }
+                true
             }
             _ => false,
         }
@@ -317,6 +354,8 @@ impl MetricValue {
                 *sum -= sum2;
                 true
             }
+            // Approximate sets can't tell which values they have in common with another one, so they can't be
+            // subtracted, and are reinitialized instead.
             _ => false,
         }
     }
@@ -458,6 +497,14 @@ impl fmt::Display for MetricValue {
                             )
                         })
                     }
+                    MetricSketch::HyperLogLog(hll) => {
+                        write!(
+                            fmt,
+                            "precision={} estimate={}",
+                            hll.precision(),
+                            hll.estimate()
+                        )
+                    }
                 }
             }
         }
@@ -472,6 +519,14 @@ impl From<AgentDDSketch> for MetricValue {
     }
 }
 
+impl From<HyperLogLog> for MetricValue {
+    fn from(hll: HyperLogLog) -> Self {
+        MetricValue::Sketch {
+            sketch: MetricSketch::HyperLogLog(hll),
+        }
+    }
+}
+
 // Currently, VRL can only read the type of the value and doesn't consider any actual metric values.
 #[cfg(feature = "vrl")]
 impl From<MetricValue> for ::value::Value {
@@ -497,6 +552,12 @@ pub enum MetricSketch {
     /// the Datadog Agent itself is subtly different.  This version is suitable for sending directly
     /// to Datadog's sketch ingest endpoint.
     AgentDDSketch(AgentDDSketch),
+
+    /// HyperLogLog, estimating the number of unique values of a set.
+    ///
+    /// This is the approximate counterpart of `MetricValue::Set`, whose memory is bounded by its precision rather than
+    /// growing with the number of unique values.
+    HyperLogLog(HyperLogLog),
 }
 
 impl MetricSketch {
@@ -504,6 +565,7 @@ impl MetricSketch {
     pub fn is_empty(&self) -> bool {
         match self {
             MetricSketch::AgentDDSketch(ddsketch) => ddsketch.is_empty(),
+            MetricSketch::HyperLogLog(hll) => hll.is_empty(),
         }
     }
 
@@ -513,6 +575,7 @@ impl MetricSketch {
     pub fn as_name(&self) -> &'static str {
         match self {
             Self::AgentDDSketch(_) => "agent dd sketch",
+            Self::HyperLogLog(_) => "hyperloglog",
         }
     }
 }
@@ -521,6 +584,7 @@ impl ByteSizeOf for MetricSketch {
     fn allocated_bytes(&self) -> usize {
         match self {
             Self::AgentDDSketch(ddsketch) => ddsketch.allocated_bytes(),
+            Self::HyperLogLog(hll) => hll.allocated_bytes(),
         }
     }
 }
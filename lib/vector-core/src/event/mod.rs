@@ -332,6 +332,15 @@ impl From<proto::StatisticKind> for StatisticKind {
     }
 }
 
+impl From<StatisticKind> for proto::StatisticKind {
+    fn from(kind: StatisticKind) -> Self {
+        match kind {
+            StatisticKind::Histogram => proto::StatisticKind::Histogram,
+            StatisticKind::Summary => proto::StatisticKind::Summary,
+        }
+    }
+}
+
 impl From<metric::Sample> for proto::DistributionSample {
     fn from(sample: metric::Sample) -> Self {
         Self {
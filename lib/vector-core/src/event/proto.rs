@@ -1,17 +1,33 @@
+use std::num::NonZeroU32;
+
 use chrono::TimeZone;
 use ordered_float::NotNan;
+use snafu::Snafu;
 
 use crate::{
     event::{self, BTreeMap, WithMetadata},
-    metrics::AgentDDSketch,
+    metrics::{AgentDDSketch, HyperLogLog},
 };
 
 include!(concat!(env!("OUT_DIR"), "/event.rs"));
+pub mod v2;
 pub use event_wrapper::Event;
 pub use metric::Value as MetricValue;
 
 use super::{array, metric::MetricSketch};
 
+/// An error converting a protobuf event into an event, when the protobuf event is well formed but
+/// holds values that an event can't.
+#[derive(Debug, Snafu)]
+pub enum ConversionError {
+    #[snafu(display(
+        "HyperLogLog has {} registers, which doesn't match its precision of {}",
+        registers,
+        precision
+    ))]
+    InvalidHyperLogLog { precision: u32, registers: usize },
+}
+
 impl event_array::Events {
     // We can't use the standard `From` traits here because the actual
     // type of `LogArray` and `TraceArray` are the same.
@@ -42,21 +58,27 @@ impl From<array::EventArray> for EventArray {
     }
 }
 
-impl From<EventArray> for array::EventArray {
-    fn from(events: EventArray) -> Self {
+impl TryFrom<EventArray> for array::EventArray {
+    type Error = ConversionError;
+
+    fn try_from(events: EventArray) -> Result<Self, Self::Error> {
         let events = events.events.unwrap();
 
-        match events {
+        Ok(match events {
             event_array::Events::Logs(logs) => {
                 array::EventArray::Logs(logs.logs.into_iter().map(Into::into).collect())
             }
-            event_array::Events::Metrics(metrics) => {
-                array::EventArray::Metrics(metrics.metrics.into_iter().map(Into::into).collect())
-            }
+            event_array::Events::Metrics(metrics) => array::EventArray::Metrics(
+                metrics
+                    .metrics
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            ),
             event_array::Events::Traces(traces) => {
                 array::EventArray::Traces(traces.traces.into_iter().map(Into::into).collect())
             }
-        }
+        })
     }
 }
 
@@ -86,7 +108,7 @@ impl From<Trace> for Event {
 
 impl From<Log> for event::LogEvent {
     fn from(log: Log) -> Self {
-        if let Some(value) = log.value {
+        let mut log_event = if let Some(value) = log.value {
             Self::from(decode_value(value).unwrap_or(::value::Value::Null))
         } else {
             // This is for backwards compatibility. Only `value` should be set
@@ -97,7 +119,13 @@ impl From<Log> for event::LogEvent {
                 .collect::<BTreeMap<_, _>>();
 
             Self::from(fields)
+        };
+        if let Some(expires_at) = log.expires_at {
+            log_event
+                .metadata_mut()
+                .set_expires_at(decode_timestamp(expires_at));
         }
+        log_event
     }
 }
 
@@ -109,12 +137,20 @@ impl From<Trace> for event::TraceEvent {
             .filter_map(|(k, v)| decode_value(v).map(|value| (k, value)))
             .collect::<BTreeMap<_, _>>();
 
-        Self::from(event::LogEvent::from(fields))
+        let mut trace_event = Self::from(event::LogEvent::from(fields));
+        if let Some(expires_at) = trace.expires_at {
+            trace_event
+                .metadata_mut()
+                .set_expires_at(decode_timestamp(expires_at));
+        }
+        trace_event
     }
 }
 
-impl From<Metric> for event::Metric {
-    fn from(metric: Metric) -> Self {
+impl TryFrom<Metric> for event::Metric {
+    type Error = ConversionError;
+
+    fn try_from(metric: Metric) -> Result<Self, Self::Error> {
         let kind = match metric.kind() {
             metric::Kind::Incremental => event::MetricKind::Incremental,
             metric::Kind::Absolute => event::MetricKind::Absolute,
@@ -128,9 +164,9 @@ impl From<Metric> for event::Metric {
             Some(metric.namespace)
         };
 
-        let timestamp = metric
-            .timestamp
-            .map(|ts| chrono::Utc.timestamp(ts.seconds, ts.nanos as u32));
+        let timestamp = metric.timestamp.map(decode_timestamp);
+
+        let interval_ms = NonZeroU32::new(metric.interval_ms);
 
         let tags = if metric.tags.is_empty() {
             None
@@ -191,25 +227,37 @@ impl From<Metric> for event::Metric {
                 sketch::Sketch::AgentDdSketch(ddsketch) => event::MetricValue::Sketch {
                     sketch: ddsketch.into(),
                 },
+                sketch::Sketch::HyperLogLog(hll) => event::MetricValue::Sketch {
+                    sketch: hll.try_into()?,
+                },
             },
         };
 
-        Self::new(name, kind, value)
+        let mut metric_event = Self::new(name, kind, value)
             .with_namespace(namespace)
             .with_tags(tags)
             .with_timestamp(timestamp)
+            .with_interval_ms(interval_ms);
+        if let Some(expires_at) = metric.expires_at {
+            metric_event
+                .metadata_mut()
+                .set_expires_at(decode_timestamp(expires_at));
+        }
+        Ok(metric_event)
     }
 }
 
-impl From<EventWrapper> for event::Event {
-    fn from(proto: EventWrapper) -> Self {
+impl TryFrom<EventWrapper> for event::Event {
+    type Error = ConversionError;
+
+    fn try_from(proto: EventWrapper) -> Result<Self, Self::Error> {
         let event = proto.event.unwrap();
 
-        match event {
+        Ok(match event {
             Event::Log(proto) => Self::Log(proto.into()),
-            Event::Metric(proto) => Self::Metric(proto.into()),
+            Event::Metric(proto) => Self::Metric(proto.try_into()?),
             Event::Trace(proto) => Self::Trace(proto.into()),
-        }
+        })
     }
 }
 
@@ -228,6 +276,7 @@ impl From<event::TraceEvent> for Trace {
 impl From<event::LogEvent> for WithMetadata<Log> {
     fn from(log_event: event::LogEvent) -> Self {
         let (value, metadata) = log_event.into_parts();
+        let expires_at = metadata.expires_at().map(encode_timestamp);
 
         // Due to the backwards compatibility requirement by the
         // "event_can_go_from_raw_prost_to_eventarray_encodable" test, "fields" must not
@@ -245,6 +294,7 @@ impl From<event::LogEvent> for WithMetadata<Log> {
                     .map(|(k, v)| (k, encode_value(v)))
                     .collect::<BTreeMap<_, _>>(),
                 value: None,
+                expires_at,
             }
         } else {
             let mut dummy = BTreeMap::new();
@@ -254,6 +304,7 @@ impl From<event::LogEvent> for WithMetadata<Log> {
             Log {
                 fields: dummy,
                 value: Some(encode_value(value)),
+                expires_at,
             }
         };
 
@@ -269,7 +320,8 @@ impl From<event::TraceEvent> for WithMetadata<Trace> {
             .map(|(k, v)| (k, encode_value(v)))
             .collect::<BTreeMap<_, _>>();
 
-        let data = Trace { fields };
+        let expires_at = metadata.expires_at().map(encode_timestamp);
+        let data = Trace { fields, expires_at };
         Self { data, metadata }
     }
 }
@@ -286,10 +338,10 @@ impl From<event::Metric> for WithMetadata<Metric> {
         let name = series.name.name;
         let namespace = series.name.namespace.unwrap_or_default();
 
-        let timestamp = data.timestamp.map(|ts| prost_types::Timestamp {
-            seconds: ts.timestamp(),
-            nanos: ts.timestamp_subsec_nanos() as i32,
-        });
+        let timestamp = data.timestamp.map(encode_timestamp);
+        let expires_at = metadata.expires_at().map(encode_timestamp);
+
+        let interval_ms = data.interval_ms.map_or(0, NonZeroU32::get);
 
         let tags = series.tags.unwrap_or_default();
 
@@ -333,26 +385,7 @@ impl From<event::Metric> for WithMetadata<Metric> {
                 count,
                 sum,
             }),
-            event::MetricValue::Sketch { sketch } => match sketch {
-                MetricSketch::AgentDDSketch(ddsketch) => {
-                    let bin_map = ddsketch.bin_map();
-                    let (keys, counts) = bin_map.into_parts();
-                    let keys = keys.into_iter().map(i32::from).collect();
-                    let counts = counts.into_iter().map(u32::from).collect();
-
-                    MetricValue::Sketch(Sketch {
-                        sketch: Some(sketch::Sketch::AgentDdSketch(sketch::AgentDdSketch {
-                            count: ddsketch.count(),
-                            min: ddsketch.min().unwrap_or(f64::MAX),
-                            max: ddsketch.max().unwrap_or(f64::MIN),
-                            sum: ddsketch.sum().unwrap_or(0.0),
-                            avg: ddsketch.avg().unwrap_or(0.0),
-                            k: keys,
-                            n: counts,
-                        })),
-                    })
-                }
-            },
+            event::MetricValue::Sketch { sketch } => MetricValue::Sketch(sketch.into()),
         };
 
         let data = Metric {
@@ -362,6 +395,8 @@ impl From<event::Metric> for WithMetadata<Metric> {
             tags,
             kind,
             value: Some(metric),
+            interval_ms,
+            expires_at,
         };
         Self { data, metadata }
     }
@@ -414,6 +449,20 @@ impl From<AgentDDSketch> for Sketch {
     }
 }
 
+impl From<MetricSketch> for Sketch {
+    fn from(sketch: MetricSketch) -> Self {
+        match sketch {
+            MetricSketch::AgentDDSketch(ddsketch) => ddsketch.into(),
+            MetricSketch::HyperLogLog(hll) => Sketch {
+                sketch: Some(sketch::Sketch::HyperLogLog(sketch::HyperLogLog {
+                    precision: u32::from(hll.precision()),
+                    registers: hll.registers().to_vec(),
+                })),
+            },
+        }
+    }
+}
+
 impl From<sketch::AgentDdSketch> for MetricSketch {
     fn from(sketch: sketch::AgentDdSketch) -> Self {
         // These safe conversions are annoying because the Datadog Agent internally uses i16/u16,
@@ -447,12 +496,36 @@ impl From<sketch::AgentDdSketch> for MetricSketch {
     }
 }
 
+impl TryFrom<sketch::HyperLogLog> for MetricSketch {
+    type Error = ConversionError;
+
+    fn try_from(hll: sketch::HyperLogLog) -> Result<Self, Self::Error> {
+        let precision = hll.precision;
+        let registers = hll.registers.len();
+        HyperLogLog::from_raw(precision.try_into().unwrap_or(u8::MAX), hll.registers)
+            .map(MetricSketch::HyperLogLog)
+            .ok_or(ConversionError::InvalidHyperLogLog {
+                precision,
+                registers,
+            })
+    }
+}
+
+fn encode_timestamp(ts: chrono::DateTime<chrono::Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: ts.timestamp(),
+        nanos: ts.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn decode_timestamp(ts: prost_types::Timestamp) -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc.timestamp(ts.seconds, ts.nanos as u32)
+}
+
 fn decode_value(input: Value) -> Option<event::Value> {
     match input.kind {
         Some(value::Kind::RawBytes(data)) => Some(event::Value::Bytes(data)),
-        Some(value::Kind::Timestamp(ts)) => Some(event::Value::Timestamp(
-            chrono::Utc.timestamp(ts.seconds, ts.nanos as u32),
-        )),
+        Some(value::Kind::Timestamp(ts)) => Some(event::Value::Timestamp(decode_timestamp(ts))),
         Some(value::Kind::Integer(value)) => Some(event::Value::Integer(value)),
         Some(value::Kind::Float(value)) => Some(event::Value::Float(NotNan::new(value).unwrap())),
         Some(value::Kind::Boolean(value)) => Some(event::Value::Boolean(value)),
@@ -495,10 +568,7 @@ fn encode_value(value: event::Value) -> Value {
         kind: match value {
             event::Value::Bytes(b) => Some(value::Kind::RawBytes(b)),
             event::Value::Regex(regex) => Some(value::Kind::RawBytes(regex.as_bytes())),
-            event::Value::Timestamp(ts) => Some(value::Kind::Timestamp(prost_types::Timestamp {
-                seconds: ts.timestamp(),
-                nanos: ts.timestamp_subsec_nanos() as i32,
-            })),
+            event::Value::Timestamp(ts) => Some(value::Kind::Timestamp(encode_timestamp(ts))),
             event::Value::Integer(value) => Some(value::Kind::Integer(value)),
             event::Value::Float(value) => Some(value::Kind::Float(value.into_inner())),
             event::Value::Boolean(value) => Some(value::Kind::Boolean(value)),
@@ -0,0 +1,376 @@
+//! Version 2 of the native protobuf event encoding.
+//!
+//! Each event is encoded as explicit sections: the event data, and a `Metadata` section carrying
+//! the metadata value shared by all event types. Unlike version 1, there are no deprecated fields
+//! to fill in for backwards compatibility, and metric intervals are absent rather than zero when
+//! unknown.
+
+use std::num::NonZeroU32;
+
+use super::{decode_timestamp, decode_value, encode_timestamp, encode_value, ConversionError};
+use crate::event::{self, array, BTreeMap, EventMetadata};
+
+include!(concat!(env!("OUT_DIR"), "/event.v2.rs"));
+pub use event_wrapper::Event;
+
+impl From<array::EventArray> for EventArray {
+    fn from(events: array::EventArray) -> Self {
+        let events = Some(match events {
+            array::EventArray::Logs(logs) => event_array::Events::Logs(LogArray {
+                logs: logs.into_iter().map(Into::into).collect(),
+            }),
+            array::EventArray::Metrics(metrics) => event_array::Events::Metrics(MetricArray {
+                metrics: metrics.into_iter().map(Into::into).collect(),
+            }),
+            array::EventArray::Traces(traces) => event_array::Events::Traces(TraceArray {
+                traces: traces.into_iter().map(Into::into).collect(),
+            }),
+        });
+        Self { events }
+    }
+}
+
+impl TryFrom<EventArray> for array::EventArray {
+    type Error = ConversionError;
+
+    fn try_from(events: EventArray) -> Result<Self, Self::Error> {
+        Ok(match events.events.unwrap() {
+            event_array::Events::Logs(logs) => {
+                Self::Logs(logs.logs.into_iter().map(Into::into).collect())
+            }
+            event_array::Events::Metrics(metrics) => Self::Metrics(
+                metrics
+                    .metrics
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            ),
+            event_array::Events::Traces(traces) => {
+                Self::Traces(traces.traces.into_iter().map(Into::into).collect())
+            }
+        })
+    }
+}
+
+impl From<event::Event> for EventWrapper {
+    fn from(event: event::Event) -> Self {
+        let event = match event {
+            event::Event::Log(log) => Event::Log(log.into()),
+            event::Event::Metric(metric) => Event::Metric(metric.into()),
+            event::Event::Trace(trace) => Event::Trace(trace.into()),
+        };
+        Self { event: Some(event) }
+    }
+}
+
+impl TryFrom<EventWrapper> for event::Event {
+    type Error = ConversionError;
+
+    fn try_from(proto: EventWrapper) -> Result<Self, Self::Error> {
+        Ok(match proto.event.unwrap() {
+            Event::Log(log) => Self::Log(log.into()),
+            Event::Metric(metric) => Self::Metric(metric.try_into()?),
+            Event::Trace(trace) => Self::Trace(trace.into()),
+        })
+    }
+}
+
+impl From<EventMetadata> for Option<Metadata> {
+    fn from(metadata: EventMetadata) -> Self {
+        // An empty metadata value is the default, and doesn't need to be sent.
+        match metadata.value() {
+            event::Value::Object(fields) if fields.is_empty() => None,
+            value => Some(Metadata {
+                value: Some(encode_value(value.clone())),
+            }),
+        }
+    }
+}
+
+impl From<Option<Metadata>> for EventMetadata {
+    fn from(metadata: Option<Metadata>) -> Self {
+        let mut event_metadata = Self::default();
+        if let Some(value) = metadata
+            .and_then(|metadata| metadata.value)
+            .and_then(decode_value)
+        {
+            *event_metadata.value_mut() = value;
+        }
+        event_metadata
+    }
+}
+
+impl From<event::LogEvent> for Log {
+    fn from(log: event::LogEvent) -> Self {
+        let (value, metadata) = log.into_parts();
+        Self {
+            value: Some(encode_value(value)),
+            metadata: metadata.into(),
+        }
+    }
+}
+
+impl From<Log> for event::LogEvent {
+    fn from(log: Log) -> Self {
+        let value = log
+            .value
+            .and_then(decode_value)
+            .unwrap_or(event::Value::Null);
+        Self::from_parts(value, log.metadata.into())
+    }
+}
+
+impl From<event::TraceEvent> for Trace {
+    fn from(trace: event::TraceEvent) -> Self {
+        let (fields, metadata) = trace.into_parts();
+        Self {
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| (key, encode_value(value)))
+                .collect(),
+            metadata: metadata.into(),
+        }
+    }
+}
+
+impl From<Trace> for event::TraceEvent {
+    fn from(trace: Trace) -> Self {
+        let fields = trace
+            .fields
+            .into_iter()
+            .filter_map(|(key, value)| decode_value(value).map(|value| (key, value)))
+            .collect::<BTreeMap<_, _>>();
+        Self::from_parts(fields, trace.metadata.into())
+    }
+}
+
+impl From<event::Metric> for Metric {
+    fn from(metric: event::Metric) -> Self {
+        let (series, data, metadata) = metric.into_parts();
+        Self {
+            series: Some(series.into()),
+            data: Some(data.into()),
+            metadata: metadata.into(),
+        }
+    }
+}
+
+impl TryFrom<Metric> for event::Metric {
+    type Error = ConversionError;
+
+    fn try_from(metric: Metric) -> Result<Self, Self::Error> {
+        Ok(Self::from_parts(
+            metric.series.unwrap_or_default().into(),
+            metric.data.unwrap_or_default().try_into()?,
+            metric.metadata.into(),
+        ))
+    }
+}
+
+impl From<event::metric::MetricSeries> for MetricSeries {
+    fn from(series: event::metric::MetricSeries) -> Self {
+        Self {
+            name: series.name.name,
+            namespace: series.name.namespace.unwrap_or_default(),
+            tags: series.tags.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<MetricSeries> for event::metric::MetricSeries {
+    fn from(series: MetricSeries) -> Self {
+        Self {
+            name: event::metric::MetricName {
+                name: series.name,
+                namespace: (!series.namespace.is_empty()).then(|| series.namespace),
+            },
+            tags: (!series.tags.is_empty()).then(|| series.tags),
+        }
+    }
+}
+
+impl From<event::metric::MetricData> for MetricData {
+    fn from(data: event::metric::MetricData) -> Self {
+        let kind = match data.kind {
+            event::MetricKind::Incremental => metric_data::Kind::Incremental,
+            event::MetricKind::Absolute => metric_data::Kind::Absolute,
+        };
+
+        let value = match data.value {
+            event::MetricValue::Counter { value } => {
+                metric_data::Value::Counter(super::Counter { value })
+            }
+            event::MetricValue::Gauge { value } => {
+                metric_data::Value::Gauge(super::Gauge { value })
+            }
+            event::MetricValue::Set { values } => metric_data::Value::Set(super::Set {
+                values: values.into_iter().collect(),
+            }),
+            event::MetricValue::Distribution { samples, statistic } => {
+                metric_data::Value::Distribution(super::Distribution2 {
+                    samples: samples.into_iter().map(Into::into).collect(),
+                    statistic: super::StatisticKind::from(statistic).into(),
+                })
+            }
+            event::MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            } => metric_data::Value::AggregatedHistogram(super::AggregatedHistogram3 {
+                buckets: buckets.into_iter().map(Into::into).collect(),
+                count,
+                sum,
+            }),
+            event::MetricValue::AggregatedSummary {
+                quantiles,
+                count,
+                sum,
+            } => metric_data::Value::AggregatedSummary(super::AggregatedSummary3 {
+                quantiles: quantiles.into_iter().map(Into::into).collect(),
+                count,
+                sum,
+            }),
+            event::MetricValue::Sketch { sketch } => metric_data::Value::Sketch(sketch.into()),
+        };
+
+        Self {
+            timestamp: data.timestamp.map(encode_timestamp),
+            interval: data.interval_ms.map(|ms| MetricInterval { ms: ms.get() }),
+            kind: kind.into(),
+            value: Some(value),
+        }
+    }
+}
+
+impl TryFrom<MetricData> for event::metric::MetricData {
+    type Error = ConversionError;
+
+    fn try_from(data: MetricData) -> Result<Self, Self::Error> {
+        let kind = match data.kind() {
+            metric_data::Kind::Incremental => event::MetricKind::Incremental,
+            metric_data::Kind::Absolute => event::MetricKind::Absolute,
+        };
+
+        let value = match data.value.unwrap() {
+            metric_data::Value::Counter(counter) => event::MetricValue::Counter {
+                value: counter.value,
+            },
+            metric_data::Value::Gauge(gauge) => event::MetricValue::Gauge { value: gauge.value },
+            metric_data::Value::Set(set) => event::MetricValue::Set {
+                values: set.values.into_iter().collect(),
+            },
+            metric_data::Value::Distribution(dist) => event::MetricValue::Distribution {
+                statistic: dist.statistic().into(),
+                samples: dist.samples.into_iter().map(Into::into).collect(),
+            },
+            metric_data::Value::AggregatedHistogram(hist) => {
+                event::MetricValue::AggregatedHistogram {
+                    buckets: hist.buckets.into_iter().map(Into::into).collect(),
+                    count: hist.count,
+                    sum: hist.sum,
+                }
+            }
+            metric_data::Value::AggregatedSummary(summary) => {
+                event::MetricValue::AggregatedSummary {
+                    quantiles: summary.quantiles.into_iter().map(Into::into).collect(),
+                    count: summary.count,
+                    sum: summary.sum,
+                }
+            }
+            metric_data::Value::Sketch(sketch) => match sketch.sketch.unwrap() {
+                super::sketch::Sketch::AgentDdSketch(ddsketch) => event::MetricValue::Sketch {
+                    sketch: ddsketch.into(),
+                },
+                super::sketch::Sketch::HyperLogLog(hll) => event::MetricValue::Sketch {
+                    sketch: hll.try_into()?,
+                },
+            },
+        };
+
+        Ok(Self {
+            timestamp: data.timestamp.map(decode_timestamp),
+            interval_ms: data
+                .interval
+                .and_then(|interval| NonZeroU32::new(interval.ms)),
+            kind,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use prost::Message;
+
+    use super::*;
+    use crate::event::{LogEvent, MetricKind, MetricValue};
+
+    fn round_trip(event: event::Event) -> event::Event {
+        let encoded = EventWrapper::from(event).encode_to_vec();
+        EventWrapper::decode(encoded.as_slice())
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn log_round_trips_with_metadata() {
+        let mut log = LogEvent::from("hello world");
+        log.insert("count", 3);
+        log.metadata_mut()
+            .set_expires_at(Utc.ymd(2022, 7, 1).and_hms(12, 0, 0));
+
+        let event = round_trip(log.clone().into());
+
+        assert_eq!(event.as_log(), &log);
+        assert_eq!(
+            event.as_log().metadata().expires_at(),
+            log.metadata().expires_at()
+        );
+    }
+
+    #[test]
+    fn metric_round_trips_with_interval() {
+        let metric = event::Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 42.0 },
+        )
+        .with_namespace(Some("http"))
+        .with_timestamp(Some(Utc.ymd(2022, 7, 1).and_hms(12, 0, 0)))
+        .with_interval_ms(NonZeroU32::new(10_000));
+
+        let event = round_trip(metric.clone().into());
+        assert_eq!(event.as_metric(), &metric);
+
+        let metric = metric.with_interval_ms(None);
+        let event = round_trip(metric.clone().into());
+        assert_eq!(event.as_metric().interval_ms(), None);
+    }
+
+    #[test]
+    fn rejects_mismatched_hyperloglog() {
+        let hll = super::super::sketch::HyperLogLog {
+            precision: 12,
+            registers: vec![0; 16],
+        };
+        let metric = Metric {
+            data: Some(MetricData {
+                value: Some(metric_data::Value::Sketch(super::super::Sketch {
+                    sketch: Some(super::super::sketch::Sketch::HyperLogLog(hll)),
+                })),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            event::Metric::try_from(metric),
+            Err(ConversionError::InvalidHyperLogLog {
+                precision: 12,
+                registers: 16
+            })
+        ));
+    }
+}
@@ -105,13 +105,15 @@ impl Encodable for EventArray {
         B: Buf + Clone,
     {
         if metadata.contains(EventEncodableMetadataFlags::DiskBufferV1CompatibilityMode) {
-            proto::EventArray::decode(buffer.clone())
-                .map(Into::into)
-                .or_else(|_| {
-                    proto::EventWrapper::decode(buffer)
-                        .map(|pe| EventArray::from(Event::from(pe)))
-                        .map_err(|_| DecodeError::InvalidProtobufPayload)
-                })
+            match proto::EventArray::decode(buffer.clone()) {
+                Ok(events) => EventArray::try_from(events),
+                Err(_) => {
+                    let event = proto::EventWrapper::decode(buffer)
+                        .map_err(|_| DecodeError::InvalidProtobufPayload)?;
+                    Event::try_from(event).map(EventArray::from)
+                }
+            }
+            .map_err(|_| DecodeError::InvalidProtobufPayload)
         } else {
             Err(DecodeError::UnsupportedEncodingMetadata)
         }
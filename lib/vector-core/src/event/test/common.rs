@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     iter,
+    num::NonZeroU32,
 };
 
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -12,7 +13,7 @@ use crate::{
         Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, TraceEvent,
         Value,
     },
-    metrics::AgentDDSketch,
+    metrics::{hyperloglog::DEFAULT_PRECISION, AgentDDSketch, HyperLogLog},
 };
 
 const MAX_F64_SIZE: f64 = 1_000_000.0;
@@ -175,7 +176,7 @@ impl Arbitrary for MetricValue {
         // constant here are the number of fields in `MetricValue`. Because the
         // field total is not a power of two we introduce a bias into choice
         // here toward `MetricValue::Counter` and `MetricValue::Gauge`.
-        match u8::arbitrary(g) % 7 {
+        match u8::arbitrary(g) % 8 {
             0 => MetricValue::Counter {
                 value: f64::arbitrary(g) % MAX_F64_SIZE,
             },
@@ -220,6 +221,15 @@ impl Arbitrary for MetricValue {
                     ),
                 }
             }
+            7 => {
+                let mut hll = HyperLogLog::new(DEFAULT_PRECISION).unwrap();
+                for value in BTreeSet::<String>::arbitrary(g) {
+                    hll.insert(&value);
+                }
+                MetricValue::Sketch {
+                    sketch: MetricSketch::HyperLogLog(hll),
+                }
+            }
 
             _ => unreachable!(),
         }
@@ -562,6 +572,7 @@ impl Arbitrary for MetricData {
 
         MetricData {
             timestamp: dt,
+            interval_ms: NonZeroU32::new(u32::arbitrary(g)),
             kind: MetricKind::arbitrary(g),
             value: MetricValue::arbitrary(g),
         }
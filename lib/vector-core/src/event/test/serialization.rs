@@ -69,9 +69,10 @@ fn eventarray_can_go_from_raw_prost_to_encodable_and_vice_versa() {
         .expect("events should not fail to encode");
 
     let second_decode_buf = second_encode_buf.freeze();
-    let second_decoded: EventArray = proto::EventArray::decode(second_decode_buf)
-        .map(Into::into)
-        .expect("events should not fail to decode");
+    let second_decoded = EventArray::try_from(
+        proto::EventArray::decode(second_decode_buf).expect("events should not fail to decode"),
+    )
+    .expect("events should not fail to convert");
 
     assert_eq!(events, second_decoded);
 }
@@ -162,6 +163,28 @@ fn back_and_forth_through_bytes() {
         .quickcheck(inner as fn(EventArray) -> TestResult);
 }
 
+#[test]
+fn expiration_survives_encoding() {
+    let expires_at = chrono::Utc::now() + chrono::Duration::seconds(60);
+    let mut log = LogEvent::from("expiring");
+    log.metadata_mut().set_expires_at(expires_at);
+    let mut metric = Metric::new(
+        "counter",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    );
+    metric.metadata_mut().set_expires_at(expires_at);
+
+    for event in [Event::from(log), Event::from(metric)] {
+        let mut buffer = BytesMut::with_capacity(64);
+        encode_value(EventArray::from(event), &mut buffer);
+
+        let decoded = decode_value::<EventArray, _>(buffer);
+        let decoded = decoded.iter_events().next().unwrap();
+        assert_eq!(decoded.metadata().expires_at(), Some(expires_at));
+    }
+}
+
 #[test]
 fn serialization() {
     let mut event = LogEvent::from("raw log line");
@@ -0,0 +1,190 @@
+use std::hash::Hasher;
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use twox_hash::XxHash64;
+use vector_common::byte_size_of::ByteSizeOf;
+
+/// The lowest supported precision, for a standard error of 26%.
+pub const MIN_PRECISION: u8 = 4;
+/// The highest supported precision, for a standard error of 0.4%.
+pub const MAX_PRECISION: u8 = 16;
+/// The default precision, using 4 KiB of registers for a standard error of 1.6%.
+pub const DEFAULT_PRECISION: u8 = 12;
+
+#[derive(Debug, Snafu)]
+pub enum MergeError {
+    #[snafu(display("cannot merge two HyperLogLogs with different precisions"))]
+    MismatchedPrecisions,
+}
+
+/// A HyperLogLog, estimating the number of unique values of a set without storing its members.
+///
+/// The values are hashed into `2^precision` registers, each holding the longest run of leading
+/// zeros seen among the hashes routed to it. The estimate has a standard error of
+/// `1.04 / sqrt(2^precision)`, and HyperLogLogs of the same precision merge without loss of
+/// accuracy. The hash is stable, so that HyperLogLogs built by different processes can be merged.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HyperLogLog {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    /// Creates an empty HyperLogLog with the given precision, which must be within
+    /// `MIN_PRECISION..=MAX_PRECISION`.
+    pub fn new(precision: u8) -> Option<Self> {
+        (MIN_PRECISION..=MAX_PRECISION)
+            .contains(&precision)
+            .then(|| Self {
+                precision,
+                registers: vec![0; 1 << precision],
+            })
+    }
+
+    /// Creates a HyperLogLog from its raw registers, as produced by `registers`.
+    ///
+    /// Returns `None` if the precision isn't supported, or doesn't match the number of registers.
+    pub fn from_raw(precision: u8, registers: Vec<u8>) -> Option<Self> {
+        let valid = (MIN_PRECISION..=MAX_PRECISION).contains(&precision)
+            && registers.len() == 1 << precision;
+        valid.then(|| Self {
+            precision,
+            registers,
+        })
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn registers(&self) -> &[u8] {
+        &self.registers
+    }
+
+    /// Returns `true` if no value was inserted.
+    pub fn is_empty(&self) -> bool {
+        self.registers.iter().all(|register| *register == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.registers.iter_mut().for_each(|register| *register = 0);
+    }
+
+    pub fn insert(&mut self, value: &str) {
+        let mut hasher = XxHash64::default();
+        hasher.write(value.as_bytes());
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - self.precision)) as usize;
+        // The sentinel bit bounds the rank when the remaining bits are all zeros.
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+    }
+
+    /// Merges another HyperLogLog into this one, as if its values had been inserted into it.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MergeError> {
+        if self.precision != other.precision {
+            return Err(MergeError::MismatchedPrecisions);
+        }
+        for (register, other) in self.registers.iter_mut().zip(&other.registers) {
+            *register = (*register).max(*other);
+        }
+        Ok(())
+    }
+
+    /// Estimates the number of unique values inserted.
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+
+        let (sum, zeros) = self
+            .registers
+            .iter()
+            .fold((0.0, 0_usize), |(sum, zeros), register| {
+                (
+                    sum + 2f64.powi(-i32::from(*register)),
+                    zeros + usize::from(*register == 0),
+                )
+            });
+        let raw = alpha * m * m / sum;
+
+        // Linear counting is more accurate for small cardinalities.
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+}
+
+impl ByteSizeOf for HyperLogLog {
+    fn allocated_bytes(&self) -> usize {
+        self.registers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(estimate: f64, actual: usize, precision: u8) {
+        let error = (estimate - actual as f64).abs() / actual as f64;
+        let standard_error = 1.04 / f64::from(1_u32 << precision).sqrt();
+        assert!(
+            error < 3.0 * standard_error,
+            "estimate {} is too far from {}",
+            estimate,
+            actual
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_precisions() {
+        assert!(HyperLogLog::new(MIN_PRECISION - 1).is_none());
+        assert!(HyperLogLog::new(MAX_PRECISION + 1).is_none());
+        assert!(HyperLogLog::from_raw(DEFAULT_PRECISION, vec![0; 8]).is_none());
+    }
+
+    #[test]
+    fn estimates_cardinality() {
+        for count in [1, 10, 1_000, 100_000] {
+            let mut hll = HyperLogLog::new(DEFAULT_PRECISION).unwrap();
+            for i in 0..count {
+                hll.insert(&i.to_string());
+                // Duplicates don't change the estimate.
+                hll.insert(&i.to_string());
+            }
+            assert_close(hll.estimate(), count, DEFAULT_PRECISION);
+        }
+    }
+
+    #[test]
+    fn merges() {
+        let mut a = HyperLogLog::new(DEFAULT_PRECISION).unwrap();
+        let mut b = HyperLogLog::new(DEFAULT_PRECISION).unwrap();
+        for i in 0..6_000 {
+            a.insert(&i.to_string());
+        }
+        for i in 4_000..10_000 {
+            b.insert(&i.to_string());
+        }
+        a.merge(&b).unwrap();
+        assert_close(a.estimate(), 10_000, DEFAULT_PRECISION);
+
+        let c = HyperLogLog::new(DEFAULT_PRECISION + 1).unwrap();
+        assert!(a.merge(&c).is_err());
+
+        a.clear();
+        assert!(a.is_empty());
+        assert_eq!(a.estimate(), 0.0);
+    }
+}
@@ -1,7 +1,9 @@
 mod ddsketch;
 mod handle;
+pub mod hyperloglog;
 mod label_filter;
 mod recorder;
+mod tag_policy;
 
 use std::sync::Arc;
 
@@ -14,6 +16,8 @@ use snafu::Snafu;
 pub use crate::metrics::{
     ddsketch::{AgentDDSketch, BinMap, Config},
     handle::{Counter, Handle},
+    hyperloglog::HyperLogLog,
+    tag_policy::set_tag_policy,
 };
 use crate::{
     event::Metric,
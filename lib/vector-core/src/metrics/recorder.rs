@@ -4,7 +4,7 @@ use metrics::{GaugeValue, Key, Recorder, Unit};
 use metrics_util::MetricKind;
 use once_cell::unsync::OnceCell;
 
-use super::Registry;
+use super::{tag_policy, Registry};
 use crate::metrics::handle::Handle;
 
 thread_local!(static LOCAL_REGISTRY: OnceCell<Registry>=OnceCell::new());
@@ -42,11 +42,15 @@ impl VectorRecorder {
 
 impl Recorder for VectorRecorder {
     fn register_counter(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
-        self.with_registry(|r| r.op(MetricKind::Counter, key, |_| {}, Handle::counter));
+        if let Some(key) = tag_policy::apply(MetricKind::Counter, key) {
+            self.with_registry(|r| r.op(MetricKind::Counter, &key, |_| {}, Handle::counter));
+        }
     }
 
     fn register_gauge(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
-        self.with_registry(|r| r.op(MetricKind::Gauge, key, |_| {}, Handle::gauge));
+        if let Some(key) = tag_policy::apply(MetricKind::Gauge, key) {
+            self.with_registry(|r| r.op(MetricKind::Gauge, &key, |_| {}, Handle::gauge));
+        }
     }
 
     fn register_histogram(
@@ -55,39 +59,47 @@ impl Recorder for VectorRecorder {
         _unit: Option<Unit>,
         _description: Option<&'static str>,
     ) {
-        self.with_registry(|r| r.op(MetricKind::Histogram, key, |_| {}, Handle::histogram));
+        if let Some(key) = tag_policy::apply(MetricKind::Histogram, key) {
+            self.with_registry(|r| r.op(MetricKind::Histogram, &key, |_| {}, Handle::histogram));
+        }
     }
 
     fn increment_counter(&self, key: &Key, value: u64) {
-        self.with_registry(|r| {
-            r.op(
-                MetricKind::Counter,
-                key,
-                |handle| handle.increment_counter(value),
-                Handle::counter,
-            );
-        });
+        if let Some(key) = tag_policy::apply(MetricKind::Counter, key) {
+            self.with_registry(|r| {
+                r.op(
+                    MetricKind::Counter,
+                    &key,
+                    |handle| handle.increment_counter(value),
+                    Handle::counter,
+                );
+            });
+        }
     }
 
     fn update_gauge(&self, key: &Key, value: GaugeValue) {
-        self.with_registry(|r| {
-            r.op(
-                MetricKind::Gauge,
-                key,
-                |handle| handle.update_gauge(value),
-                Handle::gauge,
-            );
-        });
+        if let Some(key) = tag_policy::apply(MetricKind::Gauge, key) {
+            self.with_registry(|r| {
+                r.op(
+                    MetricKind::Gauge,
+                    &key,
+                    |handle| handle.update_gauge(value),
+                    Handle::gauge,
+                );
+            });
+        }
     }
 
     fn record_histogram(&self, key: &Key, value: f64) {
-        self.with_registry(|r| {
-            r.op(
-                MetricKind::Histogram,
-                key,
-                |handle| handle.record_histogram(value),
-                Handle::histogram,
-            );
-        });
+        if let Some(key) = tag_policy::apply(MetricKind::Histogram, key) {
+            self.with_registry(|r| {
+                r.op(
+                    MetricKind::Histogram,
+                    &key,
+                    |handle| handle.record_histogram(value),
+                    Handle::histogram,
+                );
+            });
+        }
     }
 }
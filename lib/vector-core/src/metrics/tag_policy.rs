@@ -0,0 +1,129 @@
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+};
+
+use metrics::{Key, Label};
+use metrics_util::MetricKind;
+use once_cell::sync::Lazy;
+
+use crate::config::{TagPolicy, TelemetryTagsConfig};
+
+/// Whether any component has a policy other than emitting its high-cardinality tags, so that
+/// metrics can skip looking at their tags in the common case.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static POLICY: Lazy<RwLock<TelemetryTagsConfig>> =
+    Lazy::new(|| RwLock::new(TelemetryTagsConfig::default()));
+
+/// Sets how the high-cardinality tags of internal metrics are emitted, from the global
+/// `telemetry.tags` option.
+pub fn set_tag_policy(config: &TelemetryTagsConfig) {
+    *POLICY.write().expect("poisoned lock") = config.clone();
+    ENABLED.store(!config.emits_all(), Ordering::Relaxed);
+}
+
+/// Applies the tag policy to the key of a metric, returning the key to record the metric under, or
+/// `None` if the metric should be dropped.
+pub(super) fn apply(kind: MetricKind, key: &Key) -> Option<Cow<'_, Key>> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Some(Cow::Borrowed(key));
+    }
+
+    apply_policy(&POLICY.read().expect("poisoned lock"), kind, key)
+}
+
+fn apply_policy<'a>(
+    config: &TelemetryTagsConfig,
+    kind: MetricKind,
+    key: &'a Key,
+) -> Option<Cow<'a, Key>> {
+    let is_high_cardinality = |label: &Label| {
+        config
+            .high_cardinality
+            .iter()
+            .any(|tag| tag.as_str() == label.key())
+    };
+    if !key.labels().any(is_high_cardinality) {
+        return Some(Cow::Borrowed(key));
+    }
+
+    let component_type = key
+        .labels()
+        .find(|label| label.key() == "component_type")
+        .map(Label::value);
+    match config.policy_for(component_type) {
+        TagPolicy::Emit => Some(Cow::Borrowed(key)),
+        // The series of a gauge each hold a value of their own, such as the size of a file, which
+        // can't be combined into a meaningful one, so they're dropped rather than overwriting each
+        // other.
+        TagPolicy::Aggregate if kind == MetricKind::Gauge => None,
+        TagPolicy::Aggregate => {
+            let labels = key
+                .labels()
+                .filter(|label| !is_high_cardinality(label))
+                .cloned()
+                .collect::<Vec<_>>();
+            Some(Cow::Owned(Key::from_parts(key.name().to_owned(), labels)))
+        }
+        TagPolicy::Drop => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn key(labels: &[(&'static str, &'static str)]) -> Key {
+        let labels = labels
+            .iter()
+            .map(|(key, value)| Label::new(*key, *value))
+            .collect::<Vec<_>>();
+        Key::from_parts("test", labels)
+    }
+
+    #[test]
+    fn applies_policy_by_component_type() {
+        let config = TelemetryTagsConfig {
+            policy: TagPolicy::Aggregate,
+            components: BTreeMap::from([
+                ("file".to_owned(), TagPolicy::Drop),
+                ("socket".to_owned(), TagPolicy::Emit),
+            ]),
+            ..Default::default()
+        };
+
+        let untagged = key(&[("component_type", "file")]);
+        assert_eq!(
+            apply_policy(&config, MetricKind::Counter, &untagged).as_deref(),
+            Some(&untagged)
+        );
+
+        let file = key(&[("component_type", "file"), ("file", "/var/log/a.log")]);
+        assert_eq!(apply_policy(&config, MetricKind::Counter, &file), None);
+
+        let socket = key(&[("component_type", "socket"), ("peer_addr", "127.0.0.1")]);
+        assert_eq!(
+            apply_policy(&config, MetricKind::Counter, &socket).as_deref(),
+            Some(&socket)
+        );
+
+        let kafka = key(&[("component_type", "kafka"), ("partition", "3")]);
+        assert_eq!(
+            apply_policy(&config, MetricKind::Counter, &kafka).as_deref(),
+            Some(&key(&[("component_type", "kafka")]))
+        );
+
+        // Gauges with high-cardinality tags are dropped rather than aggregated.
+        assert_eq!(apply_policy(&config, MetricKind::Gauge, &kafka), None);
+        assert_eq!(
+            apply_policy(&config, MetricKind::Gauge, &untagged).as_deref(),
+            Some(&untagged)
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use core::TargetValueRef;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{self, Read},
     iter::IntoIterator,
@@ -9,6 +9,7 @@ use std::{
 
 use ::value::Value;
 use clap::Parser;
+use enrichment::TableRegistry;
 use lookup::LookupBuf;
 use value::Secrets;
 use vector_common::TimeZone;
@@ -51,6 +52,11 @@ pub struct Opts {
     // Should the CLI emit warnings
     #[clap(long = "print-warnings")]
     print_warnings: bool,
+
+    /// A secret to make available to the program through `get_secret`, as `KEY=VALUE`. Can be
+    /// repeated.
+    #[clap(short, long = "secret", value_name = "KEY=VALUE")]
+    secrets: Vec<String>,
 }
 
 impl Opts {
@@ -88,6 +94,36 @@ impl Opts {
         }
     }
 
+    /// The secrets given with `--secret`, by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a secret isn't of the form `KEY=VALUE`.
+    pub fn secrets(&self) -> Result<BTreeMap<String, String>, Error> {
+        self.secrets
+            .iter()
+            .map(|secret| {
+                secret
+                    .split_once('=')
+                    .map(|(key, value)| (key.to_owned(), value.to_owned()))
+                    .ok_or_else(|| {
+                        Error::Parse(format!(
+                            "unable to parse secret, expected KEY=VALUE: {}",
+                            secret
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    fn target_secrets(&self) -> Result<Secrets, Error> {
+        let mut secrets = Secrets::new();
+        for (key, value) in self.secrets()? {
+            secrets.insert(key, value);
+        }
+        Ok(secrets)
+    }
+
     fn should_open_repl(&self) -> bool {
         self.program.is_none() && self.program_file.is_none()
     }
@@ -95,7 +131,18 @@ impl Opts {
 
 #[must_use]
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
-    match run(opts) {
+    cmd_with_enrichment_tables(opts, &TableRegistry::default())
+}
+
+/// Runs the CLI with the given enrichment tables available to programs. The tables must be loaded
+/// into the registry, and `finish_load` must not have been called yet, as programs add the indexes
+/// they need when compiled.
+#[must_use]
+pub fn cmd_with_enrichment_tables(
+    opts: &Opts,
+    enrichment_tables: &TableRegistry,
+) -> exitcode::ExitCode {
+    match run(opts, enrichment_tables) {
         Ok(_) => exitcode::OK,
         Err(err) => {
             #[allow(clippy::print_stderr)]
@@ -107,8 +154,9 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
     }
 }
 
-fn run(opts: &Opts) -> Result<(), Error> {
+fn run(opts: &Opts, enrichment_tables: &TableRegistry) -> Result<(), Error> {
     let tz = opts.timezone()?;
+    let secrets = opts.target_secrets()?;
     // Run the REPL if no program or program file is specified
     if opts.should_open_repl() {
         // If an input file is provided, use that for the REPL objects, otherwise provide a
@@ -119,7 +167,7 @@ fn run(opts: &Opts) -> Result<(), Error> {
             default_objects()
         };
 
-        repl(repl_objects, tz, opts.runtime)
+        repl(repl_objects, &secrets, tz, opts.runtime, enrichment_tables)
     } else {
         let objects = opts.read_into_objects()?;
         let source = opts.read_program()?;
@@ -127,13 +175,19 @@ fn run(opts: &Opts) -> Result<(), Error> {
         let mut external = ExternalEnv::default();
         // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
         external.set_read_only_metadata_path(LookupBuf::from("vector"), true);
+        external.set_external_context(enrichment_tables.clone());
+
+        let mut functions = stdlib::all();
+        functions.extend(enrichment::vrl_functions());
 
-        let (program, warnings) =
-            vrl::compile_with_external(&source, &stdlib::all(), &mut external).map_err(
-                |diagnostics| {
-                    Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
-                },
-            )?;
+        // Make sure the registry is in its loading stage, so the program can add indexes.
+        enrichment_tables.load(HashMap::new());
+        let compiled = vrl::compile_with_external(&source, &functions, &mut external);
+        enrichment_tables.finish_load();
+
+        let (program, warnings) = compiled.map_err(|diagnostics| {
+            Error::Parse(Formatter::new(&source, diagnostics).colored().to_string())
+        })?;
 
         #[allow(clippy::print_stderr)]
         if opts.print_warnings {
@@ -143,7 +197,7 @@ fn run(opts: &Opts) -> Result<(), Error> {
 
         for mut object in objects {
             let mut metadata = Value::Object(BTreeMap::new());
-            let mut secrets = Secrets::new();
+            let mut secrets = secrets.clone();
             let mut target = TargetValueRef {
                 value: &mut object,
                 metadata: &mut metadata,
@@ -174,7 +228,13 @@ fn run(opts: &Opts) -> Result<(), Error> {
 
 #[cfg(feature = "repl")]
 #[allow(clippy::unnecessary_wraps)]
-fn repl(objects: Vec<Value>, timezone: TimeZone, vrl_runtime: VrlRuntime) -> Result<(), Error> {
+fn repl(
+    objects: Vec<Value>,
+    secrets: &Secrets,
+    timezone: TimeZone,
+    vrl_runtime: VrlRuntime,
+    enrichment_tables: &TableRegistry,
+) -> Result<(), Error> {
     use core::TargetValue;
 
     let objects = objects
@@ -182,17 +242,23 @@ fn repl(objects: Vec<Value>, timezone: TimeZone, vrl_runtime: VrlRuntime) -> Res
         .map(|value| TargetValue {
             value,
             metadata: Value::Object(BTreeMap::new()),
-            secrets: Secrets::new(),
+            secrets: secrets.clone(),
         })
         .collect();
 
-    repl::run(objects, timezone, vrl_runtime);
+    repl::run(objects, secrets, timezone, vrl_runtime, enrichment_tables);
     Ok(())
 }
 
 #[cfg(not(feature = "repl"))]
 #[allow(clippy::needless_pass_by_value)]
-fn repl(_objects: Vec<Value>, _timezone: TimeZone, _vrl_runtime: VrlRuntime) -> Result<(), Error> {
+fn repl(
+    _objects: Vec<Value>,
+    _secrets: &Secrets,
+    _timezone: TimeZone,
+    _vrl_runtime: VrlRuntime,
+    _enrichment_tables: &TableRegistry,
+) -> Result<(), Error> {
     Err(Error::ReplFeature)
 }
 
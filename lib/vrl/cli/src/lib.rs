@@ -16,7 +16,7 @@ pub mod cmd;
 #[cfg(feature = "repl")]
 mod repl;
 
-pub use cmd::{cmd, Opts};
+pub use cmd::{cmd, cmd_with_enrichment_tables, Opts};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -1,7 +1,11 @@
 use core::TargetValue;
-use std::borrow::Cow::{self, Borrowed, Owned};
+use std::{
+    borrow::Cow::{self, Borrowed, Owned},
+    collections::HashMap,
+};
 
 use ::value::Value;
+use enrichment::TableRegistry;
 use indoc::indoc;
 use lookup::LookupBuf;
 use once_cell::sync::Lazy;
@@ -46,12 +50,19 @@ const RESERVED_TERMS: &[&str] = &[
     "help docs",
 ];
 
-pub(crate) fn run(mut objects: Vec<TargetValue>, timezone: TimeZone, vrl_runtime: VrlRuntime) {
+pub(crate) fn run(
+    mut objects: Vec<TargetValue>,
+    secrets: &Secrets,
+    timezone: TimeZone,
+    vrl_runtime: VrlRuntime,
+    enrichment_tables: &TableRegistry,
+) {
     let mut index = 0;
     let func_docs_regex = Regex::new(r"^help\sdocs\s(\w{1,})$").unwrap();
     let error_docs_regex = Regex::new(r"^help\serror\s(\w{1,})$").unwrap();
 
     let mut external_state = state::ExternalEnv::default();
+    external_state.set_external_context(enrichment_tables.clone());
     let mut local_state = state::LocalEnv::default();
     let mut rt = Runtime::new(state::Runtime::default());
     let mut rl = Editor::<Repl>::new();
@@ -92,7 +103,7 @@ pub(crate) fn run(mut objects: Vec<TargetValue>, timezone: TimeZone, vrl_runtime
                             objects.push(TargetValue {
                                 value: Value::Null,
                                 metadata: Value::Object(BTreeMap::new()),
-                                secrets: Secrets::new(),
+                                secrets: secrets.clone(),
                             });
                         }
 
@@ -120,6 +131,7 @@ pub(crate) fn run(mut objects: Vec<TargetValue>, timezone: TimeZone, vrl_runtime
                     std::mem::take(&mut local_state),
                     timezone,
                     vrl_runtime,
+                    enrichment_tables,
                 );
 
                 let _v = std::mem::replace(&mut local_state, local);
@@ -154,14 +166,22 @@ fn resolve(
     local: state::LocalEnv,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
+    enrichment_tables: &TableRegistry,
 ) -> (state::LocalEnv, Result<Value, String>) {
     let mut functions = stdlib::all();
+    functions.extend(enrichment::vrl_functions());
     functions.extend(vector_vrl_functions::vrl_functions());
 
     // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
     external.set_read_only_metadata_path(LookupBuf::from("vector"), true);
 
-    let program = match vrl::compile_with_state(program, &functions, external, local.clone()) {
+    // Each line is compiled as a new program, which may add indexes to the enrichment tables, so
+    // the tables are moved back into their loading stage for the duration of the compilation.
+    enrichment_tables.load(HashMap::new());
+    let compiled = vrl::compile_with_state(program, &functions, external, local.clone());
+    enrichment_tables.finish_load();
+
+    let program = match compiled {
         Ok((program, _)) => program,
         Err(diagnostics) => {
             return (
@@ -212,6 +232,7 @@ impl Repl {
 fn initial_hints() -> Vec<&'static str> {
     stdlib::all()
         .into_iter()
+        .chain(enrichment::vrl_functions())
         .chain(vrl_functions())
         .map(|f| f.identifier())
         .chain(RESERVED_TERMS.iter().copied())
@@ -8,6 +8,7 @@ use crate::{
         Container, Error, Expr, Expression, FunctionArgument, FunctionCall, Group, IfStatement,
         Literal, Noop, Not, Object, Op, Predicate, Query, Target, Unary, Variable,
     },
+    lint::{self, Assignments, Place},
     parser::ast::RootExpr,
     program::ProgramInfo,
     state::{ExternalEnv, LocalEnv},
@@ -38,6 +39,10 @@ pub struct Compiler<'a> {
     /// back to `None`, if the parent expression of a fallible expression
     /// nullifies the fallibility of that expression.
     fallible_expression_error: Option<Box<dyn DiagnosticMessage>>,
+
+    /// Track the assignments whose value hasn't been read yet, to warn about
+    /// those that are overwritten before being read.
+    assignments: Assignments,
 }
 
 impl<'a> Compiler<'a> {
@@ -57,6 +62,7 @@ impl<'a> Compiler<'a> {
             external_assignments: vec![],
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
+            assignments: Assignments::default(),
         };
         let expressions = compiler.compile_root_exprs(ast, external);
 
@@ -88,7 +94,9 @@ impl<'a> Compiler<'a> {
     ) -> Option<Vec<Expr>> {
         let mut exprs = vec![];
         for node in nodes {
+            let (span, tracked) = (node.span(), is_tracked_assignment(&node));
             let expr = self.compile_expr(node, external)?;
+            self.check_overwrites(span, tracked, &expr);
             let type_def = expr.type_def((&self.local, external));
             exprs.push(expr);
 
@@ -228,12 +236,14 @@ impl<'a> Compiler<'a> {
                 RootExpr::Expr(node_expr) => {
                     self.fallible_expression_error = None;
 
+                    let (span, tracked) = (node_expr.span(), is_tracked_assignment(&node_expr));
                     if let Some(expr) = self.compile_expr(node_expr, external) {
                         if let Some(error) = self.fallible_expression_error.take() {
                             self.diagnostics.push(error);
                         }
 
                         if terminated_state.is_none() {
+                            self.check_overwrites(span, tracked, &expr);
                             let type_def = expr.type_def((&self.local, external));
                             node_exprs.push(expr);
                             // an expression that has the "never" type is a terminating expression
@@ -270,10 +280,17 @@ impl<'a> Compiler<'a> {
         // program.
         let local_snapshot = self.local.clone();
 
+        // Assignments within the block don't always run, so they can't
+        // overwrite those made before the block.
+        self.assignments.enter_scope();
+
         // We can now start compiling the expressions within the block, which
         // will use the existing local state of the compiler, as blocks have
         // access to any state of their parent expressions.
-        let exprs = match self.compile_exprs(node.into_inner().into_iter(), external) {
+        let exprs = self.compile_exprs(node.into_inner().into_iter(), external);
+        self.assignments.exit_scope();
+
+        let exprs = match exprs {
             Some(exprs) => exprs,
             None => {
                 self.local = local_snapshot.apply_child_scope(self.local.clone());
@@ -337,11 +354,28 @@ impl<'a> Compiler<'a> {
             alternative,
         } = node.into_inner();
 
-        let predicate = self
-            .compile_predicate(predicate, external)?
+        let predicate_span = predicate.span();
+        let (predicate, known_result) = self.compile_predicate(predicate, external)?;
+        let predicate = predicate
             .map_err(|err| self.diagnostics.push(Box::new(err)))
             .ok()?;
 
+        // Warn about the branch that never runs if the type of the value
+        // checked by the predicate is known.
+        let unreachable_span = match (known_result, &alternative) {
+            (Some(true), Some(block)) => Some(block.span()),
+            (Some(false), _) => Some(consequent.span()),
+            _ => None,
+        };
+        if let Some(branch_span) = unreachable_span {
+            self.diagnostics
+                .push(Box::new(lint::Warning::UnreachableBranch {
+                    predicate_span,
+                    branch_span,
+                    predicate: known_result.unwrap_or_default(),
+                }));
+        }
+
         let original_locals = self.local.clone();
         let original_external = external.target().clone();
 
@@ -396,7 +430,7 @@ impl<'a> Compiler<'a> {
         &mut self,
         node: Node<ast::Predicate>,
         external: &mut ExternalEnv,
-    ) -> Option<predicate::Result> {
+    ) -> Option<(predicate::Result, Option<bool>)> {
         use ast::Predicate::{Many, One};
 
         let (span, predicate) = node.take();
@@ -406,11 +440,39 @@ impl<'a> Compiler<'a> {
             Many(nodes) => self.compile_exprs(nodes, external)?,
         };
 
-        Some(Predicate::new(
+        let known_result = match exprs.as_slice() {
+            [expr] => self.type_check_result(expr, external),
+            _ => None,
+        };
+
+        let predicate = Predicate::new(
             Node::new(span, exprs),
             (&self.local, external),
             self.fallible_expression_error.as_deref(),
-        ))
+        );
+
+        Some((predicate, known_result))
+    }
+
+    /// Returns the result of a type checking function call, such as
+    /// `is_string(.foo)`, if it is known from the type of its argument.
+    #[cfg(feature = "expr-function_call")]
+    fn type_check_result(&self, expr: &Expr, external: &ExternalEnv) -> Option<bool> {
+        match expr {
+            Expr::FunctionCall(call) => match call.arguments() {
+                [argument] => {
+                    let type_def = argument.inner().expr().type_def((&self.local, external));
+                    lint::type_check_result(call.ident(), type_def.kind())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn type_check_result(&self, _: &Expr, _: &ExternalEnv) -> Option<bool> {
+        None
     }
 
     #[cfg(feature = "expr-op")]
@@ -603,6 +665,7 @@ impl<'a> Compiler<'a> {
         // potential external optimizations.
         if let Target::External = target {
             self.external_queries.push(path.clone());
+            self.assignments.read(&Place::External(path.clone()));
         }
 
         Some(Query::new(target, path))
@@ -661,6 +724,7 @@ impl<'a> Compiler<'a> {
         // See: https://github.com/vectordotdev/vector/issues/12547
         if ident.as_deref() == "get" {
             self.external_queries.push(LookupBuf::root());
+            self.assignments.read(&Place::External(LookupBuf::root()));
         }
 
         let arguments = arguments
@@ -773,6 +837,9 @@ impl<'a> Compiler<'a> {
             return None;
         }
 
+        self.assignments
+            .read(&Place::Internal(ident.clone(), LookupBuf::root()));
+
         Variable::new(span, ident, &self.local)
             .map_err(|err| self.diagnostics.push(Box::new(err)))
             .ok()
@@ -852,6 +919,26 @@ impl<'a> Compiler<'a> {
         None
     }
 
+    /// Warns about the previous assignments overwritten by the compiled
+    /// statement, if it is an assignment.
+    #[cfg(feature = "expr-assignment")]
+    fn check_overwrites(&mut self, span: Span, tracked: bool, expr: &Expr) {
+        if let Expr::Assignment(assignment) = expr {
+            for place in assignment
+                .targets()
+                .into_iter()
+                .filter_map(Place::from_target)
+            {
+                for warning in self.assignments.write(place, span, tracked) {
+                    self.diagnostics.push(Box::new(warning));
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "expr-assignment"))]
+    fn check_overwrites(&mut self, _: Span, _: bool, _: &Expr) {}
+
     #[cfg(feature = "expr-assignment")]
     fn skip_missing_assignment_target(&mut self, target: ast::AssignmentTarget) {
         let query = match target {
@@ -871,3 +958,14 @@ impl<'a> Compiler<'a> {
         self.skip_missing_query_target.push(query);
     }
 }
+
+/// Whether an overwrite of the value assigned by the statement is reported.
+///
+/// The error target of infallible assignments is often left unused, so these
+/// are not.
+fn is_tracked_assignment(node: &Node<ast::Expr>) -> bool {
+    matches!(
+        node.inner(),
+        ast::Expr::Assignment(assignment) if matches!(assignment.inner(), ast::Assignment::Single { .. })
+    )
+}
@@ -567,6 +567,14 @@ impl FunctionCall {
         Ok(result)
     }
 
+    pub(crate) fn ident(&self) -> &'static str {
+        self.ident
+    }
+
+    pub(crate) fn arguments(&self) -> &[Node<FunctionArgument>] {
+        &self.arguments
+    }
+
     #[must_use]
     pub fn arguments_fmt(&self) -> Vec<String> {
         self.arguments
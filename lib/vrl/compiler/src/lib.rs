@@ -30,6 +30,7 @@
 
 mod compiler;
 mod context;
+mod lint;
 mod program;
 mod test_util;
 
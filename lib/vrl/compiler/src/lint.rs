@@ -0,0 +1,315 @@
+use diagnostic::{DiagnosticMessage, Label, Note, Severity};
+use lookup::{LookupBuf, SegmentBuf};
+use value::Kind;
+
+use crate::{expression::assignment, parser::Ident, Span};
+
+/// A variable or event path written to by an assignment.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Place {
+    Internal(Ident, LookupBuf),
+    External(LookupBuf),
+}
+
+impl Place {
+    pub(crate) fn from_target(target: assignment::Target) -> Option<Self> {
+        match target {
+            assignment::Target::Noop => None,
+            assignment::Target::Internal(ident, path) => Some(Self::Internal(ident, path)),
+            assignment::Target::External(path) => Some(Self::External(path)),
+        }
+    }
+
+    fn split(&self) -> (Option<&Ident>, &LookupBuf) {
+        match self {
+            Self::Internal(ident, path) => (Some(ident), path),
+            Self::External(path) => (None, path),
+        }
+    }
+
+    /// Whether reading this place can observe a value written to `other`.
+    fn overlaps(&self, other: &Self) -> bool {
+        let (ident, path) = self.split();
+        let (other_ident, other_path) = other.split();
+
+        ident == other_ident
+            && path
+                .iter()
+                .zip(other_path.iter())
+                .all(|(segment, other)| !are_distinct(segment, other))
+    }
+
+    /// Whether writing to this place replaces any value written to `other`.
+    fn covers(&self, other: &Self) -> bool {
+        let (ident, path) = self.split();
+        let (other_ident, other_path) = other.split();
+
+        ident == other_ident
+            && path.len() <= other_path.len()
+            && path
+                .iter()
+                .zip(other_path.iter())
+                .all(|(segment, other)| are_same(segment, other))
+    }
+
+    /// Whether the segments of the path always designate the same value. Coalesced fields depend on
+    /// the fields present, and negative indices on the length of the array.
+    fn is_static(&self) -> bool {
+        self.split().1.iter().all(|segment| match segment {
+            SegmentBuf::Field(_) => true,
+            SegmentBuf::Index(index) => *index >= 0,
+            SegmentBuf::Coalesce(_) => false,
+        })
+    }
+}
+
+fn are_same(segment: &SegmentBuf, other: &SegmentBuf) -> bool {
+    match (segment, other) {
+        (SegmentBuf::Field(field), SegmentBuf::Field(other)) => field.name == other.name,
+        (SegmentBuf::Index(index), SegmentBuf::Index(other)) => index == other && *index >= 0,
+        _ => false,
+    }
+}
+
+fn are_distinct(segment: &SegmentBuf, other: &SegmentBuf) -> bool {
+    match (segment, other) {
+        (SegmentBuf::Field(field), SegmentBuf::Field(other)) => field.name != other.name,
+        (SegmentBuf::Index(index), SegmentBuf::Index(other)) => {
+            index != other && *index >= 0 && *other >= 0
+        }
+        (SegmentBuf::Field(_), SegmentBuf::Index(_))
+        | (SegmentBuf::Index(_), SegmentBuf::Field(_)) => true,
+        _ => false,
+    }
+}
+
+#[derive(Debug)]
+struct Write {
+    place: Place,
+    span: Span,
+}
+
+/// Tracks the assignments whose value hasn't been read yet, to warn about those overwritten
+/// before being read.
+///
+/// Each block has its own scope, as assignments in a block don't always run. Reads clear the
+/// assignments of all scopes, while assignments only overwrite those of the current scope.
+#[derive(Debug)]
+pub(crate) struct Assignments {
+    scopes: Vec<Vec<Write>>,
+}
+
+impl Default for Assignments {
+    fn default() -> Self {
+        Self {
+            scopes: vec![vec![]],
+        }
+    }
+}
+
+impl Assignments {
+    pub(crate) fn enter_scope(&mut self) {
+        self.scopes.push(vec![]);
+    }
+
+    pub(crate) fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    pub(crate) fn read(&mut self, place: &Place) {
+        for scope in &mut self.scopes {
+            scope.retain(|write| !place.overlaps(&write.place));
+        }
+    }
+
+    /// Records an unconditional assignment, returning warnings for the previous assignments it
+    /// overwrites. Unless `track` is set, the assignment itself isn't reported when overwritten.
+    pub(crate) fn write(&mut self, place: Place, span: Span, track: bool) -> Vec<Warning> {
+        let scope = self.scopes.last_mut().expect("at least one scope");
+
+        let mut warnings = vec![];
+        if place.is_static() {
+            scope.retain(|write| {
+                let overwritten = place.covers(&write.place);
+                if overwritten {
+                    warnings.push(Warning::UnusedAssignment {
+                        span: write.span,
+                        overwrite_span: span,
+                    });
+                }
+                !overwritten
+            });
+        }
+
+        if track && place.is_static() {
+            scope.push(Write { place, span });
+        }
+
+        warnings
+    }
+}
+
+/// Returns the result of calls to the type checking functions, such as `is_string`, when it's
+/// known from the type of their argument.
+pub(crate) fn type_check_result(function: &str, kind: &Kind) -> Option<bool> {
+    let (is, contains) = match function {
+        "is_array" => (kind.is_array(), kind.contains_array()),
+        "is_boolean" => (kind.is_boolean(), kind.contains_boolean()),
+        "is_float" => (kind.is_float(), kind.contains_float()),
+        "is_integer" => (kind.is_integer(), kind.contains_integer()),
+        "is_null" => (kind.is_null(), kind.contains_null()),
+        "is_object" => (kind.is_object(), kind.contains_object()),
+        "is_regex" => (kind.is_regex(), kind.contains_regex()),
+        "is_string" => (kind.is_bytes(), kind.contains_bytes()),
+        "is_timestamp" => (kind.is_timestamp(), kind.contains_timestamp()),
+        _ => return None,
+    };
+
+    if kind.is_never() {
+        None
+    } else if is {
+        Some(true)
+    } else if !contains {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Warning {
+    #[error("unused assignment")]
+    UnusedAssignment { span: Span, overwrite_span: Span },
+
+    #[error("unreachable branch")]
+    UnreachableBranch {
+        predicate_span: Span,
+        branch_span: Span,
+        predicate: bool,
+    },
+}
+
+impl DiagnosticMessage for Warning {
+    fn code(&self) -> usize {
+        use Warning::{UnreachableBranch, UnusedAssignment};
+
+        match self {
+            UnusedAssignment { .. } => 800,
+            UnreachableBranch { .. } => 801,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Warning::{UnreachableBranch, UnusedAssignment};
+
+        match self {
+            UnusedAssignment {
+                span,
+                overwrite_span,
+            } => vec![
+                Label::primary("this value is overwritten before being read", span),
+                Label::context("overwritten here", overwrite_span),
+            ],
+            UnreachableBranch {
+                predicate_span,
+                branch_span,
+                predicate,
+            } => vec![
+                Label::context(
+                    format!("the type of the value makes this always {}", predicate),
+                    predicate_span,
+                ),
+                Label::primary("this block never runs", branch_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        vec![Note::SeeCodeDocs(self.code())]
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn external(path: &str) -> Place {
+        Place::External(LookupBuf::from_str(path).unwrap())
+    }
+
+    #[test]
+    fn overlaps_paths_sharing_a_prefix() {
+        assert!(external("foo").overlaps(&external("foo.bar")));
+        assert!(external("foo.bar").overlaps(&external("foo")));
+        assert!(external("foo[1]").overlaps(&external("foo[-1]")));
+        assert!(external("(foo|bar)").overlaps(&external("bar.baz")));
+        assert!(!external("foo.bar").overlaps(&external("foo.baz")));
+        assert!(!external("foo[0]").overlaps(&external("foo[1]")));
+        assert!(!Place::Internal(Ident::new("foo"), LookupBuf::root()).overlaps(&external("foo")));
+    }
+
+    #[test]
+    fn covers_child_paths() {
+        assert!(external("foo").covers(&external("foo.bar")));
+        assert!(external("foo[0]").covers(&external("foo[0]")));
+        assert!(!external("foo.bar").covers(&external("foo")));
+        assert!(!external("foo[-1]").covers(&external("foo[-1]")));
+    }
+
+    #[test]
+    fn warns_about_overwritten_assignments() {
+        let mut assignments = Assignments::default();
+        let first = Span::new(0, 8);
+        let second = Span::new(9, 17);
+
+        assert!(assignments.write(external("foo"), first, true).is_empty());
+        let warnings = assignments.write(external("foo"), second, true);
+        assert!(matches!(
+            warnings.as_slice(),
+            [Warning::UnusedAssignment { span, overwrite_span }]
+                if *span == first && *overwrite_span == second
+        ));
+
+        assignments.read(&external("foo.bar"));
+        assert!(assignments.write(external("foo"), first, true).is_empty());
+    }
+
+    #[test]
+    fn ignores_assignments_of_other_scopes() {
+        let mut assignments = Assignments::default();
+
+        assert!(assignments
+            .write(external("foo"), Span::new(0, 1), true)
+            .is_empty());
+        assignments.enter_scope();
+        assert!(assignments
+            .write(external("foo"), Span::new(1, 2), true)
+            .is_empty());
+        assignments.exit_scope();
+        assert_eq!(
+            assignments
+                .write(external("foo"), Span::new(2, 3), true)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn resolves_type_checks() {
+        assert_eq!(type_check_result("is_string", &Kind::bytes()), Some(true));
+        assert_eq!(
+            type_check_result("is_string", &Kind::integer()),
+            Some(false)
+        );
+        assert_eq!(
+            type_check_result("is_string", &Kind::bytes().or_null()),
+            None
+        );
+        assert_eq!(type_check_result("is_string", &Kind::any()), None);
+        assert_eq!(type_check_result("upcase", &Kind::bytes()), None);
+    }
+}
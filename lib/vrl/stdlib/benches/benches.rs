@@ -10,7 +10,9 @@ criterion_group!(
     // encapsulates CI noise we saw in
     // https://github.com/vectordotdev/vector/pull/6408
     config = Criterion::default().noise_threshold(0.05);
-    targets = array,
+    targets = add_business_days,
+              add_duration,
+              array,
               assert,
               assert_eq,
               r#bool,
@@ -54,6 +56,7 @@ criterion_group!(
               ipv6_to_ipv4,
               is_array,
               is_boolean,
+              is_business_day,
               is_empty,
               is_float,
               is_integer,
@@ -71,6 +74,7 @@ criterion_group!(
               match_any,
               match_array,
               match_datadog_query,
+              matches_rules,
               md5,
               merge,
               // TODO: value is dynamic so we cannot assert equality
@@ -83,6 +87,7 @@ criterion_group!(
               parse_common_log,
               parse_csv,
               parse_duration,
+              parse_duration_iso8601,
               parse_glog,
               parse_grok,
               parse_groks,
@@ -118,6 +123,7 @@ criterion_group!(
               strip_ansi_escape_codes,
               strip_whitespace,
               strlen,
+              subtract_duration,
               tally,
               tally_value,
               timestamp,
@@ -132,6 +138,7 @@ criterion_group!(
               to_timestamp,
               to_unix_timestamp,
               truncate,
+              truncate_timestamp,
               unique,
               // TODO: Cannot pass a Path to bench_function
               //unnest
@@ -159,6 +166,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    add_business_days => vrl_stdlib::AddBusinessDays;
+
+    literal {
+        args: func_args![value: Utc.ymd(2021, 2, 12).and_hms(10, 0, 0), days: 1],
+        want: Ok(Utc.ymd(2021, 2, 15).and_hms(10, 0, 0)),
+    }
+}
+
+bench_function! {
+    add_duration => vrl_stdlib::AddDuration;
+
+    literal {
+        args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0), duration: "1h30m"],
+        want: Ok(Utc.ymd(2021, 2, 11).and_hms(1, 2, 0)),
+    }
+}
+
 bench_function! {
     append => vrl_stdlib::Append;
 
@@ -696,6 +721,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    is_business_day => vrl_stdlib::IsBusinessDay;
+
+    weekday {
+        args: func_args![value: Utc.ymd(2021, 2, 12).and_hms(10, 0, 0)],
+        want: Ok(true),
+    }
+}
+
 bench_function! {
     is_empty => vrl_stdlib::IsEmpty;
 
@@ -1066,6 +1100,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    matches_rules => vrl_stdlib::MatchesRules;
+
+    simple {
+        args: func_args![value: "foo 2 bar", patterns: vec![Regex::new(r"foo \d bar").unwrap(), Regex::new(r"baz").unwrap()]],
+        want: Ok(value!([0])),
+    }
+}
+
 bench_function! {
     md5  => vrl_stdlib::Md5;
 
@@ -1354,6 +1397,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_duration_iso8601 => vrl_stdlib::ParseDurationIso8601;
+
+    literal {
+        args: func_args![value: "PT1H30M", unit: "m"],
+        want: Ok(90.0),
+    }
+}
+
 bench_function! {
     parse_glog  => vrl_stdlib::ParseGlog;
 
@@ -2128,6 +2180,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    subtract_duration => vrl_stdlib::SubtractDuration;
+
+    literal {
+        args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0), duration: "90s"],
+        want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 30, 30)),
+    }
+}
+
 bench_function! {
     tag_types_externally => vrl_stdlib::TagTypesExternally;
 
@@ -2411,6 +2472,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    truncate_timestamp => vrl_stdlib::TruncateTimestamp;
+
+    literal {
+        args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512), unit: "hour"],
+        want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 0, 0)),
+    }
+}
+
 bench_function! {
     unique => vrl_stdlib::Unique;
 
@@ -0,0 +1,175 @@
+use ::value::Value;
+use chrono::{DateTime, Datelike, Duration, Utc, Weekday};
+use vrl::prelude::*;
+
+/// More business days than there are days between the minimum and maximum timestamps.
+const MAX_DAYS: u64 = 100_000_000;
+
+fn add_business_days(value: Value, days: Value) -> Resolved {
+    let ts = value.try_timestamp()?;
+    let days = days.try_integer()?;
+
+    if days.unsigned_abs() > MAX_DAYS {
+        return Err("timestamp is out of range".into());
+    }
+
+    shift(ts, days)
+        .map(Into::into)
+        .ok_or_else(|| "timestamp is out of range".into())
+}
+
+fn shift(ts: DateTime<Utc>, days: i64) -> Option<DateTime<Utc>> {
+    let step = days.signum();
+    let mut ts = ts;
+
+    // Days are counted from the business day preceding the weekend when moving forward, and
+    // from the one following it when moving backward, so that Saturday plus one day is Monday.
+    while step != 0 && is_weekend(ts.weekday()) {
+        ts = ts.checked_add_signed(Duration::days(-step))?;
+    }
+
+    // Whole weeks keep the day of the week.
+    ts = ts.checked_add_signed(Duration::days(days / 5 * 7))?;
+    for _ in 0..(days % 5).abs() {
+        ts = ts.checked_add_signed(Duration::days(step))?;
+        while is_weekend(ts.weekday()) {
+            ts = ts.checked_add_signed(Duration::days(step))?;
+        }
+    }
+
+    Some(ts)
+}
+
+fn is_weekend(weekday: Weekday) -> bool {
+    matches!(weekday, Weekday::Sat | Weekday::Sun)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AddBusinessDays;
+
+impl Function for AddBusinessDays {
+    fn identifier(&self) -> &'static str {
+        "add_business_days"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "days",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let days = arguments.required("days");
+
+        Ok(Box::new(AddBusinessDaysFn { value, days }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "add business days",
+                source: r#"add_business_days!(t'2021-02-12T10:00:00Z', 1)"#,
+                result: Ok("t'2021-02-15T10:00:00Z'"),
+            },
+            Example {
+                title: "subtract business days",
+                source: r#"add_business_days!(t'2021-02-15T10:00:00Z', -6)"#,
+                result: Ok("t'2021-02-05T10:00:00Z'"),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AddBusinessDaysFn {
+    value: Box<dyn Expression>,
+    days: Box<dyn Expression>,
+}
+
+impl Expression for AddBusinessDaysFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let days = self.days.resolve(ctx)?;
+
+        add_business_days(value, days)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    test_function![
+        add_business_days => AddBusinessDays;
+
+        zero {
+            args: func_args![value: Utc.ymd(2021, 2, 13).and_hms(10, 0, 0),
+                             days: 0],
+            want: Ok(Utc.ymd(2021, 2, 13).and_hms(10, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        over_weekend {
+            args: func_args![value: Utc.ymd(2021, 2, 12).and_hms(10, 0, 0),
+                             days: 1],
+            want: Ok(Utc.ymd(2021, 2, 15).and_hms(10, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        whole_weeks {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(10, 0, 0),
+                             days: 10],
+            want: Ok(Utc.ymd(2021, 2, 24).and_hms(10, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        from_saturday {
+            args: func_args![value: Utc.ymd(2021, 2, 13).and_hms(10, 0, 0),
+                             days: 5],
+            want: Ok(Utc.ymd(2021, 2, 19).and_hms(10, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        backward {
+            args: func_args![value: Utc.ymd(2021, 2, 15).and_hms(10, 0, 0),
+                             days: -6],
+            want: Ok(Utc.ymd(2021, 2, 5).and_hms(10, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        backward_from_sunday {
+            args: func_args![value: Utc.ymd(2021, 2, 14).and_hms(10, 0, 0),
+                             days: -1],
+            want: Ok(Utc.ymd(2021, 2, 12).and_hms(10, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        out_of_range {
+            args: func_args![value: Utc.ymd(2021, 2, 15).and_hms(10, 0, 0),
+                             days: i64::MAX],
+            want: Err("timestamp is out of range"),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+    ];
+}
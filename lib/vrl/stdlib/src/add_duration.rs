@@ -0,0 +1,131 @@
+use ::value::Value;
+use chrono::Duration;
+use vrl::prelude::*;
+
+use crate::duration_util::parse_nanoseconds;
+
+fn add_duration(value: Value, duration: Value) -> Resolved {
+    let ts = value.try_timestamp()?;
+    let duration = duration.try_bytes_utf8_lossy()?;
+    let nanoseconds = parse_nanoseconds(&duration)?;
+
+    ts.checked_add_signed(Duration::nanoseconds(nanoseconds))
+        .map(Into::into)
+        .ok_or_else(|| "timestamp is out of range".into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AddDuration;
+
+impl Function for AddDuration {
+    fn identifier(&self) -> &'static str {
+        "add_duration"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "duration",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let duration = arguments.required("duration");
+
+        Ok(Box::new(AddDurationFn { value, duration }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "add duration",
+                source: r#"add_duration!(t'2021-02-10T23:32:00Z', "1h30m")"#,
+                result: Ok("t'2021-02-11T01:02:00Z'"),
+            },
+            Example {
+                title: "add ISO 8601 duration",
+                source: r#"add_duration!(t'2021-02-10T23:32:00Z', "P1DT0.000001S")"#,
+                result: Ok("t'2021-02-11T23:32:00.000001Z'"),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AddDurationFn {
+    value: Box<dyn Expression>,
+    duration: Box<dyn Expression>,
+}
+
+impl Expression for AddDurationFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let duration = self.duration.resolve(ctx)?;
+
+        add_duration(value, duration)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    test_function![
+        add_duration => AddDuration;
+
+        hours_minutes {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0),
+                             duration: "1h30m"],
+            want: Ok(Utc.ymd(2021, 2, 11).and_hms(1, 2, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        nanoseconds {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0),
+                             duration: "1ns"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms_nano(23, 32, 0, 1)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        iso8601 {
+            args: func_args![value: Utc.ymd(2021, 2, 28).and_hms(12, 0, 0),
+                             duration: "P1W"],
+            want: Ok(Utc.ymd(2021, 3, 7).and_hms(12, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        negative {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0),
+                             duration: "-32m"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        error_invalid {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0),
+                             duration: "1w"],
+            want: Err("unable to parse duration: '1w'"),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,225 @@
+use std::{collections::HashMap, str::FromStr};
+
+use once_cell::sync::Lazy;
+#[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+use regex::Regex;
+#[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// The number of seconds of each unit of the durations.
+pub(crate) static UNITS: Lazy<HashMap<String, Decimal>> = Lazy::new(|| {
+    vec![
+        ("ns", Decimal::new(1, 9)),
+        ("us", Decimal::new(1, 6)),
+        ("µs", Decimal::new(1, 6)),
+        ("ms", Decimal::new(1, 3)),
+        ("cs", Decimal::new(1, 2)),
+        ("ds", Decimal::new(1, 1)),
+        ("s", Decimal::new(1, 0)),
+        ("m", Decimal::new(60, 0)),
+        ("h", Decimal::new(3_600, 0)),
+        ("d", Decimal::new(86_400, 0)),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_owned(), v))
+    .collect()
+});
+
+#[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+static COMPONENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)                                 # i: case-insensitive, x: ignore whitespace + comments
+            (?P<value>[0-9]*\.?[0-9]+)          # value: integer or float
+            \s?                                 # optional space between value and unit
+            (?P<unit>ns|us|µs|ms|cs|ds|s|m|h|d) # unit, longest first",
+    )
+    .unwrap()
+});
+
+/// The designators of ISO 8601 durations in the order they appear in, whether they are part of
+/// the time, and the number of seconds of each.
+const ISO8601_DESIGNATORS: &[(char, bool, i64)] = &[
+    ('W', false, 604_800),
+    ('D', false, 86_400),
+    ('H', true, 3_600),
+    ('M', true, 60),
+    ('S', true, 1),
+];
+
+/// Parses an ISO 8601 duration, such as `P1DT2H30M`, as a number of seconds.
+///
+/// Years and months don't have a fixed length, so they aren't supported.
+pub(crate) fn parse_iso8601(value: &str) -> Result<Decimal, String> {
+    let invalid = || format!("unable to parse ISO 8601 duration: '{}'", value);
+
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let rest = rest
+        .strip_prefix('P')
+        .or_else(|| rest.strip_prefix('p'))
+        .ok_or_else(invalid)?;
+
+    let mut designators = ISO8601_DESIGNATORS;
+    let mut seconds = Decimal::ZERO;
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut components = 0;
+    let mut time_components = 0;
+
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            '0'..='9' => number.push(c),
+            // Both are allowed as the decimal sign.
+            '.' | ',' => number.push('.'),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            'Y' => return Err(format!("years are not supported: '{}'", value)),
+            'M' if !in_time => return Err(format!("months are not supported: '{}'", value)),
+            designator => {
+                let position = designators
+                    .iter()
+                    .position(|&(d, time, _)| d == designator && time == in_time)
+                    .ok_or_else(invalid)?;
+                let factor = Decimal::from(designators[position].2);
+                designators = &designators[position + 1..];
+
+                let amount = Decimal::from_str(&number).map_err(|_| invalid())?;
+                seconds = amount
+                    .checked_mul(factor)
+                    .and_then(|amount| seconds.checked_add(amount))
+                    .ok_or_else(invalid)?;
+                number.clear();
+
+                components += 1;
+                if in_time {
+                    time_components += 1;
+                }
+            }
+        }
+    }
+
+    if components == 0 || !number.is_empty() || (in_time && time_components == 0) {
+        return Err(invalid());
+    }
+
+    Ok(if negative { -seconds } else { seconds })
+}
+
+/// Parses a duration as a number of nanoseconds.
+///
+/// The duration is either an ISO 8601 duration, or a sequence of numbers followed by their unit,
+/// such as `1h30m` or `1.5s`, optionally preceded by `-`.
+#[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+pub(crate) fn parse_nanoseconds(value: &str) -> Result<i64, String> {
+    let trimmed = value.trim();
+    let seconds = if trimmed.trim_start_matches('-').starts_with(['P', 'p']) {
+        parse_iso8601(trimmed)?
+    } else {
+        parse_components(trimmed).ok_or_else(|| format!("unable to parse duration: '{}'", value))?
+    };
+
+    seconds
+        .checked_mul(Decimal::from(1_000_000_000))
+        .and_then(|nanoseconds| nanoseconds.trunc().to_i64())
+        .ok_or_else(|| format!("duration is out of range: '{}'", value))
+}
+
+#[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+fn parse_components(value: &str) -> Option<Decimal> {
+    let (negative, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+
+    let mut seconds = Decimal::ZERO;
+    let mut end = 0;
+    for captures in COMPONENT_RE.captures_iter(rest) {
+        let component = captures.get(0).expect("match");
+        if component.start() != end {
+            return None;
+        }
+        end = component.end();
+
+        let amount = Decimal::from_str(&captures["value"]).ok()?;
+        let unit = UNITS.get(&captures["unit"].to_lowercase())?;
+        seconds = seconds.checked_add(amount.checked_mul(*unit)?)?;
+    }
+
+    if end == 0 || end != rest.len() {
+        return None;
+    }
+
+    Some(if negative { -seconds } else { seconds })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso8601_durations() {
+        for (value, seconds) in [
+            ("PT0S", "0"),
+            ("P1W", "604800"),
+            ("P1DT2H30M", "95400"),
+            ("PT1.5S", "1.5"),
+            ("PT0,25S", "0.25"),
+            ("-PT1M", "-60"),
+            ("pt2h", "7200"),
+        ] {
+            assert_eq!(
+                parse_iso8601(value),
+                Ok(Decimal::from_str(seconds).unwrap()),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_iso8601_durations() {
+        for value in [
+            "", "P", "PT", "P1DT", "1D", "PT1H1H", "PT1S1M", "P1H", "PT1D", "P1",
+        ] {
+            assert!(parse_iso8601(value).is_err(), "{}", value);
+        }
+        assert_eq!(
+            parse_iso8601("P1Y"),
+            Err("years are not supported: 'P1Y'".to_owned())
+        );
+        assert_eq!(
+            parse_iso8601("P1M"),
+            Err("months are not supported: 'P1M'".to_owned())
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+    fn parses_durations_as_nanoseconds() {
+        for (value, nanoseconds) in [
+            ("1h30m", 5_400_000_000_000),
+            ("1.5s", 1_500_000_000),
+            ("100 ms", 100_000_000),
+            ("1m30s", 90_000_000_000),
+            ("-2d", -172_800_000_000_000),
+            ("PT1H", 3_600_000_000_000),
+            ("-PT1.000000001S", -1_000_000_001),
+        ] {
+            assert_eq!(parse_nanoseconds(value), Ok(nanoseconds), "{}", value);
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "add_duration", feature = "subtract_duration"))]
+    fn rejects_invalid_durations() {
+        for value in ["", "1", "h", "1x", "1h 30m", "1h-30m", "--1h"] {
+            assert!(parse_nanoseconds(value).is_err(), "{}", value);
+        }
+        assert_eq!(
+            parse_nanoseconds("1000000d"),
+            Err("duration is out of range: '1000000d'".to_owned())
+        );
+    }
+}
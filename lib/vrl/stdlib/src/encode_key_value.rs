@@ -1,7 +1,7 @@
 use std::result::Result;
 
 use ::value::Value;
-use vector_common::encode_key_value;
+use vector_common::encode_key_value::{self, Escaping};
 use vrl::prelude::*;
 
 /// Also used by `encode_logfmt`.
@@ -11,6 +11,8 @@ pub(crate) fn encode_key_value(
     key_value_delimiter: Value,
     field_delimiter: Value,
     flatten_boolean: Value,
+    flatten_separator: Value,
+    escaping: Escaping,
 ) -> Result<Value, ExpressionError> {
     let fields = match fields {
         None => Ok(vec![]),
@@ -20,12 +22,15 @@ pub(crate) fn encode_key_value(
     let key_value_delimiter = key_value_delimiter.try_bytes_utf8_lossy()?;
     let field_delimiter = field_delimiter.try_bytes_utf8_lossy()?;
     let flatten_boolean = flatten_boolean.try_boolean()?;
-    Ok(encode_key_value::to_string(
+    let flatten_separator = flatten_separator.try_bytes_utf8_lossy()?;
+    Ok(encode_key_value::to_string_with_options(
         &object,
         &fields[..],
         &key_value_delimiter,
         &field_delimiter,
         flatten_boolean,
+        &flatten_separator,
+        escaping,
     )
     .expect("Should always succeed.")
     .into())
@@ -96,6 +101,8 @@ impl Function for EncodeKeyValue {
             key_value_delimiter,
             field_delimiter,
             flatten_boolean,
+            flatten_separator: expr!("."),
+            escaping: Escaping::Default,
         }))
     }
 
@@ -127,6 +134,8 @@ pub(crate) struct EncodeKeyValueFn {
     pub(crate) key_value_delimiter: Box<dyn Expression>,
     pub(crate) field_delimiter: Box<dyn Expression>,
     pub(crate) flatten_boolean: Box<dyn Expression>,
+    pub(crate) flatten_separator: Box<dyn Expression>,
+    pub(crate) escaping: Escaping,
 }
 
 fn resolve_fields(fields: Value) -> Result<Vec<String>, ExpressionError> {
@@ -152,6 +161,7 @@ impl Expression for EncodeKeyValueFn {
         let key_value_delimiter = self.key_value_delimiter.resolve(ctx)?;
         let field_delimiter = self.field_delimiter.resolve(ctx)?;
         let flatten_boolean = self.flatten_boolean.resolve(ctx)?;
+        let flatten_separator = self.flatten_separator.resolve(ctx)?;
 
         encode_key_value(
             fields,
@@ -159,6 +169,8 @@ impl Expression for EncodeKeyValueFn {
             key_value_delimiter,
             field_delimiter,
             flatten_boolean,
+            flatten_separator,
+            self.escaping,
         )
     }
 
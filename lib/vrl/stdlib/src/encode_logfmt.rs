@@ -1,7 +1,13 @@
+use ::value::Value;
+use vector_common::encode_key_value::Escaping;
 use vrl::prelude::*;
 
 use crate::encode_key_value::EncodeKeyValueFn;
 
+fn escaping_variants() -> Vec<Value> {
+    vec![value!("default"), value!("strict")]
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct EncodeLogfmt;
 
@@ -10,6 +16,20 @@ impl Function for EncodeLogfmt {
         "encode_logfmt"
     }
 
+    fn summary(&self) -> &'static str {
+        "encode an object to a logfmt string"
+    }
+
+    fn usage(&self) -> &'static str {
+        indoc! {r#"
+            Encodes the provided `value` to logfmt.
+
+            Fields not listed in `fields_ordering` are always sorted by key, after the listed ones.
+            VRL objects are sorted maps, so the order in which fields were inserted isn't known and
+            can't be preserved.
+        "#}
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -22,6 +42,16 @@ impl Function for EncodeLogfmt {
                 kind: kind::ARRAY,
                 required: false,
             },
+            Parameter {
+                keyword: "flatten_separator",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "escaping",
+                kind: kind::BYTES,
+                required: false,
+            },
         ]
     }
 
@@ -39,6 +69,19 @@ impl Function for EncodeLogfmt {
 
         let value = arguments.required("value");
         let fields = arguments.optional("fields_ordering");
+        let flatten_separator = arguments
+            .optional("flatten_separator")
+            .unwrap_or_else(|| expr!("."));
+        let escaping = match arguments
+            .optional_enum("escaping", &escaping_variants())?
+            .unwrap_or_else(|| value!("default"))
+            .try_bytes()
+            .expect("escaping not bytes")
+            .as_ref()
+        {
+            b"strict" => Escaping::Strict,
+            _ => Escaping::Default,
+        };
 
         Ok(Box::new(EncodeKeyValueFn {
             value,
@@ -46,6 +89,8 @@ impl Function for EncodeLogfmt {
             key_value_delimiter,
             field_delimiter,
             flatten_boolean,
+            flatten_separator,
+            escaping,
         }))
     }
 
@@ -61,6 +106,63 @@ impl Function for EncodeLogfmt {
                 source: r#"encode_logfmt!({"msg": "This is a message", "lvl": "info", "log_id": 12345}, ["lvl", "msg"])"#,
                 result: Ok(r#"s'lvl=info msg="This is a message" log_id=12345'"#),
             },
+            Example {
+                title: "encode object for Loki",
+                source: r#"encode_logfmt!({"msg": "a=b", "request": {"path": "/", "query": ""}}, ["msg", "request"], flatten_separator: "_", escaping: "strict")"#,
+                result: Ok(r#"s'msg="a=b" request_path=/ request_query=""'"#),
+            },
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        encode_logfmt => EncodeLogfmt;
+
+        default_escaping {
+            args: func_args![value:
+                btreemap! {
+                    "msg" => "a=b",
+                    "empty" => "",
+                }
+            ],
+            want: Ok(r#"empty= msg=a=b"#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        strict_escaping {
+            args: func_args![value:
+                btreemap! {
+                    "msg" => "a=b",
+                    "empty" => "",
+                    "error" => "line 1\nline 2",
+                },
+                escaping: "strict"
+            ],
+            want: Ok(r#"empty="" error="line 1\nline 2" msg="a=b""#),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        nested_fields_ordering {
+            args: func_args![value:
+                btreemap! {
+                    "request" => btreemap! {
+                        "path" => "/",
+                        "method" => "GET",
+                    },
+                    "level" => "info",
+                    "msg" => "done",
+                },
+                fields_ordering: value!(["msg", "request"]),
+                flatten_separator: "_"
+            ],
+            want: Ok("msg=done request_method=GET request_path=/ level=info"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,218 @@
+use std::{borrow::Cow, collections::BTreeMap, fmt, fs, io, path::Path};
+
+use vrl::{
+    diagnostic::{Label, Span},
+    function::Error as FunctionError,
+    prelude::*,
+};
+
+#[derive(Debug)]
+pub(crate) enum Error {
+    Read { path: String, source: io::Error },
+    InvalidDefinition { path: String, line: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Read { path, source } => {
+                write!(f, "unable to read pattern file {:?}: {}", path, source)
+            }
+            Error::InvalidDefinition { path, line } => write!(
+                f,
+                "invalid pattern definition at line {} of {:?}, expected a name and a definition",
+                line, path
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        109
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        vec![Label::primary(
+            format!("grok pattern error: {}", self),
+            Span::default(),
+        )]
+    }
+}
+
+/// Resolves the `pattern_paths` argument, which must be an array of string literals.
+pub(crate) fn pattern_paths(
+    arguments: &mut ArgumentList,
+) -> std::result::Result<Vec<String>, FunctionError> {
+    arguments
+        .optional_array("pattern_paths")?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|expr| {
+            let path = expr
+                .as_value()
+                .ok_or(FunctionError::ExpectedStaticExpression {
+                    keyword: "pattern_paths",
+                    expr,
+                })?
+                .try_bytes_utf8_lossy()
+                .expect("pattern path not bytes")
+                .into_owned();
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Resolves the `pattern_paths` argument from the arguments given to `compile_argument`.
+pub(crate) fn static_pattern_paths(
+    args: &[(&'static str, Option<FunctionArgument>)],
+) -> Vec<String> {
+    args.iter()
+        .find_map(|(name, arg)| {
+            if *name == "pattern_paths" {
+                arg.as_ref()
+            } else {
+                None
+            }
+        })
+        .and_then(|arg| arg.as_value())
+        .and_then(|paths| paths.try_array().ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|path| path.try_bytes_utf8_lossy().ok().map(Cow::into_owned))
+        .collect()
+}
+
+/// Loads the pattern definitions of Logstash-style pattern files, from the given files and from
+/// the files of the given directories.
+///
+/// Each line of a pattern file is a pattern name followed by whitespace and its definition, such
+/// as `POSTFIX_QUEUEID [0-9A-F]{6,12}`. Empty lines and lines starting with `#` are skipped. Later
+/// definitions of a name replace the earlier ones, and the files of a directory are read in the
+/// order of their names.
+pub(crate) fn load_pattern_definitions(
+    paths: &[String],
+) -> std::result::Result<BTreeMap<String, String>, Error> {
+    let mut definitions = BTreeMap::new();
+    for path in paths {
+        let read_error = |source| Error::Read {
+            path: path.clone(),
+            source,
+        };
+        if Path::new(path).is_dir() {
+            let mut files = fs::read_dir(path)
+                .map_err(read_error)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<io::Result<Vec<_>>>()
+                .map_err(read_error)?;
+            files.retain(|file| file.is_file());
+            files.sort();
+            for file in files {
+                let file = file.to_string_lossy().into_owned();
+                load_pattern_file(&file, &mut definitions)?;
+            }
+        } else {
+            load_pattern_file(path, &mut definitions)?;
+        }
+    }
+    Ok(definitions)
+}
+
+fn load_pattern_file(
+    path: &str,
+    definitions: &mut BTreeMap<String, String>,
+) -> std::result::Result<(), Error> {
+    let contents = fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_owned(),
+        source,
+    })?;
+    parse_pattern_definitions(path, &contents, definitions)
+}
+
+fn parse_pattern_definitions(
+    path: &str,
+    contents: &str,
+    definitions: &mut BTreeMap<String, String>,
+) -> std::result::Result<(), Error> {
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once(char::is_whitespace) {
+            Some((name, definition)) if !definition.trim().is_empty() => {
+                definitions.insert(name.to_owned(), definition.trim().to_owned());
+            }
+            _ => {
+                return Err(Error::InvalidDefinition {
+                    path: path.to_owned(),
+                    line: index + 1,
+                })
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pattern_definitions() {
+        let mut definitions = BTreeMap::new();
+        parse_pattern_definitions(
+            "postfix",
+            indoc! {r#"
+                # Postfix patterns
+                POSTFIX_QUEUEID [0-9A-F]{6,12}
+
+                POSTFIX_CLIENT  %{HOSTNAME:client_host}\[%{IP:client_ip}\]
+            "#},
+            &mut definitions,
+        )
+        .unwrap();
+
+        assert_eq!(
+            definitions,
+            BTreeMap::from([
+                ("POSTFIX_QUEUEID".to_owned(), "[0-9A-F]{6,12}".to_owned()),
+                (
+                    "POSTFIX_CLIENT".to_owned(),
+                    r#"%{HOSTNAME:client_host}\[%{IP:client_ip}\]"#.to_owned()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_definitions_without_a_pattern() {
+        let error = parse_pattern_definitions("postfix", "FOO bar\nBAZ\n", &mut BTreeMap::new())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"invalid pattern definition at line 2 of "postfix", expected a name and a definition"#
+        );
+    }
+
+    #[test]
+    fn loads_directories() {
+        let dir = std::env::temp_dir().join(format!("vrl-grok-patterns-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a"), "FOO foo\nBAR bar\n").unwrap();
+        fs::write(dir.join("b"), "FOO other\n").unwrap();
+
+        let definitions = load_pattern_definitions(&[dir.to_string_lossy().into_owned()]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            definitions.unwrap(),
+            BTreeMap::from([
+                ("BAR".to_owned(), "bar".to_owned()),
+                ("FOO".to_owned(), "other".to_owned()),
+            ])
+        );
+    }
+}
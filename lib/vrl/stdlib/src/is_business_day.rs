@@ -0,0 +1,98 @@
+use ::value::Value;
+use chrono::{Datelike, Weekday};
+use vrl::prelude::*;
+
+fn is_business_day(value: Value) -> Resolved {
+    let ts = value.try_timestamp()?;
+
+    Ok(value!(!matches!(ts.weekday(), Weekday::Sat | Weekday::Sun)))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsBusinessDay;
+
+impl Function for IsBusinessDay {
+    fn identifier(&self) -> &'static str {
+        "is_business_day"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::TIMESTAMP,
+            required: true,
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(IsBusinessDayFn { value }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "weekday",
+                source: r#"is_business_day(t'2021-02-12T10:00:00Z')"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "weekend",
+                source: r#"is_business_day(t'2021-02-13T10:00:00Z')"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsBusinessDayFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for IsBusinessDayFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        is_business_day(value)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::boolean().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    test_function![
+        is_business_day => IsBusinessDay;
+
+        friday {
+            args: func_args![value: Utc.ymd(2021, 2, 12).and_hms(23, 59, 59)],
+            want: Ok(true),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        saturday {
+            args: func_args![value: Utc.ymd(2021, 2, 13).and_hms(0, 0, 0)],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+
+        sunday {
+            args: func_args![value: Utc.ymd(2021, 2, 14).and_hms(12, 0, 0)],
+            want: Ok(false),
+            tdef: TypeDef::boolean().infallible(),
+        }
+    ];
+}
@@ -28,6 +28,10 @@
 
 mod util;
 
+#[cfg(feature = "add_business_days")]
+mod add_business_days;
+#[cfg(feature = "add_duration")]
+mod add_duration;
 #[cfg(feature = "append")]
 mod append;
 #[cfg(feature = "array")]
@@ -54,6 +58,12 @@ mod decrypt;
 mod del;
 #[cfg(feature = "downcase")]
 mod downcase;
+#[cfg(any(
+    feature = "add_duration",
+    feature = "parse_duration_iso8601",
+    feature = "subtract_duration"
+))]
+mod duration_util;
 #[cfg(feature = "encode_base64")]
 mod encode_base64;
 #[cfg(feature = "encode_json")]
@@ -94,6 +104,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(any(feature = "parse_grok", feature = "parse_groks"))]
+mod grok_util;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -118,6 +130,8 @@ mod ipv6_to_ipv4;
 mod is_array;
 #[cfg(feature = "is_boolean")]
 mod is_boolean;
+#[cfg(feature = "is_business_day")]
+mod is_business_day;
 #[cfg(feature = "is_empty")]
 mod is_empty;
 #[cfg(feature = "is_float")]
@@ -162,6 +176,8 @@ mod match_any;
 mod match_array;
 #[cfg(feature = "match_datadog_query")]
 mod match_datadog_query;
+#[cfg(feature = "matches_rules")]
+mod matches_rules;
 #[cfg(feature = "md5")]
 mod md5;
 #[cfg(feature = "merge")]
@@ -186,6 +202,8 @@ mod parse_common_log;
 mod parse_csv;
 #[cfg(feature = "parse_duration")]
 mod parse_duration;
+#[cfg(feature = "parse_duration_iso8601")]
+mod parse_duration_iso8601;
 #[cfg(feature = "parse_glog")]
 mod parse_glog;
 #[cfg(feature = "parse_grok")]
@@ -262,6 +280,8 @@ mod strip_ansi_escape_codes;
 mod strip_whitespace;
 #[cfg(feature = "strlen")]
 mod strlen;
+#[cfg(feature = "subtract_duration")]
+mod subtract_duration;
 #[cfg(feature = "tag_types_externally")]
 mod tag_types_externally;
 #[cfg(feature = "tally")]
@@ -292,6 +312,8 @@ mod to_timestamp;
 mod to_unix_timestamp;
 #[cfg(feature = "truncate")]
 mod truncate;
+#[cfg(feature = "truncate_timestamp")]
+mod truncate_timestamp;
 #[cfg(feature = "type_def")]
 mod type_def;
 #[cfg(feature = "unique")]
@@ -305,6 +327,10 @@ mod uuid_v4;
 
 // -----------------------------------------------------------------------------
 
+#[cfg(feature = "add_business_days")]
+pub use add_business_days::AddBusinessDays;
+#[cfg(feature = "add_duration")]
+pub use add_duration::AddDuration;
 #[cfg(feature = "append")]
 pub use append::Append;
 #[cfg(feature = "assert")]
@@ -393,6 +419,8 @@ pub use ipv6_to_ipv4::Ipv6ToIpV4;
 pub use is_array::IsArray;
 #[cfg(feature = "is_boolean")]
 pub use is_boolean::IsBoolean;
+#[cfg(feature = "is_business_day")]
+pub use is_business_day::IsBusinessDay;
 #[cfg(feature = "is_empty")]
 pub use is_empty::IsEmpty;
 #[cfg(feature = "is_float")]
@@ -429,6 +457,8 @@ pub use match_any::MatchAny;
 pub use match_array::MatchArray;
 #[cfg(feature = "match_datadog_query")]
 pub use match_datadog_query::MatchDatadogQuery;
+#[cfg(feature = "matches_rules")]
+pub use matches_rules::MatchesRules;
 #[cfg(feature = "merge")]
 pub use merge::Merge;
 #[cfg(feature = "now")]
@@ -451,6 +481,8 @@ pub use parse_common_log::ParseCommonLog;
 pub use parse_csv::ParseCsv;
 #[cfg(feature = "parse_duration")]
 pub use parse_duration::ParseDuration;
+#[cfg(feature = "parse_duration_iso8601")]
+pub use parse_duration_iso8601::ParseDurationIso8601;
 #[cfg(feature = "parse_glog")]
 pub use parse_glog::ParseGlog;
 #[cfg(feature = "parse_grok")]
@@ -527,6 +559,8 @@ pub use strip_ansi_escape_codes::StripAnsiEscapeCodes;
 pub use strip_whitespace::StripWhitespace;
 #[cfg(feature = "strlen")]
 pub use strlen::Strlen;
+#[cfg(feature = "subtract_duration")]
+pub use subtract_duration::SubtractDuration;
 #[cfg(feature = "tag_types_externally")]
 pub use tag_types_externally::TagTypesExternally;
 #[cfg(feature = "tally")]
@@ -557,6 +591,8 @@ pub use to_timestamp::ToTimestamp;
 pub use to_unix_timestamp::ToUnixTimestamp;
 #[cfg(feature = "truncate")]
 pub use truncate::Truncate;
+#[cfg(feature = "truncate_timestamp")]
+pub use truncate_timestamp::TruncateTimestamp;
 #[cfg(feature = "type_def")]
 pub use type_def::TypeDef;
 #[cfg(feature = "unique")]
@@ -578,6 +614,10 @@ pub use crate::sha1::Sha1;
 #[must_use]
 pub fn all() -> Vec<Box<dyn vrl::Function>> {
     vec![
+        #[cfg(feature = "add_business_days")]
+        Box::new(AddBusinessDays),
+        #[cfg(feature = "add_duration")]
+        Box::new(AddDuration),
         #[cfg(feature = "append")]
         Box::new(Append),
         #[cfg(feature = "array")]
@@ -668,6 +708,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IsArray),
         #[cfg(feature = "is_boolean")]
         Box::new(IsBoolean),
+        #[cfg(feature = "is_business_day")]
+        Box::new(IsBusinessDay),
         #[cfg(feature = "is_empty")]
         Box::new(IsEmpty),
         #[cfg(feature = "is_float")]
@@ -706,6 +748,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(MatchArray),
         #[cfg(feature = "match_datadog_query")]
         Box::new(MatchDatadogQuery),
+        #[cfg(feature = "matches_rules")]
+        Box::new(MatchesRules),
         #[cfg(feature = "md5")]
         Box::new(Md5),
         #[cfg(feature = "merge")]
@@ -732,6 +776,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseCsv),
         #[cfg(feature = "parse_duration")]
         Box::new(ParseDuration),
+        #[cfg(feature = "parse_duration_iso8601")]
+        Box::new(ParseDurationIso8601),
         #[cfg(feature = "parse_glog")]
         Box::new(ParseGlog),
         #[cfg(feature = "parse_grok")]
@@ -808,6 +854,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(StripWhitespace),
         #[cfg(feature = "strlen")]
         Box::new(Strlen),
+        #[cfg(feature = "subtract_duration")]
+        Box::new(SubtractDuration),
         #[cfg(feature = "tally")]
         Box::new(Tally),
         #[cfg(feature = "tally_value")]
@@ -838,6 +886,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ToUnixTimestamp),
         #[cfg(feature = "truncate")]
         Box::new(Truncate),
+        #[cfg(feature = "truncate_timestamp")]
+        Box::new(TruncateTimestamp),
         #[cfg(feature = "type_def")]
         Box::new(TypeDef),
         #[cfg(feature = "unique")]
@@ -0,0 +1,132 @@
+use ::value::Value;
+use regex::bytes::RegexSet;
+use vrl::prelude::*;
+
+fn matches_rules(value: Value, patterns: &RegexSet) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let matched = patterns
+        .matches(&bytes)
+        .into_iter()
+        .map(|index| Value::Integer(index as i64))
+        .collect::<Vec<_>>();
+
+    Ok(matched.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct MatchesRules;
+
+impl Function for MatchesRules {
+    fn identifier(&self) -> &'static str {
+        "matches_rules"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "patterns",
+                kind: kind::ARRAY,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "match",
+                source: r#"matches_rules("foo bar baz", patterns: [r'foo', r'\d+', r'baz$'])"#,
+                result: Ok("[0, 2]"),
+            },
+            Example {
+                title: "no match",
+                source: r#"matches_rules("My name is John Doe", patterns: [r'\d+', r'Jane'])"#,
+                result: Ok("[]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let patterns = arguments.required_array("patterns")?;
+
+        let mut re_strings = Vec::with_capacity(patterns.len());
+        for expr in patterns {
+            let value = expr
+                .as_value()
+                .ok_or(vrl::function::Error::ExpectedStaticExpression {
+                    keyword: "patterns",
+                    expr,
+                })?;
+
+            let re = value
+                .try_regex()
+                .map_err(|e| Box::new(e) as Box<dyn DiagnosticMessage>)?;
+            re_strings.push(re.to_string());
+        }
+
+        let regex_set = RegexSet::new(re_strings).expect("regex were already valid");
+
+        Ok(Box::new(MatchesRulesFn { value, regex_set }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct MatchesRulesFn {
+    value: Box<dyn Expression>,
+    regex_set: RegexSet,
+}
+
+impl Expression for MatchesRulesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        matches_rules(value, &self.regex_set)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::array(Collection::from_unknown(Kind::integer())).infallible()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::trivial_regex)]
+mod tests {
+    use regex::Regex;
+
+    use super::*;
+
+    test_function![
+        matches_rules => MatchesRules;
+
+        some {
+            args: func_args![value: "foobar",
+                             patterns: Value::Array(vec![
+                                 Value::Regex(Regex::new("foo").unwrap().into()),
+                                 Value::Regex(Regex::new("baz").unwrap().into()),
+                                 Value::Regex(Regex::new("bar$").unwrap().into()),
+                             ])],
+            want: Ok(value!([0, 2])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())).infallible(),
+        }
+
+        none {
+            args: func_args![value: "foo 2 bar",
+                             patterns: Value::Array(vec![
+                                 Value::Regex(Regex::new("baz|quux").unwrap().into()),
+                                 Value::Regex(Regex::new("foobar").unwrap().into()),
+                             ])],
+            want: Ok(value!([])),
+            tdef: TypeDef::array(Collection::from_unknown(Kind::integer())).infallible(),
+        }
+    ];
+}
@@ -0,0 +1,152 @@
+use ::value::Value;
+use rust_decimal::prelude::ToPrimitive;
+use vrl::prelude::*;
+
+use crate::duration_util::{parse_iso8601, UNITS};
+
+fn parse_duration_iso8601(bytes: Value, unit: Value) -> Resolved {
+    let bytes = bytes.try_bytes()?;
+    let value = String::from_utf8_lossy(&bytes);
+    let conversion_factor = {
+        let bytes = unit.try_bytes()?;
+        let string = String::from_utf8_lossy(&bytes);
+
+        UNITS
+            .get(string.as_ref())
+            .ok_or(format!("unknown unit format: '{}'", string))?
+    };
+    let seconds = parse_iso8601(value.trim())?;
+    let number = seconds / conversion_factor;
+    let number = number
+        .to_f64()
+        .ok_or(format!("unable to format duration: '{}'", number))?;
+    Ok(Value::from_f64_or_zero(number))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseDurationIso8601;
+
+impl Function for ParseDurationIso8601 {
+    fn identifier(&self) -> &'static str {
+        "parse_duration_iso8601"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "minutes",
+                source: r#"parse_duration_iso8601!("PT1H30M", unit: "m")"#,
+                result: Ok("90.0"),
+            },
+            Example {
+                title: "fractional seconds",
+                source: r#"parse_duration_iso8601!("P1DT0.5S", unit: "ms")"#,
+                result: Ok("86400500.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments.required("unit");
+
+        Ok(Box::new(ParseDurationIso8601Fn { value, unit }))
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseDurationIso8601Fn {
+    value: Box<dyn Expression>,
+    unit: Box<dyn Expression>,
+}
+
+impl Expression for ParseDurationIso8601Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?;
+        let unit = self.unit.resolve(ctx)?;
+
+        parse_duration_iso8601(bytes, unit)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::float().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_duration_iso8601 => ParseDurationIso8601;
+
+        hours_minutes {
+            args: func_args![value: "PT1H30M",
+                             unit: "m"],
+            want: Ok(90.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        weeks_days {
+            args: func_args![value: "P1W2D",
+                             unit: "d"],
+            want: Ok(9.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        fractional_seconds {
+            args: func_args![value: "PT0.000001S",
+                             unit: "ns"],
+            want: Ok(1000.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        negative {
+            args: func_args![value: "-PT2M",
+                             unit: "s"],
+            want: Ok(-120.0),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_invalid {
+            args: func_args![value: "1h",
+                             unit: "s"],
+            want: Err("unable to parse ISO 8601 duration: '1h'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_months {
+            args: func_args![value: "P1M",
+                             unit: "s"],
+            want: Err("months are not supported: 'P1M'"),
+            tdef: TypeDef::float().fallible(),
+        }
+
+        error_format {
+            args: func_args![value: "PT1S",
+                             unit: "w"],
+            want: Err("unknown unit format: 'w'"),
+            tdef: TypeDef::float().fallible(),
+        }
+    ];
+}
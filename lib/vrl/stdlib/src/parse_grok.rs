@@ -6,6 +6,8 @@ use vrl::{
     prelude::*,
 };
 
+use crate::grok_util;
+
 fn parse_grok(value: Value, pattern: Arc<grok::Pattern>) -> Resolved {
     let bytes = value.try_bytes_utf8_lossy()?;
     match pattern.match_against(&bytes) {
@@ -74,6 +76,11 @@ impl Function for ParseGrok {
                 kind: kind::BYTES,
                 required: true,
             },
+            Parameter {
+                keyword: "pattern_paths",
+                kind: kind::ARRAY,
+                required: false,
+            },
         ]
     }
 
@@ -111,18 +118,16 @@ impl Function for ParseGrok {
             .expect("grok pattern not bytes")
             .into_owned();
 
-        let mut grok = grok::Grok::with_default_patterns();
-        let pattern =
-            Arc::new(grok.compile(&pattern, true).map_err(|e| {
-                Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>
-            })?);
+        let pattern_paths = grok_util::pattern_paths(&mut arguments)?;
+
+        let pattern = Arc::new(compile_pattern(&pattern, &pattern_paths)?);
 
         Ok(Box::new(ParseGrokFn { value, pattern }))
     }
 
     fn compile_argument(
         &self,
-        _args: &[(&'static str, Option<FunctionArgument>)],
+        args: &[(&'static str, Option<FunctionArgument>)],
         _ctx: &mut FunctionCompileContext,
         name: &str,
         expr: Option<&expression::Expr>,
@@ -135,10 +140,9 @@ impl Function for ParseGrok {
                     .expect("grok pattern not bytes")
                     .into_owned();
 
-                let mut grok = grok::Grok::with_default_patterns();
-                let pattern = Arc::new(grok.compile(&pattern, true).map_err(|e| {
-                    Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>
-                })?);
+                let pattern_paths = grok_util::static_pattern_paths(args);
+
+                let pattern = Arc::new(compile_pattern(&pattern, &pattern_paths)?);
 
                 Ok(Some(Box::new(pattern) as _))
             }
@@ -147,6 +151,22 @@ impl Function for ParseGrok {
     }
 }
 
+/// Compiles the pattern with the default patterns and the patterns defined in the pattern files.
+fn compile_pattern(
+    pattern: &str,
+    pattern_paths: &[String],
+) -> std::result::Result<grok::Pattern, Box<dyn DiagnosticMessage>> {
+    let mut grok = grok::Grok::with_default_patterns();
+    let definitions = grok_util::load_pattern_definitions(pattern_paths)
+        .map_err(|e| Box::new(e) as Box<dyn DiagnosticMessage>)?;
+    for (name, definition) in definitions {
+        grok.insert_definition(name, definition);
+    }
+
+    grok.compile(pattern, true)
+        .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)
+}
+
 #[derive(Clone, Debug)]
 struct ParseGrokFn {
     value: Box<dyn Expression>,
@@ -217,5 +237,16 @@ mod test {
             })),
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
+
+        pattern_files {
+            args: func_args![ value: "postfix/smtp[4242]: 4F9D195432C: to=<user@example.com>",
+                              pattern: "%{POSTFIX_PREFIX} %{POSTFIX_QUEUEID:queue_id}: to=<%{DATA:to}>",
+                              pattern_paths: vec![concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/grok")]],
+            want: Ok(Value::from(btreemap! {
+                "queue_id" => "4F9D195432C",
+                "to" => "user@example.com",
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
     ];
 }
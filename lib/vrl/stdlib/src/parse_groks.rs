@@ -1,5 +1,6 @@
 use std::{collections::BTreeMap, fmt};
 
+use ::value::Value;
 use datadog_grok::{
     parse_grok,
     parse_grok_rules::{self, GrokRule},
@@ -9,6 +10,8 @@ use vrl::{
     prelude::*,
 };
 
+use crate::grok_util;
+
 #[derive(Debug)]
 pub(crate) enum Error {
     InvalidGrokPattern(datadog_grok::parse_grok_rules::Error),
@@ -66,13 +69,24 @@ impl Function for ParseGroks {
                 kind: kind::OBJECT,
                 required: false,
             },
+            Parameter {
+                keyword: "pattern_paths",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "include_pattern",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "parse grok pattern",
-            source: indoc! {r#"
+        &[
+            Example {
+                title: "parse grok pattern",
+                source: indoc! {r#"
                 parse_groks!(
                     "2020-10-02T23:22:12.223222Z info hello world",
                     patterns: [
@@ -87,14 +101,38 @@ impl Function for ParseGroks {
                         "_message": "%{GREEDYDATA:message}"
                     })
             "#},
-            result: Ok(indoc! {r#"
+                result: Ok(indoc! {r#"
                 {
                     "timestamp": "2020-10-02T23:22:12.223222Z",
                     "level": "info",
                     "message": "hello world"
                 }
             "#}),
-        }]
+            },
+            Example {
+                title: "include the matched pattern",
+                source: indoc! {r#"
+                    parse_groks!(
+                        "2020-10-02T23:22:12.223222Z info hello world",
+                        patterns: [
+                            "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{POSINT:status} %{GREEDYDATA:message}",
+                            "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}"
+                        ],
+                        include_pattern: true)
+                "#},
+                result: Ok(indoc! {r#"
+                    {
+                        "fields": {
+                            "timestamp": "2020-10-02T23:22:12.223222Z",
+                            "level": "info",
+                            "message": "hello world"
+                        },
+                        "pattern": "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+                        "pattern_index": 1
+                    }
+                "#}),
+            },
+        ]
     }
 
     fn compile_argument(
@@ -155,11 +193,9 @@ impl Function for ParseGroks {
                 })
                 .unwrap_or_default();
 
-                // We use a datadog library here because it is a superset of grok.
-                let grok_rules =
-                    parse_grok_rules::parse_grok_rules(&patterns, aliases).map_err(|e| {
-                        Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>
-                    })?;
+                let pattern_paths = grok_util::static_pattern_paths(args);
+
+                let grok_rules = compile_rules(&patterns, aliases, &pattern_paths)?;
 
                 Ok(Some(Box::new(grok_rules) as _))
             }
@@ -212,29 +248,75 @@ impl Function for ParseGroks {
             })
             .collect::<std::result::Result<BTreeMap<String, String>, vrl::function::Error>>()?;
 
-        // we use a datadog library here because it is a superset of grok
-        let grok_rules = parse_grok_rules::parse_grok_rules(&patterns, aliases)
-            .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)?;
+        let pattern_paths = grok_util::pattern_paths(&mut arguments)?;
+
+        let include_pattern = arguments
+            .optional("include_pattern")
+            .unwrap_or_else(|| expr!(false));
 
-        Ok(Box::new(ParseGrokFn { value, grok_rules }))
+        let grok_rules = compile_rules(&patterns, aliases, &pattern_paths)?;
+
+        // Empty patterns are skipped when compiling, so keep the position of each compiled
+        // pattern in the list.
+        let patterns = patterns
+            .into_iter()
+            .enumerate()
+            .filter(|(_, pattern)| !pattern.is_empty())
+            .collect();
+
+        Ok(Box::new(ParseGrokFn {
+            value,
+            grok_rules,
+            patterns,
+            include_pattern,
+        }))
     }
 }
 
+/// Compiles the patterns with the given aliases and the patterns defined in the pattern files,
+/// which are overridden by aliases of the same name.
+fn compile_rules(
+    patterns: &[String],
+    aliases: BTreeMap<String, String>,
+    pattern_paths: &[String],
+) -> std::result::Result<Vec<GrokRule>, Box<dyn DiagnosticMessage>> {
+    let mut definitions = grok_util::load_pattern_definitions(pattern_paths)
+        .map_err(|e| Box::new(e) as Box<dyn DiagnosticMessage>)?;
+    definitions.extend(aliases);
+
+    // We use a datadog library here because it is a superset of grok.
+    parse_grok_rules::parse_grok_rules(patterns, definitions)
+        .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticMessage>)
+}
+
 #[derive(Clone, Debug)]
 struct ParseGrokFn {
     value: Box<dyn Expression>,
     grok_rules: Vec<GrokRule>,
+    /// The patterns of the rules, with their positions in the `patterns` argument.
+    patterns: Vec<(usize, String)>,
+    include_pattern: Box<dyn Expression>,
 }
 
 impl Expression for ParseGrokFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         let value = self.value.resolve(ctx)?;
         let bytes = value.try_bytes_utf8_lossy()?;
+        let include_pattern = self.include_pattern.resolve(ctx)?.try_boolean()?;
 
-        let v = parse_grok::parse_grok(bytes.as_ref(), &self.grok_rules)
+        let (rule, v) = parse_grok::parse_grok_with_rule(bytes.as_ref(), &self.grok_rules)
             .map_err(|err| format!("unable to parse grok: {}", err))?;
 
-        Ok(v)
+        if include_pattern {
+            let (index, pattern) = &self.patterns[rule];
+            Ok(Value::from(BTreeMap::from([
+                ("fields".to_owned(), v),
+                ("pattern".to_owned(), Value::from(pattern.as_str())),
+                ("pattern_index".to_owned(), Value::from(*index)),
+            ])))
+        } else {
+            Ok(v)
+        }
     }
 
     fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
@@ -341,6 +423,44 @@ mod test {
             tdef: TypeDef::object(Collection::any()).fallible(),
         }
 
+        include_pattern {
+            args: func_args![
+                value: "2020-10-02T23:22:12.223222Z info hello world",
+                patterns: Value::Array(vec![
+                    "".into(),
+                    "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{POSINT:status} %{GREEDYDATA:message}".into(),
+                    "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}".into(),
+                    ]),
+                include_pattern: true
+            ],
+            want: Ok(value!({
+                "fields": {
+                    "timestamp": "2020-10-02T23:22:12.223222Z",
+                    "level": "info",
+                    "message": "hello world"
+                },
+                "pattern": "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+                "pattern_index": 2
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
+        pattern_files {
+            args: func_args![
+                value: "postfix/smtp[4242]: 4F9D195432C: to=<user@example.com>",
+                patterns: vec!["%{POSTFIX_PREFIX} %{POSTFIX_QUEUEID:queue_id}: to=<%{_to}>"],
+                aliases: value!({
+                    "_to": "%{data:to}"
+                }),
+                pattern_paths: vec![concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/grok/postfix")]
+            ],
+            want: Ok(Value::from(btreemap! {
+                "queue_id" => "4F9D195432C",
+                "to" => "user@example.com",
+            })),
+            tdef: TypeDef::object(Collection::any()).fallible(),
+        }
+
         datadog_nginx {
             args: func_args![
                 value: r##"127.0.0.1 - frank [13/Jul/2016:10:55:36] "GET /apache_pb.gif HTTP/1.0" 200 2326 0.202 "http://www.perdu.com/" "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/55.0.2883.87 Safari/537.36" "-""##,
@@ -0,0 +1,124 @@
+use ::value::Value;
+use chrono::Duration;
+use vrl::prelude::*;
+
+use crate::duration_util::parse_nanoseconds;
+
+fn subtract_duration(value: Value, duration: Value) -> Resolved {
+    let ts = value.try_timestamp()?;
+    let duration = duration.try_bytes_utf8_lossy()?;
+    let nanoseconds = parse_nanoseconds(&duration)?;
+
+    ts.checked_sub_signed(Duration::nanoseconds(nanoseconds))
+        .map(Into::into)
+        .ok_or_else(|| "timestamp is out of range".into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SubtractDuration;
+
+impl Function for SubtractDuration {
+    fn identifier(&self) -> &'static str {
+        "subtract_duration"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "duration",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let duration = arguments.required("duration");
+
+        Ok(Box::new(SubtractDurationFn { value, duration }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "subtract duration",
+                source: r#"subtract_duration!(t'2021-02-10T23:32:00Z', "90s")"#,
+                result: Ok("t'2021-02-10T23:30:30Z'"),
+            },
+            Example {
+                title: "subtract ISO 8601 duration",
+                source: r#"subtract_duration!(t'2021-03-01T00:00:00Z', "P1D")"#,
+                result: Ok("t'2021-02-28T00:00:00Z'"),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SubtractDurationFn {
+    value: Box<dyn Expression>,
+    duration: Box<dyn Expression>,
+}
+
+impl Expression for SubtractDurationFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let duration = self.duration.resolve(ctx)?;
+
+        subtract_duration(value, duration)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::timestamp().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    test_function![
+        subtract_duration => SubtractDuration;
+
+        seconds {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0),
+                             duration: "90s"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 30, 30)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        milliseconds {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms(23, 32, 0),
+                             duration: "1.5ms"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms_micro(23, 31, 59, 998_500)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        iso8601 {
+            args: func_args![value: Utc.ymd(2021, 3, 1).and_hms(0, 0, 0),
+                             duration: "P1D"],
+            want: Ok(Utc.ymd(2021, 2, 28).and_hms(0, 0, 0)),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+
+        error_months {
+            args: func_args![value: Utc.ymd(2021, 3, 1).and_hms(0, 0, 0),
+                             duration: "P1M"],
+            want: Err("months are not supported: 'P1M'"),
+            tdef: TypeDef::timestamp().fallible(),
+        }
+    ];
+}
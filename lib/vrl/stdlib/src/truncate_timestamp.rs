@@ -0,0 +1,224 @@
+use ::value::Value;
+use chrono::{Datelike, Duration, TimeZone, Timelike, Utc};
+use vrl::prelude::*;
+
+fn truncate_timestamp(value: Value, unit: Unit) -> Resolved {
+    let ts = value.try_timestamp()?;
+    let date = ts.date();
+
+    let truncated = match unit {
+        Unit::Year => Utc.ymd(ts.year(), 1, 1).and_hms(0, 0, 0),
+        Unit::Month => Utc.ymd(ts.year(), ts.month(), 1).and_hms(0, 0, 0),
+        // Weeks start on Monday, as in ISO 8601.
+        Unit::Week => {
+            let days = ts.weekday().num_days_from_monday();
+            (date - Duration::days(i64::from(days))).and_hms(0, 0, 0)
+        }
+        Unit::Day => date.and_hms(0, 0, 0),
+        Unit::Hour => date.and_hms(ts.hour(), 0, 0),
+        Unit::Minute => date.and_hms(ts.hour(), ts.minute(), 0),
+        Unit::Second => ts.with_nanosecond(0).expect("valid nanosecond"),
+        Unit::Millisecond => ts
+            .with_nanosecond(ts.nanosecond() / 1_000_000 * 1_000_000)
+            .expect("valid nanosecond"),
+        Unit::Microsecond => ts
+            .with_nanosecond(ts.nanosecond() / 1_000 * 1_000)
+            .expect("valid nanosecond"),
+    };
+
+    Ok(truncated.into())
+}
+
+fn units() -> Vec<Value> {
+    Unit::ALL.iter().map(|unit| value!(unit.as_str())).collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Unit {
+    Year,
+    Month,
+    Week,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    Millisecond,
+    Microsecond,
+}
+
+impl Unit {
+    const ALL: [Unit; 9] = [
+        Unit::Year,
+        Unit::Month,
+        Unit::Week,
+        Unit::Day,
+        Unit::Hour,
+        Unit::Minute,
+        Unit::Second,
+        Unit::Millisecond,
+        Unit::Microsecond,
+    ];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Unit::Year => "year",
+            Unit::Month => "month",
+            Unit::Week => "week",
+            Unit::Day => "day",
+            Unit::Hour => "hour",
+            Unit::Minute => "minute",
+            Unit::Second => "second",
+            Unit::Millisecond => "millisecond",
+            Unit::Microsecond => "microsecond",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TruncateTimestamp;
+
+impl Function for TruncateTimestamp {
+    fn identifier(&self) -> &'static str {
+        "truncate_timestamp"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::TIMESTAMP,
+                required: true,
+            },
+            Parameter {
+                keyword: "unit",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: (&mut state::LocalEnv, &mut state::ExternalEnv),
+        _ctx: &mut FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let unit = arguments
+            .required_enum("unit", &units())?
+            .try_bytes_utf8_lossy()
+            .expect("unit not bytes")
+            .into_owned();
+        let unit = Unit::ALL
+            .into_iter()
+            .find(|variant| variant.as_str() == unit)
+            .expect("validated enum");
+
+        Ok(Box::new(TruncateTimestampFn { value, unit }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "truncate to the hour",
+                source: r#"truncate_timestamp(t'2021-02-10T23:32:17.512Z', unit: "hour")"#,
+                result: Ok("t'2021-02-10T23:00:00Z'"),
+            },
+            Example {
+                title: "truncate to the week",
+                source: r#"truncate_timestamp(t'2021-02-10T23:32:17.512Z', unit: "week")"#,
+                result: Ok("t'2021-02-08T00:00:00Z'"),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TruncateTimestampFn {
+    value: Box<dyn Expression>,
+    unit: Unit,
+}
+
+impl Expression for TruncateTimestampFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        truncate_timestamp(value, self.unit)
+    }
+
+    fn type_def(&self, _: (&state::LocalEnv, &state::ExternalEnv)) -> TypeDef {
+        TypeDef::timestamp()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        truncate_timestamp => TruncateTimestamp;
+
+        year {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512),
+                             unit: "year"],
+            want: Ok(Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        month {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512),
+                             unit: "month"],
+            want: Ok(Utc.ymd(2021, 2, 1).and_hms(0, 0, 0)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        week {
+            args: func_args![value: Utc.ymd(2021, 1, 3).and_hms_milli(23, 32, 17, 512),
+                             unit: "week"],
+            want: Ok(Utc.ymd(2020, 12, 28).and_hms(0, 0, 0)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        day {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512),
+                             unit: "day"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms(0, 0, 0)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        hour {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512),
+                             unit: "hour"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 0, 0)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        minute {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512),
+                             unit: "minute"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 32, 0)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        second {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512),
+                             unit: "second"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms(23, 32, 17)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        millisecond {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_nano(23, 32, 17, 512_345_678),
+                             unit: "millisecond"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms_milli(23, 32, 17, 512)),
+            tdef: TypeDef::timestamp(),
+        }
+
+        microsecond {
+            args: func_args![value: Utc.ymd(2021, 2, 10).and_hms_nano(23, 32, 17, 512_345_678),
+                             unit: "microsecond"],
+            want: Ok(Utc.ymd(2021, 2, 10).and_hms_micro(23, 32, 17, 512_345)),
+            tdef: TypeDef::timestamp(),
+        }
+    ];
+}
@@ -204,7 +204,11 @@ fn main() {
             .unwrap_or_default();
 
         match program {
-            Ok((program, warnings)) if warnings.is_empty() => {
+            // Programs compiling with warnings run, unless the test expects
+            // these warnings.
+            Ok((program, warnings))
+                if warnings.is_empty() || !want.trim_start().starts_with("warning") =>
+            {
                 let run_start = Instant::now();
                 let result = run_vrl(
                     runtime,
@@ -1,11 +1,19 @@
-use std::sync::{
-    atomic::{self, AtomicBool},
-    Arc,
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
 };
 
 use serde_json::json;
 use warp::{reply::json, Rejection, Reply};
 
+use crate::{
+    config::api::ComponentHealthOptions,
+    topology::health::{self, ComponentHealth},
+};
+
 // Health handler, responds with '{ ok: true }' when running and '{ ok: false}'
 // when shutting down
 pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejection> {
@@ -21,3 +29,29 @@ pub(super) async fn health(running: Arc<AtomicBool>) -> Result<impl Reply, Rejec
         ))
     }
 }
+
+// Component health handler, responds with the health of each component, and `ok: false` when any
+// component is starting or failed, or degraded if configured so
+pub(super) async fn component_health(
+    options: ComponentHealthOptions,
+) -> Result<impl Reply, Rejection> {
+    let components = health::all()
+        .into_iter()
+        .map(|(key, health)| (key.id().to_owned(), health))
+        .collect::<BTreeMap<_, _>>();
+    let ok = components.values().all(|health| match health {
+        ComponentHealth::Healthy => true,
+        ComponentHealth::Degraded => !options.fail_on_degraded,
+        ComponentHealth::Starting | ComponentHealth::Failed => false,
+    });
+
+    let status = if ok {
+        warp::http::StatusCode::OK
+    } else {
+        warp::http::StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok(warp::reply::with_status(
+        json(&json!({ "ok": ok, "components": components })),
+        status,
+    ))
+}
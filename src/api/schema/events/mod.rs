@@ -15,7 +15,12 @@ use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{select, sync::mpsc, time};
 use tokio_stream::wrappers::ReceiverStream;
 
-use crate::{api::tap::TapController, topology::WatchRx};
+use crate::{
+    api::tap::{TapController, TapPayload},
+    conditions::{AnyCondition, Condition},
+    event::{Event, LogEvent, TraceEvent},
+    topology::WatchRx,
+};
 
 /// Patterns (glob) used by tap to match against components and access events
 /// flowing into (for_inputs) or out of (for_outputs) specified components
@@ -43,28 +48,138 @@ impl TapPatterns {
     }
 }
 
+/// Server-side filtering applied to tapped events before they're sampled. Events must match the
+/// VRL `condition`, if provided, and logs and traces are projected down to `fields`, if provided.
+#[derive(Debug, Default, Clone)]
+pub struct TapFilter {
+    condition: Option<Condition>,
+    fields: Vec<String>,
+}
+
+impl TapFilter {
+    /// Compiles the VRL `condition` source, returning an error if it isn't a valid condition.
+    pub fn new(condition: Option<&str>, fields: Vec<String>) -> crate::Result<Self> {
+        let condition = condition
+            .map(|source| AnyCondition::String(source.to_string()).build(&Default::default()))
+            .transpose()?;
+
+        Ok(Self { condition, fields })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.condition.is_none() && self.fields.is_empty()
+    }
+
+    /// Filter and project the events of a tap payload. Notifications are passed through as-is.
+    pub(crate) fn apply(&self, payload: TapPayload) -> TapPayload {
+        if self.is_empty() {
+            return payload;
+        }
+
+        match payload {
+            TapPayload::Log(output, logs) => TapPayload::Log(
+                output,
+                logs.into_iter()
+                    .filter_map(|log| self.filter(log.into()))
+                    .map(Event::into_log)
+                    .collect(),
+            ),
+            TapPayload::Metric(output, metrics) => TapPayload::Metric(
+                output,
+                metrics
+                    .into_iter()
+                    .filter_map(|metric| self.filter(metric.into()))
+                    .map(Event::into_metric)
+                    .collect(),
+            ),
+            TapPayload::Trace(output, traces) => TapPayload::Trace(
+                output,
+                traces
+                    .into_iter()
+                    .filter_map(|trace| self.filter(trace.into()))
+                    .map(Event::into_trace)
+                    .collect(),
+            ),
+            notification => notification,
+        }
+    }
+
+    fn filter(&self, event: Event) -> Option<Event> {
+        let event = match &self.condition {
+            Some(condition) => match condition.check(event) {
+                (true, event) => event,
+                (false, _) => return None,
+            },
+            None => event,
+        };
+
+        Some(self.project(event))
+    }
+
+    /// Keep only the projected fields of logs and traces. Metrics have a fixed shape, so they
+    /// aren't projected.
+    fn project(&self, event: Event) -> Event {
+        if self.fields.is_empty() {
+            return event;
+        }
+
+        match event {
+            Event::Log(log) => Event::Log(self.project_log(log)),
+            Event::Trace(trace) => {
+                let (fields, metadata) = trace.into_parts();
+                let log = self.project_log(LogEvent::from_map(fields, metadata));
+                Event::Trace(TraceEvent::from(log))
+            }
+            metric => metric,
+        }
+    }
+
+    fn project_log(&self, log: LogEvent) -> LogEvent {
+        let mut projected = LogEvent::new_with_metadata(log.metadata().clone());
+        for field in &self.fields {
+            if let Some(value) = log.get(field.as_str()) {
+                projected.insert(field.as_str(), value.clone());
+            }
+        }
+        projected
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct EventsSubscription;
 
 #[Subscription]
 impl EventsSubscription {
-    /// A stream of events emitted from matched component ID patterns
+    /// A stream of events emitted from matched component ID patterns. Events can be filtered
+    /// server-side with a VRL `filter` condition, and projected down to a list of `fields`.
+    #[allow(clippy::too_many_arguments)]
     pub async fn output_events_by_component_id_patterns<'a>(
         &'a self,
         ctx: &'a Context<'a>,
         outputs_patterns: Vec<String>,
         inputs_patterns: Option<Vec<String>>,
+        filter: Option<String>,
+        fields: Option<Vec<String>>,
         #[graphql(default = 500)] interval: u32,
         #[graphql(default = 100, validator(minimum = 1, maximum = 10_000))] limit: u32,
-    ) -> impl Stream<Item = Vec<OutputEventsPayload>> + 'a {
+    ) -> async_graphql::Result<impl Stream<Item = Vec<OutputEventsPayload>> + 'a> {
         let watch_rx = ctx.data_unchecked::<WatchRx>().clone();
 
         let patterns = TapPatterns {
             for_outputs: outputs_patterns.into_iter().collect(),
             for_inputs: inputs_patterns.unwrap_or_default().into_iter().collect(),
         };
+        let filter = TapFilter::new(filter.as_deref(), fields.unwrap_or_default())
+            .map_err(|error| format!("Invalid filter: {}", error))?;
+
         // Client input is confined to `u32` to provide sensible bounds.
-        create_events_stream(watch_rx, patterns, interval as u64, limit as usize)
+        Ok(create_filtered_events_stream(
+            watch_rx,
+            patterns,
+            filter,
+            interval as u64,
+            limit as usize,
+        ))
     }
 }
 
@@ -76,12 +191,25 @@ pub(crate) fn create_events_stream(
     patterns: TapPatterns,
     interval: u64,
     limit: usize,
+) -> impl Stream<Item = Vec<OutputEventsPayload>> {
+    create_filtered_events_stream(watch_rx, patterns, TapFilter::default(), interval, limit)
+}
+
+/// Creates an events stream like `create_events_stream`, applying the `filter` to events before
+/// they're sampled, so that only matching events count towards the `limit`.
+pub(crate) fn create_filtered_events_stream(
+    watch_rx: WatchRx,
+    patterns: TapPatterns,
+    filter: TapFilter,
+    interval: u64,
+    limit: usize,
 ) -> impl Stream<Item = Vec<OutputEventsPayload>> {
     // Channel for receiving individual tap payloads. Since we can process at most `limit` per
     // interval, this is capped to the same value.
     let (tap_tx, tap_rx) = mpsc::channel(limit);
-    let mut tap_rx = ReceiverStream::new(tap_rx)
-        .flat_map(|payload| stream::iter(<Vec<OutputEventsPayload>>::from(payload)));
+    let mut tap_rx = ReceiverStream::new(tap_rx).flat_map(move |payload| {
+        stream::iter(<Vec<OutputEventsPayload>>::from(filter.apply(payload)))
+    });
 
     // The resulting vector of `Event` sent to the client. Only one result set will be streamed
     // back to the client at a time. This value is set higher than `1` to prevent blocking the event
@@ -1,8 +1,11 @@
-use async_graphql::{Object, SimpleObject, Subscription};
+use async_graphql::{Enum, Object, SimpleObject, Subscription};
 use chrono::{DateTime, Utc};
 use tokio::time::Duration;
 use tokio_stream::{wrappers::IntervalStream, Stream, StreamExt};
 
+use super::metrics::component_errors_totals;
+use crate::topology::health::{self, ComponentHealth};
+
 #[derive(SimpleObject)]
 pub struct Heartbeat {
     utc: DateTime<Utc>,
@@ -14,6 +17,37 @@ impl Heartbeat {
     }
 }
 
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum ComponentHealthState {
+    /// The component is starting, waiting for its healthcheck to complete
+    Starting,
+    /// The component is running
+    Healthy,
+    /// The healthcheck of the component failed, or its error rate is above the configured threshold
+    Degraded,
+    /// The component stopped with an error
+    Failed,
+}
+
+impl From<ComponentHealth> for ComponentHealthState {
+    fn from(health: ComponentHealth) -> Self {
+        match health {
+            ComponentHealth::Starting => Self::Starting,
+            ComponentHealth::Healthy => Self::Healthy,
+            ComponentHealth::Degraded => Self::Degraded,
+            ComponentHealth::Failed => Self::Failed,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ComponentHealthStatus {
+    /// Component id
+    component_id: String,
+    /// Health of the component
+    health: ComponentHealthState,
+}
+
 #[derive(Default)]
 pub(super) struct HealthQuery;
 
@@ -23,6 +57,28 @@ impl HealthQuery {
     async fn health(&self) -> bool {
         true
     }
+
+    /// Health of each component
+    async fn component_health(&self) -> Vec<ComponentHealthStatus> {
+        let mut components = health::all()
+            .into_iter()
+            .map(|(key, health)| ComponentHealthStatus {
+                component_id: key.id().to_owned(),
+                health: health.into(),
+            })
+            .collect::<Vec<_>>();
+        components.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+        components
+    }
+}
+
+/// Updates the health of the components from their error rate since the previous call.
+pub fn record_component_errors(max_errors_per_sec: f64) {
+    let totals = component_errors_totals();
+    for (key, _) in health::all() {
+        let total = totals.get(key.id()).copied().unwrap_or(0.0);
+        health::record_errors(&key, total, max_errors_per_sec);
+    }
 }
 
 #[derive(Default)]
@@ -0,0 +1,45 @@
+use async_graphql::{Object, SimpleObject};
+
+use crate::memory_budget;
+
+#[derive(SimpleObject)]
+pub struct ComponentMemoryUsage {
+    /// Component id
+    component_id: String,
+    /// Estimated size of the state of the component, in bytes
+    used_bytes: u64,
+}
+
+#[derive(SimpleObject)]
+pub struct MemoryBudget {
+    /// Maximum estimated size of the state of all components, in bytes, if a budget is configured
+    max_bytes: Option<u64>,
+    /// Estimated size of the state of all components, in bytes
+    used_bytes: u64,
+    /// Estimated size of the state of each component reporting it
+    components: Vec<ComponentMemoryUsage>,
+}
+
+#[derive(Default)]
+pub(super) struct MemoryBudgetQuery;
+
+#[Object]
+impl MemoryBudgetQuery {
+    /// Memory budget and estimated memory usage of the components
+    async fn memory_budget(&self) -> MemoryBudget {
+        let mut components = memory_budget::component_usage()
+            .into_iter()
+            .map(|(key, used_bytes)| ComponentMemoryUsage {
+                component_id: key.id().to_owned(),
+                used_bytes: used_bytes as u64,
+            })
+            .collect::<Vec<_>>();
+        components.sort_by(|a, b| a.component_id.cmp(&b.component_id));
+
+        MemoryBudget {
+            max_bytes: memory_budget::max_bytes().map(|max_bytes| max_bytes as u64),
+            used_bytes: memory_budget::used_bytes() as u64,
+            components,
+        }
+    }
+}
@@ -196,6 +196,26 @@ pub fn by_component_key(component_key: &ComponentKey) -> Vec<Metric> {
         .collect()
 }
 
+/// Returns the errors total of each component, by component id.
+pub fn component_errors_totals() -> BTreeMap<String, f64> {
+    let mut totals = BTreeMap::new();
+    let controller = match Controller::get() {
+        Ok(controller) => controller,
+        Err(_) => return totals,
+    };
+    for metric in controller.capture_metrics() {
+        if metric.name() != "component_errors_total" {
+            continue;
+        }
+        if let (Some(id), MetricValue::Counter { value }) =
+            (metric.tag_value("component_id"), metric.value())
+        {
+            *totals.entry(id).or_insert(0.0) += value;
+        }
+    }
+    totals
+}
+
 type MetricFilterFn = dyn Fn(&Metric) -> bool + Send + Sync;
 
 /// Returns a stream of `Vec<Metric>`, where `metric_name` matches the name of the metric
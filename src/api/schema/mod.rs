@@ -1,7 +1,8 @@
 pub mod components;
 pub mod events;
 pub mod filter;
-mod health;
+pub(crate) mod health;
+mod memory_budget;
 mod meta;
 mod metrics;
 mod relay;
@@ -15,6 +16,7 @@ pub struct Query(
     components::ComponentsQuery,
     metrics::MetricsQuery,
     meta::MetaQuery,
+    memory_budget::MemoryBudgetQuery,
 );
 
 #[derive(MergedSubscription, Default)]
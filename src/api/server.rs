@@ -9,14 +9,18 @@ use async_graphql::{
     Data, Request, Schema,
 };
 use async_graphql_warp::{graphql_protocol, GraphQLResponse, GraphQLWebSocket};
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, time::Duration};
 use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
 
 use super::{handler, schema, ShutdownTx};
-use crate::{config, topology};
+use crate::{
+    config::{self, api::ComponentHealthOptions},
+    topology,
+};
 
 pub struct Server {
     _shutdown: ShutdownTx,
+    _health_shutdown: ShutdownTx,
     addr: SocketAddr,
 }
 
@@ -28,7 +32,12 @@ impl Server {
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
     ) -> Self {
-        let routes = make_routes(config.api.playground, watch_rx, running);
+        let routes = make_routes(
+            config.api.playground,
+            config.api.component_health,
+            watch_rx,
+            running,
+        );
 
         let (_shutdown, rx) = oneshot::channel();
         let (addr, server) = warp::serve(routes).bind_with_graceful_shutdown(
@@ -44,7 +53,27 @@ impl Server {
         // Spawn the server in the background.
         tokio::spawn(server);
 
-        Self { _shutdown, addr }
+        // Periodically update the health of the components from their error rate.
+        let (_health_shutdown, mut health_rx) = oneshot::channel();
+        let health_options = config.api.component_health;
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(health_options.window_secs.max(1)));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        schema::health::record_component_errors(health_options.max_errors_per_sec);
+                    }
+                    _ = &mut health_rx => break,
+                }
+            }
+        });
+
+        Self {
+            _shutdown,
+            _health_shutdown,
+            addr,
+        }
     }
 
     /// Returns a copy of the SocketAddr that the server was started on.
@@ -62,11 +91,17 @@ impl Server {
 
 fn make_routes(
     playground: bool,
+    component_health: ComponentHealthOptions,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
+    // Component health.
+    let component_health = warp::path!("health" / "components")
+        .map(move || component_health)
+        .and_then(handler::component_health);
+
     // Health.
     let health = warp::path("health")
         .and(with_shared(running))
@@ -130,7 +165,8 @@ fn make_routes(
 
     // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
     // cross-origin interaction with the Vector API.
-    health
+    component_health
+        .or(health)
         .or(graphql_handler)
         .or(graphql_playground)
         .or(not_found)
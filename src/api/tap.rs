@@ -424,7 +424,7 @@ mod tests {
 
     use super::*;
     use crate::api::schema::events::output::OutputEventsPayload;
-    use crate::api::schema::events::{create_events_stream, log, metric};
+    use crate::api::schema::events::{create_events_stream, log, metric, TapFilter};
     use crate::config::{Config, OutputId};
     use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
     use crate::sinks::blackhole::BlackholeConfig;
@@ -449,6 +449,37 @@ mod tests {
         }
     }
 
+    #[test]
+    /// A tap filter should drop events not matching its condition, and project the rest.
+    fn filters_and_projects_events() {
+        let filter =
+            TapFilter::new(Some(r#".level == "error""#), vec!["message".to_string()]).unwrap();
+        let output = TapOutput {
+            output_id: OutputId::from(&ComponentKey::from("test")),
+            component_kind: "source",
+            component_type: "demo".to_string(),
+        };
+
+        let mut error = LogEvent::from("boom");
+        error.insert("level", "error");
+        let mut info = LogEvent::from("fine");
+        info.insert("level", "info");
+
+        match filter.apply(TapPayload::Log(output, vec![error, info])) {
+            TapPayload::Log(_, logs) => {
+                assert_eq!(logs.len(), 1);
+                assert_eq!(logs[0].get("message"), Some(&"boom".into()));
+                assert_eq!(logs[0].get("level"), None);
+            }
+            _ => panic!("Expected a log payload."),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_filter() {
+        assert!(TapFilter::new(Some(".level =="), Vec::new()).is_err());
+    }
+
     #[tokio::test]
     /// A tap sink should match a pattern, receive the correct notifications,
     /// and receive events
@@ -579,6 +610,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -634,6 +666,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -682,6 +715,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -733,6 +767,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -805,6 +840,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -863,6 +899,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -937,6 +974,7 @@ mod tests {
                 print_interval_secs: 1,
                 rate: None,
                 acknowledgements: Default::default(),
+                ..Default::default()
             },
         );
 
@@ -9,22 +9,29 @@ use tokio::{
     sync::mpsc,
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
+use vector_core::config::RunAsConfig;
 
 #[cfg(feature = "enterprise")]
 use crate::config::enterprise::{
     attach_enterprise_components, report_configuration, report_on_reload, EnterpriseError,
     EnterpriseMetadata, EnterpriseReporter,
 };
+#[cfg(all(feature = "api-client", feature = "transforms-remap"))]
+use crate::dry_run;
 #[cfg(not(feature = "enterprise-tests"))]
 use crate::metrics;
 #[cfg(windows)]
 use crate::service;
+#[cfg(feature = "vrl-cli")]
+use crate::vrl_cmd;
 #[cfg(feature = "api")]
 use crate::{api, internal_events::ApiStarted};
 use crate::{
+    audit::{AuditLog, ChangeSummary, ReloadOutcome, ReloadTrigger},
+    bench, buffer_cmd,
     cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand},
     config::{self},
-    generate, graph, heartbeat, list,
+    convert_config, generate, graph, heartbeat, list,
     signal::{self, SignalTo},
     topology::{self, RunningTopology},
     trace, unit_test, validate,
@@ -34,6 +41,9 @@ use crate::{tap, top};
 
 pub static WORKER_THREADS: OnceNonZeroUsize = OnceNonZeroUsize::new();
 
+#[cfg(feature = "enterprise")]
+const MISSING_API_KEY: &str = "Enterprise configuration incomplete: missing API key.";
+
 use crate::internal_events::{
     VectorConfigLoadError, VectorQuit, VectorRecoveryError, VectorReloadError, VectorReloaded,
     VectorStarted, VectorStopped,
@@ -51,6 +61,7 @@ pub struct ApplicationConfig {
     pub enterprise: Option<EnterpriseReporter<BoxFuture<'static, ()>>>,
     pub signal_handler: signal::SignalHandler,
     pub signal_rx: signal::SignalRx,
+    pub audit: AuditLog,
 }
 
 pub struct Application {
@@ -146,19 +157,24 @@ impl Application {
                     let code = match s {
                         SubCommand::Generate(g) => generate::cmd(&g),
                         SubCommand::Graph(g) => graph::cmd(&g),
+                        SubCommand::Buffer(b) => buffer_cmd::cmd(&b),
                         SubCommand::Config(c) => config::cmd(&c),
+                        SubCommand::ConvertConfig(c) => convert_config::cmd(&c),
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t, &mut signal_handler).await,
+                        SubCommand::Bench(b) => bench::cmd(&b, &mut signal_handler).await,
                         #[cfg(windows)]
                         SubCommand::Service(s) => service::cmd(&s),
                         #[cfg(feature = "api-client")]
                         SubCommand::Top(t) => top::cmd(&t).await,
                         #[cfg(feature = "api-client")]
                         SubCommand::Tap(t) => tap::cmd(&t, signal_rx).await,
+                        #[cfg(all(feature = "api-client", feature = "transforms-remap"))]
+                        SubCommand::DryRun(d) => dry_run::cmd(&d, signal_rx).await,
 
                         SubCommand::Validate(v) => validate::validate(&v, color).await,
                         #[cfg(feature = "vrl-cli")]
-                        SubCommand::Vrl(s) => vrl_cli::cmd::cmd(&s),
+                        SubCommand::Vrl(s) => vrl_cmd::cmd(&s).await,
                     };
 
                     return Err(code);
@@ -183,14 +199,17 @@ impl Application {
                 );
 
                 #[cfg(not(feature = "enterprise-tests"))]
-                config::init_log_schema(&config_paths, true).map_err(handle_config_errors)?;
+                config::init_log_schema(&config_paths, true).map_err(reject_startup_config)?;
 
                 let mut config = config::load_from_paths_with_provider_and_secrets(
                     &config_paths,
                     &mut signal_handler,
                 )
                 .await
-                .map_err(handle_config_errors)?;
+                .map_err(reject_startup_config)?;
+
+                // Global options can't change on reload, so neither does the audit log.
+                let audit = AuditLog::new(config.global.audit.as_ref());
 
                 if !config.healthchecks.enabled {
                     info!("Health checks are disabled.");
@@ -209,22 +228,52 @@ impl Application {
                         Some(enterprise)
                     }
                     Err(EnterpriseError::MissingApiKey) => {
-                        error!("Enterprise configuration incomplete: missing API key.");
+                        error!("{}", MISSING_API_KEY);
+                        audit.record(
+                            ReloadTrigger::Startup,
+                            ReloadOutcome::Rejected,
+                            None,
+                            &[MISSING_API_KEY.to_string()],
+                        );
                         return Err(exitcode::CONFIG);
                     }
                     Err(_) => None,
                 };
 
                 let diff = config::ConfigDiff::initial(&config);
+                let changes = ChangeSummary::initial(&config);
                 let pieces = topology::build_or_log_errors(&config, &diff, HashMap::new())
                     .await
-                    .ok_or(exitcode::CONFIG)?;
+                    .ok_or_else(|| {
+                        audit.record(
+                            ReloadTrigger::Startup,
+                            ReloadOutcome::Failed,
+                            Some(&changes),
+                            &[],
+                        );
+                        exitcode::CONFIG
+                    })?;
 
                 #[cfg(feature = "api")]
                 let api = config.api;
 
+                let run_as = config.global.run_as.clone();
+
                 let result = topology::start_validated(config, diff, pieces).await;
-                let (topology, graceful_crash) = result.ok_or(exitcode::CONFIG)?;
+                let (topology, graceful_crash) = result.ok_or_else(|| {
+                    audit.record(
+                        ReloadTrigger::Startup,
+                        ReloadOutcome::Failed,
+                        Some(&changes),
+                        &[],
+                    );
+                    exitcode::CONFIG
+                })?;
+
+                // The sources have bound the sockets they listen on by now.
+                if let Some(run_as) = run_as {
+                    drop_privileges(&run_as)?;
+                }
 
                 Ok(ApplicationConfig {
                     config_paths,
@@ -236,6 +285,7 @@ impl Application {
                     enterprise,
                     signal_handler,
                     signal_rx,
+                    audit,
                 })
             })
         }?;
@@ -265,6 +315,7 @@ impl Application {
 
         let mut signal_handler = self.config.signal_handler;
         let mut signal_rx = self.config.signal_rx;
+        let audit = self.config.audit;
 
         // Any internal_logs sources will have grabbed a copy of the
         // early buffer by this point and set up a subscriber.
@@ -272,6 +323,13 @@ impl Application {
 
         rt.block_on(async move {
             emit!(VectorStarted);
+
+            audit.record(
+                ReloadTrigger::Startup,
+                ReloadOutcome::Applied,
+                Some(&ChangeSummary::initial(topology.config())),
+                &[],
+            );
             tokio::spawn(heartbeat::heartbeat());
 
             // Configure the API server, if applicable.
@@ -297,7 +355,10 @@ impl Application {
                     signal = signal_rx.recv() => {
                         match signal {
                             Ok(SignalTo::ReloadFromConfigBuilder(config_builder)) => {
-                                match config_builder.build().map_err(handle_config_errors) {
+                                match config_builder.build().map_err(|errors| {
+                                    audit.record(ReloadTrigger::Provider, ReloadOutcome::Rejected, None, &errors);
+                                    handle_config_errors(errors)
+                                }) {
                                     Ok(mut new_config) => {
                                         new_config.healthchecks.set_require_healthy(opts.require_healthy);
 
@@ -311,16 +372,19 @@ impl Application {
                                             },
                                             Err(err) => {
                                                 if let EnterpriseError::MissingApiKey = err {
+                                                    audit.record(ReloadTrigger::Provider, ReloadOutcome::Rejected, None, &[MISSING_API_KEY.to_string()]);
                                                     emit!(VectorReloadError);
                                                     continue;
                                                 }
                                             },
                                         }
 
-                                        match topology
+                                        let changes = ChangeSummary::new(topology.config(), &new_config);
+                                        let reloaded = topology
                                             .reload_config_and_respawn(new_config)
-                                            .await
-                                        {
+                                            .await;
+                                        audit.record(ReloadTrigger::Provider, reload_outcome(&reloaded), Some(&changes), &[]);
+                                        match reloaded {
                                             Ok(true) => {
                                                 #[cfg(feature = "api")]
                                                 // Pass the new config to the API server.
@@ -352,7 +416,10 @@ impl Application {
                                 // Reload config
                                 let new_config = config::load_from_paths_with_provider_and_secrets(&config_paths, &mut signal_handler)
                                     .await
-                                    .map_err(handle_config_errors).ok();
+                                    .map_err(|errors| {
+                                        audit.record(ReloadTrigger::Disk, ReloadOutcome::Rejected, None, &errors);
+                                        handle_config_errors(errors)
+                                    }).ok();
 
                                 if let Some(mut new_config) = new_config {
                                     new_config.healthchecks.set_require_healthy(opts.require_healthy);
@@ -366,16 +433,19 @@ impl Application {
                                         },
                                         Err(err) => {
                                             if let EnterpriseError::MissingApiKey = err {
+                                                audit.record(ReloadTrigger::Disk, ReloadOutcome::Rejected, None, &[MISSING_API_KEY.to_string()]);
                                                 emit!(VectorReloadError);
                                                 continue;
                                             }
                                         },
                                     }
 
-                                    match topology
+                                    let changes = ChangeSummary::new(topology.config(), &new_config);
+                                    let reloaded = topology
                                         .reload_config_and_respawn(new_config)
-                                        .await
-                                    {
+                                        .await;
+                                    audit.record(ReloadTrigger::Disk, reload_outcome(&reloaded), Some(&changes), &[]);
+                                    match reloaded {
                                         Ok(true) => {
                                             #[cfg(feature = "api")]
                                             // Pass the new config to the API server.
@@ -432,3 +502,33 @@ impl Application {
         });
     }
 }
+
+/// Records a configuration that failed to load at startup, and logs its errors. The audit file is
+/// set in the configuration itself, so the record is only emitted as an internal event.
+fn reject_startup_config(errors: Vec<String>) -> exitcode::ExitCode {
+    AuditLog::default().record(ReloadTrigger::Startup, ReloadOutcome::Rejected, None, &errors);
+    handle_config_errors(errors)
+}
+
+/// The outcome of a reload, as recorded in the audit log.
+const fn reload_outcome(reloaded: &Result<bool, ()>) -> ReloadOutcome {
+    match reloaded {
+        Ok(true) => ReloadOutcome::Applied,
+        Ok(false) => ReloadOutcome::RolledBack,
+        Err(()) => ReloadOutcome::Failed,
+    }
+}
+
+#[cfg(unix)]
+fn drop_privileges(run_as: &RunAsConfig) -> Result<(), exitcode::ExitCode> {
+    crate::privileges::drop_privileges(run_as).map_err(|error| {
+        error!(message = "Failed dropping privileges.", %error);
+        exitcode::NOPERM
+    })
+}
+
+#[cfg(not(unix))]
+fn drop_privileges(_run_as: &RunAsConfig) -> Result<(), exitcode::ExitCode> {
+    error!(message = "The `run_as` option is only supported on Unix.");
+    Err(exitcode::CONFIG)
+}
@@ -0,0 +1,265 @@
+//! Audit log of configuration changes.
+//!
+//! Every load of the configuration, at startup or on reload, is recorded as an internal event,
+//! with what triggered it, which components it added, changed or removed, and whether it was
+//! applied, rejected or rolled back. When the global `audit` option is set, the records are also
+//! appended to a file, one JSON object per line, as evidence of the changes made to the pipelines.
+
+use std::{
+    collections::HashSet,
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use vector_core::config::AuditConfig;
+
+use crate::{
+    config::{Config, ConfigDiff, Difference},
+    internal_events::{ConfigAuditWriteError, ConfigChangeAudited},
+};
+
+/// What triggered a configuration load.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadTrigger {
+    /// The initial load, when Vector starts.
+    Startup,
+
+    /// A reload of the configuration files, on `SIGHUP` or when watching them.
+    Disk,
+
+    /// A new configuration from the configured provider.
+    Provider,
+}
+
+impl ReloadTrigger {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Startup => "startup",
+            Self::Disk => "disk",
+            Self::Provider => "provider",
+        }
+    }
+}
+
+/// What became of a configuration load.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReloadOutcome {
+    /// The configuration is now running.
+    Applied,
+
+    /// The configuration couldn't be loaded, and the running one was left untouched.
+    Rejected,
+
+    /// The configuration couldn't be applied, and the previous one was kept or restored.
+    RolledBack,
+
+    /// The configuration failed to start, and there was no previous one to restore, or it couldn't
+    /// be restored either.
+    Failed,
+}
+
+impl ReloadOutcome {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Applied => "applied",
+            Self::Rejected => "rejected",
+            Self::RolledBack => "rolled_back",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// The components added, changed and removed by a configuration, by kind.
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+pub struct ChangeSummary {
+    #[serde(skip_serializing_if = "ComponentChanges::is_empty")]
+    sources: ComponentChanges,
+    #[serde(skip_serializing_if = "ComponentChanges::is_empty")]
+    transforms: ComponentChanges,
+    #[serde(skip_serializing_if = "ComponentChanges::is_empty")]
+    sinks: ComponentChanges,
+    #[serde(skip_serializing_if = "ComponentChanges::is_empty")]
+    enrichment_tables: ComponentChanges,
+}
+
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+struct ComponentChanges {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    added: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    changed: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    removed: Vec<String>,
+}
+
+impl ComponentChanges {
+    fn new(difference: &Difference) -> Self {
+        let sorted = |keys: &HashSet<_>| {
+            let mut keys = keys.iter().map(ToString::to_string).collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+        Self {
+            added: sorted(&difference.to_add),
+            changed: sorted(&difference.to_change),
+            removed: sorted(&difference.to_remove),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl ChangeSummary {
+    /// Summarizes the changes from the running configuration to a new one.
+    pub fn new(old: &Config, new: &Config) -> Self {
+        Self::from_diff(&ConfigDiff::new(old, new))
+    }
+
+    /// Summarizes the initial configuration, all of whose components are added.
+    pub fn initial(config: &Config) -> Self {
+        Self::from_diff(&ConfigDiff::initial(config))
+    }
+
+    fn from_diff(diff: &ConfigDiff) -> Self {
+        Self {
+            sources: ComponentChanges::new(&diff.sources),
+            transforms: ComponentChanges::new(&diff.transforms),
+            sinks: ComponentChanges::new(&diff.sinks),
+            enrichment_tables: ComponentChanges::new(&diff.enrichment_tables),
+        }
+    }
+
+    /// A short description of the changes, such as `2 added, 1 changed, 0 removed`.
+    fn describe(&self) -> String {
+        let (mut added, mut changed, mut removed) = (0, 0, 0);
+        for kind in [
+            &self.sources,
+            &self.transforms,
+            &self.sinks,
+            &self.enrichment_tables,
+        ] {
+            added += kind.added.len();
+            changed += kind.changed.len();
+            removed += kind.removed.len();
+        }
+        format!("{} added, {} changed, {} removed", added, changed, removed)
+    }
+}
+
+/// A record of a configuration load.
+#[derive(Debug, Serialize)]
+struct AuditRecord<'a> {
+    timestamp: DateTime<Utc>,
+    trigger: ReloadTrigger,
+    outcome: ReloadOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changes: Option<&'a ChangeSummary>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    errors: &'a [String],
+}
+
+/// Records the loads of the configuration.
+#[derive(Debug, Default)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+}
+
+impl AuditLog {
+    /// Creates an audit log from the global `audit` option. Without one, the records are only
+    /// emitted as internal events.
+    pub fn new(config: Option<&AuditConfig>) -> Self {
+        Self {
+            path: config.map(|config| config.path.clone()),
+        }
+    }
+
+    /// Records a configuration load. `changes` is only known for configurations that could be
+    /// loaded, and `errors` only for those that couldn't.
+    pub fn record(
+        &self,
+        trigger: ReloadTrigger,
+        outcome: ReloadOutcome,
+        changes: Option<&ChangeSummary>,
+        errors: &[String],
+    ) {
+        let summary = match changes {
+            Some(changes) => changes.describe(),
+            None => format!("{} errors", errors.len()),
+        };
+        emit!(ConfigChangeAudited {
+            trigger: trigger.as_str(),
+            outcome: outcome.as_str(),
+            summary: &summary,
+        });
+
+        if let Some(path) = &self.path {
+            let record = AuditRecord {
+                timestamp: Utc::now(),
+                trigger,
+                outcome,
+                changes,
+                errors,
+            };
+            if let Err(error) = append(path, &record) {
+                emit!(ConfigAuditWriteError { path, error });
+            }
+        }
+    }
+}
+
+fn append(path: &Path, record: &AuditRecord<'_>) -> std::io::Result<()> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(&line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_file;
+
+    #[test]
+    fn appends_records_to_file() {
+        let path = temp_file();
+        let audit = AuditLog::new(Some(&AuditConfig { path: path.clone() }));
+
+        let changes = ChangeSummary::initial(&Config::default());
+        audit.record(
+            ReloadTrigger::Startup,
+            ReloadOutcome::Applied,
+            Some(&changes),
+            &[],
+        );
+        audit.record(
+            ReloadTrigger::Disk,
+            ReloadOutcome::Rejected,
+            None,
+            &["unknown variant `nope`".to_owned()],
+        );
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let records = contents
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["trigger"], "startup");
+        assert_eq!(records[0]["outcome"], "applied");
+        assert_eq!(records[0]["changes"], serde_json::json!({}));
+        assert_eq!(records[1]["outcome"], "rejected");
+        assert_eq!(records[1]["errors"][0], "unknown variant `nope`");
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,683 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use futures_util::{future, stream::BoxStream, FutureExt, StreamExt};
+use rand::{distributions::Alphanumeric, rngs::SmallRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use vector_core::{
+    config::{DataType, Input, LogNamespace, Output},
+    sink::{StreamSink, VectorSink},
+    ByteSizeOf,
+};
+
+use crate::{
+    cli::handle_config_errors,
+    config::{
+        self, AcknowledgementsConfig, ComponentKey, ConfigBuilder, SinkConfig, SinkContext,
+        SinkOuter, SourceConfig, SourceContext, SourceOuter,
+    },
+    event::{Event, LogEvent, Value},
+    signal,
+    sinks::Healthcheck,
+    sources,
+    topology::{self, builder},
+};
+
+/// Name of the source sending the synthetic events.
+const BENCH_SOURCE: &str = "_bench_source";
+
+/// Field holding the time at which an event was sent, used to measure the latency.
+const SENT_AT_FIELD: &str = "_bench_sent_at_ns";
+
+/// Maximum number of distinct events generated, the events sent cycle through them.
+const MAX_TEMPLATES: usize = 1024;
+
+/// Maximum number of latencies kept to compute the percentiles, sampled from all the events.
+const MAX_LATENCY_SAMPLES: usize = 100_000;
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files in TOML format to benchmark.
+    #[clap(name = "config-toml", long, use_value_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format to benchmark.
+    #[clap(name = "config-json", long, use_value_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format to benchmark.
+    #[clap(name = "config-yaml", long, use_value_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Any number of Vector config files to benchmark. If none are specified the
+    /// default config path `/etc/vector/vector.toml` will be targeted.
+    #[clap(use_value_delimiter(true))]
+    paths: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[clap(
+        name = "config-dir",
+        short = 'C',
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_value_delimiter(true)
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    /// Components under test, receiving the synthetic events. By default the synthetic events
+    /// are sent to the components reading from the sources of the config.
+    #[clap(name = "input", long, use_value_delimiter(true))]
+    inputs: Vec<String>,
+
+    /// Number of events to send.
+    #[clap(long, default_value = "1000000")]
+    events: usize,
+
+    /// Number of events sent at once.
+    #[clap(long, default_value = "1000")]
+    batch_size: usize,
+
+    /// Minimum size of the message of the events, in bytes.
+    #[clap(long, default_value = "256")]
+    min_message_bytes: usize,
+
+    /// Maximum size of the message of the events, in bytes. Sizes are uniformly distributed
+    /// between the minimum and the maximum.
+    #[clap(long, default_value = "256")]
+    max_message_bytes: usize,
+
+    /// Number of fields added to the events besides the message and the timestamp.
+    #[clap(long, default_value = "0")]
+    fields: usize,
+
+    /// Keep the sinks of the config instead of replacing them with sinks discarding the events.
+    /// Latency isn't measured when the sinks are kept.
+    #[clap(long)]
+    keep_sinks: bool,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+pub async fn cmd(opts: &Opts, signal_handler: &mut signal::SignalHandler) -> exitcode::ExitCode {
+    if opts.batch_size == 0 || opts.min_message_bytes > opts.max_message_bytes {
+        error!(
+            message = "Invalid workload: batch size must be positive and the minimum message size must not exceed the maximum."
+        );
+        return exitcode::USAGE;
+    }
+
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let mut builder = match load_builder(&paths, signal_handler) {
+        Ok(builder) => builder,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    let stats = Arc::new(BenchStats::default());
+    if let Err(errors) = prepare(&mut builder, opts, &stats) {
+        return handle_config_errors(errors);
+    }
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => return handle_config_errors(errors),
+    };
+    let diff = config::ConfigDiff::initial(&config);
+    let pieces = match builder::build_pieces(&config, &diff, HashMap::new()).await {
+        Ok(pieces) => pieces,
+        Err(errors) => return handle_config_errors(errors),
+    };
+
+    let baseline = allocated_bytes();
+    let peak = Arc::new(AtomicUsize::new(baseline.unwrap_or(0)));
+    let sampler = baseline.map(|_| tokio::spawn(sample_allocations(Arc::clone(&peak))));
+
+    let start = Instant::now();
+    let topology = match topology::start_validated(config, diff, pieces).await {
+        Some((topology, _)) => topology,
+        None => return exitcode::CONFIG,
+    };
+    topology.sources_finished().await;
+    topology.stop().await;
+    let elapsed = start.elapsed();
+
+    if let Some(sampler) = sampler {
+        sampler.abort();
+    }
+    let allocations = baseline.map(|baseline| (baseline, peak.load(Ordering::Relaxed)));
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}", stats.report(elapsed, !opts.keep_sinks, allocations));
+    }
+
+    exitcode::OK
+}
+
+fn load_builder(
+    paths: &[config::ConfigPath],
+    signal_handler: &mut signal::SignalHandler,
+) -> Result<ConfigBuilder, Vec<String>> {
+    config::init_log_schema(paths, false)?;
+    let (mut secrets_backends_loader, _) = config::load_secret_backends_from_paths(paths)?;
+    let (builder, _) = if secrets_backends_loader.has_secrets_to_retrieve() {
+        let resolved_secrets = secrets_backends_loader
+            .retrieve(&mut signal_handler.subscribe())
+            .map_err(|e| vec![e])?;
+        config::load_builder_from_paths_with_secrets(paths, resolved_secrets)?
+    } else {
+        config::load_builder_from_paths(paths)?
+    };
+    Ok(builder)
+}
+
+/// Replaces the sources of the config with the synthetic source, and its sinks with sinks
+/// measuring the events unless they are kept.
+fn prepare(
+    builder: &mut ConfigBuilder,
+    opts: &Opts,
+    stats: &Arc<BenchStats>,
+) -> Result<(), Vec<String>> {
+    let sources = std::mem::take(&mut builder.sources)
+        .into_keys()
+        .map(|key| key.id().to_owned())
+        .collect::<Vec<_>>();
+    rewire_inputs(builder, &sources, &opts.inputs)?;
+
+    if !opts.keep_sinks {
+        for sink in builder.sinks.values_mut() {
+            let inputs = std::mem::take(&mut sink.inputs);
+            *sink = SinkOuter::new(
+                inputs,
+                Box::new(BenchSinkConfig {
+                    stats: Some(Arc::clone(stats)),
+                }),
+            );
+        }
+    }
+
+    let events = generate_events(opts);
+    builder.sources.insert(
+        ComponentKey::from(BENCH_SOURCE),
+        SourceOuter::new(BenchSourceConfig {
+            events,
+            count: opts.events,
+            batch_size: opts.batch_size,
+            stamp: !opts.keep_sinks,
+            stats: Some(Arc::clone(stats)),
+        }),
+    );
+
+    Ok(())
+}
+
+/// Drops the inputs referring to the removed sources, and feeds the synthetic source into the
+/// components under test, defaulting to the components which were reading from the sources.
+fn rewire_inputs(
+    builder: &mut ConfigBuilder,
+    sources: &[String],
+    targets: &[String],
+) -> Result<(), Vec<String>> {
+    let is_source = |input: &String| {
+        sources.iter().any(|source| {
+            input == source
+                || input
+                    .strip_prefix(source.as_str())
+                    .map_or(false, |output| output.starts_with('.'))
+        })
+    };
+
+    let mut found = Vec::new();
+    let mut rewire = |id: &str, inputs: &mut Vec<String>| {
+        let reads_source = inputs.iter().any(is_source);
+        inputs.retain(|input| !is_source(input));
+        let targeted = if targets.is_empty() {
+            reads_source
+        } else {
+            targets.iter().any(|target| target == id)
+        };
+        if targeted {
+            inputs.push(BENCH_SOURCE.to_owned());
+            found.push(id.to_owned());
+        }
+    };
+
+    for (key, transform) in builder.transforms.iter_mut() {
+        rewire(key.id(), &mut transform.inputs);
+    }
+    for (key, sink) in builder.sinks.iter_mut() {
+        rewire(key.id(), &mut sink.inputs);
+    }
+
+    let missing = targets
+        .iter()
+        .filter(|target| !found.contains(target))
+        .map(|target| {
+            format!(
+                "Component {:?} under test is not a transform or a sink.",
+                target
+            )
+        })
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        Err(missing)
+    } else if found.is_empty() {
+        Err(vec!["No component reads from the sources of the config, use `--input` to select the components under test.".to_owned()])
+    } else {
+        Ok(())
+    }
+}
+
+/// Generates the distinct events sent by the synthetic source.
+fn generate_events(opts: &Opts) -> Vec<Event> {
+    let mut rng = SmallRng::from_entropy();
+    let random_string = |rng: &mut SmallRng, len: usize| {
+        rng.sample_iter(&Alphanumeric)
+            .take(len)
+            .map(char::from)
+            .collect::<String>()
+    };
+
+    (0..opts.events.clamp(1, MAX_TEMPLATES))
+        .map(|_| {
+            let len = rng.gen_range(opts.min_message_bytes..=opts.max_message_bytes);
+            let mut log = LogEvent::from_str_legacy(random_string(&mut rng, len));
+            for field in 0..opts.fields {
+                log.insert(
+                    format!("field_{}", field).as_str(),
+                    random_string(&mut rng, 8),
+                );
+            }
+            Event::from(log)
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct BenchStats {
+    start: Instant,
+    sent_events: AtomicUsize,
+    sent_bytes: AtomicUsize,
+    received_events: AtomicUsize,
+    latencies_ns: Mutex<Reservoir>,
+}
+
+impl Default for BenchStats {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            sent_events: AtomicUsize::new(0),
+            sent_bytes: AtomicUsize::new(0),
+            received_events: AtomicUsize::new(0),
+            latencies_ns: Mutex::new(Reservoir::new(MAX_LATENCY_SAMPLES)),
+        }
+    }
+}
+
+impl BenchStats {
+    fn now_ns(&self) -> u64 {
+        self.start.elapsed().as_nanos() as u64
+    }
+
+    fn report(
+        &self,
+        elapsed: Duration,
+        measured_latency: bool,
+        allocations: Option<(usize, usize)>,
+    ) -> String {
+        let seconds = elapsed.as_secs_f64().max(f64::EPSILON);
+        let sent_events = self.sent_events.load(Ordering::Relaxed);
+        let sent_bytes = self.sent_bytes.load(Ordering::Relaxed);
+
+        let mut lines = vec![
+            format!(
+                "Sent {} events ({:.1} MiB) in {:.3}s",
+                sent_events,
+                mebibytes(sent_bytes),
+                seconds
+            ),
+            format!(
+                "Throughput: {:.0} events/s, {:.1} MiB/s",
+                sent_events as f64 / seconds,
+                mebibytes(sent_bytes) / seconds
+            ),
+        ];
+
+        if measured_latency {
+            lines.push(format!(
+                "Received {} events in sinks",
+                self.received_events.load(Ordering::Relaxed)
+            ));
+            let reservoir = self.latencies_ns.lock().expect("poisoned lock");
+            let mut latencies = reservoir.samples.clone();
+            latencies.sort_unstable();
+            if latencies.is_empty() {
+                lines.push("Latency: no events received with their send time".to_owned());
+            } else {
+                lines.push(format!(
+                    "Latency: p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+                    percentile(&latencies, 0.5),
+                    percentile(&latencies, 0.9),
+                    percentile(&latencies, 0.99),
+                    Duration::from_nanos(reservoir.max),
+                ));
+            }
+        }
+
+        match allocations {
+            Some((baseline, peak)) => lines.push(format!(
+                "Peak allocated memory: {:.1} MiB ({:.1} MiB above baseline)",
+                mebibytes(peak),
+                mebibytes(peak.saturating_sub(baseline))
+            )),
+            None => lines.push("Allocations: not available in this build".to_owned()),
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// A uniform sample of bounded size of the values added, so that long runs don't keep the latency
+/// of every event.
+#[derive(Debug)]
+struct Reservoir {
+    samples: Vec<u64>,
+    capacity: usize,
+    seen: u64,
+    max: u64,
+    rng: SmallRng,
+}
+
+impl Reservoir {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: Vec::new(),
+            capacity,
+            seen: 0,
+            max: 0,
+            rng: SmallRng::from_entropy(),
+        }
+    }
+
+    fn add(&mut self, value: u64) {
+        self.seen += 1;
+        self.max = self.max.max(value);
+        if self.samples.len() < self.capacity {
+            self.samples.push(value);
+        } else {
+            // Each of the values seen so far stays in the sample with the same probability.
+            let index = self.rng.gen_range(0..self.seen);
+            if let Some(sample) = self.samples.get_mut(index as usize) {
+                *sample = value;
+            }
+        }
+    }
+}
+
+fn mebibytes(bytes: usize) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Returns the value at quantile `q` of the sorted, non-empty latencies.
+fn percentile(sorted: &[u64], q: f64) -> Duration {
+    let index = ((sorted.len() - 1) as f64 * q).round() as usize;
+    Duration::from_nanos(sorted[index])
+}
+
+#[cfg(feature = "tikv-jemalloc-ctl")]
+fn allocated_bytes() -> Option<usize> {
+    tikv_jemalloc_ctl::epoch::advance().ok()?;
+    tikv_jemalloc_ctl::stats::allocated::read().ok()
+}
+
+#[cfg(not(feature = "tikv-jemalloc-ctl"))]
+const fn allocated_bytes() -> Option<usize> {
+    None
+}
+
+async fn sample_allocations(peak: Arc<AtomicUsize>) {
+    let mut interval = tokio::time::interval(Duration::from_millis(10));
+    loop {
+        interval.tick().await;
+        if let Some(allocated) = allocated_bytes() {
+            peak.fetch_max(allocated, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The error building the bench components outside of `vector bench`, as they're registered like
+/// any other but only work with the workload and the stats of the command.
+const NOT_CONFIGURABLE: &str = "The `bench` components are only available to `vector bench`.";
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+struct BenchSourceConfig {
+    #[serde(skip)]
+    events: Vec<Event>,
+    count: usize,
+    batch_size: usize,
+    stamp: bool,
+    #[serde(skip)]
+    stats: Option<Arc<BenchStats>>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "bench")]
+impl SourceConfig for BenchSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
+        let templates = self
+            .events
+            .iter()
+            .map(|event| (event.clone(), event.size_of()))
+            .collect::<Vec<_>>();
+        let count = self.count;
+        let batch_size = self.batch_size;
+        let stamp = self.stamp;
+        let stats = Arc::clone(self.stats.as_ref().ok_or(NOT_CONFIGURABLE)?);
+
+        Ok(Box::pin(async move {
+            let mut out = cx.out;
+            // To appropriately shut down the topology after the source is done
+            // sending events, we need to hold on to this shutdown trigger.
+            let _shutdown = cx.shutdown;
+            let mut templates = templates.iter().cycle();
+            let mut sent = 0;
+
+            while sent < count {
+                let size = batch_size.min(count - sent);
+                let sent_at = Value::from(stats.now_ns() as i64);
+                let mut bytes = 0;
+                let batch = templates
+                    .by_ref()
+                    .take(size)
+                    .map(|(event, event_bytes)| {
+                        bytes += *event_bytes;
+                        let mut event = event.clone();
+                        if stamp {
+                            event.as_mut_log().insert(SENT_AT_FIELD, sent_at.clone());
+                        }
+                        event
+                    })
+                    .collect::<Vec<_>>();
+
+                out.send_batch(batch).await.map_err(|_| ())?;
+                sent += size;
+                stats.sent_events.fetch_add(size, Ordering::Relaxed);
+                stats.sent_bytes.fetch_add(bytes, Ordering::Relaxed);
+            }
+            Ok(())
+        }))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "bench"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct BenchSinkConfig {
+    #[serde(skip)]
+    stats: Option<Arc<BenchStats>>,
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "bench")]
+impl SinkConfig for BenchSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = BenchSink {
+            stats: Arc::clone(self.stats.as_ref().ok_or(NOT_CONFIGURABLE)?),
+        };
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "bench"
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        None
+    }
+}
+
+struct BenchSink {
+    stats: Arc<BenchStats>,
+}
+
+#[async_trait::async_trait]
+impl StreamSink<Event> for BenchSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.ready_chunks(1024);
+        while let Some(events) = input.next().await {
+            let now = self.stats.now_ns();
+            let latencies = events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Log(log) => match log.get(SENT_AT_FIELD) {
+                        Some(Value::Integer(sent_at)) => Some(now.saturating_sub(*sent_at as u64)),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            self.stats
+                .received_events
+                .fetch_add(events.len(), Ordering::Relaxed);
+            let mut reservoir = self.stats.latencies_ns.lock().expect("poisoned lock");
+            for latency in latencies {
+                reservoir.add(latency);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_events_of_the_requested_shape() {
+        let opts = Opts::parse_from([
+            "bench",
+            "--events",
+            "10",
+            "--min-message-bytes",
+            "5",
+            "--max-message-bytes",
+            "20",
+            "--fields",
+            "3",
+        ]);
+        let events = generate_events(&opts);
+
+        assert_eq!(events.len(), 10);
+        for event in events {
+            let log = event.into_log();
+            let message = log
+                .get(crate::config::log_schema().message_key())
+                .unwrap()
+                .to_string_lossy();
+            assert!((5..=20).contains(&message.len()));
+            assert!(log.contains("field_0") && log.contains("field_2"));
+            assert!(!log.contains("field_3"));
+        }
+    }
+
+    #[test]
+    fn computes_percentiles() {
+        let latencies = (1..=100).collect::<Vec<u64>>();
+
+        assert_eq!(percentile(&latencies, 0.5), Duration::from_nanos(51));
+        assert_eq!(percentile(&latencies, 0.99), Duration::from_nanos(99));
+        assert_eq!(percentile(&latencies, 1.0), Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn samples_a_bounded_number_of_latencies() {
+        let mut reservoir = Reservoir::new(10);
+        for latency in 1..=1000 {
+            reservoir.add(latency);
+        }
+
+        assert_eq!(reservoir.samples.len(), 10);
+        assert_eq!(reservoir.seen, 1000);
+        assert_eq!(reservoir.max, 1000);
+        assert!(reservoir.samples.iter().all(|latency| (1..=1000).contains(latency)));
+    }
+
+    #[tokio::test]
+    async fn bench_components_are_not_configurable() {
+        let (sender, _) = crate::SourceSender::new_test();
+        let source = BenchSourceConfig::default();
+        assert!(source.build(SourceContext::new_test(sender, None)).await.is_err());
+
+        let sink = BenchSinkConfig::default();
+        assert!(sink.build(SinkContext::new_test()).await.is_err());
+    }
+}
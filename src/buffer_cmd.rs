@@ -0,0 +1,193 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use vector_buffers::inspect::{
+    self, BufferInspection, DataFileDamage, DataFileInspection, InspectError,
+};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    #[clap(subcommand)]
+    sub_command: SubCommand,
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+enum SubCommand {
+    /// List the disk buffers in the data directory, by sink.
+    Ls(DataDirOpts),
+    /// Report the records and any damage found in each data file of a disk buffer.
+    Inspect(BufferOpts),
+    /// Truncate the damaged data files of a disk buffer to their last valid record. Vector must
+    /// not be using the buffer.
+    Repair(BufferOpts),
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct DataDirOpts {
+    /// The data directory of Vector, as set with the global `data_dir` option.
+    #[clap(long, default_value = "/var/lib/vector/")]
+    data_dir: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+struct BufferOpts {
+    /// The ID of the sink the buffer belongs to.
+    id: String,
+
+    #[clap(flatten)]
+    data_dir: DataDirOpts,
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let result = match &opts.sub_command {
+        SubCommand::Ls(opts) => ls(opts),
+        SubCommand::Inspect(opts) => inspect(opts),
+        SubCommand::Repair(opts) => repair(opts),
+    };
+
+    result.unwrap_or_else(|error| {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("{}", error);
+        }
+        match error {
+            InspectError::NotFound { .. } => exitcode::NOINPUT,
+            InspectError::Locked => exitcode::TEMPFAIL,
+            InspectError::Io { .. } => exitcode::IOERR,
+        }
+    })
+}
+
+#[allow(clippy::print_stdout)]
+fn ls(opts: &DataDirOpts) -> Result<exitcode::ExitCode, InspectError> {
+    for id in inspect::list_buffers(&opts.data_dir)? {
+        match inspect::inspect_buffer(&opts.data_dir, &id) {
+            Ok(buffer) => println!(
+                "{}\t{} data files\t{} bytes\t{} unread events{}",
+                id,
+                buffer.data_files.len(),
+                buffer.data_files.iter().map(|file| file.size).sum::<u64>(),
+                buffer.ledger.map_or_else(
+                    || "?".to_owned(),
+                    |ledger| ledger.unread_events().to_string()
+                ),
+                if buffer.is_damaged() { "\tdamaged" } else { "" },
+            ),
+            Err(error) => println!("{}\t{}", id, error),
+        }
+    }
+    Ok(exitcode::OK)
+}
+
+#[allow(clippy::print_stdout)]
+fn inspect(opts: &BufferOpts) -> Result<exitcode::ExitCode, InspectError> {
+    let buffer = inspect::inspect_buffer(&opts.data_dir.data_dir, &opts.id)?;
+    print_buffer(&buffer);
+
+    Ok(if buffer.is_damaged() {
+        exitcode::DATAERR
+    } else {
+        exitcode::OK
+    })
+}
+
+#[allow(clippy::print_stdout)]
+fn repair(opts: &BufferOpts) -> Result<exitcode::ExitCode, InspectError> {
+    let repaired = inspect::repair_buffer(&opts.data_dir.data_dir, &opts.id)?;
+    if repaired.is_empty() {
+        println!("No damaged data files found.");
+    }
+    for file in repaired {
+        println!(
+            "Truncated {} bytes from {}.",
+            file.truncated_bytes,
+            file.path.display()
+        );
+    }
+    Ok(exitcode::OK)
+}
+
+#[allow(clippy::print_stdout)]
+fn print_buffer(buffer: &BufferInspection) {
+    println!("Buffer: {}", buffer.path.display());
+    match &buffer.ledger {
+        Some(ledger) => {
+            println!(
+                "Ledger: writer at file {} next record {}, reader at file {} last record {}",
+                ledger.writer_current_data_file_id,
+                ledger.writer_next_record_id,
+                ledger.reader_current_data_file_id,
+                ledger.reader_last_record_id,
+            );
+            println!("Unread events: {}", ledger.unread_events());
+        }
+        None => println!("Ledger: missing or unreadable"),
+    }
+
+    println!("Data files:");
+    for file in &buffer.data_files {
+        println!("  {}", describe_data_file(file));
+    }
+}
+
+fn describe_data_file(file: &DataFileInspection) -> String {
+    let ids = match (file.first_record_id, file.last_record_id) {
+        (Some(first), Some(last)) => format!(", record IDs {}..={}", first, last),
+        _ => String::new(),
+    };
+    let status = match &file.damage {
+        None => "ok".to_owned(),
+        Some(DataFileDamage::PartialWrite { offset }) => {
+            format!("partial write at offset {}", offset)
+        }
+        Some(DataFileDamage::Corrupted {
+            offset,
+            calculated,
+            actual,
+        }) => format!(
+            "checksum mismatch at offset {} (calculated {:#010x}, stored {:#010x})",
+            offset, calculated, actual
+        ),
+        Some(DataFileDamage::Malformed { offset, reason }) => {
+            format!("malformed record at offset {}: {}", offset, reason)
+        }
+    };
+
+    format!(
+        "{} (file {}): {} bytes, {} records{}: {}",
+        file.path.display(),
+        file.file_id,
+        file.size,
+        file.records,
+        ids,
+        status
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_damaged_data_file() {
+        let file = DataFileInspection {
+            path: PathBuf::from("/var/lib/vector/buffer/v2/out/buffer-data-3.dat"),
+            file_id: 3,
+            size: 4096,
+            records: 2,
+            first_record_id: Some(11),
+            last_record_id: Some(21),
+            damage: Some(DataFileDamage::PartialWrite { offset: 1024 }),
+        };
+
+        assert_eq!(
+            describe_data_file(&file),
+            "/var/lib/vector/buffer/v2/out/buffer-data-3.dat (file 3): 4096 bytes, 2 records, \
+             record IDs 11..=21: partial write at offset 1024"
+        );
+    }
+}
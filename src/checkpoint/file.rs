@@ -0,0 +1,81 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+
+use super::CheckpointStore;
+
+/// Stores each checkpoint in its own file within a directory.
+#[derive(Clone, Debug)]
+pub struct FileCheckpointStore {
+    dir: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, name: &str) -> crate::Result<Option<Vec<u8>>> {
+        match tokio::fs::read(self.dir.join(name)).await {
+            Ok(checkpoint) => Ok(Some(checkpoint)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Writes the checkpoint to a temporary file flushed to disk first, then renames it over the
+    /// previous one, so that a crash leaves either checkpoint behind but never a truncated one.
+    async fn save(&self, name: &str, checkpoint: &[u8]) -> crate::Result<()> {
+        let dir = self.dir.clone();
+        let name = name.to_owned();
+        let checkpoint = checkpoint.to_vec();
+        tokio::task::spawn_blocking(move || write_atomically(&dir, &name, &checkpoint)).await??;
+        Ok(())
+    }
+}
+
+fn write_atomically(dir: &Path, name: &str, checkpoint: &[u8]) -> io::Result<()> {
+    let path = dir.join(name);
+    let temp_path = dir.join(format!("{}.tmp", name));
+
+    let mut file = File::create(&temp_path)?;
+    file.write_all(checkpoint)?;
+    file.sync_all()?;
+    fs::rename(&temp_path, &path)?;
+
+    // The rename itself is only durable once the directory is flushed too.
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn saves_and_loads_checkpoints() {
+        let dir = tempdir().unwrap();
+        let store = FileCheckpointStore::new(dir.path().to_owned());
+
+        assert_eq!(store.load("checkpoint.txt").await.unwrap(), None);
+
+        store.save("checkpoint.txt", b"first").await.unwrap();
+        store.save("checkpoint.txt", b"second").await.unwrap();
+        assert_eq!(
+            store.load("checkpoint.txt").await.unwrap(),
+            Some(b"second".to_vec())
+        );
+        assert!(!dir.path().join("checkpoint.txt.tmp").exists());
+    }
+}
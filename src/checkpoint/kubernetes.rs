@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use k8s_openapi::{api::core::v1::ConfigMap, ByteString};
+use kube::{
+    api::{ObjectMeta, Patch, PatchParams, PostParams},
+    Api, Client,
+};
+use serde_json::json;
+
+use super::CheckpointStore;
+
+const SELF_NODE_NAME_ENV_KEY: &str = "VECTOR_SELF_NODE_NAME";
+const SELF_POD_NAME_ENV_KEY: &str = "VECTOR_SELF_POD_NAME";
+
+/// Stores the checkpoints of all components in the binary data of a single ConfigMap.
+///
+/// The ConfigMap is usually shared by every instance of a DaemonSet, so the checkpoints are keyed
+/// by the identity of the instance as well as by component.
+#[derive(Clone)]
+pub struct KubernetesCheckpointStore {
+    api: Api<ConfigMap>,
+    config_map: String,
+    identity: String,
+    component: String,
+}
+
+impl KubernetesCheckpointStore {
+    pub async fn new(
+        config_map: &str,
+        namespace: Option<&str>,
+        identity: Option<&str>,
+        component: &str,
+    ) -> crate::Result<Self> {
+        let identity = match identity {
+            Some(identity) => identity.to_owned(),
+            None => default_identity()?,
+        };
+        let client = Client::try_default().await?;
+        let api = match namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        };
+        Ok(Self {
+            api,
+            config_map: config_map.to_owned(),
+            identity,
+            component: component.to_owned(),
+        })
+    }
+
+    /// ConfigMap keys may only contain alphanumeric characters, `-`, `_` and `.`.
+    fn key(&self, name: &str) -> String {
+        format!("{}.{}.{}", self.identity, self.component, name)
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+}
+
+/// The node Vector runs on, as the files a source reads on it outlive the pod, falling back to
+/// the pod and then the host.
+fn default_identity() -> crate::Result<String> {
+    std::env::var(SELF_NODE_NAME_ENV_KEY)
+        .or_else(|_| std::env::var(SELF_POD_NAME_ENV_KEY))
+        .or_else(|_| crate::get_hostname())
+        .map_err(|error| format!("couldn't identify this instance of Vector: {}", error).into())
+}
+
+#[async_trait]
+impl CheckpointStore for KubernetesCheckpointStore {
+    async fn load(&self, name: &str) -> crate::Result<Option<Vec<u8>>> {
+        let key = self.key(name);
+        let checkpoint = self
+            .api
+            .get_opt(&self.config_map)
+            .await?
+            .and_then(|config_map| config_map.binary_data)
+            .and_then(|mut data| data.remove(&key))
+            .map(|checkpoint| checkpoint.0);
+        Ok(checkpoint)
+    }
+
+    /// Merges the checkpoint into the ConfigMap, leaving the checkpoints of other components
+    /// untouched, and creates the ConfigMap if it doesn't exist yet.
+    async fn save(&self, name: &str, checkpoint: &[u8]) -> crate::Result<()> {
+        let key = self.key(name);
+        let data = ByteString(checkpoint.to_vec());
+        let patch = json!({ "binaryData": { &key: &data } });
+
+        match self
+            .api
+            .patch(
+                &self.config_map,
+                &PatchParams::default(),
+                &Patch::Merge(&patch),
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(kube::Error::Api(response)) if response.code == 404 => {
+                let config_map = ConfigMap {
+                    metadata: ObjectMeta {
+                        name: Some(self.config_map.clone()),
+                        ..ObjectMeta::default()
+                    },
+                    binary_data: Some([(key, data)].into_iter().collect()),
+                    ..ConfigMap::default()
+                };
+                match self.api.create(&PostParams::default(), &config_map).await {
+                    Ok(_) => Ok(()),
+                    // Another component created it in the meantime.
+                    Err(kube::Error::Api(response)) if response.code == 409 => {
+                        self.api
+                            .patch(
+                                &self.config_map,
+                                &PatchParams::default(),
+                                &Patch::Merge(&patch),
+                            )
+                            .await?;
+                        Ok(())
+                    }
+                    Err(error) => Err(error.into()),
+                }
+            }
+            Err(error) => Err(error.into()),
+        }
+    }
+}
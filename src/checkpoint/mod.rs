@@ -0,0 +1,74 @@
+//! Storage for source checkpoints.
+//!
+//! Sources that resume reading their input after a restart persist the position they reached as
+//! opaque checkpoints, keyed by name. Where the checkpoints are stored is configured once, with
+//! the global `checkpoint_store` option, and every store replaces a checkpoint atomically so that
+//! a crash never leaves a partially written one behind.
+
+use std::{path::PathBuf, sync::Arc};
+
+use async_trait::async_trait;
+use vector_core::config::{CheckpointStoreConfig, ComponentKey, GlobalOptions};
+
+mod file;
+#[cfg(feature = "checkpoint-stores-kubernetes")]
+mod kubernetes;
+#[cfg(feature = "checkpoint-stores-sqlite")]
+mod sqlite;
+
+pub use file::FileCheckpointStore;
+#[cfg(feature = "checkpoint-stores-kubernetes")]
+pub use kubernetes::KubernetesCheckpointStore;
+#[cfg(feature = "checkpoint-stores-sqlite")]
+pub use sqlite::SqliteCheckpointStore;
+
+/// A store of the checkpoints of a single component.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Reads a checkpoint, returning `None` if it was never saved.
+    async fn load(&self, name: &str) -> crate::Result<Option<Vec<u8>>>;
+
+    /// Atomically replaces a checkpoint.
+    async fn save(&self, name: &str, checkpoint: &[u8]) -> crate::Result<()>;
+}
+
+pub type SharedCheckpointStore = Arc<dyn CheckpointStore>;
+
+/// Builds the checkpoint store of a component from the global `checkpoint_store` option.
+///
+/// File checkpoints are kept in a subdirectory of the data directory named after the component,
+/// while the shared stores prefix the checkpoint names with the component ID.
+pub async fn build_store(
+    globals: &GlobalOptions,
+    local_data_dir: Option<&PathBuf>,
+    component: &ComponentKey,
+) -> crate::Result<SharedCheckpointStore> {
+    match &globals.checkpoint_store {
+        CheckpointStoreConfig::File => {
+            let dir = globals.resolve_and_make_data_subdir(local_data_dir, component.id())?;
+            Ok(Arc::new(FileCheckpointStore::new(dir)))
+        }
+        #[cfg(feature = "checkpoint-stores-sqlite")]
+        CheckpointStoreConfig::Sqlite { path } => Ok(Arc::new(
+            SqliteCheckpointStore::open(path, component.id()).await?,
+        )),
+        #[cfg(feature = "checkpoint-stores-kubernetes")]
+        CheckpointStoreConfig::KubernetesConfigMap {
+            name,
+            namespace,
+            identity,
+        } => Ok(Arc::new(
+            KubernetesCheckpointStore::new(
+                name,
+                namespace.as_deref(),
+                identity.as_deref(),
+                component.id(),
+            )
+            .await?,
+        )),
+        #[allow(unreachable_patterns)]
+        _ => {
+            Err("the configured checkpoint store was not compiled into this build of Vector".into())
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use rusqlite::{Connection, OptionalExtension};
+
+use super::CheckpointStore;
+
+/// Stores the checkpoints of all components in a single SQLite database.
+#[derive(Clone, Debug)]
+pub struct SqliteCheckpointStore {
+    connection: Arc<Mutex<Connection>>,
+    component: String,
+}
+
+impl SqliteCheckpointStore {
+    pub async fn open(path: &Path, component: &str) -> crate::Result<Self> {
+        let path = path.to_owned();
+        let connection = tokio::task::spawn_blocking(move || {
+            let connection = Connection::open(path)?;
+            // The write-ahead log keeps concurrent readers from blocking checkpoint writes.
+            connection.pragma_update(None, "journal_mode", "WAL")?;
+            connection.execute(
+                "CREATE TABLE IF NOT EXISTS checkpoints (
+                    component TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    checkpoint BLOB NOT NULL,
+                    PRIMARY KEY (component, name)
+                )",
+                [],
+            )?;
+            Ok::<_, rusqlite::Error>(connection)
+        })
+        .await??;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            component: component.to_owned(),
+        })
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for SqliteCheckpointStore {
+    async fn load(&self, name: &str) -> crate::Result<Option<Vec<u8>>> {
+        let connection = Arc::clone(&self.connection);
+        let component = self.component.clone();
+        let name = name.to_owned();
+        let checkpoint = tokio::task::spawn_blocking(move || {
+            connection
+                .lock()
+                .expect("poisoned lock")
+                .query_row(
+                    "SELECT checkpoint FROM checkpoints WHERE component = ?1 AND name = ?2",
+                    [&component, &name],
+                    |row| row.get(0),
+                )
+                .optional()
+        })
+        .await??;
+        Ok(checkpoint)
+    }
+
+    async fn save(&self, name: &str, checkpoint: &[u8]) -> crate::Result<()> {
+        let connection = Arc::clone(&self.connection);
+        let component = self.component.clone();
+        let name = name.to_owned();
+        let checkpoint = checkpoint.to_vec();
+        tokio::task::spawn_blocking(move || {
+            connection.lock().expect("poisoned lock").execute(
+                "INSERT INTO checkpoints (component, name, checkpoint) VALUES (?1, ?2, ?3)
+                ON CONFLICT (component, name) DO UPDATE SET checkpoint = excluded.checkpoint",
+                rusqlite::params![component, name, checkpoint],
+            )
+        })
+        .await??;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn saves_and_loads_checkpoints() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("checkpoints.db");
+        let first = SqliteCheckpointStore::open(&path, "first").await.unwrap();
+        let second = SqliteCheckpointStore::open(&path, "second").await.unwrap();
+
+        assert_eq!(first.load("checkpoint.txt").await.unwrap(), None);
+
+        first.save("checkpoint.txt", b"first").await.unwrap();
+        first.save("checkpoint.txt", b"updated").await.unwrap();
+        second.save("checkpoint.txt", b"second").await.unwrap();
+
+        assert_eq!(
+            first.load("checkpoint.txt").await.unwrap(),
+            Some(b"updated".to_vec())
+        );
+        assert_eq!(
+            second.load("checkpoint.txt").await.unwrap(),
+            Some(b"second".to_vec())
+        );
+    }
+}
@@ -2,13 +2,20 @@ use std::path::PathBuf;
 
 use clap::{AppSettings, FromArgMatches, IntoApp, Parser};
 
+#[cfg(all(feature = "api-client", feature = "transforms-remap"))]
+use crate::dry_run;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
 use crate::tap;
 #[cfg(feature = "api-client")]
 use crate::top;
-use crate::{config, generate, get_version, graph, list, unit_test, validate};
+#[cfg(feature = "vrl-cli")]
+use crate::vrl_cmd;
+use crate::{
+    bench, buffer_cmd, config, convert_config, generate, get_version, graph, list, unit_test,
+    validate,
+};
 
 #[derive(Parser, Debug)]
 #[clap(rename_all = "kebab-case")]
@@ -35,7 +42,10 @@ impl Opts {
             | Some(SubCommand::Graph(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
-            | Some(SubCommand::Test(_)) => {
+            | Some(SubCommand::Test(_))
+            | Some(SubCommand::Bench(_))
+            | Some(SubCommand::Buffer(_))
+            | Some(SubCommand::ConvertConfig(_)) => {
                 if self.root.verbose == 0 {
                     (self.root.quiet + 1, self.root.verbose)
                 } else {
@@ -183,6 +193,10 @@ pub enum SubCommand {
     #[clap(hide = true)]
     Config(config::Opts),
 
+    /// Convert a config file between the TOML, YAML and JSON formats, optionally splitting it into
+    /// a file per component. Comments are kept when converting from TOML to YAML.
+    ConvertConfig(convert_config::Opts),
+
     /// List available components, then exit.
     List(list::Opts),
 
@@ -190,9 +204,16 @@ pub enum SubCommand {
     /// For guidance on how to write unit tests check out <https://vector.dev/guides/level-up/unit-testing/>.
     Test(unit_test::Opts),
 
+    /// Run synthetic workloads against the target config in-process and report its throughput,
+    /// latency and memory usage, then exit. This command is experimental and therefore subject to change.
+    Bench(bench::Opts),
+
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// List, inspect and repair the disk buffers of sinks. Vector must be stopped to repair a buffer.
+    Buffer(buffer_cmd::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
@@ -201,13 +222,18 @@ pub enum SubCommand {
     #[cfg(feature = "api-client")]
     Tap(tap::Opts),
 
+    /// Run a candidate VRL program against log events sampled from a component of a running Vector
+    /// instance, and display how the program would change them. The running instance is not modified.
+    #[cfg(all(feature = "api-client", feature = "transforms-remap"))]
+    DryRun(dry_run::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
 
     /// Vector Remap Language CLI
     #[cfg(feature = "vrl-cli")]
-    Vrl(vrl_cli::Opts),
+    Vrl(vrl_cmd::Opts),
 }
 
 #[derive(Debug, Clone, PartialEq)]
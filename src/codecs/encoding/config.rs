@@ -106,7 +106,9 @@ impl EncodingConfigWithFraming {
             }
             (
                 None,
-                Serializer::Gelf(_)
+                Serializer::Cef(_)
+                | Serializer::Gelf(_)
+                | Serializer::Leef(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
@@ -1,4 +1,4 @@
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use codecs::{
     encoding::{Error, Framer, Serializer},
     CharacterDelimitedEncoder, NewlineDelimitedEncoder, TextSerializer,
@@ -116,8 +116,10 @@ impl Encoder<Framer> {
             (Serializer::Native(_), _) => "application/octet-stream",
             (
                 Serializer::Avro(_)
+                | Serializer::Cef(_)
                 | Serializer::Gelf(_)
                 | Serializer::Json(_)
+                | Serializer::Leef(_)
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
@@ -142,6 +144,15 @@ impl Encoder<()> {
     pub const fn serializer(&self) -> &Serializer {
         &self.serializer
     }
+
+    /// Get the encoded event without serializing it, when the serializer outputs bytes already
+    /// stored in the event. This avoids copying the payload of raw bytes pipelines.
+    pub fn passthrough(&self, event: &Event) -> Option<Bytes> {
+        match &self.serializer {
+            Serializer::RawMessage(serializer) => serializer.passthrough(event),
+            _ => None,
+        }
+    }
 }
 
 impl tokio_util::codec::Encoder<Event> for Encoder<Framer> {
@@ -0,0 +1,17 @@
+use crate::aws::ClientBuilder;
+
+pub(crate) struct CloudwatchMetricsClientBuilder;
+
+impl ClientBuilder for CloudwatchMetricsClientBuilder {
+    type Config = aws_sdk_cloudwatch::config::Config;
+    type Client = aws_sdk_cloudwatch::client::Client;
+    type DefaultMiddleware = aws_sdk_cloudwatch::middleware::DefaultMiddleware;
+
+    fn default_middleware() -> Self::DefaultMiddleware {
+        aws_sdk_cloudwatch::middleware::DefaultMiddleware::new()
+    }
+
+    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
+        aws_sdk_cloudwatch::client::Client::with_config(client, config.into())
+    }
+}
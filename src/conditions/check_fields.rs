@@ -129,13 +129,10 @@ impl ContainsPredicate {
 
 impl CheckFieldsPredicate for ContainsPredicate {
     fn check(&self, event: &Event) -> bool {
-        match event {
-            Event::Log(l) => l.get(self.target.as_str()).map_or(false, |v| {
-                let v = v.to_string_lossy();
-                self.arg.iter().any(|s| v.contains(s))
-            }),
-            _ => false,
-        }
+        log_or_trace_field(event, &self.target).map_or(false, |v| {
+            let v = v.to_string_lossy();
+            self.arg.iter().any(|s| v.contains(s))
+        })
     }
 }
 
@@ -168,13 +165,10 @@ impl StartsWithPredicate {
 
 impl CheckFieldsPredicate for StartsWithPredicate {
     fn check(&self, event: &Event) -> bool {
-        match event {
-            Event::Log(l) => l.get(self.target.as_str()).map_or(false, |v| {
-                let v = v.to_string_lossy();
-                self.arg.iter().any(|s| v.starts_with(s))
-            }),
-            _ => false,
-        }
+        log_or_trace_field(event, &self.target).map_or(false, |v| {
+            let v = v.to_string_lossy();
+            self.arg.iter().any(|s| v.starts_with(s))
+        })
     }
 }
 
@@ -205,13 +199,10 @@ impl EndsWithPredicate {
 
 impl CheckFieldsPredicate for EndsWithPredicate {
     fn check(&self, event: &Event) -> bool {
-        match event {
-            Event::Log(l) => l.get(self.target.as_str()).map_or(false, |v| {
-                let v = v.to_string_lossy();
-                self.arg.iter().any(|s| v.ends_with(s))
-            }),
-            _ => false,
-        }
+        log_or_trace_field(event, &self.target).map_or(false, |v| {
+            let v = v.to_string_lossy();
+            self.arg.iter().any(|s| v.ends_with(s))
+        })
     }
 }
 
@@ -366,15 +357,12 @@ impl IpCidrPredicate {
 
 impl CheckFieldsPredicate for IpCidrPredicate {
     fn check(&self, event: &Event) -> bool {
-        match event {
-            Event::Log(l) => l.get(self.target.as_str()).map_or(false, |v| {
-                let v = v.to_string_lossy();
-                IpAddr::from_str(&v).map_or(false, |ip_addr| {
-                    self.cidrs.iter().any(|cidr| cidr.contains(ip_addr))
-                })
-            }),
-            _ => false,
-        }
+        log_or_trace_field(event, &self.target).map_or(false, |v| {
+            let v = v.to_string_lossy();
+            IpAddr::from_str(&v).map_or(false, |ip_addr| {
+                self.cidrs.iter().any(|cidr| cidr.contains(ip_addr))
+            })
+        })
     }
 }
 
@@ -426,20 +414,26 @@ impl LengthEqualsPredicate {
 
 impl CheckFieldsPredicate for LengthEqualsPredicate {
     fn check(&self, event: &Event) -> bool {
-        match event {
-            Event::Log(l) => l.get(self.target.as_str()).map_or(false, |v| {
-                let len = match v {
-                    Value::Bytes(value) => value.len(),
-                    Value::Array(value) => value.len(),
-                    Value::Object(value) => value.len(),
-                    Value::Null => 0,
-                    value => value.to_string_lossy().len(),
-                };
-
-                len as i64 == self.arg
-            }),
-            _ => false,
-        }
+        log_or_trace_field(event, &self.target).map_or(false, |v| {
+            let len = match v {
+                Value::Bytes(value) => value.len(),
+                Value::Array(value) => value.len(),
+                Value::Object(value) => value.len(),
+                Value::Null => 0,
+                value => value.to_string_lossy().len(),
+            };
+
+            len as i64 == self.arg
+        })
+    }
+}
+
+/// Gets the field of a log or trace event targeted by a predicate.
+fn log_or_trace_field<'a>(event: &'a Event, target: &str) -> Option<&'a Value> {
+    match event {
+        Event::Log(log) => log.get(target),
+        Event::Trace(trace) => trace.get(target),
+        Event::Metric(_) => None,
     }
 }
 
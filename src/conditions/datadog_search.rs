@@ -46,11 +46,13 @@ impl ConditionalConfig for DatadogSearchConfig {
     }
 }
 
-/// Run the provided `Matcher` when we're dealing with `LogEvent`s. Otherwise, return false.
+/// Run the provided `Matcher` when we're dealing with `LogEvent`s, or against the fields of
+/// `TraceEvent`s. Otherwise, return false.
 fn as_log(matcher: Box<dyn Matcher<LogEvent>>) -> Box<dyn Matcher<Event>> {
     Run::boxed(move |ev| match ev {
         Event::Log(log) => matcher.run(log),
-        _ => false,
+        Event::Trace(trace) => matcher.run(trace.as_ref()),
+        Event::Metric(_) => false,
     })
 }
 
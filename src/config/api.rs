@@ -13,6 +13,9 @@ pub struct Options {
 
     #[serde(default = "default_playground")]
     pub playground: bool,
+
+    #[serde(default)]
+    pub component_health: ComponentHealthOptions,
 }
 
 impl Default for Options {
@@ -21,6 +24,32 @@ impl Default for Options {
             enabled: default_enabled(),
             playground: default_playground(),
             address: default_address(),
+            component_health: ComponentHealthOptions::default(),
+        }
+    }
+}
+
+/// Thresholds of the health of the components reported by the `/health/components` endpoint.
+#[derive(Debug, Deserialize, Serialize, PartialEq, Copy, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComponentHealthOptions {
+    /// The error rate of a component above which it is degraded, in errors per second.
+    pub max_errors_per_sec: f64,
+
+    /// The interval the error rate of the components is evaluated over, in seconds.
+    pub window_secs: u64,
+
+    /// Whether degraded components, and not only failed ones, make `/health/components` report
+    /// Vector as not ready.
+    pub fail_on_degraded: bool,
+}
+
+impl Default for ComponentHealthOptions {
+    fn default() -> Self {
+        Self {
+            max_errors_per_sec: 1.0,
+            window_secs: 60,
+            fail_on_degraded: false,
         }
     }
 }
@@ -61,10 +90,22 @@ impl Options {
             }
         };
 
+        // Prefer non default component health thresholds
+        let component_health = if other.component_health == ComponentHealthOptions::default() {
+            self.component_health
+        } else if self.component_health == ComponentHealthOptions::default()
+            || self.component_health == other.component_health
+        {
+            other.component_health
+        } else {
+            return Err("Conflicting `api` component health thresholds.".to_owned());
+        };
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
+            component_health,
         };
 
         *self = options;
@@ -78,6 +119,7 @@ fn bool_merge() {
         enabled: true,
         address: None,
         playground: false,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -88,6 +130,7 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
+            ..Options::default()
         }
     );
 }
@@ -99,6 +142,7 @@ fn bind_merge() {
         enabled: true,
         address: Some(address),
         playground: true,
+        ..Options::default()
     };
 
     a.merge(Options::default()).unwrap();
@@ -109,6 +153,7 @@ fn bind_merge() {
             enabled: true,
             address: Some(address),
             playground: true,
+            ..Options::default()
         }
     );
 }
@@ -6,7 +6,11 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "enterprise")]
 use serde_json::Value;
-use vector_core::{config::GlobalOptions, default_data_dir, transform::TransformConfig};
+use vector_core::{
+    config::{CheckpointStoreConfig, GlobalOptions, TelemetryConfig},
+    default_data_dir,
+    transform::TransformConfig,
+};
 
 #[cfg(feature = "api")]
 use super::api;
@@ -14,8 +18,8 @@ use super::api;
 use super::enterprise;
 use super::{
     compiler, provider, schema, ComponentKey, Config, EnrichmentTableConfig, EnrichmentTableOuter,
-    HealthcheckOptions, SecretBackend, SinkConfig, SinkOuter, SourceConfig, SourceOuter,
-    TestDefinition, TransformOuter,
+    HealthcheckOptions, PipelineDefinition, SecretBackend, SinkConfig, SinkOuter, SourceConfig,
+    SourceOuter, TestDefinition, TransformOuter,
 };
 
 #[derive(Deserialize, Serialize, Debug, Default)]
@@ -41,6 +45,8 @@ pub struct ConfigBuilder {
     pub sinks: IndexMap<ComponentKey, SinkOuter<String>>,
     #[serde(default)]
     pub transforms: IndexMap<ComponentKey, TransformOuter<String>>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub pipelines: IndexMap<String, PipelineDefinition>,
     #[serde(default)]
     pub tests: Vec<TestDefinition<String>>,
     pub provider: Option<Box<dyn provider::ProviderConfig>>,
@@ -200,6 +206,7 @@ impl From<Config> for ConfigBuilder {
             sources,
             sinks,
             transforms,
+            pipelines: IndexMap::new(),
             provider: None,
             tests,
             secret,
@@ -269,6 +276,7 @@ impl ConfigBuilder {
         let transform = TransformOuter {
             inner: Box::new(transform),
             inputs,
+            input_priorities: IndexMap::new(),
         };
 
         self.transforms
@@ -342,6 +350,42 @@ impl ConfigBuilder {
             errors.push("conflicting values for 'data_dir' found".to_owned());
         }
 
+        if self.global.checkpoint_store == CheckpointStoreConfig::default() {
+            self.global.checkpoint_store = with.global.checkpoint_store;
+        } else if with.global.checkpoint_store != CheckpointStoreConfig::default()
+            && self.global.checkpoint_store != with.global.checkpoint_store
+        {
+            errors.push("conflicting values for 'checkpoint_store' found".to_owned());
+        }
+
+        if self.global.run_as.is_none() {
+            self.global.run_as = with.global.run_as;
+        } else if with.global.run_as.is_some() && self.global.run_as != with.global.run_as {
+            errors.push("conflicting values for 'run_as' found".to_owned());
+        }
+
+        if self.global.memory_budget.is_none() {
+            self.global.memory_budget = with.global.memory_budget;
+        } else if with.global.memory_budget.is_some()
+            && self.global.memory_budget != with.global.memory_budget
+        {
+            errors.push("conflicting values for 'memory_budget' found".to_owned());
+        }
+
+        if self.global.telemetry == TelemetryConfig::default() {
+            self.global.telemetry = with.global.telemetry;
+        } else if with.global.telemetry != TelemetryConfig::default()
+            && self.global.telemetry != with.global.telemetry
+        {
+            errors.push("conflicting values for 'telemetry' found".to_owned());
+        }
+
+        if self.global.audit.is_none() {
+            self.global.audit = with.global.audit;
+        } else if with.global.audit.is_some() && self.global.audit != with.global.audit {
+            errors.push("conflicting values for 'audit' found".to_owned());
+        }
+
         // If the user has multiple config files, we must *merge* log schemas
         // until we meet a conflict, then we are allowed to error.
         if let Err(merge_errors) = self.global.log_schema.merge(&with.global.log_schema) {
@@ -370,6 +414,11 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform id found: {}", k));
             }
         });
+        with.pipelines.keys().for_each(|k| {
+            if self.pipelines.contains_key(k) {
+                errors.push(format!("duplicate pipeline name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -388,6 +437,7 @@ impl ConfigBuilder {
         self.sources.extend(with.sources);
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
+        self.pipelines.extend(with.pipelines);
         self.tests.extend(with.tests);
         self.secret.extend(with.secret);
 
@@ -1,15 +1,21 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clap::Parser;
-use serde_json::Value;
+use colored::*;
+use serde_json::{Map, Value};
 
-use super::{load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder};
+use super::{
+    load_builder_from_paths, load_source_from_paths, process_paths, ConfigBuilder, ConfigPath,
+};
 use crate::cli::handle_config_errors;
 use crate::config;
 
 #[derive(Parser, Debug, Clone)]
 #[clap(rename_all = "kebab-case")]
 pub struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// Pretty print JSON
     #[clap(short, long)]
     pretty: bool,
@@ -75,6 +81,24 @@ impl Opts {
     }
 }
 
+#[derive(Parser, Debug, Clone)]
+#[clap(rename_all = "kebab-case")]
+pub enum Command {
+    /// Semantically diff two configs, listing the added, removed and changed components and
+    /// options, and the sinks whose buffers won't be kept when reloading from one to the other.
+    Diff(DiffOpts),
+}
+
+#[derive(Parser, Debug, Clone)]
+#[clap(rename_all = "kebab-case")]
+pub struct DiffOpts {
+    /// The current config, either a file or a directory of config files.
+    old: PathBuf,
+
+    /// The config to compare to the current one, either a file or a directory of config files.
+    new: PathBuf,
+}
+
 /// Helper to merge JSON. Handles objects and array concatenation.
 fn merge_json(a: &mut Value, b: Value) {
     match (a, b) {
@@ -163,6 +187,10 @@ fn serialize_to_json(
 /// Pipelines expansions, etc. The JSON result of this serialization can itself be used as a config,
 /// which also makes it useful for version control or treating as a singular unit of configuration.
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Some(Command::Diff(diff_opts)) = &opts.command {
+        return diff_cmd(diff_opts);
+    }
+
     let paths = opts.paths_with_formats();
     // Start by serializing to a `ConfigBuilder`. This will leverage validation in config
     // builder fields which we'll use to error out if required.
@@ -190,6 +218,290 @@ pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
     exitcode::OK
 }
 
+/// Top level keys of the config holding components, with their titles.
+const COMPONENT_SECTIONS: [(&str, &str); 4] = [
+    ("sources", "Sources"),
+    ("transforms", "Transforms"),
+    ("sinks", "Sinks"),
+    ("enrichment_tables", "Enrichment tables"),
+];
+
+#[derive(Debug, PartialEq)]
+enum OptionChange {
+    Added {
+        path: String,
+        value: Value,
+    },
+    Removed {
+        path: String,
+        value: Value,
+    },
+    Changed {
+        path: String,
+        old: Value,
+        new: Value,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum ComponentChange {
+    Added {
+        id: String,
+        component_type: Option<String>,
+    },
+    Removed {
+        id: String,
+        drops_buffer: bool,
+    },
+    Changed {
+        id: String,
+        options: Vec<OptionChange>,
+        migrates_buffer: bool,
+    },
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ConfigChanges {
+    global: Vec<OptionChange>,
+    sections: Vec<(&'static str, Vec<ComponentChange>)>,
+}
+
+impl ConfigChanges {
+    fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.sections.is_empty()
+    }
+}
+
+/// Function used by the `vector config diff` subcommand, comparing the configs as loaded, with
+/// their defaults, so that only changes of behavior are listed.
+fn diff_cmd(opts: &DiffOpts) -> exitcode::ExitCode {
+    let (old, new) = match (load_diff_builder(&opts.old), load_diff_builder(&opts.new)) {
+        (Ok(old), Ok(new)) => (old, new),
+        (Err(errs), _) | (_, Err(errs)) => return handle_config_errors(errs),
+    };
+
+    let changes = diff_configs(
+        serde_json::to_value(&old).expect("should serialize ConfigBuilder to JSON. Please report."),
+        serde_json::to_value(&new).expect("should serialize ConfigBuilder to JSON. Please report."),
+    );
+
+    #[allow(clippy::print_stdout)]
+    {
+        print!("{}", render_changes(&changes));
+    }
+
+    exitcode::OK
+}
+
+fn load_diff_builder(path: &Path) -> Result<ConfigBuilder, Vec<String>> {
+    let config_path = if path.is_dir() {
+        ConfigPath::Dir(path.to_path_buf())
+    } else {
+        ConfigPath::File(path.to_path_buf(), None)
+    };
+    let paths = process_paths(&[config_path])
+        .ok_or_else(|| vec![format!("Could not load config from {:?}.", path)])?;
+    load_builder_from_paths(&paths).map(|(builder, _)| builder)
+}
+
+fn take_object(object: &mut Map<String, Value>, key: &str) -> Map<String, Value> {
+    match object.remove(key) {
+        Some(Value::Object(object)) => object,
+        _ => Map::new(),
+    }
+}
+
+/// Takes the components of a section, with their inputs sorted as their order doesn't matter.
+/// Other arrays are compared as they are, as the order of some of them does, like conditions.
+fn take_components(config: &mut Map<String, Value>, key: &str) -> Map<String, Value> {
+    let mut components = take_object(config, key);
+    for component in components.values_mut() {
+        if let Some(Value::Array(inputs)) = component.get_mut("inputs") {
+            inputs.sort_by_cached_key(Value::to_string);
+        }
+    }
+    components
+}
+
+/// Diffs two configs serialized to JSON.
+fn diff_configs(old: Value, new: Value) -> ConfigChanges {
+    let (mut old, mut new) = match (old, new) {
+        (Value::Object(old), Value::Object(new)) => (old, new),
+        _ => return ConfigChanges::default(),
+    };
+
+    // Unit tests don't affect the running topology.
+    old.remove("tests");
+    new.remove("tests");
+
+    let sections = COMPONENT_SECTIONS
+        .iter()
+        .filter_map(|(key, title)| {
+            let components = diff_components(
+                take_components(&mut old, key),
+                take_components(&mut new, key),
+                *key == "sinks",
+            );
+            (!components.is_empty()).then(|| (*title, components))
+        })
+        .collect();
+
+    // What remains are the global options.
+    let mut global = Vec::new();
+    diff_values("", &Value::Object(old), &Value::Object(new), &mut global);
+
+    ConfigChanges { global, sections }
+}
+
+fn diff_components(
+    old: Map<String, Value>,
+    new: Map<String, Value>,
+    buffered: bool,
+) -> Vec<ComponentChange> {
+    let mut changes = Vec::new();
+
+    for (id, old_component) in &old {
+        match new.get(id) {
+            None => changes.push(ComponentChange::Removed {
+                id: id.clone(),
+                drops_buffer: buffered,
+            }),
+            Some(new_component) if new_component != old_component => {
+                let mut options = Vec::new();
+                diff_values("", old_component, new_component, &mut options);
+                changes.push(ComponentChange::Changed {
+                    id: id.clone(),
+                    options,
+                    // The events of a changed buffer are kept on reload: they're moved into the new
+                    // buffer, or drained by the previous sink for disk buffers of the same version.
+                    migrates_buffer: buffered
+                        && old_component.get("buffer") != new_component.get("buffer"),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (id, new_component) in new {
+        if !old.contains_key(&id) {
+            changes.push(ComponentChange::Added {
+                id,
+                component_type: new_component
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned),
+            });
+        }
+    }
+
+    changes
+}
+
+fn diff_values(path: &str, old: &Value, new: &Value, changes: &mut Vec<OptionChange>) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                match new.get(key) {
+                    Some(new_value) => diff_values(&join(key), old_value, new_value, changes),
+                    None => changes.push(OptionChange::Removed {
+                        path: join(key),
+                        value: old_value.clone(),
+                    }),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    changes.push(OptionChange::Added {
+                        path: join(key),
+                        value: new_value.clone(),
+                    });
+                }
+            }
+        }
+        (old, new) if old != new => changes.push(OptionChange::Changed {
+            path: path.to_owned(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn render_changes(changes: &ConfigChanges) -> String {
+    if changes.is_empty() {
+        return "No changes.\n".to_owned();
+    }
+
+    let mut out = String::new();
+    if !changes.global.is_empty() {
+        out.push_str("Global options\n");
+        render_options(&mut out, &changes.global, 2);
+        out.push('\n');
+    }
+
+    for (title, components) in &changes.sections {
+        out.push_str(&format!("{}\n", title));
+        for component in components {
+            match component {
+                ComponentChange::Added { id, component_type } => {
+                    let line = match component_type {
+                        Some(component_type) => format!("  + {} ({})", id, component_type),
+                        None => format!("  + {}", id),
+                    };
+                    out.push_str(&format!("{}\n", line.green()));
+                }
+                ComponentChange::Removed { id, drops_buffer } => {
+                    out.push_str(&format!("{}\n", format!("  - {}", id).red()));
+                    if *drops_buffer {
+                        render_buffer_warning(&mut out, "buffer removed with the sink");
+                    }
+                }
+                ComponentChange::Changed {
+                    id,
+                    options,
+                    migrates_buffer,
+                } => {
+                    out.push_str(&format!("{}\n", format!("  ~ {}", id).yellow()));
+                    render_options(&mut out, options, 6);
+                    if *migrates_buffer {
+                        let note = "~ buffer configuration changed: its events are kept on reload";
+                        out.push_str(&format!("      {}\n", note.yellow()));
+                    }
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn render_options(out: &mut String, options: &[OptionChange], indent: usize) {
+    for option in options {
+        let line = match option {
+            OptionChange::Added { path, value } => format!("+ {}: {}", path, value).green(),
+            OptionChange::Removed { path, value } => format!("- {}: {}", path, value).red(),
+            OptionChange::Changed { path, old, new } => {
+                format!("~ {}: {} -> {}", path, old, new).yellow()
+            }
+        };
+        out.push_str(&format!("{:indent$}{}\n", "", line, indent = indent));
+    }
+}
+
+fn render_buffer_warning(out: &mut String, reason: &str) {
+    let warning = format!("! {}: events buffered in memory will be dropped", reason);
+    out.push_str(&format!("      {}\n", warning.red().bold()));
+}
+
 #[cfg(all(test, feature = "sources", feature = "transforms", feature = "sinks"))]
 mod tests {
     use std::collections::HashMap;
@@ -209,7 +521,7 @@ mod tests {
         generate::{generate_example, TransformInputsStrategy},
     };
 
-    use super::merge_json;
+    use super::{diff_configs, merge_json, ComponentChange, OptionChange};
 
     #[test]
     fn test_array_override() {
@@ -278,6 +590,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diffs_configs() {
+        let old = json!({
+            "data_dir": "/var/lib/vector",
+            "sources": { "in": { "type": "stdin" } },
+            "transforms": { "parse": { "type": "remap", "inputs": ["in"], "source": "." } },
+            "sinks": {
+                "out": {
+                    "type": "console",
+                    "inputs": ["parse", "in"],
+                    "buffer": { "type": "memory", "max_events": 500 }
+                },
+                "old": { "type": "blackhole", "inputs": ["in"] }
+            },
+            "tests": []
+        });
+        let new = json!({
+            "data_dir": "/tmp/vector",
+            "sources": { "in": { "type": "stdin" }, "other": { "type": "demo_logs" } },
+            "transforms": { "parse": { "type": "remap", "inputs": ["in"], "source": ". = {}" } },
+            "sinks": {
+                "out": {
+                    "type": "console",
+                    "inputs": ["in", "parse"],
+                    "buffer": { "type": "memory", "max_events": 1000 }
+                }
+            },
+            "tests": [{ "name": "new test" }]
+        });
+
+        let changes = diff_configs(old, new);
+
+        assert_eq!(
+            changes.global,
+            vec![OptionChange::Changed {
+                path: "data_dir".into(),
+                old: json!("/var/lib/vector"),
+                new: json!("/tmp/vector"),
+            }]
+        );
+        assert_eq!(
+            changes.sections,
+            vec![
+                (
+                    "Sources",
+                    vec![ComponentChange::Added {
+                        id: "other".into(),
+                        component_type: Some("demo_logs".into()),
+                    }]
+                ),
+                (
+                    "Transforms",
+                    vec![ComponentChange::Changed {
+                        id: "parse".into(),
+                        options: vec![OptionChange::Changed {
+                            path: "source".into(),
+                            old: json!("."),
+                            new: json!(". = {}"),
+                        }],
+                        migrates_buffer: false,
+                    }]
+                ),
+                (
+                    "Sinks",
+                    vec![
+                        ComponentChange::Removed {
+                            id: "old".into(),
+                            drops_buffer: true,
+                        },
+                        ComponentChange::Changed {
+                            id: "out".into(),
+                            options: vec![OptionChange::Changed {
+                                path: "buffer.max_events".into(),
+                                old: json!(500),
+                                new: json!(1000),
+                            }],
+                            migrates_buffer: true,
+                        },
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn diffs_order_of_arrays_other_than_inputs() {
+        let old = json!({
+            "transforms": {
+                "route": { "type": "filter", "inputs": ["a", "b"], "condition": ["x", "y"] }
+            }
+        });
+        let new = json!({
+            "transforms": {
+                "route": { "type": "filter", "inputs": ["b", "a"], "condition": ["y", "x"] }
+            }
+        });
+
+        let changes = diff_configs(old, new);
+
+        assert_eq!(
+            changes.sections,
+            vec![(
+                "Transforms",
+                vec![ComponentChange::Changed {
+                    id: "route".into(),
+                    options: vec![OptionChange::Changed {
+                        path: "condition".into(),
+                        old: json!(["x", "y"]),
+                        new: json!(["y", "x"]),
+                    }],
+                    migrates_buffer: false,
+                }]
+            )]
+        );
+    }
+
+    #[test]
+    fn identical_configs_have_no_changes() {
+        let config = json!({ "sources": { "in": { "type": "stdin" } } });
+
+        assert!(diff_configs(config.clone(), config).is_empty());
+    }
+
     /// Select any 2-4 sources
     fn arb_sources() -> impl Strategy<Value = Vec<&'static str>> {
         sample::subsequence(SourceDescription::types(), 2..=4)
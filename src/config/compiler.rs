@@ -3,7 +3,8 @@ use std::collections::HashSet;
 use indexmap::{IndexMap, IndexSet};
 
 use super::{
-    builder::ConfigBuilder, graph::Graph, schema, validation, ComponentKey, Config, OutputId,
+    builder::ConfigBuilder, graph::Graph, pipeline, schema, validation, ComponentKey, Config,
+    OutputId,
 };
 
 /// to handle the expansions when building the graph we need to be able to get the list of inputs
@@ -37,6 +38,11 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(name_errors);
     }
 
+    if let Err(pipeline_errors) = pipeline::instantiate_pipelines(&mut builder) {
+        errors.extend(pipeline_errors);
+        return Err(errors);
+    }
+
     let expansions = expand_macros(&mut builder)?;
 
     expand_globs(&mut builder);
@@ -71,6 +77,7 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         sources,
         sinks,
         transforms,
+        pipelines: _,
         tests,
         provider: _,
         secret,
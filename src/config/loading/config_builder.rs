@@ -1,7 +1,10 @@
-use std::{collections::HashMap, io::Read};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
 
 use indexmap::IndexMap;
-use toml::value::Table;
+use toml::{value::Table, Value};
 
 use super::{deserialize_table, loader, prepare_input, secret};
 use super::{ComponentHint, Process};
@@ -44,7 +47,42 @@ impl Process for ConfigBuilderLoader {
     }
 
     /// Merge a TOML `Table` with a `ConfigBuilder`. Component types extend specific keys.
-    fn merge(&mut self, table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+    ///
+    /// The inputs carrying an inline condition are expanded first, so that the transforms they
+    /// are replaced by go through the same checks for duplicate IDs as the other components.
+    fn merge(&mut self, mut table: Table, hint: Option<ComponentHint>) -> Result<(), Vec<String>> {
+        let mut filters = Table::new();
+        match hint {
+            Some(ComponentHint::Transform) | Some(ComponentHint::Sink) => {
+                let taken = table.keys().cloned().collect();
+                expand_input_conditions(&mut table, &taken, &mut filters)?;
+                extend_unique(&mut self.builder.transforms, deserialize_table(filters)?)?;
+            }
+            None => {
+                let taken = ["sources", "transforms", "sinks"]
+                    .iter()
+                    .filter_map(|kind| table.get(*kind).and_then(Value::as_table))
+                    .flat_map(|components| components.keys().cloned())
+                    .collect();
+                for kind in ["transforms", "sinks"] {
+                    if let Some(Value::Table(components)) = table.get_mut(kind) {
+                        expand_input_conditions(components, &taken, &mut filters)?;
+                    }
+                }
+                if !filters.is_empty() {
+                    if let Value::Table(transforms) = table
+                        .entry("transforms")
+                        .or_insert(Value::Table(Table::new()))
+                    {
+                        for (id, filter) in filters {
+                            transforms.insert(id, filter);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
         match hint {
             Some(ComponentHint::Source) => {
                 self.builder.sources.extend(deserialize_table::<
@@ -57,9 +95,7 @@ impl Process for ConfigBuilderLoader {
                 );
             }
             Some(ComponentHint::Transform) => {
-                self.builder.transforms.extend(deserialize_table::<
-                    IndexMap<ComponentKey, TransformOuter<_>>,
-                >(table)?);
+                extend_unique(&mut self.builder.transforms, deserialize_table(table)?)?;
             }
             Some(ComponentHint::EnrichmentTable) => {
                 self.builder.enrichment_tables.extend(deserialize_table::<
@@ -84,9 +120,209 @@ impl Process for ConfigBuilderLoader {
     }
 }
 
+/// Adds transforms to those already loaded, rejecting the IDs already taken as
+/// `ConfigBuilder::append` does.
+fn extend_unique(
+    transforms: &mut IndexMap<ComponentKey, TransformOuter<String>>,
+    with: IndexMap<ComponentKey, TransformOuter<String>>,
+) -> Result<(), Vec<String>> {
+    let errors = with
+        .keys()
+        .filter(|key| transforms.contains_key(*key))
+        .map(|key| format!("duplicate transform id found: {}", key))
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        transforms.extend(with);
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Replaces the inputs carrying an inline condition, like
+/// `inputs = [{ component = "app_logs", condition = '.level == "error"' }]`, by `filter`
+/// transforms reading from the input component, named after the component they feed and the
+/// position of the input, e.g. `errors_input_0`. The transforms are added to `filters`, and
+/// must not take any of the `taken` IDs.
+fn expand_input_conditions(
+    components: &mut Table,
+    taken: &HashSet<String>,
+    filters: &mut Table,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (id, component) in components.iter_mut() {
+        let inputs = match component.get_mut("inputs") {
+            Some(Value::Array(inputs)) => inputs,
+            _ => continue,
+        };
+
+        for (index, input) in inputs.iter_mut().enumerate() {
+            let mut entry = match input {
+                Value::Table(entry) => std::mem::take(entry),
+                _ => continue,
+            };
+
+            let (input_component, condition) = match (
+                entry.remove("component"),
+                entry.remove("condition"),
+            ) {
+                (Some(Value::String(input_component)), Some(condition)) if entry.is_empty() => {
+                    (input_component, condition)
+                }
+                _ => {
+                    errors.push(format!(
+                            "Input {} of \"{}\" must be a component name, or a table with only a `component` name and a `condition`.",
+                            index, id
+                        ));
+                    continue;
+                }
+            };
+
+            let filter_id = format!("{}_input_{}", id, index);
+            if taken.contains(&filter_id) || filters.contains_key(&filter_id) {
+                errors.push(format!(
+                    "The condition of input {} of \"{}\" can't be added as \"{}\", which is already a component.",
+                    index, id, filter_id
+                ));
+                continue;
+            }
+            let mut filter = Table::new();
+            filter.insert("type".to_owned(), Value::String("filter".to_owned()));
+            filter.insert(
+                "inputs".to_owned(),
+                Value::Array(vec![Value::String(input_component)]),
+            );
+            filter.insert("condition".to_owned(), condition);
+            filters.insert(filter_id.clone(), Value::Table(filter));
+
+            *input = Value::String(filter_id);
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 impl loader::Loader<ConfigBuilder> for ConfigBuilderLoader {
     /// Returns the resulting `ConfigBuilder`.
     fn take(self) -> ConfigBuilder {
         self.builder
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_input_conditions() {
+        let mut sinks: Table = toml::from_str(
+            r#"
+            [errors]
+            type = "console"
+            inputs = ["other", { component = "app_logs", condition = '.level == "error"' }]
+            "#,
+        )
+        .unwrap();
+        let mut filters = Table::new();
+
+        expand_input_conditions(&mut sinks, &HashSet::new(), &mut filters).unwrap();
+
+        let expected_sinks: Table = toml::from_str(
+            r#"
+            [errors]
+            type = "console"
+            inputs = ["other", "errors_input_1"]
+            "#,
+        )
+        .unwrap();
+        let expected_filters: Table = toml::from_str(
+            r#"
+            [errors_input_1]
+            type = "filter"
+            inputs = ["app_logs"]
+            condition = '.level == "error"'
+            "#,
+        )
+        .unwrap();
+        assert_eq!(sinks, expected_sinks);
+        assert_eq!(filters, expected_filters);
+    }
+
+    #[test]
+    fn rejects_invalid_input_conditions() {
+        let mut transforms: Table = toml::from_str(
+            r#"
+            [parse]
+            type = "remap"
+            inputs = [{ component = "app_logs" }]
+            source = "."
+            "#,
+        )
+        .unwrap();
+
+        let errors = expand_input_conditions(&mut transforms, &HashSet::new(), &mut Table::new())
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn rejects_input_conditions_colliding_in_same_file() {
+        let table: Table = toml::from_str(
+            r#"
+            [transforms.errors_input_0]
+            type = "filter"
+            inputs = ["app_logs"]
+            condition = "true"
+
+            [sinks.errors]
+            type = "console"
+            inputs = [{ component = "app_logs", condition = '.level == "error"' }]
+            "#,
+        )
+        .unwrap();
+
+        let errors = ConfigBuilderLoader::new().merge(table, None).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "The condition of input 0 of \"errors\" can't be added as \"errors_input_0\", which is already a component."
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "sinks-console", feature = "transforms-filter"))]
+    #[test]
+    fn rejects_input_conditions_colliding_with_loaded_components() {
+        let mut loader = ConfigBuilderLoader::new();
+        let loaded: Table = toml::from_str(
+            r#"
+            [transforms.errors_input_0]
+            type = "filter"
+            inputs = ["app_logs"]
+            condition = "true"
+            "#,
+        )
+        .unwrap();
+        loader.merge(loaded, None).unwrap();
+
+        let table: Table = toml::from_str(
+            r#"
+            [sinks.errors]
+            type = "console"
+            inputs = [{ component = "app_logs", condition = '.level == "error"' }]
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        let errors = loader.merge(table, None).unwrap_err();
+
+        assert_eq!(errors, vec!["duplicate transform id found: errors_input_0"]);
+    }
+}
@@ -27,6 +27,7 @@ pub mod format;
 mod graph;
 mod id;
 mod loading;
+mod pipeline;
 pub mod provider;
 mod schema;
 mod sink;
@@ -39,16 +40,22 @@ pub mod watcher;
 
 pub use builder::ConfigBuilder;
 pub use cmd::{cmd, Opts};
-pub use diff::ConfigDiff;
+pub use diff::{ConfigDiff, Difference};
 pub use format::{Format, FormatHint};
 pub use id::{ComponentKey, OutputId};
+pub use pipeline::{PipelineConfig, PipelineDefinition, PipelineParam};
 pub use loading::{
-    load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, SecretBackend,
-    CONFIG_PATHS,
+    load, load_builder_from_paths, load_builder_from_paths_with_secrets, load_from_paths,
+    load_from_paths_with_provider_and_secrets, load_from_str, load_secret_backends_from_paths,
+    load_source_from_paths, merge_path_lists, process_paths, SecretBackend, CONFIG_PATHS,
+};
+pub use sink::{
+    DegradationPolicy, SinkConfig, SinkContext, SinkDegradationOptions, SinkDescription,
+    SinkHealthcheckOptions, SinkMirrorOptions, SinkOuter,
+};
+pub use source::{
+    ReadyGuard, SourceConfig, SourceContext, SourceDescription, SourceOuter, SourceReady,
 };
-pub use sink::{SinkConfig, SinkContext, SinkDescription, SinkHealthcheckOptions, SinkOuter};
-pub use source::{SourceConfig, SourceContext, SourceDescription, SourceOuter};
 pub use transform::{TransformDescription, TransformOuter};
 pub use unit_test::{build_unit_tests, build_unit_tests_main, UnitTestResult};
 pub use validation::warnings;
@@ -677,6 +684,30 @@ mod tests {
         )
     }
 
+    #[test]
+    fn legacy_source_warnings() {
+        let config = r#"
+            [sources.in]
+            type = "basic_source"
+
+            [sinks.out]
+            type = "basic_sink"
+            inputs = ["in"]
+            "#;
+        let warnings = config::warnings(&config::load_from_str(config, Format::Toml).unwrap());
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        let config = format!("[schema]\nlog_namespace = true\n{}", config);
+        let warnings = config::warnings(&config::load_from_str(&config, Format::Toml).unwrap());
+        assert_eq!(
+            warnings,
+            vec![
+                "Log namespacing is enabled, but sources \"in\" don't support it and emit logs in \
+                 the legacy layout. Use a `log_namespace_shim` transform to convert them."
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn cycle() {
         let errors = load(
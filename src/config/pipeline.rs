@@ -0,0 +1,391 @@
+//! Reusable pipelines: named, parameterized chains of transforms.
+//!
+//! A pipeline is defined once, at the top level of the configuration:
+//!
+//! ```toml
+//! [pipelines.parse_app]
+//! params.service = {}
+//! params.level_field = { default = "level" }
+//!
+//! [[pipelines.parse_app.transforms]]
+//! type = "remap"
+//! source = '.service = "{{ params.service }}"'
+//!
+//! [[pipelines.parse_app.transforms]]
+//! type = "filter"
+//! condition = 'exists(.{{ params.level_field }})'
+//! ```
+//!
+//! and instantiated any number of times, as a transform of type `pipeline`:
+//!
+//! ```toml
+//! [transforms.app_a]
+//! type = "pipeline"
+//! pipeline = "parse_app"
+//! inputs = ["source_a"]
+//! params.service = "a"
+//! ```
+//!
+//! Each instance is replaced by the transforms of the pipeline, with the parameters substituted in
+//! their string values, chained one after the other and named `app_a.0`, `app_a.1`, and so on.
+//! The first transform reads from the inputs of the instance, and the components reading from the
+//! instance read from the last transform.
+
+use indexmap::IndexMap;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use vector_core::{
+    config::{DataType, Input, Output},
+    transform::{InnerTopology, InnerTopologyTransform, Transform, TransformConfig},
+};
+
+use super::{builder::ConfigBuilder, ComponentKey, TransformContext};
+use crate::schema;
+
+static PARAM_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*params\.([A-Za-z0-9_]+)\s*\}\}").unwrap());
+
+/// The definition of a reusable pipeline.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineDefinition {
+    /// The parameters of the pipeline, referenced as `{{ params.<name> }}` in the string values of
+    /// its transforms.
+    #[serde(default)]
+    pub params: IndexMap<String, PipelineParam>,
+
+    /// The transforms of the pipeline, in order, as they would be written in the `transforms`
+    /// section, without their `inputs`.
+    pub transforms: Vec<serde_json::Value>,
+}
+
+/// A parameter of a pipeline.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineParam {
+    /// The value of the parameter for instances not setting it. Instances must set parameters
+    /// without a default.
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// An instance of a pipeline.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PipelineConfig {
+    /// The name of the pipeline.
+    pipeline: String,
+
+    /// The values of the parameters of the pipeline.
+    #[serde(default)]
+    params: IndexMap<String, String>,
+
+    /// The transforms of the pipeline, once instantiated.
+    #[serde(skip)]
+    transforms: Vec<Box<dyn TransformConfig>>,
+}
+
+impl PipelineConfig {
+    /// Builds the transforms of this instance from the definition of its pipeline.
+    fn instantiate(&mut self, definition: &PipelineDefinition) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        for name in self.params.keys() {
+            if !definition.params.contains_key(name) {
+                errors.push(format!("unknown parameter {:?}", name));
+            }
+        }
+        let mut params = IndexMap::new();
+        for (name, param) in &definition.params {
+            match self.params.get(name).or_else(|| param.default.as_ref()) {
+                Some(value) => {
+                    params.insert(name.as_str(), value.as_str());
+                }
+                None => errors.push(format!("missing parameter {:?}", name)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        for (index, transform) in definition.transforms.iter().enumerate() {
+            if transform.get("inputs").is_some() {
+                errors.push(format!(
+                    "transform {}: transforms of a pipeline can't have inputs",
+                    index
+                ));
+                continue;
+            }
+            let mut transform = transform.clone();
+            if let Err(error) = substitute_params(&mut transform, &params) {
+                errors.push(format!("transform {}: {}", index, error));
+                continue;
+            }
+            match serde_json::from_value(transform) {
+                Ok(transform) => self.transforms.push(transform),
+                Err(error) => errors.push(format!("transform {}: {}", index, error)),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Replaces the references to parameters in the string values of `value`.
+fn substitute_params(
+    value: &mut serde_json::Value,
+    params: &IndexMap<&str, &str>,
+) -> Result<(), String> {
+    match value {
+        serde_json::Value::String(string) => {
+            let mut unknown = None;
+            let substituted = PARAM_PATTERN.replace_all(string, |captures: &Captures| {
+                let name = &captures[1];
+                params.get(name).copied().unwrap_or_else(|| {
+                    unknown = Some(name.to_owned());
+                    ""
+                })
+            });
+            if let Some(name) = unknown {
+                return Err(format!("unknown parameter {:?}", name));
+            }
+            *string = substituted.into_owned();
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                substitute_params(value, params)?;
+            }
+        }
+        serde_json::Value::Object(fields) => {
+            for value in fields.values_mut() {
+                substitute_params(value, params)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replaces the `pipeline` transforms of the builder by instances of their pipeline, ready to be
+/// expanded into their transforms.
+pub(super) fn instantiate_pipelines(builder: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, transform) in builder.transforms.iter_mut() {
+        if transform.inner.transform_type() != "pipeline" {
+            continue;
+        }
+
+        // The instance is read back from its serialized form, since the transform is only known
+        // as a `dyn TransformConfig` at this point.
+        let mut instance = match serde_json::to_value(&transform.inner)
+            .and_then(serde_json::from_value::<PipelineInstance>)
+        {
+            Ok(PipelineInstance::Pipeline(instance)) => instance,
+            Err(error) => {
+                errors.push(format!("Transform \"{}\": {}", key, error));
+                continue;
+            }
+        };
+
+        let definition = match builder.pipelines.get(&instance.pipeline) {
+            Some(definition) => definition,
+            None => {
+                errors.push(format!(
+                    "Transform \"{}\": unknown pipeline {:?}",
+                    key, instance.pipeline
+                ));
+                continue;
+            }
+        };
+
+        match instance.instantiate(definition) {
+            Ok(()) => transform.inner = Box::new(instance),
+            Err(instance_errors) => errors.extend(
+                instance_errors
+                    .into_iter()
+                    .map(|error| format!("Transform \"{}\": {}", key, error)),
+            ),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The serialized form of a `pipeline` transform, tagged with its type.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum PipelineInstance {
+    Pipeline(PipelineConfig),
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pipeline")]
+impl TransformConfig for PipelineConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Err("this transform must be expanded".into())
+    }
+
+    fn expand(
+        &mut self,
+        name: &ComponentKey,
+        inputs: &[String],
+    ) -> crate::Result<Option<InnerTopology>> {
+        let last = match self.transforms.last() {
+            Some(last) => last,
+            None => return Err(format!("pipeline {:?} has no transforms", self.pipeline).into()),
+        };
+        let last_outputs = last
+            .outputs(&schema::Definition::any())
+            .into_iter()
+            .filter(|output| output.port.is_none())
+            .collect::<Vec<_>>();
+
+        let mut inner = IndexMap::new();
+        let mut step_inputs = inputs.to_vec();
+        let mut last_key = name.clone();
+        for (index, transform) in self.transforms.drain(..).enumerate() {
+            last_key = name.join(index);
+            inner.insert(
+                last_key.clone(),
+                InnerTopologyTransform {
+                    inputs: std::mem::replace(&mut step_inputs, vec![last_key.to_string()]),
+                    inner: transform,
+                },
+            );
+        }
+
+        Ok(Some(InnerTopology {
+            inner,
+            outputs: vec![(last_key, last_outputs)],
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "pipeline"
+    }
+}
+
+#[cfg(all(
+    test,
+    feature = "sources-demo_logs",
+    feature = "transforms-remap",
+    feature = "transforms-filter",
+    feature = "sinks-blackhole"
+))]
+mod tests {
+    use crate::config::{ComponentKey, ConfigBuilder, OutputId};
+
+    const DEFINITION: &str = r#"
+        [sources.in_a]
+        type = "demo_logs"
+        format = "shuffle"
+        lines = ["a"]
+
+        [sources.in_b]
+        type = "demo_logs"
+        format = "shuffle"
+        lines = ["b"]
+
+        [pipelines.tag]
+        params.service = {}
+        params.level = { default = "info" }
+
+        [[pipelines.tag.transforms]]
+        type = "remap"
+        source = '.service = "{{ params.service }}"'
+
+        [[pipelines.tag.transforms]]
+        type = "filter"
+        condition = '.level == "{{ params.level }}"'
+    "#;
+
+    #[test]
+    fn instantiates_pipelines() {
+        let config = ConfigBuilder::from_toml(&format!(
+            r#"{}
+            [transforms.tag_a]
+            type = "pipeline"
+            pipeline = "tag"
+            inputs = ["in_a"]
+            params.service = "a"
+
+            [transforms.tag_b]
+            type = "pipeline"
+            pipeline = "tag"
+            inputs = ["in_b"]
+            params.service = "b"
+            params.level = "error"
+
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["tag_a", "tag_b"]
+            "#,
+            DEFINITION
+        ))
+        .build()
+        .unwrap();
+
+        let output = |id: &str| OutputId::from(ComponentKey::from(id));
+        for id in ["tag_a.0", "tag_a.1", "tag_b.0", "tag_b.1"] {
+            assert!(
+                config.transforms.contains_key(&ComponentKey::from(id)),
+                "missing {}",
+                id
+            );
+        }
+        assert_eq!(
+            config.transforms[&ComponentKey::from("tag_b.1")].inputs,
+            vec![output("tag_b.0")]
+        );
+        assert_eq!(
+            config.sinks[&ComponentKey::from("out")].inputs,
+            vec![output("tag_a.1"), output("tag_b.1")]
+        );
+    }
+
+    #[test]
+    fn rejects_missing_and_unknown_params() {
+        let errors = ConfigBuilder::from_toml(&format!(
+            r#"{}
+            [transforms.tag_a]
+            type = "pipeline"
+            pipeline = "tag"
+            inputs = ["in_a"]
+            params.unknown = "a"
+
+            [sinks.out]
+            type = "blackhole"
+            inputs = ["tag_a"]
+            "#,
+            DEFINITION
+        ))
+        .build()
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"Transform "tag_a": unknown parameter "unknown""#.to_owned(),
+                r#"Transform "tag_a": missing parameter "service""#.to_owned(),
+            ]
+        );
+    }
+}
@@ -1,11 +1,17 @@
+use std::path::PathBuf;
+
 use async_trait::async_trait;
 use component::ComponentDescription;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use vector_buffers::{BufferConfig, BufferType};
 use vector_core::config::{AcknowledgementsConfig, GlobalOptions, Input};
 
 use super::{component, schema, ComponentKey, ProxyConfig, Resource};
-use crate::sinks::{self, util::UriSerde};
+use crate::{
+    http::ConnectionPoolConfig,
+    sinks::{self, util::UriSerde},
+};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter<T> {
@@ -28,6 +34,18 @@ pub struct SinkOuter<T> {
     )]
     proxy: ProxyConfig,
 
+    #[serde(default, skip_serializing_if = "ConnectionPoolConfig::is_default")]
+    connection_pool: ConnectionPoolConfig,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degradation: Option<SinkDegradationOptions>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<SinkMirrorOptions>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub input_priorities: IndexMap<String, u32>,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -41,6 +59,10 @@ impl<T> SinkOuter<T> {
             healthcheck_uri: None,
             inner,
             proxy: Default::default(),
+            connection_pool: Default::default(),
+            degradation: None,
+            mirror: None,
+            input_priorities: IndexMap::new(),
         }
     }
 
@@ -79,6 +101,10 @@ impl<T> SinkOuter<T> {
         &self.proxy
     }
 
+    pub const fn connection_pool(&self) -> &ConnectionPoolConfig {
+        &self.connection_pool
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U> {
         let inputs = self.inputs.iter().map(f).collect();
         self.with_inputs(inputs)
@@ -92,6 +118,10 @@ impl<T> SinkOuter<T> {
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            connection_pool: self.connection_pool,
+            degradation: self.degradation,
+            mirror: self.mirror,
+            input_priorities: self.input_priorities,
         }
     }
 }
@@ -127,6 +157,74 @@ impl From<UriSerde> for SinkHealthcheckOptions {
     }
 }
 
+/// Makes a sink a mirror, shadowing the sinks sharing its inputs: it receives a sample of their
+/// events, without applying back pressure to its inputs nor affecting the acknowledgement of the
+/// events.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SinkMirrorOptions {
+    /// The percentage of the events sent to the sink.
+    #[serde(default = "default_mirror_percent")]
+    pub percent: f64,
+}
+
+const fn default_mirror_percent() -> f64 {
+    100.0
+}
+
+/// How a sink behaves once its downstream has been unavailable for longer than `threshold_secs`,
+/// that is once the sink stopped accepting events for that long.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SinkDegradationOptions {
+    #[serde(default = "default_degradation_threshold_secs")]
+    pub threshold_secs: u64,
+    #[serde(default)]
+    pub policy: DegradationPolicy,
+    /// The file events are spilled to, required by the `spill` policy.
+    #[serde(default)]
+    pub spill_path: Option<PathBuf>,
+    /// The maximum size of the events spilled and not replayed yet. Events are shed past it.
+    #[serde(default = "default_degradation_spill_max_bytes")]
+    pub spill_max_bytes: u64,
+}
+
+const fn default_degradation_threshold_secs() -> u64 {
+    30
+}
+
+const fn default_degradation_spill_max_bytes() -> u64 {
+    1024 * 1024 * 1024 // 1 GiB
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationPolicy {
+    /// Keep waiting for the downstream, applying back pressure.
+    Block,
+    /// Drop the oldest events held for the downstream as new events are received.
+    Shed,
+    /// Write the oldest events held for the downstream to a local file as new events are received,
+    /// and replay them once the downstream is available again.
+    Spill,
+}
+
+impl Default for DegradationPolicy {
+    fn default() -> Self {
+        Self::Block
+    }
+}
+
+impl DegradationPolicy {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Block => "block",
+            Self::Shed => "shed",
+            Self::Spill => "spill",
+        }
+    }
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait SinkConfig: core::fmt::Debug + Send + Sync {
@@ -149,9 +247,11 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
 
 #[derive(Debug, Clone)]
 pub struct SinkContext {
+    pub key: ComponentKey,
     pub healthcheck: SinkHealthcheckOptions,
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
+    pub connection_pool: ConnectionPoolConfig,
     pub schema: schema::Options,
 }
 
@@ -159,9 +259,11 @@ impl SinkContext {
     #[cfg(test)]
     pub fn new_test() -> Self {
         Self {
+            key: ComponentKey::from("default"),
             healthcheck: SinkHealthcheckOptions::default(),
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
+            connection_pool: ConnectionPoolConfig::default(),
             schema: schema::Options::default(),
         }
     }
@@ -173,6 +275,10 @@ impl SinkContext {
     pub const fn proxy(&self) -> &ProxyConfig {
         &self.proxy
     }
+
+    pub const fn connection_pool(&self) -> &ConnectionPoolConfig {
+        &self.connection_pool
+    }
 }
 
 pub type SinkDescription = ComponentDescription<Box<dyn SinkConfig>>;
@@ -1,12 +1,18 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 use async_trait::async_trait;
 use component::ComponentDescription;
 use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
 use vector_core::config::{AcknowledgementsConfig, GlobalOptions, LogNamespace, Output};
 
 use super::{component, schema, ComponentKey, ProxyConfig, Resource};
-use crate::{shutdown::ShutdownSignal, sources, SourceSender};
+use crate::{
+    leader_election::LeaderElectionConfig, shutdown::ShutdownSignal, sinks, sources, SourceSender,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct SourceOuter {
@@ -15,6 +21,14 @@ pub struct SourceOuter {
         skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
     )]
     pub proxy: ProxyConfig,
+    /// The time after which the events of the source expire, and are dropped instead of being
+    /// delivered by sinks. Expirations set by the source itself or by `remap` take precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    /// Elects a leader among the replicas of a highly available deployment, so that only one of
+    /// them runs the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader_election: Option<LeaderElectionConfig>,
     #[serde(flatten)]
     pub(crate) inner: Box<dyn SourceConfig>,
     #[serde(default, skip)]
@@ -26,6 +40,8 @@ impl SourceOuter {
         Self {
             inner: Box::new(source),
             proxy: Default::default(),
+            ttl_secs: None,
+            leader_election: None,
             sink_acknowledgements: false,
         }
     }
@@ -46,8 +62,17 @@ pub trait SourceConfig: core::fmt::Debug + Send + Sync {
     }
 
     fn can_acknowledge(&self) -> bool;
+
+    /// Builds a check of the connection to the external system the source reads from.
+    ///
+    /// This is only run by `vector validate --deep`. Sources not reading from an external
+    /// system have nothing to check.
+    fn healthcheck(&self) -> Option<sinks::Healthcheck> {
+        None
+    }
 }
 
+#[derive(Clone)]
 pub struct SourceContext {
     pub key: ComponentKey,
     pub globals: GlobalOptions,
@@ -62,6 +87,47 @@ pub struct SourceContext {
     /// Given a source can expose multiple [`Output`] channels, the ID is tied to the identifier of
     /// that `Output`.
     pub schema_definitions: HashMap<Option<String>, schema::Definition>,
+
+    /// Tells when the source holds the sockets it listens on.
+    pub ready: SourceReady,
+}
+
+/// Tells when a source holds the sockets it was started to listen on, after which `run_as` can
+/// drop the privileges that may have been needed to open them.
+///
+/// A source is ready as soon as it is built, unless it takes a guard with [`SourceReady::defer`]
+/// while being built, in which case it is ready once the guard is dropped. Sources binding their
+/// sockets in the future they return, rather than in `build`, drop the guard once bound.
+#[derive(Clone, Debug, Default)]
+pub struct SourceReady(Arc<Mutex<Option<oneshot::Sender<()>>>>);
+
+impl SourceReady {
+    pub(crate) fn new() -> (Self, oneshot::Receiver<()>) {
+        let (tx, rx) = oneshot::channel();
+        (Self(Arc::new(Mutex::new(Some(tx)))), rx)
+    }
+
+    /// Defers the readiness of the source until the returned guard is dropped.
+    pub fn defer(&self) -> ReadyGuard {
+        ReadyGuard(self.0.lock().expect("poisoned lock").take())
+    }
+
+    /// Marks the source as ready once built, unless it deferred its readiness.
+    pub(crate) fn built(&self) {
+        drop(self.defer());
+    }
+}
+
+/// Marks a source as ready when dropped.
+#[derive(Debug)]
+pub struct ReadyGuard(Option<oneshot::Sender<()>>);
+
+impl Drop for ReadyGuard {
+    fn drop(&mut self) {
+        if let Some(tx) = self.0.take() {
+            let _ = tx.send(());
+        }
+    }
 }
 
 impl SourceContext {
@@ -82,6 +148,7 @@ impl SourceContext {
                 acknowledgements: false,
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
+                ready: Default::default(),
             },
             shutdown,
         )
@@ -101,6 +168,7 @@ impl SourceContext {
             acknowledgements: false,
             schema_definitions: schema_definitions.unwrap_or_default(),
             schema: Default::default(),
+            ready: Default::default(),
         }
     }
 
@@ -131,3 +199,28 @@ impl SourceContext {
 pub type SourceDescription = ComponentDescription<Box<dyn SourceConfig>>;
 
 inventory::collect!(SourceDescription);
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::oneshot::error::TryRecvError;
+
+    use super::*;
+
+    #[test]
+    fn ready_once_built() {
+        let (ready, mut ready_rx) = SourceReady::new();
+        ready.built();
+        assert_eq!(ready_rx.try_recv(), Ok(()));
+    }
+
+    #[test]
+    fn deferred_until_guard_dropped() {
+        let (ready, mut ready_rx) = SourceReady::new();
+        let guard = ready.defer();
+        ready.built();
+        assert_eq!(ready_rx.try_recv(), Err(TryRecvError::Empty));
+
+        drop(guard);
+        assert_eq!(ready_rx.try_recv(), Ok(()));
+    }
+}
@@ -11,6 +11,8 @@ use super::{component, ComponentKey};
 pub struct TransformOuter<T> {
     #[serde(default = "Default::default")] // https://github.com/serde-rs/serde/issues/1541
     pub inputs: Vec<T>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub input_priorities: IndexMap<String, u32>,
     #[serde(flatten)]
     pub inner: Box<dyn TransformConfig>,
 }
@@ -20,6 +22,7 @@ impl<T> TransformOuter<T> {
     pub(super) fn new(inputs: Vec<T>, transform: impl TransformConfig + 'static) -> Self {
         TransformOuter {
             inputs,
+            input_priorities: IndexMap::new(),
             inner: Box::new(transform),
         }
     }
@@ -32,6 +35,7 @@ impl<T> TransformOuter<T> {
     pub(crate) fn with_inputs<U>(self, inputs: Vec<U>) -> TransformOuter<U> {
         TransformOuter {
             inputs,
+            input_priorities: self.input_priorities,
             inner: self.inner,
         }
     }
@@ -76,6 +80,7 @@ impl TransformOuter<String> {
             for (inner_name, inner_transform) in inner_topology.inner {
                 let child = TransformOuter {
                     inputs: inner_transform.inputs,
+                    input_priorities: IndexMap::new(),
                     inner: inner_transform.inner,
                 };
                 children.push(inner_name.clone());
@@ -1,9 +1,9 @@
 use crate::config::schema;
 use crate::topology::schema::merged_definition;
 use std::collections::HashMap;
-use vector_core::internal_event::DEFAULT_OUTPUT;
+use vector_core::{config::LogNamespace, internal_event::DEFAULT_OUTPUT};
 
-use super::{builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource};
+use super::{builder::ConfigBuilder, ComponentKey, Config, DataType, OutputId, Resource};
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
 pub fn check_provider(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -73,6 +73,38 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         ));
     }
 
+    for (key, sink) in &config.sinks {
+        if let Some(mirror) = &sink.mirror {
+            if !(mirror.percent > 0.0 && mirror.percent <= 100.0) {
+                errors.push(format!(
+                    "Sink \"{}\" has a `mirror.percent` of {}, which must be greater than 0 and at most 100",
+                    key, mirror.percent
+                ));
+            }
+        }
+    }
+
+    let sink_priorities = config
+        .sinks
+        .iter()
+        .map(|(key, sink)| ("sink", key, &sink.input_priorities));
+    let transform_priorities = config
+        .transforms
+        .iter()
+        .map(|(key, transform)| ("transform", key, &transform.input_priorities));
+    for (component_type, key, priorities) in sink_priorities.chain(transform_priorities) {
+        for (input, weight) in priorities {
+            if *weight == 0 {
+                errors.push(format!(
+                    "{} \"{}\" has an `input_priorities` weight of 0 for input \"{}\", which must be at least 1",
+                    capitalize(component_type),
+                    key,
+                    input
+                ));
+            }
+        }
+    }
+
     // Warnings and errors
     let sink_inputs = config
         .sinks
@@ -238,6 +270,37 @@ pub fn warnings(config: &Config) -> Vec<String> {
         }
     }
 
+    if config.schema.log_namespace() == LogNamespace::Vector {
+        let mut legacy_sources = config
+            .sources
+            .iter()
+            .filter(|(_, source)| {
+                source
+                    .inner
+                    .outputs(LogNamespace::Vector)
+                    .iter()
+                    .filter(|output| output.ty.contains(DataType::Log))
+                    .any(|output| {
+                        !output
+                            .log_schema_definition
+                            .as_ref()
+                            .map_or(false, |definition| {
+                                definition.log_namespaces().contains(&LogNamespace::Vector)
+                            })
+                    })
+            })
+            .map(|(key, _)| format!("\"{}\"", key))
+            .collect::<Vec<_>>();
+        if !legacy_sources.is_empty() {
+            legacy_sources.sort();
+            warnings.push(format!(
+                "Log namespacing is enabled, but sources {} don't support it and emit logs in the legacy layout. \
+                 Use a `log_namespace_shim` transform to convert them.",
+                legacy_sources.join(", ")
+            ));
+        }
+    }
+
     warnings
 }
 
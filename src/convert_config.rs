@@ -0,0 +1,722 @@
+use std::{
+    collections::HashMap,
+    fs::{self, create_dir_all, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use clap::Parser;
+use colored::*;
+use serde_yaml::{Mapping, Value};
+
+use crate::config::{format, Format};
+
+/// Top level keys of the config holding components, which are written to their own files when
+/// splitting the config.
+const COMPONENT_KINDS: [&str; 4] = ["sources", "transforms", "sinks", "enrichment_tables"];
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// The config file to convert.
+    input_path: PathBuf,
+
+    /// The file to write the converted config to, or the directory to write the component files
+    /// to when splitting the config.
+    output_path: PathBuf,
+
+    /// The format of the config file to convert, detected from its extension by default.
+    #[clap(long, value_parser = parse_format)]
+    input_format: Option<Format>,
+
+    /// The format to convert the config to, detected from the extension of the output file by
+    /// default. Required when splitting the config.
+    #[clap(long, value_parser = parse_format)]
+    output_format: Option<Format>,
+
+    /// Splits the config into a file per component, in `sources`, `transforms`, `sinks`,
+    /// `enrichment_tables` and `tests` sub-directories of the output directory, loadable with
+    /// `--config-dir`. The remaining global options are written to a `vector` file.
+    #[clap(long)]
+    split: bool,
+}
+
+fn parse_format(format: &str) -> Result<Format, String> {
+    match format {
+        "toml" => Ok(Format::Toml),
+        "yaml" | "yml" => Ok(Format::Yaml),
+        "json" => Ok(Format::Json),
+        _ => Err(format!(
+            "unknown format {:?}, expected one of toml, yaml or json",
+            format
+        )),
+    }
+}
+
+const fn extension(format: Format) -> &'static str {
+    match format {
+        Format::Toml => "toml",
+        Format::Yaml => "yaml",
+        Format::Json => "json",
+    }
+}
+
+pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match convert(opts) {
+        Ok((written, warnings)) => {
+            #[allow(clippy::print_stderr)]
+            {
+                warnings.iter().for_each(|w| eprintln!("{}", w.yellow()));
+            }
+            #[allow(clippy::print_stdout)]
+            {
+                for path in written {
+                    println!("Wrote {:?}", path);
+                }
+            }
+            exitcode::OK
+        }
+        Err(errors) => {
+            #[allow(clippy::print_stderr)]
+            {
+                errors.iter().for_each(|e| eprintln!("{}", e.red()));
+            }
+            exitcode::CONFIG
+        }
+    }
+}
+
+/// Converts the config, returning the paths of the files written and any warnings.
+fn convert(opts: &Opts) -> Result<(Vec<PathBuf>, Vec<String>), Vec<String>> {
+    let input_format = match opts.input_format {
+        Some(format) => format,
+        None => Format::from_path(&opts.input_path).map_err(|path| {
+            vec![format!(
+                "Could not detect the format of {:?}, use `--input-format`.",
+                path
+            )]
+        })?,
+    };
+    let output_format = match opts.output_format {
+        Some(format) => format,
+        None if opts.split => {
+            return Err(vec![
+                "`--output-format` is required when splitting the config.".to_owned(),
+            ])
+        }
+        None => Format::from_path(&opts.output_path).map_err(|path| {
+            vec![format!(
+                "Could not detect the format of {:?}, use `--output-format`.",
+                path
+            )]
+        })?,
+    };
+
+    let content = fs::read_to_string(&opts.input_path)
+        .map_err(|error| vec![format!("Could not read {:?}: {}", opts.input_path, error)])?;
+    // The config is converted as written, without interpolating environment variables, so that
+    // they are kept as they are in the converted config.
+    let value: Value = format::deserialize(&content, input_format)?;
+    let root = match value {
+        Value::Mapping(root) => root,
+        _ => return Err(vec!["The config must be a table.".to_owned()]),
+    };
+    let comments = match input_format {
+        Format::Toml => Comments::from_toml(&content),
+        Format::Yaml | Format::Json => Comments::default(),
+    };
+    let mut warnings = Vec::new();
+    // Comments are only carried over from TOML to YAML, so make it known when they're lost.
+    let has_comments = match input_format {
+        Format::Toml => !comments.is_empty(),
+        Format::Yaml => content.lines().any(|line| line.trim_start().starts_with('#')),
+        Format::Json => false,
+    };
+    if has_comments && (input_format, output_format) != (Format::Toml, Format::Yaml) {
+        warnings.push(format!(
+            "Comments of {:?} are left out, they're only kept when converting from TOML to YAML.",
+            opts.input_path
+        ));
+    }
+
+    let files = if opts.split {
+        split(root, &comments)
+            .into_iter()
+            .map(|(path, value, comments)| {
+                let mut file_name = path.into_os_string();
+                file_name.push(".");
+                file_name.push(extension(output_format));
+                (opts.output_path.join(file_name), value, comments)
+            })
+            .collect()
+    } else {
+        vec![(opts.output_path.clone(), root, comments)]
+    };
+
+    let rendered = files
+        .into_iter()
+        .map(|(path, value, comments)| {
+            render(&value, output_format, &comments)
+                .map(|body| (path, body))
+                .map_err(|error| format!("Could not convert {:?}: {}", opts.input_path, error))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| vec![error])?;
+
+    let written = rendered
+        .into_iter()
+        .map(|(path, body)| {
+            write_file(&path, &body)
+                .map(|_| path.clone())
+                .map_err(|error| format!("Could not write {:?}: {}", path, error))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| vec![error])?;
+
+    Ok((written, warnings))
+}
+
+/// Splits the config into a file per component, and a file with the remaining options.
+fn split(mut root: Mapping, comments: &Comments) -> Vec<(PathBuf, Mapping, Comments)> {
+    let mut files = Vec::new();
+
+    for kind in COMPONENT_KINDS {
+        if let Some(Value::Mapping(components)) = root.remove(&Value::from(kind)) {
+            for (id, component) in components {
+                if let (Value::String(id), Value::Mapping(component)) = (id, component) {
+                    let path = [kind.to_owned(), id.clone()];
+                    files.push((Path::new(kind).join(&id), component, comments.rebase(&path)));
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Sequence(tests)) = root.remove(&Value::from("tests")) {
+        for (index, test) in tests.into_iter().enumerate() {
+            if let Value::Mapping(test) = test {
+                let name = test
+                    .get(&Value::from("name"))
+                    .and_then(Value::as_str)
+                    .map(sanitize_file_name)
+                    .unwrap_or_else(|| format!("test_{}", index));
+                let path = ["tests".to_owned(), index.to_string()];
+                files.push((Path::new("tests").join(name), test, comments.rebase(&path)));
+            }
+        }
+    }
+
+    if !root.is_empty() {
+        files.push((PathBuf::from("vector"), root, comments.clone()));
+    }
+
+    files
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn render(value: &Mapping, format: Format, comments: &Comments) -> Result<String, String> {
+    match format {
+        // Tables must follow the other values of a TOML table, which `toml::Value` takes care of.
+        Format::Toml => toml::Value::try_from(value)
+            .and_then(|value| toml::to_string_pretty(&value))
+            .map_err(|error| error.to_string()),
+        Format::Json => serde_json::to_string_pretty(value)
+            .map(|json| json + "\n")
+            .map_err(|error| error.to_string()),
+        Format::Yaml => {
+            let mut out = String::new();
+            write_yaml_mapping(&mut out, value, 0, &mut Vec::new(), comments)?;
+            Ok(out)
+        }
+    }
+}
+
+fn write_file(path: &Path, body: &str) -> Result<(), crate::Error> {
+    if path.exists() {
+        // Same as `vector generate`, existing files are never overwritten.
+        Err(format!("{:?} already exists", path).into())
+    } else {
+        if let Some(directory) = path.parent() {
+            create_dir_all(directory)?;
+        }
+        File::create(path)
+            .and_then(|mut file| file.write_all(body.as_bytes()))
+            .map_err(Into::into)
+    }
+}
+
+/// Comments of a config, by the path of the key they're attached to. Array items are
+/// addressed by their index.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Comments {
+    /// Comment lines preceding a key.
+    leading: HashMap<Vec<String>, Vec<String>>,
+    /// Comment following the value of a key on the same line.
+    trailing: HashMap<Vec<String>, String>,
+}
+
+impl Comments {
+    /// Extracts the comments of a TOML document.
+    ///
+    /// This is a line based scan, comments inside multi-line arrays, inline tables and strings
+    /// aren't kept.
+    fn from_toml(content: &str) -> Self {
+        let mut comments = Self::default();
+        let mut table = Vec::new();
+        let mut table_arrays: HashMap<Vec<String>, usize> = HashMap::new();
+        let mut pending = Vec::new();
+        let mut multiline_string: Option<&'static str> = None;
+        let mut depth = 0;
+
+        for line in content.lines() {
+            if let Some(delimiter) = multiline_string {
+                if line.contains(delimiter) {
+                    multiline_string = None;
+                }
+                continue;
+            }
+            if depth > 0 {
+                let scan = scan_toml_value(line);
+                depth += scan.depth;
+                multiline_string = scan.multiline_string;
+                continue;
+            }
+
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Some(comment) = trimmed.strip_prefix('#') {
+                pending.push(comment.to_owned());
+                continue;
+            }
+
+            let path = if let Some(header) = trimmed.strip_prefix("[[") {
+                let (key, rest) = header.split_once("]]").unwrap_or((header, ""));
+                let key = parse_toml_key(key);
+                let index = table_arrays.entry(key.clone()).or_insert(0);
+                table = key;
+                table.push(index.to_string());
+                *index += 1;
+                comments.add_trailing(&table, rest);
+                table.clone()
+            } else if let Some(header) = trimmed.strip_prefix('[') {
+                let (key, rest) = header.split_once(']').unwrap_or((header, ""));
+                table = parse_toml_key(key);
+                comments.add_trailing(&table, rest);
+                table.clone()
+            } else {
+                let (key, value) = match split_toml_key_value(trimmed) {
+                    Some(split) => split,
+                    None => continue,
+                };
+                let mut path = table.clone();
+                path.extend(parse_toml_key(key));
+                let scan = scan_toml_value(value);
+                depth = scan.depth;
+                multiline_string = scan.multiline_string;
+                if let Some(comment) = scan.comment {
+                    comments.trailing.insert(path.clone(), comment.to_owned());
+                }
+                path
+            };
+
+            if !pending.is_empty() {
+                comments.leading.insert(path, std::mem::take(&mut pending));
+            }
+        }
+
+        comments
+    }
+
+    fn add_trailing(&mut self, path: &[String], rest: &str) {
+        if let Some(comment) = rest.trim().strip_prefix('#') {
+            self.trailing.insert(path.to_vec(), comment.to_owned());
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty()
+    }
+
+    /// Gets the comments under `prefix`, with paths relative to it.
+    fn rebase(&self, prefix: &[String]) -> Self {
+        fn strip<T: Clone>(
+            map: &HashMap<Vec<String>, T>,
+            prefix: &[String],
+        ) -> HashMap<Vec<String>, T> {
+            map.iter()
+                .filter(|(path, _)| path.len() > prefix.len() && path.starts_with(prefix))
+                .map(|(path, value)| (path[prefix.len()..].to_vec(), value.clone()))
+                .collect()
+        }
+
+        Self {
+            leading: strip(&self.leading, prefix),
+            trailing: strip(&self.trailing, prefix),
+        }
+    }
+}
+
+/// Splits a `key = value` line on the first `=` outside of a quoted key.
+fn split_toml_key_value(line: &str) -> Option<(&str, &str)> {
+    let mut quote = None;
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '=') => return Some((&line[..index], &line[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a possibly dotted and quoted TOML key into its segments.
+fn parse_toml_key(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut segment = String::new();
+    let mut quote = None;
+    for c in key.trim().chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '.') => segments.push(std::mem::take(&mut segment).trim().to_owned()),
+            (None, c) if c.is_whitespace() => {}
+            (_, c) => segment.push(c),
+        }
+    }
+    segments.push(segment);
+    segments
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct TomlValueScan<'a> {
+    /// The comment following the value.
+    comment: Option<&'a str>,
+    /// The change in the nesting of arrays and inline tables.
+    depth: i32,
+    /// The delimiter of a multi-line string left open at the end of the line.
+    multiline_string: Option<&'static str>,
+}
+
+/// Scans (part of) a TOML value on a single line, skipping over strings.
+fn scan_toml_value(value: &str) -> TomlValueScan<'_> {
+    let mut scan = TomlValueScan::default();
+    let bytes = value.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let rest = &value[index..];
+        if let Some(delimiter) = ["\"\"\"", "'''"]
+            .into_iter()
+            .find(|delimiter| rest.starts_with(delimiter))
+        {
+            match rest[3..].find(delimiter) {
+                Some(end) => index += 3 + end + 3,
+                None => {
+                    scan.multiline_string = Some(delimiter);
+                    return scan;
+                }
+            }
+            continue;
+        }
+
+        match bytes[index] {
+            b'"' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            b'\'' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'\'' {
+                    index += 1;
+                }
+            }
+            b'[' | b'{' => scan.depth += 1,
+            b']' | b'}' => scan.depth -= 1,
+            b'#' => {
+                scan.comment = Some(&value[index + 1..]);
+                return scan;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    scan
+}
+
+fn write_yaml_mapping(
+    out: &mut String,
+    mapping: &Mapping,
+    indent: usize,
+    path: &mut Vec<String>,
+    comments: &Comments,
+) -> Result<(), String> {
+    for (key, value) in mapping {
+        let key_name = match key {
+            Value::String(key) => key.clone(),
+            key => yaml_scalar(key)?,
+        };
+        path.push(key_name);
+        if let Some(leading) = comments.leading.get(path) {
+            for comment in leading {
+                out.push_str(&format!("{:indent$}#{}\n", "", comment, indent = indent));
+            }
+        }
+        out.push_str(&format!(
+            "{:indent$}{}:",
+            "",
+            yaml_scalar(key)?,
+            indent = indent
+        ));
+        write_yaml_value(out, value, indent, path, comments)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Writes a value following a key or a sequence dash, which is already written.
+fn write_yaml_value(
+    out: &mut String,
+    value: &Value,
+    indent: usize,
+    path: &mut Vec<String>,
+    comments: &Comments,
+) -> Result<(), String> {
+    let trailing = comments
+        .trailing
+        .get(path)
+        .map(|comment| format!(" #{}", comment))
+        .unwrap_or_default();
+
+    match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            out.push_str(&format!("{}\n", trailing));
+            write_yaml_mapping(out, mapping, indent + 2, path, comments)
+        }
+        Value::Sequence(sequence) if !sequence.is_empty() => {
+            out.push_str(&format!("{}\n", trailing));
+            write_yaml_sequence(out, sequence, indent + 2, path, comments)
+        }
+        Value::String(string) if string.contains('\n') && block_scalar_compatible(string) => {
+            let chomping = if string.ends_with('\n') { "" } else { "-" };
+            out.push_str(&format!(" |{}{}\n", chomping, trailing));
+            for line in string.trim_end_matches('\n').split('\n') {
+                if line.is_empty() {
+                    out.push('\n');
+                } else {
+                    out.push_str(&format!("{:indent$}{}\n", "", line, indent = indent + 2));
+                }
+            }
+            Ok(())
+        }
+        value => {
+            out.push_str(&format!(" {}{}\n", yaml_scalar(value)?, trailing));
+            Ok(())
+        }
+    }
+}
+
+fn write_yaml_sequence(
+    out: &mut String,
+    sequence: &[Value],
+    indent: usize,
+    path: &mut Vec<String>,
+    comments: &Comments,
+) -> Result<(), String> {
+    for (index, item) in sequence.iter().enumerate() {
+        path.push(index.to_string());
+        if let Some(leading) = comments.leading.get(path) {
+            for comment in leading {
+                out.push_str(&format!("{:indent$}#{}\n", "", comment, indent = indent));
+            }
+        }
+
+        // Collections are written indented under the dash, which then replaces the indentation
+        // of their first line.
+        let mut item_out = String::new();
+        match item {
+            Value::Mapping(mapping) if !mapping.is_empty() => {
+                write_yaml_mapping(&mut item_out, mapping, indent + 2, path, comments)?;
+                item_out.replace_range(..indent + 2, " ");
+            }
+            Value::Sequence(sequence) if !sequence.is_empty() => {
+                write_yaml_sequence(&mut item_out, sequence, indent + 2, path, comments)?;
+                item_out.replace_range(..indent + 2, " ");
+            }
+            item => write_yaml_value(&mut item_out, item, indent, path, comments)?,
+        }
+        out.push_str(&format!("{:indent$}-{}", "", item_out, indent = indent));
+
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Whether the string can be written as a literal block scalar, keeping it as is.
+fn block_scalar_compatible(string: &str) -> bool {
+    let is_blank = |c: char| c == ' ' || c == '\t';
+    !string.starts_with(is_blank)
+        && !string.ends_with("\n\n")
+        && !string.contains('\r')
+        && !string.lines().any(|line| line.ends_with(is_blank))
+}
+
+/// Serializes a scalar, or an empty collection, on a single line.
+fn yaml_scalar(value: &Value) -> Result<String, String> {
+    let yaml = serde_yaml::to_string(value).map_err(|error| error.to_string())?;
+    let yaml = yaml.strip_prefix("---").unwrap_or(&yaml);
+    Ok(yaml.trim().to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+# Where the logs come from.
+[sources.in]
+type = "stdin" # Read lines.
+
+[transforms.parse]
+inputs = ["in"]
+type = "remap"
+# Parse the logs.
+source = """
+. = parse_json!(.message)
+.seen = true
+"""
+
+[sinks.out]
+inputs = [
+  "parse", # Parsed events.
+]
+type = "console"
+encoding.codec = "json"
+
+# A test.
+[[tests]]
+name = "parses json"
+"#;
+
+    fn convert_toml(content: &str, format: Format) -> String {
+        let value: Mapping = format::deserialize(content, Format::Toml).unwrap();
+        render(&value, format, &Comments::from_toml(content)).unwrap()
+    }
+
+    #[test]
+    fn extracts_toml_comments() {
+        let comments = Comments::from_toml(CONFIG);
+        let path = |path: &[&str]| path.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            comments.leading.get(&path(&["sources", "in"])),
+            Some(&vec![" Where the logs come from.".to_owned()])
+        );
+        assert_eq!(
+            comments.trailing.get(&path(&["sources", "in", "type"])),
+            Some(&" Read lines.".to_owned())
+        );
+        assert_eq!(
+            comments
+                .leading
+                .get(&path(&["transforms", "parse", "source"])),
+            Some(&vec![" Parse the logs.".to_owned()])
+        );
+        assert_eq!(
+            comments.leading.get(&path(&["tests", "0"])),
+            Some(&vec![" A test.".to_owned()])
+        );
+        // Comments inside of multi-line arrays aren't kept.
+        assert_eq!(comments.leading.len() + comments.trailing.len(), 4);
+    }
+
+    #[test]
+    fn converts_toml_to_yaml_with_comments() {
+        let yaml = convert_toml(CONFIG, Format::Yaml);
+
+        assert_eq!(
+            yaml,
+            r#"sources:
+  # Where the logs come from.
+  in:
+    type: stdin # Read lines.
+transforms:
+  parse:
+    inputs:
+      - in
+    type: remap
+    # Parse the logs.
+    source: |
+      . = parse_json!(.message)
+      .seen = true
+sinks:
+  out:
+    inputs:
+      - parse
+    type: console
+    encoding:
+      codec: json
+tests:
+  # A test.
+  - name: parses json
+"#
+        );
+
+        let original: Value = format::deserialize(CONFIG, Format::Toml).unwrap();
+        let converted: Value = format::deserialize(&yaml, Format::Yaml).unwrap();
+        assert_eq!(original, converted);
+    }
+
+    #[test]
+    fn converts_to_json_and_toml() {
+        let original: Value = format::deserialize(CONFIG, Format::Toml).unwrap();
+
+        for format in [Format::Json, Format::Toml] {
+            let converted: Value =
+                format::deserialize(&convert_toml(CONFIG, format), format).unwrap();
+            assert_eq!(original, converted);
+        }
+    }
+
+    #[test]
+    fn splits_components() {
+        let root: Mapping = format::deserialize(CONFIG, Format::Toml).unwrap();
+        let files = split(root, &Comments::from_toml(CONFIG));
+        let paths = files
+            .iter()
+            .map(|(path, _, _)| path.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("sources/in"),
+                PathBuf::from("transforms/parse"),
+                PathBuf::from("sinks/out"),
+                PathBuf::from("tests/parses_json"),
+            ]
+        );
+        assert_eq!(
+            files[0].2.trailing.get(&vec!["type".to_owned()]),
+            Some(&" Read lines.".to_owned())
+        );
+    }
+}
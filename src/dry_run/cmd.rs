@@ -0,0 +1,222 @@
+use lookup::{lookup_v2::Path, path};
+use tokio_stream::StreamExt;
+use url::Url;
+use vector_api_client::{
+    connect_subscription_client,
+    gql::{
+        output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns,
+        TapEncodingFormat, TapSubscriptionExt,
+    },
+    Client,
+};
+
+use super::diff::diff;
+use crate::{
+    config::{self, log_schema, DataType, Output, TransformContext},
+    event::{Event, EventContainer, LogEvent},
+    signal::{SignalRx, SignalTo},
+    transforms::{
+        remap::{AstRunner, Remap, RemapConfig},
+        SyncTransform, TransformOutputsBuf,
+    },
+};
+
+const DROPPED: &str = "dropped";
+
+/// CLI command func for running a candidate VRL program against events sampled from a running
+/// Vector instance, and printing how the program would change them. Nothing is changed in the
+/// running instance.
+pub(crate) async fn cmd(opts: &super::Opts, mut signal_rx: SignalRx) -> exitcode::ExitCode {
+    let mut remap = match build_remap(opts) {
+        Ok(remap) => remap,
+        Err(error) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("[dry-run] Couldn't compile the VRL program:\n{}", error);
+            }
+            return exitcode::CONFIG;
+        }
+    };
+
+    // Use the provided URL as the Vector GraphQL API server, or default to the local port
+    // provided by the API config, as `vector tap` does.
+    let mut url = opts.url.clone().unwrap_or_else(|| {
+        let addr = config::api::default_address().unwrap();
+        Url::parse(&*format!("http://{}/graphql", addr))
+            .expect("Couldn't parse default API URL. Please report this.")
+    });
+
+    if Client::new_with_healthcheck(url.clone()).await.is_none() {
+        return exitcode::UNAVAILABLE;
+    }
+
+    url.set_scheme(match url.scheme() {
+        "https" => "wss",
+        _ => "ws",
+    })
+    .expect("Couldn't build WebSocket URL. Please report.");
+
+    tokio::select! {
+        biased;
+        Ok(SignalTo::Shutdown | SignalTo::Quit) = signal_rx.recv() => exitcode::OK,
+        status = run(url, opts, &mut remap) => status,
+    }
+}
+
+fn build_remap(opts: &super::Opts) -> crate::Result<Remap<AstRunner>> {
+    let config = RemapConfig {
+        source: opts.source.clone(),
+        file: opts.file.clone(),
+        drop_on_error: true,
+        drop_on_abort: true,
+        reroute_dropped: true,
+        ..Default::default()
+    };
+    let (remap, warnings) = Remap::new_ast(config, &TransformContext::default())?;
+
+    if !warnings.is_empty() {
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("{}", warnings);
+        }
+    }
+
+    Ok(remap)
+}
+
+async fn run(url: Url, opts: &super::Opts, remap: &mut Remap<AstRunner>) -> exitcode::ExitCode {
+    let subscription_client = match connect_subscription_client(url).await {
+        Ok(c) => c,
+        Err(e) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!(
+                    "[dry-run] Couldn't connect to Vector API via WebSockets: {}",
+                    e
+                );
+            }
+            return exitcode::UNAVAILABLE;
+        }
+    };
+
+    tokio::pin! {
+        let stream = subscription_client.output_events_by_component_id_patterns_subscription(
+            vec![opts.component_id.clone()],
+            vec![],
+            opts.filter.clone(),
+            vec![],
+            TapEncodingFormat::Json,
+            opts.limit as i64,
+            opts.interval as i64,
+        );
+    };
+
+    let mut sampled = 0;
+
+    #[allow(clippy::print_stderr)]
+    while sampled < opts.limit {
+        let res = match stream.next().await {
+            Some(Some(res)) => res,
+            _ => {
+                eprintln!("[dry-run] Connection to the Vector API was closed.");
+                return exitcode::TEMPFAIL;
+            }
+        };
+        let data = match res.data {
+            Some(data) => data,
+            None => continue,
+        };
+
+        for tap_event in data.output_events_by_component_id_patterns {
+            match tap_event {
+                OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::Log(ev) => {
+                    if sampled == opts.limit {
+                        break;
+                    }
+                    sampled += 1;
+                    match parse_log(&ev.string) {
+                        Ok(log) => print_dry_run(sampled, log, remap, opts.full),
+                        Err(error) => eprintln!("[dry-run] Couldn't decode event {}: {}", sampled, error),
+                    }
+                }
+                OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns::EventNotification(ev) => {
+                    eprintln!("{}", ev.message);
+                }
+                // Only log events are supported for now.
+                _ => {}
+            }
+        }
+    }
+
+    exitcode::OK
+}
+
+fn parse_log(json: &str) -> crate::Result<LogEvent> {
+    LogEvent::try_from(serde_json::from_str::<serde_json::Value>(json)?)
+}
+
+/// Run the program against a single event, returning the events it outputs and the events it
+/// dropped.
+fn transform(remap: &mut Remap<AstRunner>, log: LogEvent) -> (Vec<Event>, Vec<Event>) {
+    let mut outputs = TransformOutputsBuf::new_with_capacity(
+        vec![
+            Output::default(DataType::all()),
+            Output::default(DataType::all()).with_port(DROPPED),
+        ],
+        1,
+    );
+    remap.transform(Event::from(log), &mut outputs);
+
+    (
+        outputs.take_primary().into_events().collect(),
+        outputs.drain_named(DROPPED).collect(),
+    )
+}
+
+#[allow(clippy::print_stdout)]
+fn print_dry_run(number: usize, log: LogEvent, remap: &mut Remap<AstRunner>, full: bool) {
+    let (events, dropped) = transform(remap, log.clone());
+
+    if events.is_empty() && dropped.is_empty() {
+        // Nothing was output, so the program dropped the event without an error.
+        println!("Event {}: dropped", number);
+    }
+    for event in dropped {
+        let message = event
+            .as_log()
+            .get(
+                log_schema()
+                    .metadata_key()
+                    .concat(path!("dropped", "message")),
+            )
+            .map(|message| message.to_string_lossy())
+            .unwrap_or_default();
+        println!("Event {}: dropped ({})", number, message);
+    }
+
+    let count = events.len();
+    for (index, event) in events.iter().enumerate() {
+        if count > 1 {
+            println!("Event {} (output {} of {}):", number, index + 1, count);
+        } else {
+            println!("Event {}:", number);
+        }
+
+        let output = event.as_log();
+        if full {
+            println!(
+                "{}",
+                serde_json::to_string(output).expect("Log events always serialize to JSON.")
+            );
+            continue;
+        }
+
+        let changes = diff(&log, output);
+        if changes.is_empty() {
+            println!("  (no changes)");
+        }
+        for change in changes {
+            println!("  {}", change);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::BTreeMap, fmt};
+
+use colored::Colorize;
+
+use crate::event::{LogEvent, Value};
+
+/// A change made to a single field of a log event.
+#[derive(Debug, PartialEq)]
+pub(super) enum FieldChange<'a> {
+    Added {
+        path: String,
+        value: &'a Value,
+    },
+    Removed {
+        path: String,
+        value: &'a Value,
+    },
+    Changed {
+        path: String,
+        before: &'a Value,
+        after: &'a Value,
+    },
+}
+
+impl<'a> FieldChange<'a> {
+    fn path(&self) -> &str {
+        match self {
+            Self::Added { path, .. } | Self::Removed { path, .. } | Self::Changed { path, .. } => {
+                path
+            }
+        }
+    }
+}
+
+impl<'a> fmt::Display for FieldChange<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { path, value } => {
+                write!(f, "{}", format!("+ .{} = {}", path, value).green())
+            }
+            Self::Removed { path, value } => {
+                write!(f, "{}", format!("- .{} = {}", path, value).red())
+            }
+            Self::Changed {
+                path,
+                before,
+                after,
+            } => write!(
+                f,
+                "{}",
+                format!("~ .{}: {} -> {}", path, before, after).yellow()
+            ),
+        }
+    }
+}
+
+fn fields(log: &LogEvent) -> BTreeMap<String, &Value> {
+    log.all_fields()
+        .map(|fields| fields.collect())
+        .unwrap_or_default()
+}
+
+/// Compare the leaf fields of a log event before and after it was processed, in path order.
+pub(super) fn diff<'a>(before: &'a LogEvent, after: &'a LogEvent) -> Vec<FieldChange<'a>> {
+    let mut before = fields(before);
+    let mut changes = Vec::new();
+
+    for (path, after) in fields(after) {
+        match before.remove(&path) {
+            None => changes.push(FieldChange::Added { path, value: after }),
+            Some(before) if before != after => changes.push(FieldChange::Changed {
+                path,
+                before,
+                after,
+            }),
+            Some(_) => {}
+        }
+    }
+    changes.extend(
+        before
+            .into_iter()
+            .map(|(path, value)| FieldChange::Removed { path, value }),
+    );
+    changes.sort_by(|a, b| a.path().cmp(b.path()));
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(fields: serde_json::Value) -> LogEvent {
+        LogEvent::try_from(fields).unwrap()
+    }
+
+    #[test]
+    fn unchanged_event_has_no_changes() {
+        let event = log(serde_json::json!({"message": "hello", "host": "a"}));
+        assert!(diff(&event, &event.clone()).is_empty());
+    }
+
+    #[test]
+    fn reports_field_changes_in_path_order() {
+        let before = log(serde_json::json!({
+            "message": "hello",
+            "host": "a",
+            "nested": {"keep": 1, "drop": true},
+        }));
+        let after = log(serde_json::json!({
+            "message": "HELLO",
+            "host": "a",
+            "level": "info",
+            "nested": {"keep": 1},
+        }));
+
+        assert_eq!(
+            diff(&before, &after),
+            vec![
+                FieldChange::Added {
+                    path: "level".to_string(),
+                    value: &Value::from("info"),
+                },
+                FieldChange::Changed {
+                    path: "message".to_string(),
+                    before: &Value::from("hello"),
+                    after: &Value::from("HELLO"),
+                },
+                FieldChange::Removed {
+                    path: "nested.drop".to_string(),
+                    value: &Value::Boolean(true),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+mod cmd;
+mod diff;
+
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Parser};
+pub(crate) use cmd::cmd;
+use url::Url;
+
+#[derive(Parser, Debug, Clone)]
+#[clap(rename_all = "kebab-case")]
+#[clap(group(ArgGroup::new("program").required(true).args(&["file", "source"])))]
+pub struct Opts {
+    /// ID of the component whose output events are sampled
+    component_id: String,
+
+    /// Path to the candidate VRL program to run against the sampled events
+    #[clap(long, value_name = "FILE")]
+    file: Option<PathBuf>,
+
+    /// Candidate VRL program to run against the sampled events
+    #[clap(long)]
+    source: Option<String>,
+
+    /// Vector GraphQL API server endpoint
+    #[clap(short, long)]
+    url: Option<Url>,
+
+    /// Number of events to sample before exiting
+    #[clap(default_value = "10", short = 'l', long)]
+    limit: usize,
+
+    /// Interval to sample events at, in milliseconds
+    #[clap(default_value = "500", short = 'i', long)]
+    interval: u32,
+
+    /// VRL boolean expression that events must match to be sampled, evaluated by the Vector API server
+    #[clap(long)]
+    filter: Option<String>,
+
+    /// Print the whole event as output by the program, rather than only the fields it changed
+    #[clap(long)]
+    full: bool,
+}
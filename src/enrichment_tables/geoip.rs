@@ -1,14 +1,28 @@
-use std::{collections::BTreeMap, fs, net::IpAddr, sync::Arc, time::SystemTime};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    net::IpAddr,
+    sync::{Arc, Weak},
+    time::{Duration, SystemTime},
+};
 
+use arc_swap::ArcSwap;
+use bytes::Bytes;
 use enrichment::{Case, Condition, IndexHandle, Table};
+use hyper::{body::to_bytes as body_to_bytes, Body, Request};
 use maxminddb::{
     geoip2::{City, Isp},
     MaxMindDBError, Reader,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use value::Value;
 
-use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig};
+use crate::{
+    config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig, ProxyConfig},
+    http::HttpClient,
+};
 
 // MaxMind GeoIP database files have a type field we can use to recognize specific
 // products. If we encounter one of these two types, we look for ASN/ISP information;
@@ -21,6 +35,27 @@ pub struct GeoipConfig {
     pub path: String,
     #[serde(default = "default_locale")]
     pub locale: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download: Option<DownloadConfig>,
+}
+
+/// Settings for periodically downloading the database from MaxMind.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DownloadConfig {
+    /// The MaxMind edition to download, such as `GeoLite2-City`.
+    pub edition_id: String,
+
+    /// The MaxMind license key. This is expected to be provided via a secret.
+    pub license_key: String,
+
+    /// The download endpoint. Only useful to override for testing or when using a mirror.
+    #[serde(default = "default_download_endpoint")]
+    pub endpoint: String,
+
+    /// How often, in seconds, to check for an updated database.
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
 }
 
 // valid locales are: “de”, "en", “es”, “fr”, “ja”, “pt-BR”, “ru”, and “zh-CN”
@@ -32,11 +67,21 @@ fn default_locale() -> String {
     "en".to_string()
 }
 
+fn default_download_endpoint() -> String {
+    "https://download.maxmind.com/app/geoip_download".to_string()
+}
+
+const fn default_refresh_interval_secs() -> u64 {
+    // MaxMind updates the GeoLite2 databases twice a week, checking once a day is plenty.
+    86400
+}
+
 impl GenerateConfig for GeoipConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             path: "/path/to/GeoLite2-City.mmdb".to_string(),
             locale: default_locale(),
+            download: None,
         })
         .unwrap()
     }
@@ -51,49 +96,262 @@ inventory::submit! {
 impl EnrichmentTableConfig for GeoipConfig {
     async fn build(
         &self,
-        _: &crate::config::GlobalOptions,
+        globals: &crate::config::GlobalOptions,
     ) -> crate::Result<Box<dyn Table + Send + Sync>> {
-        Ok(Box::new(Geoip::new(self.clone())?))
+        let downloader = match &self.download {
+            Some(download) => {
+                let proxy = ProxyConfig::merge_with_env(&globals.proxy, &ProxyConfig::default());
+                let mut downloader = Downloader {
+                    config: download.clone(),
+                    path: self.path.clone(),
+                    client: HttpClient::new(None, &proxy)?,
+                    last_checksum: None,
+                };
+
+                // Only block on the initial download if there's no database available yet,
+                // otherwise start with what we have and let the refresh task catch up.
+                if tokio::fs::metadata(&self.path).await.is_err() {
+                    downloader.download().await?;
+                } else {
+                    downloader.last_checksum = downloader.read_checksum().await;
+                }
+
+                Some(downloader)
+            }
+            None => None,
+        };
+
+        let table = Geoip::new(self.clone())?;
+
+        if let Some(downloader) = downloader {
+            tokio::spawn(downloader.run(Arc::downgrade(&table.database)));
+        }
+
+        Ok(Box::new(table))
     }
 }
 
-#[derive(Clone)]
-pub struct Geoip {
-    config: GeoipConfig,
-    dbreader: Arc<maxminddb::Reader<Vec<u8>>>,
+/// An opened database along with the modification time of the file it was read from.
+struct Database {
+    reader: Reader<Vec<u8>>,
     last_modified: SystemTime,
 }
 
-impl Geoip {
-    pub fn new(config: GeoipConfig) -> crate::Result<Self> {
-        let table = Geoip {
-            last_modified: fs::metadata(&config.path)?.modified()?,
-            dbreader: Arc::new(Reader::open_readfile(config.path.clone())?),
-            config,
-        };
+impl Database {
+    fn open(path: &str) -> crate::Result<Self> {
+        Ok(Self {
+            last_modified: fs::metadata(path)?.modified()?,
+            reader: Reader::open_readfile(path)?,
+        })
+    }
+
+    fn has_isp_db(&self) -> bool {
+        self.reader.metadata.database_type == ASN_DATABASE_TYPE
+            || self.reader.metadata.database_type == ISP_DATABASE_TYPE
+    }
 
-        // Check if we can read database with dummy Ip.
+    /// Check if we can read database with dummy Ip.
+    fn verify(&self) -> crate::Result<()> {
         let ip = IpAddr::V4(std::net::Ipv4Addr::new(0, 0, 0, 0));
-        let result = if table.has_isp_db() {
-            table.dbreader.lookup::<Isp>(ip).map(|_| ())
+        let result = if self.has_isp_db() {
+            self.reader.lookup::<Isp>(ip).map(|_| ())
         } else {
-            table.dbreader.lookup::<City>(ip).map(|_| ())
+            self.reader.lookup::<City>(ip).map(|_| ())
         };
 
         match result {
-            Ok(_) | Err(MaxMindDBError::AddressNotFoundError(_)) => (),
-            Err(error) => return Err(error.into()),
+            Ok(_) | Err(MaxMindDBError::AddressNotFoundError(_)) => Ok(()),
+            Err(error) => Err(error.into()),
         }
+    }
+}
 
-        Ok(table)
+/// Periodically downloads the database from MaxMind, replacing the file on disk and swapping the
+/// opened database in place.
+struct Downloader {
+    config: DownloadConfig,
+    path: String,
+    client: HttpClient,
+    last_checksum: Option<String>,
+}
+
+impl Downloader {
+    async fn run(mut self, database: Weak<ArcSwap<Database>>) {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            self.config.refresh_interval_secs.max(1),
+        ));
+        // The first tick completes immediately, but the database has just been opened.
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            // The table has been dropped, most likely due to a reload, so there is nothing left
+            // to refresh.
+            let database = match database.upgrade() {
+                Some(database) => database,
+                None => break,
+            };
+
+            match self.download().await {
+                Ok(false) => debug!(message = "GeoIP database is up to date.", path = %self.path),
+                Ok(true) => match self.open().await {
+                    Ok(opened) => {
+                        info!(message = "Reloaded downloaded GeoIP database.", path = %self.path);
+                        database.store(Arc::new(opened));
+                    }
+                    Err(error) => error!(
+                        message = "Unable to open downloaded GeoIP database.",
+                        path = %self.path,
+                        %error,
+                    ),
+                },
+                Err(error) => error!(
+                    message = "Unable to download GeoIP database.",
+                    edition_id = %self.config.edition_id,
+                    %error,
+                ),
+            }
+        }
     }
 
-    fn has_isp_db(&self) -> bool {
-        self.dbreader.metadata.database_type == ASN_DATABASE_TYPE
-            || self.dbreader.metadata.database_type == ISP_DATABASE_TYPE
+    /// Opens the downloaded database on a blocking thread, as the whole file is read.
+    async fn open(&self) -> crate::Result<Database> {
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || Database::open(&path)).await?
+    }
+
+    /// The checksum of the archive the database was extracted from is kept next to it, so that
+    /// the first refresh after a restart doesn't download an unchanged database again.
+    fn checksum_path(&self) -> String {
+        format!("{}.sha256", self.path)
+    }
+
+    async fn read_checksum(&self) -> Option<String> {
+        tokio::fs::read_to_string(self.checksum_path())
+            .await
+            .ok()
+            .map(|checksum| checksum.trim().to_owned())
+    }
+
+    fn uri(&self, suffix: &str) -> String {
+        format!(
+            "{}?edition_id={}&license_key={}&suffix={}",
+            self.config.endpoint,
+            percent_encoding::utf8_percent_encode(
+                &self.config.edition_id,
+                percent_encoding::NON_ALPHANUMERIC
+            ),
+            percent_encoding::utf8_percent_encode(
+                &self.config.license_key,
+                percent_encoding::NON_ALPHANUMERIC
+            ),
+            suffix
+        )
+    }
+
+    async fn get(&self, suffix: &str) -> crate::Result<Bytes> {
+        let request = Request::get(self.uri(suffix)).body(Body::empty())?;
+        let response = self.client.send(request).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("unexpected response status {}", status).into());
+        }
+
+        Ok(body_to_bytes(response.into_body()).await?)
+    }
+
+    /// Downloads the database if it has changed since the last download, verifying its checksum
+    /// and atomically replacing the file on disk. Returns `true` if the file was replaced.
+    async fn download(&mut self) -> crate::Result<bool> {
+        let checksum = self.get("tar.gz.sha256").await?;
+        // The checksum file has the same format as the output of `sha256sum`.
+        let checksum = std::str::from_utf8(&checksum)?
+            .split_whitespace()
+            .next()
+            .ok_or("empty checksum file")?
+            .to_lowercase();
+
+        if self.last_checksum.as_deref() == Some(&checksum) {
+            return Ok(false);
+        }
+
+        let archive = self.get("tar.gz").await?;
+        let actual = hex::encode(Sha256::digest(&archive));
+        if actual != checksum {
+            return Err(format!(
+                "checksum mismatch, expected {} but downloaded archive has {}",
+                checksum, actual
+            )
+            .into());
+        }
+
+        let database = tokio::task::spawn_blocking(move || {
+            let database = extract_database(&archive)?;
+            // Make sure the database is usable before replacing a working one.
+            Reader::from_source(database.as_slice())?;
+            Ok::<_, crate::Error>(database)
+        })
+        .await??;
+
+        let tmp_path = format!("{}.tmp", self.path);
+        tokio::fs::write(&tmp_path, &database).await?;
+        tokio::fs::rename(&tmp_path, &self.path).await?;
+        // Without the checksum the database is only downloaded again after a restart.
+        if let Err(error) = tokio::fs::write(self.checksum_path(), &checksum).await {
+            warn!(
+                message = "Unable to write GeoIP database checksum.",
+                path = %self.checksum_path(),
+                %error,
+            );
+        }
+
+        self.last_checksum = Some(checksum);
+
+        Ok(true)
+    }
+}
+
+/// Extracts the `.mmdb` file from the `.tar.gz` archive that MaxMind distributes.
+fn extract_database(archive: &[u8]) -> crate::Result<Vec<u8>> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry
+            .path()?
+            .extension()
+            .map_or(false, |extension| extension == "mmdb")
+        {
+            let mut database = Vec::new();
+            entry.read_to_end(&mut database)?;
+            return Ok(database);
+        }
+    }
+
+    Err("no .mmdb file found in downloaded archive".into())
+}
+
+#[derive(Clone)]
+pub struct Geoip {
+    config: GeoipConfig,
+    database: Arc<ArcSwap<Database>>,
+}
+
+impl Geoip {
+    pub fn new(config: GeoipConfig) -> crate::Result<Self> {
+        let database = Database::open(&config.path)?;
+        database.verify()?;
+
+        Ok(Geoip {
+            database: Arc::new(ArcSwap::from_pointee(database)),
+            config,
+        })
     }
 
     fn lookup(&self, ip: IpAddr, select: Option<&[String]>) -> Option<BTreeMap<String, Value>> {
+        let database = self.database.load();
         let mut map = BTreeMap::new();
         let mut add_field = |key: &str, value: Option<Value>| {
             if select
@@ -104,8 +362,8 @@ impl Geoip {
             }
         };
 
-        if self.has_isp_db() {
-            let data = self.dbreader.lookup::<Isp>(ip).ok()?;
+        if database.has_isp_db() {
+            let data = database.reader.lookup::<Isp>(ip).ok()?;
 
             add_field(
                 "autonomous_system_number",
@@ -120,7 +378,7 @@ impl Geoip {
 
             add_field("organization", data.organization.map(Into::into));
         } else {
-            let data = self.dbreader.lookup::<City>(ip).ok()?;
+            let data = database.reader.lookup::<City>(ip).ok()?;
 
             add_field(
                 "city_name",
@@ -280,7 +538,7 @@ impl Table for Geoip {
     fn needs_reload(&self) -> bool {
         matches!(fs::metadata(&self.config.path)
             .and_then(|metadata| metadata.modified()),
-            Ok(modified) if modified > self.last_modified)
+            Ok(modified) if modified > self.database.load().last_modified)
     }
 }
 
@@ -400,6 +658,122 @@ mod tests {
         assert!(values.is_none());
     }
 
+    fn archive(database: &str) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        builder
+            .append_path_with_name(database, "GeoIP2-City_20220101/GeoIP2-City.mmdb")
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn extracts_database_from_archive() {
+        let archive = archive("tests/data/GeoIP2-City-Test.mmdb");
+
+        assert_eq!(
+            extract_database(&archive).unwrap(),
+            fs::read("tests/data/GeoIP2-City-Test.mmdb").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn downloads_database() {
+        use wiremock::{
+            matchers::{method, query_param},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let archive = archive("tests/data/GeoIP2-City-Test.mmdb");
+        let checksum = format!(
+            "{}  GeoIP2-City_20220101.tar.gz\n",
+            hex::encode(Sha256::digest(&archive))
+        );
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("suffix", "tar.gz.sha256"))
+            .and(query_param("license_key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(checksum))
+            .expect(3)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("suffix", "tar.gz"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(archive))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("GeoIP2-City.mmdb");
+        let mut downloader = Downloader {
+            config: DownloadConfig {
+                edition_id: "GeoIP2-City".to_string(),
+                license_key: "secret".to_string(),
+                endpoint: server.uri(),
+                refresh_interval_secs: default_refresh_interval_secs(),
+            },
+            path: path.to_str().unwrap().to_string(),
+            client: HttpClient::new(None, &ProxyConfig::default()).unwrap(),
+            last_checksum: None,
+        };
+
+        assert!(downloader.download().await.unwrap());
+        // The checksum hasn't changed, so the archive isn't downloaded again.
+        assert!(!downloader.download().await.unwrap());
+
+        // Nor is it after a restart, with the checksum read back from disk.
+        downloader.last_checksum = downloader.read_checksum().await;
+        assert!(downloader.last_checksum.is_some());
+        assert!(!downloader.download().await.unwrap());
+
+        let values = find("2.125.160.216", path.to_str().unwrap()).unwrap();
+        assert_eq!(values.get("city_name"), Some(&Value::from("Boxford")));
+    }
+
+    #[tokio::test]
+    async fn rejects_checksum_mismatch() {
+        use wiremock::{
+            matchers::{method, query_param},
+            Mock, MockServer, ResponseTemplate,
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("suffix", "tar.gz.sha256"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("deadbeef  file.tar.gz"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("suffix", "tar.gz"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_bytes(archive("tests/data/GeoIP2-City-Test.mmdb")),
+            )
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("GeoIP2-City.mmdb");
+        let mut downloader = Downloader {
+            config: DownloadConfig {
+                edition_id: "GeoIP2-City".to_string(),
+                license_key: "secret".to_string(),
+                endpoint: server.uri(),
+                refresh_interval_secs: default_refresh_interval_secs(),
+            },
+            path: path.to_str().unwrap().to_string(),
+            client: HttpClient::new(None, &ProxyConfig::default()).unwrap(),
+            last_checksum: None,
+        };
+
+        assert!(downloader.download().await.is_err());
+        assert!(!path.exists());
+    }
+
     fn find(ip: &str, database: &str) -> Option<BTreeMap<String, Value>> {
         find_select(ip, database, None)
     }
@@ -412,6 +786,7 @@ mod tests {
         Geoip::new(GeoipConfig {
             path: database.to_string(),
             locale: default_locale(),
+            download: None,
         })
         .unwrap()
         .find_table_rows(
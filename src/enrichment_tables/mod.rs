@@ -5,3 +5,6 @@ pub mod file;
 
 #[cfg(feature = "enrichment-tables-geoip")]
 pub mod geoip;
+
+#[cfg(feature = "enrichment-tables-sqlite")]
+pub mod sqlite;
@@ -0,0 +1,690 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use enrichment::{Case, Condition, IndexHandle, Table};
+use ordered_float::NotNan;
+use rusqlite::{
+    types::{Value as SqlValue, ValueRef},
+    Connection, OpenFlags,
+};
+use serde::{Deserialize, Serialize};
+use value::Value;
+
+use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription, GenerateConfig};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteConfig {
+    /// Path to the SQLite database file.
+    pub path: PathBuf,
+
+    /// The table within the database to perform lookups against.
+    pub table: String,
+
+    /// How often, in seconds, to check whether the database file has been replaced and needs
+    /// to be re-opened.
+    #[serde(default = "default_reopen_interval_secs")]
+    pub reopen_interval_secs: u64,
+}
+
+const fn default_reopen_interval_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for SqliteConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: "/path/to/dimensions.db".into(),
+            table: "dimensions".to_string(),
+            reopen_interval_secs: default_reopen_interval_secs(),
+        })
+        .unwrap()
+    }
+}
+
+inventory::submit! {
+    EnrichmentTableDescription::new::<SqliteConfig>("sqlite")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sqlite")]
+impl EnrichmentTableConfig for SqliteConfig {
+    async fn build(
+        &self,
+        _: &crate::config::GlobalOptions,
+    ) -> crate::Result<Box<dyn Table + Send + Sync>> {
+        Ok(Box::new(Sqlite::new(self.clone())?))
+    }
+}
+
+fn open_connection(config: &SqliteConfig) -> rusqlite::Result<Connection> {
+    Connection::open_with_flags(
+        &config.path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+}
+
+/// The idle read-only connections to the database along with what we need to know to detect that
+/// the file on disk has been replaced.
+///
+/// A lookup takes an idle connection, or opens a new one if there is none, and hands it back once
+/// done, so that lookups running concurrently on several threads don't wait for each other.
+struct Pool {
+    idle: Vec<Connection>,
+    /// Incremented each time the database is re-opened, so that connections to the replaced file
+    /// are closed rather than handed back.
+    generation: u64,
+    modified: SystemTime,
+    last_checked: Instant,
+}
+
+impl Pool {
+    fn open(config: &SqliteConfig) -> crate::Result<Self> {
+        let modified = fs::metadata(&config.path)?.modified()?;
+        let connection = open_connection(config)?;
+
+        Ok(Self {
+            idle: vec![connection],
+            generation: 0,
+            modified,
+            last_checked: Instant::now(),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Sqlite {
+    config: SqliteConfig,
+    pool: Arc<Mutex<Pool>>,
+    columns: Vec<String>,
+    indexes: Vec<(Case, Vec<String>)>,
+}
+
+impl Sqlite {
+    pub fn new(config: SqliteConfig) -> crate::Result<Self> {
+        let pool = Pool::open(&config)?;
+        let columns = table_columns(&pool.idle[0], &config.table)?;
+
+        if columns.is_empty() {
+            return Err(format!(
+                "table {:?} not found in database {:?}",
+                config.table, config.path
+            )
+            .into());
+        }
+
+        Ok(Self {
+            config,
+            pool: Arc::new(Mutex::new(pool)),
+            columns,
+            indexes: Vec::new(),
+        })
+    }
+
+    /// Re-opens the database if the reopen interval has elapsed and the file on disk has been
+    /// modified or replaced since it was last opened.
+    fn maybe_reopen(&self, pool: &mut Pool) {
+        let interval = Duration::from_secs(self.config.reopen_interval_secs);
+        if pool.last_checked.elapsed() < interval {
+            return;
+        }
+        pool.last_checked = Instant::now();
+
+        let modified = match fs::metadata(&self.config.path).and_then(|m| m.modified()) {
+            Ok(modified) => modified,
+            Err(error) => {
+                warn!(
+                    message = "Unable to stat SQLite enrichment table database.",
+                    path = ?self.config.path,
+                    %error,
+                    internal_log_rate_secs = 30,
+                );
+                return;
+            }
+        };
+
+        if modified == pool.modified {
+            return;
+        }
+
+        match Pool::open(&self.config) {
+            Ok(reopened) => {
+                debug!(message = "Re-opened SQLite enrichment table database.", path = ?self.config.path);
+                *pool = Pool {
+                    generation: pool.generation + 1,
+                    ..reopened
+                };
+            }
+            Err(error) => error!(
+                message = "Unable to re-open SQLite enrichment table database.",
+                path = ?self.config.path,
+                %error,
+                internal_log_rate_secs = 30,
+            ),
+        }
+    }
+
+    /// Takes a connection from the pool, along with the generation of the pool it belongs to.
+    fn take_connection(&self) -> Result<(Connection, u64), String> {
+        let mut pool = self
+            .pool
+            .lock()
+            .map_err(|_| "connection pool lock poisoned".to_string())?;
+        self.maybe_reopen(&mut pool);
+        let generation = pool.generation;
+        let idle = pool.idle.pop();
+        drop(pool);
+
+        let connection = match idle {
+            Some(connection) => connection,
+            None => open_connection(&self.config).map_err(|error| error.to_string())?,
+        };
+        Ok((connection, generation))
+    }
+
+    /// Hands a connection back to the pool, unless the database has been re-opened since it was
+    /// taken.
+    fn return_connection(&self, connection: Connection, generation: u64) {
+        if let Ok(mut pool) = self.pool.lock() {
+            if pool.generation == generation {
+                pool.idle.push(connection);
+            }
+        }
+    }
+
+    fn has_column(&self, field: &str) -> bool {
+        self.columns.iter().any(|column| column == field)
+    }
+
+    /// Builds the query for the given conditions. Equality conditions are pushed down to SQLite so
+    /// that any indexes in the database can be used, date ranges are checked once the rows have
+    /// been retrieved since SQLite has no native timestamp type.
+    fn build_query(
+        &self,
+        case: Case,
+        condition: &[Condition],
+    ) -> Result<(String, Vec<SqlValue>), String> {
+        let mut clauses = Vec::new();
+        let mut params = Vec::new();
+
+        for condition in condition {
+            let field = match condition {
+                Condition::Equals { field, .. } | Condition::BetweenDates { field, .. } => *field,
+            };
+            if !self.has_column(field) {
+                return Err(format!("field {:?} missing from table", field));
+            }
+
+            if let Condition::Equals { field, value } = condition {
+                params.push(to_sql_value(value)?);
+                clauses.push(match case {
+                    Case::Sensitive => format!("{} = ?{}", quote_identifier(field), params.len()),
+                    Case::Insensitive => format!(
+                        "{} = ?{} COLLATE NOCASE",
+                        quote_identifier(field),
+                        params.len()
+                    ),
+                });
+            }
+        }
+
+        let mut query = format!(
+            "SELECT {} FROM {}",
+            self.columns
+                .iter()
+                .map(|column| quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", "),
+            quote_identifier(&self.config.table)
+        );
+        if !clauses.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&clauses.join(" AND "));
+        }
+
+        Ok((query, params))
+    }
+
+    fn query(
+        &self,
+        case: Case,
+        condition: &[Condition],
+        select: Option<&[String]>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let (query, params) = self.build_query(case, condition)?;
+
+        let (connection, generation) = self.take_connection()?;
+        let results = self.query_connection(&connection, &query, &params, condition, select);
+        self.return_connection(connection, generation);
+        results
+    }
+
+    fn query_connection(
+        &self,
+        connection: &Connection,
+        query: &str,
+        params: &[SqlValue],
+        condition: &[Condition],
+        select: Option<&[String]>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let mut statement = connection
+            .prepare_cached(query)
+            .map_err(|error| error.to_string())?;
+        let mut rows = statement
+            .query(rusqlite::params_from_iter(params.iter()))
+            .map_err(|error| error.to_string())?;
+
+        let mut results = Vec::new();
+        while let Some(row) = rows.next().map_err(|error| error.to_string())? {
+            let values = (0..self.columns.len())
+                .map(|idx| row.get_ref(idx).map(from_sql_value))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| error.to_string())?;
+
+            if !self.dates_match(condition, &values) {
+                continue;
+            }
+
+            results.push(
+                self.columns
+                    .iter()
+                    .zip(values)
+                    .filter(|(column, _)| {
+                        select
+                            .map(|select| select.contains(column))
+                            // If no select is passed, we assume all columns are included
+                            .unwrap_or(true)
+                    })
+                    .map(|(column, value)| (column.clone(), value))
+                    .collect(),
+            );
+        }
+
+        Ok(results)
+    }
+
+    fn dates_match(&self, condition: &[Condition], row: &[Value]) -> bool {
+        condition.iter().all(|condition| match condition {
+            Condition::Equals { .. } => true,
+            Condition::BetweenDates { field, from, to } => self
+                .columns
+                .iter()
+                .position(|column| column == field)
+                .and_then(|idx| to_timestamp(&row[idx]))
+                .map_or(false, |date| from <= &date && &date <= to),
+        })
+    }
+}
+
+/// Returns the column names of the given table, in the order they are defined.
+fn table_columns(connection: &Connection, table: &str) -> rusqlite::Result<Vec<String>> {
+    let mut statement =
+        connection.prepare(&format!("PRAGMA table_info({})", quote_identifier(table)))?;
+    let columns = statement
+        .query_map([], |row| row.get::<_, String>("name"))?
+        .collect();
+    columns
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+fn to_sql_value(value: &Value) -> Result<SqlValue, String> {
+    Ok(match value {
+        Value::Bytes(bytes) => SqlValue::Text(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(int) => SqlValue::Integer(*int),
+        Value::Float(float) => SqlValue::Real(float.into_inner()),
+        Value::Boolean(boolean) => SqlValue::Integer(i64::from(*boolean)),
+        Value::Timestamp(timestamp) => SqlValue::Text(timestamp.to_rfc3339()),
+        Value::Null => SqlValue::Null,
+        value => {
+            return Err(format!(
+                "unable to compare {} against a SQLite column",
+                value.kind_str()
+            ))
+        }
+    })
+}
+
+fn from_sql_value(value: ValueRef<'_>) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(int) => Value::Integer(int),
+        ValueRef::Real(float) => NotNan::new(float).map_or(Value::Null, Value::Float),
+        ValueRef::Text(bytes) | ValueRef::Blob(bytes) => {
+            Value::Bytes(bytes::Bytes::copy_from_slice(bytes))
+        }
+    }
+}
+
+/// SQLite has no dedicated date type, so dates are conventionally stored either as unix
+/// timestamps or as ISO-8601 text.
+fn to_timestamp(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(timestamp) => Some(*timestamp),
+        Value::Integer(seconds) => Utc.timestamp_opt(*seconds, 0).single(),
+        Value::Bytes(bytes) => {
+            let text = std::str::from_utf8(bytes).ok()?;
+            DateTime::parse_from_rfc3339(text)
+                .map(|date| date.with_timezone(&Utc))
+                .ok()
+                .or_else(|| {
+                    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+                        .ok()
+                        .map(|date| Utc.from_utc_datetime(&date))
+                })
+                .or_else(|| {
+                    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                        .ok()
+                        .map(|date| Utc.from_utc_datetime(&date.and_hms(0, 0, 0)))
+                })
+        }
+        _ => None,
+    }
+}
+
+impl Table for Sqlite {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.query(case, condition, select)?;
+
+        match rows.pop() {
+            Some(row) if rows.is_empty() => Ok(row),
+            Some(_) => Err("more than one row found".to_string()),
+            None => Err("no rows found".to_string()),
+        }
+    }
+
+    fn find_table_rows<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        self.query(case, condition, select)
+    }
+
+    /// The indexes themselves live in the database, so this only validates that the fields exist
+    /// and remembers them so they can be reapplied when the table is reloaded.
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        let missing = fields
+            .iter()
+            .filter(|field| !self.has_column(field))
+            .copied()
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(format!(
+                "field(s) '{}' missing from dataset",
+                missing.join(", ")
+            ));
+        }
+
+        let fields = fields.iter().map(|field| field.to_string()).collect();
+        match self
+            .indexes
+            .iter()
+            .position(|index| index.0 == case && index.1 == fields)
+        {
+            Some(pos) => Ok(IndexHandle(pos)),
+            None => {
+                self.indexes.push((case, fields));
+                Ok(IndexHandle(self.indexes.len() - 1))
+            }
+        }
+    }
+
+    /// Returns a list of the field names that are in each index
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.indexes.clone()
+    }
+
+    /// The database is re-opened in place when the file changes, so there's never a need to
+    /// rebuild the table.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Debug for Sqlite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Sqlite table {} database {:?}",
+            self.config.table, self.config.path
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_database(path: &std::path::Path, rows: &[(&str, i64, &str)]) {
+        let connection = Connection::open(path).unwrap();
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS dimensions (name TEXT, id INTEGER, added TEXT)",
+                [],
+            )
+            .unwrap();
+        connection
+            .execute(
+                "CREATE INDEX IF NOT EXISTS name_idx ON dimensions (name)",
+                [],
+            )
+            .unwrap();
+        for (name, id, added) in rows {
+            connection
+                .execute(
+                    "INSERT INTO dimensions (name, id, added) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![name, id, added],
+                )
+                .unwrap();
+        }
+    }
+
+    fn config(path: PathBuf) -> SqliteConfig {
+        SqliteConfig {
+            path,
+            table: "dimensions".to_string(),
+            reopen_interval_secs: 0,
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SqliteConfig>();
+    }
+
+    #[test]
+    fn finds_row() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(&path, &[("zip", 1, "2020-01-01"), ("zup", 2, "2021-01-01")]);
+
+        let table = Sqlite::new(config(path)).unwrap();
+        let condition = Condition::Equals {
+            field: "name",
+            value: Value::from("zup"),
+        };
+
+        assert_eq!(
+            Ok(BTreeMap::from([
+                ("name".to_string(), Value::from("zup")),
+                ("id".to_string(), Value::from(2)),
+                ("added".to_string(), Value::from("2021-01-01")),
+            ])),
+            table.find_table_row(Case::Sensitive, &[condition], None, None)
+        );
+    }
+
+    #[test]
+    fn finds_row_case_insensitive_with_select() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(&path, &[("zip", 1, "2020-01-01"), ("zup", 2, "2021-01-01")]);
+
+        let table = Sqlite::new(config(path)).unwrap();
+        let condition = Condition::Equals {
+            field: "name",
+            value: Value::from("ZIP"),
+        };
+
+        assert_eq!(
+            Ok(BTreeMap::from([("id".to_string(), Value::from(1))])),
+            table.find_table_row(
+                Case::Insensitive,
+                &[condition],
+                Some(&["id".to_string()]),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn finds_rows_between_dates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(
+            &path,
+            &[
+                ("zip", 1, "2020-01-01"),
+                ("zip", 2, "2021-06-01"),
+                ("zip", 3, "2022-01-01"),
+            ],
+        );
+
+        let table = Sqlite::new(config(path)).unwrap();
+        let conditions = [
+            Condition::Equals {
+                field: "name",
+                value: Value::from("zip"),
+            },
+            Condition::BetweenDates {
+                field: "added",
+                from: Utc.ymd(2021, 1, 1).and_hms(0, 0, 0),
+                to: Utc.ymd(2021, 12, 31).and_hms(0, 0, 0),
+            },
+        ];
+
+        let rows = table
+            .find_table_rows(
+                Case::Sensitive,
+                &conditions,
+                Some(&["id".to_string()]),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            vec![BTreeMap::from([("id".to_string(), Value::from(2))])],
+            rows
+        );
+    }
+
+    #[test]
+    fn add_index_missing_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(&path, &[]);
+
+        let mut table = Sqlite::new(config(path)).unwrap();
+
+        assert_eq!(
+            Ok(IndexHandle(0)),
+            table.add_index(Case::Sensitive, &["name"])
+        );
+        assert_eq!(
+            Ok(IndexHandle(0)),
+            table.add_index(Case::Sensitive, &["name"])
+        );
+        assert_eq!(
+            Err("field(s) 'nope' missing from dataset".to_string()),
+            table.add_index(Case::Sensitive, &["name", "nope"])
+        );
+    }
+
+    #[test]
+    fn integers_out_of_range_are_not_timestamps() {
+        assert_eq!(to_timestamp(&Value::Integer(0)), Some(Utc.timestamp(0, 0)));
+        assert_eq!(to_timestamp(&Value::Integer(i64::MAX)), None);
+    }
+
+    #[test]
+    fn missing_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(&path, &[]);
+
+        let mut config = config(path);
+        config.table = "nope".to_string();
+        assert!(Sqlite::new(config).is_err());
+    }
+
+    #[test]
+    fn concurrent_lookups_use_separate_connections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(&path, &[("zip", 1, "2020-01-01")]);
+
+        let table = Sqlite::new(config(path.clone())).unwrap();
+        let (first, first_generation) = table.take_connection().unwrap();
+        let (second, second_generation) = table.take_connection().unwrap();
+        table.return_connection(first, first_generation);
+        table.return_connection(second, second_generation);
+        assert_eq!(table.pool.lock().unwrap().idle.len(), 2);
+
+        // Connections to a replaced database are closed rather than handed back.
+        let (stale, stale_generation) = table.take_connection().unwrap();
+        let replacement = dir.path().join("replacement.db");
+        create_database(&replacement, &[("zap", 5, "2020-01-01")]);
+        fs::rename(&replacement, &path).unwrap();
+        let (fresh, fresh_generation) = table.take_connection().unwrap();
+        table.return_connection(stale, stale_generation);
+        table.return_connection(fresh, fresh_generation);
+        assert_eq!(table.pool.lock().unwrap().idle.len(), 1);
+    }
+
+    #[test]
+    fn reopens_replaced_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        create_database(&path, &[("zip", 1, "2020-01-01")]);
+
+        let table = Sqlite::new(config(path.clone())).unwrap();
+        let condition = Condition::Equals {
+            field: "name",
+            value: Value::from("zap"),
+        };
+        assert!(table
+            .find_table_row(Case::Sensitive, &[condition.clone()], None, None)
+            .is_err());
+
+        // Replace the file on disk with a new database.
+        let replacement = dir.path().join("replacement.db");
+        create_database(&replacement, &[("zap", 5, "2020-01-01")]);
+        fs::rename(&replacement, &path).unwrap();
+
+        assert_eq!(
+            Ok(BTreeMap::from([("id".to_string(), Value::from(5))])),
+            table.find_table_row(
+                Case::Sensitive,
+                &[condition],
+                Some(&["id".to_string()]),
+                None
+            )
+        );
+    }
+}
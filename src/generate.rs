@@ -1,6 +1,8 @@
+mod interactive;
+
 use std::{
     fs::{create_dir_all, File},
-    io::Write,
+    io::{self, Write},
     path::{Path, PathBuf},
 };
 
@@ -52,11 +54,21 @@ pub struct Opts {
     /// from the last transform or, if none are specified, from all sources. It
     /// is then up to you to restructure the `inputs` of each component to build
     /// the topology you need.
-    expression: String,
+    #[clap(required_unless_present = "interactive")]
+    expression: Option<String>,
 
     /// Generate config as a file
     #[clap(long, parse(from_os_str))]
     file: Option<PathBuf>,
+
+    /// Build the config with an interactive wizard instead of an expression.
+    ///
+    /// The wizard prompts for the sources, transforms and sinks to add and for
+    /// the options of each, checks every component's configuration as it goes,
+    /// and emits the result as YAML. Prompts are written to stderr, so the
+    /// config can be redirected from stdout.
+    #[clap(short, long, conflicts_with = "expression")]
+    interactive: bool,
 }
 
 #[derive(Serialize)]
@@ -77,11 +89,23 @@ pub struct TransformOuter {
 
 #[derive(Serialize, Default)]
 pub struct Config {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sources: Option<IndexMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transforms: Option<IndexMap<String, TransformOuter>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sinks: Option<IndexMap<String, SinkOuter>>,
 }
 
+/// A complete config, as emitted by the interactive wizard.
+#[derive(Serialize)]
+struct Document {
+    #[serde(flatten)]
+    globals: GlobalOptions,
+    #[serde(flatten)]
+    config: Config,
+}
+
 /// Controls how the resulting transform topology is wired up. This is not
 /// user-configurable.
 pub(crate) enum TransformInputsStrategy {
@@ -369,13 +393,50 @@ pub(crate) fn generate_example(
     }
 }
 
+fn generate_interactive(
+    include_globals: bool,
+    file: &Option<PathBuf>,
+) -> Result<String, Vec<String>> {
+    let stdin = io::stdin();
+    let config = interactive::Wizard::new(stdin.lock(), io::stderr())
+        .run()
+        .map_err(|error| vec![format!("failed to build config: {}", error)])?;
+
+    let yaml = if include_globals {
+        serde_yaml::to_string(&Document {
+            globals: GlobalOptions {
+                data_dir: default_data_dir(),
+                ..Default::default()
+            },
+            config,
+        })
+    } else {
+        serde_yaml::to_string(&config)
+    }
+    .map_err(|error| vec![format!("failed to marshal config: {}", error)])?;
+
+    if let Some(file) = file {
+        write_config(file, &yaml)
+            .map_err(|error| vec![format!("failed to write to file: {}", error)])?;
+        #[allow(clippy::print_stderr)]
+        {
+            eprintln!("Config file written to {:?}", file);
+        }
+    }
+    Ok(yaml)
+}
+
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
-    match generate_example(
-        !opts.fragment,
-        &opts.expression,
-        &opts.file,
-        TransformInputsStrategy::Auto,
-    ) {
+    let result = match &opts.expression {
+        Some(expression) if !opts.interactive => generate_example(
+            !opts.fragment,
+            expression,
+            &opts.file,
+            TransformInputsStrategy::Auto,
+        ),
+        _ => generate_interactive(!opts.fragment, &opts.file),
+    };
+    match result {
         Ok(s) => {
             #[allow(clippy::print_stdout)]
             {
@@ -0,0 +1,494 @@
+//! The interactive configuration wizard behind `vector generate --interactive`.
+//!
+//! The wizard asks for the components of the pipeline, walks through the
+//! fields of each component's example configuration, and checks every
+//! component against its configuration type before moving on, so the
+//! resulting config is known to deserialize.
+
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    io::{self, BufRead, Write},
+};
+
+use indexmap::IndexMap;
+use toml::{map::Map, Value};
+use vector_core::buffers::BufferConfig;
+
+use super::{Config, SinkOuter, TransformOuter};
+use crate::config::{
+    SinkConfig, SinkDescription, SinkHealthcheckOptions, SourceConfig, SourceDescription,
+    TransformConfig, TransformDescription,
+};
+
+/// Codecs suggested for sinks with an `encoding` option. These need no
+/// further options.
+const ENCODING_CODECS: &[&str] = &[
+    "json",
+    "text",
+    "logfmt",
+    "gelf",
+    "native",
+    "native_json",
+    "raw_message",
+];
+
+/// Codecs suggested for sources with a `decoding` option. These need no
+/// further options.
+const DECODING_CODECS: &[&str] = &["bytes", "json", "syslog", "gelf", "native", "native_json"];
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Source,
+    Transform,
+    Sink,
+}
+
+impl Kind {
+    const fn name(self) -> &'static str {
+        match self {
+            Kind::Source => "source",
+            Kind::Transform => "transform",
+            Kind::Sink => "sink",
+        }
+    }
+
+    fn types(self) -> Vec<&'static str> {
+        match self {
+            Kind::Source => SourceDescription::types(),
+            Kind::Transform => TransformDescription::types(),
+            Kind::Sink => SinkDescription::types(),
+        }
+    }
+
+    fn example(self, component_type: &str) -> Map<String, Value> {
+        let example = match self {
+            Kind::Source => SourceDescription::example(component_type),
+            Kind::Transform => TransformDescription::example(component_type),
+            Kind::Sink => SinkDescription::example(component_type),
+        };
+        let mut table = match example {
+            Ok(Value::Table(table)) => table,
+            _ => Map::new(),
+        };
+        table.insert("type".into(), component_type.into());
+        table
+    }
+}
+
+pub(super) struct Wizard<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Wizard<R, W> {
+    pub(super) const fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Runs the wizard to completion, returning the configured components.
+    pub(super) fn run(&mut self) -> io::Result<Config> {
+        writeln!(
+            self.output,
+            "This wizard builds a Vector configuration. Press enter to accept the value in brackets."
+        )?;
+
+        let mut names = HashSet::new();
+        let mut config = Config::default();
+
+        let mut sources = IndexMap::new();
+        let mut acknowledging_sources = false;
+        for (name, component_type) in self.components(Kind::Source, &mut names)? {
+            let table = self.configure(Kind::Source, &name, component_type, |value| {
+                value
+                    .try_into::<Box<dyn SourceConfig>>()
+                    .map(|source| acknowledging_sources |= source.can_acknowledge())
+            })?;
+            sources.insert(name, Value::Table(table));
+        }
+        let source_names = sources.keys().cloned().collect::<Vec<_>>();
+        config.sources = Some(sources);
+
+        let mut transforms = IndexMap::<String, TransformOuter>::new();
+        for (name, component_type) in self.components(Kind::Transform, &mut names)? {
+            let table = self.configure(Kind::Transform, &name, component_type, |value| {
+                value.try_into::<Box<dyn TransformConfig>>().map(drop)
+            })?;
+            let inputs = match transforms.keys().last() {
+                Some(previous) => vec![previous.clone()],
+                None => source_names.clone(),
+            };
+            transforms.insert(
+                name,
+                TransformOuter {
+                    inputs,
+                    inner: Value::Table(table),
+                },
+            );
+        }
+        let sink_inputs = match transforms.keys().last() {
+            Some(last) => vec![last.clone()],
+            None => source_names,
+        };
+        if !transforms.is_empty() {
+            config.transforms = Some(transforms);
+        }
+
+        let mut sinks = IndexMap::new();
+        for (name, component_type) in self.components(Kind::Sink, &mut names)? {
+            let mut acknowledges = false;
+            let mut table = self.configure(Kind::Sink, &name, component_type, |value| {
+                value
+                    .try_into::<Box<dyn SinkConfig>>()
+                    .map(|sink| acknowledges = sink.acknowledgements().is_some())
+            })?;
+            if acknowledges {
+                let question = format!(
+                    "Enable end-to-end acknowledgements for sink \"{}\", so sources wait for delivery before acknowledging events?",
+                    name
+                );
+                if self.confirm(&question, acknowledging_sources)? {
+                    let mut acknowledgements = Map::new();
+                    acknowledgements.insert("enabled".into(), true.into());
+                    table.insert("acknowledgements".into(), acknowledgements.into());
+                }
+            }
+            sinks.insert(
+                name,
+                SinkOuter {
+                    inputs: sink_inputs.clone(),
+                    inner: Value::Table(table),
+                    healthcheck: SinkHealthcheckOptions::default(),
+                    buffer: BufferConfig::default(),
+                },
+            );
+        }
+        config.sinks = Some(sinks);
+
+        Ok(config)
+    }
+
+    /// Asks for the components of one kind and their names.
+    fn components(
+        &mut self,
+        kind: Kind,
+        names: &mut HashSet<String>,
+    ) -> io::Result<Vec<(String, &'static str)>> {
+        let types = kind.types();
+        let optional = matches!(kind, Kind::Transform);
+        let question = format!(
+            "{}s to add, comma-separated ({}`?` lists the available types)",
+            capitalize(kind.name()),
+            if optional {
+                "leave empty for none, "
+            } else {
+                ""
+            },
+        );
+
+        let selected = loop {
+            let answer = self.ask(&question, None)?;
+            if answer == "?" {
+                writeln!(self.output, "  {}", types.join(", "))?;
+                continue;
+            }
+            match parse_types(&answer, &types) {
+                Ok(selected) if selected.is_empty() && !optional => {
+                    writeln!(self.output, "  At least one {} is required.", kind.name())?
+                }
+                Ok(selected) => break selected,
+                Err(error) => writeln!(self.output, "  {}", error)?,
+            }
+        };
+
+        let mut components = Vec::with_capacity(selected.len());
+        for (i, component_type) in selected.into_iter().enumerate() {
+            let default = format!("{}{}", kind.name(), i);
+            let question = format!("Name of the {} {}", component_type, kind.name());
+            let name = self.ask_valid(&question, Some(&default), |name| {
+                validate_name(name, names).map(|_| name.to_owned())
+            })?;
+            names.insert(name.clone());
+            components.push((name, component_type));
+        }
+        Ok(components)
+    }
+
+    /// Walks through the options of a component's example configuration
+    /// until `check` accepts the result.
+    fn configure<E: Display>(
+        &mut self,
+        kind: Kind,
+        name: &str,
+        component_type: &str,
+        mut check: impl FnMut(Value) -> Result<(), E>,
+    ) -> io::Result<Map<String, Value>> {
+        let mut table = kind.example(component_type);
+        loop {
+            writeln!(
+                self.output,
+                "Configuring {} \"{}\" ({}):",
+                kind.name(),
+                name,
+                component_type
+            )?;
+            self.fill(&mut table)?;
+            match check(Value::Table(table.clone())) {
+                Ok(()) => return Ok(table),
+                Err(error) => writeln!(self.output, "  Invalid configuration: {}", error)?,
+            }
+        }
+    }
+
+    fn fill(&mut self, table: &mut Map<String, Value>) -> io::Result<()> {
+        for (key, value) in table.iter_mut() {
+            match (key.as_str(), value) {
+                ("type", _) => {}
+                ("encoding", Value::Table(codec)) => self.codec(key, codec, ENCODING_CODECS)?,
+                ("decoding", Value::Table(codec)) => self.codec(key, codec, DECODING_CODECS)?,
+                (_, Value::Table(_)) | (_, Value::Datetime(_)) => {}
+                (_, value) => {
+                    let example = value.clone();
+                    let question = format!("  {}", key);
+                    *value = self.ask_valid(&question, Some(&display(&example)), |answer| {
+                        parse(answer, &example)
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn codec(
+        &mut self,
+        key: &str,
+        table: &mut Map<String, Value>,
+        suggestions: &[&str],
+    ) -> io::Result<()> {
+        let current = match table.get("codec") {
+            Some(Value::String(codec)) => codec.clone(),
+            _ => return Ok(()),
+        };
+        let question = format!("  {}.codec (one of {})", key, suggestions.join(", "));
+        let codec = self.ask_valid(&question, Some(&current), |answer| {
+            if answer == current || suggestions.contains(&answer) {
+                Ok(answer.to_owned())
+            } else {
+                Err(format!("unknown codec \"{}\"", answer))
+            }
+        })?;
+        if codec != current {
+            // Options of the previous codec don't apply to the new one.
+            table.clear();
+            table.insert("codec".into(), codec.into());
+        }
+        Ok(())
+    }
+
+    fn confirm(&mut self, question: &str, default: bool) -> io::Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        self.ask_valid(question, Some(hint), |answer| {
+            match answer.to_lowercase().as_str() {
+                _ if answer == hint => Ok(default),
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err("please answer \"y\" or \"n\"".to_owned()),
+            }
+        })
+    }
+
+    fn ask_valid<T>(
+        &mut self,
+        question: &str,
+        default: Option<&str>,
+        mut parse: impl FnMut(&str) -> Result<T, String>,
+    ) -> io::Result<T> {
+        loop {
+            let answer = self.ask(question, default)?;
+            match parse(&answer) {
+                Ok(value) => return Ok(value),
+                Err(error) => writeln!(self.output, "  {}", error)?,
+            }
+        }
+    }
+
+    fn ask(&mut self, question: &str, default: Option<&str>) -> io::Result<String> {
+        match default {
+            Some(default) => write!(self.output, "{} [{}]: ", question, default)?,
+            None => write!(self.output, "{}: ", question)?,
+        }
+        self.output.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "input ended before the configuration was complete",
+            ));
+        }
+        let answer = line.trim();
+        Ok(match (answer.is_empty(), default) {
+            (true, Some(default)) => default.to_owned(),
+            _ => answer.to_owned(),
+        })
+    }
+}
+
+fn parse_types(answer: &str, types: &[&'static str]) -> Result<Vec<&'static str>, String> {
+    answer
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            types
+                .iter()
+                .find(|t| **t == s)
+                .copied()
+                .ok_or_else(|| format!("unknown component type \"{}\"", s))
+        })
+        .collect()
+}
+
+fn validate_name(name: &str, names: &HashSet<String>) -> Result<(), String> {
+    if name.is_empty() {
+        Err("the name can't be empty".to_owned())
+    } else if name.contains('.') || name.contains(char::is_whitespace) {
+        Err("the name can't contain a \".\" or whitespace".to_owned())
+    } else if names.contains(name) {
+        Err(format!("a component named \"{}\" already exists", name))
+    } else {
+        Ok(())
+    }
+}
+
+/// Renders a value the way it's typed back in.
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(display).collect::<Vec<_>>().join(","),
+        value => value.to_string(),
+    }
+}
+
+/// Parses an answer into a value of the same type as the example value.
+fn parse(answer: &str, example: &Value) -> Result<Value, String> {
+    match example {
+        Value::String(_) => Ok(answer.into()),
+        Value::Integer(_) => answer
+            .parse::<i64>()
+            .map(Value::Integer)
+            .map_err(|_| format!("\"{}\" is not an integer", answer)),
+        Value::Float(_) => answer
+            .parse::<f64>()
+            .map(Value::Float)
+            .map_err(|_| format!("\"{}\" is not a number", answer)),
+        Value::Boolean(_) => answer
+            .parse::<bool>()
+            .map(Value::Boolean)
+            .map_err(|_| "please answer \"true\" or \"false\"".to_owned()),
+        Value::Array(items) => {
+            let item = items.first().cloned().unwrap_or_else(|| "".into());
+            answer
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| parse(s, &item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+        }
+        Value::Table(_) | Value::Datetime(_) => Ok(example.clone()),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> (io::Result<Config>, String) {
+        let mut output = Vec::new();
+        let config = Wizard::new(input.as_bytes(), &mut output).run();
+        (config, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn parses_answers_like_the_example() {
+        assert_eq!(parse("12", &Value::Integer(1)), Ok(Value::Integer(12)));
+        assert!(parse("twelve", &Value::Integer(1)).is_err());
+        assert_eq!(
+            parse("true", &Value::Boolean(false)),
+            Ok(Value::Boolean(true))
+        );
+        assert_eq!(
+            parse("a, b", &Value::Array(vec!["x".into()])),
+            Ok(Value::Array(vec!["a".into(), "b".into()]))
+        );
+        assert_eq!(
+            display(&Value::Array(vec!["a".into(), "b".into()])),
+            "a,b".to_owned()
+        );
+    }
+
+    #[test]
+    fn validates_names() {
+        let names = ["in".to_owned()].into_iter().collect();
+        assert!(validate_name("out", &names).is_ok());
+        assert!(validate_name("in", &names).is_err());
+        assert!(validate_name("a.b", &names).is_err());
+        assert!(validate_name("", &names).is_err());
+    }
+
+    #[test]
+    fn input_ending_early_is_an_error() {
+        let (config, _) = run("");
+        assert_eq!(config.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(all(feature = "sources-stdin", feature = "sinks-console"))]
+    #[test]
+    fn builds_a_pipeline() {
+        let input = [
+            // Sources: an unknown type is asked for again.
+            "nope", "stdin", "in",
+            // Source options, in order: decoding.codec, then max_length,
+            // which rejects a non-integer.
+            "json", "big", "", // No transforms.
+            "",
+            // Sinks, then the sink's options: encoding.codec only accepts
+            // the suggested codecs, then target.
+            "console", "", "yaml", "json", "", // Acknowledgements.
+            "y",
+        ]
+        .join("\n");
+        let (config, output) = run(&input);
+        let config = config.unwrap();
+
+        assert!(output.contains("unknown component type \"nope\""));
+        assert!(output.contains("\"big\" is not an integer"));
+        assert!(output.contains("unknown codec \"yaml\""));
+
+        let sources = config.sources.unwrap();
+        assert_eq!(sources["in"]["type"].as_str(), Some("stdin"));
+        assert_eq!(sources["in"]["decoding"]["codec"].as_str(), Some("json"));
+        assert!(config.transforms.is_none());
+        assert!(output.contains("[y/N]"));
+
+        let sinks = config.sinks.unwrap();
+        assert_eq!(sinks["sink0"].inputs, vec!["in".to_owned()]);
+        assert_eq!(
+            sinks["sink0"].inner["encoding"]["codec"].as_str(),
+            Some("json")
+        );
+        assert_eq!(
+            sinks["sink0"].inner["acknowledgements"]["enabled"].as_bool(),
+            Some(true)
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use std::{
     fmt,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use futures::future::BoxFuture;
@@ -52,8 +54,83 @@ impl HttpError {
 
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
+type Connector = ProxyConnector<HttpsConnector<HttpConnector>>;
+
+/// Connection pool configuration for HTTP clients.
+///
+/// Long-lived connections can be pinned to a single backend behind a load balancer, or be silently
+/// dropped by NAT gateways and firewalls that time out idle flows, which makes requests sent over
+/// them fail. These settings bound how long connections are kept and reused.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectionPoolConfig {
+    /// The maximum number of idle connections kept open per host.
+    ///
+    /// Set to `0` to close connections as soon as their requests complete.
+    pub max_idle_per_host: Option<usize>,
+
+    /// How long, in seconds, idle connections are kept open before being closed.
+    pub idle_timeout_secs: Option<u64>,
+
+    /// The interval, in seconds, at which TCP keepalive probes are sent on idle connections.
+    ///
+    /// Keepalive probes keep the connection tracked by NAT gateways and firewalls that would
+    /// otherwise drop it.
+    pub tcp_keepalive_secs: Option<u64>,
+
+    /// The maximum time, in seconds, connections are reused for.
+    ///
+    /// Connections are rotated at this interval: once it elapses, new requests are sent over new
+    /// connections, and the previous connections are closed once their in-flight requests
+    /// complete.
+    pub max_connection_lifetime_secs: Option<u64>,
+}
+
+impl ConnectionPoolConfig {
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn configure(&self, builder: &mut client::Builder) {
+        if let Some(max_idle_per_host) = self.max_idle_per_host {
+            builder.pool_max_idle_per_host(max_idle_per_host);
+        }
+        if let Some(idle_timeout_secs) = self.idle_timeout_secs {
+            builder.pool_idle_timeout(Duration::from_secs(idle_timeout_secs));
+        }
+    }
+}
+
+/// The pool of connections a client sends requests over, which is replaced by a new one with new
+/// connections after the maximum connection lifetime.
+struct RotatingPool<B> {
+    builder: client::Builder,
+    connector: Connector,
+    lifetime: Duration,
+    current: Mutex<(Client<Connector, B>, Instant)>,
+}
+
+impl<B> RotatingPool<B>
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+{
+    fn client(&self) -> Client<Connector, B> {
+        let mut current = self.current.lock().expect("mutex poisoned");
+        if current.1.elapsed() >= self.lifetime {
+            // Dropping the previous client closes its idle connections, and the connections in
+            // use once their requests complete.
+            *current = (self.builder.build(self.connector.clone()), Instant::now());
+            emit!(http_client::HttpConnectionPoolRotated);
+        }
+        current.0.clone()
+    }
+}
+
 pub struct HttpClient<B = Body> {
-    client: Client<ProxyConnector<HttpsConnector<HttpConnector>>, B>,
+    client: Client<Connector, B>,
+    pool: Option<Arc<RotatingPool<B>>>,
     user_agent: HeaderValue,
 }
 
@@ -75,14 +152,57 @@ where
         proxy_config: &ProxyConfig,
         client_builder: &mut client::Builder,
     ) -> Result<HttpClient<B>, HttpError> {
-        let proxy = build_proxy_connector(tls_settings.into(), proxy_config)?;
-        let client = client_builder.build(proxy);
+        HttpClient::build(
+            tls_settings,
+            proxy_config,
+            &ConnectionPoolConfig::default(),
+            client_builder,
+        )
+    }
+
+    pub fn new_with_connection_pool(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy_config: &ProxyConfig,
+        pool_config: &ConnectionPoolConfig,
+    ) -> Result<HttpClient<B>, HttpError> {
+        HttpClient::build(
+            tls_settings,
+            proxy_config,
+            pool_config,
+            &mut Client::builder(),
+        )
+    }
+
+    fn build(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy_config: &ProxyConfig,
+        pool_config: &ConnectionPoolConfig,
+        client_builder: &mut client::Builder,
+    ) -> Result<HttpClient<B>, HttpError> {
+        let tcp_keepalive = pool_config.tcp_keepalive_secs.map(Duration::from_secs);
+        let proxy =
+            build_proxy_connector_with_keepalive(tls_settings.into(), proxy_config, tcp_keepalive)?;
+        pool_config.configure(client_builder);
+        let client = client_builder.build(proxy.clone());
+
+        let pool = pool_config.max_connection_lifetime_secs.map(|lifetime| {
+            Arc::new(RotatingPool {
+                builder: client_builder.clone(),
+                connector: proxy,
+                lifetime: Duration::from_secs(lifetime),
+                current: Mutex::new((client.clone(), Instant::now())),
+            })
+        });
 
         let version = crate::get_version();
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
             .expect("Invalid header value for version!");
 
-        Ok(HttpClient { client, user_agent })
+        Ok(HttpClient {
+            client,
+            pool,
+            user_agent,
+        })
     }
 
     pub fn send(
@@ -96,7 +216,10 @@ where
 
         emit!(http_client::AboutToSendHttpRequest { request: &request });
 
-        let response = self.client.request(request);
+        let response = match &self.pool {
+            Some(pool) => pool.client().request(request),
+            None => self.client.request(request),
+        };
 
         let fut = async move {
             // Capture the time right before we issue the request.
@@ -139,7 +262,15 @@ pub fn build_proxy_connector(
     tls_settings: MaybeTlsSettings,
     proxy_config: &ProxyConfig,
 ) -> Result<ProxyConnector<HttpsConnector<HttpConnector>>, HttpError> {
-    let https = build_tls_connector(tls_settings)?;
+    build_proxy_connector_with_keepalive(tls_settings, proxy_config, None)
+}
+
+fn build_proxy_connector_with_keepalive(
+    tls_settings: MaybeTlsSettings,
+    proxy_config: &ProxyConfig,
+    tcp_keepalive: Option<Duration>,
+) -> Result<ProxyConnector<HttpsConnector<HttpConnector>>, HttpError> {
+    let https = build_tls_connector_with_keepalive(tls_settings, tcp_keepalive)?;
     let mut proxy = ProxyConnector::new(https).unwrap();
     proxy_config
         .configure(&mut proxy)
@@ -149,9 +280,17 @@ pub fn build_proxy_connector(
 
 pub fn build_tls_connector(
     tls_settings: MaybeTlsSettings,
+) -> Result<HttpsConnector<HttpConnector>, HttpError> {
+    build_tls_connector_with_keepalive(tls_settings, None)
+}
+
+fn build_tls_connector_with_keepalive(
+    tls_settings: MaybeTlsSettings,
+    tcp_keepalive: Option<Duration>,
 ) -> Result<HttpsConnector<HttpConnector>, HttpError> {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
+    http.set_keepalive(tcp_keepalive);
 
     let tls = tls_connector_builder(&tls_settings).context(BuildTlsConnectorSnafu)?;
     let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnectorSnafu)?;
@@ -206,6 +345,7 @@ impl<B> Clone for HttpClient<B> {
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            pool: self.pool.clone(),
             user_agent: self.user_agent.clone(),
         }
     }
@@ -335,4 +475,59 @@ mod tests {
             Some(&HeaderValue::from_static("foo"))
         );
     }
+
+    async fn count_connections(pool_config: ConnectionPoolConfig, requests: usize) -> usize {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use hyper::{
+            server::conn::AddrStream,
+            service::{make_service_fn, service_fn},
+            Response, Server,
+        };
+
+        let addr = crate::test_util::next_addr();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&connections);
+        let server = Server::bind(&addr).serve(make_service_fn(move |_: &AddrStream| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async {
+                Ok::<_, hyper::Error>(service_fn(|_| async {
+                    Ok::<_, hyper::Error>(Response::new(Body::empty()))
+                }))
+            }
+        }));
+        tokio::spawn(server);
+
+        let client =
+            HttpClient::new_with_connection_pool(None, &ProxyConfig::default(), &pool_config)
+                .unwrap();
+        for _ in 0..requests {
+            let request = Request::get(format!("http://{}/", addr))
+                .body(Body::empty())
+                .unwrap();
+            let response = client.send(request).await.unwrap();
+            hyper::body::to_bytes(response.into_body()).await.unwrap();
+            // Give the connection time to return to the pool.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        connections.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn reuses_connections() {
+        assert_eq!(
+            count_connections(ConnectionPoolConfig::default(), 3).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn rotates_connections_after_max_lifetime() {
+        let pool_config = ConnectionPoolConfig {
+            max_connection_lifetime_secs: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(count_connections(pool_config, 3).await, 3);
+    }
 }
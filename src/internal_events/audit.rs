@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct ConfigChangeAudited<'a> {
+    pub trigger: &'static str,
+    pub outcome: &'static str,
+    pub summary: &'a str,
+}
+
+impl InternalEvent for ConfigChangeAudited<'_> {
+    fn emit(self) {
+        info!(
+            target: "vector::audit",
+            message = "Configuration change recorded.",
+            trigger = self.trigger,
+            outcome = self.outcome,
+            changes = self.summary,
+        );
+        counter!(
+            "config_changes_total", 1,
+            "trigger" => self.trigger,
+            "outcome" => self.outcome,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ConfigAuditWriteError<'a> {
+    pub path: &'a Path,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ConfigAuditWriteError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to write configuration audit record.",
+            path = ?self.path,
+            error = %self.error,
+            error_code = "config_audit_write",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "config_audit_write",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
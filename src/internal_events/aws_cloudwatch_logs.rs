@@ -1,66 +1,128 @@
 use metrics::counter;
+#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+pub use sink::*;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub use source::*;
 use vector_core::internal_event::InternalEvent;
 
-use super::prelude::{error_stage, error_type, io_error_code};
+use super::prelude::{error_stage, error_type};
 
-#[derive(Debug)]
-pub struct AwsCloudwatchLogsMessageSizeError {
-    pub size: usize,
-    pub max_size: usize,
-}
+#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+mod sink {
+    use super::*;
+    use crate::internal_events::prelude::io_error_code;
 
-impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
-    fn emit(self) {
-        error!(
-            message = "Encoded event is too long.",
-            size = self.size as u64,
-            max_size = self.max_size as u64,
-            error_code = "message_too_long",
-            error_type = error_type::ENCODER_FAILED,
-            stage = error_stage::PROCESSING,
-        );
-        counter!(
-            "component_errors_total", 1,
-            "error_code" => "message_too_long",
-            "error_type" => error_type::ENCODER_FAILED,
-            "stage" => error_stage::PROCESSING,
-        );
-        counter!(
-            "component_discarded_events_total", 1,
-            "error_code" => "message_too_long",
-            "error_type" => error_type::ENCODER_FAILED,
-            "stage" => error_stage::PROCESSING,
-        );
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsMessageSizeError {
+        pub size: usize,
+        pub max_size: usize,
+    }
+
+    impl InternalEvent for AwsCloudwatchLogsMessageSizeError {
+        fn emit(self) {
+            error!(
+                message = "Encoded event is too long.",
+                size = self.size as u64,
+                max_size = self.max_size as u64,
+                error_code = "message_too_long",
+                error_type = error_type::ENCODER_FAILED,
+                stage = error_stage::PROCESSING,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "message_too_long",
+                "error_type" => error_type::ENCODER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+            counter!(
+                "component_discarded_events_total", 1,
+                "error_code" => "message_too_long",
+                "error_type" => error_type::ENCODER_FAILED,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
     }
-}
 
-#[derive(Debug)]
-pub struct AwsCloudwatchLogsEncoderError {
-    pub error: codecs::encoding::Error,
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsEncoderError {
+        pub error: codecs::encoding::Error,
+    }
+
+    impl InternalEvent for AwsCloudwatchLogsEncoderError {
+        fn emit(self) {
+            let error_code = io_error_code(&std::io::ErrorKind::InvalidData.into());
+            error!(
+                message = "Error when encoding event.",
+                error = %self.error,
+                error_type = error_type::ENCODER_FAILED,
+                stage = error_stage::PROCESSING,
+                error_code = error_code,
+                internal_log_rate_secs = 10,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::ENCODER_FAILED,
+                "error_code" => error_code,
+                "stage" => error_stage::PROCESSING,
+            );
+            counter!(
+                "component_discarded_events_total", 1,
+                "error_type" => error_type::ENCODER_FAILED,
+                "error_code" => error_code,
+                "stage" => error_stage::PROCESSING,
+            );
+        }
+    }
 }
 
-impl InternalEvent for AwsCloudwatchLogsEncoderError {
-    fn emit(self) {
-        let error_code = io_error_code(&std::io::ErrorKind::InvalidData.into());
-        error!(
-            message = "Error when encoding event.",
-            error = %self.error,
-            error_type = error_type::ENCODER_FAILED,
-            stage = error_stage::PROCESSING,
-            error_code = error_code,
-            internal_log_rate_secs = 10,
-        );
-        counter!(
-            "component_errors_total", 1,
-            "error_type" => error_type::ENCODER_FAILED,
-            "error_code" => error_code,
-            "stage" => error_stage::PROCESSING,
-        );
-        counter!(
-            "component_discarded_events_total", 1,
-            "error_type" => error_type::ENCODER_FAILED,
-            "error_code" => error_code,
-            "stage" => error_stage::PROCESSING,
-        );
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+mod source {
+    use super::*;
+
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsFilterError<'a, E> {
+        pub log_group: &'a str,
+        pub error: &'a E,
+    }
+
+    impl<'a, E: std::fmt::Display> InternalEvent for AwsCloudwatchLogsFilterError<'a, E> {
+        fn emit(self) {
+            error!(
+                message = "Failed to fetch CloudWatch Logs events.",
+                log_group = %self.log_group,
+                error = %self.error,
+                error_code = "failed_filtering_log_events",
+                error_type = error_type::REQUEST_FAILED,
+                stage = error_stage::RECEIVING,
+                internal_log_rate_secs = 10,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_code" => "failed_filtering_log_events",
+                "error_type" => error_type::REQUEST_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct AwsCloudwatchLogsCheckpointError {
+        pub error: crate::Error,
+    }
+
+    impl InternalEvent for AwsCloudwatchLogsCheckpointError {
+        fn emit(self) {
+            error!(
+                message = "Unable to write checkpoints.",
+                error = %self.error,
+                error_type = error_type::WRITER_FAILED,
+                stage = error_stage::RECEIVING,
+            );
+            counter!(
+                "component_errors_total", 1,
+                "error_type" => error_type::WRITER_FAILED,
+                "stage" => error_stage::RECEIVING,
+            );
+        }
     }
 }
@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AwsCloudwatchMetricsRequestError<'a, E> {
+    pub operation: &'static str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AwsCloudwatchMetricsRequestError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "CloudWatch request failed.",
+            operation = %self.operation,
+            error = %self.error,
+            error_code = "failed_collecting_metrics",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_collecting_metrics",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
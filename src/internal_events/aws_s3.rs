@@ -0,0 +1,30 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct AwsS3ManifestWriteError<'a, E> {
+    pub key: &'a str,
+    pub error: &'a E,
+}
+
+impl<'a, E: std::fmt::Display> InternalEvent for AwsS3ManifestWriteError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing manifest, its objects will be listed in the next one.",
+            key = %self.key,
+            error = %self.error,
+            error_code = "failed_writing_manifest",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "failed_writing_manifest",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+/// A failure injected by the `blackhole` sink, for testing how the rest of the topology reacts.
+#[derive(Debug)]
+pub struct BlackholeInjectedFailure {
+    pub count: usize,
+    pub rejected: bool,
+}
+
+impl InternalEvent for BlackholeInjectedFailure {
+    fn emit(self) {
+        let error_code = if self.rejected {
+            "injected_rejection"
+        } else {
+            "injected_error"
+        };
+        error!(
+            message = "Injected failure of a batch of events.",
+            count = %self.count,
+            error_code,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => error_code,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
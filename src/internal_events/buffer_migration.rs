@@ -0,0 +1,79 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct BufferMigrationStarted {
+    pub from: &'static str,
+    pub to: &'static str,
+}
+
+impl InternalEvent for BufferMigrationStarted {
+    fn emit(self) {
+        info!(
+            message = "Migrating buffer contents to the new buffer.",
+            from = %self.from,
+            to = %self.to,
+        );
+        counter!(
+            "buffer_migrations_total", 1,
+            "from" => self.from,
+            "to" => self.to,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferEventsMigrated {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for BufferEventsMigrated {
+    fn emit(self) {
+        trace!(
+            message = "Migrated events to the new buffer.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!("buffer_migrated_events_total", self.count as u64);
+        counter!("buffer_migrated_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferMigrationCompleted {
+    pub count: usize,
+}
+
+impl InternalEvent for BufferMigrationCompleted {
+    fn emit(self) {
+        info!(
+            message = "Buffer migration completed.",
+            count = %self.count,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferMigrationInterrupted {
+    pub count: usize,
+}
+
+impl InternalEvent for BufferMigrationInterrupted {
+    fn emit(self) {
+        error!(
+            message = "New buffer closed during migration; dropping events.",
+            count = %self.count,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        counter!("events_discarded_total", self.count as u64);
+    }
+}
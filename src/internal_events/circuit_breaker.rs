@@ -0,0 +1,41 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct CircuitBreakerOpened {
+    pub failures: u32,
+    pub cooldown_secs: u64,
+}
+
+impl InternalEvent for CircuitBreakerOpened {
+    fn emit(self) {
+        warn!(
+            message = "Circuit breaker opened; holding back requests.",
+            failures = %self.failures,
+            cooldown_secs = %self.cooldown_secs,
+        );
+        gauge!("circuit_breaker_open", 1.0);
+        counter!("circuit_breaker_transitions_total", 1, "state" => "open");
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerClosed;
+
+impl InternalEvent for CircuitBreakerClosed {
+    fn emit(self) {
+        info!(message = "Circuit breaker closed; resuming requests.");
+        gauge!("circuit_breaker_open", 0.0);
+        counter!("circuit_breaker_transitions_total", 1, "state" => "closed");
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerRejected;
+
+impl InternalEvent for CircuitBreakerRejected {
+    fn emit(self) {
+        trace!(message = "Circuit breaker open; request rejected.");
+        counter!("circuit_breaker_rejected_requests_total", 1);
+    }
+}
@@ -0,0 +1,25 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct EtwSessionError {
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for EtwSessionError {
+    fn emit(self) {
+        error!(
+            message = "Error processing ETW trace session.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
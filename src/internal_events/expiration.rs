@@ -0,0 +1,19 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct EventsExpired {
+    pub count: usize,
+}
+
+impl InternalEvent for EventsExpired {
+    fn emit(self) {
+        debug!(
+            message = "Dropped expired events.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!("events_discarded_total", self.count as u64);
+        counter!("expired_events_total", self.count as u64);
+    }
+}
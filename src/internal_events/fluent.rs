@@ -1,14 +1,22 @@
+#[cfg(feature = "sources-fluent")]
+use std::net::SocketAddr;
+
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
 use super::prelude::{error_stage, error_type};
-use crate::sources::fluent::DecodeError;
+#[cfg(feature = "sinks-fluent")]
+use crate::sinks::fluent::FluentError;
+#[cfg(feature = "sources-fluent")]
+use crate::sources::fluent::{DecodeError, HandshakeError};
 
+#[cfg(feature = "sources-fluent")]
 #[derive(Debug)]
 pub struct FluentMessageReceived {
     pub byte_size: u64,
 }
 
+#[cfg(feature = "sources-fluent")]
 impl InternalEvent for FluentMessageReceived {
     fn emit(self) {
         trace!(message = "Received fluent message.", byte_size = %self.byte_size);
@@ -17,12 +25,14 @@ impl InternalEvent for FluentMessageReceived {
     }
 }
 
+#[cfg(feature = "sources-fluent")]
 #[derive(Debug)]
 pub struct FluentMessageDecodeError<'a> {
     pub error: &'a DecodeError,
     pub base64_encoded_message: String,
 }
 
+#[cfg(feature = "sources-fluent")]
 impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
     fn emit(self) {
         error!(
@@ -42,3 +52,53 @@ impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
         counter!("decode_errors_total", 1);
     }
 }
+
+#[cfg(feature = "sources-fluent")]
+#[derive(Debug)]
+pub struct FluentHandshakeError<'a> {
+    pub error: &'a HandshakeError,
+    pub peer_addr: SocketAddr,
+}
+
+#[cfg(feature = "sources-fluent")]
+impl<'a> InternalEvent for FluentHandshakeError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Fluent handshake failed, closing connection.",
+            error = %self.error,
+            peer_addr = %self.peer_addr,
+            internal_log_rate_secs = 10,
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[cfg(feature = "sinks-fluent")]
+#[derive(Debug)]
+pub struct FluentSendError<'a> {
+    pub error: &'a FluentError,
+}
+
+#[cfg(feature = "sinks-fluent")]
+impl<'a> InternalEvent for FluentSendError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to send message.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
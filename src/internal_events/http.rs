@@ -102,6 +102,22 @@ impl<'a> InternalEvent for HttpBadRequest<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct HttpBackpressureRejected {
+    pub count: usize,
+}
+
+impl InternalEvent for HttpBackpressureRejected {
+    fn emit(self) {
+        debug!(
+            message = "Rejected request because downstream components are not keeping up.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!("http_backpressure_rejected_requests_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct HttpDecompressError<'a> {
     pub error: &'a dyn Error,
@@ -90,6 +90,62 @@ impl<'a> InternalEvent for GotHttpError<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct HttpRequestTooLarge {
+    /// Whether the batch of the request could be split in two, to be sent again as two requests.
+    pub split: bool,
+}
+
+impl InternalEvent for HttpRequestTooLarge {
+    fn emit(self) {
+        if self.split {
+            debug!(
+                message = "Request was rejected as too large, splitting the batch in two.",
+                internal_log_rate_secs = 10,
+            );
+        } else {
+            warn!(
+                message =
+                    "Request was rejected as too large, and its batch can't be split any further.",
+                internal_log_rate_secs = 10,
+            );
+        }
+        counter!("http_client_requests_too_large_total", 1, "split" => self.split.to_string());
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpCompressionFallback<'a> {
+    /// The `Content-Encoding` the server rejected.
+    pub rejected: &'a str,
+    /// The `Content-Encoding` requests are sent with from now on, or `identity` if uncompressed.
+    pub fallback: &'a str,
+}
+
+impl<'a> InternalEvent for HttpCompressionFallback<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Request compression was rejected as unsupported, falling back.",
+            rejected = %self.rejected,
+            fallback = %self.fallback,
+        );
+        counter!(
+            "http_client_compression_fallbacks_total", 1,
+            "content_encoding" => self.rejected.to_string(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpConnectionPoolRotated;
+
+impl InternalEvent for HttpConnectionPoolRotated {
+    fn emit(self) {
+        debug!(message = "Rotating HTTP connections after their maximum lifetime.");
+        counter!("http_client_connection_pool_rotations_total", 1);
+    }
+}
+
 /// Newtype placeholder to provide a formatter for the request and response body.
 struct FormatBody<'a, B>(&'a B);
 
@@ -32,3 +32,13 @@ impl InternalEvent for InternalLogsEventsReceived {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct InternalLogsEventSampled;
+
+impl InternalEvent for InternalLogsEventSampled {
+    fn emit(self) {
+        // MUST not emit logs here to avoid an infinite log loop
+        counter!("events_discarded_total", 1);
+    }
+}
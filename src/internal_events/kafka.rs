@@ -199,3 +199,28 @@ impl InternalEvent for KafkaNegativeAcknowledgmentError<'_> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaPartitionsPauseError {
+    pub error: rdkafka::error::KafkaError,
+    pub operation: &'static str,
+}
+
+impl InternalEvent for KafkaPartitionsPauseError {
+    fn emit(self) {
+        error!(
+            message = "Unable to pause or resume the assigned partitions.",
+            error = %self.error,
+            operation = self.operation,
+            error_code = "kafka_partitions_pause",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_partitions_pause",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use metrics::histogram;
+use vector_core::internal_event::InternalEvent;
+
+/// The age of the oldest event of a batch, the time since its timestamp, when its source sent it
+/// downstream.
+#[derive(Debug)]
+pub struct SourceEventLag {
+    pub lag: Duration,
+}
+
+impl InternalEvent for SourceEventLag {
+    fn emit(self) {
+        histogram!("source_lag_time_seconds", self.lag);
+    }
+}
+
+/// The age of the oldest event of a batch, the time since its timestamp, when its sink delivered
+/// it.
+#[derive(Debug)]
+pub struct SinkEventLag {
+    pub lag: Duration,
+}
+
+impl InternalEvent for SinkEventLag {
+    fn emit(self) {
+        histogram!("sink_lag_time_seconds", self.lag);
+    }
+}
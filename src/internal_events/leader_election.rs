@@ -0,0 +1,92 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct LeaderElected<'a> {
+    pub lease: &'a str,
+}
+
+impl InternalEvent for LeaderElected<'_> {
+    fn emit(self) {
+        info!(message = "Elected leader, starting source.", lease = %self.lease);
+        gauge!("leader", 1.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct LeadershipLost<'a> {
+    pub lease: &'a str,
+}
+
+impl InternalEvent for LeadershipLost<'_> {
+    fn emit(self) {
+        error!(
+            message = "Lost leadership, stopping source until elected again.",
+            lease = %self.lease,
+            error_code = "leadership_lost",
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "leadership_lost",
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        gauge!("leader", 0.0);
+    }
+}
+
+#[derive(Debug)]
+pub struct LeaderElectionError<'a> {
+    pub error: kube::Error,
+    pub lease: &'a str,
+}
+
+impl InternalEvent for LeaderElectionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to acquire or renew the lease.",
+            lease = %self.lease,
+            error = %self.error,
+            error_code = "lease_request_failed",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "lease_request_failed",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LeaderSourceBuildError<'a> {
+    pub error: crate::Error,
+    pub lease: &'a str,
+}
+
+impl InternalEvent for LeaderSourceBuildError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to build the source after being elected leader.",
+            lease = %self.lease,
+            error = %self.error,
+            error_code = "source_build_failed",
+            error_type = error_type::CONFIGURATION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "source_build_failed",
+            "error_type" => error_type::CONFIGURATION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        gauge!("leader", 0.0);
+    }
+}
@@ -5,18 +5,18 @@ use super::prelude::{error_stage, error_type, io_error_code};
 
 #[derive(Debug)]
 pub struct HerokuLogplexRequestReceived<'a> {
-    pub msg_count: usize,
-    pub frame_id: &'a str,
-    pub drain_token: &'a str,
+    pub msg_count: Option<usize>,
+    pub frame_id: Option<&'a str>,
+    pub drain_token: Option<&'a str>,
 }
 
 impl<'a> InternalEvent for HerokuLogplexRequestReceived<'a> {
     fn emit(self) {
         info!(
             message = "Handling logplex request.",
-            msg_count = %self.msg_count,
-            frame_id = %self.frame_id,
-            drain_token = %self.drain_token,
+            msg_count = ?self.msg_count,
+            frame_id = ?self.frame_id,
+            drain_token = ?self.drain_token,
             internal_log_rate_secs = 10
         );
         counter!("requests_received_total", 1);
@@ -0,0 +1,40 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct MemoryBudgetTrimmed {
+    pub released_bytes: usize,
+}
+
+impl InternalEvent for MemoryBudgetTrimmed {
+    fn emit(self) {
+        debug!(
+            message = "Trimmed component state to stay within the memory budget.",
+            released_bytes = %self.released_bytes,
+            internal_log_rate_secs = 10,
+        );
+        counter!("memory_budget_trims_total", 1);
+        counter!(
+            "memory_budget_trimmed_bytes_total",
+            self.released_bytes as u64
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryBudgetExceeded {
+    pub used_bytes: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for MemoryBudgetExceeded {
+    fn emit(self) {
+        warn!(
+            message = "Memory budget exceeded, waiting before sending more events.",
+            used_bytes = %self.used_bytes,
+            max_bytes = %self.max_bytes,
+            internal_log_rate_secs = 10,
+        );
+        counter!("memory_budget_exceeded_total", 1);
+    }
+}
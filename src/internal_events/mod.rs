@@ -6,21 +6,33 @@ mod aggregate;
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+mod audit;
 #[cfg(feature = "aws-core")]
 mod aws;
-#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+#[cfg(any(
+    feature = "sinks-aws_cloudwatch_logs",
+    feature = "sources-aws_cloudwatch_logs"
+))]
 mod aws_cloudwatch_logs;
+#[cfg(feature = "sources-aws_cloudwatch_metrics")]
+mod aws_cloudwatch_metrics;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 mod aws_ec2_metadata;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 mod aws_kinesis_firehose;
+#[cfg(feature = "sinks-aws_s3")]
+mod aws_s3;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 mod aws_sqs;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod azure_blob;
 mod batch;
+#[cfg(feature = "sinks-blackhole")]
+mod blackhole;
+mod buffer_migration;
+mod circuit_breaker;
 mod common;
 mod conditions;
 #[cfg(feature = "sinks-datadog_metrics")]
@@ -38,13 +50,16 @@ mod dnstap;
 mod docker_logs;
 mod elasticsearch;
 mod encoding_transcode;
+#[cfg(all(windows, feature = "sources-etw"))]
+mod etw;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
 mod exec;
+mod expiration;
 #[cfg(feature = "transforms-filter")]
 mod filter;
-#[cfg(feature = "sources-fluent")]
+#[cfg(any(feature = "sources-fluent", feature = "sinks-fluent"))]
 mod fluent;
 #[cfg(feature = "sources-gcp_pubsub")]
 mod gcp_pubsub;
@@ -61,6 +76,9 @@ mod journald;
 mod kafka;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
+mod lag;
+#[cfg(feature = "kubernetes")]
+mod leader_election;
 #[cfg(feature = "transforms-log_to_metric")]
 mod log_to_metric;
 mod logplex;
@@ -68,12 +86,21 @@ mod logplex;
 mod loki;
 #[cfg(feature = "transforms-lua")]
 mod lua;
+mod memory_budget;
 #[cfg(feature = "transforms-metric_to_log")]
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
+#[cfg(feature = "sources-netflow")]
+mod netflow;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "sources-network_flows"
+))]
+mod network_flows;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
@@ -94,9 +121,12 @@ mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
 mod remap;
+#[cfg(any(feature = "sources-replay", feature = "sinks-record"))]
+mod replay;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+mod sink_degradation;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -112,6 +142,10 @@ mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-top_k")]
+mod top_k;
+#[cfg(feature = "transforms-trace_sampling")]
+mod trace_sampling;
 mod udp;
 mod unix;
 mod vector;
@@ -122,9 +156,12 @@ mod websocket;
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
     feature = "sinks-file",
+    feature = "sinks-record",
 ))]
 mod file;
 mod windows;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+mod windows_event_log;
 
 #[cfg(feature = "sources-mongodb_metrics")]
 pub(crate) use mongodb_metrics::*;
@@ -137,16 +174,25 @@ pub(crate) use self::apache_metrics::*;
 pub(crate) use self::api::*;
 #[cfg(feature = "aws-core")]
 pub(crate) use self::aws::*;
-#[cfg(feature = "sinks-aws_cloudwatch_logs")]
+#[cfg(any(
+    feature = "sinks-aws_cloudwatch_logs",
+    feature = "sources-aws_cloudwatch_logs"
+))]
 pub(crate) use self::aws_cloudwatch_logs::*;
+#[cfg(feature = "sources-aws_cloudwatch_metrics")]
+pub(crate) use self::aws_cloudwatch_metrics::*;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub(crate) use self::aws_ec2_metadata::*;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub(crate) use self::aws_ecs_metrics::*;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
 pub(crate) use self::aws_kinesis_firehose::*;
+#[cfg(feature = "sinks-aws_s3")]
+pub(crate) use self::aws_s3::*;
 #[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs",))]
 pub(crate) use self::aws_sqs::*;
+#[cfg(feature = "sinks-blackhole")]
+pub(crate) use self::blackhole::*;
 #[cfg(feature = "sinks-datadog_metrics")]
 pub(crate) use self::datadog_metrics::*;
 #[cfg(feature = "sinks-datadog_traces")]
@@ -162,6 +208,8 @@ pub(crate) use self::dnstap::*;
 pub(crate) use self::docker_logs::*;
 #[cfg(feature = "sinks-elasticsearch")]
 pub(crate) use self::elasticsearch::*;
+#[cfg(all(windows, feature = "sources-etw"))]
+pub(crate) use self::etw::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub(crate) use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
@@ -170,11 +218,12 @@ pub(crate) use self::exec::*;
     feature = "sources-file",
     feature = "sources-kubernetes_logs",
     feature = "sinks-file",
+    feature = "sinks-record",
 ))]
 pub(crate) use self::file::*;
 #[cfg(feature = "transforms-filter")]
 pub(crate) use self::filter::*;
-#[cfg(feature = "sources-fluent")]
+#[cfg(any(feature = "sources-fluent", feature = "sinks-fluent"))]
 pub(crate) use self::fluent::*;
 #[cfg(feature = "sources-gcp_pubsub")]
 pub(crate) use self::gcp_pubsub::*;
@@ -195,6 +244,8 @@ pub(crate) use self::journald::*;
 pub(crate) use self::kafka::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
+#[cfg(feature = "kubernetes")]
+pub(crate) use self::leader_election::*;
 #[cfg(feature = "transforms-log_to_metric")]
 pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "sources-heroku_logs")]
@@ -205,8 +256,16 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) use self::nats::*;
+#[cfg(feature = "sources-netflow")]
+pub(crate) use self::netflow::*;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "sources-network_flows"
+))]
+pub(crate) use self::network_flows::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 #[cfg(any(
@@ -226,7 +285,9 @@ pub(crate) use self::redis::*;
 pub(crate) use self::reduce::*;
 #[cfg(feature = "transforms-remap")]
 pub(crate) use self::remap::*;
-#[cfg(feature = "transforms-sample")]
+#[cfg(any(feature = "sources-replay", feature = "sinks-record"))]
+pub(crate) use self::replay::*;
+#[cfg(any(feature = "transforms-sample", feature = "transforms-adaptive_sample"))]
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
@@ -242,6 +303,10 @@ pub(crate) use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-top_k")]
+pub(crate) use self::top_k::*;
+#[cfg(feature = "transforms-trace_sampling")]
+pub(crate) use self::trace_sampling::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
@@ -261,9 +326,13 @@ pub(crate) use self::vector::*;
 pub(crate) use self::websocket::*;
 #[cfg(windows)]
 pub(crate) use self::windows::*;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+pub(crate) use self::windows_event_log::*;
 pub(crate) use self::{
-    adaptive_concurrency::*, batch::*, common::*, conditions::*, encoding_transcode::*,
-    heartbeat::*, open::*, process::*, socket::*, tcp::*, template::*, udp::*,
+    adaptive_concurrency::*, audit::*, batch::*, buffer_migration::*, circuit_breaker::*,
+    common::*, conditions::*, encoding_transcode::*, expiration::*, heartbeat::*, lag::*,
+    memory_budget::*, open::*, process::*, sink_degradation::*, socket::*, tcp::*, template::*,
+    udp::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
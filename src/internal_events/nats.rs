@@ -4,12 +4,16 @@ use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
 use super::prelude::{error_stage, error_type, io_error_code};
+#[cfg(feature = "sinks-nats")]
+use crate::nats::JetStreamError;
 
+#[cfg(feature = "sinks-nats")]
 #[derive(Debug)]
 pub struct NatsEventSendError {
     pub error: Error,
 }
 
+#[cfg(feature = "sinks-nats")]
 impl InternalEvent for NatsEventSendError {
     fn emit(self) {
         error!(
@@ -29,3 +33,54 @@ impl InternalEvent for NatsEventSendError {
         counter!("send_errors_total", 1);
     }
 }
+
+#[cfg(feature = "sinks-nats")]
+#[derive(Debug)]
+pub struct NatsJetStreamPublishError<'a> {
+    pub error: &'a JetStreamError,
+}
+
+#[cfg(feature = "sinks-nats")]
+impl<'a> InternalEvent for NatsJetStreamPublishError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed to publish message to JetStream.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        // deprecated
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sources-nats")]
+#[derive(Debug)]
+pub struct NatsAckError {
+    pub error: Error,
+}
+
+#[cfg(feature = "sources-nats")]
+impl InternalEvent for NatsAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge JetStream message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
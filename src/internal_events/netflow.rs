@@ -0,0 +1,76 @@
+use std::{io, net::SocketAddr};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NetflowReceiveError {
+    pub error: io::Error,
+}
+
+impl InternalEvent for NetflowReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving datagram.",
+            error = %self.error,
+            error_code = "receiving_data",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "receiving_data",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowParseError {
+    pub error: String,
+    pub exporter: SocketAddr,
+}
+
+impl InternalEvent for NetflowParseError {
+    fn emit(self) {
+        error!(
+            message = "Invalid flow datagram, discarding.",
+            error = %self.error,
+            exporter = %self.exporter,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowTemplateMissing {
+    pub template_id: u16,
+    pub exporter: SocketAddr,
+}
+
+impl InternalEvent for NetflowTemplateMissing {
+    fn emit(self) {
+        // Until an exporter resends its templates, for example after Vector restarts, its data
+        // can't be decoded. This is expected, so it's not reported as an error.
+        warn!(
+            message = "Received data for an unknown template, discarding.",
+            template_id = %self.template_id,
+            exporter = %self.exporter,
+            internal_log_rate_secs = 30,
+        );
+        counter!(
+            "component_discarded_events_total", 1,
+            "reason" => "missing_template",
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NetworkFlowsReadError {
+    pub error: aya::maps::perf::PerfBufferError,
+}
+
+impl InternalEvent for NetworkFlowsReadError {
+    fn emit(self) {
+        error!(
+            message = "Error reading flow records from the perf buffer.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetworkFlowsRecordsLost {
+    pub count: usize,
+}
+
+impl InternalEvent for NetworkFlowsRecordsLost {
+    fn emit(self) {
+        warn!(
+            message = "Flow records were lost because the perf buffer was full.",
+            count = %self.count,
+            error_code = "perf_buffer_full",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "perf_buffer_full",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        counter!(
+            "component_discarded_events_total", self.count as u64,
+            "error_code" => "perf_buffer_full",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -130,6 +130,33 @@ impl InternalEvent for PrometheusHttpError {
     }
 }
 
+#[cfg(feature = "sources-prometheus")]
+#[derive(Debug)]
+pub struct PrometheusServiceDiscoveryError {
+    pub error: crate::Error,
+    pub mechanism: &'static str,
+}
+
+#[cfg(feature = "sources-prometheus")]
+impl InternalEvent for PrometheusServiceDiscoveryError {
+    fn emit(self) {
+        error!(
+            message = "Service discovery failed; keeping the previously discovered targets.",
+            mechanism = self.mechanism,
+            error = ?self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+            "mechanism" => self.mechanism,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct PrometheusRemoteWriteParseError {
     pub error: prost::DecodeError,
@@ -5,6 +5,8 @@ use super::prelude::{error_stage, error_type};
 
 #[derive(Debug)]
 pub struct RemapMappingError {
+    /// The number of events processed by the failed mapping.
+    pub count: usize,
     /// If set to true, the remap transform has dropped the event after a failed
     /// mapping. This internal event will reflect that in its messaging.
     pub event_dropped: bool,
@@ -33,7 +35,7 @@ impl InternalEvent for RemapMappingError {
         );
         if self.event_dropped {
             counter!(
-                "component_discarded_events_total", 1,
+                "component_discarded_events_total", self.count as u64,
                 "error_type" => error_type::CONVERSION_FAILED,
                 "stage" => error_stage::PROCESSING,
             );
@@ -45,6 +47,8 @@ impl InternalEvent for RemapMappingError {
 
 #[derive(Debug)]
 pub struct RemapMappingAbort {
+    /// The number of events processed by the aborted mapping.
+    pub count: usize,
     /// If set to true, the remap transform has dropped the event after an abort
     /// during mapping. This internal event will reflect that in its messaging.
     pub event_dropped: bool,
@@ -62,7 +66,7 @@ impl InternalEvent for RemapMappingAbort {
 
         if self.event_dropped {
             counter!(
-                "component_discarded_events_total", 1,
+                "component_discarded_events_total", self.count as u64,
                 "error_type" => error_type::CONVERSION_FAILED,
                 "stage" => error_stage::PROCESSING,
             );
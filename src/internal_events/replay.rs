@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[cfg(feature = "sinks-record")]
+#[derive(Debug)]
+pub struct RecordWriteError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+#[cfg(feature = "sinks-record")]
+impl<'a> InternalEvent for RecordWriteError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed writing recording.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "writing_recording",
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::SENDING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "writing_recording",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[cfg(feature = "sources-replay")]
+#[derive(Debug)]
+pub struct ReplayReadError<'a, E> {
+    pub error: E,
+    pub path: &'a Path,
+}
+
+#[cfg(feature = "sources-replay")]
+impl<'a, E: std::fmt::Display> InternalEvent for ReplayReadError<'a, E> {
+    fn emit(self) {
+        error!(
+            message = "Failed reading recording.",
+            error = %self.error,
+            path = ?self.path,
+            error_code = "reading_recording",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "reading_recording",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
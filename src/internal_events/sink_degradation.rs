@@ -0,0 +1,146 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct SinkDegraded {
+    pub policy: &'static str,
+    pub threshold_secs: u64,
+}
+
+impl InternalEvent for SinkDegraded {
+    fn emit(self) {
+        warn!(
+            message = "Sink downstream unavailable; degrading.",
+            policy = %self.policy,
+            threshold_secs = %self.threshold_secs,
+        );
+        gauge!("sink_degraded", 1.0);
+        counter!(
+            "sink_degradation_transitions_total", 1,
+            "state" => "degraded",
+            "policy" => self.policy,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkRecovered {
+    pub policy: &'static str,
+}
+
+impl InternalEvent for SinkRecovered {
+    fn emit(self) {
+        info!(
+            message = "Sink downstream available again; recovered.",
+            policy = %self.policy,
+        );
+        gauge!("sink_degraded", 0.0);
+        counter!(
+            "sink_degradation_transitions_total", 1,
+            "state" => "recovered",
+            "policy" => self.policy,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkDegradationEventsShed {
+    pub count: usize,
+}
+
+impl InternalEvent for SinkDegradationEventsShed {
+    fn emit(self) {
+        debug!(
+            message = "Shed events while the sink downstream is unavailable.",
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!("events_discarded_total", self.count as u64);
+        counter!("sink_shed_events_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkDegradationEventsSpilled {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for SinkDegradationEventsSpilled {
+    fn emit(self) {
+        trace!(
+            message = "Spilled events while the sink downstream is unavailable.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!("sink_spilled_events_total", self.count as u64);
+        counter!("sink_spilled_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkDegradationSpillError {
+    pub error: std::io::Error,
+    pub count: usize,
+}
+
+impl InternalEvent for SinkDegradationSpillError {
+    fn emit(self) {
+        error!(
+            message = "Failed spilling events; dropping them.",
+            error = %self.error,
+            count = %self.count,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        counter!("events_discarded_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkDegradationEventsReplayed {
+    pub count: usize,
+}
+
+impl InternalEvent for SinkDegradationEventsReplayed {
+    fn emit(self) {
+        debug!(
+            message = "Replayed spilled events to the recovered sink.",
+            count = %self.count,
+        );
+        counter!("sink_replayed_events_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SinkDegradationReplayError {
+    pub error: std::io::Error,
+    pub count: usize,
+}
+
+impl InternalEvent for SinkDegradationReplayError {
+    fn emit(self) {
+        error!(
+            message = "Failed replaying spilled events; dropping them.",
+            error = %self.error,
+            count = %self.count,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        counter!("events_discarded_total", self.count as u64);
+    }
+}
@@ -0,0 +1,26 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TopKSeriesRolledUp {
+    pub count: usize,
+}
+
+impl InternalEvent for TopKSeriesRolledUp {
+    fn emit(self) {
+        trace!(
+            message = "Rolled up series beyond the top K.",
+            count = %self.count,
+        );
+        counter!("top_k_rolled_up_series_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct TopKUpdateFailed;
+
+impl InternalEvent for TopKUpdateFailed {
+    fn emit(self) {
+        counter!("top_k_failed_updates", 1);
+    }
+}
@@ -0,0 +1,27 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TraceSamplingTraceDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for TraceSamplingTraceDropped {
+    fn emit(self) {
+        counter!("events_discarded_total", self.count as u64);
+        counter!("traces_dropped_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct TraceSamplingTraceEvicted;
+
+impl InternalEvent for TraceSamplingTraceEvicted {
+    fn emit(self) {
+        debug!(
+            message = "Too many traces awaiting a decision, deciding on the oldest one early.",
+            internal_log_rate_secs = 10
+        );
+        counter!("traces_evicted_total", 1);
+    }
+}
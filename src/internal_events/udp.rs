@@ -1,4 +1,4 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
 use super::prelude::{error_stage, error_type};
@@ -87,3 +87,37 @@ impl InternalEvent for UdpSendIncompleteError {
         counter!("connection_send_errors_total", 1, "mode" => "udp");
     }
 }
+
+#[derive(Debug)]
+pub struct UdpSocketDatagramsDropped {
+    pub count: u64,
+}
+
+impl InternalEvent for UdpSocketDatagramsDropped {
+    fn emit(self) {
+        warn!(
+            message = "Datagrams dropped by the kernel because the receive buffer was full.",
+            count = self.count,
+            internal_log_rate_secs = 10,
+        );
+        counter!("udp_socket_datagrams_dropped_total", self.count);
+    }
+}
+
+#[derive(Debug)]
+pub struct UdpReceiveBufferResized {
+    pub receive_buffer_bytes: usize,
+}
+
+impl InternalEvent for UdpReceiveBufferResized {
+    fn emit(self) {
+        debug!(
+            message = "Resized UDP socket receive buffer.",
+            receive_buffer_bytes = self.receive_buffer_bytes,
+        );
+        gauge!(
+            "udp_socket_receive_buffer_bytes",
+            self.receive_buffer_bytes as f64
+        );
+    }
+}
@@ -1,12 +1,11 @@
 use metrics::counter;
-use prost::DecodeError;
 use vector_core::internal_event::InternalEvent;
 
 use super::prelude::{error_stage, error_type};
 
 #[derive(Debug)]
 pub struct VectorProtoDecodeError<'a> {
-    pub error: &'a DecodeError,
+    pub error: &'a crate::Error,
 }
 
 impl<'a> InternalEvent for VectorProtoDecodeError<'a> {
@@ -28,3 +27,29 @@ impl<'a> InternalEvent for VectorProtoDecodeError<'a> {
         counter!("protobuf_decode_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct VectorStreamEventsReceived<'a> {
+    pub stream_id: &'a str,
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl<'a> InternalEvent for VectorStreamEventsReceived<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Events received from upstream stream.",
+            stream_id = %self.stream_id,
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!(
+            "stream_received_events_total", self.count as u64,
+            "stream_id" => self.stream_id.to_owned(),
+        );
+        counter!(
+            "stream_received_event_bytes_total", self.byte_size as u64,
+            "stream_id" => self.stream_id.to_owned(),
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use std::io;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct WindowsEventLogReadError {
+    pub error: io::Error,
+}
+
+impl InternalEvent for WindowsEventLogReadError {
+    fn emit(self) {
+        error!(
+            message = "Error reading from Windows event log subscription.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogRenderError {
+    pub error: io::Error,
+}
+
+impl InternalEvent for WindowsEventLogRenderError {
+    fn emit(self) {
+        error!(
+            message = "Unable to render Windows event, discarding.",
+            error = %self.error,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogParseError {
+    pub error: String,
+}
+
+impl InternalEvent for WindowsEventLogParseError {
+    fn emit(self) {
+        error!(
+            message = "Invalid Windows event XML, discarding.",
+            error = %self.error,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct WindowsEventLogCheckpointError {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for WindowsEventLogCheckpointError {
+    fn emit(self) {
+        error!(
+            message = "Unable to write checkpoint.",
+            error = %self.error,
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
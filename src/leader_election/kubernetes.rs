@@ -0,0 +1,269 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::{MicroTime, ObjectMeta},
+};
+use kube::{api::PostParams, Api, Client};
+use tokio::time::{interval, Instant, Interval};
+
+use super::SourceFactory;
+use crate::{
+    internal_events::{LeaderElected, LeaderElectionError, LeaderSourceBuildError, LeadershipLost},
+    shutdown::ShutdownSignal,
+    sources::Source,
+};
+
+/// Elects a leader among the replicas by having it hold a Lease.
+pub(super) struct LeaseElector {
+    api: Api<Lease>,
+    lease_name: String,
+    identity: String,
+    lease_duration: Duration,
+    retry_period: Duration,
+}
+
+impl LeaseElector {
+    pub(super) async fn new(
+        lease_name: &str,
+        namespace: Option<&str>,
+        identity: String,
+        lease_duration: Duration,
+        retry_period: Duration,
+    ) -> crate::Result<Self> {
+        let client = Client::try_default().await?;
+        let api = match namespace {
+            Some(namespace) => Api::namespaced(client, namespace),
+            None => Api::default_namespaced(client),
+        };
+        Ok(Self {
+            api,
+            lease_name: lease_name.to_owned(),
+            identity,
+            lease_duration,
+            retry_period,
+        })
+    }
+
+    pub(super) async fn run(
+        self,
+        source: Source,
+        rebuild: SourceFactory,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        tokio::pin!(shutdown);
+        let mut retries = interval(self.retry_period);
+        let mut initial_source = Some(source);
+        loop {
+            loop {
+                tokio::select! {
+                    _ = &mut shutdown => return Ok(()),
+                    _ = retries.tick() => {}
+                }
+                match self.try_acquire_or_renew().await {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(error) => emit!(LeaderElectionError {
+                        error,
+                        lease: &self.lease_name,
+                    }),
+                }
+            }
+
+            let source = match initial_source.take() {
+                Some(source) => source,
+                None => match rebuild().await {
+                    Ok(source) => source,
+                    Err(error) => {
+                        emit!(LeaderSourceBuildError {
+                            error,
+                            lease: &self.lease_name,
+                        });
+                        self.release().await;
+                        return Err(());
+                    }
+                },
+            };
+
+            emit!(LeaderElected {
+                lease: &self.lease_name,
+            });
+
+            match self.lead(source, &mut retries).await {
+                Some(result) => return result,
+                None => emit!(LeadershipLost {
+                    lease: &self.lease_name,
+                }),
+            }
+        }
+    }
+
+    /// Runs the source while renewing the Lease. Returns the result of the source if it stopped on
+    /// its own, or `None` if the leadership was lost, in which case the source is dropped.
+    async fn lead(&self, source: Source, retries: &mut Interval) -> Option<Result<(), ()>> {
+        // Leadership is given up before the Lease expires, to leave time for the source to stop
+        // before another replica takes over.
+        let renew_deadline = self.lease_duration * 2 / 3;
+        let mut last_renewal = Instant::now();
+        tokio::pin!(source);
+        loop {
+            tokio::select! {
+                result = &mut source => {
+                    self.release().await;
+                    return Some(result);
+                }
+                _ = retries.tick() => {
+                    match self.try_acquire_or_renew().await {
+                        Ok(true) => last_renewal = Instant::now(),
+                        Ok(false) => return None,
+                        Err(error) => {
+                            emit!(LeaderElectionError {
+                                error,
+                                lease: &self.lease_name,
+                            });
+                            if last_renewal.elapsed() > renew_deadline {
+                                return None;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Acquires the Lease if it's free or expired, or renews it if this replica already holds it.
+    /// Returns whether this replica holds the Lease.
+    async fn try_acquire_or_renew(&self) -> Result<bool, kube::Error> {
+        let now = Utc::now();
+        let lease_duration_seconds = Some(self.lease_duration.as_secs() as i32);
+
+        let lease = match self.api.get_opt(&self.lease_name).await? {
+            Some(lease) => lease,
+            None => {
+                let lease = Lease {
+                    metadata: ObjectMeta {
+                        name: Some(self.lease_name.clone()),
+                        ..ObjectMeta::default()
+                    },
+                    spec: Some(LeaseSpec {
+                        holder_identity: Some(self.identity.clone()),
+                        lease_duration_seconds,
+                        acquire_time: Some(MicroTime(now)),
+                        renew_time: Some(MicroTime(now)),
+                        lease_transitions: Some(0),
+                    }),
+                };
+                return match self.api.create(&PostParams::default(), &lease).await {
+                    Ok(_) => Ok(true),
+                    // Another replica created it first.
+                    Err(kube::Error::Api(response)) if response.code == 409 => Ok(false),
+                    Err(error) => Err(error),
+                };
+            }
+        };
+
+        let mut spec = lease.spec.unwrap_or_default();
+        let held = spec.holder_identity.as_deref() == Some(self.identity.as_str());
+        if !held && !is_expired(&spec, now) {
+            return Ok(false);
+        }
+        if !held {
+            spec.acquire_time = Some(MicroTime(now));
+            spec.lease_transitions = Some(spec.lease_transitions.unwrap_or(0) + 1);
+        }
+        spec.holder_identity = Some(self.identity.clone());
+        spec.lease_duration_seconds = lease_duration_seconds;
+        spec.renew_time = Some(MicroTime(now));
+
+        // The resource version of the fetched Lease makes the replacement fail if another replica
+        // updated it in the meantime.
+        let lease = Lease {
+            metadata: lease.metadata,
+            spec: Some(spec),
+        };
+        match self
+            .api
+            .replace(&self.lease_name, &PostParams::default(), &lease)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(kube::Error::Api(response)) if response.code == 409 => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Clears the holder of the Lease, so that another replica takes over without waiting for it
+    /// to expire.
+    async fn release(&self) {
+        let result = async {
+            if let Some(mut lease) = self.api.get_opt(&self.lease_name).await? {
+                if let Some(spec) = lease.spec.as_mut() {
+                    if spec.holder_identity.as_deref() == Some(self.identity.as_str()) {
+                        spec.holder_identity = None;
+                        self.api
+                            .replace(&self.lease_name, &PostParams::default(), &lease)
+                            .await?;
+                    }
+                }
+            }
+            Ok::<_, kube::Error>(())
+        }
+        .await;
+
+        if let Err(error) = result {
+            emit!(LeaderElectionError {
+                error,
+                lease: &self.lease_name,
+            });
+        }
+    }
+}
+
+/// Whether the Lease is free to be taken over, because it has no holder or the holder stopped
+/// renewing it.
+fn is_expired(spec: &LeaseSpec, now: DateTime<Utc>) -> bool {
+    match (
+        spec.holder_identity.as_deref(),
+        spec.renew_time.as_ref(),
+        spec.lease_duration_seconds,
+    ) {
+        (Some(holder), Some(renew_time), Some(duration)) if !holder.is_empty() => {
+            renew_time.0 + chrono::Duration::seconds(duration.into()) < now
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn lease_expires_once_not_renewed() {
+        let renew_time = Utc.ymd(2022, 8, 1).and_hms(12, 0, 0);
+        let spec = LeaseSpec {
+            holder_identity: Some("vector-0".to_owned()),
+            lease_duration_seconds: Some(15),
+            renew_time: Some(MicroTime(renew_time)),
+            ..LeaseSpec::default()
+        };
+
+        assert!(!is_expired(
+            &spec,
+            renew_time + chrono::Duration::seconds(10)
+        ));
+        assert!(is_expired(
+            &spec,
+            renew_time + chrono::Duration::seconds(16)
+        ));
+
+        let released = LeaseSpec {
+            holder_identity: None,
+            ..spec
+        };
+        assert!(is_expired(&released, renew_time));
+    }
+}
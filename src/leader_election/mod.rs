@@ -0,0 +1,135 @@
+//! Leader election for sources that must run as singletons.
+//!
+//! Sources such as polling HTTP clients emit duplicated events when every replica of a highly
+//! available deployment runs them. With the `leader_election` option, all replicas build the
+//! source but only the elected leader runs it, while the others wait to take over if the leader
+//! goes away.
+
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::{shutdown::ShutdownSignal, sources::Source};
+
+#[cfg(feature = "kubernetes")]
+mod kubernetes;
+
+/// How the replicas running a source elect their leader.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LeaderElectionConfig {
+    /// A Kubernetes Lease, held by the leader and renewed while it is running.
+    KubernetesLease {
+        /// The name of the Lease, created if it doesn't exist. Every source must use its own.
+        lease_name: String,
+
+        /// The namespace of the Lease. Defaults to the namespace Vector runs in.
+        #[serde(default)]
+        namespace: Option<String>,
+
+        /// The identity of this replica. Defaults to the `VECTOR_SELF_POD_NAME` environment
+        /// variable, or the hostname.
+        #[serde(default)]
+        identity: Option<String>,
+
+        /// How long the other replicas wait after the last renewal before taking over the Lease.
+        #[serde(default = "default_lease_duration_secs")]
+        lease_duration_secs: u64,
+
+        /// How often the Lease is renewed by the leader, and tried by the other replicas.
+        #[serde(default = "default_retry_period_secs")]
+        retry_period_secs: u64,
+    },
+}
+
+/// Builds the source again, each time this replica is elected leader after having lost the
+/// leadership.
+pub type SourceFactory = Box<dyn Fn() -> BoxFuture<'static, crate::Result<Source>> + Send + Sync>;
+
+const fn default_lease_duration_secs() -> u64 {
+    15
+}
+
+const fn default_retry_period_secs() -> u64 {
+    2
+}
+
+impl LeaderElectionConfig {
+    /// Wraps a source so that it only runs once this replica is elected leader.
+    ///
+    /// The source is stopped if the leadership is lost, and this replica goes back to waiting for
+    /// its election. As a stopped source can't be resumed, `rebuild` builds a new one to run once
+    /// it's elected again. The leadership is released when the source stops on its own.
+    pub async fn build(
+        &self,
+        source: Source,
+        rebuild: SourceFactory,
+        shutdown: ShutdownSignal,
+    ) -> crate::Result<Source> {
+        match self {
+            #[cfg(feature = "kubernetes")]
+            Self::KubernetesLease {
+                lease_name,
+                namespace,
+                identity,
+                lease_duration_secs,
+                retry_period_secs,
+            } => {
+                if *retry_period_secs == 0 {
+                    return Err("`retry_period_secs` must be greater than zero".into());
+                }
+                if retry_period_secs >= lease_duration_secs {
+                    return Err(
+                        "`retry_period_secs` must be lower than `lease_duration_secs`".into(),
+                    );
+                }
+                let identity = match identity {
+                    Some(identity) => identity.clone(),
+                    None => {
+                        std::env::var("VECTOR_SELF_POD_NAME").or_else(|_| crate::get_hostname())?
+                    }
+                };
+                let elector = kubernetes::LeaseElector::new(
+                    lease_name,
+                    namespace.as_deref(),
+                    identity,
+                    std::time::Duration::from_secs(*lease_duration_secs),
+                    std::time::Duration::from_secs(*retry_period_secs),
+                )
+                .await?;
+                Ok(Box::pin(elector.run(source, rebuild, shutdown)))
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = (source, rebuild, shutdown);
+                Err("leader election was not compiled into this build of Vector".into())
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "kubernetes"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_zero_retry_period() {
+        let config: LeaderElectionConfig = toml::from_str(
+            r#"
+            type = "kubernetes_lease"
+            lease_name = "vector"
+            retry_period_secs = 0
+            "#,
+        )
+        .unwrap();
+        let source: Source = Box::pin(async { Ok(()) });
+        let rebuild: SourceFactory =
+            Box::new(|| Box::pin(async { Err::<Source, crate::Error>("unused".into()) }));
+
+        let error = config
+            .build(source, rebuild, ShutdownSignal::noop())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "`retry_period_secs` must be greater than zero");
+    }
+}
@@ -34,6 +34,9 @@ pub mod conditions;
 pub mod dns;
 #[cfg(feature = "docker")]
 pub mod docker;
+#[cfg(all(feature = "api-client", feature = "transforms-remap"))]
+#[allow(unreachable_pub)]
+mod dry_run;
 pub mod expiring_hash_map;
 pub mod generate;
 #[macro_use]
@@ -44,11 +47,16 @@ pub mod internal_events;
 pub mod api;
 pub mod app;
 pub mod async_read;
+pub mod audit;
 #[cfg(feature = "aws-config")]
 pub mod aws;
+pub mod bench;
+pub mod buffer_cmd;
+pub mod checkpoint;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub(crate) mod common;
+pub mod convert_config;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
 #[cfg(feature = "gcp")]
@@ -60,13 +68,17 @@ pub mod http;
 pub(crate) mod kafka;
 #[allow(unreachable_pub)]
 pub mod kubernetes;
+pub mod leader_election;
 pub mod line_agg;
 pub mod list;
+pub mod memory_budget;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
 #[cfg(feature = "opentelemetry")]
 #[allow(unreachable_pub)]
 pub mod opentelemetry;
+#[cfg(unix)]
+pub mod privileges;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
@@ -104,6 +116,8 @@ pub(crate) mod utilization;
 pub mod validate;
 #[cfg(windows)]
 pub mod vector_windows;
+#[cfg(feature = "vrl-cli")]
+pub mod vrl_cmd;
 
 pub use source_sender::SourceSender;
 pub use vector_common::shutdown;
@@ -0,0 +1,278 @@
+//! Memory budget shared by the running components.
+//!
+//! Components keeping state that grows with their input, such as the `reduce`, `dedupe` and
+//! `aggregate` transforms, report the estimated size of that state, based on `ByteSizeOf`. When
+//! the total across components reaches the trim threshold of the global `memory_budget` option,
+//! these components trim their state, flushing or evicting the oldest parts of it until the total
+//! is back under the threshold. If the total still exceeds `max_bytes`, sources wait before sending
+//! more events, asking the components to trim their state in the meantime, so that Vector slows
+//! down instead of being killed for running out of memory.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::sync::Notify;
+use vector_core::config::MemoryBudgetConfig;
+
+use crate::{
+    config::ComponentKey,
+    internal_events::{MemoryBudgetExceeded, MemoryBudgetTrimmed},
+};
+
+const INVARIANT: &str = "Couldn't acquire lock on memory budget. Please report this.";
+
+/// How often waiting sources check the usage again, and ask the components to trim their state
+/// again, in case no component released memory while they were waiting.
+const RECHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The budget of the running components, set from the global `memory_budget` option.
+static GLOBAL: Lazy<Arc<Budget>> = Lazy::new(Default::default);
+
+/// A memory budget, shared by the components registered with it.
+#[derive(Debug, Default)]
+pub struct Budget {
+    /// The maximum total usage, in bytes, or zero if there is no budget.
+    max_bytes: AtomicUsize,
+
+    /// The total usage, in bytes, from which components trim their state.
+    trim_threshold_bytes: AtomicUsize,
+
+    /// The total usage of all components, in bytes.
+    total_bytes: AtomicUsize,
+
+    components: RwLock<HashMap<ComponentKey, Arc<AtomicUsize>>>,
+
+    /// Notified when the total usage goes back under the budget.
+    room_available: Notify,
+
+    /// Notified by waiting sources, so that components trim their state even when no events
+    /// reach them, as the sources feeding them are the ones waiting.
+    trim_requested: Notify,
+}
+
+impl Budget {
+    /// Sets the maximum total usage and the trim threshold, in bytes. A maximum of zero disables
+    /// the budget.
+    pub fn set_limits(&self, max_bytes: usize, trim_threshold_bytes: usize) {
+        self.max_bytes.store(max_bytes, Ordering::Relaxed);
+        self.trim_threshold_bytes
+            .store(trim_threshold_bytes, Ordering::Relaxed);
+        self.room_available.notify_waiters();
+    }
+
+    fn is_exceeded(&self) -> bool {
+        let max_bytes = self.max_bytes.load(Ordering::Relaxed);
+        max_bytes != 0 && self.total_bytes.load(Ordering::Relaxed) >= max_bytes
+    }
+
+    fn is_above_trim_threshold(&self) -> bool {
+        self.max_bytes.load(Ordering::Relaxed) != 0
+            && self.total_bytes.load(Ordering::Relaxed)
+                >= self.trim_threshold_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Waits until the total usage of the components is under the budget, asking the components to
+    /// trim their state in the meantime.
+    pub async fn wait_for_room(&self) {
+        if !self.is_exceeded() {
+            return;
+        }
+
+        emit!(MemoryBudgetExceeded {
+            used_bytes: self.total_bytes.load(Ordering::Relaxed),
+            max_bytes: self.max_bytes.load(Ordering::Relaxed),
+        });
+        while self.is_exceeded() {
+            self.trim_requested.notify_waiters();
+            tokio::select! {
+                _ = self.room_available.notified() => {},
+                _ = tokio::time::sleep(RECHECK_INTERVAL) => {},
+            }
+        }
+    }
+}
+
+/// Sets the budget from the global `memory_budget` option. Without one, the usage of components is
+/// still tracked, but never limited.
+pub fn configure(config: Option<&MemoryBudgetConfig>) {
+    let (max_bytes, trim_threshold_bytes) = config.map_or((0, 0), |config| {
+        (config.max_bytes, config.trim_threshold_bytes())
+    });
+    GLOBAL.set_limits(max_bytes, trim_threshold_bytes);
+}
+
+/// Waits until the total usage of the components is under the budget.
+pub async fn wait_for_room() {
+    GLOBAL.wait_for_room().await;
+}
+
+/// The estimated memory usage of a component, counted towards the budget until dropped.
+#[derive(Debug)]
+pub struct MemoryUsage {
+    budget: Arc<Budget>,
+    key: Option<ComponentKey>,
+    bytes: Arc<AtomicUsize>,
+}
+
+impl MemoryUsage {
+    /// Starts tracking the usage of a component. Components built without a key, as in unit tests,
+    /// count towards the budget but aren't reported.
+    pub fn register(key: Option<&ComponentKey>) -> Self {
+        Self::register_with(&GLOBAL, key)
+    }
+
+    /// Starts tracking the usage of a component against the given budget.
+    pub fn register_with(budget: &Arc<Budget>, key: Option<&ComponentKey>) -> Self {
+        let bytes = Arc::new(AtomicUsize::new(0));
+        // A component rebuilt on reload registers before the previous one is dropped, which then
+        // stops being reported, but keeps counting towards the budget until it is dropped.
+        if let Some(key) = key {
+            budget
+                .components
+                .write()
+                .expect(INVARIANT)
+                .insert(key.clone(), Arc::clone(&bytes));
+        }
+        Self {
+            budget: Arc::clone(budget),
+            key: key.cloned(),
+            bytes,
+        }
+    }
+
+    /// Sets the estimated usage of the component, in bytes.
+    pub fn set(&self, bytes: usize) {
+        let previous = self.bytes.swap(bytes, Ordering::Relaxed);
+        if bytes >= previous {
+            self.budget
+                .total_bytes
+                .fetch_add(bytes - previous, Ordering::Relaxed);
+        } else {
+            self.budget
+                .total_bytes
+                .fetch_sub(previous - bytes, Ordering::Relaxed);
+            if !self.budget.is_exceeded() {
+                self.budget.room_available.notify_waiters();
+            }
+        }
+    }
+
+    /// Whether the component should trim its state, because the total usage is at or above the
+    /// trim threshold of the budget.
+    pub fn should_trim(&self) -> bool {
+        self.bytes.load(Ordering::Relaxed) != 0 && self.budget.is_above_trim_threshold()
+    }
+
+    /// Trims the state of the component until the total usage is back under the trim threshold.
+    /// Each call to `trim_step` releases part of the state and returns the usage left, or `None`
+    /// once there is nothing left to release.
+    pub fn trim_with(&self, mut trim_step: impl FnMut() -> Option<usize>) {
+        let previous = self.bytes.load(Ordering::Relaxed);
+        let mut trimmed = false;
+        while self.should_trim() {
+            match trim_step() {
+                Some(bytes) => {
+                    self.set(bytes);
+                    trimmed = true;
+                }
+                None => break,
+            }
+        }
+        if trimmed {
+            emit!(MemoryBudgetTrimmed {
+                released_bytes: previous.saturating_sub(self.bytes.load(Ordering::Relaxed)),
+            });
+        }
+    }
+
+    /// Sets the usage of the component after it released all of its state at once.
+    pub fn trimmed(&self, bytes: usize) {
+        let previous = self.bytes.load(Ordering::Relaxed);
+        emit!(MemoryBudgetTrimmed {
+            released_bytes: previous.saturating_sub(bytes),
+        });
+        self.set(bytes);
+    }
+
+    /// A signal telling the component to trim its state, which it should wait for along with its
+    /// input, as the sources feeding it may be waiting for it to release memory.
+    pub fn trim_signal(&self) -> TrimSignal {
+        TrimSignal(Arc::clone(&self.budget))
+    }
+}
+
+impl Drop for MemoryUsage {
+    fn drop(&mut self) {
+        self.set(0);
+        if let Some(key) = &self.key {
+            let mut components = self.budget.components.write().expect(INVARIANT);
+            if components
+                .get(key)
+                .map_or(false, |bytes| Arc::ptr_eq(bytes, &self.bytes))
+            {
+                components.remove(key);
+            }
+        }
+    }
+}
+
+/// Resolves when sources waiting for room in the budget ask the components to trim their state.
+#[derive(Debug)]
+pub struct TrimSignal(Arc<Budget>);
+
+impl TrimSignal {
+    pub async fn requested(&self) {
+        self.0.trim_requested.notified().await;
+    }
+}
+
+/// The maximum total usage, if there is a budget.
+pub fn max_bytes() -> Option<usize> {
+    Some(GLOBAL.max_bytes.load(Ordering::Relaxed)).filter(|max_bytes| *max_bytes != 0)
+}
+
+/// The total usage of the components.
+pub fn used_bytes() -> usize {
+    GLOBAL.total_bytes.load(Ordering::Relaxed)
+}
+
+/// The usage of each component.
+pub fn component_usage() -> Vec<(ComponentKey, usize)> {
+    GLOBAL
+        .components
+        .read()
+        .expect(INVARIANT)
+        .iter()
+        .map(|(key, bytes)| (key.clone(), bytes.load(Ordering::Relaxed)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_usage_of_components() {
+        let key = ComponentKey::from("memory_budget_test");
+        let usage = MemoryUsage::register(Some(&key));
+        usage.set(1024);
+        assert_eq!(
+            component_usage()
+                .into_iter()
+                .find(|(component, _)| *component == key),
+            Some((key.clone(), 1024))
+        );
+
+        drop(usage);
+        assert!(component_usage()
+            .into_iter()
+            .all(|(component, _)| component != key));
+    }
+}
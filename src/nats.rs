@@ -1,4 +1,5 @@
 use nkeys::error::Error as NKeysError;
+use serde::{de::DeserializeOwned, Deserialize};
 use snafu::{ResultExt, Snafu};
 use vector_config::configurable_component;
 
@@ -14,6 +15,46 @@ pub enum NatsConfigError {
     TlsMissingCert,
 }
 
+#[derive(Debug, Snafu)]
+pub enum JetStreamError {
+    #[snafu(display("JetStream request failed: {}", source))]
+    Request { source: std::io::Error },
+    #[snafu(display("JetStream request timed out"))]
+    Timeout,
+    #[snafu(display("Invalid JetStream response: {}", source))]
+    Response { source: serde_json::Error },
+    #[snafu(display("JetStream API error {}: {}", code, description))]
+    Api { code: u16, description: String },
+}
+
+/// The error returned by the JetStream API in place of a response.
+#[derive(Debug, Deserialize)]
+struct JetStreamApiError {
+    code: u16,
+    #[serde(default)]
+    description: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JetStreamResponse<T> {
+    Error { error: JetStreamApiError },
+    Ok(T),
+}
+
+/// Parses a response of the JetStream API, which is JSON holding either the response or an error.
+pub(crate) fn parse_jetstream_response<T: DeserializeOwned>(
+    data: &[u8],
+) -> Result<T, JetStreamError> {
+    match serde_json::from_slice(data).context(ResponseSnafu)? {
+        JetStreamResponse::Error { error } => Err(JetStreamError::Api {
+            code: error.code,
+            description: error.description,
+        }),
+        JetStreamResponse::Ok(response) => Ok(response),
+    }
+}
+
 /// Configuration of the authentication strategy when interacting with NATS.
 #[configurable_component]
 #[derive(Clone, Debug)]
@@ -176,6 +217,38 @@ pub(crate) fn from_tls_auth_config(
 mod tests {
     use super::*;
 
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct PubAck {
+        stream: String,
+        seq: u64,
+    }
+
+    #[test]
+    fn parses_jetstream_responses() {
+        assert_eq!(
+            parse_jetstream_response::<PubAck>(br#"{"stream":"logs","seq":42}"#).unwrap(),
+            PubAck {
+                stream: "logs".into(),
+                seq: 42
+            }
+        );
+
+        match parse_jetstream_response::<PubAck>(
+            br#"{"error":{"code":503,"description":"no responders available for request"}}"#,
+        ) {
+            Err(JetStreamError::Api { code, description }) => {
+                assert_eq!(code, 503);
+                assert_eq!(description, "no responders available for request");
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        assert!(matches!(
+            parse_jetstream_response::<PubAck>(b"+OK"),
+            Err(JetStreamError::Response { .. })
+        ));
+    }
+
     fn parse_auth(s: &str) -> Result<nats::asynk::Options, crate::Error> {
         toml::from_str(s)
             .map_err(Into::into)
@@ -0,0 +1,228 @@
+//! Dropping the privileges Vector was started with.
+//!
+//! Vector can be started as root so that its sources are able to listen on privileged ports and
+//! open protected files, and then switch to the identity configured with the global `run_as`
+//! option once the topology is running. Anything opened afterwards, such as the sockets of sources
+//! added by a reload or files that rotate into place, is opened as that identity.
+
+use std::path::PathBuf;
+
+use nix::unistd::{self, Gid, Group, Uid, User};
+use snafu::{ResultExt, Snafu};
+use vector_core::config::RunAsConfig;
+
+/// The filter of a seccomp profile that is applied to Vector.
+#[cfg(target_os = "linux")]
+const SECCOMP_FILTER: &str = "vector";
+
+#[derive(Debug, Snafu)]
+pub enum PrivilegesError {
+    #[snafu(display("Unknown user {:?}", user))]
+    UnknownUser { user: String },
+    #[snafu(display("Unknown group {:?}", group))]
+    UnknownGroup { group: String },
+    #[snafu(display("Failed looking up {:?}: {}", name, source))]
+    Lookup { name: String, source: nix::Error },
+    #[snafu(display("Vector must be started as root to switch to {:?}", name))]
+    NotRoot { name: String },
+    #[snafu(display("Failed switching to group {}: {}", gid, source))]
+    SetGroup { gid: Gid, source: nix::Error },
+    #[snafu(display("Failed switching to user {:?}: {}", user, source))]
+    SetUser { user: String, source: nix::Error },
+    #[snafu(display("Root privileges could be regained after switching to user {:?}", user))]
+    RegainedRoot { user: String },
+    #[snafu(display("Failed reading seccomp profile {:?}: {}", path, source))]
+    ReadSeccompProfile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid seccomp profile {:?}: {}", path, message))]
+    InvalidSeccompProfile { path: PathBuf, message: String },
+    #[snafu(display("Failed applying seccomp profile: {}", message))]
+    ApplySeccompProfile { message: String },
+    #[snafu(display("Seccomp profiles are only supported on Linux"))]
+    SeccompUnsupported,
+}
+
+/// Switches the process to the user and group of `config`, then applies its seccomp profile.
+///
+/// The seccomp profile is read before switching user, so it may live in a file only root can
+/// read. Switching user also clears the supplementary groups inherited from the parent process.
+pub fn drop_privileges(config: &RunAsConfig) -> Result<(), PrivilegesError> {
+    #[cfg(target_os = "linux")]
+    let seccomp_filter = config
+        .seccomp_profile
+        .as_deref()
+        .map(load_seccomp_profile)
+        .transpose()?;
+    #[cfg(not(target_os = "linux"))]
+    {
+        if config.seccomp_profile.is_some() {
+            return Err(PrivilegesError::SeccompUnsupported);
+        }
+    }
+
+    let user = config.user.as_deref().map(resolve_user).transpose()?;
+    let gid = match (&config.group, &user) {
+        (Some(group), _) => Some(resolve_group(group)?),
+        (None, Some(user)) => Some(user.gid),
+        (None, None) => None,
+    };
+
+    if Uid::effective().is_root() {
+        if let Some(gid) = gid {
+            set_supplementary_groups(gid).context(SetGroupSnafu { gid })?;
+            unistd::setgid(gid).context(SetGroupSnafu { gid })?;
+        }
+
+        if let Some(user) = &user {
+            unistd::setuid(user.uid).context(SetUserSnafu { user: &user.name })?;
+            if !user.uid.is_root() && unistd::setuid(Uid::from_raw(0)).is_ok() {
+                return Err(PrivilegesError::RegainedRoot {
+                    user: user.name.clone(),
+                });
+            }
+        }
+    } else {
+        // Without root the identity can't change, but it may already be the configured one.
+        let unchanged = gid.map_or(true, |gid| gid == Gid::effective())
+            && user
+                .as_ref()
+                .map_or(true, |user| user.uid == Uid::effective());
+        if !unchanged {
+            return Err(PrivilegesError::NotRoot {
+                name: config
+                    .user
+                    .clone()
+                    .or_else(|| config.group.clone())
+                    .unwrap_or_default(),
+            });
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(filter) = seccomp_filter {
+            seccompiler::apply_filter_all_threads(&filter).map_err(|error| {
+                PrivilegesError::ApplySeccompProfile {
+                    message: error.to_string(),
+                }
+            })?;
+        }
+    }
+
+    info!(
+        message = "Dropped privileges.",
+        uid = %Uid::effective(),
+        gid = %Gid::effective(),
+        seccomp = config.seccomp_profile.is_some(),
+    );
+    Ok(())
+}
+
+/// Looks up a user by name, falling back to a numeric user ID.
+fn resolve_user(user: &str) -> Result<User, PrivilegesError> {
+    let found = match user.parse::<u32>() {
+        Ok(uid) => User::from_uid(Uid::from_raw(uid)),
+        Err(_) => User::from_name(user),
+    }
+    .context(LookupSnafu { name: user })?;
+
+    found.ok_or_else(|| PrivilegesError::UnknownUser {
+        user: user.to_owned(),
+    })
+}
+
+/// Looks up a group by name, falling back to a numeric group ID.
+fn resolve_group(group: &str) -> Result<Gid, PrivilegesError> {
+    if let Ok(gid) = group.parse::<u32>() {
+        return Ok(Gid::from_raw(gid));
+    }
+
+    Group::from_name(group)
+        .context(LookupSnafu { name: group })?
+        .map(|group| group.gid)
+        .ok_or_else(|| PrivilegesError::UnknownGroup {
+            group: group.to_owned(),
+        })
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+fn set_supplementary_groups(gid: Gid) -> nix::Result<()> {
+    unistd::setgroups(&[gid])
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn set_supplementary_groups(gid: Gid) -> nix::Result<()> {
+    let groups = [gid.as_raw()];
+    // SAFETY: `groups` outlives the call and holds exactly the one group passed as its length.
+    let result = unsafe { libc::setgroups(1, groups.as_ptr()) };
+    nix::errno::Errno::result(result).map(drop)
+}
+
+#[cfg(target_os = "linux")]
+fn load_seccomp_profile(
+    path: &std::path::Path,
+) -> Result<seccompiler::BpfProgram, PrivilegesError> {
+    let invalid = |message: String| PrivilegesError::InvalidSeccompProfile {
+        path: path.to_owned(),
+        message,
+    };
+
+    let profile = std::fs::File::open(path).context(ReadSeccompProfileSnafu { path })?;
+    let arch: Result<seccompiler::TargetArch, _> = std::env::consts::ARCH.try_into();
+    let arch = arch.map_err(|error| invalid(error.to_string()))?;
+    let mut filters = seccompiler::compile_from_json(profile, arch)
+        .map_err(|error| invalid(error.to_string()))?;
+
+    filters
+        .remove(SECCOMP_FILTER)
+        .ok_or_else(|| invalid(format!("no filter named {:?}", SECCOMP_FILTER)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_user_by_name_and_id() {
+        assert_eq!(resolve_user("root").unwrap().uid, Uid::from_raw(0));
+        assert_eq!(resolve_user("0").unwrap().name, "root");
+        assert!(matches!(
+            resolve_user("vector-no-such-user"),
+            Err(PrivilegesError::UnknownUser { .. })
+        ));
+    }
+
+    #[test]
+    fn resolves_group_by_id() {
+        assert_eq!(resolve_group("1234").unwrap(), Gid::from_raw(1234));
+        assert!(matches!(
+            resolve_group("vector-no-such-group"),
+            Err(PrivilegesError::UnknownGroup { .. })
+        ));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn loads_vector_seccomp_filter() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profile.json");
+        std::fs::write(
+            &path,
+            r#"{"vector": {"mismatch_action": "allow", "match_action": "trap", "filter": [{"syscall": "reboot"}]}}"#,
+        )
+        .unwrap();
+        assert!(load_seccomp_profile(&path).is_ok());
+
+        std::fs::write(
+            &path,
+            r#"{"other": {"mismatch_action": "allow", "match_action": "trap", "filter": [{"syscall": "reboot"}]}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            load_seccomp_profile(&path),
+            Err(PrivilegesError::InvalidSeccompProfile { .. })
+        ));
+    }
+}
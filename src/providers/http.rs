@@ -3,6 +3,10 @@ use bytes::Buf;
 use futures::Stream;
 use hyper::Body;
 use indexmap::IndexMap;
+use openssl::{
+    pkey::{PKey, Public},
+    sign::Verifier,
+};
 use serde::{Deserialize, Serialize};
 use tokio::time;
 use url::Url;
@@ -33,12 +37,29 @@ impl Default for RequestConfig {
     }
 }
 
+/// Verification of the configuration against a detached signature.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SignatureConfig {
+    /// The ed25519 public key the configuration must be signed with, in PEM format.
+    public_key: String,
+
+    /// The URL of the base64-encoded ed25519 signature of the configuration. Defaults to the URL of
+    /// the configuration with `.sig` appended to its path.
+    #[serde(default)]
+    url: Option<Url>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
 pub(crate) struct HttpConfig {
     url: Option<Url>,
     request: RequestConfig,
     poll_interval_secs: u64,
+    signature: Option<SignatureConfig>,
+    /// Whether a new configuration fetched while polling is rolled back when the healthchecks of
+    /// its components fail, rather than only when they fail to build.
+    rollback_on_unhealthy: bool,
     #[serde(flatten)]
     tls_options: Option<TlsConfig>,
     #[serde(
@@ -54,12 +75,53 @@ impl Default for HttpConfig {
             url: None,
             request: RequestConfig::default(),
             poll_interval_secs: 30,
+            signature: None,
+            rollback_on_unhealthy: true,
             tls_options: None,
             proxy: Default::default(),
         }
     }
 }
 
+/// Verifies the configuration against its detached signature.
+#[derive(Clone)]
+struct SignatureVerifier {
+    public_key: PKey<Public>,
+    url: Url,
+}
+
+impl SignatureVerifier {
+    fn new(config: &SignatureConfig, config_url: &Url) -> std::result::Result<Self, String> {
+        let public_key = PKey::public_key_from_pem(config.public_key.as_bytes())
+            .map_err(|error| format!("Invalid signature public key: {}", error))?;
+        let url = config.url.clone().unwrap_or_else(|| {
+            let mut url = config_url.clone();
+            url.set_path(&format!("{}.sig", config_url.path()));
+            url
+        });
+        Ok(Self { public_key, url })
+    }
+
+    fn verify(&self, config: &[u8], signature: &[u8]) -> std::result::Result<(), &'static str> {
+        let signature = std::str::from_utf8(signature)
+            .ok()
+            .and_then(|signature| openssl::base64::decode_block(signature.trim()).ok())
+            .ok_or("Invalid configuration signature encoding")?;
+        let verified = Verifier::new_without_digest(&self.public_key)
+            .and_then(|mut verifier| verifier.verify_oneshot(&signature, config))
+            .unwrap_or(false);
+        if verified {
+            Ok(())
+        } else {
+            error!(
+                message = "Configuration signature verification failed.",
+                url = ?self.url.as_str()
+            );
+            Err("Configuration signature verification failed")
+        }
+    }
+}
+
 /// Makes an HTTP request to the provided endpoint, returning the String body.
 async fn http_request(
     url: &Url,
@@ -113,19 +175,28 @@ async fn http_request(
         })
 }
 
-/// Calls `http_request`, serializing the result to a `ConfigBuilder`.
-async fn http_request_to_config_builder(
+/// Calls `http_request`, verifying the result against its signature if one is required.
+async fn fetch_config(
     url: &Url,
     tls_options: &Option<TlsConfig>,
     headers: &IndexMap<String, String>,
     proxy: &ProxyConfig,
-) -> Result {
-    let config_str = http_request(url, tls_options, headers, proxy)
-        .await
-        .map_err(|e| vec![e.to_owned()])?;
+    signature: &Option<SignatureVerifier>,
+) -> std::result::Result<bytes::Bytes, &'static str> {
+    let config = http_request(url, tls_options, headers, proxy).await?;
+
+    if let Some(verifier) = signature {
+        let signature = http_request(&verifier.url, tls_options, headers, proxy).await?;
+        verifier.verify(&config, &signature)?;
+    }
+
+    Ok(config)
+}
 
+/// Deserializes the configuration to a `ConfigBuilder`.
+fn load_config_builder(config: &bytes::Bytes) -> Result {
     let (config_builder, warnings) =
-        config::load(config_str.chunk(), crate::config::format::Format::Toml)?;
+        config::load(config.chunk(), crate::config::format::Format::Toml)?;
 
     for warning in warnings.into_iter() {
         warn!("{}", warning);
@@ -135,12 +206,19 @@ async fn http_request_to_config_builder(
 }
 
 /// Polls the HTTP endpoint after/every `poll_interval_secs`, returning a stream of `ConfigBuilder`.
+///
+/// Only configurations differing from the last one fetched are returned, so that a configuration
+/// rolled back because it failed to load isn't retried until the endpoint serves a new one.
+#[allow(clippy::too_many_arguments)]
 fn poll_http(
     poll_interval_secs: u64,
     url: Url,
     tls_options: Option<TlsConfig>,
     headers: IndexMap<String, String>,
     proxy: ProxyConfig,
+    signature: Option<SignatureVerifier>,
+    rollback_on_unhealthy: bool,
+    mut last_config: bytes::Bytes,
 ) -> impl Stream<Item = signal::SignalTo> {
     let duration = time::Duration::from_secs(poll_interval_secs);
     let mut interval = time::interval_at(time::Instant::now() + duration, duration);
@@ -149,8 +227,21 @@ fn poll_http(
         loop {
             interval.tick().await;
 
-            match http_request_to_config_builder(&url, &tls_options, &headers, &proxy).await {
-                Ok(config_builder) => yield signal::SignalTo::ReloadFromConfigBuilder(config_builder),
+            match fetch_config(&url, &tls_options, &headers, &proxy, &signature).await {
+                Ok(config) if config == last_config => {
+                    debug!(message = "Configuration unchanged.", url = ?url.as_str());
+                }
+                Ok(config) => {
+                    last_config = config;
+                    if let Ok(mut config_builder) = load_config_builder(&last_config) {
+                        // Requiring healthy sinks makes the topology restore the previous
+                        // configuration when healthchecks fail.
+                        if rollback_on_unhealthy {
+                            config_builder.healthchecks.require_healthy = true;
+                        }
+                        yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                    }
+                }
                 Err(_) => {},
             };
 
@@ -174,10 +265,18 @@ impl ProviderConfig for HttpConfig {
         let tls_options = self.tls_options.take();
         let poll_interval_secs = self.poll_interval_secs;
         let request = self.request.clone();
+        let signature = self
+            .signature
+            .as_ref()
+            .map(|signature| SignatureVerifier::new(signature, &url))
+            .transpose()
+            .map_err(|error| vec![error])?;
 
         let proxy = ProxyConfig::from_env().merge(&self.proxy);
-        let config_builder =
-            http_request_to_config_builder(&url, &tls_options, &request.headers, &proxy).await?;
+        let config = fetch_config(&url, &tls_options, &request.headers, &proxy, &signature)
+            .await
+            .map_err(|e| vec![e.to_owned()])?;
+        let config_builder = load_config_builder(&config)?;
 
         // Poll for changes to remote configuration.
         signal_handler.add(poll_http(
@@ -186,6 +285,9 @@ impl ProviderConfig for HttpConfig {
             tls_options,
             request.headers.clone(),
             proxy.clone(),
+            signature,
+            self.rollback_on_unhealthy,
+            config,
         ));
 
         Ok(config_builder)
@@ -201,3 +303,35 @@ inventory::submit! {
 }
 
 impl_generate_config_from_default!(HttpConfig);
+
+#[cfg(test)]
+mod tests {
+    use openssl::{pkey::PKey, sign::Signer};
+
+    use super::*;
+
+    #[test]
+    fn verifies_signatures() {
+        let key = PKey::generate_ed25519().unwrap();
+        let config = SignatureConfig {
+            public_key: String::from_utf8(key.public_key_to_pem().unwrap()).unwrap(),
+            url: None,
+        };
+        let url = Url::parse("https://example.com/vector.toml").unwrap();
+        let verifier = SignatureVerifier::new(&config, &url).unwrap();
+        assert_eq!(verifier.url.as_str(), "https://example.com/vector.toml.sig");
+
+        let payload = b"[sources.in]\ntype = \"stdin\"\n";
+        let signature = Signer::new_without_digest(&key)
+            .unwrap()
+            .sign_oneshot_to_vec(payload)
+            .unwrap();
+        let signature = openssl::base64::encode_block(&signature);
+
+        assert!(verifier.verify(payload, signature.as_bytes()).is_ok());
+        assert!(verifier
+            .verify(b"[sources.in]\ntype = \"file\"\n", signature.as_bytes())
+            .is_err());
+        assert!(verifier.verify(payload, b"not a signature").is_err());
+    }
+}
@@ -0,0 +1,339 @@
+//! A provider assembling the configuration from Kubernetes resources.
+//!
+//! Each team owning part of the topology can manage it as its own resource, for instance from a
+//! GitOps repository, and the provider reloads Vector whenever any of those resources changes. The
+//! resources are either ConfigMaps, of which every key named like a configuration file (ending in
+//! `.toml`, `.yaml` or `.json`) is loaded, or `VectorPipeline` custom resources
+//! (`vectorpipelines.vector.dev`, version `v1alpha1`), whose `spec` is the configuration itself:
+//!
+//! ```yaml
+//! apiVersion: vector.dev/v1alpha1
+//! kind: VectorPipeline
+//! metadata:
+//!   name: payments
+//!   labels:
+//!     vector.dev/pipeline: "true"
+//! spec:
+//!   sources:
+//!     payments_logs:
+//!       type: kubernetes_logs
+//!       extra_label_selector: app=payments
+//!   sinks:
+//!     payments_archive:
+//!       type: aws_s3
+//!       inputs: [payments_logs]
+//! ```
+//!
+//! The configurations of all the matching resources are merged together, the same way as multiple
+//! configuration files are, so components with the same name in two resources are an error. When
+//! the configuration can't be assembled, the running one is kept.
+
+use async_stream::stream;
+use futures::{stream::BoxStream, Stream, StreamExt};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{ApiResource, DynamicObject, GroupVersionKind, ListParams, ObjectMeta},
+    runtime::watcher,
+    Api, Client,
+};
+use serde::{Deserialize, Serialize};
+use tokio::time;
+
+use super::Result;
+use crate::{
+    config::{
+        self,
+        format::Format,
+        provider::{ProviderConfig, ProviderDescription},
+        ConfigBuilder,
+    },
+    signal,
+};
+
+/// How long to wait before watching the resources again after the watch failed.
+const WATCH_RETRY_DELAY: time::Duration = time::Duration::from_secs(5);
+
+/// The kind of resources the configuration is read from.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    /// ConfigMaps, with one configuration file per key.
+    ConfigMap,
+
+    /// `VectorPipeline` custom resources, with the configuration as their `spec`.
+    VectorPipeline,
+}
+
+impl Default for ResourceKind {
+    fn default() -> Self {
+        Self::ConfigMap
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields, default)]
+pub(crate) struct KubernetesConfig {
+    /// The kind of resources the configuration is read from.
+    resource: ResourceKind,
+    /// The namespace the resources are read from. Defaults to all namespaces, which requires
+    /// Vector to be allowed to list and watch the resources cluster-wide.
+    namespace: Option<String>,
+    /// The label selector the resources must match, such as `vector.dev/pipeline=true`.
+    label_selector: Option<String>,
+}
+
+/// The resources the configuration is assembled from.
+#[derive(Clone)]
+enum Resources {
+    ConfigMaps(Api<ConfigMap>),
+    Pipelines(Api<DynamicObject>),
+}
+
+/// The content of the resources a configuration was assembled from, to tell whether it changed.
+type Fingerprint = Vec<(String, String)>;
+
+impl Resources {
+    async fn new(config: &KubernetesConfig) -> std::result::Result<Self, String> {
+        let client = Client::try_default()
+            .await
+            .map_err(|error| format!("Couldn't create Kubernetes client: {}", error))?;
+        let namespace = config.namespace.as_deref();
+        Ok(match config.resource {
+            ResourceKind::ConfigMap => Self::ConfigMaps(match namespace {
+                Some(namespace) => Api::namespaced(client, namespace),
+                None => Api::all(client),
+            }),
+            ResourceKind::VectorPipeline => {
+                let resource = ApiResource::from_gvk(&GroupVersionKind::gvk(
+                    "vector.dev",
+                    "v1alpha1",
+                    "VectorPipeline",
+                ));
+                Self::Pipelines(match namespace {
+                    Some(namespace) => Api::namespaced_with(client, namespace, &resource),
+                    None => Api::all_with(client, &resource),
+                })
+            }
+        })
+    }
+
+    /// Lists the resources, returning the configuration files they contain, named after the
+    /// resource and key they come from, in a stable order.
+    async fn files(
+        &self,
+        params: &ListParams,
+    ) -> std::result::Result<Vec<(String, String, Format)>, String> {
+        let list_error = |error| format!("Couldn't list Kubernetes resources: {}", error);
+        let mut files = Vec::new();
+        match self {
+            Self::ConfigMaps(api) => {
+                for config_map in api.list(params).await.map_err(list_error)? {
+                    let name = resource_name("ConfigMap", &config_map.metadata);
+                    for (key, data) in config_map.data.unwrap_or_default() {
+                        // Keys that aren't configuration files, such as scripts or enrichment
+                        // tables mounted next to them, are left alone.
+                        if let Ok(format) = Format::from_path(&key) {
+                            files.push((format!("{}, key {}", name, key), data, format));
+                        }
+                    }
+                }
+            }
+            Self::Pipelines(api) => {
+                for pipeline in api.list(params).await.map_err(list_error)? {
+                    let name = resource_name("VectorPipeline", &pipeline.metadata);
+                    let spec = pipeline.data.get("spec").cloned().unwrap_or_default();
+                    files.push((name, spec.to_string(), Format::Json));
+                }
+            }
+        }
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(files)
+    }
+
+    /// Watches the resources, returning a stream yielding whenever any of them may have changed.
+    fn changes(&self, params: ListParams) -> BoxStream<'static, ()> {
+        fn ignore_errors<T, E: std::fmt::Display>(
+            events: impl Stream<Item = std::result::Result<T, E>> + Send + 'static,
+        ) -> BoxStream<'static, ()> {
+            events
+                .then(|event| async move {
+                    if let Err(error) = event {
+                        warn!(
+                            message = "Failed to watch Kubernetes resources, retrying.",
+                            %error,
+                        );
+                        time::sleep(WATCH_RETRY_DELAY).await;
+                    }
+                })
+                .boxed()
+        }
+
+        match self.clone() {
+            Self::ConfigMaps(api) => ignore_errors(watcher(api, params)),
+            Self::Pipelines(api) => ignore_errors(watcher(api, params)),
+        }
+    }
+}
+
+fn resource_name(kind: &str, metadata: &ObjectMeta) -> String {
+    format!(
+        "{} {}/{}",
+        kind,
+        metadata.namespace.as_deref().unwrap_or_default(),
+        metadata.name.as_deref().unwrap_or_default()
+    )
+}
+
+/// Merges the configuration files of the resources into a single `ConfigBuilder`.
+fn assemble(files: &[(String, String, Format)]) -> Result {
+    let mut builder = ConfigBuilder::default();
+    let mut errors = Vec::new();
+
+    for (name, data, format) in files {
+        let with_name =
+            |messages: Vec<String>| messages.into_iter().map(move |m| format!("{}: {}", name, m));
+        match config::load::<_, ConfigBuilder>(data.as_bytes(), *format) {
+            Ok((fragment, warnings)) => {
+                for warning in with_name(warnings) {
+                    warn!("{}", warning);
+                }
+                if let Err(append_errors) = builder.append(fragment) {
+                    errors.extend(with_name(append_errors));
+                }
+            }
+            Err(load_errors) => errors.extend(with_name(load_errors)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(builder)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Loads the configuration from the resources, also returning their fingerprint.
+async fn load(
+    resources: &Resources,
+    params: &ListParams,
+) -> std::result::Result<(ConfigBuilder, Fingerprint), Vec<String>> {
+    let files = resources.files(params).await.map_err(|error| vec![error])?;
+    let builder = assemble(&files)?;
+    let fingerprint = files
+        .into_iter()
+        .map(|(name, data, _)| (name, data))
+        .collect();
+    Ok((builder, fingerprint))
+}
+
+/// Watches the resources, returning a stream of `ConfigBuilder` assembled from them.
+///
+/// Only configurations differing from the last one assembled are returned, so that unrelated
+/// changes to the resources, such as to their status, don't reload the topology.
+fn watch_resources(
+    resources: Resources,
+    params: ListParams,
+    mut last_fingerprint: Fingerprint,
+) -> impl Stream<Item = signal::SignalTo> {
+    stream! {
+        let mut changes = resources.changes(params.clone());
+
+        while changes.next().await.is_some() {
+            match load(&resources, &params).await {
+                Ok((_, fingerprint)) if fingerprint == last_fingerprint => {
+                    debug!(message = "Configuration unchanged.");
+                }
+                Ok((config_builder, fingerprint)) => {
+                    info!(message = "Kubernetes resources changed, reloading configuration.");
+                    last_fingerprint = fingerprint;
+                    yield signal::SignalTo::ReloadFromConfigBuilder(config_builder);
+                }
+                Err(errors) => {
+                    for error in errors {
+                        error!(
+                            message = "Failed to assemble configuration from Kubernetes resources.",
+                            %error,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "kubernetes")]
+impl ProviderConfig for KubernetesConfig {
+    async fn build(&mut self, signal_handler: &mut signal::SignalHandler) -> Result {
+        let resources = Resources::new(self).await.map_err(|error| vec![error])?;
+        let params = ListParams {
+            label_selector: self.label_selector.clone(),
+            ..Default::default()
+        };
+
+        let (config_builder, fingerprint) = load(&resources, &params).await?;
+
+        // Reload whenever the resources change.
+        signal_handler.add(watch_resources(resources, params, fingerprint));
+
+        Ok(config_builder)
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "kubernetes"
+    }
+}
+
+inventory::submit! {
+    ProviderDescription::new::<KubernetesConfig>("kubernetes")
+}
+
+impl_generate_config_from_default!(KubernetesConfig);
+
+#[cfg(all(test, feature = "sources-stdin"))]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, data: &str, format: Format) -> (String, String, Format) {
+        (name.to_owned(), data.to_owned(), format)
+    }
+
+    #[test]
+    fn assembles_resources() {
+        let builder = assemble(&[
+            file(
+                "ConfigMap a/team-a, key vector.toml",
+                "[sources.a]\ntype = \"stdin\"\n",
+                Format::Toml,
+            ),
+            file(
+                "VectorPipeline b/team-b",
+                r#"{"sources":{"b":{"type":"stdin"}}}"#,
+                Format::Json,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(builder.sources.len(), 2);
+    }
+
+    #[test]
+    fn rejects_duplicate_components() {
+        let errors = assemble(&[
+            file(
+                "ConfigMap a/team-a, key vector.toml",
+                "[sources.in]\ntype = \"stdin\"\n",
+                Format::Toml,
+            ),
+            file(
+                "ConfigMap b/team-b, key vector.toml",
+                "[sources.in]\ntype = \"stdin\"\n",
+                Format::Toml,
+            ),
+        ])
+        .unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("ConfigMap b/team-b, key vector.toml: "));
+    }
+}
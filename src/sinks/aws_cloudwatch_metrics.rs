@@ -15,9 +15,10 @@ use vector_core::ByteSizeOf;
 
 use super::util::SinkBatchSettings;
 use crate::aws::RegionOrEndpoint;
-use crate::aws::{create_client, is_retriable_error, ClientBuilder};
+use crate::aws::{create_client, is_retriable_error};
 use crate::{
     aws::auth::AwsAuthentication,
+    common::cloudwatch::CloudwatchMetricsClientBuilder,
     config::{
         AcknowledgementsConfig, Input, ProxyConfig, SinkConfig, SinkContext, SinkDescription,
     },
@@ -29,7 +30,8 @@ use crate::{
         batch::BatchConfig,
         buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
         retries::RetryLogic,
-        Compression, EncodedEvent, PartitionBuffer, PartitionInnerBuffer, TowerRequestConfig,
+        Compression, EncodedEvent, PartitionBuffer, PartitionInnerBuffer, TagRule, TagRules,
+        TowerRequestConfig,
     },
     tls::TlsConfig,
 };
@@ -61,6 +63,8 @@ pub struct CloudWatchMetricsSinkConfig {
     pub batch: BatchConfig<CloudWatchMetricsDefaultBatchSettings>,
     #[serde(default)]
     pub request: TowerRequestConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_rules: Vec<TagRule>,
     pub tls: Option<TlsConfig>,
     // Deprecated name. Moved to auth.
     assume_role: Option<String>,
@@ -80,22 +84,6 @@ inventory::submit! {
 
 impl_generate_config_from_default!(CloudWatchMetricsSinkConfig);
 
-struct CloudwatchMetricsClientBuilder;
-
-impl ClientBuilder for CloudwatchMetricsClientBuilder {
-    type Config = aws_sdk_cloudwatch::config::Config;
-    type Client = aws_sdk_cloudwatch::client::Client;
-    type DefaultMiddleware = aws_sdk_cloudwatch::middleware::DefaultMiddleware;
-
-    fn default_middleware() -> Self::DefaultMiddleware {
-        aws_sdk_cloudwatch::middleware::DefaultMiddleware::new()
-    }
-
-    fn build(client: aws_smithy_client::Client, config: &aws_types::SdkConfig) -> Self::Client {
-        aws_sdk_cloudwatch::client::Client::with_config(client, config.into())
-    }
-}
-
 #[async_trait::async_trait]
 #[typetag::serde(name = "aws_cloudwatch_metrics")]
 impl SinkConfig for CloudWatchMetricsSinkConfig {
@@ -174,6 +162,7 @@ impl CloudWatchMetricsSvc {
 
         let service = CloudWatchMetricsSvc { client };
         let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
+        let tag_rules = TagRules::new(&config.tag_rules)?;
         let mut normalizer = MetricNormalizer::<AwsCloudwatchMetricNormalize>::default();
 
         let sink = request_settings
@@ -182,7 +171,9 @@ impl CloudWatchMetricsSvc {
             .with_flat_map(move |event: Event| {
                 stream::iter({
                     let byte_size = event.size_of();
-                    normalizer.normalize(event.into_metric()).map(|mut metric| {
+                    let mut metric = event.into_metric();
+                    tag_rules.apply(&mut metric);
+                    normalizer.normalize(metric).map(|mut metric| {
                         let namespace = metric
                             .take_namespace()
                             .take()
@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, time::Duration};
 
 use aws_sdk_s3::Client as S3Client;
 use codecs::encoding::{Framer, FramingConfig};
@@ -15,16 +15,22 @@ use crate::{
         SinkContext,
     },
     sinks::{
-        aws_s3::sink::S3RequestOptions,
+        aws_s3::{
+            manifest::{
+                ManifestRecorder, ManifestService, ManifestSink, ManifestWriter, S3ManifestConfig,
+            },
+            sink::S3RequestOptions,
+        },
         s3_common::{
             self,
             config::{S3Options, S3RetryLogic},
+            partitioner::{S3KeyPartitioner, TimePartitioning},
             service::S3Service,
             sink::S3Sink,
         },
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
+            TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -40,6 +46,9 @@ const DEFAULT_FILENAME_APPEND_UUID: bool = true;
 pub struct S3SinkConfig {
     pub bucket: String,
     pub key_prefix: Option<String>,
+    pub time_partitioning: Option<TimePartitioning>,
+    pub max_object_age_secs: Option<u64>,
+    pub manifest: Option<S3ManifestConfig>,
     pub filename_time_format: Option<String>,
     pub filename_append_uuid: Option<bool>,
     pub filename_extension: Option<String>,
@@ -71,6 +80,9 @@ impl GenerateConfig for S3SinkConfig {
         toml::Value::try_from(Self {
             bucket: "".to_owned(),
             key_prefix: None,
+            time_partitioning: None,
+            max_object_age_secs: None,
+            manifest: None,
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
@@ -92,6 +104,7 @@ impl GenerateConfig for S3SinkConfig {
 #[typetag::serde(name = "aws_s3")]
 impl SinkConfig for S3SinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.validate()?;
         let service = self.create_service(&cx.proxy).await?;
         let healthcheck = self.build_healthcheck(service.client())?;
         let sink = self.build_processor(service)?;
@@ -112,25 +125,45 @@ impl SinkConfig for S3SinkConfig {
 }
 
 impl S3SinkConfig {
+    /// Objects are only finalized early once their time bucket ends, so the maximum object age
+    /// means nothing without time partitioning.
+    fn validate(&self) -> crate::Result<()> {
+        if self.max_object_age_secs.is_some() && self.time_partitioning.is_none() {
+            return Err("`max_object_age_secs` requires `time_partitioning` to be set".into());
+        }
+        if matches!(&self.manifest, Some(manifest) if manifest.interval_secs == 0) {
+            return Err("`manifest.interval_secs` must be greater than zero".into());
+        }
+        Ok(())
+    }
+
     pub fn build_processor(&self, service: S3Service) -> crate::Result<VectorSink> {
         // Build our S3 client/service, which is what we'll ultimately feed
         // requests into in order to ship files to S3.  We build this here in
         // order to configure the client/service with retries, concurrency
         // limits, rate limits, and whatever else the client should have.
         let request_limits = self.request.unwrap_with(&Default::default());
+        let client = service.client();
+        let recorder = ManifestRecorder::default();
         let service = ServiceBuilder::new()
             .settings(request_limits, S3RetryLogic)
-            .service(service);
+            .service(ManifestService::new(service, recorder.clone()));
 
-        // Configure our partitioning/batching.
+        // Configure our partitioning/batching. The time partitions follow the key prefix, which
+        // isn't set by default as it would only repeat the date.
         let batch_settings = self.batch.into_batcher_settings()?;
-        let key_prefix = self
-            .key_prefix
-            .as_ref()
-            .cloned()
-            .unwrap_or_else(|| DEFAULT_KEY_PREFIX.into())
-            .try_into()?;
-        let partitioner = KeyPartitioner::new(key_prefix);
+        let key_prefix = match (&self.key_prefix, self.time_partitioning) {
+            (key_prefix, Some(time_partitioning)) => format!(
+                "{}{}",
+                key_prefix.as_deref().unwrap_or_default(),
+                time_partitioning.template()
+            ),
+            (Some(key_prefix), None) => key_prefix.clone(),
+            (None, None) => DEFAULT_KEY_PREFIX.into(),
+        }
+        .try_into()?;
+        let partitioner = S3KeyPartitioner::new(key_prefix, self.time_partitioning);
+        let max_object_age = self.max_object_age_secs.map(Duration::from_secs);
 
         // And now collect all of the S3-specific options and configuration knobs.
         let filename_time_format = self
@@ -156,9 +189,27 @@ impl S3SinkConfig {
             compression: self.compression,
         };
 
-        let sink = S3Sink::new(service, request_options, partitioner, batch_settings);
+        let sink = S3Sink::new(
+            service,
+            request_options,
+            partitioner,
+            batch_settings,
+            max_object_age,
+        );
 
-        Ok(VectorSink::from_event_streamsink(sink))
+        Ok(match &self.manifest {
+            Some(manifest) => {
+                let writer = ManifestWriter::new(
+                    client,
+                    self.bucket.clone(),
+                    manifest,
+                    self.options.clone(),
+                    recorder,
+                );
+                VectorSink::from_event_streamsink(ManifestSink::new(sink, writer, manifest))
+            }
+            None => VectorSink::from_event_streamsink(sink),
+        })
     }
 
     pub fn build_healthcheck(&self, client: S3Client) -> crate::Result<Healthcheck> {
@@ -173,9 +224,63 @@ impl S3SinkConfig {
 #[cfg(test)]
 mod tests {
     use super::S3SinkConfig;
+    use crate::sinks::s3_common::partitioner::TimeGranularity;
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<S3SinkConfig>();
     }
+
+    #[test]
+    fn parses_time_partitioning() {
+        let config: S3SinkConfig = toml::from_str(
+            r#"
+            bucket = "logs"
+            key_prefix = "app/"
+            time_partitioning.granularity = "hour"
+            time_partitioning.hive_style = true
+            max_object_age_secs = 300
+            manifest.key_prefix = "app-manifests/"
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        let time_partitioning = config.time_partitioning.unwrap();
+        assert_eq!(time_partitioning.granularity, TimeGranularity::Hour);
+        assert!(time_partitioning.hive_style);
+        assert_eq!(config.max_object_age_secs, Some(300));
+        let manifest = config.manifest.unwrap();
+        assert_eq!(manifest.key_prefix, "app-manifests/");
+        assert_eq!(manifest.interval_secs, 3600);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_max_object_age_without_time_partitioning() {
+        let config: S3SinkConfig = toml::from_str(
+            r#"
+            bucket = "logs"
+            max_object_age_secs = 300
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn rejects_zero_manifest_interval() {
+        let config: S3SinkConfig = toml::from_str(
+            r#"
+            bucket = "logs"
+            manifest.interval_secs = 0
+            encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.validate().is_err());
+    }
 }
@@ -0,0 +1,305 @@
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use aws_sdk_s3::{types::ByteStream, Client as S3Client};
+use chrono::{DateTime, Utc};
+use futures::{future::BoxFuture, stream::BoxStream};
+use md5::Digest;
+use serde::{Deserialize, Serialize};
+use tokio::time::{interval_at, Instant};
+use tower::Service;
+use vector_core::sink::StreamSink;
+
+use crate::{
+    event::Event,
+    internal_events::AwsS3ManifestWriteError,
+    sinks::s3_common::{config::S3Options, service::S3Request},
+};
+
+const DEFAULT_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_KEY_PREFIX: &str = "manifests/";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct S3ManifestConfig {
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    #[serde(default = "default_key_prefix")]
+    pub key_prefix: String,
+}
+
+const fn default_interval_secs() -> u64 {
+    DEFAULT_INTERVAL_SECS
+}
+
+fn default_key_prefix() -> String {
+    DEFAULT_KEY_PREFIX.into()
+}
+
+/// A manifest listing the objects written during an interval, modeled after the manifests of
+/// S3 inventory reports.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    source_bucket: String,
+    /// Milliseconds since the Unix epoch, as a string like in S3 inventory manifests.
+    creation_timestamp: String,
+    files: Vec<ManifestFile>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+struct ManifestFile {
+    key: String,
+    size: usize,
+    #[serde(rename = "MD5checksum")]
+    md5_checksum: String,
+    #[serde(rename = "eventCount")]
+    event_count: usize,
+}
+
+/// The objects written since the last manifest.
+#[derive(Clone, Default)]
+pub struct ManifestRecorder {
+    files: Arc<Mutex<Vec<ManifestFile>>>,
+}
+
+impl ManifestRecorder {
+    fn record(&self, file: ManifestFile) {
+        self.files.lock().expect("lock poisoned").push(file);
+    }
+
+    fn take(&self) -> Vec<ManifestFile> {
+        mem::take(&mut *self.files.lock().expect("lock poisoned"))
+    }
+
+    /// Puts back the files of a manifest that couldn't be written, ahead of newer ones.
+    fn restore(&self, mut files: Vec<ManifestFile>) {
+        let mut current = self.files.lock().expect("lock poisoned");
+        files.append(&mut current);
+        *current = files;
+    }
+}
+
+/// Records the objects that were written successfully by the wrapped service.
+#[derive(Clone)]
+pub struct ManifestService<S> {
+    inner: S,
+    recorder: ManifestRecorder,
+}
+
+impl<S> ManifestService<S> {
+    pub const fn new(inner: S, recorder: ManifestRecorder) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+impl<S> Service<S3Request> for ManifestService<S>
+where
+    S: Service<S3Request>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: S3Request) -> Self::Future {
+        let file = ManifestFile {
+            key: request.metadata.partition_key.clone(),
+            size: request.body.len(),
+            md5_checksum: format!("{:x}", md5::Md5::digest(&request.body)),
+            event_count: request.metadata.count,
+        };
+        let recorder = self.recorder.clone();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await?;
+            recorder.record(file);
+            Ok(response)
+        })
+    }
+}
+
+/// Writes the manifests to the bucket.
+pub struct ManifestWriter {
+    client: S3Client,
+    bucket: String,
+    key_prefix: String,
+    options: S3Options,
+    recorder: ManifestRecorder,
+}
+
+impl ManifestWriter {
+    pub fn new(
+        client: S3Client,
+        bucket: String,
+        config: &S3ManifestConfig,
+        options: S3Options,
+        recorder: ManifestRecorder,
+    ) -> Self {
+        Self {
+            client,
+            bucket,
+            key_prefix: config.key_prefix.clone(),
+            options,
+            recorder,
+        }
+    }
+
+    /// Writes a manifest of the objects written since the last one, if there are any.
+    async fn write(&self) {
+        let files = self.recorder.take();
+        if files.is_empty() {
+            return;
+        }
+
+        let now = Utc::now();
+        let key = manifest_key(&self.key_prefix, now);
+        let manifest = Manifest {
+            source_bucket: self.bucket.clone(),
+            creation_timestamp: now.timestamp_millis().to_string(),
+            files,
+        };
+        let body = serde_json::to_vec(&manifest).expect("manifest is serializable");
+
+        let result = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .body(ByteStream::from(body))
+            .content_type("application/json")
+            .set_server_side_encryption(self.options.server_side_encryption.map(Into::into))
+            .set_ssekms_key_id(self.options.ssekms_key_id.clone())
+            .send()
+            .await;
+        if let Err(error) = result {
+            emit!(AwsS3ManifestWriteError {
+                key: &key,
+                error: &error,
+            });
+            self.recorder.restore(manifest.files);
+        }
+    }
+}
+
+/// The key of a manifest, laid out like S3 inventory reports: `<prefix><YYYY-MM-DDTHH-MMZ>/manifest.json`.
+fn manifest_key(key_prefix: &str, timestamp: DateTime<Utc>) -> String {
+    format!(
+        "{}{}/manifest.json",
+        key_prefix,
+        timestamp.format("%Y-%m-%dT%H-%MZ")
+    )
+}
+
+/// Runs the wrapped sink, writing a manifest every interval and a last one once it stops.
+pub struct ManifestSink<S> {
+    inner: S,
+    writer: ManifestWriter,
+    interval: Duration,
+}
+
+impl<S> ManifestSink<S> {
+    pub fn new(inner: S, writer: ManifestWriter, config: &S3ManifestConfig) -> Self {
+        Self {
+            inner,
+            writer,
+            interval: Duration::from_secs(config.interval_secs),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for ManifestSink<S>
+where
+    S: StreamSink<Event> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            inner,
+            writer,
+            interval,
+        } = *self;
+
+        let run = Box::new(inner).run(input);
+        tokio::pin!(run);
+        let mut ticks = interval_at(Instant::now() + interval, interval);
+        loop {
+            tokio::select! {
+                result = &mut run => {
+                    writer.write().await;
+                    return result;
+                }
+                _ = ticks.tick() => writer.write().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn file(key: &str) -> ManifestFile {
+        ManifestFile {
+            key: key.into(),
+            size: 10,
+            md5_checksum: "9e107d9d372bb6826bd81d3542a419d6".into(),
+            event_count: 1,
+        }
+    }
+
+    #[test]
+    fn lays_out_manifest_keys_like_inventory_reports() {
+        assert_eq!(
+            manifest_key("manifests/", Utc.ymd(2022, 8, 1).and_hms(13, 5, 42)),
+            "manifests/2022-08-01T13-05Z/manifest.json"
+        );
+    }
+
+    #[test]
+    fn serializes_manifests() {
+        let manifest = Manifest {
+            source_bucket: "logs".into(),
+            creation_timestamp: "1659359142000".into(),
+            files: vec![file("2022/08/01/13/1659359100-abc.log.gz")],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&manifest).unwrap(),
+            serde_json::json!({
+                "sourceBucket": "logs",
+                "creationTimestamp": "1659359142000",
+                "files": [{
+                    "key": "2022/08/01/13/1659359100-abc.log.gz",
+                    "size": 10,
+                    "MD5checksum": "9e107d9d372bb6826bd81d3542a419d6",
+                    "eventCount": 1,
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn restores_files_of_failed_manifests_first() {
+        let recorder = ManifestRecorder::default();
+        recorder.record(file("a"));
+        let files = recorder.take();
+        assert!(recorder.take().is_empty());
+
+        recorder.record(file("b"));
+        recorder.restore(files);
+        assert_eq!(recorder.take(), vec![file("a"), file("b")]);
+    }
+}
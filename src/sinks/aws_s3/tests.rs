@@ -345,6 +345,9 @@ mod integration_tests {
         S3SinkConfig {
             bucket: bucket.to_string(),
             key_prefix: Some(random_string(10) + "/date=%F"),
+            time_partitioning: None,
+            max_object_age_secs: None,
+            manifest: None,
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
@@ -1,8 +1,9 @@
-use std::{convert::TryInto, sync::Arc};
+use std::{convert::TryInto, num::NonZeroUsize, sync::Arc};
 
 use azure_storage_blobs::prelude::*;
 use codecs::{encoding::Framer, JsonSerializerConfig, NewlineDelimitedEncoderConfig};
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 use tower::ServiceBuilder;
 
 use super::request_builder::AzureBlobRequestOptions;
@@ -11,7 +12,10 @@ use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
     sinks::{
         azure_common::{
-            self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
+            self,
+            config::AzureBlobRetryLogic,
+            service::{AzureBlobService, UploadMode},
+            sink::AzureBlobSink,
         },
         util::{
             partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
@@ -31,6 +35,10 @@ pub struct AzureBlobSinkConfig {
     pub blob_prefix: Option<String>,
     pub blob_time_format: Option<String>,
     pub blob_append_uuid: Option<bool>,
+    #[serde(default)]
+    pub blob_type: BlobType,
+    pub block_size_bytes: Option<NonZeroUsize>,
+    pub block_upload_concurrency: Option<NonZeroUsize>,
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
     #[serde(default = "Compression::gzip_default")]
@@ -47,6 +55,27 @@ pub struct AzureBlobSinkConfig {
     pub(super) acknowledgements: AcknowledgementsConfig,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Derivative, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum BlobType {
+    #[derivative(Default)]
+    Block,
+    Append,
+}
+
+#[derive(Debug, Snafu, PartialEq)]
+enum BuildError {
+    #[snafu(display("Append blobs can't be written with `blob_append_uuid` set"))]
+    AppendWithUuid,
+    #[snafu(display(
+        "Append blobs can only be compressed with gzip, as zlib streams can't be concatenated"
+    ))]
+    AppendWithZlib,
+    #[snafu(display("`block_size_bytes` can't be larger than {} bytes", MAX_BLOCK_SIZE))]
+    BlockSizeTooLarge,
+}
+
 impl GenerateConfig for AzureBlobSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -56,6 +85,9 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_prefix: Some(String::from("blob")),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
+            blob_type: BlobType::Block,
+            block_size_bytes: None,
+            block_upload_concurrency: None,
             encoding: (Some(NewlineDelimitedEncoderConfig::new()), JsonSerializerConfig::new()).into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
@@ -103,25 +135,33 @@ const DEFAULT_REQUEST_LIMITS: TowerRequestConfig =
 const DEFAULT_KEY_PREFIX: &str = "blob/%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
+/// Append blobs are named after the hour by default, so each one collects an hour of events.
+const DEFAULT_APPEND_FILENAME_TIME_FORMAT: &str = "%H";
+const DEFAULT_BLOCK_UPLOAD_CONCURRENCY: usize = 4;
+/// The largest block `Put Block` accepts.
+const MAX_BLOCK_SIZE: usize = 4000 * 1024 * 1024;
 
 impl AzureBlobSinkConfig {
     pub fn build_processor(&self, client: Arc<ContainerClient>) -> crate::Result<VectorSink> {
+        let upload_mode = self.upload_mode()?;
         let request_limits = self.request.unwrap_with(&DEFAULT_REQUEST_LIMITS);
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(AzureBlobService::new(client).with_upload_mode(upload_mode));
 
         // Configure our partitioning/batching.
         let batcher_settings = self.batch.into_batcher_settings()?;
 
+        let (default_time_format, default_append_uuid) = match self.blob_type {
+            BlobType::Block => (DEFAULT_FILENAME_TIME_FORMAT, DEFAULT_FILENAME_APPEND_UUID),
+            BlobType::Append => (DEFAULT_APPEND_FILENAME_TIME_FORMAT, false),
+        };
         let blob_time_format = self
             .blob_time_format
             .as_ref()
             .cloned()
-            .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into());
-        let blob_append_uuid = self
-            .blob_append_uuid
-            .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
+            .unwrap_or_else(|| default_time_format.into());
+        let blob_append_uuid = self.blob_append_uuid.unwrap_or(default_append_uuid);
 
         let transformer = self.encoding.transformer();
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
@@ -145,6 +185,31 @@ impl AzureBlobSinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
+    pub(super) fn upload_mode(&self) -> crate::Result<UploadMode> {
+        match self.blob_type {
+            BlobType::Block => {
+                if matches!(self.block_size_bytes, Some(size) if size.get() > MAX_BLOCK_SIZE) {
+                    return Err(BuildError::BlockSizeTooLarge.into());
+                }
+                Ok(UploadMode::Block {
+                    block_size: self.block_size_bytes,
+                    concurrency: self.block_upload_concurrency.unwrap_or_else(|| {
+                        NonZeroUsize::new(DEFAULT_BLOCK_UPLOAD_CONCURRENCY).expect("non-zero")
+                    }),
+                })
+            }
+            BlobType::Append => {
+                if self.blob_append_uuid == Some(true) {
+                    return Err(BuildError::AppendWithUuid.into());
+                }
+                if matches!(self.compression, Compression::Zlib(_)) {
+                    return Err(BuildError::AppendWithZlib.into());
+                }
+                Ok(UploadMode::Append)
+            }
+        }
+    }
+
     pub fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
         let blob_prefix = self
             .blob_prefix
@@ -15,7 +15,7 @@ use futures::{stream, Stream, StreamExt};
 use http::StatusCode;
 use vector_core::ByteSizeOf;
 
-use super::config::AzureBlobSinkConfig;
+use super::config::{AzureBlobSinkConfig, BlobType};
 use crate::{
     event::{Event, EventArray, LogEvent},
     sinks::{
@@ -228,6 +228,9 @@ impl AzureBlobSinkConfig {
                 blob_prefix: None,
                 blob_time_format: None,
                 blob_append_uuid: None,
+                blob_type: BlobType::Block,
+                block_size_bytes: None,
+                block_upload_concurrency: None,
                 encoding: (None::<FramingConfig>, TextSerializerConfig::new()).into(),
                 compression: Compression::None,
                 batch: Default::default(),
@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
@@ -6,11 +8,14 @@ use codecs::{
 };
 use vector_core::partition::Partitioner;
 
-use super::config::AzureBlobSinkConfig;
+use super::config::{AzureBlobSinkConfig, BlobType};
 use super::request_builder::AzureBlobRequestOptions;
 use crate::codecs::EncodingConfigWithFraming;
 use crate::event::{Event, LogEvent};
-use crate::sinks::util::{request_builder::RequestBuilder, Compression};
+use crate::sinks::{
+    azure_common::service::{split_blocks, UploadMode},
+    util::{request_builder::RequestBuilder, Compression},
+};
 use crate::{codecs::Encoder, sinks::util::request_builder::EncodeResult};
 
 fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
@@ -21,6 +26,9 @@ fn default_config(encoding: EncodingConfigWithFraming) -> AzureBlobSinkConfig {
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
+        blob_type: Default::default(),
+        block_size_bytes: Default::default(),
+        block_upload_concurrency: Default::default(),
         encoding,
         compression: Compression::gzip_default(),
         batch: Default::default(),
@@ -200,3 +208,48 @@ fn azure_blob_build_request_with_uuid() {
     assert_eq!(request.content_encoding, None);
     assert_eq!(request.content_type, "text/plain");
 }
+
+#[test]
+fn azure_blob_upload_mode_defaults_to_parallel_blocks() {
+    let sink_config = default_config((None::<FramingConfig>, TextSerializerConfig::new()).into());
+
+    assert_eq!(
+        sink_config.upload_mode().unwrap(),
+        UploadMode::Block {
+            block_size: None,
+            concurrency: NonZeroUsize::new(4).unwrap(),
+        }
+    );
+}
+
+#[test]
+fn azure_blob_append_mode_rejects_uuid_and_zlib() {
+    let sink_config = AzureBlobSinkConfig {
+        blob_type: BlobType::Append,
+        ..default_config((None::<FramingConfig>, TextSerializerConfig::new()).into())
+    };
+    assert_eq!(sink_config.upload_mode().unwrap(), UploadMode::Append);
+
+    let with_uuid = AzureBlobSinkConfig {
+        blob_append_uuid: Some(true),
+        ..sink_config.clone()
+    };
+    assert!(with_uuid.upload_mode().is_err());
+
+    let with_zlib = AzureBlobSinkConfig {
+        compression: Compression::zlib_default(),
+        ..sink_config
+    };
+    assert!(with_zlib.upload_mode().is_err());
+}
+
+#[test]
+fn azure_blob_splits_payloads_into_blocks() {
+    let payload = Bytes::from_static(b"0123456789");
+
+    let blocks = split_blocks(&payload, 4);
+    assert_eq!(blocks, vec!["0123", "4567", "89"]);
+
+    let blocks = split_blocks(&payload, 10);
+    assert_eq!(blocks, vec![payload]);
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 use azure_core::{new_http_client, HttpError};
 use azure_identity::{AutoRefreshingTokenCredential, DefaultAzureCredential};
 use azure_storage::prelude::*;
-use azure_storage_blobs::{blob::responses::PutBlockBlobResponse, prelude::*};
+use azure_storage_blobs::prelude::*;
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
@@ -56,7 +56,6 @@ impl RetryLogic for AzureBlobRetryLogic {
 
 #[derive(Debug)]
 pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
     pub count: usize,
     pub events_byte_size: usize,
 }
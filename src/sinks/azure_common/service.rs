@@ -1,12 +1,20 @@
 use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
     result::Result as StdResult,
     sync::Arc,
     task::{Context, Poll},
 };
 
 use azure_core::HttpError;
-use azure_storage_blobs::prelude::*;
-use futures::{future::BoxFuture, TryFutureExt};
+use azure_storage_blobs::{
+    blob::{BlobBlockType, BlockList},
+    prelude::*,
+};
+use bytes::Bytes;
+use futures::{future::BoxFuture, StreamExt, TryFutureExt, TryStreamExt};
+use http::StatusCode;
+use tokio::sync::Mutex;
 use tower::Service;
 use tracing::Instrument;
 
@@ -16,20 +24,64 @@ use crate::{
 };
 use vector_common::internal_event::BytesSent;
 
+/// The largest block `Append Block` accepts.
+const MAX_APPEND_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+type Error = Box<dyn std::error::Error + std::marker::Send + std::marker::Sync>;
+
+/// The locks serializing the appends to each blob, by blob name.
+type AppendLocks = Arc<std::sync::Mutex<HashMap<String, Arc<Mutex<()>>>>>;
+
+/// How the payload of a request is written to its blob.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UploadMode {
+    /// Each request is written to a new block blob. Payloads larger than `block_size` are split
+    /// in blocks uploaded `concurrency` at a time, and committed once all are uploaded.
+    Block {
+        block_size: Option<NonZeroUsize>,
+        concurrency: NonZeroUsize,
+    },
+    /// Each request is appended to its append blob, which is created if it doesn't exist yet.
+    Append,
+}
+
+impl Default for UploadMode {
+    fn default() -> Self {
+        Self::Block {
+            block_size: None,
+            concurrency: NonZeroUsize::new(1).expect("non-zero"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct AzureBlobService {
     pub(self) client: Arc<ContainerClient>,
+    upload: UploadMode,
+    /// Appends to a blob are written one at a time, so that the blob is only created once and the
+    /// blocks of a request aren't interleaved with those of another. Appends to different blobs
+    /// are written concurrently.
+    append_locks: AppendLocks,
 }
 
 impl AzureBlobService {
-    pub const fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+    pub fn new(client: Arc<ContainerClient>) -> AzureBlobService {
+        AzureBlobService {
+            client,
+            upload: UploadMode::default(),
+            append_locks: AppendLocks::default(),
+        }
+    }
+
+    pub const fn with_upload_mode(mut self, upload: UploadMode) -> Self {
+        self.upload = upload;
+        self
     }
 }
 
 impl Service<AzureBlobRequest> for AzureBlobService {
     type Response = AzureBlobResponse;
-    type Error = Box<dyn std::error::Error + std::marker::Send + std::marker::Sync>;
+    type Error = Error;
     type Future = BoxFuture<'static, StdResult<Self::Response, Self::Error>>;
 
     fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<StdResult<(), Self::Error>> {
@@ -39,43 +91,177 @@ impl Service<AzureBlobRequest> for AzureBlobService {
     fn call(&mut self, request: AzureBlobRequest) -> Self::Future {
         let client =
             Arc::clone(&self.client).as_blob_client(request.metadata.partition_key.as_str());
+        let upload = self.upload;
+        let append_locks = Arc::clone(&self.append_locks);
 
         Box::pin(async move {
             let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
-            };
+            let result = async {
+                match upload {
+                    UploadMode::Block {
+                        block_size: Some(block_size),
+                        concurrency,
+                    } if byte_size > block_size.get() => {
+                        put_blocks(&client, &request, block_size.get(), concurrency.get()).await
+                    }
+                    UploadMode::Block { .. } => put_block_blob(&client, &request).await,
+                    UploadMode::Append => {
+                        let blob_name = request.metadata.partition_key.as_str();
+                        let lock = Arc::clone(
+                            append_locks
+                                .lock()
+                                .expect("poisoned lock")
+                                .entry(blob_name.to_owned())
+                                .or_default(),
+                        );
+                        let result = {
+                            let _guard = lock.lock().await;
+                            append_blocks(&client, &request).await
+                        };
 
-            let result = blob
-                .execute()
-                .inspect_err(|reason| {
-                    match reason.downcast_ref::<HttpError>() {
-                        Some(HttpError::StatusCode { status, .. }) => {
-                            emit!(AzureBlobResponseError::from(*status))
+                        // Forget the lock once no other append to the blob holds or awaits it.
+                        let mut append_locks = append_locks.lock().expect("poisoned lock");
+                        if Arc::strong_count(&lock) == 2 {
+                            append_locks.remove(blob_name);
                         }
-                        _ => emit!(AzureBlobHttpError {
-                            error: reason.to_string()
-                        }),
-                    };
-                })
-                .inspect_ok(|_| {
-                    emit!(BytesSent {
-                        byte_size,
-                        protocol: "https",
-                    });
-                })
-                .instrument(info_span!("request").or_current())
-                .await;
-
-            result.map(|inner| AzureBlobResponse {
-                inner,
+                        result
+                    }
+                }
+            }
+            .inspect_err(|reason| {
+                match reason.downcast_ref::<HttpError>() {
+                    Some(HttpError::StatusCode { status, .. }) => {
+                        emit!(AzureBlobResponseError::from(*status))
+                    }
+                    _ => emit!(AzureBlobHttpError {
+                        error: reason.to_string()
+                    }),
+                };
+            })
+            .inspect_ok(|_| {
+                emit!(BytesSent {
+                    byte_size,
+                    protocol: "https",
+                });
+            })
+            .instrument(info_span!("request").or_current())
+            .await;
+
+            result.map(|()| AzureBlobResponse {
                 count: request.metadata.count,
                 events_byte_size: request.metadata.byte_size,
             })
         })
     }
 }
+
+async fn put_block_blob(client: &BlobClient, request: &AzureBlobRequest) -> StdResult<(), Error> {
+    let blob = client
+        .put_block_blob(request.blob_data.clone())
+        .content_type(request.content_type);
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+    blob.execute().await.map(drop)
+}
+
+/// Uploads the payload in blocks, then commits them in order.
+async fn put_blocks(
+    client: &BlobClient,
+    request: &AzureBlobRequest,
+    block_size: usize,
+    concurrency: usize,
+) -> StdResult<(), Error> {
+    let blocks = split_blocks(&request.blob_data, block_size)
+        .into_iter()
+        .enumerate()
+        // Block IDs must all have the same length within a blob.
+        .map(|(index, block)| (BlockId::new(format!("{:08}", index)), block))
+        .collect::<Vec<_>>();
+
+    futures::stream::iter(blocks.iter().cloned())
+        .map(|(block_id, block)| async move { client.put_block(block_id, block).execute().await })
+        .buffer_unordered(concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let block_list = BlockList {
+        blocks: blocks
+            .into_iter()
+            .map(|(block_id, _)| BlobBlockType::Uncommitted(block_id))
+            .collect(),
+    };
+    let blob = client
+        .put_block_list(&block_list)
+        .content_type(request.content_type);
+    let blob = match request.content_encoding {
+        Some(encoding) => blob.content_encoding(encoding),
+        None => blob,
+    };
+    blob.execute().await.map(drop)
+}
+
+/// Appends the payload to the blob, creating the blob first if it doesn't exist yet.
+///
+/// Each block is appended on the condition that the blob ends where the previous block did, so
+/// that blocks written in the meantime by another writer fail the request rather than being
+/// interleaved with its blocks, and so that a block whose append is retried after it actually
+/// succeeded isn't written twice.
+async fn append_blocks(client: &BlobClient, request: &AzureBlobRequest) -> StdResult<(), Error> {
+    let mut position = match blob_length(client).await? {
+        Some(length) => length,
+        None => {
+            let blob = client.put_append_blob().content_type(request.content_type);
+            let blob = match request.content_encoding {
+                Some(encoding) => blob.content_encoding(encoding),
+                None => blob,
+            };
+            blob.execute().await?;
+            0
+        }
+    };
+
+    for block in split_blocks(&request.blob_data, MAX_APPEND_BLOCK_SIZE) {
+        let end = position + block.len() as u64;
+        let appended = client
+            .append_block(block)
+            .condition_append_position(position.into())
+            .execute()
+            .await;
+        if let Err(error) = appended {
+            // The block may have been appended, with only the response to the first attempt lost.
+            if !(has_status(&error, StatusCode::PRECONDITION_FAILED)
+                && blob_length(client).await? == Some(end))
+            {
+                return Err(error);
+            }
+        }
+        position = end;
+    }
+    Ok(())
+}
+
+/// Returns the length of the blob, or `None` if it doesn't exist.
+async fn blob_length(client: &BlobClient) -> StdResult<Option<u64>, Error> {
+    match client.get_properties().execute().await {
+        Ok(response) => Ok(Some(response.blob.properties.content_length)),
+        Err(error) if has_status(&error, StatusCode::NOT_FOUND) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+fn has_status(error: &Error, expected: StatusCode) -> bool {
+    matches!(
+        error.downcast_ref::<HttpError>(),
+        Some(HttpError::StatusCode { status, .. }) if *status == expected
+    )
+}
+
+/// Splits the payload in blocks of at most `block_size` bytes, without copying it.
+pub(crate) fn split_blocks(data: &Bytes, block_size: usize) -> Vec<Bytes> {
+    (0..data.len())
+        .step_by(block_size)
+        .map(|start| data.slice(start..data.len().min(start + block_size)))
+        .collect()
+}
@@ -106,7 +106,11 @@ impl SinkConfig for AzureMonitorLogsConfig {
             .into_batch_settings()?;
 
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(Some(tls_settings), &cx.proxy)?;
+        let client = HttpClient::new_with_connection_pool(
+            Some(tls_settings),
+            &cx.proxy,
+            cx.connection_pool(),
+        )?;
 
         let sink = AzureMonitorLogsSink::new(self)?;
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use futures::{future, FutureExt};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -18,6 +21,21 @@ pub struct BlackholeConfig {
     #[serde(default = "default_print_interval_secs")]
     pub print_interval_secs: u64,
     pub rate: Option<usize>,
+    /// The maximum number of bytes accepted per second, estimated from the in-memory size of the
+    /// events.
+    pub rate_bytes: Option<usize>,
+    /// The latency added to every batch of events, before it's acknowledged and the next one is
+    /// accepted.
+    pub latency: Option<LatencyConfig>,
+    /// The fraction of batches failing with an error, acknowledged as errored to their sources.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub error_rate: f64,
+    /// The fraction of batches rejected, acknowledged as rejected to their sources.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub rejection_rate: f64,
+    /// The seed of the random latencies and failures, so that runs are reproducible. Without one,
+    /// each run differs.
+    pub seed: Option<u64>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -26,10 +44,76 @@ pub struct BlackholeConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The distribution of the latency added to every batch.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "distribution", rename_all = "snake_case")]
+pub enum LatencyConfig {
+    /// The same latency for every batch.
+    Fixed { ms: u64 },
+
+    /// A latency uniformly distributed between `min_ms` and `max_ms`.
+    Uniform { min_ms: u64, max_ms: u64 },
+
+    /// An exponentially distributed latency averaging `mean_ms`, with occasional long stalls.
+    Exponential { mean_ms: u64 },
+}
+
+impl LatencyConfig {
+    fn validate(&self) -> crate::Result<()> {
+        match self {
+            Self::Uniform { min_ms, max_ms } if min_ms > max_ms => {
+                Err("`latency.min_ms` must not be greater than `latency.max_ms`".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Draws the latency of a batch.
+    pub(super) fn sample(&self, rng: &mut impl Rng) -> Duration {
+        match *self {
+            Self::Fixed { ms } => Duration::from_millis(ms),
+            Self::Uniform { min_ms, max_ms } => {
+                Duration::from_millis(rng.gen_range(min_ms..=max_ms))
+            }
+            Self::Exponential { mean_ms } => {
+                // Inverse transform sampling, with `1 - u` in (0, 1] so that the log is finite.
+                let u: f64 = rng.gen();
+                Duration::from_secs_f64(-(1.0 - u).ln() * mean_ms as f64 / 1000.0)
+            }
+        }
+    }
+}
+
+impl BlackholeConfig {
+    fn validate(&self) -> crate::Result<()> {
+        for (name, limit) in [("rate", self.rate), ("rate_bytes", self.rate_bytes)] {
+            if limit == Some(0) {
+                return Err(format!("`{}` must be greater than 0", name).into());
+            }
+        }
+        for (name, rate) in [
+            ("error_rate", self.error_rate),
+            ("rejection_rate", self.rejection_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(format!("`{}` must be between 0 and 1", name).into());
+            }
+        }
+        if self.error_rate + self.rejection_rate > 1.0 {
+            return Err("`error_rate` and `rejection_rate` must not add up to more than 1".into());
+        }
+        if let Some(latency) = &self.latency {
+            latency.validate()?;
+        }
+        Ok(())
+    }
+}
+
 #[async_trait::async_trait]
 #[typetag::serde(name = "blackhole")]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.validate()?;
         let sink = BlackholeSink::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
@@ -63,4 +147,20 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<BlackholeConfig>();
     }
+
+    #[test]
+    fn rejects_zero_rates() {
+        for config in [
+            BlackholeConfig {
+                rate: Some(0),
+                ..Default::default()
+            },
+            BlackholeConfig {
+                rate_bytes: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(config.validate().is_err());
+        }
+    }
 }
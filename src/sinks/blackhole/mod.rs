@@ -11,6 +11,7 @@ inventory::submit! {
 
 #[cfg(test)]
 mod tests {
+    use vector_core::event::{BatchNotifier, BatchStatus};
 
     use crate::{
         sinks::{
@@ -28,6 +29,7 @@ mod tests {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         };
         let sink = BlackholeSink::new(config);
         let sink = VectorSink::Stream(Box::new(sink));
@@ -35,4 +37,27 @@ mod tests {
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
         run_and_assert_nonsending_sink_compliance(sink, events, &[]).await;
     }
+
+    #[tokio::test]
+    async fn injects_failures() {
+        for (error_rate, rejection_rate, expected) in [
+            (0.0, 0.0, BatchStatus::Delivered),
+            (1.0, 0.0, BatchStatus::Errored),
+            (0.0, 1.0, BatchStatus::Rejected),
+        ] {
+            let config = BlackholeConfig {
+                print_interval_secs: 0,
+                error_rate,
+                rejection_rate,
+                seed: Some(0),
+                ..Default::default()
+            };
+            let sink = VectorSink::Stream(Box::new(BlackholeSink::new(config)));
+
+            let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+            let (_input_lines, events) = random_events_with_stream(100, 10, Some(batch));
+            sink.run(events).await.unwrap();
+            assert_eq!(receiver.try_recv(), Ok(expected));
+        }
+    }
 }
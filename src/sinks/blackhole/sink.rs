@@ -8,15 +8,17 @@ use std::{
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{
     select,
     sync::watch,
-    time::{interval, sleep_until},
+    time::{interval, sleep, sleep_until},
 };
 use vector_core::{internal_event::EventsSent, ByteSizeOf};
 
 use crate::{
-    event::{EventArray, EventContainer},
+    event::{EventArray, EventContainer, EventStatus, Finalizable},
+    internal_events::BlackholeInjectedFailure,
     sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
 };
 
@@ -25,15 +27,46 @@ pub struct BlackholeSink {
     total_raw_bytes: Arc<AtomicUsize>,
     config: BlackholeConfig,
     last: Option<Instant>,
+    rng: SmallRng,
 }
 
 impl BlackholeSink {
     pub fn new(config: BlackholeConfig) -> Self {
+        let rng = config
+            .seed
+            .map_or_else(SmallRng::from_entropy, SmallRng::seed_from_u64);
         BlackholeSink {
             config,
             total_events: Arc::new(AtomicUsize::new(0)),
             total_raw_bytes: Arc::new(AtomicUsize::new(0)),
             last: None,
+            rng,
+        }
+    }
+
+    /// How long accepting the given batch takes at the configured throughput caps, if any.
+    fn pace(&self, count: usize, byte_size: usize) -> Option<Duration> {
+        let by_events = self.config.rate.map(|rate| count as f64 / rate as f64);
+        let by_bytes = self
+            .config
+            .rate_bytes
+            .map(|rate| byte_size as f64 / rate as f64);
+        by_events
+            .into_iter()
+            .chain(by_bytes)
+            .reduce(f64::max)
+            .map(Duration::from_secs_f64)
+    }
+
+    /// Draws the status of a batch from the configured failure rates.
+    fn draw_status(&mut self) -> EventStatus {
+        let roll: f64 = self.rng.gen();
+        if roll < self.config.error_rate {
+            EventStatus::Errored
+        } else if roll < self.config.error_rate + self.config.rejection_rate {
+            EventStatus::Rejected
+        } else {
+            EventStatus::Delivered
         }
     }
 }
@@ -71,16 +104,29 @@ impl StreamSink<EventArray> for BlackholeSink {
             });
         }
 
-        while let Some(events) = input.next().await {
-            if let Some(rate) = self.config.rate {
-                let factor: f32 = 1.0 / rate as f32;
-                let secs: f32 = factor * (events.len() as f32);
-                let until = self.last.unwrap_or_else(Instant::now) + Duration::from_secs_f32(secs);
+        while let Some(mut events) = input.next().await {
+            let message_len = events.size_of();
+
+            if let Some(duration) = self.pace(events.len(), message_len) {
+                let until = self.last.unwrap_or_else(Instant::now) + duration;
                 sleep_until(until.into()).await;
                 self.last = Some(until);
             }
 
-            let message_len = events.size_of();
+            if let Some(latency) = self.config.latency {
+                sleep(latency.sample(&mut self.rng)).await;
+            }
+
+            let finalizers = events.take_finalizers();
+            let status = self.draw_status();
+            finalizers.update_status(status);
+            if status != EventStatus::Delivered {
+                emit!(BlackholeInjectedFailure {
+                    count: events.len(),
+                    rejected: status == EventStatus::Rejected,
+                });
+                continue;
+            }
 
             let _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
             let _ = self
@@ -66,7 +66,8 @@ impl SinkConfig for ClickhouseConfig {
         let batch = self.batch.into_batch_settings()?;
         let request = self.request.unwrap_with(&TowerRequestConfig::default());
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, &cx.proxy)?;
+        let client =
+            HttpClient::new_with_connection_pool(tls_settings, &cx.proxy, cx.connection_pool())?;
 
         let config = ClickhouseConfig {
             auth: self.auth.choose_one(&self.endpoint.auth)?,
@@ -242,6 +243,10 @@ impl RetryLogic for ClickhouseRetryLogic {
             _ => self.inner.should_retry_response(response),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        self.inner.response_status(response)
+    }
 }
 
 #[cfg(test)]
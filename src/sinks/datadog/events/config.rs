@@ -6,7 +6,7 @@ use vector_core::config::proxy::ProxyConfig;
 
 use crate::{
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{ConnectionPoolConfig, HttpClient},
     sinks::{
         datadog::{
             events::{
@@ -61,9 +61,13 @@ impl DatadogEventsConfig {
         http::Uri::try_from(format!("{}/api/v1/events", api_base_endpoint)).expect("URI not valid")
     }
 
-    fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    fn build_client(
+        &self,
+        proxy: &ProxyConfig,
+        connection_pool: &ConnectionPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let client = HttpClient::new(tls, proxy)?;
+        let client = HttpClient::new_with_connection_pool(tls, proxy, connection_pool)?;
         Ok(client)
     }
 
@@ -98,7 +102,7 @@ impl DatadogEventsConfig {
 #[typetag::serde(name = "datadog_events")]
 impl SinkConfig for DatadogEventsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.build_client(cx.proxy())?;
+        let client = self.build_client(cx.proxy(), cx.connection_pool())?;
         let healthcheck = self.build_healthcheck(client.clone())?;
         let sink = self.build_sink(client)?;
 
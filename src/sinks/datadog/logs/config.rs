@@ -7,11 +7,14 @@ use tower::ServiceBuilder;
 use value::Kind;
 use vector_core::config::proxy::ProxyConfig;
 
-use super::{service::LogApiRetry, sink::LogSinkBuilder};
+use super::{
+    service::LogApiRetry,
+    sink::{LogSinkBuilder, ReservedAttributes},
+};
 use crate::{
     codecs::Transformer,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{ConnectionPoolConfig, HttpClient},
     schema,
     sinks::{
         datadog::{get_api_validate_endpoint, healthcheck, logs::service::LogApiService, Region},
@@ -21,6 +24,7 @@ use crate::{
         },
         Healthcheck, VectorSink,
     },
+    template::Template,
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
@@ -60,6 +64,15 @@ pub(crate) struct DatadogLogsConfig {
     #[serde(default)]
     pub compression: Option<Compression>,
 
+    // Templates for the attributes the Datadog Logs API treats specially. When set, these take
+    // precedence over any existing `service`, `ddsource`, and `host` fields, while rendered
+    // tags are appended to any existing `ddtags`.
+    pub service: Option<Template>,
+    pub source: Option<Template>,
+    pub hostname: Option<Template>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Template>,
+
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -115,6 +128,15 @@ impl DatadogLogsConfig {
 }
 
 impl DatadogLogsConfig {
+    fn reserved_attributes(&self) -> ReservedAttributes {
+        ReservedAttributes {
+            service: self.service.clone(),
+            source: self.source.clone(),
+            hostname: self.hostname.clone(),
+            tags: self.tags.clone(),
+        }
+    }
+
     pub fn build_processor(&self, client: HttpClient) -> crate::Result<VectorSink> {
         let default_api_key: Arc<str> = Arc::from(self.default_api_key.clone().as_str());
         let request_limits = self.request.unwrap_with(&Default::default());
@@ -134,6 +156,7 @@ impl DatadogLogsConfig {
 
         let sink = LogSinkBuilder::new(self.encoding.clone(), service, default_api_key, batch)
             .compression(self.compression.unwrap_or_default())
+            .reserved_attributes(self.reserved_attributes())
             .build();
 
         Ok(VectorSink::from_event_streamsink(sink))
@@ -145,7 +168,11 @@ impl DatadogLogsConfig {
         Ok(healthcheck(client, validate_endpoint, self.default_api_key.clone()).boxed())
     }
 
-    pub fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    pub fn create_client(
+        &self,
+        proxy: &ProxyConfig,
+        connection_pool: &ConnectionPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls_settings = MaybeTlsSettings::from_config(
             &Some(
                 self.tls
@@ -154,7 +181,11 @@ impl DatadogLogsConfig {
             ),
             false,
         )?;
-        Ok(HttpClient::new(tls_settings, proxy)?)
+        Ok(HttpClient::new_with_connection_pool(
+            tls_settings,
+            proxy,
+            connection_pool,
+        )?)
     }
 }
 
@@ -162,7 +193,7 @@ impl DatadogLogsConfig {
 #[typetag::serde(name = "datadog_logs")]
 impl SinkConfig for DatadogLogsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.create_client(&cx.proxy)?;
+        let client = self.create_client(&cx.proxy, cx.connection_pool())?;
         let healthcheck = self.build_healthcheck(client.clone())?;
         let sink = self.build_processor(client)?;
         Ok((sink, healthcheck))
@@ -14,7 +14,7 @@ use snafu::Snafu;
 use tower::Service;
 use vector_core::{
     config::{log_schema, LogSchema},
-    event::{Event, EventFinalizers, Finalizable, Value},
+    event::{Event, EventFinalizers, Finalizable, LogEvent, Value},
     partition::Partitioner,
     sink::StreamSink,
     stream::{BatcherSettings, DriverResponse},
@@ -24,10 +24,12 @@ use vector_core::{
 use super::{config::MAX_PAYLOAD_BYTES, service::LogApiRequest};
 use crate::{
     codecs::{Encoder, Transformer},
+    internal_events::TemplateRenderingError,
     sinks::util::{
         encoding::Encoder as _, request_builder::EncodeResult, Compression, Compressor,
         RequestBuilder, SinkBuilderExt,
     },
+    template::Template,
 };
 #[derive(Default)]
 struct EventPartitioner;
@@ -71,6 +73,11 @@ impl<S> LogSinkBuilder<S> {
         self
     }
 
+    pub fn reserved_attributes(mut self, reserved_attributes: ReservedAttributes) -> Self {
+        self.encoding.reserved_attributes = reserved_attributes;
+        self
+    }
+
     pub fn build(self) -> LogSink<S> {
         LogSink {
             default_api_key: self.default_api_key,
@@ -103,12 +110,77 @@ pub struct LogSink<S> {
     batch_settings: BatcherSettings,
 }
 
+/// Templates for the attributes that the Datadog Logs API gives special treatment to.
+#[derive(Clone, Debug, Default)]
+pub struct ReservedAttributes {
+    pub service: Option<Template>,
+    pub source: Option<Template>,
+    pub hostname: Option<Template>,
+    pub tags: Vec<Template>,
+}
+
+impl ReservedAttributes {
+    /// Renders the templates against the event as it was received, before any normalization.
+    ///
+    /// Templates that fail to render are skipped, leaving the attribute untouched. Rendered tags
+    /// are appended to any tags already present in the event.
+    fn render(&self, log: &LogEvent) -> Vec<(&'static str, String)> {
+        let mut attributes = Vec::new();
+
+        for (attribute, template) in [
+            ("service", &self.service),
+            ("ddsource", &self.source),
+            ("host", &self.hostname),
+        ] {
+            if let Some(value) = template
+                .as_ref()
+                .and_then(|template| render_template(template, log, attribute))
+            {
+                attributes.push((attribute, value));
+            }
+        }
+
+        let tags = self
+            .tags
+            .iter()
+            .filter_map(|template| render_template(template, log, "ddtags"))
+            .filter(|tag| !tag.is_empty())
+            .collect::<Vec<_>>();
+        if !tags.is_empty() {
+            let tags = tags.join(",");
+            let tags = match log.get("ddtags") {
+                Some(Value::Bytes(existing)) if !existing.is_empty() => {
+                    format!("{},{}", String::from_utf8_lossy(existing), tags)
+                }
+                _ => tags,
+            };
+            attributes.push(("ddtags", tags));
+        }
+
+        attributes
+    }
+}
+
+fn render_template(template: &Template, log: &LogEvent, attribute: &str) -> Option<String> {
+    template
+        .render_string(log)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(attribute),
+                drop_event: false,
+            });
+        })
+        .ok()
+}
+
 /// Customized encoding specific to the Datadog Logs sink, as the logs API only accepts JSON encoded
 /// log lines, and requires some specific normalization of certain event fields.
 #[derive(Clone, Debug)]
 pub struct JsonEncoding {
     log_schema: &'static LogSchema,
     encoder: (Transformer, Encoder<Framer>),
+    reserved_attributes: ReservedAttributes,
 }
 
 impl JsonEncoding {
@@ -122,6 +194,7 @@ impl JsonEncoding {
                     JsonSerializer::new().into(),
                 ),
             ),
+            reserved_attributes: ReservedAttributes::default(),
         }
     }
 }
@@ -130,17 +203,20 @@ impl JsonEncoding {
 pub struct SemanticJsonEncoding {
     log_schema: &'static LogSchema,
     encoder: (Transformer, Encoder<Framer>),
+    reserved_attributes: ReservedAttributes,
 }
 
 impl crate::sinks::util::encoding::Encoder<Vec<Event>> for JsonEncoding {
     fn encode_input(&self, mut input: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
         for event in input.iter_mut() {
             let log = event.as_mut_log();
+            let attributes = self.reserved_attributes.render(log);
             log.rename_key(self.log_schema.message_key(), path!("message"));
             log.rename_key(self.log_schema.host_key(), path!("host"));
             if let Some(Value::Timestamp(ts)) = log.remove(self.log_schema.timestamp_key()) {
                 log.insert(path!("timestamp"), Value::Integer(ts.timestamp_millis()));
             }
+            insert_attributes(log, attributes);
         }
 
         self.encoder.encode_input(input, writer)
@@ -151,6 +227,7 @@ impl crate::sinks::util::encoding::Encoder<Vec<Event>> for SemanticJsonEncoding
     fn encode_input(&self, mut input: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
         for event in input.iter_mut() {
             let log = event.as_mut_log();
+            let attributes = self.reserved_attributes.render(log);
 
             // message
             let message_key = log
@@ -171,12 +248,20 @@ impl crate::sinks::util::encoding::Encoder<Vec<Event>> for SemanticJsonEncoding
                 .as_timestamp_unwrap();
             let ms = ts.timestamp_millis();
             log.insert(path!("timestamp"), Value::Integer(ms));
+
+            insert_attributes(log, attributes);
         }
 
         self.encoder.encode_input(input, writer)
     }
 }
 
+fn insert_attributes(log: &mut LogEvent, attributes: Vec<(&'static str, String)>) {
+    for (attribute, value) in attributes {
+        log.insert(attribute, value);
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum RequestBuildError {
     #[snafu(display("Encoded payload is greater than the max limit."))]
@@ -382,6 +467,7 @@ where
                         encoding: SemanticJsonEncoding {
                             log_schema: self.encoding.log_schema,
                             encoder: self.encoding.encoder,
+                            reserved_attributes: self.encoding.reserved_attributes,
                         },
                         compression: self.compression,
                     },
@@ -437,6 +437,52 @@ async fn no_enterprise_headers_inner(api_status: ApiStatus) {
     assert!(parts.headers.get("DD-EVP-ORIGIN-VERSION").is_some());
 }
 
+#[tokio::test]
+/// Assert that reserved attributes are rendered from their templates
+///
+/// The `service`, `source`, `hostname` and `tags` options are rendered against
+/// each event and written to the attributes the Datadog Logs API treats
+/// specially. Rendered tags are appended to any tags already on the event.
+async fn reserved_attributes() {
+    let (mut config, cx) = load_sink::<DatadogLogsConfig>(indoc! {r#"
+            default_api_key = "atoken"
+            compression = "none"
+            service = "{{ app }}"
+            source = "nginx"
+            hostname = "{{ pod }}"
+            tags = ["env:{{ env }}", "team:core"]
+        "#})
+    .unwrap();
+
+    let addr = next_addr();
+    // Swap out the endpoint so we can force send it to our local server
+    let endpoint = format!("http://{}", addr);
+    config.endpoint = Some(endpoint.clone());
+
+    let (sink, _) = config.build(cx).await.unwrap();
+
+    let (rx, _trigger, server) = test_server(addr, ApiStatus::OKv2);
+    tokio::spawn(server);
+
+    let mut event = LogEvent::from("hello");
+    event.insert("app", "checkout");
+    event.insert("pod", "checkout-7d9f");
+    event.insert("env", "prod");
+    event.insert("ddtags", "version:1.2");
+
+    sink.run_events(vec![Event::Log(event)]).await.unwrap();
+
+    let output: (Parts, Bytes) = rx.take(1).collect::<Vec<_>>().await.pop().unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&output.1[..]).unwrap();
+    let log = payload.get(0).unwrap();
+
+    assert_eq!(log["message"], "hello");
+    assert_eq!(log["service"], "checkout");
+    assert_eq!(log["ddsource"], "nginx");
+    assert_eq!(log["host"], "checkout-7d9f");
+    assert_eq!(log["ddtags"], "version:1.2,env:prod,team:core");
+}
+
 #[tokio::test]
 /// Assert the RetryLogic implementation of LogApiRetry
 async fn error_is_retriable() {
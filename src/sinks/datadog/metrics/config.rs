@@ -14,12 +14,12 @@ use crate::tls::{MaybeTlsSettings, TlsEnableableConfig};
 use crate::{
     common::datadog::get_base_domain,
     config::{AcknowledgementsConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{ConnectionPoolConfig, HttpClient},
     sinks::{
         datadog::{get_api_validate_endpoint, healthcheck, Region},
         util::{
-            batch::BatchConfig, Concurrency, ServiceBuilderExt, SinkBatchSettings,
-            TowerRequestConfig,
+            batch::BatchConfig, Concurrency, ServiceBuilderExt, SinkBatchSettings, TagRule,
+            TagRules, TowerRequestConfig,
         },
         Healthcheck, UriParseSnafu, VectorSink,
     },
@@ -72,6 +72,25 @@ impl DatadogMetricsEndpoint {
     }
 }
 
+/// How counters are submitted to the series API.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CounterSubmission {
+    /// Counters are submitted as `count` metrics, holding the increase over the interval.
+    Count,
+
+    /// Counters are submitted as `rate` metrics, holding the per-second increase over the interval.
+    ///
+    /// Counters whose interval isn't known are still submitted as `count` metrics.
+    Rate,
+}
+
+impl Default for CounterSubmission {
+    fn default() -> Self {
+        Self::Count
+    }
+}
+
 /// Maps Datadog metric endpoints to their actual URI.
 pub struct DatadogMetricsEndpointConfiguration {
     series_endpoint: Uri,
@@ -111,6 +130,13 @@ pub struct DatadogMetricsConfig {
     pub batch: BatchConfig<DatadogMetricsDefaultBatchSettings>,
     #[serde(default)]
     pub request: TowerRequestConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_rules: Vec<TagRule>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub counter_submission: CounterSubmission,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -126,7 +152,7 @@ impl_generate_config_from_default!(DatadogMetricsConfig);
 #[typetag::serde(name = "datadog_metrics")]
 impl SinkConfig for DatadogMetricsConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.build_client(&cx.proxy)?;
+        let client = self.build_client(&cx.proxy, cx.connection_pool())?;
         let healthcheck = self.build_healthcheck(client.clone())?;
         let sink = self.build_sink(client)?;
 
@@ -180,7 +206,11 @@ impl DatadogMetricsConfig {
         ))
     }
 
-    fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    fn build_client(
+        &self,
+        proxy: &ProxyConfig,
+        connection_pool: &ConnectionPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls_settings = MaybeTlsSettings::from_config(
             &Some(
                 self.tls
@@ -189,7 +219,7 @@ impl DatadogMetricsConfig {
             ),
             false,
         )?;
-        let client = HttpClient::new(tls_settings, proxy)?;
+        let client = HttpClient::new_with_connection_pool(tls_settings, proxy, connection_pool)?;
         Ok(client)
     }
 
@@ -214,9 +244,11 @@ impl DatadogMetricsConfig {
         let request_builder = DatadogMetricsRequestBuilder::new(
             endpoint_configuration,
             self.default_namespace.clone(),
+            self.counter_submission,
         )?;
 
-        let sink = DatadogMetricsSink::new(service, request_builder, batcher_settings);
+        let tag_rules = TagRules::new(&self.tag_rules)?;
+        let sink = DatadogMetricsSink::new(service, request_builder, batcher_settings, tag_rules);
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
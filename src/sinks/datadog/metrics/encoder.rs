@@ -18,7 +18,8 @@ use vector_core::{
 };
 
 use super::config::{
-    DatadogMetricsEndpoint, MAXIMUM_PAYLOAD_COMPRESSED_SIZE, MAXIMUM_PAYLOAD_SIZE,
+    CounterSubmission, DatadogMetricsEndpoint, MAXIMUM_PAYLOAD_COMPRESSED_SIZE,
+    MAXIMUM_PAYLOAD_SIZE,
 };
 use crate::{
     common::datadog::{DatadogMetricType, DatadogPoint, DatadogSeriesMetric},
@@ -118,6 +119,7 @@ pub struct DatadogMetricsEncoder {
     default_namespace: Option<Arc<str>>,
     uncompressed_limit: usize,
     compressed_limit: usize,
+    counter_submission: CounterSubmission,
 
     state: EncoderState,
     last_sent: Option<Instant>,
@@ -156,6 +158,7 @@ impl DatadogMetricsEncoder {
             default_namespace: default_namespace.map(Arc::from),
             uncompressed_limit,
             compressed_limit,
+            counter_submission: CounterSubmission::default(),
             state: EncoderState::default(),
             last_sent: None,
             log_schema: log_schema(),
@@ -164,6 +167,12 @@ impl DatadogMetricsEncoder {
 }
 
 impl DatadogMetricsEncoder {
+    /// Sets how counters are submitted to the series endpoint.
+    pub const fn with_counter_submission(mut self, counter_submission: CounterSubmission) -> Self {
+        self.counter_submission = counter_submission;
+        self
+    }
+
     fn reset_state(&mut self) -> EncoderState {
         self.last_sent = Some(Instant::now());
         mem::take(&mut self.state)
@@ -192,6 +201,7 @@ impl DatadogMetricsEncoder {
                     &self.default_namespace,
                     self.log_schema,
                     self.last_sent,
+                    self.counter_submission,
                 )?;
 
                 // We handle adding the JSON array separator (comma) manually since the encoding is
@@ -212,7 +222,9 @@ impl DatadogMetricsEncoder {
             // simply store it for later, and in `try_encode_pending`, any such pending metrics will be
             // encoded in a single operation.
             DatadogMetricsEndpoint::Sketches => match metric.value() {
-                MetricValue::Sketch { .. } => {}
+                MetricValue::Sketch {
+                    sketch: MetricSketch::AgentDDSketch(_),
+                } => {}
                 value => {
                     return Err(EncoderError::InvalidMetric {
                         expected: "sketches",
@@ -417,11 +429,28 @@ fn encode_timestamp(timestamp: Option<DateTime<Utc>>) -> i64 {
     }
 }
 
+/// Gets the interval, in seconds, that the value of a metric covers.
+///
+/// The interval the metric was collected over is used if the source provided it, as it is what the value actually
+/// covers. Otherwise, we fall back to the time since the previous payload was sent.
+fn encode_interval(metric: &Metric, last_sent: Option<Instant>) -> Option<i64> {
+    match metric.interval_ms() {
+        Some(interval_ms) => Some(i64::from(cmp::max(
+            interval_ms.get().saturating_add(500) / 1000,
+            1,
+        ))),
+        None => last_sent
+            .map(|then| then.elapsed())
+            .map(|d| d.as_secs().try_into().unwrap_or(i64::MAX)),
+    }
+}
+
 fn generate_series_metrics(
     metric: &Metric,
     default_namespace: &Option<Arc<str>>,
     log_schema: &'static LogSchema,
     last_sent: Option<Instant>,
+    counter_submission: CounterSubmission,
 ) -> Result<Vec<DatadogSeriesMetric>, EncoderError> {
     let name = get_namespaced_name(metric, default_namespace);
 
@@ -431,26 +460,44 @@ fn generate_series_metrics(
     let device = tags.remove("device");
     let ts = encode_timestamp(metric.timestamp());
     let tags = Some(encode_tags(&tags));
-    let interval = last_sent
-        .map(|then| then.elapsed())
-        .map(|d| d.as_secs().try_into().unwrap_or(i64::MAX));
+    let interval = encode_interval(metric, last_sent);
 
     let results = match metric.value() {
-        MetricValue::Counter { value } => vec![DatadogSeriesMetric {
+        MetricValue::Counter { value } => {
+            let (r#type, value) = match (counter_submission, interval) {
+                (CounterSubmission::Rate, Some(interval)) if interval > 0 => {
+                    (DatadogMetricType::Rate, *value / interval as f64)
+                }
+                _ => (DatadogMetricType::Count, *value),
+            };
+            vec![DatadogSeriesMetric {
+                metric: name,
+                r#type,
+                interval,
+                points: vec![DatadogPoint(ts, value)],
+                tags,
+                host,
+                source_type_name,
+                device,
+            }]
+        }
+        MetricValue::Set { values } => vec![DatadogSeriesMetric {
             metric: name,
-            r#type: DatadogMetricType::Count,
-            interval,
-            points: vec![DatadogPoint(ts, *value)],
+            r#type: DatadogMetricType::Gauge,
+            interval: None,
+            points: vec![DatadogPoint(ts, values.len() as f64)],
             tags,
             host,
             source_type_name,
             device,
         }],
-        MetricValue::Set { values } => vec![DatadogSeriesMetric {
+        MetricValue::Sketch {
+            sketch: MetricSketch::HyperLogLog(hll),
+        } => vec![DatadogSeriesMetric {
             metric: name,
             r#type: DatadogMetricType::Gauge,
             interval: None,
-            points: vec![DatadogPoint(ts, values.len() as f64)],
+            points: vec![DatadogPoint(ts, hll.estimate().round())],
             tags,
             host,
             source_type_name,
@@ -539,6 +586,8 @@ where
 
                     sketches.push(sketch);
                 }
+                // Approximate sets are sent to the series endpoint.
+                MetricSketch::HyperLogLog(_) => unreachable!(),
             },
             // We filter out non-sketch metrics during `encode_single_metric` if we're targeting
             // the sketches endpoint.
@@ -655,26 +704,35 @@ mod tests {
     use std::{
         collections::BTreeMap,
         io::{self, copy},
+        num::NonZeroU32,
     };
 
-    use bytes::{BufMut, Bytes, BytesMut};
+    use bytes::{Buf, BufMut, Bytes, BytesMut};
     use chrono::{DateTime, TimeZone, Utc};
     use flate2::read::ZlibDecoder;
     use proptest::{
         arbitrary::any, collection::btree_map, num::f64::POSITIVE as ARB_POSITIVE_F64, prop_assert,
         proptest, strategy::Strategy, string::string_regex,
     };
+    use prost::Message;
     use vector_core::{
+        config::log_schema,
         event::{Metric, MetricKind, MetricValue},
         metrics::AgentDDSketch,
     };
 
     use super::{
-        encode_tags, encode_timestamp, get_compressor, max_compression_overhead_len,
-        max_uncompressed_header_len, validate_payload_size_limits, write_payload_footer,
-        write_payload_header, DatadogMetricsEncoder,
+        ddmetric_proto, encode_tags, encode_timestamp, generate_series_metrics, get_compressor,
+        max_compression_overhead_len, max_uncompressed_header_len, validate_payload_size_limits,
+        write_payload_footer, write_payload_header, DatadogMetricsEncoder,
+    };
+    use crate::{
+        common::datadog::{DatadogMetricType, DatadogPoint},
+        sinks::datadog::metrics::{
+            config::{CounterSubmission, DatadogMetricsEndpoint},
+            encoder::EncoderError,
+        },
     };
-    use crate::sinks::datadog::metrics::{config::DatadogMetricsEndpoint, encoder::EncoderError};
 
     fn get_simple_counter() -> Metric {
         let value = MetricValue::Counter { value: 3.14 };
@@ -812,6 +870,75 @@ mod tests {
         assert_eq!(70, raw_bytes);
     }
 
+    #[test]
+    fn counter_interval_comes_from_metric() {
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 20.0 },
+        )
+        .with_timestamp(Some(ts()))
+        .with_interval_ms(NonZeroU32::new(10_000));
+
+        let series = generate_series_metrics(
+            &counter,
+            &None,
+            log_schema(),
+            None,
+            CounterSubmission::Count,
+        )
+        .unwrap();
+        assert_eq!(series[0].r#type, DatadogMetricType::Count);
+        assert_eq!(series[0].interval, Some(10));
+        assert_eq!(series[0].points, vec![DatadogPoint(1542182950, 20.0)]);
+
+        let series =
+            generate_series_metrics(&counter, &None, log_schema(), None, CounterSubmission::Rate)
+                .unwrap();
+        assert_eq!(series[0].r#type, DatadogMetricType::Rate);
+        assert_eq!(series[0].interval, Some(10));
+        assert_eq!(series[0].points, vec![DatadogPoint(1542182950, 2.0)]);
+
+        // Without a known interval, counters can't be turned into rates.
+        let series = generate_series_metrics(
+            &counter.with_interval_ms(None),
+            &None,
+            log_schema(),
+            None,
+            CounterSubmission::Rate,
+        )
+        .unwrap();
+        assert_eq!(series[0].r#type, DatadogMetricType::Count);
+        assert_eq!(series[0].interval, None);
+    }
+
+    #[test]
+    fn sketches_are_encoded_without_rebucketing() {
+        let mut ddsketch = AgentDDSketch::with_agent_defaults();
+        ddsketch.insert_many(&[1.0, 2.0, 2.0, 150.0, 9000.0]);
+        let (bins, counts) = ddsketch.bin_map().into_parts();
+
+        let mut encoder = DatadogMetricsEncoder::new(DatadogMetricsEndpoint::Sketches, None)
+            .expect("default payload size limits should be valid");
+        let sketch = Metric::new("latency", MetricKind::Incremental, ddsketch.into())
+            .with_timestamp(Some(ts()));
+        assert_eq!(encoder.try_encode(sketch).unwrap(), None);
+        let (payload, _, _) = encoder.finish().unwrap();
+
+        let payload = decompress_payload(payload).unwrap();
+        let decoded = ddmetric_proto::SketchPayload::decode(payload.chunk()).unwrap();
+        let dogsketch = &decoded.sketches[0].dogsketches[0];
+        assert_eq!(dogsketch.cnt, 5);
+        assert_eq!(
+            dogsketch.k,
+            bins.into_iter().map(i32::from).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            dogsketch.n,
+            counts.into_iter().map(u32::from).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn payload_size_limits() {
         // Get the maximum length of the header/trailer data.
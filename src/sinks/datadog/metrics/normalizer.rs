@@ -1,5 +1,5 @@
 use vector_core::{
-    event::{Metric, MetricValue},
+    event::{metric::MetricSketch, Metric, MetricValue},
     metrics::AgentDDSketch,
 };
 
@@ -27,9 +27,15 @@ impl MetricNormalize for DatadogMetricsNormalizer {
                 .make_incremental(metric)
                 .filter(|metric| !metric.value().is_empty())
                 .and_then(|metric| AgentDDSketch::transform_to_sketch(metric).ok()),
-            // Sketches cannot be subtracted from one another, so we treat them as implicitly
-            // incremental, and just update the metric type.
-            MetricValue::Sketch { .. } => Some(metric.into_incremental()),
+            // Sketches provided by the source, such as those forwarded by the Datadog Agent, are
+            // sent as-is rather than being re-bucketed. They cannot be subtracted from one another,
+            // so we treat them as implicitly incremental, and just update the metric type.
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(_),
+            } => Some(metric.into_incremental()),
+            MetricValue::Sketch {
+                sketch: MetricSketch::HyperLogLog(_),
+            } => Some(metric.into_incremental()),
             // Otherwise, ensure that it's incremental.
             _ => state.make_incremental(metric),
         }
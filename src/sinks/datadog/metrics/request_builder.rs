@@ -5,7 +5,7 @@ use std::sync::Arc;
 use vector_core::event::{EventFinalizers, Finalizable, Metric};
 
 use super::{
-    config::{DatadogMetricsEndpoint, DatadogMetricsEndpointConfiguration},
+    config::{CounterSubmission, DatadogMetricsEndpoint, DatadogMetricsEndpointConfiguration},
     encoder::{CreateError, DatadogMetricsEncoder, EncoderError, FinishError},
     service::DatadogMetricsRequest,
 };
@@ -129,13 +129,15 @@ impl DatadogMetricsRequestBuilder {
     pub fn new(
         endpoint_configuration: DatadogMetricsEndpointConfiguration,
         default_namespace: Option<String>,
+        counter_submission: CounterSubmission,
     ) -> Result<Self, RequestBuilderError> {
         Ok(Self {
             endpoint_configuration,
             series_encoder: DatadogMetricsEncoder::new(
                 DatadogMetricsEndpoint::Series,
                 default_namespace.clone(),
-            )?,
+            )?
+            .with_counter_submission(counter_submission),
             sketches_encoder: DatadogMetricsEncoder::new(
                 DatadogMetricsEndpoint::Sketches,
                 default_namespace,
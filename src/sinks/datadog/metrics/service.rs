@@ -51,6 +51,10 @@ impl RetryLogic for DatadogMetricsRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status_code)
+    }
 }
 
 /// Generalized request for sending metrics to the Datadog metrics endpoints.
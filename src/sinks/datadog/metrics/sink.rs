@@ -8,7 +8,7 @@ use futures_util::{
 };
 use tower::Service;
 use vector_core::{
-    event::{Event, Metric, MetricValue},
+    event::{metric::MetricSketch, Event, Metric, MetricValue},
     partition::Partitioner,
     sink::StreamSink,
     stream::{BatcherSettings, DriverResponse},
@@ -23,7 +23,7 @@ use crate::{
     sinks::util::{
         buffer::metrics::sort::sort_for_compression,
         buffer::metrics::{AggregatedSummarySplitter, MetricSplitter},
-        SinkBuilderExt,
+        SinkBuilderExt, TagRules,
     },
 };
 
@@ -46,7 +46,12 @@ impl Partitioner for DatadogMetricsTypePartitioner {
             MetricValue::Distribution { .. } => DatadogMetricsEndpoint::Sketches,
             MetricValue::AggregatedHistogram { .. } => DatadogMetricsEndpoint::Sketches,
             MetricValue::AggregatedSummary { .. } => DatadogMetricsEndpoint::Series,
-            MetricValue::Sketch { .. } => DatadogMetricsEndpoint::Sketches,
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(_),
+            } => DatadogMetricsEndpoint::Sketches,
+            MetricValue::Sketch {
+                sketch: MetricSketch::HyperLogLog(_),
+            } => DatadogMetricsEndpoint::Series,
         };
         (item.metadata().datadog_api_key(), endpoint)
     }
@@ -56,6 +61,7 @@ pub(crate) struct DatadogMetricsSink<S> {
     service: S,
     request_builder: DatadogMetricsRequestBuilder,
     batch_settings: BatcherSettings,
+    tag_rules: TagRules,
 }
 
 impl<S> DatadogMetricsSink<S>
@@ -70,20 +76,29 @@ where
         service: S,
         request_builder: DatadogMetricsRequestBuilder,
         batch_settings: BatcherSettings,
+        tag_rules: TagRules,
     ) -> Self {
         DatadogMetricsSink {
             service,
             request_builder,
             batch_settings,
+            tag_rules,
         }
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut splitter: MetricSplitter<AggregatedSummarySplitter> = MetricSplitter::default();
+        let tag_rules = self.tag_rules;
 
         let sink = input
             // Convert `Event` to `Metric` so we don't have to deal with constant conversions.
             .filter_map(|event| ready(event.try_into_metric()))
+            // Rewrite the tags before normalization, so that series which end up with the same tags are normalized
+            // together.
+            .map(|mut metric| {
+                tag_rules.apply(&mut metric);
+                metric
+            })
             // Split aggregated summaries into individual metrics for count, sum, and the quantiles, which lets us
             // ensure that aggregated summaries effectively make it through normalization, as we can't actually
             // normalize them and so they would be dropped during normalization otherwise.
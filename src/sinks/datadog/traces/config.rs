@@ -12,7 +12,7 @@ use super::service::TraceApiRetry;
 use crate::{
     common::datadog::get_base_domain,
     config::{GenerateConfig, Input, SinkConfig, SinkContext},
-    http::HttpClient,
+    http::{ConnectionPoolConfig, HttpClient},
     sinks::{
         datadog::{
             get_api_validate_endpoint, healthcheck,
@@ -161,7 +161,11 @@ impl DatadogTracesConfig {
         Ok(healthcheck(client, validate_endpoint, self.default_api_key.clone()).boxed())
     }
 
-    pub fn build_client(&self, proxy: &ProxyConfig) -> crate::Result<HttpClient> {
+    pub fn build_client(
+        &self,
+        proxy: &ProxyConfig,
+        connection_pool: &ConnectionPoolConfig,
+    ) -> crate::Result<HttpClient> {
         let tls_settings = MaybeTlsSettings::from_config(
             &Some(
                 self.tls
@@ -170,7 +174,11 @@ impl DatadogTracesConfig {
             ),
             false,
         )?;
-        Ok(HttpClient::new(tls_settings, proxy)?)
+        Ok(HttpClient::new_with_connection_pool(
+            tls_settings,
+            proxy,
+            connection_pool,
+        )?)
     }
 }
 
@@ -178,7 +186,7 @@ impl DatadogTracesConfig {
 #[typetag::serde(name = "datadog_traces")]
 impl SinkConfig for DatadogTracesConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = self.build_client(&cx.proxy)?;
+        let client = self.build_client(&cx.proxy, cx.connection_pool())?;
         let healthcheck = self.build_healthcheck(client.clone())?;
         let sink = self.build_sink(client)?;
         Ok((sink, healthcheck))
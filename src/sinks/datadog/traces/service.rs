@@ -52,6 +52,10 @@ impl RetryLogic for TraceApiRetry {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status_code)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +56,7 @@ use crate::{
             config::{
                 create_service, S3CannedAcl, S3RetryLogic, S3ServerSideEncryption, S3StorageClass,
             },
+            partitioner::S3KeyPartitioner,
             service::{S3Metadata, S3Request, S3Service},
             sink::S3Sink,
         },
@@ -229,7 +230,8 @@ impl DatadogArchivesSinkConfig {
                 let auth = gcs_config.auth.build(Scope::DevStorageReadWrite).await?;
                 let base_url = format!("{}{}/", BASE_URL, self.bucket);
                 let tls = TlsSettings::from_options(&self.tls)?;
-                let client = HttpClient::new(tls, cx.proxy())?;
+                let client =
+                    HttpClient::new_with_connection_pool(tls, cx.proxy(), cx.connection_pool())?;
                 let healthcheck = gcs_common::config::build_healthcheck(
                     self.bucket.clone(),
                     client.clone(),
@@ -273,7 +275,10 @@ impl DatadogArchivesSinkConfig {
             .into_batcher_settings()
             .expect("invalid batch settings");
 
-        let partitioner = DatadogArchivesSinkConfig::build_partitioner();
+        let partitioner = S3KeyPartitioner::new(
+            Template::try_from(KEY_TEMPLATE).expect("invalid object key format"),
+            None,
+        );
 
         let s3_config = self
             .aws_s3
@@ -287,7 +292,7 @@ impl DatadogArchivesSinkConfig {
             self.encoding.clone(),
         );
 
-        let sink = S3Sink::new(service, request_builder, partitioner, batcher_settings);
+        let sink = S3Sink::new(service, request_builder, partitioner, batcher_settings, None);
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
@@ -295,7 +295,11 @@ impl SinkConfig for ElasticsearchConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let common = ElasticsearchCommon::parse_config(self).await?;
 
-        let http_client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
+        let http_client = HttpClient::new_with_connection_pool(
+            common.tls_settings.clone(),
+            cx.proxy(),
+            cx.connection_pool(),
+        )?;
         let batch_settings = self.batch.into_batcher_settings()?;
 
         let request_limits = self
@@ -328,7 +332,11 @@ impl SinkConfig for ElasticsearchConfig {
             id_key_field: self.id_key.clone(),
         };
 
-        let client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
+        let client = HttpClient::new_with_connection_pool(
+            common.tls_settings.clone(),
+            cx.proxy(),
+            cx.connection_pool(),
+        )?;
         let healthcheck = common.healthcheck(client).boxed();
         let stream = VectorSink::from_event_streamsink(sink);
         Ok((stream, healthcheck))
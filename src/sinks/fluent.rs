@@ -0,0 +1,739 @@
+//! A sink speaking Fluentd's forward protocol, sending batches as `PackedForward` messages.
+//!
+//! https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+
+use std::{
+    io::{self, Write},
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use flate2::{write::GzEncoder, Compression};
+use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Mutex,
+    time::timeout,
+};
+use tower::{Service, ServiceBuilder};
+use vector_common::internal_event::BytesSent;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    codecs::Transformer,
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig,
+        SinkContext, SinkDescription,
+    },
+    event::{Event, Value},
+    internal_events::{FluentSendError, TemplateRenderingError},
+    sinks::util::{
+        batch::BatchConfig,
+        retries::{RetryAction, RetryLogic},
+        tcp::{TcpConnector, TcpError},
+        BatchSink, Concurrency, EncodedEvent, EncodedLength, ServiceBuilderExt, SinkBatchSettings,
+        SinkBuildError, TowerRequestConfig, VecBuffer,
+    },
+    tcp::TcpKeepaliveConfig,
+    template::Template,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsEnableableConfig},
+};
+
+/// The largest acknowledgement accepted from the server.
+const MAX_ACK_BYTES: usize = 64 * 1024;
+
+inventory::submit! {
+    SinkDescription::new::<FluentSinkConfig>("fluent")
+}
+
+#[derive(Debug, Snafu)]
+pub enum FluentError {
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: TcpError },
+    #[snafu(display("Failed to send message: {}", source))]
+    Send { source: io::Error },
+    #[snafu(display("No acknowledgement received for chunk {:?} within the timeout", chunk))]
+    AckTimeout { chunk: String },
+    #[snafu(display("Expected acknowledgement for chunk {:?}, got {:?}", chunk, ack))]
+    AckMismatch { chunk: String, ack: String },
+    #[snafu(display("Invalid acknowledgement: {}", reason))]
+    InvalidAck { reason: &'static str },
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FluentDefaultBatchSettings;
+
+impl SinkBatchSettings for FluentDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(1_048_576);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSinkConfig {
+    address: String,
+    tag: Template,
+    #[serde(default)]
+    compression: FluentCompression,
+    #[serde(default = "crate::serde::default_true")]
+    require_ack: bool,
+    #[serde(default = "default_ack_timeout_secs")]
+    ack_timeout_secs: u64,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsEnableableConfig>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    encoding: Transformer,
+    #[serde(default)]
+    batch: BatchConfig<FluentDefaultBatchSettings>,
+    #[serde(default)]
+    request: TowerRequestConfig,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// How the entries of each message are compressed.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FluentCompression {
+    /// The entries are sent as is.
+    #[derivative(Default)]
+    None,
+    /// The entries are compressed with gzip, as a `CompressedPackedForward` message.
+    Gzip,
+}
+
+const fn default_ack_timeout_secs() -> u64 {
+    30
+}
+
+impl GenerateConfig for FluentSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:24224"
+            tag = "vector.{{ host }}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SinkConfig for FluentSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let connector = TcpConnector::new(host, port, self.keepalive, tls, None);
+
+        let healthcheck = {
+            let connector = connector.clone();
+            async move { connector.healthcheck().await }.boxed()
+        };
+
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            concurrency: Concurrency::Fixed(1),
+            ..Default::default()
+        });
+        let batch = self.batch.into_batch_settings()?;
+
+        let service = FluentService {
+            connector,
+            connection: Arc::new(Mutex::new(None)),
+            require_ack: self.require_ack,
+            ack_timeout: Duration::from_secs(self.ack_timeout_secs),
+        };
+        // The messages are built before the retries, so that a retried message keeps its chunk ID
+        // and the server can tell it's a duplicate.
+        let compression = self.compression;
+        let service = ServiceBuilder::new()
+            .map(move |entries| Message::build(entries, compression))
+            .settings(request, FluentRetryLogic)
+            .service(service);
+
+        let tag = self.tag.clone();
+        let transformer = self.encoding.clone();
+        let sink = BatchSink::new(service, VecBuffer::new(batch.size), batch.timeout)
+            .with_flat_map(move |event| {
+                stream::iter(encode_event(event, &tag, &transformer)).map(Ok)
+            })
+            .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
+
+        Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "fluent"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+/// A single event, encoded as a forward protocol entry: `[time, record]`.
+#[derive(Debug, Clone)]
+struct FluentEntry {
+    tag: String,
+    entry: Bytes,
+}
+
+impl EncodedLength for FluentEntry {
+    fn encoded_length(&self) -> usize {
+        self.entry.len()
+    }
+}
+
+impl ByteSizeOf for FluentEntry {
+    fn allocated_bytes(&self) -> usize {
+        self.tag.len() + self.entry.len()
+    }
+}
+
+fn encode_event(
+    mut event: Event,
+    tag: &Template,
+    transformer: &Transformer,
+) -> Option<EncodedEvent<FluentEntry>> {
+    let tag = tag
+        .render_string(&event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("tag"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
+
+    let byte_size = event.size_of();
+    let finalizers = event.metadata_mut().take_finalizers();
+
+    // The timestamp is sent as the entry's time rather than as part of the record.
+    let timestamp = match event.as_mut_log().remove(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => timestamp,
+        _ => Utc::now(),
+    };
+    transformer.transform(&mut event);
+    let (record, _) = event.into_log().into_parts();
+
+    let entry = rmpv::Value::Array(vec![event_time(timestamp), to_msgpack(record)]);
+    let mut buf = BytesMut::new().writer();
+    rmpv::encode::write_value(&mut buf, &entry).expect("writing to memory can't fail");
+
+    Some(EncodedEvent {
+        item: FluentEntry {
+            tag,
+            entry: buf.into_inner().freeze(),
+        },
+        finalizers,
+        byte_size,
+    })
+}
+
+/// The `EventTime` extension: seconds and nanoseconds, as big-endian 32-bit integers.
+fn event_time(timestamp: DateTime<Utc>) -> rmpv::Value {
+    let mut bytes = Vec::with_capacity(8);
+    bytes.extend_from_slice(&(timestamp.timestamp() as u32).to_be_bytes());
+    bytes.extend_from_slice(&timestamp.timestamp_subsec_nanos().to_be_bytes());
+    rmpv::Value::Ext(0, bytes)
+}
+
+fn to_msgpack(value: Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => string.into(),
+            Err(error) => rmpv::Value::Binary(error.into_bytes()),
+        },
+        Value::Regex(regex) => regex.as_str().into(),
+        Value::Integer(integer) => integer.into(),
+        Value::Float(float) => float.into_inner().into(),
+        Value::Boolean(boolean) => boolean.into(),
+        Value::Timestamp(timestamp) => timestamp
+            .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+            .into(),
+        Value::Object(object) => rmpv::Value::Map(
+            object
+                .into_iter()
+                .map(|(key, value)| (key.into(), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(array) => rmpv::Value::Array(array.into_iter().map(to_msgpack).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+/// A `PackedForward` message, with the chunk ID the server acknowledges it with.
+#[derive(Clone, Debug)]
+struct Message {
+    chunk: String,
+    bytes: Bytes,
+}
+
+impl Message {
+    /// Builds one message per tag, in the order the tags first appear in the batch.
+    fn build(entries: Vec<FluentEntry>, compression: FluentCompression) -> Vec<Message> {
+        let mut tags: Vec<(String, usize, Vec<u8>)> = Vec::new();
+        for FluentEntry { tag, entry } in entries {
+            match tags.iter_mut().find(|(existing, _, _)| *existing == tag) {
+                Some((_, size, buf)) => {
+                    *size += 1;
+                    buf.extend_from_slice(&entry);
+                }
+                None => tags.push((tag, 1, entry.to_vec())),
+            }
+        }
+
+        tags.into_iter()
+            .map(|(tag, size, entries)| Message::new(tag, size, entries, compression))
+            .collect()
+    }
+
+    fn new(tag: String, size: usize, entries: Vec<u8>, compression: FluentCompression) -> Self {
+        let chunk = base64::encode(thread_rng().gen::<[u8; 16]>());
+        let mut options = vec![
+            ("size".into(), (size as u64).into()),
+            ("chunk".into(), chunk.as_str().into()),
+        ];
+        let entries = match compression {
+            FluentCompression::None => entries,
+            FluentCompression::Gzip => {
+                options.push(("compressed".into(), "gzip".into()));
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder
+                    .write_all(&entries)
+                    .expect("writing to memory can't fail");
+                encoder.finish().expect("writing to memory can't fail")
+            }
+        };
+
+        let message = rmpv::Value::Array(vec![
+            tag.into(),
+            rmpv::Value::Binary(entries),
+            rmpv::Value::Map(options),
+        ]);
+        let mut bytes = BytesMut::new().writer();
+        rmpv::encode::write_value(&mut bytes, &message).expect("writing to memory can't fail");
+        Self {
+            chunk,
+            bytes: bytes.into_inner().freeze(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FluentRetryLogic;
+
+impl RetryLogic for FluentRetryLogic {
+    type Error = FluentError;
+    type Response = ();
+
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+
+    fn should_retry_response(&self, _response: &Self::Response) -> RetryAction {
+        RetryAction::Successful
+    }
+}
+
+/// Sends messages over a single connection, which is reopened after any failure.
+#[derive(Clone)]
+struct FluentService {
+    connector: TcpConnector,
+    connection: Arc<Mutex<Option<MaybeTlsStream<TcpStream>>>>,
+    require_ack: bool,
+    ack_timeout: Duration,
+}
+
+impl FluentService {
+    async fn send(self, messages: Vec<Message>) -> Result<(), FluentError> {
+        let mut connection = self.connection.lock().await;
+        let result = async {
+            if connection.is_none() {
+                *connection = Some(self.connector.connect().await.context(ConnectSnafu)?);
+            }
+            let socket = connection.as_mut().expect("connected above");
+
+            for message in &messages {
+                socket.write_all(&message.bytes).await.context(SendSnafu)?;
+                socket.flush().await.context(SendSnafu)?;
+                emit!(BytesSent {
+                    byte_size: message.bytes.len(),
+                    protocol: "tcp",
+                });
+
+                if self.require_ack {
+                    let ack =
+                        timeout(self.ack_timeout, read_ack(socket))
+                            .await
+                            .map_err(|_| FluentError::AckTimeout {
+                                chunk: message.chunk.clone(),
+                            })??;
+                    if ack != message.chunk {
+                        return Err(FluentError::AckMismatch {
+                            chunk: message.chunk.clone(),
+                            ack,
+                        });
+                    }
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(error) = &result {
+            emit!(FluentSendError { error });
+            *connection = None;
+        }
+        result
+    }
+}
+
+impl Service<Vec<Message>> for FluentService {
+    type Response = ();
+    type Error = FluentError;
+    type Future = BoxFuture<'static, Result<(), FluentError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, messages: Vec<Message>) -> Self::Future {
+        Box::pin(self.clone().send(messages))
+    }
+}
+
+/// Reads an acknowledgement, `{"ack": chunk}`, returning its chunk ID. Fluentd sends it as
+/// msgpack, but some servers, like Vector's `fluent` source, send it as JSON.
+async fn read_ack<S: AsyncRead + Unpin>(socket: &mut S) -> Result<String, FluentError> {
+    let mut buf = BytesMut::with_capacity(128);
+    loop {
+        if socket.read_buf(&mut buf).await.context(SendSnafu)? == 0 {
+            return Err(FluentError::Send {
+                source: io::ErrorKind::UnexpectedEof.into(),
+            });
+        }
+        if let Some(ack) = parse_ack(&buf)? {
+            return Ok(ack);
+        }
+        if buf.len() >= MAX_ACK_BYTES {
+            return Err(FluentError::InvalidAck {
+                reason: "acknowledgement too large",
+            });
+        }
+    }
+}
+
+/// Parses a complete acknowledgement, or returns `None` if more bytes are needed.
+fn parse_ack(buf: &[u8]) -> Result<Option<String>, FluentError> {
+    if buf.first() == Some(&b'{') {
+        return match serde_json::from_slice::<serde_json::Value>(buf) {
+            Ok(value) => value
+                .get("ack")
+                .and_then(|ack| ack.as_str())
+                .map(|ack| Some(ack.to_owned()))
+                .ok_or(FluentError::InvalidAck {
+                    reason: "missing \"ack\" key",
+                }),
+            Err(error) if error.is_eof() => Ok(None),
+            Err(_) => Err(FluentError::InvalidAck {
+                reason: "not a JSON object",
+            }),
+        };
+    }
+
+    let mut cursor = io::Cursor::new(buf);
+    match rmpv::decode::read_value(&mut cursor) {
+        Ok(value) => value
+            .as_map()
+            .and_then(|map| {
+                map.iter()
+                    .find(|(key, _)| key.as_str() == Some("ack"))
+                    .and_then(|(_, ack)| ack.as_str())
+            })
+            .map(|ack| Some(ack.to_owned()))
+            .ok_or(FluentError::InvalidAck {
+                reason: "missing \"ack\" key",
+            }),
+        Err(
+            rmpv::decode::Error::InvalidMarkerRead(error)
+            | rmpv::decode::Error::InvalidDataRead(error),
+        ) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(_) => Err(FluentError::InvalidAck {
+            reason: "not a msgpack value",
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use chrono::TimeZone;
+    use flate2::read::GzDecoder;
+    use tokio::net::TcpListener;
+    use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+    use crate::test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        next_addr, trace_init,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentSinkConfig>();
+    }
+
+    /// Decodes a message into its tag, entries, and options.
+    fn decode_message(message: &rmpv::Value) -> (String, Vec<rmpv::Value>, Vec<(String, String)>) {
+        let fields = message.as_array().unwrap();
+        let options = fields[2]
+            .as_map()
+            .unwrap()
+            .iter()
+            .map(|(key, value)| (key.as_str().unwrap().to_owned(), value.to_string()))
+            .collect::<Vec<_>>();
+        let mut entries = fields[1].as_slice().unwrap().to_vec();
+        if options.iter().any(|(key, _)| key == "compressed") {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(&entries[..])
+                .read_to_end(&mut decompressed)
+                .unwrap();
+            entries = decompressed;
+        }
+
+        let mut cursor = io::Cursor::new(&entries[..]);
+        let mut decoded = Vec::new();
+        while (cursor.position() as usize) < entries.len() {
+            decoded.push(rmpv::decode::read_value(&mut cursor).unwrap());
+        }
+        (fields[0].as_str().unwrap().to_owned(), decoded, options)
+    }
+
+    fn entry(tag: &str, message: &str) -> FluentEntry {
+        let mut log = LogEvent::from(message);
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 5, 20).and_hms_nano(20, 17, 28, 161_827_000),
+        );
+        encode_event(
+            log.into(),
+            &Template::try_from(tag).unwrap(),
+            &Default::default(),
+        )
+        .unwrap()
+        .item
+    }
+
+    #[test]
+    fn encodes_entries() {
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 5, 20).and_hms_nano(20, 17, 28, 161_827_000),
+        );
+        log.insert("nested.count", 3);
+        log.insert("host", "example.com");
+        let event = encode_event(
+            log.into(),
+            &Template::try_from("app.{{ host }}").unwrap(),
+            &Default::default(),
+        )
+        .unwrap();
+        assert_eq!(event.item.tag, "app.example.com");
+
+        let entry = rmpv::decode::read_value(&mut &event.item.entry[..]).unwrap();
+        let entry = entry.as_array().unwrap();
+        assert_eq!(
+            entry[0],
+            rmpv::Value::Ext(0, vec![0x62, 0x87, 0xF7, 0x58, 0x09, 0xA5, 0x48, 0xB8])
+        );
+        let record = entry[1].as_map().unwrap();
+        let field = |name: &str| {
+            record
+                .iter()
+                .find(|(key, _)| key.as_str() == Some(name))
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(field("message"), Some("hello".into()));
+        assert_eq!(
+            field("nested"),
+            Some(rmpv::Value::Map(vec![("count".into(), 3.into())]))
+        );
+        assert_eq!(field("timestamp"), None);
+    }
+
+    #[test]
+    fn groups_entries_by_tag() {
+        let entries = vec![entry("a", "1"), entry("b", "2"), entry("a", "3")];
+        let messages = Message::build(entries, FluentCompression::Gzip);
+        assert_eq!(messages.len(), 2);
+
+        let message = rmpv::decode::read_value(&mut &messages[0].bytes[..]).unwrap();
+        let (tag, entries, options) = decode_message(&message);
+        assert_eq!(tag, "a");
+        assert_eq!(entries.len(), 2);
+        assert!(options.contains(&("size".to_owned(), "2".to_owned())));
+        assert!(options.contains(&("chunk".to_owned(), format!("{:?}", messages[0].chunk))));
+        assert!(options.contains(&("compressed".to_owned(), r#""gzip""#.to_owned())));
+    }
+
+    #[test]
+    fn parses_acks() {
+        let mut msgpack = Vec::new();
+        let ack = rmpv::Value::Map(vec![("ack".into(), "chunk".into())]);
+        rmpv::encode::write_value(&mut msgpack, &ack).unwrap();
+        assert_eq!(parse_ack(&msgpack).unwrap(), Some("chunk".to_owned()));
+        assert_eq!(parse_ack(&msgpack[..4]).unwrap(), None);
+
+        assert_eq!(
+            parse_ack(br#"{"ack": "chunk"}"#).unwrap(),
+            Some("chunk".to_owned())
+        );
+        assert_eq!(parse_ack(br#"{"ack": "ch"#).unwrap(), None);
+        assert!(parse_ack(br#"{"nack": "chunk"}"#).is_err());
+    }
+
+    /// Reads a message from the socket, returning it along with its chunk ID.
+    async fn read_message(socket: &mut TcpStream, buf: &mut BytesMut) -> (rmpv::Value, String) {
+        loop {
+            socket.read_buf(buf).await.unwrap();
+            let mut cursor = io::Cursor::new(&buf[..]);
+            let message = match rmpv::decode::read_value(&mut cursor) {
+                Ok(message) => message,
+                Err(_) => continue,
+            };
+            let consumed = cursor.position() as usize;
+            let _ = buf.split_to(consumed);
+
+            let (_, _, options) = decode_message(&message);
+            let chunk = options
+                .iter()
+                .find(|(key, _)| key == "chunk")
+                .map(|(_, chunk)| chunk.trim_matches('"').to_owned())
+                .unwrap();
+            return (message, chunk);
+        }
+    }
+
+    /// Accepts a connection and acknowledges each message, returning the messages received.
+    async fn server(listener: TcpListener, count: usize) -> Vec<rmpv::Value> {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut messages = Vec::new();
+        let mut buf = BytesMut::new();
+        while messages.len() < count {
+            let (message, chunk) = read_message(&mut socket, &mut buf).await;
+            let mut ack = Vec::new();
+            let value = rmpv::Value::Map(vec![("ack".into(), chunk.into())]);
+            rmpv::encode::write_value(&mut ack, &value).unwrap();
+            socket.write_all(&ack).await.unwrap();
+            messages.push(message);
+        }
+        messages
+    }
+
+    #[tokio::test]
+    async fn sends_packed_forward_messages() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(server(listener, 1));
+
+        let config: FluentSinkConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            tag = "vector"
+            compression = "gzip"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        healthcheck.await.unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = (0..3)
+            .map(|i| {
+                Event::from(LogEvent::from(format!("message {}", i)).with_batch_notifier(&batch))
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+        run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let messages = server.await.unwrap();
+        let (tag, entries, _) = decode_message(&messages[0]);
+        assert_eq!(tag, "vector");
+        assert_eq!(entries.len(), 3);
+        let record = entries[2].as_array().unwrap()[1].as_map().unwrap();
+        assert!(record.contains(&("message".into(), "message 2".into())));
+    }
+
+    #[tokio::test]
+    async fn retries_with_the_same_chunk() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            // The first attempt is dropped without an acknowledgement.
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let (_, first) = read_message(&mut socket, &mut BytesMut::new()).await;
+            drop(socket);
+
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let (_, second) = read_message(&mut socket, &mut BytesMut::new()).await;
+            let mut ack = Vec::new();
+            let value = rmpv::Value::Map(vec![("ack".into(), second.as_str().into())]);
+            rmpv::encode::write_value(&mut ack, &value).unwrap();
+            socket.write_all(&ack).await.unwrap();
+            (first, second)
+        });
+
+        let config: FluentSinkConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            tag = "vector"
+            request.retry_initial_backoff_secs = 1
+            "#,
+            addr
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from(LogEvent::from("message").with_batch_notifier(&batch));
+        drop(batch);
+        sink.run_events(vec![event]).await.unwrap();
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let (first, second) = server.await.unwrap();
+        assert_eq!(first, second);
+    }
+}
@@ -5,12 +5,16 @@ use chrono::Utc;
 use codecs::encoding::Framer;
 use http::header::{HeaderName, HeaderValue};
 use indoc::indoc;
+use percent_encoding::{utf8_percent_encode, CONTROLS};
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use snafu::Snafu;
 use tower::ServiceBuilder;
 use uuid::Uuid;
-use vector_core::event::{EventFinalizers, Finalizable};
+use vector_core::{
+    event::{EventFinalizers, Finalizable},
+    partition::Partitioner,
+};
 
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
@@ -21,6 +25,7 @@ use crate::{
     event::Event,
     gcp::{GcpAuthConfig, GcpAuthenticator, Scope},
     http::HttpClient,
+    internal_events::TemplateRenderingError,
     serde::json::to_string,
     sinks::{
         gcs_common::{
@@ -33,7 +38,6 @@ use crate::{
         util::{
             batch::BatchConfig,
             metadata::{RequestMetadata, RequestMetadataBuilder},
-            partitioner::KeyPartitioner,
             request_builder::EncodeResult,
             BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder, ServiceBuilderExt,
             TowerRequestConfig,
@@ -49,6 +53,11 @@ use crate::{
 pub enum GcsHealthcheckError {
     #[snafu(display("key_prefix template parse error: {}", source))]
     KeyPrefixTemplate { source: TemplateParseError },
+    #[snafu(display("metadata {:?} template parse error: {}", name, source))]
+    MetadataTemplate {
+        name: String,
+        source: TemplateParseError,
+    },
 }
 
 const NAME: &str = "gcp_cloud_storage";
@@ -60,6 +69,7 @@ pub struct GcsSinkConfig {
     acl: Option<GcsPredefinedAcl>,
     storage_class: Option<GcsStorageClass>,
     metadata: Option<HashMap<String, String>>,
+    encryption_key_name: Option<String>,
     key_prefix: Option<String>,
     filename_time_format: Option<String>,
     filename_append_uuid: Option<bool>,
@@ -90,6 +100,7 @@ fn default_config(encoding: EncodingConfigWithFraming) -> GcsSinkConfig {
         acl: Default::default(),
         storage_class: Default::default(),
         metadata: Default::default(),
+        encryption_key_name: Default::default(),
         key_prefix: Default::default(),
         filename_time_format: Default::default(),
         filename_append_uuid: Default::default(),
@@ -127,7 +138,7 @@ impl SinkConfig for GcsSinkConfig {
         let auth = self.auth.build(Scope::DevStorageReadWrite).await?;
         let base_url = format!("{}{}/", BASE_URL, self.bucket);
         let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls, cx.proxy())?;
+        let client = HttpClient::new_with_connection_pool(tls, cx.proxy(), cx.connection_pool())?;
         let healthcheck = build_healthcheck(
             self.bucket.clone(),
             client.clone(),
@@ -179,14 +190,96 @@ impl GcsSinkConfig {
         Ok(VectorSink::from_event_streamsink(sink))
     }
 
-    fn key_partitioner(&self) -> crate::Result<KeyPartitioner> {
-        Ok(KeyPartitioner::new(
-            Template::try_from(self.key_prefix.as_deref().unwrap_or("date=%F/"))
-                .context(KeyPrefixTemplateSnafu)?,
-        ))
+    fn key_partitioner(&self) -> crate::Result<GcsKeyPartitioner> {
+        let key_prefix = Template::try_from(self.key_prefix.as_deref().unwrap_or("date=%F/"))
+            .context(KeyPrefixTemplateSnafu)?;
+        let metadata = self
+            .metadata_templates()?
+            .into_iter()
+            .filter(|(_, template)| template.is_dynamic())
+            .collect();
+        Ok(GcsKeyPartitioner {
+            key_prefix,
+            metadata,
+        })
+    }
+
+    fn metadata_templates(&self) -> crate::Result<Vec<(HeaderName, Template)>> {
+        let mut templates = self
+            .metadata
+            .iter()
+            .flatten()
+            .map(|(name, value)| {
+                let template =
+                    Template::try_from(value.as_str()).context(MetadataTemplateSnafu { name })?;
+                Ok((HeaderName::from_bytes(name.as_bytes())?, template))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        // Keep the headers in a stable order, so that equal metadata gives equal partitions.
+        templates.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        Ok(templates)
+    }
+}
+
+/// The key prefix of an object and the custom metadata rendered for its events.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct GcsPartitionKey {
+    key_prefix: String,
+    metadata: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// Partitions events by their key prefix and the values of the templated metadata, so that all
+/// the events of an object share its metadata.
+struct GcsKeyPartitioner {
+    key_prefix: Template,
+    metadata: Vec<(HeaderName, Template)>,
+}
+
+impl Partitioner for GcsKeyPartitioner {
+    type Item = Event;
+    type Key = Option<GcsPartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let key_prefix = self
+            .key_prefix
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("key_prefix"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+        let metadata = self
+            .metadata
+            .iter()
+            .map(|(name, template)| {
+                let value = template.render_string(item).map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some(name.as_str()),
+                        drop_event: true,
+                    });
+                })?;
+                Ok((name.clone(), metadata_value(&value)))
+            })
+            .collect::<Result<_, ()>>()
+            .ok()?;
+
+        Some(GcsPartitionKey {
+            key_prefix,
+            metadata,
+        })
     }
 }
 
+/// Percent-encodes the characters of a rendered metadata value that can't be sent in a header.
+fn metadata_value(value: &str) -> HeaderValue {
+    HeaderValue::from_str(&utf8_percent_encode(value, CONTROLS).to_string())
+        .expect("percent-encoded values are valid header values")
+}
+
 // Settings required to produce a request that do not change per
 // request. All possible values are pre-computed for direct use in
 // producing a request.
@@ -204,8 +297,8 @@ struct RequestSettings {
     compression: Compression,
 }
 
-impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
-    type Metadata = (String, EventFinalizers, RequestMetadataBuilder);
+impl RequestBuilder<(GcsPartitionKey, Vec<Event>)> for RequestSettings {
+    type Metadata = (GcsPartitionKey, EventFinalizers, RequestMetadataBuilder);
     type Events = Vec<Event>;
     type Encoder = (Transformer, Encoder<Framer>);
     type Payload = Bytes;
@@ -220,7 +313,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
         &self.encoder
     }
 
-    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+    fn split_input(&self, input: (GcsPartitionKey, Vec<Event>)) -> (Self::Metadata, Self::Events) {
         let (partition_key, mut events) = input;
         let metadata_builder = RequestMetadata::builder(&events);
         let finalizers = events.take_finalizers();
@@ -246,7 +339,13 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
             }
         };
 
-        let key = format!("{}{}.{}", key, filename, self.extension);
+        let GcsPartitionKey {
+            key_prefix,
+            metadata: rendered_metadata,
+        } = key;
+        let key = format!("{}{}.{}", key_prefix, filename, self.extension);
+        let mut headers = self.headers.clone();
+        headers.extend(rendered_metadata);
 
         let metadata = metadata_builder.build(&payload);
         let body = payload.into_payload();
@@ -260,7 +359,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
                 content_type: self.content_type.clone(),
                 content_encoding: self.content_encoding.clone(),
                 storage_class: self.storage_class.clone(),
-                headers: self.headers.clone(),
+                headers,
             },
             metadata,
         }
@@ -282,16 +381,19 @@ impl RequestSettings {
             .map(|ce| HeaderValue::from_str(&to_string(ce)).unwrap());
         let storage_class = config.storage_class.unwrap_or_default();
         let storage_class = HeaderValue::from_str(&to_string(storage_class)).unwrap();
-        let metadata = config
-            .metadata
-            .as_ref()
-            .map(|metadata| {
-                metadata
-                    .iter()
-                    .map(make_header)
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .unwrap_or_else(|| Ok(vec![]))?;
+        // Templated metadata is rendered by the partitioner, the rest is the same for every object.
+        let mut headers = config
+            .metadata_templates()?
+            .into_iter()
+            .filter(|(_, template)| !template.is_dynamic())
+            .map(|(name, template)| Ok((name, HeaderValue::from_str(template.get_ref())?)))
+            .collect::<crate::Result<Vec<_>>>()?;
+        if let Some(key_name) = &config.encryption_key_name {
+            headers.push((
+                HeaderName::from_static("x-goog-encryption-kms-key-name"),
+                HeaderValue::from_str(key_name)?,
+            ));
+        }
         let extension = config
             .filename_extension
             .clone()
@@ -306,7 +408,7 @@ impl RequestSettings {
             content_type,
             content_encoding,
             storage_class,
-            headers: metadata,
+            headers,
             extension,
             time_format,
             append_uuid,
@@ -316,14 +418,6 @@ impl RequestSettings {
     }
 }
 
-// Make a header pair from a key-value string pair
-fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName, HeaderValue)> {
-    Ok((
-        HeaderName::from_bytes(name.as_bytes())?,
-        HeaderValue::from_str(value)?,
-    ))
-}
-
 #[cfg(test)]
 mod tests {
     use codecs::encoding::FramingConfig;
@@ -381,7 +475,7 @@ mod tests {
             .partition(&Event::Log(event))
             .expect("key wasn't provided");
 
-        assert_eq!(key, "key: value");
+        assert_eq!(key.key_prefix, "key: value");
     }
 
     fn request_settings(sink_config: &GcsSinkConfig) -> RequestSettings {
@@ -428,4 +522,55 @@ mod tests {
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.key, "key/date.log.gz".to_string());
     }
+
+    #[test]
+    fn gcs_build_request_with_metadata() {
+        let sink_config = GcsSinkConfig {
+            metadata: Some(HashMap::from([
+                ("x-goog-meta-team".into(), "{{ team }}".into()),
+                ("x-goog-meta-source".into(), "vector".into()),
+            ])),
+            encryption_key_name: Some("projects/p/locations/global/keyRings/r/cryptoKeys/k".into()),
+            ..default_config((None::<FramingConfig>, TextSerializerConfig::new()).into())
+        };
+        let partitioner = sink_config.key_partitioner().unwrap();
+
+        let mut log = LogEvent::from("hello");
+        log.insert("team", "data\nplatform");
+        let key = partitioner
+            .partition(&log.into())
+            .expect("key wasn't provided");
+        assert_eq!(
+            key.metadata,
+            vec![(
+                HeaderName::from_static("x-goog-meta-team"),
+                HeaderValue::from_static("data%0Aplatform")
+            )]
+        );
+
+        // Events missing fields of the metadata templates are dropped.
+        assert_eq!(partitioner.partition(&LogEvent::from("hello").into()), None);
+
+        let request_settings = request_settings(&sink_config);
+        let (metadata, _events) = request_settings.split_input((key, vec![]));
+        let request =
+            request_settings.build_request(metadata, EncodeResult::uncompressed(Bytes::new()));
+        let headers = request
+            .settings
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.to_str().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            headers,
+            vec![
+                ("x-goog-meta-source", "vector"),
+                (
+                    "x-goog-encryption-kms-key-name",
+                    "projects/p/locations/global/keyRings/r/cryptoKeys/k"
+                ),
+                ("x-goog-meta-team", "data%0Aplatform"),
+            ]
+        );
+    }
 }
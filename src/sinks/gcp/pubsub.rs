@@ -99,7 +99,8 @@ impl SinkConfig for PubsubConfig {
             .into_batch_settings()?;
         let request_settings = self.request.unwrap_with(&Default::default());
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client =
+            HttpClient::new_with_connection_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
 
         let healthcheck = healthcheck(client.clone(), sink.uri("")?, sink.auth.clone()).boxed();
 
@@ -80,7 +80,8 @@ impl SinkConfig for StackdriverConfig {
             ..Default::default()
         });
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client =
+            HttpClient::new_with_connection_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let batch_settings = self.batch.into_batch_settings()?;
 
         let sink = HttpEventSink {
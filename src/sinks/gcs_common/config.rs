@@ -111,4 +111,8 @@ impl RetryLogic for GcsRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.inner.status())
+    }
 }
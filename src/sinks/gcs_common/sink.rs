@@ -1,4 +1,4 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{fmt, hash::Hash, num::NonZeroUsize};
 
 use async_trait::async_trait;
 use futures::stream::BoxStream;
@@ -6,6 +6,7 @@ use futures_util::StreamExt;
 use tower::Service;
 use vector_core::{
     event::Finalizable,
+    partition::Partitioner,
     sink::StreamSink,
     stream::{BatcherSettings, DriverResponse},
 };
@@ -15,18 +16,18 @@ use crate::{
     sinks::util::{partitioner::KeyPartitioner, RequestBuilder, SinkBuilderExt},
 };
 
-pub struct GcsSink<Svc, RB> {
+pub struct GcsSink<Svc, RB, P = KeyPartitioner> {
     service: Svc,
     request_builder: RB,
-    partitioner: KeyPartitioner,
+    partitioner: P,
     batcher_settings: BatcherSettings,
 }
 
-impl<Svc, RB> GcsSink<Svc, RB> {
+impl<Svc, RB, P> GcsSink<Svc, RB, P> {
     pub const fn new(
         service: Svc,
         request_builder: RB,
-        partitioner: KeyPartitioner,
+        partitioner: P,
         batcher_settings: BatcherSettings,
     ) -> Self {
         Self {
@@ -38,15 +39,17 @@ impl<Svc, RB> GcsSink<Svc, RB> {
     }
 }
 
-impl<Svc, RB> GcsSink<Svc, RB>
+impl<Svc, RB, P, K> GcsSink<Svc, RB, P>
 where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
     Svc::Error: fmt::Debug + Into<crate::Error> + Send,
-    RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
+    RB: RequestBuilder<(K, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Debug + Send,
     RB::Request: Finalizable + Send,
+    P: Partitioner<Item = Event, Key = Option<K>> + Unpin + Send,
+    K: Clone + Eq + Hash + Send + 'static,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let partitioner = self.partitioner;
@@ -75,15 +78,17 @@ where
 }
 
 #[async_trait]
-impl<Svc, RB> StreamSink<Event> for GcsSink<Svc, RB>
+impl<Svc, RB, P, K> StreamSink<Event> for GcsSink<Svc, RB, P>
 where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
     Svc::Error: fmt::Debug + Into<crate::Error> + Send,
-    RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
+    RB: RequestBuilder<(K, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Debug + Send,
     RB::Request: Finalizable + Send,
+    P: Partitioner<Item = Event, Key = Option<K>> + Unpin + Send,
+    K: Clone + Eq + Hash + Send + 'static,
 {
     async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
@@ -1,8 +1,12 @@
-use bytes::Bytes;
+use std::io::Write;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::GzEncoder;
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use snafu::Snafu;
 
 use crate::{
     codecs::Transformer,
@@ -12,10 +16,13 @@ use crate::{
     },
     event::{Event, Value},
     http::HttpClient,
+    internal_events::TemplateRenderingError,
     sinks::util::{
-        http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
-        BatchConfig, BoxedRawValue, JsonArrayBuffer, SinkBatchSettings, TowerRequestConfig,
+        http::{HttpEventEncoder, HttpSink, PartitionHttpSink},
+        BatchConfig, BoxedRawValue, Compression, JsonArrayBuffer, PartitionBuffer,
+        PartitionInnerBuffer, SinkBatchSettings, TowerRequestConfig,
     },
+    template::Template,
 };
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,9 +32,14 @@ pub(super) struct HoneycombConfig {
 
     api_key: String,
 
-    // TODO: we probably want to make this a template
-    // but this limits us in how we can do our healthcheck.
-    dataset: String,
+    dataset: Template,
+
+    // The field holding the rate the event was sampled at, which is sent to Honeycomb so that
+    // sampled events are weighted correctly.
+    sample_rate_field: Option<String>,
+
+    #[serde(default)]
+    compression: Compression,
 
     #[serde(default)]
     batch: BatchConfig<HoneycombDefaultBatchSettings>,
@@ -50,7 +62,13 @@ pub(super) struct HoneycombConfig {
 }
 
 fn default_endpoint() -> String {
-    "https://api.honeycomb.io/1/batch".to_string()
+    "https://api.honeycomb.io".to_string()
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Honeycomb only supports gzip compression"))]
+    UnsupportedCompression,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -83,14 +101,18 @@ impl SinkConfig for HoneycombConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if matches!(self.compression, Compression::Zlib(_)) {
+            return Err(Box::new(BuildError::UnsupportedCompression));
+        }
+
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batch_settings()?;
 
-        let buffer = JsonArrayBuffer::new(batch_settings.size);
+        let buffer = PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size));
 
-        let client = HttpClient::new(None, cx.proxy())?;
+        let client = HttpClient::new_with_connection_pool(None, cx.proxy(), cx.connection_pool())?;
 
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             self.clone(),
             buffer,
             request_settings,
@@ -118,11 +140,28 @@ impl SinkConfig for HoneycombConfig {
 }
 
 pub struct HoneycombEventEncoder {
+    dataset: Template,
+    sample_rate_field: Option<String>,
     transformer: Transformer,
 }
 
-impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<serde_json::Value> {
+impl HttpEventEncoder<PartitionInnerBuffer<serde_json::Value, String>> for HoneycombEventEncoder {
+    fn encode_event(
+        &mut self,
+        mut event: Event,
+    ) -> Option<PartitionInnerBuffer<serde_json::Value, String>> {
+        let dataset = self
+            .dataset
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("dataset"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
         self.transformer.transform(&mut event);
         let mut log = event.into_log();
 
@@ -133,57 +172,102 @@ impl HttpEventEncoder<serde_json::Value> for HoneycombEventEncoder {
             chrono::Utc::now()
         };
 
-        let data = json!({
+        let sample_rate = self
+            .sample_rate_field
+            .as_ref()
+            .and_then(|field| log.remove(field.as_str()))
+            .and_then(|value| sample_rate(&value));
+
+        let mut data = json!({
             "timestamp": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
             "data": log.convert_to_fields(),
         });
+        if let Some(sample_rate) = sample_rate {
+            data["samplerate"] = json!(sample_rate);
+        }
 
-        Some(data)
+        Some(PartitionInnerBuffer::new(data, dataset))
+    }
+}
+
+/// Honeycomb expects the sample rate as a positive integer, where a rate of `n` means the event
+/// represents `n` events. Sample rates that aren't positive are ignored.
+fn sample_rate(value: &Value) -> Option<i64> {
+    let sample_rate = match value {
+        Value::Integer(rate) => *rate,
+        Value::Float(rate) => rate.round() as i64,
+        Value::Bytes(rate) => String::from_utf8_lossy(rate).trim().parse().ok()?,
+        _ => return None,
+    };
+    if sample_rate >= 1 {
+        Some(sample_rate)
+    } else {
+        None
     }
 }
 
 #[async_trait::async_trait]
 impl HttpSink for HoneycombConfig {
-    type Input = serde_json::Value;
-    type Output = Vec<BoxedRawValue>;
+    type Input = PartitionInnerBuffer<serde_json::Value, String>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, String>;
     type Encoder = HoneycombEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
         HoneycombEventEncoder {
+            dataset: self.dataset.clone(),
+            sample_rate_field: self.sample_rate_field.clone(),
             transformer: self.encoding.clone(),
         }
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
-        let uri = self.build_uri();
-        let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.clone());
-        let body = crate::serde::json::to_bytes(&events).unwrap().freeze();
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let (events, dataset) = output.into_parts();
+        let uri = self.build_uri(&dataset);
+        let mut request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.clone());
+        let mut body = crate::serde::json::to_bytes(&events).unwrap().freeze();
+
+        if let Compression::Gzip(level) = self.compression {
+            request = request.header("Content-Encoding", "gzip");
+
+            let mut w = GzEncoder::new(BytesMut::new().writer(), level);
+            w.write_all(&body).expect("Writing to Vec can't fail");
+            body = w
+                .finish()
+                .expect("Writing to Vec can't fail")
+                .into_inner()
+                .freeze();
+        }
 
         request.body(body).map_err(Into::into)
     }
 }
 
 impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", self.endpoint, self.dataset);
+    fn build_uri(&self, dataset: &str) -> Uri {
+        let uri = format!(
+            "{}/1/batch/{}",
+            self.endpoint,
+            percent_encoding::utf8_percent_encode(dataset, percent_encoding::NON_ALPHANUMERIC)
+        );
 
         uri.parse::<http::Uri>()
             .expect("This should be a valid uri")
     }
 }
 
+/// Checks that the API key is valid. This uses the auth endpoint rather than sending an empty
+/// batch, as the dataset may only be known once events arrive.
 async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Result<()> {
-    let req = config
-        .build_request(Vec::new())
-        .await?
-        .map(hyper::Body::from);
+    let req = Request::get(format!("{}/1/auth", config.endpoint))
+        .header("X-Honeycomb-Team", config.api_key.clone())
+        .body(hyper::Body::empty())?;
 
     let res = client.send(req).await?;
 
     let status = res.status();
     let body = hyper::body::to_bytes(res.into_body()).await?;
 
-    if status == StatusCode::BAD_REQUEST {
+    if status == StatusCode::OK {
         Ok(())
     } else if status == StatusCode::UNAUTHORIZED {
         let json: serde_json::Value = serde_json::from_slice(&body[..])?;
@@ -211,11 +295,18 @@ async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Resu
 }
 #[cfg(test)]
 mod test {
+    use std::io::Read;
+
     use futures::{future::ready, stream};
     use vector_core::event::{Event, LogEvent};
 
     use crate::{
         config::{GenerateConfig, SinkConfig, SinkContext},
+        sinks::util::{
+            http::{HttpEventEncoder, HttpSink},
+            test::load_sink,
+            BoxedRawValue, PartitionInnerBuffer,
+        },
         test_util::{
             components::{run_and_assert_sink_compliance, SINK_TAGS},
             http::{always_200_response, spawn_blackhole_http_server},
@@ -229,6 +320,87 @@ mod test {
         crate::test_util::test_generate_config::<super::HoneycombConfig>();
     }
 
+    #[test]
+    fn encode_event_with_dataset_and_sample_rate() {
+        let (config, _cx) = load_sink::<HoneycombConfig>(
+            r#"
+            api_key = "mykey"
+            dataset = "{{ service }}"
+            sample_rate_field = "sample_rate"
+            "#,
+        )
+        .unwrap();
+        let mut encoder = config.build_encoder();
+
+        let mut log = LogEvent::from("sampled");
+        log.insert("service", "checkout");
+        log.insert("sample_rate", 10);
+        let (data, dataset) = encoder.encode_event(Event::Log(log)).unwrap().into_parts();
+
+        assert_eq!(dataset, "checkout");
+        assert_eq!(data["samplerate"], 10);
+        assert_eq!(data["data"]["message"], "sampled");
+        assert!(data["data"].get("sample_rate").is_none());
+
+        // Events without the dataset field can't be routed, and are dropped.
+        assert!(encoder
+            .encode_event(Event::Log(LogEvent::from("no service")))
+            .is_none());
+    }
+
+    #[test]
+    fn ignores_invalid_sample_rates() {
+        let (config, _cx) = load_sink::<HoneycombConfig>(
+            r#"
+            api_key = "mykey"
+            dataset = "my-dataset"
+            sample_rate_field = "sample_rate"
+            "#,
+        )
+        .unwrap();
+        let mut encoder = config.build_encoder();
+
+        let mut log = LogEvent::from("sampled");
+        log.insert("sample_rate", 0);
+        let (data, _) = encoder.encode_event(Event::Log(log)).unwrap().into_parts();
+
+        assert!(data.get("samplerate").is_none());
+    }
+
+    #[tokio::test]
+    async fn build_request_compressed() {
+        let (config, _cx) = load_sink::<HoneycombConfig>(
+            r#"
+            api_key = "mykey"
+            dataset = "{{ service }}"
+            compression = "gzip"
+            "#,
+        )
+        .unwrap();
+
+        let events: Vec<BoxedRawValue> = vec![serde_json::value::to_raw_value(
+            &serde_json::json!({"data": {"message": "hello"}}),
+        )
+        .unwrap()];
+        let request = config
+            .build_request(PartitionInnerBuffer::new(events, "my service".into()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            request.uri().to_string(),
+            "https://api.honeycomb.io/1/batch/my%20service"
+        );
+        assert_eq!(request.headers()["Content-Encoding"], "gzip");
+        assert_eq!(request.headers()["X-Honeycomb-Team"], "mykey");
+
+        let mut body = String::new();
+        flate2::read::GzDecoder::new(&request.body()[..])
+            .read_to_string(&mut body)
+            .unwrap();
+        assert_eq!(body, r#"[{"data":{"message":"hello"}}]"#);
+    }
+
     #[tokio::test]
     async fn component_spec_compliance() {
         let mock_endpoint = spawn_blackhole_http_server(always_200_response).await;
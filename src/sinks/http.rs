@@ -1,8 +1,7 @@
-use std::io::Write;
+use std::sync::{Arc, Mutex};
 
 use bytes::{BufMut, Bytes, BytesMut};
 use codecs::encoding::{CharacterDelimitedEncoder, Framer, Serializer};
-use flate2::write::{GzEncoder, ZlibEncoder};
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
@@ -21,12 +20,17 @@ use crate::{
         SinkDescription,
     },
     event::Event,
-    http::{Auth, HttpClient, MaybeAuth},
+    http::{Auth, HttpClient, HttpError, MaybeAuth},
+    internal_events::http_client::HttpCompressionFallback,
     sinks::util::{
         self,
-        http::{BatchedHttpSink, HttpEventEncoder, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{
+            BatchedHttpSink, HttpCompression, HttpEventEncoder, HttpRetryLogic, RequestConfig,
+            RequestContentEncoding,
+        },
+        retries::{RetryAction, RetryLogic},
+        BatchConfig, Compression, RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig,
+        UriSerde, VecBuffer,
     },
     tls::{TlsConfig, TlsSettings},
 };
@@ -54,7 +58,7 @@ pub struct HttpSinkConfig {
     // Deprecated, moved to request.
     pub headers: Option<IndexMap<String, String>>,
     #[serde(default)]
-    pub compression: Compression,
+    pub compression: HttpCompression,
     #[serde(flatten)]
     pub encoding: EncodingConfigWithFraming,
     #[serde(default)]
@@ -102,7 +106,110 @@ impl GenerateConfig for HttpSinkConfig {
 impl HttpSinkConfig {
     fn build_http_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        Ok(HttpClient::new(tls, cx.proxy())?)
+        Ok(HttpClient::new_with_connection_pool(
+            tls,
+            cx.proxy(),
+            cx.connection_pool(),
+        )?)
+    }
+}
+
+/// The compression requests are sent with.
+///
+/// It starts out as the configured compression, and falls back to a more widely supported one
+/// each time the server rejects it with `415 Unsupported Media Type`: zstd and brotli fall back to
+/// gzip, and gzip and zlib to no compression at all.
+#[derive(Clone, Debug, Default)]
+struct NegotiatedCompression {
+    configured: HttpCompression,
+    current: Arc<Mutex<HttpCompression>>,
+}
+
+impl NegotiatedCompression {
+    fn new(configured: HttpCompression) -> Self {
+        Self {
+            configured,
+            current: Arc::new(Mutex::new(configured)),
+        }
+    }
+
+    fn current(&self) -> HttpCompression {
+        *self.current.lock().expect("mutex poisoned")
+    }
+
+    const fn fallback(compression: HttpCompression) -> HttpCompression {
+        match compression {
+            HttpCompression::Zstd(_) | HttpCompression::Brotli(_) => {
+                HttpCompression::gzip_default()
+            }
+            HttpCompression::Common(_) => HttpCompression::Common(Compression::None),
+        }
+    }
+
+    /// Falls back from the rejected content encoding, returning whether the rejected request
+    /// should be sent again with the current compression.
+    ///
+    /// Requests sent concurrently with the same encoding are rejected too, but only the first
+    /// rejection falls back, so that the others don't skip over compressions.
+    fn reject(&self, rejected: &HeaderValue) -> bool {
+        let is_rejected = |compression: HttpCompression| {
+            compression.content_encoding().map(str::as_bytes) == Some(rejected.as_bytes())
+        };
+
+        let mut current = self.current.lock().expect("mutex poisoned");
+        let mut compression = self.configured;
+        while compression != *current {
+            if is_rejected(compression) {
+                // Already fallen back from.
+                return true;
+            }
+            compression = Self::fallback(compression);
+        }
+
+        if !is_rejected(compression) {
+            return false;
+        }
+        let fallback = Self::fallback(compression);
+        emit!(HttpCompressionFallback {
+            rejected: compression.content_encoding().unwrap_or("identity"),
+            fallback: fallback.content_encoding().unwrap_or("identity"),
+        });
+        *current = fallback;
+        true
+    }
+}
+
+/// Retries the requests whose compression the server rejected, once it fell back to another one.
+#[derive(Clone, Debug)]
+struct HttpSinkRetryLogic {
+    compression: NegotiatedCompression,
+}
+
+impl RetryLogic for HttpSinkRetryLogic {
+    type Error = HttpError;
+    type Response = hyper::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        HttpRetryLogic.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        if response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE {
+            if let Some(RequestContentEncoding(content_encoding)) =
+                response.extensions().get::<RequestContentEncoding>()
+            {
+                if self.compression.reject(content_encoding) {
+                    return RetryAction::Retry(
+                        format!("unsupported content encoding {:?}", content_encoding).into(),
+                    );
+                }
+            }
+        }
+        HttpRetryLogic.should_retry_response(response)
+    }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        HttpRetryLogic.response_status(response)
     }
 }
 
@@ -110,7 +217,7 @@ struct HttpSink {
     pub uri: UriSerde,
     pub method: Option<HttpMethod>,
     pub auth: Option<Auth>,
-    pub compression: Compression,
+    pub compression: NegotiatedCompression,
     pub transformer: Transformer,
     pub encoder: Encoder<Framer>,
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
@@ -157,11 +264,17 @@ impl SinkConfig for HttpSinkConfig {
         let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
+        let uri = self.uri.with_default_parts();
+        let compression = NegotiatedCompression::new(self.compression);
+        let retry_logic = HttpSinkRetryLogic {
+            compression: compression.clone(),
+        };
+
         let sink = HttpSink {
-            uri: self.uri.with_default_parts(),
+            uri,
             method: self.method.clone(),
             auth: self.auth.choose_one(&self.uri.auth)?,
-            compression: self.compression,
+            compression,
             transformer: self.encoding.transformer(),
             encoder,
             batch: self.batch,
@@ -174,9 +287,10 @@ impl SinkConfig for HttpSinkConfig {
             .unwrap_with(&TowerRequestConfig::default());
 
         let batch = sink.batch.into_batch_settings()?;
-        let sink = BatchedHttpSink::new(
+        let sink = BatchedHttpSink::with_splitting(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            VecBuffer::new(batch.size),
+            retry_logic,
             request,
             batch.timeout,
             client,
@@ -206,21 +320,23 @@ pub struct HttpSinkEventEncoder {
     transformer: Transformer,
 }
 
-impl HttpEventEncoder<BytesMut> for HttpSinkEventEncoder {
-    fn encode_event(&mut self, mut event: Event) -> Option<BytesMut> {
+impl HttpEventEncoder<Bytes> for HttpSinkEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<Bytes> {
         self.transformer.transform(&mut event);
 
         let mut body = BytesMut::new();
         self.encoder.encode(event, &mut body).ok()?;
 
-        Some(body)
+        Some(body.freeze())
     }
 }
 
 #[async_trait::async_trait]
 impl util::http::HttpSink for HttpSink {
-    type Input = BytesMut;
-    type Output = BytesMut;
+    type Input = Bytes;
+    // The encoded events are kept apart until the request is built, so that batches the server
+    // rejects as too large can be split.
+    type Output = Vec<Bytes>;
     type Encoder = HttpSinkEventEncoder;
 
     fn build_encoder(&self) -> Self::Encoder {
@@ -230,7 +346,12 @@ impl util::http::HttpSink for HttpSink {
         }
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Bytes>> {
+    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let mut body = BytesMut::with_capacity(events.iter().map(Bytes::len).sum());
+        for event in events {
+            body.extend_from_slice(&event);
+        }
+
         let method = match &self.method.clone().unwrap_or(HttpMethod::Post) {
             HttpMethod::Get => Method::GET,
             HttpMethod::Head => Method::HEAD,
@@ -281,31 +402,21 @@ impl util::http::HttpSink for HttpSink {
             builder = builder.header("Content-Type", content_type);
         }
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
-
-                let buffer = BytesMut::new();
-                let mut w = GzEncoder::new(buffer.writer(), level);
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
-            }
-            Compression::Zlib(level) => {
-                builder = builder.header("Content-Encoding", "deflate");
-
-                let buffer = BytesMut::new();
-                let mut w = ZlibEncoder::new(buffer.writer(), level);
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail").into_inner();
+        // The compression is chosen per request, as it falls back when the server rejects it.
+        let compression = self.compression.current();
+        let body = match compression.content_encoding() {
+            Some(content_encoding) => {
+                builder = builder.header("Content-Encoding", content_encoding);
+                compression.compress(&body)
             }
-            Compression::None => {}
-        }
+            None => body.freeze(),
+        };
 
         for (header, value) in self.request.headers.iter() {
             builder = builder.header(header.as_str(), value.as_str());
         }
 
-        let mut request = builder.body(body.freeze()).unwrap();
+        let mut request = builder.body(body).unwrap();
 
         if let Some(auth) = &self.auth {
             auth.apply(&mut request);
@@ -620,6 +731,125 @@ mod tests {
         assert_eq!(input_lines, output_lines);
     }
 
+    #[tokio::test]
+    async fn splits_batches_rejected_as_too_large() {
+        const NUM_LINES: usize = 1000;
+
+        let (in_addr, sink) = build_sink("").await;
+
+        let counter = Arc::new(atomic::AtomicUsize::new(0));
+        let in_counter = Arc::clone(&counter);
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            let count = in_counter.fetch_add(1, atomic::Ordering::Relaxed);
+            if count == 0 {
+                // Reject the first request as too large, so its batch is split in two
+                Response::builder()
+                    .status(StatusCode::PAYLOAD_TOO_LARGE)
+                    .body(Body::empty())
+                    .unwrap_or_else(|_| unreachable!())
+            } else {
+                Response::new(Body::empty())
+            }
+        });
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input_lines, events) = random_lines_with_stream(100, NUM_LINES, Some(batch));
+        let pump = sink.run(events);
+
+        tokio::spawn(server);
+
+        pump.await.unwrap();
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let output_lines = get_received(rx, |parts| {
+            assert_eq!(Method::POST, parts.method);
+            assert_eq!("/frames", parts.uri.path());
+        })
+        .await;
+
+        assert!(counter.load(atomic::Ordering::Relaxed) > 2);
+        assert_eq!(NUM_LINES, output_lines.len());
+        assert_eq!(input_lines, output_lines);
+    }
+
+    #[test]
+    fn negotiated_compression_falls_back() {
+        let compression = NegotiatedCompression::new(HttpCompression::brotli_default());
+
+        assert!(compression.reject(&HeaderValue::from_static("br")));
+        assert_eq!(compression.current(), HttpCompression::gzip_default());
+
+        // Requests sent concurrently with brotli are retried without falling back any further.
+        assert!(compression.reject(&HeaderValue::from_static("br")));
+        assert_eq!(compression.current(), HttpCompression::gzip_default());
+
+        // Encodings the sink never used aren't retried.
+        assert!(!compression.reject(&HeaderValue::from_static("deflate")));
+
+        assert!(compression.reject(&HeaderValue::from_static("gzip")));
+        assert_eq!(compression.current(), HttpCompression::Common(Compression::None));
+    }
+
+    #[tokio::test]
+    async fn falls_back_from_rejected_compression() {
+        const NUM_LINES: usize = 1000;
+
+        let in_addr = next_addr();
+        let config = format!(
+            r#"
+                uri = "http://{addr}/frames"
+                compression = "zstd"
+                framing.method = "newline_delimited"
+                encoding.codec = "json"
+            "#,
+            addr = in_addr,
+        );
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let counter = Arc::new(atomic::AtomicUsize::new(0));
+        let in_counter = Arc::clone(&counter);
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            let count = in_counter.fetch_add(1, atomic::Ordering::Relaxed);
+            if count == 0 {
+                // Reject the first request, compressed with zstd, as unsupported
+                Response::builder()
+                    .status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+                    .body(Body::empty())
+                    .unwrap_or_else(|_| unreachable!())
+            } else {
+                Response::new(Body::empty())
+            }
+        });
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input_lines, events) = random_lines_with_stream(100, NUM_LINES, Some(batch));
+        let pump = sink.run(events);
+
+        tokio::spawn(server);
+
+        pump.await.unwrap();
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let output_lines = get_received(rx, |parts| {
+            assert_eq!(
+                Some("gzip"),
+                parts
+                    .headers
+                    .get("Content-Encoding")
+                    .map(|value| value.to_str().unwrap())
+            );
+        })
+        .await;
+
+        assert_eq!(NUM_LINES, output_lines.len());
+        assert_eq!(input_lines, output_lines);
+    }
+
     #[tokio::test]
     async fn fails_on_permanent_error() {
         let num_lines = 1000;
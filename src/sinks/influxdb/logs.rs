@@ -17,7 +17,7 @@ use crate::{
     sinks::{
         influxdb::{
             encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDb1Settings, InfluxDb2Settings, ProtocolVersion,
+            InfluxDb1Settings, InfluxDb2Settings, InfluxDb3Settings, ProtocolVersion,
         },
         util::{
             http::{BatchedHttpSink, HttpEventEncoder, HttpSink},
@@ -49,6 +49,13 @@ pub struct InfluxDbLogsConfig {
     pub influxdb1_settings: Option<InfluxDb1Settings>,
     #[serde(flatten)]
     pub influxdb2_settings: Option<InfluxDb2Settings>,
+    #[serde(flatten)]
+    pub influxdb3_settings: Option<InfluxDb3Settings>,
+    /// The types that event fields are written as, by field name.
+    #[serde(default)]
+    pub field_types: HashMap<String, FieldType>,
+    #[serde(default)]
+    pub compression: Compression,
     #[serde(
         skip_serializing_if = "crate::serde::skip_serializing_if_default",
         default
@@ -67,13 +74,27 @@ pub struct InfluxDbLogsConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// The line protocol type of a field, for values whose type doesn't map to the intended one,
+/// such as unsigned integers or numbers and booleans parsed from strings.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    Int,
+    Uint,
+    Float,
+    Bool,
+    String,
+}
+
 #[derive(Debug)]
 struct InfluxDbLogsSink {
     uri: Uri,
-    token: String,
+    authorization: String,
     protocol_version: ProtocolVersion,
     measurement: String,
     tags: HashSet<String>,
+    field_types: HashMap<String, FieldType>,
+    compression: Compression,
     transformer: Transformer,
 }
 
@@ -106,7 +127,8 @@ impl SinkConfig for InfluxDbLogsConfig {
         tags.insert("metric_type".to_string());
 
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client =
+            HttpClient::new_with_connection_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let healthcheck = self.healthcheck(client.clone())?;
 
         let batch = self.batch.into_batch_settings()?;
@@ -118,27 +140,30 @@ impl SinkConfig for InfluxDbLogsConfig {
         let settings = influxdb_settings(
             self.influxdb1_settings.clone(),
             self.influxdb2_settings.clone(),
+            self.influxdb3_settings.clone(),
         )
         .unwrap();
 
         let endpoint = self.endpoint.clone();
         let uri = settings.write_uri(endpoint).unwrap();
 
-        let token = settings.token();
+        let authorization = settings.authorization();
         let protocol_version = settings.protocol_version();
 
         let sink = InfluxDbLogsSink {
             uri,
-            token,
+            authorization,
             protocol_version,
             measurement,
             tags,
+            field_types: self.field_types.clone(),
+            compression: self.compression,
             transformer: self.encoding.clone(),
         };
 
         let sink = BatchedHttpSink::new(
             sink,
-            Buffer::new(batch.size, Compression::None),
+            Buffer::new(batch.size, self.compression),
             request,
             batch.timeout,
             client,
@@ -165,6 +190,7 @@ struct InfluxDbLogsEncoder {
     protocol_version: ProtocolVersion,
     measurement: String,
     tags: HashSet<String>,
+    field_types: HashMap<String, FieldType>,
     transformer: Transformer,
 }
 
@@ -191,7 +217,11 @@ impl HttpEventEncoder<BytesMut> for InfluxDbLogsEncoder {
             if self.tags.contains(&key) {
                 tags.insert(key, value.to_string_lossy());
             } else {
-                fields.insert(key, to_field(value));
+                let field = match self.field_types.get(&key) {
+                    Some(field_type) => to_typed_field(value, *field_type),
+                    None => to_field(value),
+                };
+                fields.insert(key, field);
             }
         });
 
@@ -223,16 +253,21 @@ impl HttpSink for InfluxDbLogsSink {
             protocol_version: self.protocol_version,
             measurement: self.measurement.clone(),
             tags: self.tags.clone(),
+            field_types: self.field_types.clone(),
             transformer: self.transformer.clone(),
         }
     }
 
     async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Bytes>> {
-        Request::post(&self.uri)
+        let mut builder = Request::post(&self.uri)
             .header("Content-Type", "text/plain")
-            .header("Authorization", format!("Token {}", &self.token))
-            .body(events.freeze())
-            .map_err(Into::into)
+            .header("Authorization", &self.authorization);
+
+        if let Some(ce) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", ce);
+        }
+
+        builder.body(events.freeze()).map_err(Into::into)
     }
 }
 
@@ -263,6 +298,7 @@ impl InfluxDbLogsConfig {
             config.endpoint,
             config.influxdb1_settings,
             config.influxdb2_settings,
+            config.influxdb3_settings,
             client,
         )?;
 
@@ -279,6 +315,28 @@ fn to_field(value: &Value) -> Field {
     }
 }
 
+/// Converts the value to the given type, falling back to the type of the value if it can't be.
+fn to_typed_field(value: &Value, field_type: FieldType) -> Field {
+    let field = match (field_type, value) {
+        (FieldType::Int, Value::Integer(num)) => Some(Field::Int(*num)),
+        (FieldType::Int, Value::Bytes(bytes)) => parse_bytes(bytes).map(Field::Int),
+        (FieldType::Uint, Value::Integer(num)) => u64::try_from(*num).ok().map(Field::UnsignedInt),
+        (FieldType::Uint, Value::Bytes(bytes)) => parse_bytes(bytes).map(Field::UnsignedInt),
+        (FieldType::Float, Value::Integer(num)) => Some(Field::Float(*num as f64)),
+        (FieldType::Float, Value::Bytes(bytes)) => parse_bytes(bytes).map(Field::Float),
+        (FieldType::Bool, Value::Integer(0)) => Some(Field::Bool(false)),
+        (FieldType::Bool, Value::Integer(1)) => Some(Field::Bool(true)),
+        (FieldType::Bool, Value::Bytes(bytes)) => parse_bytes(bytes).map(Field::Bool),
+        (FieldType::String, _) => Some(Field::String(value.to_string_lossy())),
+        _ => None,
+    };
+    field.unwrap_or_else(|| to_field(value))
+}
+
+fn parse_bytes<T: std::str::FromStr>(bytes: &[u8]) -> Option<T> {
+    std::str::from_utf8(bytes).ok()?.trim().parse().ok()
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{offset::TimeZone, Utc};
@@ -458,6 +516,48 @@ mod tests {
         assert_eq!("1542182950000000011\n", line_protocol.3);
     }
 
+    #[test]
+    fn test_encode_event_field_types() {
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("count", 4i32);
+        event.as_mut_log().insert("negative", -4i32);
+        event.as_mut_log().insert("enabled", "true");
+        event.as_mut_log().insert("latency", "12");
+        event.as_mut_log().insert("timestamp", ts());
+
+        let mut sink = create_sink(
+            "http://localhost:9999",
+            "my-token",
+            ProtocolVersion::V3,
+            "vector",
+            ["metric_type"].to_vec(),
+        );
+        sink.field_types = HashMap::from([
+            ("count".to_owned(), FieldType::Uint),
+            ("negative".to_owned(), FieldType::Uint),
+            ("enabled".to_owned(), FieldType::Bool),
+            ("latency".to_owned(), FieldType::Float),
+            ("message".to_owned(), FieldType::Int),
+        ]);
+        let mut encoder = sink.build_encoder();
+
+        let bytes = encoder.encode_event(event).unwrap();
+        let string = std::str::from_utf8(&bytes).unwrap();
+
+        let line_protocol = split_line_protocol(string);
+        assert_fields(
+            line_protocol.2.to_string(),
+            [
+                "count=4u",
+                "negative=-4i",
+                "enabled=true",
+                "latency=12",
+                "message=\"hello\"",
+            ]
+            .to_vec(),
+        );
+    }
+
     #[test]
     fn test_encode_event_without_tags() {
         let mut event = Event::Log(LogEvent::from("hello"));
@@ -603,6 +703,23 @@ mod tests {
         assert!(query.contains("precision=ns"));
     }
 
+    #[tokio::test]
+    async fn smoke_v3() {
+        let rx = smoke_test(
+            indoc! {r#"
+            database = "my-database"
+            token = "my-token"
+        "#},
+            StatusCode::OK,
+            BatchStatus::Delivered,
+        )
+        .await;
+
+        let query = receive_response(rx).await;
+        assert!(query.contains("db=my-database"));
+        assert!(query.contains("precision=nanosecond"));
+    }
+
     #[tokio::test]
     async fn smoke_v2_failure() {
         smoke_test(
@@ -718,15 +835,17 @@ mod tests {
         tags: Vec<&str>,
     ) -> InfluxDbLogsSink {
         let uri = uri.parse::<Uri>().unwrap();
-        let token = token.to_string();
+        let authorization = format!("Token {}", token);
         let measurement = measurement.to_string();
         let tags: HashSet<String> = tags.into_iter().map(|tag| tag.to_string()).collect();
         InfluxDbLogsSink {
             uri,
-            token,
+            authorization,
             protocol_version,
             measurement,
             tags,
+            field_types: HashMap::new(),
+            compression: Compression::None,
             transformer: Default::default(),
         }
     }
@@ -770,6 +889,9 @@ mod integration_tests {
                 bucket: BUCKET.to_string(),
                 token: TOKEN.to_string(),
             }),
+            influxdb3_settings: None,
+            field_types: Default::default(),
+            compression: Default::default(),
             encoding: Default::default(),
             batch: Default::default(),
             request: Default::default(),
@@ -1,6 +1,7 @@
 use std::{
     collections::{BTreeMap, HashMap},
     future::ready,
+    io::Write,
     task::Poll,
 };
 
@@ -23,14 +24,15 @@ use crate::{
     sinks::{
         influxdb::{
             encode_timestamp, healthcheck, influx_line_protocol, influxdb_settings, Field,
-            InfluxDb1Settings, InfluxDb2Settings, ProtocolVersion,
+            InfluxDb1Settings, InfluxDb2Settings, InfluxDb3Settings, ProtocolVersion,
         },
         util::{
             buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
             encode_namespace,
             http::{HttpBatchService, HttpRetryLogic},
             statistic::{validate_quantiles, DistributionStatistic},
-            BatchConfig, EncodedEvent, SinkBatchSettings, TowerRequestConfig,
+            BatchConfig, Compression, Compressor, EncodedEvent, SinkBatchSettings, TagRule,
+            TagRules, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -63,11 +65,17 @@ pub struct InfluxDbConfig {
     pub influxdb1_settings: Option<InfluxDb1Settings>,
     #[serde(flatten)]
     pub influxdb2_settings: Option<InfluxDb2Settings>,
+    #[serde(flatten)]
+    pub influxdb3_settings: Option<InfluxDb3Settings>,
+    #[serde(default)]
+    pub compression: Compression,
     #[serde(default)]
     pub batch: BatchConfig<InfluxDbDefaultBatchSettings>,
     #[serde(default)]
     pub request: TowerRequestConfig,
     pub tags: Option<HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_rules: Vec<TagRule>,
     pub tls: Option<TlsConfig>,
     #[serde(default = "default_summary_quantiles")]
     pub quantiles: Vec<f64>,
@@ -100,11 +108,13 @@ impl_generate_config_from_default!(InfluxDbConfig);
 impl SinkConfig for InfluxDbConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client =
+            HttpClient::new_with_connection_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let healthcheck = healthcheck(
             self.clone().endpoint,
             self.clone().influxdb1_settings,
             self.clone().influxdb2_settings,
+            self.clone().influxdb3_settings,
             client.clone(),
         )?;
         validate_quantiles(&self.quantiles)?;
@@ -130,10 +140,11 @@ impl InfluxDbSvc {
         let settings = influxdb_settings(
             config.influxdb1_settings.clone(),
             config.influxdb2_settings.clone(),
+            config.influxdb3_settings.clone(),
         )?;
 
         let endpoint = config.endpoint.clone();
-        let token = settings.token();
+        let authorization = settings.authorization();
         let protocol_version = settings.protocol_version();
 
         let batch = config.batch.into_batch_settings()?;
@@ -144,13 +155,17 @@ impl InfluxDbSvc {
 
         let uri = settings.write_uri(endpoint)?;
 
-        let http_service = HttpBatchService::new(client, create_build_request(uri, token));
+        let http_service = HttpBatchService::new(
+            client,
+            create_build_request(uri, authorization, config.compression),
+        );
 
         let influxdb_http_service = InfluxDbSvc {
             config,
             protocol_version,
             inner: http_service,
         };
+        let tag_rules = TagRules::new(&config.tag_rules)?;
         let mut normalizer = MetricNormalizer::<InfluxMetricNormalize>::default();
 
         let sink = request
@@ -163,8 +178,10 @@ impl InfluxDbSvc {
             .with_flat_map(move |event: Event| {
                 stream::iter({
                     let byte_size = event.size_of();
+                    let mut metric = event.into_metric();
+                    tag_rules.apply(&mut metric);
                     normalizer
-                        .normalize(event.into_metric())
+                        .normalize(metric)
                         .map(|metric| Ok(EncodedEvent::new(metric, byte_size)))
                 })
             })
@@ -199,21 +216,43 @@ impl Service<Vec<Metric>> for InfluxDbSvc {
 
 fn create_build_request(
     uri: http::Uri,
-    token: String,
+    authorization: String,
+    compression: Compression,
 ) -> impl Fn(Bytes) -> BoxFuture<'static, crate::Result<hyper::Request<Bytes>>> + Sync + Send + 'static
 {
-    let auth = format!("Token {}", token);
     move |body| {
-        Box::pin(ready(
-            hyper::Request::post(uri.clone())
-                .header("Content-Type", "text/plain")
-                .header("Authorization", auth.clone())
-                .body(body)
-                .map_err(Into::into),
-        ))
+        Box::pin(ready(build_request(
+            uri.clone(),
+            &authorization,
+            compression,
+            body,
+        )))
     }
 }
 
+fn build_request(
+    uri: http::Uri,
+    authorization: &str,
+    compression: Compression,
+    body: Bytes,
+) -> crate::Result<hyper::Request<Bytes>> {
+    let mut builder = hyper::Request::post(uri)
+        .header("Content-Type", "text/plain")
+        .header("Authorization", authorization);
+
+    let body = match compression.content_encoding() {
+        Some(ce) => {
+            builder = builder.header("Content-Encoding", ce);
+            let mut compressor = Compressor::from(compression);
+            compressor.write_all(&body)?;
+            compressor.finish()?.freeze()
+        }
+        None => body,
+    };
+
+    builder.body(body).map_err(Into::into)
+}
+
 fn merge_tags(
     event: &Metric,
     tags: Option<&HashMap<String, String>>,
@@ -380,6 +419,7 @@ fn get_type_and_fields(
 
                 ("sketch", Some(fields))
             }
+            MetricSketch::HyperLogLog(hll) => ("set", Some(to_fields(hll.estimate().round()))),
         },
     }
 }
@@ -441,6 +481,33 @@ mod tests {
         toml::from_str::<InfluxDbConfig>(config).unwrap();
     }
 
+    #[test]
+    fn test_build_request_gzip() {
+        use std::io::Read;
+
+        let request = build_request(
+            "http://localhost:8181/api/v3/write_lp?db=vector"
+                .parse()
+                .unwrap(),
+            "Bearer my-token",
+            Compression::gzip_default(),
+            Bytes::from("ns.total,metric_type=counter value=1.5 1542182950000000011\n"),
+        )
+        .unwrap();
+
+        assert_eq!(request.headers()["Authorization"], "Bearer my-token");
+        assert_eq!(request.headers()["Content-Encoding"], "gzip");
+
+        let mut body = String::new();
+        flate2::read::GzDecoder::new(&request.body()[..])
+            .read_to_string(&mut body)
+            .unwrap();
+        assert_eq!(
+            body,
+            "ns.total,metric_type=counter value=1.5 1542182950000000011\n"
+        );
+    }
+
     #[test]
     fn test_encode_counter() {
         let events = vec![
@@ -976,11 +1043,14 @@ mod integration_tests {
                 password: None,
             }),
             influxdb2_settings: None,
+            influxdb3_settings: None,
+            compression: Default::default(),
             batch: Default::default(),
             request: Default::default(),
             tls,
             quantiles: default_summary_quantiles(),
             tags: None,
+            tag_rules: Vec::new(),
             default_namespace: None,
             acknowledgements: Default::default(),
         };
@@ -1069,10 +1139,13 @@ mod integration_tests {
                 bucket: BUCKET.to_string(),
                 token: TOKEN.to_string(),
             }),
+            influxdb3_settings: None,
+            compression: Default::default(),
             quantiles: default_summary_quantiles(),
             batch: Default::default(),
             request: Default::default(),
             tags: None,
+            tag_rules: Vec::new(),
             tls: None,
             default_namespace: None,
             acknowledgements: Default::default(),
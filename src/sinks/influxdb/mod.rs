@@ -32,11 +32,12 @@ pub(in crate::sinks) enum Field {
 pub(in crate::sinks) enum ProtocolVersion {
     V1,
     V2,
+    V3,
 }
 
 #[derive(Debug, Snafu)]
 enum ConfigError {
-    #[snafu(display("InfluxDB v1 or v2 should be configured as endpoint."))]
+    #[snafu(display("InfluxDB v1, v2 or v3 should be configured as endpoint."))]
     MissingConfiguration,
     #[snafu(display(
         "Unclear settings. Both version configured v1: {:?}, v2: {:?}.",
@@ -65,11 +66,22 @@ pub struct InfluxDb2Settings {
     token: String,
 }
 
+/// InfluxDB 3 writes to a database like v1 does, and is told apart from it by the token.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct InfluxDb3Settings {
+    database: String,
+    token: String,
+}
+
 trait InfluxDbSettings: std::fmt::Debug {
     fn write_uri(&self, endpoint: String) -> crate::Result<Uri>;
     fn healthcheck_uri(&self, endpoint: String) -> crate::Result<Uri>;
     fn token(&self) -> String;
     fn protocol_version(&self) -> ProtocolVersion;
+
+    fn authorization(&self) -> String {
+        format!("Token {}", self.token())
+    }
 }
 
 impl InfluxDbSettings for InfluxDb1Settings {
@@ -127,35 +139,75 @@ impl InfluxDbSettings for InfluxDb2Settings {
     }
 }
 
+// https://docs.influxdata.com/influxdb3/core/write-data/http-api/v3-write-lp/
+impl InfluxDbSettings for InfluxDb3Settings {
+    fn write_uri(&self, endpoint: String) -> crate::Result<Uri> {
+        encode_uri(
+            &endpoint,
+            "api/v3/write_lp",
+            &[
+                ("db", Some(self.database.clone())),
+                ("precision", Some("nanosecond".to_owned())),
+            ],
+        )
+    }
+
+    fn healthcheck_uri(&self, endpoint: String) -> crate::Result<Uri> {
+        encode_uri(&endpoint, "health", &[])
+    }
+
+    fn token(&self) -> String {
+        self.token.clone()
+    }
+
+    fn protocol_version(&self) -> ProtocolVersion {
+        ProtocolVersion::V3
+    }
+
+    fn authorization(&self) -> String {
+        format!("Bearer {}", self.token)
+    }
+}
+
 fn influxdb_settings(
     influxdb1_settings: Option<InfluxDb1Settings>,
     influxdb2_settings: Option<InfluxDb2Settings>,
+    influxdb3_settings: Option<InfluxDb3Settings>,
 ) -> Result<Box<dyn InfluxDbSettings>, crate::Error> {
-    match (influxdb1_settings, influxdb2_settings) {
-        (Some(v1_settings), Some(v2_settings)) => Err(ConfigError::BothConfiguration {
+    match (influxdb1_settings, influxdb2_settings, influxdb3_settings) {
+        (Some(v1_settings), Some(v2_settings), _) => Err(ConfigError::BothConfiguration {
             v1_settings,
             v2_settings,
         }
         .into()),
-        (None, None) => Err(ConfigError::MissingConfiguration.into()),
-        (Some(settings), _) => Ok(Box::new(settings)),
-        (_, Some(settings)) => Ok(Box::new(settings)),
+        (None, None, None) => Err(ConfigError::MissingConfiguration.into()),
+        // Any v3 settings are also v1 settings, with a token.
+        (_, None, Some(settings)) => Ok(Box::new(settings)),
+        (Some(settings), None, None) => Ok(Box::new(settings)),
+        (None, Some(settings), _) => Ok(Box::new(settings)),
     }
 }
 
 // V1: https://docs.influxdata.com/influxdb/v1.7/tools/api/#ping-http-endpoint
 // V2: https://v2.docs.influxdata.com/v2.0/api/#operation/GetHealth
+// V3: https://docs.influxdata.com/influxdb3/core/api/v3/#operation/GetHealth
 fn healthcheck(
     endpoint: String,
     influxdb1_settings: Option<InfluxDb1Settings>,
     influxdb2_settings: Option<InfluxDb2Settings>,
+    influxdb3_settings: Option<InfluxDb3Settings>,
     mut client: HttpClient,
 ) -> crate::Result<super::Healthcheck> {
-    let settings = influxdb_settings(influxdb1_settings, influxdb2_settings)?;
+    let settings = influxdb_settings(influxdb1_settings, influxdb2_settings, influxdb3_settings)?;
 
     let uri = settings.healthcheck_uri(endpoint)?;
 
-    let request = hyper::Request::get(uri).body(hyper::Body::empty()).unwrap();
+    let mut request = hyper::Request::get(uri);
+    // Unlike the ping endpoint of earlier versions, the health endpoint of v3 requires a token.
+    if let ProtocolVersion::V3 = settings.protocol_version() {
+        request = request.header("Authorization", settings.authorization());
+    }
+    let request = request.body(hyper::Body::empty()).unwrap();
 
     Ok(async move {
         client
@@ -250,7 +302,7 @@ fn encode_fields(
                 output.put_slice(&i.to_string().into_bytes());
                 let c = match protocol_version {
                     ProtocolVersion::V1 => 'i',
-                    ProtocolVersion::V2 => 'u',
+                    ProtocolVersion::V2 | ProtocolVersion::V3 => 'u',
                 };
                 let mut c_buffer: [u8; 4] = [0; 4];
                 output.put_slice(c.encode_utf8(&mut c_buffer).as_bytes());
@@ -515,6 +567,8 @@ mod tests {
         pub influxdb1_settings: Option<InfluxDb1Settings>,
         #[serde(flatten)]
         pub influxdb2_settings: Option<InfluxDb2Settings>,
+        #[serde(flatten)]
+        pub influxdb3_settings: Option<InfluxDb3Settings>,
     }
 
     #[test]
@@ -526,7 +580,11 @@ mod tests {
         database = "my-database"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let settings = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings);
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3_settings,
+        );
         assert_eq!(
             settings.expect_err("expected error").to_string(),
             "Unclear settings. Both version configured v1: InfluxDb1Settings { database: \"my-database\", consistency: None, retention_policy_name: None, username: None, password: None }, v2: InfluxDb2Settings { org: \"my-org\", bucket: \"my-bucket\", token: \"my-token\" }.".to_owned()
@@ -538,10 +596,14 @@ mod tests {
         let config = r#"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let settings = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings);
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3_settings,
+        );
         assert_eq!(
             settings.expect_err("expected error").to_string(),
-            "InfluxDB v1 or v2 should be configured as endpoint.".to_owned()
+            "InfluxDB v1, v2 or v3 should be configured as endpoint.".to_owned()
         );
     }
 
@@ -551,7 +613,12 @@ mod tests {
         database = "my-database"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let _ = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings).unwrap();
+        let _ = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3_settings,
+        )
+        .unwrap();
     }
 
     #[test]
@@ -562,7 +629,49 @@ mod tests {
         token = "my-token"
     "#;
         let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
-        let _ = influxdb_settings(config.influxdb1_settings, config.influxdb2_settings).unwrap();
+        let _ = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3_settings,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_influxdb3_settings() {
+        let config = r#"
+        database = "my-database"
+        token = "my-token"
+    "#;
+        let config: InfluxDbTestConfig = toml::from_str(config).unwrap();
+        let settings = influxdb_settings(
+            config.influxdb1_settings,
+            config.influxdb2_settings,
+            config.influxdb3_settings,
+        )
+        .unwrap();
+        assert!(matches!(settings.protocol_version(), ProtocolVersion::V3));
+        assert_eq!(settings.authorization(), "Bearer my-token");
+    }
+
+    #[test]
+    fn test_influxdb3_test_write_uri() {
+        let settings = InfluxDb3Settings {
+            database: "vector_db".to_owned(),
+            token: "my-token".to_owned(),
+        };
+
+        let uri = settings
+            .write_uri("http://localhost:8181".to_owned())
+            .unwrap();
+        assert_eq!(
+            "http://localhost:8181/api/v3/write_lp?db=vector_db&precision=nanosecond",
+            uri.to_string()
+        );
+        let uri = settings
+            .healthcheck_uri("http://localhost:8181".to_owned())
+            .unwrap();
+        assert_eq!("http://localhost:8181/health", uri.to_string());
     }
 
     #[test]
@@ -866,10 +975,16 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap()
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap()
     }
 
     #[tokio::test]
@@ -887,10 +1002,16 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap();
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -908,10 +1029,16 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap();
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap();
     }
 
     #[tokio::test]
@@ -929,9 +1056,15 @@ mod integration_tests {
         let proxy = ProxyConfig::default();
         let client = HttpClient::new(None, &proxy).unwrap();
 
-        healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
-            .unwrap()
-            .await
-            .unwrap();
+        healthcheck(
+            endpoint,
+            influxdb1_settings,
+            influxdb2_settings,
+            None,
+            client,
+        )
+        .unwrap()
+        .await
+        .unwrap();
     }
 }
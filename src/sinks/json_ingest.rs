@@ -0,0 +1,611 @@
+//! A generic sink for vendor HTTP ingest APIs that accept batches of JSON events.
+//!
+//! Many smaller vendors expose an ingest endpoint that differs from the next only in how the
+//! request is authenticated, how the batch of events is laid out in the body, and which responses
+//! are worth retrying. Rather than growing a bespoke sink for each of them, this sink makes those
+//! three things configurable.
+
+use std::io::Write;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{future, FutureExt, SinkExt};
+use http::{
+    header::{self, HeaderName, HeaderValue},
+    Request, StatusCode,
+};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+use crate::{
+    codecs::Transformer,
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+    },
+    event::Event,
+    http::{Auth, HttpClient, HttpError},
+    sinks::util::{
+        http::{BatchedHttpSink, HttpEventEncoder, HttpRetryLogic, HttpSink, RequestConfig},
+        retries::{RetryAction, RetryLogic},
+        BatchConfig, BoxedRawValue, Compression, Compressor, JsonArrayBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
+    },
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("{}: {}", source, name))]
+    InvalidHeaderName {
+        name: String,
+        source: header::InvalidHeaderName,
+    },
+    #[snafu(display("{}: {}", source, value))]
+    InvalidHeaderValue {
+        value: String,
+        source: header::InvalidHeaderValue,
+    },
+    #[snafu(display("Authorization header can not be used with defined auth options"))]
+    ConflictingAuthorization,
+    #[snafu(display(
+        "Invalid status code class {:?}, expected one of \"1xx\" to \"5xx\"",
+        class
+    ))]
+    InvalidStatusClass { class: String },
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JsonIngestConfig {
+    pub uri: UriSerde,
+    pub auth: Option<IngestAuth>,
+    #[serde(default)]
+    pub envelope: Envelope,
+    #[serde(default)]
+    pub retry: RetryStatusConfig,
+    #[serde(default)]
+    pub compression: Compression,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub encoding: Transformer,
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+    #[serde(default)]
+    pub request: RequestConfig,
+    pub tls: Option<TlsConfig>,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+/// How requests are authenticated against the ingest API.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+pub enum IngestAuth {
+    /// `Authorization: Basic <base64(user:password)>`
+    Basic { user: String, password: String },
+    /// `Authorization: Bearer <token>`
+    Bearer { token: String },
+    /// `Authorization: <scheme> <credentials>`, for APIs with their own scheme such as `Api-Key`.
+    Scheme { scheme: String, credentials: String },
+    /// The key is sent as-is in a header of its own, such as `X-API-Key`.
+    Header { name: String, value: String },
+}
+
+impl IngestAuth {
+    fn validate(&self) -> crate::Result<()> {
+        match self {
+            IngestAuth::Basic { .. } | IngestAuth::Bearer { .. } => {}
+            IngestAuth::Scheme {
+                scheme,
+                credentials,
+            } => {
+                HeaderValue::from_str(&format!("{} {}", scheme, credentials)).context(
+                    InvalidHeaderValueSnafu {
+                        value: "<redacted>",
+                    },
+                )?;
+            }
+            IngestAuth::Header { name, value } => {
+                HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|_| InvalidHeaderNameSnafu { name })?;
+                HeaderValue::from_str(value).context(InvalidHeaderValueSnafu {
+                    value: "<redacted>",
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply<B>(&self, request: &mut Request<B>) {
+        let headers = request.headers_mut();
+        match self {
+            IngestAuth::Basic { user, password } => Auth::Basic {
+                user: user.clone(),
+                password: password.clone(),
+            }
+            .apply_headers_map(headers),
+            IngestAuth::Bearer { token } => Auth::Bearer {
+                token: token.clone(),
+            }
+            .apply_headers_map(headers),
+            IngestAuth::Scheme {
+                scheme,
+                credentials,
+            } => {
+                let value = HeaderValue::from_str(&format!("{} {}", scheme, credentials))
+                    .expect("validated on build");
+                headers.insert(header::AUTHORIZATION, value);
+            }
+            IngestAuth::Header { name, value } => {
+                let name = HeaderName::from_bytes(name.as_bytes()).expect("validated on build");
+                let value = HeaderValue::from_str(value).expect("validated on build");
+                headers.insert(name, value);
+            }
+        }
+    }
+}
+
+/// How a batch of events is laid out in the request body.
+#[derive(Deserialize, Serialize, Clone, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "shape")]
+pub enum Envelope {
+    /// A JSON array of events: `[{...},{...}]`.
+    #[derivative(Default)]
+    Array,
+    /// Newline delimited JSON, one event per line.
+    Ndjson,
+    /// A JSON object with the array of events under a single key: `{"events":[{...},{...}]}`.
+    Wrapped {
+        #[serde(default = "default_wrapped_key")]
+        key: String,
+    },
+}
+
+fn default_wrapped_key() -> String {
+    "events".to_string()
+}
+
+impl Envelope {
+    fn encode(&self, events: &[BoxedRawValue]) -> BytesMut {
+        let mut body = BytesMut::new();
+        match self {
+            Envelope::Array => write_array(&mut body, events),
+            Envelope::Ndjson => {
+                for (i, event) in events.iter().enumerate() {
+                    if i > 0 {
+                        body.put_u8(b'\n');
+                    }
+                    body.put_slice(event.get().as_bytes());
+                }
+            }
+            Envelope::Wrapped { key } => {
+                body.put_u8(b'{');
+                body.put_slice(&serde_json::to_vec(key).expect("Serializing a string can't fail"));
+                body.put_u8(b':');
+                write_array(&mut body, events);
+                body.put_u8(b'}');
+            }
+        }
+        body
+    }
+
+    const fn content_type(&self) -> &'static str {
+        match self {
+            Envelope::Ndjson => "application/x-ndjson",
+            Envelope::Array | Envelope::Wrapped { .. } => "application/json",
+        }
+    }
+}
+
+fn write_array(body: &mut BytesMut, events: &[BoxedRawValue]) {
+    body.put_u8(b'[');
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 {
+            body.put_u8(b',');
+        }
+        body.put_slice(event.get().as_bytes());
+    }
+    body.put_u8(b']');
+}
+
+/// Which response status codes are retried.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RetryStatusConfig {
+    /// Status codes, or classes of status codes such as `"5xx"`, to retry. Any other unsuccessful
+    /// response is not retried. By default, `429` and all `5xx` codes except `501` are retried.
+    pub status_codes: Option<Vec<StatusCodeMatcher>>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum StatusCodeMatcher {
+    Code(u16),
+    Class(String),
+}
+
+impl StatusCodeMatcher {
+    fn validate(&self) -> crate::Result<()> {
+        match self {
+            StatusCodeMatcher::Code(_) => Ok(()),
+            StatusCodeMatcher::Class(class) => match class.as_bytes() {
+                [b'1'..=b'5', b'x' | b'X', b'x' | b'X'] => Ok(()),
+                _ => Err(Box::new(BuildError::InvalidStatusClass {
+                    class: class.clone(),
+                })),
+            },
+        }
+    }
+
+    fn matches(&self, status: StatusCode) -> bool {
+        match self {
+            StatusCodeMatcher::Code(code) => status.as_u16() == *code,
+            StatusCodeMatcher::Class(class) => class.as_bytes().first().map_or(false, |class| {
+                u16::from(class - b'0') == status.as_u16() / 100
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct IngestRetryLogic {
+    status_codes: Option<Vec<StatusCodeMatcher>>,
+}
+
+impl RetryLogic for IngestRetryLogic {
+    type Error = HttpError;
+    type Response = hyper::Response<Bytes>;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        HttpRetryLogic.is_retriable_error(error)
+    }
+
+    fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+        let status_codes = match &self.status_codes {
+            Some(status_codes) => status_codes,
+            None => return HttpRetryLogic.should_retry_response(response),
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            RetryAction::Successful
+        } else if status_codes.iter().any(|matcher| matcher.matches(status)) {
+            RetryAction::Retry(
+                format!("{}: {}", status, String::from_utf8_lossy(response.body())).into(),
+            )
+        } else {
+            RetryAction::DontRetry(format!("response status: {}", status).into())
+        }
+    }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status())
+    }
+}
+
+inventory::submit! {
+    SinkDescription::new::<JsonIngestConfig>("json_ingest")
+}
+
+impl GenerateConfig for JsonIngestConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"uri = "https://ingest.example.com/v1/logs"
+            auth.strategy = "bearer"
+            auth.token = "${INGEST_TOKEN}""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "json_ingest")]
+impl SinkConfig for JsonIngestConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_connection_pool(tls, cx.proxy(), cx.connection_pool())?;
+
+        if let Some(auth) = &self.auth {
+            auth.validate()?;
+        }
+        validate_headers(&self.request, self.auth.is_some())?;
+        for matcher in self.retry.status_codes.iter().flatten() {
+            matcher.validate()?;
+        }
+
+        let healthcheck = match cx.healthcheck.uri {
+            Some(healthcheck_uri) => {
+                healthcheck(healthcheck_uri, self.auth.clone(), client.clone()).boxed()
+            }
+            None => future::ok(()).boxed(),
+        };
+
+        let request_settings = self
+            .request
+            .tower
+            .unwrap_with(&TowerRequestConfig::default());
+        let batch = self.batch.into_batch_settings()?;
+        let retry_logic = IngestRetryLogic {
+            status_codes: self.retry.status_codes.clone(),
+        };
+
+        let sink = BatchedHttpSink::with_logic(
+            self.clone(),
+            JsonArrayBuffer::new(batch.size),
+            retry_logic,
+            request_settings,
+            batch.timeout,
+            client,
+        )
+        .sink_map_err(|error| error!(message = "Fatal json_ingest sink error.", %error));
+
+        Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "json_ingest"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+pub struct JsonIngestEventEncoder {
+    transformer: Transformer,
+}
+
+impl HttpEventEncoder<serde_json::Value> for JsonIngestEventEncoder {
+    fn encode_event(&mut self, mut event: Event) -> Option<serde_json::Value> {
+        self.transformer.transform(&mut event);
+        serde_json::to_value(event.into_log()).ok()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for JsonIngestConfig {
+    type Input = serde_json::Value;
+    type Output = Vec<BoxedRawValue>;
+    type Encoder = JsonIngestEventEncoder;
+
+    fn build_encoder(&self) -> Self::Encoder {
+        JsonIngestEventEncoder {
+            transformer: self.encoding.clone(),
+        }
+    }
+
+    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Bytes>> {
+        let mut body = self.envelope.encode(&events);
+
+        let mut builder = Request::post(self.uri.with_default_parts().uri)
+            .header("Content-Type", self.envelope.content_type());
+
+        if let Some(content_encoding) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
+
+            let mut compressor = Compressor::from(self.compression);
+            compressor
+                .write_all(&body)
+                .expect("Writing to Vec can't fail");
+            body = compressor.into_inner();
+        }
+
+        for (header, value) in self.request.headers.iter() {
+            builder = builder.header(header.as_str(), value.as_str());
+        }
+
+        let mut request = builder.body(body.freeze())?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+async fn healthcheck(
+    uri: UriSerde,
+    auth: Option<IngestAuth>,
+    client: HttpClient,
+) -> crate::Result<()> {
+    let uri = uri.with_default_parts();
+    let mut request = Request::get(&uri.uri).body(Body::empty()).unwrap();
+
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        status => Err(super::HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+fn validate_headers(request: &RequestConfig, has_auth: bool) -> crate::Result<()> {
+    for (name, value) in &request.headers {
+        if has_auth && name.eq_ignore_ascii_case("Authorization") {
+            return Err(Box::new(BuildError::ConflictingAuthorization));
+        }
+
+        HeaderName::from_bytes(name.as_bytes())
+            .with_context(|_| InvalidHeaderNameSnafu { name })?;
+        HeaderValue::from_bytes(value.as_bytes())
+            .with_context(|_| InvalidHeaderValueSnafu { value })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use http::request::Parts;
+    use vector_core::event::{BatchNotifier, BatchStatus, LogEvent};
+
+    use super::*;
+    use crate::{
+        sinks::util::test::{build_test_server_status, load_sink},
+        test_util::{components, components::HTTP_SINK_TAGS, next_addr},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JsonIngestConfig>();
+    }
+
+    fn raw_events() -> Vec<BoxedRawValue> {
+        vec![
+            serde_json::value::to_raw_value(&serde_json::json!({"message": "one"})).unwrap(),
+            serde_json::value::to_raw_value(&serde_json::json!({"message": "two"})).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn encodes_envelopes() {
+        let events = raw_events();
+
+        assert_eq!(
+            &Envelope::Array.encode(&events)[..],
+            br#"[{"message":"one"},{"message":"two"}]"#
+        );
+        assert_eq!(
+            &Envelope::Ndjson.encode(&events)[..],
+            b"{\"message\":\"one\"}\n{\"message\":\"two\"}"
+        );
+        assert_eq!(
+            &Envelope::Wrapped { key: "logs".into() }.encode(&events)[..],
+            br#"{"logs":[{"message":"one"},{"message":"two"}]}"#
+        );
+        assert_eq!(
+            &Envelope::Wrapped { key: "logs".into() }.encode(&[])[..],
+            br#"{"logs":[]}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_auth_schemes() {
+        let cases = [
+            (
+                r#"auth = { strategy = "bearer", token = "abc" }"#,
+                "authorization",
+                "Bearer abc",
+            ),
+            (
+                r#"auth = { strategy = "scheme", scheme = "Api-Key", credentials = "abc" }"#,
+                "authorization",
+                "Api-Key abc",
+            ),
+            (
+                r#"auth = { strategy = "header", name = "X-API-Key", value = "abc" }"#,
+                "x-api-key",
+                "abc",
+            ),
+        ];
+
+        for (auth, header, expected) in cases {
+            let (config, _cx) = load_sink::<JsonIngestConfig>(&format!(
+                "uri = \"http://localhost/ingest\"\n{}",
+                auth
+            ))
+            .unwrap();
+
+            let request = config.build_request(raw_events()).await.unwrap();
+            assert_eq!(request.headers()[header], expected);
+        }
+    }
+
+    #[test]
+    fn classifies_retries() {
+        let logic = IngestRetryLogic {
+            status_codes: Some(vec![
+                StatusCodeMatcher::Code(409),
+                StatusCodeMatcher::Class("5xx".into()),
+            ]),
+        };
+        let response = |status: u16| {
+            hyper::Response::builder()
+                .status(status)
+                .body(Bytes::new())
+                .unwrap()
+        };
+
+        assert!(matches!(
+            logic.should_retry_response(&response(200)),
+            RetryAction::Successful
+        ));
+        assert!(matches!(
+            logic.should_retry_response(&response(409)),
+            RetryAction::Retry(_)
+        ));
+        assert!(matches!(
+            logic.should_retry_response(&response(501)),
+            RetryAction::Retry(_)
+        ));
+        assert!(matches!(
+            logic.should_retry_response(&response(429)),
+            RetryAction::DontRetry(_)
+        ));
+
+        assert!(StatusCodeMatcher::Class("6xx".into()).validate().is_err());
+        assert!(StatusCodeMatcher::Class("5xy".into()).validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_wrapped_batches() {
+        let addr = next_addr();
+        let (config, cx) = load_sink::<JsonIngestConfig>(&format!(
+            r#"
+            uri = "http://{}/ingest"
+            envelope.shape = "wrapped"
+            "#,
+            addr
+        ))
+        .unwrap();
+
+        let (rx, trigger, server) = build_test_server_status(addr, StatusCode::OK);
+        tokio::spawn(server);
+
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::Log(LogEvent::from("one").with_batch_notifier(&batch)),
+            Event::Log(LogEvent::from("two").with_batch_notifier(&batch)),
+        ];
+        drop(batch);
+
+        components::run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS)
+            .await;
+        drop(trigger);
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let requests = rx.collect::<Vec<(Parts, Bytes)>>().await;
+        assert_eq!(requests.len(), 1);
+        let (parts, body) = &requests[0];
+        assert_eq!(parts.headers["content-type"], "application/json");
+
+        let body: serde_json::Value = serde_json::from_slice(body).unwrap();
+        let messages = body["events"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["message"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two"]);
+    }
+}
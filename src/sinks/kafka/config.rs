@@ -41,6 +41,14 @@ pub(crate) struct KafkaSinkConfig {
     pub librdkafka_options: HashMap<String, String>,
     #[serde(alias = "headers_field")] // accidentally released as `headers_field` in 0.18
     pub headers_key: Option<String>,
+    /// The partitioner used to choose the partition of each message, for compatibility with other clients
+    /// producing to the same topics. This will **not** override the `partitioner` librdkafka_options value.
+    #[serde(default)]
+    pub partitioner: Option<KafkaPartitioner>,
+    /// Creates the topics that don't exist yet with these settings, before sending them any message.
+    /// Creation stops for good if the sink isn't authorized to create topics.
+    #[serde(default)]
+    pub topic_creation: Option<KafkaTopicCreationConfig>,
     #[serde(
         default,
         deserialize_with = "crate::serde::bool_or_struct",
@@ -49,6 +57,49 @@ pub(crate) struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// The partitioner used by the producer. Messages with the same key are sent to the same partition.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KafkaPartitioner {
+    /// Hashes the key with murmur2, as the Java client does. Messages without key are sent to random partitions.
+    Murmur2,
+    /// Hashes the key with FNV-1a, as the Sarama Go client does. Messages without key are sent to random partitions.
+    Fnv1a,
+    /// Hashes the key with murmur2, as the Java client does. Messages without key are sent to the same partition until
+    /// the batch is sent, as the sticky partitioner of the Java client does.
+    Sticky,
+}
+
+impl KafkaPartitioner {
+    /// The librdkafka options implementing this partitioner.
+    const fn options(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::Murmur2 => &[
+                ("partitioner", "murmur2_random"),
+                ("sticky.partitioning.linger.ms", "0"),
+            ],
+            Self::Fnv1a => &[
+                ("partitioner", "fnv1a_random"),
+                ("sticky.partitioning.linger.ms", "0"),
+            ],
+            Self::Sticky => &[("partitioner", "murmur2_random")],
+        }
+    }
+}
+
+/// The settings of the topics created by the sink.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTopicCreationConfig {
+    /// The number of partitions of the topics. Defaults to the `num.partitions` setting of the brokers.
+    pub num_partitions: Option<i32>,
+    /// The replication factor of the topics. Defaults to the `default.replication.factor` setting of the brokers.
+    pub replication_factor: Option<i32>,
+    /// The topic-level configuration of the topics, such as `retention.ms`.
+    #[serde(default)]
+    pub config: HashMap<String, String>,
+}
+
 const fn default_socket_timeout_ms() -> u64 {
     60000 // default in librdkafka
 }
@@ -141,6 +192,16 @@ impl KafkaSinkConfig {
                     );
                     client_config.set(key, &value.to_string());
                 }
+                if let Some(partitioner) = self.partitioner {
+                    for (key, value) in partitioner.options() {
+                        if let Some(val) = self.librdkafka_options.get(*key) {
+                            return Err(format!("Partitioner setting `partitioner` sets `librdkafka_options.{}={}`.\
+                                        The config already sets this as `librdkafka_options.{}={}`.\
+                                        Please delete one.", key, value, key, val).into());
+                        }
+                        client_config.set(*key, *value);
+                    }
+                }
             }
 
             KafkaRole::Consumer => {
@@ -171,6 +232,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            partitioner: None,
+            topic_creation: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -207,4 +270,46 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn config(extra: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "topic"
+            encoding.codec = "json"
+            {}
+            "#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn applies_partitioner() {
+        let client_config = config(r#"partitioner = "fnv1a""#)
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("fnv1a_random"));
+        assert_eq!(
+            client_config.get("sticky.partitioning.linger.ms"),
+            Some("0")
+        );
+
+        let client_config = config(r#"partitioner = "sticky""#)
+            .to_rdkafka(KafkaRole::Producer)
+            .unwrap();
+        assert_eq!(client_config.get("partitioner"), Some("murmur2_random"));
+        assert_eq!(client_config.get("sticky.partitioning.linger.ms"), None);
+    }
+
+    #[test]
+    fn rejects_conflicting_partitioner() {
+        let config = config(
+            r#"
+            partitioner = "murmur2"
+            librdkafka_options.partitioner = "consistent"
+            "#,
+        );
+        assert!(config.to_rdkafka(KafkaRole::Producer).is_err());
+    }
 }
@@ -32,9 +32,14 @@ impl KafkaRequestBuilder {
         };
         let event_byte_size = event.size_of();
         self.transformer.transform(&mut event);
-        let mut body = BytesMut::new();
-        self.encoder.encode(event, &mut body).ok()?;
-        let body = body.freeze();
+        let body = match self.encoder.passthrough(&event) {
+            Some(body) => body,
+            None => {
+                let mut body = BytesMut::new();
+                self.encoder.encode(event, &mut body).ok()?;
+                body.freeze()
+            }
+        };
         Some(KafkaRequest {
             body,
             metadata,
@@ -103,6 +108,7 @@ mod tests {
     use std::collections::BTreeMap;
 
     use bytes::Bytes;
+    use codecs::RawMessageSerializer;
     use rdkafka::message::Headers;
 
     use super::*;
@@ -124,4 +130,24 @@ mod tests {
         assert_eq!(headers.get(1).unwrap().0, "b-key");
         assert_eq!(headers.get(1).unwrap().1, "b-value".as_bytes());
     }
+
+    #[test]
+    fn kafka_passes_raw_message_through() {
+        let mut request_builder = KafkaRequestBuilder {
+            key_field: None,
+            headers_key: None,
+            topic_template: Template::try_from("topic").unwrap(),
+            transformer: Default::default(),
+            encoder: Encoder::<()>::new(RawMessageSerializer::new().into()),
+            log_schema: vector_core::config::log_schema(),
+        };
+        let message = Bytes::from("hello");
+
+        let request = request_builder
+            .build_request(Event::Log(LogEvent::from(message.clone())))
+            .unwrap();
+
+        assert_eq!(request.body, message);
+        assert_eq!(request.body.as_ptr(), message.as_ptr());
+    }
 }
@@ -1,13 +1,20 @@
-use std::task::{Context, Poll};
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use rdkafka::{
+    admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
     error::KafkaError,
     message::OwnedHeaders,
     producer::{FutureProducer, FutureRecord},
+    types::RDKafkaErrorCode,
     util::Timeout,
 };
+use tokio::sync::Mutex;
 use tower::Service;
 use vector_core::{
     internal_event::{BytesSent, EventsSent},
@@ -17,6 +24,7 @@ use vector_core::{
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     kafka::KafkaStatisticsContext,
+    sinks::kafka::config::KafkaTopicCreationConfig,
 };
 
 pub struct KafkaRequest {
@@ -57,15 +65,100 @@ impl Finalizable for KafkaRequest {
     }
 }
 
+/// Creates the topics the sink sends messages to, if they don't exist yet.
+pub struct TopicCreator {
+    admin_client: AdminClient<KafkaStatisticsContext>,
+    config: KafkaTopicCreationConfig,
+    /// The topics known to exist, or `None` once the sink turned out not to be allowed to create
+    /// topics, after which they're left to the brokers.
+    topics: Mutex<Option<HashSet<String>>>,
+}
+
+impl TopicCreator {
+    pub(crate) fn new(
+        admin_client: AdminClient<KafkaStatisticsContext>,
+        config: KafkaTopicCreationConfig,
+    ) -> Self {
+        Self {
+            admin_client,
+            config,
+            topics: Mutex::new(Some(HashSet::new())),
+        }
+    }
+
+    async fn ensure_topic(&self, topic: &str) -> Result<(), KafkaError> {
+        // Holding the lock during the creation keeps concurrent requests to a new topic from
+        // creating it more than once.
+        let mut guard = self.topics.lock().await;
+        let topics = match guard.as_mut() {
+            Some(topics) if !topics.contains(topic) => topics,
+            _ => return Ok(()),
+        };
+
+        // -1 uses the default of the brokers.
+        let replication = TopicReplication::Fixed(self.config.replication_factor.unwrap_or(-1));
+        let mut new_topic =
+            NewTopic::new(topic, self.config.num_partitions.unwrap_or(-1), replication);
+        for (key, value) in &self.config.config {
+            new_topic = new_topic.set(key, value);
+        }
+
+        let results = self
+            .admin_client
+            .create_topics(&[new_topic], &AdminOptions::new())
+            .await;
+        let result = results.and_then(|results| {
+            results.into_iter().try_for_each(|result| match result {
+                Ok(_) => {
+                    debug!(message = "Created topic.", %topic);
+                    Ok(())
+                }
+                Err((_, RDKafkaErrorCode::TopicAlreadyExists)) => Ok(()),
+                Err((_, code)) => Err(KafkaError::AdminOp(code)),
+            })
+        });
+        match result {
+            Ok(()) => {
+                topics.insert(topic.to_owned());
+                Ok(())
+            }
+            // Retrying won't grant the permission, and the brokers may still create the topics
+            // themselves, or they may already exist.
+            Err(KafkaError::AdminOp(code)) if is_unauthorized(code) => {
+                warn!(
+                    message = "Not allowed to create topics, no longer creating them.",
+                    %topic,
+                    error_code = %code,
+                );
+                *guard = None;
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+const fn is_unauthorized(code: RDKafkaErrorCode) -> bool {
+    matches!(
+        code,
+        RDKafkaErrorCode::TopicAuthorizationFailed | RDKafkaErrorCode::ClusterAuthorizationFailed
+    )
+}
+
 pub struct KafkaService {
     kafka_producer: FutureProducer<KafkaStatisticsContext>,
+    topic_creator: Option<Arc<TopicCreator>>,
 }
 
 impl KafkaService {
-    pub(crate) const fn new(
+    pub(crate) fn new(
         kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        topic_creator: Option<TopicCreator>,
     ) -> KafkaService {
-        KafkaService { kafka_producer }
+        KafkaService {
+            kafka_producer,
+            topic_creator: topic_creator.map(Arc::new),
+        }
     }
 }
 
@@ -80,8 +173,13 @@ impl Service<KafkaRequest> for KafkaService {
 
     fn call(&mut self, request: KafkaRequest) -> Self::Future {
         let kafka_producer = self.kafka_producer.clone();
+        let topic_creator = self.topic_creator.clone();
 
         Box::pin(async move {
+            if let Some(topic_creator) = topic_creator {
+                topic_creator.ensure_topic(&request.metadata.topic).await?;
+            }
+
             let mut record =
                 FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
             if let Some(key) = &request.metadata.key {
@@ -3,6 +3,7 @@ use std::convert::TryFrom;
 use async_trait::async_trait;
 use futures::{future, stream::BoxStream, StreamExt};
 use rdkafka::{
+    admin::AdminClient,
     consumer::{BaseConsumer, Consumer},
     error::KafkaError,
     producer::FutureProducer,
@@ -20,8 +21,9 @@ use crate::{
     kafka::KafkaStatisticsContext,
     sinks::{
         kafka::{
-            config::QUEUED_MIN_MESSAGES, request_builder::KafkaRequestBuilder,
-            service::KafkaService,
+            config::QUEUED_MIN_MESSAGES,
+            request_builder::KafkaRequestBuilder,
+            service::{KafkaService, TopicCreator},
         },
         util::{builder::SinkBuilderExt, StreamSink},
     },
@@ -32,6 +34,8 @@ use crate::{
 pub(super) enum BuildError {
     #[snafu(display("creating kafka producer failed: {}", source))]
     KafkaCreateFailed { source: KafkaError },
+    #[snafu(display("creating kafka admin client failed: {}", source))]
+    KafkaAdminCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateParseError },
 }
@@ -54,9 +58,25 @@ pub(crate) fn create_producer(
     Ok(producer)
 }
 
+pub(crate) fn create_admin_client(
+    client_config: ClientConfig,
+) -> crate::Result<AdminClient<KafkaStatisticsContext>> {
+    let admin_client = client_config
+        .create_with_context(KafkaStatisticsContext)
+        .context(KafkaAdminCreateFailedSnafu)?;
+    Ok(admin_client)
+}
+
 impl KafkaSink {
     pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
+        let topic_creator = match config.topic_creation {
+            Some(topic_creation) => Some(TopicCreator::new(
+                create_admin_client(producer_config.clone())?,
+                topic_creation,
+            )),
+            None => None,
+        };
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
@@ -66,7 +86,7 @@ impl KafkaSink {
             headers_key: config.headers_key,
             transformer,
             encoder,
-            service: KafkaService::new(producer),
+            service: KafkaService::new(producer, topic_creator),
             topic: Template::try_from(config.topic).context(TopicTemplateSnafu)?,
             key_field: config.key_field,
         })
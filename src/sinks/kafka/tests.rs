@@ -25,7 +25,7 @@ mod integration_test {
         kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
         sinks::{
             kafka::{
-                config::{KafkaRole, KafkaSinkConfig},
+                config::{KafkaPartitioner, KafkaRole, KafkaSinkConfig, KafkaTopicCreationConfig},
                 sink::KafkaSink,
                 *,
             },
@@ -65,11 +65,51 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            partitioner: None,
+            topic_creation: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn kafka_creates_topic() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: topic.clone(),
+            key_field: None,
+            encoding: TextSerializerConfig::new().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: 60000,
+            message_timeout_ms: 300000,
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            partitioner: Some(KafkaPartitioner::Murmur2),
+            topic_creation: Some(KafkaTopicCreationConfig {
+                num_partitions: Some(3),
+                replication_factor: Some(1),
+                config: HashMap::new(),
+            }),
+            acknowledgements: Default::default(),
+        };
+        let sink = VectorSink::from_event_streamsink(KafkaSink::new(config).unwrap());
+        let (_input, events) = random_lines_with_stream(100, 10, None);
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", kafka_address(9091));
+        let consumer: BaseConsumer = client_config.create().unwrap();
+        let metadata = consumer
+            .fetch_metadata(Some(&topic), Duration::from_secs(3))
+            .unwrap();
+        assert_eq!(metadata.topics()[0].partitions().len(), 3);
+    }
+
     #[tokio::test]
     async fn kafka_happy_path_plaintext() {
         crate::test_util::trace_init();
@@ -120,6 +160,8 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            partitioner: None,
+            topic_creation: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka(KafkaRole::Consumer)?;
@@ -251,6 +293,8 @@ mod integration_test {
             message_timeout_ms: 300000,
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            partitioner: None,
+            topic_creation: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
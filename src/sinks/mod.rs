@@ -42,6 +42,8 @@ pub mod datadog_archives;
 pub mod elasticsearch;
 #[cfg(feature = "sinks-file")]
 pub mod file;
+#[cfg(feature = "sinks-fluent")]
+pub mod fluent;
 #[cfg(feature = "sinks-gcp")]
 pub mod gcp;
 #[cfg(any(feature = "sinks-gcp"))]
@@ -54,6 +56,8 @@ pub mod http;
 pub mod humio;
 #[cfg(any(feature = "sinks-influxdb", feature = "prometheus-integration-tests"))]
 pub mod influxdb;
+#[cfg(feature = "sinks-json_ingest")]
+pub mod json_ingest;
 #[cfg(feature = "sinks-kafka")]
 pub mod kafka;
 #[cfg(feature = "sinks-logdna")]
@@ -68,10 +72,14 @@ pub mod new_relic;
 pub mod new_relic_logs;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
+#[cfg(feature = "sinks-postgres")]
+pub mod postgres;
 #[cfg(feature = "sinks-prometheus")]
 pub mod prometheus;
 #[cfg(feature = "sinks-pulsar")]
 pub mod pulsar;
+#[cfg(feature = "sinks-record")]
+pub mod record;
 #[cfg(feature = "sinks-redis")]
 pub mod redis;
 #[cfg(all(
@@ -87,6 +95,8 @@ pub mod socket;
 pub mod splunk_hec;
 #[cfg(feature = "sinks-statsd")]
 pub mod statsd;
+#[cfg(feature = "sinks-syslog")]
+pub mod syslog;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
 #[cfg(feature = "sinks-websocket")]
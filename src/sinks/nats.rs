@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, time::Duration};
 
 use async_trait::async_trait;
 use bytes::BytesMut;
@@ -7,6 +7,7 @@ use futures::{stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Encoder as _;
+use uuid::Uuid;
 use vector_common::internal_event::{BytesSent, EventsSent};
 use vector_core::ByteSizeOf;
 
@@ -17,8 +18,11 @@ use crate::{
         SinkDescription,
     },
     event::{Event, EventStatus, Finalizable},
-    internal_events::{NatsEventSendError, TemplateRenderingError},
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
+    internal_events::{NatsEventSendError, NatsJetStreamPublishError, TemplateRenderingError},
+    nats::{
+        from_tls_auth_config, parse_jetstream_response, JetStreamError, NatsAuthConfig,
+        NatsConfigError,
+    },
     sinks::util::StreamSink,
     template::{Template, TemplateParseError},
     tls::TlsEnableableConfig,
@@ -36,8 +40,17 @@ enum BuildError {
     Config { source: NatsConfigError },
     #[snafu(display("NATS Connect Error: {}", source))]
     Connect { source: std::io::Error },
+    #[snafu(display("NATS Subscribe Error: {}", source))]
+    Subscribe { source: std::io::Error },
+    #[snafu(display("invalid message_id template: {}", source))]
+    MessageIdTemplate { source: TemplateParseError },
+    #[snafu(display("message_id can only be set when publishing to JetStream"))]
+    MessageIdWithoutJetStream,
 }
 
+/// How long to wait for JetStream to acknowledge a message.
+const PUBLISH_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
 /**
  * Code dealing with the SinkConfig struct.
  */
@@ -58,6 +71,9 @@ pub struct NatsSinkConfig {
     url: String,
     tls: Option<TlsEnableableConfig>,
     auth: Option<NatsAuthConfig>,
+    #[serde(default)]
+    jetstream: bool,
+    message_id: Option<String>,
 }
 
 fn default_name() -> String {
@@ -78,6 +94,8 @@ impl GenerateConfig for NatsSinkConfig {
             subject: "from.vector".into(),
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
+            jetstream: false,
+            message_id: None,
         })
         .unwrap()
     }
@@ -128,27 +146,149 @@ async fn healthcheck(config: NatsSinkConfig) -> crate::Result<()> {
     config.connect().map_ok(|_| ()).map_err(|e| e.into()).await
 }
 
+/// The acknowledgement of a message published to JetStream.
+#[derive(Debug, Deserialize)]
+struct PubAck {
+    stream: String,
+    seq: u64,
+    #[serde(default)]
+    duplicate: bool,
+}
+
+/// Publishes messages to JetStream, waiting for the stream to acknowledge each of them.
+///
+/// Replies are received on a single subscription, with a subject per message so that the late
+/// replies of messages that timed out aren't mistaken for the reply to the current one.
+struct JetStreamPublisher {
+    inbox: String,
+    replies: nats::asynk::Subscription,
+    next_id: u64,
+}
+
+impl JetStreamPublisher {
+    async fn new(connection: &nats::asynk::Connection) -> Result<Self, BuildError> {
+        let inbox = format!("_INBOX.{}", Uuid::new_v4().simple());
+        let replies = connection
+            .subscribe(&format!("{}.*", inbox))
+            .await
+            .context(SubscribeSnafu)?;
+        Ok(Self {
+            inbox,
+            replies,
+            next_id: 0,
+        })
+    }
+
+    async fn publish(
+        &mut self,
+        connection: &nats::asynk::Connection,
+        subject: &str,
+        message_id: Option<String>,
+        payload: &[u8],
+    ) -> Result<PubAck, JetStreamError> {
+        self.next_id += 1;
+        let reply = format!("{}.{}", self.inbox, self.next_id);
+        let headers = message_id.map(|id| {
+            let mut headers = nats::header::HeaderMap::new();
+            // Messages with the same ID are only stored once within the duplicate window of
+            // the stream.
+            headers.insert("Nats-Msg-Id", id);
+            headers
+        });
+        connection
+            .publish_with_reply_or_headers(subject, Some(&reply), headers.as_ref(), payload)
+            .await
+            .map_err(|source| JetStreamError::Request { source })?;
+
+        loop {
+            let msg = tokio::time::timeout(PUBLISH_ACK_TIMEOUT, self.replies.next())
+                .await
+                .map_err(|_| JetStreamError::Timeout)?
+                .ok_or_else(|| JetStreamError::Request {
+                    source: std::io::Error::new(
+                        std::io::ErrorKind::ConnectionAborted,
+                        "reply subscription closed",
+                    ),
+                })?;
+            if msg.subject == reply {
+                return parse_jetstream_response(&msg.data);
+            }
+        }
+    }
+}
+
 pub struct NatsSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     connection: nats::asynk::Connection,
     subject: Template,
+    jetstream: Option<JetStreamPublisher>,
+    message_id: Option<Template>,
 }
 
 impl NatsSink {
     async fn new(config: NatsSinkConfig) -> Result<Self, BuildError> {
+        if config.message_id.is_some() && !config.jetstream {
+            return Err(BuildError::MessageIdWithoutJetStream);
+        }
+        let message_id = config
+            .message_id
+            .as_deref()
+            .map(Template::try_from)
+            .transpose()
+            .context(MessageIdTemplateSnafu)?;
+
         let connection = config.connect().await?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
+        let jetstream = match config.jetstream {
+            true => Some(JetStreamPublisher::new(&connection).await?),
+            false => None,
+        };
 
         Ok(NatsSink {
             connection,
             transformer,
             encoder,
             subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
+            jetstream,
+            message_id,
         })
     }
+
+    /// Publishes a message, returning whether it was delivered.
+    async fn publish(&mut self, subject: &str, message_id: Option<String>, payload: &[u8]) -> bool {
+        match &mut self.jetstream {
+            None => match self.connection.publish(subject, payload).await {
+                Ok(()) => true,
+                Err(error) => {
+                    emit!(NatsEventSendError { error });
+                    false
+                }
+            },
+            Some(jetstream) => {
+                match jetstream
+                    .publish(&self.connection, subject, message_id, payload)
+                    .await
+                {
+                    Ok(ack) => {
+                        trace!(
+                            message = "Message stored by JetStream.",
+                            stream = %ack.stream,
+                            seq = ack.seq,
+                            duplicate = ack.duplicate,
+                        );
+                        true
+                    }
+                    Err(error) => {
+                        emit!(NatsJetStreamPublishError { error: &error });
+                        false
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -170,6 +310,19 @@ impl StreamSink<Event> for NatsSink {
                 }
             };
 
+            let message_id = self.message_id.as_ref().and_then(|template| {
+                template
+                    .render_string(&event)
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some("message_id"),
+                            drop_event: false,
+                        });
+                    })
+                    .ok()
+            });
+
             self.transformer.transform(&mut event);
 
             let event_byte_size = event.size_of();
@@ -181,13 +334,11 @@ impl StreamSink<Event> for NatsSink {
                 continue;
             }
 
-            match self.connection.publish(&subject, &bytes).await {
-                Err(error) => {
+            match self.publish(&subject, message_id, &bytes).await {
+                false => {
                     finalizers.update_status(EventStatus::Errored);
-
-                    emit!(NatsEventSendError { error });
                 }
-                Ok(_) => {
+                true => {
                     finalizers.update_status(EventStatus::Delivered);
 
                     emit!(EventsSent {
@@ -215,6 +366,24 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSinkConfig>();
     }
+
+    #[tokio::test]
+    async fn message_id_requires_jetstream() {
+        let config: NatsSinkConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            subject = "logs"
+            encoding.codec = "json"
+            message_id = "{{ id }}"
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            NatsSink::new(config).await,
+            Err(BuildError::MessageIdWithoutJetStream)
+        ));
+    }
 }
 
 #[cfg(feature = "nats-integration-tests")]
@@ -284,6 +453,8 @@ mod integration_tests {
             std::env::var("NATS_ADDRESS").unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -310,6 +481,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -338,6 +511,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -369,6 +544,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -399,6 +576,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -429,6 +608,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -460,6 +641,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -491,6 +674,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -523,6 +708,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -549,6 +736,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -583,6 +772,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -615,6 +806,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -651,6 +844,8 @@ mod integration_tests {
             .unwrap_or_else(|_| String::from("nats://localhost:4222"));
 
         let conf = NatsSinkConfig {
+            jetstream: false,
+            message_id: None,
             acknowledgements: Default::default(),
             encoding: TextSerializerConfig::new().into(),
             connection_name: "".to_owned(),
@@ -133,7 +133,7 @@ impl NewRelicLogsConfig {
             method: Some(HttpMethod::Post),
             auth: None,
             headers: None,
-            compression: self.compression,
+            compression: self.compression.into(),
             encoding: EncodingConfigWithFraming::new(
                 Some(CharacterDelimitedEncoderConfig::new(b',').into()),
                 JsonSerializerConfig::new().into(),
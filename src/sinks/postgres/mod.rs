@@ -0,0 +1,15 @@
+//! A sink inserting batches of log events into a PostgreSQL table, with each column of the
+//! table filled from a field of the events.
+
+use crate::config::SinkDescription;
+
+mod config;
+mod service;
+mod sink;
+mod statement;
+
+pub use config::PostgresSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<PostgresSinkConfig>("postgres")
+}
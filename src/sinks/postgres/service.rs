@@ -0,0 +1,243 @@
+use std::{
+    path::PathBuf,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures::{future::BoxFuture, pin_mut, SinkExt};
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslConnector, SslMethod},
+};
+use postgres_openssl::MakeTlsConnector;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::Mutex;
+use tokio_postgres::{error::SqlState, Client, Config, Error as PgError, NoTls};
+use tower::Service;
+use vector_common::internal_event::BytesSent;
+use vector_core::{internal_event::EventsSent, stream::DriverResponse};
+
+use super::statement::{InsertMethod, Statements};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    sinks::util::retries::RetryLogic,
+};
+
+#[derive(Debug, Snafu)]
+pub enum PostgresSinkError {
+    #[snafu(display("Failed to create TLS connector: {}", source))]
+    Tls { source: ErrorStack },
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: PgError },
+    #[snafu(display("Failed to write rows: {}", source))]
+    Write { source: PgError },
+}
+
+#[derive(Clone, Debug)]
+pub struct PostgresRequest {
+    /// The rows of the batch, encoded for the insert method of the sink.
+    pub body: Bytes,
+    pub finalizers: EventFinalizers,
+    pub events_count: usize,
+    pub events_byte_size: usize,
+}
+
+impl Finalizable for PostgresRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+#[derive(Debug)]
+pub struct PostgresResponse {
+    events_count: usize,
+    events_byte_size: usize,
+    body_size: usize,
+}
+
+impl DriverResponse for PostgresResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.events_count,
+            byte_size: self.events_byte_size,
+            output: None,
+        }
+    }
+
+    fn bytes_sent(&self) -> Option<BytesSent> {
+        Some(BytesSent {
+            byte_size: self.body_size,
+            protocol: "tcp",
+        })
+    }
+}
+
+/// Connects to the database, over TLS when a CA file is set.
+#[derive(Clone, Debug)]
+pub struct PostgresConnector {
+    config: Config,
+    ca_file: Option<PathBuf>,
+}
+
+impl PostgresConnector {
+    pub const fn new(config: Config, ca_file: Option<PathBuf>) -> Self {
+        Self { config, ca_file }
+    }
+
+    pub async fn connect(&self) -> Result<Client, PostgresSinkError> {
+        let client = match &self.ca_file {
+            Some(ca_file) => {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls_client()).context(TlsSnafu)?;
+                builder.set_ca_file(ca_file).context(TlsSnafu)?;
+                let connector = MakeTlsConnector::new(builder.build());
+
+                let (client, connection) =
+                    self.config.connect(connector).await.context(ConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+            None => {
+                let (client, connection) =
+                    self.config.connect(NoTls).await.context(ConnectSnafu)?;
+                tokio::spawn(connection);
+                client
+            }
+        };
+        Ok(client)
+    }
+}
+
+#[derive(Clone)]
+pub struct PostgresService {
+    connector: PostgresConnector,
+    /// The connection shared by the requests, reopened once it's closed.
+    client: Arc<Mutex<Option<Arc<Client>>>>,
+    statements: Arc<Statements>,
+    method: InsertMethod,
+}
+
+impl PostgresService {
+    pub fn new(connector: PostgresConnector, statements: Statements, method: InsertMethod) -> Self {
+        Self {
+            connector,
+            client: Arc::default(),
+            statements: Arc::new(statements),
+            method,
+        }
+    }
+
+    async fn client(&self) -> Result<Arc<Client>, PostgresSinkError> {
+        let mut client = self.client.lock().await;
+        match &*client {
+            Some(client) if !client.is_closed() => Ok(Arc::clone(client)),
+            _ => {
+                let connected = Arc::new(self.connector.connect().await?);
+                *client = Some(Arc::clone(&connected));
+                Ok(connected)
+            }
+        }
+    }
+
+    async fn write(&self, body: Bytes) -> Result<(), PostgresSinkError> {
+        let client = self.client().await?;
+        match self.method {
+            InsertMethod::Insert => {
+                let rows = String::from_utf8(body.to_vec()).expect("rows are encoded as JSON");
+                client
+                    .execute(self.statements.insert(), &[&rows])
+                    .await
+                    .context(WriteSnafu)?;
+            }
+            InsertMethod::Copy => {
+                let sink = client
+                    .copy_in::<_, Bytes>(self.statements.copy())
+                    .await
+                    .context(WriteSnafu)?;
+                pin_mut!(sink);
+                sink.send(body).await.context(WriteSnafu)?;
+                sink.finish().await.context(WriteSnafu)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Service<PostgresRequest> for PostgresService {
+    type Response = PostgresResponse;
+    type Error = PostgresSinkError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PostgresRequest) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let body_size = request.body.len();
+            service.write(request.body).await?;
+            Ok(PostgresResponse {
+                events_count: request.events_count,
+                events_byte_size: request.events_byte_size,
+                body_size,
+            })
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PostgresRetryLogic;
+
+impl RetryLogic for PostgresRetryLogic {
+    type Error = PostgresSinkError;
+    type Response = PostgresResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            PostgresSinkError::Tls { .. } => false,
+            PostgresSinkError::Connect { .. } => true,
+            PostgresSinkError::Write { source } => match source.code() {
+                // Errors without a code happened on the connection rather than in the database.
+                None => true,
+                Some(code) => is_retriable_code(code),
+            },
+        }
+    }
+}
+
+/// Whether the statement may succeed when retried, as opposed to errors caused by the rows
+/// themselves, such as invalid values or constraint violations.
+///
+/// https://www.postgresql.org/docs/current/errcodes-appendix.html
+fn is_retriable_code(code: &SqlState) -> bool {
+    let code = code.code();
+    // Connection exceptions, transaction rollbacks (such as serialization failures and
+    // deadlocks), insufficient resources and operator intervention (such as shutdowns).
+    ["08", "40", "53", "57"]
+        .iter()
+        .any(|class| code.starts_with(class))
+        && code != SqlState::QUERY_CANCELED.code()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_transient_errors_only() {
+        assert!(is_retriable_code(&SqlState::CONNECTION_FAILURE));
+        assert!(is_retriable_code(&SqlState::T_R_DEADLOCK_DETECTED));
+        assert!(is_retriable_code(&SqlState::T_R_SERIALIZATION_FAILURE));
+        assert!(is_retriable_code(&SqlState::ADMIN_SHUTDOWN));
+        assert!(!is_retriable_code(&SqlState::QUERY_CANCELED));
+        assert!(!is_retriable_code(&SqlState::UNIQUE_VIOLATION));
+        assert!(!is_retriable_code(&SqlState::INVALID_TEXT_REPRESENTATION));
+        assert!(!is_retriable_code(&SqlState::UNDEFINED_TABLE));
+    }
+}
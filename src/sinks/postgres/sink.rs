@@ -0,0 +1,78 @@
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{
+    service::PostgresRequest,
+    statement::{InsertMethod, Statements},
+};
+use crate::{
+    codecs::Transformer,
+    event::{Event, Finalizable},
+    sinks::util::{SinkBuilderExt, StreamSink},
+};
+
+pub struct PostgresSink<S> {
+    pub service: S,
+    pub batch_settings: BatcherSettings,
+    pub transformer: Transformer,
+    pub statements: Arc<Statements>,
+    pub method: InsertMethod,
+}
+
+impl<S> PostgresSink<S>
+where
+    S: Service<PostgresRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let transformer = self.transformer;
+        let statements = self.statements;
+        let method = self.method;
+
+        input
+            .map(move |mut event| {
+                transformer.transform(&mut event);
+                event
+            })
+            .batched(self.batch_settings.into_byte_size_config())
+            .map(move |mut events| {
+                let events_byte_size = events.size_of();
+                let finalizers = events.take_finalizers();
+                let body = match method {
+                    InsertMethod::Insert => statements.encode_json(&events),
+                    InsertMethod::Copy => statements.encode_csv(&events),
+                };
+                PostgresRequest {
+                    body,
+                    finalizers,
+                    events_count: events.len(),
+                    events_byte_size,
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for PostgresSink<S>
+where
+    S: Service<PostgresRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
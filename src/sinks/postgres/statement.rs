@@ -0,0 +1,295 @@
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use serde::{Deserialize, Serialize};
+
+use crate::event::{Event, Value};
+
+/// How batches are written to the table.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsertMethod {
+    /// A single `INSERT` statement per batch, which supports `on_conflict`.
+    Insert,
+    /// `COPY ... FROM STDIN`, which loads large batches faster but fails the whole batch on
+    /// conflicts.
+    Copy,
+}
+
+impl Default for InsertMethod {
+    fn default() -> Self {
+        Self::Insert
+    }
+}
+
+/// What to do with rows conflicting with a unique constraint of the table.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "action")]
+pub enum OnConflict {
+    /// Skips the conflicting rows.
+    DoNothing {
+        /// The columns of the unique constraint, if only its conflicts should be skipped.
+        #[serde(default)]
+        target: Vec<String>,
+    },
+    /// Updates the conflicting rows with the values of the new rows.
+    Update {
+        /// The columns of the unique constraint. The other columns are updated.
+        target: Vec<String>,
+    },
+}
+
+/// The statements writing the columns of batches of events to a table.
+#[derive(Clone, Debug)]
+pub struct Statements {
+    columns: Vec<(String, String)>,
+    select_none: String,
+    insert: String,
+    copy: String,
+}
+
+impl Statements {
+    /// Builds the statements from the table name and the mapping of column names to event
+    /// fields.
+    pub fn new(
+        table: &str,
+        columns: &BTreeMap<String, String>,
+        on_conflict: Option<&OnConflict>,
+    ) -> Self {
+        let table = quote_table(table);
+        let names = columns.keys().map(|name| quote_identifier(name));
+        let names = names.collect::<Vec<_>>().join(", ");
+
+        // The batch is sent as a single JSON array of objects keyed by column name, which
+        // Postgres converts to the types of the columns of the table.
+        let mut insert = format!(
+            "INSERT INTO {table} ({names}) SELECT {names} FROM json_populate_recordset(NULL::{table}, $1::text::json)",
+            table = table,
+            names = names
+        );
+        match on_conflict {
+            None => {}
+            Some(OnConflict::DoNothing { target }) => {
+                insert.push_str(" ON CONFLICT");
+                if !target.is_empty() {
+                    write!(insert, " ({})", quote_list(target)).expect("writing to a string");
+                }
+                insert.push_str(" DO NOTHING");
+            }
+            Some(OnConflict::Update { target }) => {
+                let updates = columns
+                    .keys()
+                    .filter(|name| !target.contains(name))
+                    .map(|name| {
+                        let name = quote_identifier(name);
+                        format!("{} = EXCLUDED.{}", name, name)
+                    })
+                    .collect::<Vec<_>>();
+                write!(insert, " ON CONFLICT ({})", quote_list(target))
+                    .expect("writing to a string");
+                if updates.is_empty() {
+                    insert.push_str(" DO NOTHING");
+                } else {
+                    write!(insert, " DO UPDATE SET {}", updates.join(", "))
+                        .expect("writing to a string");
+                }
+            }
+        }
+
+        let select_none = format!("SELECT {} FROM {} LIMIT 0", names, table);
+        let copy = format!("COPY {} ({}) FROM STDIN WITH (FORMAT csv)", table, names);
+
+        Self {
+            columns: columns
+                .iter()
+                .map(|(name, field)| (name.clone(), field.clone()))
+                .collect(),
+            select_none,
+            insert,
+            copy,
+        }
+    }
+
+    /// A query returning no rows, which fails if the table or one of the columns is missing.
+    pub fn select_none(&self) -> &str {
+        &self.select_none
+    }
+
+    pub fn insert(&self) -> &str {
+        &self.insert
+    }
+
+    pub fn copy(&self) -> &str {
+        &self.copy
+    }
+
+    /// Encodes the rows of the events as the parameter of the `INSERT` statement.
+    pub fn encode_json(&self, events: &[Event]) -> Bytes {
+        let rows = events
+            .iter()
+            .map(|event| {
+                self.columns
+                    .iter()
+                    .map(|(name, field)| {
+                        let value = get_field(event, field)
+                            .map(|value| {
+                                serde_json::to_value(value).expect("value is serializable")
+                            })
+                            .unwrap_or(serde_json::Value::Null);
+                        (name.clone(), value)
+                    })
+                    .collect::<serde_json::Map<_, _>>()
+            })
+            .collect::<Vec<_>>();
+        serde_json::to_vec(&rows)
+            .expect("rows are serializable")
+            .into()
+    }
+
+    /// Encodes the rows of the events as the CSV data of the `COPY` statement.
+    pub fn encode_csv(&self, events: &[Event]) -> Bytes {
+        let mut csv = BytesMut::new();
+        for event in events {
+            for (i, (_, field)) in self.columns.iter().enumerate() {
+                if i > 0 {
+                    csv.put_u8(b',');
+                }
+                write_csv_value(&mut csv, get_field(event, field));
+            }
+            csv.put_u8(b'\n');
+        }
+        csv.freeze()
+    }
+}
+
+fn get_field<'a>(event: &'a Event, field: &str) -> Option<&'a Value> {
+    event
+        .maybe_as_log()
+        .and_then(|log| log.get(field))
+        .filter(|value| !matches!(value, Value::Null))
+}
+
+/// Writes a value of a CSV row. Missing values are written unquoted, which `COPY` reads as
+/// `NULL`, while all others are quoted so that empty strings stay empty strings.
+fn write_csv_value(csv: &mut BytesMut, value: Option<&Value>) {
+    let text = match value {
+        None => return,
+        Some(Value::Bytes(bytes)) => String::from_utf8_lossy(bytes).into_owned(),
+        Some(Value::Object(_) | Value::Array(_)) => {
+            serde_json::to_string(value).expect("value is serializable")
+        }
+        Some(value) => value.to_string_lossy(),
+    };
+    csv.put_u8(b'"');
+    csv.put_slice(text.replace('"', "\"\"").as_bytes());
+    csv.put_u8(b'"');
+}
+
+/// Quotes a table name, which may be qualified by its schema.
+fn quote_table(table: &str) -> String {
+    table
+        .split('.')
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn quote_list(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn columns() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("id".into(), "request_id".into()),
+            ("message".into(), "message".into()),
+            ("timestamp".into(), "timestamp".into()),
+        ])
+    }
+
+    #[test]
+    fn builds_statements() {
+        let statements = Statements::new("logs.requests", &columns(), None);
+        assert_eq!(
+            statements.insert(),
+            r#"INSERT INTO "logs"."requests" ("id", "message", "timestamp") SELECT "id", "message", "timestamp" FROM json_populate_recordset(NULL::"logs"."requests", $1::text::json)"#
+        );
+        assert_eq!(
+            statements.copy(),
+            r#"COPY "logs"."requests" ("id", "message", "timestamp") FROM STDIN WITH (FORMAT csv)"#
+        );
+    }
+
+    #[test]
+    fn builds_conflict_clauses() {
+        let statements = Statements::new(
+            "requests",
+            &columns(),
+            Some(&OnConflict::DoNothing { target: vec![] }),
+        );
+        assert!(statements.insert().ends_with(" ON CONFLICT DO NOTHING"));
+
+        let statements = Statements::new(
+            "requests",
+            &columns(),
+            Some(&OnConflict::Update {
+                target: vec!["id".into()],
+            }),
+        );
+        assert!(statements.insert().ends_with(
+            r#" ON CONFLICT ("id") DO UPDATE SET "message" = EXCLUDED."message", "timestamp" = EXCLUDED."timestamp""#
+        ));
+    }
+
+    #[test]
+    fn quotes_identifiers() {
+        assert_eq!(quote_table(r#"my "table""#), r#""my ""table""""#);
+    }
+
+    fn events() -> Vec<Event> {
+        let mut log = LogEvent::from("hello, \"world\"");
+        log.insert("request_id", 42);
+        log.insert("timestamp", Utc.ymd(2022, 8, 1).and_hms(13, 5, 0));
+        let mut empty = LogEvent::from("");
+        empty.insert("request_id", Value::Null);
+        empty.remove("timestamp");
+        vec![log.into(), empty.into()]
+    }
+
+    #[test]
+    fn encodes_json_rows() {
+        let statements = Statements::new("requests", &columns(), None);
+        let rows: serde_json::Value =
+            serde_json::from_slice(&statements.encode_json(&events())).unwrap();
+        assert_eq!(
+            rows,
+            serde_json::json!([
+                {"id": 42, "message": "hello, \"world\"", "timestamp": "2022-08-01T13:05:00Z"},
+                {"id": null, "message": "", "timestamp": null},
+            ])
+        );
+    }
+
+    #[test]
+    fn encodes_csv_rows() {
+        let statements = Statements::new("requests", &columns(), None);
+        assert_eq!(
+            statements.encode_csv(&events()),
+            "\"42\",\"hello, \"\"world\"\"\",\"2022-08-01T13:05:00Z\"\n,\"\",\n"
+        );
+    }
+}
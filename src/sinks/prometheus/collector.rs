@@ -212,6 +212,9 @@ pub(super) trait MetricCollector {
                             None,
                         );
                     }
+                    MetricSketch::HyperLogLog(hll) => {
+                        self.emit_value(timestamp, name, "", hll.estimate().round(), tags, None);
+                    }
                 },
             }
         }
@@ -433,7 +436,13 @@ const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType
         } => MetricType::Summary,
         MetricValue::AggregatedHistogram { .. } => MetricType::Histogram,
         MetricValue::AggregatedSummary { .. } => MetricType::Summary,
-        MetricValue::Sketch { .. } => MetricType::Summary,
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(_),
+        } => MetricType::Summary,
+        // Like sets, approximate sets are exposed as the gauge of their number of unique values.
+        MetricValue::Sketch {
+            sketch: MetricSketch::HyperLogLog(_),
+        } => MetricType::Gauge,
     }
 }
 
@@ -1,4 +1,4 @@
-use std::task;
+use std::{collections::HashMap, task};
 
 use bytes::{Bytes, BytesMut};
 use futures::{future::BoxFuture, stream, FutureExt, SinkExt};
@@ -11,7 +11,7 @@ use vector_core::ByteSizeOf;
 use super::collector::{self, MetricCollector as _};
 use crate::{
     config::{self, AcknowledgementsConfig, Input, SinkConfig, SinkDescription},
-    event::{Event, Metric},
+    event::{Event, EventStatus, Finalizable, Metric},
     http::{Auth, HttpClient},
     internal_events::TemplateRenderingError,
     sinks::{
@@ -20,8 +20,8 @@ use crate::{
             batch::BatchConfig,
             buffer::metrics::{MetricNormalize, MetricNormalizer, MetricSet, MetricsBuffer},
             http::HttpRetryLogic,
-            EncodedEvent, PartitionBuffer, PartitionInnerBuffer, SinkBatchSettings,
-            TowerRequestConfig,
+            EncodedEvent, PartitionBuffer, PartitionInnerBuffer, SinkBatchSettings, TagRule,
+            TagRules, TowerRequestConfig,
         },
     },
     template::Template,
@@ -63,6 +63,15 @@ pub struct RemoteWriteConfig {
     #[serde(default)]
     pub tenant_id: Option<Template>,
 
+    /// The tenant to send metrics to when `tenant_id` fails to render for a metric. If not set,
+    /// such metrics are dropped rather than sent without a tenant.
+    #[serde(default)]
+    pub default_tenant_id: Option<String>,
+
+    /// Rules rewriting the tags of metrics, applied in order before they are sent.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_rules: Vec<TagRule>,
+
     pub tls: Option<TlsConfig>,
 
     pub auth: Option<Auth>,
@@ -88,7 +97,8 @@ impl SinkConfig for RemoteWriteConfig {
         let buckets = self.buckets.clone();
         let quantiles = self.quantiles.clone();
 
-        let client = HttpClient::new(tls_settings, cx.proxy())?;
+        let client =
+            HttpClient::new_with_connection_pool(tls_settings, cx.proxy(), cx.connection_pool())?;
         let tenant_id = self.tenant_id.clone();
         let auth = self.auth.clone();
 
@@ -104,28 +114,40 @@ impl SinkConfig for RemoteWriteConfig {
 
         let sink = {
             let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
-            let mut normalizer = MetricNormalizer::<PrometheusMetricNormalize>::default();
+            let default_tenant_id = self.default_tenant_id.clone();
+            let tag_rules = TagRules::new(&self.tag_rules)?;
+            // Each tenant keeps its own normalizer state, so that incremental metrics with the
+            // same series from different tenants are never accumulated together.
+            let mut normalizers =
+                HashMap::<Option<String>, MetricNormalizer<PrometheusMetricNormalize>>::new();
 
             request_settings
                 .partition_sink(HttpRetryLogic, service, buffer, batch.timeout)
                 .with_flat_map(move |event: Event| {
                     let byte_size = event.size_of();
-                    stream::iter(normalizer.normalize(event.into_metric()).map(|event| {
-                        let tenant_id = tenant_id.as_ref().and_then(|template| {
-                            template
-                                .render_string(&event)
-                                .map_err(|error| {
-                                    emit!(TemplateRenderingError {
-                                        error,
-                                        field: Some("tenant_id"),
-                                        drop_event: false,
-                                    })
-                                })
-                                .ok()
-                        });
+                    let mut metric = event.into_metric();
+                    tag_rules.apply(&mut metric);
+                    let tenant_id = match &tenant_id {
+                        Some(template) => {
+                            match render_tenant_id(template, &metric, &default_tenant_id) {
+                                Some(tenant_id) => Some(tenant_id),
+                                None => {
+                                    // The metric can never be sent, so it's rejected to its source.
+                                    metric.take_finalizers().update_status(EventStatus::Rejected);
+                                    return stream::iter(None);
+                                }
+                            }
+                        }
+                        None => None,
+                    };
+                    let normalized = normalizers
+                        .entry(tenant_id.clone())
+                        .or_insert_with(MetricNormalizer::default)
+                        .normalize(metric);
+                    stream::iter(normalized.map(|metric| {
                         let key = PartitionKey { tenant_id };
                         Ok(EncodedEvent::new(
-                            PartitionInnerBuffer::new(event, key),
+                            PartitionInnerBuffer::new(metric, key),
                             byte_size,
                         ))
                     }))
@@ -156,6 +178,26 @@ struct PartitionKey {
     tenant_id: Option<String>,
 }
 
+/// Renders the tenant of a metric, falling back to the default tenant if the template can't be
+/// rendered. Returns `None` if the metric has no tenant and should be dropped.
+fn render_tenant_id(
+    template: &Template,
+    metric: &Metric,
+    default_tenant_id: &Option<String>,
+) -> Option<String> {
+    match template.render_string(metric) {
+        Ok(tenant_id) => Some(tenant_id),
+        Err(error) => {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some("tenant_id"),
+                drop_event: default_tenant_id.is_none(),
+            });
+            default_tenant_id.clone()
+        }
+    }
+}
+
 async fn healthcheck(endpoint: Uri, client: HttpClient) -> crate::Result<()> {
     let request = http::Request::get(endpoint)
         .body(hyper::Body::empty())
@@ -260,7 +302,7 @@ mod tests {
     use super::*;
     use crate::{
         config::SinkContext,
-        event::{MetricKind, MetricValue},
+        event::{BatchNotifier, BatchStatus, MetricKind, MetricValue},
         sinks::util::test::build_test_server,
         test_util,
     };
@@ -358,6 +400,72 @@ mod tests {
         assert_eq!(orgid.len(), 11);
     }
 
+    #[tokio::test]
+    async fn partitions_by_tenant() {
+        let outputs = send_request(
+            r#"tenant_id = "{{ tags.tenant }}""#,
+            vec![
+                create_tenant_event("counter-1", "a", 1.0),
+                create_tenant_event("counter-1", "b", 2.0),
+                create_tenant_event("counter-1", "a", 3.0),
+            ],
+        )
+        .await;
+
+        assert_eq!(outputs.len(), 2);
+        let mut values = outputs
+            .iter()
+            .map(|(headers, req)| {
+                assert_eq!(req.timeseries.len(), 1);
+                let samples = &req.timeseries[0].samples;
+                (
+                    headers["x-scope-orgid"].to_str().unwrap().to_owned(),
+                    samples[samples.len() - 1].value,
+                )
+            })
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // Incremental values are accumulated separately for each tenant.
+        assert_eq!(values, vec![("a".into(), 4.0), ("b".into(), 2.0)]);
+    }
+
+    #[tokio::test]
+    async fn drops_metrics_without_tenant() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let outputs = send_request(
+            r#"tenant_id = "{{ tags.tenant }}""#,
+            vec![
+                create_event("gauge-1".into(), 1.0).with_batch_notifier(&batch),
+                create_tenant_event("counter-1", "a", 1.0),
+            ],
+        )
+        .await;
+        drop(batch);
+
+        assert_eq!(outputs.len(), 1);
+        let (headers, req) = &outputs[0];
+        assert_eq!(headers["x-scope-orgid"], "a");
+        assert_eq!(req.timeseries.len(), 1);
+        assert_eq!(receiver.await, BatchStatus::Rejected);
+    }
+
+    #[tokio::test]
+    async fn sends_metrics_without_tenant_to_default_tenant() {
+        let outputs = send_request(
+            indoc! {r#"
+                tenant_id = "{{ tags.tenant }}"
+                default_tenant_id = "fallback"
+            "#},
+            vec![create_event("gauge-1".into(), 1.0)],
+        )
+        .await;
+
+        assert_eq!(outputs.len(), 1);
+        let (headers, _) = &outputs[0];
+        assert_eq!(headers["x-scope-orgid"], "fallback");
+    }
+
     #[tokio::test]
     async fn retains_state_between_requests() {
         // This sink converts all incremental events to absolute, and
@@ -440,6 +548,21 @@ mod tests {
             .into()
     }
 
+    fn create_tenant_event(name: &str, tenant: &str, value: f64) -> Event {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+        .with_tags(Some(
+            vec![("tenant".to_owned(), tenant.to_owned())]
+                .into_iter()
+                .collect(),
+        ))
+        .with_timestamp(Some(chrono::Utc::now()))
+        .into()
+    }
+
     fn create_inc_event(name: String, value: f64) -> Event {
         Metric::new(
             name,
@@ -25,13 +25,21 @@ use crate::{
         AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
     },
     event::{Event, EventFinalizers, Finalizable},
+    internal_events::TemplateRenderingError,
     sinks::util::metadata::RequestMetadata,
+    template::{Template, TemplateParseError},
 };
 
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("creating pulsar producer failed: {}", source))]
     CreatePulsarSink { source: PulsarError },
+    #[snafu(display("partition_key template parse error: {}", source))]
+    PartitionKeyTemplate { source: TemplateParseError },
+    #[snafu(display("json_schema is not valid JSON: {}", source))]
+    InvalidJsonSchema { source: serde_json::Error },
+    #[snafu(display("json_schema can only be set with the `json` codec"))]
+    JsonSchemaWithoutJsonCodec,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -40,7 +48,13 @@ pub struct PulsarSinkConfig {
     #[serde(alias = "address")]
     endpoint: String,
     topic: String,
+    /// The key used to route the message to a partition of the topic, and to order messages
+    /// with `Key_Shared` subscriptions.
+    partition_key: Option<String>,
     pub encoding: EncodingConfig,
+    /// A JSON schema definition, in the Avro schema format Pulsar uses for JSON schemas, attached
+    /// to the producer when encoding events with the `json` codec.
+    json_schema: Option<String>,
     auth: Option<AuthConfig>,
 }
 
@@ -79,6 +93,7 @@ enum PulsarSinkState {
 }
 
 struct PulsarSink {
+    partition_key: Option<Template>,
     transformer: Transformer,
     encoder: Encoder<()>,
     state: PulsarSinkState,
@@ -103,7 +118,9 @@ impl GenerateConfig for PulsarSinkConfig {
         toml::Value::try_from(Self {
             endpoint: "pulsar://127.0.0.1:6650".to_string(),
             topic: "topic-1234".to_string(),
+            partition_key: None,
             encoding: TextSerializerConfig::new().into(),
+            json_schema: None,
             auth: None,
         })
         .unwrap()
@@ -117,8 +134,10 @@ impl SinkConfig for PulsarSinkConfig {
         &self,
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let schema = self.schema()?;
+        let partition_key = self.partition_key()?;
         let producer = self
-            .create_pulsar_producer()
+            .create_pulsar_producer(schema.clone())
             .await
             .context(CreatePulsarSinkSnafu)?;
 
@@ -126,10 +145,10 @@ impl SinkConfig for PulsarSinkConfig {
         let serializer = self.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
 
-        let sink = PulsarSink::new(producer, transformer, encoder)?;
+        let sink = PulsarSink::new(producer, partition_key, transformer, encoder)?;
 
         let producer = self
-            .create_pulsar_producer()
+            .create_pulsar_producer(schema)
             .await
             .context(CreatePulsarSinkSnafu)?;
         let healthcheck = healthcheck(producer).boxed();
@@ -151,7 +170,42 @@ impl SinkConfig for PulsarSinkConfig {
 }
 
 impl PulsarSinkConfig {
-    async fn create_pulsar_producer(&self) -> Result<PulsarProducer, PulsarError> {
+    fn partition_key(&self) -> crate::Result<Option<Template>> {
+        self.partition_key
+            .as_deref()
+            .map(Template::try_from)
+            .transpose()
+            .context(PartitionKeyTemplateSnafu)
+            .map_err(Into::into)
+    }
+
+    /// The schema attached to the producer, letting the broker check that messages match the
+    /// schema of the topic.
+    fn schema(&self) -> crate::Result<Option<proto::Schema>> {
+        match (self.encoding.config(), &self.json_schema) {
+            (SerializerConfig::Avro { avro }, _) => Ok(Some(proto::Schema {
+                schema_data: avro.schema.as_bytes().into(),
+                r#type: proto::schema::Type::Avro as i32,
+                ..Default::default()
+            })),
+            (SerializerConfig::Json, Some(json_schema)) => {
+                serde_json::from_str::<serde_json::Value>(json_schema)
+                    .context(InvalidJsonSchemaSnafu)?;
+                Ok(Some(proto::Schema {
+                    schema_data: json_schema.as_bytes().into(),
+                    r#type: proto::schema::Type::Json as i32,
+                    ..Default::default()
+                }))
+            }
+            (_, Some(_)) => Err(BuildError::JsonSchemaWithoutJsonCodec.into()),
+            (_, None) => Ok(None),
+        }
+    }
+
+    async fn create_pulsar_producer(
+        &self,
+        schema: Option<proto::Schema>,
+    ) -> Result<PulsarProducer, PulsarError> {
         let mut builder = Pulsar::builder(&self.endpoint, TokioExecutor);
         if let Some(auth) = &self.auth {
             builder = match (
@@ -179,23 +233,15 @@ impl PulsarSinkConfig {
         }
 
         let pulsar = builder.build().await?;
-        if let SerializerConfig::Avro { avro } = self.encoding.config() {
-            pulsar
-                .producer()
-                .with_options(pulsar::producer::ProducerOptions {
-                    schema: Some(proto::Schema {
-                        schema_data: avro.schema.as_bytes().into(),
-                        r#type: proto::schema::Type::Avro as i32,
-                        ..Default::default()
-                    }),
-                    ..Default::default()
-                })
-                .with_topic(&self.topic)
-                .build()
-                .await
-        } else {
-            pulsar.producer().with_topic(&self.topic).build().await
-        }
+        pulsar
+            .producer()
+            .with_options(pulsar::producer::ProducerOptions {
+                schema,
+                ..Default::default()
+            })
+            .with_topic(&self.topic)
+            .build()
+            .await
     }
 }
 
@@ -206,10 +252,12 @@ async fn healthcheck(producer: PulsarProducer) -> crate::Result<()> {
 impl PulsarSink {
     fn new(
         producer: PulsarProducer,
+        partition_key: Option<Template>,
         transformer: Transformer,
         encoder: Encoder<()>,
     ) -> crate::Result<Self> {
         Ok(Self {
+            partition_key,
             transformer,
             encoder,
             state: PulsarSinkState::Ready(Box::new(producer)),
@@ -254,6 +302,19 @@ impl Sink<Event> for PulsarSink {
                 .and_then(|v| v.as_timestamp().map(|dt| dt.timestamp_millis()))
         });
 
+        let partition_key = self.partition_key.as_ref().and_then(|template| {
+            template
+                .render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("partition_key"),
+                        drop_event: false,
+                    });
+                })
+                .ok()
+        });
+
         let metadata_builder = RequestMetadata::builder(&event);
         self.transformer.transform(&mut event);
 
@@ -279,6 +340,9 @@ impl Sink<Event> for PulsarSink {
                 if let Some(et) = event_time {
                     builder = builder.event_time(et as u64);
                 }
+                if let Some(key) = partition_key {
+                    builder = builder.with_partition_key(key);
+                }
                 let result = builder.send().await;
                 (producer, result, metadata, finalizers)
             })),
@@ -338,6 +402,71 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSinkConfig>();
     }
+
+    fn config(toml: &str) -> PulsarSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topic = "topic-1234"
+            {}
+            "#,
+            toml
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn attaches_json_schema() {
+        let schema = config(
+            r#"
+            encoding.codec = "json"
+            json_schema = '{"type": "record", "name": "log", "fields": [{"name": "message", "type": "string"}]}'
+            "#,
+        )
+        .schema()
+        .unwrap()
+        .unwrap();
+        assert_eq!(schema.r#type, proto::schema::Type::Json as i32);
+
+        let schema = config(r#"encoding.codec = "json""#).schema().unwrap();
+        assert!(schema.is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_json_schemas() {
+        assert!(config(
+            r#"
+            encoding.codec = "json"
+            json_schema = "{"
+            "#,
+        )
+        .schema()
+        .is_err());
+        assert!(config(
+            r#"
+            encoding.codec = "text"
+            json_schema = '{"type": "string"}'
+            "#,
+        )
+        .schema()
+        .is_err());
+    }
+
+    #[test]
+    fn parses_partition_key_templates() {
+        let template = config(
+            r#"
+            partition_key = "{{ host }}"
+            encoding.codec = "text"
+            "#,
+        )
+        .partition_key()
+        .unwrap()
+        .unwrap();
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("host", "example.com");
+        assert_eq!(template.render_string(&event).unwrap(), "example.com");
+    }
 }
 
 #[cfg(feature = "pulsar-integration-tests")]
@@ -368,7 +497,9 @@ mod integration_tests {
         let cnf = PulsarSinkConfig {
             endpoint: pulsar_address(),
             topic: topic.clone(),
+            partition_key: None,
             encoding: TextSerializerConfig::new().into(),
+            json_schema: None,
             auth: None,
         };
 
@@ -390,11 +521,11 @@ mod integration_tests {
             .await
             .unwrap();
 
-        let producer = cnf.create_pulsar_producer().await.unwrap();
+        let producer = cnf.create_pulsar_producer(None).await.unwrap();
         let transformer = cnf.encoding.transformer();
         let serializer = cnf.encoding.build().unwrap();
         let encoder = Encoder::<()>::new(serializer);
-        let sink = PulsarSink::new(producer, transformer, encoder).unwrap();
+        let sink = PulsarSink::new(producer, None, transformer, encoder).unwrap();
         let sink = VectorSink::from_event_sink(sink);
         run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
 
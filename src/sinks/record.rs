@@ -0,0 +1,223 @@
+//! The `record` sink writes events to a file, so they can be replayed into a topology later on
+//! with the `replay` source.
+//!
+//! A recording is a sequence of length-delimited frames, each prefixed with its length as a 4-byte
+//! big-endian integer. A frame holds the time its events were recorded, as big-endian Unix
+//! microseconds, followed by the events in the native protobuf encoding.
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use bytes::{BufMut, Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use futures::{
+    future,
+    stream::{BoxStream, StreamExt},
+    FutureExt, SinkExt,
+};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tokio::fs::OpenOptions;
+use tokio_util::codec::{FramedWrite, LengthDelimitedCodec};
+use vector_core::{internal_event::EventsSent, ByteSizeOf};
+
+use crate::{
+    config::{
+        AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext, SinkDescription,
+    },
+    event::{proto, EventArray, EventContainer, EventStatus, Finalizable},
+    internal_events::{FileBytesSent, RecordWriteError},
+    sinks::{util::StreamSink, Healthcheck, VectorSink},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct RecordSinkConfig {
+    pub path: PathBuf,
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SinkDescription::new::<RecordSinkConfig>("record")
+}
+
+impl GenerateConfig for RecordSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: PathBuf::from("/var/lib/vector/recording.bin"),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "record")]
+impl SinkConfig for RecordSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = RecordSink {
+            path: self.path.clone(),
+        };
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "record"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        Some(&self.acknowledgements)
+    }
+}
+
+/// The codec used to delimit the frames of a recording.
+pub(crate) fn recording_codec() -> LengthDelimitedCodec {
+    LengthDelimitedCodec::builder()
+        .max_frame_length(u32::MAX as usize)
+        .new_codec()
+}
+
+/// Encode events recorded at `recorded_at` into a recording frame.
+pub(crate) fn encode_frame(recorded_at: DateTime<Utc>, events: EventArray) -> Bytes {
+    let events = proto::EventArray::from(events);
+    let mut frame = BytesMut::with_capacity(8 + events.encoded_len());
+    frame.put_i64(
+        recorded_at.timestamp() * 1_000_000 + i64::from(recorded_at.timestamp_subsec_micros()),
+    );
+    events
+        .encode(&mut frame)
+        .expect("Encoding into a growable buffer can't fail.");
+    frame.freeze()
+}
+
+struct RecordSink {
+    path: PathBuf,
+}
+
+#[async_trait]
+impl StreamSink<EventArray> for RecordSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, EventArray>) -> Result<(), ()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|error| {
+                emit!(RecordWriteError {
+                    error,
+                    path: &self.path,
+                })
+            })?;
+        let mut writer = FramedWrite::new(file, recording_codec());
+
+        while let Some(mut events) = input.next().await {
+            let count = events.len();
+            let byte_size = events.size_of();
+            let finalizers = events.take_finalizers();
+
+            let frame = encode_frame(Utc::now(), events);
+            let frame_len = frame.len();
+
+            // Sending flushes the frame to the file, so events are only acknowledged once written.
+            match writer.send(frame).await {
+                Ok(()) => {
+                    finalizers.update_status(EventStatus::Delivered);
+                    emit!(EventsSent {
+                        count,
+                        byte_size,
+                        output: None,
+                    });
+                    emit!(FileBytesSent {
+                        byte_size: frame_len,
+                        file: self.path.to_string_lossy(),
+                    });
+                }
+                Err(error) => {
+                    finalizers.update_status(EventStatus::Errored);
+                    emit!(RecordWriteError {
+                        error,
+                        path: &self.path,
+                    });
+                    return Err(());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Buf;
+    use futures::stream;
+    use tokio_util::codec::FramedRead;
+
+    use super::*;
+    use crate::{
+        event::{Event, LogEvent},
+        test_util::components::{run_and_assert_sink_compliance, FILE_SINK_TAGS},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RecordSinkConfig>();
+    }
+
+    #[test]
+    fn encodes_frames() {
+        let recorded_at = Utc::now();
+        let events = EventArray::from(Event::from(LogEvent::from("hello")));
+
+        let mut frame = encode_frame(recorded_at, events);
+        let micros = frame.get_i64();
+        assert_eq!(micros / 1_000_000, recorded_at.timestamp());
+        let decoded = EventArray::try_from(proto::EventArray::decode(frame).unwrap()).unwrap();
+        let event = decoded.into_events().next().unwrap();
+        assert_eq!(event.as_log()["message"], "hello".into());
+    }
+
+    #[tokio::test]
+    async fn records_events() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("recording.bin");
+        let config = RecordSinkConfig {
+            path: path.clone(),
+            acknowledgements: Default::default(),
+        };
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let events = vec![
+            Event::from(LogEvent::from("hello")),
+            Event::from(LogEvent::from("world")),
+        ];
+        run_and_assert_sink_compliance(sink, stream::iter(events), &FILE_SINK_TAGS).await;
+
+        let file = tokio::fs::File::open(&path).await.unwrap();
+        let events: Vec<_> = FramedRead::new(file, recording_codec())
+            .map(|frame| {
+                let mut frame = frame.unwrap();
+                frame.advance(8);
+                EventArray::try_from(proto::EventArray::decode(frame).unwrap()).unwrap()
+            })
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flat_map(EventContainer::into_events)
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].as_log()["message"], "hello".into());
+        assert_eq!(events[1].as_log()["message"], "world".into());
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use futures::ready;
+use serde::{Deserialize, Serialize};
+use tokio_util::time::{delay_queue::Key, DelayQueue};
+use vector_core::{partition::Partitioner, time::KeyedTimer};
+
+use crate::{
+    config::log_schema,
+    event::{Event, Value},
+    internal_events::TemplateRenderingError,
+    template::Template,
+};
+
+/// How finely objects are partitioned by the timestamp of their events.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeGranularity {
+    Year,
+    Month,
+    Day,
+    Hour,
+}
+
+/// Partitions objects by the timestamp of their events, appending the time bucket of the events
+/// to the key prefix.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimePartitioning {
+    pub granularity: TimeGranularity,
+    /// Whether to name the partitions in the Hive style, such as `year=2022/month=08/`, rather
+    /// than `2022/08/`. Athena and Glue crawlers pick up the partition columns from their names.
+    #[serde(default)]
+    pub hive_style: bool,
+}
+
+impl TimePartitioning {
+    /// The template rendering the time bucket of an event.
+    pub fn template(&self) -> String {
+        let parts: &[(&str, &str)] = match self.granularity {
+            TimeGranularity::Year => &[("year", "%Y")],
+            TimeGranularity::Month => &[("year", "%Y"), ("month", "%m")],
+            TimeGranularity::Day => &[("year", "%Y"), ("month", "%m"), ("day", "%d")],
+            TimeGranularity::Hour => &[
+                ("year", "%Y"),
+                ("month", "%m"),
+                ("day", "%d"),
+                ("hour", "%H"),
+            ],
+        };
+        parts
+            .iter()
+            .map(|(name, format)| match self.hive_style {
+                true => format!("{}={}/", name, format),
+                false => format!("{}/", format),
+            })
+            .collect()
+    }
+
+    /// The end of the time bucket containing the timestamp.
+    pub fn bucket_end(&self, timestamp: DateTime<Utc>) -> DateTime<Utc> {
+        let (year, month) = (timestamp.year(), timestamp.month());
+        match self.granularity {
+            TimeGranularity::Year => Utc.ymd(year + 1, 1, 1).and_hms(0, 0, 0),
+            TimeGranularity::Month if month == 12 => Utc.ymd(year + 1, 1, 1).and_hms(0, 0, 0),
+            TimeGranularity::Month => Utc.ymd(year, month + 1, 1).and_hms(0, 0, 0),
+            TimeGranularity::Day => timestamp.date().and_hms(0, 0, 0) + chrono::Duration::days(1),
+            TimeGranularity::Hour => {
+                timestamp.date().and_hms(timestamp.hour(), 0, 0) + chrono::Duration::hours(1)
+            }
+        }
+    }
+}
+
+/// The key prefix of the objects a batch is written to, along with the end of the time bucket
+/// of its events when objects are partitioned by time.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct S3PartitionKey {
+    pub key_prefix: String,
+    pub bucket_end: Option<DateTime<Utc>>,
+}
+
+/// Partitions events based on the key prefix rendered for them.
+pub struct S3KeyPartitioner {
+    key_prefix: Template,
+    time_partitioning: Option<TimePartitioning>,
+}
+
+impl S3KeyPartitioner {
+    pub const fn new(key_prefix: Template, time_partitioning: Option<TimePartitioning>) -> Self {
+        Self {
+            key_prefix,
+            time_partitioning,
+        }
+    }
+}
+
+impl Partitioner for S3KeyPartitioner {
+    type Item = Event;
+    type Key = Option<S3PartitionKey>;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        let key_prefix = self
+            .key_prefix
+            .render_string(item)
+            .map_err(|error| {
+                emit!(TemplateRenderingError {
+                    error,
+                    field: Some("key_prefix"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        // Use the same timestamp as the template, so the bucket matches the rendered key.
+        let bucket_end = self.time_partitioning.map(|time_partitioning| {
+            let timestamp = match item {
+                Event::Log(log) => log
+                    .get(log_schema().timestamp_key())
+                    .and_then(Value::as_timestamp)
+                    .copied(),
+                Event::Metric(metric) => metric.timestamp(),
+                Event::Trace(trace) => trace
+                    .get(log_schema().timestamp_key())
+                    .and_then(Value::as_timestamp)
+                    .copied(),
+            };
+            time_partitioning.bucket_end(timestamp.unwrap_or_else(Utc::now))
+        });
+
+        Some(S3PartitionKey {
+            key_prefix,
+            bucket_end,
+        })
+    }
+}
+
+/// Expires batches after the batch timeout, or once their time bucket ended `max_object_age`
+/// ago, whichever comes first.
+///
+/// This allows a long batch timeout to write fewer, larger objects, while still completing the
+/// objects of a time bucket shortly after it ends.
+pub struct ObjectAgeTimer {
+    batch_timeout: Duration,
+    max_object_age: Option<Duration>,
+    expirations: DelayQueue<Option<S3PartitionKey>>,
+    expiration_map: HashMap<Option<S3PartitionKey>, Key>,
+}
+
+impl ObjectAgeTimer {
+    pub fn new(batch_timeout: Duration, max_object_age: Option<Duration>) -> Self {
+        Self {
+            batch_timeout,
+            max_object_age,
+            expirations: DelayQueue::new(),
+            expiration_map: HashMap::default(),
+        }
+    }
+
+    fn timeout(&self, key: &Option<S3PartitionKey>, now: DateTime<Utc>) -> Duration {
+        key.as_ref()
+            .and_then(|key| key.bucket_end)
+            .zip(self.max_object_age)
+            .and_then(|(bucket_end, max_object_age)| {
+                let finalize_at = bucket_end + chrono::Duration::from_std(max_object_age).ok()?;
+                // Late events of buckets that were already finalized are batched as usual.
+                (finalize_at - now).to_std().ok()
+            })
+            .map_or(self.batch_timeout, |until| until.min(self.batch_timeout))
+    }
+}
+
+impl KeyedTimer<Option<S3PartitionKey>> for ObjectAgeTimer {
+    fn clear(&mut self) {
+        self.expirations.clear();
+        self.expiration_map.clear();
+    }
+
+    fn insert(&mut self, item_key: Option<S3PartitionKey>) {
+        let timeout = self.timeout(&item_key, Utc::now());
+        if let Some(expiration_key) = self.expiration_map.get(&item_key) {
+            self.expirations.reset(expiration_key, timeout);
+        } else {
+            let expiration_key = self.expirations.insert(item_key.clone(), timeout);
+            self.expiration_map.insert(item_key, expiration_key);
+        }
+    }
+
+    fn poll_expired(&mut self, cx: &mut Context) -> Poll<Option<Option<S3PartitionKey>>> {
+        match ready!(self.expirations.poll_expired(cx)) {
+            None => Poll::Ready(None),
+            Some(expiration) => {
+                self.expiration_map.remove(expiration.get_ref());
+                Poll::Ready(Some(expiration.into_inner()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_partitioning(granularity: TimeGranularity, hive_style: bool) -> TimePartitioning {
+        TimePartitioning {
+            granularity,
+            hive_style,
+        }
+    }
+
+    #[test]
+    fn renders_time_partition_templates() {
+        assert_eq!(
+            time_partitioning(TimeGranularity::Hour, false).template(),
+            "%Y/%m/%d/%H/"
+        );
+        assert_eq!(
+            time_partitioning(TimeGranularity::Day, true).template(),
+            "year=%Y/month=%m/day=%d/"
+        );
+        assert_eq!(
+            time_partitioning(TimeGranularity::Year, true).template(),
+            "year=%Y/"
+        );
+    }
+
+    #[test]
+    fn computes_bucket_ends() {
+        let timestamp = Utc.ymd(2022, 12, 31).and_hms(23, 45, 6);
+        for (granularity, end) in [
+            (TimeGranularity::Year, Utc.ymd(2023, 1, 1).and_hms(0, 0, 0)),
+            (TimeGranularity::Month, Utc.ymd(2023, 1, 1).and_hms(0, 0, 0)),
+            (TimeGranularity::Day, Utc.ymd(2023, 1, 1).and_hms(0, 0, 0)),
+            (TimeGranularity::Hour, Utc.ymd(2023, 1, 1).and_hms(0, 0, 0)),
+        ] {
+            assert_eq!(
+                time_partitioning(granularity, false).bucket_end(timestamp),
+                end
+            );
+        }
+
+        let timestamp = Utc.ymd(2022, 8, 1).and_hms(13, 5, 0);
+        assert_eq!(
+            time_partitioning(TimeGranularity::Month, false).bucket_end(timestamp),
+            Utc.ymd(2022, 9, 1).and_hms(0, 0, 0)
+        );
+        assert_eq!(
+            time_partitioning(TimeGranularity::Hour, false).bucket_end(timestamp),
+            Utc.ymd(2022, 8, 1).and_hms(14, 0, 0)
+        );
+    }
+
+    #[test]
+    fn partitions_by_event_time() {
+        let partitioner = S3KeyPartitioner::new(
+            Template::try_from("logs/year=%Y/month=%m/day=%d/hour=%H/").unwrap(),
+            Some(time_partitioning(TimeGranularity::Hour, true)),
+        );
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 8, 1).and_hms(13, 5, 0),
+        );
+
+        assert_eq!(
+            partitioner.partition(&event),
+            Some(S3PartitionKey {
+                key_prefix: "logs/year=2022/month=08/day=01/hour=13/".into(),
+                bucket_end: Some(Utc.ymd(2022, 8, 1).and_hms(14, 0, 0)),
+            })
+        );
+    }
+
+    #[test]
+    fn finalizes_objects_after_their_bucket_ends() {
+        let timer = ObjectAgeTimer::new(Duration::from_secs(3600), Some(Duration::from_secs(300)));
+        let now = Utc.ymd(2022, 8, 1).and_hms(13, 50, 0);
+        let key = |bucket_end| {
+            Some(S3PartitionKey {
+                key_prefix: "logs/".into(),
+                bucket_end,
+            })
+        };
+
+        // The bucket ends in 10 minutes, so the object is finalized 5 minutes after that.
+        assert_eq!(
+            timer.timeout(&key(Some(Utc.ymd(2022, 8, 1).and_hms(14, 0, 0))), now),
+            Duration::from_secs(900)
+        );
+        // Buckets that end after the batch timeout don't shorten it.
+        assert_eq!(
+            timer.timeout(&key(Some(Utc.ymd(2022, 8, 2).and_hms(0, 0, 0))), now),
+            Duration::from_secs(3600)
+        );
+        // Late events of finalized buckets are batched as usual.
+        assert_eq!(
+            timer.timeout(&key(Some(Utc.ymd(2022, 8, 1).and_hms(13, 0, 0))), now),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(timer.timeout(&key(None), now), Duration::from_secs(3600));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, num::NonZeroUsize};
+use std::{fmt, num::NonZeroUsize, time::Duration};
 
 use async_trait::async_trait;
 use futures::stream::BoxStream;
@@ -7,33 +7,39 @@ use tower::Service;
 use vector_core::{
     event::Finalizable,
     sink::StreamSink,
-    stream::{BatcherSettings, DriverResponse},
+    stream::{BatcherSettings, DriverResponse, PartitionedBatcher},
 };
 
 use crate::{
     event::Event,
-    sinks::util::{partitioner::KeyPartitioner, RequestBuilder, SinkBuilderExt},
+    sinks::{
+        s3_common::partitioner::{ObjectAgeTimer, S3KeyPartitioner},
+        util::{RequestBuilder, SinkBuilderExt},
+    },
 };
 
 pub struct S3Sink<Svc, RB> {
     service: Svc,
     request_builder: RB,
-    partitioner: KeyPartitioner,
+    partitioner: S3KeyPartitioner,
     batcher_settings: BatcherSettings,
+    max_object_age: Option<Duration>,
 }
 
 impl<Svc, RB> S3Sink<Svc, RB> {
     pub const fn new(
         service: Svc,
         request_builder: RB,
-        partitioner: KeyPartitioner,
+        partitioner: S3KeyPartitioner,
         batcher_settings: BatcherSettings,
+        max_object_age: Option<Duration>,
     ) -> Self {
         Self {
             partitioner,
             service,
             request_builder,
             batcher_settings,
+            max_object_age,
         }
     }
 }
@@ -51,24 +57,30 @@ where
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let partitioner = self.partitioner;
         let settings = self.batcher_settings;
+        let timer = ObjectAgeTimer::new(settings.timeout, self.max_object_age);
 
         let builder_limit = NonZeroUsize::new(64);
         let request_builder = self.request_builder;
 
-        let sink = input
-            .batched_partitioned(partitioner, settings)
-            .filter_map(|(key, batch)| async move { key.map(move |k| (k, batch)) })
-            .request_builder(builder_limit, request_builder)
-            .filter_map(|request| async move {
-                match request {
-                    Err(e) => {
-                        error!("Failed to build S3 request: {:?}.", e);
-                        None
-                    }
-                    Ok(req) => Some(req),
+        let sink = PartitionedBatcher::with_timer(
+            input,
+            partitioner,
+            timer,
+            NonZeroUsize::new(settings.item_limit).expect("batch item limit is non-zero"),
+            NonZeroUsize::new(settings.size_limit),
+        )
+        .filter_map(|(key, batch)| async move { key.map(move |k| (k.key_prefix, batch)) })
+        .request_builder(builder_limit, request_builder)
+        .filter_map(|request| async move {
+            match request {
+                Err(e) => {
+                    error!("Failed to build S3 request: {:?}.", e);
+                    None
                 }
-            })
-            .into_driver(self.service);
+                Ok(req) => Some(req),
+            }
+        })
+        .into_driver(self.service);
 
         sink.run().await
     }
@@ -1,3 +1,5 @@
+#[cfg(unix)]
+use codecs::BytesEncoder;
 use codecs::{
     encoding::{Framer, FramingConfig},
     TextSerializerConfig,
@@ -5,7 +7,7 @@ use codecs::{
 use serde::{Deserialize, Serialize};
 
 #[cfg(unix)]
-use crate::sinks::util::unix::UnixSinkConfig;
+use crate::sinks::util::unix::{UnixMessageSocketType, UnixSinkConfig};
 use crate::{
     codecs::{Encoder, EncodingConfig, EncodingConfigWithFraming, SinkType},
     config::{
@@ -29,7 +31,12 @@ pub enum Mode {
     Tcp(TcpMode),
     Udp(UdpMode),
     #[cfg(unix)]
+    #[serde(alias = "unix_stream")]
     Unix(UnixMode),
+    #[cfg(unix)]
+    UnixDatagram(UnixMode),
+    #[cfg(unix)]
+    UnixSeqpacket(UnixMode),
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -111,6 +118,14 @@ impl SinkConfig for SocketSinkConfig {
                 let encoder = Encoder::<Framer>::new(framer, serializer);
                 config.build(transformer, encoder)
             }
+            #[cfg(unix)]
+            Mode::UnixDatagram(UnixMode { config, encoding }) => {
+                build_unix_message_based(config, encoding, UnixMessageSocketType::Datagram)
+            }
+            #[cfg(unix)]
+            Mode::UnixSeqpacket(UnixMode { config, encoding }) => {
+                build_unix_message_based(config, encoding, UnixMessageSocketType::Seqpacket)
+            }
         }
     }
 
@@ -119,7 +134,9 @@ impl SinkConfig for SocketSinkConfig {
             Mode::Tcp(TcpMode { encoding, .. }) => encoding.config().1.input_type(),
             Mode::Udp(UdpMode { encoding, .. }) => encoding.config().input_type(),
             #[cfg(unix)]
-            Mode::Unix(UnixMode { encoding, .. }) => encoding.config().1.input_type(),
+            Mode::Unix(UnixMode { encoding, .. })
+            | Mode::UnixDatagram(UnixMode { encoding, .. })
+            | Mode::UnixSeqpacket(UnixMode { encoding, .. }) => encoding.config().1.input_type(),
         };
         Input::new(encoder_input_type & DataType::Log)
     }
@@ -133,6 +150,24 @@ impl SinkConfig for SocketSinkConfig {
     }
 }
 
+/// Datagram and seqpacket sockets preserve message boundaries, so events aren't framed unless
+/// framing is explicitly configured.
+#[cfg(unix)]
+fn build_unix_message_based(
+    config: &UnixSinkConfig,
+    encoding: &EncodingConfigWithFraming,
+    socket_type: UnixMessageSocketType,
+) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+    let transformer = encoding.transformer();
+    let (framing, serializer) = encoding.config();
+    let framer = match framing {
+        Some(framing) => framing.build(),
+        None => BytesEncoder::new().into(),
+    };
+    let encoder = Encoder::<Framer>::new(framer, serializer.build()?);
+    config.build_message_based(socket_type, transformer, encoder)
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -208,6 +243,38 @@ mod test {
         test_udp(next_addr_v6()).await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix_datagram_with_framing() {
+        trace_init();
+
+        let path = tempfile::tempdir()
+            .unwrap()
+            .into_path()
+            .join("unix_datagram");
+        let receiver = tokio::net::UnixDatagram::bind(&path).unwrap();
+
+        let config: SocketSinkConfig = toml::from_str(&format!(
+            r#"
+            mode = "unix_datagram"
+            path = "{}"
+            encoding.codec = "text"
+            framing.method = "newline_delimited"
+            "#,
+            path.display()
+        ))
+        .unwrap();
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let event = Event::Log(LogEvent::from("raw log line"));
+        run_and_assert_sink_compliance(sink, stream::once(ready(event)), &SINK_TAGS).await;
+
+        let mut buf = [0; 256];
+        let size = receiver.recv(&mut buf).await.unwrap();
+        assert_eq!(&buf[..size], b"raw log line\n");
+    }
+
     #[tokio::test]
     async fn tcp_stream() {
         trace_init();
@@ -13,7 +13,7 @@ use super::{
     EndpointTarget,
 };
 use crate::{
-    http::HttpClient,
+    http::{ConnectionPoolConfig, HttpClient},
     internal_events::TemplateRenderingError,
     sinks::{
         self,
@@ -44,9 +44,14 @@ pub enum HealthcheckError {
 pub fn create_client(
     tls: &Option<TlsConfig>,
     proxy_config: &ProxyConfig,
+    connection_pool: &ConnectionPoolConfig,
 ) -> crate::Result<HttpClient> {
     let tls_settings = TlsSettings::from_options(tls)?;
-    Ok(HttpClient::new(tls_settings, proxy_config)?)
+    Ok(HttpClient::new_with_connection_pool(
+        tls_settings,
+        proxy_config,
+        connection_pool,
+    )?)
 }
 
 pub fn build_http_batch_service(
@@ -164,13 +169,16 @@ mod tests {
         Mock, MockServer, ResponseTemplate,
     };
 
-    use crate::sinks::{
-        splunk_hec::common::{
-            build_healthcheck, build_uri, create_client,
-            service::{HttpRequestBuilder, MetadataFields},
-            EndpointTarget, HOST_FIELD, SOURCE_FIELD,
+    use crate::{
+        http::ConnectionPoolConfig,
+        sinks::{
+            splunk_hec::common::{
+                build_healthcheck, build_uri, create_client,
+                service::{HttpRequestBuilder, MetadataFields},
+                EndpointTarget, HOST_FIELD, SOURCE_FIELD,
+            },
+            util::Compression,
         },
-        util::Compression,
     };
 
     #[tokio::test]
@@ -184,7 +192,12 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert!(healthcheck.await.is_ok())
@@ -201,7 +214,12 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert_eq!(
@@ -221,7 +239,12 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert_eq!(
@@ -241,7 +264,12 @@ mod tests {
             .mount(&mock_server)
             .await;
 
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let healthcheck = build_healthcheck(mock_server.uri(), "token".to_string(), client);
 
         assert_eq!(
@@ -392,13 +420,18 @@ mod integration_tests {
         integration_test_helpers::{get_token, splunk_hec_address},
     };
     use crate::{
-        assert_downcast_matches, sinks::splunk_hec::common::HealthcheckError,
-        test_util::retry_until,
+        assert_downcast_matches, http::ConnectionPoolConfig,
+        sinks::splunk_hec::common::HealthcheckError, test_util::retry_until,
     };
 
     #[tokio::test]
     async fn splunk_healthcheck_ok() {
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let address = splunk_hec_address();
         let token = get_token().await;
 
@@ -412,7 +445,12 @@ mod integration_tests {
 
     #[tokio::test]
     async fn splunk_healthcheck_server_not_listening() {
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let healthcheck = build_healthcheck(
             "http://localhost:1111/".to_string(),
             get_token().await,
@@ -424,7 +462,12 @@ mod integration_tests {
 
     #[tokio::test]
     async fn splunk_healthcheck_server_unavailable() {
-        let client = create_client(&None, &ProxyConfig::default()).unwrap();
+        let client = create_client(
+            &None,
+            &ProxyConfig::default(),
+            &ConnectionPoolConfig::default(),
+        )
+        .unwrap();
         let healthcheck = build_healthcheck(
             "http://localhost:5503/".to_string(),
             get_token().await,
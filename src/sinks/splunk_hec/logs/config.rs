@@ -91,7 +91,7 @@ impl GenerateConfig for HecLogsSinkConfig {
 #[typetag::serde(name = "splunk_hec_logs")]
 impl SinkConfig for HecLogsSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let client = create_client(&self.tls, cx.proxy())?;
+        let client = create_client(&self.tls, cx.proxy(), cx.connection_pool())?;
         let healthcheck = build_healthcheck(
             self.endpoint.clone(),
             self.default_token.clone(),
@@ -29,7 +29,7 @@ use crate::{
         encode_namespace,
         tcp::TcpSinkConfig,
         udp::{UdpService, UdpSinkConfig},
-        BatchConfig, BatchSink, Buffer, Compression, EncodedEvent,
+        BatchConfig, BatchSink, Buffer, Compression, EncodedEvent, TagRule, TagRules,
     },
 };
 
@@ -43,6 +43,8 @@ pub struct StatsdSvc {
 pub struct StatsdSinkConfig {
     #[serde(alias = "namespace")]
     pub default_namespace: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tag_rules: Vec<TagRule>,
     #[serde(flatten)]
     pub mode: Mode,
 }
@@ -86,6 +88,7 @@ impl GenerateConfig for StatsdSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(&Self {
             default_namespace: None,
+            tag_rules: Vec::new(),
             mode: Mode::Udp(StatsdUdpConfig {
                 batch: Default::default(),
                 udp: UdpSinkConfig::from_address(default_address().to_string()),
@@ -103,7 +106,11 @@ impl SinkConfig for StatsdSinkConfig {
         _cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let default_namespace = self.default_namespace.clone();
-        let mut encoder = StatsdEncoder { default_namespace };
+        let tag_rules = TagRules::new(&self.tag_rules)?;
+        let mut encoder = StatsdEncoder {
+            default_namespace,
+            tag_rules,
+        };
         match &self.mode {
             Mode::Tcp(config) => config.build(Default::default(), encoder),
             Mode::Udp(config) => {
@@ -189,6 +196,7 @@ fn push_event<V: Display>(
 #[derive(Debug, Clone)]
 struct StatsdEncoder {
     default_namespace: Option<String>,
+    tag_rules: TagRules,
 }
 
 impl Encoder<Event> for StatsdEncoder {
@@ -197,7 +205,9 @@ impl Encoder<Event> for StatsdEncoder {
     fn encode(&mut self, event: Event, bytes: &mut BytesMut) -> Result<(), Self::Error> {
         let mut buf = Vec::new();
 
-        let metric = event.as_metric();
+        let mut metric = event.into_metric();
+        self.tag_rules.apply(&mut metric);
+        let metric = &metric;
         match metric.value() {
             MetricValue::Counter { value } => {
                 push_event(&mut buf, metric, value, "c", None);
@@ -350,6 +360,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            tag_rules: TagRules::default(),
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -368,6 +379,7 @@ mod test {
         let event = Event::Metric(metric1);
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            tag_rules: TagRules::default(),
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -388,6 +400,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            tag_rules: TagRules::default(),
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -407,6 +420,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            tag_rules: TagRules::default(),
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -440,6 +454,7 @@ mod test {
         let event = Event::Metric(metric1);
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            tag_rules: TagRules::default(),
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -461,6 +476,7 @@ mod test {
         let event = Event::Metric(metric1.clone());
         let mut encoder = StatsdEncoder {
             default_namespace: None,
+            tag_rules: TagRules::default(),
         };
         let mut frame = BytesMut::new();
         encoder.encode(event, &mut frame).unwrap();
@@ -478,6 +494,7 @@ mod test {
 
         let config = StatsdSinkConfig {
             default_namespace: Some("ns".into()),
+            tag_rules: Vec::new(),
             mode: Mode::Udp(StatsdUdpConfig {
                 batch,
                 udp: UdpSinkConfig::from_address(addr.to_string()),
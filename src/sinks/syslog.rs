@@ -0,0 +1,586 @@
+use std::fmt::Write as _;
+
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    codecs::{Encoder, EncodingConfig, Transformer},
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig,
+        SinkContext, SinkDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::TemplateRenderingError,
+    sinks::util::{tcp::TcpSinkConfig, udp::UdpSinkConfig},
+    template::Template,
+};
+
+/// The maximum lengths of the RFC 5424 header fields.
+const MAX_HOSTNAME_LEN: usize = 255;
+const MAX_APP_NAME_LEN: usize = 48;
+const MAX_PROC_ID_LEN: usize = 128;
+const MAX_MSG_ID_LEN: usize = 32;
+const MAX_SD_NAME_LEN: usize = 32;
+
+/// The RFC 5424 "nil" value, used for header fields with no value.
+const NILVALUE: &str = "-";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid structured data ID {:?}", id))]
+    InvalidStructuredDataId { id: String },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+// `#[serde(deny_unknown_fields)]` doesn't work when flattening internally tagged enums, see
+// https://github.com/serde-rs/serde/issues/1358.
+pub struct SyslogSinkConfig {
+    #[serde(flatten)]
+    pub mode: Mode,
+    #[serde(default)]
+    pub format: SyslogFormat,
+    #[serde(default = "default_facility")]
+    pub facility: Template,
+    #[serde(default = "default_severity")]
+    pub severity: Template,
+    pub app_name: Option<Template>,
+    pub proc_id: Option<Template>,
+    pub msg_id: Option<Template>,
+    pub structured_data: Option<StructuredDataConfig>,
+    pub encoding: EncodingConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Mode {
+    Tcp(TcpMode),
+    Udp(UdpMode),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TcpMode {
+    #[serde(flatten)]
+    config: TcpSinkConfig,
+    #[serde(default)]
+    framing: SyslogFraming,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UdpMode {
+    #[serde(flatten)]
+    config: UdpSinkConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFormat {
+    /// The legacy BSD syslog format.
+    Rfc3164,
+    /// The IETF syslog format, which supports structured data.
+    #[derivative(Default)]
+    Rfc5424,
+}
+
+/// How messages are framed on stream transports, as described in RFC 6587.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    /// Each message is prefixed with its length in bytes and a space.
+    #[derivative(Default)]
+    OctetCounting,
+    /// Each message is terminated by a newline. Newlines within messages are replaced by spaces.
+    NonTransparent,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct StructuredDataConfig {
+    #[serde(default = "default_structured_data_id")]
+    pub id: String,
+    pub fields: Vec<String>,
+}
+
+fn default_facility() -> Template {
+    Template::try_from("user").expect("valid template")
+}
+
+fn default_severity() -> Template {
+    Template::try_from("info").expect("valid template")
+}
+
+fn default_structured_data_id() -> String {
+    // 32473 is the private enterprise number reserved for documentation.
+    "vector@32473".to_string()
+}
+
+inventory::submit! {
+    SinkDescription::new::<SyslogSinkConfig>("syslog")
+}
+
+impl GenerateConfig for SyslogSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:6514"
+            mode = "tcp"
+            encoding.codec = "text""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "syslog")]
+impl SinkConfig for SyslogSinkConfig {
+    async fn build(
+        &self,
+        _cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        if let Some(structured_data) = &self.structured_data {
+            if !is_valid_sd_name(&structured_data.id) {
+                return Err(Box::new(BuildError::InvalidStructuredDataId {
+                    id: structured_data.id.clone(),
+                }));
+            }
+        }
+
+        let transformer = self.encoding.transformer();
+        let serializer = self.encoding.build()?;
+        let framing = match &self.mode {
+            Mode::Tcp(TcpMode { framing, .. }) => Some(*framing),
+            Mode::Udp(_) => None,
+        };
+
+        let encoder = SyslogEncoder {
+            format: self.format,
+            framing,
+            facility: self.facility.clone(),
+            severity: self.severity.clone(),
+            app_name: self.app_name.clone(),
+            proc_id: self.proc_id.clone(),
+            msg_id: self.msg_id.clone(),
+            structured_data: self.structured_data.clone(),
+            default_hostname: crate::get_hostname().ok(),
+            transformer,
+            encoder: Encoder::<()>::new(serializer),
+        };
+
+        // The transformer is applied by the encoder, once the header fields have been rendered.
+        match &self.mode {
+            Mode::Tcp(TcpMode { config, .. }) => config.build(Transformer::default(), encoder),
+            Mode::Udp(UdpMode { config }) => config.build(Transformer::default(), encoder),
+        }
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "syslog"
+    }
+
+    fn acknowledgements(&self) -> Option<&AcknowledgementsConfig> {
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SyslogEncoder {
+    format: SyslogFormat,
+    framing: Option<SyslogFraming>,
+    facility: Template,
+    severity: Template,
+    app_name: Option<Template>,
+    proc_id: Option<Template>,
+    msg_id: Option<Template>,
+    structured_data: Option<StructuredDataConfig>,
+    default_hostname: Option<String>,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+}
+
+impl SyslogEncoder {
+    fn priority(&self, event: &Event) -> u8 {
+        let facility = render(&self.facility, event, "facility")
+            .and_then(|facility| parse_code(&facility, "facility", FACILITIES))
+            .unwrap_or(1);
+        let severity = render(&self.severity, event, "severity")
+            .and_then(|severity| parse_code(&severity, "severity", SEVERITIES))
+            .unwrap_or(6);
+        facility * 8 + severity
+    }
+
+    fn structured_data(&self, log: &LogEvent) -> String {
+        let config = match &self.structured_data {
+            Some(config) => config,
+            None => return NILVALUE.to_string(),
+        };
+
+        let mut params = String::new();
+        for field in &config.fields {
+            if let Some(value) = log.get(field.as_str()) {
+                let name = sanitize(field, MAX_SD_NAME_LEN, |c| !matches!(c, '=' | ']' | '"'));
+                let _ = write!(params, " {}=\"", name);
+                escape_param_value(&mut params, &value.to_string_lossy());
+                params.push('"');
+            }
+        }
+
+        if params.is_empty() {
+            NILVALUE.to_string()
+        } else {
+            format!("[{}{}]", config.id, params)
+        }
+    }
+}
+
+impl tokio_util::codec::Encoder<Event> for SyslogEncoder {
+    type Error = codecs::encoding::Error;
+
+    fn encode(&mut self, mut event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let priority = self.priority(&event);
+        let app_name = self
+            .app_name
+            .as_ref()
+            .and_then(|template| render(template, &event, "app_name"))
+            .unwrap_or_else(|| "vector".to_string());
+        let proc_id = self
+            .proc_id
+            .as_ref()
+            .and_then(|template| render(template, &event, "proc_id"));
+        let msg_id = self
+            .msg_id
+            .as_ref()
+            .and_then(|template| render(template, &event, "msg_id"));
+
+        let log = event.as_log();
+        let hostname = log
+            .get(log_schema().host_key())
+            .map(Value::to_string_lossy)
+            .or_else(|| self.default_hostname.clone());
+        let timestamp = match log.get(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+        let structured_data = self.structured_data(log);
+
+        self.transformer.transform(&mut event);
+        let mut message = BytesMut::new();
+        self.encoder.encode(event, &mut message)?;
+        let mut message = String::from_utf8_lossy(&message).into_owned();
+        if self.framing == Some(SyslogFraming::NonTransparent) {
+            message = message.replace('\n', " ");
+        }
+
+        let header = Header {
+            priority,
+            timestamp,
+            hostname: hostname.as_deref(),
+            app_name: &app_name,
+            proc_id: proc_id.as_deref(),
+            msg_id: msg_id.as_deref(),
+        };
+        let line = match self.format {
+            SyslogFormat::Rfc3164 => header.rfc3164(&message),
+            SyslogFormat::Rfc5424 => header.rfc5424(&structured_data, &message),
+        };
+
+        match self.framing {
+            Some(SyslogFraming::OctetCounting) => {
+                buffer.put_slice(format!("{} ", line.len()).as_bytes());
+                buffer.put_slice(line.as_bytes());
+            }
+            Some(SyslogFraming::NonTransparent) => {
+                buffer.put_slice(line.as_bytes());
+                buffer.put_u8(b'\n');
+            }
+            None => buffer.put_slice(line.as_bytes()),
+        }
+
+        Ok(())
+    }
+}
+
+struct Header<'a> {
+    priority: u8,
+    timestamp: DateTime<Utc>,
+    hostname: Option<&'a str>,
+    app_name: &'a str,
+    proc_id: Option<&'a str>,
+    msg_id: Option<&'a str>,
+}
+
+impl<'a> Header<'a> {
+    fn rfc5424(&self, structured_data: &str, message: &str) -> String {
+        let field = |value: Option<&str>, max_len| {
+            value
+                .map(|value| sanitize(value, max_len, |_| true))
+                .filter(|value| !value.is_empty())
+                .unwrap_or_else(|| NILVALUE.to_string())
+        };
+
+        let mut line = format!(
+            "<{}>1 {} {} {} {} {} {}",
+            self.priority,
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+            field(self.hostname, MAX_HOSTNAME_LEN),
+            field(Some(self.app_name), MAX_APP_NAME_LEN),
+            field(self.proc_id, MAX_PROC_ID_LEN),
+            field(self.msg_id, MAX_MSG_ID_LEN),
+            structured_data,
+        );
+        if !message.is_empty() {
+            line.push(' ');
+            line.push_str(message);
+        }
+        line
+    }
+
+    fn rfc3164(&self, message: &str) -> String {
+        let hostname = self
+            .hostname
+            .map(|hostname| sanitize(hostname, MAX_HOSTNAME_LEN, |_| true))
+            .filter(|hostname| !hostname.is_empty())
+            .unwrap_or_else(|| "localhost".to_string());
+        // The tag is limited to 32 alphanumeric characters.
+        let tag = sanitize(self.app_name, 32, |c| c.is_ascii_alphanumeric() || c == '-');
+
+        let mut line = format!(
+            "<{}>{} {} {}",
+            self.priority,
+            self.timestamp.format("%b %e %H:%M:%S"),
+            hostname,
+            tag,
+        );
+        if let Some(proc_id) = self.proc_id {
+            let _ = write!(line, "[{}]", sanitize(proc_id, MAX_PROC_ID_LEN, |_| true));
+        }
+        line.push_str(": ");
+        line.push_str(message);
+        line
+    }
+}
+
+fn render(template: &Template, event: &Event, field: &str) -> Option<String> {
+    template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: false,
+            })
+        })
+        .ok()
+}
+
+const FACILITIES: &[&str] = &[
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITIES: &[&str] = &[
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// Parses a facility or severity from either its keyword or its numeric code.
+fn parse_code(value: &str, field: &str, keywords: &[&str]) -> Option<u8> {
+    let value = value.trim().to_ascii_lowercase();
+    let value = match value.as_str() {
+        "emergency" | "panic" => "emerg",
+        "critical" => "crit",
+        "error" => "err",
+        "warn" => "warning",
+        "informational" => "info",
+        value => value,
+    };
+
+    let code = match value.parse::<usize>() {
+        Ok(code) if code < keywords.len() => Some(code),
+        Ok(_) => None,
+        Err(_) => keywords.iter().position(|keyword| *keyword == value),
+    };
+    if code.is_none() {
+        warn!(
+            message = "Invalid syslog value, using the default.",
+            field,
+            value,
+            internal_log_rate_secs = 30,
+        );
+    }
+    code.map(|code| code as u8)
+}
+
+/// Restricts a header field to printable ASCII without spaces, as required by RFC 5424.
+fn sanitize(value: &str, max_len: usize, allowed: impl Fn(char) -> bool) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_graphic() && allowed(*c))
+        .take(max_len)
+        .collect()
+}
+
+fn is_valid_sd_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_SD_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+}
+
+fn escape_param_value(out: &mut String, value: &str) {
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use codecs::TextSerializer;
+    use tokio_util::codec::Encoder as _;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SyslogSinkConfig>();
+    }
+
+    fn encoder(format: SyslogFormat, framing: Option<SyslogFraming>) -> SyslogEncoder {
+        SyslogEncoder {
+            format,
+            framing,
+            facility: Template::try_from("{{ facility }}").unwrap(),
+            severity: Template::try_from("{{ level }}").unwrap(),
+            app_name: Some(Template::try_from("{{ app }}").unwrap()),
+            proc_id: Some(Template::try_from("1234").unwrap()),
+            msg_id: Some(Template::try_from("ID47").unwrap()),
+            structured_data: Some(StructuredDataConfig {
+                id: default_structured_data_id(),
+                fields: vec!["user".into(), "path".into(), "missing".into()],
+            }),
+            default_hostname: Some("fallback".into()),
+            transformer: Transformer::default(),
+            encoder: Encoder::<()>::new(TextSerializer::new().into()),
+        }
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("something happened");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 8, 3).and_hms_micro(14, 5, 6, 123456),
+        );
+        log.insert(log_schema().host_key(), "web 01");
+        log.insert("facility", "local4");
+        log.insert("level", "warn");
+        log.insert("app", "nginx");
+        log.insert("user", "alice");
+        log.insert("path", r#"/a"b]"#);
+        log.into()
+    }
+
+    fn encode(mut encoder: SyslogEncoder, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        encoder.encode(event, &mut buffer).unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn encodes_rfc5424() {
+        assert_eq!(
+            encode(encoder(SyslogFormat::Rfc5424, None), event()),
+            r#"<164>1 2022-08-03T14:05:06.123456Z web01 nginx 1234 ID47 [vector@32473 user="alice" path="/a\"b\]"] something happened"#
+        );
+    }
+
+    #[test]
+    fn encodes_rfc3164() {
+        assert_eq!(
+            encode(encoder(SyslogFormat::Rfc3164, None), event()),
+            "<164>Aug  3 14:05:06 web01 nginx[1234]: something happened"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_defaults() {
+        let mut log = LogEvent::from("hello");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 8, 3).and_hms(14, 5, 6),
+        );
+        log.insert("level", "bogus");
+
+        assert_eq!(
+            encode(encoder(SyslogFormat::Rfc5424, None), log.into()),
+            "<14>1 2022-08-03T14:05:06.000000Z fallback vector 1234 ID47 - hello"
+        );
+    }
+
+    #[test]
+    fn frames_messages() {
+        let line = encode(encoder(SyslogFormat::Rfc3164, None), event());
+
+        assert_eq!(
+            encode(
+                encoder(SyslogFormat::Rfc3164, Some(SyslogFraming::OctetCounting)),
+                event()
+            ),
+            format!("{} {}", line.len(), line)
+        );
+        assert_eq!(
+            encode(
+                encoder(SyslogFormat::Rfc3164, Some(SyslogFraming::NonTransparent)),
+                event()
+            ),
+            format!("{}\n", line)
+        );
+    }
+
+    #[test]
+    fn parses_codes() {
+        assert_eq!(parse_code("LOCAL7", "facility", FACILITIES), Some(23));
+        assert_eq!(parse_code("3", "facility", FACILITIES), Some(3));
+        assert_eq!(parse_code("24", "facility", FACILITIES), None);
+        assert_eq!(parse_code("error", "severity", SEVERITIES), Some(3));
+        assert_eq!(parse_code("debug", "severity", SEVERITIES), Some(7));
+    }
+
+    #[test]
+    fn validates_structured_data_id() {
+        assert!(is_valid_sd_name("vector@32473"));
+        assert!(!is_valid_sd_name("with space"));
+        assert!(!is_valid_sd_name("quote\""));
+        assert!(!is_valid_sd_name(""));
+    }
+}
@@ -0,0 +1,356 @@
+//! Circuit breaker for sink requests.
+//!
+//! After `failure_threshold` consecutive failed requests, the circuit opens and requests are
+//! rejected without reaching the downstream for `cooldown_secs`. A single request is then let
+//! through: the circuit closes again if it succeeds, or reopens for another cooldown otherwise.
+//!
+//! A request fails when it errors or times out, or when the retry logic of the sink would retry
+//! its response. Rejected requests fail with `CircuitOpenError`, which the retry policy retries
+//! with backoff, so events are held back rather than dropped while the circuit is open.
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::ready;
+use pin_project::pin_project;
+use serde::{Deserialize, Serialize};
+use tower::{timeout::error::Elapsed, Layer, Service};
+
+use crate::{
+    internal_events::{CircuitBreakerClosed, CircuitBreakerOpened, CircuitBreakerRejected},
+    sinks::util::retries::RetryLogic,
+};
+
+/// Circuit breaker configuration.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// The number of consecutive failed requests opening the circuit.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a request is let through.
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+const fn default_failure_threshold() -> u32 {
+    5
+}
+
+const fn default_cooldown_secs() -> u64 {
+    30
+}
+
+/// The error of requests rejected while the circuit is open.
+#[derive(Debug)]
+pub struct CircuitOpenError;
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "circuit breaker open")
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Closed {
+        failures: u32,
+    },
+    Open {
+        until: Instant,
+    },
+    /// A single request is let through to probe the downstream.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+pub struct Breaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+impl Breaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Whether a request may be sent.
+    fn acquire(&self, now: Instant) -> bool {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        match *state {
+            State::Closed { .. } => true,
+            State::Open { until } if now >= until => {
+                *state = State::HalfOpen;
+                true
+            }
+            State::Open { .. } | State::HalfOpen => false,
+        }
+    }
+
+    fn record(&self, success: bool, now: Instant) {
+        let mut state = self.state.lock().expect("circuit breaker lock poisoned");
+        let open = State::Open {
+            until: now + Duration::from_secs(self.config.cooldown_secs),
+        };
+        match (*state, success) {
+            (State::Closed { .. }, true) => *state = State::Closed { failures: 0 },
+            (State::Closed { failures }, false) => {
+                let failures = failures + 1;
+                if failures >= self.config.failure_threshold {
+                    *state = open;
+                    emit!(CircuitBreakerOpened {
+                        failures,
+                        cooldown_secs: self.config.cooldown_secs,
+                    });
+                } else {
+                    *state = State::Closed { failures };
+                }
+            }
+            (State::HalfOpen, true) => {
+                *state = State::Closed { failures: 0 };
+                emit!(CircuitBreakerClosed);
+            }
+            (State::HalfOpen, false) => {
+                *state = open;
+                emit!(CircuitBreakerOpened {
+                    failures: 1,
+                    cooldown_secs: self.config.cooldown_secs,
+                });
+            }
+            // Responses to requests sent before the circuit opened.
+            (State::Open { .. }, _) => {}
+        }
+    }
+}
+
+/// Opens the circuit to the downstream after consecutive failed requests.
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerLayer<L> {
+    config: Option<CircuitBreakerConfig>,
+    logic: L,
+}
+
+impl<L> CircuitBreakerLayer<L> {
+    /// Creates the layer, which passes requests through when `config` is `None`.
+    pub const fn new(config: Option<CircuitBreakerConfig>, logic: L) -> Self {
+        Self { config, logic }
+    }
+}
+
+impl<S, L: RetryLogic> Layer<S> for CircuitBreakerLayer<L> {
+    type Service = CircuitBreaker<S, L>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreaker {
+            inner,
+            logic: self.logic.clone(),
+            breaker: self.config.map(|config| Arc::new(Breaker::new(config))),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker<S, L> {
+    inner: S,
+    logic: L,
+    breaker: Option<Arc<Breaker>>,
+}
+
+impl<S, L, Request> Service<Request> for CircuitBreaker<S, L>
+where
+    S: Service<Request>,
+    S::Error: Into<crate::Error>,
+    L: RetryLogic<Response = S::Response>,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = ResponseFuture<S::Future, L>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        match &self.breaker {
+            Some(breaker) if !breaker.acquire(Instant::now()) => {
+                emit!(CircuitBreakerRejected);
+                ResponseFuture::Rejected
+            }
+            breaker => ResponseFuture::Inner {
+                inner: self.inner.call(request),
+                breaker: breaker.clone().map(|breaker| (breaker, self.logic.clone())),
+            },
+        }
+    }
+}
+
+#[pin_project(project = ResponseFutureProj)]
+pub enum ResponseFuture<F, L> {
+    Rejected,
+    Inner {
+        #[pin]
+        inner: F,
+        breaker: Option<(Arc<Breaker>, L)>,
+    },
+}
+
+impl<F, L, E> Future for ResponseFuture<F, L>
+where
+    F: Future<Output = Result<L::Response, E>>,
+    L: RetryLogic,
+    E: Into<crate::Error>,
+{
+    type Output = Result<L::Response, crate::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            ResponseFutureProj::Rejected => Poll::Ready(Err(Box::new(CircuitOpenError))),
+            ResponseFutureProj::Inner { inner, breaker } => {
+                let output = ready!(inner.poll(cx)).map_err(Into::into);
+                if let Some((breaker, logic)) = breaker {
+                    breaker.record(is_success(logic, &output), Instant::now());
+                }
+                Poll::Ready(output)
+            }
+        }
+    }
+}
+
+fn is_success<L: RetryLogic>(logic: &L, output: &Result<L::Response, crate::Error>) -> bool {
+    match output {
+        Ok(response) => !logic.should_retry_response(response).is_retryable(),
+        Err(error) => {
+            if let Some(error) = error.downcast_ref::<L::Error>() {
+                !logic.is_retriable_error(error)
+            } else {
+                error.downcast_ref::<Elapsed>().is_none()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio_test::{assert_ready_err, assert_ready_ok, task};
+    use tower_test::{assert_request_eq, mock};
+
+    use super::*;
+    use crate::sinks::util::retries::RetryAction;
+
+    #[derive(Clone, Debug)]
+    struct StatusRetryLogic;
+
+    #[derive(Debug)]
+    struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "error")
+        }
+    }
+
+    impl std::error::Error for Error {}
+
+    impl RetryLogic for StatusRetryLogic {
+        type Error = Error;
+        type Response = u16;
+
+        fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+            true
+        }
+
+        fn should_retry_response(&self, response: &u16) -> RetryAction {
+            if *response >= 500 {
+                RetryAction::Retry("server error".into())
+            } else {
+                RetryAction::Successful
+            }
+        }
+    }
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 2,
+            cooldown_secs: 10,
+        }
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let breaker = Breaker::new(config());
+        let now = Instant::now();
+
+        breaker.record(false, now);
+        breaker.record(true, now);
+        breaker.record(false, now);
+        assert!(breaker.acquire(now));
+
+        breaker.record(false, now);
+        assert!(!breaker.acquire(now));
+        assert!(!breaker.acquire(now + Duration::from_secs(9)));
+    }
+
+    #[test]
+    fn half_open_probe() {
+        let breaker = Breaker::new(config());
+        let now = Instant::now();
+        breaker.record(false, now);
+        breaker.record(false, now);
+
+        let later = now + Duration::from_secs(10);
+        assert!(breaker.acquire(later));
+        // Only one request probes the downstream.
+        assert!(!breaker.acquire(later));
+
+        breaker.record(false, later);
+        assert!(!breaker.acquire(later + Duration::from_secs(5)));
+
+        let later = later + Duration::from_secs(10);
+        assert!(breaker.acquire(later));
+        breaker.record(true, later);
+        assert!(breaker.acquire(later));
+        assert!(breaker.acquire(later));
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_while_open() {
+        let layer = CircuitBreakerLayer::new(Some(config()), StatusRetryLogic);
+        let (mut svc, mut handle) = mock::spawn_layer::<_, u16, _>(layer);
+
+        for _ in 0..2 {
+            assert_ready_ok!(svc.poll_ready());
+            let mut fut = task::spawn(svc.call("hello"));
+            assert_request_eq!(handle, "hello").send_response(503);
+            assert_ready_ok!(fut.poll());
+        }
+
+        assert_ready_ok!(svc.poll_ready());
+        let mut fut = task::spawn(svc.call("hello"));
+        let error = assert_ready_err!(fut.poll());
+        assert!(error.downcast_ref::<CircuitOpenError>().is_some());
+    }
+
+    #[tokio::test]
+    async fn passes_through_without_config() {
+        let layer = CircuitBreakerLayer::new(None, StatusRetryLogic);
+        let (mut svc, mut handle) = mock::spawn_layer::<_, u16, _>(layer);
+
+        for _ in 0..10 {
+            assert_ready_ok!(svc.poll_ready());
+            let mut fut = task::spawn(svc.call("hello"));
+            assert_request_eq!(handle, "hello").send_response(503);
+            assert_ready_ok!(fut.poll());
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     fmt,
     future::Future,
     hash::Hash,
+    io::Write,
     marker::PhantomData,
     pin::Pin,
     sync::Arc,
@@ -11,23 +12,23 @@ use std::{
 
 use bytes::{Buf, Bytes};
 use futures::{future::BoxFuture, ready, Sink};
-use http::StatusCode;
+use http::{header, HeaderValue, StatusCode};
 use hyper::{body, Body};
 use indexmap::IndexMap;
 use pin_project::pin_project;
-use serde::{Deserialize, Serialize};
-use tower::Service;
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+use tower::{Service, ServiceExt};
 use vector_core::ByteSizeOf;
 
 use super::{
     retries::{RetryAction, RetryLogic},
-    sink, uri, Batch, EncodedEvent, Partition, TowerBatchedSink, TowerPartitionSink,
-    TowerRequestConfig, TowerRequestSettings,
+    sink, uri, Batch, Compression, Compressor, EncodedEvent, Partition, TowerBatchedSink,
+    TowerPartitionSink, TowerRequestConfig, TowerRequestSettings,
 };
 use crate::{
     event::Event,
     http::{HttpClient, HttpError},
-    internal_events::EndpointBytesSent,
+    internal_events::{http_client::HttpRequestTooLarge, EndpointBytesSent},
 };
 
 pub trait HttpEventEncoder<Output> {
@@ -68,7 +69,10 @@ where
     sink: Arc<T>,
     #[pin]
     inner: TowerBatchedSink<
-        HttpBatchService<BoxFuture<'static, crate::Result<hyper::Request<Bytes>>>, B::Output>,
+        SplitTooLargeService<
+            HttpBatchService<BoxFuture<'static, crate::Result<hyper::Request<Bytes>>>, B::Output>,
+            B::Output,
+        >,
         B,
         RL,
     >,
@@ -117,6 +121,50 @@ where
         request_settings: TowerRequestSettings,
         batch_timeout: Duration,
         client: HttpClient,
+    ) -> Self {
+        Self::build(
+            sink,
+            batch,
+            retry_logic,
+            request_settings,
+            batch_timeout,
+            client,
+            None,
+        )
+    }
+
+    /// Like `with_logic`, but batches the server rejects with `413 Payload Too Large` are split
+    /// in two and sent again. See `SplitTooLargeService`.
+    pub fn with_splitting(
+        sink: T,
+        batch: B,
+        retry_logic: RL,
+        request_settings: TowerRequestSettings,
+        batch_timeout: Duration,
+        client: HttpClient,
+    ) -> Self
+    where
+        B::Output: SplitBatch,
+    {
+        Self::build(
+            sink,
+            batch,
+            retry_logic,
+            request_settings,
+            batch_timeout,
+            client,
+            Some(SplitBatch::split),
+        )
+    }
+
+    fn build(
+        sink: T,
+        batch: B,
+        retry_logic: RL,
+        request_settings: TowerRequestSettings,
+        batch_timeout: Duration,
+        client: HttpClient,
+        split: Option<Splitter<B::Output>>,
     ) -> Self {
         let sink = Arc::new(sink);
 
@@ -126,7 +174,7 @@ where
             Box::pin(async move { sink.build_request(b).await })
         };
 
-        let svc = HttpBatchService::new(client, request_builder);
+        let svc = SplitTooLargeService::new(HttpBatchService::new(client, request_builder), split);
         let inner = request_settings.batch_sink(retry_logic, svc, batch, batch_timeout);
         let encoder = sink.build_encoder();
 
@@ -380,6 +428,7 @@ where
         Box::pin(async move {
             let request = request_builder(body).await?;
             let byte_size = request.body().len();
+            let content_encoding = request.headers().get(header::CONTENT_ENCODING).cloned();
             let request = request.map(Body::from);
             let (protocol, endpoint) = uri::protocol_endpoint(request.uri().clone());
 
@@ -395,14 +444,138 @@ where
 
             let (parts, body) = response.into_parts();
             let mut body = body::aggregate(body).await?;
-            Ok(hyper::Response::from_parts(
-                parts,
-                body.copy_to_bytes(body.remaining()),
-            ))
+            let mut response =
+                hyper::Response::from_parts(parts, body.copy_to_bytes(body.remaining()));
+            if let Some(content_encoding) = content_encoding {
+                response
+                    .extensions_mut()
+                    .insert(RequestContentEncoding(content_encoding));
+            }
+            Ok(response)
         })
     }
 }
 
+/// The `Content-Encoding` of the request that a response of `HttpBatchService` was received for,
+/// stored in the response's extensions, for retry logic that reacts to rejected encodings.
+#[derive(Clone, Debug)]
+pub struct RequestContentEncoding(pub HeaderValue);
+
+/// The compression of the body of HTTP requests.
+///
+/// On top of the compressions shared by all sinks, it supports those only used as a
+/// `Content-Encoding`, which sinks writing files or objects can't use.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HttpCompression {
+    Common(Compression),
+    /// The level is used as the zstd compression level, where `0` selects zstd's own default.
+    Zstd(flate2::Compression),
+    /// The level is used as the brotli quality.
+    Brotli(flate2::Compression),
+}
+
+impl Default for HttpCompression {
+    fn default() -> Self {
+        Self::Common(Compression::None)
+    }
+}
+
+impl From<Compression> for HttpCompression {
+    fn from(compression: Compression) -> Self {
+        Self::Common(compression)
+    }
+}
+
+impl HttpCompression {
+    pub const fn gzip_default() -> Self {
+        Self::Common(Compression::gzip_default())
+    }
+
+    pub const fn zstd_default() -> Self {
+        Self::Zstd(flate2::Compression::new(6))
+    }
+
+    pub const fn brotli_default() -> Self {
+        Self::Brotli(flate2::Compression::new(6))
+    }
+
+    pub const fn content_encoding(self) -> Option<&'static str> {
+        match self {
+            Self::Common(compression) => compression.content_encoding(),
+            Self::Zstd(_) => Some("zstd"),
+            Self::Brotli(_) => Some("br"),
+        }
+    }
+
+    /// Compresses the body of a request.
+    pub fn compress(self, body: &[u8]) -> Bytes {
+        match self {
+            Self::Common(compression) => {
+                let mut compressor = Compressor::from(compression);
+                compressor.write_all(body).expect("Writing to Vec can't fail");
+                compressor.into_inner().freeze()
+            }
+            Self::Zstd(level) => zstd::bulk::compress(body, level.level() as i32)
+                .expect("Writing to Vec can't fail")
+                .into(),
+            Self::Brotli(level) => {
+                let mut writer = brotli::CompressorWriter::new(Vec::new(), 4096, level.level(), 22);
+                writer.write_all(body).expect("Writing to Vec can't fail");
+                writer.into_inner().into()
+            }
+        }
+    }
+}
+
+// The level of zstd and brotli is written the same way as for the common algorithms, so they are
+// handled as gzip with their algorithm renamed.
+impl<'de> Deserialize<'de> for HttpCompression {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        let algorithm = match &value {
+            serde_json::Value::String(algorithm) => Some(algorithm.as_str()),
+            serde_json::Value::Object(map) => {
+                map.get("algorithm").and_then(serde_json::Value::as_str)
+            }
+            _ => None,
+        };
+        let with_level: fn(flate2::Compression) -> Self = match algorithm {
+            Some("zstd") => Self::Zstd,
+            Some("brotli") => Self::Brotli,
+            _ => {
+                return Compression::deserialize(value)
+                    .map(Self::Common)
+                    .map_err(de::Error::custom)
+            }
+        };
+
+        match &mut value {
+            serde_json::Value::Object(map) => {
+                map.insert("algorithm".into(), "gzip".into());
+            }
+            value => *value = "gzip".into(),
+        }
+        match Compression::deserialize(value).map_err(de::Error::custom)? {
+            Compression::Gzip(level) => Ok(with_level(level)),
+            _ => unreachable!("deserialized as gzip"),
+        }
+    }
+}
+
+impl Serialize for HttpCompression {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (algorithm, level) = match *self {
+            Self::Common(compression) => return compression.serialize(serializer),
+            Self::Zstd(level) => ("zstd", level),
+            Self::Brotli(level) => ("brotli", level),
+        };
+        let mut value =
+            serde_json::to_value(Compression::Gzip(level)).map_err(ser::Error::custom)?;
+        value["algorithm"] = algorithm.into();
+        value.serialize(serializer)
+    }
+}
+
 impl<F, B> Clone for HttpBatchService<F, B> {
     fn clone(&self) -> Self {
         Self {
@@ -412,6 +585,116 @@ impl<F, B> Clone for HttpBatchService<F, B> {
     }
 }
 
+/// Batches that can be split in two, so that a request the server rejected as too large can be
+/// sent again as two smaller requests.
+pub trait SplitBatch: Sized {
+    /// Splits the batch into two halves, or gives it back if it can't be split.
+    fn split(self) -> Result<(Self, Self), Self>;
+}
+
+impl<T> SplitBatch for Vec<T> {
+    fn split(mut self) -> Result<(Self, Self), Self> {
+        if self.len() < 2 {
+            return Err(self);
+        }
+        let second = self.split_off(self.len() / 2);
+        Ok((self, second))
+    }
+}
+
+/// Splits a batch in two, or gives it back if it can't be split.
+type Splitter<B> = fn(B) -> Result<(B, B), B>;
+
+/// Wraps an HTTP batch service to split the batches the server rejects with `413 Payload Too
+/// Large` in two, sending each half as its own request, until the batch can't be split any
+/// further.
+///
+/// Only batches keeping their events apart until the request is built can be split, so splitting
+/// is left to the sinks batching such outputs. Without a splitter, the batches are passed through
+/// as they are.
+///
+/// The halves are sent one after the other, and the first unsuccessful response is returned. If
+/// the batch is then retried, the halves that were already delivered are sent again.
+pub struct SplitTooLargeService<S, B> {
+    inner: S,
+    split: Option<Splitter<B>>,
+}
+
+impl<S, B> SplitTooLargeService<S, B> {
+    pub const fn new(inner: S, split: Option<Splitter<B>>) -> Self {
+        Self { inner, split }
+    }
+}
+
+impl<S: Clone, B> Clone for SplitTooLargeService<S, B> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            split: self.split,
+        }
+    }
+}
+
+impl<S, B> Service<B> for SplitTooLargeService<S, B>
+where
+    S: Service<B, Response = http::Response<Bytes>, Error = crate::Error> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Clone + Send + 'static,
+{
+    type Response = http::Response<Bytes>;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, batch: B) -> Self::Future {
+        match self.split {
+            Some(split) => {
+                // Take the service that was driven to readiness, leaving a clone in its place.
+                let inner = self.inner.clone();
+                let inner = std::mem::replace(&mut self.inner, inner);
+                send_splitting_too_large(inner, split, batch)
+            }
+            None => Box::pin(self.inner.call(batch)),
+        }
+    }
+}
+
+fn send_splitting_too_large<S, B>(
+    mut service: S,
+    split: Splitter<B>,
+    batch: B,
+) -> BoxFuture<'static, crate::Result<http::Response<Bytes>>>
+where
+    S: Service<B, Response = http::Response<Bytes>, Error = crate::Error> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Clone + Send + 'static,
+{
+    Box::pin(async move {
+        let response = service.ready().await?.call(batch.clone()).await?;
+        if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+            return Ok(response);
+        }
+
+        match split(batch) {
+            Ok((first, second)) => {
+                emit!(HttpRequestTooLarge { split: true });
+                let response = send_splitting_too_large(service.clone(), split, first).await?;
+                if !response.status().is_success() {
+                    return Ok(response);
+                }
+                send_splitting_too_large(service, split, second).await
+            }
+            Err(_) => {
+                emit!(HttpRequestTooLarge { split: false });
+                Ok(response)
+            }
+        }
+    })
+}
+
 impl<T: fmt::Debug> sink::Response for http::Response<T> {
     fn is_successful(&self) -> bool {
         self.status().is_success()
@@ -448,6 +731,10 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status())
+    }
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -498,6 +785,10 @@ where
             _ => RetryAction::DontRetry(format!("Http status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &T) -> Option<StatusCode> {
+        Some((self.func)(response))
+    }
 }
 
 impl<F, T> Clone for HttpStatusRetryLogic<F, T>
@@ -544,6 +835,35 @@ mod test {
     use super::*;
     use crate::{config::ProxyConfig, test_util::next_addr};
 
+    #[test]
+    fn http_compression_serde() {
+        let fixtures = [
+            (r#""gzip""#, HttpCompression::gzip_default()),
+            (r#""zstd""#, HttpCompression::zstd_default()),
+            (
+                r#"{"algorithm": "zstd", "level": "fast"}"#,
+                HttpCompression::Zstd(flate2::Compression::fast()),
+            ),
+            (
+                r#"{"algorithm": "brotli", "level": 4}"#,
+                HttpCompression::Brotli(flate2::Compression::new(4)),
+            ),
+        ];
+        for (source, expected) in fixtures {
+            let compression: HttpCompression = serde_json::from_str(source).unwrap();
+            assert_eq!(compression, expected);
+
+            let serialized = serde_json::to_string(&compression).unwrap();
+            let deserialized: HttpCompression = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, expected);
+        }
+
+        assert!(
+            serde_json::from_str::<HttpCompression>(r#"{"algorithm": "zstd", "level": 10}"#)
+                .is_err()
+        );
+    }
+
     #[test]
     fn util_http_retry_logic() {
         let logic = HttpRetryLogic;
@@ -563,6 +883,64 @@ mod test {
             .is_not_retryable());
     }
 
+    #[tokio::test]
+    async fn util_http_splits_batches_rejected_as_too_large() {
+        // Accepts batches of up to `max_len` items, recording them.
+        let limited_service = |max_len: usize,
+                               split: Option<Splitter<Vec<usize>>>,
+                               sent: Arc<std::sync::Mutex<Vec<Vec<usize>>>>| {
+            SplitTooLargeService::new(
+                tower::service_fn(move |batch: Vec<usize>| {
+                    let sent = Arc::clone(&sent);
+                    async move {
+                        let status = if batch.len() > max_len {
+                            StatusCode::PAYLOAD_TOO_LARGE
+                        } else {
+                            sent.lock().unwrap().push(batch);
+                            StatusCode::OK
+                        };
+                        Ok::<_, crate::Error>(
+                            Response::builder()
+                                .status(status)
+                                .body(Bytes::new())
+                                .unwrap(),
+                        )
+                    }
+                }),
+                split,
+            )
+        };
+
+        let sent = Arc::default();
+        let response = limited_service(2, Some(SplitBatch::split), Arc::clone(&sent))
+            .oneshot((0..7).collect())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![vec![0], vec![1, 2], vec![3, 4], vec![5, 6]]
+        );
+
+        // A single item that is still too large can't be split any further.
+        let sent = Arc::default();
+        let response = limited_service(0, Some(SplitBatch::split), Arc::clone(&sent))
+            .oneshot(vec![0, 1])
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(sent.lock().unwrap().is_empty());
+
+        // Without a splitter, the response is returned as it is.
+        let sent = Arc::default();
+        let response = limited_service(2, None, Arc::clone(&sent))
+            .oneshot((0..7).collect())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(sent.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn util_http_it_makes_http_requests() {
         let addr = next_addr();
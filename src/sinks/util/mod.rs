@@ -2,6 +2,7 @@ pub mod adaptive_concurrency;
 pub mod batch;
 pub mod buffer;
 pub mod builder;
+pub mod circuit_breaker;
 pub mod compressor;
 pub mod encoding;
 pub mod http;
@@ -15,6 +16,7 @@ pub mod service;
 pub mod sink;
 pub mod socket_bytes_sink;
 pub mod statistic;
+pub mod tag_rules;
 pub mod tcp;
 #[cfg(test)]
 pub mod test;
@@ -46,6 +48,7 @@ pub use service::{
 };
 pub use sink::{BatchSink, PartitionBatchSink, StreamSink};
 use snafu::Snafu;
+pub use tag_rules::{TagRule, TagRules};
 pub use uri::UriSerde;
 
 use crate::event::EventFinalizers;
@@ -3,14 +3,22 @@ use std::{
     cmp,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use futures::FutureExt;
+use http::StatusCode;
+use rand::Rng;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use tokio::time::{sleep, Sleep};
-use tower::{retry::Policy, timeout::error::Elapsed};
+use tower::{
+    retry::{budget::Budget, Policy},
+    timeout::error::Elapsed,
+};
 
+use super::circuit_breaker::CircuitOpenError;
 use crate::Error;
 
 pub enum RetryAction {
@@ -32,6 +40,209 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// The HTTP status of the response, which the status codes of the retry policy configuration
+    /// apply to.
+    fn response_status(&self, _response: &Self::Response) -> Option<StatusCode> {
+        None
+    }
+}
+
+/// Retry policy configuration, overriding the retry logic of the sink.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetryPolicyConfig {
+    /// HTTP status codes of the responses to retry.
+    #[serde(default, skip_serializing_if = "StatusCodes::is_empty")]
+    pub status_codes: StatusCodes,
+    /// HTTP status codes of the responses to never retry, taking precedence over `status_codes`.
+    #[serde(default, skip_serializing_if = "StatusCodes::is_empty")]
+    pub excluded_status_codes: StatusCodes,
+    /// Whether requests that timed out are retried.
+    #[serde(default = "crate::serde::default_true")]
+    pub timeouts: bool,
+    /// Whether requests that failed with an error the sink considers retriable are retried.
+    #[serde(default = "crate::serde::default_true")]
+    pub errors: bool,
+    /// The maximum time since the first failed attempt of a request after which it isn't retried.
+    #[serde(default)]
+    pub max_elapsed_secs: Option<u64>,
+    #[serde(default)]
+    pub jitter: JitterMode,
+    #[serde(default)]
+    pub budget: Option<RetryBudgetConfig>,
+}
+
+impl Default for RetryPolicyConfig {
+    fn default() -> Self {
+        Self::const_default()
+    }
+}
+
+impl RetryPolicyConfig {
+    pub const fn const_default() -> Self {
+        Self {
+            status_codes: StatusCodes([0; 10]),
+            excluded_status_codes: StatusCodes([0; 10]),
+            timeouts: true,
+            errors: true,
+            max_elapsed_secs: None,
+            jitter: JitterMode::None,
+            budget: None,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// The randomization applied to the delay before retrying a request.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum JitterMode {
+    /// The delay is the backoff.
+    #[derivative(Default)]
+    None,
+    /// The delay is picked at random between zero and the backoff.
+    Full,
+}
+
+impl JitterMode {
+    fn apply(self, backoff: Duration) -> Duration {
+        match self {
+            Self::None => backoff,
+            Self::Full => {
+                let millis = backoff.as_millis().min(u64::MAX as u128) as u64;
+                Duration::from_millis(rand::thread_rng().gen_range(0..=millis))
+            }
+        }
+    }
+}
+
+/// Limits retries to a ratio of the successful requests, so that a failing downstream isn't
+/// overwhelmed by retries.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetryBudgetConfig {
+    /// The ratio of retries to successful requests allowed, between `0` and `1000`.
+    #[serde(deserialize_with = "deserialize_budget_ratio")]
+    pub ratio: f32,
+    /// The retries allowed per second regardless of the ratio, below `2147483647`.
+    #[serde(
+        default = "default_budget_min_per_sec",
+        deserialize_with = "deserialize_budget_min_per_sec"
+    )]
+    pub min_per_sec: u32,
+    /// The period over which successful requests are accounted for, between `1` and `60`
+    /// seconds.
+    #[serde(
+        default = "default_budget_ttl_secs",
+        deserialize_with = "deserialize_budget_ttl_secs"
+    )]
+    pub ttl_secs: u64,
+}
+
+const fn default_budget_min_per_sec() -> u32 {
+    10
+}
+
+const fn default_budget_ttl_secs() -> u64 {
+    10
+}
+
+// `Budget::new` panics outside of these limits, so they're checked when the config is loaded.
+
+fn deserialize_budget_ratio<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    let ratio = f32::deserialize(deserializer)?;
+    if !(0.0..=1000.0).contains(&ratio) {
+        return Err(de::Error::custom(format!(
+            "invalid retry budget ratio: {}, must be between 0 and 1000",
+            ratio
+        )));
+    }
+    Ok(ratio)
+}
+
+fn deserialize_budget_min_per_sec<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    let min_per_sec = u32::deserialize(deserializer)?;
+    if min_per_sec >= i32::MAX as u32 {
+        return Err(de::Error::custom(format!(
+            "invalid retry budget min_per_sec: {}, must be below {}",
+            min_per_sec,
+            i32::MAX
+        )));
+    }
+    Ok(min_per_sec)
+}
+
+fn deserialize_budget_ttl_secs<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    let ttl_secs = u64::deserialize(deserializer)?;
+    if !(1..=60).contains(&ttl_secs) {
+        return Err(de::Error::custom(format!(
+            "invalid retry budget ttl_secs: {}, must be between 1 and 60",
+            ttl_secs
+        )));
+    }
+    Ok(ttl_secs)
+}
+
+/// A set of HTTP status codes, as a bit set so that the request configuration stays `Copy`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StatusCodes([u64; 10]);
+
+impl StatusCodes {
+    fn insert(&mut self, code: u16) {
+        self.0[usize::from(code / 64)] |= 1 << (code % 64);
+    }
+
+    pub fn contains(&self, code: u16) -> bool {
+        self.0
+            .get(usize::from(code / 64))
+            .map_or(false, |bits| bits & (1 << (code % 64)) != 0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|bits| *bits == 0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        (100..600).filter(move |code| self.contains(*code))
+    }
+}
+
+impl FromIterator<u16> for StatusCodes {
+    fn from_iter<I: IntoIterator<Item = u16>>(codes: I) -> Self {
+        let mut set = Self::default();
+        for code in codes {
+            set.insert(code);
+        }
+        set
+    }
+}
+
+impl Serialize for StatusCodes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusCodes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let codes = Vec::<u16>::deserialize(deserializer)?;
+        if let Some(code) = codes.iter().find(|code| !(100..600).contains(*code)) {
+            return Err(de::Error::custom(format!(
+                "invalid HTTP status code: {}",
+                code
+            )));
+        }
+        Ok(codes.into_iter().collect())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +252,9 @@ pub struct FixedRetryPolicy<L> {
     current_duration: Duration,
     max_duration: Duration,
     logic: L,
+    config: RetryPolicyConfig,
+    budget: Option<Arc<Budget>>,
+    first_failure: Option<Instant>,
 }
 
 pub struct RetryPolicyFuture<L: RetryLogic> {
@@ -61,9 +275,25 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             current_duration: initial_backoff,
             max_duration,
             logic,
+            config: RetryPolicyConfig::const_default(),
+            budget: None,
+            first_failure: None,
         }
     }
 
+    /// Applies the retry policy configuration, overriding the retry logic.
+    pub fn with_config(mut self, config: RetryPolicyConfig) -> Self {
+        self.budget = config.budget.map(|budget| {
+            Arc::new(Budget::new(
+                Duration::from_secs(budget.ttl_secs),
+                budget.min_per_sec,
+                budget.ratio,
+            ))
+        });
+        self.config = config;
+        self
+    }
+
     fn advance(&self) -> FixedRetryPolicy<L> {
         let next_duration: Duration = self.previous_duration + self.current_duration;
 
@@ -73,6 +303,9 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             current_duration: cmp::min(next_duration, self.max_duration),
             max_duration: self.max_duration,
             logic: self.logic.clone(),
+            config: self.config,
+            budget: self.budget.clone(),
+            first_failure: Some(self.first_failure.unwrap_or_else(Instant::now)),
         }
     }
 
@@ -82,11 +315,38 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
 
     fn build_retry(&self) -> RetryPolicyFuture<L> {
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let backoff = self.config.jitter.apply(self.backoff());
+        let delay = Box::pin(sleep(backoff));
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
+        debug!(message = "Retrying request.", delay_ms = %backoff.as_millis());
         RetryPolicyFuture { delay, policy }
     }
+
+    /// Checks the limits of the retry policy configuration, withdrawing from the retry budget.
+    fn retry_allowed(&self) -> Result<(), &'static str> {
+        let max_elapsed = self.config.max_elapsed_secs.map(Duration::from_secs);
+        if let (Some(max_elapsed), Some(first_failure)) = (max_elapsed, self.first_failure) {
+            if first_failure.elapsed() >= max_elapsed {
+                return Err("maximum elapsed time reached");
+            }
+        }
+        match &self.budget {
+            Some(budget) if budget.withdraw().is_err() => Err("retry budget exhausted"),
+            _ => Ok(()),
+        }
+    }
+
+    fn retry_action(&self, response: &L::Response) -> RetryAction {
+        match self.logic.response_status(response) {
+            Some(status) if self.config.excluded_status_codes.contains(status.as_u16()) => {
+                RetryAction::DontRetry(format!("response status: {}", status).into())
+            }
+            Some(status) if self.config.status_codes.contains(status.as_u16()) => {
+                RetryAction::Retry(format!("response status: {}", status).into())
+            }
+            _ => self.logic.should_retry_response(response),
+        }
+    }
 }
 
 impl<Req, Res, L> Policy<Req, Res, Error> for FixedRetryPolicy<L>
@@ -98,7 +358,7 @@ where
 
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
         match result {
-            Ok(response) => match self.logic.should_retry_response(response) {
+            Ok(response) => match self.retry_action(response) {
                 RetryAction::Retry(reason) => {
                     if self.remaining_attempts == 0 {
                         error!(
@@ -107,6 +367,14 @@ where
                         );
                         return None;
                     }
+                    if let Err(limit) = self.retry_allowed() {
+                        error!(
+                            message = "OK/retry response but retry policy limit reached; dropping the request.",
+                            reason = ?reason,
+                            %limit,
+                        );
+                        return None;
+                    }
 
                     warn!(message = "Retrying after response.", reason = %reason);
                     Some(self.build_retry())
@@ -117,7 +385,12 @@ where
                     None
                 }
 
-                RetryAction::Successful => None,
+                RetryAction::Successful => {
+                    if let Some(budget) = &self.budget {
+                        budget.deposit();
+                    }
+                    None
+                }
             },
             Err(error) => {
                 if self.remaining_attempts == 0 {
@@ -126,7 +399,15 @@ where
                 }
 
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
-                    if self.logic.is_retriable_error(expected) {
+                    if self.config.errors && self.logic.is_retriable_error(expected) {
+                        if let Err(limit) = self.retry_allowed() {
+                            error!(
+                                message = "Retry policy limit reached; dropping the request.",
+                                %error,
+                                %limit,
+                            );
+                            return None;
+                        }
                         warn!(message = "Retrying after error.", error = %expected);
                         Some(self.build_retry())
                     } else {
@@ -136,7 +417,30 @@ where
                         );
                         None
                     }
+                } else if error.downcast_ref::<CircuitOpenError>().is_some() {
+                    if let Err(limit) = self.retry_allowed() {
+                        error!(
+                            message = "Retry policy limit reached; dropping the request.",
+                            %error,
+                            %limit,
+                        );
+                        return None;
+                    }
+                    debug!(message = "Circuit breaker open; retrying later.");
+                    Some(self.build_retry())
                 } else if error.downcast_ref::<Elapsed>().is_some() {
+                    if !self.config.timeouts {
+                        error!(message = "Request timed out; dropping the request.", %error);
+                        return None;
+                    }
+                    if let Err(limit) = self.retry_allowed() {
+                        error!(
+                            message = "Retry policy limit reached; dropping the request.",
+                            %error,
+                            %limit,
+                        );
+                        return None;
+                    }
                     warn!("Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.");
                     Some(self.build_retry())
                 } else {
@@ -261,13 +565,14 @@ impl Iterator for ExponentialBackoff {
 mod tests {
     use std::{fmt, time::Duration};
 
+    use bytes::Bytes;
     use tokio::time;
     use tokio_test::{assert_pending, assert_ready_err, assert_ready_ok, task};
     use tower::retry::RetryLayer;
     use tower_test::{assert_request_eq, mock};
 
     use super::*;
-    use crate::test_util::trace_init;
+    use crate::{sinks::util::http::HttpRetryLogic, test_util::trace_init};
 
     #[tokio::test]
     async fn service_error_retry() {
@@ -380,6 +685,145 @@ mod tests {
         assert_eq!(Duration::from_secs(10), policy.backoff());
     }
 
+    #[tokio::test]
+    async fn timeout_error_no_retry() {
+        trace_init();
+
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_config(RetryPolicyConfig {
+            timeouts: false,
+            ..Default::default()
+        });
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(Elapsed::new());
+        assert_ready_err!(fut.poll());
+    }
+
+    #[test]
+    fn configured_status_codes_override_logic() {
+        let config = RetryPolicyConfig {
+            status_codes: [409].into_iter().collect(),
+            excluded_status_codes: [503].into_iter().collect(),
+            ..Default::default()
+        };
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            StatusRetryLogic,
+        )
+        .with_config(config);
+
+        assert!(policy.retry_action(&409).is_retryable());
+        assert!(policy.retry_action(&503).is_not_retryable());
+        assert!(policy.retry_action(&500).is_retryable());
+        assert!(policy.retry_action(&400).is_not_retryable());
+        assert!(policy.retry_action(&200).is_successful());
+    }
+
+    #[test]
+    fn configured_status_codes_apply_to_http_responses() {
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            HttpRetryLogic,
+        )
+        .with_config(RetryPolicyConfig {
+            status_codes: [409].into_iter().collect(),
+            ..Default::default()
+        });
+        let response = |status| {
+            http::Response::builder()
+                .status(status)
+                .body(Bytes::new())
+                .unwrap()
+        };
+
+        assert!(policy.retry_action(&response(409)).is_retryable());
+        assert!(policy.retry_action(&response(400)).is_not_retryable());
+    }
+
+    #[test]
+    fn max_elapsed_stops_retries() {
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_config(RetryPolicyConfig {
+            max_elapsed_secs: Some(0),
+            ..Default::default()
+        });
+
+        assert!(policy.retry_allowed().is_ok());
+        assert!(policy.advance().retry_allowed().is_err());
+    }
+
+    #[test]
+    fn deserializes_status_codes() {
+        let config: RetryPolicyConfig =
+            toml::from_str("status_codes = [409, 429]\njitter = \"full\"").unwrap();
+        assert!(config.status_codes.contains(409));
+        assert!(config.status_codes.contains(429));
+        assert!(!config.status_codes.contains(500));
+        assert_eq!(config.jitter, JitterMode::Full);
+
+        assert!(toml::from_str::<RetryPolicyConfig>("status_codes = [42]").is_err());
+    }
+
+    #[test]
+    fn rejects_budgets_outside_limits() {
+        let config: RetryBudgetConfig = toml::from_str("ratio = 0.2").unwrap();
+        assert_eq!(config.ttl_secs, 10);
+
+        for budget in [
+            "ratio = -1.0",
+            "ratio = 1001.0",
+            "ratio = nan",
+            "ratio = 0.2\nttl_secs = 0",
+            "ratio = 0.2\nttl_secs = 61",
+            "ratio = 0.2\nmin_per_sec = 2147483647",
+        ] {
+            assert!(toml::from_str::<RetryBudgetConfig>(budget).is_err(), "{}", budget);
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct StatusRetryLogic;
+
+    impl RetryLogic for StatusRetryLogic {
+        type Error = Error;
+        type Response = u16;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.0
+        }
+
+        fn should_retry_response(&self, response: &u16) -> RetryAction {
+            match *response {
+                200 => RetryAction::Successful,
+                500..=599 => RetryAction::Retry("server error".into()),
+                _ => RetryAction::DontRetry("client error".into()),
+            }
+        }
+
+        fn response_status(&self, response: &u16) -> Option<StatusCode> {
+            StatusCode::from_u16(*response).ok()
+        }
+    }
+
     #[derive(Debug, Clone)]
     struct SvcRetryLogic;
 
@@ -17,7 +17,8 @@ use crate::sinks::util::{
     adaptive_concurrency::{
         AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
     },
-    retries::{FixedRetryPolicy, RetryLogic},
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerLayer},
+    retries::{FixedRetryPolicy, RetryLogic, RetryPolicyConfig},
     service::map::MapLayer,
     sink::Response,
     Batch, BatchSink, Partition, PartitionBatchSink,
@@ -26,7 +27,9 @@ use crate::sinks::util::{
 mod concurrency;
 mod map;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> = RateLimit<
+    AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, CircuitBreaker<Timeout<S>, L>>, L>,
+>;
 pub type TowerBatchedSink<S, B, RL> = BatchSink<Svc<S, RL>, B>;
 pub type TowerPartitionSink<S, B, RL, K> = PartitionBatchSink<Svc<S, RL>, B, K>;
 
@@ -77,6 +80,36 @@ pub struct TowerRequestConfig {
     pub retry_initial_backoff_secs: Option<u64>, // 1
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "RequestOrdering::is_unordered")]
+    pub ordering: RequestOrdering,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "RetryPolicyConfig::is_default")]
+    pub retry_policy: RetryPolicyConfig,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+}
+
+/// Whether requests may complete in a different order than their events were received.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Eq, PartialEq, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestOrdering {
+    /// Requests are sent concurrently, so that a request, or its retries, may complete after
+    /// those sent later.
+    #[derivative(Default)]
+    Unordered,
+
+    /// A single request is in flight at any given time, and a failed request is retried before
+    /// the next one is sent. This preserves the order of the events within a partition.
+    Strict,
+}
+
+impl RequestOrdering {
+    pub const fn is_unordered(&self) -> bool {
+        matches!(self, Self::Unordered)
+    }
 }
 
 pub const CONCURRENCY_DEFAULT: Concurrency = Concurrency::None;
@@ -104,6 +137,9 @@ impl TowerRequestConfig {
             retry_max_duration_secs: Some(RETRY_MAX_DURATION_SECONDS_DEFAULT),
             retry_initial_backoff_secs: Some(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             adaptive_concurrency: AdaptiveConcurrencySettings::const_default(),
+            ordering: RequestOrdering::Unordered,
+            retry_policy: RetryPolicyConfig::const_default(),
+            circuit_breaker: None,
         }
     }
 
@@ -142,8 +178,15 @@ impl TowerRequestConfig {
     }
 
     pub fn unwrap_with(&self, defaults: &Self) -> TowerRequestSettings {
+        // Retries happen within the concurrency limit, so a limit of one request also keeps a
+        // failed request from being overtaken by the following ones.
+        let concurrency = match self.ordering {
+            RequestOrdering::Unordered => self.concurrency.parse_concurrency(defaults.concurrency),
+            RequestOrdering::Strict => Some(1),
+        };
+
         TowerRequestSettings {
-            concurrency: self.concurrency.parse_concurrency(defaults.concurrency),
+            concurrency,
             timeout: Duration::from_secs(
                 self.timeout_secs
                     .or(defaults.timeout_secs)
@@ -173,6 +216,8 @@ impl TowerRequestConfig {
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
             adaptive_concurrency: self.adaptive_concurrency,
+            retry_policy: self.retry_policy,
+            circuit_breaker: self.circuit_breaker,
         }
     }
 }
@@ -187,16 +232,19 @@ pub struct TowerRequestSettings {
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
+    pub retry_policy: RetryPolicyConfig,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
 }
 
 impl TowerRequestSettings {
-    pub const fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
+    pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
         FixedRetryPolicy::new(
             self.retry_attempts,
             self.retry_initial_backoff_secs,
             self.retry_max_duration_secs,
             logic,
         )
+        .with_config(self.retry_policy)
     }
 
     pub fn partition_sink<B, RL, S, K>(
@@ -277,6 +325,10 @@ where
                 self.retry_logic.clone(),
             ))
             .retry(policy)
+            .layer(CircuitBreakerLayer::new(
+                self.settings.circuit_breaker,
+                self.retry_logic.clone(),
+            ))
             .timeout(self.settings.timeout)
             .service(inner)
     }
@@ -334,6 +386,24 @@ mod tests {
         assert_eq!(cfg.concurrency, None);
     }
 
+    #[test]
+    fn strict_ordering_limits_concurrency_to_one() {
+        let cfg = toml::from_str::<TowerRequestConfig>(
+            r#"
+            concurrency = "adaptive"
+            ordering = "strict"
+            "#,
+        )
+        .expect("Strict ordering failed");
+        assert_eq!(cfg.ordering, RequestOrdering::Strict);
+
+        let settings = cfg.unwrap_with(&TowerRequestConfig::new(Concurrency::Fixed(5)));
+        assert_eq!(settings.concurrency, Some(1));
+
+        let cfg = toml::from_str::<TowerRequestConfig>("").expect("Empty config failed");
+        assert_eq!(cfg.ordering, RequestOrdering::Unordered);
+    }
+
     #[tokio::test]
     async fn partition_sink_retry_concurrency() {
         let cfg = TowerRequestConfig {
@@ -0,0 +1,238 @@
+//! Rules rewriting the tags of metrics before they are sent, so that the tag constraints of a
+//! vendor, like their length or charset, can be enforced by its sink.
+
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_core::event::{metric::MetricTags, Metric};
+
+#[derive(Debug, Snafu)]
+pub enum TagRuleError {
+    #[snafu(display("Invalid tag rule pattern {:?}: {}", key, source))]
+    InvalidPattern {
+        key: String,
+        source: glob::PatternError,
+    },
+}
+
+/// A rule applied to the tags whose key matches its pattern.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TagRule {
+    /// The glob pattern of the keys of the tags the rule applies to.
+    pub key: String,
+
+    #[serde(flatten)]
+    pub action: TagAction,
+}
+
+/// The rewrite of the tags matched by a rule.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TagAction {
+    /// Renames the tags to the given key.
+    Rename { to: String },
+
+    /// Drops the tags.
+    Drop,
+
+    /// Replaces the values of the tags with their hash.
+    Hash,
+
+    /// Converts the values of the tags to lowercase.
+    Lowercase,
+
+    /// Truncates the values of the tags to the given number of characters.
+    Truncate { max_length: usize },
+}
+
+/// The compiled tag rules of a sink, applied in order.
+#[derive(Clone, Debug, Default)]
+pub struct TagRules {
+    rules: Vec<(glob::Pattern, TagAction)>,
+}
+
+impl TagRules {
+    pub fn new(rules: &[TagRule]) -> Result<Self, TagRuleError> {
+        let rules = rules
+            .iter()
+            .map(|rule| {
+                glob::Pattern::new(&rule.key)
+                    .map(|pattern| (pattern, rule.action.clone()))
+                    .context(InvalidPatternSnafu { key: &rule.key })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn apply(&self, metric: &mut Metric) {
+        if self.is_empty() {
+            return;
+        }
+        let tags = metric.series_mut().tags_mut();
+        if let Some(mut rewritten) = tags.take() {
+            self.apply_tags(&mut rewritten);
+            *tags = (!rewritten.is_empty()).then(|| rewritten);
+        }
+    }
+
+    fn apply_tags(&self, tags: &mut MetricTags) {
+        for (pattern, action) in &self.rules {
+            let keys = tags
+                .keys()
+                .filter(|key| pattern.matches(key))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            for key in keys {
+                match action {
+                    TagAction::Rename { to } => {
+                        if let Some(value) = tags.remove(&key) {
+                            tags.insert(to.clone(), value);
+                        }
+                    }
+                    TagAction::Drop => {
+                        tags.remove(&key);
+                    }
+                    TagAction::Hash => {
+                        if let Some(value) = tags.get_mut(&key) {
+                            *value = format!("{:016x}", fnv1a(value.as_bytes()));
+                        }
+                    }
+                    TagAction::Lowercase => {
+                        if let Some(value) = tags.get_mut(&key) {
+                            *value = value.to_lowercase();
+                        }
+                    }
+                    TagAction::Truncate { max_length } => {
+                        if let Some(value) = tags.get_mut(&key) {
+                            if let Some((index, _)) = value.char_indices().nth(*max_length) {
+                                value.truncate(index);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The 64-bit FNV-1a hash, which is stable across restarts and versions, unlike the hashers of the
+/// standard library.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::event::metric::{MetricKind, MetricValue};
+
+    use super::*;
+
+    fn rules(toml: &str) -> TagRules {
+        #[derive(Deserialize)]
+        struct Config {
+            tag_rules: Vec<TagRule>,
+        }
+        let config: Config = toml::from_str(toml).unwrap();
+        TagRules::new(&config.tag_rules).unwrap()
+    }
+
+    fn metric(tags: &[(&str, &str)]) -> Metric {
+        Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            tags.iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn applies_rules_in_order() {
+        let rules = rules(
+            r#"
+            [[tag_rules]]
+            key = "hostname"
+            action = "rename"
+            to = "host"
+
+            [[tag_rules]]
+            key = "host"
+            action = "lowercase"
+
+            [[tag_rules]]
+            key = "debug_*"
+            action = "drop"
+
+            [[tag_rules]]
+            key = "*"
+            action = "truncate"
+            max_length = 5
+            "#,
+        );
+        let mut metric = metric(&[
+            ("hostname", "Web-Server"),
+            ("debug_id", "42"),
+            ("debug_trace", "abc"),
+            ("region", "eu-west-1"),
+        ]);
+        rules.apply(&mut metric);
+
+        let expected = MetricTags::from([
+            ("host".to_owned(), "web-s".to_owned()),
+            ("region".to_owned(), "eu-we".to_owned()),
+        ]);
+        assert_eq!(metric.tags(), Some(&expected));
+    }
+
+    #[test]
+    fn hashes_values() {
+        let rules = rules(
+            r#"
+            [[tag_rules]]
+            key = "user_id"
+            action = "hash"
+            "#,
+        );
+        let mut first = metric(&[("user_id", "alice")]);
+        let mut second = metric(&[("user_id", "alice")]);
+        rules.apply(&mut first);
+        rules.apply(&mut second);
+
+        let hash = first.tag_value("user_id").unwrap();
+        assert_eq!(hash.len(), 16);
+        assert_ne!(hash, "alice");
+        assert_eq!(second.tag_value("user_id"), Some(hash));
+    }
+
+    #[test]
+    fn drops_empty_tags() {
+        let rules = rules(
+            r#"
+            [[tag_rules]]
+            key = "*"
+            action = "drop"
+            "#,
+        );
+        let mut metric = metric(&[("host", "a")]);
+        rules.apply(&mut metric);
+        assert!(metric.tags().is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_patterns() {
+        let rule = TagRule {
+            key: "[".to_owned(),
+            action: TagAction::Drop,
+        };
+        assert!(TagRules::new(&[rule]).is_err());
+    }
+}
@@ -42,7 +42,7 @@ use crate::{
 };
 
 #[derive(Debug, Snafu)]
-enum TcpError {
+pub enum TcpError {
     #[snafu(display("Connect error: {}", source))]
     ConnectError { source: TlsError },
     #[snafu(display("Unable to resolve DNS: {}", source))]
@@ -105,7 +105,7 @@ impl TcpSinkConfig {
 }
 
 #[derive(Clone)]
-struct TcpConnector {
+pub(crate) struct TcpConnector {
     host: String,
     port: u16,
     keepalive: Option<TcpKeepaliveConfig>,
@@ -114,7 +114,7 @@ struct TcpConnector {
 }
 
 impl TcpConnector {
-    const fn new(
+    pub(crate) const fn new(
         host: String,
         port: u16,
         keepalive: Option<TcpKeepaliveConfig>,
@@ -142,7 +142,7 @@ impl TcpConnector {
             .max_delay(Duration::from_secs(60))
     }
 
-    async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
+    pub(crate) async fn connect(&self) -> Result<MaybeTlsStream<TcpStream>, TcpError> {
         let ip = dns::Resolver
             .lookup_ip(self.host.clone())
             .await
@@ -190,7 +190,7 @@ impl TcpConnector {
         }
     }
 
-    async fn healthcheck(&self) -> crate::Result<()> {
+    pub(crate) async fn healthcheck(&self) -> crate::Result<()> {
         self.connect().await.map(|_| ()).map_err(Into::into)
     }
 }
@@ -1,19 +1,25 @@
-use std::{path::PathBuf, pin::Pin, time::Duration};
+use std::{io, path::PathBuf, pin::Pin, time::Duration};
 
 use async_trait::async_trait;
 use bytes::{Bytes, BytesMut};
 use futures::{stream::BoxStream, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use tokio::{net::UnixStream, time::sleep};
+use socket2::{Domain, SockAddr, Socket, Type};
+use tokio::{
+    io::unix::AsyncFd,
+    net::{UnixDatagram, UnixStream},
+    time::sleep,
+};
 use tokio_util::codec::Encoder;
+use vector_common::internal_event::BytesSent;
 use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::Transformer,
     event::{Event, Finalizable},
     internal_events::{
-        ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionError,
+        ConnectionOpen, OpenGauge, SocketEventsSent, SocketMode, UnixSocketConnectionError,
         UnixSocketConnectionEstablished, UnixSocketError,
     },
     sink::VecSinkExt,
@@ -55,6 +61,38 @@ impl UnixSinkConfig {
             Box::pin(async move { connector.healthcheck().await }),
         ))
     }
+
+    /// Builds a sink that sends each event as a single message over a datagram or seqpacket
+    /// socket.
+    pub fn build_message_based(
+        &self,
+        socket_type: UnixMessageSocketType,
+        transformer: Transformer,
+        encoder: impl Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync + 'static,
+    ) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = UnixMessageConnector {
+            path: self.path.clone(),
+            socket_type,
+        };
+        let sink = UnixMessageSink {
+            connector: connector.clone(),
+            transformer,
+            encoder,
+        };
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async move { connector.healthcheck().await }),
+        ))
+    }
+}
+
+/// Unix socket types that preserve message boundaries.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum UnixMessageSocketType {
+    /// `SOCK_DGRAM`, an unreliable, connectionless socket.
+    Datagram,
+    /// `SOCK_SEQPACKET`, a reliable, connection-oriented socket.
+    Seqpacket,
 }
 
 #[derive(Debug, Clone)]
@@ -102,6 +140,142 @@ impl UnixConnector {
     }
 }
 
+#[derive(Debug, Clone)]
+struct UnixMessageConnector {
+    path: PathBuf,
+    socket_type: UnixMessageSocketType,
+}
+
+impl UnixMessageConnector {
+    async fn connect(&self) -> Result<UnixMessageSocket, UnixError> {
+        match self.socket_type {
+            UnixMessageSocketType::Datagram => UnixDatagram::unbound()
+                .and_then(|socket| socket.connect(&self.path).map(|()| socket))
+                .map(UnixMessageSocket::Datagram),
+            UnixMessageSocketType::Seqpacket => connect_seqpacket(&self.path)
+                .and_then(AsyncFd::new)
+                .map(UnixMessageSocket::Seqpacket),
+        }
+        .context(ConnectSnafu)
+    }
+
+    async fn connect_backoff(&self) -> UnixMessageSocket {
+        let mut backoff = UnixConnector::fresh_backoff();
+        loop {
+            match self.connect().await {
+                Ok(socket) => {
+                    emit!(UnixSocketConnectionEstablished { path: &self.path });
+                    return socket;
+                }
+                Err(error) => {
+                    emit!(UnixSocketConnectionError {
+                        error,
+                        path: &self.path
+                    });
+                    sleep(backoff.next().unwrap()).await;
+                }
+            }
+        }
+    }
+
+    async fn healthcheck(&self) -> crate::Result<()> {
+        self.connect().await.map(|_| ()).map_err(Into::into)
+    }
+}
+
+// Tokio doesn't support seqpacket sockets, so the connected socket is registered with the reactor
+// directly.
+fn connect_seqpacket(path: &std::path::Path) -> io::Result<Socket> {
+    let socket = Socket::new(Domain::UNIX, Type::SEQPACKET, None)?;
+    socket.connect(&SockAddr::unix(path)?)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+enum UnixMessageSocket {
+    Datagram(UnixDatagram),
+    Seqpacket(AsyncFd<Socket>),
+}
+
+impl UnixMessageSocket {
+    async fn send(&self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Datagram(socket) => socket.send(buf).await,
+            Self::Seqpacket(socket) => loop {
+                let mut guard = socket.writable().await?;
+                match guard.try_io(|socket| socket.get_ref().send(buf)) {
+                    Ok(result) => break result,
+                    Err(_would_block) => continue,
+                }
+            },
+        }
+    }
+}
+
+struct UnixMessageSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    connector: UnixMessageConnector,
+    transformer: Transformer,
+    encoder: E,
+}
+
+#[async_trait]
+impl<E> StreamSink<Event> for UnixMessageSink<E>
+where
+    E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
+{
+    // Same as UdpSink, more details there.
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let mut input = input.peekable();
+
+        let mut encoder = self.encoder.clone();
+        while Pin::new(&mut input).peek().await.is_some() {
+            let socket = self.connector.connect_backoff().await;
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            while let Some(mut event) = input.next().await {
+                let byte_size = event.size_of();
+
+                self.transformer.transform(&mut event);
+
+                let finalizers = event.take_finalizers();
+                let mut bytes = BytesMut::new();
+                if encoder.encode(event, &mut bytes).is_err() {
+                    continue;
+                }
+
+                match socket.send(&bytes).await {
+                    Ok(_) => {
+                        emit!(SocketEventsSent {
+                            mode: SocketMode::Unix,
+                            count: 1,
+                            byte_size,
+                        });
+
+                        emit!(BytesSent {
+                            byte_size: bytes.len(),
+                            protocol: "unix",
+                        });
+                    }
+                    Err(error) => {
+                        emit!(UnixSocketError {
+                            error: &error,
+                            path: &self.connector.path
+                        });
+                        break;
+                    }
+                };
+
+                drop(finalizers); // Ensure we don't acknowledge until after the send
+            }
+        }
+
+        Ok(())
+    }
+}
+
 struct UnixSink<E>
 where
     E: Encoder<Event, Error = codecs::encoding::Error> + Clone + Send + Sync,
@@ -251,4 +425,64 @@ mod tests {
         // Receive the data sent by the Sink to the receiver
         assert_eq!(input_lines, receiver.await);
     }
+
+    #[tokio::test]
+    async fn unix_datagram_sink() {
+        let out_path = temp_uds_path("unix_datagram_test");
+        let receiver = UnixDatagram::bind(&out_path).unwrap();
+
+        let (sink, _healthcheck) = UnixSinkConfig::new(out_path)
+            .build_message_based(
+                UnixMessageSocketType::Datagram,
+                Default::default(),
+                Encoder::<()>::new(TextSerializer::new().into()),
+            )
+            .unwrap();
+
+        let (input_lines, events) = random_lines_with_stream(100, 10, None);
+        sink.run(events).await.unwrap();
+
+        // Each event is sent as its own datagram, without framing.
+        let mut buf = [0; 256];
+        for line in input_lines {
+            let size = receiver.recv(&mut buf).await.unwrap();
+            assert_eq!(&buf[..size], line.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn unix_seqpacket_sink() {
+        use std::io::Read;
+
+        let out_path = temp_uds_path("unix_seqpacket_test");
+        let listener = Socket::new(Domain::UNIX, Type::SEQPACKET, None).unwrap();
+        listener.bind(&SockAddr::unix(&out_path).unwrap()).unwrap();
+        listener.listen(1).unwrap();
+
+        let receiver = std::thread::spawn(move || {
+            let (connection, _) = listener.accept().unwrap();
+            let mut packets = Vec::new();
+            let mut buf = [0; 256];
+            loop {
+                let size = (&connection).read(&mut buf).unwrap();
+                if size == 0 {
+                    break packets;
+                }
+                packets.push(String::from_utf8(buf[..size].to_vec()).unwrap());
+            }
+        });
+
+        let (sink, _healthcheck) = UnixSinkConfig::new(out_path)
+            .build_message_based(
+                UnixMessageSocketType::Seqpacket,
+                Default::default(),
+                Encoder::<()>::new(TextSerializer::new().into()),
+            )
+            .unwrap();
+
+        let (input_lines, events) = random_lines_with_stream(100, 10, None);
+        sink.run(events).await.unwrap();
+
+        assert_eq!(input_lines, receiver.join().unwrap());
+    }
 }
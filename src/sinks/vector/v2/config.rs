@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
@@ -32,11 +34,17 @@ pub struct VectorConfig {
     address: String,
     #[serde(default)]
     compression: bool,
+    /// Identifies this sink to the downstream `vector` source, which applies backpressure and
+    /// reports metrics separately for each stream. Defaults to the component ID of this sink.
+    #[serde(default)]
+    stream_id: Option<String>,
     #[serde(default)]
     pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
     #[serde(default)]
     pub request: TowerRequestConfig,
     #[serde(default)]
+    http2: Http2ClientConfig,
+    #[serde(default)]
     tls: Option<TlsEnableableConfig>,
     #[serde(
         default,
@@ -46,6 +54,34 @@ pub struct VectorConfig {
     pub(in crate::sinks::vector) acknowledgements: AcknowledgementsConfig,
 }
 
+/// HTTP/2 keepalive settings for the connection to the downstream `vector` source.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Http2ClientConfig {
+    /// Interval, in seconds, at which HTTP/2 PING frames are sent to keep the connection alive.
+    /// Keepalive pings are disabled when not set.
+    #[serde(default)]
+    keepalive_interval_secs: Option<u64>,
+    /// Time, in seconds, to wait for a keepalive ping to be acknowledged before closing the
+    /// connection.
+    #[serde(default)]
+    keepalive_timeout_secs: Option<u64>,
+    /// Whether to send keepalive pings while there are no open streams on the connection.
+    #[serde(default)]
+    keepalive_while_idle: bool,
+}
+
+impl Http2ClientConfig {
+    fn configure(&self, builder: &mut hyper::client::Builder) {
+        builder
+            .http2_keep_alive_interval(self.keepalive_interval_secs.map(Duration::from_secs))
+            .http2_keep_alive_while_idle(self.keepalive_while_idle);
+        if let Some(timeout) = self.keepalive_timeout_secs {
+            builder.http2_keep_alive_timeout(Duration::from_secs(timeout));
+        }
+    }
+}
+
 impl GenerateConfig for VectorConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(default_config("127.0.0.1:6000")).unwrap()
@@ -56,8 +92,10 @@ fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
         address: address.to_owned(),
         compression: false,
+        stream_id: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
+        http2: Http2ClientConfig::default(),
         tls: None,
         acknowledgements: Default::default(),
     }
@@ -71,7 +109,7 @@ impl VectorConfig {
         let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
         let uri = with_default_scheme(&self.address, tls.is_tls())?;
 
-        let client = new_client(&tls, cx.proxy())?;
+        let client = new_client(&tls, cx.proxy(), &self.http2)?;
 
         let healthcheck_uri = cx
             .healthcheck
@@ -79,9 +117,15 @@ impl VectorConfig {
             .clone()
             .map(|uri| uri.uri)
             .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri, false);
+        let stream_id = self
+            .stream_id
+            .clone()
+            .unwrap_or_else(|| cx.key.id().to_string());
+        let healthcheck_client =
+            VectorService::new(client.clone(), healthcheck_uri, false, stream_id.clone());
         let healthcheck = healthcheck(healthcheck_client, cx.healthcheck);
-        let service = VectorService::new(client, uri, self.compression);
+        let service = VectorService::new(client, uri, self.compression, stream_id);
+        let format = service.format.clone();
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
 
@@ -92,6 +136,7 @@ impl VectorConfig {
         let sink = VectorSink {
             batch_settings,
             service,
+            format,
         };
 
         Ok((
@@ -160,6 +205,7 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
 fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
+    http2: &Http2ClientConfig,
 ) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
     let mut http = HttpConnector::new();
     http.enforce_http(false);
@@ -179,7 +225,11 @@ fn new_client(
     let mut proxy = ProxyConnector::new(https).unwrap();
     proxy_config.configure(&mut proxy)?;
 
-    Ok(hyper::Client::builder().http2_only(true).build(proxy))
+    let mut builder = hyper::Client::builder();
+    builder.http2_only(true);
+    http2.configure(&mut builder);
+
+    Ok(builder.build(proxy))
 }
 
 #[derive(Debug, Clone)]
@@ -12,6 +12,9 @@ pub enum VectorSinkError {
     #[snafu(display("Request failed: {}", source))]
     Request { source: tonic::Status },
 
+    #[snafu(display("Vector source doesn't support the v2 event encoding"))]
+    FormatUnsupported,
+
     #[snafu(display("Vector source unhealthy"))]
     Health,
 
@@ -22,10 +25,14 @@ pub enum VectorSinkError {
 #[cfg(test)]
 mod tests {
     use bytes::{BufMut, Bytes, BytesMut};
-    use futures::{channel::mpsc, StreamExt};
+    use futures::{channel::mpsc, FutureExt, SinkExt, StreamExt};
     use http::request::Parts;
-    use hyper::Method;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Method, Server,
+    };
     use prost::Message;
+    use stream_cancel::Tripwire;
     use vector_core::event::{BatchNotifier, BatchStatus};
 
     use super::*;
@@ -80,7 +87,7 @@ mod tests {
 
         let output_lines = get_received(rx, |parts| {
             assert_eq!(Method::POST, parts.method);
-            assert_eq!("/vector.Vector/PushEvents", parts.uri.path());
+            assert_eq!("/vector.Vector/PushEventsV2", parts.uri.path());
             assert_eq!(
                 "application/grpc",
                 parts.headers.get("content-type").unwrap().to_str().unwrap()
@@ -123,6 +130,69 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[tokio::test]
+    async fn falls_back_to_v1_for_older_sources() {
+        let num_lines = 10;
+
+        let in_addr = next_addr();
+
+        let config = format!(r#"address = "http://{}/""#, in_addr);
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let cx = SinkContext::new_test();
+
+        let (sink, _) = config.build(cx).await.unwrap();
+
+        // A source predating `PushEventsV2`, answering it as unimplemented.
+        let (tx, rx) = mpsc::channel(100);
+        let service = make_service_fn(move |_| {
+            let tx = tx.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |req: hyper::Request<Body>| {
+                    let mut tx = tx.clone();
+                    async move {
+                        let (parts, body) = req.into_parts();
+                        let response =
+                            hyper::Response::builder().header("content-type", "application/grpc");
+                        let response = if parts.uri.path() == "/vector.Vector/PushEvents" {
+                            let body = hyper::body::to_bytes(body).await.unwrap();
+                            tx.send((parts, body)).await.unwrap();
+                            response
+                                .header("grpc-status", "0") // OK
+                                .body(Body::from(encode_body(proto::PushEventsResponse {})))
+                        } else {
+                            response
+                                .header("grpc-status", "12") // unimplemented
+                                .body(Body::empty())
+                        };
+                        Ok::<_, hyper::Error>(response.unwrap())
+                    }
+                }))
+            }
+        });
+        let (trigger, tripwire) = Tripwire::new();
+        let server = Server::bind(&in_addr)
+            .serve(service)
+            .with_graceful_shutdown(tripwire.then(crate::shutdown::tripwire_handler));
+
+        tokio::spawn(server);
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input_lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+
+        sink.run(events).await.expect("Running sink failed");
+        drop(trigger);
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        let output_lines = get_received(rx, |parts| {
+            assert_eq!("/vector.Vector/PushEvents", parts.uri.path());
+        })
+        .await;
+
+        assert_eq!(input_lines, output_lines);
+    }
+
     #[test]
     fn test_with_default_scheme() {
         assert_eq!(
@@ -140,20 +210,27 @@ mod tests {
         assert_parts: impl Fn(Parts),
     ) -> Vec<String> {
         rx.map(|(parts, body)| {
-            assert_parts(parts);
-
             let proto_body = body.slice(GRPC_HEADER_SIZE..);
 
-            let req = proto::PushEventsRequest::decode(proto_body).unwrap();
-
-            let mut events = Vec::with_capacity(req.events.len());
-            for event in req.events {
-                let event: Event = event.into();
-                let string = event.as_log().get("message").unwrap().to_string_lossy();
-                events.push(string)
-            }
+            let events: Vec<Event> = if parts.uri.path() == "/vector.Vector/PushEventsV2" {
+                let req = proto::PushEventsV2Request::decode(proto_body).unwrap();
+                req.events
+                    .into_iter()
+                    .map(|event| Event::try_from(event).unwrap())
+                    .collect()
+            } else {
+                let req = proto::PushEventsRequest::decode(proto_body).unwrap();
+                req.events
+                    .into_iter()
+                    .map(|event| Event::try_from(event).unwrap())
+                    .collect()
+            };
+            assert_parts(parts);
 
             events
+                .into_iter()
+                .map(|event| event.as_log().get("message").unwrap().to_string_lossy())
+                .collect::<Vec<_>>()
         })
         .collect::<Vec<_>>()
         .await
@@ -1,19 +1,25 @@
-use std::task::{Context, Poll};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
-use futures::{future::BoxFuture, TryFutureExt};
+use futures::future::BoxFuture;
 use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use prost::Message;
-use proto_event::EventWrapper;
-use tonic::{body::BoxBody, IntoRequest};
+use proto_event::{v2::EventWrapper as EventWrapperV2, EventWrapper};
+use tonic::{body::BoxBody, Code, IntoRequest};
 use vector_core::{
     event::proto as proto_event, internal_event::EventsSent, stream::DriverResponse,
 };
 
 use crate::{
-    event::{EventFinalizers, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     internal_events::EndpointBytesSent,
     proto::vector as proto_vector,
     sinks::{util::uri, vector::v2::VectorSinkError},
@@ -25,6 +31,34 @@ pub struct VectorService {
     pub client: proto_vector::Client<HyperSvc>,
     pub protocol: String,
     pub endpoint: String,
+    pub stream_id: String,
+    pub format: FormatSupport,
+}
+
+/// Whether the downstream source accepts events in version 2 of the native encoding, shared by
+/// the sink encoding the events and the service sending them.
+///
+/// This is assumed until the source answers `PushEventsV2` as unimplemented, as sources that
+/// predate it do, after which events are encoded in version 1 and sent with `PushEvents`.
+#[derive(Clone, Debug)]
+pub struct FormatSupport(Arc<AtomicBool>);
+
+impl FormatSupport {
+    fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(true)))
+    }
+
+    pub fn supports_v2(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn fall_back_to_v1(&self) {
+        if self.0.swap(false, Ordering::Relaxed) {
+            info!(
+                message = "Downstream source doesn't support the v2 event encoding, falling back to v1."
+            );
+        }
+    }
 }
 
 pub struct VectorResponse {
@@ -49,6 +83,7 @@ impl DriverResponse for VectorResponse {
 #[derive(Clone, Default)]
 pub struct VectorRequest {
     pub events: Vec<EventWrapper>,
+    pub events_v2: Vec<EventWrapperV2>,
     pub finalizers: EventFinalizers,
     pub events_byte_size: usize,
 }
@@ -64,6 +99,7 @@ impl VectorService {
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
         uri: Uri,
         compression: bool,
+        stream_id: String,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
         let mut proto_client = proto_vector::Client::new(HyperSvc {
@@ -78,6 +114,8 @@ impl VectorService {
             client: proto_client,
             protocol,
             endpoint,
+            stream_id,
+            format: FormatSupport::new(),
         }
     }
 }
@@ -98,30 +136,61 @@ impl tower::Service<VectorRequest> for VectorService {
 
     fn call(&mut self, list: VectorRequest) -> Self::Future {
         let mut service = self.clone();
-        let events_count = list.events.len();
+        let events_count = list.events.len() + list.events_v2.len();
         let events_byte_size = list.events_byte_size;
+        let stream_id = service.stream_id.clone();
 
-        let request = proto_vector::PushEventsRequest {
-            events: list.events,
-        };
-        let byte_size = request.encoded_len();
         let future = async move {
-            service
-                .client
-                .push_events(request.into_request())
-                .map_ok(|_response| {
-                    emit!(EndpointBytesSent {
-                        byte_size,
-                        protocol: &service.protocol,
-                        endpoint: &service.endpoint,
-                    });
-                    VectorResponse {
-                        events_count,
-                        events_byte_size,
-                    }
-                })
-                .map_err(|source| VectorSinkError::Request { source }.into())
-                .await
+            let byte_size = if service.format.supports_v2() && list.events.is_empty() {
+                let request = proto_vector::PushEventsV2Request {
+                    events: list.events_v2,
+                    stream_id,
+                };
+                let byte_size = request.encoded_len();
+                service
+                    .client
+                    .push_events_v2(request.into_request())
+                    .await
+                    .map_err(|source| {
+                        if source.code() == Code::Unimplemented {
+                            // The request is retried, and then sent in version 1.
+                            service.format.fall_back_to_v1();
+                            VectorSinkError::FormatUnsupported
+                        } else {
+                            VectorSinkError::Request { source }
+                        }
+                    })?;
+                byte_size
+            } else {
+                // Events encoded in version 2 before falling back to version 1 are re-encoded.
+                let events = list
+                    .events
+                    .into_iter()
+                    .chain(
+                        list.events_v2
+                            .into_iter()
+                            .map(|wrapper| EventWrapper::from(Event::from(wrapper))),
+                    )
+                    .collect();
+                let request = proto_vector::PushEventsRequest { events, stream_id };
+                let byte_size = request.encoded_len();
+                service
+                    .client
+                    .push_events(request.into_request())
+                    .await
+                    .map_err(|source| VectorSinkError::Request { source })?;
+                byte_size
+            };
+
+            emit!(EndpointBytesSent {
+                byte_size,
+                protocol: &service.protocol,
+                endpoint: &service.endpoint,
+            });
+            Ok::<_, Error>(VectorResponse {
+                events_count,
+                events_byte_size,
+            })
         };
 
         Box::pin(future)
@@ -10,22 +10,49 @@ use vector_core::{
 };
 
 use crate::{
-    event::{proto::EventWrapper, Event, EventFinalizers, Finalizable},
+    event::{
+        proto::{v2::EventWrapper as EventWrapperV2, EventWrapper},
+        Event, EventFinalizers, Finalizable,
+    },
     sinks::{
         util::{SinkBuilderExt, StreamSink},
-        vector::v2::service::VectorRequest,
+        vector::v2::service::{FormatSupport, VectorRequest},
     },
 };
 
+enum EncodedEvent {
+    V1(EventWrapper),
+    V2(EventWrapperV2),
+}
+
+impl EncodedEvent {
+    fn new(event: Event, format: &FormatSupport) -> Self {
+        if format.supports_v2() {
+            Self::V2(event.into())
+        } else {
+            Self::V1(event.into())
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Self::V1(wrapper) => wrapper.encoded_len(),
+            Self::V2(wrapper) => wrapper.encoded_len(),
+        }
+    }
+}
+
 struct EventData {
     byte_size: usize,
     finalizers: EventFinalizers,
-    wrapper: EventWrapper,
+    wrapper: EncodedEvent,
 }
 
 pub struct VectorSink<S> {
     pub batch_settings: BatcherSettings,
     pub service: S,
+    /// Whether to encode events in version 2, as long as the downstream source supports it.
+    pub format: FormatSupport,
 }
 
 impl<S> VectorSink<S>
@@ -36,18 +63,22 @@ where
     S::Error: fmt::Debug + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let format = self.format;
         input
             .map(|mut event| EventData {
                 byte_size: event.size_of(),
                 finalizers: event.take_finalizers(),
-                wrapper: EventWrapper::from(event),
+                wrapper: EncodedEvent::new(event, &format),
             })
             .batched(self.batch_settings.into_reducer_config(
                 |data: &EventData| data.wrapper.encoded_len(),
                 |req: &mut VectorRequest, item: EventData| {
                     req.events_byte_size += item.byte_size;
                     req.finalizers.merge(item.finalizers);
-                    req.events.push(item.wrapper);
+                    match item.wrapper {
+                        EncodedEvent::V1(wrapper) => req.events.push(wrapper),
+                        EncodedEvent::V2(wrapper) => req.events_v2.push(wrapper),
+                    }
                 },
             ))
             .into_driver(self.service)
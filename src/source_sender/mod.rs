@@ -11,6 +11,8 @@ use vector_core::{
     ByteSizeOf,
 };
 
+use crate::memory_budget;
+
 mod errors;
 
 pub use errors::{ClosedError, StreamSendError};
@@ -187,6 +189,16 @@ impl SourceSender {
             .await
     }
 
+    /// Returns `true` if the given output, or the default output if `None`, has no capacity left,
+    /// so sending to it would wait for downstream components to catch up.
+    pub fn is_full(&self, output: Option<&str>) -> bool {
+        match output {
+            Some(name) => self.named_inners.get(name),
+            None => self.inner.as_ref(),
+        }
+        .map_or(false, |inner| inner.inner.available_capacity() == 0)
+    }
+
     pub async fn send_batch_named<I, E>(&mut self, name: &str, events: I) -> Result<(), ClosedError>
     where
         E: Into<Event> + ByteSizeOf,
@@ -215,6 +227,7 @@ impl Inner {
     async fn send(&mut self, events: EventArray) -> Result<(), ClosedError> {
         let byte_size = events.size_of();
         let count = events.len();
+        memory_budget::wait_for_room().await;
         self.inner.send(events).await.map_err(|_| ClosedError)?;
         emit!(EventsSent {
             count,
@@ -252,6 +265,7 @@ impl Inner {
         for events in array::events_into_arrays(events, Some(CHUNK_SIZE)) {
             let this_count = events.len();
             let this_size = events.size_of();
+            memory_budget::wait_for_room().await;
             match self.inner.send(events).await {
                 Ok(()) => {
                     count += this_count;
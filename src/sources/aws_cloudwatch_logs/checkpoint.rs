@@ -0,0 +1,122 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::checkpoint::CheckpointStore;
+
+const CHECKPOINT_NAME: &str = "checkpoints.json";
+
+/// The position reached in each log group, persisted between restarts.
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub(super) struct Checkpoints {
+    log_groups: HashMap<String, LogGroupCheckpoint>,
+}
+
+/// The position reached in a log group.
+///
+/// Many events can share a millisecond and `FilterLogEvents` only filters by time, so the IDs of
+/// the events at the latest timestamp are kept to skip them when the next poll starts there.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub(super) struct LogGroupCheckpoint {
+    /// The timestamp of the newest event read, in milliseconds since the Unix epoch.
+    pub(super) timestamp: i64,
+    /// The IDs of the events read at `timestamp`.
+    pub(super) event_ids: HashSet<String>,
+}
+
+impl LogGroupCheckpoint {
+    /// Whether the event was already read, either because it's older than the checkpoint or it's
+    /// one of the events read at the checkpoint.
+    pub(super) fn contains(&self, timestamp: i64, event_id: &str) -> bool {
+        timestamp < self.timestamp
+            || (timestamp == self.timestamp && self.event_ids.contains(event_id))
+    }
+
+    /// Moves the checkpoint past an event.
+    pub(super) fn advance(&mut self, timestamp: i64, event_id: &str) {
+        if timestamp > self.timestamp {
+            self.timestamp = timestamp;
+            self.event_ids.clear();
+        }
+        if timestamp == self.timestamp {
+            self.event_ids.insert(event_id.to_owned());
+        }
+    }
+}
+
+impl Checkpoints {
+    /// Reads the checkpoints, or returns empty checkpoints if they were never saved.
+    pub(super) async fn load(store: &dyn CheckpointStore) -> crate::Result<Self> {
+        match store.load(CHECKPOINT_NAME).await? {
+            Some(data) => Ok(serde_json::from_slice(&data)?),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub(super) async fn save(&self, store: &dyn CheckpointStore) -> crate::Result<()> {
+        let data = serde_json::to_vec(self)?;
+        store.save(CHECKPOINT_NAME, &data).await
+    }
+
+    pub(super) fn get(&self, log_group: &str) -> Option<&LogGroupCheckpoint> {
+        self.log_groups.get(log_group)
+    }
+
+    pub(super) fn set(&mut self, log_group: &str, checkpoint: LogGroupCheckpoint) {
+        self.log_groups.insert(log_group.to_owned(), checkpoint);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::checkpoint::FileCheckpointStore;
+
+    #[test]
+    fn skips_events_read_at_the_checkpoint() {
+        let mut checkpoint = LogGroupCheckpoint::default();
+        checkpoint.advance(1000, "a");
+        checkpoint.advance(1000, "b");
+
+        assert!(checkpoint.contains(999, "z"));
+        assert!(checkpoint.contains(1000, "a"));
+        assert!(checkpoint.contains(1000, "b"));
+        assert!(!checkpoint.contains(1000, "c"));
+        assert!(!checkpoint.contains(1001, "a"));
+
+        checkpoint.advance(1001, "c");
+        assert_eq!(checkpoint.timestamp, 1001);
+        assert_eq!(checkpoint.event_ids, HashSet::from(["c".to_owned()]));
+
+        // Older events don't move the checkpoint back.
+        checkpoint.advance(1000, "d");
+        assert_eq!(checkpoint.timestamp, 1001);
+        assert!(!checkpoint.event_ids.contains("d"));
+    }
+
+    #[tokio::test]
+    async fn saves_and_loads_checkpoints() {
+        let dir = tempdir().unwrap();
+        let store = FileCheckpointStore::new(dir.path().to_owned());
+
+        assert_eq!(
+            Checkpoints::load(&store).await.unwrap(),
+            Checkpoints::default()
+        );
+
+        let mut checkpoints = Checkpoints::default();
+        let mut checkpoint = LogGroupCheckpoint::default();
+        checkpoint.advance(
+            1660000000000,
+            "37134449278954093620556612935624785467652393618829443072",
+        );
+        checkpoints.set("/aws/lambda/example", checkpoint.clone());
+        checkpoints.save(&store).await.unwrap();
+
+        let loaded = Checkpoints::load(&store).await.unwrap();
+        assert_eq!(loaded.get("/aws/lambda/example"), Some(&checkpoint));
+        assert_eq!(loaded.get("/aws/lambda/other"), None);
+    }
+}
@@ -0,0 +1,411 @@
+use std::{path::PathBuf, time::Duration};
+
+use aws_sdk_cloudwatchlogs::{model::FilteredLogEvent, Client as CloudwatchLogsClient};
+use chrono::{TimeZone, Utc};
+use codecs::decoding::{DeserializerConfig, FramingConfig};
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio_stream::wrappers::IntervalStream;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    checkpoint::{self as checkpoint_store, SharedCheckpointStore},
+    codecs::{Decoder, DecodingConfig},
+    common::cloudwatch_logs::CloudwatchLogsClientBuilder,
+    config::{GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    event::Event,
+    internal_events::{
+        AwsCloudwatchLogsCheckpointError, AwsCloudwatchLogsFilterError, EndpointBytesReceived,
+        StreamClosedError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sources::util,
+    tls::TlsConfig,
+    SourceSender,
+};
+
+mod checkpoint;
+
+use checkpoint::{Checkpoints, LogGroupCheckpoint};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one log group must be specified"))]
+    NoLogGroups,
+    #[snafu(display("poll_interval_secs must be greater than zero"))]
+    ZeroPollInterval,
+    #[snafu(display("Could not read checkpoints: {}", source))]
+    ReadCheckpoint { source: crate::Error },
+}
+
+/// Configuration for the `aws_cloudwatch_logs` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsCloudwatchLogsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The names of the log groups to read events from.
+    pub log_groups: Vec<String>,
+
+    /// Only read events from log streams whose name starts with this prefix.
+    #[serde(default)]
+    pub log_stream_prefix: Option<String>,
+
+    /// A [filter pattern][filter_pattern] events must match to be read.
+    ///
+    /// By default, all events are read.
+    ///
+    /// [filter_pattern]: https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/FilterAndPatternSyntax.html
+    #[serde(default)]
+    pub filter_pattern: Option<String>,
+
+    /// How often to poll the log groups for new events, in seconds. Must be greater than zero.
+    #[serde(default = "default_poll_interval_secs")]
+    #[derivative(Default(value = "default_poll_interval_secs()"))]
+    pub poll_interval_secs: u64,
+
+    /// Whether to read the events already present in a log group when no checkpoint exists for it.
+    ///
+    /// By default, only events newer than the time Vector first starts reading a log group are read.
+    #[serde(default)]
+    pub read_existing_events: bool,
+
+    /// The directory used to persist the position reached in each log group.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    15
+}
+
+inventory::submit! {
+    SourceDescription::new::<AwsCloudwatchLogsConfig>("aws_cloudwatch_logs")
+}
+
+impl GenerateConfig for AwsCloudwatchLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            region = "us-east-1"
+            log_groups = ["/aws/lambda/example"]
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_cloudwatch_logs")]
+impl SourceConfig for AwsCloudwatchLogsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.log_groups.is_empty() {
+            return Err(BuildError::NoLogGroups.into());
+        }
+        if self.poll_interval_secs == 0 {
+            return Err(BuildError::ZeroPollInterval.into());
+        }
+
+        let checkpoint_store =
+            checkpoint_store::build_store(&cx.globals, self.data_dir.as_ref(), &cx.key).await?;
+        let checkpoints = Checkpoints::load(checkpoint_store.as_ref())
+            .await
+            .map_err(|source| BuildError::ReadCheckpoint { source })?;
+
+        let client = create_client::<CloudwatchLogsClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+        let decoder = DecodingConfig::new(
+            self.framing.clone(),
+            self.decoding.clone(),
+            LogNamespace::Legacy,
+        )
+        .build();
+
+        Ok(Box::pin(
+            CloudwatchLogsSource {
+                client,
+                log_groups: self.log_groups.clone(),
+                log_stream_prefix: self.log_stream_prefix.clone(),
+                filter_pattern: self.filter_pattern.clone(),
+                read_existing_events: self.read_existing_events,
+                decoder,
+                checkpoint_store,
+                checkpoints,
+            }
+            .run(
+                Duration::from_secs(self.poll_interval_secs),
+                cx.out,
+                cx.shutdown,
+            ),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(self.decoding.output_type())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_cloudwatch_logs"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct CloudwatchLogsSource {
+    client: CloudwatchLogsClient,
+    log_groups: Vec<String>,
+    log_stream_prefix: Option<String>,
+    filter_pattern: Option<String>,
+    read_existing_events: bool,
+    decoder: Decoder,
+    checkpoint_store: SharedCheckpointStore,
+    checkpoints: Checkpoints,
+}
+
+impl CloudwatchLogsSource {
+    async fn run(
+        mut self,
+        poll_interval: Duration,
+        mut out: SourceSender,
+        shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        // Log groups without a checkpoint are read from now on, unless existing events are read.
+        let now = Utc::now().timestamp_millis();
+        for log_group in &self.log_groups {
+            if !self.read_existing_events && self.checkpoints.get(log_group).is_none() {
+                self.checkpoints.set(
+                    log_group,
+                    LogGroupCheckpoint {
+                        timestamp: now,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        let mut intervals =
+            IntervalStream::new(tokio::time::interval(poll_interval)).take_until(shutdown.clone());
+        while intervals.next().await.is_some() {
+            for log_group in self.log_groups.clone() {
+                tokio::select! {
+                    result = self.poll_log_group(&log_group, &mut out) => result?,
+                    _ = shutdown.clone() => return Ok(()),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the events added to a log group since the last poll. The checkpoint only moves once
+    /// all pages have been read, so events are read again if a request fails midway.
+    async fn poll_log_group(&mut self, log_group: &str, out: &mut SourceSender) -> Result<(), ()> {
+        let checkpoint = self.checkpoints.get(log_group).cloned().unwrap_or_default();
+        let mut next_checkpoint = checkpoint.clone();
+
+        let mut next_token = None;
+        loop {
+            let result = self
+                .client
+                .filter_log_events()
+                .log_group_name(log_group)
+                .set_log_stream_name_prefix(self.log_stream_prefix.clone())
+                .set_filter_pattern(self.filter_pattern.clone())
+                .start_time(checkpoint.timestamp)
+                .set_next_token(next_token)
+                .send()
+                .await;
+            let output = match result {
+                Ok(output) => output,
+                Err(error) => {
+                    emit!(AwsCloudwatchLogsFilterError {
+                        log_group,
+                        error: &error,
+                    });
+                    return Ok(());
+                }
+            };
+
+            let mut byte_size = 0;
+            let mut events = Vec::new();
+            for log_event in output.events.unwrap_or_default() {
+                let (timestamp, event_id) = match (log_event.timestamp, log_event.event_id()) {
+                    (Some(timestamp), Some(event_id)) => (timestamp, event_id.to_owned()),
+                    _ => continue,
+                };
+                if checkpoint.contains(timestamp, &event_id) {
+                    continue;
+                }
+                next_checkpoint.advance(timestamp, &event_id);
+
+                byte_size += log_event.message().map_or(0, str::len);
+                events.extend(decode_event(self.decoder.clone(), log_group, log_event));
+            }
+
+            emit!(EndpointBytesReceived {
+                byte_size,
+                protocol: "http",
+                endpoint: log_group,
+            });
+
+            if !events.is_empty() {
+                let count = events.len();
+                if let Err(error) = out.send_batch(events).await {
+                    emit!(StreamClosedError { error, count });
+                    return Err(());
+                }
+            }
+
+            next_token = output.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        if next_checkpoint != checkpoint {
+            self.checkpoints.set(log_group, next_checkpoint);
+            if let Err(error) = self.checkpoints.save(self.checkpoint_store.as_ref()).await {
+                emit!(AwsCloudwatchLogsCheckpointError { error });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decodes the message of a log event, and adds the log group, stream and event ID to the events.
+fn decode_event(decoder: Decoder, log_group: &str, log_event: FilteredLogEvent) -> Vec<Event> {
+    let timestamp = log_event
+        .timestamp
+        .and_then(|ms| Utc.timestamp_millis_opt(ms).single());
+    let message = log_event.message.unwrap_or_default();
+    util::decode_message(
+        decoder,
+        "aws_cloudwatch_logs",
+        message.as_bytes(),
+        timestamp,
+        &None,
+    )
+    .map(|mut event| {
+        if let Event::Log(ref mut log) = event {
+            log.insert("log_group", log_group.to_owned());
+            if let Some(log_stream) = &log_event.log_stream_name {
+                log.insert("log_stream", log_stream.clone());
+            }
+            if let Some(event_id) = &log_event.event_id {
+                log.insert("event_id", event_id.clone());
+            }
+        }
+        event
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::log_schema;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsCloudwatchLogsConfig>();
+    }
+
+    #[test]
+    fn parses_config() {
+        let config: AwsCloudwatchLogsConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            log_groups = ["/aws/lambda/example"]
+            log_stream_prefix = "2022/08/01"
+            filter_pattern = "ERROR"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.log_groups, vec!["/aws/lambda/example".to_owned()]);
+        assert_eq!(config.poll_interval_secs, 15);
+        assert!(!config.read_existing_events);
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_poll_interval() {
+        let config: AwsCloudwatchLogsConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            log_groups = ["/aws/lambda/example"]
+            poll_interval_secs = 0
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+
+        let error = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "poll_interval_secs must be greater than zero");
+    }
+
+    #[test]
+    fn decodes_log_events() {
+        let log_event = FilteredLogEvent::builder()
+            .log_stream_name("2022/08/01/[$LATEST]abcdef")
+            .timestamp(1659348000000)
+            .message("START RequestId: 1234")
+            .event_id("36975485962549396425464812376834817367251929347924434944")
+            .build();
+        let events = decode_event(Decoder::default(), "/aws/lambda/example", log_event);
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().message_key()],
+            "START RequestId: 1234".into()
+        );
+        assert_eq!(log["log_group"], "/aws/lambda/example".into());
+        assert_eq!(log["log_stream"], "2022/08/01/[$LATEST]abcdef".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp_millis(1659348000000).into()
+        );
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "aws_cloudwatch_logs".into()
+        );
+    }
+}
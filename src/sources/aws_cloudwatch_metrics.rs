@@ -0,0 +1,604 @@
+use std::{collections::BTreeMap, num::NonZeroU32, time::Duration};
+
+use aws_sdk_cloudwatch::{
+    model::{DimensionFilter, Metric as CloudwatchMetric, MetricDataQuery, MetricStat, ScanBy},
+    types::DateTime as AwsDateTime,
+    Client as CloudwatchClient,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
+use snafu::Snafu;
+use tokio_stream::wrappers::IntervalStream;
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    aws::{auth::AwsAuthentication, create_client, region::RegionOrEndpoint},
+    common::cloudwatch::CloudwatchMetricsClientBuilder,
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    event::metric::{Metric, MetricKind, MetricValue},
+    internal_events::{AwsCloudwatchMetricsRequestError, OldEventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    tls::TlsConfig,
+    SourceSender,
+};
+
+/// The maximum number of queries in a `GetMetricData` request.
+const MAX_QUERIES_PER_REQUEST: usize = 500;
+/// Dimension filters with this value match any value of the dimension.
+const ANY_DIMENSION_VALUE: &str = "*";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one metric query must be specified"))]
+    NoQueries,
+    #[snafu(display(
+        "period_secs must be 1, 5, 10, 30 or a non-zero multiple of 60 seconds, got {}",
+        period_secs
+    ))]
+    InvalidPeriod { period_secs: u32 },
+    #[snafu(display("Query for namespace {:?} has no statistics", namespace))]
+    NoStatistics { namespace: String },
+}
+
+/// Configuration for the `aws_cloudwatch_metrics` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AwsCloudwatchMetricsConfig {
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+
+    /// The metrics to collect.
+    pub metrics: Vec<CloudwatchMetricsQuery>,
+
+    /// The period, in seconds, datapoints are aggregated over, which is also the interval between
+    /// collections.
+    ///
+    /// Must be 1, 5, 10, 30 or a non-zero multiple of 60. Periods shorter than 60 seconds are only
+    /// available for high-resolution metrics.
+    #[serde(default = "default_period_secs")]
+    #[derivative(Default(value = "default_period_secs()"))]
+    pub period_secs: u32,
+
+    /// How far behind the current time datapoints are collected, in seconds.
+    ///
+    /// CloudWatch takes a while to aggregate the datapoints of a period, so collecting them too
+    /// early can return partial values.
+    #[serde(default = "default_delay_secs")]
+    #[derivative(Default(value = "default_delay_secs()"))]
+    pub delay_secs: u32,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// A set of CloudWatch metrics to collect.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CloudwatchMetricsQuery {
+    /// The CloudWatch namespace of the metrics, such as `AWS/EC2`.
+    pub namespace: String,
+
+    /// The names of the metrics to collect.
+    ///
+    /// By default, all metrics of the namespace are collected.
+    #[serde(default)]
+    pub metric_names: Vec<String>,
+
+    /// The dimensions the metrics must have, by name.
+    ///
+    /// A value of `*` matches any value of the dimension.
+    #[serde(default)]
+    pub dimensions: BTreeMap<String, String>,
+
+    /// The statistics to collect, such as `Average`, `Sum`, `Minimum`, `Maximum`, `SampleCount`
+    /// or percentiles like `p99`.
+    #[serde(default = "default_statistics")]
+    pub statistics: Vec<String>,
+}
+
+const fn default_period_secs() -> u32 {
+    300
+}
+
+const fn default_delay_secs() -> u32 {
+    300
+}
+
+fn default_statistics() -> Vec<String> {
+    vec!["Average".into()]
+}
+
+inventory::submit! {
+    SourceDescription::new::<AwsCloudwatchMetricsConfig>("aws_cloudwatch_metrics")
+}
+
+impl GenerateConfig for AwsCloudwatchMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            region = "us-east-1"
+
+            [[metrics]]
+            namespace = "AWS/EC2"
+            metric_names = ["CPUUtilization"]
+            dimensions = { InstanceId = "*" }
+            statistics = ["Average", "Maximum"]
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_cloudwatch_metrics")]
+impl SourceConfig for AwsCloudwatchMetricsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.metrics.is_empty() {
+            return Err(BuildError::NoQueries.into());
+        }
+        if self.period_secs == 0
+            || (!matches!(self.period_secs, 1 | 5 | 10 | 30) && self.period_secs % 60 != 0)
+        {
+            return Err(BuildError::InvalidPeriod {
+                period_secs: self.period_secs,
+            }
+            .into());
+        }
+        if let Some(query) = self
+            .metrics
+            .iter()
+            .find(|query| query.statistics.is_empty())
+        {
+            return Err(BuildError::NoStatistics {
+                namespace: query.namespace.clone(),
+            }
+            .into());
+        }
+
+        let client = create_client::<CloudwatchMetricsClientBuilder>(
+            &self.auth,
+            self.region.region(),
+            self.region.endpoint()?,
+            &cx.proxy,
+            &self.tls,
+            false,
+        )
+        .await?;
+
+        Ok(Box::pin(
+            CloudwatchMetricsSource {
+                client,
+                queries: self.metrics.clone(),
+                period_secs: self.period_secs,
+                delay_secs: self.delay_secs,
+                collected_until: None,
+            }
+            .run(cx.out, cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_cloudwatch_metrics"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// A metric statistic requested from `GetMetricData`.
+struct Stat<'a> {
+    metric: &'a CloudwatchMetric,
+    statistic: &'a str,
+}
+
+struct CloudwatchMetricsSource {
+    client: CloudwatchClient,
+    queries: Vec<CloudwatchMetricsQuery>,
+    period_secs: u32,
+    delay_secs: u32,
+    /// The end of the last period collected, in seconds since the Unix epoch.
+    collected_until: Option<i64>,
+}
+
+impl CloudwatchMetricsSource {
+    async fn run(mut self, mut out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let interval = Duration::from_secs(self.period_secs.into());
+        let mut intervals =
+            IntervalStream::new(tokio::time::interval(interval)).take_until(shutdown.clone());
+
+        while intervals.next().await.is_some() {
+            let metrics = tokio::select! {
+                metrics = self.collect(Utc::now()) => metrics,
+                _ = shutdown.clone() => break,
+            };
+            if metrics.is_empty() {
+                continue;
+            }
+
+            emit!(OldEventsReceived {
+                count: metrics.len(),
+                byte_size: metrics.size_of(),
+            });
+
+            let count = metrics.len();
+            if let Err(error) = out.send_batch(metrics).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects the datapoints of the periods that completed since the last collection.
+    async fn collect(&mut self, now: DateTime<Utc>) -> Vec<Metric> {
+        let (start, end) = collection_window(
+            now.timestamp(),
+            self.period_secs,
+            self.delay_secs,
+            self.collected_until,
+        );
+        if start >= end {
+            return Vec::new();
+        }
+
+        let mut cloudwatch_metrics = Vec::new();
+        for query in &self.queries {
+            match self.list_metrics(query).await {
+                Ok(listed) => cloudwatch_metrics.push((query, listed)),
+                Err(()) => return Vec::new(),
+            }
+        }
+
+        let stats = cloudwatch_metrics
+            .iter()
+            .flat_map(|(query, listed)| {
+                listed.iter().flat_map(|metric| {
+                    query
+                        .statistics
+                        .iter()
+                        .map(move |statistic| Stat { metric, statistic })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut metrics = Vec::new();
+        for chunk in stats.chunks(MAX_QUERIES_PER_REQUEST) {
+            match self.get_metric_data(chunk, start, end).await {
+                Ok(collected) => metrics.extend(collected),
+                Err(()) => return Vec::new(),
+            }
+        }
+
+        self.collected_until = Some(end);
+        metrics
+    }
+
+    /// Lists the metrics matching a query, which are the metrics that received datapoints in the
+    /// last two weeks.
+    async fn list_metrics(
+        &self,
+        query: &CloudwatchMetricsQuery,
+    ) -> Result<Vec<CloudwatchMetric>, ()> {
+        let dimensions = query
+            .dimensions
+            .iter()
+            .map(|(name, value)| {
+                DimensionFilter::builder()
+                    .name(name)
+                    .set_value((value != ANY_DIMENSION_VALUE).then(|| value.clone()))
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        let metric_names = if query.metric_names.is_empty() {
+            vec![None]
+        } else {
+            query.metric_names.iter().cloned().map(Some).collect()
+        };
+
+        let mut metrics = Vec::new();
+        for metric_name in metric_names {
+            let mut next_token = None;
+            loop {
+                let output = self
+                    .client
+                    .list_metrics()
+                    .namespace(&query.namespace)
+                    .set_metric_name(metric_name.clone())
+                    .set_dimensions(Some(dimensions.clone()))
+                    .set_next_token(next_token)
+                    .send()
+                    .await
+                    .map_err(|error| {
+                        emit!(AwsCloudwatchMetricsRequestError {
+                            operation: "ListMetrics",
+                            error: &error,
+                        })
+                    })?;
+                metrics.extend(output.metrics.unwrap_or_default());
+
+                next_token = output.next_token;
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+        Ok(metrics)
+    }
+
+    async fn get_metric_data(
+        &self,
+        stats: &[Stat<'_>],
+        start: i64,
+        end: i64,
+    ) -> Result<Vec<Metric>, ()> {
+        let queries = stats
+            .iter()
+            .enumerate()
+            .map(|(index, stat)| {
+                MetricDataQuery::builder()
+                    .id(query_id(index))
+                    .metric_stat(
+                        MetricStat::builder()
+                            .metric(stat.metric.clone())
+                            .period(self.period_secs as i32)
+                            .stat(stat.statistic)
+                            .build(),
+                    )
+                    .return_data(true)
+                    .build()
+            })
+            .collect::<Vec<_>>();
+
+        let mut metrics = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .client
+                .get_metric_data()
+                .set_metric_data_queries(Some(queries.clone()))
+                .start_time(AwsDateTime::from_secs(start))
+                .end_time(AwsDateTime::from_secs(end))
+                .scan_by(ScanBy::TimestampAscending)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(|error| {
+                    emit!(AwsCloudwatchMetricsRequestError {
+                        operation: "GetMetricData",
+                        error: &error,
+                    })
+                })?;
+
+            for result in output.metric_data_results.unwrap_or_default() {
+                let stat = match result.id().and_then(parse_query_id) {
+                    Some(index) if index < stats.len() => &stats[index],
+                    _ => continue,
+                };
+                let timestamps = result.timestamps.unwrap_or_default();
+                let values = result.values.unwrap_or_default();
+                metrics.extend(timestamps.iter().zip(values).filter_map(|(timestamp, value)| {
+                    // Datapoints whose timestamp can't be represented are skipped.
+                    let timestamp = Utc
+                        .timestamp_opt(timestamp.secs(), timestamp.subsec_nanos())
+                        .single()?;
+                    Some(to_metric(
+                        stat.metric,
+                        stat.statistic,
+                        self.period_secs,
+                        timestamp,
+                        value,
+                    ))
+                }));
+            }
+
+            next_token = output.next_token;
+            if next_token.is_none() {
+                break;
+            }
+        }
+        Ok(metrics)
+    }
+}
+
+/// Computes the window of complete periods to collect, in seconds since the Unix epoch. The
+/// window starts where the last collection ended, or spans a single period on the first one.
+fn collection_window(
+    now: i64,
+    period_secs: u32,
+    delay_secs: u32,
+    collected_until: Option<i64>,
+) -> (i64, i64) {
+    let period = i64::from(period_secs);
+    let end = (now - i64::from(delay_secs)).div_euclid(period) * period;
+    let start = collected_until.unwrap_or(end - period);
+    (start, end)
+}
+
+/// Query IDs must start with a lowercase letter.
+fn query_id(index: usize) -> String {
+    format!("q{}", index)
+}
+
+fn parse_query_id(id: &str) -> Option<usize> {
+    id.strip_prefix('q')?.parse().ok()
+}
+
+/// Converts a datapoint into a metric. Sums and sample counts are the increase over the period,
+/// while the other statistics are gauges.
+fn to_metric(
+    metric: &CloudwatchMetric,
+    statistic: &str,
+    period_secs: u32,
+    timestamp: DateTime<Utc>,
+    value: f64,
+) -> Metric {
+    let (kind, value) = match statistic {
+        "Sum" | "SampleCount" => (MetricKind::Incremental, MetricValue::Counter { value }),
+        _ => (MetricKind::Absolute, MetricValue::Gauge { value }),
+    };
+    let name = format!(
+        "{}_{}",
+        snake_case(metric.metric_name().unwrap_or_default()),
+        snake_case(statistic)
+    );
+    let tags = metric
+        .dimensions()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|dimension| Some((dimension.name()?.to_owned(), dimension.value()?.to_owned())))
+        .collect::<BTreeMap<_, _>>();
+
+    Metric::new(name, kind, value)
+        .with_namespace(metric.namespace().map(snake_case))
+        .with_tags((!tags.is_empty()).then(|| tags))
+        .with_timestamp(Some(timestamp))
+        .with_interval_ms(NonZeroU32::new(period_secs.saturating_mul(1000)))
+}
+
+/// Converts CloudWatch names such as `AWS/ApplicationELB` or `CPUUtilization` into snake case,
+/// keeping acronyms together.
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() {
+            let previous = index.checked_sub(1).map(|index| chars[index]);
+            let next = chars.get(index + 1);
+            let word_start = match previous {
+                Some(previous) if previous.is_ascii_lowercase() => true,
+                Some(previous) if previous.is_ascii_uppercase() => {
+                    next.map_or(false, char::is_ascii_lowercase)
+                }
+                _ => false,
+            };
+            if word_start && !snake.ends_with('_') {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            snake.push(*c);
+        } else if !snake.is_empty() && !snake.ends_with('_') {
+            snake.push('_');
+        }
+    }
+    snake.trim_end_matches('_').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use aws_sdk_cloudwatch::model::Dimension;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsCloudwatchMetricsConfig>();
+    }
+
+    #[test]
+    fn converts_names_to_snake_case() {
+        assert_eq!(snake_case("CPUUtilization"), "cpu_utilization");
+        assert_eq!(snake_case("NetworkIn"), "network_in");
+        assert_eq!(
+            snake_case("HTTPCode_Target_5XX_Count"),
+            "http_code_target_5xx_count"
+        );
+        assert_eq!(snake_case("AWS/ApplicationELB"), "aws_application_elb");
+        assert_eq!(snake_case("SampleCount"), "sample_count");
+        assert_eq!(snake_case("p99"), "p99");
+    }
+
+    #[test]
+    fn computes_collection_window() {
+        // 10:12:34 with a 5 minute delay collects the period ending at 10:05.
+        let now = Utc.ymd(2022, 8, 1).and_hms(10, 12, 34).timestamp();
+        let end = Utc.ymd(2022, 8, 1).and_hms(10, 5, 0).timestamp();
+
+        assert_eq!(collection_window(now, 300, 300, None), (end - 300, end));
+        assert_eq!(
+            collection_window(now, 300, 300, Some(end - 900)),
+            (end - 900, end)
+        );
+        assert_eq!(collection_window(now, 300, 300, Some(end)), (end, end));
+    }
+
+    #[test]
+    fn converts_datapoints() {
+        let metric = CloudwatchMetric::builder()
+            .namespace("AWS/EC2")
+            .metric_name("CPUUtilization")
+            .dimensions(
+                Dimension::builder()
+                    .name("InstanceId")
+                    .value("i-0123456789abcdef0")
+                    .build(),
+            )
+            .build();
+        let timestamp = Utc.ymd(2022, 8, 1).and_hms(10, 0, 0);
+
+        let gauge = to_metric(&metric, "Average", 300, timestamp, 42.5);
+        assert_eq!(gauge.namespace(), Some("aws_ec2"));
+        assert_eq!(gauge.name(), "cpu_utilization_average");
+        assert_eq!(gauge.kind(), MetricKind::Absolute);
+        assert_eq!(gauge.value(), &MetricValue::Gauge { value: 42.5 });
+        assert_eq!(gauge.timestamp(), Some(timestamp));
+        assert_eq!(gauge.interval_ms(), NonZeroU32::new(300_000));
+        assert_eq!(
+            gauge.tag_value("InstanceId"),
+            Some("i-0123456789abcdef0".into())
+        );
+
+        let counter = to_metric(&metric, "Sum", 60, timestamp, 3.0);
+        assert_eq!(counter.name(), "cpu_utilization_sum");
+        assert_eq!(counter.kind(), MetricKind::Incremental);
+        assert_eq!(counter.value(), &MetricValue::Counter { value: 3.0 });
+        assert_eq!(counter.interval_ms(), NonZeroU32::new(60_000));
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_period() {
+        let config: AwsCloudwatchMetricsConfig = toml::from_str(
+            r#"
+            region = "us-east-1"
+            period_secs = 0
+
+            [[metrics]]
+            namespace = "AWS/EC2"
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+
+        let error = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "period_secs must be 1, 5, 10, 30 or a non-zero multiple of 60 seconds, got 0"
+        );
+    }
+
+    #[test]
+    fn round_trips_query_ids() {
+        assert_eq!(parse_query_id(&query_id(0)), Some(0));
+        assert_eq!(parse_query_id(&query_id(499)), Some(499));
+        assert_eq!(parse_query_id("m1"), None);
+    }
+}
@@ -8,7 +8,7 @@ use warp::{http::StatusCode, Filter};
 
 use super::{
     errors::{ParseSnafu, RequestError},
-    handlers,
+    handlers::{self, RecordParser},
     models::{FirehoseRequest, FirehoseResponse},
     Compression,
 };
@@ -22,6 +22,7 @@ use crate::{
 pub fn firehose(
     access_key: Option<String>,
     record_compression: Compression,
+    parser: RecordParser,
     decoder: codecs::Decoder,
     acknowledgements: bool,
     out: SourceSender,
@@ -45,6 +46,7 @@ pub fn firehose(
         )
         .and(parse_body())
         .and(warp::any().map(move || record_compression))
+        .and(warp::any().map(move || parser.clone()))
         .and(warp::any().map(move || decoder.clone()))
         .and(warp::any().map(move || acknowledgements))
         .and(warp::any().map(move || out.clone()))
@@ -14,13 +14,17 @@ use warp::reject;
 
 use super::{
     errors::{ParseRecordsSnafu, RequestError},
-    models::{EncodedFirehoseRecord, FirehoseRequest, FirehoseResponse},
+    models::{
+        CloudwatchLogEvent, CloudwatchLogsPayload, EncodedFirehoseRecord, FirehoseRequest,
+        FirehoseResponse,
+    },
+    vpc_flow_logs::{ParseError, VpcFlowLogFormat},
     Compression,
 };
 use crate::{
     codecs::Decoder,
     config::log_schema,
-    event::{BatchStatus, Event},
+    event::{BatchStatus, Event, LogEvent},
     internal_events::{
         AwsKinesisFirehoseAutomaticRecordDecodeError, BytesReceived, EventsReceived,
         StreamClosedError,
@@ -29,15 +33,20 @@ use crate::{
 };
 
 /// Publishes decoded events from the FirehoseRequest to the pipeline
+#[allow(clippy::too_many_arguments)]
 pub async fn firehose(
     request_id: String,
     source_arn: String,
     request: FirehoseRequest,
     compression: Compression,
+    parser: RecordParser,
     decoder: Decoder,
     acknowledgements: bool,
     mut out: SourceSender,
 ) -> Result<impl warp::Reply, reject::Rejection> {
+    // All the records are parsed before any is sent, so that a request rejected because of an
+    // invalid record doesn't forward the records before it, which Firehose would send again.
+    let mut records = Vec::with_capacity(request.records.len());
     for record in request.records {
         let bytes = decode_record(&record, compression)
             .with_context(|_| ParseRecordsSnafu {
@@ -49,77 +58,71 @@ pub async fn firehose(
             protocol: "http",
         });
 
-        let mut stream = FramedRead::new(bytes.as_ref(), decoder.clone());
-        loop {
-            match stream.next().await {
-                Some(Ok((mut events, _byte_size))) => {
-                    emit!(EventsReceived {
-                        count: events.len(),
-                        byte_size: events.size_of(),
-                    });
-
-                    let (batch, receiver) = acknowledgements
-                        .then(|| {
-                            let (batch, receiver) = BatchNotifier::new_with_receiver();
-                            (Some(batch), Some(receiver))
-                        })
-                        .unwrap_or((None, None));
-
-                    for event in &mut events {
-                        if let Some(batch) = &batch {
-                            event.add_batch_notifier(batch.clone());
-                        }
-                        if let Event::Log(ref mut log) = event {
-                            log.try_insert(
-                                log_schema().source_type_key(),
-                                Bytes::from("aws_kinesis_firehose"),
-                            );
-                            log.try_insert(log_schema().timestamp_key(), request.timestamp);
-                            log.try_insert(path!("request_id"), request_id.to_string());
-                            log.try_insert(path!("source_arn"), source_arn.to_string());
-                        }
-                    }
+        let events = parser
+            .parse(bytes, &decoder)
+            .await
+            .with_context(|_| ParseRecordsSnafu {
+                request_id: request_id.clone(),
+            })
+            .map_err(reject::custom)?;
+        if !events.is_empty() {
+            records.push(events);
+        }
+    }
 
-                    let count = events.len();
-                    if let Err(error) = out.send_batch(events).await {
-                        emit!(StreamClosedError {
-                            error: error.clone(),
-                            count,
-                        });
-                        let error = RequestError::ShuttingDown {
-                            request_id: request_id.clone(),
-                            source: error,
-                        };
-                        warp::reject::custom(error);
-                    }
+    for mut events in records {
+        emit!(EventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
 
-                    drop(batch);
-                    if let Some(receiver) = receiver {
-                        match receiver.await {
-                            BatchStatus::Delivered => Ok(()),
-                            BatchStatus::Rejected => {
-                                Err(warp::reject::custom(RequestError::DeliveryFailed {
-                                    request_id: request_id.clone(),
-                                }))
-                            }
-                            BatchStatus::Errored => {
-                                Err(warp::reject::custom(RequestError::DeliveryErrored {
-                                    request_id: request_id.clone(),
-                                }))
-                            }
-                        }?;
-                    }
-                }
-                Some(Err(error)) => {
-                    // Error is logged by `crate::codecs::Decoder`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
-                    }
-                }
-                None => break,
+        let (batch, receiver) = acknowledgements
+            .then(|| {
+                let (batch, receiver) = BatchNotifier::new_with_receiver();
+                (Some(batch), Some(receiver))
+            })
+            .unwrap_or((None, None));
+
+        for event in &mut events {
+            if let Some(batch) = &batch {
+                event.add_batch_notifier(batch.clone());
+            }
+            if let Event::Log(ref mut log) = event {
+                log.try_insert(
+                    log_schema().source_type_key(),
+                    Bytes::from("aws_kinesis_firehose"),
+                );
+                log.try_insert(log_schema().timestamp_key(), request.timestamp);
+                log.try_insert(path!("request_id"), request_id.to_string());
+                log.try_insert(path!("source_arn"), source_arn.to_string());
             }
         }
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError {
+                error: error.clone(),
+                count,
+            });
+            let error = RequestError::ShuttingDown {
+                request_id: request_id.clone(),
+                source: error,
+            };
+            return Err(warp::reject::custom(error));
+        }
+
+        drop(batch);
+        if let Some(receiver) = receiver {
+            match receiver.await {
+                BatchStatus::Delivered => Ok(()),
+                BatchStatus::Rejected => Err(warp::reject::custom(RequestError::DeliveryFailed {
+                    request_id: request_id.clone(),
+                })),
+                BatchStatus::Errored => Err(warp::reject::custom(RequestError::DeliveryErrored {
+                    request_id: request_id.clone(),
+                })),
+            }?;
+        }
     }
 
     Ok(warp::reply::json(&FirehoseResponse {
@@ -129,6 +132,110 @@ pub async fn firehose(
     }))
 }
 
+/// Turns the data of a record into events, according to the configured record format.
+#[derive(Clone, Debug)]
+pub enum RecordParser {
+    Raw,
+    CloudwatchLogs,
+    VpcFlowLogs(VpcFlowLogFormat),
+}
+
+impl RecordParser {
+    async fn parse(
+        &self,
+        bytes: Bytes,
+        decoder: &Decoder,
+    ) -> Result<Vec<Event>, RecordDecodeError> {
+        match self {
+            RecordParser::Raw => Ok(decode_frames(&bytes, decoder).await),
+            RecordParser::CloudwatchLogs if bytes.is_empty() => Ok(Vec::new()),
+            RecordParser::CloudwatchLogs => {
+                let payload = parse_cloudwatch_logs(&bytes)?;
+                let mut events = Vec::new();
+                for log_event in &payload.log_events {
+                    let decoded = decode_frames(log_event.message.as_bytes(), decoder).await;
+                    events.extend(decoded.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            insert_cloudwatch_logs_fields(log, &payload, log_event);
+                        }
+                        event
+                    }));
+                }
+                Ok(events)
+            }
+            // Flow logs are either delivered to Firehose directly as text, or wrapped in the
+            // payload of a CloudWatch Logs subscription when published to CloudWatch Logs first.
+            RecordParser::VpcFlowLogs(format) if bytes.starts_with(b"{") => {
+                let payload = parse_cloudwatch_logs(&bytes)?;
+                let mut events = Vec::new();
+                for log_event in &payload.log_events {
+                    if let Some(mut log) = format
+                        .parse_record(&log_event.message)
+                        .context(VpcFlowLogsSnafu)?
+                    {
+                        insert_cloudwatch_logs_fields(&mut log, &payload, log_event);
+                        events.push(log.into());
+                    }
+                }
+                Ok(events)
+            }
+            RecordParser::VpcFlowLogs(format) => Ok(format
+                .parse_records(&bytes)
+                .context(VpcFlowLogsSnafu)?
+                .into_iter()
+                .map(Event::from)
+                .collect()),
+        }
+    }
+}
+
+/// Decodes the data with the configured framing and decoding.
+async fn decode_frames(data: &[u8], decoder: &Decoder) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut stream = FramedRead::new(data, decoder.clone());
+    loop {
+        match stream.next().await {
+            Some(Ok((decoded, _byte_size))) => events.extend(decoded),
+            Some(Err(error)) => {
+                // Error is logged by `crate::codecs::Decoder`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+            None => break,
+        }
+    }
+    events
+}
+
+/// Parses the payload of a CloudWatch Logs subscription. Control messages have no log events
+/// worth forwarding, so they are returned without any.
+fn parse_cloudwatch_logs(data: &[u8]) -> Result<CloudwatchLogsPayload, RecordDecodeError> {
+    let mut payload: CloudwatchLogsPayload =
+        serde_json::from_slice(data).context(CloudwatchLogsSnafu)?;
+    if payload.message_type != "DATA_MESSAGE" {
+        payload.log_events.clear();
+    }
+    Ok(payload)
+}
+
+fn insert_cloudwatch_logs_fields(
+    log: &mut LogEvent,
+    payload: &CloudwatchLogsPayload,
+    log_event: &CloudwatchLogEvent,
+) {
+    log.insert(log_schema().timestamp_key(), log_event.timestamp);
+    log.insert(path!("id"), log_event.id.clone());
+    log.insert(path!("owner"), payload.owner.clone());
+    log.insert(path!("log_group"), payload.log_group.clone());
+    log.insert(path!("log_stream"), payload.log_stream.clone());
+    log.insert(
+        path!("subscription_filters"),
+        payload.subscription_filters.clone(),
+    );
+}
+
 #[derive(Debug, Snafu)]
 pub enum RecordDecodeError {
     #[snafu(display("Could not base64 decode request data: {}", source))]
@@ -138,6 +245,10 @@ pub enum RecordDecodeError {
         source: std::io::Error,
         compression: Compression,
     },
+    #[snafu(display("Could not parse CloudWatch Logs subscription payload: {}", source))]
+    CloudwatchLogs { source: serde_json::Error },
+    #[snafu(display("Could not parse VPC flow logs: {}", source))]
+    VpcFlowLogs { source: ParseError },
 }
 
 /// Decodes a Firehose record.
@@ -10,8 +10,8 @@ use warp::Filter;
 use crate::{
     codecs::DecodingConfig,
     config::{
-        AcknowledgementsConfig, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
-        SourceDescription,
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceConfig,
+        SourceContext, SourceDescription,
     },
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
@@ -21,6 +21,10 @@ pub mod errors;
 mod filters;
 mod handlers;
 mod models;
+mod vpc_flow_logs;
+
+use handlers::RecordParser;
+use vpc_flow_logs::VpcFlowLogFormat;
 
 /// Configuration for the `aws_kinesis_firehose` source.
 #[configurable_component(source)]
@@ -45,6 +49,16 @@ pub struct AwsKinesisFirehoseConfig {
     /// Firehose HTTP endpoint destination. That option controls the content encoding of the entire HTTP request.
     record_compression: Option<Compression>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    record_format: RecordFormat,
+
+    /// The [format](\(urls.aws_vpc_flow_logs_records)) of the flow log records, as set on the flow log.
+    ///
+    /// Only used when `record_format` is `vpc_flow_logs`. By default, the fields of the default format, version 2,
+    /// are expected.
+    vpc_flow_logs_format: Option<String>,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -84,6 +98,29 @@ pub enum Compression {
     Gzip,
 }
 
+/// Format of the data in records of a Firehose message.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum RecordFormat {
+    /// Each record is decoded with the configured `framing` and `decoding` options.
+    #[derivative(Default)]
+    Raw,
+
+    /// Each record is the payload of an [AWS CloudWatch Logs subscription](\(urls.aws_cloudwatch_logs_subscriptions)).
+    ///
+    /// One event is published per log event in the payload, decoded with the configured `framing` and `decoding`
+    /// options. Control messages sent by CloudWatch Logs to check the delivery stream are dropped.
+    CloudwatchLogs,
+
+    /// Each record holds [AWS VPC flow log](\(urls.aws_vpc_flow_logs)) records.
+    ///
+    /// One event is published per flow log record, with a field per value. Flow logs can either be delivered to
+    /// Firehose directly or through an AWS CloudWatch Logs subscription.
+    VpcFlowLogs,
+}
+
 impl fmt::Display for Compression {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         match self {
@@ -105,10 +142,22 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
         )
         .build();
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+        let parser = match self.record_format {
+            RecordFormat::Raw => RecordParser::Raw,
+            RecordFormat::CloudwatchLogs => RecordParser::CloudwatchLogs,
+            RecordFormat::VpcFlowLogs => RecordParser::VpcFlowLogs(
+                self.vpc_flow_logs_format
+                    .as_deref()
+                    .map(VpcFlowLogFormat::parse)
+                    .transpose()?
+                    .unwrap_or_default(),
+            ),
+        };
 
         let svc = filters::firehose(
             self.access_key.clone(),
             self.record_compression.unwrap_or_default(),
+            parser,
             decoder,
             acknowledgements,
             cx.out,
@@ -131,7 +180,10 @@ impl SourceConfig for AwsKinesisFirehoseConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(self.decoding.output_type())]
+        match self.record_format {
+            RecordFormat::VpcFlowLogs => vec![Output::default(DataType::Log)],
+            _ => vec![Output::default(self.decoding.output_type())],
+        }
     }
 
     fn source_type(&self) -> &'static str {
@@ -158,6 +210,8 @@ impl GenerateConfig for AwsKinesisFirehoseConfig {
             access_key: None,
             tls: None,
             record_compression: None,
+            record_format: Default::default(),
+            vpc_flow_logs_format: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             acknowledgements: Default::default(),
@@ -176,7 +230,7 @@ mod tests {
     };
 
     use bytes::Bytes;
-    use chrono::{DateTime, SubsecRound, Utc};
+    use chrono::{DateTime, SubsecRound, TimeZone, Utc};
     use flate2::read::GzEncoder;
     use futures::Stream;
     use pretty_assertions::assert_eq;
@@ -228,6 +282,7 @@ mod tests {
     async fn source(
         access_key: Option<String>,
         record_compression: Option<Compression>,
+        record_format: RecordFormat,
         delivered: bool,
     ) -> (impl Stream<Item = Event>, SocketAddr) {
         use EventStatus::*;
@@ -241,6 +296,8 @@ mod tests {
                 tls: None,
                 access_key,
                 record_compression,
+                record_format,
+                vpc_flow_logs_format: None,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
                 acknowledgements: true.into(),
@@ -403,7 +460,13 @@ mod tests {
                 Vec::new(),
             ),
         ] {
-            let (rx, addr) = source(None, Some(source_record_compression), true).await;
+            let (rx, addr) = source(
+                None,
+                Some(source_record_compression),
+                RecordFormat::Raw,
+                true,
+            )
+            .await;
 
             let timestamp: DateTime<Utc> = Utc::now();
 
@@ -446,7 +509,7 @@ mod tests {
     #[tokio::test]
     async fn aws_kinesis_firehose_forwards_events_gzip_request() {
         assert_source_compliance(&SOURCE_TAGS, async move {
-            let (rx, addr) = source(None, None, true).await;
+            let (rx, addr) = source(None, None, RecordFormat::Raw, true).await;
 
             let timestamp: DateTime<Utc> = Utc::now();
 
@@ -483,7 +546,13 @@ mod tests {
 
     #[tokio::test]
     async fn aws_kinesis_firehose_rejects_bad_access_key() {
-        let (_rx, addr) = source(Some("an access key".to_string()), None, true).await;
+        let (_rx, addr) = source(
+            Some("an access key".to_string()),
+            None,
+            RecordFormat::Raw,
+            true,
+        )
+        .await;
 
         let res = send(
             addr,
@@ -505,7 +574,7 @@ mod tests {
     async fn handles_acknowledgement_failure() {
         let expected = RECORD.as_bytes().to_owned();
 
-        let (rx, addr) = source(None, Some(Compression::None), false).await;
+        let (rx, addr) = source(None, Some(Compression::None), RecordFormat::Raw, false).await;
 
         let timestamp: DateTime<Utc> = Utc::now();
 
@@ -538,4 +607,122 @@ mod tests {
         let response: models::FirehoseResponse = res.json().await.unwrap();
         assert_eq!(response.request_id, REQUEST_ID);
     }
+
+    #[tokio::test]
+    async fn aws_kinesis_firehose_unwraps_cloudwatch_logs() {
+        let (rx, addr) = source(None, None, RecordFormat::CloudwatchLogs, true).await;
+
+        let timestamp: DateTime<Utc> = Utc::now();
+
+        let res = spawn_send(
+            addr,
+            timestamp,
+            vec![RECORD.as_bytes()],
+            None,
+            false,
+            Compression::Gzip,
+        )
+        .await;
+
+        let events = collect_ready(rx).await;
+        let res = res.await.unwrap().unwrap();
+        assert_eq!(200, res.status().as_u16());
+
+        assert_eq!(events.len(), 2);
+        let log = events[1].as_log();
+        assert!(log["message"]
+            .to_string_lossy()
+            .starts_with(r#"{"bytes":17707,"#));
+        assert_eq!(log["timestamp"], Utc.timestamp_millis(1600110569041).into());
+        assert_eq!(
+            log["id"],
+            "35683658089659183914001456229543810359430816722590236673".into()
+        );
+        assert_eq!(log["owner"], "071959437513".into());
+        assert_eq!(log["log_group"], "/jesse/test".into());
+        assert_eq!(log["log_stream"], "test".into());
+        assert_eq!(log["subscription_filters"], vec!["Destination"].into());
+        assert_eq!(log["request_id"], REQUEST_ID.into());
+        assert_eq!(log["source_type"], "aws_kinesis_firehose".into());
+    }
+
+    #[tokio::test]
+    async fn aws_kinesis_firehose_drops_cloudwatch_logs_control_messages() {
+        let record = r#"{"messageType":"CONTROL_MESSAGE","owner":"CloudwatchLogs","logGroup":"","logStream":"","subscriptionFilters":[],"logEvents":[{"id":"","timestamp":1600110569039,"message":"CWL CONTROL MESSAGE: Checking health of destination Firehose."}]}"#;
+        let (rx, addr) = source(None, None, RecordFormat::CloudwatchLogs, true).await;
+
+        let res = spawn_send(
+            addr,
+            Utc::now(),
+            vec![record.as_bytes()],
+            None,
+            false,
+            Compression::Gzip,
+        )
+        .await;
+
+        let events = collect_ready(rx).await;
+        let res = res.await.unwrap().unwrap();
+        assert_eq!(200, res.status().as_u16());
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn aws_kinesis_firehose_parses_vpc_flow_logs() {
+        let (rx, addr) = source(None, None, RecordFormat::VpcFlowLogs, true).await;
+
+        let timestamp: DateTime<Utc> = Utc::now();
+
+        let res = spawn_send(
+            addr,
+            timestamp,
+            vec![concat!(
+                "2 123456789010 eni-1235b8ca123456789 172.31.16.139 172.31.16.21 20641 22 6 20 4249 1418530010 1418530070 ACCEPT OK\n",
+                "2 123456789010 eni-1235b8ca123456789 - - - - - - - 1431280876 1431280934 - NODATA\n",
+            )
+            .as_bytes()],
+            None,
+            false,
+            Compression::None,
+        )
+        .await;
+
+        let events = collect_ready(rx).await;
+        let res = res.await.unwrap().unwrap();
+        assert_eq!(200, res.status().as_u16());
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        assert_eq!(log["srcaddr"], "172.31.16.139".into());
+        assert_eq!(log["srcport"], 20641.into());
+        assert_eq!(log["action"], "ACCEPT".into());
+        assert_eq!(log["timestamp"], timestamp.trunc_subsecs(3).into());
+        let log = events[1].as_log();
+        assert!(log.get("srcaddr").is_none());
+        assert_eq!(log["log_status"], "NODATA".into());
+    }
+
+    #[tokio::test]
+    async fn aws_kinesis_firehose_rejects_malformed_vpc_flow_logs() {
+        let (rx, addr) = source(None, None, RecordFormat::VpcFlowLogs, true).await;
+
+        let res = send(
+            addr,
+            Utc::now(),
+            vec![
+                "2 123456789010 eni-1235b8ca123456789 - - - - - - - 1431280876 1431280934 - NODATA"
+                    .as_bytes(),
+                "2 123456789010 eni-1235b8ca123456789".as_bytes(),
+            ],
+            None,
+            false,
+            Compression::None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(400, res.status().as_u16());
+
+        // The valid record isn't forwarded either, as Firehose sends the whole request again.
+        assert!(collect_ready(rx).await.is_empty());
+    }
 }
@@ -38,3 +38,34 @@ pub struct FirehoseResponse {
 
     pub error_message: Option<String>,
 }
+
+/// Represents the payload CloudWatch Logs subscriptions deliver in each Firehose record
+///
+/// https://docs.aws.amazon.com/AmazonCloudWatch/latest/logs/SubscriptionFilters.html#FirehoseExample
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudwatchLogsPayload {
+    /// `DATA_MESSAGE`, or `CONTROL_MESSAGE` for the messages CloudWatch Logs sends to check that
+    /// the destination is reachable
+    pub message_type: String,
+
+    pub owner: String,
+
+    pub log_group: String,
+
+    pub log_stream: String,
+
+    pub subscription_filters: Vec<String>,
+
+    pub log_events: Vec<CloudwatchLogEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CloudwatchLogEvent {
+    pub id: String,
+
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+
+    pub message: String,
+}
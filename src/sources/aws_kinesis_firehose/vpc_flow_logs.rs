@@ -0,0 +1,216 @@
+use chrono::{TimeZone, Utc};
+use snafu::Snafu;
+
+use crate::event::{LogEvent, Value};
+
+/// The fields of the default VPC flow log format, version 2.
+pub const DEFAULT_FORMAT: &str = "${version} ${account-id} ${interface-id} ${srcaddr} ${dstaddr} \
+    ${srcport} ${dstport} ${protocol} ${packets} ${bytes} ${start} ${end} ${action} ${log-status}";
+
+/// Fields whose values are integers.
+const INTEGER_FIELDS: &[&str] = &[
+    "version",
+    "srcport",
+    "dstport",
+    "protocol",
+    "packets",
+    "bytes",
+    "tcp-flags",
+    "traffic-path",
+];
+
+/// Fields whose values are Unix timestamps, in seconds.
+const TIMESTAMP_FIELDS: &[&str] = &["start", "end"];
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum FormatError {
+    #[snafu(display("VPC flow log format must contain at least one field"))]
+    Empty,
+    #[snafu(display(
+        "Invalid field {:?} in VPC flow log format, expected ${{field-name}}",
+        field
+    ))]
+    InvalidField { field: String },
+}
+
+#[derive(Debug, Snafu, PartialEq)]
+pub enum ParseError {
+    #[snafu(display("VPC flow log record is not valid UTF-8"))]
+    NotUtf8,
+    #[snafu(display(
+        "VPC flow log record has {} fields but the format has {}",
+        found,
+        expected
+    ))]
+    FieldCount { expected: usize, found: usize },
+}
+
+/// The fields of the records of a flow log, in the order they are written.
+///
+/// https://docs.aws.amazon.com/vpc/latest/userguide/flow-logs.html#flow-log-records
+#[derive(Clone, Debug, PartialEq)]
+pub struct VpcFlowLogFormat {
+    fields: Vec<String>,
+}
+
+impl VpcFlowLogFormat {
+    /// Parses a format as it's set on the flow log, such as `${version} ${srcaddr} ${dstaddr}`.
+    pub fn parse(format: &str) -> Result<Self, FormatError> {
+        let fields = format
+            .split_whitespace()
+            .map(|field| {
+                field
+                    .strip_prefix("${")
+                    .and_then(|field| field.strip_suffix('}'))
+                    .filter(|name| !name.is_empty())
+                    .map(ToOwned::to_owned)
+                    .ok_or_else(|| FormatError::InvalidField {
+                        field: field.to_owned(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if fields.is_empty() {
+            return Err(FormatError::Empty);
+        }
+        Ok(Self { fields })
+    }
+
+    /// Parses the records of a payload delivered directly to Firehose, one per line.
+    pub fn parse_records(&self, data: &[u8]) -> Result<Vec<LogEvent>, ParseError> {
+        let data = std::str::from_utf8(data).map_err(|_| ParseError::NotUtf8)?;
+        let mut events = Vec::new();
+        for line in data.lines() {
+            events.extend(self.parse_record(line)?);
+        }
+        Ok(events)
+    }
+
+    /// Parses a single record into an event with a field per value. Values that are `-`, meaning
+    /// the field doesn't apply to the record, are left out.
+    ///
+    /// Returns `None` for blank lines and for the header line listing the field names.
+    pub fn parse_record(&self, line: &str) -> Result<Option<LogEvent>, ParseError> {
+        let values = line.split_whitespace().collect::<Vec<_>>();
+        if values.is_empty()
+            || values
+                .iter()
+                .copied()
+                .eq(self.fields.iter().map(String::as_str))
+        {
+            return Ok(None);
+        }
+        if values.len() != self.fields.len() {
+            return Err(ParseError::FieldCount {
+                expected: self.fields.len(),
+                found: values.len(),
+            });
+        }
+
+        let mut log = LogEvent::default();
+        for (field, value) in self.fields.iter().zip(values) {
+            if value == "-" {
+                continue;
+            }
+            log.insert(field.replace('-', "_").as_str(), parse_value(field, value));
+        }
+        Ok(Some(log))
+    }
+}
+
+impl Default for VpcFlowLogFormat {
+    fn default() -> Self {
+        Self::parse(DEFAULT_FORMAT).expect("default format is valid")
+    }
+}
+
+/// Converts the value of a numeric field, keeping the raw string if it isn't a number.
+fn parse_value(field: &str, value: &str) -> Value {
+    if INTEGER_FIELDS.contains(&field) {
+        if let Ok(value) = value.parse::<i64>() {
+            return value.into();
+        }
+    } else if TIMESTAMP_FIELDS.contains(&field) {
+        if let Some(timestamp) = value
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+        {
+            return timestamp.into();
+        }
+    }
+    value.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            VpcFlowLogFormat::parse("${version} ${vpc-id}  ${srcaddr}").unwrap(),
+            VpcFlowLogFormat {
+                fields: vec!["version".into(), "vpc-id".into(), "srcaddr".into()]
+            }
+        );
+        assert_eq!(VpcFlowLogFormat::parse(" "), Err(FormatError::Empty));
+        assert_eq!(
+            VpcFlowLogFormat::parse("${version} srcaddr"),
+            Err(FormatError::InvalidField {
+                field: "srcaddr".into()
+            })
+        );
+        assert_eq!(
+            VpcFlowLogFormat::default().fields.len(),
+            14,
+            "default format has the version 2 fields"
+        );
+    }
+
+    #[test]
+    fn parses_default_format_records() {
+        let format = VpcFlowLogFormat::default();
+        let log = format
+            .parse_record("2 123456789010 eni-1235b8ca123456789 172.31.16.139 172.31.16.21 20641 22 6 20 4249 1418530010 1418530070 ACCEPT OK")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(log["version"], 2.into());
+        assert_eq!(log["account_id"], "123456789010".into());
+        assert_eq!(log["interface_id"], "eni-1235b8ca123456789".into());
+        assert_eq!(log["srcaddr"], "172.31.16.139".into());
+        assert_eq!(log["dstport"], 22.into());
+        assert_eq!(log["bytes"], 4249.into());
+        assert_eq!(log["start"], Utc.timestamp(1418530010, 0).into());
+        assert_eq!(log["end"], Utc.timestamp(1418530070, 0).into());
+        assert_eq!(log["action"], "ACCEPT".into());
+        assert_eq!(log["log_status"], "OK".into());
+    }
+
+    #[test]
+    fn skips_missing_values_and_headers() {
+        let format = VpcFlowLogFormat::parse("${version} ${srcaddr} ${tcp-flags}").unwrap();
+        let events = format
+            .parse_records(b"version srcaddr tcp-flags\n2 - 19\n\n2 10.0.0.1 -\n")
+            .unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert!(events[0].get("srcaddr").is_none());
+        assert_eq!(events[0]["tcp_flags"], 19.into());
+        assert_eq!(events[1]["srcaddr"], "10.0.0.1".into());
+        assert!(events[1].get("tcp_flags").is_none());
+    }
+
+    #[test]
+    fn rejects_records_not_matching_the_format() {
+        let format = VpcFlowLogFormat::parse("${version} ${srcaddr}").unwrap();
+        assert_eq!(
+            format.parse_records(b"2 10.0.0.1 10.0.0.2"),
+            Err(ParseError::FieldCount {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(format.parse_records(&[0xff]), Err(ParseError::NotUtf8));
+    }
+}
@@ -208,7 +208,9 @@ impl SourceConfig for DatadogAgentConfig {
                     // JSON deserializer can overwrite existing fields at runtime, so we have to treat
                     // those events as if there is no known type details we can provide, other than the
                     // details provided by the generic JSON schema definition.
-                    DeserializerConfig::Json => self.decoding.schema_definition(log_namespace),
+                    DeserializerConfig::Json { .. } => {
+                        self.decoding.schema_definition(log_namespace)
+                    }
 
                     // Syslog deserializer allows for arbritrary "structured data" that can overwrite
                     // existing fields, similar to the JSON deserializer.
@@ -222,6 +224,12 @@ impl SourceConfig for DatadogAgentConfig {
                         self.decoding.schema_definition(log_namespace)
                     }
                     DeserializerConfig::Gelf => self.decoding.schema_definition(log_namespace),
+
+                    // CEF and LEEF deserializers add arbitrary extension fields, similar to the
+                    // JSON deserializer.
+                    DeserializerConfig::Cef { .. } | DeserializerConfig::Leef { .. } => {
+                        self.decoding.schema_definition(log_namespace)
+                    }
                 }
             }
             LogNamespace::Vector => self.decoding.schema_definition(log_namespace),
@@ -9,7 +9,7 @@ use bytes::Bytes;
 use chrono::{TimeZone, Utc};
 use codecs::{
     decoding::{Deserializer, DeserializerConfig, Framer},
-    BytesDecoder, BytesDeserializer,
+    BytesDecoder, BytesDeserializer, JsonDeserializerConfig,
 };
 use futures::{Stream, StreamExt};
 use http::HeaderMap;
@@ -1367,7 +1367,7 @@ fn test_config_outputs() {
         (
             "json / single output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: JsonDeserializerConfig::new().into(),
                 multiple_outputs: false,
                 want: HashMap::from([(
                     None,
@@ -1382,7 +1382,7 @@ fn test_config_outputs() {
         (
             "json / multiple output",
             TestCase {
-                decoding: DeserializerConfig::Json,
+                decoding: JsonDeserializerConfig::new().into(),
                 multiple_outputs: true,
                 want: HashMap::from([
                     (
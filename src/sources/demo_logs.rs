@@ -6,7 +6,7 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use fakedata::logs::*;
+use fakedata::{logs::*, metrics::metric_json_line, traces::trace_json_line};
 use futures::StreamExt;
 use rand::seq::SliceRandom;
 use snafu::Snafu;
@@ -65,10 +65,38 @@ const fn default_count() -> usize {
     isize::MAX as usize
 }
 
+fn default_app_json_fields() -> Vec<String> {
+    [
+        "timestamp",
+        "level",
+        "service",
+        "message",
+        "request_id",
+        "duration_ms",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+const fn default_metric_series() -> usize {
+    8
+}
+
+const fn default_metric_period_secs() -> f64 {
+    86400.0
+}
+
 #[derive(Debug, PartialEq, Snafu)]
 pub enum DemoLogsConfigError {
     #[snafu(display("A non-empty list of lines is required for the shuffle format"))]
     ShuffleDemoLogsItemsEmpty,
+    #[snafu(display("A non-empty list of fields is required for the app_json format"))]
+    AppJsonFieldsEmpty,
+    #[snafu(display(
+        "`series` and `period_secs` must be positive and finite for the metrics format"
+    ))]
+    MetricsSeriesInvalid,
 }
 
 /// Output format configuration.
@@ -103,6 +131,43 @@ pub enum OutputFormat {
     /// Randomly generated HTTP server logs in [JSON](\(urls.json)) format.
     #[derivative(Default)]
     Json,
+
+    /// Randomly generated access logs in the Apache combined format, with status codes, paths,
+    /// response sizes and user agents distributed as in real traffic.
+    ApacheCombined,
+
+    /// Randomly generated access logs in the nginx `combined` format, followed by the request time,
+    /// with the same distributions as `apache_combined`.
+    Nginx,
+
+    /// Randomly generated application logs in [JSON](\(urls.json)) format.
+    AppJson {
+        /// The fields of each log.
+        ///
+        /// The `timestamp`, `level`, `service`, `message`, `host`, `request_id`, `trace_id`,
+        /// `user_id`, `duration_ms`, `status`, and `version` fields have realistic values. Other
+        /// fields have random words as values.
+        #[serde(default = "default_app_json_fields")]
+        fields: Vec<String>,
+    },
+
+    /// Randomly generated traces in [JSON](\(urls.json)) format, each with the spans of a request
+    /// and the downstream calls made to handle it.
+    Traces,
+
+    /// Randomly generated points of metric series in [JSON](\(urls.json)) format, whose values
+    /// follow a seasonal pattern with some noise.
+    Metrics {
+        /// The number of series. Each generated line is a point of the next series.
+        #[serde(default = "default_metric_series")]
+        series: usize,
+
+        /// The period of the seasonal pattern of the series, in seconds.
+        ///
+        /// The default is one day.
+        #[serde(default = "default_metric_period_secs")]
+        period_secs: f64,
+    },
 }
 
 impl OutputFormat {
@@ -119,6 +184,14 @@ impl OutputFormat {
             Self::Syslog => syslog_5424_log_line(),
             Self::BsdSyslog => syslog_3164_log_line(),
             Self::Json => json_log_line(),
+            Self::ApacheCombined => apache_combined_log_line(),
+            Self::Nginx => nginx_log_line(),
+            Self::AppJson { fields } => app_json_log_line(fields),
+            Self::Traces => trace_json_line(),
+            Self::Metrics {
+                series,
+                period_secs,
+            } => metric_json_line(n, *series, *period_secs),
         }
     }
 
@@ -133,7 +206,8 @@ impl OutputFormat {
         }
     }
 
-    // Ensures that the `lines` list is non-empty if `Shuffle` is chosen
+    // Ensures that the `lines` list is non-empty if `Shuffle` is chosen, and that the options of
+    // the other generators are usable
     pub(self) fn validate(&self) -> Result<(), DemoLogsConfigError> {
         match self {
             Self::Shuffle { lines, .. } => {
@@ -143,6 +217,15 @@ impl OutputFormat {
                     Ok(())
                 }
             }
+            Self::AppJson { fields } if fields.is_empty() => {
+                Err(DemoLogsConfigError::AppJsonFieldsEmpty)
+            }
+            Self::Metrics {
+                series,
+                period_secs,
+            } if *series == 0 || !(*period_secs > 0.0 && period_secs.is_finite()) => {
+                Err(DemoLogsConfigError::MetricsSeriesInvalid)
+            }
             _ => Ok(()),
         }
     }
@@ -354,6 +437,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_metrics_period_is_positive_and_finite() {
+        for period_secs in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let format = OutputFormat::Metrics {
+                series: 1,
+                period_secs,
+            };
+            assert_eq!(format.validate(), Err(DemoLogsConfigError::MetricsSeriesInvalid));
+        }
+    }
+
     #[tokio::test]
     async fn shuffle_demo_logs_copies_lines() {
         let message_key = log_schema().message_key();
@@ -494,6 +588,121 @@ mod tests {
         assert_eq!(poll!(rx.next()), Poll::Ready(None));
     }
 
+    async fn json_lines(config: &str, count: usize) -> Vec<serde_json::Value> {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(config).await;
+
+        let mut values = Vec::new();
+        for _ in 0..count {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            values.push(serde_json::from_str(&message).unwrap());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+        values
+    }
+
+    #[tokio::test]
+    async fn apache_combined_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "apache_combined"
+            count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            // The request, the referer and the user agent are quoted.
+            assert_eq!(message.matches('"').count(), 6);
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn nginx_format_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "nginx"
+            count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            let request_time = message.rsplit(' ').next().unwrap();
+            assert!(request_time.parse::<f64>().is_ok());
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn app_json_format_generates_fields() {
+        let logs = json_lines(
+            r#"format = "app_json"
+            fields = ["level", "user_id", "team"]
+            count = 5"#,
+            5,
+        )
+        .await;
+
+        for log in logs {
+            let fields = log.as_object().unwrap();
+            assert_eq!(fields.len(), 3);
+            let level = fields["level"].as_str().unwrap();
+            assert!(["debug", "info", "warn", "error"].contains(&level));
+            assert!(fields["user_id"].is_u64());
+            assert!(fields["team"].is_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn traces_format_generates_spans() {
+        let traces = json_lines(
+            r#"format = "traces"
+            count = 5"#,
+            5,
+        )
+        .await;
+
+        for trace in traces {
+            let spans = trace["spans"].as_array().unwrap();
+            assert!(spans.len() >= 2);
+            assert!(spans[0]["parent_span_id"].is_null());
+            for span in &spans[1..] {
+                assert_eq!(span["parent_span_id"], spans[0]["span_id"]);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_format_cycles_through_series() {
+        let points = json_lines(
+            r#"format = "metrics"
+            series = 2
+            count = 4"#,
+            4,
+        )
+        .await;
+
+        assert_eq!(points[0]["name"], points[2]["name"]);
+        assert_ne!(points[0]["name"], points[1]["name"]);
+        for point in points {
+            assert!(point["value"].as_f64().unwrap() >= 0.0);
+        }
+    }
+
     #[tokio::test]
     async fn json_format_generates_output() {
         let message_key = log_schema().message_key();
@@ -1,14 +1,18 @@
-use std::path::PathBuf;
+use std::{net::SocketAddr, path::PathBuf};
 
 use bytes::Bytes;
 use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
-use super::util::framestream::{build_framestream_unix_source, FrameHandler};
+use super::util::framestream::{
+    build_framestream_tcp_source, build_framestream_unix_source, FrameHandler,
+};
 use crate::{
     config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
     event::{Event, LogEvent},
     internal_events::{BytesReceived, DnstapParseError, EventsReceived},
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
     Result,
 };
 
@@ -36,12 +40,30 @@ pub struct DnstapConfig {
     /// used.
     pub host_key: Option<String>,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: Mode,
+
     /// Absolute path to the socket file to read DNSTAP data from.
     ///
     /// The DNS server must be configured to send its DNSTAP data to this socket file. The socket file will be created,
     /// if it doesn't already exist, when the source first starts.
+    ///
+    /// Only relevant when `mode` is `unix`.
+    #[serde(default = "default_socket_path")]
     pub socket_path: PathBuf,
 
+    /// The address to listen for DNSTAP connections on.
+    ///
+    /// Required when `mode` is `tcp`. The peer address of each connection is used as the source of its events.
+    pub address: Option<SocketAddr>,
+
+    #[configurable(derived)]
+    pub keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
     /// Whether or not to skip parsing/decoding of DNSTAP frames.
     ///
     /// If set to `true`, frames will not be parsed/decoded. The raw frame data will be set as a field on the event
@@ -71,10 +93,32 @@ pub struct DnstapConfig {
     pub socket_send_buffer_size: Option<usize>,
 }
 
+/// The type of socket to read DNSTAP data from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    /// Listen on a Unix domain socket.
+    Unix,
+
+    /// Listen on a TCP socket, optionally using TLS.
+    Tcp,
+}
+
+impl Default for Mode {
+    fn default() -> Self {
+        Self::Unix
+    }
+}
+
 fn default_max_frame_length() -> usize {
     bytesize::kib(100u64) as usize
 }
 
+fn default_socket_path() -> PathBuf {
+    PathBuf::from("/run/bind/dnstap.sock")
+}
+
 impl DnstapConfig {
     pub fn new(socket_path: PathBuf) -> Self {
         Self {
@@ -94,7 +138,11 @@ impl Default for DnstapConfig {
         Self {
             host_key: None,
             max_frame_length: default_max_frame_length(),
-            socket_path: PathBuf::from("/run/bind/dnstap.sock"),
+            mode: Mode::default(),
+            socket_path: default_socket_path(),
+            address: None,
+            keepalive: None,
+            tls: None,
             raw_data_only: None,
             multithreaded: None,
             max_frame_handling_tasks: None,
@@ -116,7 +164,24 @@ impl_generate_config_from_default!(DnstapConfig);
 impl SourceConfig for DnstapConfig {
     async fn build(&self, cx: SourceContext) -> Result<super::Source> {
         let frame_handler = DnstapFrameHandler::new(self);
-        build_framestream_unix_source(frame_handler, cx.shutdown, cx.out)
+        match self.mode {
+            Mode::Unix => build_framestream_unix_source(frame_handler, cx.shutdown, cx.out),
+            Mode::Tcp => {
+                let address = self
+                    .address
+                    .ok_or("The `address` option is required when `mode` is `tcp`.")?;
+                let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+                build_framestream_tcp_source(
+                    frame_handler,
+                    address,
+                    self.keepalive,
+                    tls,
+                    cx.shutdown,
+                    cx.out,
+                    cx.ready.defer(),
+                )
+            }
+        }
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -266,6 +331,44 @@ impl FrameHandler for DnstapFrameHandler {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SourceSender;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DnstapConfig>();
+    }
+
+    #[test]
+    fn parses_tcp_mode() {
+        let config: DnstapConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "0.0.0.0:9000"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.mode, Mode::Tcp);
+        assert_eq!(config.address, Some("0.0.0.0:9000".parse().unwrap()));
+        assert_eq!(config.socket_path, default_socket_path());
+    }
+
+    #[tokio::test]
+    async fn tcp_mode_requires_address() {
+        let config = DnstapConfig {
+            mode: Mode::Tcp,
+            ..DnstapConfig::default()
+        };
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .is_err());
+    }
+}
+
 #[cfg(all(test, feature = "dnstap-integration-tests"))]
 mod integration_tests {
     #![allow(clippy::print_stdout)] // tests
@@ -296,7 +399,11 @@ mod integration_tests {
                 DnstapConfig {
                     max_frame_length: 102400,
                     host_key: Some("key".to_string()),
+                    mode: Mode::Unix,
                     socket_path: socket,
+                    address: None,
+                    keepalive: None,
+                    tls: None,
                     raw_data_only: Some(raw_data),
                     multithreaded: Some(false),
                     max_frame_handling_tasks: Some(100000),
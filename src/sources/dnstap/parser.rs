@@ -32,8 +32,8 @@ use lookup::lookup_v2::OwnedPath;
 
 use super::{
     dns_message::{
-        DnsRecord, EdnsOptionEntry, OptPseudoSection, QueryHeader, QueryQuestion, UpdateHeader,
-        ZoneInfo,
+        ClientSubnet, DnsRecord, EdnsOptionEntry, ExtendedDnsError, OptPseudoSection, QueryHeader,
+        QueryQuestion, UpdateHeader, ZoneInfo,
     },
     dns_message_parser::DnsMessageParser,
     schema::DnstapEventSchema,
@@ -819,6 +819,88 @@ impl<'a> DnstapParser<'a> {
                     .options(),
                 &edns.options,
             );
+            if let Some(client_subnet) = &edns.client_subnet {
+                self.log_edns_client_subnet(
+                    self.event_schema
+                        .dns_message_opt_pseudo_section_schema()
+                        .client_subnet(),
+                    client_subnet,
+                );
+            }
+            if !edns.extended_errors.is_empty() {
+                self.log_edns_extended_errors(
+                    self.event_schema
+                        .dns_message_opt_pseudo_section_schema()
+                        .extended_errors(),
+                    &edns.extended_errors,
+                );
+            }
+        }
+
+        self.parent_key_path.segments.pop();
+    }
+
+    fn log_edns_client_subnet(&mut self, key_path: &'static str, client_subnet: &ClientSubnet) {
+        self.parent_key_path.push_field(key_path);
+
+        self.insert(
+            self.event_schema
+                .dns_message_client_subnet_schema()
+                .family(),
+            client_subnet.family,
+        );
+        self.insert(
+            self.event_schema
+                .dns_message_client_subnet_schema()
+                .source_prefix_length(),
+            client_subnet.source_prefix_length,
+        );
+        self.insert(
+            self.event_schema
+                .dns_message_client_subnet_schema()
+                .scope_prefix_length(),
+            client_subnet.scope_prefix_length,
+        );
+        if let Some(address) = &client_subnet.address {
+            self.insert(
+                self.event_schema
+                    .dns_message_client_subnet_schema()
+                    .address(),
+                address.clone(),
+            );
+        }
+
+        self.parent_key_path.segments.pop();
+    }
+
+    fn log_edns_extended_errors(&mut self, key_path: &'static str, errors: &[ExtendedDnsError]) {
+        self.parent_key_path.push_field(key_path);
+
+        for (i, error) in errors.iter().enumerate() {
+            self.parent_key_path.push_index(i as isize);
+            self.insert(
+                self.event_schema
+                    .dns_message_extended_error_schema()
+                    .info_code(),
+                error.info_code,
+            );
+            if let Some(purpose) = error.purpose {
+                self.insert(
+                    self.event_schema
+                        .dns_message_extended_error_schema()
+                        .purpose(),
+                    purpose,
+                );
+            }
+            if let Some(extra_text) = &error.extra_text {
+                self.insert(
+                    self.event_schema
+                        .dns_message_extended_error_schema()
+                        .extra_text(),
+                    extra_text.clone(),
+                );
+            }
+            self.parent_key_path.segments.pop();
         }
 
         self.parent_key_path.segments.pop();
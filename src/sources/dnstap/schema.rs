@@ -8,6 +8,8 @@ pub struct DnstapEventSchema {
     dns_update_header_schema: DnsUpdateHeaderSchema,
     dns_message_opt_pseudo_section_schema: DnsMessageOptPseudoSectionSchema,
     dns_message_option_schema: DnsMessageOptionSchema,
+    dns_message_client_subnet_schema: DnsMessageClientSubnetSchema,
+    dns_message_extended_error_schema: DnsMessageExtendedErrorSchema,
     dns_record_schema: DnsRecordSchema,
     dns_query_question_schema: DnsQueryQuestionSchema,
     dns_update_zone_info_schema: DnsUpdateZoneInfoSchema,
@@ -46,6 +48,14 @@ impl DnstapEventSchema {
         &self.dns_message_option_schema
     }
 
+    pub const fn dns_message_client_subnet_schema(&self) -> &DnsMessageClientSubnetSchema {
+        &self.dns_message_client_subnet_schema
+    }
+
+    pub const fn dns_message_extended_error_schema(&self) -> &DnsMessageExtendedErrorSchema {
+        &self.dns_message_extended_error_schema
+    }
+
     pub const fn dns_record_schema(&self) -> &DnsRecordSchema {
         &self.dns_record_schema
     }
@@ -72,6 +82,8 @@ impl DnstapEventSchema {
             dns_update_header_schema: DnsUpdateHeaderSchema::default(),
             dns_message_opt_pseudo_section_schema: DnsMessageOptPseudoSectionSchema::default(),
             dns_message_option_schema: DnsMessageOptionSchema::default(),
+            dns_message_client_subnet_schema: DnsMessageClientSubnetSchema::default(),
+            dns_message_extended_error_schema: DnsMessageExtendedErrorSchema::default(),
             dns_record_schema: DnsRecordSchema::default(),
             dns_query_question_schema: DnsQueryQuestionSchema::default(),
             dns_update_zone_info_schema: DnsUpdateZoneInfoSchema::default(),
@@ -643,6 +655,8 @@ pub struct DnsMessageOptPseudoSectionSchema {
     do_flag: &'static str,
     udp_max_payload_size: &'static str,
     options: &'static str,
+    client_subnet: &'static str,
+    extended_errors: &'static str,
 }
 
 impl Default for DnsMessageOptPseudoSectionSchema {
@@ -653,6 +667,8 @@ impl Default for DnsMessageOptPseudoSectionSchema {
             do_flag: "do",
             udp_max_payload_size: "udpPayloadSize",
             options: "options",
+            client_subnet: "clientSubnet",
+            extended_errors: "extendedErrors",
         }
     }
 }
@@ -677,6 +693,82 @@ impl DnsMessageOptPseudoSectionSchema {
     pub const fn options(&self) -> &'static str {
         self.options
     }
+
+    pub const fn client_subnet(&self) -> &'static str {
+        self.client_subnet
+    }
+
+    pub const fn extended_errors(&self) -> &'static str {
+        self.extended_errors
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DnsMessageClientSubnetSchema {
+    family: &'static str,
+    source_prefix_length: &'static str,
+    scope_prefix_length: &'static str,
+    address: &'static str,
+}
+
+impl Default for DnsMessageClientSubnetSchema {
+    fn default() -> Self {
+        Self {
+            family: "family",
+            source_prefix_length: "sourcePrefixLength",
+            scope_prefix_length: "scopePrefixLength",
+            address: "address",
+        }
+    }
+}
+
+impl DnsMessageClientSubnetSchema {
+    pub const fn family(&self) -> &'static str {
+        self.family
+    }
+
+    pub const fn source_prefix_length(&self) -> &'static str {
+        self.source_prefix_length
+    }
+
+    pub const fn scope_prefix_length(&self) -> &'static str {
+        self.scope_prefix_length
+    }
+
+    pub const fn address(&self) -> &'static str {
+        self.address
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DnsMessageExtendedErrorSchema {
+    info_code: &'static str,
+    purpose: &'static str,
+    extra_text: &'static str,
+}
+
+impl Default for DnsMessageExtendedErrorSchema {
+    fn default() -> Self {
+        Self {
+            info_code: "infoCode",
+            purpose: "purpose",
+            extra_text: "extraText",
+        }
+    }
+}
+
+impl DnsMessageExtendedErrorSchema {
+    pub const fn info_code(&self) -> &'static str {
+        self.info_code
+    }
+
+    pub const fn purpose(&self) -> &'static str {
+        self.purpose
+    }
+
+    pub const fn extra_text(&self) -> &'static str {
+        self.extra_text
+    }
 }
 
 #[derive(Debug, Clone)]
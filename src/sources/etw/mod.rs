@@ -0,0 +1,248 @@
+use std::sync::Arc;
+
+use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc;
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use windows_sys::core::GUID;
+
+use crate::{
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    internal_events::{EtwSessionError, OldEventsReceived, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod properties;
+mod session;
+
+use session::{Provider, Session};
+
+/// The highest level, which enables events of all levels.
+const TRACE_LEVEL_VERBOSE: u8 = 5;
+const DEFAULT_BUFFER_SIZE_KB: u32 = 64;
+const DEFAULT_BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one provider must be specified"))]
+    NoProviders,
+    #[snafu(display("Invalid provider GUID {:?}: {}", guid, source))]
+    InvalidProviderGuid { guid: String, source: uuid::Error },
+    #[snafu(display("Could not start ETW session {:?}: {}", name, source))]
+    StartSession {
+        name: String,
+        source: std::io::Error,
+    },
+}
+
+/// Configuration for the `etw` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EtwConfig {
+    /// The providers to enable in the trace session.
+    pub providers: Vec<EtwProviderConfig>,
+
+    /// The name of the real-time trace session.
+    ///
+    /// Defaults to `vector-` followed by the component ID. The name must be unique on the host, and a
+    /// session with the same name is stopped when the source starts.
+    #[serde(default)]
+    pub session_name: Option<String>,
+
+    /// The size of each buffer of the trace session, in kilobytes.
+    #[serde(default = "default_buffer_size_kb")]
+    pub buffer_size_kb: u32,
+}
+
+/// Configuration of an ETW provider.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct EtwProviderConfig {
+    /// The GUID of the provider, such as `22fb2cd6-0e7b-422b-a0c7-2fad1fd0e716` for
+    /// `Microsoft-Windows-Kernel-Process`.
+    pub guid: String,
+
+    /// The most verbose level of events to enable, from 1 for critical events to 5 for verbose
+    /// events.
+    #[serde(default = "default_level")]
+    pub level: u8,
+
+    /// A bitmask of keywords, of which events must match at least one. Zero enables all events.
+    #[serde(default)]
+    pub match_any_keyword: u64,
+
+    /// A bitmask of keywords, of which events must match all.
+    #[serde(default)]
+    pub match_all_keyword: u64,
+}
+
+const fn default_level() -> u8 {
+    TRACE_LEVEL_VERBOSE
+}
+
+const fn default_buffer_size_kb() -> u32 {
+    DEFAULT_BUFFER_SIZE_KB
+}
+
+impl EtwProviderConfig {
+    fn build(&self) -> crate::Result<Provider> {
+        let guid = uuid::Uuid::parse_str(&self.guid).context(InvalidProviderGuidSnafu {
+            guid: self.guid.clone(),
+        })?;
+        Ok(Provider {
+            guid: GUID::from_u128(guid.as_u128()),
+            level: self.level,
+            match_any_keyword: self.match_any_keyword,
+            match_all_keyword: self.match_all_keyword,
+        })
+    }
+}
+
+inventory::submit! {
+    SourceDescription::new::<EtwConfig>("etw")
+}
+
+impl GenerateConfig for EtwConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            [[providers]]
+            guid = "22fb2cd6-0e7b-422b-a0c7-2fad1fd0e716"
+            level = 4
+            "#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "etw")]
+impl SourceConfig for EtwConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.providers.is_empty() {
+            return Err(BuildError::NoProviders.into());
+        }
+        let providers = self
+            .providers
+            .iter()
+            .map(EtwProviderConfig::build)
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        let name = self
+            .session_name
+            .clone()
+            .unwrap_or_else(|| format!("vector-{}", cx.key.id()));
+        let session = Session::start(&name, self.buffer_size_kb, &providers)
+            .context(StartSessionSnafu { name })?;
+
+        Ok(Box::pin(etw_source(Arc::new(session), cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "etw"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn etw_source(
+    session: Arc<Session>,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    // ETW invokes the event callback on the thread processing the trace, which blocks until the
+    // session stops. Events are handed over through a bounded channel, so that ETW drops events
+    // in its own buffers rather than Vector buffering them without limit.
+    let (sender, mut receiver) = mpsc::channel(DEFAULT_BATCH_SIZE);
+    let processing = {
+        let session = Arc::clone(&session);
+        tokio::task::spawn_blocking(move || session.process(sender))
+    };
+
+    let stopping = {
+        let session = Arc::clone(&session);
+        tokio::spawn(async move {
+            shutdown.await;
+            session.stop();
+        })
+    };
+
+    while let Some(event) = receiver.recv().await {
+        let mut events = vec![event];
+        while events.len() < DEFAULT_BATCH_SIZE {
+            match receiver.try_recv() {
+                Ok(event) => events.push(event),
+                Err(_) => break,
+            }
+        }
+
+        emit!(OldEventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            session.stop();
+            return Err(());
+        }
+    }
+
+    stopping.abort();
+    match processing.await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(error)) => {
+            emit!(EtwSessionError { error });
+            Err(())
+        }
+        Err(_) => Err(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<EtwConfig>();
+    }
+
+    #[test]
+    fn parses_provider_guid() {
+        let provider = EtwProviderConfig {
+            guid: "22fb2cd6-0e7b-422b-a0c7-2fad1fd0e716".into(),
+            level: 4,
+            match_any_keyword: 0x10,
+            match_all_keyword: 0,
+        }
+        .build()
+        .unwrap();
+
+        assert_eq!(provider.guid.data1, 0x22fb2cd6);
+        assert_eq!(provider.guid.data2, 0x0e7b);
+        assert_eq!(
+            provider.guid.data4,
+            [0xa0, 0xc7, 0x2f, 0xad, 0x1f, 0xd0, 0xe7, 0x16]
+        );
+
+        let invalid = EtwProviderConfig {
+            guid: "Microsoft-Windows-Kernel-Process".into(),
+            level: 4,
+            match_any_keyword: 0,
+            match_all_keyword: 0,
+        };
+        assert!(invalid.build().is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::event::Value;
+
+// Input types of event properties, from `TDH_IN_TYPE` in `tdh.h`.
+const TDH_INTYPE_UNICODESTRING: u16 = 1;
+const TDH_INTYPE_ANSISTRING: u16 = 2;
+const TDH_INTYPE_INT8: u16 = 3;
+const TDH_INTYPE_UINT8: u16 = 4;
+const TDH_INTYPE_INT16: u16 = 5;
+const TDH_INTYPE_UINT16: u16 = 6;
+const TDH_INTYPE_INT32: u16 = 7;
+const TDH_INTYPE_UINT32: u16 = 8;
+const TDH_INTYPE_INT64: u16 = 9;
+const TDH_INTYPE_UINT64: u16 = 10;
+const TDH_INTYPE_FLOAT: u16 = 11;
+const TDH_INTYPE_DOUBLE: u16 = 12;
+const TDH_INTYPE_BOOLEAN: u16 = 13;
+const TDH_INTYPE_BINARY: u16 = 14;
+const TDH_INTYPE_GUID: u16 = 15;
+const TDH_INTYPE_POINTER: u16 = 16;
+const TDH_INTYPE_FILETIME: u16 = 17;
+const TDH_INTYPE_HEXINT32: u16 = 20;
+const TDH_INTYPE_HEXINT64: u16 = 21;
+
+/// The number of 100 nanosecond intervals between the `FILETIME` epoch, 1601-01-01, and the Unix
+/// epoch.
+const FILETIME_UNIX_EPOCH: i64 = 116_444_736_000_000_000;
+
+/// Decodes the raw bytes of a scalar event property according to its input type.
+///
+/// Returns `None` for types that aren't supported, such as SIDs and `SYSTEMTIME`, or if the
+/// property is shorter than its type requires.
+pub(super) fn decode(in_type: u16, data: &[u8]) -> Option<Value> {
+    let value = match in_type {
+        TDH_INTYPE_UNICODESTRING => {
+            let units = data
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .take_while(|unit| *unit != 0)
+                .collect::<Vec<_>>();
+            Value::from(String::from_utf16_lossy(&units))
+        }
+        TDH_INTYPE_ANSISTRING => {
+            let end = data.iter().position(|c| *c == 0).unwrap_or(data.len());
+            Value::from(String::from_utf8_lossy(&data[..end]).into_owned())
+        }
+        TDH_INTYPE_INT8 => Value::from(i8::from_le_bytes(bytes(data)?) as i64),
+        TDH_INTYPE_UINT8 => Value::from(u8::from_le_bytes(bytes(data)?) as i64),
+        TDH_INTYPE_INT16 => Value::from(i16::from_le_bytes(bytes(data)?) as i64),
+        TDH_INTYPE_UINT16 => Value::from(u16::from_le_bytes(bytes(data)?) as i64),
+        TDH_INTYPE_INT32 => Value::from(i32::from_le_bytes(bytes(data)?) as i64),
+        TDH_INTYPE_UINT32 => Value::from(u32::from_le_bytes(bytes(data)?) as i64),
+        TDH_INTYPE_INT64 => Value::from(i64::from_le_bytes(bytes(data)?)),
+        // Values that don't fit in an `i64` would wrap, so they are kept as strings instead.
+        TDH_INTYPE_UINT64 => {
+            let value = u64::from_le_bytes(bytes(data)?);
+            i64::try_from(value).map_or_else(|_| Value::from(value.to_string()), Value::from)
+        }
+        TDH_INTYPE_FLOAT => Value::from(f32::from_le_bytes(bytes(data)?) as f64),
+        TDH_INTYPE_DOUBLE => Value::from(f64::from_le_bytes(bytes(data)?)),
+        TDH_INTYPE_BOOLEAN => Value::from(u32::from_le_bytes(bytes(data)?) != 0),
+        TDH_INTYPE_BINARY => Value::from(base64::encode(data)),
+        TDH_INTYPE_GUID => Value::from(format_guid(data)?),
+        TDH_INTYPE_HEXINT32 => Value::from(format!("0x{:X}", u32::from_le_bytes(bytes(data)?))),
+        TDH_INTYPE_HEXINT64 => Value::from(format!("0x{:X}", u64::from_le_bytes(bytes(data)?))),
+        TDH_INTYPE_POINTER => match data.len() {
+            4 => Value::from(format!("0x{:X}", u32::from_le_bytes(bytes(data)?))),
+            _ => Value::from(format!("0x{:X}", u64::from_le_bytes(bytes(data)?))),
+        },
+        TDH_INTYPE_FILETIME => Value::from(filetime_to_datetime(i64::from_le_bytes(bytes(data)?))?),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Converts a `FILETIME`, in 100 nanosecond intervals since 1601-01-01, to a timestamp. Returns
+/// `None` for times that can't be represented with nanosecond precision.
+pub(super) fn filetime_to_datetime(filetime: i64) -> Option<DateTime<Utc>> {
+    let intervals = filetime.checked_sub(FILETIME_UNIX_EPOCH)?;
+    Some(Utc.timestamp_nanos(intervals.checked_mul(100)?))
+}
+
+/// Formats a GUID in its registry format, such as `{22FB2CD6-0E7B-422B-A0C7-2FAD1FD0E716}`.
+pub(super) fn format_guid(data: &[u8]) -> Option<String> {
+    let data: [u8; 16] = bytes(data)?;
+    Some(format!(
+        "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+        u32::from_le_bytes([data[0], data[1], data[2], data[3]]),
+        u16::from_le_bytes([data[4], data[5]]),
+        u16::from_le_bytes([data[6], data[7]]),
+        data[8],
+        data[9],
+        data[10],
+        data[11],
+        data[12],
+        data[13],
+        data[14],
+        data[15],
+    ))
+}
+
+fn bytes<const N: usize>(data: &[u8]) -> Option<[u8; N]> {
+    data.get(..N)?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_strings() {
+        let unicode = "héllo\0"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decode(TDH_INTYPE_UNICODESTRING, &unicode),
+            Some("héllo".into())
+        );
+        assert_eq!(
+            decode(TDH_INTYPE_ANSISTRING, b"hello\0"),
+            Some("hello".into())
+        );
+    }
+
+    #[test]
+    fn decodes_integers() {
+        assert_eq!(decode(TDH_INTYPE_INT8, &[0xFF]), Some((-1).into()));
+        assert_eq!(decode(TDH_INTYPE_UINT8, &[0xFF]), Some(255.into()));
+        assert_eq!(
+            decode(TDH_INTYPE_UINT32, &4624u32.to_le_bytes()),
+            Some(4624.into())
+        );
+        assert_eq!(
+            decode(TDH_INTYPE_UINT64, &u64::MAX.to_le_bytes()),
+            Some(u64::MAX.to_string().into())
+        );
+        assert_eq!(
+            decode(TDH_INTYPE_HEXINT32, &0xBEEFu32.to_le_bytes()),
+            Some("0xBEEF".into())
+        );
+        assert_eq!(
+            decode(TDH_INTYPE_BOOLEAN, &1u32.to_le_bytes()),
+            Some(true.into())
+        );
+        assert_eq!(decode(TDH_INTYPE_UINT32, &[1, 2]), None);
+    }
+
+    #[test]
+    fn decodes_guids_and_filetimes() {
+        let guid = [
+            0xD6, 0x2C, 0xFB, 0x22, 0x7B, 0x0E, 0x2B, 0x42, 0xA0, 0xC7, 0x2F, 0xAD, 0x1F, 0xD0,
+            0xE7, 0x16,
+        ];
+        assert_eq!(
+            decode(TDH_INTYPE_GUID, &guid),
+            Some("{22FB2CD6-0E7B-422B-A0C7-2FAD1FD0E716}".into())
+        );
+
+        assert_eq!(
+            filetime_to_datetime(132_410_592_000_000_000),
+            Some(Utc.ymd(2020, 8, 5).and_hms(0, 0, 0))
+        );
+        assert_eq!(filetime_to_datetime(0), None);
+    }
+
+    #[test]
+    fn skips_unsupported_types() {
+        assert_eq!(decode(19, &[1, 2, 3, 4]), None);
+    }
+}
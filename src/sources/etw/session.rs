@@ -0,0 +1,320 @@
+use std::{collections::BTreeMap, io, mem, ptr, slice};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+use windows_sys::{
+    core::GUID,
+    Win32::{
+        Foundation::{ERROR_ALREADY_EXISTS, ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS},
+        System::Diagnostics::Etw::{
+            CloseTrace, ControlTraceW, EnableTraceEx2, OpenTraceW, ProcessTrace, StartTraceW,
+            TdhGetEventInformation, TdhGetProperty, TdhGetPropertySize,
+            EVENT_CONTROL_CODE_ENABLE_PROVIDER, EVENT_RECORD, EVENT_TRACE_CONTROL_STOP,
+            EVENT_TRACE_LOGFILEW, EVENT_TRACE_PROPERTIES, EVENT_TRACE_REAL_TIME_MODE,
+            PROCESS_TRACE_MODE_EVENT_RECORD, PROCESS_TRACE_MODE_REAL_TIME,
+            PROPERTY_DATA_DESCRIPTOR, TRACE_EVENT_INFO, WNODE_FLAG_TRACED_GUID,
+        },
+    },
+};
+
+use super::properties::{decode, filetime_to_datetime, format_guid};
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// `OpenTraceW` returns this handle on failure.
+const INVALID_PROCESSTRACE_HANDLE: u64 = u64::MAX;
+/// Timestamps are reported as `FILETIME`s when the session uses the system time clock.
+const CLOCK_SYSTEM_TIME: u32 = 2;
+/// Properties flagged as structures or with a variable count are not decoded.
+const PROPERTY_STRUCT: u32 = 0x1;
+const PROPERTY_PARAM_COUNT: u32 = 0x4;
+
+/// A provider enabled in the trace session.
+pub(super) struct Provider {
+    pub(super) guid: GUID,
+    pub(super) level: u8,
+    pub(super) match_any_keyword: u64,
+    pub(super) match_all_keyword: u64,
+}
+
+/// A real-time trace session, which is stopped when dropped.
+pub(super) struct Session {
+    name: Vec<u16>,
+    handle: u64,
+}
+
+// The session handle can be used from any thread.
+unsafe impl Send for Session {}
+unsafe impl Sync for Session {}
+
+impl Session {
+    /// Starts the session and enables the providers in it. A session left over with the same
+    /// name, for example after a crash, is stopped first.
+    pub(super) fn start(
+        name: &str,
+        buffer_size_kb: u32,
+        providers: &[Provider],
+    ) -> io::Result<Self> {
+        let name = to_wide(name);
+
+        let mut properties = Properties::new(&name);
+        properties.inner.BufferSize = buffer_size_kb;
+        let mut handle = 0u64;
+        let mut status =
+            unsafe { StartTraceW(&mut handle, name.as_ptr(), properties.as_mut_ptr()) };
+        if status == ERROR_ALREADY_EXISTS {
+            stop(&name);
+            let mut properties = Properties::new(&name);
+            properties.inner.BufferSize = buffer_size_kb;
+            status = unsafe { StartTraceW(&mut handle, name.as_ptr(), properties.as_mut_ptr()) };
+        }
+        check(status)?;
+
+        let session = Self { name, handle };
+        for provider in providers {
+            check(unsafe {
+                EnableTraceEx2(
+                    session.handle,
+                    &provider.guid,
+                    EVENT_CONTROL_CODE_ENABLE_PROVIDER,
+                    provider.level,
+                    provider.match_any_keyword,
+                    provider.match_all_keyword,
+                    0,
+                    ptr::null(),
+                )
+            })?;
+        }
+
+        Ok(session)
+    }
+
+    /// Consumes the events of the session until it is stopped, sending each of them to `out`.
+    /// This blocks the calling thread.
+    pub(super) fn process(&self, out: mpsc::Sender<LogEvent>) -> io::Result<()> {
+        let mut context = Context { out };
+
+        let mut logfile: EVENT_TRACE_LOGFILEW = unsafe { mem::zeroed() };
+        logfile.LoggerName = self.name.as_ptr() as *mut u16;
+        logfile.Anonymous1.ProcessTraceMode =
+            PROCESS_TRACE_MODE_REAL_TIME | PROCESS_TRACE_MODE_EVENT_RECORD;
+        logfile.Anonymous2.EventRecordCallback = Some(event_record_callback);
+        logfile.Context = &mut context as *mut Context as *mut _;
+
+        let trace = unsafe { OpenTraceW(&mut logfile) };
+        if trace == INVALID_PROCESSTRACE_HANDLE {
+            return Err(io::Error::last_os_error());
+        }
+
+        let status = unsafe { ProcessTrace(&trace, 1, ptr::null(), ptr::null()) };
+        unsafe { CloseTrace(trace) };
+        check(status)
+    }
+
+    /// Stops the session, which makes `Session::process` return.
+    pub(super) fn stop(&self) {
+        stop(&self.name);
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// State passed to the event callback.
+struct Context {
+    out: mpsc::Sender<LogEvent>,
+}
+
+unsafe extern "system" fn event_record_callback(record: *mut EVENT_RECORD) {
+    let record = &*record;
+    let context = &*(record.UserContext as *const Context);
+    // The channel is only closed while the source shuts down.
+    let _ = context.out.blocking_send(parse_record(record));
+}
+
+/// `EVENT_TRACE_PROPERTIES` followed by the space for the session name, as required by
+/// `StartTraceW` and `ControlTraceW`.
+#[repr(C)]
+struct Properties {
+    inner: EVENT_TRACE_PROPERTIES,
+    name: [u16; 1024],
+}
+
+impl Properties {
+    fn new(name: &[u16]) -> Box<Self> {
+        let mut properties: Box<Self> = Box::new(unsafe { mem::zeroed() });
+        properties.inner.Wnode.BufferSize = mem::size_of::<Self>() as u32;
+        properties.inner.Wnode.ClientContext = CLOCK_SYSTEM_TIME;
+        properties.inner.Wnode.Flags = WNODE_FLAG_TRACED_GUID;
+        properties.inner.LogFileMode = EVENT_TRACE_REAL_TIME_MODE;
+        properties.inner.LoggerNameOffset = mem::size_of::<EVENT_TRACE_PROPERTIES>() as u32;
+        let len = name.len().min(properties.name.len() - 1);
+        properties.name[..len].copy_from_slice(&name[..len]);
+        properties
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut EVENT_TRACE_PROPERTIES {
+        &mut self.inner
+    }
+}
+
+fn stop(name: &[u16]) {
+    let mut properties = Properties::new(name);
+    unsafe {
+        ControlTraceW(
+            0,
+            name.as_ptr(),
+            properties.as_mut_ptr(),
+            EVENT_TRACE_CONTROL_STOP,
+        )
+    };
+}
+
+/// Builds a log event from the header of the record, and the properties decoded with the
+/// schema of the provider, if it's registered on the host.
+unsafe fn parse_record(record: &EVENT_RECORD) -> LogEvent {
+    let header = &record.EventHeader;
+    let descriptor = &header.EventDescriptor;
+
+    let mut log = LogEvent::default();
+    log.insert("provider_guid", guid_to_string(&header.ProviderId));
+    log.insert("event_id", descriptor.Id as i64);
+    log.insert("version", descriptor.Version as i64);
+    log.insert("level", descriptor.Level as i64);
+    log.insert("opcode", descriptor.Opcode as i64);
+    log.insert("task", descriptor.Task as i64);
+    log.insert("keywords", format!("0x{:X}", descriptor.Keyword));
+    log.insert("process_id", header.ProcessId as i64);
+    log.insert("thread_id", header.ThreadId as i64);
+    let activity_id = guid_to_string(&header.ActivityId);
+    if activity_id != "{00000000-0000-0000-0000-000000000000}" {
+        log.insert("activity_id", activity_id);
+    }
+    if let Some(timestamp) = filetime_to_datetime(header.TimeStamp) {
+        log.insert(log_schema().timestamp_key(), timestamp);
+    }
+
+    if let Some(buffer) = event_information(record) {
+        let info_ref = &*(buffer.as_ptr() as *const TRACE_EVENT_INFO);
+        let info = slice::from_raw_parts(buffer.as_ptr() as *const u8, buffer.len() * 8);
+        for (field, offset) in [
+            ("provider_name", info_ref.ProviderNameOffset),
+            ("task_name", info_ref.TaskNameOffset),
+            ("opcode_name", info_ref.OpcodeNameOffset),
+            ("event_name", info_ref.Anonymous1.EventNameOffset),
+        ] {
+            if let Some(name) = string_at(info, offset) {
+                log.insert(field, name);
+            }
+        }
+
+        let properties = slice::from_raw_parts(
+            info_ref.EventPropertyInfoArray.as_ptr(),
+            info_ref.TopLevelPropertyCount as usize,
+        );
+        let mut data = BTreeMap::new();
+        for property in properties {
+            if property.Flags & (PROPERTY_STRUCT | PROPERTY_PARAM_COUNT) != 0 {
+                continue;
+            }
+            let name = match string_at(info, property.NameOffset) {
+                Some(name) => name,
+                None => continue,
+            };
+            let descriptor = PROPERTY_DATA_DESCRIPTOR {
+                PropertyName: info.as_ptr().add(property.NameOffset as usize) as u64,
+                ArrayIndex: u32::MAX,
+                Reserved: 0,
+            };
+            let mut size = 0u32;
+            if TdhGetPropertySize(record, 0, ptr::null(), 1, &descriptor, &mut size)
+                != ERROR_SUCCESS
+            {
+                continue;
+            }
+            let mut buffer = vec![0u8; size as usize];
+            if TdhGetProperty(
+                record,
+                0,
+                ptr::null(),
+                1,
+                &descriptor,
+                size,
+                buffer.as_mut_ptr(),
+            ) != ERROR_SUCCESS
+            {
+                continue;
+            }
+            if let Some(value) = decode(property.Anonymous1.nonStructType.InType, &buffer) {
+                data.insert(name, value);
+            }
+        }
+        log.insert("event_data", Value::Object(data));
+    } else if !record.UserData.is_null() && record.UserDataLength > 0 {
+        // Without a schema, the payload can only be passed along as is.
+        let payload =
+            slice::from_raw_parts(record.UserData as *const u8, record.UserDataLength as usize);
+        log.insert("payload", Bytes::copy_from_slice(payload));
+    }
+
+    log.try_insert(log_schema().source_type_key(), Bytes::from("etw"));
+    log
+}
+
+/// Retrieves the `TRACE_EVENT_INFO` describing the record, which is followed by the strings its
+/// offsets point to. The buffer is made of `u64`s to keep it aligned for `TRACE_EVENT_INFO`.
+unsafe fn event_information(record: &EVENT_RECORD) -> Option<Vec<u64>> {
+    let mut size = 0u32;
+    let status = TdhGetEventInformation(record, 0, ptr::null(), ptr::null_mut(), &mut size);
+    if status != ERROR_INSUFFICIENT_BUFFER {
+        return None;
+    }
+
+    let mut buffer = vec![0u64; (size as usize + 7) / 8];
+    let status = TdhGetEventInformation(
+        record,
+        0,
+        ptr::null(),
+        buffer.as_mut_ptr() as *mut TRACE_EVENT_INFO,
+        &mut size,
+    );
+    (status == ERROR_SUCCESS).then(|| buffer)
+}
+
+/// Reads the nul-terminated UTF-16 string at the given offset of the event information.
+fn string_at(info: &[u8], offset: u32) -> Option<String> {
+    if offset == 0 {
+        return None;
+    }
+    let units = info
+        .get(offset as usize..)?
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect::<Vec<_>>();
+    let string = String::from_utf16_lossy(&units);
+    let string = string.trim();
+    (!string.is_empty()).then(|| string.to_owned())
+}
+
+fn guid_to_string(guid: &GUID) -> String {
+    let bytes = unsafe { slice::from_raw_parts(guid as *const GUID as *const u8, 16) };
+    format_guid(bytes).expect("GUIDs are 16 bytes")
+}
+
+fn check(status: u32) -> io::Result<()> {
+    if status == ERROR_SUCCESS {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(status as i32))
+    }
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(Some(0)).collect()
+}
@@ -1,24 +1,27 @@
 use std::{
-    collections::HashSet, convert::TryInto, path::PathBuf, sync::Arc, sync::Mutex, time::Duration,
+    collections::HashSet, convert::TryInto, io, path::PathBuf, sync::Arc, sync::Mutex,
+    time::Duration,
 };
 
 use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
-    Checkpointer, FileFingerprint, FileServer, FingerprintStrategy, Fingerprinter, Line, ReadFrom,
+    CheckpointBackend, Checkpointer, FileFingerprint, FileServer, FingerprintStrategy,
+    Fingerprinter, Line, ReadFrom, CHECKPOINT_FILE_NAME,
 };
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
 use regex::bytes::Regex;
 use snafu::{ResultExt, Snafu};
-use tokio::{sync::oneshot, task::spawn_blocking};
+use tokio::{runtime::Handle, sync::oneshot, task::spawn_blocking};
 use tracing::{Instrument, Span};
 use vector_common::finalizer::OrderedFinalizer;
 use vector_config::configurable_component;
-use vector_core::config::LogNamespace;
+use vector_core::config::{CheckpointStoreConfig, LogNamespace};
 
 use super::util::{EncodingConfig, MultilineConfig};
 use crate::{
+    checkpoint::{self, SharedCheckpointStore},
     config::{
         log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext,
         SourceDescription,
@@ -341,9 +344,18 @@ impl SourceConfig for FileConfig {
 
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
 
+        // The checkpoints stay in the data directory unless another store is configured.
+        let checkpoint_store = match cx.globals.checkpoint_store {
+            CheckpointStoreConfig::File => None,
+            _ => Some(
+                checkpoint::build_store(&cx.globals, self.data_dir.as_ref(), &cx.key).await?,
+            ),
+        };
+
         Ok(file_source(
             self,
             data_dir,
+            checkpoint_store,
             cx.shutdown,
             cx.out,
             acknowledgements,
@@ -363,16 +375,39 @@ impl SourceConfig for FileConfig {
     }
 }
 
+/// Persists the checkpoints of the file server to the configured checkpoint store.
+struct StoreBackend {
+    store: SharedCheckpointStore,
+    handle: Handle,
+}
+
+impl CheckpointBackend for StoreBackend {
+    fn load(&self) -> io::Result<Option<Vec<u8>>> {
+        self.handle
+            .block_on(self.store.load(CHECKPOINT_FILE_NAME))
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+
+    fn save(&self, checkpoints: &[u8]) -> io::Result<()> {
+        self.handle
+            .block_on(self.store.save(CHECKPOINT_FILE_NAME, checkpoints))
+            .map_err(|error| io::Error::new(io::ErrorKind::Other, error))
+    }
+}
+
 pub fn file_source(
     config: &FileConfig,
     data_dir: PathBuf,
+    checkpoint_store: Option<SharedCheckpointStore>,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
 ) -> super::Source {
-    let ignore_before = config
-        .ignore_older_secs
-        .map(|secs| Utc::now() - chrono::Duration::seconds(secs as i64));
+    // Nothing is ignored if the age goes past the earliest representable time.
+    let ignore_before = config.ignore_older_secs.and_then(|secs| {
+        let age = chrono::Duration::from_std(Duration::from_secs(secs)).ok()?;
+        Utc::now().checked_sub_signed(age)
+    });
     let glob_minimum_cooldown = Duration::from_millis(config.glob_minimum_cooldown_ms);
     let (ignore_checkpoints, read_from) = reconcile_position_options(
         config.start_at_beginning,
@@ -397,7 +432,16 @@ pub fn file_source(
         None => Bytes::from(config.line_delimiter.clone()),
     };
 
-    let checkpointer = Checkpointer::new(&data_dir);
+    let checkpointer = match checkpoint_store {
+        Some(store) => Checkpointer::with_backend(
+            &data_dir,
+            Box::new(StoreBackend {
+                store,
+                handle: Handle::current(),
+            }),
+        ),
+        None => Checkpointer::new(&data_dir),
+    };
     let file_server = FileServer {
         paths_provider,
         max_read_bytes: config.max_read_bytes,
@@ -1932,7 +1976,7 @@ mod tests {
                         )
                 })
             };
-            tokio::spawn(file::file_source(config, data_dir, shutdown, tx, acks));
+            tokio::spawn(file::file_source(config, data_dir, None, shutdown, tx, acks));
 
             inner.await;
 
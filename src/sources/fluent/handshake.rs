@@ -0,0 +1,362 @@
+//! The handshake of the forward protocol, authenticating clients with a shared key and,
+//! optionally, a username and password.
+//!
+//! https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages
+
+use std::io;
+
+use bytes::BytesMut;
+use rand::{thread_rng, Rng};
+use rmpv::Value;
+use sha2::{Digest, Sha512};
+use snafu::Snafu;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use vector_config::configurable_component;
+
+/// The largest `PING` message accepted from a client.
+const MAX_PING_BYTES: usize = 64 * 1024;
+
+/// Shared key authentication of forward protocol clients, matching Fluentd's `security` section.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct FluentSecurityConfig {
+    /// The hostname sent to clients during the handshake.
+    ///
+    /// Defaults to the hostname of the machine Vector is running on.
+    self_hostname: Option<String>,
+
+    /// The key shared with clients, which they must prove to know to connect.
+    shared_key: String,
+
+    /// The users allowed to connect.
+    ///
+    /// If any are set, clients must also authenticate with the username and password of one of them.
+    #[serde(default)]
+    users: Vec<FluentUserConfig>,
+}
+
+/// A user allowed to connect to the `fluent` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct FluentUserConfig {
+    /// The username.
+    username: String,
+
+    /// The password.
+    password: String,
+}
+
+#[derive(Debug, Snafu)]
+pub enum HandshakeError {
+    #[snafu(display("Failed to exchange handshake messages: {}", source))]
+    Io { source: io::Error },
+    #[snafu(display("Invalid PING message: {}", reason))]
+    InvalidPing { reason: &'static str },
+    #[snafu(display("Client {:?} failed to authenticate: {}", hostname, reason))]
+    Unauthenticated {
+        hostname: String,
+        reason: &'static str,
+    },
+}
+
+impl From<io::Error> for HandshakeError {
+    fn from(source: io::Error) -> Self {
+        HandshakeError::Io { source }
+    }
+}
+
+/// The server side of the handshake.
+#[derive(Clone, Debug)]
+pub(super) struct Handshake {
+    hostname: String,
+    shared_key: String,
+    users: Vec<FluentUserConfig>,
+}
+
+/// A client's `PING` message.
+struct Ping {
+    hostname: String,
+    shared_key_salt: Vec<u8>,
+    shared_key_digest: String,
+    username: String,
+    password_digest: String,
+}
+
+impl Handshake {
+    pub(super) fn new(config: &FluentSecurityConfig) -> crate::Result<Self> {
+        let hostname = match &config.self_hostname {
+            Some(hostname) => hostname.clone(),
+            None => crate::get_hostname()?,
+        };
+        Ok(Self {
+            hostname,
+            shared_key: config.shared_key.clone(),
+            users: config.users.clone(),
+        })
+    }
+
+    /// Sends `HELO`, waits for the client's `PING`, and answers with `PONG`. Fails if the client
+    /// didn't authenticate, after letting it know why.
+    pub(super) async fn run<S>(&self, socket: &mut S) -> Result<(), HandshakeError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let nonce = thread_rng().gen::<[u8; 16]>();
+        let auth_salt = (!self.users.is_empty()).then(|| thread_rng().gen::<[u8; 16]>());
+
+        write_message(socket, &self.helo(&nonce, auth_salt.as_ref())).await?;
+        let ping = Ping::try_from(read_message(socket).await?)?;
+
+        let result = self.authenticate(&ping, &nonce, auth_salt.as_ref());
+        let pong = self.pong(&ping, &nonce, result.err());
+        write_message(socket, &pong).await?;
+
+        result.map_err(|reason| HandshakeError::Unauthenticated {
+            hostname: ping.hostname,
+            reason,
+        })
+    }
+
+    fn helo(&self, nonce: &[u8], auth_salt: Option<&[u8; 16]>) -> Value {
+        let auth = match auth_salt {
+            Some(salt) => Value::Binary(salt.to_vec()),
+            None => Value::from(""),
+        };
+        Value::Array(vec![
+            "HELO".into(),
+            Value::Map(vec![
+                ("nonce".into(), Value::Binary(nonce.to_vec())),
+                ("auth".into(), auth),
+                ("keepalive".into(), true.into()),
+            ]),
+        ])
+    }
+
+    fn authenticate(
+        &self,
+        ping: &Ping,
+        nonce: &[u8],
+        auth_salt: Option<&[u8; 16]>,
+    ) -> Result<(), &'static str> {
+        let expected = digest(&[
+            &ping.shared_key_salt,
+            ping.hostname.as_bytes(),
+            nonce,
+            self.shared_key.as_bytes(),
+        ]);
+        if !expected.eq_ignore_ascii_case(&ping.shared_key_digest) {
+            return Err("shared key mismatch");
+        }
+
+        if let Some(auth_salt) = auth_salt {
+            let authenticated = self.users.iter().any(|user| {
+                user.username == ping.username
+                    && digest(&[
+                        auth_salt,
+                        user.username.as_bytes(),
+                        user.password.as_bytes(),
+                    ])
+                    .eq_ignore_ascii_case(&ping.password_digest)
+            });
+            if !authenticated {
+                return Err("username/password mismatch");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn pong(&self, ping: &Ping, nonce: &[u8], failure: Option<&'static str>) -> Value {
+        let shared_key_digest = match failure {
+            Some(_) => String::new(),
+            None => digest(&[
+                &ping.shared_key_salt,
+                self.hostname.as_bytes(),
+                nonce,
+                self.shared_key.as_bytes(),
+            ]),
+        };
+        Value::Array(vec![
+            "PONG".into(),
+            failure.is_none().into(),
+            failure.unwrap_or("").into(),
+            self.hostname.as_str().into(),
+            shared_key_digest.into(),
+        ])
+    }
+}
+
+impl TryFrom<Value> for Ping {
+    type Error = HandshakeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        let fields = match value {
+            Value::Array(fields) if fields.len() == 6 => fields,
+            _ => return Err(invalid("expected an array of 6 elements")),
+        };
+        if fields[0].as_str() != Some("PING") {
+            return Err(invalid("expected a PING message"));
+        }
+        let string = |value: &Value, reason| {
+            value
+                .as_str()
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| invalid(reason))
+        };
+        Ok(Ping {
+            hostname: string(&fields[1], "hostname is not a string")?,
+            shared_key_salt: match &fields[2] {
+                Value::Binary(salt) => salt.clone(),
+                Value::String(salt) => salt.as_bytes().to_vec(),
+                _ => return Err(invalid("shared key salt is not a string")),
+            },
+            shared_key_digest: string(&fields[3], "shared key digest is not a string")?,
+            username: string(&fields[4], "username is not a string")?,
+            password_digest: string(&fields[5], "password digest is not a string")?,
+        })
+    }
+}
+
+const fn invalid(reason: &'static str) -> HandshakeError {
+    HandshakeError::InvalidPing { reason }
+}
+
+/// The hex-encoded SHA-512 digest of the concatenated parts.
+fn digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+async fn write_message<S: AsyncWrite + Unpin>(socket: &mut S, message: &Value) -> io::Result<()> {
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, message)?;
+    socket.write_all(&buf).await?;
+    socket.flush().await
+}
+
+/// Reads a single message. Clients wait for the `PONG` before sending events, so nothing is read
+/// past it.
+async fn read_message<S: AsyncRead + Unpin>(socket: &mut S) -> Result<Value, HandshakeError> {
+    let mut buf = BytesMut::with_capacity(1024);
+    loop {
+        if socket.read_buf(&mut buf).await? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        let mut cursor = io::Cursor::new(&buf[..]);
+        match rmpv::decode::read_value(&mut cursor) {
+            Ok(value) => return Ok(value),
+            Err(
+                rmpv::decode::Error::InvalidMarkerRead(error)
+                | rmpv::decode::Error::InvalidDataRead(error),
+            ) if error.kind() == io::ErrorKind::UnexpectedEof && buf.len() < MAX_PING_BYTES => {}
+            Err(_) => return Err(invalid("not a msgpack value")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::duplex;
+
+    use super::*;
+
+    fn handshake(users: Vec<FluentUserConfig>) -> Handshake {
+        Handshake {
+            hostname: "server".to_owned(),
+            shared_key: "secret".to_owned(),
+            users,
+        }
+    }
+
+    /// Plays the client side, returning the server's `PONG`.
+    async fn client(
+        server: Handshake,
+        shared_key: &str,
+        user: (&str, &str),
+    ) -> (Result<(), HandshakeError>, Vec<Value>) {
+        let (mut client, mut socket) = duplex(4096);
+        let server = tokio::spawn(async move { server.run(&mut socket).await });
+
+        let helo = match read_message(&mut client).await.unwrap() {
+            Value::Array(fields) => fields,
+            value => panic!("unexpected HELO: {}", value),
+        };
+        assert_eq!(helo[0].as_str(), Some("HELO"));
+        let options = helo[1].as_map().unwrap();
+        let option = |name: &str| {
+            options
+                .iter()
+                .find(|(key, _)| key.as_str() == Some(name))
+                .map(|(_, value)| value.clone())
+                .unwrap()
+        };
+        let nonce = option("nonce").as_slice().unwrap().to_vec();
+        let password_digest = match option("auth") {
+            Value::Binary(salt) => digest(&[&salt, user.0.as_bytes(), user.1.as_bytes()]),
+            _ => String::new(),
+        };
+
+        let salt = b"salt";
+        let ping = Value::Array(vec![
+            "PING".into(),
+            "client".into(),
+            Value::Binary(salt.to_vec()),
+            digest(&[salt, b"client", &nonce, shared_key.as_bytes()]).into(),
+            user.0.into(),
+            password_digest.into(),
+        ]);
+        write_message(&mut client, &ping).await.unwrap();
+
+        let pong = match read_message(&mut client).await.unwrap() {
+            Value::Array(fields) => fields,
+            value => panic!("unexpected PONG: {}", value),
+        };
+        if pong[1].as_bool() == Some(true) {
+            assert_eq!(
+                pong[4].as_str().unwrap(),
+                digest(&[salt, b"server", &nonce, shared_key.as_bytes()])
+            );
+        }
+        (server.await.unwrap(), pong)
+    }
+
+    fn user() -> FluentUserConfig {
+        FluentUserConfig {
+            username: "alice".to_owned(),
+            password: "hunter2".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn accepts_shared_key() {
+        let (result, pong) = client(handshake(vec![]), "secret", ("", "")).await;
+        assert!(result.is_ok());
+        assert_eq!(pong[1].as_bool(), Some(true));
+        assert_eq!(pong[3].as_str(), Some("server"));
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_shared_key() {
+        let (result, pong) = client(handshake(vec![]), "guess", ("", "")).await;
+        assert!(matches!(
+            result,
+            Err(HandshakeError::Unauthenticated { .. })
+        ));
+        assert_eq!(pong[1].as_bool(), Some(false));
+        assert_eq!(pong[2].as_str(), Some("shared key mismatch"));
+    }
+
+    #[tokio::test]
+    async fn authenticates_users() {
+        let (result, _) = client(handshake(vec![user()]), "secret", ("alice", "hunter2")).await;
+        assert!(result.is_ok());
+
+        let (result, pong) = client(handshake(vec![user()]), "secret", ("alice", "hunter3")).await;
+        assert!(result.is_err());
+        assert_eq!(pong[2].as_str(), Some("username/password mismatch"));
+    }
+}
@@ -1,13 +1,16 @@
 use std::io::{self, Read};
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use bytes::{Buf, Bytes, BytesMut};
 use codecs::StreamDecodingError;
 use flate2::read::MultiGzDecoder;
+use futures::future::BoxFuture;
 use lookup::path;
 use rmp_serde::{decode, Deserializer};
 use serde::Deserialize;
 use smallvec::{smallvec, SmallVec};
+use tokio::net::TcpStream;
 use tokio_util::codec::Decoder;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
@@ -19,13 +22,16 @@ use crate::{
         SourceConfig, SourceContext, SourceDescription,
     },
     event::{Event, LogEvent},
-    internal_events::{FluentMessageDecodeError, FluentMessageReceived},
+    internal_events::{FluentHandshakeError, FluentMessageDecodeError, FluentMessageReceived},
     serde::bool_or_struct,
     tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsSettings, TlsSourceConfig},
+    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsSourceConfig},
 };
 
+mod handshake;
 mod message;
+pub use self::handshake::HandshakeError;
+use self::handshake::{FluentSecurityConfig, Handshake};
 use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
 
 /// Configuration for the `fluent` source.
@@ -52,6 +58,9 @@ pub struct FluentConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    security: Option<FluentSecurityConfig>,
 }
 
 inventory::submit! {
@@ -67,6 +76,7 @@ impl GenerateConfig for FluentConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
+            security: None,
         })
         .unwrap()
     }
@@ -76,7 +86,13 @@ impl GenerateConfig for FluentConfig {
 #[typetag::serde(name = "fluent")]
 impl SourceConfig for FluentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = FluentSource {};
+        let handshake = self
+            .security
+            .as_ref()
+            .map(Handshake::new)
+            .transpose()?
+            .map(Arc::new);
+        let source = FluentSource { handshake };
         let shutdown_secs = 30;
         let tls_config = self.tls.as_ref().map(|tls| tls.tls_config.clone());
         let tls_client_metadata_key = self
@@ -115,7 +131,9 @@ impl SourceConfig for FluentConfig {
 }
 
 #[derive(Debug, Clone)]
-struct FluentSource;
+struct FluentSource {
+    handshake: Option<Arc<Handshake>>,
+}
 
 impl TcpSource for FluentSource {
     type Error = DecodeError;
@@ -140,6 +158,25 @@ impl TcpSource for FluentSource {
     fn build_acker(&self, frame: &[Self::Item]) -> Self::Acker {
         FluentAcker::new(frame)
     }
+
+    fn handshake<'a>(
+        &'a self,
+        socket: &'a mut MaybeTlsIncomingStream<TcpStream>,
+        peer_addr: SocketAddr,
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        Box::pin(async move {
+            match &self.handshake {
+                Some(handshake) => handshake.run(socket).await.map_err(|error| {
+                    emit!(FluentHandshakeError {
+                        error: &error,
+                        peer_addr
+                    });
+                    error.into()
+                }),
+                None => Ok(()),
+            }
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -881,6 +918,7 @@ mod tests {
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
             connection_limit: None,
+            security: None,
         }
         .build(SourceContext::new_test(sender, None))
         .await
@@ -1130,6 +1168,7 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                security: None,
             }
             .build(SourceContext::new_test(sender, None))
             .await
@@ -1,7 +1,6 @@
 use std::{
     collections::HashMap,
     io::{BufRead, BufReader},
-    net::SocketAddr,
     str::FromStr,
 };
 
@@ -26,7 +25,9 @@ use crate::{
     internal_events::{HerokuLogplexRequestReadError, HerokuLogplexRequestReceived},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     sources::http::HttpMethod,
-    sources::util::{add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+    sources::util::{
+        add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig, SocketListenAddr,
+    },
     tls::TlsEnableableConfig,
 };
 use lookup::path;
@@ -37,7 +38,9 @@ use vector_core::config::LogNamespace;
 #[derive(Clone, Debug)]
 pub struct LogplexConfig {
     /// The address to listen for connections on.
-    address: SocketAddr,
+    ///
+    /// `systemd#N` uses the Nth socket passed by `systemd` socket activation.
+    address: SocketListenAddr,
 
     /// A list of URL query parameters to include in the log event.
     ///
@@ -62,6 +65,21 @@ pub struct LogplexConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    /// The URL path on which drains shall send requests.
+    #[serde(default = "default_path")]
+    path: String,
+
+    /// Whether to respond with `429 Too Many Requests` when downstream components are not keeping up.
+    ///
+    /// By default, requests are held open until the events can be sent downstream. Drains that retry on `429`
+    /// responses can instead back off and resend the frame later.
+    #[serde(default)]
+    reject_when_full: bool,
+}
+
+fn default_path() -> String {
+    "/events".to_string()
 }
 
 inventory::submit! {
@@ -75,13 +93,15 @@ inventory::submit! {
 impl GenerateConfig for LogplexConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "0.0.0.0:80".parse().unwrap(),
+            address: SocketListenAddr::SocketAddr("0.0.0.0:80".parse().unwrap()),
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             acknowledgements: AcknowledgementsConfig::default(),
+            path: default_path(),
+            reject_when_full: false,
         })
         .unwrap()
     }
@@ -91,6 +111,7 @@ impl GenerateConfig for LogplexConfig {
 struct LogplexSource {
     query_parameters: Vec<String>,
     decoder: Decoder,
+    reject_when_full: bool,
 }
 
 impl HttpSource for LogplexSource {
@@ -105,6 +126,10 @@ impl HttpSource for LogplexSource {
         add_query_parameters(&mut events, &self.query_parameters, query_parameters);
         Ok(events)
     }
+
+    fn reject_when_full(&self) -> bool {
+        self.reject_when_full
+    }
 }
 
 #[async_trait::async_trait]
@@ -120,10 +145,11 @@ impl SourceConfig for LogplexConfig {
         let source = LogplexSource {
             query_parameters: self.query_parameters.clone(),
             decoder,
+            reject_when_full: self.reject_when_full,
         };
         source.run(
             self.address,
-            "events",
+            &self.path,
             HttpMethod::Post,
             true,
             &self.tls,
@@ -142,7 +168,7 @@ impl SourceConfig for LogplexConfig {
     }
 
     fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+        vec![self.address.into()]
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -186,10 +212,13 @@ fn decode_message(
     body: Bytes,
     header_map: HeaderMap,
 ) -> Result<Vec<Event>, ErrorMessage> {
-    // Deal with headers
-    let msg_count = match usize::from_str(get_header(&header_map, "Logplex-Msg-Count")?) {
-        Ok(v) => v,
-        Err(e) => return Err(header_error_message("Logplex-Msg-Count", &e.to_string())),
+    // Deal with headers. Logplex always sets these, but other drains may not.
+    let msg_count = match get_header(&header_map, "Logplex-Msg-Count")? {
+        Some(msg_count) => match usize::from_str(msg_count) {
+            Ok(v) => Some(v),
+            Err(e) => return Err(header_error_message("Logplex-Msg-Count", &e.to_string())),
+        },
+        None => None,
     };
     let frame_id = get_header(&header_map, "Logplex-Frame-Id")?;
     let drain_token = get_header(&header_map, "Logplex-Drain-Token")?;
@@ -201,32 +230,48 @@ fn decode_message(
     });
 
     // Deal with body
-    let events = body_to_events(decoder, body);
+    let mut events = body_to_events(decoder, body);
+
+    if let Some(msg_count) = msg_count {
+        if events.len() != msg_count {
+            let error_msg = format!(
+                "Parsed event count does not match message count header: {} vs {}",
+                events.len(),
+                msg_count
+            );
 
-    if events.len() != msg_count {
-        let error_msg = format!(
-            "Parsed event count does not match message count header: {} vs {}",
-            events.len(),
-            msg_count
-        );
+            if cfg!(test) {
+                panic!("{}", error_msg);
+            }
+            return Err(header_error_message("Logplex-Msg-Count", &error_msg));
+        }
+    }
 
-        if cfg!(test) {
-            panic!("{}", error_msg);
+    if let Some(drain_token) = drain_token {
+        for event in &mut events {
+            if let Event::Log(log) = event {
+                LogNamespace::Legacy.insert_source_metadata(
+                    "heroku_logs",
+                    log,
+                    path!("drain_token"),
+                    drain_token.to_owned(),
+                );
+            }
         }
-        return Err(header_error_message("Logplex-Msg-Count", &error_msg));
     }
 
     Ok(events)
 }
 
-fn get_header<'a>(header_map: &'a HeaderMap, name: &str) -> Result<&'a str, ErrorMessage> {
-    if let Some(header_value) = header_map.get(name) {
-        header_value
-            .to_str()
-            .map_err(|e| header_error_message(name, &e.to_string()))
-    } else {
-        Err(header_error_message(name, "Header does not exist"))
-    }
+fn get_header<'a>(header_map: &'a HeaderMap, name: &str) -> Result<Option<&'a str>, ErrorMessage> {
+    header_map
+        .get(name)
+        .map(|header_value| {
+            header_value
+                .to_str()
+                .map_err(|e| header_error_message(name, &e.to_string()))
+        })
+        .transpose()
 }
 
 fn header_error_message(name: &str, msg: &str) -> ErrorMessage {
@@ -237,6 +282,14 @@ fn header_error_message(name: &str, msg: &str) -> ErrorMessage {
 }
 
 fn body_to_events(decoder: Decoder, body: Bytes) -> Vec<Event> {
+    if let Some(frames) = octet_counted_frames(&body) {
+        return frames
+            .into_iter()
+            .map(|frame| String::from_utf8_lossy(frame).into_owned())
+            .flat_map(|frame| frame_to_events(decoder.clone(), &frame))
+            .collect();
+    }
+
     let rdr = BufReader::new(body.reader());
     rdr.lines()
         .filter_map(|res| {
@@ -248,17 +301,61 @@ fn body_to_events(decoder: Decoder, body: Bytes) -> Vec<Event> {
         .collect()
 }
 
-fn line_to_events(mut decoder: Decoder, line: String) -> SmallVec<[Event; 1]> {
-    let parts = line.splitn(8, ' ').collect::<Vec<&str>>();
+/// Splits a body framed with octet counting, as described in RFC 6587, where each syslog frame
+/// is prefixed with its length in bytes and a space.
+///
+/// Returns `None` if the body isn't exactly a sequence of such frames, in which case it's split
+/// into lines instead.
+fn octet_counted_frames(body: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut frames = Vec::new();
+    let mut rest = body;
+    loop {
+        while let [b'\r' | b'\n', tail @ ..] = rest {
+            rest = tail;
+        }
+        if rest.is_empty() {
+            return Some(frames);
+        }
+
+        let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 || rest.get(digits) != Some(&b' ') {
+            return None;
+        }
+        let len = std::str::from_utf8(&rest[..digits])
+            .ok()?
+            .parse::<usize>()
+            .ok()?;
+        let end = (digits + 1).checked_add(len)?;
+        let frame = rest.get(digits + 1..end)?;
+        frames.push(frame);
+        rest = &rest[end..];
+    }
+}
+
+/// Converts a line holding a frame, which may be prefixed with its octet count.
+fn line_to_events(decoder: Decoder, line: String) -> SmallVec<[Event; 1]> {
+    // The octet count isn't reliable once the body has been split into lines, so it's ignored.
+    let frame = match line.split_once(' ') {
+        Some((count, frame)) if count.bytes().all(|b| b.is_ascii_digit()) && !count.is_empty() => {
+            frame
+        }
+        _ => line.as_str(),
+    };
+    frame_to_events(decoder, frame)
+}
+
+fn frame_to_events(mut decoder: Decoder, frame: &str) -> SmallVec<[Event; 1]> {
+    let frame = frame.trim_end_matches(|c| c == '\r' || c == '\n');
+    let parts = frame.splitn(7, ' ').collect::<Vec<&str>>();
 
     let mut events = SmallVec::<[Event; 1]>::new();
 
-    if parts.len() == 8 {
-        let timestamp = parts[2];
-        let hostname = parts[3];
-        let app_name = parts[4];
-        let proc_id = parts[5];
-        let message = parts[7];
+    if parts.len() == 7 {
+        let timestamp = parts[1];
+        let hostname = parts[2];
+        let app_name = parts[3];
+        let proc_id = parts[4];
+        let message = parts[6];
 
         let mut buffer = BytesMut::new();
         buffer.put(message.as_bytes());
@@ -296,7 +393,7 @@ fn line_to_events(mut decoder: Decoder, line: String) -> SmallVec<[Event; 1]> {
             internal_log_rate_secs = 10
         );
 
-        events.push(LogEvent::from_str_legacy(line).into())
+        events.push(LogEvent::from_str_legacy(frame).into())
     };
 
     let now = Utc::now();
@@ -320,8 +417,12 @@ mod tests {
     use pretty_assertions::assert_eq;
     use vector_core::event::{Event, EventStatus, Value};
 
+    use vector_core::config::LogNamespace;
+    use warp::http::HeaderMap;
+
     use super::{HttpSourceAuthConfig, LogplexConfig};
     use crate::{
+        codecs::{Decoder, DecodingConfig},
         config::{log_schema, SourceConfig, SourceContext},
         serde::{default_decoding, default_framing_message_based},
         test_util::{
@@ -347,13 +448,15 @@ mod tests {
         let context = SourceContext::new_test(sender, None);
         tokio::spawn(async move {
             LogplexConfig {
-                address,
+                address: address.into(),
                 query_parameters,
                 tls: None,
                 auth,
                 framing: default_framing_message_based(),
                 decoding: default_decoding(),
                 acknowledgements: acknowledgements.into(),
+                path: super::default_path(),
+                reject_when_full: false,
             }
             .build(context)
             .await
@@ -503,6 +606,104 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn logplex_rejects_when_full() {
+        let (sender, _rx) = SourceSender::new_with_buffer(1);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move {
+            LogplexConfig {
+                address: address.into(),
+                query_parameters: vec![],
+                tls: None,
+                auth: None,
+                framing: default_framing_message_based(),
+                decoding: default_decoding(),
+                acknowledgements: false.into(),
+                path: super::default_path(),
+                reject_when_full: true,
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+
+        assert_eq!(200, send(address, SAMPLE_BODY, None, "").await);
+        assert_eq!(429, send(address, SAMPLE_BODY, None, "").await);
+    }
+
+    fn message_decoder() -> Decoder {
+        DecodingConfig::new(
+            default_framing_message_based(),
+            default_decoding(),
+            LogNamespace::Legacy,
+        )
+        .build()
+    }
+
+    #[test]
+    fn logplex_handles_octet_counted_frames() {
+        let frames = [
+            "<40>1 2012-11-30T06:45:29+00:00 host app web.3 - first\nline\n",
+            "<40>1 2012-11-30T06:45:30+00:00 host app web.3 - second",
+        ];
+        let body = frames
+            .iter()
+            .map(|frame| format!("{} {}", frame.len(), frame))
+            .collect::<String>();
+
+        let events = super::body_to_events(message_decoder(), body.into());
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "first\nline".into()
+        );
+        assert_eq!(
+            events[1].as_log()[log_schema().message_key()],
+            "second".into()
+        );
+        assert_eq!(events[1].as_log()["proc_id"], "web.3".into());
+    }
+
+    #[test]
+    fn logplex_rejects_overflowing_octet_counts() {
+        let body = format!("{} <40>1 2012-11-30T06:45:29+00:00 host app web.3 - first", usize::MAX);
+        assert_eq!(super::octet_counted_frames(body.as_bytes()), None);
+    }
+
+    #[test]
+    fn logplex_handles_frames_without_octet_counts() {
+        let body = "<40>1 2012-11-30T06:45:29+00:00 host app web.3 - first\n\
+                    <40>1 2012-11-30T06:45:30+00:00 host app web.3 - second\n";
+
+        let events = super::body_to_events(message_decoder(), body.into());
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "first".into()
+        );
+        assert_eq!(events[1].as_log()["app_name"], "app".into());
+    }
+
+    #[test]
+    fn logplex_adds_drain_token_and_accepts_missing_headers() {
+        let body = "<40>1 2012-11-30T06:45:29+00:00 host app web.3 - hello";
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Logplex-Drain-Token", "d.token".parse().unwrap());
+        let events = super::decode_message(message_decoder(), body.into(), headers).unwrap();
+        assert_eq!(events[0].as_log()["drain_token"], "d.token".into());
+
+        let events =
+            super::decode_message(message_decoder(), body.into(), HeaderMap::new()).unwrap();
+        assert!(events[0].as_log().get("drain_token").is_none());
+    }
+
     #[test]
     fn logplex_handles_normal_lines() {
         let body = "267 <158>1 2020-01-08T22:33:57.353034+00:00 host heroku router - foo bar baz";
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::collections::{HashMap, HashSet};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -24,6 +24,7 @@ use crate::{
     serde::{bool_or_struct, default_decoding},
     sources::util::{
         add_query_parameters, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        SocketListenAddr,
     },
     tls::TlsEnableableConfig,
 };
@@ -59,7 +60,9 @@ pub enum HttpMethod {
 #[derive(Clone, Debug)]
 pub struct SimpleHttpConfig {
     /// The address to listen for connections on.
-    address: SocketAddr,
+    ///
+    /// `systemd#N` uses the Nth socket passed by `systemd` socket activation.
+    address: SocketListenAddr,
 
     /// The expected encoding of received data.
     ///
@@ -116,6 +119,48 @@ pub struct SimpleHttpConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    /// Additional URL paths to accept events on, each with its own decoding and output.
+    ///
+    /// Requests are matched against the routes in the order they are configured, and then against
+    /// `path`, whose events are sent to the default output.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    routes: Vec<HttpRouteConfig>,
+}
+
+/// A URL path accepted by the `http` source in addition to `path`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct HttpRouteConfig {
+    /// The URL path on which events for this route shall be sent.
+    path: String,
+
+    /// Whether or not to treat the route's `path` as an absolute path.
+    ///
+    /// If set to `true`, only requests using the exact URL path specified in `path` are matched. Otherwise, requests
+    /// sent to a URL path that starts with the value of `path` are matched.
+    #[serde(default = "crate::serde::default_true")]
+    strict_path: bool,
+
+    /// The name of the output that events received on this route are sent to.
+    ///
+    /// Events can be consumed from the output as `<source_id>.<output>`.
+    output: String,
+
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    decoding: Option<DeserializerConfig>,
+}
+
+impl HttpRouteConfig {
+    fn output_type(&self) -> DataType {
+        self.decoding
+            .as_ref()
+            .map(|d| d.output_type())
+            .unwrap_or(DataType::Log)
+    }
 }
 
 inventory::submit! {
@@ -125,7 +170,7 @@ inventory::submit! {
 impl GenerateConfig for SimpleHttpConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "0.0.0.0:8080".parse().unwrap(),
+            address: SocketListenAddr::SocketAddr("0.0.0.0:8080".parse().unwrap()),
             encoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
@@ -138,6 +183,7 @@ impl GenerateConfig for SimpleHttpConfig {
             framing: None,
             decoding: Some(default_decoding()),
             acknowledgements: AcknowledgementsConfig::default(),
+            routes: Vec::new(),
         })
         .unwrap()
     }
@@ -156,7 +202,39 @@ struct SimpleHttpSource {
     headers: Vec<String>,
     query_parameters: Vec<String>,
     path_key: String,
+    routes: Vec<Route>,
+}
+
+/// A path events are accepted on, with the last route being the source's own `path`.
+#[derive(Clone)]
+struct Route {
+    path: String,
+    strict_path: bool,
     decoder: Decoder,
+    output: Option<String>,
+}
+
+impl Route {
+    /// Whether a request path matches the route, comparing path segments the way the HTTP server
+    /// does.
+    fn matches(&self, request_path: &str) -> bool {
+        let mut expected = self.path.split('/').filter(|s| !s.is_empty());
+        let mut actual = request_path.split('/').filter(|s| !s.is_empty());
+        loop {
+            match (expected.next(), actual.next()) {
+                (Some(expected), Some(actual)) if expected == actual => {}
+                (Some(_), _) => return false,
+                (None, None) => return true,
+                (None, Some(_)) => return !self.strict_path,
+            }
+        }
+    }
+}
+
+impl SimpleHttpSource {
+    fn route(&self, request_path: &str) -> Option<&Route> {
+        self.routes.iter().find(|route| route.matches(request_path))
+    }
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -167,7 +245,10 @@ impl HttpSource for SimpleHttpSource {
         query_parameters: HashMap<String, String>,
         request_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        let mut decoder = self.decoder.clone();
+        let route = self
+            .route(request_path)
+            .ok_or_else(|| ErrorMessage::new(StatusCode::NOT_FOUND, "Not found".to_string()))?;
+        let mut decoder = route.decoder.clone();
         let mut events = Vec::new();
         let mut bytes = BytesMut::new();
         bytes.extend_from_slice(&body);
@@ -201,6 +282,10 @@ impl HttpSource for SimpleHttpSource {
 
         Ok(events)
     }
+
+    fn output(&self, path: &str) -> Option<String> {
+        self.route(path).and_then(|route| route.output.clone())
+    }
 }
 
 #[async_trait::async_trait]
@@ -239,18 +324,61 @@ impl SourceConfig for SimpleHttpConfig {
             (framing, decoding)
         };
 
-        let decoder = DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build();
+        let mut outputs = HashSet::new();
+        let mut routes = Vec::with_capacity(self.routes.len() + 1);
+        for route in &self.routes {
+            if route.output.is_empty() || route.output.contains('.') {
+                return Err(format!(
+                    "The output of the route for path {:?} must be a non-empty name without a \".\".",
+                    route.path
+                )
+                .into());
+            }
+            if !outputs.insert(route.output.as_str()) {
+                return Err(format!(
+                    "The output {:?} is used by more than one route.",
+                    route.output
+                )
+                .into());
+            }
+            let decoding = route.decoding.clone().unwrap_or_else(default_decoding);
+            let framing = route
+                .framing
+                .clone()
+                .unwrap_or_else(|| decoding.default_stream_framing());
+            routes.push(Route {
+                path: route.path.clone(),
+                strict_path: route.strict_path,
+                decoder: DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build(),
+                output: Some(route.output.clone()),
+            });
+        }
+        routes.push(Route {
+            path: self.path.clone(),
+            strict_path: self.strict_path,
+            decoder: DecodingConfig::new(framing, decoding, LogNamespace::Legacy).build(),
+            output: None,
+        });
+
+        // With routes, every path is accepted by the server and matched against the routes when
+        // handling the request.
+        let (path, strict_path) = if self.routes.is_empty() {
+            (self.path.as_str(), self.strict_path)
+        } else {
+            ("", false)
+        };
+
         let source = SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
-            decoder,
+            routes,
         };
         source.run(
             self.address,
-            self.path.as_str(),
+            path,
             self.method,
-            self.strict_path,
+            strict_path,
             &self.tls,
             &self.auth,
             cx,
@@ -259,12 +387,18 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(
+        let mut outputs = vec![Output::default(
             self.decoding
                 .as_ref()
                 .map(|d| d.output_type())
                 .unwrap_or(DataType::Log),
-        )]
+        )];
+        outputs.extend(
+            self.routes
+                .iter()
+                .map(|route| Output::default(route.output_type()).with_port(route.output.clone())),
+        );
+        outputs
     }
 
     fn source_type(&self) -> &'static str {
@@ -272,7 +406,7 @@ impl SourceConfig for SimpleHttpConfig {
     }
 
     fn resources(&self) -> Vec<Resource> {
-        vec![Resource::tcp(self.address)]
+        vec![self.address.into()]
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -315,16 +449,18 @@ mod tests {
         write::{GzEncoder, ZlibEncoder},
         Compression,
     };
-    use futures::Stream;
+    use futures::{Stream, StreamExt};
     use http::{HeaderMap, Method};
     use pretty_assertions::assert_eq;
+    use vector_core::config::LogNamespace;
 
     use super::SimpleHttpConfig;
     use crate::sources::http::HttpMethod;
     use crate::{
         config::{log_schema, SourceConfig, SourceContext},
-        event::{Event, EventStatus, Value},
+        event::{into_event_stream, Event, EventStatus, Value},
         test_util::{
+            collect_n,
             components::{self, assert_source_compliance, HTTP_PUSH_SOURCE_TAGS},
             next_addr, spawn_collect_n, trace_init, wait_for_tcp,
         },
@@ -363,7 +499,7 @@ mod tests {
 
         tokio::spawn(async move {
             SimpleHttpConfig {
-                address,
+                address: address.into(),
                 headers,
                 encoding: None,
                 query_parameters,
@@ -376,6 +512,7 @@ mod tests {
                 framing,
                 decoding,
                 acknowledgements: acknowledgements.into(),
+                routes: Vec::new(),
             }
             .build(context)
             .await
@@ -1031,6 +1168,56 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn http_routes() {
+        components::init_test();
+        let (mut sender, default_rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let ndjson_rx = sender
+            .add_outputs(EventStatus::Delivered, "ndjson".to_string())
+            .flat_map(into_event_stream);
+        let address = next_addr();
+        let config = toml::from_str::<SimpleHttpConfig>(&format!(
+            r#"
+            address = "{}"
+            path = "/raw"
+
+            [[routes]]
+            path = "/ndjson"
+            output = "ndjson"
+            framing.method = "newline_delimited"
+            decoding.codec = "json"
+            "#,
+            address
+        ))
+        .unwrap();
+        assert_eq!(config.outputs(LogNamespace::Legacy).len(), 2);
+
+        let context = SourceContext::new_test(sender, None);
+        tokio::spawn(async move { config.build(context).await.unwrap().await.unwrap() });
+        wait_for_tcp(address).await;
+
+        assert_eq!(
+            200,
+            send_with_path(address, "{\"key\":\"a\"}\n{\"key\":\"b\"}", "/ndjson").await
+        );
+        assert_eq!(
+            200,
+            send_with_path(address, "{\"key\":\"c\"}", "/raw").await
+        );
+        assert_eq!(404, send_with_path(address, "{}", "/other").await);
+
+        let routed = collect_n(ndjson_rx, 2).await;
+        assert_eq!(routed[0].as_log()["key"], "a".into());
+        assert_eq!(routed[1].as_log()["key"], "b".into());
+        assert_eq!(routed[1].as_log()["path"], "/ndjson".into());
+
+        let raw = collect_n(default_rx, 1).await;
+        assert_eq!(
+            raw[0].as_log()[log_schema().message_key()],
+            "{\"key\":\"c\"}".into()
+        );
+    }
+
     #[tokio::test]
     async fn http_delivery_failure() {
         assert_source_compliance(&HTTP_PUSH_SOURCE_TAGS, async {
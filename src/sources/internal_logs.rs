@@ -1,14 +1,18 @@
 use bytes::Bytes;
 use chrono::Utc;
 use futures::{stream, StreamExt};
+use lookup::path;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 use vector_core::ByteSizeOf;
 
 use crate::{
     config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
-    event::Event,
-    internal_events::{InternalLogsBytesReceived, InternalLogsEventsReceived, StreamClosedError},
+    event::{Event, Value},
+    internal_events::{
+        InternalLogsBytesReceived, InternalLogsEventSampled, InternalLogsEventsReceived,
+        StreamClosedError,
+    },
     shutdown::ShutdownSignal,
     trace::TraceSubscription,
     SourceSender,
@@ -32,6 +36,86 @@ pub struct InternalLogsConfig {
     ///
     /// By default, `"pid"` is used.
     pub pid_key: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub sample_rates: LevelSampleRates,
+}
+
+/// The rates at which internal log events are forwarded, for each level.
+///
+/// Each rate is expressed as `1/N`. For example, `info = 10` means 1 out of every 10 `INFO` events will be forwarded
+/// and the rest will be dropped. By default, all events are forwarded.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct LevelSampleRates {
+    /// The rate at which `ERROR` events are forwarded.
+    pub error: u64,
+
+    /// The rate at which `WARN` events are forwarded.
+    pub warn: u64,
+
+    /// The rate at which `INFO` events are forwarded.
+    pub info: u64,
+
+    /// The rate at which `DEBUG` events are forwarded.
+    pub debug: u64,
+
+    /// The rate at which `TRACE` events are forwarded.
+    pub trace: u64,
+}
+
+impl Default for LevelSampleRates {
+    fn default() -> Self {
+        Self {
+            error: 1,
+            warn: 1,
+            info: 1,
+            debug: 1,
+            trace: 1,
+        }
+    }
+}
+
+/// Samples internal log events by level.
+struct LevelSampler {
+    rates: [u64; 5],
+    counts: [u64; 5],
+}
+
+impl LevelSampler {
+    fn new(rates: &LevelSampleRates) -> crate::Result<Self> {
+        let rates = [
+            rates.error,
+            rates.warn,
+            rates.info,
+            rates.debug,
+            rates.trace,
+        ];
+        if rates.contains(&0) {
+            return Err("`sample_rates` must be non-zero".into());
+        }
+        Ok(Self {
+            rates,
+            counts: [0; 5],
+        })
+    }
+
+    /// Whether the next event of the level should be forwarded.
+    fn sample(&mut self, level: &[u8]) -> bool {
+        let index = match level {
+            b"ERROR" => 0,
+            b"WARN" => 1,
+            b"INFO" => 2,
+            b"DEBUG" => 3,
+            b"TRACE" => 4,
+            _ => return true,
+        };
+        let count = self.counts[index];
+        self.counts[index] = (count + 1) % self.rates[index];
+        count == 0
+    }
 }
 
 inventory::submit! {
@@ -51,11 +135,14 @@ impl SourceConfig for InternalLogsConfig {
             .to_owned();
         let pid_key = self.pid_key.as_deref().unwrap_or("pid").to_owned();
 
+        let sampler = LevelSampler::new(&self.sample_rates)?;
+
         let subscription = TraceSubscription::subscribe();
 
         Ok(Box::pin(run(
             host_key,
             pid_key,
+            sampler,
             subscription,
             cx.out,
             cx.shutdown,
@@ -78,6 +165,7 @@ impl SourceConfig for InternalLogsConfig {
 async fn run(
     host_key: String,
     pid_key: String,
+    mut sampler: LevelSampler,
     mut subscription: TraceSubscription,
     mut out: SourceSender,
     shutdown: ShutdownSignal,
@@ -96,6 +184,16 @@ async fn run(
     // any logs that don't break the loop, as that could cause an
     // infinite loop since it receives all such logs.
     while let Some(mut log) = rx.next().await {
+        let sampled = log
+            .get(path!("metadata", "level"))
+            .and_then(Value::as_bytes)
+            .map_or(true, |level| sampler.sample(level));
+        if !sampled {
+            // This event doesn't emit any log
+            emit!(InternalLogsEventSampled);
+            continue;
+        }
+
         let byte_size = log.size_of();
         // This event doesn't emit any log
         emit!(InternalLogsBytesReceived { byte_size });
@@ -228,6 +326,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn samples_by_level() {
+        let mut sampler = LevelSampler::new(&LevelSampleRates {
+            info: 3,
+            debug: 2,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let forwarded = |sampler: &mut LevelSampler, level: &[u8]| {
+            (0..6).filter(|_| sampler.sample(level)).count()
+        };
+        assert_eq!(forwarded(&mut sampler, b"ERROR"), 6);
+        assert_eq!(forwarded(&mut sampler, b"INFO"), 2);
+        assert_eq!(forwarded(&mut sampler, b"DEBUG"), 3);
+        assert_eq!(forwarded(&mut sampler, b"UNKNOWN"), 6);
+    }
+
+    #[test]
+    fn rejects_zero_sample_rate() {
+        assert!(LevelSampler::new(&LevelSampleRates {
+            trace: 0,
+            ..Default::default()
+        })
+        .is_err());
+    }
+
     async fn start_source() -> impl Stream<Item = Event> {
         let (tx, rx) = SourceSender::new_test();
 
@@ -1,6 +1,5 @@
 use std::{
     collections::{HashMap, HashSet},
-    io::SeekFrom,
     iter::FromIterator,
     path::PathBuf,
     process::Stdio,
@@ -10,6 +9,7 @@ use std::{
 };
 
 use crate::{
+    checkpoint::{self, SharedCheckpointStore},
     config::{
         log_schema, AcknowledgementsConfig, DataType, Output, SourceConfig, SourceContext,
         SourceDescription,
@@ -37,8 +37,7 @@ use once_cell::sync::Lazy;
 use serde_json::{Error as JsonError, Value as JsonValue};
 use snafu::{ResultExt, Snafu};
 use tokio::{
-    fs::{File, OpenOptions},
-    io::{self, AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
+    io,
     process::{Child, Command},
     sync::{oneshot, Mutex, MutexGuard},
     time::sleep,
@@ -196,10 +195,8 @@ impl SourceConfig for JournaldConfig {
             warn!("Option `remap_priority` has been deprecated. Please use the `remap` transform and function `to_syslog_level` instead.");
         }
 
-        let data_dir = cx
-            .globals
-            // source are only global, name can be used for subdir
-            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+        let checkpoints =
+            checkpoint::build_store(&cx.globals, self.data_dir.as_ref(), &cx.key).await?;
 
         if let Some(unit) = self
             .include_units
@@ -217,9 +214,6 @@ impl SourceConfig for JournaldConfig {
             return Err(BuildError::DuplicatedMatches { field, value }.into());
         }
 
-        let mut checkpoint_path = data_dir;
-        checkpoint_path.push(CHECKPOINT_FILENAME);
-
         let journalctl_path = self
             .journalctl_path
             .clone()
@@ -239,7 +233,7 @@ impl SourceConfig for JournaldConfig {
             JournaldSource {
                 include_matches,
                 exclude_matches,
-                checkpoint_path,
+                checkpoints,
                 batch_size,
                 remap_priority: self.remap_priority,
                 out: cx.out,
@@ -266,7 +260,7 @@ impl SourceConfig for JournaldConfig {
 struct JournaldSource {
     include_matches: Matches,
     exclude_matches: Matches,
-    checkpoint_path: PathBuf,
+    checkpoints: SharedCheckpointStore,
     batch_size: usize,
     remap_priority: bool,
     out: SourceSender,
@@ -276,14 +270,10 @@ struct JournaldSource {
 
 impl JournaldSource {
     async fn run_shutdown(self, shutdown: ShutdownSignal) -> Result<(), ()> {
-        let checkpointer = StatefulCheckpointer::new(self.checkpoint_path.clone())
+        let checkpointer = StatefulCheckpointer::new(Arc::clone(&self.checkpoints))
             .await
             .map_err(|error| {
-                error!(
-                    message = "Unable to open checkpoint file.",
-                    path = ?self.checkpoint_path,
-                    %error,
-                );
+                error!(message = "Unable to read journald checkpoint.", %error);
             })?;
 
         let checkpointer = SharedCheckpointer::new(checkpointer);
@@ -740,40 +730,27 @@ impl Finalizer {
 }
 
 struct Checkpointer {
-    file: File,
-    filename: PathBuf,
+    store: SharedCheckpointStore,
 }
 
 impl Checkpointer {
-    async fn new(filename: PathBuf) -> Result<Self, io::Error> {
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .open(&filename)
-            .await?;
-        Ok(Checkpointer { file, filename })
-    }
-
-    async fn set(&mut self, token: &str) -> Result<(), io::Error> {
-        self.file.seek(SeekFrom::Start(0)).await?;
-        self.file.write_all(format!("{}\n", token).as_bytes()).await
-    }
-
-    async fn get(&mut self) -> Result<Option<String>, io::Error> {
-        let mut buf = Vec::<u8>::new();
-        self.file.seek(SeekFrom::Start(0)).await?;
-        self.file.read_to_end(&mut buf).await?;
-        match buf.len() {
-            0 => Ok(None),
-            _ => {
-                let text = String::from_utf8_lossy(&buf);
-                match text.find('\n') {
-                    Some(nl) => Ok(Some(String::from(&text[..nl]))),
-                    None => Ok(None), // Maybe return an error?
-                }
-            }
-        }
+    fn new(store: SharedCheckpointStore) -> Self {
+        Checkpointer { store }
+    }
+
+    async fn set(&self, token: &str) -> crate::Result<()> {
+        self.store
+            .save(CHECKPOINT_FILENAME, format!("{}\n", token).as_bytes())
+            .await
+    }
+
+    async fn get(&self) -> crate::Result<Option<String>> {
+        let checkpoint = self.store.load(CHECKPOINT_FILENAME).await?;
+        Ok(checkpoint.and_then(|buf| {
+            let text = String::from_utf8_lossy(&buf);
+            // Maybe return an error if there is no newline?
+            text.find('\n').map(|nl| String::from(&text[..nl]))
+        }))
     }
 }
 
@@ -783,8 +760,8 @@ struct StatefulCheckpointer {
 }
 
 impl StatefulCheckpointer {
-    async fn new(filename: PathBuf) -> Result<Self, io::Error> {
-        let mut checkpointer = Checkpointer::new(filename).await?;
+    async fn new(store: SharedCheckpointStore) -> crate::Result<Self> {
+        let checkpointer = Checkpointer::new(store);
         let cursor = checkpointer.get().await?;
         Ok(Self {
             checkpointer,
@@ -794,11 +771,7 @@ impl StatefulCheckpointer {
 
     async fn set(&mut self, token: String) {
         if let Err(error) = self.checkpointer.set(&token).await {
-            error!(
-                message = "Could not set journald checkpoint.",
-                %error,
-                filename = ?self.checkpointer.filename,
-            );
+            error!(message = "Could not set journald checkpoint.", %error);
         }
         self.cursor = Some(token);
     }
@@ -823,6 +796,7 @@ mod checkpointer_tests {
     use tokio::fs::read_to_string;
 
     use super::*;
+    use crate::checkpoint::FileCheckpointStore;
 
     #[test]
     fn generate_config() {
@@ -834,9 +808,9 @@ mod checkpointer_tests {
         let tempdir = tempdir().unwrap();
         let mut filename = tempdir.path().to_path_buf();
         filename.push(CHECKPOINT_FILENAME);
-        let mut checkpointer = Checkpointer::new(filename.clone())
-            .await
-            .expect("Creating checkpointer failed!");
+        let checkpointer = Checkpointer::new(Arc::new(FileCheckpointStore::new(
+            tempdir.path().to_path_buf(),
+        )));
 
         assert!(checkpointer.get().await.unwrap().is_none());
 
@@ -872,7 +846,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        config::ComponentKey, event::Event, event::EventStatus,
+        checkpoint::FileCheckpointStore, config::ComponentKey, event::Event, event::EventStatus,
         test_util::components::assert_source_compliance,
     };
 
@@ -900,11 +874,9 @@ mod tests {
                 let mut checkpoint_path = tempdir.clone();
                 checkpoint_path.push(TEST_COMPONENT);
                 fs::create_dir(&checkpoint_path).unwrap();
-                checkpoint_path.push(CHECKPOINT_FILENAME);
 
-                let mut checkpointer = Checkpointer::new(checkpoint_path.clone())
-                    .await
-                    .expect("Creating checkpointer failed!");
+                let checkpointer =
+                    Checkpointer::new(Arc::new(FileCheckpointStore::new(checkpoint_path)));
 
                 checkpointer
                     .set(cursor)
@@ -1108,11 +1080,8 @@ mod tests {
         let mut checkpoint_path = tempdir.clone();
         checkpoint_path.push(TEST_COMPONENT);
         fs::create_dir(&checkpoint_path).unwrap();
-        checkpoint_path.push(CHECKPOINT_FILENAME);
 
-        let mut checkpointer = Checkpointer::new(checkpoint_path.clone())
-            .await
-            .expect("Creating checkpointer failed!");
+        let checkpointer = Checkpointer::new(Arc::new(FileCheckpointStore::new(checkpoint_path)));
 
         let config = JournaldConfig {
             journalctl_path: Some(TEST_JOURNALCTL.into()),
@@ -1,7 +1,9 @@
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     io::Cursor,
+    ops::ControlFlow,
     sync::Arc,
+    time::Duration,
 };
 
 use async_stream::stream;
@@ -11,10 +13,11 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use futures::{Stream, StreamExt};
+use futures::{stream::BoxStream, Future, FutureExt, Stream, StreamExt};
 use rdkafka::{
     config::ClientConfig,
-    consumer::{Consumer, StreamConsumer},
+    consumer::{BaseConsumer, Consumer, StreamConsumer},
+    error::KafkaResult,
     message::{BorrowedMessage, Headers, Message},
 };
 use snafu::{ResultExt, Snafu};
@@ -34,11 +37,12 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaNegativeAcknowledgmentError,
-        KafkaOffsetUpdateError, KafkaReadError, StreamClosedError,
+        KafkaOffsetUpdateError, KafkaPartitionsPauseError, KafkaReadError, StreamClosedError,
     },
     kafka::{KafkaAuthConfig, KafkaStatisticsContext},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
+    sinks::Healthcheck,
     SourceSender,
 };
 
@@ -72,6 +76,25 @@ pub struct KafkaSourceConfig {
     /// The consumer group name to be used to consume events from Kafka.
     group_id: String,
 
+    /// The static membership identifier of this consumer within the consumer group.
+    ///
+    /// When set, the consumer is a static member of the group: it keeps its partitions when it restarts within
+    /// `session_timeout_ms`, instead of triggering a rebalance of the group. It must be unique within the group, and
+    /// stable across restarts of the same instance.
+    group_instance_id: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    rebalance_protocol: RebalanceProtocol,
+
+    /// The time, in milliseconds, after which the assigned partitions are paused when events can't be sent
+    /// downstream.
+    ///
+    /// While paused, the consumer keeps polling the brokers so that it doesn't leave the consumer group, and the
+    /// partitions are resumed once the events are sent. By default, the partitions are never paused, and the consumer
+    /// stops polling while the events can't be sent, which can trigger a rebalance after `max.poll.interval.ms`.
+    backpressure_pause_ms: Option<u64>,
+
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
     /// See the [librdkafka documentation](https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md) for the `auto.offset.reset` option for further clarification.
@@ -157,6 +180,26 @@ pub struct KafkaSourceConfig {
     acknowledgements: AcknowledgementsConfig,
 }
 
+/// The protocol used to rebalance the partitions among the members of the consumer group.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceProtocol {
+    /// All the partitions are revoked from all the members, then reassigned.
+    ///
+    /// This uses the `partition.assignment.strategy` set in `librdkafka_options`, if any.
+    #[derivative(Default)]
+    Eager,
+
+    /// Only the partitions moving to another member are revoked, so that the other members keep consuming during the
+    /// rebalance.
+    ///
+    /// This uses the `cooperative-sticky` partition assignment strategy, which all the members of the consumer group
+    /// must use.
+    Cooperative,
+}
+
 const fn default_session_timeout_ms() -> u64 {
     10000 // default in librdkafka
 }
@@ -237,6 +280,24 @@ impl SourceConfig for KafkaSourceConfig {
     fn can_acknowledge(&self) -> bool {
         true
     }
+
+    fn healthcheck(&self) -> Option<Healthcheck> {
+        Some(healthcheck(self.clone()).boxed())
+    }
+}
+
+async fn healthcheck(config: KafkaSourceConfig) -> crate::Result<()> {
+    let client_config = client_config(&config)?;
+    let timeout = Duration::from_millis(config.socket_timeout_ms);
+
+    tokio::task::spawn_blocking(move || {
+        let consumer: BaseConsumer = client_config.create().context(KafkaCreateSnafu)?;
+        consumer
+            .fetch_metadata(None, timeout)
+            .map(|_| ())
+            .map_err(Into::into)
+    })
+    .await?
 }
 
 async fn kafka_source(
@@ -252,18 +313,141 @@ async fn kafka_source(
         OrderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
     let mut stream = consumer.stream();
     let keys = Keys::from(log_schema(), &config);
+    let pause_after = config.backpressure_pause_ms.map(Duration::from_millis);
 
     let mut topics = Topics::new(&config);
+    // Messages received while the partitions were paused.
+    let mut pending = VecDeque::new();
 
     loop {
-        tokio::select! {
-            _ = &mut shutdown => break,
-            entry = ack_stream.next() => if let Some((status, entry)) = entry {
-                handle_ack(&mut topics, status, entry, &consumer);
+        let msg = match pending.pop_front() {
+            Some(msg) => msg,
+            None => tokio::select! {
+                _ = &mut shutdown => break,
+                entry = ack_stream.next() => {
+                    if let Some((status, entry)) = entry {
+                        handle_ack(&mut topics, status, entry, &consumer);
+                    }
+                    continue;
+                },
+                message = stream.next() => match message {
+                    None => break,  // WHY?
+                    Some(Err(error)) => {
+                        emit!(KafkaReadError { error });
+                        continue;
+                    }
+                    Some(Ok(msg)) => {
+                        emit!(KafkaBytesReceived {
+                            byte_size: msg.payload_len(),
+                            protocol: "tcp",
+                            topic: msg.topic(),
+                            partition: msg.partition(),
+                        });
+                        msg
+                    }
+                },
             },
+        };
+
+        if topics.failed.contains(msg.topic()) {
+            continue;
+        }
+
+        let send = parse_message(msg, &decoder, keys, &finalizer, &mut out, &consumer);
+        let sent = match pause_after {
+            Some(pause_after) => {
+                send_with_backpressure(
+                    send,
+                    pause_after,
+                    &consumer,
+                    &mut stream,
+                    &mut pending,
+                    &mut topics,
+                    &mut ack_stream,
+                    &mut shutdown,
+                )
+                .await
+            }
+            None => {
+                send.await;
+                ControlFlow::Continue(())
+            }
+        };
+        if sent.is_break() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Waits for the events of a message to be sent downstream. If that takes longer than
+/// `pause_after`, the assigned partitions are paused until the events are sent, while still polling
+/// the consumer so that it doesn't leave the consumer group.
+///
+/// Acknowledgements are still handled while waiting, as the events blocking the send may only be
+/// accepted once earlier ones are delivered. Breaks if the source is shut down in the meantime.
+#[allow(clippy::too_many_arguments)]
+async fn send_with_backpressure<'a>(
+    send: impl Future<Output = ()>,
+    pause_after: Duration,
+    consumer: &StreamConsumer<KafkaStatisticsContext>,
+    stream: &mut (impl Stream<Item = KafkaResult<BorrowedMessage<'a>>> + Unpin),
+    pending: &mut VecDeque<BorrowedMessage<'a>>,
+    topics: &mut Topics,
+    ack_stream: &mut BoxStream<'static, (BatchStatus, FinalizerEntry)>,
+    shutdown: &mut ShutdownSignal,
+) -> ControlFlow<()> {
+    tokio::pin!(send);
+    let pause = tokio::time::sleep(pause_after);
+    tokio::pin!(pause);
+    loop {
+        tokio::select! {
+            _ = &mut send => return ControlFlow::Continue(()),
+            _ = &mut *shutdown => return ControlFlow::Break(()),
+            Some((status, entry)) = ack_stream.next() => {
+                handle_ack(topics, status, entry, consumer);
+            }
+            _ = &mut pause => break,
+        }
+    }
+
+    let assignment = match consumer.assignment() {
+        Ok(assignment) => assignment,
+        Err(error) => {
+            emit!(KafkaPartitionsPauseError {
+                error,
+                operation: "pause"
+            });
+            return tokio::select! {
+                _ = send => ControlFlow::Continue(()),
+                _ = shutdown => ControlFlow::Break(()),
+            };
+        }
+    };
+    if let Err(error) = consumer.pause(&assignment) {
+        emit!(KafkaPartitionsPauseError {
+            error,
+            operation: "pause"
+        });
+    }
+
+    let sent = loop {
+        tokio::select! {
+            _ = &mut send => break ControlFlow::Continue(()),
+            _ = &mut *shutdown => break ControlFlow::Break(()),
+            Some((status, entry)) = ack_stream.next() => {
+                handle_ack(topics, status, entry, consumer);
+            }
             message = stream.next() => match message {
-                None => break,  // WHY?
+                None => {
+                    break tokio::select! {
+                        _ = &mut send => ControlFlow::Continue(()),
+                        _ = &mut *shutdown => ControlFlow::Break(()),
+                    };
+                }
                 Some(Err(error)) => emit!(KafkaReadError { error }),
+                // Messages fetched before the partitions were paused are still delivered.
                 Some(Ok(msg)) => {
                     emit!(KafkaBytesReceived {
                         byte_size: msg.payload_len(),
@@ -271,14 +455,19 @@ async fn kafka_source(
                         topic: msg.topic(),
                         partition: msg.partition(),
                     });
-
-                    parse_message(msg, &decoder, keys, &finalizer, &mut out, &consumer, &topics).await;
+                    pending.push_back(msg);
                 }
             },
         }
-    }
+    };
 
-    Ok(())
+    if let Err(error) = consumer.resume(&assignment) {
+        emit!(KafkaPartitionsPauseError {
+            error,
+            operation: "resume"
+        });
+    }
+    sent
 }
 
 struct Topics {
@@ -344,9 +533,8 @@ async fn parse_message(
     finalizer: &Option<OrderedFinalizer<FinalizerEntry>>,
     out: &mut SourceSender,
     consumer: &Arc<StreamConsumer<KafkaStatisticsContext>>,
-    topics: &Topics,
 ) {
-    if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys, topics) {
+    if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys) {
         match finalizer {
             Some(finalizer) => {
                 let (batch, receiver) = BatchNotifier::new_with_receiver();
@@ -384,12 +572,7 @@ fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
     decoder: &Decoder,
     keys: Keys<'a>,
-    topics: &Topics,
 ) -> Option<(usize, impl Stream<Item = Event> + 'a)> {
-    if topics.failed.contains(msg.topic()) {
-        return None;
-    }
-
     let payload = msg.payload()?; // skip messages with empty payload
 
     let rmsg = ReceivedMessage::from(msg);
@@ -529,9 +712,7 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
     }
 }
 
-fn create_consumer(
-    config: &KafkaSourceConfig,
-) -> crate::Result<StreamConsumer<KafkaStatisticsContext>> {
+fn client_config(config: &KafkaSourceConfig) -> crate::Result<ClientConfig> {
     let mut client_config = ClientConfig::new();
     client_config
         .set("group.id", &config.group_id)
@@ -550,6 +731,14 @@ fn create_consumer(
         .set("statistics.interval.ms", "1000")
         .set("client.id", "vector");
 
+    if let Some(group_instance_id) = &config.group_instance_id {
+        client_config.set("group.instance.id", group_instance_id);
+    }
+
+    if config.rebalance_protocol == RebalanceProtocol::Cooperative {
+        client_config.set("partition.assignment.strategy", "cooperative-sticky");
+    }
+
     config.auth.apply(&mut client_config)?;
 
     if let Some(librdkafka_options) = &config.librdkafka_options {
@@ -558,7 +747,13 @@ fn create_consumer(
         }
     }
 
-    let consumer = client_config
+    Ok(client_config)
+}
+
+fn create_consumer(
+    config: &KafkaSourceConfig,
+) -> crate::Result<StreamConsumer<KafkaStatisticsContext>> {
+    let consumer = client_config(config)?
         .create_with_context::<_, StreamConsumer<_>>(KafkaStatisticsContext)
         .context(KafkaCreateSnafu)?;
     let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
@@ -609,6 +804,34 @@ mod test {
         assert!(create_consumer(&config).is_ok());
     }
 
+    #[tokio::test]
+    async fn consumer_create_with_static_membership_ok() {
+        let config = KafkaSourceConfig {
+            group_instance_id: Some("instance".into()),
+            rebalance_protocol: RebalanceProtocol::Cooperative,
+            ..make_config("topic", "group")
+        };
+        assert!(create_consumer(&config).is_ok());
+    }
+
+    #[test]
+    fn parses_rebalance_protocol() {
+        let config: KafkaSourceConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topics = ["topic"]
+            group_id = "group"
+            group_instance_id = "instance"
+            rebalance_protocol = "cooperative"
+            backpressure_pause_ms = 1000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.rebalance_protocol, RebalanceProtocol::Cooperative);
+        assert_eq!(config.group_instance_id.as_deref(), Some("instance"));
+        assert_eq!(config.backpressure_pause_ms, Some(1000));
+    }
+
     #[tokio::test]
     async fn consumer_create_incorrect_auto_offset_reset() {
         let config = KafkaSourceConfig {
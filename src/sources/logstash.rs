@@ -160,12 +160,14 @@ impl TcpSource for LogstashSource {
 struct LogstashAcker {
     sequence_number: u32,
     protocol_version: Option<LogstashProtocolVersion>,
+    window_size: Option<u32>,
 }
 
 impl LogstashAcker {
     fn new(frames: &[LogstashEventFrame]) -> Self {
         let mut sequence_number = 0;
         let mut protocol_version = None;
+        let mut window_size = None;
 
         for frame in frames {
             sequence_number = std::cmp::max(sequence_number, frame.sequence_number);
@@ -173,20 +175,29 @@ impl LogstashAcker {
             // a set of frames from a single stream, so here we just take the last. In reality, we
             // do not expect stream with multiple protocol versions to occur.
             protocol_version = Some(frame.protocol);
+            window_size = frame.window_size;
         }
 
         Self {
             sequence_number,
             protocol_version,
+            window_size,
         }
     }
 }
 
 impl TcpSourceAcker for LogstashAcker {
     // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#ack-frame-type
+    //
+    // Clients wait for the last event of a window to be acknowledged before sending the next
+    // window, so only acknowledging complete windows applies backpressure to them while the
+    // window's events are being processed.
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
+        let window_complete = self
+            .window_size
+            .map_or(true, |window_size| self.sequence_number >= window_size);
         match (ack, self.protocol_version) {
-            (TcpSourceAck::Ack, Some(protocol_version)) => {
+            (TcpSourceAck::Ack, Some(protocol_version)) if window_complete => {
                 let mut bytes: Vec<u8> = Vec::with_capacity(6);
                 bytes.push(protocol_version.into());
                 bytes.push(LogstashFrameType::Ack.into());
@@ -209,12 +220,16 @@ enum LogstashDecoderReadState {
 #[derive(Debug)]
 struct LogstashDecoder {
     state: LogstashDecoderReadState,
+    /// The number of events the client sends before waiting for an acknowledgement, if it
+    /// announced one.
+    window_size: Option<u32>,
 }
 
 impl LogstashDecoder {
     const fn new() -> Self {
         Self {
             state: LogstashDecoderReadState::ReadProtocol,
+            window_size: None,
         }
     }
 }
@@ -333,6 +348,7 @@ impl TryFrom<u8> for LogstashFrameType {
 struct LogstashEventFrame {
     protocol: LogstashProtocolVersion,
     sequence_number: u32,
+    window_size: Option<u32>,
     fields: BTreeMap<String, serde_json::Value>,
 }
 
@@ -354,7 +370,10 @@ impl Decoder for LogstashDecoder {
                 // if we have any unsent frames, send them before reading new logstash frame
                 LogstashDecoderReadState::PendingFrames(ref mut frames) => {
                     match frames.pop_front() {
-                        Some(frame) => return Ok(Some(frame)),
+                        Some((mut frame, byte_size)) => {
+                            frame.window_size = frame.window_size.or(self.window_size);
+                            return Ok(Some((frame, byte_size)));
+                        }
                         None => LogstashDecoderReadState::ReadProtocol,
                     }
                 }
@@ -398,8 +417,7 @@ impl Decoder for LogstashDecoder {
                     }
                 }
                 // The window size indicates how many events the writer will send before waiting
-                // for acks. As we forward events as we get them, and ack as they are received, we
-                // do not need to keep track of this.
+                // for acks. Each window is acknowledged once all of its events have been handled.
                 //
                 // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#window-size-frame-type
                 LogstashDecoderReadState::ReadFrame(_protocol, LogstashFrameType::WindowSize) => {
@@ -407,7 +425,7 @@ impl Decoder for LogstashDecoder {
                         return Ok(None);
                     }
 
-                    let _window_size = src.get_u32();
+                    self.window_size = Some(src.get_u32());
 
                     LogstashDecoderReadState::ReadProtocol
                 }
@@ -471,6 +489,7 @@ impl Decoder for LogstashDecoder {
                         LogstashEventFrame {
                             protocol,
                             sequence_number,
+                            window_size: None,
                             fields,
                         },
                         byte_size,
@@ -510,6 +529,7 @@ impl Decoder for LogstashDecoder {
                                 LogstashEventFrame {
                                     protocol,
                                     sequence_number,
+                                    window_size: None,
                                     fields,
                                 },
                                 byte_size,
@@ -659,6 +679,60 @@ mod test {
         assert!(log.get("timestamp").is_some());
     }
 
+    #[tokio::test]
+    async fn acks_complete_windows() {
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let source = LogstashConfig {
+            address: address.into(),
+            tls: None,
+            keepalive: None,
+            receive_buffer_bytes: None,
+            acknowledgements: true.into(),
+            connection_limit: None,
+        }
+        .build(SourceContext::new_test(sender, None))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let client = async move {
+            let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+            let mut window = BytesMut::new();
+            window.put_u8(b'2');
+            window.put_u8(b'W');
+            window.put_u32(2);
+            socket.write_all(&window).await.unwrap();
+            socket
+                .write_all(&encode_req(1, &[("message", "one")]))
+                .await
+                .unwrap();
+
+            // The window isn't complete, so the first event isn't acknowledged on its own.
+            let mut output = BytesMut::new();
+            let read = tokio::time::timeout(
+                std::time::Duration::from_millis(200),
+                socket.read_buf(&mut output),
+            )
+            .await;
+            assert!(read.is_err(), "unexpected ack: {:?}", output);
+
+            socket
+                .write_all(&encode_req(2, &[("message", "two")]))
+                .await
+                .unwrap();
+            socket.read_buf(&mut output).await.unwrap();
+            assert_eq!(output.get_u8(), b'2');
+            assert_eq!(output.get_u8(), b'A');
+            assert_eq!(output.get_u32(), 2);
+            assert_eq!(output.len(), 0);
+        };
+
+        let events = spawn_collect_n(client, recv, 2).await;
+        assert_eq!(events.len(), 2);
+    }
+
     fn encode_req(seq: u32, pairs: &[(&str, &str)]) -> Bytes {
         let mut req = BytesMut::new();
         req.put_u8(b'2');
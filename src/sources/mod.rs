@@ -2,6 +2,10 @@ use snafu::Snafu;
 
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub mod aws_cloudwatch_logs;
+#[cfg(feature = "sources-aws_cloudwatch_metrics")]
+pub mod aws_cloudwatch_metrics;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -18,6 +22,8 @@ pub mod demo_logs;
 pub mod dnstap;
 #[cfg(feature = "sources-docker_logs")]
 pub mod docker_logs;
+#[cfg(all(windows, feature = "sources-etw"))]
+pub mod etw;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -50,6 +56,14 @@ pub mod logstash;
 pub mod mongodb_metrics;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64"),
+    feature = "sources-network_flows"
+))]
+pub mod network_flows;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
 #[cfg(feature = "sources-opentelemetry")]
@@ -60,6 +74,8 @@ pub mod postgresql_metrics;
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-replay")]
+pub mod replay;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -72,6 +88,8 @@ pub mod stdin;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(all(windows, feature = "sources-windows_event_log"))]
+pub mod windows_event_log;
 
 pub(crate) mod util;
 
@@ -94,6 +112,16 @@ pub enum Sources {
     #[cfg(feature = "sources-apache_metrics")]
     ApacheMetrics(#[configurable(derived)] apache_metrics::ApacheMetricsConfig),
 
+    /// AWS CloudWatch Logs.
+    #[cfg(feature = "sources-aws_cloudwatch_logs")]
+    AwsCloudwatchLogs(#[configurable(derived)] aws_cloudwatch_logs::AwsCloudwatchLogsConfig),
+
+    /// AWS CloudWatch Metrics.
+    #[cfg(feature = "sources-aws_cloudwatch_metrics")]
+    AwsCloudwatchMetrics(
+        #[configurable(derived)] aws_cloudwatch_metrics::AwsCloudwatchMetricsConfig,
+    ),
+
     /// AWS ECS Metrics.
     #[cfg(feature = "sources-aws_ecs_metrics")]
     AwsEcsMetrics(#[configurable(derived)] aws_ecs_metrics::AwsEcsMetricsSourceConfig),
@@ -126,6 +154,10 @@ pub enum Sources {
     #[cfg(feature = "sources-docker_logs")]
     DockerLogs(#[configurable(derived)] docker_logs::DockerLogsConfig),
 
+    /// Event Tracing for Windows.
+    #[cfg(all(windows, feature = "sources-etw"))]
+    Etw(#[configurable(derived)] etw::EtwConfig),
+
     /// EventStoreDB Metrics.
     #[cfg(feature = "sources-eventstoredb_metrics")]
     EventstoreDbMetrics(#[configurable(derived)] eventstoredb_metrics::EventStoreDbConfig),
@@ -198,6 +230,18 @@ pub enum Sources {
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
 
+    /// NetFlow.
+    #[cfg(feature = "sources-netflow")]
+    Netflow(#[configurable(derived)] netflow::NetflowConfig),
+
+    /// Network Flows.
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64"),
+        feature = "sources-network_flows"
+    ))]
+    NetworkFlows(#[configurable(derived)] network_flows::NetworkFlowsConfig),
+
     /// NGINX Metrics.
     #[cfg(feature = "sources-nginx_metrics")]
     NginxMetrics(#[configurable(derived)] nginx_metrics::NginxMetricsConfig),
@@ -241,6 +285,10 @@ pub enum Sources {
     /// Vector.
     #[cfg(feature = "sources-vector")]
     Vector(#[configurable(derived)] vector::VectorConfig),
+
+    /// Windows Event Log.
+    #[cfg(all(windows, feature = "sources-windows_event_log"))]
+    WindowsEventLog(#[configurable(derived)] windows_event_log::WindowsEventLogConfig),
 }
 
 #[cfg(test)]
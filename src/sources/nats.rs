@@ -1,9 +1,13 @@
+use std::time::Duration;
+
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use futures::{pin_mut, stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
+use vector_common::finalizer::UnorderedFinalizer;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -11,11 +15,17 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
-    event::Event,
-    internal_events::{BytesReceived, OldEventsReceived, StreamClosedError},
-    nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{BytesReceived, NatsAckError, OldEventsReceived, StreamClosedError},
+    nats::{
+        from_tls_auth_config, parse_jetstream_response, JetStreamError, NatsAuthConfig,
+        NatsConfigError,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -29,8 +39,12 @@ enum BuildError {
     Connect { source: std::io::Error },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: std::io::Error },
+    #[snafu(display("NATS JetStream consumer creation error: {}", source))]
+    CreateConsumer { source: JetStreamError },
 }
 
+type Finalizer = UnorderedFinalizer<String>;
+
 /// Configuration for the `nats` source.
 #[configurable_component(source)]
 #[derive(Clone, Debug, Derivative)]
@@ -69,6 +83,105 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+/// Configuration for consuming from a JetStream stream.
+///
+/// Messages are read through a durable push consumer, created or updated when the source starts,
+/// which delivers the messages of the stream matching `subject`. Messages are acknowledged once
+/// they have been processed, or once they have been delivered to all sinks when acknowledgements
+/// are enabled.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume.
+    stream: String,
+
+    /// The name of the durable consumer.
+    ///
+    /// The consumer keeps track of the acknowledged messages across restarts, and is shared by
+    /// Vector instances using the same name and `queue`.
+    durable_name: String,
+
+    /// How long to wait for a message to be acknowledged before it's redelivered, in seconds.
+    #[serde(default = "default_ack_wait_secs")]
+    ack_wait_secs: u64,
+
+    /// The maximum number of messages delivered but not yet acknowledged.
+    #[serde(default = "default_max_ack_pending")]
+    max_ack_pending: i64,
+}
+
+const fn default_ack_wait_secs() -> u64 {
+    30
+}
+
+const fn default_max_ack_pending() -> i64 {
+    1000
+}
+
+/// The request creating a durable consumer.
+///
+/// https://docs.nats.io/reference/reference-protocols/nats_api_reference#consumers
+#[derive(Debug, Serialize)]
+struct CreateConsumerRequest<'a> {
+    stream_name: &'a str,
+    config: ConsumerConfig<'a>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConsumerConfig<'a> {
+    durable_name: &'a str,
+    deliver_subject: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deliver_group: Option<&'a str>,
+    deliver_policy: &'static str,
+    ack_policy: &'static str,
+    /// In nanoseconds.
+    ack_wait: u64,
+    max_ack_pending: i64,
+    filter_subject: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConsumerInfo {
+    name: String,
+}
+
+impl NatsJetStreamConfig {
+    /// The subject the consumer delivers messages to, which is kept the same across restarts
+    /// since it's part of the configuration of the durable consumer.
+    fn deliver_subject(&self) -> String {
+        format!("_vector.deliver.{}.{}", self.stream, self.durable_name)
+    }
+
+    fn create_consumer_request<'a>(
+        &'a self,
+        subject: &'a str,
+        queue: Option<&'a str>,
+    ) -> CreateConsumerRequest<'a> {
+        CreateConsumerRequest {
+            stream_name: &self.stream,
+            config: ConsumerConfig {
+                durable_name: &self.durable_name,
+                deliver_subject: self.deliver_subject(),
+                deliver_group: queue,
+                deliver_policy: "all",
+                ack_policy: "explicit",
+                ack_wait: Duration::from_secs(self.ack_wait_secs).as_nanos() as u64,
+                max_ack_pending: self.max_ack_pending,
+                filter_subject: subject,
+            },
+        }
+    }
 }
 
 inventory::submit! {
@@ -98,11 +211,15 @@ impl SourceConfig for NatsSourceConfig {
             LogNamespace::Legacy,
         )
         .build();
+        let jetstream = self.jetstream.is_some();
+        let acknowledgements = jetstream && cx.do_acknowledgements(&self.acknowledgements);
 
         Ok(Box::pin(nats_source(
             connection,
             subscription,
             decoder,
+            jetstream,
+            acknowledgements,
             cx.shutdown,
             cx.out,
         )))
@@ -117,7 +234,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -144,21 +261,55 @@ fn get_subscription_stream(
     })
 }
 
+/// Acknowledges a JetStream message according to the status of its events.
+///
+/// https://docs.nats.io/using-nats/developer/develop_jetstream/consumers#delivery-reliability
+async fn ack_message(connection: &nats::asynk::Connection, reply: &str, status: BatchStatus) {
+    let ack: &[u8] = match status {
+        BatchStatus::Delivered => b"+ACK",
+        // Redeliver the message, as the events may be delivered on a retry.
+        BatchStatus::Errored => b"-NAK",
+        // The events were rejected by a sink, so redelivering them won't help.
+        BatchStatus::Rejected => b"+TERM",
+    };
+    if let Err(error) = connection.publish(reply, ack).await {
+        emit!(NatsAckError { error });
+    }
+}
+
 async fn nats_source(
-    // Take ownership of the connection so it doesn't get dropped.
-    _connection: nats::asynk::Connection,
+    connection: nats::asynk::Connection,
     subscription: nats::asynk::Subscription,
     decoder: Decoder,
+    jetstream: bool,
+    acknowledgements: bool,
     shutdown: ShutdownSignal,
     mut out: SourceSender,
 ) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) = Finalizer::maybe_new(acknowledgements, shutdown.clone());
     let stream = get_subscription_stream(subscription).take_until(shutdown);
     pin_mut!(stream);
-    while let Some(msg) = stream.next().await {
+    loop {
+        let msg = tokio::select! {
+            entry = ack_stream.next() => {
+                if let Some((status, reply)) = entry {
+                    ack_message(&connection, &reply, status).await;
+                }
+                continue;
+            }
+            msg = stream.next() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
+        };
+
         emit!(BytesReceived {
             byte_size: msg.data.len(),
             protocol: "tcp",
         });
+        // JetStream messages are acknowledged by replying to them.
+        let reply = msg.reply.as_deref().filter(|_| jetstream);
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
         let mut stream = FramedRead::new(msg.data.as_ref(), decoder.clone());
         while let Some(next) = stream.next().await {
             match next {
@@ -176,7 +327,7 @@ async fn nats_source(
                             log.try_insert(log_schema().source_type_key(), Bytes::from("nats"));
                             log.try_insert(log_schema().timestamp_key(), now);
                         }
-                        event
+                        event.with_batch_notifier_option(&batch)
                     });
 
                     out.send_batch(events).await.map_err(|error| {
@@ -192,6 +343,14 @@ async fn nats_source(
                 }
             }
         }
+        drop(batch);
+
+        if let Some(reply) = reply {
+            match (&finalizer, receiver) {
+                (Some(finalizer), Some(receiver)) => finalizer.add(reply.to_owned(), receiver),
+                _ => ack_message(&connection, reply, BatchStatus::Delivered).await,
+            }
+        }
     }
     Ok(())
 }
@@ -201,9 +360,19 @@ async fn create_subscription(
 ) -> Result<(nats::asynk::Connection, nats::asynk::Subscription), BuildError> {
     let nc = config.connect().await?;
 
+    let subject = match &config.jetstream {
+        None => config.subject.clone(),
+        Some(jetstream) => {
+            create_consumer(&nc, jetstream, &config.subject, config.queue.as_deref())
+                .await
+                .context(CreateConsumerSnafu)?;
+            jetstream.deliver_subject()
+        }
+    };
+
     let subscription = match &config.queue {
-        None => nc.subscribe(&config.subject).await,
-        Some(queue) => nc.queue_subscribe(&config.subject, queue).await,
+        None => nc.subscribe(&subject).await,
+        Some(queue) => nc.queue_subscribe(&subject, queue).await,
     };
 
     let subscription = subscription.context(SubscribeSnafu)?;
@@ -211,6 +380,31 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+/// Creates the durable consumer, or updates it if the configuration changed.
+async fn create_consumer(
+    nc: &nats::asynk::Connection,
+    jetstream: &NatsJetStreamConfig,
+    subject: &str,
+    queue: Option<&str>,
+) -> Result<(), JetStreamError> {
+    let request = jetstream.create_consumer_request(subject, queue);
+    let body = serde_json::to_vec(&request).expect("request is serializable");
+    let response = nc
+        .request_timeout(
+            &format!(
+                "$JS.API.CONSUMER.DURABLE.CREATE.{}.{}",
+                jetstream.stream, jetstream.durable_name
+            ),
+            body,
+            Duration::from_secs(5),
+        )
+        .await
+        .map_err(|source| JetStreamError::Request { source })?;
+    let info: ConsumerInfo = parse_jetstream_response(&response.data)?;
+    debug!(message = "Created JetStream consumer.", consumer = %info.name);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -221,6 +415,43 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSourceConfig>();
     }
+
+    #[test]
+    fn builds_jetstream_consumer_requests() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            url = "nats://127.0.0.1:4222"
+            connection_name = "vector"
+            subject = "logs.>"
+            queue = "vector"
+            jetstream.stream = "LOGS"
+            jetstream.durable_name = "vector"
+            "#,
+        )
+        .unwrap();
+        let jetstream = config.jetstream.as_ref().unwrap();
+
+        assert_eq!(
+            serde_json::to_value(
+                jetstream.create_consumer_request(&config.subject, config.queue.as_deref())
+            )
+            .unwrap(),
+            serde_json::json!({
+                "stream_name": "LOGS",
+                "config": {
+                    "durable_name": "vector",
+                    "deliver_subject": "_vector.deliver.LOGS.vector",
+                    "deliver_group": "vector",
+                    "deliver_policy": "all",
+                    "ack_policy": "explicit",
+                    "ack_wait": 30_000_000_000u64,
+                    "max_ack_pending": 1000,
+                    "filter_subject": "logs.>",
+                },
+            })
+        );
+        assert!(config.can_acknowledge());
+    }
 }
 
 #[cfg(feature = "nats-integration-tests")]
@@ -251,7 +482,15 @@ mod integration_tests {
                 LogNamespace::Legacy,
             )
             .build();
-            tokio::spawn(nats_source(nc, sub, decoder, ShutdownSignal::noop(), tx));
+            tokio::spawn(nats_source(
+                nc,
+                sub,
+                decoder,
+                false,
+                false,
+                ShutdownSignal::noop(),
+                tx,
+            ));
             nc_pub.publish(&subject, msg).await.unwrap();
 
             collect_n(rx, 1).await
@@ -276,6 +515,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: None,
         };
@@ -301,6 +542,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -331,6 +574,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -361,6 +606,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -390,6 +637,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -419,6 +668,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -449,6 +700,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -479,6 +732,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -510,6 +765,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: None,
             auth: None,
         };
@@ -535,6 +792,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -568,6 +827,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -599,6 +860,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -634,6 +897,8 @@ mod integration_tests {
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            jetstream: None,
+            acknowledgements: Default::default(),
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -0,0 +1,334 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::event::Value;
+
+/// The number of seconds between the NTP epoch, 1900-01-01, and the Unix epoch.
+const NTP_UNIX_EPOCH: i64 = 2_208_988_800;
+
+/// How the value of a field is encoded, following the abstract data types of IPFIX.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum FieldType {
+    Unsigned,
+    Ipv4Address,
+    Ipv6Address,
+    MacAddress,
+    String,
+    DateTimeSeconds,
+    DateTimeMilliseconds,
+    /// NTP timestamps, used for both microsecond and nanosecond precision.
+    DateTimeNtp,
+    /// Milliseconds since the exporter booted, as used by NetFlow v9.
+    SysUpTime,
+    Octets,
+}
+
+/// Timing information of the export packet, which relative timestamps are resolved against.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct ExportTime {
+    pub(super) export_time: DateTime<Utc>,
+    pub(super) sys_uptime_ms: Option<u32>,
+}
+
+impl ExportTime {
+    /// Converts milliseconds since the exporter booted to a timestamp.
+    pub(super) fn resolve_uptime(&self, uptime_ms: u32) -> Option<DateTime<Utc>> {
+        let sys_uptime_ms = self.sys_uptime_ms?;
+        // The uptime wraps around after about 49 days.
+        let age = sys_uptime_ms.wrapping_sub(uptime_ms);
+        Some(self.export_time - Duration::milliseconds(age as i64))
+    }
+}
+
+/// Returns the name and type of an IANA information element, which NetFlow v9 field types share
+/// their numbering with.
+pub(super) const fn lookup(id: u16) -> Option<(&'static str, FieldType)> {
+    use FieldType::*;
+
+    let field = match id {
+        1 => ("octet_delta_count", Unsigned),
+        2 => ("packet_delta_count", Unsigned),
+        3 => ("delta_flow_count", Unsigned),
+        4 => ("protocol_identifier", Unsigned),
+        5 => ("ip_class_of_service", Unsigned),
+        6 => ("tcp_control_bits", Unsigned),
+        7 => ("source_transport_port", Unsigned),
+        8 => ("source_ipv4_address", Ipv4Address),
+        9 => ("source_ipv4_prefix_length", Unsigned),
+        10 => ("ingress_interface", Unsigned),
+        11 => ("destination_transport_port", Unsigned),
+        12 => ("destination_ipv4_address", Ipv4Address),
+        13 => ("destination_ipv4_prefix_length", Unsigned),
+        14 => ("egress_interface", Unsigned),
+        15 => ("ip_next_hop_ipv4_address", Ipv4Address),
+        16 => ("bgp_source_as_number", Unsigned),
+        17 => ("bgp_destination_as_number", Unsigned),
+        18 => ("bgp_next_hop_ipv4_address", Ipv4Address),
+        19 => ("post_mcast_packet_delta_count", Unsigned),
+        20 => ("post_mcast_octet_delta_count", Unsigned),
+        21 => ("flow_end_sys_up_time", SysUpTime),
+        22 => ("flow_start_sys_up_time", SysUpTime),
+        23 => ("post_octet_delta_count", Unsigned),
+        24 => ("post_packet_delta_count", Unsigned),
+        25 => ("minimum_ip_total_length", Unsigned),
+        26 => ("maximum_ip_total_length", Unsigned),
+        27 => ("source_ipv6_address", Ipv6Address),
+        28 => ("destination_ipv6_address", Ipv6Address),
+        29 => ("source_ipv6_prefix_length", Unsigned),
+        30 => ("destination_ipv6_prefix_length", Unsigned),
+        31 => ("flow_label_ipv6", Unsigned),
+        32 => ("icmp_type_code_ipv4", Unsigned),
+        33 => ("igmp_type", Unsigned),
+        34 => ("sampling_interval", Unsigned),
+        35 => ("sampling_algorithm", Unsigned),
+        36 => ("flow_active_timeout", Unsigned),
+        37 => ("flow_idle_timeout", Unsigned),
+        38 => ("engine_type", Unsigned),
+        39 => ("engine_id", Unsigned),
+        40 => ("exported_octet_total_count", Unsigned),
+        41 => ("exported_message_total_count", Unsigned),
+        42 => ("exported_flow_record_total_count", Unsigned),
+        44 => ("source_ipv4_prefix", Ipv4Address),
+        45 => ("destination_ipv4_prefix", Ipv4Address),
+        46 => ("mpls_top_label_type", Unsigned),
+        47 => ("mpls_top_label_ipv4_address", Ipv4Address),
+        48 => ("sampler_id", Unsigned),
+        49 => ("sampler_mode", Unsigned),
+        50 => ("sampler_random_interval", Unsigned),
+        52 => ("minimum_ttl", Unsigned),
+        53 => ("maximum_ttl", Unsigned),
+        54 => ("fragment_identification", Unsigned),
+        55 => ("post_ip_class_of_service", Unsigned),
+        56 => ("source_mac_address", MacAddress),
+        57 => ("post_destination_mac_address", MacAddress),
+        58 => ("vlan_id", Unsigned),
+        59 => ("post_vlan_id", Unsigned),
+        60 => ("ip_version", Unsigned),
+        61 => ("flow_direction", Unsigned),
+        62 => ("ip_next_hop_ipv6_address", Ipv6Address),
+        63 => ("bgp_next_hop_ipv6_address", Ipv6Address),
+        64 => ("ipv6_extension_headers", Unsigned),
+        80 => ("destination_mac_address", MacAddress),
+        81 => ("post_source_mac_address", MacAddress),
+        82 => ("interface_name", String),
+        83 => ("interface_description", String),
+        84 => ("sampler_name", String),
+        85 => ("octet_total_count", Unsigned),
+        86 => ("packet_total_count", Unsigned),
+        88 => ("fragment_offset", Unsigned),
+        89 => ("forwarding_status", Unsigned),
+        90 => ("mpls_vpn_route_distinguisher", Octets),
+        95 => ("application_id", Octets),
+        96 => ("application_name", String),
+        128 => ("bgp_next_adjacent_as_number", Unsigned),
+        129 => ("bgp_prev_adjacent_as_number", Unsigned),
+        130 => ("exporter_ipv4_address", Ipv4Address),
+        131 => ("exporter_ipv6_address", Ipv6Address),
+        136 => ("flow_end_reason", Unsigned),
+        137 => ("common_properties_id", Unsigned),
+        138 => ("observation_point_id", Unsigned),
+        139 => ("icmp_type_code_ipv6", Unsigned),
+        144 => ("exporting_process_id", Unsigned),
+        148 => ("flow_id", Unsigned),
+        149 => ("observation_domain_id", Unsigned),
+        150 => ("flow_start_seconds", DateTimeSeconds),
+        151 => ("flow_end_seconds", DateTimeSeconds),
+        152 => ("flow_start_milliseconds", DateTimeMilliseconds),
+        153 => ("flow_end_milliseconds", DateTimeMilliseconds),
+        154 => ("flow_start_microseconds", DateTimeNtp),
+        155 => ("flow_end_microseconds", DateTimeNtp),
+        156 => ("flow_start_nanoseconds", DateTimeNtp),
+        157 => ("flow_end_nanoseconds", DateTimeNtp),
+        160 => ("system_init_time_milliseconds", DateTimeMilliseconds),
+        161 => ("flow_duration_milliseconds", Unsigned),
+        162 => ("flow_duration_microseconds", Unsigned),
+        176 => ("icmp_type_ipv4", Unsigned),
+        177 => ("icmp_code_ipv4", Unsigned),
+        178 => ("icmp_type_ipv6", Unsigned),
+        179 => ("icmp_code_ipv6", Unsigned),
+        180 => ("udp_source_port", Unsigned),
+        181 => ("udp_destination_port", Unsigned),
+        182 => ("tcp_source_port", Unsigned),
+        183 => ("tcp_destination_port", Unsigned),
+        192 => ("ip_ttl", Unsigned),
+        195 => ("ip_diff_serv_code_point", Unsigned),
+        225 => ("post_nat_source_ipv4_address", Ipv4Address),
+        226 => ("post_nat_destination_ipv4_address", Ipv4Address),
+        227 => ("post_napt_source_transport_port", Unsigned),
+        228 => ("post_napt_destination_transport_port", Unsigned),
+        230 => ("nat_event", Unsigned),
+        231 => ("initiator_octets", Unsigned),
+        232 => ("responder_octets", Unsigned),
+        233 => ("firewall_event", Unsigned),
+        234 => ("ingress_vrfid", Unsigned),
+        235 => ("egress_vrfid", Unsigned),
+        281 => ("post_nat_source_ipv6_address", Ipv6Address),
+        282 => ("post_nat_destination_ipv6_address", Ipv6Address),
+        _ => return None,
+    };
+
+    Some(field)
+}
+
+/// Decodes a field value. Values whose length doesn't fit their type, or timestamps out of the
+/// supported range, are kept as hexadecimal strings, so that no data is silently dropped.
+pub(super) fn decode(field_type: FieldType, data: &[u8], time: &ExportTime) -> Value {
+    let value = match field_type {
+        FieldType::Unsigned => unsigned(data),
+        FieldType::Ipv4Address => bytes::<4>(data).map(|ip| Ipv4Addr::from(ip).to_string().into()),
+        FieldType::Ipv6Address => bytes::<16>(data).map(|ip| Ipv6Addr::from(ip).to_string().into()),
+        FieldType::MacAddress => bytes::<6>(data).map(|mac| format_mac(&mac).into()),
+        FieldType::String => {
+            let end = data.iter().position(|c| *c == 0).unwrap_or(data.len());
+            Some(String::from_utf8_lossy(&data[..end]).into_owned().into())
+        }
+        FieldType::DateTimeSeconds => bytes::<4>(data).and_then(|secs| {
+            let secs = u32::from_be_bytes(secs) as i64;
+            Some(Utc.timestamp_opt(secs, 0).single()?.into())
+        }),
+        FieldType::DateTimeMilliseconds => bytes::<8>(data).and_then(|millis| {
+            let millis = i64::try_from(u64::from_be_bytes(millis)).ok()?;
+            Some(Utc.timestamp_millis_opt(millis).single()?.into())
+        }),
+        FieldType::DateTimeNtp => bytes::<8>(data).and_then(|ntp| {
+            let secs = u32::from_be_bytes([ntp[0], ntp[1], ntp[2], ntp[3]]) as i64;
+            let fraction = u32::from_be_bytes([ntp[4], ntp[5], ntp[6], ntp[7]]) as u64;
+            let nanos = (fraction * 1_000_000_000) >> 32;
+            Some(
+                Utc.timestamp_opt(secs - NTP_UNIX_EPOCH, nanos as u32)
+                    .single()?
+                    .into(),
+            )
+        }),
+        FieldType::SysUpTime => bytes::<4>(data).map(|uptime| {
+            let uptime = u32::from_be_bytes(uptime);
+            time.resolve_uptime(uptime)
+                .map_or_else(|| Value::from(uptime as i64), Value::from)
+        }),
+        FieldType::Octets => None,
+    };
+
+    value.unwrap_or_else(|| hex(data).into())
+}
+
+/// Decodes a field that isn't in the table of known fields.
+pub(super) fn decode_unknown(data: &[u8]) -> Value {
+    unsigned(data).unwrap_or_else(|| hex(data).into())
+}
+
+/// Decodes an unsigned integer of up to 8 bytes, which exporters may shorten with reduced-size
+/// encoding. Values that don't fit in an `i64` are kept as strings.
+fn unsigned(data: &[u8]) -> Option<Value> {
+    if data.is_empty() || data.len() > 8 {
+        return None;
+    }
+    let value = data
+        .iter()
+        .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+    Some(i64::try_from(value).map_or_else(|_| Value::from(value.to_string()), Value::from))
+}
+
+pub(super) fn format_mac(mac: &[u8; 6]) -> String {
+    format!(
+        "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+        mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]
+    )
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn bytes<const N: usize>(data: &[u8]) -> Option<[u8; N]> {
+    data.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_uptime() -> ExportTime {
+        ExportTime {
+            export_time: Utc.timestamp(0, 0),
+            sys_uptime_ms: None,
+        }
+    }
+
+    #[test]
+    fn decodes_reduced_size_integers() {
+        assert_eq!(
+            decode(FieldType::Unsigned, &[0x01, 0x00], &no_uptime()),
+            256.into()
+        );
+        assert_eq!(
+            decode(FieldType::Unsigned, &u64::MAX.to_be_bytes(), &no_uptime()),
+            u64::MAX.to_string().into()
+        );
+    }
+
+    #[test]
+    fn decodes_addresses() {
+        assert_eq!(
+            decode(FieldType::Ipv4Address, &[192, 0, 2, 1], &no_uptime()),
+            "192.0.2.1".into()
+        );
+        assert_eq!(
+            decode(
+                FieldType::MacAddress,
+                &[0x00, 0x1b, 0x21, 0xaa, 0xbb, 0xcc],
+                &no_uptime()
+            ),
+            "00:1b:21:aa:bb:cc".into()
+        );
+        // Addresses of the wrong length are kept as is.
+        assert_eq!(
+            decode(FieldType::Ipv4Address, &[192, 0, 2], &no_uptime()),
+            "c00002".into()
+        );
+    }
+
+    #[test]
+    fn decodes_timestamps() {
+        assert_eq!(
+            decode(
+                FieldType::DateTimeMilliseconds,
+                &1_659_348_000_123u64.to_be_bytes(),
+                &no_uptime()
+            ),
+            Value::Timestamp(Utc.timestamp_millis(1_659_348_000_123))
+        );
+        // Out of the range of timestamps, which ends in the year 262143.
+        assert_eq!(
+            decode(
+                FieldType::DateTimeMilliseconds,
+                &i64::MAX.to_be_bytes(),
+                &no_uptime()
+            ),
+            "7fffffffffffffff".into()
+        );
+
+        // 2022-08-01T10:00:00.5Z
+        let mut ntp = ((1_659_348_000 + NTP_UNIX_EPOCH) as u32)
+            .to_be_bytes()
+            .to_vec();
+        ntp.extend(0x8000_0000u32.to_be_bytes());
+        assert_eq!(
+            decode(FieldType::DateTimeNtp, &ntp, &no_uptime()),
+            Value::Timestamp(Utc.timestamp(1_659_348_000, 500_000_000))
+        );
+
+        let time = ExportTime {
+            export_time: Utc.timestamp(1_659_348_000, 0),
+            sys_uptime_ms: Some(10_000),
+        };
+        assert_eq!(
+            decode(FieldType::SysUpTime, &4_000u32.to_be_bytes(), &time),
+            Value::Timestamp(Utc.timestamp(1_659_347_994, 0))
+        );
+        assert_eq!(
+            decode(FieldType::SysUpTime, &4_000u32.to_be_bytes(), &no_uptime()),
+            4_000.into()
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use std::net::SocketAddr;
+
+use chrono::{TimeZone, Utc};
+
+use super::{
+    fields::ExportTime,
+    reader::{ParseError, Reader},
+    templates::{FieldId, Template, TemplateCache, TemplateField, TemplateKey},
+    Decoded,
+};
+use crate::config::log_schema;
+
+const HEADER_LENGTH: usize = 16;
+const TEMPLATE_SET_ID: u16 = 2;
+const OPTIONS_TEMPLATE_SET_ID: u16 = 3;
+/// Set IDs below this are reserved, and template IDs start from it.
+const MIN_DATA_SET_ID: u16 = 256;
+/// Set in the information element ID of enterprise-specific fields.
+const ENTERPRISE_BIT: u16 = 0x8000;
+
+/// Decodes an IPFIX message. Templates are added to the cache, or removed from it when withdrawn,
+/// and data sets are decoded with previously received templates of the same exporter.
+pub(super) fn decode(
+    mut datagram: Reader,
+    exporter: SocketAddr,
+    templates: &mut TemplateCache,
+) -> Result<Decoded, ParseError> {
+    let mut header = datagram.sub(HEADER_LENGTH)?;
+    let _version = header.u16()?;
+    let length = header.u16()? as usize;
+    let export_time = header.u32()?;
+    let sequence = header.u32()?;
+    let observation_domain_id = header.u32()?;
+
+    if length < HEADER_LENGTH {
+        return Err(ParseError::InvalidLength {
+            what: "message",
+            length,
+        });
+    }
+    let mut message = datagram.sub(length - HEADER_LENGTH)?;
+
+    let time = ExportTime {
+        export_time: Utc.timestamp(export_time as i64, 0),
+        sys_uptime_ms: None,
+    };
+
+    let mut decoded = Decoded::default();
+    while message.remaining() >= 4 {
+        let set_id = message.u16()?;
+        let length = message.u16()? as usize;
+        if length < 4 {
+            return Err(ParseError::InvalidLength {
+                what: "set",
+                length,
+            });
+        }
+        let set = message.sub(length - 4)?;
+
+        let key = |template_id| TemplateKey {
+            exporter,
+            domain: observation_domain_id,
+            template_id,
+        };
+        match set_id {
+            TEMPLATE_SET_ID => decode_templates(set, false, &key, templates)?,
+            OPTIONS_TEMPLATE_SET_ID => decode_templates(set, true, &key, templates)?,
+            id if id < MIN_DATA_SET_ID => {}
+            template_id => match templates.get(&key(template_id)) {
+                Some(template) => {
+                    for mut log in template.decode_records(set, &time)? {
+                        log.insert("flow_type", "ipfix");
+                        log.insert(log_schema().timestamp_key(), time.export_time);
+                        log.insert("flow_sequence", sequence as i64);
+                        log.try_insert("observation_domain_id", observation_domain_id as i64);
+                        decoded.events.push(log);
+                    }
+                }
+                None => decoded.missing_templates.push(template_id),
+            },
+        }
+    }
+
+    Ok(decoded)
+}
+
+fn decode_templates(
+    mut set: Reader,
+    options: bool,
+    key: &dyn Fn(u16) -> TemplateKey,
+    templates: &mut TemplateCache,
+) -> Result<(), ParseError> {
+    while set.remaining() >= 4 {
+        let template_id = set.u16()?;
+        if template_id < MIN_DATA_SET_ID {
+            // Padding
+            break;
+        }
+        let field_count = set.u16()?;
+        if field_count == 0 {
+            // Withdrawn templates are announced with an empty template.
+            templates.remove(&key(template_id));
+            continue;
+        }
+        let scope_field_count = if options { set.u16()? as usize } else { 0 };
+
+        let fields = (0..field_count)
+            .map(|_| {
+                let id = set.u16()?;
+                let length = set.u16()?;
+                let id = if id & ENTERPRISE_BIT != 0 {
+                    FieldId::Enterprise {
+                        enterprise: set.u32()?,
+                        id: id & !ENTERPRISE_BIT,
+                    }
+                } else {
+                    FieldId::Standard(id)
+                };
+                Ok(TemplateField { id, length })
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+
+        templates.insert(
+            key(template_id),
+            Template {
+                fields,
+                scope_field_count,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::event::Value;
+
+    fn message(sets: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(10u16.to_be_bytes());
+        data.extend(((HEADER_LENGTH + sets.len()) as u16).to_be_bytes());
+        data.extend(1_659_348_000u32.to_be_bytes());
+        data.extend(3u32.to_be_bytes());
+        data.extend(42u32.to_be_bytes());
+        data.extend(sets);
+        data
+    }
+
+    fn template_set() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(TEMPLATE_SET_ID.to_be_bytes());
+        data.extend(32u16.to_be_bytes());
+        data.extend(300u16.to_be_bytes());
+        data.extend(5u16.to_be_bytes());
+        // sourceIPv6Address, destinationIPv6Address, octetDeltaCount with reduced-size encoding,
+        // flowStartMilliseconds and an enterprise-specific field.
+        data.extend([0, 27, 0, 16]);
+        data.extend([0, 28, 0, 16]);
+        data.extend([0, 1, 0, 4]);
+        data.extend([0, 152, 0, 8]);
+        data.extend([0x80, 0x01, 0, 2]);
+        data.extend(29305u32.to_be_bytes());
+        data
+    }
+
+    fn data_set() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(300u16.to_be_bytes());
+        data.extend(50u16.to_be_bytes());
+        data.extend(
+            "2001:db8::1"
+                .parse::<std::net::Ipv6Addr>()
+                .unwrap()
+                .octets(),
+        );
+        data.extend(
+            "2001:db8::2"
+                .parse::<std::net::Ipv6Addr>()
+                .unwrap()
+                .octets(),
+        );
+        data.extend(1500u32.to_be_bytes());
+        data.extend(1_659_347_999_500u64.to_be_bytes());
+        data.extend(7u16.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn decodes_templates_and_data() {
+        let exporter = "192.0.2.1:4739".parse().unwrap();
+        let mut templates = TemplateCache::new(Duration::from_secs(60));
+
+        let mut sets = template_set();
+        sets.extend(data_set());
+        let decoded = decode(Reader::new(&message(&sets)), exporter, &mut templates).unwrap();
+
+        assert!(decoded.missing_templates.is_empty());
+        assert_eq!(decoded.events.len(), 1);
+        let log = &decoded.events[0];
+        assert_eq!(log["flow_type"], "ipfix".into());
+        assert_eq!(log["observation_domain_id"], 42.into());
+        assert_eq!(log["source_ipv6_address"], "2001:db8::1".into());
+        assert_eq!(log["destination_ipv6_address"], "2001:db8::2".into());
+        assert_eq!(log["octet_delta_count"], 1500.into());
+        assert_eq!(
+            log["flow_start_milliseconds"],
+            Value::Timestamp(Utc.timestamp_millis(1_659_347_999_500))
+        );
+        assert_eq!(log["enterprise_29305_1"], 7.into());
+    }
+
+    #[test]
+    fn withdraws_templates() {
+        let exporter = "192.0.2.1:4739".parse().unwrap();
+        let mut templates = TemplateCache::new(Duration::from_secs(60));
+        decode(
+            Reader::new(&message(&template_set())),
+            exporter,
+            &mut templates,
+        )
+        .unwrap();
+
+        let mut withdrawal = Vec::new();
+        withdrawal.extend(TEMPLATE_SET_ID.to_be_bytes());
+        withdrawal.extend(8u16.to_be_bytes());
+        withdrawal.extend(300u16.to_be_bytes());
+        withdrawal.extend(0u16.to_be_bytes());
+        withdrawal.extend(data_set());
+        let decoded = decode(Reader::new(&message(&withdrawal)), exporter, &mut templates).unwrap();
+
+        assert!(decoded.events.is_empty());
+        assert_eq!(decoded.missing_templates, vec![300]);
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        let exporter = "192.0.2.1:4739".parse().unwrap();
+        let mut templates = TemplateCache::new(Duration::from_secs(60));
+        let mut data = message(&[0, 2, 0, 1]);
+
+        assert_eq!(
+            decode(Reader::new(&data), exporter, &mut templates).unwrap_err(),
+            ParseError::InvalidLength {
+                what: "set",
+                length: 1
+            }
+        );
+
+        data.truncate(HEADER_LENGTH + 2);
+        assert!(decode(Reader::new(&data), exporter, &mut templates).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use std::{net::SocketAddr, time::Duration};
+
+use bytes::{Bytes, BytesMut};
+use chrono::Utc;
+use tokio::net::UdpSocket;
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::LogEvent,
+    internal_events::{
+        BytesReceived, NetflowParseError, NetflowReceiveError, NetflowTemplateMissing,
+        OldEventsReceived, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+mod fields;
+mod ipfix;
+mod reader;
+mod sflow;
+mod templates;
+mod v5;
+mod v9;
+
+use reader::{ParseError, Reader};
+use templates::TemplateCache;
+
+/// Datagrams can't be larger than the maximum UDP payload.
+const MAX_DATAGRAM_SIZE: usize = 65535;
+const DEFAULT_TEMPLATE_TIMEOUT_SECS: u64 = 1800;
+
+/// Configuration for the `netflow` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The address to listen for datagrams on.
+    ///
+    /// NetFlow v5, NetFlow v9, IPFIX and sFlow v5 datagrams are all accepted on the same address.
+    pub address: SocketAddr,
+
+    /// How long to keep templates that the exporter doesn't refresh, in seconds.
+    ///
+    /// Exporters periodically resend their templates, so this should be longer than the template
+    /// refresh interval of the exporters.
+    #[serde(default = "default_template_timeout_secs")]
+    pub template_timeout_secs: u64,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    pub receive_buffer_bytes: Option<usize>,
+}
+
+const fn default_template_timeout_secs() -> u64 {
+    DEFAULT_TEMPLATE_TIMEOUT_SECS
+}
+
+inventory::submit! {
+    SourceDescription::new::<NetflowConfig>("netflow")
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:2055".parse().unwrap(),
+            template_timeout_secs: default_template_timeout_secs(),
+            receive_buffer_bytes: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "netflow")]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let socket = UdpSocket::bind(self.address).await?;
+        if let Some(receive_buffer_bytes) = self.receive_buffer_bytes {
+            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+            }
+        }
+
+        let templates = TemplateCache::new(Duration::from_secs(self.template_timeout_secs));
+        Ok(Box::pin(netflow_source(
+            socket,
+            templates,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "netflow"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// The events decoded from a datagram.
+#[derive(Default)]
+struct Decoded {
+    events: Vec<LogEvent>,
+    /// The IDs of data sets that were skipped because their template isn't known yet.
+    missing_templates: Vec<u16>,
+}
+
+/// Decodes a datagram of any of the supported protocols, which are told apart by their version.
+fn decode_datagram(
+    data: &[u8],
+    exporter: SocketAddr,
+    templates: &mut TemplateCache,
+) -> Result<Decoded, ParseError> {
+    let reader = Reader::new(data);
+    let mut version = reader;
+    match version.u16()? {
+        5 => Ok(Decoded {
+            events: v5::decode(reader)?,
+            missing_templates: Vec::new(),
+        }),
+        9 => v9::decode(reader, exporter, templates),
+        10 => ipfix::decode(reader, exporter, templates),
+        // sFlow encodes its version as a 32-bit integer.
+        0 => sflow::decode(reader),
+        version => Err(ParseError::UnsupportedVersion {
+            version: version as u32,
+        }),
+    }
+}
+
+async fn netflow_source(
+    socket: UdpSocket,
+    mut templates: TemplateCache,
+    mut out: SourceSender,
+    mut shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    info!(message = "Listening.", address = ?socket.local_addr().ok());
+
+    let mut buffer = BytesMut::new();
+    loop {
+        buffer.resize(MAX_DATAGRAM_SIZE, 0);
+        let (byte_size, exporter) = tokio::select! {
+            received = socket.recv_from(&mut buffer) => match received {
+                Ok(received) => received,
+                Err(error) => {
+                    emit!(NetflowReceiveError { error });
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        emit!(BytesReceived {
+            byte_size,
+            protocol: "udp",
+        });
+
+        let decoded = match decode_datagram(&buffer[..byte_size], exporter, &mut templates) {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                emit!(NetflowParseError {
+                    error: error.to_string(),
+                    exporter,
+                });
+                continue;
+            }
+        };
+        for template_id in decoded.missing_templates {
+            emit!(NetflowTemplateMissing {
+                template_id,
+                exporter
+            });
+        }
+
+        let mut events = decoded.events;
+        if events.is_empty() {
+            continue;
+        }
+
+        let now = Utc::now();
+        let exporter_ip = exporter.ip().to_string();
+        for log in &mut events {
+            log.insert(log_schema().host_key(), exporter_ip.as_str());
+            log.insert(log_schema().source_type_key(), Bytes::from("netflow"));
+            log.try_insert(log_schema().timestamp_key(), now);
+        }
+
+        emit!(OldEventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            return Err(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_ready, next_addr};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let mut templates = TemplateCache::new(Duration::from_secs(60));
+        let exporter = "192.0.2.1:2055".parse().unwrap();
+
+        assert!(matches!(
+            decode_datagram(&[0, 7, 0, 0], exporter, &mut templates),
+            Err(ParseError::UnsupportedVersion { version: 7 })
+        ));
+        assert!(matches!(
+            decode_datagram(&[0, 0, 0, 4], exporter, &mut templates),
+            Err(ParseError::UnsupportedVersion { version: 4 })
+        ));
+        assert!(decode_datagram(&[0], exporter, &mut templates).is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_datagrams() {
+        let address = next_addr();
+        let (tx, rx) = SourceSender::new_test();
+        let config = NetflowConfig {
+            address,
+            template_timeout_secs: default_template_timeout_secs(),
+            receive_buffer_bytes: None,
+        };
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        // A NetFlow v5 datagram without records, followed by one with a single record.
+        let mut datagram = vec![0, 5, 0, 1];
+        datagram.extend([0; 20]);
+        datagram.extend([10, 0, 0, 1, 192, 0, 2, 10]);
+        datagram.extend([0; 40]);
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        socket.send_to(&datagram[..3], address).await.unwrap();
+        socket.send_to(&datagram, address).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let events = collect_ready(rx).await;
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["flow_type"], "netflow_v5".into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log[log_schema().host_key()], "127.0.0.1".into());
+        assert_eq!(log[log_schema().source_type_key()], "netflow".into());
+    }
+}
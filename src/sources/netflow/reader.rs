@@ -0,0 +1,75 @@
+use snafu::Snafu;
+
+/// Errors that make a datagram, or the rest of it, impossible to decode.
+#[derive(Debug, PartialEq, Snafu)]
+pub(super) enum ParseError {
+    #[snafu(display("Datagram is truncated, expected {} more bytes", needed))]
+    Truncated { needed: usize },
+    #[snafu(display("Unsupported version {}", version))]
+    UnsupportedVersion { version: u32 },
+    #[snafu(display("Invalid {} length {}", what, length))]
+    InvalidLength { what: &'static str, length: usize },
+}
+
+/// A cursor over a big-endian encoded datagram.
+#[derive(Clone, Copy)]
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(super) const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(super) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(super) fn bytes(&mut self, length: usize) -> Result<&'a [u8], ParseError> {
+        if length > self.data.len() {
+            return Err(ParseError::Truncated {
+                needed: length - self.data.len(),
+            });
+        }
+        let (bytes, rest) = self.data.split_at(length);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Splits off the next `length` bytes into a reader of their own.
+    pub(super) fn sub(&mut self, length: usize) -> Result<Reader<'a>, ParseError> {
+        self.bytes(length).map(Reader::new)
+    }
+
+    pub(super) fn skip(&mut self, length: usize) -> Result<(), ParseError> {
+        self.bytes(length).map(|_| ())
+    }
+
+    pub(super) fn array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        Ok(self
+            .bytes(N)?
+            .try_into()
+            .expect("slice has the array length"))
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, ParseError> {
+        self.array().map(u8::from_be_bytes)
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, ParseError> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, ParseError> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, ParseError> {
+        self.array().map(u64::from_be_bytes)
+    }
+}
@@ -0,0 +1,447 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{
+    fields::format_mac,
+    reader::{ParseError, Reader},
+    Decoded,
+};
+use crate::event::LogEvent;
+
+const VERSION: u32 = 5;
+
+// Sample formats of the standard enterprise.
+const FLOW_SAMPLE: u32 = 1;
+const COUNTERS_SAMPLE: u32 = 2;
+const FLOW_SAMPLE_EXPANDED: u32 = 3;
+const COUNTERS_SAMPLE_EXPANDED: u32 = 4;
+
+// Flow record formats.
+const RAW_PACKET_HEADER: u32 = 1;
+const ETHERNET_FRAME_DATA: u32 = 2;
+const IPV4_DATA: u32 = 3;
+const IPV6_DATA: u32 = 4;
+const EXTENDED_SWITCH: u32 = 1001;
+
+// Counter record formats.
+const GENERIC_INTERFACE_COUNTERS: u32 = 1;
+
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Decodes an sFlow v5 datagram. Each flow sample becomes an event, with the fields of all of its
+/// records, and so does each counters sample. Samples and records of unknown formats, including
+/// all enterprise-specific ones, are skipped.
+pub(super) fn decode(mut datagram: Reader) -> Result<Decoded, ParseError> {
+    let version = datagram.u32()?;
+    if version != VERSION {
+        return Err(ParseError::UnsupportedVersion { version });
+    }
+    let agent_address = address(&mut datagram)?;
+    let sub_agent_id = datagram.u32()?;
+    let sequence = datagram.u32()?;
+    let _uptime = datagram.u32()?;
+    let sample_count = datagram.u32()?;
+
+    let mut decoded = Decoded::default();
+    for _ in 0..sample_count {
+        let format = datagram.u32()?;
+        let length = datagram.u32()? as usize;
+        let mut sample = datagram.sub(length)?;
+
+        let mut log = LogEvent::default();
+        match format {
+            FLOW_SAMPLE => decode_flow_sample(&mut sample, false, &mut log)?,
+            FLOW_SAMPLE_EXPANDED => decode_flow_sample(&mut sample, true, &mut log)?,
+            COUNTERS_SAMPLE => decode_counters_sample(&mut sample, false, &mut log)?,
+            COUNTERS_SAMPLE_EXPANDED => decode_counters_sample(&mut sample, true, &mut log)?,
+            _ => continue,
+        }
+
+        log.insert("flow_type", "sflow_v5");
+        log.insert("agent_address", agent_address.as_str());
+        log.insert("sub_agent_id", sub_agent_id as i64);
+        log.insert("flow_sequence", sequence as i64);
+        decoded.events.push(log);
+    }
+
+    Ok(decoded)
+}
+
+fn decode_flow_sample(
+    sample: &mut Reader,
+    expanded: bool,
+    log: &mut LogEvent,
+) -> Result<(), ParseError> {
+    log.insert("record_type", "flow");
+    log.insert("sample_sequence", sample.u32()? as i64);
+    source_id(sample, expanded, log)?;
+    log.insert("sampling_interval", sample.u32()? as i64);
+    log.insert("sample_pool", sample.u32()? as i64);
+    log.insert("drops", sample.u32()? as i64);
+    for key in ["ingress_interface", "egress_interface"] {
+        // Interfaces are encoded with a two bit format, of which only plain interface indexes are
+        // decoded.
+        let (format, value) = if expanded {
+            (sample.u32()?, sample.u32()?)
+        } else {
+            let interface = sample.u32()?;
+            (interface >> 30, interface & 0x3fff_ffff)
+        };
+        if format == 0 {
+            log.insert(key, value as i64);
+        }
+    }
+
+    let record_count = sample.u32()?;
+    for _ in 0..record_count {
+        let format = sample.u32()?;
+        let length = sample.u32()? as usize;
+        let mut record = sample.sub(length)?;
+        match format {
+            RAW_PACKET_HEADER => {
+                let protocol = record.u32()?;
+                log.insert("frame_length", record.u32()? as i64);
+                let _stripped = record.u32()?;
+                let header_length = record.u32()? as usize;
+                let header = record.sub(header_length)?;
+                if protocol == HEADER_PROTOCOL_ETHERNET {
+                    // Headers are truncated to a configured size, so decoding stops without an
+                    // error at whichever layer they end.
+                    let _ = decode_ethernet(header, log);
+                }
+            }
+            ETHERNET_FRAME_DATA => {
+                log.insert("frame_length", record.u32()? as i64);
+                log.insert("source_mac_address", mac(&mut record)?);
+                record.skip(2)?;
+                log.insert("destination_mac_address", mac(&mut record)?);
+                record.skip(2)?;
+                log.insert("ethernet_type", record.u32()? as i64);
+            }
+            IPV4_DATA => {
+                let _length = record.u32()?;
+                log.insert("protocol_identifier", record.u32()? as i64);
+                log.insert(
+                    "source_ipv4_address",
+                    Ipv4Addr::from(record.array::<4>()?).to_string(),
+                );
+                log.insert(
+                    "destination_ipv4_address",
+                    Ipv4Addr::from(record.array::<4>()?).to_string(),
+                );
+                log.insert("source_transport_port", record.u32()? as i64);
+                log.insert("destination_transport_port", record.u32()? as i64);
+                log.insert("tcp_control_bits", record.u32()? as i64);
+                log.insert("ip_class_of_service", record.u32()? as i64);
+            }
+            IPV6_DATA => {
+                let _length = record.u32()?;
+                log.insert("protocol_identifier", record.u32()? as i64);
+                log.insert(
+                    "source_ipv6_address",
+                    Ipv6Addr::from(record.array::<16>()?).to_string(),
+                );
+                log.insert(
+                    "destination_ipv6_address",
+                    Ipv6Addr::from(record.array::<16>()?).to_string(),
+                );
+                log.insert("source_transport_port", record.u32()? as i64);
+                log.insert("destination_transport_port", record.u32()? as i64);
+                log.insert("tcp_control_bits", record.u32()? as i64);
+                log.insert("ip_class_of_service", record.u32()? as i64);
+            }
+            EXTENDED_SWITCH => {
+                log.insert("vlan_id", record.u32()? as i64);
+                let _priority = record.u32()?;
+                log.insert("post_vlan_id", record.u32()? as i64);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn decode_counters_sample(
+    sample: &mut Reader,
+    expanded: bool,
+    log: &mut LogEvent,
+) -> Result<(), ParseError> {
+    log.insert("record_type", "counters");
+    log.insert("sample_sequence", sample.u32()? as i64);
+    source_id(sample, expanded, log)?;
+
+    let record_count = sample.u32()?;
+    for _ in 0..record_count {
+        let format = sample.u32()?;
+        let length = sample.u32()? as usize;
+        let mut record = sample.sub(length)?;
+        if format != GENERIC_INTERFACE_COUNTERS {
+            continue;
+        }
+
+        log.insert("if_index", record.u32()? as i64);
+        log.insert("if_type", record.u32()? as i64);
+        log.insert("if_speed", record.u64()? as i64);
+        log.insert("if_direction", record.u32()? as i64);
+        log.insert("if_status", record.u32()? as i64);
+        log.insert("if_in_octets", record.u64()? as i64);
+        for key in [
+            "if_in_ucast_pkts",
+            "if_in_multicast_pkts",
+            "if_in_broadcast_pkts",
+            "if_in_discards",
+            "if_in_errors",
+            "if_in_unknown_protos",
+        ] {
+            log.insert(key, record.u32()? as i64);
+        }
+        log.insert("if_out_octets", record.u64()? as i64);
+        for key in [
+            "if_out_ucast_pkts",
+            "if_out_multicast_pkts",
+            "if_out_broadcast_pkts",
+            "if_out_discards",
+            "if_out_errors",
+            "if_promiscuous_mode",
+        ] {
+            log.insert(key, record.u32()? as i64);
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes the data source of a sample, which is packed into a single field in compact samples.
+fn source_id(sample: &mut Reader, expanded: bool, log: &mut LogEvent) -> Result<(), ParseError> {
+    let (source_id_type, source_id_index) = if expanded {
+        (sample.u32()?, sample.u32()?)
+    } else {
+        let source_id = sample.u32()?;
+        (source_id >> 24, source_id & 0x00ff_ffff)
+    };
+    log.insert("source_id_type", source_id_type as i64);
+    log.insert("source_id_index", source_id_index as i64);
+    Ok(())
+}
+
+/// Decodes the Ethernet, IP and transport headers of a sampled packet.
+fn decode_ethernet(mut header: Reader, log: &mut LogEvent) -> Result<(), ParseError> {
+    log.insert("destination_mac_address", mac(&mut header)?);
+    log.insert("source_mac_address", mac(&mut header)?);
+    let mut ethernet_type = header.u16()?;
+    if ethernet_type == ETHERTYPE_VLAN {
+        log.insert("vlan_id", (header.u16()? & 0x0fff) as i64);
+        ethernet_type = header.u16()?;
+    }
+    log.insert("ethernet_type", ethernet_type as i64);
+
+    let protocol = match ethernet_type {
+        ETHERTYPE_IPV4 => {
+            let version_and_length = header.u8()?;
+            log.insert("ip_version", 4);
+            log.insert("ip_class_of_service", header.u8()? as i64);
+            header.skip(6)?;
+            log.insert("ip_ttl", header.u8()? as i64);
+            let protocol = header.u8()?;
+            log.insert("protocol_identifier", protocol as i64);
+            header.skip(2)?;
+            log.insert(
+                "source_ipv4_address",
+                Ipv4Addr::from(header.array::<4>()?).to_string(),
+            );
+            log.insert(
+                "destination_ipv4_address",
+                Ipv4Addr::from(header.array::<4>()?).to_string(),
+            );
+            let options_length = ((version_and_length & 0x0f) as usize * 4).saturating_sub(20);
+            header.skip(options_length)?;
+            protocol
+        }
+        ETHERTYPE_IPV6 => {
+            let first = header.u32()?;
+            log.insert("ip_version", 6);
+            log.insert("ip_class_of_service", ((first >> 20) & 0xff) as i64);
+            log.insert("flow_label_ipv6", (first & 0x000f_ffff) as i64);
+            header.skip(2)?;
+            let protocol = header.u8()?;
+            log.insert("protocol_identifier", protocol as i64);
+            log.insert("ip_ttl", header.u8()? as i64);
+            log.insert(
+                "source_ipv6_address",
+                Ipv6Addr::from(header.array::<16>()?).to_string(),
+            );
+            log.insert(
+                "destination_ipv6_address",
+                Ipv6Addr::from(header.array::<16>()?).to_string(),
+            );
+            protocol
+        }
+        _ => return Ok(()),
+    };
+
+    if protocol == IP_PROTOCOL_TCP || protocol == IP_PROTOCOL_UDP {
+        log.insert("source_transport_port", header.u16()? as i64);
+        log.insert("destination_transport_port", header.u16()? as i64);
+    }
+    if protocol == IP_PROTOCOL_TCP {
+        header.skip(9)?;
+        log.insert("tcp_control_bits", header.u8()? as i64);
+    }
+
+    Ok(())
+}
+
+fn address(reader: &mut Reader) -> Result<String, ParseError> {
+    match reader.u32()? {
+        1 => Ok(Ipv4Addr::from(reader.array::<4>()?).to_string()),
+        2 => Ok(Ipv6Addr::from(reader.array::<16>()?).to_string()),
+        _ => Ok(String::new()),
+    }
+}
+
+fn mac(reader: &mut Reader) -> Result<String, ParseError> {
+    Ok(format_mac(&reader.array::<6>()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(VERSION.to_be_bytes());
+        data.extend(1u32.to_be_bytes());
+        data.extend([192, 0, 2, 1]);
+        data.extend(0u32.to_be_bytes());
+        data.extend(99u32.to_be_bytes());
+        data.extend(60_000u32.to_be_bytes());
+        data.extend((samples.len() as u32).to_be_bytes());
+        for (format, sample) in samples {
+            data.extend(format.to_be_bytes());
+            data.extend((sample.len() as u32).to_be_bytes());
+            data.extend(sample);
+        }
+        data
+    }
+
+    fn record(format: u32, data: &[u8]) -> Vec<u8> {
+        let mut record = format.to_be_bytes().to_vec();
+        record.extend((data.len() as u32).to_be_bytes());
+        record.extend(data);
+        record
+    }
+
+    fn packet_header() -> Vec<u8> {
+        let mut packet = Vec::new();
+        // Ethernet with a VLAN tag
+        packet.extend([0x00, 0x1b, 0x21, 0xaa, 0xbb, 0xcc]);
+        packet.extend([0x00, 0x1b, 0x21, 0xdd, 0xee, 0xff]);
+        packet.extend(ETHERTYPE_VLAN.to_be_bytes());
+        packet.extend(100u16.to_be_bytes());
+        packet.extend(ETHERTYPE_IPV4.to_be_bytes());
+        // IPv4
+        packet.extend([0x45, 0x10, 0, 60, 0, 0, 0x40, 0, 64, IP_PROTOCOL_TCP, 0, 0]);
+        packet.extend([10, 0, 0, 1]);
+        packet.extend([192, 0, 2, 10]);
+        // TCP
+        packet.extend(54321u16.to_be_bytes());
+        packet.extend(443u16.to_be_bytes());
+        packet.extend([0; 9]);
+        packet.push(0x02);
+        // The rest of the header was cut off by the agent.
+
+        let mut header = Vec::new();
+        header.extend(HEADER_PROTOCOL_ETHERNET.to_be_bytes());
+        header.extend(74u32.to_be_bytes());
+        header.extend(4u32.to_be_bytes());
+        header.extend((packet.len() as u32).to_be_bytes());
+        header.extend(&packet);
+        header.resize((header.len() + 3) / 4 * 4, 0);
+        header
+    }
+
+    #[test]
+    fn decodes_flow_samples() {
+        let mut sample = Vec::new();
+        sample.extend(1u32.to_be_bytes());
+        sample.extend(3u32.to_be_bytes());
+        sample.extend(1024u32.to_be_bytes());
+        sample.extend(4096u32.to_be_bytes());
+        sample.extend(0u32.to_be_bytes());
+        sample.extend(3u32.to_be_bytes());
+        sample.extend(0x4000_0001u32.to_be_bytes());
+        sample.extend(2u32.to_be_bytes());
+        sample.extend(record(RAW_PACKET_HEADER, &packet_header()));
+        sample.extend(record(
+            EXTENDED_SWITCH,
+            &[0, 0, 0, 100, 0, 0, 0, 0, 0, 0, 0, 200, 0, 0, 0, 0],
+        ));
+
+        let decoded = decode(Reader::new(&datagram(&[(FLOW_SAMPLE, sample)]))).unwrap();
+
+        assert_eq!(decoded.events.len(), 1);
+        let log = &decoded.events[0];
+        assert_eq!(log["flow_type"], "sflow_v5".into());
+        assert_eq!(log["record_type"], "flow".into());
+        assert_eq!(log["agent_address"], "192.0.2.1".into());
+        assert_eq!(log["sampling_interval"], 1024.into());
+        assert_eq!(log["source_id_index"], 3.into());
+        assert_eq!(log["ingress_interface"], 3.into());
+        // Discarded packets are reported with a different interface format.
+        assert!(!log.contains("egress_interface"));
+        assert_eq!(log["frame_length"], 74.into());
+        assert_eq!(log["source_mac_address"], "00:1b:21:dd:ee:ff".into());
+        assert_eq!(log["vlan_id"], 100.into());
+        assert_eq!(log["post_vlan_id"], 200.into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log["destination_ipv4_address"], "192.0.2.10".into());
+        assert_eq!(log["ip_ttl"], 64.into());
+        assert_eq!(log["protocol_identifier"], 6.into());
+        assert_eq!(log["source_transport_port"], 54321.into());
+        assert_eq!(log["destination_transport_port"], 443.into());
+        assert_eq!(log["tcp_control_bits"], 2.into());
+    }
+
+    #[test]
+    fn decodes_counters_samples() {
+        let mut counters = Vec::new();
+        counters.extend(3u32.to_be_bytes());
+        counters.extend(6u32.to_be_bytes());
+        counters.extend(10_000_000_000u64.to_be_bytes());
+        counters.extend(1u32.to_be_bytes());
+        counters.extend(3u32.to_be_bytes());
+        counters.extend(123_456u64.to_be_bytes());
+        counters.extend([0; 24]);
+        counters.extend(654_321u64.to_be_bytes());
+        counters.extend([0; 24]);
+
+        let mut sample = Vec::new();
+        sample.extend(1u32.to_be_bytes());
+        sample.extend(3u32.to_be_bytes());
+        sample.extend(2u32.to_be_bytes());
+        sample.extend(record(GENERIC_INTERFACE_COUNTERS, &counters));
+        // An enterprise-specific record, which is skipped.
+        sample.extend(record((4300 << 12) | 1, &[1, 2, 3, 4]));
+
+        let decoded = decode(Reader::new(&datagram(&[(COUNTERS_SAMPLE, sample)]))).unwrap();
+
+        assert_eq!(decoded.events.len(), 1);
+        let log = &decoded.events[0];
+        assert_eq!(log["record_type"], "counters".into());
+        assert_eq!(log["if_index"], 3.into());
+        assert_eq!(log["if_speed"], 10_000_000_000i64.into());
+        assert_eq!(log["if_in_octets"], 123_456.into());
+        assert_eq!(log["if_out_octets"], 654_321.into());
+    }
+
+    #[test]
+    fn skips_unknown_samples() {
+        let decoded = decode(Reader::new(&datagram(&[((4300 << 12) | 1, vec![0; 8])]))).unwrap();
+        assert!(decoded.events.is_empty());
+    }
+}
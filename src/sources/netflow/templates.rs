@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use super::{
+    fields::{decode, decode_unknown, lookup, ExportTime},
+    reader::{ParseError, Reader},
+};
+use crate::event::{LogEvent, Value};
+
+/// The field length marking variable-length fields in IPFIX templates.
+pub(super) const VARIABLE_LENGTH: u16 = 65535;
+
+/// Identifies the information a template field holds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum FieldId {
+    /// An IANA information element, which NetFlow v9 field types share their numbering with.
+    Standard(u16),
+    /// An enterprise-specific IPFIX information element.
+    Enterprise { enterprise: u32, id: u16 },
+    /// A NetFlow v9 options scope, which is numbered separately from fields.
+    Scope(u16),
+}
+
+/// A field of a template.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct TemplateField {
+    pub(super) id: FieldId,
+    pub(super) length: u16,
+}
+
+impl TemplateField {
+    fn name(&self) -> String {
+        match self.id {
+            FieldId::Standard(id) => {
+                lookup(id).map_or_else(|| format!("field_{}", id), |(name, _)| name.to_owned())
+            }
+            FieldId::Enterprise { enterprise, id } => format!("enterprise_{}_{}", enterprise, id),
+            FieldId::Scope(id) => match id {
+                1 => "scope_system".to_owned(),
+                2 => "scope_interface".to_owned(),
+                3 => "scope_line_card".to_owned(),
+                4 => "scope_cache".to_owned(),
+                5 => "scope_template".to_owned(),
+                id => format!("scope_{}", id),
+            },
+        }
+    }
+
+    fn decode(&self, data: &[u8], time: &ExportTime) -> Value {
+        match self.id {
+            FieldId::Standard(id) => match lookup(id) {
+                Some((_, field_type)) => decode(field_type, data, time),
+                None => decode_unknown(data),
+            },
+            FieldId::Enterprise { .. } | FieldId::Scope(_) => decode_unknown(data),
+        }
+    }
+}
+
+/// Describes the layout of the data records of a NetFlow v9 or IPFIX data set.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Template {
+    pub(super) fields: Vec<TemplateField>,
+    /// The number of leading scope fields, which is only non-zero for options templates.
+    pub(super) scope_field_count: usize,
+}
+
+impl Template {
+    /// The shortest possible record, counting one byte for each variable-length field.
+    fn min_record_length(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => length as usize,
+            })
+            .sum()
+    }
+
+    /// Decodes the data records of a data set into log events. Sets are padded to a 4-byte
+    /// boundary, so trailing bytes too short to hold a record are ignored.
+    pub(super) fn decode_records(
+        &self,
+        mut set: Reader,
+        time: &ExportTime,
+    ) -> Result<Vec<LogEvent>, ParseError> {
+        let min_record_length = self.min_record_length();
+        if min_record_length == 0 {
+            return Ok(Vec::new());
+        }
+
+        let record_type = if self.scope_field_count > 0 {
+            "options"
+        } else {
+            "flow"
+        };
+
+        let mut events = Vec::new();
+        while set.remaining() >= min_record_length {
+            let mut log = LogEvent::default();
+            for field in &self.fields {
+                let length = match field.length {
+                    VARIABLE_LENGTH => match set.u8()? {
+                        255 => set.u16()? as usize,
+                        length => length as usize,
+                    },
+                    length => length as usize,
+                };
+                let value = field.decode(set.bytes(length)?, time);
+                log.insert(field.name().as_str(), value);
+            }
+            log.insert("record_type", record_type);
+            events.push(log);
+        }
+
+        Ok(events)
+    }
+}
+
+/// Identifies a template, which is scoped to the exporter and its observation domain, or source
+/// ID in NetFlow v9.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(super) struct TemplateKey {
+    pub(super) exporter: SocketAddr,
+    pub(super) domain: u32,
+    pub(super) template_id: u16,
+}
+
+/// Templates received from exporters, which are needed to decode their data sets.
+///
+/// Exporters periodically resend their templates, so templates that haven't been refreshed within
+/// the timeout are assumed to be stale, for example after an exporter was reconfigured.
+pub(super) struct TemplateCache {
+    templates: HashMap<TemplateKey, (Template, Instant)>,
+    timeout: Duration,
+}
+
+impl TemplateCache {
+    pub(super) fn new(timeout: Duration) -> Self {
+        Self {
+            templates: HashMap::new(),
+            timeout,
+        }
+    }
+
+    pub(super) fn insert(&mut self, key: TemplateKey, template: Template) {
+        let now = Instant::now();
+        if !self.templates.contains_key(&key) {
+            // New templates are rare, so this is a good time to forget about stale ones.
+            let timeout = self.timeout;
+            self.templates
+                .retain(|_, (_, updated)| now.duration_since(*updated) <= timeout);
+        }
+        self.templates.insert(key, (template, now));
+    }
+
+    pub(super) fn remove(&mut self, key: &TemplateKey) {
+        self.templates.remove(key);
+    }
+
+    pub(super) fn get(&mut self, key: &TemplateKey) -> Option<&Template> {
+        let expired = self
+            .templates
+            .get(key)
+            .map_or(false, |(_, updated)| updated.elapsed() > self.timeout);
+        if expired {
+            self.templates.remove(key);
+        }
+        self.templates.get(key).map(|(template, _)| template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn key(template_id: u16) -> TemplateKey {
+        TemplateKey {
+            exporter: "192.0.2.1:2055".parse().unwrap(),
+            domain: 0,
+            template_id,
+        }
+    }
+
+    #[test]
+    fn decodes_variable_length_and_enterprise_fields() {
+        let template = Template {
+            fields: vec![
+                TemplateField {
+                    id: FieldId::Standard(8),
+                    length: 4,
+                },
+                TemplateField {
+                    id: FieldId::Standard(96),
+                    length: VARIABLE_LENGTH,
+                },
+                TemplateField {
+                    id: FieldId::Enterprise {
+                        enterprise: 9,
+                        id: 1,
+                    },
+                    length: 2,
+                },
+                TemplateField {
+                    id: FieldId::Standard(1000),
+                    length: 1,
+                },
+            ],
+            scope_field_count: 0,
+        };
+        let mut data = vec![10, 0, 0, 1, 4];
+        data.extend(b"http");
+        data.extend([0x01, 0x02, 7]);
+        // Padding
+        data.extend([0, 0]);
+
+        let time = ExportTime {
+            export_time: Utc.timestamp(0, 0),
+            sys_uptime_ms: None,
+        };
+        let events = template.decode_records(Reader::new(&data), &time).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(events[0]["application_name"], "http".into());
+        assert_eq!(events[0]["enterprise_9_1"], 258.into());
+        assert_eq!(events[0]["field_1000"], 7.into());
+        assert_eq!(events[0]["record_type"], "flow".into());
+    }
+
+    #[test]
+    fn expires_stale_templates() {
+        let template = Template {
+            fields: Vec::new(),
+            scope_field_count: 0,
+        };
+
+        let mut cache = TemplateCache::new(Duration::from_secs(60));
+        cache.insert(key(256), template.clone());
+        assert!(cache.get(&key(256)).is_some());
+        assert!(cache.get(&key(257)).is_none());
+
+        let mut cache = TemplateCache::new(Duration::ZERO);
+        cache.insert(key(256), template);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.get(&key(256)).is_none());
+    }
+}
@@ -0,0 +1,158 @@
+use std::net::Ipv4Addr;
+
+use chrono::{TimeZone, Utc};
+
+use super::{
+    fields::ExportTime,
+    reader::{ParseError, Reader},
+};
+use crate::{config::log_schema, event::LogEvent};
+
+const HEADER_LENGTH: usize = 24;
+const RECORD_LENGTH: usize = 48;
+/// The most records a datagram can hold, according to the format specification.
+const MAX_RECORDS: usize = 30;
+
+/// Decodes a NetFlow v5 datagram, which holds a fixed layout of IPv4 flow records.
+pub(super) fn decode(mut datagram: Reader) -> Result<Vec<LogEvent>, ParseError> {
+    let mut header = datagram.sub(HEADER_LENGTH)?;
+    let _version = header.u16()?;
+    let count = header.u16()? as usize;
+    let sys_uptime_ms = header.u32()?;
+    let unix_secs = header.u32()?;
+    let unix_nsecs = header.u32()?;
+    let flow_sequence = header.u32()?;
+    let engine_type = header.u8()?;
+    let engine_id = header.u8()?;
+    let sampling = header.u16()?;
+
+    if count > MAX_RECORDS {
+        return Err(ParseError::InvalidLength {
+            what: "record count",
+            length: count,
+        });
+    }
+
+    let time = ExportTime {
+        export_time: Utc.timestamp(unix_secs as i64, unix_nsecs.min(999_999_999)),
+        sys_uptime_ms: Some(sys_uptime_ms),
+    };
+
+    let mut events = Vec::with_capacity(count);
+    for _ in 0..count {
+        let mut record = datagram.sub(RECORD_LENGTH)?;
+
+        let mut log = LogEvent::default();
+        log.insert("flow_type", "netflow_v5");
+        log.insert("record_type", "flow");
+        log.insert(log_schema().timestamp_key(), time.export_time);
+        log.insert("flow_sequence", flow_sequence as i64);
+        log.insert("engine_type", engine_type as i64);
+        log.insert("engine_id", engine_id as i64);
+        // The two most significant bits hold the sampling mode.
+        log.insert("sampling_interval", (sampling & 0x3fff) as i64);
+
+        log.insert("source_ipv4_address", ipv4(&mut record)?);
+        log.insert("destination_ipv4_address", ipv4(&mut record)?);
+        log.insert("ip_next_hop_ipv4_address", ipv4(&mut record)?);
+        log.insert("ingress_interface", record.u16()? as i64);
+        log.insert("egress_interface", record.u16()? as i64);
+        log.insert("packet_delta_count", record.u32()? as i64);
+        log.insert("octet_delta_count", record.u32()? as i64);
+        let first = record.u32()?;
+        let last = record.u32()?;
+        if let Some(start) = time.resolve_uptime(first) {
+            log.insert("flow_start_sys_up_time", start);
+        }
+        if let Some(end) = time.resolve_uptime(last) {
+            log.insert("flow_end_sys_up_time", end);
+        }
+        log.insert("source_transport_port", record.u16()? as i64);
+        log.insert("destination_transport_port", record.u16()? as i64);
+        record.skip(1)?;
+        log.insert("tcp_control_bits", record.u8()? as i64);
+        log.insert("protocol_identifier", record.u8()? as i64);
+        log.insert("ip_class_of_service", record.u8()? as i64);
+        log.insert("bgp_source_as_number", record.u16()? as i64);
+        log.insert("bgp_destination_as_number", record.u16()? as i64);
+        log.insert("source_ipv4_prefix_length", record.u8()? as i64);
+        log.insert("destination_ipv4_prefix_length", record.u8()? as i64);
+
+        events.push(log);
+    }
+
+    Ok(events)
+}
+
+fn ipv4(record: &mut Reader) -> Result<String, ParseError> {
+    Ok(Ipv4Addr::from(record.array::<4>()?).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Value;
+
+    fn datagram() -> Vec<u8> {
+        let mut data = Vec::new();
+        // Header
+        data.extend(5u16.to_be_bytes());
+        data.extend(1u16.to_be_bytes());
+        data.extend(60_000u32.to_be_bytes());
+        data.extend(1_659_348_000u32.to_be_bytes());
+        data.extend(0u32.to_be_bytes());
+        data.extend(42u32.to_be_bytes());
+        data.extend([1, 2]);
+        data.extend((0x4000u16 | 100).to_be_bytes());
+        // Record
+        data.extend([10, 0, 0, 1]);
+        data.extend([192, 0, 2, 10]);
+        data.extend([10, 0, 0, 254]);
+        data.extend(3u16.to_be_bytes());
+        data.extend(4u16.to_be_bytes());
+        data.extend(10u32.to_be_bytes());
+        data.extend(1500u32.to_be_bytes());
+        data.extend(50_000u32.to_be_bytes());
+        data.extend(59_000u32.to_be_bytes());
+        data.extend(54321u16.to_be_bytes());
+        data.extend(443u16.to_be_bytes());
+        data.extend([0, 0x1b, 6, 0]);
+        data.extend(64512u16.to_be_bytes());
+        data.extend(64513u16.to_be_bytes());
+        data.extend([24, 16, 0, 0]);
+        data
+    }
+
+    #[test]
+    fn decodes_records() {
+        let events = decode(Reader::new(&datagram())).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let log = &events[0];
+        assert_eq!(log["flow_type"], "netflow_v5".into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log["destination_ipv4_address"], "192.0.2.10".into());
+        assert_eq!(log["source_transport_port"], 54321.into());
+        assert_eq!(log["destination_transport_port"], 443.into());
+        assert_eq!(log["protocol_identifier"], 6.into());
+        assert_eq!(log["tcp_control_bits"], 0x1b.into());
+        assert_eq!(log["octet_delta_count"], 1500.into());
+        assert_eq!(log["packet_delta_count"], 10.into());
+        assert_eq!(log["sampling_interval"], 100.into());
+        assert_eq!(log["source_ipv4_prefix_length"], 24.into());
+        assert_eq!(
+            log["flow_start_sys_up_time"],
+            Value::Timestamp(Utc.timestamp(1_659_347_990, 0))
+        );
+        assert_eq!(
+            log["flow_end_sys_up_time"],
+            Value::Timestamp(Utc.timestamp(1_659_347_999, 0))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_datagrams() {
+        let data = datagram();
+        assert!(decode(Reader::new(&data[..data.len() - 1])).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+use std::net::SocketAddr;
+
+use chrono::{TimeZone, Utc};
+
+use super::{
+    fields::ExportTime,
+    reader::{ParseError, Reader},
+    templates::{FieldId, Template, TemplateCache, TemplateField, TemplateKey},
+    Decoded,
+};
+use crate::config::log_schema;
+
+const HEADER_LENGTH: usize = 20;
+const TEMPLATE_FLOWSET_ID: u16 = 0;
+const OPTIONS_TEMPLATE_FLOWSET_ID: u16 = 1;
+/// Flowset IDs below this are reserved, and template IDs start from it.
+const MIN_DATA_FLOWSET_ID: u16 = 256;
+
+/// Decodes a NetFlow v9 datagram. Templates are added to the cache, and data flowsets are
+/// decoded with previously received templates of the same exporter.
+pub(super) fn decode(
+    mut datagram: Reader,
+    exporter: SocketAddr,
+    templates: &mut TemplateCache,
+) -> Result<Decoded, ParseError> {
+    let mut header = datagram.sub(HEADER_LENGTH)?;
+    let _version = header.u16()?;
+    let _count = header.u16()?;
+    let sys_uptime_ms = header.u32()?;
+    let unix_secs = header.u32()?;
+    let sequence = header.u32()?;
+    let source_id = header.u32()?;
+
+    let time = ExportTime {
+        export_time: Utc.timestamp(unix_secs as i64, 0),
+        sys_uptime_ms: Some(sys_uptime_ms),
+    };
+
+    let mut decoded = Decoded::default();
+    while datagram.remaining() >= 4 {
+        let flowset_id = datagram.u16()?;
+        let length = datagram.u16()? as usize;
+        if length < 4 {
+            return Err(ParseError::InvalidLength {
+                what: "flowset",
+                length,
+            });
+        }
+        let flowset = datagram.sub(length - 4)?;
+
+        match flowset_id {
+            TEMPLATE_FLOWSET_ID => decode_templates(flowset, exporter, source_id, templates)?,
+            OPTIONS_TEMPLATE_FLOWSET_ID => {
+                decode_options_templates(flowset, exporter, source_id, templates)?
+            }
+            id if id < MIN_DATA_FLOWSET_ID => {}
+            template_id => {
+                let key = TemplateKey {
+                    exporter,
+                    domain: source_id,
+                    template_id,
+                };
+                match templates.get(&key) {
+                    Some(template) => {
+                        for mut log in template.decode_records(flowset, &time)? {
+                            log.insert("flow_type", "netflow_v9");
+                            log.insert(log_schema().timestamp_key(), time.export_time);
+                            log.insert("flow_sequence", sequence as i64);
+                            log.insert("source_id", source_id as i64);
+                            decoded.events.push(log);
+                        }
+                    }
+                    None => decoded.missing_templates.push(template_id),
+                }
+            }
+        }
+    }
+
+    Ok(decoded)
+}
+
+fn decode_templates(
+    mut flowset: Reader,
+    exporter: SocketAddr,
+    source_id: u32,
+    templates: &mut TemplateCache,
+) -> Result<(), ParseError> {
+    while flowset.remaining() >= 4 {
+        let template_id = flowset.u16()?;
+        if template_id < MIN_DATA_FLOWSET_ID {
+            // Padding
+            break;
+        }
+        let field_count = flowset.u16()?;
+        let fields = (0..field_count)
+            .map(|_| field(&mut flowset, FieldId::Standard))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        templates.insert(
+            TemplateKey {
+                exporter,
+                domain: source_id,
+                template_id,
+            },
+            Template {
+                fields,
+                scope_field_count: 0,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+fn decode_options_templates(
+    mut flowset: Reader,
+    exporter: SocketAddr,
+    source_id: u32,
+    templates: &mut TemplateCache,
+) -> Result<(), ParseError> {
+    while flowset.remaining() >= 6 {
+        let template_id = flowset.u16()?;
+        if template_id < MIN_DATA_FLOWSET_ID {
+            break;
+        }
+        // Both lengths are in bytes, with four bytes per field.
+        let scope_field_count = flowset.u16()? as usize / 4;
+        let option_field_count = flowset.u16()? as usize / 4;
+        let mut fields = Vec::with_capacity(scope_field_count + option_field_count);
+        for _ in 0..scope_field_count {
+            fields.push(field(&mut flowset, FieldId::Scope)?);
+        }
+        for _ in 0..option_field_count {
+            fields.push(field(&mut flowset, FieldId::Standard)?);
+        }
+
+        templates.insert(
+            TemplateKey {
+                exporter,
+                domain: source_id,
+                template_id,
+            },
+            Template {
+                fields,
+                scope_field_count,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+fn field(flowset: &mut Reader, id: fn(u16) -> FieldId) -> Result<TemplateField, ParseError> {
+    Ok(TemplateField {
+        id: id(flowset.u16()?),
+        length: flowset.u16()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::event::Value;
+
+    fn header(count: u16) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(9u16.to_be_bytes());
+        data.extend(count.to_be_bytes());
+        data.extend(60_000u32.to_be_bytes());
+        data.extend(1_659_348_000u32.to_be_bytes());
+        data.extend(7u32.to_be_bytes());
+        data.extend(1u32.to_be_bytes());
+        data
+    }
+
+    fn template_flowset() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(TEMPLATE_FLOWSET_ID.to_be_bytes());
+        data.extend(24u16.to_be_bytes());
+        data.extend(256u16.to_be_bytes());
+        data.extend(4u16.to_be_bytes());
+        for (field_type, length) in [(8u16, 4u16), (12, 4), (1, 4), (22, 4)] {
+            data.extend(field_type.to_be_bytes());
+            data.extend(length.to_be_bytes());
+        }
+        data
+    }
+
+    fn data_flowset() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend(256u16.to_be_bytes());
+        data.extend(20u16.to_be_bytes());
+        data.extend([10, 0, 0, 1]);
+        data.extend([192, 0, 2, 10]);
+        data.extend(1500u32.to_be_bytes());
+        data.extend(55_000u32.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn decodes_data_with_cached_templates() {
+        let exporter = "192.0.2.1:2055".parse().unwrap();
+        let mut templates = TemplateCache::new(Duration::from_secs(60));
+
+        // Data received before its template can't be decoded.
+        let mut datagram = header(1);
+        datagram.extend(data_flowset());
+        let decoded = decode(Reader::new(&datagram), exporter, &mut templates).unwrap();
+        assert!(decoded.events.is_empty());
+        assert_eq!(decoded.missing_templates, vec![256]);
+
+        let mut datagram = header(1);
+        datagram.extend(template_flowset());
+        let decoded = decode(Reader::new(&datagram), exporter, &mut templates).unwrap();
+        assert!(decoded.events.is_empty());
+        assert!(decoded.missing_templates.is_empty());
+
+        let mut datagram = header(1);
+        datagram.extend(data_flowset());
+        let decoded = decode(Reader::new(&datagram), exporter, &mut templates).unwrap();
+        assert_eq!(decoded.events.len(), 1);
+        let log = &decoded.events[0];
+        assert_eq!(log["flow_type"], "netflow_v9".into());
+        assert_eq!(log["record_type"], "flow".into());
+        assert_eq!(log["source_id"], 1.into());
+        assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+        assert_eq!(log["destination_ipv4_address"], "192.0.2.10".into());
+        assert_eq!(log["octet_delta_count"], 1500.into());
+        assert_eq!(
+            log["flow_start_sys_up_time"],
+            Value::Timestamp(Utc.timestamp(1_659_347_995, 0))
+        );
+
+        // Templates are scoped to the exporter.
+        let other = "192.0.2.2:2055".parse().unwrap();
+        let decoded = decode(Reader::new(&datagram), other, &mut templates).unwrap();
+        assert_eq!(decoded.missing_templates, vec![256]);
+    }
+
+    #[test]
+    fn decodes_options_templates() {
+        let exporter = "192.0.2.1:2055".parse().unwrap();
+        let mut templates = TemplateCache::new(Duration::from_secs(60));
+
+        let mut datagram = header(2);
+        // Options template flowset with a system scope and the sampling interval.
+        datagram.extend(OPTIONS_TEMPLATE_FLOWSET_ID.to_be_bytes());
+        datagram.extend(20u16.to_be_bytes());
+        datagram.extend(257u16.to_be_bytes());
+        datagram.extend(4u16.to_be_bytes());
+        datagram.extend(4u16.to_be_bytes());
+        datagram.extend([0, 1, 0, 4]);
+        datagram.extend([0, 34, 0, 4]);
+        datagram.extend([0, 0]);
+        // Data flowset
+        datagram.extend(257u16.to_be_bytes());
+        datagram.extend(12u16.to_be_bytes());
+        datagram.extend([192, 0, 2, 1]);
+        datagram.extend(100u32.to_be_bytes());
+
+        let decoded = decode(Reader::new(&datagram), exporter, &mut templates).unwrap();
+        assert_eq!(decoded.events.len(), 1);
+        let log = &decoded.events[0];
+        assert_eq!(log["record_type"], "options".into());
+        assert_eq!(log["scope_system"], 0xc000_0201_i64.into());
+        assert_eq!(log["sampling_interval"], 100.into());
+    }
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: GPL-2.0
+//
+// Tracks TCP connections from the moment they are established until they are closed, and emits a
+// flow record to user space on close. Byte counts are gathered from `tcp_sendmsg` and
+// `tcp_cleanup_rbuf`, which see every byte an application writes to or reads from a socket.
+//
+// The program doesn't read kernel structures, only tracepoint and function arguments, so it
+// doesn't depend on BTF or the layout of `struct sock`.
+
+#include <linux/bpf.h>
+#include <linux/in.h>
+#include <linux/ptrace.h>
+#include <linux/types.h>
+#include <bpf/bpf_helpers.h>
+#include <bpf/bpf_tracing.h>
+
+#define AF_INET 2
+#define AF_INET6 10
+
+#define TCP_ESTABLISHED 1
+#define TCP_SYN_SENT 2
+#define TCP_SYN_RECV 3
+#define TCP_CLOSE 7
+
+#define DIRECTION_OUTBOUND 1
+#define DIRECTION_INBOUND 2
+
+#define MAX_FLOWS 65536
+
+// Must match `FlowRecord` in `mod.rs`.
+struct flow_record {
+    __u64 start_ns;
+    __u64 end_ns;
+    __u64 bytes_sent;
+    __u64 bytes_received;
+    __u32 pid;
+    __u16 family;
+    __u16 sport;
+    __u16 dport;
+    __u8 direction;
+    __u8 _pad1;
+    __u8 saddr[16];
+    __u8 daddr[16];
+    char comm[16];
+    __u32 _pad2;
+};
+
+// Layout of the `sock/inet_sock_set_state` tracepoint, see
+// `/sys/kernel/debug/tracing/events/sock/inet_sock_set_state/format`.
+struct inet_sock_set_state_args {
+    __u64 common;
+    const void *skaddr;
+    int oldstate;
+    int newstate;
+    __u16 sport;
+    __u16 dport;
+    __u16 family;
+    __u16 protocol;
+    __u8 saddr[4];
+    __u8 daddr[4];
+    __u8 saddr_v6[16];
+    __u8 daddr_v6[16];
+};
+
+struct {
+    __uint(type, BPF_MAP_TYPE_LRU_HASH);
+    __uint(max_entries, MAX_FLOWS);
+    __type(key, __u64);
+    __type(value, struct flow_record);
+} FLOWS SEC(".maps");
+
+struct {
+    __uint(type, BPF_MAP_TYPE_PERF_EVENT_ARRAY);
+    __uint(key_size, sizeof(__u32));
+    __uint(value_size, sizeof(__u32));
+} EVENTS SEC(".maps");
+
+SEC("tracepoint/sock/inet_sock_set_state")
+int inet_sock_set_state(struct inet_sock_set_state_args *args)
+{
+    if (args->protocol != IPPROTO_TCP)
+        return 0;
+
+    __u64 key = (__u64)args->skaddr;
+
+    // Outbound connections are tracked from `connect`, which runs in the context of the
+    // connecting process. Inbound connections are established in softirq context, so the
+    // process isn't known.
+    if ((args->oldstate == TCP_CLOSE && args->newstate == TCP_SYN_SENT) ||
+        (args->oldstate == TCP_SYN_RECV && args->newstate == TCP_ESTABLISHED)) {
+        struct flow_record record = {};
+        record.start_ns = bpf_ktime_get_ns();
+        record.family = args->family;
+        record.sport = args->sport;
+        record.dport = args->dport;
+        if (args->family == AF_INET) {
+            __builtin_memcpy(record.saddr, args->saddr, 4);
+            __builtin_memcpy(record.daddr, args->daddr, 4);
+        } else {
+            __builtin_memcpy(record.saddr, args->saddr_v6, 16);
+            __builtin_memcpy(record.daddr, args->daddr_v6, 16);
+        }
+        if (args->newstate == TCP_SYN_SENT) {
+            record.direction = DIRECTION_OUTBOUND;
+            record.pid = bpf_get_current_pid_tgid() >> 32;
+            bpf_get_current_comm(&record.comm, sizeof(record.comm));
+        } else {
+            record.direction = DIRECTION_INBOUND;
+        }
+        bpf_map_update_elem(&FLOWS, &key, &record, BPF_ANY);
+        return 0;
+    }
+
+    if (args->newstate == TCP_CLOSE) {
+        struct flow_record *record = bpf_map_lookup_elem(&FLOWS, &key);
+        if (!record)
+            return 0;
+        record->end_ns = bpf_ktime_get_ns();
+        bpf_perf_event_output(args, &EVENTS, BPF_F_CURRENT_CPU, record, sizeof(*record));
+        bpf_map_delete_elem(&FLOWS, &key);
+    }
+
+    return 0;
+}
+
+SEC("kprobe/tcp_sendmsg")
+int BPF_KPROBE(tcp_sendmsg, void *sk, void *msg, __u64 size)
+{
+    __u64 key = (__u64)sk;
+    struct flow_record *record = bpf_map_lookup_elem(&FLOWS, &key);
+    if (record)
+        __sync_fetch_and_add(&record->bytes_sent, size);
+    return 0;
+}
+
+SEC("kprobe/tcp_cleanup_rbuf")
+int BPF_KPROBE(tcp_cleanup_rbuf, void *sk, int copied)
+{
+    if (copied <= 0)
+        return 0;
+
+    __u64 key = (__u64)sk;
+    struct flow_record *record = bpf_map_lookup_elem(&FLOWS, &key);
+    if (record)
+        __sync_fetch_and_add(&record->bytes_received, copied);
+    return 0;
+}
+
+char LICENSE[] SEC("license") = "GPL";
@@ -0,0 +1,270 @@
+use aya::{
+    include_bytes_aligned,
+    maps::{
+        perf::{AsyncPerfEventArray, AsyncPerfEventArrayBuffer, PerfBufferError},
+        MapError, MapRefMut,
+    },
+    programs::{KProbe, ProgramError, TracePoint},
+    util::online_cpus,
+    Bpf, BpfError,
+};
+use bytes::BytesMut;
+use chrono::Utc;
+use futures::StreamExt;
+use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, Output, SourceConfig, SourceContext, SourceDescription},
+    internal_events::{
+        NetworkFlowsReadError, NetworkFlowsRecordsLost, OldEventsReceived, StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod record;
+
+use record::{FlowRecord, RECORD_SIZE};
+
+/// The eBPF program, compiled from `bpf/flows.bpf.c` by the build script.
+static PROGRAM: &[u8] = include_bytes_aligned!(concat!(env!("OUT_DIR"), "/network_flows.bpf.o"));
+
+const DEFAULT_PERF_BUFFER_PAGES: usize = 64;
+const DEFAULT_BATCH_SIZE: usize = 1000;
+/// The number of samples read from a perf buffer at once.
+const READ_BUFFERS: usize = 64;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("perf_buffer_pages must be a power of two, got {}", pages))]
+    InvalidPerfBufferPages { pages: usize },
+    #[snafu(display(
+        "Could not load the eBPF program, which requires root or the CAP_BPF and CAP_PERFMON capabilities: {}",
+        source
+    ))]
+    LoadProgram { source: BpfError },
+    #[snafu(display("Could not attach the eBPF program {:?}: {}", name, source))]
+    AttachProgram {
+        name: &'static str,
+        source: ProgramError,
+    },
+    #[snafu(display("Could not open the flow records map: {}", source))]
+    OpenMap { source: MapError },
+    #[snafu(display("Could not list online CPUs: {}", source))]
+    OnlineCpus { source: std::io::Error },
+    #[snafu(display("Could not open the perf buffer of CPU {}: {}", cpu, source))]
+    OpenPerfBuffer { cpu: u32, source: PerfBufferError },
+}
+
+/// Configuration for the `network_flows` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkFlowsConfig {
+    /// Whether to emit flows of connections between loopback addresses.
+    #[serde(default)]
+    pub include_loopback: bool,
+
+    /// The size of the buffer each CPU hands flow records over to Vector with, in memory pages.
+    ///
+    /// Must be a power of two. Records are lost if Vector doesn't keep up with the rate of closed
+    /// connections, which is reported by the `component_discarded_events_total` metric.
+    #[serde(default = "default_perf_buffer_pages")]
+    pub perf_buffer_pages: usize,
+}
+
+const fn default_perf_buffer_pages() -> usize {
+    DEFAULT_PERF_BUFFER_PAGES
+}
+
+impl Default for NetworkFlowsConfig {
+    fn default() -> Self {
+        Self {
+            include_loopback: false,
+            perf_buffer_pages: default_perf_buffer_pages(),
+        }
+    }
+}
+
+inventory::submit! {
+    SourceDescription::new::<NetworkFlowsConfig>("network_flows")
+}
+
+impl_generate_config_from_default!(NetworkFlowsConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "network_flows")]
+impl SourceConfig for NetworkFlowsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if !self.perf_buffer_pages.is_power_of_two() {
+            return Err(BuildError::InvalidPerfBufferPages {
+                pages: self.perf_buffer_pages,
+            }
+            .into());
+        }
+
+        let mut bpf = Bpf::load(PROGRAM).context(LoadProgramSnafu)?;
+        attach(&mut bpf)?;
+
+        let mut events =
+            AsyncPerfEventArray::try_from(bpf.map_mut("EVENTS").context(OpenMapSnafu)?)
+                .context(OpenMapSnafu)?;
+        let buffers = online_cpus()
+            .context(OnlineCpusSnafu)?
+            .into_iter()
+            .map(|cpu| {
+                events
+                    .open(cpu, Some(self.perf_buffer_pages))
+                    .context(OpenPerfBufferSnafu { cpu })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Box::pin(network_flows_source(
+            bpf,
+            buffers,
+            self.include_loopback,
+            cx.out,
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "network_flows"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Attaches the programs tracking connections. They stay attached until `Bpf` is dropped.
+fn attach(bpf: &mut Bpf) -> Result<(), BuildError> {
+    let name = "inet_sock_set_state";
+    let program: &mut TracePoint = bpf
+        .program_mut(name)
+        .expect("program is part of the object")
+        .try_into()
+        .context(AttachProgramSnafu { name })?;
+    program.load().context(AttachProgramSnafu { name })?;
+    program
+        .attach("sock", name)
+        .context(AttachProgramSnafu { name })?;
+
+    for name in ["tcp_sendmsg", "tcp_cleanup_rbuf"] {
+        let program: &mut KProbe = bpf
+            .program_mut(name)
+            .expect("program is part of the object")
+            .try_into()
+            .context(AttachProgramSnafu { name })?;
+        program.load().context(AttachProgramSnafu { name })?;
+        program
+            .attach(name, 0)
+            .context(AttachProgramSnafu { name })?;
+    }
+
+    Ok(())
+}
+
+async fn network_flows_source(
+    // Holding on to the programs keeps them attached while the source runs.
+    _bpf: Bpf,
+    buffers: Vec<AsyncPerfEventArrayBuffer<MapRefMut>>,
+    include_loopback: bool,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let hostname = crate::get_hostname().ok();
+
+    // Each CPU has its own perf buffer, which is drained by a dedicated task.
+    let (sender, receiver) = mpsc::channel(DEFAULT_BATCH_SIZE);
+    let readers = buffers
+        .into_iter()
+        .map(|buffer| tokio::spawn(read_records(buffer, sender.clone())))
+        .collect::<Vec<_>>();
+    drop(sender);
+
+    let mut records = ReceiverStream::new(receiver)
+        .ready_chunks(DEFAULT_BATCH_SIZE)
+        .take_until(shutdown);
+
+    let mut result = Ok(());
+    while let Some(records) = records.next().await {
+        let closed_at = Utc::now();
+        let events = records
+            .into_iter()
+            .filter(|record| include_loopback || !record.is_loopback())
+            .map(|record| record.into_log_event(closed_at, hostname.as_deref()))
+            .collect::<Vec<_>>();
+        if events.is_empty() {
+            continue;
+        }
+
+        emit!(OldEventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        let count = events.len();
+        if let Err(error) = out.send_batch(events).await {
+            emit!(StreamClosedError { error, count });
+            result = Err(());
+            break;
+        }
+    }
+
+    for reader in readers {
+        reader.abort();
+    }
+    result
+}
+
+async fn read_records(
+    mut buffer: AsyncPerfEventArrayBuffer<MapRefMut>,
+    out: mpsc::Sender<FlowRecord>,
+) {
+    let mut samples = (0..READ_BUFFERS)
+        .map(|_| BytesMut::with_capacity(RECORD_SIZE + 8))
+        .collect::<Vec<_>>();
+
+    loop {
+        let events = match buffer.read_events(&mut samples).await {
+            Ok(events) => events,
+            Err(error) => {
+                emit!(NetworkFlowsReadError { error });
+                return;
+            }
+        };
+        if events.lost > 0 {
+            emit!(NetworkFlowsRecordsLost { count: events.lost });
+        }
+
+        for sample in samples.iter_mut().take(events.read) {
+            let record = FlowRecord::parse(sample);
+            sample.clear();
+            if let Some(record) = record {
+                if out.send(record).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetworkFlowsConfig>();
+    }
+}
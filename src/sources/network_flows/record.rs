@@ -0,0 +1,246 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+
+use crate::{config::log_schema, event::LogEvent};
+
+/// The size of `struct flow_record` in `bpf/flows.bpf.c`. Perf samples can be padded past it.
+pub(super) const RECORD_SIZE: usize = 96;
+
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+const DIRECTION_OUTBOUND: u8 = 1;
+const DIRECTION_INBOUND: u8 = 2;
+
+/// Which end of the connection is local to the host.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum Direction {
+    /// The connection was opened by a local process.
+    Outbound,
+    /// The connection was accepted from a remote peer.
+    Inbound,
+}
+
+impl Direction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Outbound => "outbound",
+            Self::Inbound => "inbound",
+        }
+    }
+}
+
+/// A closed TCP connection, as reported by the eBPF program.
+#[derive(Debug, PartialEq)]
+pub(super) struct FlowRecord {
+    pub(super) direction: Direction,
+    pub(super) local: SocketAddr,
+    pub(super) remote: SocketAddr,
+    pub(super) duration_ns: u64,
+    pub(super) bytes_sent: u64,
+    pub(super) bytes_received: u64,
+    /// The process that opened the connection, only known for outbound connections.
+    pub(super) process: Option<(u32, String)>,
+}
+
+impl FlowRecord {
+    /// Decodes a record from a perf sample. Returns `None` if the sample is too short or holds an
+    /// address family or direction the program never emits.
+    pub(super) fn parse(data: &[u8]) -> Option<Self> {
+        let data: &[u8; RECORD_SIZE] = data.get(..RECORD_SIZE)?.try_into().ok()?;
+
+        let start_ns = u64::from_ne_bytes(array(&data[0..8]));
+        let end_ns = u64::from_ne_bytes(array(&data[8..16]));
+        let bytes_sent = u64::from_ne_bytes(array(&data[16..24]));
+        let bytes_received = u64::from_ne_bytes(array(&data[24..32]));
+        let pid = u32::from_ne_bytes(array(&data[32..36]));
+        let family = u16::from_ne_bytes(array(&data[36..38]));
+        let local_port = u16::from_ne_bytes(array(&data[38..40]));
+        let remote_port = u16::from_ne_bytes(array(&data[40..42]));
+        let direction = match data[42] {
+            DIRECTION_OUTBOUND => Direction::Outbound,
+            DIRECTION_INBOUND => Direction::Inbound,
+            _ => return None,
+        };
+
+        let (local_ip, remote_ip) = match family {
+            AF_INET => (
+                IpAddr::from(Ipv4Addr::from(array::<4>(&data[44..48]))),
+                IpAddr::from(Ipv4Addr::from(array::<4>(&data[60..64]))),
+            ),
+            AF_INET6 => (
+                ipv6(Ipv6Addr::from(array::<16>(&data[44..60]))),
+                ipv6(Ipv6Addr::from(array::<16>(&data[60..76]))),
+            ),
+            _ => return None,
+        };
+
+        let comm = &data[76..92];
+        let comm_len = comm.iter().position(|c| *c == 0).unwrap_or(comm.len());
+        let process = (direction == Direction::Outbound && pid != 0)
+            .then(|| (pid, String::from_utf8_lossy(&comm[..comm_len]).into_owned()));
+
+        Some(Self {
+            direction,
+            local: SocketAddr::new(local_ip, local_port),
+            remote: SocketAddr::new(remote_ip, remote_port),
+            duration_ns: end_ns.saturating_sub(start_ns),
+            bytes_sent,
+            bytes_received,
+            process,
+        })
+    }
+
+    /// Whether both ends of the connection are loopback addresses.
+    pub(super) fn is_loopback(&self) -> bool {
+        self.local.ip().is_loopback() && self.remote.ip().is_loopback()
+    }
+
+    /// Builds a log event for the flow, which closed at `closed_at`.
+    ///
+    /// The source is the end that opened the connection, while `bytes_sent` and `bytes_received`
+    /// are counted from the point of view of the host.
+    pub(super) fn into_log_event(
+        self,
+        closed_at: DateTime<Utc>,
+        hostname: Option<&str>,
+    ) -> LogEvent {
+        let (source, destination) = match self.direction {
+            Direction::Outbound => (self.local, self.remote),
+            Direction::Inbound => (self.remote, self.local),
+        };
+
+        let mut log = LogEvent::default();
+        log.insert("source_ip", source.ip().to_string());
+        log.insert("source_port", source.port() as i64);
+        log.insert("destination_ip", destination.ip().to_string());
+        log.insert("destination_port", destination.port() as i64);
+        log.insert("direction", self.direction.as_str());
+        log.insert("protocol", "tcp");
+        log.insert("bytes_sent", self.bytes_sent as i64);
+        log.insert("bytes_received", self.bytes_received as i64);
+        log.insert("duration_ms", self.duration_ns as f64 / 1_000_000.0);
+        if let Some((pid, name)) = self.process {
+            log.insert("process_id", pid as i64);
+            log.insert("process_name", name);
+        }
+        if let Some(hostname) = hostname {
+            log.insert(log_schema().host_key(), hostname.to_owned());
+        }
+        log.insert(log_schema().timestamp_key(), closed_at);
+        log.insert(log_schema().source_type_key(), Bytes::from("network_flows"));
+        log
+    }
+}
+
+/// IPv4 connections on dual-stack sockets are reported as IPv4-mapped IPv6 addresses.
+fn ipv6(address: Ipv6Addr) -> IpAddr {
+    match address.octets() {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, a, b, c, d] => IpAddr::from([a, b, c, d]),
+        _ => IpAddr::V6(address),
+    }
+}
+
+fn array<const N: usize>(data: &[u8]) -> [u8; N] {
+    data.try_into().expect("slice has the array length")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::Value;
+
+    fn record(family: u16, direction: u8, local: &[u8], remote: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; RECORD_SIZE + 4];
+        data[0..8].copy_from_slice(&1_000_000u64.to_ne_bytes());
+        data[8..16].copy_from_slice(&251_000_000u64.to_ne_bytes());
+        data[16..24].copy_from_slice(&512u64.to_ne_bytes());
+        data[24..32].copy_from_slice(&4096u64.to_ne_bytes());
+        data[32..36].copy_from_slice(&4242u32.to_ne_bytes());
+        data[36..38].copy_from_slice(&family.to_ne_bytes());
+        data[38..40].copy_from_slice(&54321u16.to_ne_bytes());
+        data[40..42].copy_from_slice(&443u16.to_ne_bytes());
+        data[42] = direction;
+        data[44..44 + local.len()].copy_from_slice(local);
+        data[60..60 + remote.len()].copy_from_slice(remote);
+        data[76..80].copy_from_slice(b"curl");
+        data
+    }
+
+    #[test]
+    fn parses_outbound_ipv4_flow() {
+        let data = record(
+            AF_INET,
+            DIRECTION_OUTBOUND,
+            &[10, 0, 0, 5],
+            &[93, 184, 216, 34],
+        );
+        let flow = FlowRecord::parse(&data).unwrap();
+
+        assert_eq!(flow.direction, Direction::Outbound);
+        assert_eq!(flow.local, "10.0.0.5:54321".parse().unwrap());
+        assert_eq!(flow.remote, "93.184.216.34:443".parse().unwrap());
+        assert_eq!(flow.duration_ns, 250_000_000);
+        assert_eq!(flow.process, Some((4242, "curl".to_owned())));
+
+        let closed_at = Utc.ymd(2022, 8, 1).and_hms(10, 0, 0);
+        let log = flow.into_log_event(closed_at, Some("host.example.com"));
+        assert_eq!(log["source_ip"], "10.0.0.5".into());
+        assert_eq!(log["source_port"], 54321.into());
+        assert_eq!(log["destination_ip"], "93.184.216.34".into());
+        assert_eq!(log["destination_port"], 443.into());
+        assert_eq!(log["direction"], "outbound".into());
+        assert_eq!(log["bytes_sent"], 512.into());
+        assert_eq!(log["bytes_received"], 4096.into());
+        assert_eq!(log["duration_ms"], 250.0.into());
+        assert_eq!(log["process_id"], 4242.into());
+        assert_eq!(log["process_name"], "curl".into());
+        assert_eq!(log[log_schema().host_key()], "host.example.com".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(closed_at)
+        );
+    }
+
+    #[test]
+    fn parses_inbound_ipv6_flow() {
+        let local = "2001:db8::1".parse::<Ipv6Addr>().unwrap().octets();
+        let remote = "2001:db8::2".parse::<Ipv6Addr>().unwrap().octets();
+        let data = record(AF_INET6, DIRECTION_INBOUND, &local, &remote);
+        let flow = FlowRecord::parse(&data).unwrap();
+
+        // The process isn't known for accepted connections.
+        assert_eq!(flow.process, None);
+
+        let log = flow.into_log_event(Utc::now(), None);
+        assert_eq!(log["source_ip"], "2001:db8::2".into());
+        assert_eq!(log["source_port"], 443.into());
+        assert_eq!(log["destination_ip"], "2001:db8::1".into());
+        assert_eq!(log["destination_port"], 54321.into());
+        assert_eq!(log["direction"], "inbound".into());
+        assert!(!log.contains("process_id"));
+    }
+
+    #[test]
+    fn unmaps_ipv4_mapped_addresses() {
+        let local = "::ffff:127.0.0.1".parse::<Ipv6Addr>().unwrap().octets();
+        let remote = "::ffff:127.0.0.1".parse::<Ipv6Addr>().unwrap().octets();
+        let data = record(AF_INET6, DIRECTION_OUTBOUND, &local, &remote);
+        let flow = FlowRecord::parse(&data).unwrap();
+
+        assert_eq!(flow.local.ip(), IpAddr::from([127, 0, 0, 1]));
+        assert!(flow.is_loopback());
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        let data = record(AF_INET, DIRECTION_OUTBOUND, &[10, 0, 0, 5], &[10, 0, 0, 6]);
+        assert!(FlowRecord::parse(&data[..RECORD_SIZE - 1]).is_none());
+        assert!(FlowRecord::parse(&record(1, DIRECTION_OUTBOUND, &[], &[])).is_none());
+        assert!(FlowRecord::parse(&record(AF_INET, 0, &[], &[])).is_none());
+    }
+}
@@ -4,17 +4,23 @@ use crate::{
         SourceContext,
     },
     internal_events::{EventsReceived, StreamClosedError},
-    opentelemetry::LogService::{
-        logs_service_server::{LogsService, LogsServiceServer},
-        ExportLogsServiceRequest, ExportLogsServiceResponse,
+    opentelemetry::{
+        LogService::{
+            logs_service_server::{LogsService, LogsServiceServer},
+            ExportLogsServiceRequest, ExportLogsServiceResponse,
+        },
+        MetricService::metrics_service_server::MetricsServiceServer,
     },
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{
+        util::grpc::{run_grpc_server_with_routes, Http2ServerConfig},
+        Source,
+    },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
 use futures::TryFutureExt;
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 use tonic::{Request, Response, Status};
 use vector_config::configurable_component;
 use vector_core::{
@@ -23,6 +29,8 @@ use vector_core::{
     ByteSizeOf,
 };
 
+use super::metric::{self, MetricConverter, MetricsConfig, METRICS};
+
 pub const LOGS: &str = "logs";
 
 /// Configuration for the `opentelemetry` source.
@@ -35,6 +43,10 @@ pub struct OpentelemetryConfig {
     /// It _must_ include a port.
     address: SocketAddr,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    http2: Http2ServerConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
@@ -42,14 +54,20 @@ pub struct OpentelemetryConfig {
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    metrics: MetricsConfig,
 }
 
 impl GenerateConfig for OpentelemetryConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             address: "0.0.0.0:4317".parse().unwrap(),
+            http2: Default::default(),
             tls: Default::default(),
             acknowledgements: Default::default(),
+            metrics: Default::default(),
         })
         .unwrap()
     }
@@ -61,21 +79,41 @@ impl SourceConfig for OpentelemetryConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
         let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
-        let service = LogsServiceServer::new(Service {
+        let logs_service = LogsServiceServer::new(Service {
+            pipeline: cx.out.clone(),
+            acknowledgements,
+        })
+        .accept_gzip();
+        let metrics_service = MetricsServiceServer::new(metric::Service {
             pipeline: cx.out,
             acknowledgements,
+            converter: Arc::new(MetricConverter::new(self.metrics.clone())),
         })
         .accept_gzip();
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
+        let source = run_grpc_server_with_routes(
+            self.address,
+            tls_settings,
+            self.http2,
+            cx.shutdown,
+            cx.ready.defer(),
+            |server| {
+                server
+                    .add_service(logs_service)
+                    .add_service(metrics_service)
+            },
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
 
         Ok(Box::pin(source))
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
-        vec![Output::default(DataType::Log).with_port(LOGS)]
+        vec![
+            Output::default(DataType::Log).with_port(LOGS),
+            Output::default(DataType::Metric).with_port(METRICS),
+        ]
     }
 
     fn source_type(&self) -> &'static str {
@@ -131,7 +169,9 @@ impl LogsService for Service {
     }
 }
 
-async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
+pub(super) async fn handle_batch_status(
+    receiver: Option<BatchStatusReceiver>,
+) -> Result<(), Status> {
     let status = match receiver {
         Some(receiver) => receiver.await,
         None => BatchStatus::Delivered,
@@ -0,0 +1,681 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use futures::TryFutureExt;
+use tonic::{Request, Response, Status};
+use value::Value;
+use vector_config::configurable_component;
+use vector_core::{
+    event::{
+        metric::{Bucket, MetricSeries, Quantile},
+        BatchNotifier, Event, Metric, MetricKind, MetricTags, MetricValue,
+    },
+    ByteSizeOf,
+};
+
+use super::log::handle_batch_status;
+use crate::{
+    internal_events::{EventsReceived, StreamClosedError},
+    opentelemetry::{
+        Common::{any_value, AnyValue, KeyValue},
+        MetricService::{
+            metrics_service_server, ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+        },
+        Metrics::{
+            exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+            AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint,
+            HistogramDataPoint, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+        },
+    },
+    sinks::util::buffer::metrics::MetricSet,
+    SourceSender,
+};
+
+pub const METRICS: &str = "metrics";
+
+/// The temporality metrics are converted to.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporalityPreference {
+    /// Keep the temporality each metric was sent with.
+    ///
+    /// Delta metrics are emitted as incremental metrics, and cumulative ones as absolute metrics.
+    Preserve,
+
+    /// Convert cumulative sums and histograms to incremental metrics.
+    ///
+    /// The first data point of each cumulative series is only used as a reference, and is not emitted.
+    Delta,
+
+    /// Convert delta sums and histograms to absolute metrics, by accumulating them.
+    Cumulative,
+}
+
+impl Default for TemporalityPreference {
+    fn default() -> Self {
+        Self::Preserve
+    }
+}
+
+/// A rule selecting attributes to add as metric tags.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeTagRule {
+    /// The name of the attribute to select.
+    ///
+    /// A trailing `*` selects every attribute starting with the preceding prefix, and `*` alone selects all of them.
+    attribute: String,
+
+    /// The name of the tag the attribute is added as.
+    ///
+    /// Defaults to the name of the attribute. For wildcard rules, this is a prefix prepended to the name of each
+    /// selected attribute.
+    tag: Option<String>,
+}
+
+impl AttributeTagRule {
+    fn tag_for(&self, key: &str) -> Option<String> {
+        let tag = self.tag.as_deref();
+        match self.attribute.strip_suffix('*') {
+            Some(prefix) => key
+                .starts_with(prefix)
+                .then(|| format!("{}{}", tag.unwrap_or_default(), key)),
+            None => (key == self.attribute).then(|| tag.unwrap_or(key).to_string()),
+        }
+    }
+}
+
+const fn default_series_ttl_secs() -> u64 {
+    600
+}
+
+/// Configuration for the metrics received by the `opentelemetry` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MetricsConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    temporality: TemporalityPreference,
+
+    /// The time, in seconds, after which a series that is no longer received is forgotten when converting
+    /// temporality.
+    ///
+    /// A forgotten series starts over: its next cumulative data point is only used as a reference, and its delta
+    /// data points are accumulated from zero.
+    #[serde(default = "default_series_ttl_secs")]
+    series_ttl_secs: u64,
+
+    /// Rules selecting the resource attributes added as tags to each metric.
+    ///
+    /// The first matching rule is used for each attribute. By default, no resource attributes are added.
+    #[serde(default)]
+    resource_attributes: Vec<AttributeTagRule>,
+
+    /// Rules selecting the instrumentation scope attributes added as tags to each metric.
+    ///
+    /// The first matching rule is used for each attribute. By default, no scope attributes are added.
+    #[serde(default)]
+    scope_attributes: Vec<AttributeTagRule>,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            temporality: TemporalityPreference::default(),
+            series_ttl_secs: default_series_ttl_secs(),
+            resource_attributes: Vec::new(),
+            scope_attributes: Vec::new(),
+        }
+    }
+}
+
+/// The state of the series whose temporality is converted.
+struct ConversionState {
+    metrics: MetricSet,
+    last_seen: HashMap<MetricSeries, Instant>,
+    last_expiry: Instant,
+}
+
+impl ConversionState {
+    fn new() -> Self {
+        Self {
+            metrics: MetricSet::default(),
+            last_seen: HashMap::new(),
+            last_expiry: Instant::now(),
+        }
+    }
+
+    fn touch(&mut self, series: &MetricSeries, now: Instant) {
+        match self.last_seen.get_mut(series) {
+            Some(last_seen) => *last_seen = now,
+            None => {
+                self.last_seen.insert(series.clone(), now);
+            }
+        }
+    }
+
+    /// Forgets the series that haven't been seen for `ttl`, at most once every `ttl`.
+    fn expire(&mut self, now: Instant, ttl: Duration) {
+        if now.saturating_duration_since(self.last_expiry) < ttl {
+            return;
+        }
+        if let Some(deadline) = now.checked_sub(ttl) {
+            let metrics = &mut self.metrics;
+            self.last_seen.retain(|series, last_seen| {
+                let keep = *last_seen >= deadline;
+                if !keep {
+                    metrics.remove(series);
+                }
+                keep
+            });
+        }
+        self.last_expiry = now;
+    }
+}
+
+/// Converts OTLP metrics into Vector metrics, following the configured temporality and tag rules.
+pub(super) struct MetricConverter {
+    config: MetricsConfig,
+    state: Mutex<ConversionState>,
+}
+
+impl MetricConverter {
+    pub(super) fn new(config: MetricsConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(ConversionState::new()),
+        }
+    }
+
+    pub(super) fn convert(&self, resource_metrics: Vec<ResourceMetrics>) -> Vec<Event> {
+        let mut metrics = Vec::new();
+        for resource_metrics in resource_metrics {
+            let mut resource_tags = MetricTags::new();
+            if let Some(resource) = &resource_metrics.resource {
+                map_attributes(
+                    &self.config.resource_attributes,
+                    &resource.attributes,
+                    &mut resource_tags,
+                );
+            }
+            for scope_metrics in resource_metrics.scope_metrics {
+                let mut scope_tags = resource_tags.clone();
+                if let Some(scope) = &scope_metrics.scope {
+                    map_attributes(
+                        &self.config.scope_attributes,
+                        &scope.attributes,
+                        &mut scope_tags,
+                    );
+                }
+                for metric in scope_metrics.metrics {
+                    if let Some(data) = metric.data {
+                        convert_data(&metric.name, data, &scope_tags, &mut metrics);
+                    }
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let mut state = self.state.lock().expect("poisoned lock");
+        state.expire(now, Duration::from_secs(self.config.series_ttl_secs));
+        metrics
+            .into_iter()
+            .filter_map(|(metric, has_temporality)| {
+                if !has_temporality {
+                    return Some(metric);
+                }
+                match self.config.temporality {
+                    TemporalityPreference::Preserve => Some(metric),
+                    TemporalityPreference::Delta => {
+                        state.touch(metric.series(), now);
+                        state.metrics.make_incremental(metric)
+                    }
+                    TemporalityPreference::Cumulative => {
+                        state.touch(metric.series(), now);
+                        state.metrics.make_absolute(metric)
+                    }
+                }
+            })
+            .map(Event::Metric)
+            .collect()
+    }
+}
+
+/// Adds the attributes selected by `rules` to `tags`.
+fn map_attributes(rules: &[AttributeTagRule], attributes: &[KeyValue], tags: &mut MetricTags) {
+    for attribute in attributes {
+        if let Some(tag) = rules.iter().find_map(|rule| rule.tag_for(&attribute.key)) {
+            tags.insert(tag, tag_value(attribute.value.as_ref()));
+        }
+    }
+}
+
+fn tag_value(value: Option<&AnyValue>) -> String {
+    match value.and_then(|value| value.value.clone()) {
+        Some(any_value::Value::StringValue(value)) => value,
+        Some(any_value::Value::BoolValue(value)) => value.to_string(),
+        Some(any_value::Value::IntValue(value)) => value.to_string(),
+        Some(any_value::Value::DoubleValue(value)) => value.to_string(),
+        Some(value) => serde_json::to_string(&Value::from(value)).unwrap_or_default(),
+        None => String::new(),
+    }
+}
+
+fn point_tags(base: &MetricTags, attributes: &[KeyValue]) -> Option<MetricTags> {
+    let mut tags = base.clone();
+    for attribute in attributes {
+        tags.insert(attribute.key.clone(), tag_value(attribute.value.as_ref()));
+    }
+    (!tags.is_empty()).then(|| tags)
+}
+
+fn timestamp(time_unix_nano: u64) -> Option<DateTime<Utc>> {
+    (time_unix_nano > 0).then(|| Utc.timestamp_nanos(time_unix_nano as i64))
+}
+
+const fn has_no_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::FlagNoRecordedValue as u32 != 0
+}
+
+fn kind(temporality: i32) -> MetricKind {
+    if temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    match point.value? {
+        number_data_point::Value::AsDouble(value) => Some(value),
+        number_data_point::Value::AsInt(value) => Some(value as f64),
+    }
+}
+
+/// Converts the data points of a metric, flagging those whose temporality can be converted.
+fn convert_data(name: &str, data: Data, tags: &MetricTags, metrics: &mut Vec<(Metric, bool)>) {
+    let new_metric = |kind, value, attributes: &[KeyValue], time_unix_nano| {
+        Metric::new(name, kind, value)
+            .with_tags(point_tags(tags, attributes))
+            .with_timestamp(timestamp(time_unix_nano))
+    };
+
+    match data {
+        Data::Gauge(gauge) => {
+            for point in gauge.data_points {
+                if has_no_recorded_value(point.flags) {
+                    continue;
+                }
+                if let Some(value) = number_value(&point) {
+                    metrics.push((
+                        new_metric(
+                            MetricKind::Absolute,
+                            MetricValue::Gauge { value },
+                            &point.attributes,
+                            point.time_unix_nano,
+                        ),
+                        false,
+                    ));
+                }
+            }
+        }
+        Data::Sum(sum) => {
+            let kind = kind(sum.aggregation_temporality);
+            for point in sum.data_points {
+                if has_no_recorded_value(point.flags) {
+                    continue;
+                }
+                if let Some(value) = number_value(&point) {
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    metrics.push((
+                        new_metric(kind, value, &point.attributes, point.time_unix_nano),
+                        true,
+                    ));
+                }
+            }
+        }
+        Data::Histogram(histogram) => {
+            let kind = kind(histogram.aggregation_temporality);
+            for point in histogram.data_points {
+                if has_no_recorded_value(point.flags) {
+                    continue;
+                }
+                let time_unix_nano = point.time_unix_nano;
+                let attributes = point.attributes.clone();
+                metrics.push((
+                    new_metric(kind, histogram_value(point), &attributes, time_unix_nano),
+                    true,
+                ));
+            }
+        }
+        Data::ExponentialHistogram(histogram) => {
+            let kind = kind(histogram.aggregation_temporality);
+            for point in histogram.data_points {
+                if has_no_recorded_value(point.flags) {
+                    continue;
+                }
+                let time_unix_nano = point.time_unix_nano;
+                let attributes = point.attributes.clone();
+                metrics.push((
+                    new_metric(
+                        kind,
+                        exponential_histogram_value(point),
+                        &attributes,
+                        time_unix_nano,
+                    ),
+                    true,
+                ));
+            }
+        }
+        Data::Summary(summary) => {
+            for point in summary.data_points {
+                if has_no_recorded_value(point.flags) {
+                    continue;
+                }
+                let time_unix_nano = point.time_unix_nano;
+                let attributes = point.attributes.clone();
+                metrics.push((
+                    new_metric(
+                        MetricKind::Absolute,
+                        summary_value(point),
+                        &attributes,
+                        time_unix_nano,
+                    ),
+                    false,
+                ));
+            }
+        }
+    }
+}
+
+fn histogram_value(point: HistogramDataPoint) -> MetricValue {
+    // The last bucket count is for values above the last bound, which is implied by `count`.
+    let buckets = point
+        .explicit_bounds
+        .into_iter()
+        .zip(point.bucket_counts)
+        .map(|(upper_limit, count)| Bucket { upper_limit, count })
+        .collect();
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum,
+    }
+}
+
+/// Converts an exponential histogram into buckets with explicit upper limits.
+fn exponential_histogram_value(point: ExponentialHistogramDataPoint) -> MetricValue {
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut buckets = Vec::new();
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = point.negative
+    {
+        for (index, count) in bucket_counts.into_iter().enumerate().rev() {
+            buckets.push(Bucket {
+                upper_limit: -base.powi(offset + index as i32),
+                count,
+            });
+        }
+    }
+    if point.zero_count > 0 || !buckets.is_empty() {
+        buckets.push(Bucket {
+            upper_limit: 0.0,
+            count: point.zero_count,
+        });
+    }
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = point.positive
+    {
+        for (index, count) in bucket_counts.into_iter().enumerate() {
+            buckets.push(Bucket {
+                upper_limit: base.powi(offset + index as i32 + 1),
+                count,
+            });
+        }
+    }
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum,
+    }
+}
+
+fn summary_value(point: SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .into_iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: point.count,
+        sum: point.sum,
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Service {
+    pub(super) pipeline: SourceSender,
+    pub(super) acknowledgements: bool,
+    pub(super) converter: Arc<MetricConverter>,
+}
+
+#[tonic::async_trait]
+impl metrics_service_server::MetricsService for Service {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let mut events = self
+            .converter
+            .convert(request.into_inner().resource_metrics);
+
+        let count = events.len();
+        let byte_size = events.size_of();
+
+        emit!(EventsReceived { count, byte_size });
+
+        let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
+
+        self.pipeline
+            .clone()
+            .send_batch_named(METRICS, events)
+            .map_err(|error| {
+                let message = error.to_string();
+                emit!(StreamClosedError { error, count });
+                Status::unavailable(message)
+            })
+            .and_then(|_| handle_batch_status(receiver))
+            .await?;
+        Ok(Response::new(ExportMetricsServiceResponse {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opentelemetry::{
+        Metrics::{metric, Histogram, ResourceMetrics, ScopeMetrics, Sum},
+        Resource as OtelResource,
+    };
+
+    fn string_attribute(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.into())),
+            }),
+        }
+    }
+
+    fn request(data: metric::Data) -> Vec<ResourceMetrics> {
+        vec![ResourceMetrics {
+            resource: Some(OtelResource {
+                attributes: vec![
+                    string_attribute("service.name", "checkout"),
+                    string_attribute("k8s.pod.name", "checkout-0"),
+                    string_attribute("host.arch", "amd64"),
+                ],
+                dropped_attributes_count: 0,
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: None,
+                metrics: vec![crate::opentelemetry::Metrics::Metric {
+                    name: "requests".into(),
+                    description: String::new(),
+                    unit: String::new(),
+                    data: Some(data),
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }]
+    }
+
+    fn cumulative_sum(value: f64) -> metric::Data {
+        metric::Data::Sum(Sum {
+            data_points: vec![NumberDataPoint {
+                attributes: vec![string_attribute("method", "GET")],
+                start_time_unix_nano: 1,
+                time_unix_nano: 2,
+                value: Some(number_data_point::Value::AsDouble(value)),
+                exemplars: vec![],
+                flags: 0,
+            }],
+            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            is_monotonic: true,
+        })
+    }
+
+    fn converter(config: &str) -> MetricConverter {
+        MetricConverter::new(toml::from_str(config).unwrap())
+    }
+
+    #[test]
+    fn maps_selected_resource_attributes() {
+        let converter = converter(
+            r#"
+            resource_attributes = [
+                { attribute = "service.name", tag = "service" },
+                { attribute = "k8s.*", tag = "resource_" },
+            ]
+            "#,
+        );
+        let events = converter.convert(request(cumulative_sum(1.0)));
+        let metric = events[0].as_metric();
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        assert_eq!(
+            metric.tags().unwrap(),
+            &MetricTags::from([
+                ("method".to_string(), "GET".to_string()),
+                (
+                    "resource_k8s.pod.name".to_string(),
+                    "checkout-0".to_string()
+                ),
+                ("service".to_string(), "checkout".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn converts_cumulative_to_delta() {
+        let converter = converter(r#"temporality = "delta""#);
+        assert!(converter.convert(request(cumulative_sum(3.0))).is_empty());
+
+        let events = converter.convert(request(cumulative_sum(5.0)));
+        let metric = events[0].as_metric();
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.value(), &MetricValue::Counter { value: 2.0 });
+        assert_eq!(
+            metric.tags().unwrap(),
+            &MetricTags::from([("method".to_string(), "GET".to_string())])
+        );
+    }
+
+    #[test]
+    fn forgets_expired_series() {
+        let converter = converter(r#"temporality = "delta""#);
+        assert!(converter.convert(request(cumulative_sum(3.0))).is_empty());
+
+        let later = Instant::now() + Duration::from_secs(default_series_ttl_secs() + 1);
+        let mut state = converter.state.lock().unwrap();
+        state.expire(later, Duration::from_secs(default_series_ttl_secs()));
+        assert!(state.metrics.is_empty());
+        assert!(state.last_seen.is_empty());
+        drop(state);
+
+        // The series starts over, with its next data point as the reference.
+        assert!(converter.convert(request(cumulative_sum(5.0))).is_empty());
+    }
+
+    #[test]
+    fn converts_histogram_buckets() {
+        let converter = converter("");
+        let events = converter.convert(request(metric::Data::Histogram(Histogram {
+            data_points: vec![HistogramDataPoint {
+                attributes: vec![],
+                start_time_unix_nano: 0,
+                time_unix_nano: 0,
+                count: 6,
+                sum: 12.5,
+                bucket_counts: vec![1, 2, 3],
+                explicit_bounds: vec![1.0, 5.0],
+                exemplars: vec![],
+                flags: 0,
+                min: 0.0,
+                max: 0.0,
+            }],
+            aggregation_temporality: AggregationTemporality::Delta as i32,
+        })));
+        let metric = events[0].as_metric();
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 1, 5.0 => 2],
+                count: 6,
+                sum: 12.5,
+            }
+        );
+    }
+
+    #[test]
+    fn exponential_histogram_bounds() {
+        let value = exponential_histogram_value(ExponentialHistogramDataPoint {
+            scale: 0,
+            count: 4,
+            sum: 3.0,
+            zero_count: 1,
+            positive: Some(Buckets {
+                offset: 0,
+                bucket_counts: vec![2, 1],
+            }),
+            ..Default::default()
+        });
+        assert_eq!(
+            value,
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![0.0 => 1, 2.0 => 2, 4.0 => 1],
+                count: 4,
+                sum: 3.0,
+            }
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod log;
+mod metric;
 
 use crate::config::SourceDescription;
 use log::OpentelemetryConfig;
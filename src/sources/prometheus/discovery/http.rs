@@ -0,0 +1,98 @@
+use hyper::{Body, Request};
+use serde::Deserialize;
+
+use super::{Discoverer, Labels};
+use crate::{
+    config::ProxyConfig,
+    http::{Auth, HttpClient},
+    tls::TlsSettings,
+};
+
+/// A group of targets returned by an HTTP service discovery endpoint.
+#[derive(Debug, Deserialize)]
+struct TargetGroup {
+    targets: Vec<String>,
+    #[serde(default)]
+    labels: Labels,
+}
+
+pub(super) struct HttpDiscoverer {
+    url: ::http::Uri,
+    auth: Option<Auth>,
+    client: HttpClient,
+}
+
+impl HttpDiscoverer {
+    pub(super) fn new(
+        url: &str,
+        auth: Option<Auth>,
+        tls: TlsSettings,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            url: url.parse()?,
+            auth,
+            client: HttpClient::new(tls, proxy)?,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Discoverer for HttpDiscoverer {
+    fn mechanism(&self) -> &'static str {
+        "http"
+    }
+
+    async fn discover(&self) -> crate::Result<Vec<Labels>> {
+        let mut request = Request::get(&self.url)
+            .header(::http::header::ACCEPT, "application/json")
+            .body(Body::empty())?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await?;
+        let (parts, body) = response.into_parts();
+        if !parts.status.is_success() {
+            return Err(format!("unexpected HTTP status {}", parts.status).into());
+        }
+        let body = hyper::body::to_bytes(body).await?;
+        let groups: Vec<TargetGroup> = serde_json::from_slice(&body)?;
+        Ok(labels_from_groups(groups))
+    }
+}
+
+fn labels_from_groups(groups: Vec<TargetGroup>) -> Vec<Labels> {
+    groups
+        .into_iter()
+        .flat_map(|group| {
+            let labels = group.labels;
+            group.targets.into_iter().map(move |target| {
+                let mut labels = labels.clone();
+                labels.insert("__address__".to_string(), target);
+                labels
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_target_groups() {
+        let groups: Vec<TargetGroup> = serde_json::from_str(
+            r#"[
+                {"targets": ["10.0.0.1:9100", "10.0.0.2:9100"], "labels": {"job": "node"}},
+                {"targets": ["10.0.0.3:8080"]}
+            ]"#,
+        )
+        .unwrap();
+        let labels = labels_from_groups(groups);
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels[1]["__address__"], "10.0.0.2:9100");
+        assert_eq!(labels[1]["job"], "node");
+        assert!(!labels[2].contains_key("job"));
+    }
+}
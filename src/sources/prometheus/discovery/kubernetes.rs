@@ -0,0 +1,343 @@
+use k8s_openapi::{
+    api::core::v1::{Endpoints, Pod},
+    apimachinery::pkg::apis::meta::v1::ObjectMeta,
+};
+use kube::{api::ListParams, Api, Client, Resource};
+use serde::de::DeserializeOwned;
+
+use super::{sanitize_label_name, Discoverer, KubernetesRole, Labels};
+
+pub(super) struct KubernetesDiscoverer {
+    client: Client,
+    role: KubernetesRole,
+    namespaces: Vec<String>,
+    list_params: ListParams,
+}
+
+impl KubernetesDiscoverer {
+    pub(super) async fn new(
+        role: KubernetesRole,
+        namespaces: &[String],
+        label_selector: Option<&str>,
+        field_selector: Option<&str>,
+    ) -> crate::Result<Self> {
+        let mut list_params = ListParams::default();
+        if let Some(selector) = label_selector {
+            list_params = list_params.labels(selector);
+        }
+        if let Some(selector) = field_selector {
+            list_params = list_params.fields(selector);
+        }
+        Ok(Self {
+            client: Client::try_default().await?,
+            role,
+            namespaces: namespaces.to_vec(),
+            list_params,
+        })
+    }
+
+    async fn list<K>(&self) -> crate::Result<Vec<K>>
+    where
+        K: Resource + Clone + DeserializeOwned + std::fmt::Debug,
+        K::DynamicType: Default,
+    {
+        if self.namespaces.is_empty() {
+            let api = Api::<K>::all(self.client.clone());
+            return Ok(api.list(&self.list_params).await?.items);
+        }
+        let mut items = Vec::new();
+        for namespace in &self.namespaces {
+            let api = Api::<K>::namespaced(self.client.clone(), namespace);
+            items.extend(api.list(&self.list_params).await?.items);
+        }
+        Ok(items)
+    }
+}
+
+#[async_trait::async_trait]
+impl Discoverer for KubernetesDiscoverer {
+    fn mechanism(&self) -> &'static str {
+        "kubernetes"
+    }
+
+    async fn discover(&self) -> crate::Result<Vec<Labels>> {
+        Ok(match self.role {
+            KubernetesRole::Pod => self
+                .list::<Pod>()
+                .await?
+                .iter()
+                .flat_map(pod_labels)
+                .collect(),
+            KubernetesRole::Endpoints => self
+                .list::<Endpoints>()
+                .await?
+                .iter()
+                .flat_map(endpoints_labels)
+                .collect(),
+        })
+    }
+}
+
+fn insert_metadata(labels: &mut Labels, prefix: &str, metadata: &ObjectMeta) {
+    if let Some(namespace) = &metadata.namespace {
+        labels.insert("__meta_kubernetes_namespace".to_string(), namespace.clone());
+    }
+    if let Some(name) = &metadata.name {
+        labels.insert(format!("__meta_kubernetes_{}_name", prefix), name.clone());
+    }
+    for (name, value) in metadata.labels.iter().flatten() {
+        labels.insert(
+            format!(
+                "__meta_kubernetes_{}_label_{}",
+                prefix,
+                sanitize_label_name(name)
+            ),
+            value.clone(),
+        );
+    }
+    for (name, value) in metadata.annotations.iter().flatten() {
+        labels.insert(
+            format!(
+                "__meta_kubernetes_{}_annotation_{}",
+                prefix,
+                sanitize_label_name(name)
+            ),
+            value.clone(),
+        );
+    }
+}
+
+fn pod_labels(pod: &Pod) -> Vec<Labels> {
+    let status = match &pod.status {
+        Some(status) if status.phase.as_deref() == Some("Running") => status,
+        _ => return Vec::new(),
+    };
+    let ip = match &status.pod_ip {
+        Some(ip) => ip,
+        None => return Vec::new(),
+    };
+
+    let mut base = Labels::new();
+    insert_metadata(&mut base, "pod", &pod.metadata);
+    base.insert("__meta_kubernetes_pod_ip".to_string(), ip.clone());
+    if let Some(node) = pod.spec.as_ref().and_then(|spec| spec.node_name.as_ref()) {
+        base.insert("__meta_kubernetes_pod_node_name".to_string(), node.clone());
+    }
+
+    let mut targets = Vec::new();
+    for container in pod.spec.iter().flat_map(|spec| &spec.containers) {
+        for port in container.ports.iter().flatten() {
+            let mut labels = base.clone();
+            labels.insert(
+                "__address__".to_string(),
+                format!("{}:{}", host(ip), port.container_port),
+            );
+            labels.insert(
+                "__meta_kubernetes_pod_container_name".to_string(),
+                container.name.clone(),
+            );
+            labels.insert(
+                "__meta_kubernetes_pod_container_port_number".to_string(),
+                port.container_port.to_string(),
+            );
+            if let Some(name) = &port.name {
+                labels.insert(
+                    "__meta_kubernetes_pod_container_port_name".to_string(),
+                    name.clone(),
+                );
+            }
+            targets.push(labels);
+        }
+    }
+    // Pods without declared ports are still discovered, so that relabel rules can set the port
+    // from an annotation.
+    if targets.is_empty() {
+        base.insert("__address__".to_string(), host(ip));
+        targets.push(base);
+    }
+    targets
+}
+
+/// Brackets IPv6 addresses, so that they can be followed by a port and used as the host of a URL.
+fn host(ip: &str) -> String {
+    if ip.contains(':') {
+        format!("[{}]", ip)
+    } else {
+        ip.to_string()
+    }
+}
+
+fn endpoints_labels(endpoints: &Endpoints) -> Vec<Labels> {
+    let mut base = Labels::new();
+    insert_metadata(&mut base, "endpoints", &endpoints.metadata);
+
+    let mut targets = Vec::new();
+    for subset in endpoints.subsets.iter().flatten() {
+        let addresses = subset
+            .addresses
+            .iter()
+            .flatten()
+            .map(|address| (address, true))
+            .chain(
+                subset
+                    .not_ready_addresses
+                    .iter()
+                    .flatten()
+                    .map(|address| (address, false)),
+            );
+        for (address, ready) in addresses {
+            for port in subset.ports.iter().flatten() {
+                let mut labels = base.clone();
+                labels.insert(
+                    "__address__".to_string(),
+                    format!("{}:{}", host(&address.ip), port.port),
+                );
+                labels.insert(
+                    "__meta_kubernetes_endpoint_ready".to_string(),
+                    ready.to_string(),
+                );
+                if let Some(name) = &port.name {
+                    labels.insert(
+                        "__meta_kubernetes_endpoint_port_name".to_string(),
+                        name.clone(),
+                    );
+                }
+                if let Some(protocol) = &port.protocol {
+                    labels.insert(
+                        "__meta_kubernetes_endpoint_port_protocol".to_string(),
+                        protocol.clone(),
+                    );
+                }
+                if let Some(node) = &address.node_name {
+                    labels.insert(
+                        "__meta_kubernetes_endpoint_node_name".to_string(),
+                        node.clone(),
+                    );
+                }
+                if let Some(target) = &address.target_ref {
+                    if target.kind.as_deref() == Some("Pod") {
+                        if let Some(name) = &target.name {
+                            labels.insert("__meta_kubernetes_pod_name".to_string(), name.clone());
+                        }
+                    }
+                }
+                targets.push(labels);
+            }
+        }
+    }
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::core::v1::{
+        Container, ContainerPort, EndpointAddress, EndpointPort, EndpointSubset, PodSpec, PodStatus,
+    };
+
+    use super::*;
+
+    fn metadata(name: &str) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some("default".to_string()),
+            labels: Some([("app.kubernetes.io/name".to_string(), "web".to_string())].into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn discovers_pod_ports() {
+        let pod = Pod {
+            metadata: metadata("web-0"),
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    name: "web".to_string(),
+                    ports: Some(vec![ContainerPort {
+                        container_port: 9100,
+                        name: Some("metrics".to_string()),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            status: Some(PodStatus {
+                phase: Some("Running".to_string()),
+                pod_ip: Some("10.0.0.1".to_string()),
+                ..Default::default()
+            }),
+        };
+        let labels = pod_labels(&pod);
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0]["__address__"], "10.0.0.1:9100");
+        assert_eq!(labels[0]["__meta_kubernetes_pod_name"], "web-0");
+        assert_eq!(
+            labels[0]["__meta_kubernetes_pod_container_port_name"],
+            "metrics"
+        );
+        assert_eq!(
+            labels[0]["__meta_kubernetes_pod_label_app_kubernetes_io_name"],
+            "web"
+        );
+
+        let pending = Pod {
+            status: Some(PodStatus {
+                phase: Some("Pending".to_string()),
+                ..Default::default()
+            }),
+            ..pod
+        };
+        assert!(pod_labels(&pending).is_empty());
+    }
+
+    #[test]
+    fn discovers_endpoint_addresses() {
+        let endpoints = Endpoints {
+            metadata: metadata("web"),
+            subsets: Some(vec![EndpointSubset {
+                addresses: Some(vec![EndpointAddress {
+                    ip: "10.0.0.1".to_string(),
+                    ..Default::default()
+                }]),
+                not_ready_addresses: Some(vec![EndpointAddress {
+                    ip: "10.0.0.2".to_string(),
+                    ..Default::default()
+                }]),
+                ports: Some(vec![EndpointPort {
+                    port: 8080,
+                    ..Default::default()
+                }]),
+            }]),
+        };
+        let labels = endpoints_labels(&endpoints);
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0]["__address__"], "10.0.0.1:8080");
+        assert_eq!(labels[0]["__meta_kubernetes_endpoint_ready"], "true");
+        assert_eq!(labels[1]["__meta_kubernetes_endpoint_ready"], "false");
+        assert_eq!(labels[1]["__meta_kubernetes_endpoints_name"], "web");
+    }
+
+    #[test]
+    fn brackets_ipv6_addresses() {
+        let endpoints = Endpoints {
+            metadata: metadata("web"),
+            subsets: Some(vec![EndpointSubset {
+                addresses: Some(vec![EndpointAddress {
+                    ip: "fd00::1".to_string(),
+                    ..Default::default()
+                }]),
+                ports: Some(vec![EndpointPort {
+                    port: 8080,
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }]),
+        };
+        let labels = endpoints_labels(&endpoints);
+        assert_eq!(labels[0]["__address__"], "[fd00::1]:8080");
+        assert_eq!(
+            super::super::endpoint(&labels[0]).unwrap(),
+            "http://[fd00::1]:8080/metrics"
+        );
+    }
+}
@@ -0,0 +1,261 @@
+//! Service discovery for the `prometheus_scrape` source.
+//!
+//! Each discovery mechanism periodically produces a list of label sets, one per target, using the
+//! same `__address__`, `__scheme__`, `__metrics_path__` and `__param_<name>` conventions as
+//! Prometheus. These are rewritten by the configured relabel rules and turned into scrape targets.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use futures::StreamExt;
+use tokio::sync::watch;
+use tokio_stream::wrappers::IntervalStream;
+use vector_config::configurable_component;
+
+use crate::{
+    config::ProxyConfig, http::Auth, internal_events::PrometheusServiceDiscoveryError,
+    shutdown::ShutdownSignal, tls::TlsSettings,
+};
+
+mod http;
+#[cfg(feature = "kubernetes")]
+mod kubernetes;
+mod relabel;
+
+pub use relabel::{RelabelConfig, Relabeler};
+
+/// The labels of a discovered target.
+pub type Labels = BTreeMap<String, String>;
+
+/// A target to scrape, along with the labels added to the metrics scraped from it.
+#[derive(Clone, Debug)]
+pub struct Target {
+    pub url: ::http::Uri,
+    pub labels: Labels,
+}
+
+const fn default_http_refresh_interval_secs() -> u64 {
+    60
+}
+
+const fn default_kubernetes_refresh_interval_secs() -> u64 {
+    30
+}
+
+/// A mechanism for discovering scrape targets.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServiceDiscoveryConfig {
+    /// Fetch targets from an HTTP endpoint, in the format of Prometheus' HTTP service discovery.
+    ///
+    /// The endpoint must return a JSON array of objects with a `targets` list of `host:port` addresses and an
+    /// optional `labels` map applied to each of them.
+    Http {
+        /// The URL to fetch targets from.
+        url: String,
+
+        /// The interval between fetches, in seconds.
+        #[serde(default = "default_http_refresh_interval_secs")]
+        refresh_interval_secs: u64,
+
+        #[configurable(derived)]
+        auth: Option<Auth>,
+    },
+
+    /// Discover targets from the Kubernetes API.
+    Kubernetes {
+        #[configurable(derived)]
+        role: KubernetesRole,
+
+        /// The namespaces to discover targets in.
+        ///
+        /// By default, targets are discovered in all namespaces.
+        #[serde(default)]
+        namespaces: Vec<String>,
+
+        /// A Kubernetes label selector restricting the discovered objects.
+        label_selector: Option<String>,
+
+        /// A Kubernetes field selector restricting the discovered objects.
+        field_selector: Option<String>,
+
+        /// The interval between listings, in seconds.
+        #[serde(default = "default_kubernetes_refresh_interval_secs")]
+        refresh_interval_secs: u64,
+    },
+}
+
+/// The kind of Kubernetes object targets are discovered from.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KubernetesRole {
+    /// One target per declared container port of each running pod.
+    Pod,
+
+    /// One target per address and port of each `Endpoints` object.
+    Endpoints,
+}
+
+/// A source of target label sets.
+#[async_trait::async_trait]
+pub trait Discoverer: Send + Sync {
+    fn mechanism(&self) -> &'static str;
+
+    async fn discover(&self) -> crate::Result<Vec<Labels>>;
+}
+
+impl ServiceDiscoveryConfig {
+    pub async fn build(
+        &self,
+        tls: &TlsSettings,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<(Box<dyn Discoverer>, Duration)> {
+        match self {
+            Self::Http {
+                url,
+                refresh_interval_secs,
+                auth,
+            } => Ok((
+                Box::new(http::HttpDiscoverer::new(
+                    url,
+                    auth.clone(),
+                    tls.clone(),
+                    proxy,
+                )?),
+                Duration::from_secs(*refresh_interval_secs),
+            )),
+            #[cfg(feature = "kubernetes")]
+            Self::Kubernetes {
+                role,
+                namespaces,
+                label_selector,
+                field_selector,
+                refresh_interval_secs,
+            } => Ok((
+                Box::new(
+                    kubernetes::KubernetesDiscoverer::new(
+                        *role,
+                        namespaces,
+                        label_selector.as_deref(),
+                        field_selector.as_deref(),
+                    )
+                    .await?,
+                ),
+                Duration::from_secs(*refresh_interval_secs),
+            )),
+            #[cfg(not(feature = "kubernetes"))]
+            Self::Kubernetes { .. } => {
+                Err("Kubernetes service discovery is not compiled into this build".into())
+            }
+        }
+    }
+}
+
+/// Runs a discoverer in the background, publishing the resulting targets until `shutdown`.
+///
+/// The targets of a failed discovery round are kept until the next successful one.
+pub fn spawn(
+    discoverer: Box<dyn Discoverer>,
+    refresh_interval: Duration,
+    to_targets: Arc<dyn Fn(Vec<Labels>) -> Vec<Target> + Send + Sync>,
+    shutdown: ShutdownSignal,
+) -> watch::Receiver<Vec<Target>> {
+    let (tx, rx) = watch::channel(Vec::new());
+    tokio::spawn(async move {
+        let mut ticks =
+            IntervalStream::new(tokio::time::interval(refresh_interval)).take_until(shutdown);
+        while ticks.next().await.is_some() {
+            match discoverer.discover().await {
+                Ok(labels) => {
+                    if tx.send(to_targets(labels)).is_err() {
+                        break;
+                    }
+                }
+                Err(error) => emit!(PrometheusServiceDiscoveryError {
+                    error,
+                    mechanism: discoverer.mechanism(),
+                }),
+            }
+        }
+    });
+    rx
+}
+
+/// Builds the endpoint to scrape from the reserved labels of a relabeled target, or `None` if it
+/// has no `__address__`.
+pub fn endpoint(labels: &Labels) -> Option<String> {
+    let address = labels.get("__address__").filter(|a| !a.is_empty())?;
+    let scheme = labels
+        .get("__scheme__")
+        .map(String::as_str)
+        .unwrap_or("http");
+    let path = labels
+        .get("__metrics_path__")
+        .map(String::as_str)
+        .unwrap_or("/metrics");
+    let mut endpoint = format!("{}://{}{}", scheme, address, path);
+
+    let mut params = url::form_urlencoded::Serializer::new(String::new());
+    for (name, value) in labels {
+        if let Some(name) = name.strip_prefix("__param_") {
+            params.append_pair(name, value);
+        }
+    }
+    let params = params.finish();
+    if !params.is_empty() {
+        endpoint.push('?');
+        endpoint.push_str(&params);
+    }
+    Some(endpoint)
+}
+
+/// Returns the labels that are added to the scraped metrics, dropping the reserved `__` ones.
+pub fn public_labels(labels: Labels) -> Labels {
+    labels
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with("__"))
+        .collect()
+}
+
+/// Converts a Kubernetes label or annotation name into a valid label name.
+#[cfg_attr(not(feature = "kubernetes"), allow(dead_code))]
+fn sanitize_label_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_endpoint_from_reserved_labels() {
+        let labels = Labels::from([
+            ("__address__".to_string(), "10.0.0.1:9100".to_string()),
+            ("__scheme__".to_string(), "https".to_string()),
+            ("__metrics_path__".to_string(), "/federate".to_string()),
+            ("__param_match[]".to_string(), "up".to_string()),
+            ("job".to_string(), "node".to_string()),
+        ]);
+        assert_eq!(
+            endpoint(&labels).unwrap(),
+            "https://10.0.0.1:9100/federate?match%5B%5D=up"
+        );
+        assert_eq!(
+            public_labels(labels),
+            Labels::from([("job".to_string(), "node".to_string())])
+        );
+
+        assert_eq!(endpoint(&Labels::new()), None);
+    }
+
+    #[test]
+    fn sanitizes_label_names() {
+        assert_eq!(
+            sanitize_label_name("app.kubernetes.io/name"),
+            "app_kubernetes_io_name"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+#[derive(Debug, Snafu)]
+pub enum RelabelError {
+    #[snafu(display("Invalid relabel regex {:?}: {}", regex, source))]
+    InvalidRegex { regex: String, source: regex::Error },
+    #[snafu(display("Relabel action `{}` requires `target_label` to be set", action))]
+    MissingTargetLabel { action: &'static str },
+}
+
+/// The action performed by a relabel rule.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Set `target_label` to `replacement` if `regex` matches the joined `source_labels`.
+    Replace,
+
+    /// Drop the target unless `regex` matches the joined `source_labels`.
+    Keep,
+
+    /// Drop the target if `regex` matches the joined `source_labels`.
+    Drop,
+
+    /// Copy every label whose name matches `regex` to the name given by `replacement`.
+    Labelmap,
+
+    /// Remove every label whose name matches `regex`.
+    Labeldrop,
+
+    /// Remove every label whose name does not match `regex`.
+    Labelkeep,
+}
+
+impl RelabelAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Keep => "keep",
+            Self::Drop => "drop",
+            Self::Labelmap => "labelmap",
+            Self::Labeldrop => "labeldrop",
+            Self::Labelkeep => "labelkeep",
+        }
+    }
+}
+
+const fn default_action() -> RelabelAction {
+    RelabelAction::Replace
+}
+
+fn default_separator() -> String {
+    ";".to_string()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_string()
+}
+
+fn default_replacement() -> String {
+    "$1".to_string()
+}
+
+/// A rule rewriting the labels of discovered targets, following Prometheus' `relabel_configs`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct RelabelConfig {
+    /// The labels whose values are joined with `separator` and matched against `regex`.
+    #[serde(default)]
+    source_labels: Vec<String>,
+
+    /// The separator placed between the values of `source_labels`.
+    #[serde(default = "default_separator")]
+    separator: String,
+
+    /// The regular expression matched against the joined values, or against label names for the `label*` actions.
+    ///
+    /// The expression is anchored at both ends.
+    #[serde(default = "default_regex")]
+    regex: String,
+
+    /// The label written by the `replace` action.
+    target_label: Option<String>,
+
+    /// The value written by the `replace` action, or the label name written by the `labelmap` action.
+    ///
+    /// Capture groups from `regex` can be referenced as `$1` or `${name}`.
+    #[serde(default = "default_replacement")]
+    replacement: String,
+
+    #[configurable(derived)]
+    #[serde(default = "default_action")]
+    action: RelabelAction,
+}
+
+#[derive(Clone, Debug)]
+struct RelabelRule {
+    source_labels: Vec<String>,
+    separator: String,
+    regex: Regex,
+    target_label: Option<String>,
+    replacement: String,
+    action: RelabelAction,
+}
+
+/// A compiled list of relabel rules.
+#[derive(Clone, Debug, Default)]
+pub struct Relabeler {
+    rules: Vec<RelabelRule>,
+}
+
+impl Relabeler {
+    pub fn new(configs: &[RelabelConfig]) -> Result<Self, RelabelError> {
+        let rules = configs
+            .iter()
+            .map(|config| {
+                if config.action == RelabelAction::Replace && config.target_label.is_none() {
+                    return Err(RelabelError::MissingTargetLabel {
+                        action: config.action.as_str(),
+                    });
+                }
+                let regex =
+                    Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu {
+                        regex: config.regex.clone(),
+                    })?;
+                Ok(RelabelRule {
+                    source_labels: config.source_labels.clone(),
+                    separator: config.separator.clone(),
+                    regex,
+                    target_label: config.target_label.clone(),
+                    replacement: config.replacement.clone(),
+                    action: config.action,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Applies the rules to the labels of a target, returning `None` if the target is dropped.
+    pub fn apply(&self, mut labels: BTreeMap<String, String>) -> Option<BTreeMap<String, String>> {
+        for rule in &self.rules {
+            match rule.action {
+                RelabelAction::Replace => {
+                    let value = rule.joined(&labels);
+                    if let Some(captures) = rule.regex.captures(&value) {
+                        let mut replaced = String::new();
+                        captures.expand(&rule.replacement, &mut replaced);
+                        let target = rule.target_label.clone().expect("validated on build");
+                        if replaced.is_empty() {
+                            labels.remove(&target);
+                        } else {
+                            labels.insert(target, replaced);
+                        }
+                    }
+                }
+                RelabelAction::Keep => {
+                    if !rule.regex.is_match(&rule.joined(&labels)) {
+                        return None;
+                    }
+                }
+                RelabelAction::Drop => {
+                    if rule.regex.is_match(&rule.joined(&labels)) {
+                        return None;
+                    }
+                }
+                RelabelAction::Labelmap => {
+                    let mapped = labels
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            rule.regex.captures(name).map(|captures| {
+                                let mut replaced = String::new();
+                                captures.expand(&rule.replacement, &mut replaced);
+                                (replaced, value.clone())
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    labels.extend(mapped);
+                }
+                RelabelAction::Labeldrop => labels.retain(|name, _| !rule.regex.is_match(name)),
+                RelabelAction::Labelkeep => labels.retain(|name, _| rule.regex.is_match(name)),
+            }
+        }
+        Some(labels)
+    }
+}
+
+impl RelabelRule {
+    fn joined(&self, labels: &BTreeMap<String, String>) -> String {
+        self.source_labels
+            .iter()
+            .map(|name| labels.get(name).map(String::as_str).unwrap_or_default())
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn relabeler(toml: &str) -> Relabeler {
+        #[derive(serde::Deserialize)]
+        struct Rules {
+            rules: Vec<RelabelConfig>,
+        }
+        let rules: Rules = toml::from_str(toml).unwrap();
+        Relabeler::new(&rules.rules).unwrap()
+    }
+
+    #[test]
+    fn keeps_annotated_pods_and_rewrites_address() {
+        let relabeler = relabeler(
+            r#"
+            [[rules]]
+            source_labels = ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+            regex = "true"
+            action = "keep"
+
+            [[rules]]
+            source_labels = ["__address__", "__meta_kubernetes_pod_annotation_prometheus_io_port"]
+            regex = '([^:]+)(?::\d+)?;(\d+)'
+            replacement = "$1:$2"
+            target_label = "__address__"
+
+            [[rules]]
+            regex = "__meta_kubernetes_pod_label_(.+)"
+            action = "labelmap"
+            "#,
+        );
+
+        let relabeled = relabeler
+            .apply(labels(&[
+                ("__address__", "10.0.0.1:8080"),
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_scrape",
+                    "true",
+                ),
+                (
+                    "__meta_kubernetes_pod_annotation_prometheus_io_port",
+                    "9102",
+                ),
+                ("__meta_kubernetes_pod_label_app", "web"),
+            ]))
+            .unwrap();
+        assert_eq!(relabeled["__address__"], "10.0.0.1:9102");
+        assert_eq!(relabeled["app"], "web");
+
+        assert!(relabeler
+            .apply(labels(&[("__address__", "10.0.0.2:8080")]))
+            .is_none());
+    }
+
+    #[test]
+    fn drops_labels() {
+        let relabeler = relabeler(
+            r#"
+            [[rules]]
+            regex = "tmp_.*"
+            action = "labeldrop"
+            "#,
+        );
+        let relabeled = relabeler
+            .apply(labels(&[("tmp_a", "1"), ("job", "x")]))
+            .unwrap();
+        assert_eq!(relabeled, labels(&[("job", "x")]));
+    }
+
+    #[test]
+    fn replace_requires_target_label() {
+        let config: RelabelConfig = toml::from_str(r#"source_labels = ["a"]"#).unwrap();
+        assert!(Relabeler::new(&[config]).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 pub(crate) mod parser;
+mod discovery;
 mod remote_write;
 mod scrape;
 
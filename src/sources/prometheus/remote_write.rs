@@ -1,4 +1,4 @@
-use std::{collections::HashMap, net::SocketAddr};
+use std::collections::HashMap;
 
 use bytes::Bytes;
 use prometheus_parser::proto;
@@ -19,7 +19,7 @@ use crate::{
     sources::{
         self,
         http::HttpMethod,
-        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig, SocketListenAddr},
     },
     tls::TlsEnableableConfig,
 };
@@ -32,8 +32,9 @@ const SOURCE_NAME: &str = "prometheus_remote_write";
 pub struct PrometheusRemoteWriteConfig {
     /// The address to accept connections on.
     ///
-    /// The address _must_ include a port.
-    address: SocketAddr,
+    /// The address _must_ include a port. `systemd#N` uses the Nth socket passed by `systemd`
+    /// socket activation.
+    address: SocketListenAddr,
 
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
@@ -48,9 +49,9 @@ pub struct PrometheusRemoteWriteConfig {
 
 impl PrometheusRemoteWriteConfig {
     #[cfg(test)]
-    pub fn from_address(address: SocketAddr) -> Self {
+    pub fn from_address(address: std::net::SocketAddr) -> Self {
         Self {
-            address,
+            address: address.into(),
             tls: None,
             auth: None,
             acknowledgements: false.into(),
@@ -65,7 +66,7 @@ inventory::submit! {
 impl GenerateConfig for PrometheusRemoteWriteConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            address: "127.0.0.1:9090".parse().unwrap(),
+            address: SocketListenAddr::SocketAddr("127.0.0.1:9090".parse().unwrap()),
             tls: None,
             auth: None,
             acknowledgements: AcknowledgementsConfig::default(),
@@ -190,7 +191,7 @@ mod test {
                 .unwrap()
                 .http_protocol_name();
             let source = PrometheusRemoteWriteConfig {
-                address,
+                address: address.into(),
                 auth: None,
                 tls: tls.clone(),
                 acknowledgements: AcknowledgementsConfig::default(),
@@ -295,7 +296,7 @@ mod integration_tests {
         // It could be nice to split up the Prometheus integration tests in the future, or
         // maybe there's a way to do a one-shot remote write from Prometheus? Not sure.
         let config = PrometheusRemoteWriteConfig {
-            address: source_receive_address().parse().unwrap(),
+            address: SocketListenAddr::SocketAddr(source_receive_address().parse().unwrap()),
             auth: None,
             tls: None,
             acknowledgements: AcknowledgementsConfig::default(),
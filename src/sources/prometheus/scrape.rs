@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     future::ready,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
@@ -8,16 +9,21 @@ use futures::{stream, FutureExt, StreamExt, TryFutureExt};
 use hyper::{Body, Request};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use tokio::sync::watch;
 use tokio_stream::wrappers::IntervalStream;
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 use vector_core::ByteSizeOf;
 
-use super::parser;
+use super::{
+    discovery::{self, Labels, RelabelConfig, Relabeler, ServiceDiscoveryConfig, Target},
+    parser,
+};
 use crate::{
     config::{
         self, GenerateConfig, Output, ProxyConfig, SourceConfig, SourceContext, SourceDescription,
     },
+    event::Metric,
     http::{Auth, HttpClient},
     internal_events::{
         EndpointBytesReceived, PrometheusEventsReceived, PrometheusHttpError,
@@ -41,6 +47,23 @@ static NOT_FOUND_NO_PATH: &str = "No path is set on the endpoint and we got a 40
 enum ConfigError {
     #[snafu(display("Cannot set both `endpoints` and `hosts`"))]
     BothEndpointsAndHosts,
+    #[snafu(display("`shard_index` requires `shard_count` to be set"))]
+    ShardIndexWithoutCount,
+    #[snafu(display("`shard_count` must be greater than zero"))]
+    ZeroShardCount,
+    #[snafu(display(
+        "`shard_index` ({}) must be less than `shard_count` ({})",
+        index,
+        count
+    ))]
+    ShardIndexOutOfRange { index: u32, count: u32 },
+    #[snafu(display(
+        "Could not determine `shard_index` from the replica name {:?}; set it explicitly",
+        name
+    ))]
+    NoReplicaOrdinal { name: String },
+    #[snafu(display("At least one of `endpoints` and `service_discovery` must be set"))]
+    NoTargets,
 }
 
 /// Configuration for the `prometheus_scrape` source.
@@ -48,9 +71,23 @@ enum ConfigError {
 #[derive(Clone, Debug)]
 pub struct PrometheusScrapeConfig {
     /// Endpoints to scrape metrics from.
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
 
+    /// Mechanisms for discovering additional endpoints to scrape.
+    ///
+    /// Discovered targets are rewritten by `relabel_configs`, and their labels that don't start with `__` are added
+    /// as tags to the metrics scraped from them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    service_discovery: Vec<ServiceDiscoveryConfig>,
+
+    /// Rules rewriting the labels of discovered targets, in the format of Prometheus' `relabel_configs`.
+    ///
+    /// The rules are applied in order, and can drop targets or set the reserved `__address__`, `__scheme__`,
+    /// `__metrics_path__`, and `__param_<name>` labels used to build the scraped endpoint.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    relabel_configs: Vec<RelabelConfig>,
+
     /// The interval between scrapes, in seconds.
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
@@ -85,6 +122,20 @@ pub struct PrometheusScrapeConfig {
     /// scraping the `/federate` endpoint.
     query: Option<HashMap<String, Vec<String>>>,
 
+    /// The number of Vector replicas the endpoints are split across.
+    ///
+    /// Each endpoint is assigned to exactly one shard by a stable hash of its configured value, or of its URL if it was
+    /// discovered, so replicas sharing the same configuration scrape disjoint subsets of the endpoints. By default,
+    /// every endpoint is scraped.
+    shard_count: Option<u32>,
+
+    /// The shard scraped by this replica, from `0` to `shard_count - 1`.
+    ///
+    /// If `shard_count` is set and this is not, the index is taken from the ordinal suffix of the replica's name, as
+    /// assigned to StatefulSet pods by Kubernetes (for example, `vector-2` is shard `2`). The name is read from the
+    /// `VECTOR_SELF_POD_NAME` environment variable, falling back to the hostname.
+    shard_index: Option<u32>,
+
     #[configurable(derived)]
     tls: Option<TlsConfig>,
 
@@ -92,6 +143,83 @@ pub struct PrometheusScrapeConfig {
     auth: Option<Auth>,
 }
 
+impl PrometheusScrapeConfig {
+    /// Returns the `(index, count)` of the shard this replica scrapes, if sharding is enabled.
+    fn shard(&self) -> crate::Result<Option<(u32, u32)>> {
+        let count = match (self.shard_count, self.shard_index) {
+            (None, None) => return Ok(None),
+            (None, Some(_)) => return Err(ConfigError::ShardIndexWithoutCount.into()),
+            (Some(0), _) => return Err(ConfigError::ZeroShardCount.into()),
+            (Some(count), _) => count,
+        };
+        let index = match self.shard_index {
+            Some(index) => index,
+            None => {
+                let name =
+                    std::env::var("VECTOR_SELF_POD_NAME").or_else(|_| crate::get_hostname())?;
+                replica_ordinal(&name).ok_or(ConfigError::NoReplicaOrdinal { name })?
+            }
+        };
+        if index >= count {
+            return Err(ConfigError::ShardIndexOutOfRange { index, count }.into());
+        }
+        Ok(Some((index, count)))
+    }
+}
+
+/// Parses the ordinal from a StatefulSet-style replica name such as `vector-2`.
+fn replica_ordinal(name: &str) -> Option<u32> {
+    name.rsplit_once('-')
+        .and_then(|(_, ordinal)| ordinal.parse().ok())
+}
+
+fn in_shard(endpoint: &str, index: u32, count: u32) -> bool {
+    seahash::hash(endpoint.as_bytes()) % u64::from(count) == u64::from(index)
+}
+
+/// Appends the configured query parameters to those already present in `uri`.
+fn with_query(uri: http::Uri, query: &Option<HashMap<String, Vec<String>>>) -> http::Uri {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    if let Some(query) = uri.query() {
+        serializer.extend_pairs(url::form_urlencoded::parse(query.as_bytes()));
+    };
+    if let Some(query) = query {
+        for (k, l) in query {
+            for v in l {
+                serializer.append_pair(k, v);
+            }
+        }
+    };
+    let mut builder = http::Uri::builder();
+    if let Some(scheme) = uri.scheme() {
+        builder = builder.scheme(scheme.clone());
+    };
+    if let Some(authority) = uri.authority() {
+        builder = builder.authority(authority.clone());
+    };
+    builder = builder.path_and_query(match serializer.finish() {
+        query if !query.is_empty() => format!("{}?{}", uri.path(), query),
+        _ => uri.path().to_string(),
+    });
+    builder.build().expect("error building URI")
+}
+
+/// The static targets of the source, and those published by its service discovery mechanisms.
+struct Targets {
+    static_targets: Vec<Target>,
+    discovered: Vec<watch::Receiver<Vec<Target>>>,
+}
+
+impl Targets {
+    fn current(&self) -> Vec<Target> {
+        let mut targets = self.static_targets.clone();
+        for discovered in &self.discovered {
+            targets.extend(discovered.borrow().iter().cloned());
+        }
+        targets
+    }
+}
+
 pub(crate) const fn default_scrape_interval_secs() -> u64 {
     15
 }
@@ -108,11 +236,15 @@ impl GenerateConfig for PrometheusScrapeConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
             endpoints: vec!["http://localhost:9090/metrics".to_string()],
+            service_discovery: Vec::new(),
+            relabel_configs: Vec::new(),
             scrape_interval_secs: default_scrape_interval_secs(),
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: None,
+            shard_count: None,
+            shard_index: None,
             tls: None,
             auth: None,
         })
@@ -124,42 +256,75 @@ impl GenerateConfig for PrometheusScrapeConfig {
 #[typetag::serde(name = "prometheus_scrape")]
 impl SourceConfig for PrometheusScrapeConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<sources::Source> {
-        let urls = self
+        if self.endpoints.is_empty() && self.service_discovery.is_empty() {
+            return Err(ConfigError::NoTargets.into());
+        }
+        let shard = self.shard()?;
+        let static_targets = self
             .endpoints
             .iter()
+            .filter(|endpoint| {
+                shard.map_or(true, |(index, count)| in_shard(endpoint, index, count))
+            })
             .map(|s| s.parse::<http::Uri>().context(sources::UriParseSnafu))
             .map(|r| {
-                r.map(|uri| {
-                    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
-                    if let Some(query) = uri.query() {
-                        serializer.extend_pairs(url::form_urlencoded::parse(query.as_bytes()));
-                    };
-                    if let Some(query) = &self.query {
-                        for (k, l) in query {
-                            for v in l {
-                                serializer.append_pair(k, v);
-                            }
-                        }
-                    };
-                    let mut builder = http::Uri::builder();
-                    if let Some(scheme) = uri.scheme() {
-                        builder = builder.scheme(scheme.clone());
-                    };
-                    if let Some(authority) = uri.authority() {
-                        builder = builder.authority(authority.clone());
-                    };
-                    builder = builder.path_and_query(match serializer.finish() {
-                        query if !query.is_empty() => format!("{}?{}", uri.path(), query),
-                        _ => uri.path().to_string(),
-                    });
-                    builder.build().expect("error building URI")
+                r.map(|uri| Target {
+                    url: with_query(uri, &self.query),
+                    labels: Labels::new(),
                 })
             })
-            .collect::<Result<Vec<http::Uri>, sources::BuildError>>()?;
+            .collect::<Result<Vec<Target>, sources::BuildError>>()?;
         let tls = TlsSettings::from_options(&self.tls)?;
+
+        let relabeler = Relabeler::new(&self.relabel_configs)?;
+        let query = self.query.clone();
+        let to_targets: Arc<dyn Fn(Vec<Labels>) -> Vec<Target> + Send + Sync> = Arc::new(
+            move |discovered| {
+                discovered
+                    .into_iter()
+                    .filter_map(|labels| {
+                        let labels = relabeler.apply(labels)?;
+                        let endpoint = discovery::endpoint(&labels)?;
+                        if let Some((index, count)) = shard {
+                            if !in_shard(&endpoint, index, count) {
+                                return None;
+                            }
+                        }
+                        match endpoint.parse::<http::Uri>() {
+                            Ok(uri) => Some(Target {
+                                url: with_query(uri, &query),
+                                labels: discovery::public_labels(labels),
+                            }),
+                            Err(error) => {
+                                debug!(
+                                    message = "Skipping discovered target with an invalid endpoint.",
+                                    %endpoint,
+                                    %error,
+                                );
+                                None
+                            }
+                        }
+                    })
+                    .collect()
+            },
+        );
+        let mut discovered = Vec::with_capacity(self.service_discovery.len());
+        for config in &self.service_discovery {
+            let (discoverer, refresh_interval) = config.build(&tls, &cx.proxy).await?;
+            discovered.push(discovery::spawn(
+                discoverer,
+                refresh_interval,
+                Arc::clone(&to_targets),
+                cx.shutdown.clone(),
+            ));
+        }
+
         Ok(prometheus(
             self.clone(),
-            urls,
+            Targets {
+                static_targets,
+                discovered,
+            },
             tls,
             cx.proxy.clone(),
             cx.shutdown,
@@ -187,8 +352,12 @@ impl SourceConfig for PrometheusScrapeConfig {
 struct PrometheusCompatConfig {
     // Clone of PrometheusScrapeConfig to work around serde bug
     // https://github.com/serde-rs/serde/issues/1504
-    #[serde(alias = "hosts")]
+    #[serde(alias = "hosts", default)]
     endpoints: Vec<String>,
+    #[serde(default)]
+    service_discovery: Vec<ServiceDiscoveryConfig>,
+    #[serde(default)]
+    relabel_configs: Vec<RelabelConfig>,
     instance_tag: Option<String>,
     endpoint_tag: Option<String>,
     #[serde(default = "crate::serde::default_false")]
@@ -196,6 +365,8 @@ struct PrometheusCompatConfig {
     query: Option<HashMap<String, Vec<String>>>,
     #[serde(default = "default_scrape_interval_secs")]
     scrape_interval_secs: u64,
+    shard_count: Option<u32>,
+    shard_index: Option<u32>,
     tls: Option<TlsConfig>,
     auth: Option<Auth>,
 }
@@ -208,11 +379,15 @@ impl SourceConfig for PrometheusCompatConfig {
         // https://github.com/serde-rs/serde/issues/1504
         let config = PrometheusScrapeConfig {
             endpoints: self.endpoints.clone(),
+            service_discovery: self.service_discovery.clone(),
+            relabel_configs: self.relabel_configs.clone(),
             instance_tag: self.instance_tag.clone(),
             endpoint_tag: self.endpoint_tag.clone(),
             honor_labels: self.honor_labels,
             query: self.query.clone(),
             scrape_interval_secs: self.scrape_interval_secs,
+            shard_count: self.shard_count,
+            shard_index: self.shard_index,
             tls: self.tls.clone(),
             auth: self.auth.clone(),
         };
@@ -252,9 +427,24 @@ struct EndpointInfo {
     honor_label: bool,
 }
 
+/// Adds a tag to a scraped metric. If the metric already has the tag, the scraped value is kept if `honor_label` is set,
+/// and moved to `exported_<tag>` otherwise.
+fn insert_tag(metric: &mut Metric, tag: &str, value: &str, honor_label: bool) {
+    match (honor_label, metric.tag_value(tag)) {
+        (false, Some(old_value)) => {
+            metric.insert_tag(format!("exported_{}", tag), old_value);
+            metric.insert_tag(tag.to_string(), value.to_string());
+        }
+        (true, Some(_)) => {}
+        (_, None) => {
+            metric.insert_tag(tag.to_string(), value.to_string());
+        }
+    }
+}
+
 async fn prometheus(
     config: PrometheusScrapeConfig,
-    urls: Vec<http::Uri>,
+    targets: Targets,
     tls: TlsSettings,
     proxy: ProxyConfig,
     shutdown: ShutdownSignal,
@@ -264,9 +454,9 @@ async fn prometheus(
         config.scrape_interval_secs,
     )))
     .take_until(shutdown)
-    .map(move |_| stream::iter(urls.clone()))
+    .map(move |_| stream::iter(targets.current()))
     .flatten()
-    .map(move |url| {
+    .map(move |Target { url, labels }| {
         let client = HttpClient::new(tls.clone(), &proxy).expect("Building HTTP client failed");
         let endpoint = url.to_string();
 
@@ -300,6 +490,7 @@ async fn prometheus(
             honor_label: config.honor_labels,
         });
 
+        let honor_labels = config.honor_labels;
         let start = Instant::now();
         client
             .send(request)
@@ -318,6 +509,7 @@ async fn prometheus(
             .filter_map(move |response| {
                 let instance_info = instance_info.clone();
                 let endpoint_info = endpoint_info.clone();
+                let labels = labels.clone();
 
                 ready(match response {
                     Ok((header, body)) if header.status == hyper::StatusCode::OK => {
@@ -337,25 +529,16 @@ async fn prometheus(
                                 });
                                 Some(stream::iter(events).map(move |mut event| {
                                     let metric = event.as_mut_metric();
+                                    for (tag, value) in &labels {
+                                        insert_tag(metric, tag, value, honor_labels);
+                                    }
                                     if let Some(InstanceInfo {
                                         tag,
                                         instance,
                                         honor_label,
                                     }) = &instance_info
                                     {
-                                        match (honor_label, metric.tag_value(tag)) {
-                                            (false, Some(old_instance)) => {
-                                                metric.insert_tag(
-                                                    format!("exported_{}", tag),
-                                                    old_instance,
-                                                );
-                                                metric.insert_tag(tag.clone(), instance.clone());
-                                            }
-                                            (true, Some(_)) => {}
-                                            (_, None) => {
-                                                metric.insert_tag(tag.clone(), instance.clone());
-                                            }
-                                        }
+                                        insert_tag(metric, tag, instance, *honor_label);
                                     }
                                     if let Some(EndpointInfo {
                                         tag,
@@ -363,19 +546,7 @@ async fn prometheus(
                                         honor_label,
                                     }) = &endpoint_info
                                     {
-                                        match (honor_label, metric.tag_value(tag)) {
-                                            (false, Some(old_endpoint)) => {
-                                                metric.insert_tag(
-                                                    format!("exported_{}", tag),
-                                                    old_endpoint,
-                                                );
-                                                metric.insert_tag(tag.clone(), endpoint.clone());
-                                            }
-                                            (true, Some(_)) => {}
-                                            (_, None) => {
-                                                metric.insert_tag(tag.clone(), endpoint.clone());
-                                            }
-                                        }
+                                        insert_tag(metric, tag, endpoint, *honor_label);
                                     }
                                     event
                                 }))
@@ -466,6 +637,45 @@ mod test {
         crate::test_util::test_generate_config::<PrometheusScrapeConfig>();
     }
 
+    #[test]
+    fn shards_partition_endpoints() {
+        let endpoints = (0..100)
+            .map(|i| format!("http://10.0.0.{}:9100/metrics", i))
+            .collect::<Vec<_>>();
+        for endpoint in &endpoints {
+            let owners = (0..3).filter(|index| in_shard(endpoint, *index, 3)).count();
+            assert_eq!(owners, 1);
+        }
+        assert!((0..3).all(|index| endpoints.iter().any(|e| in_shard(e, index, 3))));
+    }
+
+    #[test]
+    fn replica_ordinals() {
+        assert_eq!(replica_ordinal("vector-0"), Some(0));
+        assert_eq!(replica_ordinal("vector-agg-12"), Some(12));
+        assert_eq!(replica_ordinal("vector"), None);
+        assert_eq!(replica_ordinal("vector-5d8f9c7b4-x2x7q"), None);
+    }
+
+    #[test]
+    fn shard_validation() {
+        let mut config: PrometheusScrapeConfig =
+            toml::from_str(r#"endpoints = ["http://localhost:9090/metrics"]"#).unwrap();
+        assert_eq!(config.shard().unwrap(), None);
+
+        config.shard_index = Some(1);
+        assert!(config.shard().is_err());
+
+        config.shard_count = Some(0);
+        assert!(config.shard().is_err());
+
+        config.shard_count = Some(1);
+        assert!(config.shard().is_err());
+
+        config.shard_count = Some(2);
+        assert_eq!(config.shard().unwrap(), Some((1, 2)));
+    }
+
     #[tokio::test]
     async fn test_prometheus_sets_headers() {
         let in_addr = next_addr();
@@ -480,11 +690,15 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            service_discovery: Vec::new(),
+            relabel_configs: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: None,
+            shard_count: None,
+            shard_index: None,
             auth: None,
             tls: None,
         };
@@ -512,11 +726,15 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            service_discovery: Vec::new(),
+            relabel_configs: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: true,
             query: None,
+            shard_count: None,
+            shard_index: None,
             auth: None,
             tls: None,
         };
@@ -562,11 +780,15 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics", in_addr)],
+            service_discovery: Vec::new(),
+            relabel_configs: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: None,
+            shard_count: None,
+            shard_index: None,
             auth: None,
             tls: None,
         };
@@ -625,6 +847,8 @@ mod test {
 
         let config = PrometheusScrapeConfig {
             endpoints: vec![format!("http://{}/metrics?key1=val1", in_addr)],
+            service_discovery: Vec::new(),
+            relabel_configs: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
@@ -636,6 +860,8 @@ mod test {
                     vec!["val1".to_string(), "val2".to_string()],
                 ),
             ])),
+            shard_count: None,
+            shard_index: None,
             auth: None,
             tls: None,
         };
@@ -730,12 +956,16 @@ mod test {
             "in",
             PrometheusScrapeConfig {
                 endpoints: vec![format!("http://{}", in_addr)],
+                service_discovery: Vec::new(),
+                relabel_configs: Vec::new(),
                 instance_tag: None,
                 endpoint_tag: None,
                 honor_labels: false,
                 query: None,
                 scrape_interval_secs: 1,
                 tls: None,
+                shard_count: None,
+                shard_index: None,
                 auth: None,
             },
         );
@@ -819,11 +1049,15 @@ mod integration_tests {
     async fn scrapes_metrics() {
         let config = PrometheusScrapeConfig {
             endpoints: vec!["http://localhost:9090/metrics".into()],
+            service_discovery: Vec::new(),
+            relabel_configs: Vec::new(),
             scrape_interval_secs: 1,
             instance_tag: Some("instance".to_string()),
             endpoint_tag: Some("endpoint".to_string()),
             honor_labels: false,
             query: None,
+            shard_count: None,
+            shard_index: None,
             auth: None,
             tls: None,
         };
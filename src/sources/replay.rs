@@ -0,0 +1,419 @@
+//! The `replay` source reads back recordings written by the `record` sink, pacing events as they
+//! were recorded.
+//!
+//! A recording is a sequence of length-delimited frames, each prefixed with its length as a 4-byte
+//! big-endian integer. A frame holds the time its events were recorded, as big-endian Unix
+//! microseconds, followed by the events in the native protobuf encoding.
+
+use std::path::PathBuf;
+
+use bytes::{Buf, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use futures::StreamExt;
+use prost::Message;
+use snafu::{ResultExt, Snafu};
+use tokio::{
+    fs::File,
+    time::{sleep_until, Duration, Instant},
+};
+use tokio_util::codec::{FramedRead, LengthDelimitedCodec};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{DataType, GenerateConfig, Output, SourceConfig, SourceContext, SourceDescription},
+    event::{proto, EventArray, EventContainer},
+    internal_events::{BytesReceived, EventsReceived, ReplayReadError, StreamClosedError},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Configuration for the `replay` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayConfig {
+    /// The path of the recording to replay, as written by the `record` sink.
+    path: PathBuf,
+
+    /// The speed at which to replay the recording, relative to the pace it was recorded at.
+    ///
+    /// For example, `2.0` replays the recording twice as fast as it was recorded. To replay the
+    /// recording as fast as possible, set `speed` to `0.0`.
+    #[serde(default = "default_speed")]
+    speed: f64,
+
+    /// Only replay events recorded at or after this time, as an RFC 3339 timestamp.
+    start: Option<String>,
+
+    /// Only replay events recorded before this time, as an RFC 3339 timestamp.
+    end: Option<String>,
+}
+
+const fn default_speed() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid `{}` timestamp {:?}: {}", option, value, source))]
+    InvalidTimestamp {
+        option: &'static str,
+        value: String,
+        source: chrono::ParseError,
+    },
+    #[snafu(display("`speed` must be a finite number that is not negative, got {}", speed))]
+    InvalidSpeed { speed: f64 },
+}
+
+#[derive(Debug, Snafu)]
+enum FrameError {
+    #[snafu(display("Frame is too short to contain a timestamp"))]
+    Truncated,
+    #[snafu(display("Recording timestamp {} is out of range", micros))]
+    TimestampOutOfRange { micros: i64 },
+    #[snafu(display("Failed decoding events: {}", source))]
+    Decode { source: prost::DecodeError },
+    #[snafu(display("Failed decoding events: {}", source))]
+    Convert { source: proto::ConversionError },
+}
+
+inventory::submit! {
+    SourceDescription::new::<ReplayConfig>("replay")
+}
+
+impl GenerateConfig for ReplayConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            path: PathBuf::from("/var/lib/vector/recording.bin"),
+            speed: default_speed(),
+            start: None,
+            end: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "replay")]
+impl SourceConfig for ReplayConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if !(self.speed >= 0.0 && self.speed.is_finite()) {
+            return Err(BuildError::InvalidSpeed { speed: self.speed }.into());
+        }
+        let window = TimeWindow {
+            start: parse_timestamp("start", &self.start)?,
+            end: parse_timestamp("end", &self.end)?,
+        };
+
+        Ok(Box::pin(replay_source(
+            self.path.clone(),
+            self.speed,
+            window,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::all())]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "replay"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+fn parse_timestamp(
+    option: &'static str,
+    value: &Option<String>,
+) -> Result<Option<DateTime<Utc>>, BuildError> {
+    value
+        .as_ref()
+        .map(|value| {
+            DateTime::parse_from_rfc3339(value)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .context(InvalidTimestampSnafu {
+                    option,
+                    value: value.clone(),
+                })
+        })
+        .transpose()
+}
+
+/// The range of recording times to replay.
+#[derive(Clone, Copy, Debug, Default)]
+struct TimeWindow {
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+fn recording_codec() -> LengthDelimitedCodec {
+    LengthDelimitedCodec::builder()
+        .max_frame_length(u32::MAX as usize)
+        .new_codec()
+}
+
+fn decode_frame(mut frame: BytesMut) -> Result<(DateTime<Utc>, EventArray), FrameError> {
+    if frame.len() < 8 {
+        return Err(FrameError::Truncated);
+    }
+    let micros = frame.get_i64();
+    let recorded_at = Utc
+        .timestamp_opt(
+            micros.div_euclid(1_000_000),
+            (micros.rem_euclid(1_000_000) * 1_000) as u32,
+        )
+        .single()
+        .ok_or(FrameError::TimestampOutOfRange { micros })?;
+    let events = proto::EventArray::decode(frame).context(DecodeSnafu)?;
+    let events = EventArray::try_from(events).context(ConvertSnafu)?;
+
+    Ok((recorded_at, events))
+}
+
+async fn replay_source(
+    path: PathBuf,
+    speed: f64,
+    window: TimeWindow,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let file = File::open(&path)
+        .await
+        .map_err(|error| emit!(ReplayReadError { error, path: &path }))?;
+    let mut frames = FramedRead::new(file, recording_codec()).take_until(shutdown.clone());
+
+    // The time the first frame in the window was replayed at and recorded at, which later frames
+    // are paced against.
+    let mut origin: Option<(Instant, DateTime<Utc>)> = None;
+
+    while let Some(frame) = frames.next().await {
+        let frame = frame.map_err(|error| emit!(ReplayReadError { error, path: &path }))?;
+        let byte_size = frame.len();
+        let (recorded_at, events) = match decode_frame(frame) {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                emit!(ReplayReadError { error, path: &path });
+                return Err(());
+            }
+        };
+
+        if window.start.map_or(false, |start| recorded_at < start) {
+            continue;
+        }
+        if window.end.map_or(false, |end| recorded_at >= end) {
+            break;
+        }
+
+        if speed > 0.0 {
+            let (replayed_at, first_recorded_at) =
+                *origin.get_or_insert_with(|| (Instant::now(), recorded_at));
+            let recorded = (recorded_at - first_recorded_at).to_std().unwrap_or_default();
+            match replay_delay(recorded, speed).and_then(|delay| replayed_at.checked_add(delay)) {
+                Some(deadline) => tokio::select! {
+                    _ = sleep_until(deadline) => {},
+                    _ = &mut shutdown => break,
+                },
+                // The frame is too far ahead to ever be replayed.
+                None => {
+                    (&mut shutdown).await;
+                    break;
+                }
+            }
+        }
+
+        emit!(BytesReceived {
+            byte_size,
+            protocol: "file",
+        });
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        out.send_event(events).await.map_err(|error| {
+            emit!(StreamClosedError { error, count });
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Returns how long after the first frame to replay a frame recorded `recorded` after it, or `None`
+/// if that is too far ahead to be represented.
+fn replay_delay(recorded: Duration, speed: f64) -> Option<Duration> {
+    let secs = recorded.as_secs_f64() / speed;
+    (secs < u64::MAX as f64).then(|| Duration::from_secs_f64(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+    use futures::SinkExt;
+    use tokio_util::codec::FramedWrite;
+
+    use super::*;
+    use crate::{
+        event::{Event, LogEvent},
+        test_util::{
+            collect_ready,
+            components::{assert_source_compliance, SOURCE_TAGS},
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ReplayConfig>();
+    }
+
+    fn frame(recorded_at: DateTime<Utc>, message: &str) -> bytes::Bytes {
+        let events =
+            proto::EventArray::from(EventArray::from(Event::from(LogEvent::from(message))));
+        let mut frame = BytesMut::new();
+        frame.put_i64(
+            recorded_at.timestamp() * 1_000_000 + i64::from(recorded_at.timestamp_subsec_micros()),
+        );
+        events.encode(&mut frame).unwrap();
+        frame.freeze()
+    }
+
+    async fn write_recording(path: &std::path::Path, frames: Vec<bytes::Bytes>) {
+        let file = File::create(path).await.unwrap();
+        let mut writer = FramedWrite::new(file, recording_codec());
+        for frame in frames {
+            writer.send(frame).await.unwrap();
+        }
+    }
+
+    async fn replay(config: ReplayConfig) -> Vec<Event> {
+        assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap()
+                .await
+                .unwrap();
+            collect_ready(rx).await
+        })
+        .await
+    }
+
+    fn recorded_at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp(1_650_000_000 + seconds, 0)
+    }
+
+    #[test]
+    fn decodes_frames() {
+        let recorded = recorded_at(0) + chrono::Duration::microseconds(123_456);
+        let (decoded_at, events) = decode_frame(BytesMut::from(&frame(recorded, "hello")[..]))
+            .expect("Frame should decode.");
+        assert_eq!(decoded_at, recorded);
+        let event = events.into_events().next().unwrap();
+        assert_eq!(event.as_log()["message"], "hello".into());
+
+        assert!(matches!(
+            decode_frame(BytesMut::from(&b"\x00\x01"[..])),
+            Err(FrameError::Truncated)
+        ));
+    }
+
+    #[tokio::test]
+    async fn replays_time_window() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("recording.bin");
+        write_recording(
+            &path,
+            vec![
+                frame(recorded_at(0), "before"),
+                frame(recorded_at(10), "first"),
+                frame(recorded_at(11), "second"),
+                frame(recorded_at(20), "after"),
+            ],
+        )
+        .await;
+
+        let events = replay(ReplayConfig {
+            path,
+            speed: 0.0,
+            start: Some(recorded_at(10).to_rfc3339()),
+            end: Some(recorded_at(20).to_rfc3339()),
+        })
+        .await;
+
+        let messages: Vec<_> = events
+            .iter()
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect();
+        assert_eq!(messages, vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn replays_at_speed() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("recording.bin");
+        write_recording(
+            &path,
+            vec![
+                frame(recorded_at(0), "first"),
+                frame(recorded_at(1), "second"),
+            ],
+        )
+        .await;
+
+        let started = Instant::now();
+        let events = replay(ReplayConfig {
+            path,
+            speed: 4.0,
+            start: None,
+            end: None,
+        })
+        .await;
+
+        assert_eq!(events.len(), 2);
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(250), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(1), "{:?}", elapsed);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_config() {
+        let (tx, _rx) = SourceSender::new_test();
+        let config = ReplayConfig {
+            path: PathBuf::from("recording.bin"),
+            speed: 1.0,
+            start: Some("yesterday".to_string()),
+            end: None,
+        };
+        assert!(config
+            .build(SourceContext::new_test(tx.clone(), None))
+            .await
+            .is_err());
+
+        for speed in [-1.0, f64::NAN, f64::INFINITY] {
+            let config = ReplayConfig {
+                path: PathBuf::from("recording.bin"),
+                speed,
+                start: None,
+                end: None,
+            };
+            assert!(config
+                .build(SourceContext::new_test(tx.clone(), None))
+                .await
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn replay_delay_does_not_overflow() {
+        let recorded = Duration::from_secs(3600);
+        assert_eq!(replay_delay(recorded, 2.0), Some(Duration::from_secs(1800)));
+        assert_eq!(replay_delay(recorded, f64::MIN_POSITIVE), None);
+    }
+}
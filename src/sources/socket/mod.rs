@@ -159,7 +159,14 @@ impl SourceConfig for SocketConfig {
                     LogNamespace::Legacy,
                 )
                 .build();
-                Ok(udp::udp(config, host_key, decoder, cx.shutdown, cx.out))
+                Ok(udp::udp(
+                    config,
+                    host_key,
+                    decoder,
+                    cx.shutdown,
+                    cx.out,
+                    cx.ready.defer(),
+                ))
             }
             #[cfg(unix)]
             Mode::UnixDatagram(config) => {
@@ -182,6 +189,7 @@ impl SourceConfig for SocketConfig {
                     decoder,
                     cx.shutdown,
                     cx.out,
+                    cx.ready.defer(),
                 )
             }
             #[cfg(unix)]
@@ -219,6 +227,7 @@ impl SourceConfig for SocketConfig {
                     decoder,
                     cx.shutdown,
                     cx.out,
+                    cx.ready.defer(),
                 )
             }
         }
@@ -235,7 +244,7 @@ impl SourceConfig for SocketConfig {
     fn resources(&self) -> Vec<Resource> {
         match self.mode.clone() {
             Mode::Tcp(tcp) => vec![tcp.address().into()],
-            Mode::Udp(udp) => vec![Resource::udp(udp.address())],
+            Mode::Udp(udp) => vec![udp.address().as_udp_resource()],
             #[cfg(unix)]
             Mode::UnixDatagram(_) => vec![],
             #[cfg(unix)]
@@ -293,6 +302,7 @@ mod test {
         event::{Event, LogEvent},
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
+        sources::util::SocketListenAddr,
         test_util::{
             collect_n, collect_n_limited,
             components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
@@ -471,7 +481,8 @@ mod test {
             );
 
             let tls_meta: BTreeMap<String, value::Value> = btreemap!(
-                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"
+                "subject" => "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US",
+                "common_name" => "localhost"
             );
 
             assert_eq!(event.as_log()["tls_peer"], tls_meta.clone().into(),);
@@ -673,7 +684,10 @@ mod test {
         config: Option<UdpConfig>,
     ) -> (SocketAddr, JoinHandle<Result<(), ()>>) {
         let (address, config) = match config {
-            Some(config) => (config.address(), config),
+            Some(config) => match config.address() {
+                SocketListenAddr::SocketAddr(address) => (address, config),
+                SocketListenAddr::SystemdFd(_) => panic!("UDP tests bind to an address"),
+            },
             None => {
                 let address = next_addr();
                 (address, UdpConfig::from_address(address))
@@ -690,6 +704,7 @@ mod test {
                 acknowledgements: false,
                 schema: Default::default(),
                 schema_definitions: HashMap::default(),
+                ready: Default::default(),
             })
             .await
             .unwrap();
@@ -787,6 +802,36 @@ mod test {
         .await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn udp_multiple_workers() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let address = next_addr();
+            let mut config = UdpConfig::from_address(address);
+            config.workers = Some(4);
+            let address = init_udp_with_config(tx, config).await;
+
+            // Each line is sent from its own socket, so that the kernel spreads them across the
+            // workers.
+            for i in 0..16 {
+                send_lines_udp(address, vec![format!("line {}", i)]);
+            }
+
+            let mut messages = collect_n(rx, 16)
+                .await
+                .into_iter()
+                .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+                .collect::<Vec<_>>();
+            messages.sort();
+
+            let mut expected = (0..16).map(|i| format!("line {}", i)).collect::<Vec<_>>();
+            expected.sort();
+            assert_eq!(messages, expected);
+        })
+        .await;
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     /// This test only works on Unix.
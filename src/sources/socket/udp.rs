@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -7,6 +7,7 @@ use codecs::{
     StreamDecodingError,
 };
 use futures::StreamExt;
+use listenfd::ListenFd;
 use tokio::net::UdpSocket;
 use tokio_util::codec::FramedRead;
 use vector_config::configurable_component;
@@ -14,14 +15,17 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::Decoder,
-    config::log_schema,
+    config::{log_schema, ReadyGuard},
     event::Event,
     internal_events::{
         BytesReceived, SocketEventsReceived, SocketMode, SocketReceiveError, StreamClosedError,
     },
     serde::{default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
-    sources::Source,
+    sources::{
+        util::{try_bind_udp_sockets, SocketListenAddr},
+        Source,
+    },
     udp, SourceSender,
 };
 
@@ -31,7 +35,9 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct UdpConfig {
     /// The address to listen for messages on.
-    address: SocketAddr,
+    ///
+    /// `systemd#N` uses the Nth socket passed by `systemd` socket activation.
+    address: SocketListenAddr,
 
     /// The maximum buffer size, in bytes, of incoming messages.
     ///
@@ -58,6 +64,19 @@ pub struct UdpConfig {
     /// This should not typically needed to be changed.
     receive_buffer_bytes: Option<usize>,
 
+    /// The maximum size, in bytes, that the receive buffer is grown to when datagrams are dropped.
+    ///
+    /// When set, the kernel's drop counter for the socket is checked periodically, and the receive
+    /// buffer is doubled, up to this size, whenever datagrams were dropped. The kernel caps the
+    /// receive buffer at `net.core.rmem_max`. Only supported on Linux.
+    receive_buffer_max_bytes: Option<usize>,
+
+    /// The number of sockets bound to the address, each read by its own worker.
+    ///
+    /// When greater than one, `SO_REUSEPORT` is set on the sockets so that the kernel spreads
+    /// incoming datagrams across them. Only supported on Unix.
+    pub(super) workers: Option<usize>,
+
     #[configurable(derived)]
     #[serde(default = "default_framing_message_based")]
     pub(super) framing: FramingConfig,
@@ -80,17 +99,19 @@ impl UdpConfig {
         &self.decoding
     }
 
-    pub(super) const fn address(&self) -> SocketAddr {
+    pub(super) const fn address(&self) -> SocketListenAddr {
         self.address
     }
 
     pub fn from_address(address: SocketAddr) -> Self {
         Self {
-            address,
+            address: address.into(),
             max_length: crate::serde::default_max_length(),
             host_key: None,
             port_key: Some(String::from("port")),
             receive_buffer_bytes: None,
+            receive_buffer_max_bytes: None,
+            workers: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
         }
@@ -101,125 +122,157 @@ pub(super) fn udp(
     config: UdpConfig,
     host_key: String,
     decoder: Decoder,
-    mut shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+    ready: ReadyGuard,
 ) -> Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&config.address)
-            .await
-            .expect("Failed to bind to udp listener socket");
+        let sockets = try_bind_udp_sockets(
+            config.address,
+            config.workers.unwrap_or(1),
+            ListenFd::from_env(),
+        )
+        .await
+        .expect("Failed to bind to udp listener socket");
+        drop(ready);
 
-        if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
+        for socket in &sockets {
+            udp::configure_receive_buffer(
+                socket,
+                config.receive_buffer_bytes,
+                config.receive_buffer_max_bytes,
+                shutdown.clone(),
+            );
         }
 
-        let max_length = match config.receive_buffer_bytes {
-            Some(receive_buffer_bytes) => std::cmp::min(config.max_length, receive_buffer_bytes),
-            None => config.max_length,
-        };
-
-        info!(message = "Listening.", address = %config.address);
-
-        // We add 1 to the max_length in order to determine if the received data has been truncated.
-        let mut buf = BytesMut::with_capacity(max_length + 1);
-        loop {
-            buf.resize(max_length + 1, 0);
-            tokio::select! {
-                recv = socket.recv_from(&mut buf) => {
-                    let (byte_size, address) = match recv {
-                        Ok(res) => res,
-                        Err(error) => {
-                            #[cfg(windows)]
-                            if let Some(err) = error.raw_os_error() {
-                                if err == 10040 {
-                                    // 10040 is the Windows error that the Udp message has exceeded max_length
-                                    warn!(
-                                        message = "Discarding frame larger than max_length.",
-                                        max_length = max_length,
-                                        internal_log_rate_secs = 30
-                                    );
-                                    continue;
-                                }
+        info!(message = "Listening.", address = %config.address, workers = sockets.len());
+
+        let config = Arc::new(config);
+        let host_key = Arc::new(host_key);
+        udp::run_workers(sockets, |socket| {
+            recv_udp(
+                socket,
+                Arc::clone(&config),
+                Arc::clone(&host_key),
+                decoder.clone(),
+                shutdown.clone(),
+                out.clone(),
+            )
+        })
+        .await
+    })
+}
+
+async fn recv_udp(
+    socket: Arc<UdpSocket>,
+    config: Arc<UdpConfig>,
+    host_key: Arc<String>,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let max_length = match config.receive_buffer_bytes {
+        Some(receive_buffer_bytes) => std::cmp::min(config.max_length, receive_buffer_bytes),
+        None => config.max_length,
+    };
+
+    // We add 1 to the max_length in order to determine if the received data has been truncated.
+    let mut buf = BytesMut::with_capacity(max_length + 1);
+    loop {
+        buf.resize(max_length + 1, 0);
+        tokio::select! {
+            recv = socket.recv_from(&mut buf) => {
+                let (byte_size, address) = match recv {
+                    Ok(res) => res,
+                    Err(error) => {
+                        #[cfg(windows)]
+                        if let Some(err) = error.raw_os_error() {
+                            if err == 10040 {
+                                // 10040 is the Windows error that the Udp message has exceeded max_length
+                                warn!(
+                                    message = "Discarding frame larger than max_length.",
+                                    max_length = max_length,
+                                    internal_log_rate_secs = 30
+                                );
+                                continue;
                             }
+                        }
 
-                            let error = codecs::decoding::Error::FramingError(error.into());
-                            return Err(emit!(SocketReceiveError {
-                                mode: SocketMode::Udp,
-                                error: &error
-                            }));
-                       }
-                    };
-
-                    emit!(BytesReceived { byte_size, protocol: "udp" });
-
-                    let payload = buf.split_to(byte_size);
-                    let truncated = byte_size == max_length + 1;
-
-                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone()).peekable();
-
-                    while let Some(result) = stream.next().await {
-                        let last = Pin::new(&mut stream).peek().await.is_none();
-                        match result {
-                            Ok((mut events, _byte_size)) => {
-                                if last && truncated {
-                                    // The last event in this payload was truncated, so we want to drop it.
-                                    let _ = events.pop();
-                                    warn!(
-                                        message = "Discarding frame larger than max_length.",
-                                        max_length = max_length,
-                                        internal_log_rate_secs = 30
-                                    );
-                                }
+                        let error = codecs::decoding::Error::FramingError(error.into());
+                        return Err(emit!(SocketReceiveError {
+                            mode: SocketMode::Udp,
+                            error: &error
+                        }));
+                   }
+                };
 
-                                if events.is_empty() {
-                                    continue;
-                                }
+                emit!(BytesReceived { byte_size, protocol: "udp" });
+
+                let payload = buf.split_to(byte_size);
+                let truncated = byte_size == max_length + 1;
+
+                let mut stream = FramedRead::new(payload.as_ref(), decoder.clone()).peekable();
+
+                while let Some(result) = stream.next().await {
+                    let last = Pin::new(&mut stream).peek().await.is_none();
+                    match result {
+                        Ok((mut events, _byte_size)) => {
+                            if last && truncated {
+                                // The last event in this payload was truncated, so we want to drop it.
+                                let _ = events.pop();
+                                warn!(
+                                    message = "Discarding frame larger than max_length.",
+                                    max_length = max_length,
+                                    internal_log_rate_secs = 30
+                                );
+                            }
 
-                                let count = events.len();
-                                emit!(SocketEventsReceived {
-                                    mode: SocketMode::Udp,
-                                    byte_size: events.size_of(),
-                                    count,
-                                });
+                            if events.is_empty() {
+                                continue;
+                            }
+
+                            let count = events.len();
+                            emit!(SocketEventsReceived {
+                                mode: SocketMode::Udp,
+                                byte_size: events.size_of(),
+                                count,
+                            });
 
-                                let now = Utc::now();
+                            let now = Utc::now();
 
-                                for event in &mut events {
-                                    if let Event::Log(ref mut log) = event {
-                                        log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
-                                        log.try_insert(log_schema().timestamp_key(), now);
-                                        log.try_insert(host_key.as_str(), address.ip().to_string());
+                            for event in &mut events {
+                                if let Event::Log(ref mut log) = event {
+                                    log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
+                                    log.try_insert(log_schema().timestamp_key(), now);
+                                    log.try_insert(host_key.as_str(), address.ip().to_string());
 
-                                        if let Some(port_key) = &config.port_key {
-                                            log.try_insert(port_key.as_str(), address.port());
-                                        }
+                                    if let Some(port_key) = &config.port_key {
+                                        log.try_insert(port_key.as_str(), address.port());
                                     }
                                 }
+                            }
 
-                                tokio::select!{
-                                    result = out.send_batch(events) => {
-                                        if let Err(error) = result {
-                                            emit!(StreamClosedError { error, count });
-                                            return Ok(())
-                                        }
+                            tokio::select!{
+                                result = out.send_batch(events) => {
+                                    if let Err(error) = result {
+                                        emit!(StreamClosedError { error, count });
+                                        return Ok(())
                                     }
-                                    _ = &mut shutdown => return Ok(()),
                                 }
+                                _ = &mut shutdown => return Ok(()),
                             }
-                            Err(error) => {
-                                // Error is logged by `crate::codecs::Decoder`, no
-                                // further handling is needed here.
-                                if !error.can_continue() {
-                                    break;
-                                }
+                        }
+                        Err(error) => {
+                            // Error is logged by `crate::codecs::Decoder`, no
+                            // further handling is needed here.
+                            if !error.can_continue() {
+                                break;
                             }
                         }
                     }
                 }
-                _ = &mut shutdown => return Ok(()),
             }
+            _ = &mut shutdown => return Ok(()),
         }
-    })
+    }
 }
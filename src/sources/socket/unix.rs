@@ -7,7 +7,7 @@ use vector_config::configurable_component;
 
 use crate::{
     codecs::Decoder,
-    config::log_schema,
+    config::{log_schema, ReadyGuard},
     event::Event,
     serde::default_decoding,
     shutdown::ShutdownSignal,
@@ -93,6 +93,7 @@ pub(super) fn unix_datagram(
     decoder: Decoder,
     shutdown: ShutdownSignal,
     out: SourceSender,
+    ready: ReadyGuard,
 ) -> crate::Result<Source> {
     build_unix_datagram_source(
         path,
@@ -102,6 +103,7 @@ pub(super) fn unix_datagram(
         move |events, received_from| handle_events(events, &host_key, received_from),
         shutdown,
         out,
+        ready,
     )
 }
 
@@ -112,6 +114,7 @@ pub(super) fn unix_stream(
     decoder: Decoder,
     shutdown: ShutdownSignal,
     out: SourceSender,
+    ready: ReadyGuard,
 ) -> crate::Result<Source> {
     build_unix_stream_source(
         path,
@@ -120,5 +123,6 @@ pub(super) fn unix_stream(
         move |events, received_from| handle_events(events, &host_key, received_from),
         shutdown,
         out,
+        ready,
     )
 }
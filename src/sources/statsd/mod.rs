@@ -1,4 +1,7 @@
-use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::{
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
+};
 
 use bytes::Bytes;
 use codecs::{
@@ -6,6 +9,7 @@ use codecs::{
     NewlineDelimitedDecoder,
 };
 use futures::{StreamExt, TryFutureExt};
+use listenfd::ListenFd;
 use smallvec::{smallvec, SmallVec};
 use tokio::net::UdpSocket;
 use tokio_util::udp::UdpFramed;
@@ -13,11 +17,12 @@ use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
 use self::parser::ParseError;
-use super::util::{SocketListenAddr, TcpNullAcker, TcpSource};
+use super::util::{try_bind_udp_sockets, SocketListenAddr, TcpNullAcker, TcpSource};
 use crate::{
     codecs::Decoder,
     config::{
-        self, GenerateConfig, Output, Resource, SourceConfig, SourceContext, SourceDescription,
+        self, GenerateConfig, Output, ReadyGuard, Resource, SourceConfig, SourceContext,
+        SourceDescription,
     },
     event::Event,
     internal_events::{
@@ -60,19 +65,36 @@ pub enum StatsdConfig {
 #[derive(Clone, Debug)]
 pub struct UdpConfig {
     /// The address to listen for messages on.
-    address: SocketAddr,
+    ///
+    /// `systemd#N` uses the Nth socket passed by `systemd` socket activation.
+    address: SocketListenAddr,
 
     /// The size, in bytes, of the receive buffer used for each connection.
     ///
     /// This should not typically needed to be changed.
     receive_buffer_bytes: Option<usize>,
+
+    /// The maximum size, in bytes, that the receive buffer is grown to when datagrams are dropped.
+    ///
+    /// When set, the kernel's drop counter for the socket is checked periodically, and the receive
+    /// buffer is doubled, up to this size, whenever datagrams were dropped. The kernel caps the
+    /// receive buffer at `net.core.rmem_max`. Only supported on Linux.
+    receive_buffer_max_bytes: Option<usize>,
+
+    /// The number of sockets bound to the address, each read by its own worker.
+    ///
+    /// When greater than one, `SO_REUSEPORT` is set on the sockets so that the kernel spreads
+    /// incoming datagrams across them. Only supported on Unix.
+    workers: Option<usize>,
 }
 
 impl UdpConfig {
     pub const fn from_address(address: SocketAddr) -> Self {
         Self {
-            address,
+            address: SocketListenAddr::SocketAddr(address),
             receive_buffer_bytes: None,
+            receive_buffer_max_bytes: None,
+            workers: None,
         }
     }
 }
@@ -141,9 +163,12 @@ impl GenerateConfig for StatsdConfig {
 impl SourceConfig for StatsdConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         match self {
-            StatsdConfig::Udp(config) => {
-                Ok(Box::pin(statsd_udp(config.clone(), cx.shutdown, cx.out)))
-            }
+            StatsdConfig::Udp(config) => Ok(Box::pin(statsd_udp(
+                config.clone(),
+                cx.shutdown,
+                cx.out,
+                cx.ready.defer(),
+            ))),
             StatsdConfig::Tcp(config) => {
                 let tls_config = config.tls.as_ref().map(|tls| tls.tls_config.clone());
                 let tls_client_metadata_key = config
@@ -164,7 +189,9 @@ impl SourceConfig for StatsdConfig {
                 )
             }
             #[cfg(unix)]
-            StatsdConfig::Unix(config) => statsd_unix(config.clone(), cx.shutdown, cx.out),
+            StatsdConfig::Unix(config) => {
+                statsd_unix(config.clone(), cx.shutdown, cx.out, cx.ready.defer())
+            }
         }
     }
 
@@ -179,7 +206,7 @@ impl SourceConfig for StatsdConfig {
     fn resources(&self) -> Vec<Resource> {
         match self.clone() {
             Self::Tcp(tcp) => vec![tcp.address.into()],
-            Self::Udp(udp) => vec![Resource::udp(udp.address)],
+            Self::Udp(udp) => vec![udp.address.as_udp_resource()],
             #[cfg(unix)]
             Self::Unix(_) => vec![],
         }
@@ -249,26 +276,47 @@ impl decoding::format::Deserializer for StatsdDeserializer {
 async fn statsd_udp(
     config: UdpConfig,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
+    ready: ReadyGuard,
 ) -> Result<(), ()> {
     // TODO: This should probably be based off of the `socket` source in UDP mode. If it's missing features needed, we
     // should add them. Reduce, reuse, recycle.
-    let socket = UdpSocket::bind(&config.address)
-        .map_err(|error| emit!(StatsdSocketError::bind(error)))
-        .await?;
-
-    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
-        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-        }
+    let sockets = try_bind_udp_sockets(
+        config.address,
+        config.workers.unwrap_or(1),
+        ListenFd::from_env(),
+    )
+    .map_err(|error| emit!(StatsdSocketError::bind(error)))
+    .await?;
+    drop(ready);
+
+    for socket in &sockets {
+        udp::configure_receive_buffer(
+            socket,
+            config.receive_buffer_bytes,
+            config.receive_buffer_max_bytes,
+            shutdown.clone(),
+        );
     }
 
     info!(
         message = "Listening.",
         addr = %config.address,
-        r#type = "udp"
+        r#type = "udp",
+        workers = sockets.len(),
     );
 
+    udp::run_workers(sockets, |socket| {
+        statsd_udp_socket(socket, shutdown.clone(), out.clone())
+    })
+    .await
+}
+
+async fn statsd_udp_socket(
+    socket: Arc<UdpSocket>,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
     let codec = Decoder::new(
         Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
         Deserializer::Boxed(Box::new(StatsdDeserializer::udp())),
@@ -9,6 +9,7 @@ use vector_config::configurable_component;
 use super::StatsdDeserializer;
 use crate::{
     codecs::Decoder,
+    config::ReadyGuard,
     shutdown::ShutdownSignal,
     sources::{util::build_unix_stream_source, Source},
     SourceSender,
@@ -28,6 +29,7 @@ pub fn statsd_unix(
     config: UnixConfig,
     shutdown: ShutdownSignal,
     out: SourceSender,
+    ready: ReadyGuard,
 ) -> crate::Result<Source> {
     let decoder = Decoder::new(
         Framer::NewlineDelimited(NewlineDelimitedDecoder::new()),
@@ -41,5 +43,6 @@ pub fn statsd_unix(
         |_events, _host| {},
         shutdown,
         out,
+        ready,
     )
 }
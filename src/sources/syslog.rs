@@ -1,14 +1,15 @@
-use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
+use std::{net::SocketAddr, sync::Arc};
 
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
     decoding::{Deserializer, Framer},
-    BytesDecoder, OctetCountingDecoder, SyslogDeserializer,
+    BytesDecoder, BytesDeserializer, OctetCountingDecoder, SyslogDeserializer,
 };
 use futures::StreamExt;
+use listenfd::ListenFd;
 use smallvec::SmallVec;
 use tokio::net::UdpSocket;
 use tokio_util::udp::UdpFramed;
@@ -20,13 +21,13 @@ use crate::codecs::Decoder;
 use crate::sources::util::build_unix_stream_source;
 use crate::{
     config::{
-        log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
-        SourceDescription,
+        log_schema, DataType, GenerateConfig, Output, ReadyGuard, Resource, SourceConfig,
+        SourceContext, SourceDescription,
     },
     event::Event,
     internal_events::SyslogUdpReadError,
     shutdown::ShutdownSignal,
-    sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
+    sources::util::{try_bind_udp_sockets, SocketListenAddr, TcpNullAcker, TcpSource},
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsSourceConfig},
     udp, SourceSender,
@@ -52,6 +53,14 @@ pub struct SyslogConfig {
     ///
     /// By default, the [global `host_key` option](https://vector.dev/docs/reference/configuration//global-options#log_schema.host_key) is used.
     host_key: Option<String>,
+
+    /// Whether to forward the messages as opaque bytes, without parsing them.
+    ///
+    /// The whole message is stored in the `message` field, and only the host, source IP, source type, and reception
+    /// timestamp are added to each event. Sinks encoding events with the `raw_message` codec send the received bytes
+    /// as-is, which makes relaying messages much cheaper.
+    #[serde(default)]
+    passthrough: bool,
 }
 
 /// Listener mode for the `syslog` source.
@@ -82,12 +91,27 @@ pub enum Mode {
     /// Listen on UDP.
     Udp {
         /// The address to listen for messages on.
-        address: SocketAddr,
+        ///
+        /// `systemd#N` uses the Nth socket passed by `systemd` socket activation.
+        address: SocketListenAddr,
 
         /// The size, in bytes, of the receive buffer used for the listening socket.
         ///
         /// This should not typically needed to be changed.
         receive_buffer_bytes: Option<usize>,
+
+        /// The maximum size, in bytes, that the receive buffer is grown to when datagrams are dropped.
+        ///
+        /// When set, the kernel's drop counter for the socket is checked periodically, and the receive
+        /// buffer is doubled, up to this size, whenever datagrams were dropped. The kernel caps the
+        /// receive buffer at `net.core.rmem_max`. Only supported on Linux.
+        receive_buffer_max_bytes: Option<usize>,
+
+        /// The number of sockets bound to the address, each read by its own worker.
+        ///
+        /// When greater than one, `SO_REUSEPORT` is set on the sockets so that the kernel spreads
+        /// incoming datagrams across them. Only supported on Unix.
+        workers: Option<usize>,
     },
 
     /// Listen on UDS. (Unix domain socket)
@@ -112,10 +136,20 @@ impl SyslogConfig {
             mode,
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            passthrough: false,
         }
     }
 }
 
+/// Parses the messages, unless they are passed through as opaque bytes.
+fn deserializer(passthrough: bool) -> Deserializer {
+    if passthrough {
+        Deserializer::Bytes(BytesDeserializer::new())
+    } else {
+        Deserializer::Syslog(SyslogDeserializer)
+    }
+}
+
 inventory::submit! {
     SourceDescription::new::<SyslogConfig>("syslog")
 }
@@ -132,6 +166,7 @@ impl GenerateConfig for SyslogConfig {
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            passthrough: false,
         })
         .unwrap()
     }
@@ -157,6 +192,7 @@ impl SourceConfig for SyslogConfig {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
+                    passthrough: self.passthrough,
                 };
                 let shutdown_secs = 30;
                 let tls_config = tls.as_ref().map(|tls| tls.tls_config.clone());
@@ -178,13 +214,19 @@ impl SourceConfig for SyslogConfig {
             Mode::Udp {
                 address,
                 receive_buffer_bytes,
+                receive_buffer_max_bytes,
+                workers,
             } => Ok(udp(
                 address,
                 self.max_length,
                 host_key,
                 receive_buffer_bytes,
+                receive_buffer_max_bytes,
+                workers.unwrap_or(1),
+                self.passthrough,
                 cx.shutdown,
                 cx.out,
+                cx.ready.defer(),
             )),
             #[cfg(unix)]
             Mode::Unix {
@@ -195,7 +237,7 @@ impl SourceConfig for SyslogConfig {
                     Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(
                         self.max_length,
                     )),
-                    Deserializer::Syslog(SyslogDeserializer),
+                    deserializer(self.passthrough),
                 );
 
                 build_unix_stream_source(
@@ -205,6 +247,7 @@ impl SourceConfig for SyslogConfig {
                     move |events, host| handle_events(events, &host_key, host),
                     cx.shutdown,
                     cx.out,
+                    cx.ready.defer(),
                 )
             }
         }
@@ -221,7 +264,7 @@ impl SourceConfig for SyslogConfig {
     fn resources(&self) -> Vec<Resource> {
         match self.mode.clone() {
             Mode::Tcp { address, .. } => vec![address.into()],
-            Mode::Udp { address, .. } => vec![Resource::udp(address)],
+            Mode::Udp { address, .. } => vec![address.as_udp_resource()],
             #[cfg(unix)]
             Mode::Unix { .. } => vec![],
         }
@@ -236,6 +279,7 @@ impl SourceConfig for SyslogConfig {
 struct SyslogTcpSource {
     max_length: usize,
     host_key: String,
+    passthrough: bool,
 }
 
 impl TcpSource for SyslogTcpSource {
@@ -247,7 +291,7 @@ impl TcpSource for SyslogTcpSource {
     fn decoder(&self) -> Self::Decoder {
         Decoder::new(
             Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
-            Deserializer::Syslog(SyslogDeserializer),
+            deserializer(self.passthrough),
         )
     }
 
@@ -260,68 +304,97 @@ impl TcpSource for SyslogTcpSource {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn udp(
-    addr: SocketAddr,
+    addr: SocketListenAddr,
     _max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    receive_buffer_max_bytes: Option<usize>,
+    workers: usize,
+    passthrough: bool,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
+    ready: ReadyGuard,
 ) -> super::Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&addr)
+        let sockets = try_bind_udp_sockets(addr, workers, ListenFd::from_env())
             .await
             .expect("Failed to bind to UDP listener socket");
+        drop(ready);
 
-        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
+        for socket in &sockets {
+            udp::configure_receive_buffer(
+                socket,
+                receive_buffer_bytes,
+                receive_buffer_max_bytes,
+                shutdown.clone(),
+            );
         }
 
         info!(
             message = "Listening.",
             addr = %addr,
-            r#type = "udp"
+            r#type = "udp",
+            workers = sockets.len(),
         );
 
-        let mut stream = UdpFramed::new(
-            socket,
-            Decoder::new(
-                Framer::Bytes(BytesDecoder::new()),
-                Deserializer::Syslog(SyslogDeserializer),
-            ),
-        )
-        .take_until(shutdown)
-        .filter_map(|frame| {
-            let host_key = host_key.clone();
-            async move {
-                match frame {
-                    Ok(((mut events, _byte_size), received_from)) => {
-                        let received_from = received_from.ip().to_string().into();
-                        handle_events(&mut events, &host_key, Some(received_from));
-                        Some(events.remove(0))
-                    }
-                    Err(error) => {
-                        emit!(SyslogUdpReadError { error });
-                        None
-                    }
-                }
-            }
+        udp::run_workers(sockets, |socket| {
+            recv_udp(
+                socket,
+                host_key.clone(),
+                passthrough,
+                shutdown.clone(),
+                out.clone(),
+            )
         })
-        .boxed();
+        .await
+    })
+}
 
-        match out.send_event_stream(&mut stream).await {
-            Ok(()) => {
-                info!("Finished sending.");
-                Ok(())
-            }
-            Err(error) => {
-                error!(message = "Error sending line.", %error);
-                Err(())
+async fn recv_udp(
+    socket: Arc<UdpSocket>,
+    host_key: String,
+    passthrough: bool,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut stream = UdpFramed::new(
+        socket,
+        Decoder::new(
+            Framer::Bytes(BytesDecoder::new()),
+            deserializer(passthrough),
+        ),
+    )
+    .take_until(shutdown)
+    .filter_map(|frame| {
+        let host_key = host_key.clone();
+        async move {
+            match frame {
+                Ok(((mut events, _byte_size), received_from)) => {
+                    let received_from = received_from.ip().to_string().into();
+                    handle_events(&mut events, &host_key, Some(received_from));
+                    Some(events.remove(0))
+                }
+                Err(error) => {
+                    emit!(SyslogUdpReadError { error });
+                    None
+                }
             }
         }
     })
+    .boxed();
+
+    match out.send_event_stream(&mut stream).await {
+        Ok(()) => {
+            info!("Finished sending.");
+            Ok(())
+        }
+        Err(error) => {
+            error!(message = "Error sending line.", %error);
+            Err(())
+        }
+    }
 }
 
 fn handle_events(events: &mut [Event], host_key: &str, default_host: Option<Bytes>) {
@@ -513,6 +586,31 @@ mod test {
         assert_eq!(receive_buffer_bytes, Some(256));
     }
 
+    #[test]
+    fn config_udp_with_workers() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "udp"
+            address = "127.0.0.1:1235"
+            receive_buffer_max_bytes = 8388608
+            workers = 4
+          "#,
+        )
+        .unwrap();
+
+        match config.mode {
+            Mode::Udp {
+                receive_buffer_max_bytes,
+                workers,
+                ..
+            } => {
+                assert_eq!(receive_buffer_max_bytes, Some(8388608));
+                assert_eq!(workers, Some(4));
+            }
+            _ => panic!("expected Mode::Udp"),
+        }
+    }
+
     #[cfg(unix)]
     #[test]
     fn config_unix() {
@@ -590,6 +688,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn passes_messages_through() {
+        let raw = r#"<13>1 2019-02-13T19:48:34+00:00 74794bfb6795 root 8449 - - qwerty"#;
+
+        let mut events = deserializer(true)
+            .parse(raw.into(), LogNamespace::Legacy)
+            .unwrap();
+        handle_events(&mut events, "host", Some("192.168.0.254".into()));
+        let log = events[0].as_log();
+
+        assert_eq!(log[log_schema().message_key()], raw.into());
+        assert_eq!(log[log_schema().host_key()], "192.168.0.254".into());
+        assert_eq!(log[log_schema().source_type_key()], "syslog".into());
+        assert!(log.contains(log_schema().timestamp_key()));
+        assert!(!log.contains("hostname"));
+        assert!(!log.contains("appname"));
+    }
+
     #[test]
     fn handles_incorrect_sd_element() {
         let msg = "qwerty";
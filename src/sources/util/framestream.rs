@@ -4,6 +4,7 @@ use std::{
     convert::TryInto,
     fs,
     marker::{Send, Sync},
+    net::SocketAddr,
     path::PathBuf,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -20,18 +21,27 @@ use futures::{
     sink::{Sink, SinkExt},
     stream::{self, StreamExt, TryStreamExt},
 };
-use tokio::{self, net::UnixListener, task::JoinHandle};
+use tokio::{
+    self,
+    io::{AsyncRead, AsyncWrite},
+    net::UnixListener,
+    task::JoinHandle,
+};
 use tokio_stream::wrappers::UnixListenerStream;
 use tokio_util::codec::{length_delimited, Framed};
-use tracing::{field, Instrument};
+use tracing::{field, Instrument, Span};
 
 use crate::{
+    config::ReadyGuard,
     event::Event,
     internal_events::{
-        SocketEventsReceived, SocketMode, UnixSocketError, UnixSocketFileDeleteError,
+        SocketEventsReceived, SocketMode, TcpSocketError, TcpSocketTlsConnectionError,
+        UnixSocketError, UnixSocketFileDeleteError,
     },
     shutdown::ShutdownSignal,
     sources::Source,
+    tcp::TcpKeepaliveConfig,
+    tls::MaybeTlsSettings,
     SourceSender,
 };
 
@@ -43,6 +53,7 @@ pub type FrameStreamSink = Box<dyn Sink<Bytes, Error = std::io::Error> + Send +
 struct FrameStreamReader {
     response_sink: Mutex<FrameStreamSink>,
     expected_content_type: String,
+    mode: SocketMode,
     state: FrameStreamState,
 }
 
@@ -136,10 +147,15 @@ fn advance_u32(b: &mut Bytes) -> Result<u32, ()> {
 }
 
 impl FrameStreamReader {
-    pub fn new(response_sink: FrameStreamSink, expected_content_type: String) -> Self {
+    pub fn new(
+        response_sink: FrameStreamSink,
+        expected_content_type: String,
+        mode: SocketMode,
+    ) -> Self {
         FrameStreamReader {
             response_sink: Mutex::new(response_sink),
             expected_content_type,
+            mode,
             state: FrameStreamState::new(),
         }
     }
@@ -157,7 +173,7 @@ impl FrameStreamReader {
             //data frame
             if self.state.control_state == ControlState::ReadingData {
                 emit!(SocketEventsReceived {
-                    mode: SocketMode::Unix,
+                    mode: self.mode,
                     byte_size: frame.len(),
                     count: 1
                 });
@@ -454,10 +470,7 @@ pub fn build_framestream_unix_source(
                 Ok(s) => s,
             };
             let peer_addr = socket.peer_addr().ok();
-            let content_type = frame_handler.content_type();
             let listen_path = path.clone();
-            let mut event_sink = out.clone();
-            let active_task_nums_ = Arc::clone(&active_parsing_task_nums);
 
             let span = info_span!("connection");
             let path = if let Some(addr) = peer_addr {
@@ -473,69 +486,22 @@ pub fn build_framestream_unix_source(
             let received_from: Option<Bytes> =
                 path.map(|p| p.to_string_lossy().into_owned().into());
 
-            let (sock_sink, sock_stream) = Framed::new(
+            spawn_frame_stream_handler(
+                frame_handler.clone(),
                 socket,
-                length_delimited::Builder::new()
-                    .max_frame_length(frame_handler.max_frame_length())
-                    .new_codec(),
-            )
-            .split();
-            let mut fs_reader = FrameStreamReader::new(Box::new(sock_sink), content_type);
-            let frame_handler_copy = frame_handler.clone();
-            let frames = sock_stream
-                .take_until(shutdown.clone())
-                .map_err(move |error| {
+                received_from,
+                SocketMode::Unix,
+                move |error| {
                     emit!(UnixSocketError {
                         error: &error,
                         path: &listen_path,
-                    });
-                })
-                .filter_map(move |frame| {
-                    future::ready(match frame {
-                        Ok(f) => fs_reader.handle_frame(Bytes::from(f)),
-                        Err(_) => None,
                     })
-                });
-            if !frame_handler.multithreaded() {
-                let mut events = frames.filter_map(move |f| {
-                    future::ready(frame_handler_copy.handle_event(received_from.clone(), f))
-                });
-
-                let handler = async move {
-                    if let Err(e) = event_sink.send_event_stream(&mut events).await {
-                        error!("Error sending event: {:?}.", e);
-                    }
-
-                    info!("Finished sending.");
-                };
-                tokio::spawn(handler.instrument(span.or_current()));
-            } else {
-                let handler = async move {
-                    frames
-                        .for_each(move |f| {
-                            future::ready({
-                                let max_frame_handling_tasks =
-                                    frame_handler_copy.max_frame_handling_tasks();
-                                let f_handler = frame_handler_copy.clone();
-                                let received_from_copy = received_from.clone();
-                                let event_sink_copy = event_sink.clone();
-                                let active_task_nums_copy = Arc::clone(&active_task_nums_);
-
-                                spawn_event_handling_tasks(
-                                    f,
-                                    f_handler,
-                                    event_sink_copy,
-                                    received_from_copy,
-                                    active_task_nums_copy,
-                                    max_frame_handling_tasks,
-                                );
-                            })
-                        })
-                        .await;
-                    info!("Finished sending.");
-                };
-                tokio::spawn(handler.instrument(span.or_current()));
-            }
+                },
+                shutdown.clone(),
+                out.clone(),
+                Arc::clone(&active_parsing_task_nums),
+                span,
+            );
         }
 
         // Cleanup
@@ -552,6 +518,173 @@ pub fn build_framestream_unix_source(
     Ok(Box::pin(fut))
 }
 
+/**
+ * Accepts framestream connections over TCP, optionally wrapped in TLS.
+ * Each connection is handled the same way as in build_framestream_unix_source,
+ * with the peer address used as the source of the received frames.
+ * The source is ready once `ready` is dropped, after the address is bound.
+ **/
+pub fn build_framestream_tcp_source(
+    frame_handler: impl FrameHandler + Send + Sync + Clone + 'static,
+    address: SocketAddr,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: MaybeTlsSettings,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
+    ready: ReadyGuard,
+) -> crate::Result<Source> {
+    let fut = async move {
+        let listener = match tls.bind(&address).await {
+            Ok(listener) => listener,
+            Err(error) => {
+                error!(message = "Failed to bind to listener socket.", %error);
+                return Err(());
+            }
+        };
+        drop(ready);
+
+        let active_parsing_task_nums = Arc::new(AtomicU32::new(0));
+
+        info!(message = "Listening...", %address, r#type = "tcp");
+
+        let mut stream = listener.accept_stream().take_until(shutdown.clone());
+        while let Some(socket) = stream.next().await {
+            let mut socket = match socket {
+                Err(error) => {
+                    emit!(TcpSocketTlsConnectionError { error });
+                    continue;
+                }
+                Ok(s) => s,
+            };
+            let peer_addr = socket.peer_addr();
+            let span = info_span!("connection", %peer_addr);
+            let frame_handler = frame_handler.clone();
+            let mut shutdown = shutdown.clone();
+            let out = out.clone();
+            let active_task_nums = Arc::clone(&active_parsing_task_nums);
+
+            let connection = async move {
+                tokio::select! {
+                    result = socket.handshake() => {
+                        if let Err(error) = result {
+                            emit!(TcpSocketTlsConnectionError { error });
+                            return;
+                        }
+                    },
+                    _ = &mut shutdown => {
+                        return;
+                    }
+                };
+
+                if let Some(keepalive) = keepalive {
+                    if let Err(error) = socket.set_keepalive(keepalive) {
+                        warn!(message = "Failed configuring TCP keepalive.", %error);
+                    }
+                }
+
+                let received_from: Option<Bytes> = Some(peer_addr.ip().to_string().into());
+
+                spawn_frame_stream_handler(
+                    frame_handler,
+                    socket,
+                    received_from,
+                    SocketMode::Tcp,
+                    |error| emit!(TcpSocketError { error }),
+                    shutdown,
+                    out,
+                    active_task_nums,
+                    Span::current(),
+                );
+            };
+            tokio::spawn(connection.instrument(span.or_current()));
+        }
+
+        Ok(())
+    };
+
+    Ok(Box::pin(fut))
+}
+
+/**
+ * Reads framestream frames from an accepted connection, answering control
+ * frames and forwarding the data frames to the frame handler.
+ **/
+#[allow(clippy::too_many_arguments)]
+fn spawn_frame_stream_handler<S>(
+    frame_handler: impl FrameHandler + Send + Sync + Clone + 'static,
+    socket: S,
+    received_from: Option<Bytes>,
+    mode: SocketMode,
+    emit_error: impl Fn(std::io::Error) + Send + 'static,
+    shutdown: ShutdownSignal,
+    mut event_sink: SourceSender,
+    active_task_nums_: Arc<AtomicU32>,
+    span: Span,
+) where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+    let content_type = frame_handler.content_type();
+
+    let (sock_sink, sock_stream) = Framed::new(
+        socket,
+        length_delimited::Builder::new()
+            .max_frame_length(frame_handler.max_frame_length())
+            .new_codec(),
+    )
+    .split();
+    let mut fs_reader = FrameStreamReader::new(Box::new(sock_sink), content_type, mode);
+    let frame_handler_copy = frame_handler.clone();
+    let frames = sock_stream
+        .take_until(shutdown)
+        .map_err(emit_error)
+        .filter_map(move |frame| {
+            future::ready(match frame {
+                Ok(f) => fs_reader.handle_frame(Bytes::from(f)),
+                Err(_) => None,
+            })
+        });
+    if !frame_handler.multithreaded() {
+        let mut events = frames.filter_map(move |f| {
+            future::ready(frame_handler_copy.handle_event(received_from.clone(), f))
+        });
+
+        let handler = async move {
+            if let Err(e) = event_sink.send_event_stream(&mut events).await {
+                error!("Error sending event: {:?}.", e);
+            }
+
+            info!("Finished sending.");
+        };
+        tokio::spawn(handler.instrument(span.or_current()));
+    } else {
+        let handler = async move {
+            frames
+                .for_each(move |f| {
+                    future::ready({
+                        let max_frame_handling_tasks =
+                            frame_handler_copy.max_frame_handling_tasks();
+                        let f_handler = frame_handler_copy.clone();
+                        let received_from_copy = received_from.clone();
+                        let event_sink_copy = event_sink.clone();
+                        let active_task_nums_copy = Arc::clone(&active_task_nums_);
+
+                        spawn_event_handling_tasks(
+                            f,
+                            f_handler,
+                            event_sink_copy,
+                            received_from_copy,
+                            active_task_nums_copy,
+                            max_frame_handling_tasks,
+                        );
+                    })
+                })
+                .await;
+            info!("Finished sending.");
+        };
+        tokio::spawn(handler.instrument(span.or_current()));
+    }
+}
+
 fn spawn_event_handling_tasks(
     event_data: Bytes,
     event_handler: impl FrameHandler + Send + Sync + 'static,
@@ -602,21 +735,22 @@ mod test {
     };
     use tokio::{
         self,
-        net::UnixStream,
+        net::{TcpStream, UnixStream},
         task::JoinHandle,
         time::{Duration, Instant},
     };
     use tokio_util::codec::{length_delimited, Framed};
 
     use super::{
-        build_framestream_unix_source, spawn_event_handling_tasks, ControlField, ControlHeader,
-        FrameHandler,
+        build_framestream_tcp_source, build_framestream_unix_source, spawn_event_handling_tasks,
+        ControlField, ControlHeader, FrameHandler,
     };
     use crate::{
-        config::{log_schema, ComponentKey},
+        config::{log_schema, ComponentKey, SourceReady},
         event::{Event, LogEvent},
         shutdown::SourceShutdownCoordinator,
-        test_util::{collect_n, collect_n_stream},
+        test_util::{collect_n, collect_n_stream, next_addr, wait_for_tcp},
+        tls::MaybeTlsSettings,
         SourceSender,
     };
 
@@ -854,6 +988,60 @@ mod test {
         let _ = source_handle.await.unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn normal_framestream_tcp() {
+        let source_name = "test_source";
+        let (tx, rx) = SourceSender::new_test();
+        let address = next_addr();
+        let source_id = ComponentKey::from(source_name);
+        let mut shutdown = SourceShutdownCoordinator::default();
+        let (shutdown_signal, _) = shutdown.register_source(&source_id);
+        let server = build_framestream_tcp_source(
+            create_frame_handler(false),
+            address,
+            None,
+            MaybeTlsSettings::Raw(()),
+            shutdown_signal,
+            tx,
+            SourceReady::default().defer(),
+        )
+        .expect("Failed to build framestream tcp source.");
+        let source_handle = tokio::spawn(server);
+        wait_for_tcp(address).await;
+
+        let socket = TcpStream::connect(address).await.unwrap();
+        let (mut sock_sink, mut sock_stream) =
+            Framed::new(socket, length_delimited::Builder::new().new_codec()).split();
+
+        //1 - send READY frame (with content_type)
+        let content_type = Bytes::from(&b"test_content"[..]);
+        let ready_msg =
+            create_control_frame_with_content(ControlHeader::Ready, vec![content_type.clone()]);
+        send_control_frame(&mut sock_sink, ready_msg).await;
+
+        //2 - wait for ACCEPT frame
+        let mut frame_vec = collect_n_stream(&mut sock_stream, 2).await;
+        assert_eq!(frame_vec[0].as_ref().unwrap().len(), 0);
+        assert_accept_frame(frame_vec[1].as_mut().unwrap(), content_type);
+
+        //3 - send START frame and data
+        send_control_frame(&mut sock_sink, create_control_frame(ControlHeader::Start)).await;
+        send_data_frames(&mut sock_sink, vec![Ok(Bytes::from("hello"))]).await;
+        let events = collect_n(rx, 1).await;
+
+        //4 - send STOP frame
+        send_control_frame(&mut sock_sink, create_control_frame(ControlHeader::Stop)).await;
+
+        let log = events[0].as_log();
+        assert_eq!(log[&log_schema().message_key()], "hello".into());
+        assert_eq!(log["test_framestream"], "127.0.0.1".into());
+
+        std::mem::drop(sock_stream);
+
+        signal_shutdown(source_name, &mut shutdown).await;
+        let _ = source_handle.await.unwrap();
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn normal_framestream_multithreaded() {
         let source_name = "test_source";
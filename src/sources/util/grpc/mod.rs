@@ -1,26 +1,66 @@
 use crate::{
+    config::ReadyGuard,
     shutdown::{ShutdownSignal, ShutdownSignalToken},
     tls::MaybeTlsSettings,
 };
 use futures::FutureExt;
 use http::{Request, Response};
 use hyper::Body;
-use std::{convert::Infallible, net::SocketAddr};
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{NamedService, Router, Server},
+};
+use tower::{
+    layer::util::{Identity, Stack},
+    Service,
 };
-use tower::Service;
 use tracing::{Instrument, Span};
+use vector_config::configurable_component;
 
 mod decompression;
 pub use self::decompression::{DecompressionAndMetrics, DecompressionAndMetricsLayer};
 
+/// HTTP/2 settings of a gRPC server.
+///
+/// Middleboxes such as load balancers, proxies and NAT gateways often close connections that look
+/// idle, even when long-lived streams are open on them. Keepalive pings keep such connections
+/// active, and detect the ones that were dropped.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Http2ServerConfig {
+    /// The interval, in seconds, at which HTTP/2 keepalive pings are sent to clients.
+    ///
+    /// If not set, no keepalive pings are sent.
+    pub keepalive_interval_secs: Option<u64>,
+
+    /// How long, in seconds, to wait for a keepalive ping to be acknowledged before closing the
+    /// connection.
+    ///
+    /// Only relevant when `keepalive_interval_secs` is set. Defaults to 20 seconds.
+    pub keepalive_timeout_secs: Option<u64>,
+
+    /// The maximum number of concurrent streams that each client connection can open.
+    pub max_concurrent_streams: Option<u32>,
+}
+
+impl Http2ServerConfig {
+    fn configure<L>(&self, server: Server<L>) -> Server<L> {
+        server
+            .http2_keepalive_interval(self.keepalive_interval_secs.map(Duration::from_secs))
+            .http2_keepalive_timeout(self.keepalive_timeout_secs.map(Duration::from_secs))
+            .max_concurrent_streams(self.max_concurrent_streams)
+    }
+}
+
 pub async fn run_grpc_server<S>(
     address: SocketAddr,
     tls_settings: MaybeTlsSettings,
+    http2: Http2ServerConfig,
     service: S,
     shutdown: ShutdownSignal,
+    ready: ReadyGuard,
 ) -> crate::Result<()>
 where
     S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
@@ -29,13 +69,39 @@ where
         + Send
         + 'static,
     S::Future: Send + 'static,
+{
+    run_grpc_server_with_routes(address, tls_settings, http2, shutdown, ready, |server| {
+        server.add_service(service)
+    })
+    .await
+}
+
+/// The layers applied to every gRPC server built by [`run_grpc_server_with_routes`].
+pub type GrpcServerLayer = Stack<DecompressionAndMetricsLayer, Identity>;
+
+/// Runs a gRPC server serving the services added to it by `routes`.
+///
+/// This is useful for sources which expose more than one gRPC service on the same address. The
+/// source is ready once `ready` is dropped, after the address is bound.
+pub async fn run_grpc_server_with_routes<F>(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    http2: Http2ServerConfig,
+    shutdown: ShutdownSignal,
+    ready: ReadyGuard,
+    routes: F,
+) -> crate::Result<()>
+where
+    F: FnOnce(&mut Server<GrpcServerLayer>) -> Router<GrpcServerLayer>,
 {
     let span = Span::current();
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
     let listener = tls_settings.bind(&address).await?;
+    drop(ready);
     let stream = listener.accept_stream();
 
-    Server::builder()
+    let mut server = http2
+        .configure(Server::builder())
         .trace_fn(move |_| span.clone())
         // This layer explicitly decompresses payloads, if compressed, and reports the number of message bytes we've
         // received if the message is processed successfully, aka `BytesReceived`. We do this because otherwise the only
@@ -46,8 +112,9 @@ where
         // use independent `tower` layers when the request body itself (the body type, not the actual bytes) must be
         // modified or wrapped.. so instead of a cleaner design, we're opting here to bake it all together until the
         // crates are sufficiently flexible for us to craft a better design.
-        .layer(DecompressionAndMetricsLayer::default())
-        .add_service(service)
+        .layer(DecompressionAndMetricsLayer::default());
+
+    routes(&mut server)
         .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
         .in_current_span()
         .await?;
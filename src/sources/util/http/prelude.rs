@@ -1,8 +1,9 @@
-use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
+use std::{collections::HashMap, convert::TryFrom, fmt};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{FutureExt, TryFutureExt};
+use futures::FutureExt;
+use listenfd::ListenFd;
 use tracing::Span;
 use vector_core::{
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
@@ -20,8 +21,13 @@ use warp::{
 
 use crate::{
     config::{AcknowledgementsConfig, SourceContext},
-    internal_events::{HttpBadRequest, HttpBytesReceived, HttpEventsReceived},
-    sources::http::HttpMethod,
+    internal_events::{
+        HttpBackpressureRejected, HttpBadRequest, HttpBytesReceived, HttpEventsReceived,
+    },
+    sources::{
+        http::HttpMethod,
+        util::{try_bind_tcp_listener, SocketListenAddr},
+    },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
@@ -42,10 +48,21 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// The named output that events received on `path` are sent to, or `None` for the default
+    /// output.
+    fn output(&self, _path: &str) -> Option<String> {
+        None
+    }
+
+    /// Whether to respond with `429 Too Many Requests` instead of waiting when the output is full.
+    fn reject_when_full(&self) -> bool {
+        false
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
-        address: SocketAddr,
+        address: SocketListenAddr,
         path: &str,
         method: HttpMethod,
         strict_path: bool,
@@ -59,6 +76,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
         let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
+        let ready = cx.ready.defer();
         Ok(Box::pin(async move {
             let span = Span::current();
             let mut filter: BoxedFilter<()> = match method {
@@ -126,7 +144,14 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                 events
                             });
 
-                        handle_request(events, acknowledgements, cx.out.clone())
+                        let output = self.output(http_path);
+                        handle_request(
+                            events,
+                            acknowledgements,
+                            output,
+                            self.reject_when_full(),
+                            cx.out.clone(),
+                        )
                     },
                 )
                 .with(warp::trace(move |_info| span.clone()));
@@ -144,7 +169,10 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
             info!(message = "Building HTTP server.", address = %address);
 
-            let listener = tls.bind(&address).await.unwrap();
+            let listener = try_bind_tcp_listener(address, ListenFd::from_env(), &tls)
+                .await
+                .map_err(|error| error!(message = "Failed to bind to listener socket.", %error))?;
+            drop(ready);
             warp::serve(routes)
                 .serve_incoming_with_graceful_shutdown(
                     listener.accept_stream(),
@@ -169,22 +197,36 @@ impl warp::reject::Reject for RejectShuttingDown {}
 async fn handle_request(
     events: Result<Vec<Event>, ErrorMessage>,
     acknowledgements: bool,
+    output: Option<String>,
+    reject_when_full: bool,
     mut out: SourceSender,
 ) -> Result<impl warp::Reply, Rejection> {
     match events {
         Ok(mut events) => {
+            if reject_when_full && out.is_full(output.as_deref()) {
+                emit!(HttpBackpressureRejected {
+                    count: events.len()
+                });
+                return Err(warp::reject::custom(ErrorMessage::new(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "Too many requests, downstream components are not keeping up".into(),
+                )));
+            }
+
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
 
-            out.send_batch(events)
-                .map_err(move |error: crate::source_sender::ClosedError| {
-                    // can only fail if receiving end disconnected, so we are shutting down,
-                    // probably not gracefully.
-                    error!(message = "Failed to forward events, downstream is closed.");
-                    error!(message = "Tried to send the following event.", %error);
-                    warp::reject::custom(RejectShuttingDown)
-                })
-                .and_then(|_| handle_batch_status(receiver))
-                .await
+            match output {
+                Some(name) => out.send_batch_named(&name, events).await,
+                None => out.send_batch(events).await,
+            }
+            .map_err(move |error: crate::source_sender::ClosedError| {
+                // can only fail if receiving end disconnected, so we are shutting down,
+                // probably not gracefully.
+                error!(message = "Failed to forward events, downstream is closed.");
+                error!(message = "Tried to send the following event.", %error);
+                warp::reject::custom(RejectShuttingDown)
+            })?;
+            handle_batch_status(receiver).await
         }
         Err(error) => {
             emit!(HttpBadRequest::new(error.code(), error.message()));
@@ -13,10 +13,16 @@ pub mod grpc;
     feature = "sources-utils-http-query"
 ))]
 mod http;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sources-aws_sqs",
+    feature = "sources-gcp_pubsub"
+))]
 mod message_decoding;
 pub mod multiline_config;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
+mod net;
+#[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 mod tcp;
 #[cfg(all(unix, any(feature = "sources-socket", feature = "sources-utils-unix",)))]
 mod unix;
@@ -34,8 +40,16 @@ mod wrappers;
 #[cfg(feature = "sources-file")]
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
+#[cfg(all(
+    feature = "sources-utils-tls",
+    feature = "listenfd",
+    feature = "sources-utils-udp"
+))]
+pub use net::try_bind_udp_sockets;
+#[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
+pub use net::{try_bind_tcp_listener, SocketListenAddr};
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
-pub use tcp::{SocketListenAddr, TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
+pub use tcp::{TcpNullAcker, TcpSource, TcpSourceAck, TcpSourceAcker};
 #[cfg(all(unix, any(feature = "sources-socket", feature = "sources-utils-unix",)))]
 pub use unix::change_socket_permissions;
 #[cfg(all(unix, feature = "sources-socket",))]
@@ -64,5 +78,9 @@ pub use self::http::ErrorMessage;
 pub use self::http::HttpSource;
 #[cfg(feature = "sources-utils-http-auth")]
 pub use self::http::HttpSourceAuthConfig;
-#[cfg(any(feature = "sources-aws_sqs", feature = "sources-gcp_pubsub"))]
+#[cfg(any(
+    feature = "sources-aws_cloudwatch_logs",
+    feature = "sources-aws_sqs",
+    feature = "sources-gcp_pubsub"
+))]
 pub use self::message_decoding::decode_message;
@@ -0,0 +1,177 @@
+use std::{fmt, net::SocketAddr};
+
+use listenfd::ListenFd;
+use serde::{de, Deserialize, Deserializer};
+use tokio::net::TcpListener;
+use vector_config::configurable_component;
+
+use crate::{
+    config::Resource,
+    tls::{MaybeTlsListener, MaybeTlsSettings},
+};
+
+/// A listening address that can be given directly or be managed via `systemd` socket activation.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum SocketListenAddr {
+    /// An IPv4/IPv6 address and port.
+    SocketAddr(#[configurable(derived)] SocketAddr),
+
+    /// A file descriptor identifier that is given from, and managed by, the socket activation feature of `systemd`.
+    #[serde(deserialize_with = "parse_systemd_fd")]
+    SystemdFd(#[configurable(transparent)] usize),
+}
+
+impl SocketListenAddr {
+    /// The resource claimed by a UDP socket listening on this address.
+    #[cfg(feature = "sources-utils-udp")]
+    pub const fn as_udp_resource(self) -> Resource {
+        match self {
+            Self::SocketAddr(addr) => Resource::udp(addr),
+            Self::SystemdFd(offset) => Resource::SystemFdOffset(offset),
+        }
+    }
+}
+
+impl fmt::Display for SocketListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SocketAddr(ref addr) => addr.fmt(f),
+            Self::SystemdFd(offset) => write!(f, "systemd socket #{}", offset),
+        }
+    }
+}
+
+impl From<SocketAddr> for SocketListenAddr {
+    fn from(addr: SocketAddr) -> Self {
+        Self::SocketAddr(addr)
+    }
+}
+
+impl From<SocketListenAddr> for Resource {
+    fn from(addr: SocketListenAddr) -> Resource {
+        match addr {
+            SocketListenAddr::SocketAddr(addr) => Resource::tcp(addr),
+            SocketListenAddr::SystemdFd(offset) => Self::SystemFdOffset(offset),
+        }
+    }
+}
+
+fn parse_systemd_fd<'de, D>(des: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: &'de str = Deserialize::deserialize(des)?;
+    match s {
+        "systemd" => Ok(0),
+        s if s.starts_with("systemd#") => s[8..]
+            .parse::<usize>()
+            .map_err(de::Error::custom)?
+            .checked_sub(1)
+            .ok_or_else(|| de::Error::custom("systemd indices start from 1, found 0")),
+        _ => Err(de::Error::custom("must start with \"systemd\"")),
+    }
+}
+
+fn missing_fd(offset: usize) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!(
+            "systemd socket #{} is not open or was already taken",
+            offset + 1
+        ),
+    )
+}
+
+/// Binds a TCP listener to `addr`, or takes over the one passed in by `systemd`.
+pub async fn try_bind_tcp_listener(
+    addr: SocketListenAddr,
+    mut listenfd: ListenFd,
+    tls: &MaybeTlsSettings,
+) -> crate::Result<MaybeTlsListener> {
+    match addr {
+        SocketListenAddr::SocketAddr(addr) => tls.bind(&addr).await.map_err(Into::into),
+        SocketListenAddr::SystemdFd(offset) => {
+            let listener = listenfd
+                .take_tcp_listener(offset)?
+                .ok_or_else(|| missing_fd(offset))?;
+            listener.set_nonblocking(true)?;
+            Ok(tls.listen(TcpListener::from_std(listener)?)?)
+        }
+    }
+}
+
+/// Binds `workers` UDP sockets to `addr`, or takes over the one passed in by `systemd`.
+///
+/// `systemd` passes a single socket per listening address, so only one worker can be used with it.
+#[cfg(feature = "sources-utils-udp")]
+pub async fn try_bind_udp_sockets(
+    addr: SocketListenAddr,
+    workers: usize,
+    mut listenfd: ListenFd,
+) -> std::io::Result<Vec<std::sync::Arc<tokio::net::UdpSocket>>> {
+    match addr {
+        SocketListenAddr::SocketAddr(addr) => crate::udp::bind_sockets(addr, workers).await,
+        SocketListenAddr::SystemdFd(offset) => {
+            if workers > 1 {
+                warn!(
+                    message = "Multiple UDP workers aren't supported with systemd socket activation, using one.",
+                    %addr
+                );
+            }
+            let socket = listenfd
+                .take_udp_socket(offset)?
+                .ok_or_else(|| missing_fd(offset))?;
+            socket.set_nonblocking(true)?;
+            let socket = tokio::net::UdpSocket::from_std(socket)?;
+            Ok(vec![std::sync::Arc::new(socket)])
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Config {
+        addr: SocketListenAddr,
+    }
+
+    #[test]
+    fn parse_socket_listen_addr() {
+        let test: Config = toml::from_str(r#"addr="127.1.2.3:1234""#).unwrap();
+        assert_eq!(
+            test.addr,
+            SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::new(127, 1, 2, 3),
+                1234,
+            )))
+        );
+        let test: Config = toml::from_str(r#"addr="systemd""#).unwrap();
+        assert_eq!(test.addr, SocketListenAddr::SystemdFd(0));
+        let test: Config = toml::from_str(r#"addr="systemd#3""#).unwrap();
+        assert_eq!(test.addr, SocketListenAddr::SystemdFd(2));
+    }
+
+    #[tokio::test]
+    async fn missing_systemd_fd() {
+        let error = try_bind_tcp_listener(
+            SocketListenAddr::SystemdFd(0),
+            ListenFd::empty(),
+            &MaybeTlsSettings::Raw(()),
+        )
+        .await
+        .err()
+        .expect("no systemd socket should have been passed in");
+        assert_eq!(
+            error.to_string(),
+            "systemd socket #1 is not open or was already taken"
+        );
+    }
+}
@@ -2,31 +2,35 @@ mod request_limiter;
 
 use std::collections::BTreeMap;
 use std::net::SocketAddr;
-use std::{fmt, io, mem::drop, time::Duration};
+use std::{io, mem::drop, time::Duration};
 
 use bytes::Bytes;
 use codecs::StreamDecodingError;
-use futures::{future::BoxFuture, FutureExt, StreamExt};
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, StreamExt,
+};
 use listenfd::ListenFd;
-use serde::{de, Deserialize, Deserializer};
 use smallvec::SmallVec;
 use socket2::SockRef;
 use tokio::{
     io::AsyncWriteExt,
-    net::{TcpListener, TcpStream},
-    time::sleep,
+    net::TcpStream,
+    time::{sleep, timeout},
 };
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing::Instrument;
 use vector_common::finalization::AddBatchNotifier;
-use vector_config::configurable_component;
 use vector_core::ByteSizeOf;
 
-use super::AfterReadExt as _;
+use super::{
+    net::{try_bind_tcp_listener, SocketListenAddr},
+    AfterReadExt as _,
+};
 use crate::sources::util::tcp::request_limiter::RequestLimiter;
 use crate::{
     codecs::ReadyFrames,
-    config::{AcknowledgementsConfig, Resource, SourceContext},
+    config::{AcknowledgementsConfig, SourceContext},
     event::{BatchNotifier, BatchStatus, Event},
     internal_events::{
         ConnectionOpen, OpenGauge, SocketEventsReceived, SocketMode, StreamClosedError,
@@ -34,44 +38,15 @@ use crate::{
     },
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
-    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsSettings},
     SourceSender,
 };
 
 const MAX_IN_FLIGHT_EVENTS_TARGET: usize = 100_000;
 
-async fn make_listener(
-    addr: SocketListenAddr,
-    mut listenfd: ListenFd,
-    tls: &MaybeTlsSettings,
-) -> Option<MaybeTlsListener> {
-    match addr {
-        SocketListenAddr::SocketAddr(addr) => match tls.bind(&addr).await {
-            Ok(listener) => Some(listener),
-            Err(error) => {
-                error!(message = "Failed to bind to listener socket.", %error);
-                None
-            }
-        },
-        SocketListenAddr::SystemdFd(offset) => match listenfd.take_tcp_listener(offset) {
-            Ok(Some(listener)) => match TcpListener::from_std(listener) {
-                Ok(listener) => Some(listener.into()),
-                Err(error) => {
-                    error!(message = "Failed to bind to listener socket.", %error);
-                    None
-                }
-            },
-            Ok(None) => {
-                error!("Failed to take listen FD, not open or already taken.");
-                None
-            }
-            Err(error) => {
-                error!(message = "Failed to take listen FD.", %error);
-                None
-            }
-        },
-    }
-}
+/// How long a new connection has to complete the handshake of the source, so that clients which
+/// never do can't hold a connection, and count against `max_connections`, forever.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum TcpSourceAck {
@@ -116,6 +91,16 @@ where
 
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
+    /// Performs an application-level handshake on a new connection, before any events are read
+    /// from it. The connection is closed if the handshake fails or takes longer than 30 seconds.
+    fn handshake<'a>(
+        &'a self,
+        _socket: &'a mut MaybeTlsIncomingStream<TcpStream>,
+        _peer_addr: SocketAddr,
+    ) -> BoxFuture<'a, crate::Result<()>> {
+        future::ok(()).boxed()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -132,12 +117,13 @@ where
         let acknowledgements = cx.do_acknowledgements(&acknowledgements);
 
         let listenfd = ListenFd::from_env();
+        let ready = cx.ready.defer();
 
         Ok(Box::pin(async move {
-            let listener = match make_listener(addr, listenfd, &tls).await {
-                None => return Err(()),
-                Some(listener) => listener,
-            };
+            let listener = try_bind_tcp_listener(addr, listenfd, &tls)
+                .await
+                .map_err(|error| error!(message = "Failed to bind to listener socket.", %error))?;
+            drop(ready);
 
             info!(
                 message = "Listening.",
@@ -273,6 +259,25 @@ async fn handle_stream<T>(
         }
     }
 
+    tokio::select! {
+        result = timeout(HANDSHAKE_TIMEOUT, source.handshake(&mut socket, peer_addr)) => {
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(error)) => {
+                    debug!(message = "Connection handshake failed.", %error);
+                    return;
+                }
+                Err(_) => {
+                    debug!(message = "Connection handshake timed out.");
+                    return;
+                }
+            }
+        },
+        _ = &mut shutdown_signal => {
+            return;
+        }
+    };
+
     let socket = socket.after_read(move |byte_size| {
         emit!(TcpBytesReceived {
             byte_size,
@@ -353,6 +358,9 @@ async fn handle_stream<T>(
                             if let Some(certificate_metadata) = &certificate_metadata {
                                 let mut metadata: BTreeMap<String, value::Value> = BTreeMap::new();
                                 metadata.insert("subject".to_string(), certificate_metadata.subject().into());
+                                if let Some(common_name) = &certificate_metadata.common_name {
+                                    metadata.insert("common_name".to_string(), common_name.clone().into());
+                                }
                                 for event in &mut events {
                                     let log = event.as_mut_log();
                                     log.insert(&tls_client_metadata_key[..], value::Value::from(metadata.clone()));
@@ -432,86 +440,3 @@ fn close_socket(socket: &MaybeTlsIncomingStream<TcpStream>) -> bool {
         true
     }
 }
-
-/// A listening address that can be given directly or be managed via `systemd` socket activation.
-#[configurable_component]
-#[derive(Clone, Copy, Debug, PartialEq)]
-#[serde(untagged)]
-pub enum SocketListenAddr {
-    /// An IPv4/IPv6 address and port.
-    SocketAddr(#[configurable(derived)] SocketAddr),
-
-    /// A file descriptor identifier that is given from, and managed by, the socket activation feature of `systemd`.
-    #[serde(deserialize_with = "parse_systemd_fd")]
-    SystemdFd(#[configurable(transparent)] usize),
-}
-
-impl fmt::Display for SocketListenAddr {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            Self::SocketAddr(ref addr) => addr.fmt(f),
-            Self::SystemdFd(offset) => write!(f, "systemd socket #{}", offset),
-        }
-    }
-}
-
-impl From<SocketAddr> for SocketListenAddr {
-    fn from(addr: SocketAddr) -> Self {
-        Self::SocketAddr(addr)
-    }
-}
-
-impl From<SocketListenAddr> for Resource {
-    fn from(addr: SocketListenAddr) -> Resource {
-        match addr {
-            SocketListenAddr::SocketAddr(addr) => Resource::tcp(addr),
-            SocketListenAddr::SystemdFd(offset) => Self::SystemFdOffset(offset),
-        }
-    }
-}
-
-fn parse_systemd_fd<'de, D>(des: D) -> Result<usize, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: &'de str = Deserialize::deserialize(des)?;
-    match s {
-        "systemd" => Ok(0),
-        s if s.starts_with("systemd#") => s[8..]
-            .parse::<usize>()
-            .map_err(de::Error::custom)?
-            .checked_sub(1)
-            .ok_or_else(|| de::Error::custom("systemd indices start from 1, found 0")),
-        _ => Err(de::Error::custom("must start with \"systemd\"")),
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
-
-    use serde::Deserialize;
-
-    use super::*;
-
-    #[derive(Debug, Deserialize)]
-    struct Config {
-        addr: SocketListenAddr,
-    }
-
-    #[test]
-    fn parse_socket_listen_addr() {
-        let test: Config = toml::from_str(r#"addr="127.1.2.3:1234""#).unwrap();
-        assert_eq!(
-            test.addr,
-            SocketListenAddr::SocketAddr(SocketAddr::V4(SocketAddrV4::new(
-                Ipv4Addr::new(127, 1, 2, 3),
-                1234,
-            )))
-        );
-        let test: Config = toml::from_str(r#"addr="systemd""#).unwrap();
-        assert_eq!(test.addr, SocketListenAddr::SystemdFd(0));
-        let test: Config = toml::from_str(r#"addr="systemd#3""#).unwrap();
-        assert_eq!(test.addr, SocketListenAddr::SystemdFd(2));
-    }
-}
@@ -10,6 +10,7 @@ use vector_core::ByteSizeOf;
 
 use crate::{
     codecs::Decoder,
+    config::ReadyGuard,
     event::Event,
     internal_events::{
         BytesReceived, SocketEventsReceived, SocketMode, SocketReceiveError, StreamClosedError,
@@ -24,7 +25,7 @@ use crate::{
 /// Returns a `Source` object corresponding to a Unix domain datagram socket.
 /// Passing in different functions for `decoder` and `handle_events` can allow
 /// for different source-specific logic (such as decoding syslog messages in the
-/// syslog source).
+/// syslog source). The source is ready once `ready` is dropped, after the socket is bound.
 pub fn build_unix_datagram_source(
     listen_path: PathBuf,
     socket_file_mode: Option<u32>,
@@ -33,6 +34,7 @@ pub fn build_unix_datagram_source(
     handle_events: impl Fn(&mut [Event], Option<Bytes>) + Clone + Send + Sync + 'static,
     shutdown: ShutdownSignal,
     out: SourceSender,
+    ready: ReadyGuard,
 ) -> crate::Result<Source> {
     Ok(Box::pin(async move {
         let socket = UnixDatagram::bind(&listen_path).expect("Failed to bind to datagram socket");
@@ -40,6 +42,7 @@ pub fn build_unix_datagram_source(
 
         change_socket_permissions(&listen_path, socket_file_mode)
             .expect("Failed to set socket permissions");
+        drop(ready);
 
         let result = listen(socket, max_length, decoder, shutdown, handle_events, out).await;
 
@@ -17,6 +17,7 @@ use super::AfterReadExt;
 use crate::{
     async_read::VecAsyncReadExt,
     codecs::Decoder,
+    config::ReadyGuard,
     event::Event,
     internal_events::{
         BytesReceived, ConnectionOpen, OpenGauge, SocketEventsReceived, SocketMode,
@@ -31,7 +32,7 @@ use crate::{
 /// Returns a `Source` object corresponding to a Unix domain stream socket.
 /// Passing in different functions for `decoder` and `handle_events` can allow
 /// for different source-specific logic (such as decoding syslog messages in the
-/// syslog source).
+/// syslog source). The source is ready once `ready` is dropped, after the socket is bound.
 pub fn build_unix_stream_source(
     listen_path: PathBuf,
     socket_file_mode: Option<u32>,
@@ -39,6 +40,7 @@ pub fn build_unix_stream_source(
     handle_events: impl Fn(&mut [Event], Option<Bytes>) + Clone + Send + Sync + 'static,
     shutdown: ShutdownSignal,
     out: SourceSender,
+    ready: ReadyGuard,
 ) -> crate::Result<Source> {
     Ok(Box::pin(async move {
         let listener = UnixListener::bind(&listen_path).expect("Failed to bind to listener socket");
@@ -46,6 +48,7 @@ pub fn build_unix_stream_source(
 
         change_socket_permissions(&listen_path, socket_file_mode)
             .expect("Failed to set socket permssions");
+        drop(ready);
 
         let connection_open = OpenGauge::new();
         let stream = UnixListenerStream::new(listener).take_until(shutdown.clone());
@@ -131,7 +131,10 @@ impl decoding::format::Deserializer for VectorDeserializer {
             protocol: "tcp",
         });
 
-        match proto::EventWrapper::decode(bytes).map(Event::from) {
+        let event = proto::EventWrapper::decode(bytes)
+            .map_err(crate::Error::from)
+            .and_then(|event| Event::try_from(event).map_err(Into::into));
+        match event {
             Ok(event) => {
                 emit!(OldEventsReceived {
                     count: 1,
@@ -141,7 +144,7 @@ impl decoding::format::Deserializer for VectorDeserializer {
             }
             Err(error) => {
                 emit!(VectorProtoDecodeError { error: &error });
-                Err(Box::new(error))
+                Err(error)
             }
         }
     }
@@ -312,6 +315,7 @@ mod test {
                 acknowledgements: false,
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
+                ready: Default::default(),
             })
             .await
             .unwrap();
@@ -352,6 +356,7 @@ mod test {
                 acknowledgements: false,
                 schema_definitions: HashMap::default(),
                 schema: Default::default(),
+                ready: Default::default(),
             })
             .await
             .unwrap();
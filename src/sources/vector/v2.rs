@@ -1,23 +1,36 @@
-use std::net::SocketAddr;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
 
 use futures::TryFutureExt;
-use tokio::net::TcpStream;
+use tokio::{
+    net::TcpStream,
+    sync::{OwnedSemaphorePermit, Semaphore},
+};
 use tonic::{
     transport::{server::Connected, Certificate},
     Request, Response, Status,
 };
 use vector_config::configurable_component;
 use vector_core::{
-    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    event::{proto::ConversionError, BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
     ByteSizeOf,
 };
 
 use crate::{
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource, SourceContext},
-    internal_events::{EventsReceived, StreamClosedError},
+    internal_events::{
+        EventsReceived, StreamClosedError, VectorProtoDecodeError, VectorStreamEventsReceived,
+    },
     proto::vector as proto,
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{
+        util::grpc::{run_grpc_server, Http2ServerConfig},
+        Source,
+    },
     tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsEnableableConfig},
     SourceSender,
 };
@@ -26,25 +39,88 @@ use crate::{
 pub struct Service {
     pipeline: SourceSender,
     acknowledgements: bool,
+    streams: Streams,
 }
 
-#[tonic::async_trait]
-impl proto::Service for Service {
-    async fn push_events(
-        &self,
-        request: Request<proto::PushEventsRequest>,
-    ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
-            .into_inner()
-            .events
-            .into_iter()
-            .map(Event::from)
-            .collect();
+type StreamLimits = Arc<Mutex<HashMap<String, Arc<Semaphore>>>>;
+
+/// Tracks the upstream streams multiplexed over this source so that each one can be flow
+/// controlled independently of the others.
+///
+/// Only streams with requests in flight are tracked, so that the upstreams that went away aren't
+/// kept around.
+#[derive(Debug, Clone)]
+struct Streams {
+    max_in_flight_requests: Option<NonZeroUsize>,
+    limits: StreamLimits,
+}
+
+impl Streams {
+    fn new(max_in_flight_requests: Option<NonZeroUsize>) -> Self {
+        Self {
+            max_in_flight_requests,
+            limits: Default::default(),
+        }
+    }
+
+    /// Waits until the given stream has capacity for another in-flight request. The returned
+    /// permit must be held until the request has been fully processed.
+    async fn acquire(&self, stream_id: &str) -> Option<StreamPermit> {
+        let max = self.max_in_flight_requests?;
+        let semaphore = Arc::clone(
+            self.limits
+                .lock()
+                .expect("poisoned lock")
+                .entry(stream_id.to_owned())
+                .or_insert_with(|| Arc::new(Semaphore::new(max.get()))),
+        );
+        let permit = Arc::clone(&semaphore).acquire_owned().await.ok()?;
+        Some(StreamPermit {
+            stream_id: stream_id.to_owned(),
+            semaphore,
+            permit: Some(permit),
+            limits: Arc::clone(&self.limits),
+        })
+    }
+}
+
+/// A request in flight for a stream, which stops tracking the stream once it has no other
+/// requests in flight or waiting.
+struct StreamPermit {
+    stream_id: String,
+    semaphore: Arc<Semaphore>,
+    permit: Option<OwnedSemaphorePermit>,
+    limits: StreamLimits,
+}
+
+impl Drop for StreamPermit {
+    fn drop(&mut self) {
+        drop(self.permit.take());
+
+        // Requests clone the semaphore of their stream with the lock held, so with it held, the
+        // semaphore is only shared with the map when no other request is in flight or waiting.
+        let mut limits = self.limits.lock().expect("poisoned lock");
+        if Arc::strong_count(&self.semaphore) == 2 {
+            limits.remove(&self.stream_id);
+        }
+    }
+}
+
+impl Service {
+    async fn push(&self, stream_id: String, mut events: Vec<Event>) -> Result<(), Status> {
+        let _permit = self.streams.acquire(&stream_id).await;
 
         let count = events.len();
         let byte_size = events.size_of();
 
         emit!(EventsReceived { count, byte_size });
+        if !stream_id.is_empty() {
+            emit!(VectorStreamEventsReceived {
+                stream_id: &stream_id,
+                count,
+                byte_size
+            });
+        }
 
         let receiver = BatchNotifier::maybe_apply_to(self.acknowledgements, &mut events);
 
@@ -57,7 +133,30 @@ impl proto::Service for Service {
                 Status::unavailable(message)
             })
             .and_then(|_| handle_batch_status(receiver))
-            .await?;
+            .await
+    }
+}
+
+#[tonic::async_trait]
+impl proto::Service for Service {
+    async fn push_events(
+        &self,
+        request: Request<proto::PushEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let request = request.into_inner();
+        let events = convert_events(request.events)?;
+        self.push(request.stream_id, events).await?;
+
+        Ok(Response::new(proto::PushEventsResponse {}))
+    }
+
+    async fn push_events_v2(
+        &self,
+        request: Request<proto::PushEventsV2Request>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let request = request.into_inner();
+        let events = convert_events(request.events)?;
+        self.push(request.stream_id, events).await?;
 
         Ok(Response::new(proto::PushEventsResponse {}))
     }
@@ -75,6 +174,24 @@ impl proto::Service for Service {
     }
 }
 
+/// Converts the events of a request, rejecting the whole request if any of them is invalid.
+fn convert_events<T>(events: Vec<T>) -> Result<Vec<Event>, Status>
+where
+    T: TryInto<Event, Error = ConversionError>,
+{
+    events
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<_, _>>()
+        .map_err(|error| {
+            let message = error.to_string();
+            emit!(VectorProtoDecodeError {
+                error: &crate::Error::from(error)
+            });
+            Status::invalid_argument(message)
+        })
+}
+
 async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
     let status = match receiver {
         Some(receiver) => receiver.await,
@@ -102,6 +219,19 @@ pub struct VectorConfig {
     #[serde(default = "default_shutdown_timeout_secs")]
     pub shutdown_timeout_secs: u64,
 
+    /// The maximum number of requests from a single upstream stream that are processed
+    /// concurrently.
+    ///
+    /// Upstream `vector` sinks identify their stream with their component ID, so this limit lets
+    /// a slow or noisy upstream be backpressured without affecting the others. Must be greater
+    /// than zero. If not set, requests are not limited per stream.
+    #[serde(default)]
+    pub max_in_flight_requests_per_stream: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    http2: Http2ServerConfig,
+
     #[configurable(derived)]
     #[serde(default)]
     tls: Option<TlsEnableableConfig>,
@@ -120,6 +250,8 @@ impl GenerateConfig for VectorConfig {
         toml::Value::try_from(Self {
             address: "0.0.0.0:6000".parse().unwrap(),
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            max_in_flight_requests_per_stream: None,
+            http2: Http2ServerConfig::default(),
             tls: None,
             acknowledgements: Default::default(),
         })
@@ -129,18 +261,33 @@ impl GenerateConfig for VectorConfig {
 
 impl VectorConfig {
     pub(super) async fn build(&self, cx: SourceContext) -> crate::Result<Source> {
+        let max_in_flight_requests_per_stream = match self.max_in_flight_requests_per_stream {
+            Some(max) => Some(
+                NonZeroUsize::new(max)
+                    .ok_or("`max_in_flight_requests_per_stream` must be greater than zero")?,
+            ),
+            None => None,
+        };
         let tls_settings = MaybeTlsSettings::from_config(&self.tls, true)?;
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
         let service = proto::Server::new(Service {
             pipeline: cx.out,
             acknowledgements,
+            streams: Streams::new(max_in_flight_requests_per_stream),
         })
         .accept_gzip();
 
-        let source =
-            run_grpc_server(self.address, tls_settings, service, cx.shutdown).map_err(|error| {
-                error!(message = "Source future failed.", %error);
-            });
+        let source = run_grpc_server(
+            self.address,
+            tls_settings,
+            self.http2,
+            service,
+            cx.shutdown,
+            cx.ready.defer(),
+        )
+        .map_err(|error| {
+            error!(message = "Source future failed.", %error);
+        });
 
         Ok(Box::pin(source))
     }
@@ -231,6 +378,93 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn receive_message_with_http2_keepalive() {
+        assert_source_compliance(&SOURCE_TAGS, async {
+            let addr = test_util::next_addr();
+            let config = format!(
+                r#"
+                address = "{}"
+                http2.keepalive_interval_secs = 1
+                http2.keepalive_timeout_secs = 5
+                http2.max_concurrent_streams = 10
+                "#,
+                addr
+            );
+            let source: VectorConfig = toml::from_str(&config).unwrap();
+
+            let (tx, rx) = SourceSender::new_test();
+            let server = source
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .unwrap();
+            tokio::spawn(server);
+            test_util::wait_for_tcp(addr).await;
+
+            let config = format!(
+                r#"
+                address = "{}"
+                http2.keepalive_interval_secs = 1
+                http2.keepalive_timeout_secs = 5
+                http2.keepalive_while_idle = true
+                "#,
+                addr
+            );
+            let sink: SinkConfig = toml::from_str(&config).unwrap();
+            let cx = SinkContext::new_test();
+            let (sink, _) = sink.build(cx).await.unwrap();
+
+            let (events, stream) = test_util::random_events_with_stream(100, 100, None);
+            sink.run(stream).await.unwrap();
+
+            let output = test_util::collect_ready(rx).await;
+            assert_event_data_eq!(events, output);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn limits_in_flight_requests_per_stream() {
+        let streams = Streams::new(NonZeroUsize::new(1));
+
+        let first = streams.acquire("upstream_a").await;
+        assert!(first.is_some());
+
+        // A different stream isn't affected by the first one being at its limit.
+        assert!(streams.acquire("upstream_b").await.is_some());
+
+        let blocked = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            streams.acquire("upstream_a"),
+        )
+        .await;
+        assert!(blocked.is_err());
+
+        drop(first);
+        assert!(streams.acquire("upstream_a").await.is_some());
+    }
+
+    #[tokio::test]
+    async fn forgets_idle_streams() {
+        let streams = Streams::new(NonZeroUsize::new(2));
+
+        let first = streams.acquire("upstream_a").await;
+        let second = streams.acquire("upstream_a").await;
+        drop(first);
+        assert_eq!(streams.limits.lock().unwrap().len(), 1);
+
+        drop(second);
+        assert!(streams.limits.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unlimited_streams() {
+        let streams = Streams::new(None);
+
+        assert!(streams.acquire("upstream_a").await.is_none());
+        assert!(streams.limits.lock().unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn receive_compressed_message() {
         assert_source_compliance(&SOURCE_TAGS, async {
@@ -0,0 +1,327 @@
+use std::{path::PathBuf, time::Duration};
+
+use snafu::Snafu;
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    checkpoint::{self, SharedCheckpointStore},
+    config::{DataType, Output, SourceConfig, SourceContext, SourceDescription},
+    internal_events::{
+        BytesReceived, OldEventsReceived, StreamClosedError, WindowsEventLogCheckpointError,
+        WindowsEventLogParseError, WindowsEventLogReadError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod parser;
+mod subscription;
+
+use subscription::Subscription;
+
+const CHECKPOINT_FILENAME: &str = "checkpoint.xml";
+const DEFAULT_BATCH_SIZE: usize = 100;
+/// How long to wait for new events before checking for shutdown.
+const WAIT_TIMEOUT: Duration = Duration::from_millis(500);
+const BACKOFF_DURATION: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one channel must be specified"))]
+    NoChannels,
+    #[snafu(display("Could not read checkpoint: {}", source))]
+    ReadCheckpoint { source: crate::Error },
+    #[snafu(display("Checkpoint is not a valid bookmark: {}", source))]
+    InvalidCheckpoint { source: std::string::FromUtf8Error },
+}
+
+/// Configuration for the `windows_event_log` source.
+#[configurable_component(source)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct WindowsEventLogConfig {
+    /// The event log channels to subscribe to, such as `Application`, `System` or `Security`.
+    pub channels: Vec<String>,
+
+    /// An XPath query selecting the events to read from each channel.
+    ///
+    /// For example, `*[System[(Level=1 or Level=2)]]` only selects critical and error events.
+    pub query: String,
+
+    /// Whether to read the events already present in the channels when no checkpoint exists.
+    ///
+    /// By default, only events published after Vector starts are read.
+    pub read_existing_events: bool,
+
+    /// Whether to render the event message from the message templates of the publisher.
+    ///
+    /// Rendering requires the publisher to be installed on the host, and events whose message
+    /// can't be rendered are emitted without one.
+    pub render_message: bool,
+
+    /// The maximum number of events read from the subscription at once. A checkpoint is set
+    /// after each batch.
+    pub batch_size: usize,
+
+    /// The directory used to persist the subscription bookmark.
+    ///
+    /// By default, the global `data_dir` option is used. Please make sure the user Vector is running as has write permissions to this directory.
+    pub data_dir: Option<PathBuf>,
+}
+
+impl Default for WindowsEventLogConfig {
+    fn default() -> Self {
+        Self {
+            channels: vec!["Application".into(), "System".into()],
+            query: "*".into(),
+            read_existing_events: false,
+            render_message: true,
+            batch_size: DEFAULT_BATCH_SIZE,
+            data_dir: None,
+        }
+    }
+}
+
+impl WindowsEventLogConfig {
+    /// Builds the structured query selecting the configured channels, as accepted by
+    /// `EvtSubscribe`.
+    fn structured_query(&self) -> String {
+        let query = escape_xml(&self.query);
+        let selects = self
+            .channels
+            .iter()
+            .map(|channel| {
+                format!(
+                    r#"<Select Path="{}">{}</Select>"#,
+                    escape_xml(channel),
+                    query
+                )
+            })
+            .collect::<String>();
+        format!(
+            r#"<QueryList><Query Id="0">{}</Query></QueryList>"#,
+            selects
+        )
+    }
+}
+
+inventory::submit! {
+    SourceDescription::new::<WindowsEventLogConfig>("windows_event_log")
+}
+
+impl_generate_config_from_default!(WindowsEventLogConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "windows_event_log")]
+impl SourceConfig for WindowsEventLogConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.channels.is_empty() {
+            return Err(BuildError::NoChannels.into());
+        }
+
+        let checkpoints =
+            checkpoint::build_store(&cx.globals, self.data_dir.as_ref(), &cx.key).await?;
+
+        let bookmark = checkpoints
+            .load(CHECKPOINT_FILENAME)
+            .await
+            .map_err(|source| BuildError::ReadCheckpoint { source })?
+            .map(String::from_utf8)
+            .transpose()
+            .map_err(|source| BuildError::InvalidCheckpoint { source })?;
+
+        Ok(Box::pin(
+            WindowsEventLogSource {
+                query: self.structured_query(),
+                read_existing_events: self.read_existing_events,
+                render_message: self.render_message,
+                batch_size: self.batch_size.max(1),
+                checkpoints,
+                bookmark,
+                out: cx.out,
+            }
+            .run(cx.shutdown),
+        ))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "windows_event_log"
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+struct WindowsEventLogSource {
+    query: String,
+    read_existing_events: bool,
+    render_message: bool,
+    batch_size: usize,
+    checkpoints: SharedCheckpointStore,
+    /// The bookmark of the last event sent, as XML.
+    bookmark: Option<String>,
+    out: SourceSender,
+}
+
+impl WindowsEventLogSource {
+    async fn run(mut self, mut shutdown: ShutdownSignal) -> Result<(), ()> {
+        loop {
+            let subscription = match Subscription::new(
+                &self.query,
+                self.bookmark.as_deref(),
+                self.read_existing_events,
+                self.render_message,
+            ) {
+                Ok(subscription) => subscription,
+                Err(error) => {
+                    emit!(WindowsEventLogReadError { error });
+                    tokio::select! {
+                        _ = &mut shutdown => return Ok(()),
+                        _ = tokio::time::sleep(BACKOFF_DURATION) => continue,
+                    }
+                }
+            };
+
+            info!(message = "Subscribed to Windows event log.");
+            match self.run_subscription(subscription, &mut shutdown).await {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(()) => return Err(()),
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => return Ok(()),
+                _ = tokio::time::sleep(BACKOFF_DURATION) => {},
+            }
+        }
+    }
+
+    /// Reads batches of events until shutdown or an error occurs. Returns `Ok(true)` if the
+    /// subscription should be recreated.
+    async fn run_subscription(
+        &mut self,
+        mut subscription: Subscription,
+        shutdown: &mut ShutdownSignal,
+    ) -> Result<bool, ()> {
+        let batch_size = self.batch_size;
+        loop {
+            // The Windows APIs block, so each batch is read on the blocking thread pool. The
+            // wait is bounded so that the subscription is released shortly after shutdown.
+            let read = tokio::task::spawn_blocking(move || {
+                let result = subscription.next_batch(batch_size, WAIT_TIMEOUT);
+                let bookmark = result
+                    .as_ref()
+                    .ok()
+                    .filter(|events| !events.is_empty())
+                    .map(|_| subscription.bookmark());
+                (subscription, result, bookmark)
+            });
+
+            let (returned, result, bookmark) = tokio::select! {
+                _ = &mut *shutdown => return Ok(false),
+                read = read => read.expect("Reading Windows events panicked."),
+            };
+            subscription = returned;
+
+            let raw_events = match result {
+                Ok(raw_events) => raw_events,
+                Err(error) => {
+                    emit!(WindowsEventLogReadError { error });
+                    return Ok(true);
+                }
+            };
+            if raw_events.is_empty() {
+                continue;
+            }
+
+            let mut byte_size = 0;
+            let events = raw_events
+                .into_iter()
+                .filter_map(|raw| {
+                    byte_size += raw.xml.len();
+                    parser::parse_event(&raw.xml, raw.message)
+                        .map_err(|error| emit!(WindowsEventLogParseError { error }))
+                        .ok()
+                })
+                .collect::<Vec<_>>();
+
+            emit!(BytesReceived {
+                byte_size,
+                protocol: "windows_event_log",
+            });
+            emit!(OldEventsReceived {
+                count: events.len(),
+                byte_size: events.size_of(),
+            });
+
+            let count = events.len();
+            if let Err(error) = self.out.send_batch(events).await {
+                emit!(StreamClosedError { error, count });
+                return Err(());
+            }
+
+            match bookmark {
+                Some(Ok(bookmark)) => {
+                    if let Err(error) = self
+                        .checkpoints
+                        .save(CHECKPOINT_FILENAME, bookmark.as_bytes())
+                        .await
+                    {
+                        emit!(WindowsEventLogCheckpointError { error });
+                    }
+                    self.bookmark = Some(bookmark);
+                }
+                Some(Err(error)) => emit!(WindowsEventLogReadError { error }),
+                None => {}
+            }
+        }
+    }
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowsEventLogConfig>();
+    }
+
+    #[test]
+    fn builds_structured_query() {
+        let config = WindowsEventLogConfig {
+            channels: vec![
+                "System".into(),
+                "Microsoft-Windows-Sysmon/Operational".into(),
+            ],
+            query: "*[System[(Level<=3)]]".into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.structured_query(),
+            concat!(
+                r#"<QueryList><Query Id="0">"#,
+                r#"<Select Path="System">*[System[(Level&lt;=3)]]</Select>"#,
+                r#"<Select Path="Microsoft-Windows-Sysmon/Operational">*[System[(Level&lt;=3)]]</Select>"#,
+                r#"</Query></QueryList>"#,
+            )
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use roxmltree::{Document, Node};
+
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+/// Returns the name of the provider that published the event, used to look up the message
+/// templates when rendering the event message.
+pub(super) fn provider_name(xml: &str) -> Option<String> {
+    let document = Document::parse(xml).ok()?;
+    document
+        .descendants()
+        .find(|node| node.has_tag_name("Provider"))
+        .and_then(|node| node.attribute("Name"))
+        .map(Into::into)
+}
+
+/// Builds a log event from the XML rendering of a Windows event, as produced by `EvtRender`.
+///
+/// The `System` section is flattened into top-level fields, while the `EventData` and `UserData`
+/// sections are kept as nested objects since their layout depends on the provider.
+pub(super) fn parse_event(xml: &str, message: Option<String>) -> Result<LogEvent, String> {
+    let document = Document::parse(xml).map_err(|error| error.to_string())?;
+    let root = document.root_element();
+    if !root.has_tag_name("Event") {
+        return Err(format!(
+            "Unexpected root element {:?}.",
+            root.tag_name().name()
+        ));
+    }
+
+    let mut log = LogEvent::default();
+
+    for section in root.children().filter(Node::is_element) {
+        match section.tag_name().name() {
+            "System" => parse_system(section, &mut log),
+            "EventData" => {
+                log.insert("event_data", parse_event_data(section));
+            }
+            "UserData" => {
+                if let Some(data) = section.children().find(Node::is_element) {
+                    log.insert("user_data", parse_element(data));
+                }
+            }
+            "RenderingInfo" => {
+                if message.is_none() {
+                    if let Some(text) = child_text(section, "Message") {
+                        log.insert(log_schema().message_key(), text);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(message) = message {
+        log.insert(log_schema().message_key(), message);
+    }
+    log.try_insert(
+        log_schema().source_type_key(),
+        Bytes::from("windows_event_log"),
+    );
+
+    Ok(log)
+}
+
+fn parse_system(system: Node, log: &mut LogEvent) {
+    for node in system.children().filter(Node::is_element) {
+        match node.tag_name().name() {
+            "Provider" => {
+                if let Some(name) = node.attribute("Name") {
+                    log.insert("provider_name", name);
+                }
+                if let Some(guid) = node.attribute("Guid") {
+                    log.insert("provider_guid", guid);
+                }
+            }
+            "EventID" => {
+                insert_integer(log, "event_id", node.text());
+                insert_integer(log, "qualifiers", node.attribute("Qualifiers"));
+            }
+            "Version" => insert_integer(log, "version", node.text()),
+            "Level" => insert_integer(log, "level", node.text()),
+            "Task" => insert_integer(log, "task", node.text()),
+            "Opcode" => insert_integer(log, "opcode", node.text()),
+            "Keywords" => {
+                if let Some(keywords) = node.text() {
+                    log.insert("keywords", keywords);
+                }
+            }
+            "TimeCreated" => {
+                if let Some(timestamp) = node
+                    .attribute("SystemTime")
+                    .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                {
+                    log.insert(log_schema().timestamp_key(), timestamp.with_timezone(&Utc));
+                }
+            }
+            "EventRecordID" => insert_integer(log, "record_id", node.text()),
+            "Correlation" => {
+                if let Some(activity_id) = node.attribute("ActivityID") {
+                    log.insert("activity_id", activity_id);
+                }
+                if let Some(related_activity_id) = node.attribute("RelatedActivityID") {
+                    log.insert("related_activity_id", related_activity_id);
+                }
+            }
+            "Execution" => {
+                insert_integer(log, "process_id", node.attribute("ProcessID"));
+                insert_integer(log, "thread_id", node.attribute("ThreadID"));
+            }
+            "Channel" => {
+                if let Some(channel) = node.text() {
+                    log.insert("channel", channel);
+                }
+            }
+            "Computer" => {
+                if let Some(computer) = node.text() {
+                    log.insert(log_schema().host_key(), computer);
+                }
+            }
+            "Security" => {
+                if let Some(user_id) = node.attribute("UserID") {
+                    log.insert("user_id", user_id);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Event data is a list of `Data` elements, which are named for events described by a manifest
+/// and anonymous for classic event sources. Named data is decoded into an object, anything else
+/// into an array of strings.
+fn parse_event_data(event_data: Node) -> Value {
+    let data = event_data
+        .children()
+        .filter(|node| node.has_tag_name("Data"))
+        .collect::<Vec<_>>();
+
+    if !data.is_empty() && data.iter().all(|node| node.attribute("Name").is_some()) {
+        Value::Object(
+            data.into_iter()
+                .map(|node| {
+                    let name = node.attribute("Name").expect("already validated");
+                    (
+                        name.to_owned(),
+                        Value::from(node.text().unwrap_or_default()),
+                    )
+                })
+                .collect(),
+        )
+    } else {
+        Value::Array(
+            data.into_iter()
+                .map(|node| Value::from(node.text().unwrap_or_default()))
+                .collect(),
+        )
+    }
+}
+
+/// Converts an arbitrary element into a value. Elements with child elements become objects keyed
+/// by the child tag names, and leaf elements become their text.
+fn parse_element(element: Node) -> Value {
+    let children = element
+        .children()
+        .filter(Node::is_element)
+        .collect::<Vec<_>>();
+    if children.is_empty() {
+        return Value::from(element.text().unwrap_or_default().trim());
+    }
+
+    Value::Object(
+        children
+            .into_iter()
+            .map(|child| (child.tag_name().name().to_owned(), parse_element(child)))
+            .collect(),
+    )
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.children()
+        .find(|child| child.has_tag_name(name))
+        .and_then(|child| child.text())
+}
+
+fn insert_integer(log: &mut LogEvent, key: &str, text: Option<&str>) {
+    if let Some(value) = text.and_then(|text| text.trim().parse::<i64>().ok()) {
+        log.insert(key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const EVENT: &str = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System>
+    <Provider Name='Microsoft-Windows-Security-Auditing' Guid='{54849625-5478-4994-A5BA-3E3B0328C30D}'/>
+    <EventID>4624</EventID>
+    <Version>2</Version>
+    <Level>0</Level>
+    <Task>12544</Task>
+    <Opcode>0</Opcode>
+    <Keywords>0x8020000000000000</Keywords>
+    <TimeCreated SystemTime='2022-08-01T10:00:00.1234567Z'/>
+    <EventRecordID>12345</EventRecordID>
+    <Correlation ActivityID='{4E1E4B1A-7E54-0000-4D4B-1E4E547ED801}'/>
+    <Execution ProcessID='636' ThreadID='700'/>
+    <Channel>Security</Channel>
+    <Computer>host.example.com</Computer>
+    <Security/>
+  </System>
+  <EventData>
+    <Data Name='SubjectUserSid'>S-1-5-18</Data>
+    <Data Name='TargetUserName'>Administrator</Data>
+    <Data Name='LogonType'>2</Data>
+  </EventData>
+</Event>"#;
+
+    #[test]
+    fn parses_system_and_named_event_data() {
+        let log =
+            parse_event(EVENT, Some("An account was successfully logged on.".into())).unwrap();
+
+        assert_eq!(
+            log["provider_name"],
+            "Microsoft-Windows-Security-Auditing".into()
+        );
+        assert_eq!(log["event_id"], 4624.into());
+        assert_eq!(log["level"], 0.into());
+        assert_eq!(log["task"], 12544.into());
+        assert_eq!(log["keywords"], "0x8020000000000000".into());
+        assert_eq!(log["record_id"], 12345.into());
+        assert_eq!(log["process_id"], 636.into());
+        assert_eq!(log["thread_id"], 700.into());
+        assert_eq!(log["channel"], "Security".into());
+        assert_eq!(log[log_schema().host_key()], "host.example.com".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Value::Timestamp(Utc.ymd(2022, 8, 1).and_hms_nano(10, 0, 0, 123_456_700))
+        );
+        assert_eq!(
+            log[log_schema().message_key()],
+            "An account was successfully logged on.".into()
+        );
+        assert_eq!(log["event_data.TargetUserName"], "Administrator".into());
+        assert_eq!(log["event_data.LogonType"], "2".into());
+        assert!(!log.contains("user_id"));
+    }
+
+    #[test]
+    fn parses_anonymous_event_data_and_user_data() {
+        let xml = r#"<Event xmlns='http://schemas.microsoft.com/win/2004/08/events/event'>
+  <System>
+    <Provider Name='Application Error'/>
+    <EventID Qualifiers='0'>1000</EventID>
+    <Security UserID='S-1-5-21-1'/>
+  </System>
+  <EventData>
+    <Data>app.exe</Data>
+    <Data>1.0.0.0</Data>
+  </EventData>
+  <UserData>
+    <LogFileCleared xmlns='http://manifests.microsoft.com/win/2004/08/windows/eventlog'>
+      <SubjectUserName>admin</SubjectUserName>
+      <SubjectDomainName>CORP</SubjectDomainName>
+    </LogFileCleared>
+  </UserData>
+</Event>"#;
+
+        let log = parse_event(xml, None).unwrap();
+
+        assert_eq!(log["qualifiers"], 0.into());
+        assert_eq!(log["user_id"], "S-1-5-21-1".into());
+        assert_eq!(
+            log["event_data"],
+            Value::Array(vec!["app.exe".into(), "1.0.0.0".into()])
+        );
+        assert_eq!(log["user_data.SubjectUserName"], "admin".into());
+        assert!(!log.contains(log_schema().message_key()));
+    }
+
+    #[test]
+    fn finds_provider_name() {
+        assert_eq!(
+            provider_name(EVENT).as_deref(),
+            Some("Microsoft-Windows-Security-Auditing")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_xml() {
+        assert!(parse_event("<Event>", None).is_err());
+        assert!(parse_event("<Other/>", None).is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std::{collections::HashMap, io, ptr, time::Duration};
+
+use windows_sys::Win32::{
+    Foundation::{
+        CloseHandle, GetLastError, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, HANDLE,
+        WAIT_OBJECT_0,
+    },
+    System::{
+        EventLog::{
+            EvtClose, EvtCreateBookmark, EvtFormatMessage, EvtFormatMessageEvent, EvtNext,
+            EvtOpenPublisherMetadata, EvtRender, EvtRenderBookmark, EvtRenderEventXml,
+            EvtSubscribe, EvtSubscribeStartAfterBookmark, EvtSubscribeStartAtOldestRecord,
+            EvtSubscribeToFutureEvents, EvtUpdateBookmark,
+        },
+        Threading::{CreateEventW, ResetEvent, WaitForSingleObject},
+    },
+};
+
+use super::parser::provider_name;
+use crate::internal_events::WindowsEventLogRenderError;
+
+/// An event read from the subscription, rendered as XML along with its formatted message.
+pub(super) struct RawEvent {
+    pub(super) xml: String,
+    pub(super) message: Option<String>,
+}
+
+/// Owns an `EVT_HANDLE` and closes it on drop.
+struct EvtHandle(isize);
+
+impl Drop for EvtHandle {
+    fn drop(&mut self) {
+        if self.0 != 0 {
+            unsafe { EvtClose(self.0) };
+        }
+    }
+}
+
+/// A pull subscription to one or more event log channels, created with `EvtSubscribe`.
+///
+/// The subscription tracks the position of the last event returned in a bookmark, which is
+/// rendered as XML to checkpoint the position between restarts.
+pub(super) struct Subscription {
+    handle: EvtHandle,
+    signal: HANDLE,
+    bookmark: EvtHandle,
+    render_message: bool,
+    publishers: HashMap<String, Option<EvtHandle>>,
+}
+
+// All handles are only ever used from one thread at a time.
+unsafe impl Send for Subscription {}
+
+impl Subscription {
+    pub(super) fn new(
+        query: &str,
+        bookmark: Option<&str>,
+        read_existing_events: bool,
+        render_message: bool,
+    ) -> io::Result<Self> {
+        let signal = unsafe { CreateEventW(ptr::null(), 1, 1, ptr::null()) };
+        if signal == 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let bookmark_xml = bookmark.map(to_wide);
+        let bookmark = EvtHandle(unsafe {
+            EvtCreateBookmark(
+                bookmark_xml
+                    .as_ref()
+                    .map_or(ptr::null(), |xml| xml.as_ptr()),
+            )
+        });
+        if bookmark.0 == 0 {
+            let error = io::Error::last_os_error();
+            unsafe { CloseHandle(signal) };
+            return Err(error);
+        }
+
+        let flags = if bookmark_xml.is_some() {
+            EvtSubscribeStartAfterBookmark
+        } else if read_existing_events {
+            EvtSubscribeStartAtOldestRecord
+        } else {
+            EvtSubscribeToFutureEvents
+        };
+        let query = to_wide(query);
+        let handle = EvtHandle(unsafe {
+            EvtSubscribe(
+                0,
+                signal,
+                ptr::null(),
+                query.as_ptr(),
+                if bookmark_xml.is_some() {
+                    bookmark.0
+                } else {
+                    0
+                },
+                ptr::null(),
+                None,
+                flags as u32,
+            )
+        });
+        if handle.0 == 0 {
+            let error = io::Error::last_os_error();
+            unsafe { CloseHandle(signal) };
+            return Err(error);
+        }
+
+        Ok(Self {
+            handle,
+            signal,
+            bookmark,
+            render_message,
+            publishers: HashMap::new(),
+        })
+    }
+
+    /// Waits up to `timeout` for new events, and returns at most `max` of them. The bookmark is
+    /// advanced past the returned events.
+    pub(super) fn next_batch(
+        &mut self,
+        max: usize,
+        timeout: Duration,
+    ) -> io::Result<Vec<RawEvent>> {
+        let wait = unsafe { WaitForSingleObject(self.signal, timeout.as_millis() as u32) };
+        if wait != WAIT_OBJECT_0 {
+            return Ok(Vec::new());
+        }
+
+        let mut handles = vec![0isize; max];
+        let mut returned = 0u32;
+        let success = unsafe {
+            EvtNext(
+                self.handle.0,
+                max as u32,
+                handles.as_mut_ptr(),
+                0,
+                0,
+                &mut returned,
+            )
+        };
+        if success == 0 {
+            let error = unsafe { GetLastError() };
+            if error == ERROR_NO_MORE_ITEMS {
+                // The signal is only set again once new events arrive.
+                unsafe { ResetEvent(self.signal) };
+                return Ok(Vec::new());
+            }
+            return Err(io::Error::from_raw_os_error(error as i32));
+        }
+
+        let handles = handles
+            .into_iter()
+            .take(returned as usize)
+            .map(EvtHandle)
+            .collect::<Vec<_>>();
+
+        let mut events = Vec::with_capacity(handles.len());
+        for event in &handles {
+            // `EvtNext` has already moved past the event, so it's skipped rather than failing the
+            // whole batch, which would only be read again from the bookmark and fail the same way.
+            let xml = match render(event.0, EvtRenderEventXml as u32) {
+                Ok(xml) => xml,
+                Err(error) => {
+                    emit!(WindowsEventLogRenderError { error });
+                    continue;
+                }
+            };
+            let message = if self.render_message {
+                self.format_message(event, &xml)
+            } else {
+                None
+            };
+            events.push(RawEvent { xml, message });
+        }
+
+        if let Some(last) = handles.last() {
+            if unsafe { EvtUpdateBookmark(self.bookmark.0, last.0) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Renders the bookmark as XML, which can be passed back to `Subscription::new` to resume
+    /// after the last returned event.
+    pub(super) fn bookmark(&self) -> io::Result<String> {
+        render(self.bookmark.0, EvtRenderBookmark as u32)
+    }
+
+    fn format_message(&mut self, event: &EvtHandle, xml: &str) -> Option<String> {
+        let provider = provider_name(xml)?;
+        let publisher = self
+            .publishers
+            .entry(provider)
+            .or_insert_with_key(|provider| {
+                let provider = to_wide(provider);
+                let handle =
+                    unsafe { EvtOpenPublisherMetadata(0, provider.as_ptr(), ptr::null(), 0, 0) };
+                (handle != 0).then(|| EvtHandle(handle))
+            })
+            .as_ref()?;
+
+        let mut used = 0u32;
+        unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event.0,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent as u32,
+                0,
+                ptr::null_mut(),
+                &mut used,
+            )
+        };
+        if unsafe { GetLastError() } != ERROR_INSUFFICIENT_BUFFER {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; used as usize];
+        let success = unsafe {
+            EvtFormatMessage(
+                publisher.0,
+                event.0,
+                0,
+                0,
+                ptr::null(),
+                EvtFormatMessageEvent as u32,
+                buffer.len() as u32,
+                buffer.as_mut_ptr(),
+                &mut used,
+            )
+        };
+        (success != 0).then(|| from_wide(&buffer))
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        unsafe { CloseHandle(self.signal) };
+    }
+}
+
+/// Renders an event or bookmark as XML.
+fn render(handle: isize, flags: u32) -> io::Result<String> {
+    let mut used = 0u32;
+    let mut property_count = 0u32;
+    let success = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            0,
+            ptr::null_mut(),
+            &mut used,
+            &mut property_count,
+        )
+    };
+    if success == 0 {
+        let error = unsafe { GetLastError() };
+        if error != ERROR_INSUFFICIENT_BUFFER {
+            return Err(io::Error::from_raw_os_error(error as i32));
+        }
+    }
+
+    // The buffer size is given in bytes.
+    let mut buffer = vec![0u16; (used as usize + 1) / 2];
+    let success = unsafe {
+        EvtRender(
+            0,
+            handle,
+            flags,
+            (buffer.len() * 2) as u32,
+            buffer.as_mut_ptr().cast(),
+            &mut used,
+            &mut property_count,
+        )
+    };
+    if success == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(from_wide(&buffer))
+}
+
+fn to_wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(Some(0)).collect()
+}
+
+fn from_wide(buffer: &[u16]) -> String {
+    let end = buffer.iter().position(|c| *c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..end])
+}
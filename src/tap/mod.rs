@@ -36,6 +36,14 @@ pub struct Opts {
     #[clap(use_value_delimiter(true), long)]
     inputs_of: Vec<String>,
 
+    /// VRL boolean expression that events must match to be observed, evaluated by the Vector API server
+    #[clap(long)]
+    filter: Option<String>,
+
+    /// Fields of log and trace events to observe (comma-separated); other fields are dropped by the Vector API server
+    #[clap(use_value_delimiter(true), long)]
+    fields: Vec<String>,
+
     /// Quiet output includes only events
     #[clap(short, long)]
     quiet: bool,
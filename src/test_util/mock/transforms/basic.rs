@@ -103,6 +103,7 @@ impl FunctionTransform for BasicTransform {
                     assert!(metric.add(&MetricData {
                         kind: metric.kind(),
                         timestamp: metric.timestamp(),
+                        interval_ms: metric.interval_ms(),
                         value: increment,
                     }));
                 }
@@ -42,32 +42,69 @@ impl TlsSettings {
 impl MaybeTlsSettings {
     pub(crate) async fn bind(&self, addr: &SocketAddr) -> crate::tls::Result<MaybeTlsListener> {
         let listener = TcpListener::bind(addr).await.context(TcpBindSnafu)?;
+        self.listen(listener)
+    }
 
-        let acceptor = match self {
-            Self::Tls(tls) => Some(tls.acceptor()?),
-            Self::Raw(()) => None,
+    /// Accepts connections on an already bound listener, such as one passed in by `systemd`.
+    pub(crate) fn listen(&self, listener: TcpListener) -> crate::tls::Result<MaybeTlsListener> {
+        let (acceptor, settings, generation) = match self {
+            Self::Tls(tls) => {
+                let generation = tls.certificate_generation();
+                let acceptor = tls.with_current_certificates().acceptor()?;
+                (Some(acceptor), Some(tls.clone()), generation)
+            }
+            Self::Raw(()) => (None, None, 0),
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            settings,
+            generation,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    settings: Option<TlsSettings>,
+    generation: u64,
 }
 
 impl MaybeTlsListener {
     pub(crate) async fn accept(&mut self) -> crate::tls::Result<MaybeTlsIncomingStream<TcpStream>> {
-        self.listener
-            .accept()
-            .await
+        let accepted = self.listener.accept().await;
+        self.reload_acceptor();
+
+        accepted
             .map(|(stream, peer_addr)| {
                 MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
             })
             .context(IncomingListenerSnafu)
     }
 
+    /// Rebuilds the acceptor if the certificates have been reloaded since it was built, so that
+    /// new connections are accepted with the new certificates.
+    fn reload_acceptor(&mut self) {
+        if let Some(settings) = &self.settings {
+            let generation = settings.certificate_generation();
+            if generation != self.generation {
+                match settings.with_current_certificates().acceptor() {
+                    Ok(acceptor) => {
+                        self.acceptor = Some(acceptor);
+                        self.generation = generation;
+                    }
+                    Err(error) => warn!(
+                        message = "Failed to build TLS acceptor with reloaded certificates.",
+                        %error,
+                        internal_log_rate_secs = 10,
+                    ),
+                }
+            }
+        }
+    }
+
     async fn into_accept(
         mut self,
     ) -> (crate::tls::Result<MaybeTlsIncomingStream<TcpStream>>, Self) {
@@ -137,6 +174,8 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            settings: None,
+            generation: 0,
         }
     }
 }
@@ -15,6 +15,7 @@ mod incoming;
 mod maybe_tls;
 mod outgoing;
 mod settings;
+mod spiffe;
 
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 pub(crate) use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
@@ -28,6 +29,7 @@ pub use settings::{
 pub use settings::{TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH};
 #[cfg(all(test, feature = "sources-socket"))]
 pub use settings::{TEST_PEM_CLIENT_CRT_PATH, TEST_PEM_CLIENT_KEY_PATH};
+pub use spiffe::SpiffeConfig;
 
 pub type Result<T> = std::result::Result<T, TlsError>;
 
@@ -126,6 +128,16 @@ pub enum TlsError {
     NewCaStack { source: ErrorStack },
     #[snafu(display("Could not push intermediate certificate onto stack"))]
     CaStackPush { source: ErrorStack },
+    #[snafu(display("TLS `spiffe` cannot be combined with `ca_file`, `crt_file`, or `key_file`"))]
+    SpiffeWithFiles,
+    #[snafu(display(
+        "TLS `spiffe.socket_path` is not set and the SPIFFE_ENDPOINT_SOCKET environment variable is missing"
+    ))]
+    MissingSpiffeSocket,
+    #[snafu(display("SPIFFE support is not available in this build"))]
+    SpiffeUnsupported,
+    #[snafu(display("Could not fetch X.509 SVID from the SPIFFE Workload API: {}", message))]
+    SpiffeWorkloadApi { message: String },
 }
 
 impl MaybeTlsStream<TcpStream> {
@@ -173,7 +185,10 @@ impl MaybeTlsStream<TcpStream> {
 pub(crate) fn tls_connector_builder(settings: &MaybeTlsSettings) -> Result<SslConnectorBuilder> {
     let mut builder = SslConnector::builder(SslMethod::tls()).context(TlsBuildConnectorSnafu)?;
     if let Some(settings) = settings.tls() {
-        settings.apply_context(&mut builder)?;
+        // Pick up any certificates that have been reloaded since the settings were created.
+        settings
+            .with_current_certificates()
+            .apply_context(&mut builder)?;
     }
     Ok(builder)
 }
@@ -1,25 +1,32 @@
 use std::{
     fmt,
-    fs::File,
+    fs::{self, File},
     io::Read,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
 };
 
 use openssl::{
     pkcs12::{ParsedPkcs12, Pkcs12},
     pkey::{PKey, Private},
-    ssl::{ConnectConfiguration, SslContextBuilder, SslVerifyMode},
+    ssl::{ConnectConfiguration, SslContext, SslContextBuilder, SslMethod, SslRef, SslVerifyMode},
     stack::Stack,
-    x509::{store::X509StoreBuilder, X509},
+    x509::{
+        store::{X509Store, X509StoreBuilder},
+        X509,
+    },
 };
 use snafu::ResultExt;
 use vector_config::configurable_component;
 
+use super::spiffe::SpiffeConfig;
 use super::{
     AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu, NewStoreBuilderSnafu,
     ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result, SetCertificateSnafu,
-    SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError, TlsIdentitySnafu, X509ParseSnafu,
+    SetPrivateKeySnafu, SetVerifyCertSnafu, SslBuildSnafu, TlsError, TlsIdentitySnafu,
+    X509ParseSnafu,
 };
 
 const PEM_START_MARKER: &str = "-----BEGIN ";
@@ -120,6 +127,9 @@ pub struct TlsConfig {
     /// an inline string in PEM format.
     ///
     /// If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+    ///
+    /// Certificate, key, and CA files are watched for changes, and new connections use the reloaded
+    /// certificates without affecting established ones.
     #[serde(alias = "crt_path")]
     pub crt_file: Option<PathBuf>,
 
@@ -133,6 +143,9 @@ pub struct TlsConfig {
     ///
     /// This has no effect unless `key_file` is set.
     pub key_pass: Option<String>,
+
+    #[configurable(derived)]
+    pub spiffe: Option<SpiffeConfig>,
 }
 
 impl TlsConfig {
@@ -154,11 +167,193 @@ pub struct TlsSettings {
     pub(super) verify_hostname: bool,
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
+    watcher: Option<Arc<CertificateWatcher>>,
+}
+
+/// How often the certificate files are checked for changes.
+const CERTIFICATE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watches the certificate, key, and CA files referenced by a `TlsConfig`, reloading them when they
+/// change on disk so that short-lived certificates can be rotated without restarting. Certificates
+/// that don't come from files, such as SPIFFE SVIDs, are pushed into the watcher instead.
+///
+/// Reloaded certificates are only used for new connections, existing connections are unaffected.
+pub(super) struct CertificateWatcher {
+    options: TlsConfig,
+    state: Mutex<WatcherState>,
+}
+
+struct WatcherState {
+    last_checked: Instant,
+    modified: Vec<Option<SystemTime>>,
+    generation: u64,
+    identity: Option<IdentityStore>,
+    authorities: Vec<X509>,
+    /// The context holding the current certificates, along with their generation, to switch
+    /// connections to.
+    context: Option<(u64, SslContext)>,
+}
+
+impl CertificateWatcher {
+    /// Creates a watcher for the files in the given options, if there are any files to watch.
+    /// Certificates provided inline are never reloaded.
+    fn new(
+        options: &TlsConfig,
+        identity: &Option<IdentityStore>,
+        authorities: &[X509],
+    ) -> Option<Self> {
+        let modified = Self::modified_times(options);
+        modified.iter().any(Option::is_some).then(|| Self {
+            options: options.clone(),
+            state: Mutex::new(WatcherState {
+                last_checked: Instant::now(),
+                modified,
+                generation: 0,
+                identity: identity.clone(),
+                authorities: authorities.to_vec(),
+                context: None,
+            }),
+        })
+    }
+
+    /// Creates a watcher that doesn't watch any files, and is only updated through `update`.
+    #[cfg_attr(not(all(unix, feature = "tls-spiffe")), allow(dead_code))]
+    pub(super) fn unwatched() -> Self {
+        let options = TlsConfig::default();
+        Self {
+            state: Mutex::new(WatcherState {
+                last_checked: Instant::now(),
+                // No files are configured, so these never change and `check` never reloads.
+                modified: Self::modified_times(&options),
+                generation: 0,
+                identity: None,
+                authorities: Vec::new(),
+                context: None,
+            }),
+            options,
+        }
+    }
+
+    /// Replaces the current certificates.
+    #[cfg_attr(not(all(unix, feature = "tls-spiffe")), allow(dead_code))]
+    pub(super) fn update(&self, identity: IdentityStore, authorities: Vec<X509>) {
+        let mut state = self.state.lock().expect("poisoned lock");
+        // The very first certificates are the initial ones rather than a reload.
+        if state.identity.is_some() {
+            state.generation += 1;
+        }
+        state.identity = Some(identity);
+        state.authorities = authorities;
+    }
+
+    fn modified_times(options: &TlsConfig) -> Vec<Option<SystemTime>> {
+        [&options.ca_file, &options.crt_file, &options.key_file]
+            .iter()
+            .map(|path| {
+                path.as_ref()
+                    .and_then(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+            })
+            .collect()
+    }
+
+    /// Reloads the certificates if any of the files have changed since they were last loaded, and
+    /// returns the generation of the currently loaded certificates. The generation starts at zero
+    /// and is incremented on every successful reload.
+    fn check(&self) -> u64 {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if state.last_checked.elapsed() < CERTIFICATE_CHECK_INTERVAL {
+            return state.generation;
+        }
+        state.last_checked = Instant::now();
+
+        let modified = Self::modified_times(&self.options);
+        if modified == state.modified {
+            return state.generation;
+        }
+
+        // The certificate and key are frequently written separately, so if loading fails we
+        // keep using the previous certificates and try again on the next check.
+        match self
+            .options
+            .load_identity()
+            .and_then(|identity| Ok((identity, self.options.load_authorities()?)))
+        {
+            Ok((identity, authorities)) => {
+                info!(message = "Reloaded TLS certificates.");
+                state.modified = modified;
+                state.generation += 1;
+                state.identity = identity;
+                state.authorities = authorities;
+            }
+            Err(error) => {
+                warn!(
+                    message = "Failed to reload TLS certificates, continuing to use the previous ones.",
+                    %error,
+                    internal_log_rate_secs = 10,
+                );
+            }
+        }
+
+        state.generation
+    }
+
+    pub(super) fn current(&self) -> (Option<IdentityStore>, Vec<X509>) {
+        let state = self.state.lock().expect("poisoned lock");
+        (state.identity.clone(), state.authorities.clone())
+    }
+
+    /// Returns a context holding the current certificates, building it once per generation.
+    fn context(&self, settings: &TlsSettings) -> Result<SslContext> {
+        let mut state = self.state.lock().expect("poisoned lock");
+        if let Some((generation, context)) = &state.context {
+            if *generation == state.generation {
+                return Ok(context.clone());
+            }
+        }
+
+        let current = TlsSettings {
+            identity: state.identity.clone(),
+            authorities: state.authorities.clone(),
+            ..settings.clone()
+        };
+        let mut builder = SslContextBuilder::new(SslMethod::tls()).context(SslBuildSnafu)?;
+        current.apply_context(&mut builder)?;
+        let context = builder.build();
+        state.context = Some((state.generation, context.clone()));
+        Ok(context)
+    }
 }
 
 #[derive(Clone)]
 pub struct IdentityStore(Vec<u8>, String);
 
+impl IdentityStore {
+    /// Builds an identity from its certificate, private key, and any intermediate certificates.
+    pub(super) fn from_parts(
+        name: &str,
+        crt: &X509,
+        key: &PKey<Private>,
+        intermediates: impl IntoIterator<Item = X509>,
+    ) -> Result<Self> {
+        let mut ca_stack = Stack::new().context(NewCaStackSnafu)?;
+        for intermediate in intermediates {
+            ca_stack.push(intermediate).context(CaStackPushSnafu)?;
+        }
+
+        let mut builder = Pkcs12::builder();
+        builder.ca(ca_stack);
+        let pkcs12 = builder.build("", name, key, crt).context(Pkcs12Snafu)?;
+        let identity = pkcs12.to_der().context(DerExportSnafu)?;
+
+        // Build the resulting parsed PKCS#12 archive,
+        // but don't store it, as it cannot be cloned.
+        // This is just for error checking.
+        pkcs12.parse("").context(TlsIdentitySnafu)?;
+
+        Ok(Self(identity, "".into()))
+    }
+}
+
 impl TlsSettings {
     /// Generate a filled out settings struct from the given optional
     /// option set, interpreted as client options. If `options` is
@@ -182,14 +377,61 @@ impl TlsSettings {
             }
         }
 
+        let (authorities, identity, watcher) = match &options.spiffe {
+            Some(spiffe) => {
+                if options.ca_file.is_some()
+                    || options.crt_file.is_some()
+                    || options.key_file.is_some()
+                {
+                    return Err(TlsError::SpiffeWithFiles);
+                }
+
+                let watcher = spiffe.watch()?;
+                let (identity, authorities) = watcher.current();
+                (authorities, identity, Some(watcher))
+            }
+            None => {
+                let authorities = options.load_authorities()?;
+                let identity = options.load_identity()?;
+                let watcher =
+                    CertificateWatcher::new(options, &identity, &authorities).map(Arc::new);
+                (authorities, identity, watcher)
+            }
+        };
+
         Ok(Self {
             verify_certificate: options.verify_certificate.unwrap_or(!for_server),
             verify_hostname: options.verify_hostname.unwrap_or(!for_server),
-            authorities: options.load_authorities()?,
-            identity: options.load_identity()?,
+            authorities,
+            identity,
+            watcher,
         })
     }
 
+    /// Checks whether the certificate files have changed on disk, reloading them if so, and
+    /// returns the generation of the currently loaded certificates. Callers that build long-lived
+    /// TLS contexts can compare generations to find out when they need to rebuild them.
+    pub(super) fn certificate_generation(&self) -> u64 {
+        self.watcher.as_ref().map_or(0, |watcher| watcher.check())
+    }
+
+    /// Returns a copy of these settings using the current certificates, reloading them first if
+    /// their files have changed.
+    pub(super) fn with_current_certificates(&self) -> Self {
+        match &self.watcher {
+            Some(watcher) => {
+                watcher.check();
+                let (identity, authorities) = watcher.current();
+                Self {
+                    identity,
+                    authorities,
+                    ..self.clone()
+                }
+            }
+            None => self.clone(),
+        }
+    }
+
     fn identity(&self) -> Option<ParsedPkcs12> {
         // This data was test-built previously, so we can just use it
         // here and expect the results will not fail. This can all be
@@ -255,14 +497,8 @@ impl TlsSettings {
             }
         }
         if !self.authorities.is_empty() {
-            let mut store = X509StoreBuilder::new().context(NewStoreBuilderSnafu)?;
-            for authority in &self.authorities {
-                store
-                    .add_cert(authority.clone())
-                    .context(AddCertToStoreSnafu)?;
-            }
             context
-                .set_verify_cert_store(store.build())
+                .set_verify_cert_store(self.authority_store()?)
                 .context(SetVerifyCertSnafu)?;
         } else {
             debug!("Fetching system root certs.");
@@ -277,8 +513,51 @@ impl TlsSettings {
         Ok(())
     }
 
+    /// Applies the per-connection configuration.
+    ///
+    /// If the certificates have been reloaded since the connector was built, the new identity and
+    /// authorities are applied to the connection, replacing the ones from the connector.
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
+
+        if self.certificate_generation() > 0 {
+            if let Err(error) = self.apply_current_certificates(connection) {
+                warn!(
+                    message = "Failed to apply reloaded TLS certificates to connection.",
+                    %error,
+                    internal_log_rate_secs = 10,
+                );
+            }
+        }
+    }
+
+    fn apply_current_certificates(&self, ssl: &mut SslRef) -> Result<()> {
+        let watcher = match &self.watcher {
+            Some(watcher) => watcher,
+            None => return Ok(()),
+        };
+        let current = self.with_current_certificates();
+        if current.identity.is_some() {
+            // Intermediate certificates can't be added to a connection, so it is switched to a
+            // context holding the whole identity, chain included, instead.
+            ssl.set_ssl_context(&watcher.context(self)?)
+                .context(SetCertificateSnafu)?;
+        }
+        if !current.authorities.is_empty() {
+            ssl.set_verify_cert_store(current.authority_store()?)
+                .context(SetVerifyCertSnafu)?;
+        }
+        Ok(())
+    }
+
+    fn authority_store(&self) -> Result<X509Store> {
+        let mut store = X509StoreBuilder::new().context(NewStoreBuilderSnafu)?;
+        for authority in &self.authorities {
+            store
+                .add_cert(authority.clone())
+                .context(AddCertToStoreSnafu)?;
+        }
+        Ok(store.build())
     }
 }
 
@@ -330,22 +609,7 @@ impl TlsConfig {
                 let crt = crt_stack.next().ok_or(TlsError::MissingCertificate)?;
                 let key = load_key(key_file, &self.key_pass)?;
 
-                let mut ca_stack = Stack::new().context(NewCaStackSnafu)?;
-                for intermediate in crt_stack {
-                    ca_stack.push(intermediate).context(CaStackPushSnafu)?;
-                }
-
-                let mut builder = Pkcs12::builder();
-                builder.ca(ca_stack);
-                let pkcs12 = builder.build("", &name, &key, &crt).context(Pkcs12Snafu)?;
-                let identity = pkcs12.to_der().context(DerExportSnafu)?;
-
-                // Build the resulting parsed PKCS#12 archive,
-                // but don't store it, as it cannot be cloned.
-                // This is just for error checking.
-                pkcs12.parse("").context(TlsIdentitySnafu)?;
-
-                Ok(Some(IdentityStore(identity, "".into())))
+                IdentityStore::from_parts(&name, &crt, &key, crt_stack).map(Some)
             }
         }
     }
@@ -712,6 +976,66 @@ mod test {
         assert!(matches!(error, TlsError::MissingRequiredIdentity));
     }
 
+    #[test]
+    fn reloads_changed_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        let crt_file = dir.path().join("crt.pem");
+        let key_file = dir.path().join("key.pem");
+        fs::copy(TEST_PEM_CRT_PATH, &crt_file).unwrap();
+        fs::copy(TEST_PEM_KEY_PATH, &key_file).unwrap();
+
+        let options = TlsConfig {
+            crt_file: Some(crt_file.clone()),
+            key_file: Some(key_file.clone()),
+            ..Default::default()
+        };
+        let settings =
+            TlsSettings::from_options(&Some(options)).expect("Failed to load PEM certificate");
+        assert_eq!(settings.certificate_generation(), 0);
+
+        std::thread::sleep(CERTIFICATE_CHECK_INTERVAL);
+        fs::copy(
+            "tests/data/ca/intermediate_client/certs/localhost-chain.cert.pem",
+            &crt_file,
+        )
+        .unwrap();
+        fs::copy(
+            "tests/data/ca/intermediate_client/private/localhost.key.pem",
+            &key_file,
+        )
+        .unwrap();
+
+        assert_eq!(settings.certificate_generation(), 1);
+        let original = settings.identity().unwrap().cert.to_der().unwrap();
+        let reloaded = settings
+            .with_current_certificates()
+            .identity()
+            .unwrap()
+            .cert
+            .to_der()
+            .unwrap();
+        assert_ne!(original, reloaded);
+
+        // Connections are switched to a context holding the reloaded identity and its chain.
+        let watcher = settings.watcher.as_ref().unwrap();
+        assert!(watcher.context(&settings).is_ok());
+    }
+
+    #[test]
+    fn inline_certificates_are_not_watched() {
+        let crt = String::from_utf8(TEST_PEM_CRT_BYTES.to_vec()).unwrap();
+        let key = String::from_utf8(TEST_PEM_KEY_BYTES.to_vec()).unwrap();
+        let options = TlsConfig {
+            crt_file: Some(crt.into()),
+            key_file: Some(key.into()),
+            ..Default::default()
+        };
+        let settings =
+            TlsSettings::from_options(&Some(options)).expect("Failed to load PEM certificate");
+        assert!(settings.watcher.is_none());
+        assert_eq!(settings.certificate_generation(), 0);
+    }
+
     #[test]
     fn from_config_with_certificate() {
         let config = settings_from_config(Some(true), true, true, true);
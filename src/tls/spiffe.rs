@@ -0,0 +1,341 @@
+//! Support for obtaining TLS identities from the [SPIFFE Workload API][workload_api].
+//!
+//! The Workload API streams X.509 SVIDs to the workload, pushing a new one whenever the current
+//! one is rotated. The stream is consumed on a dedicated thread so that the certificates are
+//! available to the synchronous TLS setup, and each update is pushed into a
+//! [`CertificateWatcher`] which makes it available to new connections.
+//!
+//! [workload_api]: https://github.com/spiffe/spiffe/blob/main/standards/SPIFFE_Workload_API.md
+
+use std::{path::PathBuf, sync::Arc};
+
+use vector_config::configurable_component;
+
+use super::{settings::CertificateWatcher, Result, TlsError};
+
+/// The environment variable conventionally used to point workloads at the Workload API socket.
+const SPIFFE_ENDPOINT_SOCKET: &str = "SPIFFE_ENDPOINT_SOCKET";
+
+/// Options for obtaining the TLS identity from the SPIFFE Workload API.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct SpiffeConfig {
+    /// Path to the SPIFFE Workload API unix socket.
+    ///
+    /// If not set, the `SPIFFE_ENDPOINT_SOCKET` environment variable is used.
+    pub socket_path: Option<PathBuf>,
+
+    /// The SPIFFE ID of the SVID to use.
+    ///
+    /// If not set, the first SVID returned by the Workload API, which is the default identity of
+    /// the workload, is used.
+    pub spiffe_id: Option<String>,
+}
+
+impl SpiffeConfig {
+    fn socket_path(&self) -> Result<PathBuf> {
+        match &self.socket_path {
+            Some(path) => Ok(path.clone()),
+            None => std::env::var(SPIFFE_ENDPOINT_SOCKET)
+                .map(|address| address.strip_prefix("unix://").unwrap_or(&address).into())
+                .map_err(|_| TlsError::MissingSpiffeSocket),
+        }
+    }
+
+    /// Starts streaming SVIDs from the Workload API, waiting for the first one to be received.
+    #[cfg(all(unix, feature = "tls-spiffe"))]
+    pub(super) fn watch(&self) -> Result<Arc<CertificateWatcher>> {
+        workload::watch(self.socket_path()?, self.spiffe_id.clone())
+    }
+
+    #[cfg(not(all(unix, feature = "tls-spiffe")))]
+    pub(super) fn watch(&self) -> Result<Arc<CertificateWatcher>> {
+        let _ = self.socket_path()?;
+        Err(TlsError::SpiffeUnsupported)
+    }
+}
+
+#[cfg(all(unix, feature = "tls-spiffe"))]
+mod workload {
+    use std::{
+        path::PathBuf,
+        sync::{mpsc, Arc, Weak},
+        time::Duration,
+    };
+
+    use openssl::{pkey::PKey, x509::X509};
+    use tokio::{
+        net::UnixStream,
+        runtime::{Handle, RuntimeFlavor},
+    };
+    use tonic::{
+        metadata::MetadataValue,
+        transport::{Endpoint, Uri},
+    };
+
+    use super::{
+        super::{settings::IdentityStore, Result, TlsError},
+        CertificateWatcher,
+    };
+
+    #[allow(clippy::clone_on_ref_ptr)]
+    mod proto {
+        tonic::include_proto!("_");
+    }
+
+    use proto::{spiffe_workload_api_client::SpiffeWorkloadApiClient, X509svidRequest};
+
+    /// How long to wait for the initial SVID before giving up.
+    const INITIAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// How long to wait before reconnecting after the stream fails.
+    const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+    pub(super) fn watch(
+        socket_path: PathBuf,
+        spiffe_id: Option<String>,
+    ) -> Result<Arc<CertificateWatcher>> {
+        let watcher = Arc::new(CertificateWatcher::unwatched());
+        let (initial_tx, initial_rx) = mpsc::sync_channel(1);
+
+        let weak = Arc::downgrade(&watcher);
+        std::thread::Builder::new()
+            .name("spiffe-workload-api".into())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(error) => {
+                        let _ = initial_tx.send(Err(error.to_string()));
+                        return;
+                    }
+                };
+                runtime.block_on(run(socket_path, spiffe_id, weak, initial_tx));
+            })
+            .map_err(|error| TlsError::SpiffeWorkloadApi {
+                message: error.to_string(),
+            })?;
+
+        // The TLS settings are built synchronously, so waiting for the first SVID blocks. On a
+        // multi-threaded runtime, the tasks of the worker are handed off to other threads first so
+        // that they aren't held up for as long as the Workload API takes to answer.
+        let wait = || initial_rx.recv_timeout(INITIAL_TIMEOUT);
+        let initial = match Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(wait)
+            }
+            _ => wait(),
+        };
+
+        match initial {
+            Ok(Ok(())) => Ok(watcher),
+            Ok(Err(message)) => Err(TlsError::SpiffeWorkloadApi { message }),
+            Err(_) => Err(TlsError::SpiffeWorkloadApi {
+                message: "timed out waiting for the initial X.509 SVID".into(),
+            }),
+        }
+    }
+
+    /// Streams SVIDs into the watcher until it is dropped, reconnecting if the stream fails.
+    async fn run(
+        socket_path: PathBuf,
+        spiffe_id: Option<String>,
+        watcher: Weak<CertificateWatcher>,
+        initial_tx: mpsc::SyncSender<std::result::Result<(), String>>,
+    ) {
+        let mut initial_tx = Some(initial_tx);
+
+        loop {
+            let result = stream_svids(
+                &socket_path,
+                spiffe_id.as_deref(),
+                &watcher,
+                &mut initial_tx,
+            )
+            .await;
+
+            if watcher.strong_count() == 0 {
+                break;
+            }
+
+            match result {
+                Ok(()) => warn!(message = "SPIFFE Workload API stream ended, reconnecting."),
+                Err(message) => {
+                    // Fail fast if we never managed to get an SVID at all.
+                    if let Some(initial_tx) = initial_tx.take() {
+                        let _ = initial_tx.send(Err(message));
+                        break;
+                    }
+                    error!(
+                        message = "SPIFFE Workload API stream failed, reconnecting.",
+                        error = %message,
+                        internal_log_rate_secs = 30,
+                    );
+                }
+            }
+
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    async fn stream_svids(
+        socket_path: &std::path::Path,
+        spiffe_id: Option<&str>,
+        watcher: &Weak<CertificateWatcher>,
+        initial_tx: &mut Option<mpsc::SyncSender<std::result::Result<(), String>>>,
+    ) -> std::result::Result<(), String> {
+        let socket_path = socket_path.to_owned();
+        // The URI is ignored by the connector, but must be valid.
+        let channel = Endpoint::from_static("http://[::]:50051")
+            .connect_with_connector(tower::service_fn(move |_: Uri| {
+                UnixStream::connect(socket_path.clone())
+            }))
+            .await
+            .map_err(|error| error.to_string())?;
+
+        let mut request = tonic::Request::new(X509svidRequest {});
+        // Required by the Workload API to guard against server-side request forgery.
+        request
+            .metadata_mut()
+            .insert("workload.spiffe.io", MetadataValue::from_static("true"));
+
+        let mut stream = SpiffeWorkloadApiClient::new(channel)
+            .fetch_x509svid(request)
+            .await
+            .map_err(|status| status.to_string())?
+            .into_inner();
+
+        while let Some(response) = stream
+            .message()
+            .await
+            .map_err(|status| status.to_string())?
+        {
+            let svid = response
+                .svids
+                .into_iter()
+                .find(|svid| spiffe_id.map_or(true, |id| svid.spiffe_id == id))
+                .ok_or_else(|| {
+                    format!(
+                        "no X.509 SVID found for SPIFFE ID {}",
+                        spiffe_id.unwrap_or("<default>")
+                    )
+                })?;
+
+            let (identity, authorities) = parse_svid(
+                &svid.spiffe_id,
+                &svid.x509_svid,
+                &svid.x509_svid_key,
+                &svid.bundle,
+            )?;
+
+            match watcher.upgrade() {
+                Some(watcher) => watcher.update(identity, authorities),
+                None => return Ok(()),
+            }
+            debug!(message = "Received X.509 SVID.", spiffe_id = %svid.spiffe_id);
+
+            if let Some(initial_tx) = initial_tx.take() {
+                let _ = initial_tx.send(Ok(()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the identity and trusted authorities from an SVID. The certificates and the bundle
+    /// are each a concatenation of DER encoded certificates, the key is DER encoded PKCS#8.
+    fn parse_svid(
+        spiffe_id: &str,
+        certificates: &[u8],
+        key: &[u8],
+        bundle: &[u8],
+    ) -> std::result::Result<(IdentityStore, Vec<X509>), String> {
+        let mut certificates = split_der_certificates(certificates)?.into_iter();
+        let certificate = certificates
+            .next()
+            .ok_or_else(|| "X.509 SVID contains no certificates".to_string())?;
+        let key = PKey::private_key_from_der(key).map_err(|error| error.to_string())?;
+
+        let identity = IdentityStore::from_parts(spiffe_id, &certificate, &key, certificates)
+            .map_err(|error| error.to_string())?;
+        let authorities = split_der_certificates(bundle)?;
+
+        Ok((identity, authorities))
+    }
+
+    /// Splits a buffer of concatenated DER encoded certificates.
+    pub(super) fn split_der_certificates(
+        mut data: &[u8],
+    ) -> std::result::Result<Vec<X509>, String> {
+        let mut certificates = Vec::new();
+
+        while !data.is_empty() {
+            let length = der_length(data).ok_or_else(|| "invalid DER certificate".to_string())?;
+            let (certificate, rest) = data.split_at(length);
+            certificates.push(X509::from_der(certificate).map_err(|error| error.to_string())?);
+            data = rest;
+        }
+
+        Ok(certificates)
+    }
+
+    /// Returns the total length of the DER encoded ASN.1 value at the start of the buffer,
+    /// including its tag and length octets.
+    fn der_length(data: &[u8]) -> Option<usize> {
+        let first = *data.get(1)?;
+        let (header, length) = if first & 0x80 == 0 {
+            (2, first as usize)
+        } else {
+            let octets = (first & 0x7f) as usize;
+            if octets == 0 || octets > std::mem::size_of::<usize>() {
+                return None;
+            }
+            let length = data
+                .get(2..2 + octets)?
+                .iter()
+                .fold(0usize, |length, octet| (length << 8) | *octet as usize);
+            (2 + octets, length)
+        };
+
+        let total = header.checked_add(length)?;
+        if total <= data.len() {
+            Some(total)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn splits_concatenated_certificates() {
+            let pem = std::fs::read(crate::tls::TEST_PEM_CRT_PATH).unwrap();
+            let expected = X509::stack_from_pem(&pem).unwrap();
+            assert!(expected.len() > 1);
+
+            let der = expected
+                .iter()
+                .flat_map(|certificate| certificate.to_der().unwrap())
+                .collect::<Vec<_>>();
+
+            let certificates = split_der_certificates(&der).unwrap();
+            assert_eq!(certificates.len(), expected.len());
+            for (certificate, expected) in certificates.iter().zip(expected.iter()) {
+                assert_eq!(certificate.to_der().unwrap(), expected.to_der().unwrap());
+            }
+        }
+
+        #[test]
+        fn rejects_truncated_certificates() {
+            let pem = std::fs::read(crate::tls::TEST_PEM_CRT_PATH).unwrap();
+            let der = X509::stack_from_pem(&pem).unwrap()[0].to_der().unwrap();
+
+            assert!(split_der_certificates(&der[..der.len() - 1]).is_err());
+        }
+    }
+}
@@ -11,6 +11,7 @@ use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::{
     select,
+    sync::oneshot,
     time::{timeout, Duration},
 };
 use tracing::Instrument;
@@ -20,7 +21,7 @@ use vector_core::{
             builder::TopologyBuilder,
             channel::{BufferReceiver, BufferSender},
         },
-        BufferType, WhenFull,
+        WhenFull,
     },
     internal_event::EventsSent,
     schema::Definition,
@@ -28,18 +29,23 @@ use vector_core::{
 };
 
 use super::{
+    degradation::Degradation,
     fanout::{self, Fanout},
-    schema,
+    lag::{self, SinkLag},
+    migration,
+    mirror::Mirror,
+    priority, schema,
     task::{Task, TaskOutput},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
     config::{
-        ComponentKey, DataType, Input, Output, OutputId, ProxyConfig, SinkContext, SourceContext,
-        TransformContext, TransformOuter,
+        ComponentKey, DataType, Input, Output, OutputId, ProxyConfig, SinkContext, SourceConfig,
+        SourceContext, SourceReady, TransformContext, TransformOuter,
     },
     event::{EventArray, EventContainer},
-    internal_events::EventsReceived,
+    internal_events::{EventsExpired, EventsReceived},
+    leader_election::SourceFactory,
     shutdown::SourceShutdownCoordinator,
     source_sender::CHUNK_SIZE,
     spawn_named,
@@ -122,9 +128,13 @@ pub(self) async fn load_enrichment_tables<'a>(
 
 pub struct Pieces {
     pub(super) inputs: HashMap<ComponentKey, (BufferSender<EventArray>, Vec<OutputId>)>,
+    /// The senders of each input of the components with input priorities.
+    pub(super) priority_inputs: HashMap<ComponentKey, HashMap<OutputId, BufferSender<EventArray>>>,
     pub(crate) outputs: HashMap<ComponentKey, HashMap<Option<String>, fanout::ControlChannel>>,
     pub(super) tasks: HashMap<ComponentKey, Task>,
     pub(crate) source_tasks: HashMap<ComponentKey, Task>,
+    /// Resolves once each source holds the sockets it listens on.
+    pub(crate) sources_ready: Vec<oneshot::Receiver<()>>,
     pub(super) healthchecks: HashMap<ComponentKey, Task>,
     pub(crate) shutdown_coordinator: SourceShutdownCoordinator,
     pub(crate) detach_triggers: HashMap<ComponentKey, Trigger>,
@@ -137,9 +147,11 @@ pub async fn build_pieces(
     mut buffers: HashMap<ComponentKey, BuiltBuffer>,
 ) -> Result<Pieces, Vec<String>> {
     let mut inputs = HashMap::new();
+    let mut priority_inputs = HashMap::new();
     let mut outputs = HashMap::new();
     let mut tasks = HashMap::new();
     let mut source_tasks = HashMap::new();
+    let mut sources_ready = Vec::new();
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
@@ -178,9 +190,17 @@ pub async fn build_pieces(
             let mut rx = builder.add_output(output.clone());
 
             let (mut fanout, control) = Fanout::new();
+            // A TTL too large to be represented never expires.
+            let ttl = source.ttl_secs.and_then(|secs| {
+                chrono::Duration::from_std(std::time::Duration::from_secs(secs)).ok()
+            });
             let pump = async move {
                 debug!("Source pump starting.");
-                while let Some(array) = rx.next().await {
+                while let Some(mut array) = rx.next().await {
+                    lag::record_source_lag(&array);
+                    if let Some(ttl) = ttl {
+                        array.for_each_event(|mut event| event.metadata_mut().set_default_ttl(ttl));
+                    }
                     fanout.send(array).await;
                 }
                 debug!("Source pump finished.");
@@ -219,6 +239,8 @@ pub async fn build_pieces(
 
         let (shutdown_signal, force_shutdown_tripwire) = shutdown_coordinator.register_source(key);
 
+        let leader_shutdown = shutdown_signal.clone();
+        let (ready, ready_rx) = SourceReady::new();
         let context = SourceContext {
             key: key.clone(),
             globals: config.global.clone(),
@@ -228,7 +250,12 @@ pub async fn build_pieces(
             acknowledgements: source.sink_acknowledgements,
             schema_definitions,
             schema: config.schema,
+            ready: ready.clone(),
         };
+        let rebuild = source
+            .leader_election
+            .as_ref()
+            .map(|_| source_factory(source.inner.as_ref(), &context));
         let server = match source.inner.build(context).await {
             Err(error) => {
                 errors.push(format!("Source \"{}\": {}", key, error));
@@ -236,6 +263,23 @@ pub async fn build_pieces(
             }
             Ok(server) => server,
         };
+        ready.built();
+        // Sources run under leader election only bind once elected, which isn't waited for.
+        if source.leader_election.is_none() {
+            sources_ready.push(ready_rx);
+        }
+        let server = match (&source.leader_election, rebuild) {
+            (Some(leader_election), Some(rebuild)) => {
+                match leader_election.build(server, rebuild, leader_shutdown).await {
+                    Err(error) => {
+                        errors.push(format!("Source \"{}\": {}", key, error));
+                        continue;
+                    }
+                    Ok(server) => server,
+                }
+            }
+            _ => server,
+        };
 
         // The force_shutdown_tripwire is a Future that when it resolves means that this source
         // has failed to shut down gracefully within its allotted time window and instead should be
@@ -298,6 +342,7 @@ pub async fn build_pieces(
         };
 
         let node = TransformNode::from_parts(key.clone(), transform, &merged_definition);
+        let input_priorities = &transform.input_priorities;
 
         let transform = match transform.inner.build(&context).await {
             Err(error) => {
@@ -310,10 +355,35 @@ pub async fn build_pieces(
         let (input_tx, input_rx) =
             TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
 
+        let priority_relay = if input_priorities.is_empty() {
+            None
+        } else {
+            let (senders, scheduler) =
+                priority::build_intakes(input_priorities, &node.inputs).await;
+            priority_inputs.insert(key.clone(), senders);
+            Some(scheduler.relay(input_tx.clone()))
+        };
+
         inputs.insert(key.clone(), (input_tx, node.inputs.clone()));
 
         let (transform_task, transform_outputs) = build_transform(transform, node, input_rx);
 
+        let transform_task = match priority_relay {
+            Some(relay) => {
+                let typetag = transform_task.typetag().to_owned();
+                let mut transform_task = transform_task;
+                let task = async move {
+                    tokio::pin!(relay);
+                    select! {
+                        result = &mut transform_task => result,
+                        () = &mut relay => transform_task.await,
+                    }
+                };
+                Task::new(key.clone(), typetag, task)
+            }
+            None => transform_task,
+        };
+
         outputs.extend(transform_outputs);
         tasks.insert(key.clone(), transform_task);
     }
@@ -341,13 +411,10 @@ pub async fn build_pieces(
             };
         }
 
-        let (tx, rx) = if let Some(buffer) = buffers.remove(key) {
+        let (mut tx, rx) = if let Some(buffer) = buffers.remove(key) {
             buffer
         } else {
-            let buffer_type = match sink.buffer.stages().first().expect("cant ever be empty") {
-                BufferType::Memory { .. } => "memory",
-                BufferType::DiskV1 { .. } | BufferType::DiskV2 { .. } => "disk",
-            };
+            let buffer_type = migration::buffer_type(&sink.buffer);
             let buffer_span = error_span!(
                 "sink",
                 component_kind = "sink",
@@ -369,13 +436,45 @@ pub async fn build_pieces(
             }
         };
 
+        // Mirror sinks are fed through an intake relaying a sample of the events to their buffer.
+        let mirror = match &sink.mirror {
+            Some(options) => {
+                let (intake_tx, intake_rx) =
+                    TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::DropNewest)
+                        .await;
+                let buffer_tx = std::mem::replace(&mut tx, intake_tx);
+                Some((Mirror::new(options), intake_rx, buffer_tx))
+            }
+            None => None,
+        };
+
+        // The inputs of sinks with input priorities are scheduled from their own intakes.
+        let priority_relay = if sink.input_priorities.is_empty() {
+            None
+        } else {
+            let (senders, scheduler) =
+                priority::build_intakes(&sink.input_priorities, sink_inputs).await;
+            priority_inputs.insert(key.clone(), senders);
+            Some(scheduler.relay(tx.clone()))
+        };
+
         let cx = SinkContext {
+            key: key.clone(),
             healthcheck,
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
+            connection_pool: *sink.connection_pool(),
             schema: config.schema,
         };
 
+        let degradation = match sink.degradation.as_ref().map(Degradation::new).transpose() {
+            Err(error) => {
+                errors.push(format!("Sink \"{}\": {}", key, error));
+                continue;
+            }
+            Ok(degradation) => degradation,
+        };
+
         let (sink, healthcheck) = match sink.inner.build(cx).await {
             Err(error) => {
                 errors.push(format!("Sink \"{}\": {}", key, error));
@@ -401,19 +500,43 @@ pub async fn build_pieces(
 
             let mut rx = wrap(rx);
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
-                    .inspect(|events| {
-                        emit!(EventsReceived {
-                            count: events.len(),
-                            byte_size: events.size_of(),
-                        })
-                    })
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+            let (sink_lag, record_sink_lag) = SinkLag::new();
+            let events = rx
+                .by_ref()
+                .filter_map(|events| ready(drop_expired(events)))
+                .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
+                .map(move |mut events| {
+                    emit!(EventsReceived {
+                        count: events.len(),
+                        byte_size: events.size_of(),
+                    });
+                    sink_lag.track(&mut events);
+                    events
+                })
+                .take_until_if(tripwire);
+            let run = async move {
+                match degradation {
+                    Some(degradation) => degradation.run(sink, events).await,
+                    None => sink.run(events).await,
+                }
+            };
+            let mirror_relay = async move {
+                if let Some((mirror, intake_rx, buffer_tx)) = mirror {
+                    mirror.relay(intake_rx.into_stream(), buffer_tx).await;
+                }
+            };
+            let priority_relay = async move {
+                if let Some(relay) = priority_relay {
+                    relay.await;
+                }
+            };
+            let relay = futures::future::join3(mirror_relay, priority_relay, record_sink_lag);
+            tokio::pin!(relay, run);
+            let result = select! {
+                result = &mut run => result,
+                _ = &mut relay => run.await,
+            };
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx)
             })
@@ -422,6 +545,7 @@ pub async fn build_pieces(
         let task = Task::new(key.clone(), typetag, sink);
 
         let component_key = key.clone();
+        super::health::starting(&component_key);
         let healthcheck_task = async move {
             if enable_healthcheck {
                 let duration = Duration::from_secs(10);
@@ -429,9 +553,11 @@ pub async fn build_pieces(
                     .map(|result| match result {
                         Ok(Ok(_)) => {
                             info!("Healthcheck: Passed.");
+                            super::health::healthcheck(&component_key, true);
                             Ok(TaskOutput::Healthcheck)
                         }
                         Ok(Err(error)) => {
+                            super::health::healthcheck(&component_key, false);
                             error!(
                                 msg = "Healthcheck: Failed Reason.",
                                 %error,
@@ -444,6 +570,7 @@ pub async fn build_pieces(
                             Err(())
                         }
                         Err(_) => {
+                            super::health::healthcheck(&component_key, false);
                             error!(
                                 msg = "Healthcheck: timeout.",
                                 component_kind = "sink",
@@ -458,6 +585,7 @@ pub async fn build_pieces(
                     .await
             } else {
                 info!("Healthcheck: Disabled.");
+                super::health::healthcheck(&component_key, true);
                 Ok(TaskOutput::Healthcheck)
             }
         };
@@ -485,9 +613,11 @@ pub async fn build_pieces(
     if errors.is_empty() {
         let pieces = Pieces {
             inputs,
+            priority_inputs,
             outputs: finalized_outputs,
             tasks,
             source_tasks,
+            sources_ready,
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
@@ -499,6 +629,41 @@ pub async fn build_pieces(
     }
 }
 
+/// Builds a source anew from a copy of its configuration, for sources that are stopped when
+/// their replica loses the leadership and started again once it's elected.
+fn source_factory(config: &dyn SourceConfig, cx: &SourceContext) -> SourceFactory {
+    // Trait objects can't be cloned, so the configuration is copied through JSON, the same way
+    // `ConfigBuilder` is cloned.
+    let config: Arc<Box<dyn SourceConfig>> = Arc::new(
+        serde_json::to_value(config)
+            .and_then(serde_json::from_value)
+            .expect("Source configuration should round trip through JSON. Please report."),
+    );
+    let cx = cx.clone();
+    Box::new(move || {
+        let config = Arc::clone(&config);
+        let cx = cx.clone();
+        Box::pin(async move { config.build(cx).await })
+    })
+}
+
+/// Drops the events which expired while buffered, so that they aren't delivered late.
+fn drop_expired(mut events: EventArray) -> Option<EventArray> {
+    let now = chrono::Utc::now();
+    let count = events.len();
+    match &mut events {
+        EventArray::Logs(logs) => logs.retain(|log| !log.metadata().is_expired(now)),
+        EventArray::Metrics(metrics) => metrics.retain(|metric| !metric.metadata().is_expired(now)),
+        EventArray::Traces(traces) => traces.retain(|trace| !trace.metadata().is_expired(now)),
+    }
+
+    let expired = count - events.len();
+    if expired > 0 {
+        emit!(EventsExpired { count: expired });
+    }
+    (!events.is_empty()).then(|| events)
+}
+
 const fn filter_events_type(events: &EventArray, data_type: DataType) -> bool {
     match events {
         EventArray::Logs(_) => data_type.contains(DataType::Log),
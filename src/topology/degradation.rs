@@ -0,0 +1,549 @@
+//! Degradation of sinks whose downstream is unavailable.
+//!
+//! The events of a sink with degradation options are relayed to it through a single slot channel.
+//! Once the sink hasn't accepted events for longer than the configured threshold, the sink is
+//! degraded: depending on the policy, the relay keeps blocking, or sheds or spills the oldest
+//! events it holds as new events are received, until the sink accepts events again. Spilled events
+//! are replayed to the sink once it recovers, and are only acknowledged once it delivers them.
+//! Events left in the spill file by a previous run are replayed when the sink starts.
+
+use std::{
+    collections::VecDeque,
+    io::{ErrorKind, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use bytes::{BufMut, BytesMut};
+use futures::{Stream, StreamExt};
+use prost::Message;
+use tokio::{
+    fs::{File, OpenOptions},
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader},
+    sync::mpsc,
+    time::{timeout, Duration},
+};
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::{
+    config::{DegradationPolicy, SinkDegradationOptions},
+    event::{proto, EventArray, EventContainer, EventFinalizers, EventStatus, Finalizable},
+    internal_events::{
+        SinkDegradationEventsReplayed, SinkDegradationEventsShed, SinkDegradationEventsSpilled,
+        SinkDegradationReplayError, SinkDegradationSpillError, SinkDegraded, SinkRecovered,
+    },
+    sinks::VectorSink,
+};
+
+enum Disposal {
+    Block,
+    Shed,
+    Spill(Spill),
+}
+
+pub(super) struct Degradation {
+    threshold: Duration,
+    policy: DegradationPolicy,
+    disposal: Disposal,
+}
+
+impl Degradation {
+    pub(super) fn new(options: &SinkDegradationOptions) -> Result<Self, &'static str> {
+        let disposal = match (options.policy, &options.spill_path) {
+            (DegradationPolicy::Block, _) => Disposal::Block,
+            (DegradationPolicy::Shed, _) => Disposal::Shed,
+            (DegradationPolicy::Spill, Some(path)) => {
+                Disposal::Spill(Spill::new(path.clone(), options.spill_max_bytes))
+            }
+            (DegradationPolicy::Spill, None) => {
+                return Err("`degradation.spill_path` is required by the `spill` policy")
+            }
+        };
+        Ok(Self {
+            threshold: Duration::from_secs(options.threshold_secs),
+            policy: options.policy,
+            disposal,
+        })
+    }
+
+    /// Runs the sink on the input, degrading it while its downstream is unavailable.
+    pub(super) async fn run(
+        self,
+        sink: VectorSink,
+        input: impl Stream<Item = EventArray> + Send,
+    ) -> Result<(), ()> {
+        let (tx, rx) = mpsc::channel(1);
+        let relay = self.relay(input, tx);
+        let run = sink.run(ReceiverStream::new(rx));
+        tokio::pin!(relay, run);
+
+        tokio::select! {
+            result = &mut run => result,
+            () = &mut relay => run.await,
+        }
+    }
+
+    async fn relay(mut self, input: impl Stream<Item = EventArray>, tx: mpsc::Sender<EventArray>) {
+        tokio::pin!(input);
+        let mut degraded = false;
+        let mut pending = None;
+
+        if let Disposal::Spill(spill) = &mut self.disposal {
+            // The events left by a previous run are older than any received since.
+            spill.recover().await;
+            if spill.replay(&tx).await.is_err() {
+                return;
+            }
+        }
+
+        loop {
+            let events = match pending.take() {
+                Some(events) => events,
+                None => match input.next().await {
+                    Some(events) => events,
+                    None => break,
+                },
+            };
+
+            if !degraded {
+                match timeout(self.threshold, tx.reserve()).await {
+                    Ok(Ok(permit)) => permit.send(events),
+                    Ok(Err(_)) => break,
+                    Err(_) => {
+                        degraded = true;
+                        emit!(SinkDegraded {
+                            policy: self.policy.as_str(),
+                            threshold_secs: self.threshold.as_secs(),
+                        });
+                        pending = Some(events);
+                    }
+                }
+                continue;
+            }
+
+            let blocking = matches!(self.disposal, Disposal::Block);
+            let permit = tokio::select! {
+                biased;
+                permit = tx.reserve() => permit,
+                next = input.next(), if !blocking => {
+                    match next {
+                        Some(next) => {
+                            self.dispose(events).await;
+                            pending = Some(next);
+                            continue;
+                        }
+                        // Events are not shed on shutdown, but kept for a reload of the sink.
+                        None => tx.reserve().await,
+                    }
+                }
+            };
+
+            match permit {
+                Ok(permit) => {
+                    permit.send(events);
+                    degraded = false;
+                    emit!(SinkRecovered {
+                        policy: self.policy.as_str()
+                    });
+                    if let Disposal::Spill(spill) = &mut self.disposal {
+                        if spill.replay(&tx).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    /// Sheds or spills the oldest events held for the sink.
+    async fn dispose(&mut self, mut events: EventArray) {
+        let count = events.len();
+        match &mut self.disposal {
+            Disposal::Block => unreachable!("Blocking sinks never dispose of events."),
+            Disposal::Shed => {
+                events.take_finalizers().update_status(EventStatus::Errored);
+                emit!(SinkDegradationEventsShed { count });
+            }
+            Disposal::Spill(spill) => match spill.write(events).await {
+                Ok(Some(byte_size)) => emit!(SinkDegradationEventsSpilled { count, byte_size }),
+                // The spill is full, so the events are shed instead.
+                Ok(None) => emit!(SinkDegradationEventsShed { count }),
+                Err(error) => emit!(SinkDegradationSpillError { error, count }),
+            },
+        }
+    }
+}
+
+/// The events spilled to a file while the sink is degraded, to replay once it recovers.
+///
+/// The file is a sequence of frames, each prefixed with its length as a 4-byte big-endian integer
+/// and holding a batch of events in the native protobuf encoding. Only the finalizers of the
+/// spilled events are kept in memory, so that the events are acknowledged once the sink delivers
+/// them after being replayed, rather than once spilled.
+struct Spill {
+    path: PathBuf,
+    max_bytes: u64,
+    file: Option<File>,
+    /// The size of the events spilled and not replayed yet.
+    byte_size: u64,
+    /// The number of events and the finalizers of the batches spilled and not replayed yet.
+    batches: VecDeque<(usize, EventFinalizers)>,
+}
+
+impl Spill {
+    fn new(path: PathBuf, max_bytes: u64) -> Self {
+        Self {
+            path,
+            max_bytes,
+            file: None,
+            byte_size: 0,
+            batches: VecDeque::new(),
+        }
+    }
+
+    /// Appends the events to the spill file, returning their size, or `None` if they don't fit in
+    /// the spill. Events which couldn't be spilled are not delivered.
+    async fn write(&mut self, mut events: EventArray) -> std::io::Result<Option<usize>> {
+        let count = events.len();
+        let finalizers = events.take_finalizers();
+        let events = proto::EventArray::from(events);
+        let len = match u32::try_from(events.encoded_len()) {
+            Ok(len) if self.byte_size + 4 + u64::from(len) <= self.max_bytes => len,
+            _ => {
+                finalizers.update_status(EventStatus::Errored);
+                return Ok(None);
+            }
+        };
+        let frame_len = 4 + len as usize;
+        let mut frame = BytesMut::with_capacity(frame_len);
+        frame.put_u32(len);
+        events
+            .encode(&mut frame)
+            .expect("Encoding into a growable buffer can't fail.");
+
+        match self.append(&frame).await {
+            Ok(()) => {
+                self.byte_size += frame_len as u64;
+                self.batches.push_back((count, finalizers));
+                Ok(Some(frame_len))
+            }
+            Err(error) => {
+                finalizers.update_status(EventStatus::Errored);
+                Err(error)
+            }
+        }
+    }
+
+    async fn append(&mut self, frame: &[u8]) -> std::io::Result<()> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)
+                    .await?;
+                self.file.insert(file)
+            }
+        };
+        file.write_all(frame).await?;
+        file.flush().await
+    }
+
+    /// Takes over the events left in the spill file by a previous run, so that they're replayed
+    /// along with those spilled by this one and count against the size of the spill. Their sources
+    /// are gone, so there is nothing to acknowledge once they're delivered. An incomplete or
+    /// corrupted frame, such as one cut short by a crash, is removed along with those after it.
+    async fn recover(&mut self) {
+        let mut reader = match open_at(&self.path, 0).await {
+            Ok(reader) => reader,
+            Err(error) if error.kind() == ErrorKind::NotFound => return,
+            Err(error) => {
+                emit!(SinkDegradationReplayError { error, count: 0 });
+                return;
+            }
+        };
+        let len = match reader.get_ref().metadata().await {
+            Ok(metadata) => metadata.len(),
+            Err(error) => {
+                emit!(SinkDegradationReplayError { error, count: 0 });
+                return;
+            }
+        };
+
+        while self.byte_size < len {
+            match read_frame(&mut reader).await {
+                Ok((events, frame_len)) => {
+                    self.byte_size += frame_len;
+                    self.batches.push_back((events.len(), EventFinalizers::default()));
+                }
+                Err(error) => {
+                    emit!(SinkDegradationReplayError { error, count: 0 });
+                    if let Err(error) = truncate(&self.path, self.byte_size).await {
+                        warn!(message = "Failed removing invalid events from spill file.", %error);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Sends the spilled events to the sink, in the order they were spilled, then removes them from
+    /// the spill file. Fails if the sink stopped.
+    async fn replay(&mut self, tx: &mpsc::Sender<EventArray>) -> Result<(), ()> {
+        if self.batches.is_empty() {
+            return Ok(());
+        }
+
+        self.file = None;
+        let mut reader = match open_at(&self.path, 0).await {
+            Ok(reader) => reader,
+            Err(error) => {
+                self.fail_batches(error);
+                return Ok(());
+            }
+        };
+        while let Some((count, finalizers)) = self.batches.pop_front() {
+            let mut events = match read_frame(&mut reader).await {
+                Ok((events, _)) => events,
+                Err(error) => {
+                    // The rest of the file can't be matched with its batches anymore.
+                    self.batches.push_front((count, finalizers));
+                    self.fail_batches(error);
+                    break;
+                }
+            };
+            events.for_each_event(|mut event| {
+                event.metadata_mut().merge_finalizers(finalizers.clone());
+            });
+            match tx.reserve().await {
+                Ok(permit) => permit.send(events),
+                Err(_) => {
+                    finalizers.update_status(EventStatus::Errored);
+                    return Err(());
+                }
+            }
+            emit!(SinkDegradationEventsReplayed { count });
+        }
+
+        self.byte_size = 0;
+        if let Err(error) = truncate(&self.path, 0).await {
+            // The replayed events are left in the file, and replayed again by the next run.
+            warn!(message = "Failed removing replayed events from the spill file.", %error);
+        }
+        Ok(())
+    }
+
+    /// Drops the batches not replayed yet, as failed.
+    fn fail_batches(&mut self, error: std::io::Error) {
+        let count = self.batches.iter().map(|(count, _)| count).sum();
+        for (_, finalizers) in self.batches.drain(..) {
+            finalizers.update_status(EventStatus::Errored);
+        }
+        emit!(SinkDegradationReplayError { error, count });
+    }
+}
+
+impl Drop for Spill {
+    /// The events not replayed by the time the sink stops are left in the spill file for the next
+    /// run to replay, but aren't acknowledged.
+    fn drop(&mut self) {
+        for (_, finalizers) in self.batches.drain(..) {
+            finalizers.update_status(EventStatus::Errored);
+        }
+    }
+}
+
+async fn open_at(path: &Path, offset: u64) -> std::io::Result<BufReader<File>> {
+    let mut file = File::open(path).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+    Ok(BufReader::new(file))
+}
+
+/// Reads the events of a frame, along with the size of the frame.
+async fn read_frame(reader: &mut BufReader<File>) -> std::io::Result<(EventArray, u64)> {
+    let invalid = |error| std::io::Error::new(ErrorKind::InvalidData, error);
+    let len = reader.read_u32().await?;
+    let mut frame = vec![0; len as usize];
+    reader.read_exact(&mut frame).await?;
+    let events = proto::EventArray::decode(&frame[..]).map_err(|error| invalid(error.to_string()))?;
+    let events = EventArray::try_from(events).map_err(|error| invalid(error.to_string()))?;
+    Ok((events, 4 + u64::from(len)))
+}
+
+/// Truncates the spill file to its first `len` bytes.
+async fn truncate(path: &Path, len: u64) -> std::io::Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .open(path)
+        .await?
+        .set_len(len)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::stream;
+
+    use super::*;
+    use crate::{
+        event::{BatchNotifier, BatchStatus, EventRef, LogEvent},
+        test_util::temp_file,
+    };
+
+    fn input(messages: &[&str]) -> impl Stream<Item = EventArray> {
+        let events = messages
+            .iter()
+            .map(|message| EventArray::from(LogEvent::from(*message)))
+            .collect::<Vec<_>>();
+        stream::iter(events).chain(stream::pending())
+    }
+
+    fn options(policy: DegradationPolicy, spill_path: Option<PathBuf>) -> SinkDegradationOptions {
+        SinkDegradationOptions {
+            threshold_secs: 30,
+            policy,
+            spill_path,
+            spill_max_bytes: 1024 * 1024,
+        }
+    }
+
+    fn message(events: EventArray) -> String {
+        match events.iter_events().next() {
+            Some(EventRef::Log(log)) => log["message"].to_string_lossy(),
+            _ => panic!("Expected a log."),
+        }
+    }
+
+    #[test]
+    fn spill_requires_path() {
+        assert!(Degradation::new(&options(DegradationPolicy::Spill, None)).is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sheds_oldest_events_while_degraded() {
+        let degradation = Degradation::new(&options(DegradationPolicy::Shed, None)).unwrap();
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(degradation.relay(input(&["one", "two", "three"]), tx));
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        assert_eq!(message(rx.recv().await.unwrap()), "one");
+        assert_eq!(message(rx.recv().await.unwrap()), "three");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn blocks_while_degraded() {
+        let degradation = Degradation::new(&options(DegradationPolicy::Block, None)).unwrap();
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(degradation.relay(input(&["one", "two", "three"]), tx));
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        assert_eq!(message(rx.recv().await.unwrap()), "one");
+        assert_eq!(message(rx.recv().await.unwrap()), "two");
+        assert_eq!(message(rx.recv().await.unwrap()), "three");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn spills_and_replays_oldest_events_while_degraded() {
+        let path = temp_file();
+        let degradation =
+            Degradation::new(&options(DegradationPolicy::Spill, Some(path.clone()))).unwrap();
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            EventArray::from(LogEvent::from("one")),
+            EventArray::from(LogEvent::from("two").with_batch_notifier(&batch)),
+            EventArray::from(LogEvent::from("three")),
+        ];
+        drop(batch);
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(degradation.relay(stream::iter(events).chain(stream::pending()), tx));
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        // The spilled event isn't acknowledged until the sink delivers it.
+        assert!(tokio::fs::metadata(&path).await.unwrap().len() > 0);
+        assert!(receiver.try_recv().is_err());
+
+        assert_eq!(message(rx.recv().await.unwrap()), "one");
+        assert_eq!(message(rx.recv().await.unwrap()), "three");
+        let mut replayed = rx.recv().await.unwrap();
+        assert_eq!(message(replayed.clone()), "two");
+        assert!(receiver.try_recv().is_err());
+        replayed
+            .take_finalizers()
+            .update_status(EventStatus::Delivered);
+        drop(replayed);
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        // The replayed events are then removed from the spill file.
+        while tokio::fs::metadata(&path).await.unwrap().len() > 0 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sheds_events_once_the_spill_is_full() {
+        let path = temp_file();
+        let mut options = options(DegradationPolicy::Spill, Some(path.clone()));
+        options.spill_max_bytes = 1;
+        let degradation = Degradation::new(&options).unwrap();
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(degradation.relay(input(&["one", "two", "three"]), tx));
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+
+        assert_eq!(message(rx.recv().await.unwrap()), "one");
+        assert_eq!(message(rx.recv().await.unwrap()), "three");
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert!(rx.try_recv().is_err());
+        assert!(tokio::fs::metadata(&path).await.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn errors_shed_events() {
+        let mut full_spill = options(DegradationPolicy::Spill, Some(temp_file()));
+        full_spill.spill_max_bytes = 1;
+        for degradation_options in [options(DegradationPolicy::Shed, None), full_spill] {
+            let degradation = Degradation::new(&degradation_options).unwrap();
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let events = vec![
+                EventArray::from(LogEvent::from("one")),
+                EventArray::from(LogEvent::from("two").with_batch_notifier(&batch)),
+                EventArray::from(LogEvent::from("three")),
+            ];
+            drop(batch);
+            let (tx, _rx) = mpsc::channel(1);
+            tokio::spawn(degradation.relay(stream::iter(events).chain(stream::pending()), tx));
+
+            tokio::time::sleep(Duration::from_secs(60)).await;
+
+            assert_eq!(receiver.await, BatchStatus::Errored);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replays_events_left_by_a_previous_run() {
+        let path = temp_file();
+        let mut previous = Spill::new(path.clone(), 1024 * 1024);
+        let spilled = previous.write(EventArray::from(LogEvent::from("left"))).await;
+        assert!(matches!(spilled, Ok(Some(_))));
+        drop(previous);
+        // A frame cut short, as if the previous run stopped while spilling it.
+        let mut file = OpenOptions::new().append(true).open(&path).await.unwrap();
+        file.write_all(&[0, 0, 1]).await.unwrap();
+        drop(file);
+
+        let degradation =
+            Degradation::new(&options(DegradationPolicy::Spill, Some(path.clone()))).unwrap();
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(degradation.relay(input(&["one"]), tx));
+
+        assert_eq!(message(rx.recv().await.unwrap()), "left");
+        assert_eq!(message(rx.recv().await.unwrap()), "one");
+        while tokio::fs::metadata(&path).await.unwrap().len() > 0 {
+            tokio::task::yield_now().await;
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! Health of the running components.
+//!
+//! Each component goes through the following states:
+//!
+//! - `Starting` until its healthcheck completes, for sinks.
+//! - `Healthy` once running.
+//! - `Degraded` when its healthcheck failed, or its error rate exceeds the configured threshold.
+//! - `Failed` when its task stopped with an error.
+
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::config::ComponentKey;
+
+const INVARIANT: &str = "Couldn't acquire lock on component health. Please report this.";
+
+static COMPONENT_HEALTH: Lazy<RwLock<HashMap<ComponentKey, HealthRecord>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// The health of a component.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentHealth {
+    Starting,
+    Healthy,
+    Degraded,
+    Failed,
+}
+
+#[derive(Debug)]
+struct HealthRecord {
+    health: ComponentHealth,
+    healthcheck_failed: bool,
+    error_rate_exceeded: bool,
+    /// The errors total of the component at the last evaluation of its error rate.
+    errors: Option<(f64, Instant)>,
+}
+
+impl HealthRecord {
+    const fn new(health: ComponentHealth) -> Self {
+        Self {
+            health,
+            healthcheck_failed: false,
+            error_rate_exceeded: false,
+            errors: None,
+        }
+    }
+
+    const fn health(&self) -> ComponentHealth {
+        match self.health {
+            ComponentHealth::Healthy if self.healthcheck_failed || self.error_rate_exceeded => {
+                ComponentHealth::Degraded
+            }
+            health => health,
+        }
+    }
+}
+
+fn update(key: &ComponentKey, f: impl FnOnce(&mut HealthRecord)) {
+    let mut components = COMPONENT_HEALTH.write().expect(INVARIANT);
+    f(components
+        .entry(key.clone())
+        .or_insert_with(|| HealthRecord::new(ComponentHealth::Starting)));
+}
+
+/// Marks a component as starting, resetting its previous health.
+pub fn starting(key: &ComponentKey) {
+    COMPONENT_HEALTH
+        .write()
+        .expect(INVARIANT)
+        .insert(key.clone(), HealthRecord::new(ComponentHealth::Starting));
+}
+
+/// Marks a component as running, unless it already failed.
+pub fn running(key: &ComponentKey) {
+    update(key, |record| {
+        if record.health == ComponentHealth::Starting {
+            record.health = ComponentHealth::Healthy;
+        }
+    });
+}
+
+/// Records the result of the healthcheck of a component, which is then running.
+pub fn healthcheck(key: &ComponentKey, passed: bool) {
+    update(key, |record| {
+        record.healthcheck_failed = !passed;
+        if record.health == ComponentHealth::Starting {
+            record.health = ComponentHealth::Healthy;
+        }
+    });
+}
+
+/// Marks a component as failed.
+pub fn failed(key: &ComponentKey) {
+    update(key, |record| record.health = ComponentHealth::Failed);
+}
+
+/// Forgets a removed component.
+pub fn remove(key: &ComponentKey) {
+    COMPONENT_HEALTH.write().expect(INVARIANT).remove(key);
+}
+
+/// Records the errors total of a component, marking it as degraded while its error rate since the
+/// previous evaluation exceeds `max_errors_per_sec`.
+pub fn record_errors(key: &ComponentKey, errors_total: f64, max_errors_per_sec: f64) {
+    record_errors_at(key, errors_total, max_errors_per_sec, Instant::now());
+}
+
+fn record_errors_at(key: &ComponentKey, errors_total: f64, max_errors_per_sec: f64, now: Instant) {
+    let mut components = COMPONENT_HEALTH.write().expect(INVARIANT);
+    if let Some(record) = components.get_mut(key) {
+        if let Some((previous, at)) = record.errors {
+            let elapsed = now
+                .saturating_duration_since(at)
+                .max(Duration::from_millis(1));
+            // Counters are reset when components are reloaded.
+            let errors = (errors_total - previous).max(0.0);
+            record.error_rate_exceeded = errors / elapsed.as_secs_f64() > max_errors_per_sec;
+        }
+        record.errors = Some((errors_total, now));
+    }
+}
+
+/// Gets the health of a component.
+pub fn get(key: &ComponentKey) -> Option<ComponentHealth> {
+    COMPONENT_HEALTH
+        .read()
+        .expect(INVARIANT)
+        .get(key)
+        .map(HealthRecord::health)
+}
+
+/// Gets the health of all the components.
+pub fn all() -> Vec<(ComponentKey, ComponentHealth)> {
+    COMPONENT_HEALTH
+        .read()
+        .expect(INVARIANT)
+        .iter()
+        .map(|(key, record)| (key.clone(), record.health()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_transitions() {
+        let key = ComponentKey::from("health_state_transitions");
+
+        starting(&key);
+        assert_eq!(get(&key), Some(ComponentHealth::Starting));
+
+        healthcheck(&key, false);
+        assert_eq!(get(&key), Some(ComponentHealth::Degraded));
+
+        starting(&key);
+        healthcheck(&key, true);
+        assert_eq!(get(&key), Some(ComponentHealth::Healthy));
+
+        failed(&key);
+        running(&key);
+        assert_eq!(get(&key), Some(ComponentHealth::Failed));
+
+        remove(&key);
+        assert_eq!(get(&key), None);
+    }
+
+    #[test]
+    fn error_rate() {
+        let key = ComponentKey::from("health_error_rate");
+        let now = Instant::now();
+
+        starting(&key);
+        running(&key);
+        record_errors_at(&key, 0.0, 1.0, now);
+        assert_eq!(get(&key), Some(ComponentHealth::Healthy));
+
+        record_errors_at(&key, 20.0, 1.0, now + Duration::from_secs(10));
+        assert_eq!(get(&key), Some(ComponentHealth::Degraded));
+
+        record_errors_at(&key, 25.0, 1.0, now + Duration::from_secs(20));
+        assert_eq!(get(&key), Some(ComponentHealth::Healthy));
+
+        remove(&key);
+    }
+}
@@ -0,0 +1,162 @@
+//! Event lag, the time elapsed since the timestamp of an event, as seen by sources and sinks.
+//!
+//! Rather than measuring every event, the lag is sampled once per batch, from its oldest
+//! timestamped event. Sources record it as they send the batch downstream. Sinks record it once
+//! that event has been delivered, so that the time spent in the sink and its buffer, up to the
+//! acknowledgement of the destination, is accounted for.
+
+use std::{future::Future, time::Duration};
+
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use vector_common::finalizer::UnorderedFinalizer;
+
+use crate::{
+    config::log_schema,
+    event::{BatchNotifier, BatchStatus, EventArray, EventFinalizer, EventRef, Value},
+    internal_events::{SinkEventLag, SourceEventLag},
+    shutdown::ShutdownSignal,
+};
+
+/// Records the lag of a batch as its source sends it downstream.
+pub(super) fn record_source_lag(events: &EventArray) {
+    if let Some((_, timestamp)) = oldest_event(events) {
+        emit!(SourceEventLag {
+            lag: lag_since(timestamp, Utc::now()),
+        });
+    }
+}
+
+/// Tracks the lag of the batches received by a sink, recording it once they are delivered.
+pub(super) struct SinkLag {
+    finalizer: UnorderedFinalizer<DateTime<Utc>>,
+}
+
+impl SinkLag {
+    /// Creates the tracker, along with the task recording the lag of the delivered batches.
+    ///
+    /// The task finishes once the tracker is dropped and the batches it tracks are finalized.
+    pub(super) fn new() -> (Self, impl Future<Output = ()>) {
+        let (finalizer, finalized) = UnorderedFinalizer::new(ShutdownSignal::noop());
+        (Self { finalizer }, record_delivered(finalized))
+    }
+
+    /// Attaches a finalizer to the oldest timestamped event of the batch, to record its lag once
+    /// the sink has delivered it.
+    pub(super) fn track(&self, events: &mut EventArray) {
+        if let Some((index, timestamp)) = oldest_event(events) {
+            let (batch, receiver) = BatchNotifier::new_with_receiver();
+            let finalizer = EventFinalizer::new(batch);
+            match events {
+                EventArray::Logs(logs) => logs[index].add_finalizer(finalizer),
+                EventArray::Metrics(metrics) => metrics[index].add_finalizer(finalizer),
+                EventArray::Traces(traces) => traces[index].add_finalizer(finalizer),
+            }
+            self.finalizer.add(timestamp, receiver);
+        }
+    }
+}
+
+async fn record_delivered(
+    mut finalized: impl Stream<Item = (BatchStatus, DateTime<Utc>)> + Unpin,
+) {
+    while let Some((status, timestamp)) = finalized.next().await {
+        // Events that failed to be delivered haven't made it through the pipeline.
+        if status == BatchStatus::Delivered {
+            emit!(SinkEventLag {
+                lag: lag_since(timestamp, Utc::now()),
+            });
+        }
+    }
+}
+
+/// Finds the oldest timestamped event of the batch, returning its position and its timestamp.
+fn oldest_event(events: &EventArray) -> Option<(usize, DateTime<Utc>)> {
+    events
+        .iter_events()
+        .enumerate()
+        .filter_map(|(index, event)| timestamp(event).map(|timestamp| (index, timestamp)))
+        .min_by_key(|(_, timestamp)| *timestamp)
+}
+
+fn timestamp(event: EventRef<'_>) -> Option<DateTime<Utc>> {
+    match event {
+        EventRef::Log(log) => log
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .copied(),
+        EventRef::Metric(metric) => metric.timestamp(),
+        EventRef::Trace(_) => None,
+    }
+}
+
+/// Events timestamped in the future have no lag.
+fn lag_since(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    (now - timestamp).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+    use crate::event::{EventStatus, Finalizable, LogEvent};
+
+    fn log_at(timestamp: Option<DateTime<Utc>>) -> LogEvent {
+        let mut log = LogEvent::from("message");
+        log.remove(log_schema().timestamp_key());
+        if let Some(timestamp) = timestamp {
+            log.insert(log_schema().timestamp_key(), timestamp);
+        }
+        log
+    }
+
+    #[test]
+    fn finds_oldest_timestamped_event() {
+        let now = Utc::now();
+        let events = EventArray::Logs(vec![
+            log_at(Some(now - chrono::Duration::seconds(5))),
+            log_at(None),
+            log_at(Some(now - chrono::Duration::seconds(10))),
+            log_at(Some(now)),
+        ]);
+
+        assert_eq!(oldest_event(&events), Some((2, now - chrono::Duration::seconds(10))));
+        assert_eq!(oldest_event(&EventArray::Logs(vec![log_at(None)])), None);
+    }
+
+    #[test]
+    fn future_timestamps_have_no_lag() {
+        let now = Utc::now();
+        assert_eq!(lag_since(now - chrono::Duration::seconds(3), now), Duration::from_secs(3));
+        assert_eq!(lag_since(now + chrono::Duration::seconds(3), now), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn tracks_only_the_oldest_event_until_finalized() {
+        let now = Utc::now();
+        let (lag, finished) = SinkLag::new();
+        let mut finished = Box::pin(finished);
+
+        let mut events = EventArray::Logs(vec![
+            log_at(Some(now)),
+            log_at(Some(now - chrono::Duration::seconds(10))),
+        ]);
+        lag.track(&mut events);
+        drop(lag);
+
+        let mut logs = match events {
+            EventArray::Logs(logs) => logs,
+            _ => unreachable!(),
+        };
+        assert!(logs[0].take_finalizers().is_empty());
+        let finalizers = logs[1].take_finalizers();
+        assert_eq!(finalizers.len(), 1);
+
+        // The lag is only recorded once the sink is done with the event.
+        assert!((&mut finished).now_or_never().is_none());
+        finalizers.update_status(EventStatus::Delivered);
+        drop(finalizers);
+        finished.await;
+    }
+}
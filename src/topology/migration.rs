@@ -0,0 +1,118 @@
+//! Migration of sink buffers whose configuration changed on reload.
+//!
+//! Rather than having the previous sink drain its buffer before the new one starts, the previous
+//! sink is detached from its buffer, and the events the buffer still holds are relayed into the
+//! buffer of the new sink, alongside the events the new sink receives from its inputs. The events
+//! keep their finalizers, so they are acknowledged once delivered by the new sink.
+
+use futures::StreamExt;
+use vector_core::{
+    buffers::{
+        topology::channel::{BufferReceiverStream, BufferSender},
+        BufferConfig, BufferType,
+    },
+    ByteSizeOf,
+};
+
+use crate::{
+    event::{EventArray, EventContainer},
+    internal_events::{
+        BufferEventsMigrated, BufferMigrationCompleted, BufferMigrationInterrupted,
+        BufferMigrationStarted,
+    },
+};
+
+/// Whether the contents of the `previous` buffer can be migrated into the `new` one.
+///
+/// Disk buffers of the same version live in the same directory, which only one of them can hold
+/// at a time, so their contents are left to the previous sink to drain.
+pub(super) fn can_migrate(previous: &BufferConfig, new: &BufferConfig) -> bool {
+    let on_disk = |config: &BufferConfig, v2: bool| {
+        config.stages().iter().any(|stage| match stage {
+            BufferType::Memory { .. } => false,
+            BufferType::DiskV1 { .. } => !v2,
+            BufferType::DiskV2 { .. } => v2,
+        })
+    };
+
+    previous != new
+        && !(on_disk(previous, false) && on_disk(new, false))
+        && !(on_disk(previous, true) && on_disk(new, true))
+}
+
+/// Relays the events of the previous buffer into the new one, until the previous buffer is empty
+/// and all of its senders are gone.
+pub(super) async fn migrate(
+    previous: BufferReceiverStream<EventArray>,
+    previous_type: &'static str,
+    mut new: BufferSender<EventArray>,
+    new_type: &'static str,
+) {
+    emit!(BufferMigrationStarted {
+        from: previous_type,
+        to: new_type,
+    });
+
+    tokio::pin!(previous);
+    let mut migrated = 0;
+    while let Some(events) = previous.next().await {
+        let count = events.len();
+        let byte_size = events.size_of();
+        if new.send(events).await.is_err() {
+            emit!(BufferMigrationInterrupted { count });
+            return;
+        }
+        migrated += count;
+        emit!(BufferEventsMigrated { count, byte_size });
+    }
+
+    emit!(BufferMigrationCompleted { count: migrated });
+}
+
+/// The name of the first stage of a buffer, as used in the `buffer_type` span field of sinks.
+pub(super) fn buffer_type(config: &BufferConfig) -> &'static str {
+    match config.stages().first().expect("cant ever be empty") {
+        BufferType::Memory { .. } => "memory",
+        BufferType::DiskV1 { .. } | BufferType::DiskV2 { .. } => "disk",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::{NonZeroU64, NonZeroUsize};
+
+    use vector_core::buffers::WhenFull;
+
+    use super::*;
+
+    fn memory(max_events: usize) -> BufferConfig {
+        BufferConfig {
+            stages: vec![BufferType::Memory {
+                max_events: NonZeroUsize::new(max_events).unwrap(),
+                when_full: WhenFull::Block,
+            }],
+        }
+    }
+
+    fn disk(max_size: u64) -> BufferConfig {
+        BufferConfig {
+            stages: vec![BufferType::DiskV2 {
+                max_size: NonZeroU64::new(max_size).unwrap(),
+                when_full: WhenFull::Block,
+            }],
+        }
+    }
+
+    #[test]
+    fn migrates_between_memory_and_disk() {
+        assert!(can_migrate(&memory(500), &memory(1000)));
+        assert!(can_migrate(&memory(500), &disk(1 << 30)));
+        assert!(can_migrate(&disk(1 << 30), &memory(500)));
+    }
+
+    #[test]
+    fn leaves_same_disk_buffers_to_previous_sink() {
+        assert!(!can_migrate(&memory(500), &memory(500)));
+        assert!(!can_migrate(&disk(1 << 30), &disk(2 << 30)));
+    }
+}
@@ -0,0 +1,96 @@
+//! Mirror sinks, shadowing other sinks with a sample of their traffic.
+//!
+//! The inputs of a mirror sink are received through an intake channel which drops events when
+//! full, so that the mirror sink never applies back pressure to its inputs. The sampled events are
+//! stripped of their finalizers before being sent to the buffer of the sink, so that neither the
+//! outcome of the mirror sink nor the time they spend in its buffer affects their acknowledgement.
+
+use futures::{Stream, StreamExt};
+use rand::Rng;
+use vector_buffers::topology::channel::BufferSender;
+
+use crate::{
+    config::SinkMirrorOptions,
+    event::{EventArray, EventContainer, Finalizable},
+};
+
+pub(super) struct Mirror {
+    ratio: f64,
+}
+
+impl Mirror {
+    pub(super) fn new(options: &SinkMirrorOptions) -> Self {
+        Self {
+            ratio: (options.percent / 100.0).clamp(0.0, 1.0),
+        }
+    }
+
+    /// Relays a sample of the events received by the intake to the buffer of the sink.
+    pub(super) async fn relay(
+        self,
+        intake: impl Stream<Item = EventArray>,
+        mut buffer: BufferSender<EventArray>,
+    ) {
+        tokio::pin!(intake);
+        while let Some(events) = intake.next().await {
+            if let Some(events) = self.sample(events) {
+                if buffer.send(events).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    fn sample(&self, mut events: EventArray) -> Option<EventArray> {
+        drop(events.take_finalizers());
+
+        if self.ratio < 1.0 {
+            let mut rng = rand::thread_rng();
+            let mut keep = || rng.gen_bool(self.ratio);
+            match &mut events {
+                EventArray::Logs(logs) => logs.retain(|_| keep()),
+                EventArray::Metrics(metrics) => metrics.retain(|_| keep()),
+                EventArray::Traces(traces) => traces.retain(|_| keep()),
+            }
+        }
+
+        (!events.is_empty()).then(|| events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::finalization::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::event::{Event, LogEvent};
+
+    fn logs(count: usize) -> EventArray {
+        EventArray::Logs((0..count).map(|i| LogEvent::from(i.to_string())).collect())
+    }
+
+    #[test]
+    fn samples_percent_of_events() {
+        let mirror = Mirror::new(&SinkMirrorOptions { percent: 10.0 });
+        let sampled = mirror.sample(logs(10_000)).map_or(0, |events| events.len());
+        assert!((800..1200).contains(&sampled), "sampled {}", sampled);
+
+        let mirror = Mirror::new(&SinkMirrorOptions { percent: 100.0 });
+        assert_eq!(mirror.sample(logs(100)).unwrap().len(), 100);
+    }
+
+    #[tokio::test]
+    async fn mirrored_events_do_not_hold_acknowledgement() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let event = Event::from(LogEvent::from("primary")).with_batch_notifier(&batch);
+        drop(batch);
+
+        let mirror = Mirror::new(&SinkMirrorOptions { percent: 100.0 });
+        let mirrored = mirror.sample(EventArray::from(event.clone())).unwrap();
+        drop(event);
+
+        // The primary copy is dropped, so the batch completes although the mirrored copy is held.
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+        assert_eq!(mirrored.len(), 1);
+    }
+}
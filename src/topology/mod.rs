@@ -10,6 +10,12 @@ pub(super) use vector_core::fanout;
 pub mod schema;
 
 pub mod builder;
+mod degradation;
+pub mod health;
+mod lag;
+mod migration;
+mod mirror;
+mod priority;
 mod ready_arrays;
 mod running;
 mod task;
@@ -23,7 +29,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use futures::{Future, FutureExt};
+use futures::{future, Future, FutureExt};
 pub(super) use running::RunningTopology;
 use tokio::sync::{mpsc, watch};
 use vector_buffers::topology::channel::{BufferReceiverStream, BufferSender};
@@ -79,6 +85,8 @@ pub async fn start_validated(
 ) -> Option<(RunningTopology, mpsc::UnboundedReceiver<()>)> {
     let (abort_tx, abort_rx) = mpsc::unbounded_channel();
 
+    crate::memory_budget::configure(config.global.memory_budget.as_ref());
+    vector_core::metrics::set_tag_policy(&config.global.telemetry.tags);
     let mut running_topology = RunningTopology::new(config, abort_tx);
 
     if !running_topology
@@ -87,9 +95,15 @@ pub async fn start_validated(
     {
         return None;
     }
+
+    let sources_ready = std::mem::take(&mut pieces.sources_ready);
     running_topology.connect_diff(&diff, &mut pieces).await;
     running_topology.spawn_diff(&diff, pieces);
 
+    // Once every source is ready, the process holds the sockets it was started to open and can
+    // drop its privileges. A source failing before then drops its sender, which also resolves.
+    future::join_all(sources_ready).await;
+
     Some((running_topology, abort_rx))
 }
 
@@ -120,6 +134,7 @@ pub(super) fn take_healthchecks(
 }
 
 async fn handle_errors(
+    key: ComponentKey,
     task: impl Future<Output = Result<TaskOutput, ()>>,
     abort_tx: mpsc::UnboundedSender<()>,
 ) -> Result<TaskOutput, ()> {
@@ -130,6 +145,7 @@ async fn handle_errors(
         .and_then(|res| res)
         .map_err(|_| {
             error!("An error occurred that Vector couldn't handle.");
+            health::failed(&key);
             let _ = abort_tx.send(());
         })
 }
@@ -0,0 +1,218 @@
+//! Priority scheduling between the inputs of a component.
+//!
+//! The inputs of a component with input priorities are each received through their own intake
+//! channel. A scheduler relays the events of the intakes to the component with weighted round
+//! robin: each round, an input may relay as many arrays as its weight before the inputs of lower
+//! weight relay theirs. An input which has nothing to relay doesn't hold up the others, so weights
+//! only matter once the component applies back pressure and events queue up in the intakes.
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+use vector_buffers::{
+    topology::{
+        builder::TopologyBuilder,
+        channel::{BufferReceiverStream, BufferSender},
+    },
+    WhenFull,
+};
+
+use super::builder::TOPOLOGY_BUFFER_SIZE;
+use crate::{config::OutputId, event::EventArray};
+
+/// The weight of the inputs without a priority.
+const DEFAULT_WEIGHT: u32 = 1;
+
+/// Returns the weight of the input, which is configured by its ID, or by the ID of its component
+/// for all of its outputs.
+pub(super) fn weight(priorities: &IndexMap<String, u32>, input: &OutputId) -> u32 {
+    priorities
+        .get(&input.to_string())
+        .or_else(|| priorities.get(input.component.id()))
+        .copied()
+        .unwrap_or(DEFAULT_WEIGHT)
+}
+
+/// Builds an intake for each input, returning their senders and the scheduler of their events.
+pub(super) async fn build_intakes(
+    priorities: &IndexMap<String, u32>,
+    inputs: &[OutputId],
+) -> (
+    HashMap<OutputId, BufferSender<EventArray>>,
+    Scheduler<BufferReceiverStream<EventArray>>,
+) {
+    let mut senders = HashMap::new();
+    let mut intakes = Vec::new();
+    for input in inputs {
+        let (tx, rx) =
+            TopologyBuilder::standalone_memory(TOPOLOGY_BUFFER_SIZE, WhenFull::Block).await;
+        senders.insert(input.clone(), tx);
+        intakes.push((weight(priorities, input), rx.into_stream()));
+    }
+    (senders, Scheduler::new(intakes))
+}
+
+struct Intake<S> {
+    weight: u32,
+    credit: u32,
+    stream: Option<S>,
+}
+
+pub(super) struct Scheduler<S> {
+    /// Sorted by descending weight.
+    intakes: Vec<Intake<S>>,
+}
+
+impl<S> Scheduler<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    pub(super) fn new(intakes: impl IntoIterator<Item = (u32, S)>) -> Self {
+        let mut intakes = intakes
+            .into_iter()
+            .map(|(weight, stream)| Intake {
+                weight,
+                credit: weight,
+                stream: Some(stream),
+            })
+            .collect::<Vec<_>>();
+        intakes.sort_by(|a, b| b.weight.cmp(&a.weight));
+        Self { intakes }
+    }
+
+    /// Relays the events of the intakes to the buffer of the component.
+    pub(super) async fn relay(mut self, mut buffer: BufferSender<EventArray>) {
+        while let Some(events) = self.next().await {
+            if buffer.send(events).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    fn refill(&mut self) {
+        for intake in &mut self.intakes {
+            intake.credit = intake.weight;
+        }
+    }
+}
+
+impl<S> Stream for Scheduler<S>
+where
+    S: Stream<Item = EventArray> + Unpin,
+{
+    type Item = EventArray;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // The second pass starts a new round, once the intakes left with credit have nothing to
+        // relay.
+        for _ in 0..2 {
+            let mut exhausted = false;
+
+            for intake in &mut self.intakes {
+                let stream = match &mut intake.stream {
+                    Some(stream) => stream,
+                    None => continue,
+                };
+                if intake.credit == 0 {
+                    exhausted = true;
+                    continue;
+                }
+                match stream.poll_next_unpin(cx) {
+                    Poll::Ready(Some(events)) => {
+                        intake.credit -= 1;
+                        return Poll::Ready(Some(events));
+                    }
+                    Poll::Ready(None) => intake.stream = None,
+                    Poll::Pending => {}
+                }
+            }
+
+            if !self.intakes.iter().any(|intake| intake.stream.is_some()) {
+                return Poll::Ready(None);
+            }
+            if !exhausted {
+                return Poll::Pending;
+            }
+            self.refill();
+        }
+
+        // Every open intake has been polled during the second pass.
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{channel::mpsc, stream};
+
+    use super::*;
+    use crate::{
+        config::ComponentKey,
+        event::{EventRef, LogEvent},
+    };
+
+    fn input(message: &str, count: usize) -> impl Stream<Item = EventArray> + Unpin {
+        let message = message.to_owned();
+        stream::iter((0..count).map(move |_| EventArray::from(LogEvent::from(message.as_str()))))
+    }
+
+    fn message(events: &EventArray) -> String {
+        match events.iter_events().next() {
+            Some(EventRef::Log(log)) => log["message"].to_string_lossy(),
+            _ => panic!("Expected a log."),
+        }
+    }
+
+    #[test]
+    fn weights_by_input_or_component() {
+        let priorities = IndexMap::from([("audit".to_owned(), 10), ("route.debug".to_owned(), 2)]);
+
+        assert_eq!(weight(&priorities, &OutputId::from("audit")), 10);
+        assert_eq!(
+            weight(
+                &priorities,
+                &OutputId::from((&ComponentKey::from("route"), "debug".to_owned()))
+            ),
+            2
+        );
+        assert_eq!(
+            weight(
+                &priorities,
+                &OutputId::from((&ComponentKey::from("route"), "info".to_owned()))
+            ),
+            DEFAULT_WEIGHT
+        );
+    }
+
+    #[tokio::test]
+    async fn consumes_by_weight_when_backlogged() {
+        let scheduler = Scheduler::new(vec![(1, input("debug", 4)), (3, input("audit", 6))]);
+        let order = scheduler.map(|events| message(&events)).collect::<Vec<_>>();
+
+        assert_eq!(
+            order.await,
+            [
+                "audit", "audit", "audit", "debug", "audit", "audit", "audit", "debug", "debug",
+                "debug"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn idle_inputs_do_not_hold_up_others() {
+        let (_audit_tx, audit_rx) = mpsc::unbounded::<EventArray>();
+        let scheduler =
+            Scheduler::new(vec![(10, audit_rx.boxed()), (1, input("debug", 3).boxed())]);
+        let order = scheduler
+            .take(3)
+            .map(|events| message(&events))
+            .collect::<Vec<_>>();
+
+        assert_eq!(order.await, ["debug", "debug", "debug"]);
+    }
+}
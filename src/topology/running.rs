@@ -17,7 +17,7 @@ use vector_common::trigger::DisabledTrigger;
 
 use super::{TapOutput, TapResource};
 use crate::{
-    config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource},
+    config::{ComponentKey, Config, ConfigDiff, HealthcheckOptions, OutputId, Resource, SinkOuter},
     event::EventArray,
     shutdown::SourceShutdownCoordinator,
     spawn_named,
@@ -25,7 +25,7 @@ use crate::{
         build_or_log_errors, builder,
         builder::Pieces,
         fanout::{ControlChannel, ControlMessage},
-        handle_errors, retain, take_healthchecks,
+        handle_errors, health, migration, retain, take_healthchecks,
         task::TaskOutput,
         BuiltBuffer, TaskHandle, WatchRx, WatchTx,
     },
@@ -34,6 +34,7 @@ use crate::{
 #[allow(dead_code)]
 pub struct RunningTopology {
     inputs: HashMap<ComponentKey, BufferSender<EventArray>>,
+    priority_inputs: HashMap<ComponentKey, HashMap<OutputId, BufferSender<EventArray>>>,
     outputs: HashMap<OutputId, ControlChannel>,
     source_tasks: HashMap<ComponentKey, TaskHandle>,
     tasks: HashMap<ComponentKey, TaskHandle>,
@@ -49,6 +50,7 @@ impl RunningTopology {
     pub fn new(config: Config, abort_tx: mpsc::UnboundedSender<()>) -> Self {
         Self {
             inputs: HashMap::new(),
+            priority_inputs: HashMap::new(),
             outputs: HashMap::new(),
             config,
             shutdown_coordinator: SourceShutdownCoordinator::default(),
@@ -220,7 +222,7 @@ impl RunningTopology {
         //
         // We also shutdown any component that is simply being removed entirely.
         let diff = ConfigDiff::new(&self.config, &new_config);
-        let buffers = self.shutdown_diff(&diff, &new_config).await;
+        let (buffers, migrations) = self.shutdown_diff(&diff, &new_config).await;
 
         // Gives windows some time to make available any port
         // released by shutdown components.
@@ -244,6 +246,7 @@ impl RunningTopology {
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
+                self.spawn_buffer_migrations(migrations, &new_config);
                 self.config = new_config;
 
                 info!("New configuration loaded successfully.");
@@ -257,7 +260,10 @@ impl RunningTopology {
         // bring back to restore the current configuration.
         warn!("Failed to completely load new configuration. Restoring old configuration.");
 
+        // The previous sinks of buffers marked for migration take their buffers back as they are.
         let diff = diff.flip();
+        let mut buffers = buffers;
+        buffers.extend(migrations);
         if let Some(mut new_pieces) = build_or_log_errors(&self.config, &diff, buffers).await {
             if self
                 .run_healthchecks(&diff, &mut new_pieces, self.config.healthchecks)
@@ -311,12 +317,16 @@ impl RunningTopology {
 
     /// Shuts down any changed/removed component in the given configuration diff.
     ///
-    /// If buffers for any of the changed/removed components can be recovered, they'll be returned.
+    /// If buffers for any of the changed/removed components can be recovered, they'll be returned,
+    /// along with the buffers of changed sinks whose contents are to be migrated to a new buffer.
     async fn shutdown_diff(
         &mut self,
         diff: &ConfigDiff,
         new_config: &Config,
-    ) -> HashMap<ComponentKey, BuiltBuffer> {
+    ) -> (
+        HashMap<ComponentKey, BuiltBuffer>,
+        HashMap<ComponentKey, BuiltBuffer>,
+    ) {
         // First, we shutdown any changed/removed sources. This ensures that we can allow downstream
         // components to terminate naturally by virtue of the flow of events stopping.
         if diff.sources.any_changed_or_removed() {
@@ -414,22 +424,39 @@ impl RunningTopology {
             .filter(|&(existing_sink, _)| existing_sink)
             .map(|(_, key)| key.clone());
 
-        // For any sink whose buffer configuration didn't change, we can reuse their buffer.
-        let reuse_buffers = diff
-            .sinks
-            .to_change
-            .iter()
-            .filter(|&key| {
-                self.config.sink(key).unwrap().buffer == new_config.sink(key).unwrap().buffer
-            })
-            .cloned()
-            .collect::<HashSet<_>>();
+        // For any sink whose buffer configuration didn't change, we can reuse their buffer, and
+        // for most sinks whose buffer configuration changed, we can migrate the contents of their
+        // buffer to the new one.
+        let mut reuse_buffers = HashSet::new();
+        let mut migrate_buffers = HashSet::new();
+        for key in &diff.sinks.to_change {
+            let (previous, new) = (
+                self.config.sink(key).unwrap(),
+                new_config.sink(key).unwrap(),
+            );
+            // The buffers of mirror sinks and of sinks with input priorities are fed by their
+            // intakes, so they are neither reused nor migrated.
+            let fed_by_intakes = |sink: &SinkOuter<OutputId>| {
+                sink.mirror.is_some() || !sink.input_priorities.is_empty()
+            };
+            if fed_by_intakes(previous) || fed_by_intakes(new) {
+                continue;
+            }
+
+            if previous.buffer == new.buffer {
+                reuse_buffers.insert(key.clone());
+            } else if migration::can_migrate(&previous.buffer, &new.buffer) {
+                migrate_buffers.insert(key.clone());
+            }
+        }
 
         // For any existing sink that has a conflicting resource dependency with a changed/added
-        // sink, or for any sink that we want to reuse their buffer, we need to explicit wait for
-        // them to finish processing so we can reclaim ownership of those resources/buffers.
+        // sink, or for any sink that we want to reuse or migrate their buffer, we need to explicit
+        // wait for them to finish processing so we can reclaim ownership of those
+        // resources/buffers.
         let wait_for_sinks = conflicting_sinks
             .chain(reuse_buffers.iter().cloned())
+            .chain(migrate_buffers.iter().cloned())
             .collect::<HashSet<_>>();
 
         // First, we remove any inputs to removed sinks so they can naturally shut down.
@@ -444,7 +471,7 @@ impl RunningTopology {
 
         for key in &diff.sinks.to_change {
             debug!(component = %key, "Changing sink.");
-            if reuse_buffers.contains(key) {
+            if reuse_buffers.contains(key) || migrate_buffers.contains(key) {
                 self.detach_triggers
                     .remove(key)
                     .unwrap()
@@ -483,13 +510,14 @@ impl RunningTopology {
         }
 
         let mut buffers = HashMap::<ComponentKey, BuiltBuffer>::new();
+        let mut migrations = HashMap::<ComponentKey, BuiltBuffer>::new();
         for key in &diff.sinks.to_change {
             if wait_for_sinks.contains(key) {
                 let previous = self.tasks.remove(key).unwrap();
                 debug!(message = "Waiting for sink to shutdown.", %key);
                 let buffer = previous.await.unwrap().unwrap();
 
-                if reuse_buffers.contains(key) {
+                if reuse_buffers.contains(key) || migrate_buffers.contains(key) {
                     // We clone instead of removing here because otherwise the input will be
                     // missing for the rest of the reload process, which violates the assumption
                     // that all previous inputs for components not being removed are still
@@ -503,12 +531,74 @@ impl RunningTopology {
                         _ => unreachable!(),
                     };
 
-                    buffers.insert(key.clone(), (tx, Arc::new(Mutex::new(Some(rx)))));
+                    let buffer = (tx, Arc::new(Mutex::new(Some(rx))));
+                    if reuse_buffers.contains(key) {
+                        buffers.insert(key.clone(), buffer);
+                    } else {
+                        migrations.insert(key.clone(), buffer);
+                    }
                 }
             }
         }
 
-        buffers
+        (buffers, migrations)
+    }
+
+    /// Relays the contents of the previous buffers of changed sinks into their new buffers.
+    ///
+    /// Must be called once the new sinks are connected, and before the running configuration is
+    /// replaced with `new_config`. The relays are tracked as tasks of their own, so that stopping
+    /// the topology waits for them to finish.
+    fn spawn_buffer_migrations(
+        &mut self,
+        migrations: HashMap<ComponentKey, BuiltBuffer>,
+        new_config: &Config,
+    ) {
+        for (key, (previous_tx, previous_rx)) in migrations {
+            // The previous buffer ends once its last sender is gone and its contents are drained.
+            drop(previous_tx);
+            let previous_rx = previous_rx
+                .lock()
+                .unwrap()
+                .take()
+                .expect("Migrated buffer was already taken");
+            let new_tx = self.inputs.get(&key).cloned().unwrap();
+
+            let (previous, new) = (
+                self.config.sink(&key).unwrap(),
+                new_config.sink(&key).unwrap(),
+            );
+            let span = error_span!(
+                "sink",
+                component_kind = "sink",
+                component_id = %key.id(),
+                component_type = %new.inner.sink_type(),
+                // maintained for compatibility
+                component_name = %key.id(),
+            );
+            let task_name = format!(">> {} ({}) buffer migration", new.inner.sink_type(), key);
+            let relay = migration::migrate(
+                previous_rx,
+                migration::buffer_type(&previous.buffer),
+                new_tx,
+                migration::buffer_type(&new.buffer),
+            )
+            .instrument(span.or_current());
+
+            // A migration left running by a previous reload is still waited for.
+            let task_key = key.join("buffer_migration");
+            let previous_migration = self.tasks.remove(&task_key);
+            let task = async move {
+                let previous_migration = async move {
+                    if let Some(handle) = previous_migration {
+                        let _ = handle.await;
+                    }
+                };
+                future::join(relay, previous_migration).await;
+                Ok(TaskOutput::BufferMigration)
+            };
+            self.tasks.insert(task_key, spawn_named(task, task_name.as_ref()));
+        }
     }
 
     /// Connects all changed/added components in the given configuration diff.
@@ -639,6 +729,7 @@ impl RunningTopology {
         new_pieces: &mut builder::Pieces,
     ) {
         let (tx, inputs) = new_pieces.inputs.remove(key).unwrap();
+        let priority_inputs = new_pieces.priority_inputs.remove(key);
 
         let old_inputs = self
             .config
@@ -653,6 +744,11 @@ impl RunningTopology {
 
         for input in inputs {
             let output = self.outputs.get_mut(&input).expect("unknown output");
+            // Components with input priorities receive each input through its own intake.
+            let tx = priority_inputs
+                .as_ref()
+                .and_then(|senders| senders.get(&input))
+                .unwrap_or(&tx);
 
             if diff.contains(&input.component) || inputs_to_add.contains(&input) {
                 // If the input we're connecting to is changing, that means its outputs will have been
@@ -673,6 +769,9 @@ impl RunningTopology {
         }
 
         self.inputs.insert(key.clone(), tx);
+        if let Some(priority_inputs) = priority_inputs {
+            self.priority_inputs.insert(key.clone(), priority_inputs);
+        }
         new_pieces
             .detach_triggers
             .remove(key)
@@ -685,6 +784,7 @@ impl RunningTopology {
 
     async fn remove_inputs(&mut self, key: &ComponentKey, diff: &ConfigDiff, new_config: &Config) {
         self.inputs.remove(key);
+        self.priority_inputs.remove(key);
         self.detach_triggers.remove(key);
 
         let old_inputs = self.config.inputs_for_node(key).expect("node exists");
@@ -735,7 +835,7 @@ impl RunningTopology {
             for output_id in changed_outputs {
                 debug!(component = %transform_key, fanout_id = %output_id.component, "Reattaching component input to fanout.");
 
-                let input = self.inputs.get(transform_key).cloned().unwrap();
+                let input = self.input_sender(transform_key, &output_id);
                 let output = self.outputs.get_mut(&output_id).unwrap();
                 let _ = output.send(ControlMessage::Add(transform_key.clone(), input));
             }
@@ -750,15 +850,35 @@ impl RunningTopology {
             for output_id in changed_outputs {
                 debug!(component = %sink_key, fanout_id = %output_id.component, "Reattaching component input to fanout.");
 
-                let input = self.inputs.get(sink_key).cloned().unwrap();
+                let input = self.input_sender(sink_key, &output_id);
                 let output = self.outputs.get_mut(&output_id).unwrap();
                 let _ = output.send(ControlMessage::Add(sink_key.clone(), input));
             }
         }
     }
 
+    /// Returns the sender through which the component receives the events of the input.
+    fn input_sender(&self, key: &ComponentKey, input: &OutputId) -> BufferSender<EventArray> {
+        self.priority_inputs
+            .get(key)
+            .and_then(|senders| senders.get(input))
+            .or_else(|| self.inputs.get(key))
+            .cloned()
+            .unwrap()
+    }
+
     /// Starts any new or changed components in the given configuration diff.
     pub(crate) fn spawn_diff(&mut self, diff: &ConfigDiff, mut new_pieces: Pieces) {
+        for key in diff
+            .sources
+            .to_remove
+            .iter()
+            .chain(&diff.transforms.to_remove)
+            .chain(&diff.sinks.to_remove)
+        {
+            health::remove(key);
+        }
+
         for key in &diff.sources.to_change {
             debug!(message = "Spawning changed source.", key = %key);
             self.spawn_source(key, &mut new_pieces);
@@ -801,7 +921,8 @@ impl RunningTopology {
             component_name = %task.id(),
         );
         let task_name = format!(">> {} ({})", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(span.or_current());
+        let task =
+            handle_errors(key.clone(), task, self.abort_tx.clone()).instrument(span.or_current());
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -819,7 +940,10 @@ impl RunningTopology {
             component_name = %task.id(),
         );
         let task_name = format!(">> {} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(span.or_current());
+        health::starting(key);
+        health::running(key);
+        let task =
+            handle_errors(key.clone(), task, self.abort_tx.clone()).instrument(span.or_current());
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -837,7 +961,10 @@ impl RunningTopology {
             component_name = %task.id(),
         );
         let task_name = format!("{} ({}) >>", task.typetag(), task.id());
-        let task = handle_errors(task, self.abort_tx.clone()).instrument(span.clone().or_current());
+        health::starting(key);
+        health::running(key);
+        let task = handle_errors(key.clone(), task, self.abort_tx.clone())
+            .instrument(span.clone().or_current());
         let spawned = spawn_named(task, task_name.as_ref());
         if let Some(previous) = self.tasks.insert(key.clone(), spawned) {
             drop(previous); // detach and forget
@@ -847,8 +974,8 @@ impl RunningTopology {
             .takeover_source(key, &mut new_pieces.shutdown_coordinator);
 
         let source_task = new_pieces.source_tasks.remove(key).unwrap();
-        let source_task =
-            handle_errors(source_task, self.abort_tx.clone()).instrument(span.or_current());
+        let source_task = handle_errors(key.clone(), source_task, self.abort_tx.clone())
+            .instrument(span.or_current());
         self.source_tasks
             .insert(key.clone(), spawn_named(source_task, task_name.as_ref()));
     }
@@ -19,6 +19,8 @@ pub(crate) enum TaskOutput {
     /// Buffer of sink
     Sink(Utilization<BufferReceiverStream<EventArray>>),
     Healthcheck,
+    /// Relay of the previous buffer of a sink into its new one
+    BufferMigration,
 }
 
 /// High level topology task.
@@ -537,6 +537,78 @@ async fn topology_swap_sink() {
     assert_eq!(vec![event1], res2);
 }
 
+#[tokio::test]
+async fn topology_migrates_changed_sink_buffer() {
+    trace_init();
+
+    let memory_buffer = |max_events| BufferConfig {
+        stages: vec![BufferType::Memory {
+            max_events: std::num::NonZeroUsize::new(max_events).unwrap(),
+            when_full: WhenFull::Block,
+        }],
+    };
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink_with_data(1, "v1");
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    let mut sink1_outer = SinkOuter::new(vec![String::from("in1")], Box::new(sink1));
+    sink1_outer.buffer = memory_buffer(10);
+    config.add_sink_outer("out1", sink1_outer);
+
+    let (mut topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    // Nothing reads from the first sink yet, so the events pile up in its buffer.
+    let events = (0..5)
+        .map(|i| Event::Log(LogEvent::from(format!("event {}", i))))
+        .collect::<Vec<_>>();
+    for event in events.clone() {
+        in1.send_event(event).await.unwrap();
+    }
+    sleep(Duration::from_millis(50)).await;
+
+    let (_in2, source2) = basic_source();
+    let (out2, sink2) = basic_sink_with_data(10, "v2");
+
+    let mut config = Config::builder();
+    config.add_source("in1", source2);
+    let mut sink2_outer = SinkOuter::new(vec![String::from("in1")], Box::new(sink2));
+    sink2_outer.buffer = memory_buffer(20);
+    config.add_sink_outer("out1", sink2_outer);
+
+    // The first sink is detached from its buffer as the reload starts, and is only let go of the
+    // events it already holds once the reload waits for it to stop.
+    let h_out2 = tokio::spawn(out2.flat_map(into_event_stream).collect::<Vec<_>>());
+    let (reloaded, res1) = future::join(
+        topology.reload_config_and_respawn(config.build().unwrap()),
+        async {
+            sleep(Duration::from_millis(50)).await;
+            out1.flat_map(into_event_stream).collect::<Vec<_>>().await
+        },
+    )
+    .await;
+    assert!(reloaded.unwrap());
+
+    drop(in1);
+    topology.stop().await;
+    let res2 = h_out2.await.unwrap();
+
+    // The events the first sink didn't get to are delivered by the new one.
+    assert!(!res2.is_empty());
+    let mut received = res1
+        .into_iter()
+        .chain(res2)
+        .map(|event| event.as_log()["message"].to_string_lossy())
+        .collect::<Vec<_>>();
+    received.sort();
+    let expected = events
+        .iter()
+        .map(|event| event.as_log()["message"].to_string_lossy())
+        .collect::<Vec<_>>();
+    assert_eq!(received, expected);
+}
+
 #[ignore] // TODO: issue #2186
 #[tokio::test]
 async fn topology_swap_transform_is_atomic() {
@@ -83,6 +83,7 @@ async fn closed_source() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
     old_config.add_sink(
@@ -92,6 +93,7 @@ async fn closed_source() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -110,6 +112,7 @@ async fn closed_source() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -143,6 +146,7 @@ async fn remove_sink() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
     old_config.add_sink(
@@ -152,6 +156,7 @@ async fn remove_sink() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -169,6 +174,7 @@ async fn remove_sink() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -202,6 +208,7 @@ async fn remove_transform() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
     old_config.add_sink(
@@ -211,6 +218,7 @@ async fn remove_transform() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -228,6 +236,7 @@ async fn remove_transform() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -257,6 +266,7 @@ async fn replace_transform() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
@@ -276,6 +286,7 @@ async fn replace_transform() {
             print_interval_secs: 10,
             rate: None,
             acknowledgements: Default::default(),
+            ..Default::default()
         },
     );
 
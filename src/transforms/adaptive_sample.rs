@@ -0,0 +1,407 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use snafu::Snafu;
+use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::Event,
+    internal_events::{SampleEventDiscarded, TemplateRenderingError},
+    schema,
+    template::Template,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `adaptive_sample` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct AdaptiveSampleConfig {
+    /// The number of events per second to forward for a given bucket.
+    ///
+    /// At least one of `target_events_per_sec` and `target_bytes_per_sec` must be set. If both are set, the sampling
+    /// rate is the one meeting both targets.
+    pub target_events_per_sec: Option<f64>,
+
+    /// The number of bytes per second to forward for a given bucket.
+    ///
+    /// The size of an event is its estimated size in memory.
+    pub target_bytes_per_sec: Option<f64>,
+
+    /// The time window over which the volume of a bucket is measured, in seconds.
+    ///
+    /// The sampling rate of each bucket is adjusted at the end of each window, based on the volume it received during
+    /// that window. All events are forwarded during the first window of a bucket. Must be at most `4294967295`.
+    #[derivative(Default(value = "default_window_secs()"))]
+    #[serde(default = "default_window_secs")]
+    pub window_secs: f64,
+
+    /// The name of the log field whose value will be used to group events into buckets, such as `{{ service }}`.
+    ///
+    /// Each bucket is sampled separately to meet the targets. If left unspecified, or if the event doesn’t have
+    /// `key_field`, the event will be sampled with all the other events without key.
+    #[configurable(metadata(templatable))]
+    pub key_field: Option<Template>,
+
+    /// The name of the field in which the effective sampling rate of each forwarded event is stored.
+    ///
+    /// The rate is expressed as `N` for `1/N` of the events being forwarded, so that counts of forwarded events can be
+    /// extrapolated by multiplying by the rate.
+    #[derivative(Default(value = "default_rate_field()"))]
+    #[serde(default = "default_rate_field")]
+    pub rate_field: String,
+
+    /// A logical condition used to exclude events from sampling.
+    pub exclude: Option<AnyCondition>,
+}
+
+const fn default_window_secs() -> f64 {
+    10.0
+}
+
+/// The longest window, which keeps the two windows after which buckets expire within a `Duration`.
+const MAX_WINDOW_SECS: f64 = u32::MAX as f64;
+
+fn default_rate_field() -> String {
+    "sample_rate".to_owned()
+}
+
+inventory::submit! {
+    TransformDescription::new::<AdaptiveSampleConfig>("adaptive_sample")
+}
+
+impl_generate_config_from_default!(AdaptiveSampleConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "adaptive_sample")]
+impl TransformConfig for AdaptiveSampleConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        AdaptiveSample::new(self, context).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "adaptive_sample"
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display(
+        "at least one of `target_events_per_sec` and `target_bytes_per_sec` must be set"
+    ))]
+    MissingTarget,
+
+    #[snafu(display(
+        "`target_events_per_sec`, `target_bytes_per_sec` and `window_secs` must be positive and finite"
+    ))]
+    NonPositive,
+
+    #[snafu(display("`window_secs` must be at most {}", MAX_WINDOW_SECS))]
+    WindowTooLong,
+}
+
+/// The volume received by a bucket during the current window, and the sampling state.
+#[derive(Clone, Debug)]
+struct Bucket {
+    window_start: Instant,
+    events: u64,
+    bytes: u64,
+    forwarded: u64,
+    rate: f64,
+}
+
+impl Bucket {
+    const fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            events: 0,
+            bytes: 0,
+            forwarded: 0,
+            rate: 1.0,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AdaptiveSample {
+    target_events_per_sec: Option<f64>,
+    target_bytes_per_sec: Option<f64>,
+    window: Duration,
+    key_field: Option<Template>,
+    rate_field: String,
+    exclude: Option<Condition>,
+    buckets: HashMap<Option<String>, Bucket>,
+    last_flush: Option<Instant>,
+}
+
+impl AdaptiveSample {
+    pub fn new(config: &AdaptiveSampleConfig, context: &TransformContext) -> crate::Result<Self> {
+        if config.target_events_per_sec.is_none() && config.target_bytes_per_sec.is_none() {
+            return Err(Box::new(ConfigError::MissingTarget));
+        }
+        let targets = [config.target_events_per_sec, config.target_bytes_per_sec];
+        let positive = |value: f64| value > 0.0 && value.is_finite();
+        if !positive(config.window_secs) || !targets.iter().flatten().copied().all(positive) {
+            return Err(Box::new(ConfigError::NonPositive));
+        }
+        if config.window_secs > MAX_WINDOW_SECS {
+            return Err(Box::new(ConfigError::WindowTooLong));
+        }
+
+        let exclude = config
+            .exclude
+            .as_ref()
+            .map(|condition| condition.build(&context.enrichment_tables))
+            .transpose()?;
+
+        Ok(Self {
+            target_events_per_sec: config.target_events_per_sec,
+            target_bytes_per_sec: config.target_bytes_per_sec,
+            window: Duration::from_secs_f64(config.window_secs),
+            key_field: config.key_field.clone(),
+            rate_field: config.rate_field.clone(),
+            exclude,
+            buckets: HashMap::new(),
+            last_flush: None,
+        })
+    }
+
+    /// Returns the rate at which the event is sampled, or `None` if the event is dropped.
+    fn sample(&mut self, key: Option<String>, size: usize, now: Instant) -> Option<f64> {
+        self.flush_buckets(now);
+
+        let bucket = self.buckets.entry(key).or_insert_with(|| Bucket::new(now));
+
+        let elapsed = now.saturating_duration_since(bucket.window_start);
+        if elapsed >= self.window {
+            let secs = elapsed.as_secs_f64();
+            let events_rate = self
+                .target_events_per_sec
+                .map_or(1.0, |target| bucket.events as f64 / secs / target);
+            let bytes_rate = self
+                .target_bytes_per_sec
+                .map_or(1.0, |target| bucket.bytes as f64 / secs / target);
+
+            bucket.rate = events_rate.max(bytes_rate).max(1.0);
+            bucket.window_start = now;
+            bucket.events = 0;
+            bucket.bytes = 0;
+            bucket.forwarded = 0;
+        }
+
+        bucket.events += 1;
+        bucket.bytes += size as u64;
+
+        // Forwarding an event each time `rate` more events are received forwards exactly `1/rate`
+        // of the events, without the variance of random sampling.
+        if (bucket.events as f64 / bucket.rate) as u64 > bucket.forwarded {
+            bucket.forwarded += 1;
+            Some(bucket.rate)
+        } else {
+            None
+        }
+    }
+
+    /// Removes the buckets that didn't receive any events in the last two windows.
+    fn flush_buckets(&mut self, now: Instant) {
+        let expiry = self.window * 2;
+        match self.last_flush {
+            Some(last_flush) if now.saturating_duration_since(last_flush) < expiry => {}
+            _ => {
+                self.buckets.retain(|_, bucket| {
+                    now.saturating_duration_since(bucket.window_start) < expiry
+                });
+                self.last_flush = Some(now);
+            }
+        }
+    }
+
+    fn transform_at(&mut self, output: &mut OutputBuffer, event: Event, now: Instant) {
+        let mut event = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                if result {
+                    output.push(event);
+                    return;
+                }
+                event
+            }
+            None => event,
+        };
+
+        let key = self.key_field.as_ref().and_then(|template| {
+            template
+                .render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        match self.sample(key, event.size_of(), now) {
+            Some(rate) => {
+                event.as_mut_log().insert(self.rate_field.as_str(), rate);
+                output.push(event);
+            }
+            None => emit!(SampleEventDiscarded),
+        }
+    }
+}
+
+impl FunctionTransform for AdaptiveSample {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        self.transform_at(output, event, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AdaptiveSampleConfig>();
+    }
+
+    fn sampler(config: &str) -> AdaptiveSample {
+        let config = toml::from_str::<AdaptiveSampleConfig>(config).unwrap();
+        AdaptiveSample::new(&config, &TransformContext::default()).unwrap()
+    }
+
+    /// Sends `count` events evenly spread over one second, returning those forwarded.
+    fn send(
+        sampler: &mut AdaptiveSample,
+        start: Instant,
+        count: u32,
+        event: impl Fn() -> LogEvent,
+    ) -> Vec<Event> {
+        let mut output = OutputBuffer::default();
+        for i in 0..count {
+            let now = start + Duration::from_secs(1) * i / count;
+            sampler.transform_at(&mut output, event().into(), now);
+        }
+        output.into_events().collect()
+    }
+
+    #[test]
+    fn adjusts_rate_to_target_events() {
+        let mut sampler = sampler(
+            r#"
+target_events_per_sec = 10
+window_secs = 1
+"#,
+        );
+        let start = Instant::now();
+
+        // Everything is forwarded until the volume is measured.
+        let forwarded = send(&mut sampler, start, 100, LogEvent::default);
+        assert_eq!(forwarded.len(), 100);
+        assert_eq!(
+            forwarded[0].as_log().get("sample_rate"),
+            Some(&Value::from(1.0))
+        );
+
+        let forwarded = send(
+            &mut sampler,
+            start + Duration::from_secs(1),
+            100,
+            LogEvent::default,
+        );
+        assert_eq!(forwarded.len(), 10);
+        assert_eq!(
+            forwarded[0].as_log().get("sample_rate"),
+            Some(&Value::from(10.0))
+        );
+    }
+
+    #[test]
+    fn adjusts_rate_to_target_bytes() {
+        let size = Event::from(LogEvent::from("message")).size_of();
+        let mut sampler = sampler(&format!(
+            r#"
+target_bytes_per_sec = {}
+window_secs = 1
+rate_field = "rate"
+"#,
+            size * 25
+        ));
+        let start = Instant::now();
+
+        send(&mut sampler, start, 100, || LogEvent::from("message"));
+        let forwarded = send(&mut sampler, start + Duration::from_secs(1), 100, || {
+            LogEvent::from("message")
+        });
+        assert_eq!(forwarded.len(), 25);
+        assert_eq!(forwarded[0].as_log().get("rate"), Some(&Value::from(4.0)));
+    }
+
+    #[test]
+    fn samples_buckets_separately() {
+        let mut sampler = sampler(
+            r#"
+target_events_per_sec = 10
+window_secs = 1
+key_field = "{{ service }}"
+"#,
+        );
+        let start = Instant::now();
+        let service = |name: &'static str| {
+            move || {
+                let mut log = LogEvent::default();
+                log.insert("service", name);
+                log
+            }
+        };
+
+        send(&mut sampler, start, 100, service("busy"));
+        send(&mut sampler, start, 5, service("quiet"));
+
+        let start = start + Duration::from_secs(1);
+        assert_eq!(send(&mut sampler, start, 100, service("busy")).len(), 10);
+        assert_eq!(send(&mut sampler, start, 5, service("quiet")).len(), 5);
+    }
+
+    #[test]
+    fn requires_target() {
+        let config = toml::from_str::<AdaptiveSampleConfig>("window_secs = 1").unwrap();
+        assert!(AdaptiveSample::new(&config, &TransformContext::default()).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_windows_and_targets() {
+        for config in [
+            "target_events_per_sec = 10\nwindow_secs = 0",
+            "target_events_per_sec = 10\nwindow_secs = nan",
+            "target_events_per_sec = 10\nwindow_secs = inf",
+            "target_events_per_sec = 10\nwindow_secs = 1e300",
+            "target_events_per_sec = inf",
+            "target_events_per_sec = 10\ntarget_bytes_per_sec = nan",
+        ] {
+            let parsed = toml::from_str::<AdaptiveSampleConfig>(config).unwrap();
+            assert!(
+                AdaptiveSample::new(&parsed, &TransformContext::default()).is_err(),
+                "{}",
+                config
+            );
+        }
+    }
+}
@@ -7,11 +7,14 @@ use std::{
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
 
 use crate::{
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
     event::{metric, Event, EventMetadata},
     internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
+    memory_budget::MemoryUsage,
+    metrics::hyperloglog::{MAX_PRECISION, MIN_PRECISION},
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -26,6 +29,13 @@ pub struct AggregateConfig {
     /// Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    /// The precision of the HyperLogLog sets are aggregated into.
+    ///
+    /// When set, sets are aggregated approximately, bounding their memory to `2^set_precision` bytes per series at
+    /// the cost of a standard error of `1.04 / sqrt(2^set_precision)` on their number of unique values. Must be between
+    /// 4 and 16.
+    pub set_precision: Option<u8>,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -41,8 +51,9 @@ impl_generate_config_from_default!(AggregateConfig);
 #[async_trait::async_trait]
 #[typetag::serde(name = "aggregate")]
 impl TransformConfig for AggregateConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Aggregate::new(self).map(Transform::event_task)
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let memory_usage = MemoryUsage::register(context.key.as_ref());
+        Aggregate::new(self, memory_usage).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -63,46 +74,95 @@ type MetricEntry = (metric::MetricData, EventMetadata);
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
+    set_precision: Option<u8>,
     map: BTreeMap<metric::MetricSeries, MetricEntry>,
+    map_byte_size: usize,
+    memory_usage: MemoryUsage,
 }
 
 impl Aggregate {
-    pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
+    pub fn new(config: &AggregateConfig, memory_usage: MemoryUsage) -> crate::Result<Self> {
+        if let Some(precision) = config.set_precision {
+            if !(MIN_PRECISION..=MAX_PRECISION).contains(&precision) {
+                return Err(format!(
+                    "`set_precision` must be between {} and {}",
+                    MIN_PRECISION, MAX_PRECISION
+                )
+                .into());
+            }
+        }
+
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
+            set_precision: config.set_precision,
             map: BTreeMap::new(),
+            map_byte_size: 0,
+            memory_usage,
         })
     }
 
     fn record(&mut self, event: Event) {
         let (series, data, metadata) = event.into_metric().into_parts();
 
+        // The size of the aggregated series is estimated from the size of the metrics stored,
+        // growing with the updates to the values that grow when aggregated.
         match data.kind {
             metric::MetricKind::Incremental => match self.map.entry(series) {
                 Entry::Occupied(mut entry) => {
                     let existing = entry.get_mut();
+                    let added_bytes = if grows_on_update(&data.value) {
+                        data.size_of()
+                    } else {
+                        0
+                    };
                     // In order to update (add) the new and old kind's must match
                     if existing.0.kind == data.kind && existing.0.update(&data) {
                         existing.1.merge(metadata);
+                        self.map_byte_size += added_bytes;
                     } else {
                         emit!(AggregateUpdateFailed);
+                        let data = approximate(self.set_precision, data);
+                        self.map_byte_size = (self.map_byte_size + data.size_of())
+                            .saturating_sub(existing.0.size_of());
                         *existing = (data, metadata);
                     }
                 }
                 Entry::Vacant(entry) => {
+                    let data = approximate(self.set_precision, data);
+                    self.map_byte_size += entry.key().size_of() + data.size_of();
                     entry.insert((data, metadata));
                 }
             },
             metric::MetricKind::Absolute => {
                 // Always replace/store
-                self.map.insert(series, (data, metadata));
+                let data = approximate(self.set_precision, data);
+                let series_bytes = series.size_of();
+                self.map_byte_size += data.size_of();
+                match self.map.insert(series, (data, metadata)) {
+                    Some((previous, _)) => {
+                        self.map_byte_size =
+                            self.map_byte_size.saturating_sub(previous.size_of());
+                    }
+                    None => self.map_byte_size += series_bytes,
+                }
             }
         };
 
+        self.memory_usage.set(self.map_byte_size);
         emit!(AggregateEventRecorded);
     }
 
+    /// Flushes early when the memory budget asks for it, releasing all of the state at once.
+    fn trim_into(&mut self, output: &mut Vec<Event>) {
+        if self.memory_usage.should_trim() {
+            self.memory_usage.trimmed(0);
+            self.flush_into(output);
+        }
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
+        self.map_byte_size = 0;
+        self.memory_usage.set(0);
         let map = std::mem::take(&mut self.map);
         for (series, entry) in map.into_iter() {
             let metric = metric::Metric::from_parts(series, entry.0, entry.1);
@@ -113,6 +173,25 @@ impl Aggregate {
     }
 }
 
+/// Whether the size of the aggregated value grows with the values added to it.
+const fn grows_on_update(value: &metric::MetricValue) -> bool {
+    matches!(
+        value,
+        metric::MetricValue::Set { .. } | metric::MetricValue::Distribution { .. }
+    )
+}
+
+/// Converts sets into approximate sets when they're aggregated approximately. Sets added to an
+/// approximate set are folded into it, so only the sets stored are converted.
+fn approximate(set_precision: Option<u8>, mut data: metric::MetricData) -> metric::MetricData {
+    if let Some(precision) = set_precision {
+        if let Some(value) = data.value.set_to_sketch(precision) {
+            data.value = value;
+        }
+    }
+    data
+}
+
 impl TaskTransform<Event> for Aggregate {
     fn transform(
         mut self: Box<Self>,
@@ -122,6 +201,7 @@ impl TaskTransform<Event> for Aggregate {
         Self: 'static,
     {
         let mut flush_stream = tokio::time::interval(self.interval);
+        let trims = self.memory_usage.trim_signal();
 
         Box::pin(stream! {
             let mut output = Vec::new();
@@ -131,13 +211,19 @@ impl TaskTransform<Event> for Aggregate {
                     _ = flush_stream.tick() => {
                         self.flush_into(&mut output);
                     },
+                    _ = trims.requested() => {
+                        self.trim_into(&mut output);
+                    },
                     maybe_event = input_rx.next() => {
                         match maybe_event {
                             None => {
                                 self.flush_into(&mut output);
                                 done = true;
                             }
-                            Some(event) => self.record(event),
+                            Some(event) => {
+                                self.record(event);
+                                self.trim_into(&mut output);
+                            }
                         }
                     }
                 };
@@ -173,9 +259,13 @@ mod tests {
 
     #[test]
     fn incremental() {
-        let mut agg = Aggregate::new(&AggregateConfig {
-            interval_ms: 1000_u64,
-        })
+        let mut agg = Aggregate::new(
+            &AggregateConfig {
+                interval_ms: 1000_u64,
+                set_precision: None,
+            },
+            MemoryUsage::register(None),
+        )
         .unwrap();
 
         let counter_a_1 = make_metric(
@@ -243,9 +333,13 @@ mod tests {
 
     #[test]
     fn absolute() {
-        let mut agg = Aggregate::new(&AggregateConfig {
-            interval_ms: 1000_u64,
-        })
+        let mut agg = Aggregate::new(
+            &AggregateConfig {
+                interval_ms: 1000_u64,
+                set_precision: None,
+            },
+            MemoryUsage::register(None),
+        )
         .unwrap();
 
         let gauge_a_1 = make_metric(
@@ -306,11 +400,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn approximate_sets() {
+        let mut agg = Aggregate::new(
+            &AggregateConfig {
+                interval_ms: 1000_u64,
+                set_precision: Some(12),
+            },
+            MemoryUsage::register(None),
+        )
+        .unwrap();
+
+        for values in [["a", "b"], ["b", "c"]] {
+            agg.record(make_metric(
+                "users",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Set {
+                    values: values.into_iter().map(Into::into).collect(),
+                },
+            ));
+        }
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        match out[0].as_metric().value() {
+            metric::MetricValue::Sketch {
+                sketch: metric::MetricSketch::HyperLogLog(hll),
+            } => assert_eq!(hll.estimate().round(), 3.0),
+            value => panic!("Expected an approximate set, got {:?}.", value),
+        }
+
+        assert!(Aggregate::new(
+            &AggregateConfig {
+                interval_ms: 1000_u64,
+                set_precision: Some(20),
+            },
+            MemoryUsage::register(None),
+        )
+        .is_err());
+    }
+
     #[test]
     fn conflicting_value_type() {
-        let mut agg = Aggregate::new(&AggregateConfig {
-            interval_ms: 1000_u64,
-        })
+        let mut agg = Aggregate::new(
+            &AggregateConfig {
+                interval_ms: 1000_u64,
+                set_precision: None,
+            },
+            MemoryUsage::register(None),
+        )
         .unwrap();
 
         let counter = make_metric(
@@ -365,9 +504,13 @@ mod tests {
 
     #[test]
     fn conflicting_kinds() {
-        let mut agg = Aggregate::new(&AggregateConfig {
-            interval_ms: 1000_u64,
-        })
+        let mut agg = Aggregate::new(
+            &AggregateConfig {
+                interval_ms: 1000_u64,
+                set_precision: None,
+            },
+            MemoryUsage::register(None),
+        )
         .unwrap();
 
         let incremental = make_metric(
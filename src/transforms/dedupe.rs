@@ -1,9 +1,11 @@
-use std::{future::ready, pin::Pin};
+use std::pin::Pin;
 
+use async_stream::stream;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
 use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
 
 use crate::{
     config::{
@@ -12,6 +14,7 @@ use crate::{
     },
     event::{Event, Value},
     internal_events::DedupeEventDiscarded,
+    memory_budget::MemoryUsage,
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -81,6 +84,8 @@ impl DedupeConfig {
 pub struct Dedupe {
     fields: FieldMatchConfig,
     cache: LruCache<CacheEntry, bool>,
+    cache_byte_size: usize,
+    memory_usage: MemoryUsage,
 }
 
 inventory::submit! {
@@ -100,8 +105,9 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(self.clone())))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let memory_usage = MemoryUsage::register(context.key.as_ref());
+        Ok(Transform::event_task(Dedupe::new(self.clone(), memory_usage)))
     }
 
     fn input(&self) -> Input {
@@ -148,6 +154,28 @@ enum CacheEntry {
     Ignore(Vec<(String, TypeId, Bytes)>),
 }
 
+impl ByteSizeOf for CacheEntry {
+    fn allocated_bytes(&self) -> usize {
+        match self {
+            Self::Match(fields) => {
+                fields.capacity() * std::mem::size_of::<Option<(TypeId, Bytes)>>()
+                    + fields
+                        .iter()
+                        .flatten()
+                        .map(|(_, bytes)| bytes.len())
+                        .sum::<usize>()
+            }
+            Self::Ignore(fields) => {
+                fields.capacity() * std::mem::size_of::<(String, TypeId, Bytes)>()
+                    + fields
+                        .iter()
+                        .map(|(name, _, bytes)| name.capacity() + bytes.len())
+                        .sum::<usize>()
+            }
+        }
+    }
+}
+
 /// Assigns a unique number to each of the types supported by Event::Value.
 const fn type_id_for_value(val: &Value) -> TypeId {
     match val {
@@ -164,22 +192,58 @@ const fn type_id_for_value(val: &Value) -> TypeId {
 }
 
 impl Dedupe {
-    pub fn new(config: DedupeConfig) -> Self {
+    pub fn new(config: DedupeConfig, memory_usage: MemoryUsage) -> Self {
         let num_entries = config.cache.num_events;
         let fields = config.fill_default_fields_match();
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            cache_byte_size: 0,
+            memory_usage,
         }
     }
 
     fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        if self.cache.get(&cache_entry).is_some() {
             emit!(DedupeEventDiscarded { event });
-            None
-        } else {
-            Some(event)
+            return None;
+        }
+
+        if self.cache.len() == self.cache.cap() {
+            self.evict_lru();
+        }
+        if self.cache.cap() != 0 {
+            self.cache_byte_size += cache_entry.size_of();
+            self.cache.put(cache_entry, true);
+        }
+
+        self.memory_usage.set(self.cache_byte_size);
+        self.trim();
+
+        Some(event)
+    }
+
+    /// Forgets the least recently seen half of the cache, as many times as the memory budget asks
+    /// for it.
+    fn trim(&mut self) {
+        let (cache, cache_byte_size) = (&mut self.cache, &mut self.cache_byte_size);
+        self.memory_usage.trim_with(|| {
+            if cache.is_empty() {
+                return None;
+            }
+            for _ in 0..(cache.len() / 2).max(1) {
+                if let Some((evicted, _)) = cache.pop_lru() {
+                    *cache_byte_size -= evicted.size_of();
+                }
+            }
+            Some(*cache_byte_size)
+        });
+    }
+
+    fn evict_lru(&mut self) {
+        if let Some((evicted, _)) = self.cache.pop_lru() {
+            self.cache_byte_size -= evicted.size_of();
         }
     }
 }
@@ -223,23 +287,42 @@ fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> CacheEntry {
 impl TaskTransform<Event> for Dedupe {
     fn transform(
         self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        let mut me = self;
+        let trims = me.memory_usage.trim_signal();
+
+        Box::pin(stream! {
+            loop {
+                let event = tokio::select! {
+                    _ = trims.requested() => {
+                        me.trim();
+                        continue;
+                    }
+                    maybe_event = input_rx.next() => match maybe_event {
+                        None => break,
+                        Some(event) => me.transform_one(event),
+                    },
+                };
+                if let Some(event) = event {
+                    yield event;
+                }
+            }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
+    use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
     use super::*;
     use crate::{
         event::{Event, LogEvent, Value},
+        memory_budget::Budget,
         transforms::dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
     };
 
@@ -249,10 +332,13 @@ mod tests {
     }
 
     fn make_match_transform(num_events: usize, fields: Vec<String>) -> Dedupe {
-        Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
-            fields: Some(FieldMatchConfig::MatchFields(fields)),
-        })
+        Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig { num_events },
+                fields: Some(FieldMatchConfig::MatchFields(fields)),
+            },
+            MemoryUsage::register(None),
+        )
     }
 
     fn make_ignore_transform(num_events: usize, given_fields: Vec<String>) -> Dedupe {
@@ -260,10 +346,13 @@ mod tests {
         let mut fields = vec!["message".into(), "timestamp".into()];
         fields.extend(given_fields);
 
-        Dedupe::new(DedupeConfig {
-            cache: CacheConfig { num_events },
-            fields: Some(FieldMatchConfig::IgnoreFields(fields)),
-        })
+        Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig { num_events },
+                fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+            },
+            MemoryUsage::register(None),
+        )
     }
 
     #[test]
@@ -502,4 +591,41 @@ mod tests {
         let new_event = transform.transform_one(event2.clone()).unwrap();
         assert_eq!(new_event, event2);
     }
+
+    #[tokio::test]
+    async fn waiting_source_makes_progress_over_memory_budget() {
+        let budget = Arc::new(Budget::default());
+        let transform = Box::new(Dedupe::new(
+            DedupeConfig {
+                cache: CacheConfig { num_events: 100 },
+                fields: Some(FieldMatchConfig::MatchFields(vec!["id".into()])),
+            },
+            MemoryUsage::register_with(&budget, None),
+        ));
+        let event = |id: usize| {
+            let mut event = Event::Log(LogEvent::from("message"));
+            event.as_mut_log().insert("id", id.to_string());
+            event
+        };
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        let mut output = transform.transform(Box::pin(rx));
+        for id in 0..10 {
+            tx.unbounded_send(event(id)).unwrap();
+            assert!(output.next().await.is_some());
+        }
+
+        // The cache is now over the budget, and no event will reach the transform to trim it
+        // before the source gets room to send one.
+        budget.set_limits(1, 1);
+        tokio::select! {
+            _ = budget.wait_for_room() => {},
+            _ = output.next() => panic!("Unexpected output while waiting for room."),
+            _ = tokio::time::sleep(Duration::from_secs(5)) => panic!("Source never got room."),
+        }
+
+        // The cache was trimmed, so the first event isn't a duplicate anymore.
+        tx.unbounded_send(event(0)).unwrap();
+        assert!(output.next().await.is_some());
+    }
 }
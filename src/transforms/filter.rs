@@ -110,8 +110,11 @@ impl FunctionTransform for Filter {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::event::{Event, LogEvent};
-    use crate::{conditions::Condition, transforms::test::transform_one};
+    use crate::event::{Event, LogEvent, TraceEvent};
+    use crate::{
+        conditions::{Condition, ConditionalConfig, VrlConfig},
+        transforms::test::transform_one,
+    };
 
     #[test]
     fn generate_config() {
@@ -126,4 +129,23 @@ mod test {
         let result = transform_one(&mut filter, event).unwrap();
         assert_eq!(result.metadata(), &metadata);
     }
+
+    #[test]
+    fn filters_traces_on_span_attributes() {
+        let condition = VrlConfig {
+            source: r#".resource == "GET /health""#.to_owned(),
+            runtime: Default::default(),
+        }
+        .build(&Default::default())
+        .unwrap();
+        let mut filter = Filter::new(condition);
+
+        let mut trace = TraceEvent::default();
+        trace.insert("resource", "GET /health");
+        assert!(transform_one(&mut filter, trace.into()).is_some());
+
+        let mut trace = TraceEvent::default();
+        trace.insert("resource", "GET /users");
+        assert!(transform_one(&mut filter, trace.into()).is_none());
+    }
 }
@@ -0,0 +1,250 @@
+use lookup::{
+    lookup_v2::{parse_path, OwnedPath},
+    path,
+};
+use value::Kind;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        log_schema, DataType, Input, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, LogEvent, Value},
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `log_namespace_shim` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct LogNamespaceShimConfig {
+    /// Fields set by the source, moved to the source metadata under the source type, such as `host`.
+    ///
+    /// Fields are paths, so `kubernetes.pod_name` is moved to `%<source type>.kubernetes.pod_name`.
+    ///
+    /// The source type and timestamp fields of the global log schema are always moved to the Vector metadata, as
+    /// `%vector.source_type` and `%vector.ingest_timestamp`.
+    pub source_metadata_fields: Vec<String>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<LogNamespaceShimConfig>("log_namespace_shim")
+}
+
+impl_generate_config_from_default!(LogNamespaceShimConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_namespace_shim")]
+impl TransformConfig for LogNamespaceShimConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(LogNamespaceShim {
+            source_metadata_fields: self
+                .source_metadata_fields
+                .iter()
+                .map(|field| parse_path(field))
+                .collect(),
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::all())
+            .with_schema_definition(schema::Definition::new(Kind::any(), [LogNamespace::Vector]))]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "log_namespace_shim"
+    }
+}
+
+/// Converts logs from the legacy layout, where the metadata set by the source is stored with the
+/// data at the root of the event, to the Vector namespace layout.
+///
+/// The fields of the global log schema and the configured fields are all paths, the same as when
+/// sources insert them, so nested fields are moved and their emptied parents removed.
+#[derive(Clone, Debug)]
+pub struct LogNamespaceShim {
+    source_metadata_fields: Vec<OwnedPath>,
+}
+
+impl LogNamespaceShim {
+    fn convert(&self, mut log: LogEvent) -> LogEvent {
+        let source_type = log.remove_prune(log_schema().source_type_key(), true);
+        let timestamp = log.remove_prune(log_schema().timestamp_key(), true);
+
+        let source_metadata = match source_type.as_ref().and_then(Value::as_bytes) {
+            Some(source_type) => {
+                let source_type = String::from_utf8_lossy(source_type).into_owned();
+                let fields = self
+                    .source_metadata_fields
+                    .iter()
+                    .filter_map(|field| log.remove_prune(field, true).map(|value| (field, value)))
+                    .collect::<Vec<_>>();
+                Some((source_type, fields))
+            }
+            None => None,
+        };
+
+        // Without the metadata, a log only holding a message is the message itself.
+        let message_key = log_schema().message_key();
+        if let Some(message) = log.remove_prune(message_key, true) {
+            if log.value().is_empty() {
+                *log.value_mut() = message;
+            } else {
+                log.insert(message_key, message);
+            }
+        }
+
+        let namespace = LogNamespace::Vector;
+        if let Some(source_type) = source_type {
+            namespace.insert_vector_metadata(
+                &mut log,
+                log_schema().source_type_key(),
+                path!("source_type"),
+                source_type,
+            );
+        }
+        if let Some(timestamp) = timestamp {
+            namespace.insert_vector_metadata(
+                &mut log,
+                log_schema().timestamp_key(),
+                path!("ingest_timestamp"),
+                timestamp,
+            );
+        }
+        if let Some((source_type, fields)) = &source_metadata {
+            for (field, value) in fields {
+                namespace.insert_source_metadata(source_type, &mut log, *field, value.clone());
+            }
+        }
+
+        log
+    }
+}
+
+impl FunctionTransform for LogNamespaceShim {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        match event {
+            Event::Log(log) => output.push(Event::from(self.convert(log))),
+            event => output.push(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use vector_common::btreemap;
+
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    fn shim(fields: &[&str]) -> LogNamespaceShim {
+        LogNamespaceShim {
+            source_metadata_fields: fields.iter().map(|field| parse_path(field)).collect(),
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogNamespaceShimConfig>();
+    }
+
+    #[test]
+    fn moves_metadata_and_unwraps_message() {
+        let now = Utc::now();
+        let mut log = LogEvent::from_str_legacy("hello");
+        log.insert(log_schema().timestamp_key(), now);
+        log.insert(log_schema().source_type_key(), "file");
+        log.insert("host", "example.com");
+
+        let log = transform_one(&mut shim(&["host"]), Event::from(log))
+            .unwrap()
+            .into_log();
+
+        assert_eq!(log.value(), &Value::from("hello"));
+        assert_eq!(
+            log.metadata().value(),
+            &Value::from(btreemap! {
+                "vector" => btreemap! {
+                    "source_type" => "file",
+                    "ingest_timestamp" => now,
+                },
+                "file" => btreemap! {
+                    "host" => "example.com",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn keeps_structured_data() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("level", "info");
+        log.insert(log_schema().source_type_key(), "http");
+
+        let log = transform_one(&mut shim(&[]), Event::from(log))
+            .unwrap()
+            .into_log();
+
+        assert_eq!(
+            log.value(),
+            &Value::from(btreemap! {
+                "message" => "hello",
+                "level" => "info",
+            })
+        );
+    }
+
+    #[test]
+    fn moves_nested_fields() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("kubernetes.pod_name", "vector-0");
+        log.insert("kubernetes.pod_namespace", "default");
+        log.insert(log_schema().source_type_key(), "kubernetes_logs");
+
+        let log = transform_one(&mut shim(&["kubernetes.pod_name"]), Event::from(log))
+            .unwrap()
+            .into_log();
+
+        assert_eq!(
+            log.value(),
+            &Value::from(btreemap! {
+                "message" => "hello",
+                "kubernetes" => btreemap! {
+                    "pod_namespace" => "default",
+                },
+            })
+        );
+        assert_eq!(
+            log.metadata().value().get("kubernetes_logs.kubernetes.pod_name"),
+            Some(&Value::from("vector-0"))
+        );
+    }
+
+    #[test]
+    fn prunes_emptied_parents() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("kubernetes.pod_name", "vector-0");
+        log.insert(log_schema().source_type_key(), "kubernetes_logs");
+
+        let log = transform_one(&mut shim(&["kubernetes.pod_name"]), Event::from(log))
+            .unwrap()
+            .into_log();
+
+        assert_eq!(log.value(), &Value::from("hello"));
+    }
+}
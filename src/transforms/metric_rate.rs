@@ -0,0 +1,279 @@
+use std::{collections::HashMap, num::NonZeroU32};
+
+use chrono::{DateTime, Duration, Utc};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::{
+        metric::{MetricData, MetricKind, MetricSeries, MetricValue},
+        Event, Metric,
+    },
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `metric_rate` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MetricRateConfig {
+    /// Whether metrics other than counters are dropped, instead of being passed through.
+    #[serde(default)]
+    pub drop_non_counters: bool,
+
+    /// The time after which the state of a series which isn't received anymore is dropped, in seconds.
+    #[serde(default = "default_series_ttl_secs")]
+    pub series_ttl_secs: u64,
+}
+
+const fn default_series_ttl_secs() -> u64 {
+    600
+}
+
+impl Default for MetricRateConfig {
+    fn default() -> Self {
+        Self {
+            drop_non_counters: false,
+            series_ttl_secs: default_series_ttl_secs(),
+        }
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<MetricRateConfig>("metric_rate")
+}
+
+impl_generate_config_from_default!(MetricRateConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "metric_rate")]
+impl TransformConfig for MetricRateConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(MetricRate::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "metric_rate"
+    }
+}
+
+/// The last sample of a series.
+#[derive(Clone, Debug)]
+struct Sample {
+    /// The value of absolute counters.
+    value: Option<f64>,
+    at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MetricRate {
+    drop_non_counters: bool,
+    series_ttl: Duration,
+    samples: HashMap<MetricSeries, Sample>,
+    last_expiry: DateTime<Utc>,
+}
+
+impl MetricRate {
+    pub fn new(config: &MetricRateConfig) -> Self {
+        Self {
+            drop_non_counters: config.drop_non_counters,
+            // A TTL too large to be represented never expires.
+            series_ttl: Duration::from_std(std::time::Duration::from_secs(config.series_ttl_secs))
+                .unwrap_or_else(|_| Duration::max_value()),
+            samples: HashMap::new(),
+            last_expiry: Utc::now(),
+        }
+    }
+
+    /// Converts the counter to a per-second gauge, returning `None` while the state of its series
+    /// doesn't allow computing its rate yet.
+    fn rate(&mut self, metric: Metric) -> Option<Metric> {
+        let (series, data, metadata) = metric.into_parts();
+        let at = data.timestamp.unwrap_or_else(Utc::now);
+
+        // Incremental counters carrying their interval don't need any state.
+        if let Some(rate) = data.to_rate() {
+            return Some(Metric::from_parts(series, rate, metadata));
+        }
+
+        let value = match data.value {
+            MetricValue::Counter { value } => value,
+            _ => unreachable!("Only counters have a rate."),
+        };
+        let sample = Sample {
+            value: (data.kind == MetricKind::Absolute).then(|| value),
+            at,
+        };
+        let previous = self.samples.insert(series.clone(), sample)?;
+
+        let delta = match (data.kind, previous.value) {
+            (MetricKind::Incremental, _) => value,
+            // The counter was reset.
+            (MetricKind::Absolute, Some(previous)) if value < previous => value,
+            (MetricKind::Absolute, Some(previous)) => value - previous,
+            // The series changed kind.
+            (MetricKind::Absolute, None) => return None,
+        };
+        let interval_ms = u32::try_from((at - previous.at).num_milliseconds())
+            .ok()
+            .and_then(NonZeroU32::new)?;
+
+        let rate = MetricData {
+            timestamp: Some(at),
+            interval_ms: Some(interval_ms),
+            kind: MetricKind::Incremental,
+            value: MetricValue::Counter { value: delta },
+        }
+        .to_rate()?;
+        Some(Metric::from_parts(series, rate, metadata))
+    }
+
+    /// Drops the state of the series which aren't received anymore.
+    fn expire(&mut self, now: DateTime<Utc>) {
+        if now - self.last_expiry < self.series_ttl {
+            return;
+        }
+        if let Some(deadline) = now.checked_sub_signed(self.series_ttl) {
+            self.samples.retain(|_, sample| sample.at >= deadline);
+        }
+        self.last_expiry = now;
+    }
+}
+
+impl FunctionTransform for MetricRate {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        self.expire(Utc::now());
+
+        let metric = event.into_metric();
+        if matches!(metric.value(), MetricValue::Counter { .. }) {
+            if let Some(rate) = self.rate(metric) {
+                output.push(Event::Metric(rate));
+            }
+        } else if !self.drop_non_counters {
+            output.push(Event::Metric(metric));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MetricRateConfig>();
+    }
+
+    fn counter(kind: MetricKind, value: f64, at: DateTime<Utc>) -> Event {
+        Metric::new("requests", kind, MetricValue::Counter { value })
+            .with_timestamp(Some(at))
+            .into()
+    }
+
+    fn gauge(event: Option<Event>) -> f64 {
+        let metric = event.expect("Expected a rate.").into_metric();
+        assert_eq!(metric.kind(), MetricKind::Absolute);
+        match metric.value() {
+            MetricValue::Gauge { value } => *value,
+            value => panic!("Expected a gauge, got {:?}.", value),
+        }
+    }
+
+    #[test]
+    fn incremental_counter_with_interval() {
+        let mut rate = MetricRate::new(&MetricRateConfig::default());
+        let event = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 50.0 },
+        )
+        .with_interval_ms(NonZeroU32::new(10_000))
+        .into();
+
+        assert_eq!(gauge(transform_one(&mut rate, event)), 5.0);
+    }
+
+    #[test]
+    fn incremental_counter_without_interval() {
+        let mut rate = MetricRate::new(&MetricRateConfig::default());
+        let now = Utc::now();
+
+        let event = counter(MetricKind::Incremental, 10.0, now);
+        assert!(transform_one(&mut rate, event).is_none());
+
+        let event = counter(MetricKind::Incremental, 20.0, now + Duration::seconds(4));
+        assert_eq!(gauge(transform_one(&mut rate, event)), 5.0);
+    }
+
+    #[test]
+    fn absolute_counter() {
+        let mut rate = MetricRate::new(&MetricRateConfig::default());
+        let now = Utc::now();
+
+        let event = counter(MetricKind::Absolute, 100.0, now);
+        assert!(transform_one(&mut rate, event).is_none());
+
+        let event = counter(MetricKind::Absolute, 130.0, now + Duration::seconds(10));
+        assert_eq!(gauge(transform_one(&mut rate, event)), 3.0);
+
+        // After a reset, the new value is the increase.
+        let event = counter(MetricKind::Absolute, 20.0, now + Duration::seconds(20));
+        assert_eq!(gauge(transform_one(&mut rate, event)), 2.0);
+    }
+
+    #[test]
+    fn non_counters() {
+        let event: Event = Metric::new(
+            "temperature",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 20.0 },
+        )
+        .into();
+
+        let mut rate = MetricRate::new(&MetricRateConfig::default());
+        assert_eq!(transform_one(&mut rate, event.clone()), Some(event.clone()));
+
+        let mut rate = MetricRate::new(&MetricRateConfig {
+            drop_non_counters: true,
+            ..Default::default()
+        });
+        assert!(transform_one(&mut rate, event).is_none());
+    }
+
+    #[test]
+    fn expires_series() {
+        let mut rate = MetricRate::new(&MetricRateConfig::default());
+        let now = Utc::now();
+
+        let event = counter(MetricKind::Absolute, 100.0, now);
+        assert!(transform_one(&mut rate, event).is_none());
+
+        rate.expire(now + Duration::seconds(601));
+        assert!(rate.samples.is_empty());
+    }
+
+    #[test]
+    fn large_series_ttl_never_expires() {
+        let mut rate = MetricRate::new(&MetricRateConfig {
+            series_ttl_secs: u64::MAX,
+            ..Default::default()
+        });
+        let now = Utc::now();
+
+        let event = counter(MetricKind::Absolute, 100.0, now);
+        assert!(transform_one(&mut rate, event).is_none());
+
+        rate.expire(now + Duration::days(365));
+        assert_eq!(rate.samples.len(), 1);
+    }
+}
@@ -4,6 +4,8 @@ use std::collections::HashSet;
 use async_trait::async_trait;
 use snafu::Snafu;
 
+#[cfg(feature = "transforms-adaptive_sample")]
+pub mod adaptive_sample;
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -14,10 +16,14 @@ pub mod dedupe;
 pub mod filter;
 #[cfg(feature = "transforms-geoip")]
 pub mod geoip;
+#[cfg(feature = "transforms-log_namespace_shim")]
+pub mod log_namespace_shim;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
+#[cfg(feature = "transforms-metric_rate")]
+pub mod metric_rate;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
 #[cfg(feature = "transforms-pipelines")]
@@ -30,10 +36,16 @@ pub mod remap;
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-span_correlation")]
+pub mod span_correlation;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-top_k")]
+pub mod top_k;
+#[cfg(feature = "transforms-trace_sampling")]
+pub mod trace_sampling;
 
 use vector_config::configurable_component;
 pub use vector_core::transform::{
@@ -60,6 +72,10 @@ enum BuildError {
 #[derive(Clone, Debug)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum Transforms {
+    /// Adaptive sample.
+    #[cfg(feature = "transforms-adaptive_sample")]
+    AdaptiveSample(#[configurable(derived)] adaptive_sample::AdaptiveSampleConfig),
+
     /// Aggregate.
     #[cfg(feature = "transforms-aggregate")]
     Aggregate(#[configurable(derived)] aggregate::AggregateConfig),
@@ -80,6 +96,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-geoip")]
     Geoip(#[configurable(derived)] geoip::GeoipConfig),
 
+    /// Log namespace shim.
+    #[cfg(feature = "transforms-log_namespace_shim")]
+    LogNamespaceShim(#[configurable(derived)] log_namespace_shim::LogNamespaceShimConfig),
+
     /// Log to metric.
     #[cfg(feature = "transforms-log_to_metric")]
     LogToMetric(#[configurable(derived)] log_to_metric::LogToMetricConfig),
@@ -88,6 +108,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-lua")]
     Lua(#[configurable(derived)] lua::LuaConfig),
 
+    /// Metric rate.
+    #[cfg(feature = "transforms-metric_rate")]
+    MetricRate(#[configurable(derived)] metric_rate::MetricRateConfig),
+
     /// Metric to log.
     #[cfg(feature = "transforms-metric_to_log")]
     MetricToLog(#[configurable(derived)] metric_to_log::MetricToLogConfig),
@@ -114,6 +138,10 @@ pub enum Transforms {
     #[serde(alias = "sampler")]
     Sample(#[configurable(derived)] sample::SampleConfig),
 
+    /// Span correlation.
+    #[cfg(feature = "transforms-span_correlation")]
+    SpanCorrelation(#[configurable(derived)] span_correlation::SpanCorrelationConfig),
+
     /// Tag cardinality limit.
     #[cfg(feature = "transforms-tag_cardinality_limit")]
     TagCardinalityLimit(#[configurable(derived)] tag_cardinality_limit::TagCardinalityLimitConfig),
@@ -121,6 +149,14 @@ pub enum Transforms {
     /// Throttle.
     #[cfg(feature = "transforms-throttle")]
     Throttle(#[configurable(derived)] throttle::ThrottleConfig),
+
+    /// Top K.
+    #[cfg(feature = "transforms-top_k")]
+    TopK(#[configurable(derived)] top_k::TopKConfig),
+
+    /// Trace sampling.
+    #[cfg(feature = "transforms-trace_sampling")]
+    TraceSampling(#[configurable(derived)] trace_sampling::TraceSamplingConfig),
 }
 
 #[async_trait]
@@ -128,6 +164,8 @@ impl TransformConfig for Transforms {
     #[allow(unused_variables)]
     async fn build(&self, globals: &TransformContext) -> crate::Result<Transform> {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -138,10 +176,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-pipelines")]
@@ -154,10 +196,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.build(globals).await,
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.build(globals).await,
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.build(globals).await,
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -165,6 +213,8 @@ impl TransformConfig for Transforms {
 
     fn input(&self) -> Input {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.input(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.input(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -175,10 +225,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.input(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.input(),
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.input(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.input(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.input(),
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.input(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.input(),
             #[cfg(feature = "transforms-pipelines")]
@@ -191,10 +245,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.input(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.input(),
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.input(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.input(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.input(),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.input(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.input(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -203,6 +263,8 @@ impl TransformConfig for Transforms {
     #[allow(unused_variables)]
     fn outputs(&self, merged_definition: &schema::Definition) -> Vec<Output> {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -213,10 +275,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-pipelines")]
@@ -229,10 +295,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.outputs(merged_definition),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.outputs(merged_definition),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.outputs(merged_definition),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -240,6 +312,8 @@ impl TransformConfig for Transforms {
 
     fn transform_type(&self) -> &'static str {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -250,10 +324,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-pipelines")]
@@ -266,10 +344,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.transform_type(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.transform_type(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.transform_type(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -277,6 +361,8 @@ impl TransformConfig for Transforms {
 
     fn typetag_name(&self) -> &'static str {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -287,10 +373,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-pipelines")]
@@ -303,10 +393,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.typetag_name(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.typetag_name(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.typetag_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -314,6 +410,8 @@ impl TransformConfig for Transforms {
 
     fn typetag_deserialize(&self) {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -324,10 +422,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-pipelines")]
@@ -340,10 +442,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.typetag_deserialize(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.typetag_deserialize(),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.typetag_deserialize(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -352,6 +460,8 @@ impl TransformConfig for Transforms {
     #[allow(unused_variables)]
     fn nestable(&self, parents: &HashSet<&'static str>) -> bool {
         match self {
+            #[cfg(feature = "transforms-adaptive_sample")]
+            Transforms::AdaptiveSample(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-aggregate")]
             Transforms::Aggregate(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -362,10 +472,14 @@ impl TransformConfig for Transforms {
             Transforms::Filter(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-log_namespace_shim")]
+            Transforms::LogNamespaceShim(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-log_to_metric")]
             Transforms::LogToMetric(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-metric_rate")]
+            Transforms::MetricRate(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-metric_to_log")]
             Transforms::MetricToLog(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-pipelines")]
@@ -378,10 +492,16 @@ impl TransformConfig for Transforms {
             Transforms::Route(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-sample")]
             Transforms::Sample(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-span_correlation")]
+            Transforms::SpanCorrelation(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-tag_cardinality_limit")]
             Transforms::TagCardinalityLimit(inner) => inner.nestable(parents),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-top_k")]
+            Transforms::TopK(inner) => inner.nestable(parents),
+            #[cfg(feature = "transforms-trace_sampling")]
+            Transforms::TraceSampling(inner) => inner.nestable(parents),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -9,12 +9,14 @@ use async_stream::stream;
 use futures::{stream, Stream, StreamExt};
 use indexmap::IndexMap;
 use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
     event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
     internal_events::ReduceStaleEventFlushed,
+    memory_budget::MemoryUsage,
     schema,
     transforms::{TaskTransform, Transform},
 };
@@ -78,7 +80,8 @@ impl_generate_config_from_default!(ReduceConfig);
 #[typetag::serde(name = "reduce")]
 impl TransformConfig for ReduceConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Reduce::new(self, &context.enrichment_tables).map(Transform::event_task)
+        let memory_usage = MemoryUsage::register(context.key.as_ref());
+        Reduce::new(self, &context.enrichment_tables, memory_usage).map(Transform::event_task)
     }
 
     fn input(&self) -> Input {
@@ -99,10 +102,13 @@ struct ReduceState {
     fields: HashMap<String, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
     metadata: EventMetadata,
+    /// The size of the events reduced into this state, as an estimate of its own size.
+    byte_size: usize,
 }
 
 impl ReduceState {
     fn new(e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
+        let byte_size = e.size_of();
         let (value, metadata) = e.into_parts();
 
         let fields = if let Value::Object(fields) = value {
@@ -130,10 +136,12 @@ impl ReduceState {
             stale_since: Instant::now(),
             fields,
             metadata,
+            byte_size,
         }
     }
 
     fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+        self.byte_size += e.size_of();
         let (value, metadata) = e.into_parts();
         self.metadata.merge(metadata);
 
@@ -189,12 +197,15 @@ pub struct Reduce {
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Condition>,
     starts_when: Option<Condition>,
+    state_byte_size: usize,
+    memory_usage: MemoryUsage,
 }
 
 impl Reduce {
     pub fn new(
         config: &ReduceConfig,
         enrichment_tables: &enrichment::TableRegistry,
+        memory_usage: MemoryUsage,
     ) -> crate::Result<Self> {
         if config.ends_when.is_some() && config.starts_when.is_some() {
             return Err("only one of `ends_when` and `starts_when` can be provided".into());
@@ -220,9 +231,38 @@ impl Reduce {
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
+            state_byte_size: 0,
+            memory_usage,
         })
     }
 
+    /// Reports the size of the reduce states to the memory budget, then trims them if the budget
+    /// asks for it.
+    fn update_memory_usage(&mut self, output: &mut Vec<Event>) {
+        self.memory_usage.set(self.state_byte_size);
+        self.trim(output);
+    }
+
+    /// Flushes the stalest half of the reduce states, as many times as the memory budget asks for
+    /// it.
+    fn trim(&mut self, output: &mut Vec<Event>) {
+        let (states, state_byte_size) = (&mut self.reduce_merge_states, &mut self.state_byte_size);
+        self.memory_usage.trim_with(|| {
+            if states.is_empty() {
+                return None;
+            }
+            let mut stalest = states.drain().collect::<Vec<_>>();
+            stalest.sort_by_key(|(_, state)| state.stale_since);
+            let kept = stalest.split_off((stalest.len() / 2).max(1));
+            for (_, state) in stalest {
+                *state_byte_size -= state.byte_size;
+                output.push(Event::from(state.flush()));
+            }
+            states.extend(kept);
+            Some(*state_byte_size)
+        });
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let mut flush_discriminants = Vec::new();
         for (k, t) in &self.reduce_merge_states {
@@ -231,7 +271,7 @@ impl Reduce {
             }
         }
         for k in &flush_discriminants {
-            if let Some(t) = self.reduce_merge_states.remove(k) {
+            if let Some(t) = self.remove_state(k) {
                 emit!(ReduceStaleEventFlushed);
                 output.push(Event::from(t.flush()));
             }
@@ -239,12 +279,20 @@ impl Reduce {
     }
 
     fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        self.state_byte_size = 0;
         self.reduce_merge_states
             .drain()
             .for_each(|(_, s)| output.push(Event::from(s.flush())));
     }
 
+    fn remove_state(&mut self, discriminant: &Discriminant) -> Option<ReduceState> {
+        let state = self.reduce_merge_states.remove(discriminant)?;
+        self.state_byte_size -= state.byte_size;
+        Some(state)
+    }
+
     fn push_or_new_reduce_state(&mut self, event: LogEvent, discriminant: Discriminant) {
+        self.state_byte_size += event.size_of();
         match self.reduce_merge_states.entry(discriminant) {
             hash_map::Entry::Vacant(entry) => {
                 entry.insert(ReduceState::new(event, &self.merge_strategies));
@@ -270,13 +318,13 @@ impl Reduce {
         let discriminant = Discriminant::from_log_event(&event, &self.group_by);
 
         if starts_here {
-            if let Some(state) = self.reduce_merge_states.remove(&discriminant) {
+            if let Some(state) = self.remove_state(&discriminant) {
                 output.push(state.flush().into());
             }
 
             self.push_or_new_reduce_state(event, discriminant)
         } else if ends_here {
-            output.push(match self.reduce_merge_states.remove(&discriminant) {
+            output.push(match self.remove_state(&discriminant) {
                 Some(mut state) => {
                     state.add_event(event, &self.merge_strategies);
                     state.flush().into()
@@ -290,6 +338,7 @@ impl Reduce {
         }
 
         self.flush_into(output);
+        self.update_memory_usage(output);
     }
 }
 
@@ -306,6 +355,7 @@ impl TaskTransform<Event> for Reduce {
         let poll_period = me.flush_period;
 
         let mut flush_stream = tokio::time::interval(poll_period);
+        let trims = me.memory_usage.trim_signal();
 
         Box::pin(
             stream! {
@@ -314,6 +364,11 @@ impl TaskTransform<Event> for Reduce {
                 let done = tokio::select! {
                     _ = flush_stream.tick() => {
                       me.flush_into(&mut output);
+                      me.memory_usage.set(me.state_byte_size);
+                      false
+                    }
+                    _ = trims.requested() => {
+                      me.trim(&mut output);
                       false
                     }
                     maybe_event = input_rx.next() => {
@@ -9,7 +9,7 @@ use std::{
 use lookup::lookup_v2::Path;
 use lookup::path;
 use snafu::{ResultExt, Snafu};
-use value::Kind;
+use value::{kind::Collection, Kind};
 use vector_common::TimeZone;
 use vector_config::configurable_component;
 use vector_core::compile_vrl;
@@ -29,7 +29,7 @@ use crate::{
         log_schema, ComponentKey, DataType, Input, Output, TransformConfig, TransformContext,
         TransformDescription,
     },
-    event::{Event, TargetEvents, VrlTarget},
+    event::{Event, EventArray, LogEvent, TargetEvents, VrlTarget},
     internal_events::{RemapMappingAbort, RemapMappingError},
     schema,
     transforms::{SyncTransform, Transform, TransformOutputsBuf},
@@ -99,6 +99,17 @@ pub struct RemapConfig {
     /// the event was dropped.
     pub reroute_dropped: bool,
 
+    /// Runs the program once per batch of incoming log events, instead of once per event.
+    ///
+    /// The root of the event (`.`) is then an array of all the events in the batch, which allows the program to
+    /// compute aggregates or remove duplicates across events. Each element of the array returned by the program is
+    /// forwarded as a separate event. The finalizers and secrets of all the events in the batch are merged, the
+    /// rest of the metadata is taken from the first event, and every forwarded event carries the merged metadata.
+    /// The events of the batch are then acknowledged once all the forwarded events are.
+    ///
+    /// Metrics and traces are still processed one event at a time.
+    pub batch: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     pub runtime: VrlRuntime,
@@ -134,8 +145,16 @@ impl RemapConfig {
         functions.append(&mut enrichment::vrl_functions());
         functions.append(&mut vector_vrl_functions::vrl_functions());
 
-        let mut state =
-            vrl::state::ExternalEnv::new_with_kind(merged_schema_definition.kind().clone());
+        // In batch mode, the program receives an array of events.
+        let kind = if self.batch {
+            Kind::array(Collection::from_unknown(
+                merged_schema_definition.kind().clone(),
+            ))
+        } else {
+            merged_schema_definition.kind().clone()
+        };
+
+        let mut state = vrl::state::ExternalEnv::new_with_kind(kind);
         state.set_external_context(enrichment_tables);
         state.set_external_context(MeaningList::default());
 
@@ -205,10 +224,16 @@ impl TransformConfig for RemapConfig {
                     .expect("context exists")
                     .0;
 
-                let mut new_type_def = Definition::new(
-                    state.target_kind().clone(),
-                    input_definition.log_namespaces().clone(),
-                );
+                // In batch mode, each element of the returned array is forwarded as an event.
+                let kind = match state.target_kind() {
+                    kind if self.batch && kind.is_array() => {
+                        kind.as_array().expect("array kind").reduced_kind()
+                    }
+                    kind => kind.clone(),
+                };
+
+                let mut new_type_def =
+                    Definition::new(kind, input_definition.log_namespaces().clone());
                 for (id, path) in meaning {
                     new_type_def = new_type_def.with_meaning(path, &id);
                 }
@@ -280,6 +305,7 @@ where
     drop_on_error: bool,
     drop_on_abort: bool,
     reroute_dropped: bool,
+    batch: bool,
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
@@ -367,6 +393,7 @@ where
             drop_on_error: config.drop_on_error,
             drop_on_abort: config.drop_on_abort,
             reroute_dropped: config.reroute_dropped,
+            batch: config.batch,
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
@@ -437,11 +464,12 @@ where
     }
 }
 
-impl<Runner> SyncTransform for Remap<Runner>
+impl<Runner> Remap<Runner>
 where
-    Runner: VrlRunner + Clone + Send + Sync,
+    Runner: VrlRunner,
 {
-    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+    /// Whether the original events must be kept around while the program runs.
+    fn keep_original(&self) -> bool {
         // If a program can fail or abort at runtime and we know that we will still need to forward
         // the event in that case (either to the main output or `dropped`, depending on the
         // config), we need to clone the original event and keep it around, to allow us to discard
@@ -454,55 +482,117 @@ where
         // the event to the `dropped` output.
         let forward_on_error = !self.drop_on_error || self.reroute_dropped;
         let forward_on_abort = !self.drop_on_abort || self.reroute_dropped;
-        let original_event = if (self.program.info().fallible && forward_on_error)
+
+        (self.program.info().fallible && forward_on_error)
             || (self.program.info().abortable && forward_on_abort)
-        {
-            Some(event.clone())
-        } else {
-            None
+    }
+
+    fn push_target(&self, target: VrlTarget, output: &mut TransformOutputsBuf) {
+        match target.into_events() {
+            TargetEvents::One(event) => {
+                push_default(event, output, &self.default_schema_definition)
+            }
+            TargetEvents::Logs(events) => events
+                .for_each(|event| push_default(event, output, &self.default_schema_definition)),
+            TargetEvents::Traces(events) => events
+                .for_each(|event| push_default(event, output, &self.default_schema_definition)),
+        }
+    }
+
+    /// Forwards the original events, when the program failed or aborted while processing `count`
+    /// events.
+    fn push_terminated(
+        &self,
+        reason: Terminate,
+        count: usize,
+        original_events: Option<Vec<Event>>,
+        output: &mut TransformOutputsBuf,
+    ) {
+        let (reason, error, drop) = match reason {
+            Terminate::Abort(error) => {
+                emit!(RemapMappingAbort {
+                    count,
+                    event_dropped: self.drop_on_abort,
+                });
+
+                ("abort", error, self.drop_on_abort)
+            }
+            Terminate::Error(error) => {
+                emit!(RemapMappingError {
+                    count,
+                    error: error.to_string(),
+                    event_dropped: self.drop_on_error,
+                });
+
+                ("error", error, self.drop_on_error)
+            }
         };
 
-        let mut target = VrlTarget::new(event, self.program.info());
-        let result = self.run_vrl(&mut target);
+        if !drop {
+            for event in original_events.expect("events will be set") {
+                push_default(event, output, &self.default_schema_definition);
+            }
+        } else if self.reroute_dropped {
+            for mut event in original_events.expect("events will be set") {
+                self.annotate_dropped(&mut event, reason, error.clone());
+                push_dropped(event, output, &self.dropped_schema_definition);
+            }
+        }
+    }
 
-        match result {
-            Ok(_) => match target.into_events() {
-                TargetEvents::One(event) => {
-                    push_default(event, output, &self.default_schema_definition)
-                }
-                TargetEvents::Logs(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
-                TargetEvents::Traces(events) => events
-                    .for_each(|event| push_default(event, output, &self.default_schema_definition)),
-            },
-            Err(reason) => {
-                let (reason, error, drop) = match reason {
-                    Terminate::Abort(error) => {
-                        emit!(RemapMappingAbort {
-                            event_dropped: self.drop_on_abort,
-                        });
-
-                        ("abort", error, self.drop_on_abort)
-                    }
-                    Terminate::Error(error) => {
-                        emit!(RemapMappingError {
-                            error: error.to_string(),
-                            event_dropped: self.drop_on_error,
-                        });
+    /// Runs the program once, with the root of the target set to an array of the events.
+    fn transform_batch(&mut self, events: Vec<LogEvent>, output: &mut TransformOutputsBuf) {
+        let count = events.len();
+        let original_events = self
+            .keep_original()
+            .then(|| events.iter().cloned().map(Event::from).collect());
+
+        let mut values = Vec::with_capacity(count);
+        let mut metadata = None;
+        for event in events {
+            let (value, event_metadata) = event.into_parts();
+            values.push(value);
+            match metadata {
+                None => metadata = Some(event_metadata),
+                Some(ref mut metadata) => metadata.merge(event_metadata),
+            }
+        }
 
-                        ("error", error, self.drop_on_error)
-                    }
-                };
+        let mut target =
+            VrlTarget::LogEvent(value::Value::Array(values), metadata.unwrap_or_default());
+        match self.run_vrl(&mut target) {
+            Ok(_) => self.push_target(target, output),
+            Err(reason) => self.push_terminated(reason, count, original_events, output),
+        }
+    }
+}
 
-                if !drop {
-                    let event = original_event.expect("event will be set");
+impl<Runner> SyncTransform for Remap<Runner>
+where
+    Runner: VrlRunner + Clone + Send + Sync,
+{
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let original_event = self.keep_original().then(|| vec![event.clone()]);
 
-                    push_default(event, output, &self.default_schema_definition);
-                } else if self.reroute_dropped {
-                    let mut event = original_event.expect("event will be set");
+        let mut target = VrlTarget::new(event, self.program.info());
+        let result = self.run_vrl(&mut target);
 
-                    self.annotate_dropped(&mut event, reason, error);
-                    push_dropped(event, output, &self.dropped_schema_definition);
+        match result {
+            Ok(_) => self.push_target(target, output),
+            Err(reason) => self.push_terminated(reason, 1, original_event, output),
+        }
+    }
+
+    fn transform_all(&mut self, events: EventArray, output: &mut TransformOutputsBuf) {
+        match events {
+            EventArray::Logs(logs) if self.batch => {
+                if !logs.is_empty() {
+                    self.transform_batch(logs, output);
+                }
+            }
+            events => {
+                for event in events.into_events() {
+                    self.transform(event, output);
                 }
             }
         }
@@ -1314,6 +1404,67 @@ mod tests {
         COMPONENT_MULTIPLE_OUTPUTS_TESTS.assert(&["output"]);
     }
 
+    #[test]
+    fn batch_processes_events_together() {
+        let conf = RemapConfig {
+            source: Some(r#". = push(., { "message": "summary", "count": length(.) })"#.to_owned()),
+            batch: true,
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        let out = collect_batch_outputs(
+            &mut tform,
+            vec![LogEvent::from("event1"), LogEvent::from("event2")],
+        );
+
+        let messages = out
+            .primary
+            .into_events()
+            .map(|event| get_field_string(&event, "message"))
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["event1", "event2", "summary"]);
+    }
+
+    #[test]
+    fn batch_reroutes_all_events_on_abort() {
+        let conf = RemapConfig {
+            source: Some(
+                indoc! {r#"
+                . = push(., { "message": "summary" })
+                if length(.) > 2 {
+                    abort
+                }
+            "#}
+                .to_owned(),
+            ),
+            drop_on_abort: true,
+            reroute_dropped: true,
+            batch: true,
+            ..Default::default()
+        };
+        let schema_definitions = HashMap::from([
+            (None, test_default_schema_definition()),
+            (Some(DROPPED.to_owned()), test_dropped_schema_definition()),
+        ]);
+        let mut tform = Remap::new_ast(conf, &TransformContext::new_test(schema_definitions))
+            .unwrap()
+            .0;
+
+        let out = collect_batch_outputs(
+            &mut tform,
+            vec![LogEvent::from("event1"), LogEvent::from("event2")],
+        );
+
+        assert!(out.primary.is_empty());
+        let dropped = out.named[DROPPED]
+            .clone()
+            .into_events()
+            .map(|event| get_field_string(&event, "message"))
+            .collect::<Vec<_>>();
+        assert_eq!(dropped, vec!["event1", "event2"]);
+    }
+
     struct CollectedOuput {
         primary: OutputBuffer,
         named: HashMap<String, OutputBuffer>,
@@ -1336,6 +1487,23 @@ mod tests {
         }
     }
 
+    fn collect_batch_outputs(ft: &mut dyn SyncTransform, events: Vec<LogEvent>) -> CollectedOuput {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::all()),
+                Output::default(DataType::all()).with_port(DROPPED),
+            ],
+            events.len(),
+        );
+
+        ft.transform_all(EventArray::Logs(events), &mut outputs);
+
+        CollectedOuput {
+            primary: outputs.take_primary(),
+            named: outputs.take_all_named(),
+        }
+    }
+
     fn transform_one(ft: &mut dyn SyncTransform, event: Event) -> Option<Event> {
         let out = collect_outputs(ft, event);
         assert_eq!(0, out.named.iter().map(|(_, v)| v.len()).sum::<usize>());
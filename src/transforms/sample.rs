@@ -23,7 +23,7 @@ pub struct SampleConfig {
     /// For example, `rate = 10` means 1 out of every 10 events will be forwarded and the rest will be dropped.
     pub rate: u64,
 
-    /// The name of the log field whose value will be hashed to determine if the event should be passed.
+    /// The name of the log or trace field whose value will be hashed to determine if the event should be passed.
     ///
     /// Consistently samples the same events. Actual rate of sampling may differ from the configured one if values in
     /// the field are not uniformly distributed. If left unspecified, or if the event doesn’t have `key_field`, events
@@ -68,11 +68,11 @@ impl TransformConfig for SampleConfig {
     }
 
     fn input(&self) -> Input {
-        Input::log()
+        Input::new(DataType::Log | DataType::Trace)
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        vec![Output::default(DataType::Log | DataType::Trace)]
     }
 
     fn transform_type(&self) -> &'static str {
@@ -142,7 +142,11 @@ impl FunctionTransform for Sample {
         let value = self
             .key_field
             .as_ref()
-            .and_then(|key_field| event.as_log().get(key_field.as_str()))
+            .and_then(|key_field| match &event {
+                Event::Log(log) => log.get(key_field.as_str()),
+                Event::Trace(trace) => trace.get(key_field),
+                Event::Metric(_) => unreachable!("sample only accepts log and trace events"),
+            })
             .map(|v| v.to_string_lossy());
 
         let num = if let Some(value) = value {
@@ -154,9 +158,11 @@ impl FunctionTransform for Sample {
         self.count = (self.count + 1) % self.rate;
 
         if num % self.rate == 0 {
-            event
-                .as_mut_log()
-                .insert("sample_rate", self.rate.to_string());
+            match event {
+                Event::Log(ref mut log) => log.insert("sample_rate", self.rate.to_string()),
+                Event::Trace(ref mut trace) => trace.insert("sample_rate", self.rate.to_string()),
+                Event::Metric(_) => unreachable!("sample only accepts log and trace events"),
+            };
             output.push(event);
         } else {
             emit!(SampleEventDiscarded);
@@ -172,7 +178,7 @@ mod tests {
     use crate::{
         conditions::{Condition, ConditionalConfig, VrlConfig},
         config::log_schema,
-        event::{Event, LogEvent},
+        event::{Event, LogEvent, TraceEvent, Value},
         test_util::random_lines,
         transforms::test::transform_one,
     };
@@ -355,6 +361,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn samples_traces() {
+        let mut sampler = Sample::new(2, Some("trace_id".into()), None);
+        let total_passed = (0..1000i64)
+            .filter_map(|id| {
+                let mut trace = TraceEvent::default();
+                trace.insert("trace_id", id);
+                transform_one(&mut sampler, trace.into())
+            })
+            .inspect(|event| {
+                assert_eq!(event.as_trace().get("sample_rate"), Some(&Value::from("2")))
+            })
+            .count();
+        let ideal = 1.0f64 / 2.0f64;
+        let actual = total_passed as f64 / 1000.0f64;
+        assert_relative_eq!(ideal, actual, epsilon = ideal * 0.5);
+    }
+
     fn random_events(n: usize) -> Vec<Event> {
         random_lines(10)
             .take(n)
@@ -0,0 +1,274 @@
+use std::{collections::BTreeMap, future::ready, pin::Pin};
+
+use futures::{Stream, StreamExt};
+use lru::LruCache;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::{Event, LogEvent, TraceEvent, Value},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `span_correlation` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SpanCorrelationConfig {
+    /// The log field holding the ID of the trace the log was emitted in.
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: String,
+
+    /// The log field holding the ID of the span the log was emitted in.
+    ///
+    /// Logs with a trace ID but without a span ID are matched with the root span of their trace.
+    #[serde(default = "default_span_id_field")]
+    pub span_id_field: String,
+
+    /// The log field the fields of the matched span are inserted into.
+    ///
+    /// The `service`, `operation`, `resource` and `duration` fields of the span are inserted when present.
+    #[serde(default = "default_target_field")]
+    pub target_field: String,
+
+    /// The number of recent spans remembered for matching logs.
+    #[serde(default = "default_cache_size")]
+    pub cache_size: usize,
+}
+
+fn default_trace_id_field() -> String {
+    "trace_id".to_owned()
+}
+
+fn default_span_id_field() -> String {
+    "span_id".to_owned()
+}
+
+fn default_target_field() -> String {
+    "span".to_owned()
+}
+
+const fn default_cache_size() -> usize {
+    10_000
+}
+
+impl Default for SpanCorrelationConfig {
+    fn default() -> Self {
+        Self {
+            trace_id_field: default_trace_id_field(),
+            span_id_field: default_span_id_field(),
+            target_field: default_target_field(),
+            cache_size: default_cache_size(),
+        }
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<SpanCorrelationConfig>("span_correlation")
+}
+
+impl_generate_config_from_default!(SpanCorrelationConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "span_correlation")]
+impl TransformConfig for SpanCorrelationConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.cache_size == 0 {
+            return Err("`cache_size` must be non-zero".into());
+        }
+        Ok(Transform::event_task(SpanCorrelation::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Log | DataType::Trace)
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Log | DataType::Trace)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "span_correlation"
+    }
+}
+
+/// The fields of a span inserted into the logs emitted within it, by their name in the span.
+const SPAN_FIELDS: [(&str, &str); 4] = [
+    ("service", "service"),
+    ("name", "operation"),
+    ("resource", "resource"),
+    ("duration", "duration"),
+];
+
+/// The key of a span in the cache. Root spans are also cached without their span ID.
+type SpanKey = (String, Option<String>);
+
+pub struct SpanCorrelation {
+    trace_id_field: String,
+    span_id_field: String,
+    target_field: String,
+    spans: LruCache<SpanKey, BTreeMap<String, Value>>,
+}
+
+impl SpanCorrelation {
+    pub fn new(config: &SpanCorrelationConfig) -> Self {
+        Self {
+            trace_id_field: config.trace_id_field.clone(),
+            span_id_field: config.span_id_field.clone(),
+            target_field: config.target_field.clone(),
+            spans: LruCache::new(config.cache_size),
+        }
+    }
+
+    fn record(&mut self, trace: &TraceEvent) {
+        let spans = match trace.get("spans") {
+            Some(Value::Array(spans)) => spans.iter().filter_map(Value::as_object).collect(),
+            _ => vec![trace.as_map()],
+        };
+
+        for span in spans {
+            let trace_id = match span.get("trace_id").or_else(|| trace.get("trace_id")) {
+                Some(trace_id) => trace_id.to_string_lossy(),
+                None => continue,
+            };
+            let fields = SPAN_FIELDS
+                .iter()
+                .filter_map(|(from, to)| {
+                    span.get(*from)
+                        .map(|value| ((*to).to_owned(), value.clone()))
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            if matches!(span.get("parent_id"), None | Some(Value::Integer(0))) {
+                self.spans.put((trace_id.clone(), None), fields.clone());
+            }
+            if let Some(span_id) = span.get("span_id") {
+                self.spans
+                    .put((trace_id, Some(span_id.to_string_lossy())), fields);
+            }
+        }
+    }
+
+    fn enrich(&mut self, log: &mut LogEvent) {
+        let trace_id = match log.get(self.trace_id_field.as_str()) {
+            Some(trace_id) => trace_id.to_string_lossy(),
+            None => return,
+        };
+        let span_id = log
+            .get(self.span_id_field.as_str())
+            .map(Value::to_string_lossy);
+
+        if let Some(fields) = self.spans.get(&(trace_id, span_id)) {
+            for (name, value) in fields {
+                log.insert(
+                    format!("{}.{}", self.target_field, name).as_str(),
+                    value.clone(),
+                );
+            }
+        }
+    }
+
+    fn transform_one(&mut self, mut event: Event) -> Event {
+        match &mut event {
+            Event::Log(log) => self.enrich(log),
+            Event::Trace(trace) => self.record(trace),
+            Event::Metric(_) => {}
+        }
+        event
+    }
+}
+
+impl TaskTransform<Event> for SpanCorrelation {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut inner = self;
+        Box::pin(task.map(move |event| inner.transform_one(event)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SpanCorrelationConfig>();
+    }
+
+    fn trace() -> TraceEvent {
+        let mut trace = TraceEvent::default();
+        trace.insert(
+            "spans",
+            vec![
+                Value::from(btreemap! {
+                    "trace_id" => 1,
+                    "span_id" => 10,
+                    "parent_id" => 0,
+                    "service" => "api",
+                    "name" => "http.request",
+                    "resource" => "GET /users",
+                    "duration" => 2_000_000,
+                }),
+                Value::from(btreemap! {
+                    "trace_id" => 1,
+                    "span_id" => 11,
+                    "parent_id" => 10,
+                    "service" => "db",
+                    "name" => "postgres.query",
+                    "duration" => 500_000,
+                }),
+            ],
+        );
+        trace
+    }
+
+    #[test]
+    fn enriches_logs_with_matching_span() {
+        let mut correlation = SpanCorrelation::new(&SpanCorrelationConfig::default());
+        correlation.transform_one(trace().into());
+
+        let mut log = LogEvent::from("querying users");
+        log.insert("trace_id", "1");
+        log.insert("span_id", 11);
+        let log = correlation.transform_one(log.into()).into_log();
+
+        assert_eq!(log["span.service"], "db".into());
+        assert_eq!(log["span.operation"], "postgres.query".into());
+        assert_eq!(log["span.duration"], 500_000.into());
+        assert!(log.get("span.resource").is_none());
+    }
+
+    #[test]
+    fn matches_root_span_without_span_id() {
+        let mut correlation = SpanCorrelation::new(&SpanCorrelationConfig::default());
+        correlation.transform_one(trace().into());
+
+        let mut log = LogEvent::from("request done");
+        log.insert("trace_id", 1);
+        let log = correlation.transform_one(log.into()).into_log();
+
+        assert_eq!(log["span.service"], "api".into());
+        assert_eq!(log["span.resource"], "GET /users".into());
+    }
+
+    #[test]
+    fn passes_unmatched_logs() {
+        let mut correlation = SpanCorrelation::new(&SpanCorrelationConfig::default());
+        correlation.transform_one(trace().into());
+
+        let mut log = LogEvent::from("other");
+        log.insert("trace_id", 2);
+        let expected = log.clone();
+        let log = correlation.transform_one(log.into()).into_log();
+
+        assert_eq!(log, expected);
+    }
+}
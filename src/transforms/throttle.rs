@@ -1,10 +1,9 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{collections::HashMap, num::NonZeroU32, time::Duration};
 
-use async_stream::stream;
-use futures::{stream, Stream, StreamExt};
-use governor::{clock, Quota, RateLimiter};
+use governor::clock;
 use snafu::Snafu;
 use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
 
 use crate::{
     conditions::{AnyCondition, Condition},
@@ -13,9 +12,11 @@ use crate::{
     internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
     schema,
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
 };
 
+const THROTTLED: &str = "throttled";
+
 /// Configuration for the `throttle` transform.
 #[configurable_component(transform)]
 #[derive(Clone, Debug, Default)]
@@ -26,10 +27,25 @@ pub struct ThrottleConfig {
     /// Each unique key will have its own `threshold`.
     threshold: u32,
 
+    /// The number of bytes allowed for a given bucket per configured `window_secs`.
+    ///
+    /// This limit applies in addition to `threshold`. The size of an event is its estimated size in memory.
+    threshold_bytes: Option<u32>,
+
+    /// The number of events allowed at once for a given bucket, before being limited to the `threshold` rate.
+    ///
+    /// Defaults to `threshold`.
+    burst: Option<u32>,
+
+    /// The number of bytes allowed at once for a given bucket, before being limited to the `threshold_bytes` rate.
+    ///
+    /// Defaults to `threshold_bytes`. Events larger than this are always throttled.
+    burst_bytes: Option<u32>,
+
     /// The time window in which the configured `threshold` is applied, in seconds.
     window_secs: f64,
 
-    /// The name of the log field whose value will be hashed to determine if the event should be rate limited.
+    /// The name of the log or trace field whose value will be hashed to determine if the event should be rate limited.
     ///
     /// Each unique key will create a buckets of related events to be rate limited separately. If left unspecified, or if the event doesn’t have `key_field`, the event be will not be rate limited separately.
     #[configurable(metadata(templatable))]
@@ -37,6 +53,11 @@ pub struct ThrottleConfig {
 
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
+
+    /// Sends throttled events to the `throttled` output instead of dropping them.
+    ///
+    /// This allows routing the events exceeding the limits elsewhere, such as to cheaper storage.
+    reroute_throttled: bool,
 }
 
 inventory::submit! {
@@ -49,15 +70,23 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        Throttle::new(self, context, clock::MonotonicClock).map(Transform::synchronous)
     }
 
     fn input(&self) -> Input {
-        Input::log()
+        Input::new(DataType::Log | DataType::Trace)
     }
 
     fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
-        vec![Output::default(DataType::Log)]
+        let ty = DataType::Log | DataType::Trace;
+        if self.reroute_throttled {
+            vec![
+                Output::default(ty),
+                Output::default(ty).with_port(THROTTLED),
+            ]
+        } else {
+            vec![Output::default(ty)]
+        }
     }
 
     fn transform_type(&self) -> &'static str {
@@ -65,12 +94,63 @@ impl TransformConfig for ThrottleConfig {
     }
 }
 
+/// A rate limit, enforced with the generic cell rate algorithm like in `governor`.
+///
+/// Rather than counting the units left, each bucket tracks the theoretical arrival time of its
+/// next unit, which advances by `interval` for every unit allowed and may run ahead of the current
+/// time by up to `tolerance`, the time it takes to replenish a full burst.
+#[derive(Clone, Copy, Debug)]
+struct Limit {
+    interval: Duration,
+    tolerance: Duration,
+}
+
+impl Limit {
+    fn new(threshold: u32, burst: Option<u32>, window_secs: f64) -> Result<Self, ConfigError> {
+        let threshold = NonZeroU32::new(threshold).ok_or(ConfigError::NonZero)?;
+        let burst =
+            NonZeroU32::new(burst.unwrap_or_else(|| threshold.get())).ok_or(ConfigError::NonZero)?;
+        if !(window_secs > 0.0 && window_secs.is_finite()) {
+            return Err(ConfigError::NonZero);
+        }
+
+        let interval = Duration::from_secs_f64(window_secs / threshold.get() as f64);
+        let tolerance = interval
+            .checked_mul(burst.get())
+            .filter(|_| !interval.is_zero())
+            .ok_or(ConfigError::NonZero)?;
+        Ok(Self {
+            interval,
+            tolerance,
+        })
+    }
+
+    /// Returns the theoretical arrival time of the bucket once `units` more are allowed, or `None`
+    /// if they exceed the limit. Both are measured from the creation of the transform.
+    fn check(&self, arrival: Duration, now: Duration, units: u32) -> Option<Duration> {
+        let next = arrival.max(now).checked_add(self.interval.checked_mul(units)?)?;
+        (next - now <= self.tolerance).then(|| next)
+    }
+}
+
+/// The theoretical arrival times of the next event and byte of a bucket.
+#[derive(Clone, Copy, Debug, Default)]
+struct Bucket {
+    events: Duration,
+    bytes: Duration,
+}
+
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    limit: Limit,
+    bytes_limit: Option<Limit>,
+    buckets: HashMap<Option<String>, Bucket>,
+    start: I,
     flush_keys_interval: Duration,
+    last_flush: I,
     key_field: Option<Template>,
     exclude: Option<Condition>,
+    reroute_throttled: bool,
     clock: C,
 }
 
@@ -84,19 +164,13 @@ where
         context: &TransformContext,
         clock: C,
     ) -> crate::Result<Self> {
+        let limit = Limit::new(config.threshold, config.burst, config.window_secs)?;
+        let bytes_limit = config
+            .threshold_bytes
+            .map(|threshold| Limit::new(threshold, config.burst_bytes, config.window_secs))
+            .transpose()?;
         let flush_keys_interval = Duration::from_secs_f64(config.window_secs);
 
-        let threshold = match NonZeroU32::new(config.threshold) {
-            Some(threshold) => threshold,
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
-
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            config.window_secs / threshold.get() as f64,
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
         let exclude = config
             .exclude
             .as_ref()
@@ -104,306 +178,360 @@ where
             .transpose()?;
 
         Ok(Self {
-            quota,
-            clock,
+            limit,
+            bytes_limit,
+            buckets: HashMap::new(),
+            start: clock.now(),
             flush_keys_interval,
+            last_flush: clock.now(),
             key_field: config.key_field.clone(),
             exclude,
+            reroute_throttled: config.reroute_throttled,
+            clock,
         })
     }
+
+    /// The time elapsed since the creation of the transform.
+    fn now(&self) -> Duration {
+        Duration::from(self.clock.now().duration_since(self.start))
+    }
+
+    /// Whether the event is within the limits of its bucket, counting it against them if so.
+    fn check(&mut self, key: &Option<String>, event: &Event) -> bool {
+        let now = self.now();
+        let size = u32::try_from(event.size_of()).unwrap_or(u32::MAX);
+        let bucket = self.buckets.entry(key.clone()).or_default();
+
+        // Both limits are checked before either is updated, so that an event throttled by one of
+        // them doesn't count against the other.
+        let events = self.limit.check(bucket.events, now, 1);
+        let bytes = match &self.bytes_limit {
+            Some(limit) => limit.check(bucket.bytes, now, size),
+            None => Some(bucket.bytes),
+        };
+        match (events, bytes) {
+            (Some(events), Some(bytes)) => {
+                *bucket = Bucket { events, bytes };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes the buckets of the keys not seen recently, which are back to their full burst.
+    fn flush_keys(&mut self) {
+        let now = self.clock.now();
+        if Duration::from(now.duration_since(self.last_flush)) >= self.flush_keys_interval * 2 {
+            let elapsed = self.now();
+            self.buckets.retain(|_, bucket| bucket.events > elapsed || bucket.bytes > elapsed);
+            self.last_flush = now;
+        }
+    }
 }
 
-impl<C, I> TaskTransform<Event> for Throttle<C, I>
+impl<C, I> SyncTransform for Throttle<C, I>
 where
-    C: clock::Clock<Instant = I> + Send + 'static,
-    I: clock::Reference + Send + 'static,
+    C: clock::Clock<Instant = I> + Send + Sync,
+    I: clock::Reference,
 {
-    fn transform(
-        self: Box<Self>,
-        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
-    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
-    where
-        Self: 'static,
-    {
-        let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
-
-        let mut flush_stream = tokio::time::interval(Duration::from_millis(1000));
-
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
-
-        Box::pin(
-            stream! {
-              loop {
-                let mut output = Vec::new();
-                let done = tokio::select! {
-                    biased;
-
-                    maybe_event = input_rx.next() => {
-                        match maybe_event {
-                            None => true,
-                            Some(event) => {
-                                let (throttle, event) = match self.exclude.as_ref() {
-                                        Some(condition) => {
-                                            let (result, event) = condition.check(event);
-                                            (!result, event)
-                                        },
-                                        _ => (true, event)
-                                    };
-                                    if throttle {
-                                        let key = self.key_field.as_ref().and_then(|t| {
-                                            t.render_string(&event)
-                                                .map_err(|error| {
-                                                    emit!(TemplateRenderingError {
-                                                        error,
-                                                        field: Some("key_field"),
-                                                        drop_event: false,
-                                                    })
-                                                })
-                                                .ok()
-                                        });
-
-                                        match limiter.check_key(&key) {
-                                            Ok(()) => {
-                                                output.push(event);
-                                            }
-                                            _ => {
-                                                if let Some(key) = key {
-                                                  emit!(ThrottleEventDiscarded{key})
-                                                } else {
-                                                  emit!(ThrottleEventDiscarded{key: "None".to_string()})
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        output.push(event)
-                                    }
-                                false
-                            }
-                        }
-                    }
-                    _ = flush_keys.tick() => {
-                        limiter.retain_recent();
-                        false
-                    }
-                    _ = flush_stream.tick() => {
-                        false
-                    }
-                };
-                yield stream::iter(output.into_iter());
-                if done { break }
-              }
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        self.flush_keys();
+
+        let (throttle, event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
             }
-            .flatten(),
-        )
+            _ => (true, event),
+        };
+
+        if !throttle {
+            output.push(event);
+            return;
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        if self.check(&key, &event) {
+            output.push(event);
+        } else if self.reroute_throttled {
+            output.push_named(THROTTLED, event);
+        } else {
+            emit!(ThrottleEventDiscarded {
+                key: key.unwrap_or_else(|| "None".to_string()),
+            });
+        }
     }
 }
 
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
-    #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
+    #[snafu(display(
+        "`threshold`, `threshold_bytes`, `burst`, `burst_bytes` and `window_secs` must be non-zero"
+    ))]
     NonZero,
 }
 
 #[cfg(test)]
 mod tests {
-    use std::task::Poll;
-
-    use futures::SinkExt;
-
     use super::*;
-    use crate::event::LogEvent;
+    use crate::event::{LogEvent, TraceEvent};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<ThrottleConfig>();
     }
 
-    #[tokio::test]
-    async fn throttle_events() {
+    fn throttle(config: &str, clock: &clock::FakeRelativeClock) -> impl SyncTransform {
+        let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+        Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap()
+    }
+
+    /// Returns the number of events forwarded to the default and `throttled` outputs.
+    fn transform(throttle: &mut dyn SyncTransform, event: impl Into<Event>) -> (usize, usize) {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                Output::default(DataType::Log),
+                Output::default(DataType::Log).with_port(THROTTLED),
+            ],
+            1,
+        );
+
+        throttle.transform(event.into(), &mut outputs);
+
+        (
+            outputs.drain().count(),
+            outputs.drain_named(THROTTLED).count(),
+        )
+    }
+
+    #[test]
+    fn throttle_events() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let mut throttle = throttle(
             r#"
 threshold = 2
 window_secs = 5
 "#,
-        )
-        .unwrap();
+            &clock,
+        );
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
 
-        let throttle = throttle.into_task();
+        clock.advance(Duration::from_secs(2));
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        // The third event is dropped
+        assert_eq!((0, 0), transform(&mut throttle, LogEvent::default()));
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        clock.advance(Duration::from_secs(3));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-        tx.send(LogEvent::default().into()).await.unwrap();
+        // The rate limiter should now be refreshed and allow an additional event through
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+        assert_eq!((0, 0), transform(&mut throttle, LogEvent::default()));
+    }
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+    #[test]
+    fn throttle_exclude() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 2
+window_secs = 5
+exclude = """
+exists(.special)
+"""
+"#,
+            &clock,
+        );
+
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
 
         clock.advance(Duration::from_secs(2));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+        assert_eq!((0, 0), transform(&mut throttle, LogEvent::default()));
 
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // The rate limiter should allow this log through regardless of current limit
+        let mut special_log = LogEvent::default();
+        special_log.insert("special", "true");
+        assert_eq!((1, 0), transform(&mut throttle, special_log));
 
         clock.advance(Duration::from_secs(3));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-
         // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+    }
 
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+    #[test]
+    fn throttle_buckets() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+            &clock,
+        );
 
-        tx.disconnect();
+        let mut log_a = LogEvent::default();
+        log_a.insert("bucket", "a");
+        let mut log_b = LogEvent::default();
+        log_b.insert("bucket", "b");
 
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert_eq!((1, 0), transform(&mut throttle, log_a.clone()));
+        assert_eq!((1, 0), transform(&mut throttle, log_b));
+        assert_eq!((0, 0), transform(&mut throttle, log_a));
     }
 
-    #[tokio::test]
-    async fn throttle_exclude() {
+    #[test]
+    fn throttle_traces() {
         let clock = clock::FakeRelativeClock::default();
-        let config = toml::from_str::<ThrottleConfig>(
+        let mut throttle = throttle(
             r#"
-threshold = 2
+threshold = 1
 window_secs = 5
-exclude = """
-exists(.special)
-"""
+key_field = "{{ service }}"
 "#,
-        )
-        .unwrap();
+            &clock,
+        );
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
+        let trace = |service: &str| {
+            let mut trace = TraceEvent::default();
+            trace.insert("service", service);
+            trace
+        };
 
-        let throttle = throttle.into_task();
+        assert_eq!((1, 0), transform(&mut throttle, trace("api")));
+        assert_eq!((1, 0), transform(&mut throttle, trace("db")));
 
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
+        // The second trace of each service is dropped
+        assert_eq!((0, 0), transform(&mut throttle, trace("api")));
+        assert_eq!((0, 0), transform(&mut throttle, trace("db")));
+    }
 
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+    #[test]
+    fn throttle_bytes() {
+        let clock = clock::FakeRelativeClock::default();
+        let size = Event::from(LogEvent::from("message")).size_of();
+        let mut throttle = throttle(
+            &format!(
+                r#"
+threshold = 100
+threshold_bytes = {}
+window_secs = 5
+"#,
+                size * 2
+            ),
+            &clock,
+        );
 
-        tx.send(LogEvent::default().into()).await.unwrap();
-        tx.send(LogEvent::default().into()).await.unwrap();
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::from("message")));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::from("message")));
+        assert_eq!((0, 0), transform(&mut throttle, LogEvent::from("message")));
 
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
+        clock.advance(Duration::from_secs(5));
 
-        clock.advance(Duration::from_secs(2));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::from("message")));
+    }
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+    #[test]
+    fn throttled_events_count_against_neither_limit() {
+        let clock = clock::FakeRelativeClock::default();
+        let size = Event::from(LogEvent::from("message")).size_of();
+        let mut throttle = throttle(
+            &format!(
+                r#"
+threshold = 2
+burst = 1
+threshold_bytes = {}
+burst_bytes = {}
+window_secs = 5
+"#,
+                size,
+                size * 2
+            ),
+            &clock,
+        );
 
-        // We should be back to pending, having the second event dropped
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        // Throttled by the byte limit, so it mustn't use up the event limit.
+        let large = LogEvent::from("message".repeat(1000));
+        assert_eq!((0, 0), transform(&mut throttle, large));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::from("message")));
 
-        let mut special_log = LogEvent::default();
-        special_log.insert("special", "true");
-        tx.send(special_log.into()).await.unwrap();
-        // The rate limiter should allow this log through regardless of current limit
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+        // Throttled by the event limit, so it mustn't use up the byte limit, which would otherwise
+        // throttle the event allowed once the event limit is replenished.
+        assert_eq!((0, 0), transform(&mut throttle, LogEvent::from("message")));
 
-        clock.advance(Duration::from_secs(3));
+        clock.advance(Duration::from_millis(2500));
 
-        tx.send(LogEvent::default().into()).await.unwrap();
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::from("message")));
+    }
 
-        // The rate limiter should now be refreshed and allow an additional event through
-        if let Some(_event) = out_stream.next().await {
-        } else {
-            panic!("Unexpectedly received None in output stream");
-        }
+    #[test]
+    fn throttle_burst() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 2
+burst = 1
+window_secs = 5
+"#,
+            &clock,
+        );
 
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+        assert_eq!((0, 0), transform(&mut throttle, LogEvent::default()));
 
-        tx.disconnect();
+        clock.advance(Duration::from_millis(2500));
 
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
     }
 
-    #[tokio::test]
-    async fn throttle_buckets() {
+    #[test]
+    fn throttle_reroute() {
         let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 5
+reroute_throttled = true
+"#,
+            &clock,
+        );
+
+        assert_eq!((1, 0), transform(&mut throttle, LogEvent::default()));
+        assert_eq!((0, 1), transform(&mut throttle, LogEvent::default()));
+    }
+
+    #[test]
+    fn zero_burst() {
         let config = toml::from_str::<ThrottleConfig>(
             r#"
 threshold = 1
+burst = 0
 window_secs = 5
-key_field = "{{ bucket }}"
 "#,
         )
         .unwrap();
 
-        let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
-            .map(Transform::event_task)
-            .unwrap();
-
-        let throttle = throttle.into_task();
-
-        let (mut tx, rx) = futures::channel::mpsc::channel(10);
-        let mut out_stream = throttle.transform_events(Box::pin(rx));
-
-        // tokio interval is always immediately ready, so we poll once to make sure
-        // we trip it/set the interval in the future
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
-
-        let mut log_a = LogEvent::default();
-        log_a.insert("bucket", "a");
-        let mut log_b = LogEvent::default();
-        log_b.insert("bucket", "b");
-        tx.send(log_a.into()).await.unwrap();
-        tx.send(log_b.into()).await.unwrap();
-
-        let mut count = 0_u8;
-        while count < 2 {
-            if let Some(_event) = out_stream.next().await {
-                count += 1;
-            } else {
-                panic!("Unexpectedly received None in output stream");
-            }
-        }
-        assert_eq!(2, count);
-
-        // We should be back to pending, having nothing waiting for us
-        assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
-
-        tx.disconnect();
-
-        // And still nothing there
-        assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+        assert!(Throttle::new(
+            &config,
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default()
+        )
+        .is_err());
     }
 }
@@ -0,0 +1,340 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::{
+        metric::{
+            MetricData, MetricKind, MetricName, MetricSeries, MetricSketch, MetricTags, MetricValue,
+        },
+        Event, EventMetadata, Metric,
+    },
+    internal_events::{TopKSeriesRolledUp, TopKUpdateFailed},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// The tag value of the series rolling up the series beyond the top K.
+const OTHER: &str = "_other";
+
+/// Configuration for the `top_k` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TopKConfig {
+    /// The number of series kept per metric name.
+    #[serde(default = "default_k")]
+    pub k: usize,
+
+    /// The interval between flushes, in milliseconds. Must be at least 1.
+    ///
+    /// Over this period, metrics of the same series are aggregated, and the series are ranked by
+    /// their aggregated value.
+    #[serde(default = "default_interval_ms")]
+    pub interval_ms: u64,
+}
+
+const fn default_k() -> usize {
+    10
+}
+
+const fn default_interval_ms() -> u64 {
+    10 * 1000
+}
+
+impl Default for TopKConfig {
+    fn default() -> Self {
+        Self {
+            k: default_k(),
+            interval_ms: default_interval_ms(),
+        }
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<TopKConfig>("top_k")
+}
+
+impl_generate_config_from_default!(TopKConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "top_k")]
+impl TransformConfig for TopKConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.k == 0 {
+            return Err("`k` must be at least 1".into());
+        }
+        if self.interval_ms == 0 {
+            return Err("`interval_ms` must be at least 1".into());
+        }
+        Ok(Transform::event_task(TopK::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "top_k"
+    }
+}
+
+type MetricEntry = (MetricData, EventMetadata);
+
+#[derive(Debug)]
+pub struct TopK {
+    k: usize,
+    interval: Duration,
+    map: BTreeMap<MetricName, BTreeMap<Option<MetricTags>, MetricEntry>>,
+}
+
+impl TopK {
+    pub fn new(config: &TopKConfig) -> Self {
+        Self {
+            k: config.k,
+            interval: Duration::from_millis(config.interval_ms),
+            map: BTreeMap::new(),
+        }
+    }
+
+    fn record(&mut self, event: Event) {
+        let (series, data, metadata) = event.into_metric().into_parts();
+        let series_map = self.map.entry(series.name).or_default();
+
+        match data.kind {
+            MetricKind::Incremental => match series_map.entry(series.tags) {
+                Entry::Occupied(mut entry) => {
+                    let existing = entry.get_mut();
+                    // In order to update (add) the new and old kind's must match
+                    if existing.0.kind == data.kind && existing.0.update(&data) {
+                        existing.1.merge(metadata);
+                    } else {
+                        emit!(TopKUpdateFailed);
+                        *existing = (data, metadata);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((data, metadata));
+                }
+            },
+            MetricKind::Absolute => {
+                series_map.insert(series.tags, (data, metadata));
+            }
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        for (name, series_map) in std::mem::take(&mut self.map) {
+            let mut ranked = series_map.into_iter().collect::<Vec<_>>();
+            ranked
+                .sort_by(|(_, a), (_, b)| magnitude(&b.0.value).total_cmp(&magnitude(&a.0.value)));
+            let rest = ranked.split_off(self.k.min(ranked.len()));
+
+            for (tags, (data, metadata)) in ranked {
+                let series = MetricSeries {
+                    name: name.clone(),
+                    tags,
+                };
+                output.push(Event::Metric(Metric::from_parts(series, data, metadata)));
+            }
+
+            if let Some(other) = roll_up(name, rest) {
+                output.push(Event::Metric(other));
+            }
+        }
+    }
+}
+
+/// The value by which series are ranked.
+fn magnitude(value: &MetricValue) -> f64 {
+    match value {
+        MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+        MetricValue::Set { values } => values.len() as f64,
+        MetricValue::Distribution { samples, .. } => samples
+            .iter()
+            .map(|sample| sample.value * f64::from(sample.rate))
+            .sum(),
+        MetricValue::AggregatedHistogram { sum, .. }
+        | MetricValue::AggregatedSummary { sum, .. } => *sum,
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => sketch.sum().unwrap_or(0.0),
+        MetricValue::Sketch {
+            sketch: MetricSketch::HyperLogLog(hll),
+        } => hll.estimate(),
+    }
+}
+
+/// Rolls the series up into a single series, tagged with each of their tags set to `_other`.
+fn roll_up(name: MetricName, series: Vec<(Option<MetricTags>, MetricEntry)>) -> Option<Metric> {
+    let count = series.len();
+    let mut series = series.into_iter();
+    let (tags, (mut data, mut metadata)) = series.next()?;
+
+    let mut keys = tags
+        .into_iter()
+        .flat_map(BTreeMap::into_keys)
+        .collect::<Vec<_>>();
+    for (tags, (other_data, other_metadata)) in series {
+        if data.update(&other_data) {
+            metadata.merge(other_metadata);
+        } else {
+            emit!(TopKUpdateFailed);
+        }
+        keys.extend(tags.into_iter().flat_map(BTreeMap::into_keys));
+    }
+
+    emit!(TopKSeriesRolledUp { count });
+    let tags = keys
+        .into_iter()
+        .map(|key| (key, OTHER.to_owned()))
+        .collect::<MetricTags>();
+    let series = MetricSeries {
+        name,
+        tags: (!tags.is_empty()).then(|| tags),
+    };
+    Some(Metric::from_parts(series, data, metadata))
+}
+
+impl TaskTransform<Event> for TopK {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.interval);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TopKConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_k_and_interval() {
+        for config in ["k = 0", "interval_ms = 0"] {
+            let config = toml::from_str::<TopKConfig>(config).unwrap();
+            assert!(config.build(&TransformContext::default()).await.is_err());
+        }
+    }
+
+    fn counter(name: &str, host: &str, value: f64) -> Event {
+        Metric::new(
+            name,
+            MetricKind::Incremental,
+            MetricValue::Counter { value },
+        )
+        .with_tags(Some(MetricTags::from([(
+            "host".to_owned(),
+            host.to_owned(),
+        )])))
+        .into()
+    }
+
+    fn flush(top_k: &mut TopK) -> BTreeMap<(String, String), f64> {
+        let mut output = Vec::new();
+        top_k.flush_into(&mut output);
+        output
+            .into_iter()
+            .map(|event| {
+                let metric = event.into_metric();
+                let host = metric.tags().unwrap()["host"].clone();
+                let value = match metric.value() {
+                    MetricValue::Counter { value } => *value,
+                    value => panic!("Expected a counter, got {:?}.", value),
+                };
+                ((metric.name().to_owned(), host), value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_top_k_series_per_name() {
+        let mut top_k = TopK::new(&TopKConfig {
+            k: 2,
+            ..Default::default()
+        });
+
+        for (host, value) in [("a", 1.0), ("b", 5.0), ("c", 3.0), ("d", 2.0), ("b", 1.0)] {
+            top_k.record(counter("requests", host, value));
+        }
+        top_k.record(counter("errors", "a", 1.0));
+
+        let expected = BTreeMap::from([
+            (("requests".to_owned(), "b".to_owned()), 6.0),
+            (("requests".to_owned(), "c".to_owned()), 3.0),
+            (("requests".to_owned(), OTHER.to_owned()), 3.0),
+            (("errors".to_owned(), "a".to_owned()), 1.0),
+        ]);
+        assert_eq!(flush(&mut top_k), expected);
+
+        // The series are ranked anew each interval.
+        assert!(flush(&mut top_k).is_empty());
+    }
+
+    #[test]
+    fn absolute_metrics_replace_previous_values() {
+        let mut top_k = TopK::new(&TopKConfig {
+            k: 1,
+            ..Default::default()
+        });
+
+        for (host, value) in [("a", 10.0), ("b", 5.0), ("a", 1.0)] {
+            let event =
+                Metric::new("cpu", MetricKind::Absolute, MetricValue::Gauge { value }).with_tags(
+                    Some(MetricTags::from([("host".to_owned(), host.to_owned())])),
+                );
+            top_k.record(event.into());
+        }
+
+        let mut output = Vec::new();
+        top_k.flush_into(&mut output);
+        let hosts = output
+            .into_iter()
+            .map(|event| event.into_metric().tags().unwrap()["host"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(hosts, ["b", OTHER]);
+    }
+}
@@ -0,0 +1,523 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use lru::LruCache;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, Input, Output, TransformConfig, TransformContext, TransformDescription},
+    event::{Event, TraceEvent, Value},
+    internal_events::{TraceSamplingTraceDropped, TraceSamplingTraceEvicted},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+/// Configuration for the `trace_sampling` transform.
+#[configurable_component(transform)]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct TraceSamplingConfig {
+    /// The time to wait after the first span of a trace is received before deciding whether to sample it, in milliseconds.
+    ///
+    /// Spans of the trace received within this window are released or dropped together. Spans received after the
+    /// decision follow the decision taken for their trace, as long as it is still remembered.
+    pub decision_wait_ms: Option<u64>,
+
+    /// The interval to check for traces ready for a decision, in milliseconds. Must be non-zero.
+    pub flush_period_ms: Option<u64>,
+
+    /// The maximum number of traces awaiting a decision.
+    ///
+    /// When exceeded, the decision for the oldest trace is taken early. This is also the number of decisions
+    /// remembered for late spans.
+    pub max_traces: Option<usize>,
+
+    /// The policies deciding whether a trace is sampled.
+    ///
+    /// A trace is sampled if any of the policies samples it. When no policies are specified, all traces are sampled.
+    pub policies: Vec<SamplingPolicy>,
+}
+
+/// A policy deciding whether a trace is sampled.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SamplingPolicy {
+    /// Samples traces with at least one span with a non-zero `error` field.
+    Error,
+
+    /// Samples traces with at least one span lasting at least a given time.
+    Latency(#[configurable(derived)] LatencyPolicy),
+
+    /// Samples a proportion of traces, optionally at a different rate for each service.
+    Probabilistic(#[configurable(derived)] ProbabilisticPolicy),
+}
+
+/// Latency sampling policy configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct LatencyPolicy {
+    /// The minimum duration of a span sampling its trace, in milliseconds.
+    ///
+    /// The duration of a span is read from its `duration` field, in nanoseconds.
+    pub threshold_ms: u64,
+}
+
+/// Probabilistic sampling policy configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ProbabilisticPolicy {
+    /// The proportion of traces sampled, between `0.0` and `1.0`.
+    ///
+    /// The decision is made from a hash of the trace ID, so that all the instances of the transform take the same
+    /// decision for a trace.
+    pub rate: f64,
+
+    /// The proportion of traces sampled for specific services, overriding `rate`.
+    ///
+    /// The service of a trace is the `service` field of its root span, or of its first span if the root span wasn't
+    /// received.
+    #[serde(default)]
+    pub service_rates: HashMap<String, f64>,
+}
+
+inventory::submit! {
+    TransformDescription::new::<TraceSamplingConfig>("trace_sampling")
+}
+
+impl_generate_config_from_default!(TraceSamplingConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "trace_sampling")]
+impl TransformConfig for TraceSamplingConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TraceSampling::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Trace)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "trace_sampling"
+    }
+}
+
+impl SamplingPolicy {
+    fn samples(&self, trace_id: &str, events: &[TraceEvent]) -> bool {
+        match self {
+            SamplingPolicy::Error => spans(events).any(|span| match span.get("error") {
+                Some(Value::Integer(error)) => *error != 0,
+                Some(Value::Boolean(error)) => *error,
+                _ => false,
+            }),
+            SamplingPolicy::Latency(LatencyPolicy { threshold_ms }) => {
+                let threshold_ns = threshold_ms.saturating_mul(1_000_000);
+                spans(events).any(|span| match span.get("duration") {
+                    Some(Value::Integer(duration)) => {
+                        u64::try_from(*duration).map_or(false, |duration| duration >= threshold_ns)
+                    }
+                    _ => false,
+                })
+            }
+            SamplingPolicy::Probabilistic(ProbabilisticPolicy {
+                rate,
+                service_rates,
+            }) => {
+                let rate = service(events)
+                    .and_then(|service| service_rates.get(&service))
+                    .unwrap_or(rate);
+                (seahash::hash(trace_id.as_bytes()) as f64 / u64::MAX as f64) < *rate
+            }
+        }
+    }
+}
+
+/// Iterates over the spans of trace events, which either hold their spans in a `spans` array or
+/// are spans themselves.
+fn spans(events: &[TraceEvent]) -> impl Iterator<Item = &BTreeMap<String, Value>> {
+    events.iter().flat_map(|event| match event.get("spans") {
+        Some(Value::Array(spans)) => spans
+            .iter()
+            .filter_map(Value::as_object)
+            .collect::<Vec<_>>(),
+        _ => vec![event.as_map()],
+    })
+}
+
+fn service(events: &[TraceEvent]) -> Option<String> {
+    let is_root = |span: &&BTreeMap<String, Value>| {
+        matches!(span.get("parent_id"), None | Some(Value::Integer(0)))
+    };
+    spans(events)
+        .find(is_root)
+        .or_else(|| spans(events).next())
+        .and_then(|span| span.get("service"))
+        .map(|service| service.to_string_lossy())
+}
+
+fn trace_id(event: &TraceEvent) -> Option<String> {
+    event
+        .get("trace_id")
+        .or_else(|| spans(std::slice::from_ref(event)).find_map(|span| span.get("trace_id")))
+        .map(Value::to_string_lossy)
+}
+
+struct PendingTrace {
+    events: Vec<TraceEvent>,
+    first_seen: Instant,
+}
+
+pub struct TraceSampling {
+    decision_wait: Duration,
+    flush_period: Duration,
+    max_traces: usize,
+    policies: Vec<SamplingPolicy>,
+    pending: HashMap<String, PendingTrace>,
+    /// The IDs of the pending traces, in the order they were first seen.
+    order: VecDeque<String>,
+    decisions: LruCache<String, bool>,
+}
+
+impl TraceSampling {
+    pub fn new(config: &TraceSamplingConfig) -> crate::Result<Self> {
+        for policy in &config.policies {
+            if let SamplingPolicy::Probabilistic(ProbabilisticPolicy {
+                rate,
+                service_rates,
+            }) = policy
+            {
+                if std::iter::once(rate)
+                    .chain(service_rates.values())
+                    .any(|rate| !(0.0..=1.0).contains(rate))
+                {
+                    return Err("probabilistic sampling rates must be between 0.0 and 1.0".into());
+                }
+            }
+        }
+
+        let max_traces = config.max_traces.unwrap_or(50_000);
+        if max_traces == 0 {
+            return Err("`max_traces` must be non-zero".into());
+        }
+        let flush_period_ms = config.flush_period_ms.unwrap_or(1000);
+        if flush_period_ms == 0 {
+            return Err("`flush_period_ms` must be non-zero".into());
+        }
+
+        Ok(Self {
+            decision_wait: Duration::from_millis(config.decision_wait_ms.unwrap_or(30000)),
+            flush_period: Duration::from_millis(flush_period_ms),
+            max_traces,
+            policies: config.policies.clone(),
+            pending: HashMap::new(),
+            order: VecDeque::new(),
+            decisions: LruCache::new(max_traces),
+        })
+    }
+
+    fn sample(&self, trace_id: &str, events: &[TraceEvent]) -> bool {
+        self.policies.is_empty()
+            || self
+                .policies
+                .iter()
+                .any(|policy| policy.samples(trace_id, events))
+    }
+
+    fn decide(&mut self, trace_id: String, output: &mut Vec<Event>) {
+        if let Some(trace) = self.pending.remove(&trace_id) {
+            let sampled = self.sample(&trace_id, &trace.events);
+            if sampled {
+                output.extend(trace.events.into_iter().map(Event::from));
+            } else {
+                emit!(TraceSamplingTraceDropped {
+                    count: trace.events.len()
+                });
+            }
+            self.decisions.put(trace_id, sampled);
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        while let Some(trace_id) = self.order.front() {
+            match self.pending.get(trace_id) {
+                Some(trace) if trace.first_seen.elapsed() < self.decision_wait => break,
+                _ => {
+                    let trace_id = self.order.pop_front().expect("front exists");
+                    self.decide(trace_id, output);
+                }
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        while let Some(trace_id) = self.order.pop_front() {
+            self.decide(trace_id, output);
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let event = event.into_trace();
+        let trace_id = match trace_id(&event) {
+            Some(trace_id) => trace_id,
+            None => {
+                output.push(event.into());
+                return;
+            }
+        };
+
+        if let Some(sampled) = self.decisions.get(&trace_id) {
+            if *sampled {
+                output.push(event.into());
+            } else {
+                emit!(TraceSamplingTraceDropped { count: 1 });
+            }
+            return;
+        }
+
+        match self.pending.get_mut(&trace_id) {
+            Some(trace) => trace.events.push(event),
+            None => {
+                self.pending.insert(
+                    trace_id.clone(),
+                    PendingTrace {
+                        events: vec![event],
+                        first_seen: Instant::now(),
+                    },
+                );
+                self.order.push_back(trace_id);
+
+                if self.pending.len() > self.max_traces {
+                    emit!(TraceSamplingTraceEvicted);
+                    let oldest = self.order.pop_front().expect("pending traces are ordered");
+                    self.decide(oldest, output);
+                }
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for TraceSampling {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TraceSamplingConfig>();
+    }
+
+    fn span(trace_id: i64, parent_id: i64, service: &str) -> Value {
+        Value::from(btreemap! {
+            "trace_id" => trace_id,
+            "span_id" => parent_id + 1,
+            "parent_id" => parent_id,
+            "service" => service,
+            "duration" => 1_000_000,
+            "error" => 0,
+        })
+    }
+
+    fn trace(spans: Vec<Value>) -> TraceEvent {
+        let mut trace = TraceEvent::default();
+        trace.insert("spans", spans);
+        trace
+    }
+
+    fn sampling(config: &str) -> TraceSampling {
+        TraceSampling::new(&toml::from_str::<TraceSamplingConfig>(config).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn error_policy() {
+        let sampling = sampling(
+            r#"
+[[policies]]
+type = "error"
+"#,
+        );
+
+        let ok = vec![trace(vec![span(1, 0, "api"), span(1, 1, "db")])];
+        assert!(!sampling.sample("1", &ok));
+
+        let mut failed = span(1, 1, "db");
+        failed
+            .as_object_mut()
+            .unwrap()
+            .insert("error".into(), 1.into());
+        let failed = vec![trace(vec![span(1, 0, "api")]), trace(vec![failed])];
+        assert!(sampling.sample("1", &failed));
+    }
+
+    #[test]
+    fn latency_policy() {
+        let sampling = sampling(
+            r#"
+[[policies]]
+type = "latency"
+threshold_ms = 500
+"#,
+        );
+
+        let fast = vec![trace(vec![span(1, 0, "api")])];
+        assert!(!sampling.sample("1", &fast));
+
+        let mut slow = span(1, 0, "api");
+        slow.as_object_mut()
+            .unwrap()
+            .insert("duration".into(), 600_000_000.into());
+        assert!(sampling.sample("1", &[trace(vec![slow])]));
+    }
+
+    #[test]
+    fn probabilistic_policy_per_service() {
+        let sampling = sampling(
+            r#"
+[[policies]]
+type = "probabilistic"
+rate = 0.0
+service_rates.checkout = 1.0
+"#,
+        );
+
+        let api = vec![trace(vec![span(1, 0, "api"), span(1, 1, "checkout")])];
+        assert!(!sampling.sample("1", &api));
+
+        let checkout = vec![trace(vec![span(2, 1, "api"), span(2, 0, "checkout")])];
+        assert!(sampling.sample("2", &checkout));
+    }
+
+    #[test]
+    fn rejects_invalid_rates() {
+        let config = toml::from_str::<TraceSamplingConfig>(
+            r#"
+[[policies]]
+type = "probabilistic"
+rate = 1.5
+"#,
+        )
+        .unwrap();
+        assert!(TraceSampling::new(&config).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_flush_period() {
+        let config = toml::from_str::<TraceSamplingConfig>("flush_period_ms = 0").unwrap();
+        assert!(TraceSampling::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn releases_whole_traces() {
+        let sampling = toml::from_str::<TraceSamplingConfig>(
+            r#"
+[[policies]]
+type = "error"
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap()
+        .into_task();
+
+        let mut failed = span(2, 1, "db");
+        failed
+            .as_object_mut()
+            .unwrap()
+            .insert("error".into(), 1.into());
+        let inputs = vec![
+            trace(vec![span(1, 0, "api")]),
+            trace(vec![span(2, 0, "api")]),
+            TraceEvent::default(),
+            trace(vec![span(1, 1, "db")]),
+            trace(vec![failed]),
+        ];
+        let expected = vec![inputs[2].clone(), inputs[1].clone(), inputs[4].clone()];
+
+        let out_stream =
+            sampling.transform_events(Box::pin(stream::iter(inputs.into_iter().map(Event::from))));
+        let output = out_stream.map(Event::into_trace).collect::<Vec<_>>().await;
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn late_spans_follow_decision() {
+        let mut sampling = sampling(
+            r#"
+max_traces = 1
+
+[[policies]]
+type = "error"
+"#,
+        );
+
+        let mut output = Vec::new();
+        sampling.transform_one(&mut output, trace(vec![span(1, 0, "api")]).into());
+        // Exceeding `max_traces` decides on the first trace early.
+        sampling.transform_one(&mut output, trace(vec![span(2, 0, "api")]).into());
+
+        let mut failed = span(1, 1, "db");
+        failed
+            .as_object_mut()
+            .unwrap()
+            .insert("error".into(), 1.into());
+        sampling.transform_one(&mut output, trace(vec![failed]).into());
+
+        assert!(output.is_empty());
+        assert_eq!(sampling.pending.len(), 1);
+    }
+}
@@ -1,5 +1,19 @@
+use std::{future::Future, io, net::SocketAddr, sync::Arc};
+
+#[cfg(target_os = "linux")]
+use std::time::Duration;
+
 use socket2::SockRef;
 use tokio::net::UdpSocket;
+use tracing::Instrument;
+
+#[cfg(target_os = "linux")]
+use crate::internal_events::{UdpReceiveBufferResized, UdpSocketDatagramsDropped};
+use crate::shutdown::ShutdownSignal;
+
+/// How often the kernel drop counter of an autotuned socket is checked.
+#[cfg(target_os = "linux")]
+const AUTOTUNE_INTERVAL: Duration = Duration::from_secs(1);
 
 // This function will be obsolete after tokio/mio internally use `socket2` and expose the methods to
 // apply options to a socket.
@@ -12,3 +26,256 @@ pub fn set_receive_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Resu
 pub fn set_send_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Result<()> {
     SockRef::from(socket).set_send_buffer_size(size)
 }
+
+/// Binds `workers` UDP sockets to `address`.
+///
+/// When more than one socket is requested, `SO_REUSEPORT` is set on each of them, so that the
+/// kernel spreads incoming datagrams across the sockets instead of queueing them all on one.
+pub async fn bind_sockets(address: SocketAddr, workers: usize) -> io::Result<Vec<Arc<UdpSocket>>> {
+    if workers <= 1 {
+        return Ok(vec![Arc::new(UdpSocket::bind(address).await?)]);
+    }
+
+    (0..workers)
+        .map(|_| bind_reuse_port(address).map(Arc::new))
+        .collect()
+}
+
+#[cfg(unix)]
+fn bind_reuse_port(address: SocketAddr) -> io::Result<UdpSocket> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )?;
+    socket.set_reuse_port(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+    UdpSocket::from_std(socket.into())
+}
+
+#[cfg(not(unix))]
+fn bind_reuse_port(_address: SocketAddr) -> io::Result<UdpSocket> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "multiple UDP workers require SO_REUSEPORT, which is only supported on Unix",
+    ))
+}
+
+/// Applies the configured receive buffer size to `socket`.
+///
+/// If `receive_buffer_max_bytes` is set, a task is spawned that doubles the receive buffer, up to
+/// that size, whenever the kernel reports datagrams dropped on the socket.
+pub fn configure_receive_buffer(
+    socket: &Arc<UdpSocket>,
+    receive_buffer_bytes: Option<usize>,
+    receive_buffer_max_bytes: Option<usize>,
+    shutdown: ShutdownSignal,
+) {
+    if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+        if let Err(error) = set_receive_buffer_size(socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    if let Some(receive_buffer_max_bytes) = receive_buffer_max_bytes {
+        #[cfg(target_os = "linux")]
+        {
+            // The kernel doubles the requested size to leave room for bookkeeping, and reports
+            // the doubled value back.
+            let initial = match receive_buffer_bytes {
+                Some(size) => size,
+                None => SockRef::from(&**socket)
+                    .recv_buffer_size()
+                    .map(|size| size / 2)
+                    .unwrap_or(receive_buffer_max_bytes),
+            };
+            tokio::spawn(
+                autotune_receive_buffer(
+                    Arc::clone(socket),
+                    initial,
+                    receive_buffer_max_bytes,
+                    shutdown,
+                )
+                .in_current_span(),
+            );
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = (receive_buffer_max_bytes, shutdown);
+            warn!(message = "Receive buffer autotuning is only supported on Linux.");
+        }
+    }
+}
+
+/// Runs `worker` for each socket, each on its own task, and completes once all of them have.
+///
+/// A single socket is read on the current task.
+pub async fn run_workers<F, Fut>(sockets: Vec<Arc<UdpSocket>>, worker: F) -> Result<(), ()>
+where
+    F: Fn(Arc<UdpSocket>) -> Fut,
+    Fut: Future<Output = Result<(), ()>> + Send + 'static,
+{
+    if sockets.len() == 1 {
+        let socket = sockets.into_iter().next().expect("one socket");
+        return worker(socket).await;
+    }
+
+    let handles = sockets
+        .into_iter()
+        .map(|socket| tokio::spawn(worker(socket).in_current_span()))
+        .collect::<Vec<_>>();
+
+    let mut result = Ok(());
+    for handle in handles {
+        match handle.await {
+            Ok(Ok(())) => {}
+            Ok(Err(())) => result = Err(()),
+            Err(error) => {
+                error!(message = "UDP worker task failed.", %error);
+                result = Err(());
+            }
+        }
+    }
+    result
+}
+
+#[cfg(target_os = "linux")]
+async fn autotune_receive_buffer(
+    socket: Arc<UdpSocket>,
+    mut size: usize,
+    max_size: usize,
+    mut shutdown: ShutdownSignal,
+) {
+    let mut interval = tokio::time::interval(AUTOTUNE_INTERVAL);
+    let mut last_drops = None;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = &mut shutdown => return,
+        }
+
+        // The workers reading from the socket are gone.
+        if Arc::strong_count(&socket) == 1 {
+            return;
+        }
+
+        let drops = match socket_drops(&socket) {
+            Ok(Some(drops)) => drops,
+            Ok(None) => continue,
+            Err(error) => {
+                warn!(
+                    message = "Failed reading UDP socket drop counter, disabling receive buffer autotuning.",
+                    %error
+                );
+                return;
+            }
+        };
+        let dropped = drops.saturating_sub(last_drops.unwrap_or(drops));
+        last_drops = Some(drops);
+        if dropped == 0 {
+            continue;
+        }
+
+        emit!(UdpSocketDatagramsDropped { count: dropped });
+
+        if size < max_size {
+            size = size.saturating_mul(2).min(max_size);
+            match set_receive_buffer_size(&socket, size) {
+                Ok(()) => emit!(UdpReceiveBufferResized {
+                    receive_buffer_bytes: size
+                }),
+                Err(error) => {
+                    warn!(message = "Failed resizing receive buffer on UDP socket.", %error)
+                }
+            }
+        }
+    }
+}
+
+/// Reads the number of datagrams the kernel dropped on `socket` from `/proc/net/udp{,6}`.
+#[cfg(target_os = "linux")]
+fn socket_drops(socket: &UdpSocket) -> io::Result<Option<u64>> {
+    use std::os::unix::io::AsRawFd;
+
+    let link = std::fs::read_link(format!("/proc/self/fd/{}", socket.as_raw_fd()))?;
+    let inode = match parse_socket_inode(&link.to_string_lossy()) {
+        Some(inode) => inode,
+        None => return Ok(None),
+    };
+    let table = if socket.local_addr()?.is_ipv4() {
+        "/proc/net/udp"
+    } else {
+        "/proc/net/udp6"
+    };
+
+    Ok(find_drops(&std::fs::read_to_string(table)?, inode))
+}
+
+/// Parses the inode out of a socket file descriptor link, i.e. `socket:[12345]`.
+#[cfg(any(target_os = "linux", test))]
+fn parse_socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Finds the `drops` column of the socket with the given inode in a `/proc/net/udp` table.
+#[cfg(any(target_os = "linux", test))]
+fn find_drops(table: &str, inode: u64) -> Option<u64> {
+    table.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        if fields.nth(9)?.parse::<u64>().ok()? != inode {
+            return None;
+        }
+        fields.nth(2)?.parse().ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UDP_TABLE: &str = "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  123: 00000000:1F90 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 41521 2 0000000000000000 0
+  456: 0100007F:1F91 00000000:0000 07 00000000:00034000 00:00000000 00000000  1000        0 41522 2 0000000000000000 1337
+";
+
+    #[test]
+    fn parses_socket_inode() {
+        assert_eq!(parse_socket_inode("socket:[41522]"), Some(41522));
+        assert_eq!(parse_socket_inode("pipe:[41522]"), None);
+        assert_eq!(parse_socket_inode("/dev/null"), None);
+    }
+
+    #[test]
+    fn finds_drops_by_inode() {
+        assert_eq!(find_drops(UDP_TABLE, 41521), Some(0));
+        assert_eq!(find_drops(UDP_TABLE, 41522), Some(1337));
+        assert_eq!(find_drops(UDP_TABLE, 1), None);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn binds_multiple_workers_to_one_address() {
+        let address = crate::test_util::next_addr();
+        let sockets = bind_sockets(address, 3).await.unwrap();
+
+        assert_eq!(sockets.len(), 3);
+        for socket in &sockets {
+            assert_eq!(socket.local_addr().unwrap(), address);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn reads_socket_drops() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        assert_eq!(socket_drops(&socket).unwrap(), Some(0));
+    }
+}
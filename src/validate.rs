@@ -1,11 +1,18 @@
-use std::{collections::HashMap, fmt, fs::remove_dir_all, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::remove_dir_all,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 use clap::Parser;
 use colored::*;
 use exitcode::ExitCode;
 
 use crate::{
-    config::{self, Config, ConfigDiff},
+    config::{self, ComponentKey, Config, ConfigDiff, ProxyConfig, SinkContext},
+    sinks::Healthcheck,
     topology::{self, builder::Pieces},
 };
 
@@ -23,6 +30,15 @@ pub struct Opts {
     #[clap(short, long)]
     pub deny_warnings: bool,
 
+    /// Attempts real connections to the external systems of each source and sink, running
+    /// their health checks even when they are disabled, and prints a summary of the results.
+    #[clap(long, conflicts_with = "no-environment")]
+    pub deep: bool,
+
+    /// Timeout of each check of the deep mode, in seconds.
+    #[clap(long, default_value = "10")]
+    pub deep_timeout_secs: u64,
+
     /// Vector config files in TOML format to validate.
     #[clap(
         name = "config-toml",
@@ -176,7 +192,11 @@ async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter)
         return false;
     };
 
-    validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await
+    if opts.deep {
+        validate_deep(opts, config, fmt).await
+    } else {
+        validate_healthchecks(opts, config, &diff, &mut pieces, fmt).await
+    }
 }
 
 async fn validate_components(
@@ -244,6 +264,135 @@ async fn validate_healthchecks(
     validated
 }
 
+enum DeepCheckResult {
+    Passed,
+    Failed(String),
+    TimedOut,
+    Unsupported,
+}
+
+struct DeepCheck {
+    kind: &'static str,
+    id: String,
+    component_type: &'static str,
+    result: DeepCheckResult,
+    duration: Duration,
+}
+
+/// Runs the health checks of all sources and sinks, regardless of whether they are enabled, with a
+/// timeout for each, then prints a summary table.
+async fn validate_deep(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
+    let timeout = Duration::from_secs(opts.deep_timeout_secs);
+    let mut checks = Vec::new();
+
+    for (key, source) in config.sources() {
+        let healthcheck = source.inner.healthcheck().map(Ok);
+        checks.push(
+            run_deep_check(
+                "source",
+                key,
+                source.inner.source_type(),
+                healthcheck,
+                timeout,
+            )
+            .await,
+        );
+    }
+
+    for (key, sink) in config.sinks() {
+        let cx = SinkContext {
+            key: key.clone(),
+            healthcheck: config::SinkHealthcheckOptions {
+                enabled: true,
+                ..sink.healthcheck()
+            },
+            globals: config.global.clone(),
+            proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
+            connection_pool: *sink.connection_pool(),
+            schema: config.schema,
+        };
+        let healthcheck = sink
+            .inner
+            .build(cx)
+            .await
+            .map(|(_, healthcheck)| healthcheck);
+        checks.push(
+            run_deep_check(
+                "sink",
+                key,
+                sink.inner.sink_type(),
+                Some(healthcheck),
+                timeout,
+            )
+            .await,
+        );
+    }
+
+    let mut validated = true;
+    let rows = checks
+        .iter()
+        .map(|check| {
+            let result = match &check.result {
+                DeepCheckResult::Passed => "passed".to_owned(),
+                DeepCheckResult::Failed(error) => {
+                    validated = false;
+                    format!("failed: {}", error)
+                }
+                DeepCheckResult::TimedOut => {
+                    validated = false;
+                    format!("timed out after {}s", timeout.as_secs())
+                }
+                DeepCheckResult::Unsupported => "no check".to_owned(),
+            };
+            [
+                check.kind.to_owned(),
+                check.id.clone(),
+                check.component_type.to_owned(),
+                format!("{}ms", check.duration.as_millis()),
+                result,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    fmt.title("Deep checks");
+    fmt.table(["Kind", "ID", "Type", "Duration", "Result"], rows);
+
+    validated
+}
+
+async fn run_deep_check(
+    kind: &'static str,
+    key: &ComponentKey,
+    component_type: &'static str,
+    healthcheck: Option<crate::Result<Healthcheck>>,
+    timeout: Duration,
+) -> DeepCheck {
+    let start = Instant::now();
+    let result = match healthcheck {
+        None => DeepCheckResult::Unsupported,
+        Some(Err(error)) => DeepCheckResult::Failed(error.to_string()),
+        Some(Ok(healthcheck)) => {
+            match tokio::spawn(tokio::time::timeout(timeout, healthcheck)).await {
+                Ok(Ok(Ok(()))) => DeepCheckResult::Passed,
+                Ok(Ok(Err(error))) => DeepCheckResult::Failed(error.to_string()),
+                Ok(Err(_)) => DeepCheckResult::TimedOut,
+                Err(error) if error.is_cancelled() => {
+                    DeepCheckResult::Failed("cancelled".to_owned())
+                }
+                Err(_) => DeepCheckResult::Failed("panicked".to_owned()),
+            }
+        }
+    };
+
+    DeepCheck {
+        kind,
+        id: key.to_string(),
+        component_type,
+        result,
+        duration: start.elapsed(),
+    }
+}
+
 /// For data directory that we write to:
 /// 1. Create a tmp directory in it.
 /// 2. Change config to point to that tmp directory.
@@ -374,6 +523,36 @@ impl Formatter {
         self.sub(self.error_intro.clone(), errors)
     }
 
+    /// A table with a header, columns are padded to their widest cell.
+    fn table<const N: usize>(&mut self, header: [&str; N], rows: Vec<[String; N]>) {
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let line = |cells: Vec<&str>| {
+            cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_owned()
+        };
+
+        let mut table = line(header.to_vec());
+        table.push('\n');
+        for row in &rows {
+            table.push_str(&line(row.iter().map(String::as_str).collect()));
+            table.push('\n');
+        }
+        self.print(table);
+        self.space();
+    }
+
     fn sub<I: IntoIterator>(&mut self, intro: impl AsRef<str>, msgs: I)
     where
         I::Item: fmt::Display,
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use clap::Parser;
+use exitcode::ExitCode;
+
+use crate::config::{self, ConfigPath};
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Vector config files whose enrichment tables are made available to programs, for example to
+    /// `get_enrichment_table_record`.
+    ///
+    /// Secret backends aren't contacted: each `SECRET[backend.key]` placeholder in the config is
+    /// replaced by the `--secret` with the key `backend.key`, or by an empty string.
+    #[clap(name = "config", short, long, use_value_delimiter(true))]
+    config_paths: Vec<PathBuf>,
+
+    #[clap(flatten)]
+    vrl: vrl_cli::Opts,
+}
+
+type TableMap = HashMap<String, Box<dyn enrichment::Table + Send + Sync>>;
+
+/// Runs the VRL CLI, with the enrichment tables of the given config loaded.
+pub async fn cmd(opts: &Opts) -> ExitCode {
+    let enrichment_tables = enrichment::TableRegistry::default();
+
+    if !opts.config_paths.is_empty() {
+        match load_enrichment_tables(opts).await {
+            Ok(tables) => enrichment_tables.load(tables),
+            Err(errors) => {
+                #[allow(clippy::print_stderr)]
+                for error in errors {
+                    eprintln!("{}", error);
+                }
+                return exitcode::CONFIG;
+            }
+        }
+    }
+
+    vrl_cli::cmd_with_enrichment_tables(&opts.vrl, &enrichment_tables)
+}
+
+async fn load_enrichment_tables(opts: &Opts) -> Result<TableMap, Vec<String>> {
+    let paths = opts
+        .config_paths
+        .iter()
+        .map(|path| ConfigPath::File(path.clone(), None))
+        .collect::<Vec<_>>();
+
+    let mocked_secrets = opts
+        .vrl
+        .secrets()
+        .map_err(|error| vec![error.to_string()])?;
+    let (secret_backends, _) = config::load_secret_backends_from_paths(&paths)?;
+    let secrets = secret_backends
+        .secret_keys
+        .into_iter()
+        .flat_map(|(backend, keys)| {
+            keys.into_iter()
+                .map(move |key| format!("{}.{}", backend, key))
+        })
+        .map(|name| {
+            let value = mocked_secrets.get(&name).cloned().unwrap_or_default();
+            (name, value)
+        })
+        .collect();
+
+    let (builder, _) = config::load_builder_from_paths_with_secrets(&paths, secrets)?;
+
+    let mut tables = HashMap::new();
+    let mut errors = Vec::new();
+    for (name, table) in builder.enrichment_tables.iter() {
+        match table.inner.build(&builder.global).await {
+            Ok(table) => {
+                tables.insert(name.to_string(), table);
+            }
+            Err(error) => errors.push(format!("Enrichment Table \"{}\": {}", name, error)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(tables)
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(all(test, feature = "enrichment-tables-file"))]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn loads_enrichment_tables_with_mocked_secrets() {
+        let directory = tempfile::tempdir().unwrap();
+        fs::write(directory.path().join("codes.csv"), "code,name\n1,one\n").unwrap();
+        let config = directory.path().join("vector.toml");
+        fs::write(
+            &config,
+            r#"
+                [enrichment_tables.codes]
+                type = "file"
+                file.path = "SECRET[files.directory]/codes.csv"
+                file.encoding.type = "csv"
+            "#,
+        )
+        .unwrap();
+
+        let opts = Opts::try_parse_from([
+            "vrl".to_string(),
+            "--config".to_string(),
+            config.to_string_lossy().into_owned(),
+            "--secret".to_string(),
+            format!("files.directory={}", directory.path().display()),
+        ])
+        .unwrap();
+        let tables = load_enrichment_tables(&opts).await.unwrap();
+        assert!(tables.contains_key("codes"));
+
+        // Without the secret, the placeholder is replaced by an empty string and the table can't
+        // be found.
+        let opts = Opts::try_parse_from([
+            "vrl".to_string(),
+            "--config".to_string(),
+            config.to_string_lossy().into_owned(),
+        ])
+        .unwrap();
+        assert!(load_enrichment_tables(&opts).await.is_err());
+    }
+}
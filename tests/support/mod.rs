@@ -277,6 +277,7 @@ impl FunctionTransform for MockTransform {
                     assert!(metric.add(&MetricData {
                         kind: metric.kind(),
                         timestamp: metric.timestamp(),
+                        interval_ms: metric.interval_ms(),
                         value: increment,
                     }));
                 }
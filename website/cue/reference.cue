@@ -21,7 +21,7 @@ _values: {
 //
 // * `none` - compression is not applied
 // * `gzip` - gzip compression applied
-#CompressionAlgorithm: "none" | "gzip" | "lz4" | "snappy" | "zstd"
+#CompressionAlgorithm: "none" | "gzip" | "lz4" | "snappy" | "zstd" | "brotli"
 
 #CompressionLevel: "none" | "fast" | "default" | "best" | >=0 & <=9
 
@@ -55,7 +55,7 @@ _values: {
 // * `removed` - The component has been removed.
 #DevelopmentStatus: "beta" | "stable" | "deprecated" | "removed"
 
-#EncodingCodec: "json" | "logfmt" | "text" | "native" | "native_json" | "avro"
+#EncodingCodec: "json" | "logfmt" | "text" | "native" | "native_json" | "avro" | "cef" | "leef"
 
 #Endpoint: {
 	description: string
@@ -234,7 +234,7 @@ _values: {
 	}
 }
 
-#Protocol: "http" | "tcp" | "udp" | "unix" | "unix_datagram" | "unix_stream"
+#Protocol: "http" | "tcp" | "udp" | "unix" | "unix_datagram" | "unix_seqpacket" | "unix_stream"
 
 #Service: {
 	// `description` describes the components with a single paragraph.
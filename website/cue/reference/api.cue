@@ -47,6 +47,37 @@ api: {
 				of the address set using the `bind` parameter.
 				"""
 		}
+		component_health: {
+			common:      false
+			required:    false
+			description: "Options for the health of the components, reported by the `/health/components` endpoint."
+			type: object: options: {
+				max_errors_per_sec: {
+					common:      false
+					required:    false
+					description: "The error rate above which a running component is reported as `degraded`."
+					type: float: default: 1.0
+				}
+				window_secs: {
+					common:      false
+					required:    false
+					description: "The interval over which the error rate of the components is evaluated."
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+				fail_on_degraded: {
+					common:   false
+					required: false
+					description: """
+						Whether `/health/components` responds with a `503` when a component is
+						`degraded`, in addition to when a component is `starting` or `failed`.
+						"""
+					type: bool: default: false
+				}
+			}
+		}
 	}
 
 	endpoints: {
@@ -82,6 +113,23 @@ api: {
 				}
 			}
 		}
+		"/health/components": {
+			GET: {
+				description: """
+					Component health endpoint, reporting the health of each
+					component: `starting`, `healthy`, `degraded` or `failed`.
+					Useful as a readiness probe.
+					"""
+				responses: {
+					"200": {
+						description: "All the components are healthy, or degraded unless `fail_on_degraded` is set."
+					}
+					"503": {
+						description: "A component is starting or failed, or degraded when `fail_on_degraded` is set."
+					}
+				}
+			}
+		}
 		"/playground": {
 			GET: {
 				description: """
@@ -167,6 +167,81 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"bench": {
+			description: """
+				Run a synthetic workload against the target config in-process and report
+				its throughput, latency and memory usage, then exit. The sources of the
+				config are replaced by a source generating the events, and its sinks by
+				sinks discarding them unless `--keep-sinks` is set. The latency percentiles
+				are computed from a uniform sample of up to 100,000 events. This command is
+				experimental and therefore subject to change.
+				"""
+
+			example: "vector bench --events 1000000 --fields 10 --input parse_logs /etc/vector/vector.toml"
+
+			flags: _default_flags & {
+				"keep-sinks": {
+					description: "Keep the sinks of the config instead of replacing them. Latency isn't measured when the sinks are kept."
+				}
+			}
+
+			options: {
+				"config-toml": {
+					description: env_vars.VECTOR_CONFIG_TOML.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_TOML"
+				}
+				"config-json": {
+					description: env_vars.VECTOR_CONFIG_JSON.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_JSON"
+				}
+				"config-yaml": {
+					description: env_vars.VECTOR_CONFIG_YAML.description
+					type:        "string"
+					env_var:     "VECTOR_CONFIG_YAML"
+				}
+				"input": {
+					description: "Components under test, receiving the synthetic events. By default the events are sent to the components reading from the sources of the config."
+					type:        "string"
+				}
+				"events": {
+					description: "Number of events to send."
+					type:        "integer"
+					default:     1000000
+				}
+				"batch-size": {
+					description: "Number of events sent at once."
+					type:        "integer"
+					default:     1000
+				}
+				"min-message-bytes": {
+					description: "Minimum size of the message of the events, in bytes."
+					type:        "integer"
+					default:     256
+				}
+				"max-message-bytes": {
+					description: "Maximum size of the message of the events, in bytes. Sizes are uniformly distributed between the minimum and the maximum."
+					type:        "integer"
+					default:     256
+				}
+				"fields": {
+					description: "Number of fields added to the events besides the message and the timestamp."
+					type:        "integer"
+					default:     0
+				}
+			}
+
+			args: {
+				paths: _paths_arg & {
+					description: """
+						Any number of Vector config files to benchmark. If none are specified
+						the default config path `/etc/vector/vector.toml` will be targeted
+						"""
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -179,6 +254,90 @@ cli: {
 
 			options: _core_options
 		}
+		"buffer": {
+			description: """
+				List, inspect and repair the disk buffers of sinks. The `ls` subcommand
+				lists the buffers in the data directory, `inspect <id>` reports the
+				records and any damage found in each data file of a buffer, and
+				`repair <id>` truncates the damaged data files to their last valid
+				record. The records after the damage are lost. Vector must be stopped
+				to repair a buffer.
+				"""
+
+			example: "vector buffer repair --data-dir /var/lib/vector/ my_sink"
+
+			options: {
+				"data-dir": {
+					description: "The data directory of Vector, as set with the global `data_dir` option"
+					type:        "string"
+					default:     "/var/lib/vector/"
+				}
+			}
+
+			args: {
+				id: {
+					description: "The ID of the sink the buffer belongs to, for `inspect` and `repair`"
+					type:        "string"
+				}
+			}
+		}
+		"convert-config": {
+			description: """
+				Convert a config file between the TOML, YAML and JSON formats. Comments
+				are kept when converting from TOML to YAML, otherwise a warning is
+				printed as they're left out. Environment variables are left as written.
+				Existing files are never overwritten.
+				"""
+
+			example: "vector convert-config --split --output-format yaml /etc/vector/vector.toml /etc/vector/config"
+
+			flags: _default_flags & {
+				"split": {
+					description: """
+						Split the config into a file per component, in `sources`,
+						`transforms`, `sinks`, `enrichment_tables` and `tests`
+						sub-directories of the output directory, loadable with
+						`--config-dir`. The remaining global options are written to a
+						`vector` file
+						"""
+				}
+			}
+
+			options: {
+				"input-format": {
+					description: "The format of the config file to convert, detected from its extension by default"
+					type:        "enum"
+					enum: {
+						toml: "TOML format"
+						yaml: "YAML format"
+						json: "JSON format"
+					}
+				}
+				"output-format": {
+					description: "The format to convert the config to, detected from the extension of the output file by default. Required with `--split`"
+					type:        "enum"
+					enum: {
+						toml: "TOML format"
+						yaml: "YAML format"
+						json: "JSON format"
+					}
+				}
+			}
+
+			args: {
+				input_path: {
+					description: "The config file to convert"
+					type:        "string"
+					required:    true
+				}
+				output_path: {
+					description: "The file to write the converted config to, or the directory to write the component files to with `--split`"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
+
 		"generate": {
 			description: "Generate a Vector configuration containing a list of components"
 
@@ -187,6 +346,14 @@ cli: {
 					_short:      "f"
 					description: "Whether to skip the generation of global fields"
 				}
+				"interactive": {
+					_short: "i"
+					description: """
+						Build the config with an interactive wizard instead of a pipeline expression. The wizard
+						prompts for components and their options, suggests codecs and acknowledgement settings,
+						validates each component, and emits the config as YAML
+						"""
+				}
 			}
 
 			options: {
@@ -199,7 +366,7 @@ cli: {
 
 			args: {
 				pipeline: {
-					description: "Pipeline expression, e.g. `stdin/remap,filter/console`"
+					description: "Pipeline expression, e.g. `stdin/remap,filter/console`. Required unless `--interactive` is set"
 					type:        "string"
 				}
 			}
@@ -321,6 +488,18 @@ cli: {
 					description: "Components (sources, transforms) to observe for their inputs (comma-separated; accepts glob patterns)"
 					type:        "list"
 				}
+				"filter": {
+					description: """
+						VRL boolean expression that events must match to be observed, for example
+						`.status >= 500`. Filtering is done by the Vector API server, before events
+						are sampled, so only matching events count towards `--limit`.
+						"""
+					type: "string"
+				}
+				"fields": {
+					description: "Fields of log and trace events to observe (comma-separated); other fields are dropped by the Vector API server"
+					type:        "list"
+				}
 			}
 
 			args: {
@@ -338,6 +517,64 @@ cli: {
 			}
 		}
 
+		"dry-run": {
+			description: """
+				Run a candidate VRL program against log events sampled from a
+				component of a running Vector instance, and display the fields the
+				program would add, remove, or change in each event, or whether it
+				would drop the event. The running instance is not modified, which
+				makes this useful for checking a change to a `remap` transform
+				against real traffic before deploying it. Events are sampled as
+				JSON, so timestamps are seen by the program as strings.
+				"""
+
+			flags: _default_flags & {
+				"full": {
+					description: "Print each whole event output by the program, rather than only the fields it changed"
+				}
+			}
+
+			options: {
+				"file": {
+					description: "Path to the candidate VRL program. Either `--file` or `--source` is required."
+					type:        "string"
+				}
+				"source": {
+					description: "Candidate VRL program. Either `--file` or `--source` is required."
+					type:        "string"
+				}
+				"url": {
+					_short:      "u"
+					description: "Vector GraphQL API server endpoint"
+					type:        "string"
+				}
+				"limit": {
+					_short:      "l"
+					description: "Number of events to sample before exiting"
+					type:        "integer"
+					default:     10
+				}
+				"interval": {
+					_short:      "i"
+					description: "Interval to sample events at, in milliseconds"
+					type:        "integer"
+					default:     500
+				}
+				"filter": {
+					description: "VRL boolean expression that events must match to be sampled, evaluated by the Vector API server"
+					type:        "string"
+				}
+			}
+
+			args: {
+				component_id: {
+					description: "The ID of the component (source, transform) whose output events are sampled"
+					type:        "string"
+					required:    true
+				}
+			}
+		}
+
 		"top": {
 			description: """
 				Display topology and metrics in the console, for a local or remote Vector
@@ -388,9 +625,21 @@ cli: {
 					_short:      "d"
 					description: "Fail validation on warnings"
 				}
+				"deep": {
+					description: """
+						Attempt real connections to the external systems of each
+						source and sink, running their health checks even when they
+						are disabled, and print a summary table of the results
+						"""
+				}
 			}
 
 			options: {
+				"deep-timeout-secs": {
+					description: "Timeout of each check of the deep mode, in seconds"
+					type:        "integer"
+					default:     10
+				}
 				"config-toml": {
 					description: """
 						Any number of Vector config files to validate.
@@ -455,6 +704,27 @@ cli: {
 						"""
 					type: "string"
 				}
+
+				"config": {
+					_short: "c"
+					description: """
+						Vector config files whose enrichment tables are loaded, so that
+						`get_enrichment_table_record` and `find_enrichment_table_records` can be
+						used in programs and in the REPL. Secret backends are not contacted; each
+						`SECRET[backend.key]` placeholder in the config is replaced by the
+						`--secret` with the key `backend.key`, or by an empty string.
+						"""
+					type: "list"
+				}
+
+				"secret": {
+					_short: "s"
+					description: """
+						A secret available to programs through `get_secret`, as `KEY=VALUE`. Can
+						be repeated.
+						"""
+					type: "string"
+				}
 			}
 
 			args: {
@@ -392,6 +392,10 @@ components: {
 			}
 		}
 
+		// `tag_rules` describes if the component rewrites the tags of
+		// metrics with the shared `tag_rules` option.
+		tag_rules?: bool
+
 		// `tls` describes if the component secures network communication
 		// via TLS.
 		tls: #FeaturesTLS & {_args: {mode: "connect"}}
@@ -629,7 +633,7 @@ components: {
 					}
 					crt_file: {
 						common:      false
-						description: "Absolute path to a certificate file used to identify this server, in DER or PEM format (X.509) or PKCS#12, or an in-line certificate in PEM format. If this is set, and is not a PKCS#12 archive, `key_file` must also be set. This is required if `enabled` is set to `true`."
+						description: "Absolute path to a certificate file used to identify this server, in DER or PEM format (X.509) or PKCS#12, or an in-line certificate in PEM format. If this is set, and is not a PKCS#12 archive, `key_file` must also be set. This is required if `enabled` is set to `true`. Changes to the certificate and key files are picked up automatically and used for new connections."
 						required:    false
 						type: string: {
 							default: null
@@ -654,6 +658,36 @@ components: {
 							examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
 					}
+					spiffe: {
+						common:      false
+						description: """
+							Obtain the identity certificate and trusted authorities from the
+							[SPIFFE Workload API](\(urls.spiffe_workload_api)) instead of from files.
+							Rotated SVIDs are picked up automatically and used for new connections.
+							This can't be combined with `ca_file`, `crt_file`, or `key_file`.
+							"""
+						required: false
+						type: object: options: {
+							socket_path: {
+								common:      false
+								description: "Path to the Workload API unix socket. Defaults to the value of the `SPIFFE_ENDPOINT_SOCKET` environment variable."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["/run/spire/sockets/agent.sock"]
+								}
+							}
+							spiffe_id: {
+								common:      false
+								description: "The SPIFFE ID of the SVID to use. Defaults to the first SVID returned, which is the default identity of the workload."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["spiffe://example.org/vector"]
+								}
+							}
+						}
+					}
 
 					if Args.can_verify_certificate {
 						verify_certificate: {
@@ -696,7 +730,7 @@ components: {
 					}
 					crt_file: {
 						common:      true
-						description: "Absolute path to a certificate file used to identify this connection, in DER or PEM format (X.509) or PKCS#12, or an inline certificate in PEM format. If this is set and is not a PKCS#12 archive, `key_file` must also be set."
+						description: "Absolute path to a certificate file used to identify this connection, in DER or PEM format (X.509) or PKCS#12, or an inline certificate in PEM format. If this is set and is not a PKCS#12 archive, `key_file` must also be set. Changes to the certificate and key files are picked up automatically and used for new connections."
 						required:    false
 						type: string: {
 							default: null
@@ -721,6 +755,36 @@ components: {
 							examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
 						}
 					}
+					spiffe: {
+						common:      false
+						description: """
+							Obtain the identity certificate and trusted authorities from the
+							[SPIFFE Workload API](\(urls.spiffe_workload_api)) instead of from files.
+							Rotated SVIDs are picked up automatically and used for new connections.
+							This can't be combined with `ca_file`, `crt_file`, or `key_file`.
+							"""
+						required: false
+						type: object: options: {
+							socket_path: {
+								common:      false
+								description: "Path to the Workload API unix socket. Defaults to the value of the `SPIFFE_ENDPOINT_SOCKET` environment variable."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["/run/spire/sockets/agent.sock"]
+								}
+							}
+							spiffe_id: {
+								common:      false
+								description: "The SPIFFE ID of the SVID to use. Defaults to the first SVID returned, which is the default identity of the workload."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["spiffe://example.org/vector"]
+								}
+							}
+						}
+					}
 
 					if Args.can_verify_certificate {
 						verify_certificate: {
@@ -795,6 +859,61 @@ components: {
 				}
 			}
 
+			_connection_pool: {
+				common: false
+				description: """
+					Configures the pool of HTTP connections the sink sends requests over. Bounding how long
+					connections are kept and reused spreads long-lived connections across the backends
+					of a load balancer, and avoids sending requests over connections that a NAT gateway
+					or firewall silently dropped. Sinks built on the AWS SDK manage their own connections
+					and don't use these settings.
+					"""
+				required: false
+				type: object: options: {
+					max_idle_per_host: {
+						common:      false
+						description: "The maximum number of idle connections kept open per host. Set to `0` to close connections as soon as their requests complete."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    null
+						}
+					}
+					idle_timeout_secs: {
+						common:      false
+						description: "How long idle connections are kept open before being closed."
+						required:    false
+						type: uint: {
+							default: 90
+							unit:    "seconds"
+						}
+					}
+					tcp_keepalive_secs: {
+						common:      false
+						description: "The interval at which TCP keepalive probes are sent on idle connections, keeping them tracked by NAT gateways and firewalls."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					max_connection_lifetime_secs: {
+						common: false
+						description: """
+							The maximum time connections are reused for. Connections are rotated at this
+							interval: once it elapses, new requests are sent over new connections, and the
+							previous connections are closed once their in-flight requests complete.
+							"""
+						required: false
+						type: uint: {
+							default: null
+							examples: [300]
+							unit: "seconds"
+						}
+					}
+				}
+			}
+
 			_http_auth: {
 				_args: {
 					password_example: string
@@ -907,6 +1026,12 @@ components: {
 						A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms))
 						IDs. Wildcards (`*`) are supported.
 
+						An input can also be a table with the `component` ID and a `condition` that events
+						must match, e.g. `{ component = "app_logs", condition = '.level == "error"' }`. The
+						condition accepts the same values as the [`filter` transform](\(urls.vector_filter_transform))
+						condition, and is expanded into a `filter` transform named after the component and
+						the position of the input, e.g. `my-sink_input_0`.
+
 						See [configuration](\(urls.vector_configuration)) for more info.
 						"""
 					required:    true
@@ -918,6 +1043,32 @@ components: {
 						]
 					}
 				}
+
+				input_priorities: {
+					common: false
+					description: """
+						The weights of the inputs, by input ID or by component ID for all of its outputs.
+						When the component applies back pressure, each input is consumed in proportion to
+						its weight, so that high priority streams, e.g. audit logs, are consumed ahead of
+						low priority ones, e.g. debug logs. Inputs without a weight have a weight of `1`.
+						"""
+					required: false
+					type: object: {
+						examples: [{"audit_logs": 10, "debug_logs": 1}]
+						options: {
+							"*": {
+								common:      false
+								description: "The weight of the input, at least `1`."
+								required:    false
+								type: uint: {
+									default: null
+									examples: [10]
+									unit: null
+								}
+							}
+						}
+					}
+				}
 			}
 
 			"type": {
@@ -129,6 +129,88 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		degradation: {
+			common: false
+			description: """
+				Configures the sink behavior once its downstream has been unavailable, that is once the
+				sink hasn't accepted events, for longer than `threshold_secs`. Transitions between
+				degraded and recovered are reported by the `sink_degraded` and
+				`sink_degradation_transitions_total` internal metrics.
+				"""
+			required: false
+			type: object: {
+				examples: []
+				options: {
+					policy: {
+						common:      true
+						description: "The behavior while the sink is degraded."
+						required:    false
+						type: string: {
+							default: "block"
+							enum: {
+								block: "Keeps applying back pressure, as when no degradation is configured."
+								shed:  "Drops the oldest events held for the sink as new events are received. This data is lost, and reported to the sources as failed."
+								spill: "Writes the oldest events held for the sink to `spill_path` as new events are received, up to `spill_max_bytes`, past which they're shed. The spilled events are replayed to the sink once it recovers, and only acknowledged once it delivers them."
+							}
+						}
+					}
+					spill_max_bytes: {
+						common:        false
+						description:   "The maximum size of the events spilled and not replayed yet. Events are shed past it."
+						required:      false
+						relevant_when: "policy = \"spill\""
+						type: uint: {
+							default: 1073741824
+							unit:    "bytes"
+						}
+					}
+					spill_path: {
+						common:        true
+						description:   "The local file events are appended to by the `spill` policy. Events spilled by a previous run and never replayed are replayed when the sink starts, and count against `spill_max_bytes` until then."
+						required:      true
+						relevant_when: "policy = \"spill\""
+						type: string: {
+							examples: ["/var/lib/vector/spill.json"]
+						}
+					}
+					threshold_secs: {
+						common:      true
+						description: "How long the sink must have not accepted events before being degraded."
+						required:    false
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+
+		mirror: {
+			common: false
+			description: """
+				Makes the sink a mirror, shadowing the sinks sharing its `inputs` with a sample of their
+				events, for example to test a new backend with production traffic. A mirror sink never
+				applies back pressure to its inputs, dropping events it can't keep up with, and its
+				outcome doesn't affect the acknowledgement of the events.
+				"""
+			required: false
+			type: object: {
+				examples: []
+				options: {
+					percent: {
+						common:      true
+						description: "The percentage of the events sent to the sink."
+						required:    false
+						type: float: {
+							default: 100.0
+							examples: [10.0]
+						}
+					}
+				}
+			}
+		}
+
 		if features.send != _|_ {
 			if features.send.compression.enabled {
 				compression: {
@@ -159,6 +241,9 @@ components: sinks: [Name=string]: {
 								if algo == "zstd" {
 									zstd: "[zstd](\(urls.zstd)) compression."
 								}
+								if algo == "brotli" {
+									brotli: "[Brotli](\(urls.brotli)) compression."
+								}
 							}
 						}
 					}
@@ -335,7 +420,8 @@ components: sinks: [Name=string]: {
 		if features.send != _|_ {
 			if features.send.proxy != _|_ {
 				if features.send.proxy.enabled {
-					proxy: configuration._proxy
+					connection_pool: configuration._connection_pool
+					proxy:           configuration._proxy
 				}
 			}
 
@@ -394,6 +480,18 @@ components: sinks: [Name=string]: {
 									unit:    "requests"
 								}
 							}
+							ordering: {
+								common:      false
+								description: "Whether requests may complete out of order. With `strict`, a single request is in flight at any given time and a failed request is retried before the next one is sent, preserving the order of the events within a partition, such as a file or a stream. This overrides the `concurrency` option."
+								required:    false
+								type: string: {
+									default: "unordered"
+									enum: {
+										unordered: "Requests are sent concurrently and may complete in any order."
+										strict:    "Requests are sent one at a time, in the order their events were received."
+									}
+								}
+							}
 							rate_limit_duration_secs: {
 								common:      true
 								description: "The time window, in seconds, used for the `rate_limit_num` option."
@@ -439,6 +537,140 @@ components: sinks: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							circuit_breaker: {
+								common: false
+								description: """
+									Opens the circuit to the downstream after consecutive failed requests: requests are
+									then held back and retried with backoff, without reaching the downstream, until the
+									cooldown elapsed. A single request then probes the downstream, closing the circuit if
+									it succeeds. Combine with the `degradation` option of the sink to shed or spill events
+									while the circuit is open.
+									"""
+								required: false
+								type: object: {
+									examples: []
+									options: {
+										failure_threshold: {
+											common:      false
+											description: "The number of consecutive failed requests opening the circuit."
+											required:    false
+											type: uint: {
+												default: 5
+												unit:    null
+											}
+										}
+										cooldown_secs: {
+											common:      false
+											description: "How long the circuit stays open before a request probes the downstream."
+											required:    false
+											type: uint: {
+												default: 30
+												unit:    "seconds"
+											}
+										}
+									}
+								}
+							}
+							retry_policy: {
+								common:      false
+								description: "Overrides which failed requests are retried, and limits retries. Status codes apply to HTTP-based sinks."
+								required:    false
+								type: object: {
+									examples: []
+									options: {
+										status_codes: {
+											common:      false
+											description: "The HTTP status codes of the responses to retry, in addition to those the sink retries."
+											required:    false
+											type: array: {
+												default: []
+												items: type: uint: {
+													examples: [409, 429]
+													unit: null
+												}
+											}
+										}
+										excluded_status_codes: {
+											common:      false
+											description: "The HTTP status codes of the responses to never retry. Takes precedence over `status_codes`."
+											required:    false
+											type: array: {
+												default: []
+												items: type: uint: {
+													examples: [500]
+													unit: null
+												}
+											}
+										}
+										timeouts: {
+											common:      false
+											description: "Whether requests that timed out are retried."
+											required:    false
+											type: bool: default: true
+										}
+										errors: {
+											common:      false
+											description: "Whether requests that failed with an error the sink considers retriable, such as a connection error, are retried."
+											required:    false
+											type: bool: default: true
+										}
+										max_elapsed_secs: {
+											common:      false
+											description: "The maximum time since the first failed attempt of a request after which it isn't retried anymore."
+											required:    false
+											type: uint: {
+												default: null
+												unit:    "seconds"
+											}
+										}
+										jitter: {
+											common:      false
+											description: "The randomization applied to the delay before retrying a request."
+											required:    false
+											type: string: {
+												default: "none"
+												enum: {
+													none: "The delay is the backoff."
+													full: "The delay is picked at random between zero and the backoff, spreading the retries of concurrent requests."
+												}
+											}
+										}
+										budget: {
+											common:      false
+											description: "Limits retries to a ratio of the successful requests, so that a failing downstream isn't overwhelmed by retries."
+											required:    false
+											type: object: {
+												examples: []
+												options: {
+													ratio: {
+														description: "The ratio of retries to successful requests allowed, between `0` and `1000`, for example `0.2` for one retry every five successful requests."
+														required:    true
+														type: float: examples: [0.2]
+													}
+													min_per_sec: {
+														common:      false
+														description: "The retries allowed per second regardless of the ratio. Must be lower than `2147483647`."
+														required:    false
+														type: uint: {
+															default: 10
+															unit:    null
+														}
+													}
+													ttl_secs: {
+														common:      false
+														description: "The period over which successful requests are accounted for, between `1` and `60` seconds."
+														required:    false
+														type: uint: {
+															default: 10
+															unit:    "seconds"
+														}
+													}
+												}
+											}
+										}
+									}
+								}
+							}
 							timeout_secs: {
 								common:      true
 								description: "The maximum time a request can take before being aborted. It is highly recommended that you do not lower this value below the service's internal timeout, as this could create orphaned requests, pile on retries, and result in duplicate data downstream."
@@ -510,6 +742,63 @@ components: sinks: [Name=string]: {
 				}
 			}
 
+			if features.send.tag_rules != _|_ {
+				if features.send.tag_rules {
+					tag_rules: {
+						common: false
+						description: """
+							Rules rewriting the tags of metrics before they are sent, applied in order. Each
+							rule applies to the tags whose key matches its pattern, so that the tag constraints
+							of the destination, like their length or charset, can be enforced without a remap
+							transform per sink.
+							"""
+						required: false
+						type: array: {
+							default: []
+							items: type: object: {
+								examples: [
+									{key: "hostname", action: "rename", to: "host"},
+									{key: "*", action: "truncate", max_length: 200},
+								]
+								options: {
+									action: {
+										description: "The rewrite of the matching tags."
+										required:    true
+										type: string: enum: {
+											rename:    "Renames the tags to the key set by `to`."
+											drop:      "Drops the tags."
+											hash:      "Replaces the values of the tags with their hexadecimal FNV-1a hash."
+											lowercase: "Converts the values of the tags to lowercase."
+											truncate:  "Truncates the values of the tags to `max_length` characters."
+										}
+									}
+									key: {
+										description: "The glob pattern of the keys of the tags the rule applies to."
+										required:    true
+										type: string: examples: ["hostname", "user_*", "*"]
+									}
+									max_length: {
+										description:   "The maximum number of characters of the tag values."
+										relevant_when: "action = `truncate`"
+										required:      true
+										type: uint: {
+											examples: [200]
+											unit: null
+										}
+									}
+									to: {
+										description:   "The key the tags are renamed to."
+										relevant_when: "action = `rename`"
+										required:      true
+										type: string: examples: ["host"]
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+
 			if features.send.tls.enabled {
 				tls: configuration._tls_connect & {_args: {
 					can_verify_certificate: features.send.tls.can_verify_certificate
@@ -567,6 +856,21 @@ components: sinks: [Name=string]: {
 						"""
 				}
 			}
+
+			buffer_migration: {
+				title: "Changing buffers on reload"
+				body: """
+					When the [`buffer.*`](#buffer) options of this component change on a configuration
+					reload, the events still held by the previous buffer are migrated to the new buffer in
+					the background, while the new buffer already receives new events. Migrated events keep
+					their acknowledgements, and the progress of the migration is reported by the
+					`buffer_migrated_events_total` metric.
+
+					A disk buffer keeps its contents on disk when only its size or `when_full` behavior
+					changes. In that case the previous buffer is drained by the previous sink before the new
+					one is opened, since both can't open the same buffer at once.
+					"""
+			}
 		}
 
 		if features.healthcheck.enabled {
@@ -713,5 +1017,8 @@ components: sinks: [Name=string]: {
 		buffer_sent_events_total:             components.sources.internal_metrics.output.metrics.buffer_sent_events_total
 		buffer_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.buffer_sent_event_bytes_total
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
+		buffer_migrations_total:              components.sources.internal_metrics.output.metrics.buffer_migrations_total
+		buffer_migrated_events_total:         components.sources.internal_metrics.output.metrics.buffer_migrated_events_total
+		buffer_migrated_bytes_total:          components.sources.internal_metrics.output.metrics.buffer_migrated_bytes_total
 	}
 }
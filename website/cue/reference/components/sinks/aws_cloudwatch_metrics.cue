@@ -31,6 +31,7 @@ components: sinks: aws_cloudwatch_metrics: components._aws & {
 			encoding: enabled: false
 			proxy: enabled:    true
 			request: enabled:  false
+			tag_rules: true
 			tls: {
 				enabled:                true
 				can_verify_certificate: true
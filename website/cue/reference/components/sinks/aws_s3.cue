@@ -194,6 +194,54 @@ components: sinks: aws_s3: components._aws & {
 				syntax: "template"
 			}
 		}
+		manifest: {
+			common:      false
+			description: """
+				Write a manifest listing the objects written during each interval, laid out like the manifests of
+				[S3 inventory reports](\(urls.aws_s3_inventory)). Objects are listed with their key, size, MD5 checksum
+				and event count.
+				"""
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					interval_secs: {
+						common:      true
+						description: "How often to write a manifest, in seconds. Must be greater than zero. A last manifest is written when Vector stops."
+						required:    false
+						type: uint: {
+							default: 3600
+							unit:    "seconds"
+						}
+					}
+					key_prefix: {
+						common:      true
+						description: "The prefix of the manifest keys. Each manifest is written to `<key_prefix><YYYY-MM-DDTHH-MMZ>/manifest.json`."
+						required:    false
+						type: string: {
+							default: "manifests/"
+							examples: ["manifests/", "logs/_manifests/"]
+						}
+					}
+				}
+			}
+		}
+		max_object_age_secs: {
+			category:    "File Naming"
+			common:      false
+			description: """
+				Finalize the objects of a time partition once it ended this long ago, even if the batch timeout
+				hasn't elapsed yet. Requires `time_partitioning` to be set. This allows raising the batch timeout to write fewer,
+				larger objects while still completing each partition shortly after it ends. Events arriving after
+				their partition was finalized are batched as usual.
+				"""
+			required:    false
+			type: uint: {
+				default: null
+				examples: [300]
+				unit: "seconds"
+			}
+		}
 		server_side_encryption: {
 			category:    "Encryption"
 			common:      false
@@ -235,6 +283,38 @@ components: sinks: aws_s3: components._aws & {
 				}
 			}
 		}
+		time_partitioning: {
+			category:    "File Naming"
+			common:      false
+			description: """
+				Partition objects by the timestamp of their events, appending the time partition to the `key_prefix`.
+				When set, `key_prefix` defaults to no prefix.
+				"""
+			required:    false
+			type: object: {
+				examples: [{"granularity": "hour", "hive_style": true}]
+				options: {
+					granularity: {
+						description: "How finely to partition objects."
+						required:    true
+						type: string: {
+							enum: {
+								year:  "`%Y/`, or `year=%Y/` in the Hive style."
+								month: "`%Y/%m/`, or `year=%Y/month=%m/` in the Hive style."
+								day:   "`%Y/%m/%d/`, or `year=%Y/month=%m/day=%d/` in the Hive style."
+								hour:  "`%Y/%m/%d/%H/`, or `year=%Y/month=%m/day=%d/hour=%H/` in the Hive style."
+							}
+						}
+					}
+					hive_style: {
+						common:      true
+						description: "Name the partitions in the Hive style, such as `year=2022/month=08/`, so that [Athena](\(urls.aws_athena)) and Glue crawlers pick up the partition columns from them."
+						required:    false
+						type: bool: default: false
+					}
+				}
+			}
+		}
 		tags: {
 			common:      false
 			description: "The tag-set for the object."
@@ -321,6 +401,24 @@ components: sinks: aws_s3: components._aws & {
 				"""
 		}
 
+		time_partitioning: {
+			title: "Time partitioning"
+			body:  """
+				The [`time_partitioning`](#time_partitioning) option partitions objects by the
+				timestamp of their events, following the conventions of Athena and Glue. For
+				example, with an `hour` granularity in the Hive style, objects are named:
+
+				```text
+				<key_prefix>year=2022/month=08/day=01/hour=13/<timestamp>-<uuidv4>.log.gz
+				```
+
+				Objects of a partition are written whenever a batch fills up or times out. Set
+				[`max_object_age_secs`](#max_object_age_secs) to also finalize them shortly
+				after the partition ends, and [`manifest`](#manifest) to list the objects
+				written during each interval.
+				"""
+		}
+
 		object_tags_and_metadata: {
 			title: "Object Tags & metadata"
 			body:  """
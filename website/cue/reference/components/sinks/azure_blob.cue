@@ -106,20 +106,51 @@ components: sinks: azure_blob: {
 		blob_append_uuid: {
 			category:    "File Naming"
 			common:      false
-			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases."
+			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases. Defaults to `false` for append blobs, which can't be written with a UUID."
 			required:    false
 			type: bool: default: true
 		}
 		blob_time_format: {
 			category:    "File Naming"
 			common:      false
-			description: "The format of the resulting object file name. [`strftime` specifiers](\(urls.strptime_specifiers)) are supported."
+			description: "The format of the resulting object file name. [`strftime` specifiers](\(urls.strptime_specifiers)) are supported. Defaults to `%H` for append blobs, so that each blob collects an hour of events."
 			required:    false
 			type: string: {
 				default: "%s"
 				syntax:  "strftime"
 			}
 		}
+		blob_type: {
+			common:      false
+			description: "The [type of blob](\(urls.azure_blob_types)) to write."
+			required:    false
+			type: string: {
+				default: "block"
+				enum: {
+					block:  "Each batch is written to a new block blob."
+					append: "Each batch is appended to its blob, which is created if it doesn't exist yet. Suited to small, frequent writes."
+				}
+			}
+		}
+		block_size_bytes: {
+			common:      false
+			description: "The size of the blocks large batches are split into when writing block blobs. Batches larger than this are uploaded as several blocks, in parallel, and committed once all are uploaded. By default, each batch is uploaded in a single request."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [8388608]
+				unit: "bytes"
+			}
+		}
+		block_upload_concurrency: {
+			common:      false
+			description: "How many blocks of a batch are uploaded at once when writing block blobs."
+			required:    false
+			type: uint: {
+				default: 4
+				unit:    null
+			}
+		}
 	}
 
 	input: {
@@ -166,6 +197,21 @@ components: sinks: azure_blob: {
 				[`blob_time_format`](#blob_time_format), and [`blob_append_uuid`](#blob_append_uuid) options.
 				"""
 		}
+		append_blobs: {
+			title: "Append blobs"
+			body:  """
+				With [`blob_type`](#blob_type) set to `append`, batches are appended to their blob
+				instead of each being written to a new one. Without other naming options, each blob
+				collects an hour of events under `blob/%F/%H.log`.
+
+				Appends to a blob are written one at a time, in blocks of up to 4 MiB, while appends to
+				different blobs are written concurrently. Each block is appended on the condition that
+				the blob ends where the previous block did, so a batch fails rather than being
+				interleaved with blocks appended by another writer. Compressed appends are only
+				supported with `gzip`, since gzip members can be concatenated into a valid file, and
+				`blob_append_uuid` can't be enabled as it would give each batch its own blob.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -42,7 +42,7 @@ components: sinks: blackhole: {
 		}
 		rate: {
 			common:      false
-			description: "Rates the amount of events that the sink can consume per second."
+			description: "Rates the amount of events that the sink can consume per second. Must be greater than zero."
 			required:    false
 			type: uint: {
 				default: null
@@ -50,6 +50,100 @@ components: sinks: blackhole: {
 				unit: null
 			}
 		}
+		rate_bytes: {
+			common:      false
+			description: "Rates the amount of bytes, estimated from the in-memory size of the events, that the sink can consume per second. Must be greater than zero."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [1048576]
+				unit: "bytes"
+			}
+		}
+		latency: {
+			common:      false
+			description: "The latency added to every batch of events, before it's acknowledged and the next batch is accepted, to test how upstream components handle backpressure."
+			required:    false
+			type: object: options: {
+				distribution: {
+					description: "The distribution of the latency."
+					required:    true
+					type: string: enum: {
+						fixed:       "The same latency, `ms`, for every batch."
+						uniform:     "A latency uniformly distributed between `min_ms` and `max_ms`."
+						exponential: "An exponentially distributed latency averaging `mean_ms`, with occasional long stalls."
+					}
+				}
+				ms: {
+					description:   "The latency of every batch."
+					relevant_when: "distribution = \"fixed\""
+					required:      false
+					type: uint: {
+						default: null
+						examples: [100]
+						unit: "milliseconds"
+					}
+				}
+				min_ms: {
+					description:   "The minimum latency."
+					relevant_when: "distribution = \"uniform\""
+					required:      false
+					type: uint: {
+						default: null
+						examples: [10]
+						unit: "milliseconds"
+					}
+				}
+				max_ms: {
+					description:   "The maximum latency."
+					relevant_when: "distribution = \"uniform\""
+					required:      false
+					type: uint: {
+						default: null
+						examples: [500]
+						unit: "milliseconds"
+					}
+				}
+				mean_ms: {
+					description:   "The average latency."
+					relevant_when: "distribution = \"exponential\""
+					required:      false
+					type: uint: {
+						default: null
+						examples: [100]
+						unit: "milliseconds"
+					}
+				}
+			}
+		}
+		error_rate: {
+			common:      false
+			description: "The fraction of batches failing with an error, whose events are acknowledged as errored to their sources."
+			required:    false
+			type: float: {
+				default: 0.0
+				examples: [0.01]
+			}
+		}
+		rejection_rate: {
+			common:      false
+			description: "The fraction of batches rejected, whose events are acknowledged as rejected to their sources."
+			required:    false
+			type: float: {
+				default: 0.0
+				examples: [0.01]
+			}
+		}
+		seed: {
+			common:      false
+			description: "The seed of the random latencies and failures, so that test runs are reproducible. Without one, each run differs."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [42]
+				unit: null
+			}
+		}
 	}
 
 	input: {
@@ -66,6 +160,7 @@ components: sinks: blackhole: {
 	}
 
 	telemetry: metrics: {
+		component_errors_total: components.sources.internal_metrics.output.metrics.component_errors_total
 		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
 	}
@@ -62,6 +62,48 @@ components: sinks: datadog_logs: {
 		endpoint:        sinks._datadog.configuration.endpoint
 		region:          sinks._datadog.configuration.region
 		site:            sinks._datadog.configuration.site
+		hostname: {
+			common:      false
+			description: "The value to set the `host` attribute to. Takes precedence over any existing `host` field."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ kubernetes.pod_name }}"]
+				syntax: "template"
+			}
+		}
+		service: {
+			common:      false
+			description: "The value to set the `service` attribute to. Takes precedence over any existing `service` field."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ app }}", "checkout"]
+				syntax: "template"
+			}
+		}
+		source: {
+			common:      false
+			description: "The value to set the `ddsource` attribute to. Takes precedence over any existing `ddsource` field."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["nginx", "{{ source_type }}"]
+				syntax: "template"
+			}
+		}
+		tags: {
+			common:      false
+			description: "Tags to add to the `ddtags` attribute. Tags are appended to any tags already present on the event, and tags that render to an empty string are skipped."
+			required:    false
+			type: array: {
+				default: null
+				items: type: string: {
+					examples: ["env:{{ env }}", "team:core"]
+					syntax: "template"
+				}
+			}
+		}
 	}
 
 	input: {
@@ -76,6 +118,10 @@ components: sinks: datadog_logs: {
 			body: """
 				Datadog's logs API has special handling for the following fields: `ddsource`, `ddtags`, `hostname`, `message`, and `service`.
 				If your event contains any of these fields they will be used as described by the [API reference](https://docs.datadoghq.com/api/latest/logs/#send-logs).
+
+				Rather than shaping each event with a `remap` transform, the `hostname`, `service`, `source`, and `tags`
+				options can be used to template these attributes from the fields of each event. Templates that fail to
+				render leave the attribute as it was on the event.
 				"""
 		}
 	}
@@ -27,6 +27,7 @@ components: sinks: datadog_metrics: {
 				timeout_secs:               60
 				headers:                    false
 			}
+			tag_rules: true
 			tls: {
 				enabled:                true
 				can_verify_certificate: true
@@ -54,6 +55,21 @@ components: sinks: datadog_metrics: {
 	support: sinks._datadog.support
 
 	configuration: {
+		counter_submission: {
+			common: false
+			description: """
+				How counters are submitted to Datadog. The interval of each point is the interval the metric was
+				collected over, if the source provided it, and otherwise the time since the previous request.
+				"""
+			required: false
+			type: string: {
+				default: "count"
+				enum: {
+					count: "Counters are submitted as `count` metrics, holding the increase over the interval."
+					rate:  "Counters are submitted as `rate` metrics, holding the per-second increase over the interval. Counters whose interval isn't known are still submitted as `count` metrics."
+				}
+			}
+		}
 		default_api_key: sinks._datadog.configuration.default_api_key
 		endpoint:        sinks._datadog.configuration.endpoint
 		region:          sinks._datadog.configuration.region
@@ -86,6 +102,17 @@ components: sinks: datadog_metrics: {
 		traces: false
 	}
 
+	how_it_works: {
+		sketches: {
+			title: "Sketches"
+			body: """
+				Distributions and aggregated histograms are converted to sketches before being sent to Datadog.
+				Sketches received from the source, such as those forwarded by the Datadog Agent through the
+				`datadog_agent` source, are sent as-is, without being re-bucketed, so their accuracy is preserved.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
 		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
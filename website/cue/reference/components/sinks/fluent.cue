@@ -0,0 +1,156 @@
+package metadata
+
+components: sinks: fluent: {
+	title: "Fluent"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1_048_576
+				max_events:   null
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			keepalive: enabled: true
+			request: {
+				enabled:     true
+				concurrency: 1
+				headers:     false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.fluent
+
+				interface: {
+					socket: {
+						api: {
+							title: "Fluent"
+							url:   urls.fluent
+						}
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the Fluentd or Fluent Bit server to forward events to. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["127.0.0.1:24224", "fluentd.example.com:24224"]
+			}
+		}
+		tag: {
+			description: "The tag of the forwarded events, which the server routes them by."
+			required:    true
+			type: string: {
+				examples: ["vector", "app.{{ service }}"]
+				syntax: "template"
+			}
+		}
+		compression: {
+			common:      false
+			description: "How the entries of each message are compressed."
+			required:    false
+			type: string: {
+				default: "none"
+				enum: {
+					none: "The entries are sent as is."
+					gzip: "The entries are compressed with gzip, as a `CompressedPackedForward` message."
+				}
+			}
+		}
+		require_ack: {
+			common:      false
+			description: """
+				Whether to wait for the server to acknowledge each message before considering it sent. Messages that
+				aren't acknowledged within `ack_timeout_secs` are retried over a new connection. Disable this for
+				servers that don't support acknowledgements.
+				"""
+			required: false
+			type: bool: default: true
+		}
+		ack_timeout_secs: {
+			common:        false
+			description:   "How long to wait for the server to acknowledge a message."
+			relevant_when: "require_ack = true"
+			required:      false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		forward_protocol: {
+			title: "Forward protocol"
+			body: """
+				Each batch is sent as one `PackedForward` message per tag, with the events' timestamps as the entries'
+				`EventTime`, and the rest of their fields as the records. Each message carries a random `chunk` option,
+				which the server echoes back to acknowledge it, so that Vector can feed existing Fluentd aggregators
+				without losing data. This is equivalent to Fluentd's `require_ack_response` option.
+				"""
+		}
+
+		fluentd_configuration: {
+			title: "Fluentd configuration"
+			body: """
+				To receive events from Vector, configure a `forward` input in Fluentd:
+
+				```text
+					<source>
+					  @type forward
+					  port 24224
+					  bind 0.0.0.0
+					</source>
+				```
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+	}
+}
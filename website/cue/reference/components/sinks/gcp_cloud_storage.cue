@@ -108,6 +108,16 @@ components: sinks: gcp_cloud_storage: {
 				examples: ["/path/to/credentials.json"]
 			}
 		}
+		encryption_key_name: {
+			category:    "Storage"
+			common:      false
+			description: "The resource name of the Cloud KMS key used to encrypt the created objects, instead of the default encryption key of the bucket. See the [customer-managed encryption keys](\(urls.gcs_cmek)) documentation for more details. The service account of the bucket's project must be allowed to use the key."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["projects/my-project/locations/global/keyRings/my-ring/cryptoKeys/my-key"]
+			}
+		}
 		filename_append_uuid: {
 			category:    "File Naming"
 			common:      false
@@ -146,11 +156,20 @@ components: sinks: gcp_cloud_storage: {
 		}
 		metadata: {
 			common:      false
-			description: "The set of metadata `key:value` pairs for the created objects. See the [GCS custom metadata](\(urls.gcs_custom_metadata)) documentation for more details."
+			description: "The set of metadata `key:value` pairs for the created objects, sent as headers. Custom metadata keys must start with `x-goog-meta-`. Values are templates, so that each object is tagged with the values of its events. See the [GCS custom metadata](\(urls.gcs_custom_metadata)) documentation for more details."
 			required:    false
-			type: string: {
-				default: null
-				examples: []
+			type: object: {
+				examples: [{"x-goog-meta-team": "{{ team }}", "x-goog-meta-source": "vector"}]
+				options: {
+					"*": {
+						description: "A metadata value."
+						required:    true
+						type: string: {
+							examples: ["{{ team }}"]
+							syntax: "template"
+						}
+					}
+				}
 			}
 		}
 		storage_class: {
@@ -243,6 +262,11 @@ components: sinks: gcp_cloud_storage: {
 					Vector supports adding [custom metadata](\(urls.gcs_custom_metadata)) to
 					created objects. These metadata items are a way of associating extra
 					data items with the object that are not part of the uploaded data.
+
+					Metadata values can be templated. Events are batched by the rendered values, so
+					that all the events of an object share its metadata, and events missing the
+					fields of a template are dropped. Control and non-ASCII characters of rendered
+					values are percent-encoded.
 					"""
 		}
 	}
@@ -22,7 +22,12 @@ components: sinks: honeycomb: {
 				max_bytes:    100_000
 				timeout_secs: 1.0
 			}
-			compression: enabled: false
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
@@ -66,10 +71,24 @@ components: sinks: honeycomb: {
 			}
 		}
 		dataset: {
-			description: "The dataset that Vector will send logs to."
+			description: "The dataset that Vector will send logs to. Events for which the template can't be rendered are dropped."
 			required:    true
 			type: string: {
-				examples: ["my-honeycomb-dataset"]
+				examples: ["my-honeycomb-dataset", "{{ service }}"]
+				syntax: "template"
+			}
+		}
+		sample_rate_field: {
+			common:      false
+			description: """
+				The field containing the rate at which the event was sampled. The field is removed from the event and sent
+				as the event's sample rate, so that Honeycomb weights sampled events correctly. Values that aren't a
+				positive integer are ignored.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["sample_rate"]
 			}
 		}
 	}
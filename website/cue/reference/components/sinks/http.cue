@@ -25,7 +25,7 @@ components: sinks: http: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
+				algorithms: ["none", "gzip", "zstd", "brotli"]
 				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
 			}
 			encoding: {
@@ -107,14 +107,36 @@ components: sinks: http: {
 		traces:  false
 	}
 
+	how_it_works: {
+		request_too_large: {
+			title: "Requests rejected as too large"
+			body: """
+				When the server responds to a request with `413 Payload Too Large`, the batch of the
+				request is split in two, and each half is sent as its own request. Halves that are
+				still rejected are split again, until a single event is left, which is then rejected.
+				"""
+		}
+		compression_fallback: {
+			title: "Compression fallback"
+			body: """
+				When the server responds to a compressed request with `415 Unsupported Media Type`, the
+				sink falls back to a more widely supported compression, and the request is retried with
+				it: `zstd` and `brotli` fall back to `gzip`, and `gzip` to no compression at all. Later
+				requests are sent with the compression that was fallen back to.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
-		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
-		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
-		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
-		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
-		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
-		http_bad_requests_total:          components.sources.internal_metrics.output.metrics.http_bad_requests_total
-		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:           components.sources.internal_metrics.output.metrics.processed_events_total
+		component_sent_bytes_total:              components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:             components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total:        components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                        components.sources.internal_metrics.output.metrics.events_out_total
+		events_discarded_total:                  components.sources.internal_metrics.output.metrics.events_discarded_total
+		http_bad_requests_total:                 components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		http_client_compression_fallbacks_total: components.sources.internal_metrics.output.metrics.http_client_compression_fallbacks_total
+		http_client_requests_too_large_total:    components.sources.internal_metrics.output.metrics.http_client_requests_too_large_total
+		processed_bytes_total:                   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                  components.sources.internal_metrics.output.metrics.processed_events_total
 	}
 }
@@ -50,8 +50,8 @@ components: sinks: _influxdb: {
 			}
 		}
 		database: {
-			description: "Sets the target database for the write into InfluxDB 1."
-			groups: ["v1"]
+			description: "Sets the target database for the write into InfluxDB 1 or InfluxDB 3."
+			groups: ["v1", "v3"]
 			required: true
 			type: string: {
 				examples: ["vector-database", "iot-store"]
@@ -59,7 +59,7 @@ components: sinks: _influxdb: {
 		}
 		endpoint: {
 			description: "The endpoint to send data to."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			required: true
 			type: string: {
 				examples: ["http://localhost:8086/", "https://us-west-2-1.aws.cloud1.influxdata.com", "https://us-west-2-1.aws.cloud2.influxdata.com"]
@@ -98,8 +98,12 @@ components: sinks: _influxdb: {
 		}
 		token: {
 			category:    "Auth"
-			description: "[Authentication token](\(urls.influxdb_authentication_token)) for InfluxDB 2."
-			groups: ["v2"]
+			description: """
+				[Authentication token](\(urls.influxdb_authentication_token)) for InfluxDB 2 or InfluxDB 3. Setting
+				both a `database` and a `token`, without an `org` and a `bucket`, writes to the
+				[InfluxDB 3 write API](\(urls.influxdb_http_api_v3)).
+				"""
+			groups: ["v2", "v3"]
 			required: true
 			type: string: {
 				examples: ["${INFLUXDB_TOKEN}", "ef8d5de700e7989468166c40fc8a0ccd"]
@@ -22,7 +22,12 @@ components: sinks: influxdb_logs: {
 				max_bytes:    1_000_000
 				timeout_secs: 1.0
 			}
-			compression: enabled: false
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
@@ -43,9 +48,31 @@ components: sinks: influxdb_logs: {
 	}
 
 	configuration: sinks._influxdb.configuration & {
+		field_types: {
+			required:    false
+			common:      false
+			description: "The types that fields are written as, by field name. Values that can't be converted, such as negative numbers as `uint`, keep the type of their value."
+			groups: ["v1", "v2", "v3"]
+			type: object: {
+				examples: [{"status": "uint", "success": "bool"}]
+				options: {
+					"*": {
+						description: "The type of the field."
+						required:    true
+						type: string: enum: {
+							int:    "A signed integer, parsed from strings."
+							uint:   "An unsigned integer, parsed from strings. InfluxDB 1 writes it as a signed integer."
+							float:  "A float, parsed from strings."
+							bool:   "A boolean, parsed from `true` and `false` strings or `0` and `1` integers."
+							string: "A string."
+						}
+					}
+				}
+			}
+		}
 		measurement: {
 			description: "The influxdb measurement name that will be written to."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			required: true
 			type: string: {
 				examples: ["vector-logs"]
@@ -55,7 +82,7 @@ components: sinks: influxdb_logs: {
 			description: """
 				`{namespace}.vector` will be encoded as the destination infuxdb measurement.
 				"""
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			required: false
 			common:   true
 			warnings: ["Deprecated, please use `measurement` instead."]
@@ -68,7 +95,7 @@ components: sinks: influxdb_logs: {
 			required:    false
 			common:      false
 			description: "The set of fields that will be attached to each LineProtocol as tags. Note: If the set of tag values has high cardinality this also increase cardinality in InfluxDB."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			type: array: {
 				default: null
 				items: type: string: {
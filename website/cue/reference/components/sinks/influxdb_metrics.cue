@@ -22,7 +22,12 @@ components: sinks: influxdb_metrics: {
 				max_events:   20
 				timeout_secs: 1.0
 			}
-			compression: enabled: false
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
@@ -31,6 +36,7 @@ components: sinks: influxdb_metrics: {
 				enabled: true
 				headers: false
 			}
+			tag_rules: true
 			tls: sinks._influxdb.features.send.tls
 			to:  sinks._influxdb.features.send.to
 		}
@@ -59,7 +65,7 @@ components: sinks: influxdb_metrics: {
 			required:    false
 			common:      false
 			description: "A map of additional key-value pairs that will be attached to each LineProtocol as tags."
-			groups: ["v1", "v2"]
+			groups: ["v1", "v2", "v3"]
 			type: object: {
 				examples: [{region: "us-west-1"}]
 			}
@@ -0,0 +1,219 @@
+package metadata
+
+components: sinks: json_ingest: {
+	title: "JSON Ingest"
+
+	classes: {
+		commonly_used: false
+		service_providers: []
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip", "zlib"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: true
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: {
+					name:     "HTTP ingest API"
+					thing:    "an \(name)"
+					url:      urls.http_server
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		uri: {
+			description: "The full URI of the ingest endpoint. Batches are sent to it with `POST` requests."
+			required:    true
+			type: string: {
+				examples: ["https://ingest.example.com/v1/logs"]
+			}
+		}
+		auth: {
+			common:      true
+			description: "Options for authenticating requests against the ingest API."
+			required:    false
+			type: object: options: {
+				strategy: {
+					description: "The authentication strategy to use."
+					required:    true
+					type: string: enum: {
+						basic:  "`Authorization: Basic` with the base64 encoded `user` and `password`."
+						bearer: "`Authorization: Bearer` with the `token`."
+						scheme: "`Authorization` with a custom `scheme` followed by the `credentials`, such as `Api-Key abc123`."
+						header: "The `value` is sent as-is in the header called `name`, such as `X-API-Key`."
+					}
+				}
+				user: {
+					description:   "The basic authentication user name."
+					required:      true
+					relevant_when: "strategy = \"basic\""
+					type: string: examples: ["${INGEST_USERNAME}"]
+				}
+				password: {
+					description:   "The basic authentication password."
+					required:      true
+					relevant_when: "strategy = \"basic\""
+					type: string: examples: ["${INGEST_PASSWORD}"]
+				}
+				token: {
+					description:   "The bearer token."
+					required:      true
+					relevant_when: "strategy = \"bearer\""
+					type: string: examples: ["${INGEST_TOKEN}"]
+				}
+				scheme: {
+					description:   "The authorization scheme."
+					required:      true
+					relevant_when: "strategy = \"scheme\""
+					type: string: examples: ["Api-Key", "Splunk"]
+				}
+				credentials: {
+					description:   "The credentials sent after the authorization scheme."
+					required:      true
+					relevant_when: "strategy = \"scheme\""
+					type: string: examples: ["${INGEST_API_KEY}"]
+				}
+				name: {
+					description:   "The name of the header to send the key in."
+					required:      true
+					relevant_when: "strategy = \"header\""
+					type: string: examples: ["X-API-Key"]
+				}
+				value: {
+					description:   "The value of the header."
+					required:      true
+					relevant_when: "strategy = \"header\""
+					type: string: examples: ["${INGEST_API_KEY}"]
+				}
+			}
+		}
+		envelope: {
+			common:      true
+			description: "How each batch of events is laid out in the request body."
+			required:    false
+			type: object: options: {
+				shape: {
+					common:      true
+					description: "The shape of the request body."
+					required:    false
+					type: string: {
+						default: "array"
+						enum: {
+							array:   "A JSON array of events."
+							ndjson:  "Newline delimited JSON, with one event per line."
+							wrapped: "A JSON object holding the array of events under `key`."
+						}
+					}
+				}
+				key: {
+					common:        false
+					description:   "The key of the array of events in the wrapping object."
+					required:      false
+					relevant_when: "shape = \"wrapped\""
+					type: string: {
+						default: "events"
+						examples: ["logs", "records"]
+					}
+				}
+			}
+		}
+		retry: {
+			common:      false
+			description: "Which unsuccessful responses are retried."
+			required:    false
+			type: object: options: status_codes: {
+				common:      false
+				description: """
+					The status codes, or classes of status codes such as `"5xx"`, to retry. Any other unsuccessful
+					response is not retried. By default, `429` and all `5xx` codes except `501` are retried.
+					"""
+				required: false
+				type: array: {
+					default: null
+					items: type: string: examples: ["409", "5xx"]
+				}
+			}
+		}
+		healthcheck: type: object: options: uri: {
+			common:      false
+			description: "The full URI to make a `GET` health check request to. If not set, no health check is made."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["https://ingest.example.com/health"]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		vendors: {
+			title: "Vendor ingest APIs"
+			body: """
+				Many vendors accept batches of JSON events over HTTP, differing only in how requests are authenticated,
+				how the batch is laid out, and which responses should be retried. The `auth`, `envelope`, and `retry`
+				options cover these differences, so that a dedicated sink isn't needed for each of them.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_out_total:                 components.sources.internal_metrics.output.metrics.events_out_total
+	}
+}
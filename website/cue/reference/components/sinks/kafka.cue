@@ -71,6 +71,19 @@ components: sinks: kafka: {
 				unit: null
 			}
 		}
+		partitioner: {
+			common:      false
+			description: "The partitioner used to choose the partition of each message, for compatibility with other clients producing to the same topics. If unspecified, the librdkafka default (`consistent_random`) is used."
+			required:    false
+			type: string: {
+				default: null
+				enum: {
+					murmur2: "Hashes the key with murmur2, as the Java client does. Messages without key are sent to random partitions."
+					fnv1a:   "Hashes the key with FNV-1a, as the Sarama Go client does. Messages without key are sent to random partitions."
+					sticky:  "Hashes the key with murmur2, as the Java client does. Messages without key are sent to the same partition until the batch is sent."
+				}
+			}
+		}
 		sasl: {
 			common:      false
 			description: "Options for SASL/SCRAM authentication support."
@@ -123,6 +136,49 @@ components: sinks: kafka: {
 				syntax: "template"
 			}
 		}
+		topic_creation: {
+			common:      false
+			description: """
+				Creates the topics that don't exist yet, using the admin API, before sending them any message. If the
+				sink isn't authorized to create topics, it logs a warning and stops trying, sending the messages
+				regardless.
+				"""
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					num_partitions: {
+						common:      true
+						description: "The number of partitions of the topics. Defaults to the `num.partitions` setting of the brokers."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [6]
+							unit: null
+						}
+					}
+					replication_factor: {
+						common:      true
+						description: "The replication factor of the topics. Defaults to the `default.replication.factor` setting of the brokers."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [3]
+							unit: null
+						}
+					}
+					config: {
+						common:      false
+						description: "The topic-level configuration of the topics."
+						required:    false
+						type: object: {
+							examples: [{"retention.ms": "86400000", "cleanup.policy": "delete"}]
+							options: {}
+						}
+					}
+				}
+			}
+		}
 		headers_key: {
 			common:      false
 			description: "The log field name to use for the Kafka headers. If omitted, no headers will be written."
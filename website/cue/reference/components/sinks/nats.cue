@@ -51,7 +51,24 @@ components: sinks: nats: {
 		notices: []
 	}
 
-	configuration: components._nats.configuration & {}
+	configuration: components._nats.configuration & {
+		jetstream: {
+			common:      false
+			description: "Publish to [JetStream](\(urls.nats_jetstream)), waiting for the stream to acknowledge each message before it's considered delivered. The subject must be bound to a stream."
+			required:    false
+			type: bool: default: false
+		}
+		message_id: {
+			common:      false
+			description: "The ID of the messages published to JetStream, sent in the `Nats-Msg-Id` header. Messages with the same ID are only stored once within the duplicate window of the stream, so retried messages aren't duplicated. Messages are published without an ID when the template can't be rendered."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ id }}"]
+				syntax: "template"
+			}
+		}
+	}
 
 	input: {
 		logs:    true
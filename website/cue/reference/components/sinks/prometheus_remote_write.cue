@@ -35,6 +35,7 @@ components: sinks: prometheus_remote_write: {
 				timeout_secs:               60
 				headers:                    false
 			}
+			tag_rules: true
 			tls: {
 				enabled:                true
 				can_verify_certificate: true
@@ -120,14 +121,28 @@ components: sinks: prometheus_remote_write: {
 		}
 		tenant_id: {
 			common:      false
-			description: "If set, a header named `X-Scope-OrgID` will be added to outgoing requests with the text of this setting. This may be used by Cortex or other remote services to identify the tenant making the request."
-			required:    false
+			description: """
+				If set, a header named `X-Scope-OrgID` will be added to outgoing requests with the text of this setting. This may be used by Cortex, Mimir, or other remote services to identify the tenant making the request.
+
+				When templated, metrics are batched separately for each tenant, so a single sink can write to many tenants. Metrics
+				for which the template can't be rendered are sent to `default_tenant_id` if it is set, and dropped otherwise.
+				"""
+			required: false
 			type: string: {
 				default: null
-				examples: ["my-domain"]
+				examples: ["my-domain", "{{ tags.tenant }}"]
 				syntax: "template"
 			}
 		}
+		default_tenant_id: {
+			common:      false
+			description: "The tenant to send metrics to when `tenant_id` can't be rendered for them. If not set, those metrics are dropped."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["anonymous"]
+			}
+		}
 	}
 
 	input: {
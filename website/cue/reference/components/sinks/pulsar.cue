@@ -128,6 +128,25 @@ components: sinks: pulsar: {
 				examples: ["pulsar://127.0.0.1:6650"]
 			}
 		}
+		json_schema: {
+			common:      false
+			description: "A [JSON schema](\(urls.pulsar_schema)) definition, in the Avro schema format, attached to the producer when encoding events with the `json` codec. Pulsar checks it against the schema of the topic. Schemas of the `avro` codec are always attached."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"{"type": "record", "name": "log", "fields": [{"name": "message", "type": "string"}]}"#]
+			}
+		}
+		partition_key: {
+			common:      false
+			description: "The key of the messages, used to route them to a partition of the topic, and to keep messages with the same key in order with `Key_Shared` subscriptions. Messages are sent without a key when the template can't be rendered."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ host }}", "{{ user_id }}"]
+				syntax: "template"
+			}
+		}
 		topic: {
 			description: "The Pulsar topic name to write events to."
 			required:    true
@@ -0,0 +1,85 @@
+package metadata
+
+components: sinks: record: {
+	title: "Record"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: enabled:    false
+			request: enabled:     false
+			tls: enabled:         false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		path: {
+			description: """
+				The file to record events to. The file is created if it doesn't exist, and
+				events are appended to it otherwise.
+				"""
+			required: true
+			type: string: {
+				examples: ["/var/lib/vector/recording.bin"]
+				syntax: "file_system_path"
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		recording_format: {
+			title: "Recording format"
+			body: """
+				Events are recorded in Vector's native protobuf encoding, along with the time they
+				were recorded at, so they can be replayed with their original pacing by the
+				[`replay` source](\(urls.vector_sources)/replay). A recording is a sequence of frames,
+				each prefixed with its length as a 4-byte big-endian integer. Each frame holds the
+				time its events were recorded, as big-endian Unix microseconds, followed by the events.
+				"""
+		}
+
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Events are acknowledged once the frame holding them has been written to the file.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
@@ -43,7 +43,7 @@ components: sinks: socket: {
 				interface: {
 					socket: {
 						direction: "outgoing"
-						protocols: ["tcp", "udp", "unix"]
+						protocols: ["tcp", "udp", "unix", "unix_datagram", "unix_seqpacket"]
 						ssl: "required"
 					}
 				}
@@ -71,15 +71,17 @@ components: sinks: socket: {
 			required:    true
 			type: string: {
 				enum: {
-					tcp:  "TCP socket"
-					udp:  "UDP socket"
-					unix: "Unix domain socket"
+					tcp:            "TCP socket"
+					udp:            "UDP socket"
+					unix:           "Unix domain socket, in stream mode. Also accepted as `unix_stream`."
+					unix_datagram:  "Unix domain socket, in datagram mode. Each event is sent as its own datagram."
+					unix_seqpacket: "Unix domain socket, in seqpacket mode. Each event is sent as its own packet."
 				}
 			}
 		}
 		path: {
 			description:   "The unix socket path. This should be the absolute path."
-			relevant_when: "mode = `unix`"
+			relevant_when: "mode = `unix` or `unix_datagram` or `unix_seqpacket`"
 			required:      true
 			type: string: {
 				examples: ["/path/to/socket"]
@@ -93,6 +95,16 @@ components: sinks: socket: {
 		traces:  false
 	}
 
+	how_it_works: unix_message_modes: {
+		title: "Unix datagram and seqpacket modes"
+		body: """
+			In the `unix_datagram` and `unix_seqpacket` modes, the socket preserves message boundaries, so each
+			event is written as a single message. Events are not framed unless `framing` is set explicitly, which
+			matches what local daemons such as rsyslog's `imuxsock` input expect. Datagram sockets are unreliable:
+			events are lost if the receiver isn't reading fast enough.
+			"""
+	}
+
 	telemetry: metrics: {
 		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
 		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
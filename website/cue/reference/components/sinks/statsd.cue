@@ -16,6 +16,7 @@ components: sinks: statsd: {
 				enabled:       true
 				relevant_when: "mode = `tcp` or mode = `udp`"
 			}
+			tag_rules: true
 			tls: sinks.socket.features.send.tls
 			to: {
 				service: services.statsd_receiver
@@ -0,0 +1,195 @@
+package metadata
+
+components: sinks: syslog: {
+	title: "Syslog"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: false
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					framing: false
+					enum: ["json", "text"]
+				}
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: enabled:           false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.syslog
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "udp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description: "The address to connect to. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["92.12.333.224:6514"]
+			}
+		}
+		mode: {
+			description: "The type of socket to use."
+			required:    true
+			type: string: {
+				enum: {
+					tcp: "TCP socket, optionally with TLS."
+					udp: "UDP socket. Each message is sent in its own datagram."
+				}
+			}
+		}
+		format: {
+			common:      true
+			description: "The syslog message format."
+			required:    false
+			type: string: {
+				default: "rfc5424"
+				enum: {
+					rfc3164: "The legacy BSD syslog format. Structured data isn't supported by this format."
+					rfc5424: "The IETF syslog format."
+				}
+			}
+		}
+		framing: {
+			common:        false
+			description:   "How messages are delimited on the TCP stream, as described in RFC 6587."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: string: {
+				default: "octet_counting"
+				enum: {
+					octet_counting:  "Each message is prefixed with its length in bytes."
+					non_transparent: "Each message is terminated by a newline. Newlines within messages are replaced by spaces."
+				}
+			}
+		}
+		facility: {
+			common:      false
+			description: "The facility of the message, either as a keyword such as `local0` or as its numeric code. Values that can't be rendered or parsed fall back to `user`."
+			required:    false
+			type: string: {
+				default: "user"
+				examples: ["local0", "{{ facility }}"]
+				syntax: "template"
+			}
+		}
+		severity: {
+			common:      false
+			description: "The severity of the message, either as a keyword such as `err` or as its numeric code. Values that can't be rendered or parsed fall back to `info`."
+			required:    false
+			type: string: {
+				default: "info"
+				examples: ["warning", "{{ level }}"]
+				syntax: "template"
+			}
+		}
+		app_name: {
+			common:      true
+			description: "The name of the application that generated the message."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["{{ service }}"]
+				syntax: "template"
+			}
+		}
+		proc_id: {
+			common:      false
+			description: "The process ID of the application that generated the message."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ pid }}"]
+				syntax: "template"
+			}
+		}
+		msg_id: {
+			common:      false
+			description: "The type of the message. Only used by the `rfc5424` format."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["{{ event_type }}"]
+				syntax: "template"
+			}
+		}
+		structured_data: {
+			common:      false
+			description: "Renders selected event fields as an RFC 5424 structured data element."
+			required:    false
+			type: object: options: {
+				id: {
+					common:      false
+					description: "The ID of the structured data element."
+					required:    false
+					type: string: {
+						default: "vector@32473"
+						examples: ["origin@12345"]
+					}
+				}
+				fields: {
+					description: "The fields to render as parameters of the element. Fields missing from the event are skipped."
+					required:    true
+					type: array: items: type: string: examples: ["user", "request_id"]
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: {
+		header: {
+			title: "Message header"
+			body: """
+				The hostname is taken from the event's `host` field, falling back to the hostname of the machine
+				Vector is running on, and the timestamp from the event's `timestamp` field. The message itself is the
+				event encoded with the configured codec.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
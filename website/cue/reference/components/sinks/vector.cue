@@ -90,6 +90,52 @@ components: sinks: vector: {
 			required:    false
 			type: bool: default: false
 		}
+		http2: {
+			common:        false
+			description:   "HTTP/2 keepalive settings for the connection to the downstream `vector` source."
+			required:      false
+			relevant_when: "version = \"2\""
+			type: object: options: {
+				keepalive_interval_secs: {
+					common:      false
+					description: "The interval at which HTTP/2 PING frames are sent to keep the connection alive. Keepalive pings are disabled if this isn't set."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+				keepalive_timeout_secs: {
+					common:      false
+					description: "How long to wait for a keepalive ping to be acknowledged before the connection is closed."
+					required:    false
+					type: uint: {
+						default: 20
+						unit:    "seconds"
+					}
+				}
+				keepalive_while_idle: {
+					common:      false
+					description: "Whether to keep sending keepalive pings while no requests are in flight."
+					required:    false
+					type: bool: default: false
+				}
+			}
+		}
+		stream_id: {
+			description: """
+				Identifies this sink to the downstream `vector` source, which applies backpressure and reports
+				metrics separately for each stream. Defaults to the component ID of this sink.
+				"""
+			common:        false
+			required:      false
+			relevant_when: "version = \"2\""
+			type: string: {
+				default: null
+				examples: ["edge-us-east-1"]
+			}
+		}
 		version: {
 			description: "Sink API version. Specifying this version ensures that Vector does not break backward compatibility."
 			common:      true
@@ -171,6 +171,56 @@ components: sources: [Name=string]: {
 									syslog:      "Events being parsed from a Syslog message."
 									native:      "Events being parsed from Vector's [native protobuf format](\(urls.native_proto_schema)) ([EXPERIMENTAL](/highlights/2022-03-31-native-event-codecs))."
 									native_json: "Events being parsed from Vector's [native JSON format](\(urls.native_json_schema)) ([EXPERIMENTAL](/highlights/2022-03-31-native-event-codecs))."
+									cef:         "Events being parsed from an ArcSight Common Event Format (CEF) message."
+									leef:        "Events being parsed from an IBM QRadar Log Event Extended Format (LEEF) 1.0 or 2.0 message."
+								}
+							}
+						}
+						cef: {
+							description:   "Options for the CEF decoder."
+							required:      false
+							common:        false
+							relevant_when: "codec = `cef`"
+							type: object: options: {
+								extensions: {
+									description: "Mapping of CEF extension keys to the event fields they are decoded into. Extensions that aren't mapped are decoded into top-level fields named after their key."
+									required:    false
+									common:      false
+									type: object: {
+										examples: [{src: "source.ip", suser: "user.name"}]
+										options: {}
+									}
+								}
+							}
+						}
+						json: {
+							description:   "Options for the JSON decoder."
+							required:      false
+							common:        false
+							relevant_when: "codec = `json`"
+							type: object: options: {
+								simd: {
+									description: "Parse JSON with SIMD instructions, which is significantly faster for large messages. This requires Vector to be built with the `codecs-simd-json` feature, for CPUs with AVX2 or SSE4.2 on x86 and x86_64 (such as with `-C target-cpu=native`), as the instructions are selected at compile time. Other builds fall back to the standard parser."
+									required:    false
+									common:      false
+									type: bool: default: false
+								}
+							}
+						}
+						leef: {
+							description:   "Options for the LEEF decoder."
+							required:      false
+							common:        false
+							relevant_when: "codec = `leef`"
+							type: object: options: {
+								attributes: {
+									description: "Mapping of LEEF attribute keys to the event fields they are decoded into. Attributes that aren't mapped are decoded into top-level fields named after their key."
+									required:    false
+									common:      false
+									type: object: {
+										examples: [{src: "source.ip", usrName: "user.name"}]
+										options: {}
+									}
 								}
 							}
 						}
@@ -200,6 +250,80 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		ttl_secs: {
+			common: false
+			description: """
+				The time to live of the events of the source. Once expired, events still held in buffers
+				are dropped instead of being delivered by sinks, so that stale events aren't delivered
+				late after an outage. An expiration already set on an event, for example by `remap`
+				with `set_metadata_field(.expires_at, ...)`, takes precedence. Dropped events are counted
+				by the `expired_events_total` metric and acknowledged without being delivered.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [3600]
+				unit: "seconds"
+			}
+		}
+
+		leader_election: {
+			common: false
+			description: """
+				Elects a leader among the replicas of a highly available deployment, so that only the leader
+				runs the source while the others wait to take over. This is meant for sources that must run
+				as singletons, such as sources polling an API. The source is stopped if the leader loses its
+				leadership, and started again once the replica is elected leader again.
+				"""
+			required: false
+			type: object: options: {
+				type: {
+					description: "The coordination mechanism."
+					required:    true
+					type: string: enum: {
+						kubernetes_lease: "A Kubernetes Lease, held by the leader and renewed while it runs. Vector needs the permissions to get, create, and update Leases."
+					}
+				}
+				lease_name: {
+					description: "The name of the Lease, created if it doesn't exist. Every source must use its own Lease."
+					required:    true
+					type: string: examples: ["vector-http-poller"]
+				}
+				namespace: {
+					description: "The namespace of the Lease. Defaults to the namespace Vector runs in."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector"]
+					}
+				}
+				identity: {
+					description: "The identity of this replica. Defaults to the `VECTOR_SELF_POD_NAME` environment variable, or the hostname."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector-0"]
+					}
+				}
+				lease_duration_secs: {
+					description: "How long the other replicas wait after the last renewal of the Lease before taking it over."
+					required:    false
+					type: uint: {
+						default: 15
+						unit:    "seconds"
+					}
+				}
+				retry_period_secs: {
+					description: "How often the leader renews the Lease, and the other replicas try to acquire it. Must be greater than zero and lower than `lease_duration_secs`."
+					required:    false
+					type: uint: {
+						default: 2
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+
 		if features.collect != _|_ {
 			if features.collect.proxy != _|_ {
 				if features.collect.proxy.enabled {
@@ -306,6 +430,15 @@ components: sources: [Name=string]: {
 									examples: [ "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US"]
 								}
 							}
+							common_name: {
+								common:      true
+								description: "The common name (CN) from the subject of the client TLS certificate, if it has one, for example to attribute events to tenants. Only added if `tls.client_metadata_key` is set."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["tenant-a.example.com"]
+								}
+							}
 						}
 					}
 				}
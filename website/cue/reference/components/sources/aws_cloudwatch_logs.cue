@@ -0,0 +1,192 @@
+package metadata
+
+components: sources: aws_cloudwatch_logs: components._aws & {
+	title: "AWS CloudWatch Logs"
+
+	features: {
+		acknowledgements: false
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			from: service:       services.aws_cloudwatch_logs
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: [
+			"""
+				The IAM identity Vector runs as requires the `logs:FilterLogEvents` permission on the
+				log groups.
+				""",
+		]
+		warnings: [
+			"""
+				Events are read by their timestamp, so events that are delivered to CloudWatch Logs
+				with a timestamp older than the last poll are missed. For log groups with late
+				events, prefer [subscriptions](\(urls.aws_cloudwatch_logs_subscriptions)) through
+				the `aws_kinesis_firehose` source.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the position reached in each log group. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+			}
+		}
+		filter_pattern: {
+			common:      false
+			description: "A [filter pattern](\(urls.aws_cloudwatch_logs_filter_pattern)) events must match to be read. By default, all events are read."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["ERROR", "{ $.level = \"error\" }"]
+			}
+		}
+		log_groups: {
+			description: "The names of the [log groups](\(urls.aws_cloudwatch_logs_group_name)) to read events from."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["/aws/lambda/example"]
+			}
+		}
+		log_stream_prefix: {
+			common:      false
+			description: "Only read events from log streams whose name starts with this prefix."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["2022/08/01/"]
+			}
+		}
+		poll_interval_secs: {
+			common:      true
+			description: "How often to poll the log groups for new events. Must be greater than zero."
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		read_existing_events: {
+			common:      false
+			description: "Whether to read the events already present in a log group when no checkpoint exists for it. By default, only events newer than the time Vector first starts reading a log group are read."
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	output: logs: event: {
+		description: "An individual CloudWatch Logs event."
+		fields: {
+			event_id: {
+				description: "The ID of the event in CloudWatch Logs."
+				required:    true
+				type: string: {
+					examples: ["36975485962549396425464812376834817367251929347924434944"]
+				}
+			}
+			log_group: {
+				description: "The log group the event was read from."
+				required:    true
+				type: string: {
+					examples: ["/aws/lambda/example"]
+				}
+			}
+			log_stream: {
+				description: "The log stream the event was read from."
+				required:    true
+				type: string: {
+					examples: ["2022/08/01/[$LATEST]0123456789abcdef0123456789abcdef"]
+				}
+			}
+			message: {
+				description: "The message of the event."
+				required:    true
+				type: string: {
+					examples: ["START RequestId: 7c6a8b3e-0e8f-4e5c-9b3a-3d8f2f1c1e6b Version: $LATEST"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_cloudwatch_logs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The timestamp of the event."
+			}
+		}
+	}
+
+	how_it_works: {
+		polling: {
+			title: "Polling"
+			body: """
+				Unlike [subscriptions](\(urls.aws_cloudwatch_logs_subscriptions)), which require
+				a Kinesis stream or Firehose delivery stream, this source polls each log group with
+				the [`FilterLogEvents`](\(urls.aws_cloudwatch_logs_filter_log_events)) API every
+				`poll_interval_secs`. `FilterLogEvents` requests are subject to a per-account
+				[quota](\(urls.aws_cloudwatch_logs_service_limits)), so the interval should be
+				raised when reading many log groups.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				The timestamp of the newest event read from each log group, along with the IDs of
+				the events read at that timestamp, is persisted in the `data_dir` once all of its
+				new events have been read. After a restart, reading resumes from there. Events are
+				read again if Vector stops while reading a log group, so delivery is at least once.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
@@ -0,0 +1,171 @@
+package metadata
+
+components: sources: aws_cloudwatch_metrics: components._aws & {
+	title: "AWS CloudWatch Metrics"
+
+	description: """
+		Collects metrics from AWS CloudWatch with the `GetMetricData` API.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			checkpoint: enabled: false
+			proxy: enabled:      true
+			from: {
+				service: services.aws_cloudwatch_metrics
+
+				interface: {
+					socket: {
+						api: {
+							title: "AWS CloudWatch API"
+							url:   urls.aws_cloudwatch_metrics_get_metric_data
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		requirements: [
+			"""
+				The IAM identity Vector runs as requires the `cloudwatch:ListMetrics` and
+				`cloudwatch:GetMetricData` permissions.
+				""",
+		]
+		warnings: [
+			"""
+				`GetMetricData` requests are [billed](\(urls.aws_cloudwatch_pricing)) by the number of
+				metrics requested.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		delay_secs: {
+			common:      false
+			description: "How far behind the current time datapoints are collected. CloudWatch takes a while to aggregate the datapoints of a period, so collecting them too early can return partial values."
+			required:    false
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+		metrics: {
+			description: "The metrics to collect."
+			required:    true
+			type: array: items: type: object: options: {
+				dimensions: {
+					common:      true
+					description: "The dimensions the metrics must have, by name. A value of `*` matches any value of the dimension."
+					required:    false
+					type: object: {
+						examples: [{InstanceId: "*"}, {LoadBalancer: "app/my-load-balancer/50dc6c495c0c9188"}]
+						options: {}
+					}
+				}
+				metric_names: {
+					common:      true
+					description: "The names of the metrics to collect. By default, all metrics of the namespace are collected."
+					required:    false
+					type: array: {
+						default: []
+						items: type: string: {
+							examples: ["CPUUtilization", "NetworkIn"]
+						}
+					}
+				}
+				namespace: {
+					description: "The [CloudWatch namespace](\(urls.aws_cloudwatch_metrics_namespaces)) of the metrics."
+					required:    true
+					type: string: {
+						examples: ["AWS/EC2", "AWS/ApplicationELB"]
+					}
+				}
+				statistics: {
+					common:      true
+					description: "The [statistics](\(urls.aws_cloudwatch_metrics_statistics)) to collect. `Sum` and `SampleCount` are emitted as counters, and all other statistics as gauges."
+					required:    false
+					type: array: {
+						default: ["Average"]
+						items: type: string: {
+							examples: ["Average", "Sum", "Minimum", "Maximum", "SampleCount", "p99"]
+						}
+					}
+				}
+			}
+		}
+		period_secs: {
+			common:      true
+			description: "The period datapoints are aggregated over, which is also the interval between collections. Must be 1, 5, 10, 30 or a non-zero multiple of 60. Periods shorter than 60 seconds are only available for high-resolution metrics."
+			required:    false
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: metrics: {
+		_tags: {
+			"*": {
+				description: "The dimensions of the CloudWatch metric."
+				examples: ["i-0123456789abcdef0"]
+				required: false
+			}
+		}
+
+		counter: output._passthrough_counter & {
+			description: "The `Sum` or `SampleCount` statistic of a CloudWatch metric over a period, named after the metric and the statistic, such as `request_count_sum`. The namespace is the CloudWatch namespace, such as `aws_application_elb`."
+			tags: _tags
+		}
+		gauge: output._passthrough_gauge & {
+			description: "Any other statistic of a CloudWatch metric over a period, named after the metric and the statistic, such as `cpu_utilization_average`. The namespace is the CloudWatch namespace, such as `aws_ec2`."
+			tags: _tags
+		}
+	}
+
+	how_it_works: {
+		collection: {
+			title: "Collection"
+			body: """
+				Every `period_secs`, the metrics matching each query are listed with `ListMetrics`,
+				and the datapoints of the periods that completed since the last collection are
+				fetched with `GetMetricData`, in batches of up to 500 metrics and statistics. Each
+				datapoint is emitted with the start of its period as timestamp, and the period as
+				`interval_ms`, so that downstream sinks can tell how long it spans.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
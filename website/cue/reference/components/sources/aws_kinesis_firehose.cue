@@ -111,20 +111,108 @@ components: sources: aws_kinesis_firehose: {
 				}
 			}
 		}
+		record_format: {
+			common:      true
+			description: "The format of the data in the records of the Firehose message."
+			required:    false
+			type: string: {
+				default: "raw"
+				enum: {
+					raw: "Each record is decoded with the configured `framing` and `decoding` options."
+					cloudwatch_logs: """
+						Each record is the payload of an [AWS CloudWatch Logs subscription](\(urls.aws_cloudwatch_logs_subscriptions)).
+						One event is published per log event in the payload, decoded with the configured `framing` and
+						`decoding` options. Control messages sent by CloudWatch Logs to check the delivery stream are
+						dropped.
+						"""
+					vpc_flow_logs: """
+						Each record holds [AWS VPC flow log](\(urls.aws_vpc_flow_logs)) records. One event is published
+						per flow log record, with a field per value. Flow logs can either be delivered to Firehose
+						directly or through an AWS CloudWatch Logs subscription.
+						"""
+				}
+			}
+		}
+		vpc_flow_logs_format: {
+			common:      false
+			description: """
+				The [format](\(urls.aws_vpc_flow_logs_records)) of the flow log records, as set on the flow log.
+				Only used when `record_format` is `vpc_flow_logs`. By default, the fields of the default format,
+				version 2, are expected.
+
+				Each field is published under its name, with dashes replaced by underscores. Values of `-` are left
+				out, and the `start` and `end` fields are published as timestamps.
+				"""
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${version} ${vpc-id} ${srcaddr} ${dstaddr} ${srcport} ${dstport} ${protocol} ${bytes} ${action}"]
+			}
+		}
 	}
 
 	output: logs: {
 		line: {
-			description: "One event will be published per incoming AWS Kinesis Firehose record."
+			description: """
+				One event will be published per incoming AWS Kinesis Firehose record, or per log event or flow log
+				record depending on the `record_format` option.
+				"""
 			fields: {
 				timestamp: fields._current_timestamp
 				message: {
-					description: "The raw record from the incoming payload."
-					required:    true
+					description: "The raw record from the incoming payload, or the message of the log event for CloudWatch Logs subscription payloads."
+					required:    false
+					common:      true
 					type: string: {
+						default: null
 						examples: ["Started GET / for 127.0.0.1 at 2012-03-10 14:28:14 +0100"]
 					}
 				}
+				id: {
+					description: "The ID of the log event, for CloudWatch Logs subscription payloads."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["35683658089614582423604394983260738922885519999578275840"]
+					}
+				}
+				owner: {
+					description: "The AWS account ID of the log group, for CloudWatch Logs subscription payloads."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["111111111111"]
+					}
+				}
+				log_group: {
+					description: "The log group of the log event, for CloudWatch Logs subscription payloads."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["/aws/lambda/example"]
+					}
+				}
+				log_stream: {
+					description: "The log stream of the log event, for CloudWatch Logs subscription payloads."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["2020/09/14/[$LATEST]abcdef"]
+					}
+				}
+				subscription_filters: {
+					description: "The subscription filters that matched the log event, for CloudWatch Logs subscription payloads."
+					required:    false
+					common:      false
+					type: array: {
+						default: null
+						items: type: string: examples: ["Destination"]
+					}
+				}
 				request_id: {
 					description: "The AWS Kinesis Firehose request ID, value of the `X-Amz-Firehose-Request-Id` header."
 					required:    true
@@ -184,9 +272,8 @@ components: sources: aws_kinesis_firehose: {
 				set this up:
 
 				1. Deploy vector with a publicly exposed HTTP endpoint using
-				   this source. You will likely also want to use the
-				   [`parse_aws_cloudwatch_log_subscription_message`](\(urls.vrl_functions)/#parse_aws_cloudwatch_log_subscription_message)
-				   function to extract the log events. Make sure to set
+				   this source, with `record_format` set to `cloudwatch_logs`
+				   to publish one event per log event. Make sure to set
 				   the `access_key` to secure this endpoint. Your
 				   configuration might look something like:
 
@@ -196,25 +283,12 @@ components: sources: aws_kinesis_firehose: {
 					type = "aws_kinesis_firehose"
 					address = "127.0.0.1:9000"
 					access_key = "secret"
-
-					[transforms.cloudwatch]
-					type = "remap"
-					inputs = ["firehose"]
-					drop_on_error = false
-					source = '''
-					parsed = parse_aws_cloudwatch_log_subscription_message!(.message)
-					. = unnest(parsed.log_events)
-					. = map_values(.) -> |value| {
-						event = del(value.log_events)
-						value |= event
-						message = del(.message)
-						. |= object!(parse_json!(message))
-					}
-					'''
+					record_format = "cloudwatch_logs"
+					decoding.codec = "json"
 
 					[sinks.console]
 					type = "console"
-					inputs = ["cloudwatch"]
+					inputs = ["firehose"]
 					encoding.codec = "json"
 				   ```
 
@@ -229,6 +303,24 @@ components: sources: aws_kinesis_firehose: {
 				   forward the events to your delivery stream
 				"""
 		}
+
+		vpc_flow_logs: {
+			title: "Forwarding VPC flow logs"
+			body:  """
+				[AWS VPC flow logs](\(urls.aws_vpc_flow_logs)) can be published
+				to a Kinesis Firehose delivery stream, either directly or
+				through a CloudWatch Logs subscription. With `record_format`
+				set to `vpc_flow_logs`, both are handled and one event is
+				published per flow log record, with a field per value.
+
+				If the flow log uses a custom format, set `vpc_flow_logs_format`
+				to the same format, for example
+				`${version} ${vpc-id} ${srcaddr} ${dstaddr} ${action}`.
+				Records that don't have as many values as the format has fields
+				cause the request to be rejected, so that Firehose can retry
+				it or back it up.
+				"""
+		}
 	}
 
 	telemetry: metrics: {
@@ -44,12 +44,17 @@ components: sources: demo_logs: {
 			required:    true
 			type: string: {
 				enum: {
-					"shuffle":       "Lines are chosen at random from the list specified using `lines`."
-					"apache_common": "Randomly generated logs in [Apache common](\(urls.apache_common)) format."
-					"apache_error":  "Randomly generated logs in [Apache error](\(urls.apache_error)) format."
-					"syslog":        "Randomly generated logs in Syslog format ([RFC 5424](\(urls.syslog_5424)))."
-					"bsd_syslog":    "Randomly generated logs in Syslog format ([RFC 3164](\(urls.syslog_3164)))."
-					"json":          "Randomly generated HTTP server logs in [JSON](\(urls.json)) format."
+					"shuffle":         "Lines are chosen at random from the list specified using `lines`."
+					"apache_common":   "Randomly generated logs in [Apache common](\(urls.apache_common)) format."
+					"apache_error":    "Randomly generated logs in [Apache error](\(urls.apache_error)) format."
+					"syslog":          "Randomly generated logs in Syslog format ([RFC 5424](\(urls.syslog_5424)))."
+					"bsd_syslog":      "Randomly generated logs in Syslog format ([RFC 3164](\(urls.syslog_3164)))."
+					"json":            "Randomly generated HTTP server logs in [JSON](\(urls.json)) format."
+					"apache_combined": "Randomly generated access logs in the Apache combined format, with status codes, paths, response sizes and user agents distributed as in real traffic."
+					"nginx":           "Randomly generated access logs in the nginx `combined` format, followed by the request time, with the same distributions as `apache_combined`."
+					"app_json":        "Randomly generated application logs in [JSON](\(urls.json)) format, with the fields set in `fields`."
+					"traces":          "Randomly generated traces in [JSON](\(urls.json)) format, each with the spans of a request and the downstream calls made to handle it."
+					"metrics":         "Randomly generated points of metric series in [JSON](\(urls.json)) format, whose values follow a seasonal pattern with some noise."
 				}
 			}
 		}
@@ -94,6 +99,42 @@ components: sources: demo_logs: {
 			required:      false
 			type: bool: default: false
 		}
+		fields: {
+			common:        false
+			relevant_when: "`format` = `app_json`"
+			description: """
+				The fields of each log. The `timestamp`, `level`, `service`, `message`, `host`, `request_id`,
+				`trace_id`, `user_id`, `duration_ms`, `status`, and `version` fields have realistic values. Other
+				fields have random words as values.
+				"""
+			required: false
+			type: array: {
+				default: ["timestamp", "level", "service", "message", "request_id", "duration_ms"]
+				items: type: string: {
+					examples: ["level", "message", "team"]
+				}
+			}
+		}
+		series: {
+			common:        false
+			relevant_when: "`format` = `metrics`"
+			description:   "The number of series. Each generated line is a point of the next series."
+			required:      false
+			type: uint: {
+				default: 8
+				unit:    null
+			}
+		}
+		period_secs: {
+			common:        false
+			relevant_when: "`format` = `metrics`"
+			description:   "The period of the seasonal pattern of the series. The default is one day."
+			required:      false
+			type: float: {
+				default: 86400.0
+				unit:    "seconds"
+			}
+		}
 	}
 
 	output: logs: {}
@@ -25,12 +25,13 @@ components: sources: dnstap: {
 					}
 					direction: "incoming"
 					port:      0
-					protocols: ["unix"]
+					protocols: ["unix", "tcp"]
 					socket: "/run/bind/dnstap.sock"
-					ssl:    "disabled"
+					ssl:    "optional"
 				}
 			}
-			tls: enabled: false
+			keepalive: enabled: true
+			tls: sources.socket.features.receive.tls
 		}
 	}
 
@@ -54,18 +55,46 @@ components: sources: dnstap: {
 				unit:    "bytes"
 			}
 		}
+		mode: {
+			common:      true
+			description: "The type of socket to read dnstap data from."
+			required:    false
+			type: string: {
+				default: "unix"
+				enum: {
+					unix: "Listen on a Unix domain socket."
+					tcp:  "Listen on a TCP socket, optionally using TLS."
+				}
+			}
+		}
 		socket_path: {
+			common: true
 			description: """
 				Absolute path of server socket file to which the DNS server is
 				configured to send dnstap data. The socket file will be created
 				by dnstap source component automatically upon startup.
 				"""
-			required: true
+			relevant_when: "mode = `unix`"
+			required:      false
 			type: string: {
+				default: "/run/bind/dnstap.sock"
 				examples: ["/run/bind/dnstap.sock"]
 				syntax: "file_system_path"
 			}
 		}
+		address: {
+			common: true
+			description: """
+				The address to listen for dnstap connections on. The peer address
+				of each connection is used as the source of its events.
+				"""
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["0.0.0.0:9000"]
+			}
+		}
 		socket_file_mode: {
 			common: true
 			description: """
@@ -465,6 +494,7 @@ components: sources: dnstap: {
 									NXRRSet:   "RR Set that should exist does not"
 									NotAuth:   "Server Not Authoritative for zone"
 									NotZone:   "Name not contained in zone"
+									BADVERS:   "Bad OPT Version"
 									BADSIG:    "TSIG Signature Failure"
 									BADKEY:    "Key not recognized"
 									BADTIME:   "Signature out of time window"
@@ -566,7 +596,9 @@ components: sources: dnstap: {
 							description: """
 								A pseudo section containing EDNS options of DNS query request
 								message. See [RFC 6891](\(urls.rfc_6891)) for detailed
-								information about its content.
+								information about its content. The EDNS Client Subnet option
+								([RFC 7871](https://datatracker.ietf.org/doc/html/rfc7871)) is
+								decoded into `clientSubnet`.
 								"""
 							required:    false
 							type: object: {
@@ -716,6 +748,7 @@ components: sources: dnstap: {
 									NXRRSet:   "RR Set that should exist does not"
 									NotAuth:   "Server Not Authoritative for zone"
 									NotZone:   "Name not contained in zone"
+									BADVERS:   "Bad OPT Version"
 									BADSIG:    "TSIG Signature Failure"
 									BADKEY:    "Key not recognized"
 									BADTIME:   "Signature out of time window"
@@ -861,7 +894,11 @@ components: sources: dnstap: {
 							description: """
 								A pseudo section containing EDNS options of DNS query response
 								message. See [RFC 6891](\(urls.rfc_6891)) for detailed
-								information about its content.
+								information about its content. The EDNS Client Subnet option
+								([RFC 7871](https://datatracker.ietf.org/doc/html/rfc7871)) is
+								decoded into `clientSubnet`, and Extended DNS Errors
+								([RFC 8914](https://datatracker.ietf.org/doc/html/rfc8914)) are
+								decoded into `extendedErrors`.
 								"""
 							required:    false
 							type: object: {
@@ -879,6 +916,26 @@ components: sources: dnstap: {
 										]
 										"udpPayloadSize": 4096
 									},
+									{
+										"do":            true
+										"ednsVersion":   0
+										"extendedRcode": 0
+										"clientSubnet": {
+											"family":             1
+											"sourcePrefixLength": 24
+											"scopePrefixLength":  24
+											"address":            "192.0.2.0"
+										}
+										"extendedErrors": [
+											{
+												"infoCode":  6
+												"purpose":   "DNSSEC Bogus"
+												"extraText": "signature expired"
+											},
+										]
+										"options": []
+										"udpPayloadSize": 1232
+									},
 								]
 								options: {}
 							}
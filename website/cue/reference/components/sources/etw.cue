@@ -0,0 +1,288 @@
+package metadata
+
+components: sources: etw: {
+	title: "Event Tracing for Windows"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.etw
+
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: [
+			"""
+				Vector must run as an administrator or as a member of the `Performance Log Users`
+				group to start trace sessions.
+				""",
+		]
+		warnings: [
+			"""
+				ETW drops events when the session buffers fill up faster than Vector consumes
+				them. Increase `buffer_size_kb` or narrow the enabled levels and keywords if
+				events are lost.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		buffer_size_kb: {
+			common:      false
+			description: "The size of each buffer of the trace session, in kilobytes."
+			required:    false
+			type: uint: {
+				default: 64
+				unit:    null
+			}
+		}
+		providers: {
+			description: "The providers to enable in the trace session."
+			required:    true
+			type: array: items: type: object: options: {
+				guid: {
+					description: "The GUID of the provider."
+					required:    true
+					type: string: {
+						examples: ["22fb2cd6-0e7b-422b-a0c7-2fad1fd0e716"]
+					}
+				}
+				level: {
+					common:      true
+					description: "The most verbose level of events to enable, from 1 for critical events to 5 for verbose events."
+					required:    false
+					type: uint: {
+						default: 5
+						unit:    null
+					}
+				}
+				match_any_keyword: {
+					common:      true
+					description: "A bitmask of keywords, of which events must match at least one. Zero enables all events."
+					required:    false
+					type: uint: {
+						default: 0
+						unit:    null
+					}
+				}
+				match_all_keyword: {
+					common:      false
+					description: "A bitmask of keywords, of which events must match all."
+					required:    false
+					type: uint: {
+						default: 0
+						unit:    null
+					}
+				}
+			}
+		}
+		session_name: {
+			common:      false
+			description: "The name of the real-time trace session. The name must be unique on the host, and a session with the same name is stopped when the source starts. Defaults to `vector-` followed by the component ID."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-etw"]
+			}
+		}
+	}
+
+	output: logs: event: {
+		description: "An ETW event."
+		fields: {
+			activity_id: {
+				description: "The activity identifier of the event, if any."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["{4E1E4B1A-7E54-0000-4D4B-1E4E547ED801}"]
+				}
+			}
+			event_data: {
+				description: "The scalar properties of the event, decoded with the schema of the provider. Structured and variable-length array properties are not decoded."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"ProcessID": 4242, "ImageName": "\\Device\\HarddiskVolume3\\Windows\\System32\\notepad.exe"}]
+					options: {}
+				}
+			}
+			event_id: {
+				description: "The identifier of the event, as defined by the provider."
+				required:    true
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			event_name: {
+				description: "The name of the event, for providers that define one."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["ProcessStart"]
+				}
+			}
+			keywords: {
+				description: "The keywords bitmask of the event, as a hexadecimal string."
+				required:    true
+				type: string: {
+					examples: ["0x8000000000000010"]
+				}
+			}
+			level: {
+				description: "The level of the event."
+				required:    true
+				type: uint: {
+					examples: [4]
+					unit: null
+				}
+			}
+			opcode: {
+				description: "The opcode of the event."
+				required:    true
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			opcode_name: {
+				description: "The name of the opcode of the event."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["Start"]
+				}
+			}
+			payload: {
+				description: "The raw payload of events whose provider schema is not registered on the host."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["AQI="]
+				}
+			}
+			process_id: {
+				description: "The identifier of the process that published the event."
+				required:    true
+				type: uint: {
+					examples: [4]
+					unit: null
+				}
+			}
+			provider_guid: {
+				description: "The GUID of the provider that published the event."
+				required:    true
+				type: string: {
+					examples: ["{22FB2CD6-0E7B-422B-A0C7-2FAD1FD0E716}"]
+				}
+			}
+			provider_name: {
+				description: "The name of the provider that published the event."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["Microsoft-Windows-Kernel-Process"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["etw"]
+				}
+			}
+			task: {
+				description: "The task of the event."
+				required:    true
+				type: uint: {
+					examples: [1]
+					unit: null
+				}
+			}
+			task_name: {
+				description: "The name of the task of the event."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["ProcessStart"]
+				}
+			}
+			thread_id: {
+				description: "The identifier of the thread that published the event."
+				required:    true
+				type: uint: {
+					examples: [1234]
+					unit: null
+				}
+			}
+			timestamp: {
+				description: "The time the event was published."
+				required:    true
+				type: timestamp: {}
+			}
+			version: {
+				description: "The version of the event schema."
+				required:    true
+				type: uint: {
+					examples: [3]
+					unit: null
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		sessions: {
+			title: "Trace sessions"
+			body: """
+				The source starts a real-time trace session, enables the configured providers in
+				it and consumes its events as they are published. Events never reach the Windows
+				Event Log, so high-frequency telemetry can be collected without filling it up.
+				The session is stopped when Vector shuts down.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
@@ -69,6 +69,48 @@ components: sources: fluent: {
 				unit:    "concurrency"
 			}
 		}
+		security: {
+			common: false
+			description: """
+				Requires clients to authenticate with the handshake of the forward protocol, like Fluentd's
+				`<security>` section. Clients that fail to authenticate are disconnected.
+				"""
+			required: false
+			type: object: options: {
+				self_hostname: {
+					description: "The hostname sent to clients during the handshake. Defaults to the hostname of the machine Vector is running on."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector.example.com"]
+					}
+				}
+				shared_key: {
+					description: "The key shared with clients, which they must prove to know to connect."
+					required:    true
+					type: string: examples: ["${FLUENT_SHARED_KEY}"]
+				}
+				users: {
+					description: "The users allowed to connect. If any are set, clients must also authenticate with the username and password of one of them."
+					required:    false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							username: {
+								description: "The username."
+								required:    true
+								type: string: examples: ["fluent-bit"]
+							}
+							password: {
+								description: "The password."
+								required:    true
+								type: string: examples: ["${FLUENT_PASSWORD}"]
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -180,16 +222,22 @@ components: sources: fluent: {
 
 		secure_mode: {
 			title: "Secure forward mode support"
-			body:  """
-				The `fluent` source currently supports using TLS, but does not support the authentication part of the
-				Fluent protocol including:
-
-				- Shared key
-				- Username and password
-
-				And so these options of the secure forward output plugins for Fluent and Fluent Bit cannot be used.
+			body: """
+				The `fluent` source supports TLS and the authentication handshake of the Fluent protocol, with a
+				shared key and, optionally, usernames and passwords. Configure the same values in the `security`
+				option as in the `<security>` section of Fluentd's forward output, or the `Shared_Key`,
+				`Username`, and `Password` options of Fluent Bit's forward output. Connections that don't
+				complete the handshake within 30 seconds are closed.
 
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7532).
+				```text
+					[OUTPUT]
+						Name          forward
+						Match         *
+						Host          127.0.0.1
+						Port          24224
+						Shared_Key    secret
+						Self_Hostname fluent-bit
+				```
 				"""
 		}
 	}
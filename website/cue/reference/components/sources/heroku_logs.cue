@@ -6,7 +6,8 @@ components: sources: heroku_logs: {
 	title: "Heroku Logplex"
 
 	description: """
-		Receives log data from Heroku log drains via Heroku's logplex system.
+		Receives log data from Heroku log drains via Heroku's logplex system, and from other HTTPS log
+		drains sending syslog frames.
 		"""
 
 	alias: "logplex"
@@ -66,6 +67,25 @@ components: sources: heroku_logs: {
 		address:          sources.http.configuration.address
 		auth:             sources.http.configuration.auth
 		query_parameters: sources.http.configuration.query_parameters
+		path: {
+			common:      false
+			description: "The URL path on which drains shall send requests."
+			required:    false
+			type: string: {
+				default: "/events"
+				examples: ["/drain"]
+			}
+		}
+		reject_when_full: {
+			common: false
+			description: """
+				Whether to respond with `429 Too Many Requests` when downstream components are not keeping up. By
+				default, requests are held open until their events can be sent downstream. Drains that retry on
+				`429` responses can instead back off and resend the frame later.
+				"""
+			required: false
+			type: bool: default: false
+		}
 	}
 
 	output: logs: line: {
@@ -78,6 +98,14 @@ components: sources: heroku_logs: {
 					examples: ["erlang"]
 				}
 			}
+			drain_token: {
+				description: "The drain token sent in the `Logplex-Drain-Token` header, identifying the drain the event was received from."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["d.fc6b856b-3332-4546-93de-587d69f3f8ba"]
+				}
+			}
 			host: fields._local_host
 			message: {
 				description: "The message field, containing the plain text message."
@@ -97,14 +125,29 @@ components: sources: heroku_logs: {
 		}
 	}
 
+	how_it_works: {
+		framing: {
+			title: "Framing"
+			body: """
+				Request bodies are expected to hold syslog frames in the format sent by Logplex. Frames
+				prefixed with their length in bytes, as described in RFC 6587, may span several lines. If
+				the body isn't a sequence of such frames, each line is read as a frame instead, with or
+				without a length prefix. The `Logplex-Msg-Count`, `Logplex-Frame-Id`, and
+				`Logplex-Drain-Token` headers are optional, so drains other than Logplex can send to this
+				source; if `Logplex-Msg-Count` is set, the number of frames must match it.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
-		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
-		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
-		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
-		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
-		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
-		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
-		request_read_errors_total:            components.sources.internal_metrics.output.metrics.request_read_errors_total
-		requests_received_total:              components.sources.internal_metrics.output.metrics.requests_received_total
+		component_errors_total:                    components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:            components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:           components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total:      components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		events_in_total:                           components.sources.internal_metrics.output.metrics.events_in_total
+		http_backpressure_rejected_requests_total: components.sources.internal_metrics.output.metrics.http_backpressure_rejected_requests_total
+		processed_bytes_total:                     components.sources.internal_metrics.output.metrics.processed_bytes_total
+		request_read_errors_total:                 components.sources.internal_metrics.output.metrics.request_read_errors_total
+		requests_received_total:                   components.sources.internal_metrics.output.metrics.requests_received_total
 	}
 }
@@ -56,10 +56,10 @@ components: sources: http: {
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
 		address: {
-			description: "The address to accept connections on. The address _must_ include a port."
+			description: "The address to accept connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
 			required:    true
 			type: string: {
-				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)"]
+				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)", "systemd", "systemd#3"]
 			}
 		}
 		encoding: {
@@ -128,6 +128,60 @@ components: sources: http: {
 				examples: ["vector_http_path"]
 			}
 		}
+		routes: {
+			common: false
+			description: """
+				Additional URL paths to accept events on, each with its own decoding and named output, so a
+				single listener can accept differently encoded payloads. Requests are matched against the
+				routes in the order they are configured, and then against `path`, whose events are sent to the
+				default output. All routes share the `method`, `auth`, and `tls` settings of the source.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					path: {
+						description: "The URL path on which events for this route shall be sent."
+						required:    true
+						type: string: examples: ["/ndjson", "/protobuf"]
+					}
+					strict_path: {
+						description: "If set to `true`, only requests using the exact URL path specified in the route's `path` are matched; otherwise requests sent to a URL path that starts with it are matched."
+						required:    false
+						type: bool: default: true
+					}
+					output: {
+						description: "The name of the output that events received on this route are sent to. Events can be consumed from it as `<source_id>.<output>`."
+						required:    true
+						type: string: examples: ["ndjson"]
+					}
+					framing: {
+						description: "Configures how the request body is framed into events, with the same options as the source's `framing`."
+						required:    false
+						type: object: options: method: {
+							description: "The framing method."
+							required:    false
+							type: string: {
+								default: null
+								examples: ["newline_delimited", "bytes"]
+							}
+						}
+					}
+					decoding: {
+						description: "Configures how events are decoded, with the same options as the source's `decoding`."
+						required:    false
+						type: object: options: codec: {
+							description: "The codec used to decode events."
+							required:    false
+							type: string: {
+								default: "bytes"
+								examples: ["json", "native"]
+							}
+						}
+					}
+				}
+			}
+		}
 		method: {
 			common:      false
 			description: "Specifies the action of the HTTP request."
@@ -65,6 +65,65 @@ components: sources: internal_logs: {
 				default: "pid"
 			}
 		}
+		sample_rates: {
+			common: false
+			description: """
+				The rates at which internal log events are forwarded, for each level. Each rate is expressed as `1/N`.
+				For example, `info = 10` means 1 out of every 10 `INFO` events will be forwarded and the rest will be
+				dropped. By default, all events are forwarded.
+				"""
+			required: false
+			type: object: {
+				examples: [{info: 10, debug: 100}]
+				options: {
+						error: {
+							common:      false
+							description: "The rate at which `ERROR` events are forwarded."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    null
+							}
+						}
+						warn: {
+							common:      false
+							description: "The rate at which `WARN` events are forwarded."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    null
+							}
+						}
+						info: {
+							common:      false
+							description: "The rate at which `INFO` events are forwarded."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    null
+							}
+						}
+						debug: {
+							common:      false
+							description: "The rate at which `DEBUG` events are forwarded."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    null
+							}
+						}
+						trace: {
+							common:      false
+							description: "The rate at which `TRACE` events are forwarded."
+							required:    false
+							type: uint: {
+								default: 1
+								unit:    null
+							}
+						}
+				}
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -94,6 +153,36 @@ components: sources: internal_logs: {
 				required:    true
 				type: "*": {}
 			}
+			vector: {
+				description: "The Vector component the log or trace was emitted by. Only set for events emitted within a component."
+				required:    false
+				type: object: {
+					examples: []
+					options: {
+						component_id: {
+							description: "The ID of the component."
+							required:    true
+							type: string: {
+								examples: ["my_source"]
+							}
+						}
+						component_kind: {
+							description: "The kind of the component."
+							required:    true
+							type: string: {
+								examples: ["source", "transform", "sink"]
+							}
+						}
+						component_type: {
+							description: "The type of the component."
+							required:    true
+							type: string: {
+								examples: ["internal_logs"]
+							}
+						}
+					}
+				}
+			}
 			metadata: {
 				description: "Metadata from the source log or trace event."
 				required:    true
@@ -189,6 +189,32 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		config_changes_total: {
+			description:       "The total number of configuration loads, at startup or on reload, recorded in the audit log."
+			type:              "counter"
+			default_namespace: "vector"
+			tags: _internal_metrics_tags & {
+				trigger: {
+					description: "What triggered the configuration load."
+					required:    true
+					enum: {
+						startup:  "The initial load, when Vector starts."
+						disk:     "A reload of the configuration files."
+						provider: "A new configuration from the configured provider."
+					}
+				}
+				outcome: {
+					description: "What became of the configuration."
+					required:    true
+					enum: {
+						applied:     "The configuration is now running."
+						rejected:    "The configuration couldn't be loaded, and the running one was left untouched."
+						rolled_back: "The configuration couldn't be applied, and the previous one was kept or restored."
+						failed:      "The configuration failed to start, and there was no previous one to restore, or it couldn't be restored either."
+					}
+				}
+			}
+		}
 		reloaded_total: {
 			description:       "The total number of times the Vector instance has been reloaded."
 			type:              "counter"
@@ -254,6 +280,33 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		circuit_breaker_open: {
+			description:       "Whether the circuit breaker of the sink is open."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_rejected_requests_total: {
+			description:       "The total number of requests rejected by the circuit breaker of the sink while open."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_transitions_total: {
+			description:       "The total number of transitions of the circuit breaker of the sink."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				state: {
+					description: "The state transitioned to."
+					required:    true
+					enum: {
+						open:   "The circuit opened."
+						closed: "The circuit closed."
+					}
+				}
+			}
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -411,6 +464,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		leader: {
+			description:       "Whether this replica is the elected leader running a source with `leader_election`, `1` if it is, `0` once it lost the leadership."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		encode_errors_total: {
 			description:       "The total number of errors encountered when encoding an event."
 			type:              "counter"
@@ -452,6 +511,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags & {output: _output}
 		}
+		expired_events_total: {
+			description:       "The total number of events dropped by the sink because they expired."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		processed_events_total: {
 			description:       """
 				The total number of events processed by this component.
@@ -481,6 +546,33 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffer_migrated_bytes_total: {
+			description:       "The number of bytes migrated from the previous buffer of the sink after its buffer configuration changed on reload."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_migrated_events_total: {
+			description:       "The number of events migrated from the previous buffer of the sink after its buffer configuration changed on reload."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_migrations_total: {
+			description:       "The number of buffer migrations started after the buffer configuration of the sink changed on reload."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				from: {
+					description: "The type of the previous buffer."
+					required:    true
+				}
+				to: {
+					description: "The type of the new buffer."
+					required:    true
+				}
+			}
+		}
 		buffer_received_event_bytes_total: {
 			description:       "The number of bytes received by this buffer."
 			type:              "counter"
@@ -656,6 +748,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		memory_budget_exceeded_total: {
+			description:       "The number of times a source waited before sending events because the memory budget was exceeded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		memory_budget_trims_total: {
+			description:       "The number of times the component trimmed its state to stay within the memory budget."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		memory_budget_trimmed_bytes_total: {
+			description:       "The estimated number of bytes released by the component when trimming its state to stay within the memory budget."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		kafka_requests_total: {
 			description:       "Total number of requests sent to Kafka brokers."
 			type:              "counter"
@@ -768,6 +878,12 @@ components: sources: internal_metrics: {
 				path: _path
 			}
 		}
+		http_backpressure_rejected_requests_total: {
+			description:       "The total number of HTTP requests rejected with `429 Too Many Requests` because downstream components weren't keeping up."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		http_bad_requests_total: {
 			description:       "The total number of HTTP `400 Bad Request` errors encountered."
 			type:              "counter"
@@ -790,6 +906,34 @@ components: sources: internal_metrics: {
 				status: _status
 			}
 		}
+		http_client_compression_fallbacks_total: {
+			description:       "The total number of times the HTTP sink fell back to another compression after the server rejected one with `415 Unsupported Media Type`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				content_encoding: {
+					description: "The content encoding the server rejected."
+					required:    true
+				}
+			}
+		}
+		http_client_connection_pool_rotations_total: {
+			description:       "The total number of times an HTTP client rotated its connections after their maximum lifetime."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		http_client_requests_too_large_total: {
+			description:       "The total number of HTTP requests rejected by the server with `413 Payload Too Large`."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				split: {
+					description: "Whether the batch of the request was split in two, to be sent again as two smaller requests."
+					required:    true
+				}
+			}
+		}
 		http_client_rtt_seconds: {
 			description:       "The round-trip time (RTT) of HTTP requests."
 			type:              "histogram"
@@ -962,6 +1106,61 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sink_degraded: {
+			description:       "Whether the sink is degraded because its downstream has been unavailable for longer than the `degradation.threshold_secs` option."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		sink_degradation_transitions_total: {
+			description:       "The total number of transitions of the sink between degraded and recovered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				state: {
+					description: "The state transitioned to."
+					required:    true
+					enum: {
+						degraded:  "The downstream of the sink became unavailable."
+						recovered: "The downstream of the sink became available again."
+					}
+				}
+				policy: {
+					description: "The degradation policy of the sink."
+					required:    true
+				}
+			}
+		}
+		sink_lag_time_seconds: {
+			description:       "The time elapsed between the timestamp of the oldest event of each batch received by the sink and the moment the sink delivered it. Events without a timestamp, and events that failed to be delivered, aren't measured."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		sink_shed_events_total: {
+			description:       "The total number of events shed by the sink while degraded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		sink_spilled_bytes_total: {
+			description:       "The total number of bytes spilled by the sink while degraded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		sink_spilled_events_total: {
+			description:       "The total number of events spilled by the sink while degraded."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		source_lag_time_seconds: {
+			description:       "The time elapsed between the timestamp of the oldest event of each batch sent by the source and the moment the source sent it downstream. Events without a timestamp aren't measured."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
@@ -1037,6 +1236,22 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		stream_received_events_total: {
+			description:       "The number of events received by the `vector` source from a single upstream stream."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				stream_id: _stream_id
+			}
+		}
+		stream_received_event_bytes_total: {
+			description:       "The number of event bytes received by the `vector` source from a single upstream stream."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				stream_id: _stream_id
+			}
+		}
 		stdin_reads_failed_total: {
 			description:       "The total number of errors reading from stdin."
 			type:              "counter"
@@ -1058,6 +1273,30 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		top_k_failed_updates: {
+			description:       "The number of failed metric updates, `incremental` adds, encountered by the top_k transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		top_k_rolled_up_series_total: {
+			description:       "The total number of series beyond the top K rolled up into `_other` series by the top_k transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		udp_socket_datagrams_dropped_total: {
+			description:       "The total number of datagrams the kernel dropped on a UDP socket because its receive buffer was full. Only reported on Linux, when receive buffer autotuning is enabled."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		udp_socket_receive_buffer_bytes: {
+			description:       "The size of a UDP socket's receive buffer after it was last grown by receive buffer autotuning."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -1251,6 +1490,10 @@ components: sources: internal_metrics: {
 			description: "The HTTP status code of the request."
 			required:    false
 		}
+		_stream_id: {
+			description: "The stream ID sent by the upstream `vector` sink, which defaults to its component ID."
+			required:    true
+		}
 		_path: {
 			description: "The path that produced the error."
 			required:    true
@@ -63,6 +63,16 @@ components: sources: kafka: {
 				unit: "milliseconds"
 			}
 		}
+		backpressure_pause_ms: {
+			common:      false
+			description: "The time after which the assigned partitions are paused when events can't be sent downstream. While paused, the consumer keeps polling the brokers so that it doesn't leave the consumer group, and the partitions are resumed once the events are sent. By default, the partitions are never paused."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [1000]
+				unit: "milliseconds"
+			}
+		}
 		fetch_wait_max_ms: {
 			common:      false
 			description: "Maximum time the broker may wait to fill the response."
@@ -80,6 +90,15 @@ components: sources: kafka: {
 				examples: ["consumer-group-name"]
 			}
 		}
+		group_instance_id: {
+			common:      false
+			description: "The static membership identifier of this consumer within the consumer group. A static member keeps its partitions when it restarts within `session_timeout_ms`, instead of triggering a rebalance of the group. It must be unique within the group, and stable across restarts of the same instance."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector-0"]
+			}
+		}
 		key_field: {
 			common:      true
 			description: "The log field name to use for the Kafka message key."
@@ -169,6 +188,18 @@ components: sources: kafka: {
 				}
 			}
 		}
+		rebalance_protocol: {
+			common:      false
+			description: "The protocol used to rebalance the partitions among the members of the consumer group."
+			required:    false
+			type: string: {
+				default: "eager"
+				enum: {
+					eager:       "All the partitions are revoked from all the members, then reassigned."
+					cooperative: "Only the partitions moving to another member are revoked, using the `cooperative-sticky` assignment strategy. All the members of the consumer group must use it."
+				}
+			}
+		}
 		session_timeout_ms: {
 			common:      false
 			description: "The Kafka session timeout in milliseconds."
@@ -261,6 +261,26 @@ components: sources: logstash: {
 	]
 
 	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements and backpressure"
+			body: """
+				Clients send events in windows, announcing how many events each window holds, and wait for the last
+				event of a window to be acknowledged before sending the next one. This source acknowledges a window
+				only once all of its events have been accepted by Vector, or delivered to their sinks if
+				`acknowledgements` are enabled, so slow downstream components slow the clients down rather than
+				buffering their events in Vector.
+				"""
+		}
+
+		client_certificates: {
+			title: "Client certificates"
+			body: """
+				When TLS is enabled with client certificate verification, setting `tls.client_metadata_key` adds the
+				subject of each client's certificate, and its common name (CN), to the events it sends. This can be
+				used to attribute events to tenants, for example by routing on the common name.
+				"""
+		}
+
 		aggregator: {
 			title: "Sending data from logstash agents to Vector aggregators"
 			body: """
@@ -4,7 +4,7 @@ components: sources: nats: {
 	title: "NATS"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -32,6 +32,48 @@ components: sources: nats: {
 	}
 
 	configuration: components._nats.configuration & {
+		jetstream: {
+			common:      false
+			description: "Consume a [JetStream](\(urls.nats_jetstream)) stream through a durable consumer, created or updated when the source starts. The consumer delivers the messages of the stream matching `subject`, and is shared by the Vector instances using the same `durable_name` and `queue`. Messages are acknowledged once their events are processed, or once they are delivered to all sinks when acknowledgements are enabled."
+			required:    false
+			type: object: {
+				examples: [{"stream": "LOGS", "durable_name": "vector"}]
+				options: {
+					stream: {
+						description: "The name of the stream to consume."
+						required:    true
+						type: string: {
+							examples: ["LOGS"]
+						}
+					}
+					durable_name: {
+						description: "The name of the durable consumer, which keeps track of the acknowledged messages across restarts."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+						}
+					}
+					ack_wait_secs: {
+						common:      false
+						description: "How long to wait for a message to be acknowledged before it's redelivered."
+						required:    false
+						type: uint: {
+							default: 30
+							unit:    "seconds"
+						}
+					}
+					max_ack_pending: {
+						common:      false
+						description: "The maximum number of messages delivered but not yet acknowledged."
+						required:    false
+						type: uint: {
+							default: 1000
+							unit:    null
+						}
+					}
+				}
+			}
+		}
 		queue: {
 			common:      false
 			description: "NATS Queue Group to join"
@@ -67,5 +109,15 @@ components: sources: nats: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream_acknowledgements: {
+			title: "JetStream acknowledgements"
+			body:  """
+				With acknowledgements enabled, JetStream messages are acknowledged once all their
+				events are delivered. Messages whose events failed to be delivered are negatively
+				acknowledged, so that JetStream redelivers them, and messages whose events were
+				rejected by a sink are terminated, so that they aren't redelivered.
+				"""
+		}
+	}
 }
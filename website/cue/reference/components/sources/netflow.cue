@@ -0,0 +1,225 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					api: {
+						title: "NetFlow"
+						url:   urls.netflow_v9
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				Data of NetFlow v9 and IPFIX exporters can only be decoded once they have sent
+				their templates, so flows are discarded for up to a template refresh interval after
+				Vector starts.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for datagrams on. NetFlow v5, NetFlow v9, IPFIX and sFlow v5 datagrams are all accepted on the same address."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "0.0.0.0:4739", "0.0.0.0:6343"]
+			}
+		}
+		template_timeout_secs: {
+			common:      false
+			description: "How long to keep templates that the exporter doesn't refresh. This should be longer than the template refresh interval of the exporters."
+			required:    false
+			type: uint: {
+				default: 1800
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: flow: {
+		description: "A flow record, options record or sFlow counters sample."
+		fields: {
+			"*": {
+				description: "The fields of the record, named after the snake-cased IPFIX information element, such as `source_ipv4_address` or `octet_delta_count`. Fields that aren't known are named `field_<id>`, or `enterprise_<number>_<id>` for enterprise-specific IPFIX fields."
+				required:    false
+				common:      true
+				type: "*": {}
+			}
+			destination_ipv4_address: {
+				description: "The destination address of the flow."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["192.0.2.10"]
+				}
+			}
+			destination_transport_port: {
+				description: "The destination port of the flow."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [443]
+					unit: null
+				}
+			}
+			flow_type: {
+				description: "The protocol the record was exported with."
+				required:    true
+				type: string: {
+					enum: {
+						ipfix:      "IPFIX."
+						netflow_v5: "NetFlow v5."
+						netflow_v9: "NetFlow v9."
+						sflow_v5:   "sFlow v5."
+					}
+				}
+			}
+			host: {
+				description: "The address of the exporter."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			octet_delta_count: {
+				description: "The number of bytes of the flow."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [1500]
+					unit: "bytes"
+				}
+			}
+			protocol_identifier: {
+				description: "The IP protocol number of the flow."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [6]
+					unit: null
+				}
+			}
+			record_type: {
+				description: "The kind of record."
+				required:    true
+				type: string: {
+					enum: {
+						counters: "An sFlow counters sample, holding interface counters."
+						flow:     "A flow record, or an sFlow flow sample."
+						options:  "A NetFlow v9 or IPFIX options record, describing the exporter itself, such as its sampling interval."
+					}
+				}
+			}
+			source_ipv4_address: {
+				description: "The source address of the flow."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["10.0.0.1"]
+				}
+			}
+			source_transport_port: {
+				description: "The source port of the flow."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [54321]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["netflow"]
+				}
+			}
+			timestamp: {
+				description: "The export time of the datagram, or the time it was received for sFlow, which doesn't carry one."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		protocols: {
+			title: "Protocol detection"
+			body: """
+				The protocol of each datagram is detected from its version number, so exporters of
+				different protocols can send to the same address. NetFlow v5, [NetFlow
+				v9](\(urls.netflow_v9)), [IPFIX](\(urls.ipfix)) and [sFlow v5](\(urls.sflow)) over
+				UDP are supported.
+				"""
+		}
+		templates: {
+			title: "Templates"
+			body: """
+				NetFlow v9 and IPFIX exporters describe the layout of their records with templates,
+				which are cached per exporter address and observation domain until they're
+				withdrawn, replaced or time out. Data records for templates that haven't been
+				received yet are discarded, and reported by the
+				`component_discarded_events_total` metric.
+				"""
+		}
+		field_names: {
+			title: "Field names"
+			body: """
+				Fields are named after their [IPFIX information element](\(urls.ipfix_information_elements)),
+				which NetFlow v9 field types share their numbering with, and the same names are
+				used for NetFlow v5 records and sFlow samples. The `flow_start_sys_up_time` and
+				`flow_end_sys_up_time` fields of NetFlow v5 and v9 are converted from the uptime of
+				the exporter to timestamps.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
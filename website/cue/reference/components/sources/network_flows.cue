@@ -0,0 +1,210 @@
+package metadata
+
+components: sources: network_flows: {
+	title: "Network Flows"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.ebpf
+
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-pc-windows-msv":          false
+		}
+
+		requirements: [
+			"""
+				Vector must run as root, or with the `CAP_BPF` and `CAP_PERFMON` capabilities on
+				Linux 5.8 and later, to load the eBPF program. The source is only included in
+				builds with the `sources-network_flows` feature, which need `clang` and the libbpf
+				headers to compile the program.
+				""",
+		]
+		warnings: [
+			"""
+				Flow records are lost when connections close faster than Vector reads them.
+				Increase `perf_buffer_pages` if `component_discarded_events_total` grows.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		include_loopback: {
+			common:      false
+			description: "Whether to emit flows of connections between loopback addresses."
+			required:    false
+			type: bool: default: false
+		}
+		perf_buffer_pages: {
+			common:      false
+			description: "The size of the buffer each CPU hands flow records over to Vector with, in memory pages. Must be a power of two."
+			required:    false
+			type: uint: {
+				default: 64
+				unit:    null
+			}
+		}
+	}
+
+	output: logs: flow: {
+		description: "A closed TCP connection."
+		fields: {
+			bytes_received: {
+				description: "The number of bytes read by the local process."
+				required:    true
+				type: uint: {
+					examples: [4096]
+					unit: "bytes"
+				}
+			}
+			bytes_sent: {
+				description: "The number of bytes written by the local process."
+				required:    true
+				type: uint: {
+					examples: [512]
+					unit: "bytes"
+				}
+			}
+			destination_ip: {
+				description: "The address the connection was opened to."
+				required:    true
+				type: string: {
+					examples: ["93.184.216.34", "2001:db8::1"]
+				}
+			}
+			destination_port: {
+				description: "The port the connection was opened to."
+				required:    true
+				type: uint: {
+					examples: [443]
+					unit: null
+				}
+			}
+			direction: {
+				description: "Whether the connection was opened by a local process or accepted from a remote peer."
+				required:    true
+				type: string: {
+					enum: {
+						inbound:  "The connection was accepted from a remote peer."
+						outbound: "The connection was opened by a local process."
+					}
+				}
+			}
+			duration_ms: {
+				description: "The time between the connection being established and closed, in milliseconds."
+				required:    true
+				type: float: {
+					examples: [250.0]
+				}
+			}
+			host: fields._local_host
+			process_id: {
+				description: "The identifier of the process that opened the connection. Only set for outbound connections."
+				required:    false
+				common:      true
+				type: uint: {
+					default: null
+					examples: [4242]
+					unit: null
+				}
+			}
+			process_name: {
+				description: "The command name of the process that opened the connection. Only set for outbound connections."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["curl"]
+				}
+			}
+			protocol: {
+				description: "The transport protocol of the connection."
+				required:    true
+				type: string: {
+					examples: ["tcp"]
+				}
+			}
+			source_ip: {
+				description: "The address the connection was opened from."
+				required:    true
+				type: string: {
+					examples: ["10.0.0.5"]
+				}
+			}
+			source_port: {
+				description: "The port the connection was opened from."
+				required:    true
+				type: uint: {
+					examples: [54321]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["network_flows"]
+				}
+			}
+			timestamp: {
+				description: "The time the connection was closed."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		tracing: {
+			title: "Connection tracing"
+			body: """
+				The source loads an eBPF program that follows TCP connections through the
+				`sock/inet_sock_set_state` tracepoint, and counts the bytes applications write to
+				and read from them with kprobes on `tcp_sendmsg` and `tcp_cleanup_rbuf`. A flow
+				event is emitted when a connection closes. Connections that were already open
+				when Vector started are not reported.
+				"""
+		}
+		processes: {
+			title: "Process attribution"
+			body: """
+				Outbound connections are attributed to the process that called `connect`.
+				Inbound connections are established by the kernel before any process accepts
+				them, so they don't carry process fields.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
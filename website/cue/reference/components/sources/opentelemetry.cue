@@ -6,7 +6,7 @@ components: sources: opentelemetry: {
 	title: "OpenTelemetry"
 
 	description: """
-		Collect OpenTelemetry data over gRPC (currently, only logs are supported).
+		Collect OpenTelemetry logs and metrics over gRPC.
 		"""
 
 	classes: {
@@ -63,6 +63,124 @@ components: sources: opentelemetry: {
 				examples: ["0.0.0.0:\(_port)"]
 			}
 		}
+		http2: {
+			common:      false
+			description: "HTTP/2 settings for the gRPC server, used to keep long-lived streams alive through intermediaries that close idle connections."
+			required:    false
+			type: object: options: {
+				keepalive_interval_secs: {
+					common:      false
+					description: "The interval at which HTTP/2 PING frames are sent to connected clients. Keepalive pings are disabled if this isn't set."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+				keepalive_timeout_secs: {
+					common:      false
+					description: "How long to wait for a keepalive ping to be acknowledged before the connection is closed. Only used if `keepalive_interval_secs` is set."
+					required:    false
+					type: uint: {
+						default: 20
+						unit:    "seconds"
+					}
+				}
+				max_concurrent_streams: {
+					common:      false
+					description: "The maximum number of concurrent HTTP/2 streams a single client connection may open. Streams are not limited if this isn't set."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [100]
+						unit: null
+					}
+				}
+			}
+		}
+		metrics: {
+			common:      false
+			description: "Controls how received metrics are converted."
+			required:    false
+			type: object: options: {
+				temporality: {
+					common: true
+					description: """
+						The temporality received sums and histograms are converted to. Gauges and summaries are not
+						affected.
+						"""
+					required: false
+					type: string: {
+						default: "preserve"
+						enum: {
+							preserve:   "Keep the temporality each metric was sent with. Delta metrics are emitted as incremental metrics, and cumulative ones as absolute metrics."
+							delta:      "Convert cumulative metrics to incremental metrics. The first data point of each cumulative series is only used as a reference, and is not emitted."
+							cumulative: "Convert delta metrics to absolute metrics, by accumulating them."
+						}
+					}
+				}
+				resource_attributes: {
+					common: true
+					description: """
+						Rules selecting the resource attributes added as tags to each metric. The first matching rule
+						is used for each attribute. By default, no resource attributes are added. Data point attributes
+						are always added as tags.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							attribute: {
+								description: "The name of the attribute to select. A trailing `*` selects every attribute starting with the preceding prefix, and `*` alone selects all of them."
+								required:    true
+								type: string: examples: ["service.name", "k8s.*"]
+							}
+							tag: {
+								description: "The name of the tag the attribute is added as. Defaults to the name of the attribute. For wildcard rules, this is a prefix prepended to the name of each selected attribute."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["service"]
+								}
+							}
+						}
+					}
+				}
+				scope_attributes: {
+					common:      false
+					description: "Rules selecting the instrumentation scope attributes added as tags to each metric, in the same format as `resource_attributes`. By default, no scope attributes are added."
+					required:    false
+					type: array: {
+						default: []
+						items: type: object: options: {
+							attribute: {
+								description: "The name of the attribute to select."
+								required:    true
+								type: string: examples: ["*"]
+							}
+							tag: {
+								description: "The name of the tag the attribute is added as."
+								required:    false
+								type: string: {
+									default: null
+									examples: ["scope_"]
+								}
+							}
+						}
+					}
+				}
+				series_ttl_secs: {
+					common:      false
+					description: "The time, in seconds, after which a series that is no longer received is forgotten when converting temporality. A forgotten series starts over: its next cumulative data point is only used as a reference, and its delta data points are accumulated from zero."
+					required:    false
+					type: uint: {
+						default: 600
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 	}
 
 	outputs: [
@@ -72,6 +190,12 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
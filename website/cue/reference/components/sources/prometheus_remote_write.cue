@@ -51,10 +51,10 @@ components: sources: prometheus_remote_write: {
 	configuration: {
 		acknowledgements: configuration._source_acknowledgements
 		address: {
-			description: "The address to accept connections on. The address _must_ include a port."
+			description: "The address to accept connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
 			required:    true
 			type: string: {
-				examples: ["0.0.0.0:9090"]
+				examples: ["0.0.0.0:9090", "systemd", "systemd#3"]
 			}
 		}
 		auth: configuration._http_basic_auth
@@ -53,10 +53,11 @@ components: sources: prometheus_scrape: {
 
 	configuration: {
 		endpoints: {
-			description: "Endpoints to scrape metrics from."
-			required:    true
+			description: "Endpoints to scrape metrics from. Required unless `service_discovery` is set."
+			required:    false
 			warnings: ["You must explicitly add the path to your endpoints. Vector will _not_ automatically add `/metrics`."]
 			type: array: {
+				default: []
 				items: type: string: {
 					examples: ["http://localhost:9090/metrics"]
 				}
@@ -143,6 +144,172 @@ components: sources: prometheus_scrape: {
 				}
 			}
 		}
+		service_discovery: {
+			common: false
+			description: """
+				Mechanisms for discovering additional endpoints to scrape. Discovered targets are rewritten by
+				`relabel_configs`, and their labels that don't start with `__` are added as tags to the metrics
+				scraped from them. If a discovery round fails, the previously discovered targets are kept.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					type: {
+						description: "The discovery mechanism."
+						required:    true
+						type: string: enum: {
+							http:       "Fetch targets from an endpoint implementing Prometheus' HTTP service discovery format: a JSON array of objects with a `targets` list of `host:port` addresses and an optional `labels` map."
+							kubernetes: "List pods or endpoints from the Kubernetes API. Targets carry the same `__meta_kubernetes_*` labels as in Prometheus."
+						}
+					}
+					url: {
+						description:   "The URL to fetch targets from."
+						required:      true
+						relevant_when: "type = \"http\""
+						type: string: examples: ["http://sd.example.com/targets"]
+					}
+					auth: configuration._http_auth & {_args: {
+						password_example: "${SD_PASSWORD}"
+						username_example: "${SD_USERNAME}"
+					}}
+					role: {
+						description:   "The kind of Kubernetes object targets are discovered from."
+						required:      true
+						relevant_when: "type = \"kubernetes\""
+						type: string: enum: {
+							pod:       "One target per declared container port of each running pod, or one per pod if it declares no ports."
+							endpoints: "One target per address and port of each `Endpoints` object."
+						}
+					}
+					namespaces: {
+						description:   "The namespaces to discover targets in. Targets are discovered in all namespaces by default."
+						required:      false
+						relevant_when: "type = \"kubernetes\""
+						type: array: {
+							default: []
+							items: type: string: examples: ["monitoring"]
+						}
+					}
+					label_selector: {
+						description:   "A Kubernetes label selector restricting the discovered objects."
+						required:      false
+						relevant_when: "type = \"kubernetes\""
+						type: string: {
+							default: null
+							examples: ["app.kubernetes.io/part-of=web"]
+						}
+					}
+					field_selector: {
+						description:   "A Kubernetes field selector restricting the discovered objects."
+						required:      false
+						relevant_when: "type = \"kubernetes\""
+						type: string: {
+							default: null
+							examples: ["spec.nodeName=node-1"]
+						}
+					}
+					refresh_interval_secs: {
+						description: "The interval between discovery rounds. Defaults to 60 seconds for `http` and 30 seconds for `kubernetes`."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+		relabel_configs: {
+			common: false
+			description: """
+				Rules rewriting the labels of discovered targets, in the format of Prometheus' `relabel_configs`. The
+				rules are applied in order, and can drop targets or set the reserved `__address__`, `__scheme__`,
+				`__metrics_path__`, and `__param_<name>` labels used to build the scraped endpoint.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: options: {
+					source_labels: {
+						description: "The labels whose values are joined with `separator` and matched against `regex`."
+						required:    false
+						type: array: {
+							default: []
+							items: type: string: examples: ["__meta_kubernetes_pod_annotation_prometheus_io_scrape"]
+						}
+					}
+					separator: {
+						description: "The separator placed between the values of `source_labels`."
+						required:    false
+						type: string: default: ";"
+					}
+					regex: {
+						description: "The regular expression matched against the joined values, or against label names for the `label*` actions. It is anchored at both ends."
+						required:    false
+						type: string: default: "(.*)"
+					}
+					target_label: {
+						description: "The label written by the `replace` action."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["__address__"]
+						}
+					}
+					replacement: {
+						description: "The value written by the `replace` action, or the label name written by the `labelmap` action. Capture groups from `regex` can be referenced as `$1`."
+						required:    false
+						type: string: default: "$1"
+					}
+					action: {
+						description: "The action performed by the rule."
+						required:    false
+						type: string: {
+							default: "replace"
+							enum: {
+								replace:   "Set `target_label` to `replacement` if `regex` matches."
+								keep:      "Drop the target unless `regex` matches."
+								drop:      "Drop the target if `regex` matches."
+								labelmap:  "Copy every label whose name matches `regex` to the name given by `replacement`."
+								labeldrop: "Remove every label whose name matches `regex`."
+								labelkeep: "Remove every label whose name doesn't match `regex`."
+							}
+						}
+					}
+				}
+			}
+		}
+		shard_count: {
+			common: false
+			description: """
+				The number of Vector replicas the endpoints are split across. Each endpoint is assigned to exactly one
+				shard by a stable hash of its configured value, so replicas sharing the same `endpoints` list scrape
+				disjoint subsets of it. All endpoints are scraped if this isn't set.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [3]
+				unit: null
+			}
+		}
+		shard_index: {
+			common: false
+			description: """
+				The shard scraped by this replica, from `0` to `shard_count - 1`. If `shard_count` is set and this
+				isn't, the index is taken from the ordinal suffix of the replica's name, as assigned to StatefulSet
+				pods by Kubernetes (for example, `vector-2` is shard `2`). The name is read from the
+				`VECTOR_SELF_POD_NAME` environment variable, falling back to the hostname.
+				"""
+			required:      false
+			relevant_when: "shard_count is set"
+			type: uint: {
+				default: null
+				examples: [0]
+				unit: null
+			}
+		}
 		auth: configuration._http_auth & {_args: {
 			password_example: "${PROMETHEUS_PASSWORD}"
 			username_example: "${PROMETHEUS_USERNAME}"
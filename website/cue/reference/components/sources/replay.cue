@@ -0,0 +1,116 @@
+package metadata
+
+components: sources: replay: {
+	title: "Replay"
+
+	description: """
+		Replays events recorded by the `record` sink, so that production traffic can be
+		replayed against another configuration.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		generate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		path: {
+			description: "The recording to replay, as written by the [`record` sink](\(urls.vector_sinks)/record)."
+			required:    true
+			type: string: {
+				examples: ["/var/lib/vector/recording.bin"]
+				syntax: "file_system_path"
+			}
+		}
+		speed: {
+			common: true
+			description: """
+				The speed at which to replay the recording, relative to the pace it was recorded
+				at. For example, `2.0` replays the recording twice as fast as it was recorded. To
+				replay the recording as fast as possible, set `speed` to `0.0`. Must be a finite
+				number that is not negative.
+				"""
+			required: false
+			type: float: {
+				default: 1.0
+				examples: [0.0, 0.5, 10.0]
+			}
+		}
+		start: {
+			common:      false
+			description: "Only replay events recorded at or after this time, as an RFC 3339 timestamp."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["2022-05-17T14:00:00Z"]
+			}
+		}
+		end: {
+			common:      false
+			description: "Only replay events recorded before this time, as an RFC 3339 timestamp."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["2022-05-17T15:00:00Z"]
+			}
+		}
+	}
+
+	output: {
+		logs: event: {
+			description: "A recorded event"
+			fields: {
+				"*": {
+					description: "Events are replayed as they were recorded. The `replay` source will not modify or add fields."
+					required:    true
+					type: "*": {}
+				}
+			}
+		}
+		metrics: {
+			counter:      output._passthrough_counter
+			distribution: output._passthrough_distribution
+			gauge:        output._passthrough_gauge
+			histogram:    output._passthrough_histogram
+			set:          output._passthrough_set
+		}
+	}
+
+	how_it_works: {
+		pacing: {
+			title: "Pacing"
+			body: """
+				Events are replayed with the same spacing they were recorded with, divided by
+				`speed`. The pacing starts from the first event in the `start`/`end` window, and
+				the source stops once it reaches the end of the recording or of the window.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
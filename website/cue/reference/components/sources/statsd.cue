@@ -103,6 +103,36 @@ components: sources: statsd: {
 			}
 		}
 
+		receive_buffer_max_bytes: {
+			common:        false
+			description:   """
+				The maximum size that the receive buffer is grown to when the kernel drops datagrams. When set,
+				the drop counter of each socket is checked every second, and the receive buffer is doubled, up to
+				this size, whenever datagrams were dropped. The kernel caps the receive buffer at
+				`net.core.rmem_max`. Only supported on Linux.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: null
+				examples: [8388608]
+				unit: "bytes"
+			}
+		}
+		workers: {
+			common:        false
+			description:   """
+				The number of sockets bound to the address, each read by its own worker. When greater than one,
+				`SO_REUSEPORT` is set on the sockets so that the kernel spreads incoming datagrams across them.
+				Only supported on Unix.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
 	}
 
 	output: metrics: {
@@ -138,5 +168,7 @@ components: sources: statsd: {
 		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		udp_socket_datagrams_dropped_total:   components.sources.internal_metrics.output.metrics.udp_socket_datagrams_dropped_total
+		udp_socket_receive_buffer_bytes:      components.sources.internal_metrics.output.metrics.udp_socket_receive_buffer_bytes
 	}
 }
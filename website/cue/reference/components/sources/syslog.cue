@@ -89,6 +89,12 @@ components: sources: syslog: {
 				}
 			}
 		}
+		passthrough: {
+			common:      false
+			description: "Forward the messages as opaque bytes, without parsing them. The whole message is stored in the `message` field, and only the host, source IP, source type, and reception timestamp are added to each event. Sinks encoding events with the `raw_message` codec, such as the `kafka` sink, send the received bytes as-is, which makes relaying messages much cheaper."
+			required:    false
+			type: bool: default: false
+		}
 		path: {
 			description:   "The unix socket path. *This should be an absolute path*."
 			relevant_when: "mode = `unix`"
@@ -108,6 +114,36 @@ components: sources: syslog: {
 				unit:    "concurrency"
 			}
 		}
+		receive_buffer_max_bytes: {
+			common:        false
+			description:   """
+				The maximum size that the receive buffer is grown to when the kernel drops datagrams. When set,
+				the drop counter of each socket is checked every second, and the receive buffer is doubled, up to
+				this size, whenever datagrams were dropped. The kernel caps the receive buffer at
+				`net.core.rmem_max`. Only supported on Linux.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: null
+				examples: [8388608]
+				unit: "bytes"
+			}
+		}
+		workers: {
+			common:        false
+			description:   """
+				The number of sockets bound to the address, each read by its own worker. When greater than one,
+				`SO_REUSEPORT` is set on the sockets so that the kernel spreads incoming datagrams across them.
+				Only supported on Unix.
+				"""
+			relevant_when: "mode = `udp`"
+			required:      false
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -258,12 +294,14 @@ components: sources: syslog: {
 	}
 
 	telemetry: metrics: {
-		events_in_total:                 components.sources.internal_metrics.output.metrics.events_in_total
-		connection_read_errors_total:    components.sources.internal_metrics.output.metrics.connection_read_errors_total
-		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total:          components.sources.internal_metrics.output.metrics.processed_events_total
-		component_received_bytes_total:  components.sources.internal_metrics.output.metrics.component_received_bytes_total
-		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
-		utf8_convert_errors_total:       components.sources.internal_metrics.output.metrics.utf8_convert_errors_total
+		events_in_total:                    components.sources.internal_metrics.output.metrics.events_in_total
+		connection_read_errors_total:       components.sources.internal_metrics.output.metrics.connection_read_errors_total
+		processed_bytes_total:              components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:             components.sources.internal_metrics.output.metrics.processed_events_total
+		component_received_bytes_total:     components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:    components.sources.internal_metrics.output.metrics.component_received_events_total
+		utf8_convert_errors_total:          components.sources.internal_metrics.output.metrics.utf8_convert_errors_total
+		udp_socket_datagrams_dropped_total: components.sources.internal_metrics.output.metrics.udp_socket_datagrams_dropped_total
+		udp_socket_receive_buffer_bytes:    components.sources.internal_metrics.output.metrics.udp_socket_receive_buffer_bytes
 	}
 }
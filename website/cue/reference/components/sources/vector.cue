@@ -63,6 +63,59 @@ components: sources: vector: {
 				examples: ["0.0.0.0:\(_port)"]
 			}
 		}
+		http2: {
+			common:      false
+			description: "HTTP/2 settings for the gRPC server, used to keep long-lived streams alive through intermediaries that close idle connections."
+			required:    false
+			relevant_when: "version = \"2\""
+			type: object: options: {
+				keepalive_interval_secs: {
+					common:      false
+					description: "The interval at which HTTP/2 PING frames are sent to connected clients. Keepalive pings are disabled if this isn't set."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [30]
+						unit: "seconds"
+					}
+				}
+				keepalive_timeout_secs: {
+					common:      false
+					description: "How long to wait for a keepalive ping to be acknowledged before the connection is closed. Only used if `keepalive_interval_secs` is set."
+					required:    false
+					type: uint: {
+						default: 20
+						unit:    "seconds"
+					}
+				}
+				max_concurrent_streams: {
+					common:      false
+					description: "The maximum number of concurrent HTTP/2 streams a single client connection may open. Streams are not limited if this isn't set."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [100]
+						unit: null
+					}
+				}
+			}
+		}
+		max_in_flight_requests_per_stream: {
+			common:        false
+			description:   """
+				The maximum number of requests from a single upstream stream that are processed concurrently.
+				Upstream `vector` sinks identify their stream with their component ID, so this limit lets a slow
+				or noisy upstream be backpressured without affecting the others. Must be greater than zero.
+				Requests are not limited per stream if this isn't set.
+				"""
+			required:      false
+			relevant_when: "version = \"2\""
+			type: uint: {
+				default: null
+				examples: [4]
+				unit: "requests"
+			}
+		}
 		shutdown_timeout_secs: {
 			common:      false
 			description: "The timeout before a connection is forcefully closed during shutdown."
@@ -108,6 +161,18 @@ components: sources: vector: {
 		}
 	}
 
+	how_it_works: {
+		event_format: {
+			title: "Event format"
+			body: """
+				Events are sent between Vector instances in Vector's native protobuf encoding. The `vector`
+				sink sends events in version 2 of the encoding, through a call that `vector` sources predating
+				it reject. When the downstream source rejects it, the sink falls back to the original version
+				of the encoding and re-sends the events, so that both ends can be upgraded independently.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		component_discarded_events_total:     components.sources.internal_metrics.output.metrics.component_discarded_events_total
 		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
@@ -116,5 +181,7 @@ components: sources: vector: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
 		protobuf_decode_errors_total:         components.sources.internal_metrics.output.metrics.protobuf_decode_errors_total
+		stream_received_events_total:         components.sources.internal_metrics.output.metrics.stream_received_events_total
+		stream_received_event_bytes_total:    components.sources.internal_metrics.output.metrics.stream_received_event_bytes_total
 	}
 }
@@ -0,0 +1,297 @@
+package metadata
+
+components: sources: windows_event_log: {
+	title: "Windows Event Log"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: true
+			from: {
+				service: services.windows_event_log
+
+				interface: ffi: {}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      false
+			"aarch64-unknown-linux-musl":     false
+			"armv7-unknown-linux-gnueabihf":  false
+			"armv7-unknown-linux-musleabihf": false
+			"x86_64-apple-darwin":            false
+			"x86_64-unknown-linux-gnu":       false
+			"x86_64-unknown-linux-musl":      false
+		}
+
+		requirements: []
+		warnings: [
+			"""
+				Reading the `Security` channel requires Vector to run as a user with the
+				"Manage auditing and security log" privilege, such as `LocalSystem`.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		batch_size: {
+			common:      false
+			description: "The maximum number of events read from the subscription at once. A checkpoint is set after each batch."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    "events"
+			}
+		}
+		channels: {
+			common:      true
+			description: "The event log channels to subscribe to."
+			required:    false
+			type: array: {
+				default: ["Application", "System"]
+				items: type: string: {
+					examples: ["Security", "Microsoft-Windows-Sysmon/Operational"]
+				}
+			}
+		}
+		data_dir: {
+			common:      false
+			description: "The directory used to persist the subscription bookmark. By default, the [global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used. Please make sure the Vector project has write permissions to this dir."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["C:\\ProgramData\\vector"]
+			}
+		}
+		query: {
+			common:      true
+			description: "An [XPath query](\(urls.windows_event_log_xpath)) selecting the events to read from each channel."
+			required:    false
+			type: string: {
+				default: "*"
+				examples: ["*[System[(Level=1 or Level=2)]]", "*[System[EventID=4624]]"]
+			}
+		}
+		read_existing_events: {
+			common:      true
+			description: "Whether to read the events already present in the channels when no checkpoint exists. By default, only events published after Vector starts are read."
+			required:    false
+			type: bool: default: false
+		}
+		render_message: {
+			common:      false
+			description: "Whether to render the event message from the message templates of the publisher. Events whose message can't be rendered are emitted without one."
+			required:    false
+			type: bool: default: true
+		}
+	}
+
+	output: logs: event: {
+		description: "A Windows event."
+		fields: {
+			activity_id: {
+				description: "The activity identifier of the event, used to correlate related events."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["{4E1E4B1A-7E54-0000-4D4B-1E4E547ED801}"]
+				}
+			}
+			channel: {
+				description: "The channel the event was published to."
+				required:    true
+				type: string: {
+					examples: ["Security"]
+				}
+			}
+			event_data: {
+				description: "The event data. Named data is decoded into an object, anonymous data into an array of strings."
+				required:    false
+				common:      true
+				type: object: {
+					examples: [{"TargetUserName": "Administrator", "LogonType": "2"}]
+					options: {}
+				}
+			}
+			event_id: {
+				description: "The identifier of the event, as defined by the provider."
+				required:    true
+				type: uint: {
+					examples: [4624]
+					unit: null
+				}
+			}
+			host: {
+				description: "The name of the computer that published the event."
+				required:    true
+				type: string: {
+					examples: ["host.example.com"]
+				}
+			}
+			keywords: {
+				description: "The keywords bitmask of the event, as a hexadecimal string."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["0x8020000000000000"]
+				}
+			}
+			level: {
+				description: "The severity level of the event: 1 for critical, 2 for error, 3 for warning, 4 for information and 5 for verbose."
+				required:    true
+				type: uint: {
+					examples: [4]
+					unit: null
+				}
+			}
+			message: {
+				description: "The event message, rendered from the message templates of the provider."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["An account was successfully logged on."]
+				}
+			}
+			opcode: {
+				description: "The opcode of the event."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [0]
+					unit: null
+				}
+			}
+			process_id: {
+				description: "The identifier of the process that published the event."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [636]
+					unit: null
+				}
+			}
+			provider_guid: {
+				description: "The GUID of the provider that published the event."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["{54849625-5478-4994-A5BA-3E3B0328C30D}"]
+				}
+			}
+			provider_name: {
+				description: "The name of the provider that published the event."
+				required:    true
+				type: string: {
+					examples: ["Microsoft-Windows-Security-Auditing"]
+				}
+			}
+			record_id: {
+				description: "The number of the event record in the channel."
+				required:    true
+				type: uint: {
+					examples: [12345]
+					unit: null
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["windows_event_log"]
+				}
+			}
+			task: {
+				description: "The task of the event, as defined by the provider."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [12544]
+					unit: null
+				}
+			}
+			thread_id: {
+				description: "The identifier of the thread that published the event."
+				required:    false
+				common:      false
+				type: uint: {
+					default: null
+					examples: [700]
+					unit: null
+				}
+			}
+			timestamp: {
+				description: "The time the event was published."
+				required:    true
+				type: timestamp: {}
+			}
+			user_data: {
+				description: "The user data of events that don't use event data, decoded into an object keyed by element name."
+				required:    false
+				common:      false
+				type: object: {
+					examples: [{"SubjectUserName": "admin"}]
+					options: {}
+				}
+			}
+			user_id: {
+				description: "The security identifier of the user the event was published for."
+				required:    false
+				common:      false
+				type: string: {
+					default: null
+					examples: ["S-1-5-18"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		subscription: {
+			title: "Subscription"
+			body: """
+				Vector subscribes to the configured channels through the native Windows Event Log
+				API, so no additional agent has to run on the host. Each event is rendered as XML,
+				and its `System` section is flattened into top-level fields.
+				"""
+		}
+		checkpointing: {
+			title: "Checkpointing"
+			body: """
+				After each batch of events has been sent, Vector saves the subscription bookmark
+				to the `checkpoint.xml` file in its data directory. On restart, reading resumes
+				after the last event that was sent.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
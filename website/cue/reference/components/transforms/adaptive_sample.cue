@@ -0,0 +1,126 @@
+package metadata
+
+components: transforms: adaptive_sample: {
+	title: "Adaptive Sample"
+
+	description: """
+		Samples events at a rate adjusted automatically to forward a target volume of events or bytes per second.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		exclude: {
+			common: true
+			description: """
+				The set of logical conditions to exclude events from sampling.
+				"""
+			required: false
+			type: condition: {}
+		}
+		key_field: {
+			common: false
+			description: """
+				A [template string](/docs/reference/configuration/template-syntax/) that will be rendered to group
+				events into buckets, each sampled separately to meet the targets. If left unspecified, or if the event
+				doesn't have `key_field`, the event will be sampled with all the other events without key.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["{{ service }}"]
+				syntax: "template"
+			}
+		}
+		rate_field: {
+			common: false
+			description: """
+				The name of the field in which the effective sampling rate of each forwarded event is stored, expressed
+				as `N` for `1/N` of the events being forwarded.
+				"""
+			required: false
+			type: string: {
+				default: "sample_rate"
+				syntax:  "literal"
+			}
+		}
+		target_bytes_per_sec: {
+			common: false
+			description: """
+				The number of bytes per second to forward for a given bucket. The size of an event is its estimated
+				size in memory.
+				"""
+			required: false
+			type: float: {
+				default: null
+				examples: [1048576.0]
+				unit: "bytes"
+			}
+		}
+		target_events_per_sec: {
+			common: true
+			description: """
+				The number of events per second to forward for a given bucket. At least one of
+				`target_events_per_sec` and `target_bytes_per_sec` must be set.
+				"""
+			required: false
+			type: float: {
+				default: null
+				examples: [100.0]
+				unit: null
+			}
+		}
+		window_secs: {
+			common: false
+			description: """
+				The time window over which the volume of a bucket is measured. Must be at most
+				`4294967295`.
+				"""
+			required: false
+			type: float: {
+				default: 10.0
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+
+	how_it_works: {
+		adaptive_rate: {
+			title: "Adaptive Rate"
+			body:  """
+				At the end of each `window_secs`, the sampling rate of each bucket is set from the volume it received
+				during the window, so that the forwarded volume meets the targets. Events are never sampled at a rate
+				above the received volume: quiet buckets forward all their events. All events are forwarded during the
+				first window of a bucket, before its volume is known.
+
+				Each forwarded event is annotated with the rate at which it was sampled in `rate_field`, so that
+				downstream counts can be extrapolated by multiplying by the rate.
+				"""
+		}
+	}
+}
@@ -39,6 +39,20 @@ components: transforms: aggregate: {
 				unit:    "milliseconds"
 			}
 		}
+		set_precision: {
+			common: false
+			description: """
+				The precision of the HyperLogLog sets are aggregated into, between 4 and 16. When set, sets are
+				aggregated approximately, bounding their memory to `2^set_precision` bytes per series at the cost
+				of a standard error of `1.04 / sqrt(2^set_precision)` on their number of unique values.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [12]
+				unit: null
+			}
+		}
 	}
 
 	input: {
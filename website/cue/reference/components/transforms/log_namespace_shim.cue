@@ -0,0 +1,94 @@
+package metadata
+
+components: transforms: log_namespace_shim: {
+	title: "Log Namespace Shim"
+
+	description: """
+		Converts logs from sources that don't support log namespacing to the Vector namespace layout, moving the
+		metadata set by the source out of the event and into the event metadata.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		source_metadata_fields: {
+			description: """
+				Fields set by the source, moved to the source metadata under the source type, such as `host`.
+				Fields are paths, so `kubernetes.pod_name` is moved to `%<source type>.kubernetes.pod_name`. The
+				source type and timestamp fields of the global log schema are always moved to the Vector metadata, as
+				`%vector.source_type` and `%vector.ingest_timestamp`.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["host", "file", "kubernetes.pod_name"]
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	examples: [
+		{
+			title: "Convert file logs"
+			configuration: {
+				source_metadata_fields: ["host", "file"]
+			}
+			input: log: {
+				message:     "Hello world"
+				host:        "my-host.local"
+				file:        "/var/log/app.log"
+				source_type: "file"
+			}
+			output: log: "Hello world"
+		},
+	]
+
+	how_it_works: {
+		layout: {
+			title: "Namespaced layout"
+			body: """
+				Logs with only a message left once the metadata is moved use the message as the whole event, as
+				sources supporting log namespacing do. Fields emptied by moving the metadata out of them are
+				removed. Metrics and traces pass through unchanged.
+				"""
+		}
+		legacy_sources: {
+			title: "Sources without log namespacing"
+			body: """
+				This transform converts the output of sources that still use the legacy layout, it doesn't change
+				the sources themselves. Those sources are listed in a warning when the `schema.log_namespace`
+				global option is enabled, and their output can be routed through this transform until they
+				support log namespacing.
+				"""
+		}
+	}
+}
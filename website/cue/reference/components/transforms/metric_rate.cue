@@ -0,0 +1,113 @@
+package metadata
+
+components: transforms: metric_rate: {
+	title: "Metric Rate"
+
+	description: """
+		Converts counters into per-second rates, sent as `absolute` `gauge` metrics, so that
+		backends without rate functions receive ready-made rates.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		drop_non_counters: {
+			common:      false
+			description: "Whether metrics other than counters are dropped, instead of being passed through."
+			required:    false
+			type: bool: default: false
+		}
+		series_ttl_secs: {
+			common:      false
+			description: "The time after which the state of a series which isn't received anymore is dropped."
+			required:    false
+			type: uint: {
+				default: 600
+				unit:    "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Rate of an absolute counter"
+			input: [
+				{
+					metric: {
+						kind:      "absolute"
+						name:      "requests_total"
+						timestamp: "2021-07-12T07:58:40Z"
+						counter: {
+							value: 100.0
+						}
+					}
+				},
+				{
+					metric: {
+						kind:      "absolute"
+						name:      "requests_total"
+						timestamp: "2021-07-12T07:58:50Z"
+						counter: {
+							value: 130.0
+						}
+					}
+				},
+			]
+			configuration: {}
+			output: [
+				{
+					metric: {
+						kind:      "absolute"
+						name:      "requests_total"
+						timestamp: "2021-07-12T07:58:50Z"
+						gauge: {
+							value: 3.0
+						}
+					}
+				},
+			]
+		},
+	]
+
+	how_it_works: {
+		rate_computation: {
+			title: "Rate Computation"
+			body: """
+				An `incremental` counter with a known interval, as sent by sources collecting metrics
+				periodically, is divided by its interval. Otherwise, the rate is computed from the
+				previous counter of the same series: the increase of `absolute` counters, or the value
+				of `incremental` counters, is divided by the time elapsed since the previous counter,
+				from their timestamps. The first counter of such a series only initializes its state
+				and isn't sent, and a decrease of an `absolute` counter is handled as a reset.
+				"""
+		}
+	}
+}
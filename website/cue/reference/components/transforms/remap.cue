@@ -104,6 +104,21 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		batch: {
+			common:   false
+			required: false
+			description: """
+				Run the program once per batch of incoming log events, with `.` set to an
+				array of the events, to compute aggregates or remove duplicates across
+				events. Each element of the returned array is forwarded as a separate event.
+				The finalizers and secrets of all the events in the batch are merged, the rest
+				of the metadata is taken from the first event, and every forwarded event
+				carries the merged metadata, so the events of the batch are acknowledged once
+				all the forwarded events are. Metrics and traces are still processed one event
+				at a time.
+				"""
+			type: bool: default: false
+		}
 	}
 
 	input: {
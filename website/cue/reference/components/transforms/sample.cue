@@ -28,7 +28,7 @@ components: transforms: sample: {
 		key_field: {
 			common: false
 			description: """
-				The name of the log field whose value will be hashed to determine if the event should be passed.
+				The name of the log or trace field whose value will be hashed to determine if the event should be passed.
 
 				Consistently samples the same events. Actual rate of sampling may differ from the configured one if
 				values in the field are not uniformly distributed. If left unspecified, or if the event doesn't have
@@ -64,7 +64,7 @@ components: transforms: sample: {
 	input: {
 		logs:    true
 		metrics: null
-		traces:  false
+		traces:  true
 	}
 
 	telemetry: metrics: {
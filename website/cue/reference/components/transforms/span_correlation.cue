@@ -0,0 +1,94 @@
+package metadata
+
+components: transforms: span_correlation: {
+	title: "Span Correlation"
+
+	description: """
+		Enriches logs with the fields of the span they were emitted in, by matching their trace and span IDs with
+		the spans of the traces flowing through the transform.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		enrich: {
+			from: service: {
+				name:     "Traces"
+				url:      urls.vector_data_model
+				versions: null
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		trace_id_field: {
+			common:      true
+			description: "The log field holding the ID of the trace the log was emitted in."
+			required:    false
+			type: string: {
+				default: "trace_id"
+				examples: ["dd.trace_id"]
+			}
+		}
+		span_id_field: {
+			common: true
+			description: """
+				The log field holding the ID of the span the log was emitted in. Logs with a trace ID but without a
+				span ID are matched with the root span of their trace.
+				"""
+			required: false
+			type: string: {
+				default: "span_id"
+				examples: ["dd.span_id"]
+			}
+		}
+		target_field: {
+			common: false
+			description: """
+				The log field the fields of the matched span are inserted into. The `service`, `operation`,
+				`resource` and `duration` fields of the span are inserted when present.
+				"""
+			required: false
+			type: string: {
+				default: "span"
+			}
+		}
+		cache_size: {
+			common:      false
+			description: "The number of recent spans remembered for matching logs."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		ordering: {
+			title: "Ordering of logs and traces"
+			body: """
+				Logs are only enriched with the spans received before them. As tracing libraries usually send spans
+				once they are finished, logs emitted while a span is in progress may reach the transform before the
+				span, and are then forwarded without being enriched.
+				"""
+		}
+	}
+}
@@ -25,6 +25,32 @@ components: transforms: throttle: {
 	}
 
 	configuration: {
+		burst: {
+			common: false
+			description: """
+				The number of events allowed at once for a given bucket, before being limited to the `threshold`
+				rate. Defaults to `threshold`.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [10, 1000]
+				unit: null
+			}
+		}
+		burst_bytes: {
+			common: false
+			description: """
+				The number of bytes allowed at once for a given bucket, before being limited to the `threshold_bytes`
+				rate. Defaults to `threshold_bytes`. Events larger than this are always throttled.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [1048576]
+				unit: "bytes"
+			}
+		}
 		exclude: {
 			common: true
 			description: """
@@ -50,6 +76,15 @@ components: transforms: throttle: {
 				syntax: "template"
 			}
 		}
+		reroute_throttled: {
+			common: false
+			description: """
+				Send throttled events to the `throttled` output instead of dropping them, to route them elsewhere
+				such as to cheaper storage.
+				"""
+			required: false
+			type: bool: default: false
+		}
 		threshold: {
 			description: """
 				The number of events allowed for a given bucket per configured `window_secs`.
@@ -62,6 +97,19 @@ components: transforms: throttle: {
 				unit: null
 			}
 		}
+		threshold_bytes: {
+			common: false
+			description: """
+				The number of bytes allowed for a given bucket per configured `window_secs`, in addition to the
+				`threshold` on the number of events. The size of an event is its estimated size in memory.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				examples: [1048576, 104857600]
+				unit: "bytes"
+			}
+		}
 		window_secs: {
 			description: """
 				The time frame in which the configured `threshold` is applied.
@@ -77,7 +125,7 @@ components: transforms: throttle: {
 	input: {
 		logs:    true
 		metrics: null
-		traces:  false
+		traces:  true
 	}
 
 	telemetry: metrics: {
@@ -144,7 +192,8 @@ components: transforms: throttle: {
 						pass through a rate limiter. Each event passing through the transform consumes an available cell,
 						if there is no available cell the event will be rate limited.
 
-						A rate limiter is created with a maximum number of cells equal to the `threshold`, and cells replenish
+						A rate limiter is created with a maximum number of cells equal to the `burst`, which defaults to
+						the `threshold`, and cells replenish
 						at a rate of `window_secs` divided by `threshold`. For example, a `window_secs` of 60 with a `threshold` of 10
 						replenishes a cell every 6 seconds and allows a burst of up to 10 events.
 						"""
@@ -155,7 +204,12 @@ components: transforms: throttle: {
 						The rate limiter will allow up to `threshold` number of events through and drop any further events
 						for that particular bucket when the rate limiter is at capacity. Any event passed when the rate
 						limiter is at capacity will be discarded and tracked by an `events_discarded_total` metric tagged
-						by the bucket's `key`.
+						by the bucket's `key`, unless `reroute_throttled` is set, in which case it's sent to the
+						`throttled` output.
+
+						When `threshold_bytes` is set, events are also rate limited on their size, with cells
+						representing bytes instead of events. An event must be within both limits to be passed, and
+						an event throttled by one of them doesn't use up the other.
 						"""
 				},
 			]
@@ -0,0 +1,94 @@
+package metadata
+
+components: transforms: top_k: {
+	title: "Top K"
+
+	description: """
+		Keeps, per metric name, only the K series with the highest values over an interval, and
+		rolls the remaining series up into an `_other` series. This tames unbounded cardinality
+		before expensive sinks, while keeping the totals of the metrics.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	configuration: {
+		k: {
+			common:      true
+			description: "The number of series kept per metric name."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    null
+			}
+		}
+		interval_ms: {
+			common: true
+			description: """
+				The interval between flushes, in milliseconds. Must be at least 1. Over this period, metrics of
+				the same series are aggregated, and the series are ranked by their aggregated value.
+				"""
+			required: false
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	how_it_works: {
+		ranking: {
+			title: "Ranking"
+			body: """
+				Over each interval, metrics are aggregated like with the `aggregate` transform: `incremental`
+				metrics of the same series are added, and newer `absolute` metrics replace older ones. The
+				series of each metric name are then ranked by their value: the value of counters and gauges,
+				the number of values of sets, and the sum of the samples of distributions, histograms and
+				summaries.
+				"""
+		}
+
+		rollup: {
+			title: "Rollup"
+			body: """
+				The series beyond the top K of a metric name are added into a single series of the same
+				name, whose tags are the tags of the rolled up series, all set to `_other`. For example,
+				with `k = 2`, the `requests` counters of the hosts `a`, `b`, `c` and `d`, with values 1, 6,
+				3 and 2, are sent as the `requests` counters of the hosts `b` and `c`, and the `requests`
+				counter of the host `_other` with a value of 3.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		top_k_failed_updates:         components.sources.internal_metrics.output.metrics.top_k_failed_updates
+		top_k_rolled_up_series_total: components.sources.internal_metrics.output.metrics.top_k_rolled_up_series_total
+	}
+}
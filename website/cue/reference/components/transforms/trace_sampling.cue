@@ -0,0 +1,149 @@
+package metadata
+
+components: transforms: trace_sampling: {
+	title: "Trace Sampling"
+
+	description: """
+		Samples whole traces once their spans have been received, keeping the traces matching a set of policies,
+		such as traces with errors or slow spans.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		decision_wait_ms: {
+			common: true
+			description: """
+				The time to wait after the first span of a trace is received before deciding whether to sample it.
+				Spans of the trace received within this window are released or dropped together. Spans received after
+				the decision follow the decision taken for their trace, as long as it is still remembered.
+				"""
+			required: false
+			type: uint: {
+				default: 30000
+				unit:    "milliseconds"
+			}
+		}
+		flush_period_ms: {
+			common:      false
+			description: "Controls the frequency that Vector checks for traces ready for a decision. Must be non-zero."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		max_traces: {
+			common: false
+			description: """
+				The maximum number of traces awaiting a decision. When exceeded, the decision for the oldest trace is
+				taken early. This is also the number of decisions remembered for late spans.
+				"""
+			required: false
+			type: uint: {
+				default: 50000
+				unit:    null
+			}
+		}
+		policies: {
+			common: true
+			description: """
+				The policies deciding whether a trace is sampled. A trace is sampled if any of the policies samples it.
+				When no policies are specified, all traces are sampled.
+				"""
+			required: false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						type: {
+							description: "The policy type."
+							required:    true
+							type: string: {
+								enum: {
+									error:         "Samples traces with at least one span with a non-zero `error` field."
+									latency:       "Samples traces with at least one span lasting at least `threshold_ms`."
+									probabilistic: "Samples a proportion of traces, optionally at a different rate for each service."
+								}
+							}
+						}
+						threshold_ms: {
+							description: """
+								The minimum duration of a span sampling its trace. The duration of a span is read from its
+								`duration` field, in nanoseconds.
+								"""
+							required:      true
+							relevant_when: #"type = "latency""#
+							type: uint: {
+								examples: [500]
+								unit: "milliseconds"
+							}
+						}
+						rate: {
+							description: """
+								The proportion of traces sampled, between `0.0` and `1.0`. The decision is made from a
+								hash of the trace ID, so that all the instances of the transform take the same decision
+								for a trace.
+								"""
+							required:      true
+							relevant_when: #"type = "probabilistic""#
+							type: float: examples: [0.1]
+						}
+						service_rates: {
+							common: false
+							description: """
+								The proportion of traces sampled for specific services, overriding `rate`. The service
+								of a trace is the `service` field of its root span, or of its first span if the root
+								span wasn't received.
+								"""
+							required:      false
+							relevant_when: #"type = "probabilistic""#
+							type: object: {
+								examples: [{checkout: 1.0}]
+								options: {}
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		buffering: {
+			title: "Buffering spans"
+			body: """
+				Spans are grouped by the `trace_id` field of the trace events, or of their spans, and buffered until
+				`decision_wait_ms` has elapsed since the first span of the trace was received. The decision is then
+				taken on all the spans of the trace, as with the tail sampling processor of the OpenTelemetry
+				collector. Trace events without a trace ID are forwarded immediately.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
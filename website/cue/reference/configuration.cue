@@ -22,6 +22,86 @@ configuration: {
 			}
 		}
 
+		audit: {
+			common: false
+			description: """
+				Records every load of the configuration, at startup or on reload, with what triggered it, the
+				components it added, changed or removed, and whether it was applied, rejected, or rolled back.
+				The records are always emitted as internal logs and counted by the `config_changes_total` internal
+				metric. With this option, they are also appended to a file, one JSON object per line, as evidence
+				of the changes made to the pipelines. Changing this option requires a restart.
+				"""
+			required: false
+			type: object: options: {
+				path: {
+					description: "The file the audit records are appended to."
+					required:    true
+					type: string: {
+						examples: ["/var/log/vector/audit.log"]
+					}
+				}
+			}
+		}
+
+		checkpoint_store: {
+			common: false
+			description: """
+				Where sources persist their checkpoints, the position they reached in their input, so that they
+				resume from it after a restart. By default, checkpoints are written to files in the data directory
+				of each source. The `file`, `journald`, `aws_cloudwatch_logs`, and `windows_event_log` sources use
+				this store.
+				"""
+			required: false
+			type: object: options: {
+				type: {
+					description: "The type of checkpoint store."
+					required:    false
+					type: string: {
+						default: "file"
+						enum: {
+							file:                  "One file per checkpoint, in the data directory of each source."
+							sqlite:                "A SQLite database shared by all sources."
+							kubernetes_config_map: "A Kubernetes ConfigMap shared by all sources, so that checkpoints outlive the pod Vector runs in."
+						}
+					}
+				}
+				path: {
+					description:   "The path of the SQLite database, created if it doesn't exist."
+					relevant_when: "type = \"sqlite\""
+					required:      true
+					type: string: examples: ["/var/lib/vector/checkpoints.db"]
+				}
+				name: {
+					description:   "The name of the ConfigMap, created if it doesn't exist."
+					relevant_when: "type = \"kubernetes_config_map\""
+					required:      true
+					type: string: examples: ["vector-checkpoints"]
+				}
+				namespace: {
+					description:   "The namespace of the ConfigMap. Defaults to the namespace Vector runs in."
+					relevant_when: "type = \"kubernetes_config_map\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["vector"]
+					}
+				}
+				identity: {
+					description: """
+						Identifies the instance of Vector the checkpoints belong to, so that instances sharing the
+						ConfigMap don't overwrite each other's checkpoints. Defaults to the node name in
+						`VECTOR_SELF_NODE_NAME`, then the pod name in `VECTOR_SELF_POD_NAME`, then the hostname.
+						"""
+					relevant_when: "type = \"kubernetes_config_map\""
+					required:      false
+					type: string: {
+						default: null
+						examples: ["node-1"]
+					}
+				}
+			}
+		}
+
 		data_dir: {
 			common: false
 			description: """
@@ -45,6 +125,7 @@ configuration: {
 
 				* [CSV](\(urls.csv)) files
 				* [MaxMind](\(urls.maxmind)) databases
+				* [SQLite](\(urls.sqlite)) databases
 
 				For the lookup in the enrichment tables to be as performant as possible, the data is indexed according
 				to the fields that are used in the search. Note that indices can only be created for fields for which an
@@ -184,6 +265,99 @@ configuration: {
 								examples: ["de", "en", "es", "fr", "ja", "pt-BR", "ru", "zh-CN"]
 							}
 						}
+						download: {
+							description: """
+								Periodically download the database from [MaxMind](\(urls.maxmind)). The
+								archive's checksum is verified before the file at `path` is replaced, and the
+								new database is swapped in without reloading Vector.
+
+								If no database exists at `path` on startup, Vector waits for the first download
+								to complete. The checksum of the downloaded archive is written to
+								`<path>.sha256`, so that an unchanged database isn't downloaded again after a
+								restart.
+								"""
+							required: false
+							common:   false
+							type: object: options: {
+								edition_id: {
+									description: "The MaxMind edition to download."
+									required:    true
+									type: string: {
+										examples: ["GeoLite2-City", "GeoLite2-ASN"]
+									}
+								}
+								license_key: {
+									description: """
+										The MaxMind license key. We recommend providing this with a secret, for
+										example `SECRET[backend.maxmind_license_key]`.
+										"""
+									required: true
+									type: string: {
+										examples: ["SECRET[backend.maxmind_license_key]"]
+									}
+								}
+								endpoint: {
+									description: "The endpoint to download the database from."
+									required:    false
+									common:      false
+									type: string: {
+										default: "https://download.maxmind.com/app/geoip_download"
+									}
+								}
+								refresh_interval_secs: {
+									description: "How often to check for an updated database."
+									required:    false
+									common:      false
+									type: uint: {
+										default: 86400
+										unit:    "seconds"
+									}
+								}
+							}
+						}
+					}
+				}
+				sqlite: {
+					required:    true
+					description: """
+						Configuration options for [SQLite](\(urls.sqlite)) databases.
+
+						Equality conditions are evaluated by SQLite, so lookups make use of any indexes
+						defined in the database. Create indexes for the fields used in your conditions
+						to avoid full table scans. Date range conditions are evaluated against columns
+						containing either unix timestamps or ISO-8601 text.
+
+						The database is opened read-only and is re-opened automatically when the file is
+						replaced. Lookups running concurrently each use their own connection to the
+						database, so they don't wait for each other.
+						"""
+					type: object: options: {
+						path: {
+							description: "Path to the SQLite database file."
+							required:    true
+							type: string: {
+								examples: ["/path/to/dimensions.db"]
+							}
+						}
+						table: {
+							description: "The table within the database to perform lookups against."
+							required:    true
+							type: string: {
+								examples: ["dimensions"]
+							}
+						}
+						reopen_interval_secs: {
+							description: """
+								How often, in seconds, to check whether the database file has been
+								modified or replaced and needs to be re-opened.
+								"""
+							required: false
+							common:   false
+							type: uint: {
+								default: 30
+								unit:    "seconds"
+							}
+						}
 					}
 				}
 			}
@@ -302,6 +476,174 @@ configuration: {
 			}
 		}
 
+		memory_budget: {
+			common: false
+			description: """
+				Limits the estimated memory used by the state of the components that accumulate events: the
+				`reduce`, `dedupe` and `aggregate` transforms. When their total usage reaches the trim
+				threshold, these components trim their state until it is back under it: `reduce` flushes its
+				stalest transactions, `dedupe` forgets the least recently seen events of its cache, and
+				`aggregate` flushes early. While the total usage reaches `max_bytes`, sources wait before
+				sending more events, asking the components to trim their state meanwhile. The usage is estimated, and is reported by the `memoryBudget` query of the API.
+				Changing this option requires a restart.
+				"""
+			required: false
+			type: object: options: {
+				max_bytes: {
+					description: "The maximum estimated size, in bytes, of the state of all components together."
+					required:    true
+					type: uint: {
+						examples: [268435456]
+						unit: "bytes"
+					}
+				}
+				trim_threshold_percent: {
+					description: "The percentage of `max_bytes` from which components trim their state."
+					required:    false
+					type: uint: {
+						default: 80
+						unit:    null
+					}
+				}
+			}
+		}
+
+		pipelines: {
+			common: false
+			description: """
+				Reusable chains of transforms, defined once and instantiated any number of times by transforms of
+				type `pipeline`, which set the `pipeline` to use and its `params`. Each instance is replaced by the
+				transforms of the pipeline, chained in order and named after the instance and their position,
+				such as `my_instance.0`. The first transform reads from the inputs of the instance, and components
+				reading from the instance read from the last transform. In the string values of the transforms,
+				`{{ params.<name> }}` is replaced by the value of the parameter for the instance.
+				"""
+			required: false
+			type: object: options: {
+				"*": {
+					description: "The definition of a pipeline, named by its key."
+					required:    true
+					type: object: options: {
+						params: {
+							description: """
+								The parameters of the pipeline, by name. A parameter can set a `default` value,
+								otherwise every instance must set it.
+								"""
+							required: false
+							type: object: {
+								examples: [{service: {}, level: {default: "info"}}]
+								options: {}
+							}
+						}
+						transforms: {
+							description: """
+								The transforms of the pipeline, in order, written as in the `transforms` section,
+								without `inputs`.
+								"""
+							required: true
+							type: array: items: type: object: {
+								examples: [{type: "remap", source: ".service = \"{{ params.service }}\""}]
+								options: {}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		run_as: {
+			common: false
+			description: """
+				Switches Vector to another identity once its sources are ready, that is once the sources
+				listening for events have bound their sockets and the others have been built. This lets Vector
+				start as root to listen on privileged ports, then process events as an unprivileged user. Files
+				read by sources as they run, such as those of the `file` source, are opened as that user, as are
+				the sockets of sources added on a configuration reload or run under leader election. Unix only;
+				changing this option requires a restart.
+				"""
+			required: false
+			type: object: options: {
+				user: {
+					description: "The user, by name or numeric ID, to switch to. Supplementary groups are cleared."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector", "997"]
+					}
+				}
+				group: {
+					description: "The group, by name or numeric ID, to switch to. Defaults to the primary group of `user`."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector", "997"]
+					}
+				}
+				seccomp_profile: {
+					description: """
+						The path of a seccomp profile, in the JSON format of
+						[seccompiler](https://github.com/rust-vmm/seccompiler), applied to the whole process after
+						switching user. The filter named `vector` in the profile is used. The profile is read
+						before switching user, so it can be readable by root only. Linux only.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["/etc/vector/seccomp.json"]
+					}
+				}
+			}
+		}
+
+		telemetry: {
+			common: false
+			description: """
+				Options for the internal metrics Vector reports about itself, as collected by the
+				`internal_metrics` source. Changing this option requires a restart.
+				"""
+			required: false
+			type: object: options: {
+				tags: {
+					description: """
+						Controls high-cardinality tags, such as the file a source reads from or the address of a peer,
+						on internal metrics. Those tags create one series per file, peer or partition, which can
+						make internal metrics dominate the cost of storing metrics downstream.
+						"""
+					required: false
+					type: object: options: {
+						high_cardinality: {
+							description: "The tags considered high-cardinality."
+							required:    false
+							type: array: {
+								default: ["container_id", "container_name", "file", "partition", "peer_addr", "peer_path", "pod_name"]
+								items: type: string: examples: ["file", "peer_addr"]
+							}
+						}
+						policy: {
+							description: "The policy applied to high-cardinality tags, for components without one of their own."
+							required:    false
+							type: string: {
+								default: "emit"
+								enum: {
+									emit:      "Emit the metrics with the tags."
+									aggregate: "Emit the metrics without the tags, aggregating the series that only differed by them. Gauges with any of the tags are dropped, as the values of their series can't be combined."
+									drop:      "Don't emit metrics that have any of the tags."
+								}
+							}
+						}
+						components: {
+							description: "The policy applied to high-cardinality tags, by component type. Accepts the same values as `policy`."
+							required:    false
+							type: object: {
+								examples: [{file: "aggregate", socket: "drop"}]
+								options: {}
+							}
+						}
+					}
+				}
+			}
+		}
+
 		secret: {
 			common: false
 			description: """
@@ -210,6 +210,16 @@ data_model: schema: {
 					}
 				}
 
+				"interval_ms": {
+					description: "The time span, in milliseconds, the value of the metric was collected over. Only set by sources that know it, such as the `aws_cloudwatch_metrics` source."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [60000]
+						unit: "milliseconds"
+					}
+				}
+
 				"kind": {
 					description: "The metric value kind."
 					required:    true
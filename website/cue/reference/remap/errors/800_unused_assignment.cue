@@ -0,0 +1,33 @@
+package metadata
+
+remap: errors: "800": {
+	title: "Unused assignment"
+	description: """
+		You've assigned a value to a path or variable that's unconditionally overwritten before being read.
+		"""
+
+	rationale: """
+		The overwritten value has no effect on the program, which usually means that one of the assignments targets
+		the wrong path or variable. This is a warning, reported when Vector loads its configuration: the program still
+		runs.
+		"""
+
+	resolution: """
+		Remove the first assignment, or assign to the intended path or variable.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				.message = "foo"
+				.message = "bar"
+				"""#
+			diff: #"""
+				-.message = "foo"
+				+.source_message = "foo"
+				 .message = "bar"
+				"""#
+		},
+	]
+}
@@ -0,0 +1,41 @@
+package metadata
+
+remap: errors: "801": {
+	title: "Unreachable branch"
+	description: """
+		A branch of an `if` statement never runs, as the type of the value checked by its predicate is already known.
+		"""
+
+	rationale: """
+		Type checking functions such as `is_string` always return the same result when the type of their argument
+		is known at compile time, for example after a type assertion such as `string!`. The branch that never runs
+		is dead code. This is a warning, reported when Vector loads its configuration: the program still runs.
+		"""
+
+	resolution: """
+		Remove the branch that never runs, along with the `if` statement.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				.message = string!(.message)
+				if is_string(.message) {
+					.message = downcase(.message)
+				} else {
+					.message = "invalid"
+				}
+				"""#
+			diff: #"""
+				 .message = string!(.message)
+				-if is_string(.message) {
+				-	.message = downcase(.message)
+				-} else {
+				-	.message = "invalid"
+				-}
+				+.message = downcase(.message)
+				"""#
+		},
+	]
+}
@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: add_business_days: {
+	category: "Timestamp"
+	description: """
+		Adds `days` business days, Monday to Friday, to the `value` timestamp, keeping its time of day. Negative `days`
+		go back in time.
+
+		A timestamp on a weekend counts from the preceding Friday when moving forward and from the following Monday
+		when moving backward, so that adding one business day to a Saturday gives the following Monday.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The timestamp to add the business days to."
+			required:    true
+			type: ["timestamp"]
+		},
+		{
+			name:        "days"
+			description: "The number of business days to add."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"the resulting timestamp is out of range",
+	]
+	return: types: ["timestamp"]
+
+	examples: [
+		{
+			title: "Add business days"
+			source: #"""
+				add_business_days!(t'2021-02-12T10:00:00Z', 1)
+				"""#
+			return: "2021-02-15T10:00:00Z"
+		},
+		{
+			title: "Subtract business days"
+			source: #"""
+				add_business_days!(t'2021-02-15T10:00:00Z', -6)
+				"""#
+			return: "2021-02-05T10:00:00Z"
+		},
+	]
+}
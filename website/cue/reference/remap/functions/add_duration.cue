@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: add_duration: {
+	category: "Timestamp"
+	description: """
+		Adds the `duration` to the `value` timestamp, with nanosecond precision.
+
+		The `duration` is either an [ISO 8601 duration](\(urls.iso_8601_duration)) without years or months, such as
+		`P1DT12H`, or a sequence of numbers followed by their unit, such as `1h30m` or `1.5s`, using the units of
+		`parse_duration`. A leading `-` negates the duration.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The timestamp to add the duration to."
+			required:    true
+			type: ["timestamp"]
+		},
+		{
+			name:        "duration"
+			description: "The duration to add."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`duration` isn't a properly formatted duration",
+		"the resulting timestamp is out of range",
+	]
+	return: types: ["timestamp"]
+
+	examples: [
+		{
+			title: "Add duration"
+			source: #"""
+				add_duration!(t'2021-02-10T23:32:00Z', "1h30m")
+				"""#
+			return: "2021-02-11T01:02:00Z"
+		},
+		{
+			title: "Add ISO 8601 duration"
+			source: #"""
+				add_duration!(t'2021-02-10T23:32:00Z', "P1DT0.000001S")
+				"""#
+			return: "2021-02-11T23:32:00.000001Z"
+		},
+	]
+}
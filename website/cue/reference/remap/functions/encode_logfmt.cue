@@ -4,6 +4,9 @@ remap: functions: encode_logfmt: {
 	category:    "Codec"
 	description: """
 		Encodes the `value` to [logfmt](\(urls.logfmt)).
+
+		Fields not listed in `fields_ordering` are always sorted by key, after the listed ones. VRL objects are
+		sorted maps, so the order in which fields were inserted isn't known and can't be preserved.
 		"""
 	notices:     functions.encode_key_value.notices
 
@@ -16,10 +19,35 @@ remap: functions: encode_logfmt: {
 		},
 		{
 			name:        "fields_ordering"
-			description: "The ordering of fields to preserve. Any fields not in this list will appear unordered, after any ordered fields."
-			required:    false
+			description: """
+				The ordering of fields to preserve. Any fields not in this list appear sorted by key, after any ordered
+				fields. Naming an object field places all of its nested fields at that position.
+				"""
+			required: false
 			type: ["array"]
 		},
+		{
+			name:        "flatten_separator"
+			description: "The separator joining the keys of nested fields."
+			required:    false
+			default:     "."
+			type: ["string"]
+		},
+		{
+			name:        "escaping"
+			description: "How keys and values are escaped."
+			required:    false
+			default:     "default"
+			enum: {
+				default: "Quotes values containing whitespace or double quotes."
+				strict:  """
+					Quotes empty values and values containing whitespace, `=`, double quotes or control characters,
+					escaping them as in JSON strings, and replaces the same characters in keys with `_`, as expected
+					by the logfmt parser of Grafana Loki.
+					"""
+			}
+			type: ["string"]
+		},
 	]
 	internal_failure_reasons: [
 		"`fields_ordering` contains a non-string element",
@@ -55,5 +83,12 @@ remap: functions: encode_logfmt: {
 				"""
 			return: #"event=log log.file.path=my.log agent.name=vector"#
 		},
+		{
+			title: "Encode to logfmt (Loki compatible)"
+			source: """
+				encode_logfmt!({"msg": "a=b", "request": {"path": "/", "query": ""}}, ["msg", "request"], flatten_separator: "_", escaping: "strict")
+				"""
+			return: #"msg="a=b" request_path=/ request_query="""#
+		},
 	]
 }
@@ -0,0 +1,36 @@
+package metadata
+
+remap: functions: is_business_day: {
+	category: "Timestamp"
+	description: """
+		Determines whether the `value` timestamp falls on a business day, Monday to Friday, in UTC.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The timestamp to check."
+			required:    true
+			type: ["timestamp"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Weekday"
+			source: #"""
+				is_business_day(t'2021-02-12T10:00:00Z')
+				"""#
+			return: true
+		},
+		{
+			title: "Weekend"
+			source: #"""
+				is_business_day(t'2021-02-13T10:00:00Z')
+				"""#
+			return: false
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: matches_rules: {
+	category: "String"
+	description: """
+		Returns the indices of the given `patterns` that match the `value`, in
+		ascending order. All patterns are compiled once when the program is
+		compiled, and checked in a single pass over the target string, giving
+		this function a significant performance advantage over multiple calls to
+		`match`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The value to match."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "patterns"
+			description: "The array of regular expression patterns to match against."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["array"]
+		rules: [
+			"The array is empty if none of the patterns match.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Find the matching rules"
+			source: """
+				matches_rules("I'm a little teapot", [r'frying pan', r'teapot', r'little'])
+				"""
+			return: [1, 2]
+		},
+	]
+}
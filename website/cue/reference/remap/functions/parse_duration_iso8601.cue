@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: parse_duration_iso8601: {
+	category: "Parse"
+	description: """
+		Parses the `value` as an [ISO 8601 duration](\(urls.iso_8601_duration)), such as `P1DT2H30M`, into the
+		number of `unit` it lasts.
+
+		Years and months don't have a fixed length, so durations containing them are rejected. A leading `-` negates
+		the duration.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string of the ISO 8601 duration."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "unit"
+			description: "The output units for the duration."
+			required:    true
+			type: ["string"]
+			enum:        functions.parse_duration.arguments[1].enum
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted ISO 8601 duration",
+		"`value` contains years or months",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Parse ISO 8601 duration (minutes)"
+			source: #"""
+				parse_duration_iso8601!("PT1H30M", unit: "m")
+				"""#
+			return: 90.0
+		},
+		{
+			title: "Parse ISO 8601 duration (milliseconds)"
+			source: #"""
+				parse_duration_iso8601!("P1DT0.5S", unit: "ms")
+				"""#
+			return: 86400500.0
+		},
+	]
+}
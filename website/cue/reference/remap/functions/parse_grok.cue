@@ -26,6 +26,16 @@ remap: functions: parse_grok: {
 			required:    true
 			type: ["string"]
 		},
+		{
+			name:        "pattern_paths"
+			description: """
+				Files of pattern definitions, or directories of such files, in the format of Logstash pattern files: each
+				line is a pattern name followed by whitespace and its definition. The files are read when the program is
+				compiled.
+				"""
+			required:    false
+			type: ["array"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse using the provided `pattern`",
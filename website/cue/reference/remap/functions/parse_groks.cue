@@ -33,6 +33,26 @@ remap: functions: parse_groks: {
 			default:     true
 			type: ["object"]
 		},
+		{
+			name:        "pattern_paths"
+			description: """
+				Files of pattern definitions, or directories of such files, in the format of Logstash pattern files: each
+				line is a pattern name followed by whitespace and its definition. The definitions can be referenced like
+				`aliases`, which take precedence over them. The files are read when the program is compiled.
+				"""
+			required:    false
+			type: ["array"]
+		},
+		{
+			name:        "include_pattern"
+			description: """
+				Returns the parsed fields under `fields`, along with the pattern that matched under `pattern` and its
+				position in `patterns` under `pattern_index`.
+				"""
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse using the provided `pattern`",
@@ -64,5 +84,27 @@ remap: functions: parse_groks: {
 				message:   "Hello world"
 			}
 		},
+		{
+			title: "Find out which Grok pattern matched"
+			source: #"""
+				parse_groks!(
+					"2020-10-02T23:22:12.223222Z info Hello world",
+					patterns: [
+						"%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{POSINT:status} %{GREEDYDATA:message}",
+						"%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}",
+					],
+					include_pattern: true
+				)
+				"""#
+			return: {
+				fields: {
+					timestamp: "2020-10-02T23:22:12.223222Z"
+					level:     "info"
+					message:   "Hello world"
+				}
+				pattern:       "%{TIMESTAMP_ISO8601:timestamp} %{LOGLEVEL:level} %{GREEDYDATA:message}"
+				pattern_index: 1
+			}
+		},
 	]
 }
@@ -5,6 +5,9 @@ remap: functions: set_metadata_field: {
 	description: """
 		Sets the given field in the event metadata to the provided value. This can utilize VRL paths and store
 		arbitrarily typed metadata on an event.
+
+		A timestamp set at `.expires_at` is the expiration of the event: once expired, the event is dropped
+		instead of being delivered by sinks.
 		"""
 
 	arguments: [
@@ -34,5 +37,12 @@ remap: functions: set_metadata_field: {
 				"""#
 			return: "null"
 		},
+		{
+			title: "Sets the expiration of an event."
+			source: #"""
+				set_metadata_field(.expires_at, to_timestamp!(.deadline))
+				"""#
+			return: "null"
+		},
 	]
 }
@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: subtract_duration: {
+	category: "Timestamp"
+	description: """
+		Subtracts the `duration` from the `value` timestamp, with nanosecond precision.
+
+		The `duration` has the same format as in `add_duration`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The timestamp to subtract the duration from."
+			required:    true
+			type: ["timestamp"]
+		},
+		{
+			name:        "duration"
+			description: "The duration to subtract."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`duration` isn't a properly formatted duration",
+		"the resulting timestamp is out of range",
+	]
+	return: types: ["timestamp"]
+
+	examples: [
+		{
+			title: "Subtract duration"
+			source: #"""
+				subtract_duration!(t'2021-02-10T23:32:00Z', "90s")
+				"""#
+			return: "2021-02-10T23:30:30Z"
+		},
+		{
+			title: "Subtract ISO 8601 duration"
+			source: #"""
+				subtract_duration!(t'2021-03-01T00:00:00Z', "P1D")
+				"""#
+			return: "2021-02-28T00:00:00Z"
+		},
+	]
+}
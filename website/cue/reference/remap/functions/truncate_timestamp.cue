@@ -0,0 +1,53 @@
+package metadata
+
+remap: functions: truncate_timestamp: {
+	category: "Timestamp"
+	description: """
+		Truncates the `value` timestamp to the start of the `unit` it falls in, in UTC.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The timestamp to truncate."
+			required:    true
+			type: ["timestamp"]
+		},
+		{
+			name:        "unit"
+			description: "The unit to truncate the timestamp to."
+			required:    true
+			type: ["string"]
+			enum: {
+				year:        "The first day of the year."
+				month:       "The first day of the month."
+				week:        "The Monday of the week."
+				day:         "The start of the day."
+				hour:        "The start of the hour."
+				minute:      "The start of the minute."
+				second:      "The start of the second."
+				millisecond: "The start of the millisecond."
+				microsecond: "The start of the microsecond."
+			}
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["timestamp"]
+
+	examples: [
+		{
+			title: "Truncate to the hour"
+			source: #"""
+				truncate_timestamp(t'2021-02-10T23:32:17.512Z', unit: "hour")
+				"""#
+			return: "2021-02-10T23:00:00Z"
+		},
+		{
+			title: "Truncate to the week"
+			source: #"""
+				truncate_timestamp(t'2021-02-10T23:32:17.512Z', unit: "week")
+				"""#
+			return: "2021-02-08T00:00:00Z"
+		},
+	]
+}
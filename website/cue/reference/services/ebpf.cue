@@ -0,0 +1,10 @@
+package metadata
+
+services: ebpf: {
+	name:     "eBPF"
+	thing:    "the Linux kernel, through \(name)"
+	url:      urls.ebpf
+	versions: ">= 4.18"
+
+	description: "[eBPF](\(urls.ebpf)) runs sandboxed programs in the Linux kernel, which can observe kernel events such as the lifecycle of TCP connections without changes to applications."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: etw: {
+	name:     "Event Tracing for Windows"
+	thing:    "\(name) (ETW)"
+	url:      urls.windows_etw
+	versions: null
+
+	description: "[Event Tracing for Windows](\(urls.windows_etw)) (ETW) is a kernel-level tracing facility that lets providers publish high-frequency telemetry to real-time trace sessions."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: netflow: {
+	name:     "NetFlow, IPFIX and sFlow"
+	thing:    "flow exporters"
+	url:      urls.ipfix
+	versions: null
+
+	description: "NetFlow, [IPFIX](\(urls.ipfix)) and [sFlow](\(urls.sflow)) are protocols with which routers, switches and firewalls export summaries of the traffic flowing through them."
+}
@@ -23,6 +23,8 @@ urls: {
 	aws_cloudwatch:                             "https://aws.amazon.com/cloudwatch/"
 	aws_cloudwatch_logs:                        "\(aws_docs)/AmazonCloudWatch/latest/logs/WhatIsCloudWatchLogs.html"
 	aws_cloudwatch_logs_api:                    "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/Welcome.html"
+	aws_cloudwatch_logs_filter_log_events:      "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/API_FilterLogEvents.html"
+	aws_cloudwatch_logs_filter_pattern:         "\(aws_docs)/AmazonCloudWatch/latest/logs/FilterAndPatternSyntax.html"
 	aws_cloudwatch_logs_firehose:               "\(aws_docs)/firehose/latest/dev/writing-with-cloudwatch-logs.html"
 	aws_cloudwatch_logs_group_name:             "\(aws_docs)/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html"
 	aws_cloudwatch_logs_service_limits:         "\(aws_docs)/AmazonCloudWatch/latest/logs/cloudwatch_limits_cwl.html"
@@ -34,8 +36,12 @@ urls: {
 	aws_cloudwatch_logs_subscriptions_firehose: "\(aws_docs)/AmazonCloudWatch/latest/logs/SubscriptionFilters.html#FirehoseExample"
 	aws_cloudwatch_metrics:                     "\(aws_docs)/AmazonCloudWatch/latest/monitoring/working_with_metrics.html"
 	aws_cloudwatch_metrics_api:                 "\(aws_docs)/AmazonCloudWatch/latest/APIReference/API_PutMetricData.html"
+	aws_cloudwatch_metrics_get_metric_data:     "\(aws_docs)/AmazonCloudWatch/latest/APIReference/API_GetMetricData.html"
+	aws_cloudwatch_metrics_namespaces:          "\(aws_docs)/AmazonCloudWatch/latest/monitoring/aws-services-cloudwatch-metrics.html"
 	aws_cloudwatch_metrics_service_limits:      "\(aws_docs)/en_pv/AmazonCloudWatch/latest/monitoring/cloudwatch_limits.html"
+	aws_cloudwatch_metrics_statistics:          "\(aws_docs)/AmazonCloudWatch/latest/monitoring/Statistics-definitions.html"
 	aws_cloudwatch_metrics_regions:             "\(aws_docs)/general/latest/gr/rande.html#cw_region"
+	aws_cloudwatch_pricing:                     "https://aws.amazon.com/cloudwatch/pricing/"
 	aws_credential_process:                     "\(aws_docs)/cli/latest/userguide/cli-configure-sourcing-external.html"
 	aws_credentials_file:                       "\(aws_docs)/cli/latest/userguide/cli-configure-files.html"
 	aws_docs:                                   "https://docs.aws.amazon.com"
@@ -69,6 +75,7 @@ urls: {
 	aws_s3_cross_account_tutorial:              "\(aws_docs)/AmazonS3/latest/dev/example-walkthroughs-managing-access-example3.html"
 	aws_s3_endpoints:                           "\(aws_docs)/general/latest/gr/rande.html#s3_endpoint"
 	aws_s3_grantee:                             "\(aws_docs)/AmazonS3/latest/dev/acl-overview.html#specifying-grantee"
+	aws_s3_inventory:                           "\(aws_docs)/AmazonS3/latest/userguide/storage-inventory.html"
 	aws_s3_metadata:                            "\(aws_docs)/AmazonS3/latest/dev/UsingMetadata.html#object-metadata"
 	aws_s3_regions:                             "\(aws_docs)/general/latest/gr/rande.html#s3_region"
 	aws_s3_server_access_logs:                  "\(aws_docs)/AmazonS3/latest/dev/ServerLogs.html"
@@ -81,10 +88,12 @@ urls: {
 	aws_sqs_create:                             "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
 	aws_sqs_message_deduplication_id:           "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/using-messagededuplicationid-property.html"
 	aws_vpc_flow_logs:                          "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
+	aws_vpc_flow_logs_records:                  "\(aws_docs)/vpc/latest/userguide/flow-logs.html#flow-log-records"
 	axiom:                                      "https://axiom.co"
 	axiom_cloud:                                "https://cloud.axiom.co"
 	azure_blob:                                 "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                       "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_blob_types:                           "https://docs.microsoft.com/en-us/rest/api/storageservices/understanding-block-blobs--append-blobs--and-page-blobs"
 	azure_monitor:                              "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:               "https://docs.microsoft.com/en-us/rest/api/monitor/"
 	base64:                                     "\(wikipedia)/wiki/Base64"
@@ -94,6 +103,7 @@ urls: {
 	basic_auth:                                 "\(wikipedia)/wiki/Basic_access_authentication"
 	big_query_streaming:                        "https://cloud.google.com/bigquery/streaming-data-into-bigquery"
 	bind_dnstap:                                "https://kb.isc.org/docs/aa-01342"
+	brotli:                                     "https://github.com/google/brotli"
 	b_tree_map:                                 "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
 	cargo_audit:                                "\(github)/RustSec/cargo-audit"
 	centos:                                     "https://www.centos.org/"
@@ -163,6 +173,7 @@ urls: {
 	dpkg:                                       "https://wiki.debian.org/dpkg"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
+	ebpf:                                       "https://ebpf.io/what-is-ebpf/"
 	elastic_beats:                              "https://www.elastic.co/beats/"
 	elasticsearch:                              "https://www.elastic.co/products/elasticsearch"
 	elasticsearch_bulk:                         "https://www.elastic.co/guide/en/elasticsearch/reference/current/docs-bulk.html"
@@ -211,6 +222,7 @@ urls: {
 	gcs:                                        "https://cloud.google.com/storage/"
 	gcs_predefined_acl:                         "\(gcp)/storage/docs/access-control/lists#predefined-acl"
 	gcs_storage_classes:                        "\(gcp)/storage/docs/storage-classes"
+	gcs_cmek:                                   "\(gcp)/storage/docs/encryption/customer-managed-keys"
 	gcs_custom_metadata:                        "\(gcp)/storage/docs/metadata#custom-metadata"
 	git:                                        "https://git-scm.com/"
 	github:                                     "https://github.com"
@@ -251,6 +263,7 @@ urls: {
 	influxdb:                                   "https://www.influxdata.com/products/influxdb-overview/"
 	influxdb_http_api_v1:                       "https://docs.influxdata.com/influxdb/latest/tools/api/#write-http-endpoint"
 	influxdb_http_api_v2:                       "https://v2.docs.influxdata.com/v2.0/api/#tag/Write"
+	influxdb_http_api_v3:                       "https://docs.influxdata.com/influxdb3/core/write-data/http-api/v3-write-lp/"
 	influxdb_authentication_token:              "https://v2.docs.influxdata.com/v2.0/security/tokens/"
 	influxdb_line_protocol:                     "https://v2.docs.influxdata.com/v2.0/reference/syntax/line-protocol/"
 	inode:                                      "\(wikipedia)/wiki/Inode"
@@ -258,7 +271,10 @@ urls: {
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                    "https://linux.die.net/man/3/inet_pton"
+	ipfix:                                      "https://www.rfc-editor.org/rfc/rfc7011"
+	ipfix_information_elements:                 "https://www.iana.org/assignments/ipfix/ipfix.xhtml"
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
+	iso_8601_duration:                          "\(wikipedia)/wiki/ISO_8601#Durations"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
@@ -341,7 +357,9 @@ urls: {
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
+	nats_jetstream:                             "https://docs.nats.io/nats-concepts/jetstream"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
+	netflow_v9:                                 "https://www.rfc-editor.org/rfc/rfc3954"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_relic:                                  "https://newrelic.com/"
@@ -376,8 +394,10 @@ urls: {
 	percent_encoding_www_form_urlencoded:       "https://url.spec.whatwg.org/#application-x-www-form-urlencoded-percent-encode-set"
 	posix_acls:                                 "https://www.usenix.org/legacy/publications/library/proceedings/usenix03/tech/freenix03/full_papers/gruenbacher/gruenbacher_html/main.html"
 	postgresql:                                 "https://www.postgresql.org/"
+	postgresql_copy:                            "https://www.postgresql.org/docs/current/sql-copy.html"
 	postgresql_csvlog:                          "https://www.postgresql.org/docs/current/runtime-config-logging.html#RUNTIME-CONFIG-LOGGING-CSVLOG"
 	postgresql_matching:                        "https://www.postgresql.org/docs/current/functions-matching.html#FUNCTIONS-POSIX-REGEXP"
+	postgresql_on_conflict:                     "https://www.postgresql.org/docs/current/sql-insert.html#SQL-ON-CONFLICT"
 	procfs:                                     "https://en.wikipedia.org/wiki/Procfs#:~:text=The%20proc%20filesystem%20(procfs)%20is,in%20the%20kernel%20than%20traditional"
 	prometheus:                                 "https://prometheus.io/"
 	prometheus_client:                          "https://prometheus.io/docs/instrumenting/clientlibs/"
@@ -396,6 +416,7 @@ urls: {
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	pulsar_schema:                              "https://pulsar.apache.org/docs/en/schema-understand/"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -437,6 +458,7 @@ urls: {
 	sematext_monitoring:                        "https://sematext.com/docs/monitoring/"
 	sematext_registration:                      "https://apps.sematext.com/ui/registration"
 	semver:                                     "https://semver.org/"
+	sflow:                                      "https://sflow.org/sflow_version_5.txt"
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
@@ -444,6 +466,7 @@ urls: {
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
+	spiffe_workload_api:                        "https://github.com/spiffe/spiffe/blob/main/standards/SPIFFE_Workload_API.md"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_channel_header:                  "https://docs.splunk.com/Documentation/Splunk/8.2.4/Data/FormateventsforHTTPEventCollector#Channel_identifier_header"
@@ -455,6 +478,7 @@ urls: {
 	splunk_hec_setup:                           "https://docs.splunk.com/Documentation/Splunk/latest/Data/UsetheHTTPEventCollector"
 	splunk_hec_metadata:                        "https://docs.splunk.com/Documentation/Splunk/latest/Data/FormateventsforHTTPEventCollector#Event_metadata"
 	specs_instrumentation:                      "\(vector_repo)/blob/master/docs/specs/instrumentation.md)"
+	sqlite:                                     "https://www.sqlite.org/"
 	standard_streams:                           "\(wikipedia)/wiki/Standard_streams"
 	statsd:                                     "\(github)/statsd/statsd"
 	statsd_multi:                               "\(github)/statsd/statsd/blob/master/docs/metric_types.md#multi-metric-packets"
@@ -521,6 +545,7 @@ urls: {
 	vector_download:                            "/releases/latest/download/"
 	vector_download_nightly:                    "/releases/nightly/download/"
 	vector_enriching_transforms:                "/components/?functions%5B%5D=enrich"
+	vector_filter_transform:                    "/docs/reference/configuration/transforms/filter/"
 	vector_file_source:                         "/docs/reference/configuration/sources/file/"
 	vector_exec_source:                         "/docs/reference/configuration/sources/exec"
 	vector_file_source:                         "/docs/reference/configuration/sources/file/"
@@ -623,6 +648,9 @@ urls: {
 	websocket:                                  "\(wikipedia)/wiki/WebSocket"
 	wikipedia:                                  "https://en.wikipedia.org"
 	windows:                                    "https://www.microsoft.com/en-us/windows"
+	windows_etw:                                "https://docs.microsoft.com/en-us/windows/win32/etw/about-event-tracing"
+	windows_event_log:                          "https://docs.microsoft.com/en-us/windows/win32/wes/windows-event-log"
+	windows_event_log_xpath:                    "https://docs.microsoft.com/en-us/windows/win32/wes/consuming-events#xpath-10-limitations"
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"